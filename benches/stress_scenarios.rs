@@ -0,0 +1,205 @@
+// ==================== STRESS SCENARIO BENCHMARKS ====================
+// Spawns standardized scenes on a headless app and reports per-frame update
+// time percentiles. Run all scenes with `cargo bench --bench stress_scenarios`
+// or pass scene names to run a subset, e.g. `-- units_1000 large_minimap`.
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use culiacan_rts::ai::unit_ai_system;
+use culiacan_rts::app_state::GameSet;
+use culiacan_rts::components::*;
+use culiacan_rts::config::UnitCatalog;
+use culiacan_rts::resources::{GameAssets, GameSetupComplete, GameState};
+use culiacan_rts::spawners::spawn_unit;
+use culiacan_rts::ui::minimap_system;
+use culiacan_rts::utils::{
+    spawn_optimized_combat_particles, update_pooled_particles_system, ParticlePool, SimulationSet,
+};
+use culiacan_rts::SimulationPlugin;
+use std::time::{Duration, Instant};
+
+const WARMUP_FRAMES: usize = 60;
+const MEASURED_FRAMES: usize = 600;
+const RENDER_FRAME: Duration = Duration::from_micros(16_667); // 60 FPS client frame
+
+#[derive(Clone, Copy)]
+struct Scenario {
+    name: &'static str,
+    units: usize,
+    particle_bursts_per_frame: usize,
+    minimap: bool,
+}
+
+const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "units_200",
+        units: 200,
+        particle_bursts_per_frame: 0,
+        minimap: false,
+    },
+    Scenario {
+        name: "units_500",
+        units: 500,
+        particle_bursts_per_frame: 0,
+        minimap: false,
+    },
+    Scenario {
+        name: "units_1000",
+        units: 1000,
+        particle_bursts_per_frame: 0,
+        minimap: false,
+    },
+    Scenario {
+        name: "heavy_particles",
+        units: 200,
+        particle_bursts_per_frame: 40,
+        minimap: false,
+    },
+    Scenario {
+        name: "large_minimap",
+        units: 1000,
+        particle_bursts_per_frame: 0,
+        minimap: true,
+    },
+];
+
+#[derive(Resource, Clone, Copy)]
+struct ActiveScenario(Scenario);
+
+fn main() {
+    // cargo bench forwards its own flags (e.g. --bench); anything else is a scene filter
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    println!(
+        "{:<18} {:>7} {:>9} {:>9} {:>9} {:>9}",
+        "scenario", "units", "p50 ms", "p95 ms", "p99 ms", "max ms"
+    );
+
+    for scenario in SCENARIOS
+        .iter()
+        .filter(|scenario| filters.is_empty() || filters.iter().any(|f| scenario.name.contains(f)))
+    {
+        let frame_times = run_scenario(*scenario);
+        println!(
+            "{:<18} {:>7} {:>9.3} {:>9.3} {:>9.3} {:>9.3}",
+            scenario.name,
+            scenario.units,
+            percentile(&frame_times, 0.50),
+            percentile(&frame_times, 0.95),
+            percentile(&frame_times, 0.99),
+            percentile(&frame_times, 1.0),
+        );
+    }
+}
+
+fn run_scenario(scenario: Scenario) -> Vec<f64> {
+    let mut app = build_scenario_app(scenario);
+    app.finish();
+    app.cleanup();
+
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+
+    let mut frame_times = Vec::with_capacity(MEASURED_FRAMES);
+    for _ in 0..MEASURED_FRAMES {
+        let start = Instant::now();
+        app.update();
+        frame_times.push(start.elapsed().as_secs_f64() * 1000.0);
+    }
+    frame_times
+}
+
+fn build_scenario_app(scenario: Scenario) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(SimulationPlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(RENDER_FRAME))
+        .insert_resource(GameState {
+            game_phase: GamePhase::InitialRaid,
+            rng_seed: 42,
+            ..default()
+        })
+        .insert_resource(GameAssets::default())
+        .insert_resource(ActiveScenario(scenario))
+        .add_systems(Startup, spawn_scenario_system)
+        .add_systems(
+            FixedUpdate,
+            unit_ai_system
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        );
+
+    if scenario.particle_bursts_per_frame > 0 {
+        app.init_resource::<ParticlePool>().add_systems(
+            Update,
+            (particle_burst_system, update_pooled_particles_system).chain(),
+        );
+    }
+    if scenario.minimap {
+        app.add_systems(Update, minimap_system);
+    }
+
+    app
+}
+
+fn spawn_scenario_system(
+    mut commands: Commands,
+    scenario: Res<ActiveScenario>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+) {
+    let scenario = scenario.0;
+    // Two opposing blocks on a square grid, close enough to engage
+    let columns = (scenario.units as f32).sqrt().ceil() as usize;
+    for i in 0..scenario.units {
+        let (faction, unit_type, x_offset) = if i % 2 == 0 {
+            (Faction::Cartel, UnitType::Sicario, -200.0)
+        } else {
+            (Faction::Military, UnitType::Soldier, 200.0)
+        };
+        let position = Vec3::new(
+            x_offset + (i % columns) as f32 * 8.0,
+            (i / columns) as f32 * 8.0 - 200.0,
+            0.0,
+        );
+        spawn_unit(
+            &mut commands,
+            unit_type,
+            faction,
+            position,
+            &game_assets,
+            &catalog,
+        );
+    }
+
+    if scenario.minimap {
+        commands.spawn((NodeBundle::default(), MiniMap));
+    }
+
+    commands.insert_resource(GameSetupComplete);
+}
+
+fn particle_burst_system(
+    mut commands: Commands,
+    mut particle_pool: ResMut<ParticlePool>,
+    scenario: Res<ActiveScenario>,
+    mut frame: Local<u32>,
+) {
+    *frame += 1;
+    for burst in 0..scenario.0.particle_bursts_per_frame {
+        let angle = (*frame as f32 * 0.1) + burst as f32;
+        let position = Vec3::new(angle.cos() * 250.0, angle.sin() * 250.0, 0.0);
+        spawn_optimized_combat_particles(&mut commands, &mut particle_pool, position, 1.0);
+    }
+}
+
+fn percentile(samples: &[f64], fraction: f64) -> f64 {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+    sorted[index]
+}
@@ -0,0 +1,25 @@
+-- Create lobby_invites table
+CREATE TABLE IF NOT EXISTS lobby_invites (
+    id TEXT PRIMARY KEY NOT NULL,
+    from_user_id TEXT NOT NULL,
+    to_user_id TEXT NOT NULL,
+    session_id TEXT NOT NULL,
+    lobby_name TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (from_user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (to_user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Create user_presence table
+CREATE TABLE IF NOT EXISTS user_presence (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    last_seen TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Create indexes for better performance
+CREATE INDEX IF NOT EXISTS idx_lobby_invites_to_user_id ON lobby_invites(to_user_id);
+CREATE INDEX IF NOT EXISTS idx_lobby_invites_status ON lobby_invites(status);
+CREATE INDEX IF NOT EXISTS idx_lobby_invites_expires_at ON lobby_invites(expires_at);
@@ -0,0 +1,17 @@
+-- Create leaderboard_scores table: each player's best result per mission and difficulty
+CREATE TABLE IF NOT EXISTS leaderboard_scores (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    mission TEXT NOT NULL,
+    difficulty TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    completion_time REAL NOT NULL,
+    medal TEXT,
+    challenges TEXT NOT NULL DEFAULT '',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE (user_id, mission, difficulty)
+);
+
+-- Create indexes for better performance
+CREATE INDEX IF NOT EXISTS idx_leaderboard_scores_board ON leaderboard_scores(mission, difficulty, score DESC);
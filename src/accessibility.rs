@@ -0,0 +1,255 @@
+use crate::components::FormationType;
+use crate::narration::NarrationMode;
+use crate::profile::ProfileManager;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ==================== ACCESSIBILITY ====================
+
+// Options from the accessibility page of the main menu, kept in the player's
+// profile. Text scale drives UiScale, slow mode caps virtual time (and with it
+// the fixed simulation tick), toggle mode latches the formation modifier keys,
+// reduced motion is read by screen_feedback, extended decision timers slow
+// the political clock so events unfold with more time to react and narration
+// picks the narration module's backend.
+
+pub const TEXT_SCALES: [f32; 4] = [1.0, 1.25, 1.5, 2.0];
+pub const SLOW_MODE_SPEED: f64 = 0.5; // Virtual time never runs faster than this in slow mode
+const EXTENDED_DECISION_TIME_SCALE: f32 = 0.5; // Political events take twice as long
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccessibilitySettings>()
+            .init_resource::<FormationModifier>()
+            .add_systems(PreUpdate, formation_modifier_system.after(InputSystem))
+            .add_systems(
+                Update,
+                (
+                    apply_accessibility_settings_system,
+                    (text_scale_system, slow_mode_system),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModifierKeyMode {
+    #[default]
+    Hold, // Formation applies while Ctrl/Alt is held
+    Toggle, // Ctrl/Alt switches the formation on until pressed again
+}
+
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)] // Profiles saved before a setting existed get its default
+pub struct AccessibilitySettings {
+    pub text_scale: f32, // One of TEXT_SCALES
+    pub modifier_keys: ModifierKeyMode,
+    pub reduced_motion: bool, // Gentler camera shake and hit flashes
+    pub extended_decision_timers: bool,
+    pub slow_mode: bool,
+    pub narration: NarrationMode,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            text_scale: 1.0,
+            modifier_keys: ModifierKeyMode::Hold,
+            reduced_motion: false,
+            extended_decision_timers: false,
+            slow_mode: false,
+            narration: NarrationMode::Off,
+        }
+    }
+}
+
+impl AccessibilitySettings {
+    pub fn next_text_scale(&self) -> f32 {
+        TEXT_SCALES
+            .iter()
+            .position(|scale| *scale >= self.text_scale - f32::EPSILON)
+            .map_or(TEXT_SCALES[0], |index| {
+                TEXT_SCALES[(index + 1) % TEXT_SCALES.len()]
+            })
+    }
+
+    // Multiplier for the political simulation's clock
+    pub fn political_time_scale(&self) -> f32 {
+        if self.extended_decision_timers {
+            EXTENDED_DECISION_TIME_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    pub fn max_game_speed(&self) -> f64 {
+        if self.slow_mode {
+            SLOW_MODE_SPEED
+        } else {
+            1.0
+        }
+    }
+}
+
+// Copies the active profile's accessibility options
+pub fn apply_accessibility_settings_system(
+    profiles: Res<ProfileManager>,
+    mut settings: ResMut<AccessibilitySettings>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    if let Some(profile) = profiles.active.as_ref() {
+        settings.set_if_neq(profile.settings.accessibility.clone());
+    }
+}
+
+pub fn text_scale_system(settings: Res<AccessibilitySettings>, mut ui_scale: ResMut<UiScale>) {
+    if settings.is_changed() {
+        ui_scale.0 = settings.text_scale as f64;
+    }
+}
+
+pub fn slow_mode_system(settings: Res<AccessibilitySettings>, mut time: ResMut<Time<Virtual>>) {
+    let cap = settings.max_game_speed();
+    if time.relative_speed_f64() > cap {
+        time.set_relative_speed_f64(cap);
+    } else if settings.is_changed()
+        && !settings.slow_mode
+        && time.relative_speed_f64() == SLOW_MODE_SPEED
+    {
+        // Slow mode was just switched off
+        time.set_relative_speed_f64(1.0);
+    }
+}
+
+// ==================== FORMATION MODIFIER KEYS ====================
+
+// Formation picked by Ctrl (wedge) or Alt (circle) for the next move order.
+// Without either, a recalled custom formation (number keys) applies.
+#[derive(Resource, Default)]
+pub struct FormationModifier {
+    active: Option<FormationType>,
+    custom: Option<FormationType>,
+}
+
+impl FormationModifier {
+    pub fn formation(&self) -> FormationType {
+        self.active
+            .clone()
+            .or_else(|| self.custom.clone())
+            .unwrap_or(FormationType::Line)
+    }
+
+    pub fn custom(&self) -> Option<&FormationType> {
+        self.custom.as_ref()
+    }
+
+    pub fn set_custom(&mut self, formation: Option<FormationType>) {
+        self.custom = formation;
+    }
+
+    // Held (or toggled on) - the order preview shows while this is true
+    pub fn is_active(&self) -> bool {
+        self.active.is_some()
+    }
+
+    fn toggle(&mut self, formation: FormationType) {
+        self.active = if self.active.as_ref() == Some(&formation) {
+            None
+        } else {
+            Some(formation)
+        };
+    }
+}
+
+pub fn formation_modifier_system(
+    settings: Res<AccessibilitySettings>,
+    input: Res<Input<KeyCode>>,
+    mut modifier: ResMut<FormationModifier>,
+) {
+    match settings.modifier_keys {
+        ModifierKeyMode::Hold => {
+            let held = if input.pressed(KeyCode::ControlLeft) {
+                Some(FormationType::Wedge)
+            } else if input.pressed(KeyCode::AltLeft) {
+                Some(FormationType::Circle)
+            } else {
+                None
+            };
+            if modifier.active != held {
+                modifier.active = held;
+            }
+        }
+        ModifierKeyMode::Toggle => {
+            if input.just_pressed(KeyCode::ControlLeft) {
+                modifier.toggle(FormationType::Wedge);
+                info!("Formation modifier: {:?}", modifier.formation());
+            } else if input.just_pressed(KeyCode::AltLeft) {
+                modifier.toggle(FormationType::Circle);
+                info!("Formation modifier: {:?}", modifier.formation());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_scale_cycles_and_old_profiles_get_defaults() {
+        let mut settings = AccessibilitySettings::default();
+        let mut seen = Vec::new();
+        for _ in 0..TEXT_SCALES.len() {
+            settings.text_scale = settings.next_text_scale();
+            seen.push(settings.text_scale);
+        }
+        assert_eq!(seen, vec![1.25, 1.5, 2.0, 1.0]);
+
+        let partial: AccessibilitySettings = serde_json::from_str(r#"{"slow_mode":true}"#).unwrap();
+        assert!(partial.slow_mode);
+        assert_eq!(partial.text_scale, 1.0);
+        assert_eq!(partial.modifier_keys, ModifierKeyMode::Hold);
+        assert_eq!(partial.max_game_speed(), SLOW_MODE_SPEED);
+    }
+
+    #[test]
+    fn test_toggle_mode_latches_formation() {
+        let mut app = App::new();
+        app.init_resource::<Input<KeyCode>>()
+            .init_resource::<FormationModifier>()
+            .insert_resource(AccessibilitySettings {
+                modifier_keys: ModifierKeyMode::Toggle,
+                ..default()
+            })
+            .add_systems(Update, formation_modifier_system);
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::ControlLeft);
+        app.update();
+        // Released, but the wedge stays picked until Ctrl is pressed again
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        input.release(KeyCode::ControlLeft);
+        input.clear();
+        app.update();
+        assert_eq!(
+            app.world.resource::<FormationModifier>().formation(),
+            FormationType::Wedge
+        );
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::ControlLeft);
+        app.update();
+        assert_eq!(
+            app.world.resource::<FormationModifier>().formation(),
+            FormationType::Line
+        );
+    }
+}
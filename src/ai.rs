@@ -1,23 +1,38 @@
+use crate::assists::{assist_running, MissionAssist, MissionAssists};
 use crate::components::*;
+use crate::config::{BalanceConfig, DirectorBalance, UnitCatalog};
+use crate::daily::{daily_running, DailyChallengeState};
+use crate::diplomacy::Diplomacy;
+use crate::hvt::TargetPackage;
+use crate::localization::tr;
+use crate::production::deploy_military_unit;
 use crate::resources::*;
-use crate::spawners::spawn_unit;
 use crate::utils::{
-    calculate_flanking_position, calculate_kill_ratio, calculate_unit_ratio,
-    count_living_units_by_faction, play_tactical_sound,
+    avoid_threat_clusters, calculate_flanking_position, calculate_kill_ratio, calculate_unit_ratio,
+    count_living_units_by_faction, play_tactical_sound, RngStream, SimRng,
 };
 use bevy::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::Rng;
 
 // ==================== AI DIRECTOR SYSTEM ====================
 
+#[allow(clippy::too_many_arguments)]
 pub fn ai_director_system(
     mut ai_director: ResMut<AiDirector>,
     game_state: ResMut<GameState>,
     mut commands: Commands,
     game_assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    mut sim_rng: ResMut<SimRng>,
     unit_query: Query<&Unit>,
+    mut staging_query: Query<&mut ProductionQueue, With<StagingArea>>,
+    assists: Option<Res<MissionAssists>>,
     time: Res<Time>,
 ) {
+    let _span = info_span!("ai_director_system").entered();
+    let tuning = &balance.director;
     ai_director.last_spawn_time += time.delta_seconds();
 
     // Enhanced player performance calculation using utility functions
@@ -40,39 +55,56 @@ pub fn ai_director_system(
             .clamp(0.0, 1.0);
 
     // Smooth performance tracking with exponential moving average
-    ai_director.player_performance =
-        ai_director.player_performance * 0.85 + current_performance * 0.15;
+    ai_director.player_performance = ai_director.player_performance * tuning.performance_smoothing
+        + current_performance * (1.0 - tuning.performance_smoothing);
 
     // Phase-based difficulty progression
-    let phase_difficulty = match game_state.game_phase {
-        GamePhase::MainMenu
-        | GamePhase::SaveMenu
-        | GamePhase::LoadMenu
-        | GamePhase::MissionBriefing => 0.0,
-        GamePhase::Preparation => 0.6,
-        GamePhase::InitialRaid => 1.0,
-        GamePhase::BlockConvoy => 1.3,
-        GamePhase::ApplyPressure => 1.6,
-        GamePhase::HoldTheLine => 2.0,
-        GamePhase::Victory | GamePhase::Defeat | GamePhase::GameOver => 0.0,
-    };
+    let phase_difficulty = game_state
+        .game_phase
+        .mission_phase()
+        .map_or(0.0, |phase| phase.intensity());
 
     // Enhanced adaptive difficulty system
     let adaptive_modifier = if ai_director.adaptive_difficulty {
-        calculate_adaptive_modifier(ai_director.player_performance, game_state.mission_timer)
+        calculate_adaptive_modifier(
+            ai_director.player_performance,
+            game_state.mission_timer,
+            tuning,
+        )
     } else {
         1.0
     };
 
-    ai_director.intensity_level = (phase_difficulty * adaptive_modifier).max(0.1);
+    let personality = ai_director.personality.intensity_scale(tuning);
+    // A retry assist after repeated defeats
+    let escalation = if assist_running(assists.as_deref(), MissionAssist::SlowerEscalation) {
+        balance.assists.escalation_scale
+    } else {
+        1.0
+    };
+    ai_director.intensity_level =
+        (phase_difficulty * adaptive_modifier * personality * escalation).max(0.1);
 
     // Dynamic spawning with multiple triggers
-    let should_spawn =
-        check_spawn_conditions(&ai_director, &game_state, cartel_units, military_units);
+    let should_spawn = check_spawn_conditions(
+        &ai_director,
+        &game_state,
+        cartel_units,
+        military_units,
+        tuning,
+    );
 
     if should_spawn {
-        let spawn_result =
-            execute_dynamic_spawning(&mut commands, &ai_director, &game_assets, &game_state);
+        let spawn_result = execute_dynamic_spawning(
+            &mut commands,
+            &mut staging_query,
+            &ai_director,
+            &game_assets,
+            &game_state,
+            &balance,
+            &catalog,
+            sim_rng.stream(RngStream::Spawning),
+        );
 
         if spawn_result.spawned > 0 {
             play_tactical_sound(
@@ -95,38 +127,68 @@ pub fn ai_director_system(
 
 // ==================== UNIT AI SYSTEM ====================
 
+// Type aliases to reduce complexity
+type UnitAiQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Unit,
+        &'static Transform,
+        &'static mut Movement,
+        Option<&'static HoldArea>,
+        Option<&'static ProtectedPrincipal>,
+        Has<Bodyguard>,
+        Has<HvtTasking>,
+        Has<Fleeing>,
+        Has<Investigating>,
+    ),
+    Without<Objective>,
+>;
+
 pub fn unit_ai_system(
-    mut unit_query: Query<(&mut Unit, &Transform, &mut Movement), Without<Objective>>,
+    mut unit_query: UnitAiQuery,
     _objective_query: Query<&Transform, (With<Objective>, Without<Unit>)>,
     time: Res<Time>,
     _game_state: Res<GameState>,
+    target_package: Res<TargetPackage>,
+    diplomacy: Res<Diplomacy>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
-    // Collect all unit positions for tactical analysis
-    let mut cartel_positions = Vec::new();
-    let mut military_positions = Vec::new();
+    let _span = info_span!("unit_ai_system").entered();
+    let rng = sim_rng.stream(RngStream::Ai);
+
+    // Collect, for each faction, where the living units it's at war with are
+    let mut hostile_positions: [Vec<Vec3>; Faction::ALL.len()] = Default::default();
     let mut ovidio_position = None;
 
     // First pass: collect positions for tactical analysis
-    for (unit, transform, _) in unit_query.iter() {
+    for (unit, transform, _, _, _, _, _, _, _) in unit_query.iter() {
         if unit.health <= 0.0 {
             continue;
         }
 
-        match unit.faction {
-            Faction::Cartel => {
-                cartel_positions.push(transform.translation);
-                if unit.unit_type == UnitType::Ovidio {
-                    ovidio_position = Some(transform.translation);
-                }
-            }
-            Faction::Military => {
-                military_positions.push(transform.translation);
+        for faction in Faction::ALL {
+            if diplomacy.is_hostile(&faction, &unit.faction) {
+                hostile_positions[faction.index()].push(transform.translation);
             }
-            _ => {}
+        }
+        if unit.unit_type == UnitType::Ovidio {
+            ovidio_position = Some(transform.translation);
         }
     }
 
-    for (mut unit, transform, mut movement) in unit_query.iter_mut() {
+    for (
+        mut unit,
+        transform,
+        mut movement,
+        hold_area,
+        principal,
+        is_bodyguard,
+        hvt_tasked,
+        is_fleeing,
+        investigating,
+    ) in unit_query.iter_mut()
+    {
         if unit.health <= 0.0 {
             continue;
         }
@@ -134,17 +196,52 @@ pub fn unit_ai_system(
         // Update attack cooldown
         unit.attack_cooldown.tick(time.delta());
 
-        // Enhanced AI behavior based on faction and unit type
-        match unit.faction {
+        // The protection detail moves itself and a sheltered or relocating
+        // principal; the hunt for him moves the units tasked to it, units
+        // running from gas or flames move themselves until clear, and patrols
+        // follow up what they heard
+        if is_bodyguard
+            || hvt_tasked
+            || is_fleeing
+            || investigating
+            || principal.is_some_and(ProtectedPrincipal::is_moved_by_detail)
+        {
+            continue;
+        }
+
+        let enemy_positions = &hostile_positions[unit.faction.index()];
+
+        // Units holding an area answer to their leash, not their faction behavior
+        if let Some(hold_area) = hold_area {
+            movement.target_position = hold_area_destination(
+                transform.translation,
+                unit.range,
+                hold_area,
+                enemy_positions,
+            );
+            continue;
+        }
+
+        // Enhanced AI behavior based on side, enemies and unit type
+        match unit.faction.side() {
             Faction::Military => {
-                let behavior =
-                    choose_military_behavior(&unit, transform, &cartel_positions, ovidio_position);
-                execute_military_behavior(&mut movement, transform, behavior, &cartel_positions);
+                // The military only knows where he is from its target package
+                let behavior = choose_military_behavior(
+                    &unit,
+                    transform,
+                    enemy_positions,
+                    target_package.fresh_position(),
+                    rng,
+                );
+                execute_military_behavior(&mut movement, transform, behavior, enemy_positions, rng);
             }
             Faction::Cartel => {
+                // Only his own people rally to Ovidio
+                let rally_point = ovidio_position
+                    .filter(|_| diplomacy.is_friendly(&unit.faction, &Faction::Cartel));
                 let behavior =
-                    choose_cartel_behavior(&unit, transform, &military_positions, ovidio_position);
-                execute_cartel_behavior(&mut movement, transform, behavior, &military_positions);
+                    choose_cartel_behavior(&unit, transform, enemy_positions, rally_point, rng);
+                execute_cartel_behavior(&mut movement, transform, behavior, enemy_positions, rng);
             }
             _ => {}
         }
@@ -170,6 +267,7 @@ fn choose_military_behavior(
     transform: &Transform,
     cartel_positions: &[Vec3],
     ovidio_position: Option<Vec3>,
+    rng: &mut StdRng,
 ) -> TacticalBehavior {
     let unit_pos = transform.translation;
 
@@ -190,14 +288,14 @@ fn choose_military_behavior(
         .iter()
         .filter(|&&pos| unit_pos.distance(pos) < 150.0)
         .count();
-    let nearby_allies = count_nearby_military_units(unit_pos, &[], 100.0); // Would need all_units_query
+    let nearby_allies = count_nearby_military_units(unit_pos, &[], 100.0, rng); // Would need all_units_query
 
     // Tactical decision making based on situation
     match unit.unit_type {
         UnitType::SpecialForces => {
             if unit.health < unit.max_health * 0.3 {
                 // Low health - retreat
-                let retreat_pos = find_retreat_position(unit_pos, cartel_positions);
+                let retreat_pos = find_retreat_position(unit_pos, cartel_positions, rng);
                 TacticalBehavior::RetreatAndRegroup(retreat_pos)
             } else if nearby_enemies > 2 && nearby_allies < 2 {
                 // Outnumbered - use flanking
@@ -238,7 +336,7 @@ fn choose_military_behavior(
         }
         UnitType::Soldier => {
             if unit.health < unit.max_health * 0.4 {
-                let retreat_pos = find_retreat_position(unit_pos, cartel_positions);
+                let retreat_pos = find_retreat_position(unit_pos, cartel_positions, rng);
                 TacticalBehavior::RetreatAndRegroup(retreat_pos)
             } else if nearby_allies >= 2 {
                 // Strength in numbers - advance
@@ -268,6 +366,7 @@ fn choose_cartel_behavior(
     transform: &Transform,
     military_positions: &[Vec3],
     ovidio_position: Option<Vec3>,
+    rng: &mut StdRng,
 ) -> TacticalBehavior {
     let unit_pos = transform.translation;
 
@@ -321,7 +420,7 @@ fn choose_cartel_behavior(
                 TacticalBehavior::RetreatAndRegroup(safe_pos)
             } else if nearby_enemies > 2 {
                 // Use hit-and-run tactics
-                let retreat_pos = find_retreat_position(unit_pos, military_positions);
+                let retreat_pos = find_retreat_position(unit_pos, military_positions, rng);
                 TacticalBehavior::RetreatAndRegroup(retreat_pos)
             } else if let Some(threat_pos) = nearest_threat {
                 if unit_pos.distance(threat_pos) < 100.0 {
@@ -339,7 +438,7 @@ fn choose_cartel_behavior(
                 let sniper_distance = unit_pos.distance(threat_pos);
                 if sniper_distance < 150.0 {
                     // Too close - retreat to optimal range
-                    let retreat_pos = find_retreat_position(unit_pos, military_positions);
+                    let retreat_pos = find_retreat_position(unit_pos, military_positions, rng);
                     TacticalBehavior::RetreatAndRegroup(retreat_pos)
                 } else {
                     // Good position - hold and fire
@@ -397,17 +496,14 @@ fn execute_military_behavior(
     transform: &Transform,
     behavior: TacticalBehavior,
     cartel_positions: &[Vec3],
+    rng: &mut StdRng,
 ) {
     let current_pos = transform.translation;
 
     let target_pos = match behavior {
         TacticalBehavior::AssaultObjective(target) => {
             // Direct approach with slight randomization
-            let offset = Vec3::new(
-                thread_rng().gen_range(-20.0..20.0),
-                thread_rng().gen_range(-20.0..20.0),
-                0.0,
-            );
+            let offset = Vec3::new(rng.gen_range(-20.0..20.0), rng.gen_range(-20.0..20.0), 0.0);
             target + offset
         }
         TacticalBehavior::FlankingManeuver(target) => {
@@ -417,7 +513,7 @@ fn execute_military_behavior(
             // Move toward target but maintain distance from enemies
             let direction = (target - current_pos).normalize();
             let safe_advance = current_pos + direction * 40.0;
-            avoid_enemy_clusters(safe_advance, cartel_positions, 60.0)
+            avoid_threat_clusters(safe_advance, cartel_positions, 60.0)
         }
         TacticalBehavior::RetreatAndRegroup(retreat_pos) => retreat_pos,
         TacticalBehavior::SuppressiveFire(target) => {
@@ -435,17 +531,14 @@ fn execute_cartel_behavior(
     transform: &Transform,
     behavior: TacticalBehavior,
     military_positions: &[Vec3],
+    rng: &mut StdRng,
 ) {
     let current_pos = transform.translation;
 
     let target_pos = match behavior {
         TacticalBehavior::DefensivePosition(_) => {
             // Hold position with minor adjustments
-            let adjustment = Vec3::new(
-                thread_rng().gen_range(-15.0..15.0),
-                thread_rng().gen_range(-15.0..15.0),
-                0.0,
-            );
+            let adjustment = Vec3::new(rng.gen_range(-15.0..15.0), rng.gen_range(-15.0..15.0), 0.0);
             current_pos + adjustment
         }
         TacticalBehavior::SupportAllies(ally_pos) => {
@@ -462,7 +555,7 @@ fn execute_cartel_behavior(
         TacticalBehavior::PatrolArea(_) => {
             // Patrol around current area
             let patrol_radius = 80.0;
-            let angle = thread_rng().gen_range(0.0..std::f32::consts::PI * 2.0);
+            let angle = rng.gen_range(0.0..std::f32::consts::PI * 2.0);
             current_pos
                 + Vec3::new(
                     angle.cos() * patrol_radius,
@@ -473,7 +566,7 @@ fn execute_cartel_behavior(
         TacticalBehavior::AdvanceCarefully(target) => {
             let direction = (target - current_pos).normalize();
             let careful_advance = current_pos + direction * 30.0;
-            avoid_enemy_clusters(careful_advance, military_positions, 80.0)
+            avoid_threat_clusters(careful_advance, military_positions, 80.0)
         }
         _ => current_pos,
     };
@@ -483,6 +576,33 @@ fn execute_cartel_behavior(
 
 // ==================== TACTICAL UTILITY FUNCTIONS ====================
 
+const HOLD_POST_SLACK: f32 = 10.0; // Close enough to the post to stand still
+
+// Where a unit holding an area should go: toward the nearest enemy inside the
+// leash (stopping at its own weapon range, and never past the leash), else
+// back to its post. None means stay put.
+fn hold_area_destination(
+    unit_pos: Vec3,
+    weapon_range: f32,
+    hold_area: &HoldArea,
+    enemy_positions: &[Vec3],
+) -> Option<Vec3> {
+    let intruder = enemy_positions
+        .iter()
+        .filter(|pos| pos.distance(hold_area.center) <= hold_area.leash_radius)
+        .min_by(|a, b| unit_pos.distance(**a).total_cmp(&unit_pos.distance(**b)));
+
+    match intruder {
+        Some(intruder) if unit_pos.distance(*intruder) <= weapon_range => None,
+        Some(intruder) => {
+            let toward = *intruder - hold_area.center;
+            Some(hold_area.center + toward.clamp_length_max(hold_area.leash_radius))
+        }
+        None if unit_pos.distance(hold_area.post) > HOLD_POST_SLACK => Some(hold_area.post),
+        None => None,
+    }
+}
+
 fn calculate_flanking_position_legacy(
     unit_pos: Vec3,
     target_pos: Vec3,
@@ -491,12 +611,12 @@ fn calculate_flanking_position_legacy(
     calculate_flanking_position(unit_pos, target_pos, 120.0)
 }
 
-fn find_retreat_position(unit_pos: Vec3, threat_positions: &[Vec3]) -> Vec3 {
+fn find_retreat_position(unit_pos: Vec3, threat_positions: &[Vec3], rng: &mut StdRng) -> Vec3 {
     if threat_positions.is_empty() {
         return unit_pos
             + Vec3::new(
-                thread_rng().gen_range(-100.0..100.0),
-                thread_rng().gen_range(-100.0..100.0),
+                rng.gen_range(-100.0..100.0),
+                rng.gen_range(-100.0..100.0),
                 0.0,
             );
     }
@@ -543,7 +663,7 @@ fn find_firing_position(unit_pos: Vec3, target_pos: Vec3, enemy_positions: &[Vec
     let firing_pos = target_pos - to_target * optimal_distance;
 
     // Adjust to avoid other enemies
-    avoid_enemy_clusters(firing_pos, enemy_positions, 50.0)
+    avoid_threat_clusters(firing_pos, enemy_positions, 50.0)
 }
 
 fn find_covered_approach(unit_pos: Vec3, target_pos: Vec3, threat_positions: &[Vec3]) -> Vec3 {
@@ -559,53 +679,44 @@ fn find_covered_approach(unit_pos: Vec3, target_pos: Vec3, threat_positions: &[V
     let direction = direct_path.normalize();
     let intermediate_pos = unit_pos + direction * safe_distance;
 
-    avoid_enemy_clusters(intermediate_pos, threat_positions, 70.0)
+    avoid_threat_clusters(intermediate_pos, threat_positions, 70.0)
 }
 
-fn avoid_enemy_clusters(
-    desired_pos: Vec3,
-    enemy_positions: &[Vec3],
-    avoidance_radius: f32,
-) -> Vec3 {
-    let mut adjusted_pos = desired_pos;
-
-    for &enemy_pos in enemy_positions {
-        let distance = adjusted_pos.distance(enemy_pos);
-        if distance < avoidance_radius {
-            let push_direction = (adjusted_pos - enemy_pos).normalize();
-            let push_strength = avoidance_radius - distance;
-            adjusted_pos += push_direction * push_strength;
-        }
-    }
-
-    adjusted_pos
-}
-
-fn count_nearby_military_units(pos: Vec3, _all_units: &[Vec3], radius: f32) -> usize {
+fn count_nearby_military_units(
+    pos: Vec3,
+    _all_units: &[Vec3],
+    radius: f32,
+    rng: &mut StdRng,
+) -> usize {
     // Placeholder - would count nearby military units in actual implementation
-    thread_rng().gen_range(0..3) // Random for now
+    rng.gen_range(0..3) // Random for now
 }
 
 // ==================== DIFFICULTY CALCULATION FUNCTIONS ====================
 
-fn calculate_adaptive_modifier(player_performance: f32, mission_time: f32) -> f32 {
+fn calculate_adaptive_modifier(
+    player_performance: f32,
+    mission_time: f32,
+    tuning: &DirectorBalance,
+) -> f32 {
     // Base adaptive scaling
-    let performance_modifier = if player_performance < 0.2 {
-        0.5 // Significant reduction for struggling players
-    } else if player_performance < 0.4 {
-        0.7 // Moderate reduction
-    } else if player_performance < 0.6 {
-        0.9 // Slight reduction
-    } else if player_performance > 0.8 {
-        1.4 // Increase for dominating players
-    } else if player_performance > 0.65 {
-        1.2 // Moderate increase
+    let performance_modifier = if player_performance < tuning.struggling_threshold {
+        tuning.struggling_modifier // Significant reduction for struggling players
+    } else if player_performance < tuning.weak_threshold {
+        tuning.weak_modifier // Moderate reduction
+    } else if player_performance < tuning.below_par_threshold {
+        tuning.below_par_modifier // Slight reduction
+    } else if player_performance > tuning.dominating_threshold {
+        tuning.dominating_modifier // Increase for dominating players
+    } else if player_performance > tuning.strong_threshold {
+        tuning.strong_modifier // Moderate increase
     } else {
         1.0 // Balanced
     };
 
     // Time-based scaling - get harder as mission progresses
-    let time_modifier = 1.0 + (mission_time / 600.0) * 0.3; // Up to 30% harder over 10 minutes
+    let time_modifier =
+        1.0 + (mission_time / tuning.time_scaling_period) * tuning.time_scaling_bonus;
 
     performance_modifier * time_modifier
 }
@@ -615,11 +726,16 @@ fn check_spawn_conditions(
     game_state: &GameState,
     cartel_units: usize,
     military_units: usize,
+    tuning: &DirectorBalance,
 ) -> bool {
     // Multiple spawn triggers
-    let time_trigger = ai_director.last_spawn_time > (60.0 / ai_director.intensity_level.max(0.5));
-    let intensity_trigger = ai_director.intensity_level > 1.5;
-    let imbalance_trigger = cartel_units > military_units * 2; // Too many cartel units
+    let interval =
+        tuning.base_spawn_interval * ai_director.personality.spawn_interval_scale(tuning);
+    let time_trigger =
+        ai_director.last_spawn_time > (interval / ai_director.intensity_level.max(0.5));
+    let intensity_trigger = ai_director.intensity_level > tuning.intensity_spawn_threshold;
+    // Too many cartel units
+    let imbalance_trigger = cartel_units > military_units * tuning.imbalance_ratio;
     let phase_trigger = matches!(
         game_state.game_phase,
         GamePhase::ApplyPressure | GamePhase::HoldTheLine
@@ -633,11 +749,16 @@ struct SpawnResult {
     unit_type_name: &'static str,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_dynamic_spawning(
     commands: &mut Commands,
+    staging_areas: &mut Query<&mut ProductionQueue, With<StagingArea>>,
     ai_director: &AiDirector,
     game_assets: &Res<GameAssets>,
     game_state: &GameState,
+    balance: &BalanceConfig,
+    catalog: &UnitCatalog,
+    rng: &mut StdRng,
 ) -> SpawnResult {
     let base_spawn_count = (ai_director.intensity_level * 1.5) as u32;
     let spawn_count = base_spawn_count.clamp(1, 4);
@@ -652,31 +773,37 @@ fn execute_dynamic_spawning(
     };
 
     // Smart spawn positioning - avoid clustering
-    let spawn_positions = generate_tactical_spawn_positions(spawn_count);
+    let spawn_positions = generate_tactical_spawn_positions(spawn_count, rng);
 
+    // With base play they're queued at the staging areas instead
+    let mut spawned = 0;
     for (i, position) in spawn_positions.iter().enumerate() {
-        let unit_type = if i == 0 || thread_rng().gen_bool(0.4) {
+        let unit_type = if i == 0 || rng.gen_bool(0.4) {
             primary_unit.clone()
         } else {
             secondary_unit.clone()
         };
 
-        spawn_unit(
+        if deploy_military_unit(
             commands,
+            staging_areas,
             unit_type,
-            Faction::Military,
             *position,
             game_assets,
-        );
+            catalog,
+            &balance.production,
+        ) {
+            spawned += 1;
+        }
     }
 
     SpawnResult {
-        spawned: spawn_count,
+        spawned,
         unit_type_name,
     }
 }
 
-fn generate_tactical_spawn_positions(count: u32) -> Vec<Vec3> {
+fn generate_tactical_spawn_positions(count: u32, rng: &mut StdRng) -> Vec<Vec3> {
     let mut positions = Vec::new();
     let spawn_radius = 250.0;
 
@@ -688,10 +815,10 @@ fn generate_tactical_spawn_positions(count: u32) -> Vec<Vec3> {
         let base_angle = (entry_angles[angle_index] as f32).to_radians();
 
         // Add some randomization to avoid predictable spawning
-        let angle_variation = thread_rng().gen_range(-0.3..0.3);
+        let angle_variation = rng.gen_range(-0.3..0.3);
         let final_angle = base_angle + angle_variation;
 
-        let distance_variation = thread_rng().gen_range(0.8..1.2);
+        let distance_variation = rng.gen_range(0.8..1.2);
         let final_distance = spawn_radius * distance_variation;
 
         positions.push(Vec3::new(
@@ -711,37 +838,83 @@ fn apply_difficulty_modifiers(_ai_director: &AiDirector, _game_state: &GameState
 
 // ==================== DIFFICULTY SETTINGS SYSTEM ====================
 
-pub fn difficulty_settings_system(mut ai_director: ResMut<AiDirector>, input: Res<Input<KeyCode>>) {
+pub fn difficulty_settings_system(
+    mut ai_director: ResMut<AiDirector>,
+    input: Res<Input<KeyCode>>,
+    daily: Option<Res<DailyChallengeState>>,
+) {
+    // Every player faces the same director in a daily challenge
+    if daily_running(daily.as_deref()) {
+        return;
+    }
+
     // Toggle adaptive difficulty with 'D' key
     if input.just_pressed(KeyCode::D) {
         ai_director.adaptive_difficulty = !ai_director.adaptive_difficulty;
 
-        let status = if ai_director.adaptive_difficulty {
-            "ENABLED"
+        let message = if ai_director.adaptive_difficulty {
+            "radio.dynamic_difficulty_on"
         } else {
-            "DISABLED"
+            "radio.dynamic_difficulty_off"
         };
-        play_tactical_sound("radio", &format!("Dynamic Difficulty: {}", status));
+        play_tactical_sound("radio", &tr(message));
     }
 
     // Manual intensity adjustment with F1-F4 keys
     if input.just_pressed(KeyCode::F1) {
         ai_director.intensity_level = 0.5;
         ai_director.adaptive_difficulty = false; // Disable adaptive when manually set
-        play_tactical_sound("radio", "Difficulty set to: EASY");
+        play_tactical_sound("radio", &tr("radio.difficulty_easy"));
     } else if input.just_pressed(KeyCode::F2) {
         ai_director.intensity_level = 1.0;
         ai_director.adaptive_difficulty = false;
-        play_tactical_sound("radio", "Difficulty set to: NORMAL");
+        play_tactical_sound("radio", &tr("radio.difficulty_normal"));
     } else if input.just_pressed(KeyCode::F3) {
         ai_director.intensity_level = 1.5;
         ai_director.adaptive_difficulty = false;
-        play_tactical_sound("radio", "Difficulty set to: HARD");
+        play_tactical_sound("radio", &tr("radio.difficulty_hard"));
     } else if input.just_pressed(KeyCode::F4) {
         ai_director.intensity_level = 2.0;
         ai_director.adaptive_difficulty = false;
-        play_tactical_sound("radio", "Difficulty set to: EXTREME");
+        play_tactical_sound("radio", &tr("radio.difficulty_extreme"));
     }
 }
 
 // ==================== END OF AI SYSTEMS ====================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hold_area_engages_inside_leash_and_returns() {
+        let hold_area = HoldArea {
+            center: Vec3::ZERO,
+            post: Vec3::new(20.0, 0.0, 0.0),
+            leash_radius: 150.0,
+        };
+
+        // Bait outside the leash is ignored; the unit walks back to its post
+        let bait = [Vec3::new(400.0, 0.0, 0.0)];
+        let away = Vec3::new(120.0, 0.0, 0.0);
+        assert_eq!(
+            hold_area_destination(away, 100.0, &hold_area, &bait),
+            Some(hold_area.post)
+        );
+        assert_eq!(
+            hold_area_destination(hold_area.post, 100.0, &hold_area, &bait),
+            None
+        );
+
+        // An intruder out of weapon range is approached, one in range is fought in place
+        let intruder = [Vec3::new(0.0, 140.0, 0.0)];
+        assert_eq!(
+            hold_area_destination(Vec3::new(0.0, -100.0, 0.0), 100.0, &hold_area, &intruder),
+            Some(intruder[0])
+        );
+        assert_eq!(
+            hold_area_destination(Vec3::new(0.0, 60.0, 0.0), 100.0, &hold_area, &intruder),
+            None
+        );
+    }
+}
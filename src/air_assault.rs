@@ -0,0 +1,456 @@
+use crate::ai::ai_director_system;
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{BalanceConfig, UnitCatalog};
+use crate::localization::{tr, tr_args};
+use crate::map::{CityMap, TileKind};
+use crate::resources::{AiDirector, GameAssets, GameState, IntelSystem};
+use crate::spawners::spawn_unit;
+use crate::utils::{
+    iso_to_world, play_tactical_sound, play_tactical_sound_at_position, SimulationSet,
+};
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+// ==================== AIR ASSAULT PLUGIN ====================
+
+// Landing zones are marked at the start of each mission: the map's "LZ"
+// objective markers, or else open park ground. While the army applies
+// pressure or holds the line, the director puts a helicopter down on the LZ
+// nearest the cartel every so often. It comes in from off the map, circles
+// the LZ and fast-ropes a squad of special forces one by one, then flies
+// off. Intel hears it called in, so the LZ gets a warning marker. Cartel
+// fighters inside the LZ contest it: the pilot calls the landing off and
+// whoever hasn't roped down yet stays on board.
+
+// Used when the map has neither LZ markers nor parks
+const FALLBACK_SITES: [Vec2; 2] = [Vec2::new(-480.0, 240.0), Vec2::new(480.0, -240.0)];
+const SITE_SPACING: f32 = 4.0; // Tiles between landing zones on open ground
+const ROPE_SPREAD: f32 = 24.0; // How far from the middle of the LZ each soldier lands
+
+pub struct AirAssaultPlugin;
+
+impl Plugin for AirAssaultPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AirAssaultDesk>()
+            .add_event::<AirAssaultEvent>()
+            .add_systems(OnEnter(AppState::InGame), landing_zone_setup_system)
+            .add_systems(
+                FixedUpdate,
+                (
+                    air_assault_director_system.after(ai_director_system),
+                    air_assault_launch_system,
+                    air_assault_system,
+                )
+                    .chain()
+                    .run_if(resource_exists::<GameAssets>())
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+/// Sends a helicopter to put a squad down on a landing zone.
+#[derive(Event, Clone, Debug)]
+pub struct AirAssaultEvent {
+    pub landing_zone: Entity,
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AirAssaultDesk {
+    pub cooldown: f32, // Seconds before the director can send the next one
+}
+
+/// Where the landing zones go: the map's LZ markers, or else park tiles at
+/// least a few tiles apart.
+pub fn landing_sites(city_map: &CityMap) -> Vec<Vec2> {
+    let marked: Vec<Vec2> = city_map
+        .objective_markers
+        .iter()
+        .filter(|marker| marker.label == "LZ")
+        .map(|marker| city_map.tile_center(marker.tile.0, marker.tile.1))
+        .collect();
+    if !marked.is_empty() {
+        return marked;
+    }
+
+    let spacing = SITE_SPACING * city_map.tile_size;
+    let mut sites: Vec<Vec2> = Vec::new();
+    for (col, row, kind) in city_map.tiles() {
+        let center = city_map.tile_center(col, row);
+        if kind == TileKind::Park && sites.iter().all(|site| site.distance(center) >= spacing) {
+            sites.push(center);
+        }
+    }
+    if sites.is_empty() {
+        return FALLBACK_SITES.to_vec();
+    }
+    sites
+}
+
+/// Whether any cartel fighter is inside the landing zone.
+pub fn contested(landing_zone: Vec2, radius: f32, cartel: &[Vec2]) -> bool {
+    cartel
+        .iter()
+        .any(|position| position.distance(landing_zone) <= radius)
+}
+
+/// The landing zone to put an assault down on: the uncontested one closest
+/// to a cartel fighter.
+pub fn pick_landing_zone(zones: &[(Entity, Vec2, f32)], cartel: &[Vec2]) -> Option<Entity> {
+    let closest = |position: Vec2| {
+        cartel
+            .iter()
+            .map(|fighter| fighter.distance(position))
+            .fold(f32::MAX, f32::min)
+    };
+    zones
+        .iter()
+        .filter(|(_, position, radius)| !contested(*position, *radius, cartel))
+        .min_by(|(_, a, _), (_, b, _)| closest(*a).total_cmp(&closest(*b)))
+        .map(|(entity, _, _)| *entity)
+}
+
+/// Moves `position` up to `step` toward `target`, and whether it got there.
+fn fly_toward(position: Vec2, target: Vec2, step: f32) -> (Vec2, bool) {
+    let remaining = position.distance(target);
+    if remaining <= step {
+        (target, true)
+    } else {
+        (position + (target - position) / remaining * step, false)
+    }
+}
+
+// Dust kicked up under the rotors, in a ring around where a soldier lands
+fn spawn_rotor_wash(commands: &mut Commands, position: Vec2) {
+    for i in 0..8 {
+        let velocity = Vec2::from_angle(i as f32 * TAU / 8.0) * 90.0;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(0.76, 0.66, 0.48, 0.8),
+                    custom_size: Some(Vec2::new(5.0, 5.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(0.5)),
+                ..default()
+            },
+            ParticleEffect {
+                lifetime: Timer::from_seconds(1.2, TimerMode::Once),
+                velocity: velocity.extend(0.0),
+            },
+        ));
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn landing_zone_setup_system(
+    mut commands: Commands,
+    city_map: Res<CityMap>,
+    balance: Res<BalanceConfig>,
+    mut desk: ResMut<AirAssaultDesk>,
+    zone_query: Query<Entity, Or<(With<LandingZone>, With<AirAssault>)>>,
+) {
+    for entity in zone_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let balance = &balance.air_assault;
+    desk.cooldown = balance.interval;
+
+    for site in landing_sites(&city_map) {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(0.9, 0.9, 0.2, 0.15),
+                    custom_size: Some(Vec2::splat(balance.lz_radius * 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(site.extend(0.2)),
+                ..default()
+            },
+            LandingZone {
+                radius: balance.lz_radius,
+            },
+        ));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn air_assault_director_system(
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    director: Res<AiDirector>,
+    game_state: Res<GameState>,
+    mut desk: ResMut<AirAssaultDesk>,
+    zone_query: Query<(Entity, &Transform, &LandingZone)>,
+    unit_query: Query<(&Unit, &Transform)>,
+    assault_query: Query<(), With<AirAssault>>,
+    mut assault_events: EventWriter<AirAssaultEvent>,
+) {
+    let balance = &balance.air_assault;
+    desk.cooldown -= time.delta_seconds();
+    let pushing = matches!(
+        game_state.game_phase,
+        GamePhase::ApplyPressure | GamePhase::HoldTheLine
+    );
+    // One helicopter in the air at a time
+    if desk.cooldown > 0.0
+        || !pushing
+        || director.intensity_level < balance.min_intensity
+        || !assault_query.is_empty()
+    {
+        return;
+    }
+
+    let zones: Vec<(Entity, Vec2, f32)> = zone_query
+        .iter()
+        .map(|(entity, transform, zone)| (entity, transform.translation.truncate(), zone.radius))
+        .collect();
+    let cartel: Vec<Vec2> = unit_query
+        .iter()
+        .filter(|(unit, _)| unit.health > 0.0 && unit.faction == Faction::Cartel)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    if let Some(landing_zone) = pick_landing_zone(&zones, &cartel) {
+        assault_events.send(AirAssaultEvent { landing_zone });
+        desk.cooldown = balance.interval;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn air_assault_launch_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    mut intel_system: Option<ResMut<IntelSystem>>,
+    mut assault_events: EventReader<AirAssaultEvent>,
+    zone_query: Query<(&Transform, &LandingZone)>,
+) {
+    let balance = &balance.air_assault;
+    for event in assault_events.read() {
+        let Ok((zone_transform, zone)) = zone_query.get(event.landing_zone) else {
+            continue;
+        };
+        let landing_zone = zone_transform.translation.truncate();
+
+        // In from beyond the edge of town, on the far side of the LZ
+        let outward = landing_zone.try_normalize().unwrap_or(Vec2::Y);
+        let entry = landing_zone + outward * balance.approach_distance;
+        let entity = spawn_unit(
+            &mut commands,
+            UnitType::Helicopter,
+            Faction::Military,
+            iso_to_world(entry.extend(0.0)),
+            &game_assets,
+            &catalog,
+        );
+        commands
+            .entity(entity)
+            .remove::<(Movement, PathfindingAgent)>()
+            .insert(AirAssault {
+                landing_zone,
+                radius: zone.radius,
+                stage: AssaultStage::Inbound,
+                timer: 0.0,
+                roped: 0,
+                exit: entry,
+                angle: outward.y.atan2(outward.x),
+            });
+
+        if let Some(intel_system) = intel_system.as_mut() {
+            let network = &mut intel_system.global_intel_network;
+            network.active_intercepts.push(RadioIntercept {
+                message_type: RadioMessageType::AirSupport(landing_zone.extend(0.0)),
+                source_position: entry.extend(0.0),
+                intercept_time: time.elapsed_seconds(),
+                reliability: 0.9,
+                content: tr_args(
+                    "radio.air_assault_intercept",
+                    &[
+                        ("x", &format!("{:.0}", landing_zone.x)),
+                        ("y", &format!("{:.0}", landing_zone.y)),
+                    ],
+                ),
+            });
+            if network.active_intercepts.len() > 20 {
+                network.active_intercepts.remove(0);
+            }
+            intel_system.reports_gathered += 1;
+        }
+        play_tactical_sound("radio", &tr("radio.air_assault_inbound"));
+        play_tactical_sound_at_position("helicopter", "Helicopter inbound", entry.extend(0.0));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn air_assault_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    mut helicopter_query: Query<(Entity, &Unit, &mut Transform, &mut AirAssault)>,
+    unit_query: Query<(&Unit, &Transform), Without<AirAssault>>,
+) {
+    let balance = &balance.air_assault;
+    let dt = time.delta_seconds();
+    let cartel: Vec<Vec2> = unit_query
+        .iter()
+        .filter(|(unit, _)| unit.health > 0.0 && unit.faction == Faction::Cartel)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+
+    for (entity, unit, mut transform, mut assault) in helicopter_query.iter_mut() {
+        if unit.health <= 0.0 {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let step = unit.movement_speed * dt;
+        assault.timer += dt;
+
+        // A contested LZ is called off before anyone else ropes down
+        if matches!(
+            assault.stage,
+            AssaultStage::Circling | AssaultStage::FastRoping
+        ) && contested(assault.landing_zone, assault.radius, &cartel)
+        {
+            assault.stage = AssaultStage::Aborted;
+            play_tactical_sound("radio", &tr("radio.air_assault_aborted"));
+        }
+
+        let next = match assault.stage {
+            AssaultStage::Inbound => {
+                let orbit =
+                    assault.landing_zone + Vec2::from_angle(assault.angle) * balance.circle_radius;
+                let (next, arrived) = fly_toward(position, orbit, step);
+                if arrived {
+                    assault.stage = AssaultStage::Circling;
+                    assault.timer = 0.0;
+                    play_tactical_sound_at_position(
+                        "helicopter",
+                        "Helicopter circling the LZ",
+                        next.extend(0.0),
+                    );
+                }
+                next
+            }
+            AssaultStage::Circling => {
+                assault.angle += step / balance.circle_radius.max(1.0);
+                if assault.timer >= balance.circle_seconds {
+                    assault.stage = AssaultStage::FastRoping;
+                    assault.timer = 0.0;
+                    play_tactical_sound("radio", &tr("radio.air_assault_fast_rope"));
+                }
+                assault.landing_zone + Vec2::from_angle(assault.angle) * balance.circle_radius
+            }
+            AssaultStage::FastRoping => {
+                let (next, over) = fly_toward(position, assault.landing_zone, step);
+                if !over {
+                    assault.timer = 0.0;
+                }
+                while over
+                    && assault.timer >= balance.rope_interval
+                    && assault.roped < balance.squad_size
+                {
+                    assault.timer -= balance.rope_interval;
+                    let angle = assault.roped as f32 * TAU / balance.squad_size as f32;
+                    let landing = assault.landing_zone + Vec2::from_angle(angle) * ROPE_SPREAD;
+                    spawn_unit(
+                        &mut commands,
+                        UnitType::SpecialForces,
+                        Faction::Military,
+                        iso_to_world(landing.extend(0.0)),
+                        &game_assets,
+                        &catalog,
+                    );
+                    spawn_rotor_wash(&mut commands, landing);
+                    assault.roped += 1;
+                }
+                if assault.roped >= balance.squad_size {
+                    assault.stage = AssaultStage::Departing;
+                }
+                next
+            }
+            AssaultStage::Departing | AssaultStage::Aborted => {
+                let (next, gone) = fly_toward(position, assault.exit, step);
+                if gone {
+                    commands.entity(entity).despawn_recursive();
+                }
+                next
+            }
+        };
+        transform.translation = next.extend(transform.translation.z);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assaults_go_in_near_the_cartel_but_not_on_top_of_it() {
+        let zones = [
+            (Entity::from_raw(1), Vec2::new(0.0, 0.0), 100.0),
+            (Entity::from_raw(2), Vec2::new(300.0, 0.0), 100.0),
+            (Entity::from_raw(3), Vec2::new(1000.0, 0.0), 100.0),
+        ];
+        // Fighters in the first LZ contest it, so the next closest is picked
+        let cartel = [Vec2::new(50.0, 0.0), Vec2::new(20.0, 30.0)];
+        assert!(contested(zones[0].1, zones[0].2, &cartel));
+        assert!(!contested(zones[1].1, zones[1].2, &cartel));
+        assert_eq!(
+            pick_landing_zone(&zones, &cartel),
+            Some(Entity::from_raw(2))
+        );
+
+        let everywhere = [Vec2::ZERO, Vec2::new(300.0, 0.0), Vec2::new(1000.0, 50.0)];
+        assert_eq!(pick_landing_zone(&zones, &everywhere), None);
+    }
+
+    #[test]
+    fn test_landing_zones_go_on_marked_or_open_ground() {
+        let parks = CityMap::from_json(
+            r#"{
+                "name": "Parks",
+                "tile_size": 10.0,
+                "tiles": ["\"\"......\"", "\"\"......."],
+                "districts": [".........", "........."],
+                "neighborhoods": []
+            }"#,
+        )
+        .unwrap();
+        // One site per park, not one per park tile
+        assert_eq!(
+            landing_sites(&parks),
+            vec![parks.tile_center(0, 0), parks.tile_center(8, 0)]
+        );
+
+        let marked = CityMap::from_json(
+            r#"{
+                "name": "Marked",
+                "tile_size": 10.0,
+                "tiles": ["\"..", "..."],
+                "districts": ["...", "..."],
+                "neighborhoods": [],
+                "objective_markers": [{ "label": "LZ", "tile": [2, 1] }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(landing_sites(&marked), vec![marked.tile_center(2, 1)]);
+
+        let bare = CityMap::from_json(
+            r#"{
+                "name": "Bare",
+                "tile_size": 10.0,
+                "tiles": ["..."],
+                "districts": ["..."],
+                "neighborhoods": []
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(landing_sites(&bare), FALLBACK_SITES.to_vec());
+    }
+}
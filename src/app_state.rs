@@ -0,0 +1,193 @@
+use crate::components::GamePhase;
+use crate::resources::{GameSetupComplete, GameState};
+use bevy::prelude::*;
+
+// ==================== APP STATE ====================
+
+// GamePhase names every screen and every beat of a mission in one enum. The
+// coarse split lives here: AppState is a Bevy state for which part of the
+// game is showing, and MissionPhase is the beat of the mission being played.
+// Both follow GameState::game_phase, which stays the one place phases are
+// changed and saved; the state moves at the next frame's transition.
+//
+// Systems are scoped through GameSet instead of repeating run conditions:
+// GameSet::Session runs once the battlefield is set up, on any screen, and
+// GameSet::InGame only while a mission is being played.
+#[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    Menu, // Loading, profiles, menus and the campaign map
+    Briefing, // Mission briefing before the battle
+    InGame,   // Preparation through the last mission beat
+    PostGame, // Victory, defeat and game over screens
+}
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MissionPhase {
+    #[default]
+    Preparation,
+    InitialRaid,
+    BlockConvoy,
+    ApplyPressure,
+    HoldTheLine,
+}
+
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    Session,
+    InGame,
+}
+
+impl GamePhase {
+    pub fn app_state(&self) -> AppState {
+        match self {
+            GamePhase::Loading
+            | GamePhase::ProfileSelect
+            | GamePhase::MainMenu
+            | GamePhase::SaveMenu
+            | GamePhase::LoadMenu
+            | GamePhase::ModsMenu
+            | GamePhase::AccessibilityMenu
+            | GamePhase::TelemetryMenu
+            | GamePhase::LeaderboardMenu
+            | GamePhase::CampaignMap => AppState::Menu,
+            GamePhase::MissionBriefing => AppState::Briefing,
+            GamePhase::Preparation
+            | GamePhase::InitialRaid
+            | GamePhase::BlockConvoy
+            | GamePhase::ApplyPressure
+            | GamePhase::HoldTheLine => AppState::InGame,
+            GamePhase::Victory | GamePhase::Defeat | GamePhase::GameOver => AppState::PostGame,
+        }
+    }
+
+    // None on every screen that isn't part of a mission being played
+    pub fn mission_phase(&self) -> Option<MissionPhase> {
+        match self {
+            GamePhase::Preparation => Some(MissionPhase::Preparation),
+            GamePhase::InitialRaid => Some(MissionPhase::InitialRaid),
+            GamePhase::BlockConvoy => Some(MissionPhase::BlockConvoy),
+            GamePhase::ApplyPressure => Some(MissionPhase::ApplyPressure),
+            GamePhase::HoldTheLine => Some(MissionPhase::HoldTheLine),
+            _ => None,
+        }
+    }
+}
+
+impl MissionPhase {
+    // How hard the AI director pushes during this beat
+    pub fn intensity(&self) -> f32 {
+        match self {
+            MissionPhase::Preparation => 0.6,
+            MissionPhase::InitialRaid => 1.0,
+            MissionPhase::BlockConvoy => 1.3,
+            MissionPhase::ApplyPressure => 1.6,
+            MissionPhase::HoldTheLine => 2.0,
+        }
+    }
+}
+
+pub struct AppStatePlugin;
+
+impl Plugin for AppStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<AppState>()
+            .init_resource::<MissionPhase>()
+            .configure_sets(
+                Update,
+                (
+                    GameSet::Session.run_if(resource_exists::<GameSetupComplete>()),
+                    GameSet::InGame
+                        .in_set(GameSet::Session)
+                        .run_if(in_state(AppState::InGame)),
+                ),
+            )
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameSet::Session.run_if(resource_exists::<GameSetupComplete>()),
+                    GameSet::InGame
+                        .in_set(GameSet::Session)
+                        .run_if(in_state(AppState::InGame)),
+                ),
+            )
+            .add_systems(PreUpdate, app_state_sync_system);
+    }
+}
+
+// Runs before the state transition, so Update already sees the new state
+pub fn app_state_sync_system(
+    game_state: Res<GameState>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut mission_phase: ResMut<MissionPhase>,
+) {
+    let target = game_state.game_phase.app_state();
+    if *state.get() != target {
+        next_state.set(target);
+    }
+    // Outside a mission the last beat played is kept
+    if let Some(phase) = game_state.game_phase.mission_phase() {
+        if *mission_phase != phase {
+            *mission_phase = phase;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_mission_beat_is_in_game() {
+        for phase in [
+            GamePhase::Preparation,
+            GamePhase::InitialRaid,
+            GamePhase::BlockConvoy,
+            GamePhase::ApplyPressure,
+            GamePhase::HoldTheLine,
+        ] {
+            assert_eq!(phase.app_state(), AppState::InGame);
+            assert!(phase.mission_phase().is_some());
+        }
+        for phase in [
+            GamePhase::MainMenu,
+            GamePhase::CampaignMap,
+            GamePhase::MissionBriefing,
+            GamePhase::Defeat,
+        ] {
+            assert_ne!(phase.app_state(), AppState::InGame);
+            assert_eq!(phase.mission_phase(), None);
+        }
+    }
+
+    #[test]
+    fn test_sync_follows_the_game_phase() {
+        let mut app = App::new();
+        app.add_plugins(AppStatePlugin).insert_resource(GameState {
+            game_phase: GamePhase::BlockConvoy,
+            ..default()
+        });
+
+        app.update();
+        assert_eq!(
+            *app.world.resource::<State<AppState>>().get(),
+            AppState::InGame
+        );
+        assert_eq!(
+            *app.world.resource::<MissionPhase>(),
+            MissionPhase::BlockConvoy
+        );
+
+        app.world.resource_mut::<GameState>().game_phase = GamePhase::Victory;
+        app.update();
+        assert_eq!(
+            *app.world.resource::<State<AppState>>().get(),
+            AppState::PostGame
+        );
+        assert_eq!(
+            *app.world.resource::<MissionPhase>(),
+            MissionPhase::BlockConvoy
+        );
+    }
+}
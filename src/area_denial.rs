@@ -0,0 +1,480 @@
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{AreaDenialBalance, BalanceConfig};
+use crate::damage::{damage_system, DamageEvent};
+use crate::diplomacy::Diplomacy;
+use crate::economy::{charge, CartelTreasury};
+use crate::localization::tr;
+use crate::utils::{play_tactical_sound, play_tactical_sound_at_position, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== AREA DENIAL PLUGIN ====================
+
+// Ground can be made too costly to hold. Army and police riot units throw
+// tear gas at the enemy; cartel enforcers set barricades alight. Either
+// lingers a while: gas chokes the enemies of whoever threw it, hurting them
+// and spoiling their aim, while fire burns anyone standing in it. The smoke
+// of both blocks sight through the zone, for ordinary fire and overwatch
+// alike. AI units whose morale has cracked flee a zone that hurts them, and
+// routes are planned around such zones. The cartel lights barricades toward
+// the cursor (Comma); army, police and rival units use their kit on their
+// own once an enemy is in sight. Zones are cleared as each mission starts.
+
+const ZONE_Z: f32 = 0.45;
+const TEAR_GAS_COLOR: Color = Color::rgba(0.85, 0.9, 0.7, 0.35);
+const FIRE_COLOR: Color = Color::rgba(1.0, 0.45, 0.1, 0.4);
+const FADE_SECONDS: f32 = 2.0;
+const RETREAT_MARGIN: f32 = 30.0; // How far past the edge of a zone fleeing units stop
+
+pub struct AreaDenialPlugin;
+
+impl Plugin for AreaDenialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), area_denial_reset_system)
+            .add_systems(
+                FixedUpdate,
+                (ai_area_denial_system, area_denial_system)
+                    .chain()
+                    .before(damage_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, area_denial_fade_system.in_set(GameSet::Session));
+    }
+}
+
+impl AreaDenialKind {
+    /// How far from the unit the canister or barricade can go.
+    pub fn range(self, balance: &AreaDenialBalance) -> f32 {
+        match self {
+            AreaDenialKind::TearGas => balance.tear_gas_range,
+            AreaDenialKind::BurningBarricade => balance.barricade_range,
+        }
+    }
+
+    pub fn cooldown(self, balance: &AreaDenialBalance) -> f32 {
+        match self {
+            AreaDenialKind::TearGas => balance.tear_gas_cooldown,
+            AreaDenialKind::BurningBarricade => balance.barricade_cooldown,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            AreaDenialKind::TearGas => TEAR_GAS_COLOR,
+            AreaDenialKind::BurningBarricade => FIRE_COLOR,
+        }
+    }
+}
+
+impl AreaDenialKit {
+    pub fn ready(&self) -> bool {
+        self.cooldown <= 0.0
+    }
+}
+
+impl AreaDenialZone {
+    pub fn new(
+        kind: AreaDenialKind,
+        faction: Faction,
+        center: Vec2,
+        balance: &AreaDenialBalance,
+    ) -> Self {
+        let (radius, remaining) = match kind {
+            AreaDenialKind::TearGas => (balance.tear_gas_radius, balance.tear_gas_seconds),
+            AreaDenialKind::BurningBarricade => {
+                (balance.barricade_radius, balance.barricade_seconds)
+            }
+        };
+        Self {
+            kind,
+            faction,
+            center,
+            radius,
+            remaining,
+        }
+    }
+
+    pub fn damage_per_second(&self, balance: &AreaDenialBalance) -> f32 {
+        match self.kind {
+            AreaDenialKind::TearGas => balance.tear_gas_damage,
+            AreaDenialKind::BurningBarricade => balance.barricade_damage,
+        }
+    }
+
+    pub fn contains(&self, position: Vec2) -> bool {
+        position.distance(self.center) <= self.radius
+    }
+
+    /// Gas only chokes the enemies of whoever threw it; fire burns anyone.
+    pub fn affects(&self, faction: &Faction, diplomacy: &Diplomacy) -> bool {
+        match self.kind {
+            AreaDenialKind::TearGas => diplomacy.is_hostile(&self.faction, faction),
+            AreaDenialKind::BurningBarricade => true,
+        }
+    }
+
+    /// Whether the smoke hides `to` from `from`: the line between them
+    /// passes through the zone, or either stands in it.
+    pub fn blocks_sight(&self, from: Vec2, to: Vec2) -> bool {
+        let line = to - from;
+        let along = ((self.center - from).dot(line) / line.length_squared().max(f32::EPSILON))
+            .clamp(0.0, 1.0);
+        self.contains(from + line * along)
+    }
+
+    /// The nearest spot clear of the zone for a unit standing at `position`.
+    pub fn way_out(&self, position: Vec2) -> Vec2 {
+        let direction = (position - self.center).try_normalize().unwrap_or(Vec2::X);
+        self.center + direction * (self.radius + RETREAT_MARGIN)
+    }
+}
+
+/// Where a zone aimed from `from` at `toward` goes: there, or as far as the
+/// kit reaches.
+pub fn placement(from: Vec2, toward: Vec2, range: f32) -> Vec2 {
+    from + (toward - from).clamp_length_max(range)
+}
+
+/// Where an AI unit at `from` puts its zone with `enemy` in sight: gas right
+/// on the enemy once in throwing range, a barricade lit halfway between them.
+pub fn ai_site(
+    kind: AreaDenialKind,
+    from: Vec2,
+    enemy: Vec2,
+    balance: &AreaDenialBalance,
+) -> Option<Vec2> {
+    let range = kind.range(balance);
+    match kind {
+        AreaDenialKind::TearGas => (from.distance(enemy) <= range).then_some(enemy),
+        AreaDenialKind::BurningBarricade => Some(placement(from, from.lerp(enemy, 0.5), range)),
+    }
+}
+
+pub fn spawn_area_denial_zone(commands: &mut Commands, zone: AreaDenialZone) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: zone.kind.color(),
+                custom_size: Some(Vec2::splat(zone.radius * 2.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(zone.center.extend(ZONE_Z)),
+            ..default()
+        },
+        zone,
+    ));
+}
+
+fn replan_routes(agents: &mut Query<&mut PathfindingAgent>) {
+    for mut agent in agents.iter_mut() {
+        agent.path.clear();
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn area_denial_reset_system(
+    mut commands: Commands,
+    zone_query: Query<Entity, With<AreaDenialZone>>,
+) {
+    for entity in zone_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Comma: the nearest selected cartel unit with its kit ready lights a
+// barricade toward the cursor, as far out as it can reach
+#[allow(clippy::too_many_arguments)]
+pub fn area_denial_order_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut carrier_query: Query<(Entity, &Unit, &Transform, &mut AreaDenialKit), With<Selected>>,
+    mut agent_query: Query<&mut PathfindingAgent>,
+) {
+    if !input.just_pressed(KeyCode::Comma) {
+        return;
+    }
+    let balance = &balance.area_denial;
+    let Some(cursor) = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+    else {
+        return;
+    };
+
+    let carriers: Vec<(Entity, Vec2, bool)> = carrier_query
+        .iter()
+        .filter(|(_, unit, _, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(entity, _, transform, kit)| (entity, transform.translation.truncate(), kit.ready()))
+        .collect();
+    let Some((carrier, from)) = carriers
+        .iter()
+        .filter(|(_, _, ready)| *ready)
+        .min_by(|(_, a, _), (_, b, _)| a.distance(cursor).total_cmp(&b.distance(cursor)))
+        .map(|(entity, position, _)| (*entity, *position))
+    else {
+        let reason = if carriers.is_empty() {
+            "radio.area_denial_no_carrier"
+        } else {
+            "radio.area_denial_not_ready"
+        };
+        play_tactical_sound("radio", &tr(reason));
+        return;
+    };
+    if !charge(treasury.as_deref_mut(), balance.barricade_cost) {
+        return;
+    }
+
+    let Ok((_, _, _, mut kit)) = carrier_query.get_mut(carrier) else {
+        return;
+    };
+    let site = placement(from, cursor, kit.kind.range(balance));
+    spawn_area_denial_zone(
+        &mut commands,
+        AreaDenialZone::new(kit.kind, Faction::Cartel, site, balance),
+    );
+    kit.cooldown = kit.kind.cooldown(balance);
+    replan_routes(&mut agent_query);
+    play_tactical_sound_at_position("ability", &tr("radio.barricade_lit"), site.extend(0.0));
+}
+
+// Army, police and rival units carrying a kit use it on the nearest enemy
+// they can see, unless it is already standing in their side's gas or fire
+pub fn ai_area_denial_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    mut carrier_query: Query<(&Unit, &Transform, &mut AreaDenialKit)>,
+    unit_query: Query<(&Unit, &Transform)>,
+    zone_query: Query<&AreaDenialZone>,
+    mut agent_query: Query<&mut PathfindingAgent>,
+) {
+    let balance = &balance.area_denial;
+    let mut zones: Vec<AreaDenialZone> = zone_query.iter().cloned().collect();
+    let mut thrown = false;
+
+    for (unit, transform, mut kit) in carrier_query.iter_mut() {
+        if unit.faction == Faction::Cartel || unit.health <= 0.0 || !kit.ready() {
+            continue;
+        }
+        let from = transform.translation.truncate();
+        let nearest = unit_query
+            .iter()
+            .filter(|(other, _)| {
+                other.health > 0.0 && diplomacy.is_hostile(&unit.faction, &other.faction)
+            })
+            .map(|(_, other_transform)| other_transform.translation.truncate())
+            .filter(|position| {
+                position.distance(from) <= unit.vision
+                    && !zones.iter().any(|zone| zone.blocks_sight(from, *position))
+            })
+            .min_by(|a, b| a.distance(from).total_cmp(&b.distance(from)));
+        let Some(site) = nearest.and_then(|enemy| ai_site(kit.kind, from, enemy, balance)) else {
+            continue;
+        };
+
+        let zone = AreaDenialZone::new(kit.kind, unit.faction.clone(), site, balance);
+        if zone.kind == AreaDenialKind::TearGas
+            && diplomacy.is_hostile(&zone.faction, &Faction::Cartel)
+        {
+            play_tactical_sound_at_position(
+                "ability",
+                &tr("radio.tear_gas_incoming"),
+                site.extend(0.0),
+            );
+        }
+        spawn_area_denial_zone(&mut commands, zone.clone());
+        zones.push(zone);
+        kit.cooldown = kit.kind.cooldown(balance);
+        thrown = true;
+    }
+
+    if thrown {
+        replan_routes(&mut agent_query);
+    }
+}
+
+// Kits come back into use and zones burn out. While a zone lasts, whoever
+// it affects takes damage standing in it, and AI units too shaken to stand
+// it head for the nearest way out, leaving their own AI to pick up again
+// once they are clear.
+#[allow(clippy::type_complexity)]
+pub fn area_denial_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    mut kit_query: Query<&mut AreaDenialKit>,
+    mut zone_query: Query<(Entity, &mut AreaDenialZone)>,
+    mut unit_query: Query<(
+        Entity,
+        &Unit,
+        &Transform,
+        Option<&TacticalState>,
+        Option<&mut Movement>,
+        Option<&mut PathfindingAgent>,
+        Has<Captive>,
+        Has<Fleeing>,
+    )>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let balance = &balance.area_denial;
+    let dt = time.delta_seconds();
+    for mut kit in kit_query.iter_mut() {
+        if !kit.ready() {
+            kit.cooldown -= dt;
+        }
+    }
+
+    let mut zones = Vec::new();
+    for (entity, mut zone) in zone_query.iter_mut() {
+        zone.remaining -= dt;
+        if zone.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        } else {
+            zones.push(zone.clone());
+        }
+    }
+
+    // Helicopters fly over it, and nobody gasses a restrained prisoner
+    for (entity, unit, transform, tactical_state, movement, agent, is_captive, fleeing) in
+        unit_query.iter_mut()
+    {
+        if unit.health <= 0.0 || is_captive || unit.unit_type == UnitType::Helicopter {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let hurting: Vec<&AreaDenialZone> = zones
+            .iter()
+            .filter(|zone| zone.contains(position) && zone.affects(&unit.faction, &diplomacy))
+            .collect();
+        for zone in &hurting {
+            damage_events.send(DamageEvent {
+                attacker: None,
+                target: entity,
+                amount: zone.damage_per_second(balance) * dt,
+                weapon: None,
+            });
+        }
+
+        let Some(zone) = hurting.first() else {
+            if fleeing {
+                commands.entity(entity).remove::<Fleeing>();
+            }
+            continue;
+        };
+        let shaken = tactical_state.is_some_and(|state| state.morale < balance.retreat_morale);
+        if fleeing || !shaken {
+            continue;
+        }
+        if let Some(mut movement) = movement {
+            let way_out = zone.way_out(position);
+            movement.target_position = Some(way_out.extend(transform.translation.z));
+            if let Some(mut agent) = agent {
+                agent.path.clear();
+            }
+            commands.entity(entity).insert(Fleeing);
+        }
+    }
+}
+
+// Zones fade out over their last couple of seconds
+pub fn area_denial_fade_system(
+    mut zone_query: Query<(&AreaDenialZone, &mut Sprite), Changed<AreaDenialZone>>,
+) {
+    for (zone, mut sprite) in zone_query.iter_mut() {
+        let color = zone.kind.color();
+        sprite
+            .color
+            .set_a(color.a() * (zone.remaining / FADE_SECONDS).min(1.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_chokes_the_enemy_and_fire_burns_everyone() {
+        let balance = AreaDenialBalance::default();
+        let diplomacy = Diplomacy::default();
+        let gas = AreaDenialZone::new(
+            AreaDenialKind::TearGas,
+            Faction::Military,
+            Vec2::ZERO,
+            &balance,
+        );
+        assert!(gas.affects(&Faction::Cartel, &diplomacy));
+        assert!(!gas.affects(&Faction::Police, &diplomacy));
+        assert!(!gas.affects(&Faction::Civilian, &diplomacy));
+
+        let fire = AreaDenialZone::new(
+            AreaDenialKind::BurningBarricade,
+            Faction::Cartel,
+            Vec2::ZERO,
+            &balance,
+        );
+        assert!(fire.affects(&Faction::Cartel, &diplomacy));
+        assert!(fire.damage_per_second(&balance) > gas.damage_per_second(&balance));
+
+        let way_out = fire.way_out(Vec2::new(0.0, 10.0));
+        assert!(!fire.contains(way_out));
+        assert!(way_out.y > 0.0);
+    }
+
+    #[test]
+    fn test_smoke_blocks_sight_through_the_zone() {
+        let zone = AreaDenialZone::new(
+            AreaDenialKind::TearGas,
+            Faction::Military,
+            Vec2::ZERO,
+            &AreaDenialBalance::default(),
+        );
+        let west = Vec2::new(-200.0, 0.0);
+        let east = Vec2::new(200.0, 0.0);
+        assert!(zone.blocks_sight(west, east));
+        assert!(zone.blocks_sight(west, Vec2::new(10.0, 0.0)));
+        assert!(!zone.blocks_sight(west, Vec2::new(-120.0, 0.0)));
+        assert!(!zone.blocks_sight(west + Vec2::Y * 200.0, east + Vec2::Y * 200.0));
+    }
+
+    #[test]
+    fn test_ai_gasses_the_enemy_and_lights_barricades_between() {
+        let balance = AreaDenialBalance::default();
+        let enemy = Vec2::new(100.0, 0.0);
+        assert_eq!(
+            ai_site(AreaDenialKind::TearGas, Vec2::ZERO, enemy, &balance),
+            Some(enemy)
+        );
+        assert_eq!(
+            ai_site(
+                AreaDenialKind::TearGas,
+                Vec2::ZERO,
+                Vec2::new(balance.tear_gas_range + 1.0, 0.0),
+                &balance
+            ),
+            None
+        );
+        assert_eq!(
+            ai_site(
+                AreaDenialKind::BurningBarricade,
+                Vec2::ZERO,
+                enemy,
+                &balance
+            ),
+            Some(Vec2::new(50.0, 0.0))
+        );
+        let far = placement(Vec2::ZERO, Vec2::new(1000.0, 0.0), balance.barricade_range);
+        assert!((far.x - balance.barricade_range).abs() < 1e-3);
+    }
+}
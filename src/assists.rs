@@ -0,0 +1,324 @@
+use crate::app_state::GameSet;
+use crate::campaign::Campaign;
+use crate::components::{Faction, GamePhase, Unit, UnitType};
+use crate::config::{AssistBalance, BalanceConfig, ScoringBalance, UnitCatalog};
+use crate::daily::{daily_running, DailyChallengeState};
+use crate::localization::{tr, tr_args};
+use crate::resources::{GameAssets, GameState};
+use crate::save::save_system::{CampaignProgress, MissionId};
+use crate::spawners::spawn_unit;
+use crate::utils::{iso_to_world, play_tactical_sound};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+
+// ==================== MISSION ASSISTS PLUGIN ====================
+
+// After losing the same mission a couple of times, the defeat screen offers
+// assists for the retry: an extra squad at Ovidio's side, an AI director that
+// escalates more slowly, and a shorter hold on timed missions (those are won
+// by outlasting the clock, so easing the time limit means less of it). They
+// are optional, stay picked until the mission is won, and each one lowers the
+// score multiplier; the score breakdown lists the ones a win was played with.
+
+const SQUAD_RING: f32 = 70.0; // The extra squad stands this far from Ovidio
+
+pub struct MissionAssistsPlugin;
+
+impl Plugin for MissionAssistsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MissionAssists>().add_systems(
+            Update,
+            (defeat_tracking_system, assist_setup_system).in_set(GameSet::Session),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MissionAssist {
+    ExtraSquad,       // More fighters guarding Ovidio from the start
+    SlowerEscalation, // The AI director ramps up more gently
+    ShorterHold,      // Less of the time limit to outlast
+}
+
+impl MissionAssist {
+    pub const ALL: [MissionAssist; 3] = [
+        MissionAssist::ExtraSquad,
+        MissionAssist::SlowerEscalation,
+        MissionAssist::ShorterHold,
+    ];
+
+    // Toggled on the defeat screen; keys no other screen uses
+    pub fn key(&self) -> KeyCode {
+        match self {
+            MissionAssist::ExtraSquad => KeyCode::B,
+            MissionAssist::SlowerEscalation => KeyCode::C,
+            MissionAssist::ShorterHold => KeyCode::F,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        tr(&format!("assist.{:?}", self))
+    }
+
+    pub fn multiplier(&self, balance: &ScoringBalance) -> f32 {
+        match self {
+            MissionAssist::ExtraSquad => balance.extra_squad_multiplier,
+            MissionAssist::SlowerEscalation => balance.slower_escalation_multiplier,
+            MissionAssist::ShorterHold => balance.shorter_hold_multiplier,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct MissionAssists {
+    pub mission: Option<MissionId>, // The mission the picked assists are for
+    pub selected: Vec<MissionAssist>,
+    pub running: Vec<MissionAssist>, // In effect for the attempt being played
+    applied: bool,
+}
+
+impl MissionAssists {
+    pub fn is_selected(&self, mission: &MissionId, assist: MissionAssist) -> bool {
+        self.mission.as_ref() == Some(mission) && self.selected.contains(&assist)
+    }
+
+    // Picking an assist for another mission starts a fresh selection
+    pub fn toggle(&mut self, mission: &MissionId, assist: MissionAssist) {
+        if self.mission.as_ref() != Some(mission) {
+            self.mission = Some(mission.clone());
+            self.selected.clear();
+        }
+        if let Some(index) = self.selected.iter().position(|a| *a == assist) {
+            self.selected.remove(index);
+        } else {
+            self.selected.push(assist);
+        }
+    }
+
+    pub fn is_running(&self, assist: MissionAssist) -> bool {
+        self.running.contains(&assist)
+    }
+
+    // Assists stack multiplicatively, like challenge modifiers
+    pub fn multiplier(&self, balance: &ScoringBalance) -> f32 {
+        self.running
+            .iter()
+            .map(|assist| assist.multiplier(balance))
+            .product()
+    }
+
+    // Picked assists are dropped once their mission is won
+    pub fn mission_won(&mut self, mission: &MissionId) {
+        if self.mission.as_ref() == Some(mission) {
+            self.mission = None;
+            self.selected.clear();
+        }
+    }
+}
+
+/// Whether an assist is in effect; false where no assists exist.
+pub fn assist_running(assists: Option<&MissionAssists>, assist: MissionAssist) -> bool {
+    assists.is_some_and(|assists| assists.is_running(assist))
+}
+
+pub fn assists_offered(
+    progress: &CampaignProgress,
+    mission: &MissionId,
+    balance: &AssistBalance,
+) -> bool {
+    progress.defeats(mission) >= balance.offer_after_defeats.max(1)
+}
+
+/// Defeat screen lines: a title, then each assist with its key, score
+/// multiplier and whether it is picked.
+pub fn assist_option_lines(
+    assists: &MissionAssists,
+    progress: &CampaignProgress,
+    balance: &ScoringBalance,
+) -> Vec<(String, bool)> {
+    let mission = &progress.current_mission;
+    let mut lines = vec![(
+        tr_args(
+            "result.assists_title",
+            &[("count", &progress.defeats(mission))],
+        ),
+        false,
+    )];
+    for assist in MissionAssist::ALL {
+        let selected = assists.is_selected(mission, assist);
+        let state = if selected {
+            tr("briefing.challenge_on")
+        } else {
+            tr("briefing.challenge_off")
+        };
+        lines.push((
+            tr_args(
+                "result.assist_option",
+                &[
+                    ("key", &format!("{:?}", assist.key())),
+                    ("name", &assist.label()),
+                    ("multiplier", &format!("{:.2}", assist.multiplier(balance))),
+                    ("state", &state),
+                ],
+            ),
+            selected,
+        ));
+    }
+    lines
+}
+
+// ==================== SYSTEMS ====================
+
+// A defeat counts once, as the defeat screen comes up; daily challenges are
+// kept out of the campaign
+pub fn defeat_tracking_system(
+    game_state: Res<GameState>,
+    mut campaign: ResMut<Campaign>,
+    daily: Option<Res<DailyChallengeState>>,
+    mut last_phase: Local<Option<GamePhase>>,
+) {
+    if last_phase.as_ref() == Some(&game_state.game_phase) {
+        return;
+    }
+    *last_phase = Some(game_state.game_phase.clone());
+
+    if game_state.game_phase == GamePhase::Defeat && !daily_running(daily.as_deref()) {
+        let mission = campaign.progress.current_mission.clone();
+        campaign.progress.record_defeat(&mission);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assist_setup_system(
+    mut commands: Commands,
+    mut assists: ResMut<MissionAssists>,
+    mut campaign: ResMut<Campaign>,
+    game_state: Res<GameState>,
+    balance: Res<BalanceConfig>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    daily: Option<Res<DailyChallengeState>>,
+    units: Query<(&Unit, &Transform)>,
+) {
+    match game_state.game_phase {
+        GamePhase::Preparation if !assists.applied => {}
+        GamePhase::Preparation
+        | GamePhase::InitialRaid
+        | GamePhase::BlockConvoy
+        | GamePhase::ApplyPressure
+        | GamePhase::HoldTheLine
+        | GamePhase::Defeat
+        | GamePhase::GameOver => return,
+        GamePhase::Victory => {
+            // Still running for the score; the pick is done with
+            let mission = campaign.progress.current_mission.clone();
+            assists.mission_won(&mission);
+            return;
+        }
+        _ => {
+            // Between attempts nothing is in effect
+            if assists.applied || !assists.running.is_empty() {
+                assists.applied = false;
+                assists.running.clear();
+                campaign.time_limit_scale = 1.0;
+            }
+            return;
+        }
+    }
+    assists.applied = true;
+
+    let mission = campaign.progress.current_mission.clone();
+    if daily_running(daily.as_deref()) || assists.mission.as_ref() != Some(&mission) {
+        return;
+    }
+    assists.running = assists.selected.clone();
+    if assists.running.is_empty() {
+        return;
+    }
+
+    let tuning = &balance.assists;
+    if assists.is_running(MissionAssist::ShorterHold) {
+        campaign.time_limit_scale = tuning.hold_scale.clamp(0.1, 1.0);
+    }
+    if assists.is_running(MissionAssist::ExtraSquad) {
+        let center = units
+            .iter()
+            .find(|(unit, _)| unit.unit_type == UnitType::Ovidio && unit.health > 0.0)
+            .map_or(Vec2::ZERO, |(_, transform)| {
+                transform.translation.truncate()
+            });
+        let count = tuning.extra_squad.len();
+        for (index, unit_type) in tuning.extra_squad.iter().enumerate() {
+            let angle = index as f32 / count as f32 * TAU;
+            let position = center + Vec2::from_angle(angle) * SQUAD_RING;
+            spawn_unit(
+                &mut commands,
+                unit_type.clone(),
+                Faction::Cartel,
+                iso_to_world(position.extend(0.0)),
+                &game_assets,
+                &catalog,
+            );
+        }
+    }
+
+    let names: Vec<String> = assists.running.iter().map(MissionAssist::label).collect();
+    play_tactical_sound(
+        "radio",
+        &tr_args("radio.assists_applied", &[("assists", &names.join(", "))]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assists_are_offered_after_repeated_defeats() {
+        let balance = AssistBalance::default();
+        let mut progress = CampaignProgress::default();
+        let mission = MissionId::InitialRaid;
+
+        progress.record_defeat(&mission);
+        assert!(!assists_offered(&progress, &mission, &balance));
+        progress.record_defeat(&mission);
+        assert!(assists_offered(&progress, &mission, &balance));
+
+        // A win wipes the slate for that mission
+        progress.complete_mission(mission.clone(), 200.0, 1000);
+        assert!(!assists_offered(&progress, &mission, &balance));
+    }
+
+    #[test]
+    fn test_picked_assists_belong_to_one_mission() {
+        let mut assists = MissionAssists::default();
+        assists.toggle(&MissionId::InitialRaid, MissionAssist::ExtraSquad);
+        assists.toggle(&MissionId::InitialRaid, MissionAssist::ShorterHold);
+        assert!(assists.is_selected(&MissionId::InitialRaid, MissionAssist::ExtraSquad));
+        assert!(!assists.is_selected(&MissionId::UrbanWarfare, MissionAssist::ExtraSquad));
+
+        assists.toggle(&MissionId::InitialRaid, MissionAssist::ExtraSquad);
+        assert_eq!(assists.selected, vec![MissionAssist::ShorterHold]);
+
+        assists.toggle(&MissionId::UrbanWarfare, MissionAssist::SlowerEscalation);
+        assert_eq!(assists.selected, vec![MissionAssist::SlowerEscalation]);
+        assists.mission_won(&MissionId::UrbanWarfare);
+        assert!(assists.selected.is_empty());
+    }
+
+    #[test]
+    fn test_running_assists_lower_the_multiplier() {
+        let balance = ScoringBalance::default();
+        let mut assists = MissionAssists::default();
+        assert_eq!(assists.multiplier(&balance), 1.0);
+
+        assists.running = MissionAssist::ALL.to_vec();
+        let expected: f32 = MissionAssist::ALL
+            .iter()
+            .map(|assist| assist.multiplier(&balance))
+            .product();
+        assert!((assists.multiplier(&balance) - expected).abs() < 1e-6);
+        assert!(assists.multiplier(&balance) < 1.0);
+    }
+}
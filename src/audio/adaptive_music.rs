@@ -0,0 +1,245 @@
+use crate::audio::{AudioBus, AudioManager};
+use crate::campaign::Campaign;
+use crate::components::*;
+use crate::resources::*;
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::{Audio, AudioControl, AudioInstance, AudioTween, PlaybackState};
+use std::collections::HashMap;
+
+// ==================== ADAPTIVE MUSIC ====================
+
+// Missions are scored with four looped stems that start together and stay in
+// sync; only their volumes move. Intensity blends the AI director's level, how
+// much fighting is near the camera and the political pressure on the government.
+
+const COMBAT_RADIUS: f32 = 600.0; // Fights within this distance of the camera drive the score
+const COMBAT_DENSITY_FULL: f32 = 12.0; // Engaged units for a full combat reading
+const STEM_FADE_RATE: f32 = 0.35; // Gain per second while crossfading between layers
+const PHASE_FADE_RATE: f32 = 0.8; // Fade-out speed when a mission ends
+const PHASE_SWELL: f32 = 0.3; // Extra intensity when the mission moves to a new phase
+const PHASE_SWELL_DECAY: f32 = 0.05; // Per second
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MusicStem {
+    Ambient,
+    Tension,
+    Combat,
+    Climax,
+}
+
+impl MusicStem {
+    pub const ALL: [MusicStem; 4] = [
+        MusicStem::Ambient,
+        MusicStem::Tension,
+        MusicStem::Combat,
+        MusicStem::Climax,
+    ];
+
+    pub fn asset_path(&self) -> &'static str {
+        match self {
+            MusicStem::Ambient => "audio/music/stems/ambient.ogg",
+            MusicStem::Tension => "audio/music/stems/tension.ogg",
+            MusicStem::Combat => "audio/music/stems/combat.ogg",
+            MusicStem::Climax => "audio/music/stems/climax.ogg",
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct AdaptiveMusic {
+    pub intensity: f32,
+    gains: HashMap<MusicStem, f32>,
+    instances: HashMap<MusicStem, Handle<AudioInstance>>,
+    stinger: Option<Handle<AudioInstance>>,
+    phase_swell: f32,
+}
+
+impl AdaptiveMusic {
+    // background_music_system holds the victory/defeat theme until the stinger is done
+    pub fn stinger_playing(&self, audio_instances: &Assets<AudioInstance>) -> bool {
+        self.stinger
+            .as_ref()
+            .and_then(|handle| audio_instances.get(handle))
+            .is_some_and(|instance| instance.state() != PlaybackState::Stopped)
+    }
+}
+
+pub fn is_mission_phase(phase: &GamePhase) -> bool {
+    matches!(
+        phase,
+        GamePhase::Preparation
+            | GamePhase::InitialRaid
+            | GamePhase::BlockConvoy
+            | GamePhase::ApplyPressure
+            | GamePhase::HoldTheLine
+    )
+}
+
+pub fn music_intensity(
+    director_intensity: f32,
+    engaged_units: usize,
+    political_pressure: f32,
+) -> f32 {
+    let director = (director_intensity / 2.5).clamp(0.0, 1.0);
+    let combat = (engaged_units as f32 / COMBAT_DENSITY_FULL).clamp(0.0, 1.0);
+    let pressure = political_pressure.clamp(0.0, 1.0);
+    (director * 0.35 + combat * 0.45 + pressure * 0.2).clamp(0.0, 1.0)
+}
+
+// Target gain per stem. Ambient carries the quiet stretches and thins out in
+// heavy fighting; each higher layer fades in over its own intensity band.
+pub fn stem_target(stem: MusicStem, intensity: f32) -> f32 {
+    let band = |start: f32, end: f32| ((intensity - start) / (end - start)).clamp(0.0, 1.0);
+    match stem {
+        MusicStem::Ambient => 1.0 - 0.6 * band(0.5, 0.9),
+        MusicStem::Tension => band(0.15, 0.4),
+        MusicStem::Combat => band(0.4, 0.7),
+        MusicStem::Climax => band(0.75, 0.95),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn adaptive_music_system(
+    audio_manager: Res<AudioManager>,
+    audio: Res<Audio>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    mut music: ResMut<AdaptiveMusic>,
+    game_state: Res<GameState>,
+    ai_director: Res<AiDirector>,
+    campaign: Res<Campaign>,
+    unit_query: Query<(&Unit, &Transform)>,
+    camera_query: Query<&Transform, With<IsometricCamera>>,
+    time: Res<Time>,
+    mut last_phase: Local<Option<GamePhase>>,
+) {
+    let phase = game_state.game_phase.clone();
+    let in_mission = is_mission_phase(&phase);
+    let music_volume = audio_manager.bus_volume(AudioBus::Music);
+
+    if last_phase.as_ref() != Some(&phase) {
+        let was_in_mission = last_phase.as_ref().is_some_and(is_mission_phase);
+        if was_in_mission && in_mission {
+            music.phase_swell = PHASE_SWELL;
+        }
+
+        // Stinger over the fading score when the mission resolves
+        let stinger = match phase {
+            GamePhase::Victory => Some("victory"),
+            GamePhase::Defeat => Some("defeat"),
+            _ => None,
+        };
+        if let Some(handle) = stinger
+            .filter(|_| was_in_mission)
+            .and_then(|name| audio_manager.ui_sounds.get(name))
+        {
+            music.stinger = Some(
+                audio
+                    .play(handle.clone())
+                    .with_volume(music_volume as f64)
+                    .handle(),
+            );
+        }
+
+        *last_phase = Some(phase.clone());
+    }
+
+    // Start every stem silent so they share a timeline, then fade in
+    if in_mission && music.instances.is_empty() {
+        for stem in MusicStem::ALL {
+            if let Some(handle) = audio_manager.music_stems.get(&stem) {
+                let instance = audio
+                    .play(handle.clone())
+                    .with_volume(0.0)
+                    .looped()
+                    .handle();
+                music.instances.insert(stem, instance);
+                music.gains.insert(stem, 0.0);
+            }
+        }
+    }
+
+    if music.instances.is_empty() {
+        return;
+    }
+
+    if in_mission {
+        let listener = camera_query
+            .get_single()
+            .map(|transform| transform.translation.truncate())
+            .unwrap_or(Vec2::ZERO);
+        let engaged_units = unit_query
+            .iter()
+            .filter(|(unit, transform)| {
+                unit.health > 0.0
+                    && unit.target.is_some()
+                    && transform.translation.truncate().distance(listener) <= COMBAT_RADIUS
+            })
+            .count();
+
+        music.phase_swell = (music.phase_swell - PHASE_SWELL_DECAY * time.delta_seconds()).max(0.0);
+        music.intensity = (music_intensity(
+            ai_director.intensity_level,
+            engaged_units,
+            campaign.political_pressure.total_pressure,
+        ) + music.phase_swell)
+            .min(1.0);
+    }
+
+    let fade_rate = if in_mission {
+        STEM_FADE_RATE
+    } else {
+        PHASE_FADE_RATE
+    };
+    let max_step = fade_rate * time.delta_seconds();
+    let intensity = music.intensity;
+    let music = &mut *music;
+
+    for (stem, instance_handle) in music.instances.iter() {
+        let target = if in_mission {
+            stem_target(*stem, intensity)
+        } else {
+            0.0
+        };
+        let gain = music.gains.entry(*stem).or_insert(0.0);
+        *gain += (target - *gain).clamp(-max_step, max_step);
+
+        if let Some(instance) = audio_instances.get_mut(instance_handle) {
+            instance.set_volume((*gain * music_volume) as f64, AudioTween::default());
+        }
+    }
+
+    // Score fully faded after the mission - release the stems
+    if !in_mission && music.gains.values().all(|gain| *gain <= 0.0) {
+        for (_, instance_handle) in music.instances.drain() {
+            if let Some(instance) = audio_instances.get_mut(&instance_handle) {
+                instance.stop(AudioTween::default());
+            }
+        }
+        music.gains.clear();
+        music.intensity = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_music_intensity_is_bounded() {
+        assert_eq!(music_intensity(0.0, 0, 0.0), 0.0);
+        assert!((music_intensity(10.0, 100, 5.0) - 1.0).abs() < 1e-5);
+        assert!(music_intensity(1.0, 6, 0.3) > music_intensity(1.0, 0, 0.3));
+    }
+
+    #[test]
+    fn test_stems_layer_in_with_intensity() {
+        // Calm: ambient only
+        assert_eq!(stem_target(MusicStem::Ambient, 0.0), 1.0);
+        assert_eq!(stem_target(MusicStem::Combat, 0.0), 0.0);
+
+        // Full battle: combat and climax up, ambient pulled back
+        assert_eq!(stem_target(MusicStem::Combat, 1.0), 1.0);
+        assert_eq!(stem_target(MusicStem::Climax, 1.0), 1.0);
+        assert!(stem_target(MusicStem::Ambient, 1.0) < 0.5);
+    }
+}
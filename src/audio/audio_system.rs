@@ -1,11 +1,28 @@
+use crate::audio::{AdaptiveMusic, MusicStem};
 use crate::components::*;
+use crate::profile::ProfileManager;
 use crate::resources::*;
+use crate::utils::{drain_tactical_sounds, ObstacleGrid};
 use bevy::log::info;
 use bevy::prelude::*;
-use bevy_kira_audio::prelude::{Audio, AudioControl};
+use bevy_kira_audio::prelude::{Audio, AudioControl, AudioInstance, AudioTween, PlaybackState};
 use bevy_kira_audio::AudioSource as KiraAudioSource;
+use rand::{thread_rng, Rng};
 use std::collections::HashMap;
 
+const SPATIAL_AUDIO_RANGE: f32 = 800.0; // Roughly one screen width at default zoom
+const MAX_TACTICAL_SOUNDS_PER_FRAME: usize = 8; // Large firefights would otherwise stack dozens of shots
+
+// Urban acoustics
+const OCCLUSION_PER_CELL: f32 = 0.3; // Each blocked grid cell between source and camera
+const MAX_OCCLUSION: f32 = 0.8;
+const OPEN_AIR_CUTOFF: f32 = 20_000.0; // Hz, nothing filtered
+const DISTANT_CUTOFF: f32 = 3_000.0; // Hz at the edge of the audible range
+const OCCLUDED_CUTOFF: f32 = 600.0; // Hz behind fully occluding cover
+const MAX_REVERB_SEND: f32 = 0.6; // Send level when boxed in on every side
+const MIN_REVERB_SEND: f32 = 0.1; // Below this open streets stay dry
+const REVERB_REFLECTIONS: [(f32, f32); 2] = [(0.08, 0.5), (0.17, 0.25)]; // Delay (s), level of the send
+
 // ==================== AUDIO SYSTEM COMPONENTS ====================
 
 #[derive(Resource)]
@@ -15,13 +32,45 @@ pub struct AudioManager {
     pub ambient_sounds: HashMap<String, Handle<KiraAudioSource>>,
     pub radio_sounds: HashMap<String, Handle<KiraAudioSource>>,
     pub background_music: HashMap<String, Handle<KiraAudioSource>>,
+    pub music_stems: HashMap<MusicStem, Handle<KiraAudioSource>>,
     pub master_volume: f32,
     pub sfx_volume: f32,
     pub music_volume: f32,
     pub radio_volume: f32,
+    pub ambience_volume: f32,
     pub spatial_audio_enabled: bool,
 }
 
+// Volume buses; each category's volume is scaled by the master volume
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioBus {
+    Sfx,
+    Radio,
+    Music,
+    Ambience,
+}
+
+impl AudioManager {
+    pub fn bus_volume(&self, bus: AudioBus) -> f32 {
+        let bus_volume = match bus {
+            AudioBus::Sfx => self.sfx_volume,
+            AudioBus::Radio => self.radio_volume,
+            AudioBus::Music => self.music_volume,
+            AudioBus::Ambience => self.ambience_volume,
+        };
+        self.master_volume * bus_volume
+    }
+
+    // Looks a sample up by name across the effect banks
+    pub fn sound(&self, sound_name: &str) -> Option<&Handle<KiraAudioSource>> {
+        self.combat_sounds
+            .get(sound_name)
+            .or_else(|| self.ui_sounds.get(sound_name))
+            .or_else(|| self.ambient_sounds.get(sound_name))
+            .or_else(|| self.radio_sounds.get(sound_name))
+    }
+}
+
 impl Default for AudioManager {
     fn default() -> Self {
         Self {
@@ -30,10 +79,12 @@ impl Default for AudioManager {
             ambient_sounds: HashMap::new(),
             radio_sounds: HashMap::new(),
             background_music: HashMap::new(),
+            music_stems: HashMap::new(),
             master_volume: 0.7,
             sfx_volume: 0.8,
             music_volume: 0.6,
             radio_volume: 0.9,
+            ambience_volume: 0.5,
             spatial_audio_enabled: true,
         }
     }
@@ -45,6 +96,18 @@ pub struct AudioSource3D {
     pub range: f32,
     pub volume: f32,
     pub is_playing: bool,
+    pub instance: Handle<AudioInstance>,
+    pub acoustics: Acoustics,
+}
+
+// A delayed copy of a sound bounced off the walls of a narrow street
+#[derive(Component)]
+pub struct ReverbReflection {
+    pub sound: Handle<KiraAudioSource>,
+    pub position: Vec3,
+    pub volume: f32,
+    pub playback_rate: f64,
+    pub delay: Timer,
 }
 
 #[derive(Component)]
@@ -53,6 +116,7 @@ pub struct BackgroundMusicPlayer {
     pub fade_timer: Timer,
     pub is_fading: bool,
     pub target_volume: f32,
+    pub instance: Option<Handle<AudioInstance>>,
 }
 
 #[derive(Component)]
@@ -207,7 +271,15 @@ pub fn setup_audio_system(mut commands: Commands, asset_server: Res<AssetServer>
         asset_server.load("audio/music/defeat_theme.ogg"),
     );
 
+    // Load adaptive mission score stems
+    for stem in MusicStem::ALL {
+        audio_manager
+            .music_stems
+            .insert(stem, asset_server.load(stem.asset_path()));
+    }
+
     commands.insert_resource(audio_manager);
+    commands.insert_resource(AdaptiveMusic::default());
 
     // Spawn background music player
     commands.spawn(BackgroundMusicPlayer {
@@ -215,6 +287,7 @@ pub fn setup_audio_system(mut commands: Commands, asset_server: Res<AssetServer>
         fade_timer: Timer::from_seconds(2.0, TimerMode::Once),
         is_fading: false,
         target_volume: 0.6,
+        instance: None,
     });
 
     // Spawn radio chatter player
@@ -229,76 +302,153 @@ pub fn setup_audio_system(mut commands: Commands, asset_server: Res<AssetServer>
 
 // ==================== ENHANCED AUDIO FUNCTIONS ====================
 
+// Volume and stereo panning for a sound at `source` heard from `listener`.
+// Only the map plane counts; the camera's z is a render detail.
+pub fn spatial_mix(listener: Vec3, source: Vec3, range: f32) -> (f32, f64) {
+    let offset = (source - listener).truncate();
+    let attenuation = 1.0 - (offset.length() / range).clamp(0.0, 1.0);
+    let panning = 0.5 + 0.5 * (offset.x / range).clamp(-1.0, 1.0);
+    (attenuation, panning as f64)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Acoustics {
+    pub occlusion: f32,      // 0 = clear line of sight
+    pub lowpass_cutoff: f32, // Hz
+    pub reverb_send: f32,
+}
+
+impl Acoustics {
+    // bevy_kira_audio has no per-instance filters, so the cutoff is applied
+    // as the loudness the filtered-out highs would have carried
+    pub fn filter_gain(&self) -> f32 {
+        (self.lowpass_cutoff / OPEN_AIR_CUTOFF)
+            .clamp(0.0, 1.0)
+            .powf(0.2)
+    }
+}
+
+// Occlusion from cover between source and camera, a distance low-pass and a
+// reverb send that grows the more boxed in the source is
+pub fn acoustics(grid: &ObstacleGrid, listener: Vec3, source: Vec3, range: f32) -> Acoustics {
+    let (listener, source) = (listener.truncate(), source.truncate());
+    let distance = (listener.distance(source) / range).clamp(0.0, 1.0);
+    let occlusion = (grid.blocked_cells_between(source, listener) as f32 * OCCLUSION_PER_CELL)
+        .min(MAX_OCCLUSION);
+
+    let open_cutoff = OPEN_AIR_CUTOFF + (DISTANT_CUTOFF - OPEN_AIR_CUTOFF) * distance;
+    let lowpass_cutoff = open_cutoff + (OCCLUDED_CUTOFF - open_cutoff) * occlusion / MAX_OCCLUSION;
+
+    Acoustics {
+        occlusion,
+        lowpass_cutoff,
+        reverb_send: grid.enclosure(source) * MAX_REVERB_SEND,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_spatial_instance(
+    commands: &mut Commands,
+    audio: &Audio,
+    obstacle_grid: &ObstacleGrid,
+    handle: &Handle<KiraAudioSource>,
+    position: Vec3,
+    listener: Vec3,
+    volume: f32,
+    playback_rate: f64,
+) -> Acoustics {
+    let (attenuation, panning) = spatial_mix(listener, position, SPATIAL_AUDIO_RANGE);
+    let acoustics = acoustics(obstacle_grid, listener, position, SPATIAL_AUDIO_RANGE);
+    let instance = audio
+        .play(handle.clone())
+        .with_volume((volume * attenuation * acoustics.filter_gain()) as f64)
+        .with_panning(panning)
+        .with_playback_rate(playback_rate)
+        .handle();
+
+    // spatial_audio_system keeps the mix in step with the camera while it plays
+    commands.spawn((
+        AudioSource3D {
+            position,
+            range: SPATIAL_AUDIO_RANGE,
+            volume,
+            is_playing: true,
+            instance,
+            acoustics,
+        },
+        SpatialBundle::from_transform(Transform::from_translation(position)),
+    ));
+    acoustics
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn play_spatial_sound(
     commands: &mut Commands,
     audio_manager: &AudioManager,
     audio: &Audio,
-    sound_type: &str,
+    obstacle_grid: &ObstacleGrid,
+    bus: AudioBus,
     sound_name: &str,
     position: Vec3,
+    listener: Vec3,
     volume: f32,
+    playback_rate: f64,
 ) {
-    let sound_handle = match sound_type {
-        "combat" => audio_manager.combat_sounds.get(sound_name),
-        "ui" => audio_manager.ui_sounds.get(sound_name),
-        "ambient" => audio_manager.ambient_sounds.get(sound_name),
-        "radio" => audio_manager.radio_sounds.get(sound_name),
-        _ => None,
+    let Some(handle) = audio_manager.sound(sound_name) else {
+        // Fallback to console audio for missing files
+        play_console_fallback("combat", sound_name);
+        return;
     };
 
-    if let Some(handle) = sound_handle {
-        let final_volume = volume * audio_manager.master_volume * audio_manager.sfx_volume;
-
-        if audio_manager.spatial_audio_enabled {
-            // Create spatial audio source
-            commands.spawn((
-                AudioSource3D {
-                    position,
-                    range: 500.0,
-                    volume: final_volume,
-                    is_playing: true,
-                },
-                SpatialBundle::from_transform(Transform::from_translation(position)),
-            ));
-
-            audio.play(handle.clone()).with_volume(final_volume as f64);
-        } else {
-            // Play as regular 2D audio
-            audio.play(handle.clone()).with_volume(final_volume as f64);
-        }
+    let final_volume = volume * audio_manager.bus_volume(bus);
+    let acoustics = spawn_spatial_instance(
+        commands,
+        audio,
+        obstacle_grid,
+        handle,
+        position,
+        listener,
+        final_volume,
+        playback_rate,
+    );
 
-        // Console fallback for debugging
-        let icon = match sound_type {
-            "combat" => "🔫",
-            "ui" => "🔊",
-            "ambient" => "🌆",
-            "radio" => "📻",
-            _ => "🔊",
-        };
-        info!(
-            "{} [{}] Playing: {} at {:.1}, {:.1}",
-            icon,
-            sound_type.to_uppercase(),
-            sound_name,
-            position.x,
-            position.y
-        );
-    } else {
-        // Fallback to console audio for missing files
-        play_console_fallback(sound_type, sound_name);
+    // Narrow streets throw the sound back as a couple of early reflections
+    if acoustics.reverb_send >= MIN_REVERB_SEND {
+        for (delay, level) in REVERB_REFLECTIONS {
+            commands.spawn(ReverbReflection {
+                sound: handle.clone(),
+                position,
+                volume: final_volume * acoustics.reverb_send * level,
+                playback_rate: playback_rate * 0.97,
+                delay: Timer::from_seconds(delay, TimerMode::Once),
+            });
+        }
     }
 }
 
-pub fn play_ui_sound(audio_manager: &AudioManager, audio: &Audio, sound_name: &str) {
-    if let Some(handle) = audio_manager.ui_sounds.get(sound_name) {
-        let volume = audio_manager.master_volume * audio_manager.sfx_volume;
-        audio.play(handle.clone()).with_volume(volume as f64);
-        info!("🔊 [UI] Playing: {}", sound_name);
+pub fn play_bus_sound(
+    audio_manager: &AudioManager,
+    audio: &Audio,
+    bus: AudioBus,
+    sound_name: &str,
+    volume: f32,
+    playback_rate: f64,
+) {
+    if let Some(handle) = audio_manager.sound(sound_name) {
+        let final_volume = volume * audio_manager.bus_volume(bus);
+        audio
+            .play(handle.clone())
+            .with_volume(final_volume as f64)
+            .with_playback_rate(playback_rate);
     } else {
         play_console_fallback("ui", sound_name);
     }
 }
 
+pub fn play_ui_sound(audio_manager: &AudioManager, audio: &Audio, sound_name: &str) {
+    play_bus_sound(audio_manager, audio, AudioBus::Sfx, sound_name, 1.0, 1.0);
+}
+
 pub fn queue_radio_message(
     radio_player_query: &mut Query<&mut RadioChatterPlayer>,
     message: &str,
@@ -341,39 +491,119 @@ pub fn background_music_system(
     mut music_player_query: Query<&mut BackgroundMusicPlayer>,
     audio_manager: Res<AudioManager>,
     audio: Res<Audio>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    adaptive_music: Res<AdaptiveMusic>,
     game_state: Res<GameState>,
     time: Res<Time>,
 ) {
     if let Ok(mut music_player) = music_player_query.get_single_mut() {
         music_player.fade_timer.tick(time.delta());
+        let volume = audio_manager.bus_volume(AudioBus::Music);
 
         // Determine what music should be playing based on game state
         let desired_track = match game_state.game_phase {
-            GamePhase::MainMenu => "menu_theme",
+            GamePhase::MainMenu | GamePhase::CampaignMap => "menu_theme",
             GamePhase::MissionBriefing => "tension_theme",
+            // Missions are scored by adaptive_music_system
             GamePhase::Preparation
             | GamePhase::InitialRaid
             | GamePhase::BlockConvoy
             | GamePhase::ApplyPressure
-            | GamePhase::HoldTheLine => "battle_theme",
+            | GamePhase::HoldTheLine => {
+                if let Some(previous) = music_player.instance.take() {
+                    if let Some(instance) = audio_instances.get_mut(&previous) {
+                        instance.stop(AudioTween::linear(std::time::Duration::from_secs(2)));
+                    }
+                }
+                music_player.current_track = None;
+                return;
+            }
             GamePhase::Victory => "victory_theme",
             GamePhase::Defeat => "defeat_theme",
             _ => "tension_theme",
         };
 
+        // Let the victory/defeat stinger finish before the theme comes in
+        if adaptive_music.stinger_playing(&audio_instances) {
+            return;
+        }
+
         // Change music if needed
         if music_player.current_track.as_deref() != Some(desired_track) {
             if let Some(handle) = audio_manager.background_music.get(desired_track) {
-                let volume = audio_manager.master_volume * audio_manager.music_volume;
-                audio
-                    .play(handle.clone())
-                    .with_volume(volume as f64)
-                    .looped();
-
+                if let Some(previous) = music_player.instance.take() {
+                    if let Some(instance) = audio_instances.get_mut(&previous) {
+                        instance.stop(AudioTween::default());
+                    }
+                }
+
+                music_player.instance = Some(
+                    audio
+                        .play(handle.clone())
+                        .with_volume(volume as f64)
+                        .looped()
+                        .handle(),
+                );
                 music_player.current_track = Some(desired_track.to_string());
                 info!("🎵 [MUSIC] Now playing: {}", desired_track);
             }
+        } else if audio_manager.is_changed() {
+            // Music bus or master volume moved while the track keeps playing
+            if let Some(instance) = music_player
+                .instance
+                .as_ref()
+                .and_then(|handle| audio_instances.get_mut(handle))
+            {
+                instance.set_volume(volume as f64, AudioTween::default());
+            }
+        }
+    }
+}
+
+// Looped city ambience under missions, silent in menus
+pub fn ambience_system(
+    audio_manager: Res<AudioManager>,
+    audio: Res<Audio>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    game_state: Res<GameState>,
+    mut ambience: Local<Option<Handle<AudioInstance>>>,
+) {
+    let in_mission = matches!(
+        game_state.game_phase,
+        GamePhase::Preparation
+            | GamePhase::InitialRaid
+            | GamePhase::BlockConvoy
+            | GamePhase::ApplyPressure
+            | GamePhase::HoldTheLine
+    );
+    let volume = audio_manager.bus_volume(AudioBus::Ambience) as f64;
+
+    match (in_mission, ambience.as_ref()) {
+        (true, None) => {
+            if let Some(handle) = audio_manager.ambient_sounds.get("city_ambience") {
+                *ambience = Some(
+                    audio
+                        .play(handle.clone())
+                        .with_volume(volume)
+                        .looped()
+                        .handle(),
+                );
+            }
         }
+        (true, Some(handle)) if audio_manager.is_changed() => {
+            if let Some(instance) = audio_instances.get_mut(handle) {
+                instance.set_volume(volume, AudioTween::default());
+            }
+        }
+        (false, Some(_)) => {
+            if let Some(instance) = ambience
+                .take()
+                .and_then(|handle| audio_instances.get_mut(&handle))
+            {
+                instance.stop(AudioTween::default());
+            }
+        }
+        _ => {}
     }
 }
 
@@ -393,7 +623,7 @@ pub fn radio_chatter_system(
 
             // Play radio static first
             if let Some(static_handle) = audio_manager.radio_sounds.get("radio_static") {
-                let volume = audio_manager.master_volume * audio_manager.radio_volume * 0.3;
+                let volume = audio_manager.bus_volume(AudioBus::Radio) * 0.3;
                 audio.play(static_handle.clone()).with_volume(volume as f64);
             }
 
@@ -410,98 +640,257 @@ pub fn radio_chatter_system(
 }
 
 pub fn spatial_audio_system(
-    mut audio_3d_query: Query<(&mut AudioSource3D, &Transform)>,
-    camera_query: Query<&Transform, (With<Camera>, Without<AudioSource3D>)>,
-    _time: Res<Time>,
+    mut commands: Commands,
+    mut audio_3d_query: Query<(Entity, &mut AudioSource3D)>,
+    camera_query: Query<&Transform, (With<IsometricCamera>, Without<AudioSource3D>)>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    obstacle_grid: Res<ObstacleGrid>,
 ) {
-    if let Ok(camera_transform) = camera_query.get_single() {
-        let listener_pos = camera_transform.translation;
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let listener_pos = camera_transform.translation;
 
-        for (mut audio_source, source_transform) in audio_3d_query.iter_mut() {
-            let distance = listener_pos.distance(source_transform.translation);
+    for (entity, mut audio_source) in audio_3d_query.iter_mut() {
+        let Some(instance) = audio_instances.get_mut(&audio_source.instance) else {
+            continue; // Not started by the backend yet
+        };
+        if instance.state() == PlaybackState::Stopped {
+            commands.entity(entity).despawn();
+            continue;
+        }
 
-            // Calculate volume based on distance
-            let distance_factor = 1.0 - (distance / audio_source.range).clamp(0.0, 1.0);
-            let _spatial_volume = audio_source.volume * distance_factor;
+        // Calculate volume, panning and muffling based on where the camera is now
+        let (attenuation, panning) =
+            spatial_mix(listener_pos, audio_source.position, audio_source.range);
+        audio_source.acoustics = acoustics(
+            &obstacle_grid,
+            listener_pos,
+            audio_source.position,
+            audio_source.range,
+        );
+        instance.set_volume(
+            (audio_source.volume * attenuation * audio_source.acoustics.filter_gain()) as f64,
+            AudioTween::default(),
+        );
+        instance.set_panning(panning, AudioTween::default());
+        audio_source.is_playing = attenuation > 0.0;
+    }
+}
 
-            // Update audio source properties based on distance
-            if distance > audio_source.range {
-                audio_source.is_playing = false;
-            } else if !audio_source.is_playing && distance <= audio_source.range {
-                audio_source.is_playing = true;
-            }
+// Plays reverb reflections once their delay has elapsed
+pub fn reverb_reflection_system(
+    mut commands: Commands,
+    audio: Res<Audio>,
+    obstacle_grid: Res<ObstacleGrid>,
+    mut reflection_query: Query<(Entity, &mut ReverbReflection)>,
+    camera_query: Query<&Transform, With<IsometricCamera>>,
+    time: Res<Time>,
+) {
+    let listener = camera_query
+        .get_single()
+        .map(|transform| transform.translation)
+        .unwrap_or(Vec3::ZERO);
+
+    for (entity, mut reflection) in reflection_query.iter_mut() {
+        if !reflection.delay.tick(time.delta()).finished() {
+            continue;
         }
+
+        spawn_spatial_instance(
+            &mut commands,
+            &audio,
+            &obstacle_grid,
+            &reflection.sound,
+            reflection.position,
+            listener,
+            reflection.volume,
+            reflection.playback_rate,
+        );
+        commands.entity(entity).despawn();
     }
 }
 
-// ==================== ENHANCED TACTICAL SOUND FUNCTION ====================
+// ==================== TACTICAL SOUND PLAYBACK ====================
 
+// Sample bank per tactical sound type; one sample is picked at random and
+// played with a little pitch and volume variation so repeats don't sound canned
+pub fn sample_bank(sound_type: &str) -> Option<(AudioBus, &'static [&'static str])> {
+    match sound_type {
+        "gunfire" => Some((
+            AudioBus::Sfx,
+            &["gunfire_pistol", "gunfire_rifle", "gunfire_machinegun"],
+        )),
+        "explosion" => Some((AudioBus::Sfx, &["explosion_small", "explosion_large"])),
+        "vehicle" => Some((AudioBus::Sfx, &["vehicle_engine", "helicopter"])),
+        "helicopter" => Some((AudioBus::Sfx, &["helicopter"])),
+        // A shell's whistle, until there's a sample of its own
+        "incoming" => Some((AudioBus::Sfx, &["ricochet"])),
+        "ability" => Some((AudioBus::Sfx, &["notification"])),
+        "movement" => Some((AudioBus::Radio, &["radio_beep"])),
+        "alarm" => Some((AudioBus::Sfx, &["warning", "distant_sirens"])),
+        _ => None,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn play_enhanced_tactical_sound(
     commands: &mut Commands,
-    audio_manager: &Res<AudioManager>,
-    audio: &Res<Audio>,
+    audio_manager: &AudioManager,
+    audio: &Audio,
     radio_player_query: &mut Query<&mut RadioChatterPlayer>,
+    obstacle_grid: &ObstacleGrid,
     sound_type: &str,
     message: &str,
     position: Option<Vec3>,
+    listener: Option<Vec3>,
 ) {
-    match sound_type {
-        "gunfire" => {
-            let sound_name = "gunfire_rifle";
-            if let Some(pos) = position {
-                play_spatial_sound(
-                    commands,
-                    audio_manager,
-                    audio,
-                    "combat",
-                    sound_name,
-                    pos,
-                    0.8,
-                );
-            } else {
-                play_ui_sound(audio_manager, audio, sound_name);
-            }
-        }
-        "explosion" => {
-            let sound_name = "explosion_small";
-            if let Some(pos) = position {
-                play_spatial_sound(
-                    commands,
-                    audio_manager,
-                    audio,
-                    "combat",
-                    sound_name,
-                    pos,
-                    1.0,
-                );
-            } else {
-                play_ui_sound(audio_manager, audio, sound_name);
-            }
-        }
-        "vehicle" => {
-            let sound_name = "vehicle_engine";
-            if let Some(pos) = position {
-                play_spatial_sound(
-                    commands,
-                    audio_manager,
-                    audio,
-                    "combat",
-                    sound_name,
-                    pos,
-                    0.7,
-                );
-            } else {
-                play_ui_sound(audio_manager, audio, sound_name);
-            }
-        }
-        "ability" => {
-            play_ui_sound(audio_manager, audio, "notification");
-        }
-        "radio" => {
-            queue_radio_message(radio_player_query, message, "radio", 5);
-        }
-        _ => {
-            play_console_fallback(sound_type, message);
+    // Radio traffic goes through the chatter queue, which plays its own static
+    if sound_type == "radio" {
+        queue_radio_message(radio_player_query, message, "radio", 5);
+        return;
+    }
+
+    let Some((bus, samples)) = sample_bank(sound_type) else {
+        return; // Console output from play_tactical_sound is all there is
+    };
+
+    let mut rng = thread_rng();
+    let sound_name = samples[rng.gen_range(0..samples.len())];
+    let volume = rng.gen_range(0.85..1.0);
+    let playback_rate = rng.gen_range(0.92..1.08);
+
+    match (position, listener) {
+        (Some(position), Some(listener)) if audio_manager.spatial_audio_enabled => {
+            play_spatial_sound(
+                commands,
+                audio_manager,
+                audio,
+                obstacle_grid,
+                bus,
+                sound_name,
+                position,
+                listener,
+                volume,
+                playback_rate,
+            );
         }
+        _ => play_bus_sound(audio_manager, audio, bus, sound_name, volume, playback_rate),
+    }
+}
+
+pub fn tactical_sound_system(
+    mut commands: Commands,
+    audio_manager: Res<AudioManager>,
+    audio: Res<Audio>,
+    mut radio_player_query: Query<&mut RadioChatterPlayer>,
+    obstacle_grid: Res<ObstacleGrid>,
+    camera_query: Query<&Transform, With<IsometricCamera>>,
+) {
+    let requests = drain_tactical_sounds();
+    let listener = camera_query
+        .get_single()
+        .ok()
+        .map(|transform| transform.translation);
+
+    // Newest requests win when a frame has more than we want to play at once
+    let skip = requests.len().saturating_sub(MAX_TACTICAL_SOUNDS_PER_FRAME);
+    for request in requests.iter().skip(skip) {
+        play_enhanced_tactical_sound(
+            &mut commands,
+            &audio_manager,
+            &audio,
+            &mut radio_player_query,
+            &obstacle_grid,
+            &request.sound_type,
+            &request.message,
+            request.position,
+            listener,
+        );
+    }
+}
+
+// Copies the active profile's volume buses into the audio manager
+pub fn apply_audio_settings_system(
+    profiles: Res<ProfileManager>,
+    mut audio_manager: ResMut<AudioManager>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    let Some(profile) = profiles.active.as_ref() else {
+        return;
+    };
+
+    let settings = &profile.settings;
+    audio_manager.master_volume = settings.master_volume;
+    audio_manager.sfx_volume = settings.sfx_volume;
+    audio_manager.radio_volume = settings.radio_volume;
+    audio_manager.music_volume = settings.music_volume;
+    audio_manager.ambience_volume = settings.ambience_volume;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spatial_mix_attenuates_and_pans() {
+        let listener = Vec3::new(0.0, 0.0, 999.9);
+
+        let (near, center) = spatial_mix(listener, Vec3::ZERO, 800.0);
+        assert_eq!(near, 1.0);
+        assert_eq!(center, 0.5);
+
+        let (half, right) = spatial_mix(listener, Vec3::new(400.0, 0.0, 0.0), 800.0);
+        assert_eq!(half, 0.5);
+        assert!(right > 0.5);
+
+        let (silent, left) = spatial_mix(listener, Vec3::new(-2000.0, 0.0, 0.0), 800.0);
+        assert_eq!(silent, 0.0);
+        assert_eq!(left, 0.0);
+    }
+
+    #[test]
+    fn test_bus_volume_scales_with_master() {
+        let manager = AudioManager {
+            master_volume: 0.5,
+            radio_volume: 0.8,
+            ..default()
+        };
+
+        assert_eq!(manager.bus_volume(AudioBus::Radio), 0.4);
+        assert!(sample_bank("gunfire")
+            .is_some_and(|(bus, samples)| { bus == AudioBus::Sfx && samples.len() > 1 }));
+        assert!(sample_bank("radio").is_none());
+    }
+
+    #[test]
+    fn test_acoustics_muffle_behind_cover_and_reverb_in_streets() {
+        let mut grid = ObstacleGrid::default();
+        let listener = Vec3::ZERO;
+
+        let open = acoustics(&grid, listener, Vec3::new(400.0, 0.0, 0.0), 800.0);
+        assert_eq!(open.occlusion, 0.0);
+        assert_eq!(open.reverb_send, 0.0);
+        assert!(open.lowpass_cutoff < OPEN_AIR_CUTOFF);
+
+        // Cover halfway between camera and shooter
+        grid.rebuild([(Vec2::new(200.0, 0.0), 50.0)].into_iter());
+        let behind = acoustics(&grid, listener, Vec3::new(400.0, 0.0, 0.0), 800.0);
+        assert!(behind.occlusion > 0.0);
+        assert!(behind.lowpass_cutoff < open.lowpass_cutoff);
+        assert!(behind.filter_gain() < open.filter_gain());
+
+        // Shooter between two walls
+        grid.rebuild(
+            [
+                (Vec2::new(-60.0, 420.0), 60.0),
+                (Vec2::new(100.0, 420.0), 60.0),
+            ]
+            .into_iter(),
+        );
+        let street = acoustics(&grid, listener, Vec3::new(20.0, 420.0, 0.0), 800.0);
+        assert!(street.reverb_send >= MIN_REVERB_SEND);
     }
 }
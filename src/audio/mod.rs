@@ -1,3 +1,5 @@
+pub mod adaptive_music;
 pub mod audio_system;
 
+pub use adaptive_music::*;
 pub use audio_system::*;
@@ -1,5 +1,6 @@
 use crate::auth::{
-    AuthError, CreateUserRequest, EmailVerification, OAuthProvider, PasswordReset, Session, User,
+    AuthError, CreateUserRequest, EmailVerification, FriendPresence, LeaderboardEntry,
+    LeaderboardRow, LobbyInvite, OAuthProvider, PasswordReset, Session, SubmitScoreRequest, User,
     UserRole,
 };
 use bcrypt::{hash, verify, DEFAULT_COST};
@@ -8,6 +9,9 @@ use chrono::{DateTime, Duration, Utc};
 use sqlx::{Pool, Sqlite};
 use uuid::Uuid;
 
+// Users count as online if they polled the social endpoints this recently
+const PRESENCE_TIMEOUT_SECONDS: i64 = 60;
+
 pub struct AuthDatabase {
     pub pool: Pool<Sqlite>,
 }
@@ -469,4 +473,217 @@ impl AuthDatabase {
 
         Ok(providers)
     }
+
+    // Social: presence and lobby invites
+    pub async fn touch_presence(&self, user_id: Uuid) -> Result<(), AuthError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_presence (user_id, last_seen) VALUES (?1, ?2)
+            ON CONFLICT(user_id) DO UPDATE SET last_seen = excluded.last_seen
+            "#,
+        )
+        .bind(user_id)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_presence(&self, user_ids: &[Uuid]) -> Result<Vec<FriendPresence>, AuthError> {
+        let online_cutoff = Utc::now() - Duration::seconds(PRESENCE_TIMEOUT_SECONDS);
+        let mut presence = Vec::with_capacity(user_ids.len());
+
+        for user_id in user_ids {
+            // Unknown ids are skipped rather than failing the whole batch
+            let Ok(user) = self.get_user_by_id(*user_id).await else {
+                continue;
+            };
+
+            let last_seen: Option<DateTime<Utc>> =
+                sqlx::query_scalar("SELECT last_seen FROM user_presence WHERE user_id = ?1")
+                    .bind(user_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+            presence.push(FriendPresence {
+                user_id: user.id,
+                username: user.username,
+                online: user.is_active && last_seen.is_some_and(|seen| seen > online_cutoff),
+                last_seen,
+            });
+        }
+
+        Ok(presence)
+    }
+
+    pub async fn create_lobby_invite(
+        &self,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+        session_id: Uuid,
+        lobby_name: &str,
+    ) -> Result<LobbyInvite, AuthError> {
+        let invite_id = Uuid::new_v4();
+        let now = Utc::now();
+        let expires_at = now + Duration::minutes(10); // Lobbies are short-lived
+
+        let invite = sqlx::query_as::<_, LobbyInvite>(
+            r#"
+            INSERT INTO lobby_invites (id, from_user_id, to_user_id, session_id, lobby_name, status, expires_at, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            RETURNING *
+            "#,
+        )
+        .bind(invite_id)
+        .bind(from_user_id)
+        .bind(to_user_id)
+        .bind(session_id)
+        .bind(lobby_name)
+        .bind("pending")
+        .bind(expires_at)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    pub async fn get_pending_invites(&self, user_id: Uuid) -> Result<Vec<LobbyInvite>, AuthError> {
+        let invites = sqlx::query_as::<_, LobbyInvite>(
+            "SELECT * FROM lobby_invites WHERE to_user_id = ?1 AND status = 'pending' AND expires_at > ?2",
+        )
+        .bind(user_id)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(invites)
+    }
+
+    pub async fn respond_to_invite(
+        &self,
+        invite_id: Uuid,
+        user_id: Uuid,
+        accept: bool,
+    ) -> Result<LobbyInvite, AuthError> {
+        let invite = sqlx::query_as::<_, LobbyInvite>(
+            r#"
+            UPDATE lobby_invites SET status = ?1
+            WHERE id = ?2 AND to_user_id = ?3 AND status = 'pending' AND expires_at > ?4
+            RETURNING *
+            "#,
+        )
+        .bind(if accept { "accepted" } else { "declined" })
+        .bind(invite_id)
+        .bind(user_id)
+        .bind(Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    // Leaderboards: each player keeps their best result per mission and difficulty
+    pub async fn submit_score(
+        &self,
+        user_id: Uuid,
+        request: &SubmitScoreRequest,
+    ) -> Result<(), AuthError> {
+        // A higher score wins, a faster time breaks a tie
+        sqlx::query(
+            r#"
+            INSERT INTO leaderboard_scores (id, user_id, mission, difficulty, score, completion_time, medal, challenges, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT(user_id, mission, difficulty) DO UPDATE SET
+                score = excluded.score,
+                completion_time = excluded.completion_time,
+                medal = excluded.medal,
+                challenges = excluded.challenges,
+                created_at = excluded.created_at
+            WHERE excluded.score > leaderboard_scores.score
+                OR (excluded.score = leaderboard_scores.score
+                    AND excluded.completion_time < leaderboard_scores.completion_time)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(&request.mission)
+        .bind(&request.difficulty)
+        .bind(request.score as i64)
+        .bind(request.completion_time as f64)
+        .bind(&request.medal)
+        .bind(request.challenges.join(","))
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_leaderboard(
+        &self,
+        mission: &str,
+        difficulty: &str,
+        limit: u32,
+    ) -> Result<Vec<LeaderboardEntry>, AuthError> {
+        let rows = sqlx::query_as::<_, LeaderboardRow>(
+            r#"
+            SELECT users.username, scores.score, scores.completion_time, scores.medal, scores.challenges
+            FROM leaderboard_scores scores
+            JOIN users ON users.id = scores.user_id
+            WHERE scores.mission = ?1 AND scores.difficulty = ?2 AND users.is_active = 1
+            ORDER BY scores.score DESC, scores.completion_time ASC
+            LIMIT ?3
+            "#,
+        )
+        .bind(mission)
+        .bind(difficulty)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| LeaderboardEntry {
+                rank: index as u32 + 1,
+                username: row.username,
+                score: row.score.max(0) as u32,
+                completion_time: row.completion_time as f32,
+                medal: row.medal,
+                challenges: row
+                    .challenges
+                    .split(',')
+                    .filter(|challenge| !challenge.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+            .collect())
+    }
+
+    // 1-based position of the player's best result on a board
+    pub async fn get_leaderboard_rank(
+        &self,
+        user_id: Uuid,
+        mission: &str,
+        difficulty: &str,
+    ) -> Result<u32, AuthError> {
+        let ahead: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM leaderboard_scores other, leaderboard_scores mine
+            WHERE mine.user_id = ?1 AND mine.mission = ?2 AND mine.difficulty = ?3
+                AND other.mission = ?2 AND other.difficulty = ?3
+                AND (other.score > mine.score
+                    OR (other.score = mine.score AND other.completion_time < mine.completion_time))
+            "#,
+        )
+        .bind(user_id)
+        .bind(mission)
+        .bind(difficulty)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(ahead as u32 + 1)
+    }
 }
@@ -46,6 +46,8 @@ async fn run_migrations(pool: &SqlitePool) -> Result<(), AuthError> {
         include_str!("../../migrations/003_create_password_resets_table.sql"),
         include_str!("../../migrations/004_create_email_verifications_table.sql"),
         include_str!("../../migrations/005_create_oauth_providers_table.sql"),
+        include_str!("../../migrations/006_create_social_tables.sql"),
+        include_str!("../../migrations/007_create_leaderboard_table.sql"),
     ];
 
     for (i, migration) in migrations.iter().enumerate() {
@@ -107,6 +109,20 @@ pub async fn cleanup_expired_data(pool: &SqlitePool) -> Result<(), AuthError> {
         expired_verifications.rows_affected()
     );
 
+    // Clean up expired lobby invites
+    let expired_invites =
+        sqlx::query("DELETE FROM lobby_invites WHERE expires_at < datetime('now')")
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                AuthError::DatabaseError(format!("Failed to clean expired lobby invites: {}", e))
+            })?;
+
+    info!(
+        "Cleaned up {} expired lobby invites",
+        expired_invites.rows_affected()
+    );
+
     Ok(())
 }
 
@@ -182,6 +198,7 @@ mod tests {
         assert!(table_names.contains(&"password_resets".to_string()));
         assert!(table_names.contains(&"email_verifications".to_string()));
         assert!(table_names.contains(&"oauth_providers".to_string()));
+        assert!(table_names.contains(&"leaderboard_scores".to_string()));
     }
 
     #[tokio::test]
@@ -26,6 +26,7 @@ pub enum AuthError {
     TokenGenerationError,
     EmailSendError,
     OAuthError(String),
+    NetworkError(String),
     InternalServerError,
 }
 
@@ -50,6 +51,7 @@ impl fmt::Display for AuthError {
             AuthError::TokenGenerationError => write!(f, "Token generation error"),
             AuthError::EmailSendError => write!(f, "Failed to send email"),
             AuthError::OAuthError(msg) => write!(f, "OAuth error: {}", msg),
+            AuthError::NetworkError(msg) => write!(f, "Network error: {}", msg),
             AuthError::InternalServerError => write!(f, "Internal server error"),
         }
     }
@@ -91,6 +93,7 @@ impl IntoResponse for AuthError {
                 "Failed to send email".to_string(),
             ),
             AuthError::OAuthError(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            AuthError::NetworkError(_) => (StatusCode::BAD_GATEWAY, self.to_string()),
             AuthError::InternalServerError => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Internal server error".to_string(),
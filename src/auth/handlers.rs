@@ -1,9 +1,10 @@
 use crate::auth::{
     discord_user_to_create_request, github_user_to_create_request, google_user_to_create_request,
     AuthDatabase, AuthError, AuthResponse, AuthService, ChangePasswordRequest, Claims,
-    ConfirmResetPasswordRequest, CreateUserRequest, CurrentUser, JwtService, LoginRequest,
-    OAuthCallback, OAuthService, OptionalCurrentUser, ResetPasswordRequest, UpdateUserRequest,
-    UserInfo,
+    ConfirmResetPasswordRequest, CreateUserRequest, CurrentUser, JwtService, LeaderboardQuery,
+    LobbyInviteInfo, LoginRequest, OAuthCallback, OAuthService, OptionalCurrentUser,
+    PresenceRequest, ResetPasswordRequest, RespondInviteRequest, SendInviteRequest,
+    SubmitScoreRequest, SubmitScoreResponse, UpdateUserRequest, UserInfo,
 };
 use axum::{
     extract::{Path, Query, State},
@@ -707,3 +708,173 @@ pub async fn discord_oauth_callback(
 
     Ok(Json(response))
 }
+
+// Look up a player by username so they can be added as a friend
+pub async fn find_user(
+    State(handlers): State<Arc<AuthHandlers>>,
+    _current_user: CurrentUser,
+    Path(username): Path<String>,
+) -> Result<impl IntoResponse, AuthError> {
+    let user = handlers.auth_db.get_user_by_username(&username).await?;
+
+    Ok(Json(json!({
+        "id": user.id,
+        "username": user.username
+    })))
+}
+
+// Online status for a batch of friends
+pub async fn get_friend_presence(
+    State(handlers): State<Arc<AuthHandlers>>,
+    current_user: CurrentUser,
+    Json(request): Json<PresenceRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+    if request.user_ids.len() > 200 {
+        return Err(AuthError::ValidationError(
+            "Too many users in presence request".to_string(),
+        ));
+    }
+
+    // Asking about friends counts as being online ourselves
+    handlers.auth_db.touch_presence(current_user.id).await?;
+
+    let presence = handlers.auth_db.get_presence(&request.user_ids).await?;
+    Ok(Json(presence))
+}
+
+// Send a lobby invite to another player
+pub async fn send_lobby_invite(
+    State(handlers): State<Arc<AuthHandlers>>,
+    current_user: CurrentUser,
+    Json(request): Json<SendInviteRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+    // Validate input
+    request.validate()?;
+
+    if request.to_user_id == current_user.id {
+        return Err(AuthError::ValidationError(
+            "Cannot invite yourself".to_string(),
+        ));
+    }
+
+    // Make sure the recipient exists and is active
+    let recipient = handlers.auth_db.get_user_by_id(request.to_user_id).await?;
+    if !recipient.is_active {
+        return Err(AuthError::AccountDeactivated);
+    }
+
+    let invite = handlers
+        .auth_db
+        .create_lobby_invite(
+            current_user.id,
+            recipient.id,
+            request.session_id,
+            &request.lobby_name,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(json!({"invite_id": invite.id}))))
+}
+
+// Pending lobby invites for the current user
+pub async fn get_lobby_invites(
+    State(handlers): State<Arc<AuthHandlers>>,
+    current_user: CurrentUser,
+) -> Result<impl IntoResponse, AuthError> {
+    handlers.auth_db.touch_presence(current_user.id).await?;
+
+    let invites = handlers
+        .auth_db
+        .get_pending_invites(current_user.id)
+        .await?;
+
+    let mut invite_infos = Vec::with_capacity(invites.len());
+    for invite in invites {
+        let from_username = handlers
+            .auth_db
+            .get_user_by_id(invite.from_user_id)
+            .await
+            .map(|user| user.username)
+            .unwrap_or_else(|_| "Unknown".to_string());
+
+        invite_infos.push(LobbyInviteInfo {
+            id: invite.id,
+            from_user_id: invite.from_user_id,
+            from_username,
+            session_id: invite.session_id,
+            lobby_name: invite.lobby_name,
+            expires_at: invite.expires_at,
+        });
+    }
+
+    Ok(Json(invite_infos))
+}
+
+// Accept or decline a lobby invite
+pub async fn respond_to_lobby_invite(
+    State(handlers): State<Arc<AuthHandlers>>,
+    current_user: CurrentUser,
+    Path(invite_id): Path<Uuid>,
+    Json(request): Json<RespondInviteRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+    let invite = handlers
+        .auth_db
+        .respond_to_invite(invite_id, current_user.id, request.accept)
+        .await
+        .map_err(|_| AuthError::ValidationError("Invite not found or expired".to_string()))?;
+
+    Ok(Json(json!({
+        "invite_id": invite.id,
+        "session_id": invite.session_id,
+        "status": invite.status
+    })))
+}
+
+const LEADERBOARD_DEFAULT_LIMIT: u32 = 10;
+const LEADERBOARD_MAX_LIMIT: u32 = 100;
+
+// Record a won mission on its leaderboard
+pub async fn submit_leaderboard_score(
+    State(handlers): State<Arc<AuthHandlers>>,
+    current_user: CurrentUser,
+    Json(request): Json<SubmitScoreRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+    // Validate input
+    request.validate()?;
+
+    if !request.completion_time.is_finite() || request.completion_time < 0.0 {
+        return Err(AuthError::ValidationError(
+            "Invalid completion time".to_string(),
+        ));
+    }
+
+    handlers
+        .auth_db
+        .submit_score(current_user.id, &request)
+        .await?;
+    let rank = handlers
+        .auth_db
+        .get_leaderboard_rank(current_user.id, &request.mission, &request.difficulty)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(SubmitScoreResponse { rank })))
+}
+
+// Top entries for a mission and difficulty
+pub async fn get_leaderboard(
+    State(handlers): State<Arc<AuthHandlers>>,
+    _current_user: CurrentUser,
+    Path((mission, difficulty)): Path<(String, String)>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<impl IntoResponse, AuthError> {
+    let limit = query
+        .limit
+        .unwrap_or(LEADERBOARD_DEFAULT_LIMIT)
+        .clamp(1, LEADERBOARD_MAX_LIMIT);
+
+    let entries = handlers
+        .auth_db
+        .get_leaderboard(&mission, &difficulty, limit)
+        .await?;
+    Ok(Json(entries))
+}
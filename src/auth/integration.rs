@@ -66,6 +66,9 @@ impl GameAuthIntegration {
         info!("  GET  /api/auth/oauth/google      - Google OAuth login");
         info!("  GET  /api/auth/oauth/github      - GitHub OAuth login");
         info!("  GET  /api/auth/oauth/discord     - Discord OAuth login");
+        info!("  POST /api/social/presence        - Friend online status");
+        info!("  GET  /api/social/invites         - Pending lobby invites");
+        info!("  POST /api/social/invites         - Send lobby invite");
         info!("  GET  /api/info/health            - Health check");
         info!("  GET  /api/info/stats             - Service statistics");
         info!("");
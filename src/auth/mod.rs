@@ -8,6 +8,7 @@ pub mod middleware;
 pub mod models;
 pub mod oauth;
 pub mod server;
+pub mod session;
 
 pub use database::*;
 pub use db_init::*;
@@ -19,3 +20,4 @@ pub use middleware::*;
 pub use models::*;
 pub use oauth::*;
 pub use server::*;
+pub use session::*;
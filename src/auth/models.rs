@@ -173,3 +173,95 @@ pub struct EmailVerification {
     pub verified: bool,
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LobbyInvite {
+    pub id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub session_id: Uuid,
+    pub lobby_name: String,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SendInviteRequest {
+    pub to_user_id: Uuid,
+    pub session_id: Uuid,
+
+    #[validate(length(min = 1, max = 64))]
+    pub lobby_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RespondInviteRequest {
+    pub accept: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PresenceRequest {
+    pub user_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FriendPresence {
+    pub user_id: Uuid,
+    pub username: String,
+    pub online: bool,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LobbyInviteInfo {
+    pub id: Uuid,
+    pub from_user_id: Uuid,
+    pub from_username: String,
+    pub session_id: Uuid,
+    pub lobby_name: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SubmitScoreRequest {
+    #[validate(length(min = 1, max = 64))]
+    pub mission: String,
+
+    #[validate(length(min = 1, max = 32))]
+    pub difficulty: String,
+
+    pub score: u32,
+    pub completion_time: f32,
+    pub medal: Option<String>,
+    pub challenges: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitScoreResponse {
+    pub rank: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct LeaderboardRow {
+    pub username: String,
+    pub score: i64,
+    pub completion_time: f64,
+    pub medal: Option<String>,
+    pub challenges: String, // Comma-separated
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub username: String,
+    pub score: u32,
+    pub completion_time: f32,
+    pub medal: Option<String>,
+    pub challenges: Vec<String>,
+}
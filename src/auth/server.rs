@@ -114,6 +114,33 @@ impl AuthServer {
                 "/api/auth/change-password",
                 post(crate::auth::handlers::change_password),
             )
+            // Social routes
+            .route(
+                "/api/social/users/:username",
+                get(crate::auth::handlers::find_user),
+            )
+            .route(
+                "/api/social/presence",
+                post(crate::auth::handlers::get_friend_presence),
+            )
+            .route(
+                "/api/social/invites",
+                get(crate::auth::handlers::get_lobby_invites)
+                    .post(crate::auth::handlers::send_lobby_invite),
+            )
+            .route(
+                "/api/social/invites/:id/respond",
+                post(crate::auth::handlers::respond_to_lobby_invite),
+            )
+            // Leaderboard routes
+            .route(
+                "/api/leaderboards/scores",
+                post(crate::auth::handlers::submit_leaderboard_score),
+            )
+            .route(
+                "/api/leaderboards/:mission/:difficulty",
+                get(crate::auth::handlers::get_leaderboard),
+            )
             .layer(middleware::from_fn_with_state(
                 auth_service.clone(),
                 auth_middleware,
@@ -0,0 +1,445 @@
+use crate::auth::{AuthError, AuthResponse, GameAuthIntegration, LoginRequest, UserInfo};
+use crate::localization::{tr, tr_args};
+use crate::multiplayer::NetworkManager;
+use crate::utils::play_tactical_sound;
+use bevy::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{Client, StatusCode};
+use tokio::sync::mpsc;
+
+// ==================== TOKEN LIFECYCLE ====================
+
+// Renew this long before the access token actually expires
+const RENEWAL_MARGIN_SECONDS: i64 = 60;
+const RENEWAL_RETRY_DELAY_SECONDS: u64 = 5;
+const MAX_RENEWAL_FAILURES: u32 = 3;
+
+#[derive(Debug, Clone)]
+pub struct TokenSet {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub access_expires_at: DateTime<Utc>,
+}
+
+impl TokenSet {
+    pub fn from_auth_response(response: &AuthResponse, issued_at: DateTime<Utc>) -> Self {
+        Self {
+            access_token: response.access_token.clone(),
+            refresh_token: response.refresh_token.clone(),
+            access_expires_at: issued_at + Duration::seconds(response.expires_in),
+        }
+    }
+
+    pub fn is_access_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.access_expires_at
+    }
+
+    pub fn needs_renewal(&self, now: DateTime<Utc>) -> bool {
+        now >= self.access_expires_at - Duration::seconds(RENEWAL_MARGIN_SECONDS)
+    }
+
+    pub fn time_until_renewal(&self, now: DateTime<Utc>) -> std::time::Duration {
+        let renew_at = self.access_expires_at - Duration::seconds(RENEWAL_MARGIN_SECONDS);
+        (renew_at - now)
+            .to_std()
+            .unwrap_or(std::time::Duration::ZERO)
+    }
+}
+
+// ==================== AUTH CLIENT ====================
+
+#[derive(Clone)]
+pub struct AuthClient {
+    http_client: Client,
+    base_url: String,
+}
+
+impl AuthClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http_client: Client::new(),
+            base_url: base_url.into(),
+        }
+    }
+
+    pub async fn login(&self, request: &LoginRequest) -> Result<AuthResponse, AuthError> {
+        let response = self
+            .http_client
+            .post(format!("{}/auth/login", self.base_url))
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| AuthError::NetworkError(format!("Login request failed: {}", e)))?;
+
+        Self::parse_auth_response(response, AuthError::InvalidCredentials).await
+    }
+
+    pub async fn refresh(&self, refresh_token: &str) -> Result<AuthResponse, AuthError> {
+        let response = self
+            .http_client
+            .post(format!("{}/auth/refresh", self.base_url))
+            .json(&serde_json::json!({ "refresh_token": refresh_token }))
+            .send()
+            .await
+            .map_err(|e| AuthError::NetworkError(format!("Token refresh failed: {}", e)))?;
+
+        Self::parse_auth_response(response, AuthError::InvalidRefreshToken).await
+    }
+
+    async fn parse_auth_response(
+        response: reqwest::Response,
+        unauthorized_error: AuthError,
+    ) -> Result<AuthResponse, AuthError> {
+        match response.status() {
+            status if status.is_success() => response
+                .json::<AuthResponse>()
+                .await
+                .map_err(|e| AuthError::NetworkError(format!("Invalid auth response: {}", e))),
+            StatusCode::UNAUTHORIZED => Err(unauthorized_error),
+            StatusCode::FORBIDDEN => Err(AuthError::AccountDeactivated),
+            status => Err(AuthError::NetworkError(format!(
+                "Auth server returned {}",
+                status
+            ))),
+        }
+    }
+}
+
+// ==================== AUTH SESSION RESOURCE ====================
+
+pub enum TokenEvent {
+    Renewed(TokenSet),
+    Revoked(String),
+}
+
+type LoginResult = Result<(AuthClient, AuthResponse), AuthError>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthMode {
+    Offline(String),
+    Online,
+}
+
+#[derive(Resource)]
+pub struct AuthSession {
+    pub mode: AuthMode,
+    pub user: Option<UserInfo>,
+    pub tokens: Option<TokenSet>,
+    token_events: Option<mpsc::UnboundedReceiver<TokenEvent>>,
+    login: Option<mpsc::UnboundedReceiver<LoginResult>>,
+}
+
+impl Default for AuthSession {
+    fn default() -> Self {
+        Self {
+            mode: AuthMode::Offline("Not signed in".to_string()),
+            user: None,
+            tokens: None,
+            token_events: None,
+            login: None,
+        }
+    }
+}
+
+impl AuthSession {
+    // The server answers on a background thread; sign_in_system starts the
+    // session with it
+    pub fn sign_in(&mut self, identifier: &str, password: &str) {
+        let client = AuthClient::new(GameAuthIntegration::new().get_api_base_url());
+        let request = LoginRequest {
+            identifier: identifier.to_string(),
+            password: password.to_string(),
+        };
+        let (sender, receiver) = mpsc::unbounded_channel();
+        spawn_login_task(client, request, sender);
+        self.login = Some(receiver);
+    }
+
+    pub fn is_signing_in(&self) -> bool {
+        self.login.is_some()
+    }
+
+    // Dropping the token channel stops the renewal task
+    pub fn sign_out(&mut self) {
+        if self.user.is_some() {
+            info!("🔐 Signed out");
+        }
+        *self = Self::default();
+    }
+
+    pub fn start(&mut self, client: AuthClient, response: AuthResponse) {
+        let tokens = TokenSet::from_auth_response(&response, Utc::now());
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        // Replacing the receiver drops any previous session's channel, which stops its task
+        spawn_token_renewal_task(client, tokens.clone(), sender);
+
+        info!("🔐 Signed in as {}", response.user.username);
+        self.mode = AuthMode::Online;
+        self.user = Some(response.user);
+        self.tokens = Some(tokens);
+        self.token_events = Some(receiver);
+    }
+
+    pub fn go_offline(&mut self, reason: &str) {
+        if self.mode != AuthMode::Online && self.tokens.is_none() {
+            return;
+        }
+
+        warn!("🔌 Switching to offline mode: {}", reason);
+        self.mode = AuthMode::Offline(reason.to_string());
+        self.tokens = None;
+        self.token_events = None;
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.mode == AuthMode::Online
+    }
+
+    pub fn access_token(&self) -> Option<&str> {
+        self.tokens
+            .as_ref()
+            .filter(|tokens| !tokens.is_access_expired(Utc::now()))
+            .map(|tokens| tokens.access_token.as_str())
+    }
+}
+
+pub struct AuthSessionPlugin;
+
+impl Plugin for AuthSessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AuthSession>()
+            .add_systems(Update, (sign_in_system, token_renewal_system).chain());
+    }
+}
+
+fn spawn_login_task(
+    client: AuthClient,
+    request: LoginRequest,
+    sender: mpsc::UnboundedSender<LoginResult>,
+) {
+    std::thread::spawn(move || {
+        let result = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime
+                .block_on(client.login(&request))
+                .map(|response| (client, response)),
+            Err(e) => Err(AuthError::NetworkError(format!(
+                "Failed to start sign-in: {}",
+                e
+            ))),
+        };
+        let _ = sender.send(result);
+    });
+}
+
+// Localization key of the radio line for a failed sign-in
+fn sign_in_error_key(error: &AuthError) -> &'static str {
+    match error {
+        AuthError::InvalidCredentials | AuthError::UserNotFound => "radio.sign_in_invalid",
+        AuthError::AccountDeactivated => "radio.sign_in_deactivated",
+        AuthError::AccountNotVerified => "radio.sign_in_unverified",
+        AuthError::NetworkError(_) => "radio.sign_in_unreachable",
+        _ => "radio.sign_in_failed",
+    }
+}
+
+pub fn sign_in_system(mut auth_session: ResMut<AuthSession>) {
+    // Polling every frame shouldn't count as a change to the session
+    let Some(receiver) = auth_session.bypass_change_detection().login.as_mut() else {
+        return;
+    };
+    let result = match receiver.try_recv() {
+        Ok(result) => result,
+        Err(mpsc::error::TryRecvError::Empty) => return,
+        Err(mpsc::error::TryRecvError::Disconnected) => {
+            Err(AuthError::NetworkError("Sign-in task stopped".to_string()))
+        }
+    };
+    auth_session.login = None;
+
+    match result {
+        Ok((client, response)) => {
+            let username = response.user.username.clone();
+            auth_session.start(client, response);
+            play_tactical_sound("radio", &tr_args("radio.signed_in", &[("name", &username)]));
+        }
+        Err(e) => {
+            warn!("🔐 Sign-in failed: {}", e);
+            play_tactical_sound("radio", &tr(sign_in_error_key(&e)));
+        }
+    }
+}
+
+fn spawn_token_renewal_task(
+    client: AuthClient,
+    tokens: TokenSet,
+    sender: mpsc::UnboundedSender<TokenEvent>,
+) {
+    // Bevy doesn't drive a tokio reactor, so renewal gets its own small runtime
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                let _ = sender.send(TokenEvent::Revoked(format!(
+                    "Failed to start token renewal: {}",
+                    e
+                )));
+                return;
+            }
+        };
+
+        runtime.block_on(async move {
+            let mut tokens = tokens;
+            let mut failures = 0;
+
+            while !sender.is_closed() {
+                tokio::time::sleep(tokens.time_until_renewal(Utc::now())).await;
+
+                match client.refresh(&tokens.refresh_token).await {
+                    Ok(response) => {
+                        failures = 0;
+                        tokens = TokenSet::from_auth_response(&response, Utc::now());
+                        if sender.send(TokenEvent::Renewed(tokens.clone())).is_err() {
+                            break;
+                        }
+                    }
+                    Err(AuthError::NetworkError(e)) => {
+                        failures += 1;
+                        if failures >= MAX_RENEWAL_FAILURES {
+                            let _ = sender.send(TokenEvent::Revoked(e));
+                            break;
+                        }
+                        tokio::time::sleep(std::time::Duration::from_secs(
+                            RENEWAL_RETRY_DELAY_SECONDS * failures as u64,
+                        ))
+                        .await;
+                    }
+                    Err(e) => {
+                        let _ = sender.send(TokenEvent::Revoked(e.to_string()));
+                        break;
+                    }
+                }
+            }
+        });
+    });
+}
+
+pub fn token_renewal_system(
+    mut auth_session: ResMut<AuthSession>,
+    network_manager: Option<ResMut<NetworkManager>>,
+) {
+    let _span = info_span!("token_renewal_system").entered();
+    let mut events = Vec::new();
+    if let Some(receiver) = auth_session.token_events.as_mut() {
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+    }
+
+    for event in events {
+        match event {
+            TokenEvent::Renewed(tokens) => {
+                info!("🔐 Access token renewed");
+                auth_session.tokens = Some(tokens);
+            }
+            TokenEvent::Revoked(reason) => {
+                auth_session.go_offline(&reason);
+                play_tactical_sound("radio", &tr("radio.signed_out_offline"));
+            }
+        }
+    }
+
+    // Catch a stalled renewal task before the server starts rejecting us
+    if auth_session
+        .tokens
+        .as_ref()
+        .is_some_and(|tokens| tokens.is_access_expired(Utc::now()))
+    {
+        auth_session.go_offline("Access token expired");
+        play_tactical_sound("radio", &tr("radio.signed_out_offline"));
+    }
+
+    if let Some(mut network_manager) = network_manager {
+        let current_token = auth_session.access_token().map(str::to_string);
+        if network_manager.auth_token != current_token {
+            network_manager.auth_token = current_token;
+            network_manager.offline_mode = !auth_session.is_online();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_tokens(expires_at: DateTime<Utc>) -> TokenSet {
+        TokenSet {
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+            access_expires_at: expires_at,
+        }
+    }
+
+    #[test]
+    fn test_renewal_window() {
+        let now = Utc::now();
+        let tokens = create_test_tokens(now + Duration::seconds(3600));
+
+        assert!(!tokens.needs_renewal(now));
+        assert!(!tokens.is_access_expired(now));
+        assert!(tokens.needs_renewal(now + Duration::seconds(3600 - RENEWAL_MARGIN_SECONDS)));
+        assert!(tokens.is_access_expired(now + Duration::seconds(3600)));
+    }
+
+    #[test]
+    fn test_time_until_renewal_never_negative() {
+        let now = Utc::now();
+        let expired = create_test_tokens(now - Duration::seconds(10));
+        assert_eq!(expired.time_until_renewal(now), std::time::Duration::ZERO);
+
+        let fresh = create_test_tokens(now + Duration::seconds(120));
+        assert_eq!(
+            fresh.time_until_renewal(now).as_secs(),
+            (120 - RENEWAL_MARGIN_SECONDS) as u64
+        );
+    }
+
+    #[test]
+    fn test_session_downgrades_to_offline() {
+        let mut session = AuthSession {
+            mode: AuthMode::Online,
+            user: None,
+            tokens: Some(create_test_tokens(Utc::now() + Duration::seconds(3600))),
+            token_events: None,
+            login: None,
+        };
+
+        assert_eq!(session.access_token(), Some("access"));
+
+        session.go_offline("Refresh token revoked");
+        assert!(!session.is_online());
+        assert!(session.access_token().is_none());
+    }
+
+    #[test]
+    fn test_sign_in_errors_tell_bad_password_from_no_server() {
+        assert_eq!(
+            sign_in_error_key(&AuthError::InvalidCredentials),
+            "radio.sign_in_invalid"
+        );
+        assert_eq!(
+            sign_in_error_key(&AuthError::NetworkError("refused".to_string())),
+            "radio.sign_in_unreachable"
+        );
+        assert_eq!(
+            sign_in_error_key(&AuthError::InternalServerError),
+            "radio.sign_in_failed"
+        );
+    }
+}
@@ -0,0 +1,441 @@
+use crate::app_state::{AppState, GameSet};
+use crate::campaign::Campaign;
+use crate::components::*;
+use crate::localization::{tr, tr_args};
+use crate::multiplayer::{MultiplayerState, NetworkManager, NetworkMessage};
+use crate::save::save_system::MissionId;
+use crate::utils::play_tactical_sound;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// ==================== BATTLE PLAN PLUGIN ====================
+
+// A tactical layer drawn over the map. Minus cycles through the drawing
+// tools - arrows, zones and lettered labels - and while one is picked, a
+// left-click drag on the map draws with it (from press to release for
+// arrows, center to edge for zones) and a right-click rubs out the nearest
+// mark. Equals hides or shows the layer, End wipes it. Each mission keeps
+// its own plan with the campaign, so a retry starts from the last one, and
+// in a multiplayer session the plan goes out to teammates, whose marks are
+// drawn alongside in their own color.
+
+const PLAN_Z: f32 = 1.5; // Above the ground, under the order markers
+const LINE_WIDTH: f32 = 4.0;
+const HEAD_LENGTH: f32 = 16.0;
+const ZONE_DASHES: usize = 24;
+const MIN_ZONE_RADIUS: f32 = 20.0;
+const ERASE_RADIUS: f32 = 40.0;
+const LABEL_SIZE: f32 = 22.0;
+const OWN_COLOR: Color = Color::rgba(1.0, 0.85, 0.2, 0.8);
+const TEAM_COLOR: Color = Color::rgba(0.4, 0.8, 1.0, 0.8);
+
+pub struct BattlePlanPlugin;
+
+impl Plugin for BattlePlanPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BattlePlan>()
+            .add_systems(OnEnter(AppState::InGame), battle_plan_load_system)
+            .add_systems(Update, plan_layer_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PlanMark {
+    Arrow { from: Vec2, to: Vec2 },
+    Zone { center: Vec2, radius: f32 },
+    Label { at: Vec2, text: String },
+}
+
+impl PlanMark {
+    /// How far `point` is from the drawn line of the mark.
+    pub fn distance_to(&self, point: Vec2) -> f32 {
+        match self {
+            PlanMark::Arrow { from, to } => {
+                let along = *to - *from;
+                let t = ((point - *from).dot(along) / along.length_squared().max(f32::EPSILON))
+                    .clamp(0.0, 1.0);
+                point.distance(*from + along * t)
+            }
+            PlanMark::Zone { center, radius } => (point.distance(*center) - radius).abs(),
+            PlanMark::Label { at, .. } => point.distance(*at),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlanTool {
+    #[default]
+    Off,
+    Arrow,
+    Zone,
+    Label,
+}
+
+impl PlanTool {
+    pub fn next(self) -> Self {
+        match self {
+            PlanTool::Off => PlanTool::Arrow,
+            PlanTool::Arrow => PlanTool::Zone,
+            PlanTool::Zone => PlanTool::Label,
+            PlanTool::Label => PlanTool::Off,
+        }
+    }
+
+    fn name_key(self) -> &'static str {
+        match self {
+            PlanTool::Off => "plan.tool_off",
+            PlanTool::Arrow => "plan.tool_arrow",
+            PlanTool::Zone => "plan.tool_zone",
+            PlanTool::Label => "plan.tool_label",
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct BattlePlan {
+    pub mission: Option<MissionId>,
+    pub marks: Vec<PlanMark>, // This player's own
+    pub team: HashMap<Uuid, (MissionId, Vec<PlanMark>)>,
+    pub tool: PlanTool,
+    pub hidden: bool,
+    drag_start: Option<Vec2>,
+}
+
+impl BattlePlan {
+    /// The next label's letter: A to Z, then round again.
+    pub fn next_label(&self) -> String {
+        let labels = self
+            .marks
+            .iter()
+            .filter(|mark| matches!(mark, PlanMark::Label { .. }))
+            .count();
+        char::from(b'A' + (labels % 26) as u8).to_string()
+    }
+
+    /// Rubs out this player's mark nearest `point`, if one is within `radius`.
+    pub fn erase_near(&mut self, point: Vec2, radius: f32) -> bool {
+        let nearest = self
+            .marks
+            .iter()
+            .enumerate()
+            .map(|(i, mark)| (i, mark.distance_to(point)))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i);
+        if let Some(i) = nearest {
+            self.marks.remove(i);
+        }
+        nearest.is_some()
+    }
+
+    /// Takes in a teammate's plan; an empty one takes theirs off the map.
+    pub fn receive(&mut self, from: Uuid, mission: MissionId, marks: Vec<PlanMark>) {
+        if marks.is_empty() {
+            self.team.remove(&from);
+        } else {
+            self.team.insert(from, (mission, marks));
+        }
+    }
+
+    /// Teammates' marks for the mission being played.
+    pub fn team_marks(&self) -> impl Iterator<Item = &PlanMark> {
+        self.team
+            .values()
+            .filter(|(mission, _)| Some(mission) == self.mission.as_ref())
+            .flat_map(|(_, marks)| marks.iter())
+    }
+}
+
+// Keeps left and right clicks on the map for drawing while a tool is picked
+pub fn plan_tool_inactive(plan: Option<Res<BattlePlan>>) -> bool {
+    plan.map_or(true, |plan| plan.tool == PlanTool::Off)
+}
+
+// The plan is kept with the campaign and sent to teammates whenever it changes
+fn publish(
+    plan: &BattlePlan,
+    campaign: &mut Campaign,
+    multiplayer_state: Option<&MultiplayerState>,
+    network_manager: Option<&NetworkManager>,
+) {
+    let Some(mission) = plan.mission.clone() else {
+        return;
+    };
+    if plan.marks.is_empty() {
+        campaign.progress.battle_plans.remove(&mission);
+    } else {
+        campaign
+            .progress
+            .battle_plans
+            .insert(mission.clone(), plan.marks.clone());
+    }
+
+    let Some(network_manager) = network_manager.filter(|_| {
+        multiplayer_state.is_some_and(|multiplayer_state| multiplayer_state.game_started)
+    }) else {
+        return;
+    };
+    if let Some(sender) = &network_manager.message_sender {
+        let _ = sender.send(NetworkMessage::BattlePlan {
+            player_id: network_manager.player_id,
+            mission,
+            marks: plan.marks.clone(),
+        });
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn battle_plan_load_system(mut plan: ResMut<BattlePlan>, campaign: Res<Campaign>) {
+    let mission = campaign.progress.current_mission.clone();
+    plan.marks = campaign
+        .progress
+        .battle_plans
+        .get(&mission)
+        .cloned()
+        .unwrap_or_default();
+    plan.mission = Some(mission);
+    plan.tool = PlanTool::Off;
+    plan.drag_start = None;
+}
+
+// Minus picks the tool, Equals hides the layer, End wipes it; the mouse draws
+#[allow(clippy::too_many_arguments)]
+pub fn battle_plan_input_system(
+    input: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    mut plan: ResMut<BattlePlan>,
+    mut campaign: ResMut<Campaign>,
+    multiplayer_state: Option<Res<MultiplayerState>>,
+    network_manager: Option<Res<NetworkManager>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+) {
+    if input.just_pressed(KeyCode::Minus) {
+        plan.tool = plan.tool.next();
+        plan.drag_start = None;
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.plan_tool", &[("tool", &tr(plan.tool.name_key()))]),
+        );
+    }
+    if input.just_pressed(KeyCode::Equals) {
+        plan.hidden = !plan.hidden;
+        let message = if plan.hidden {
+            "radio.plan_hidden"
+        } else {
+            "radio.plan_shown"
+        };
+        play_tactical_sound("radio", &tr(message));
+    }
+
+    let mut changed = false;
+    if input.just_pressed(KeyCode::End) && !plan.marks.is_empty() {
+        plan.marks.clear();
+        changed = true;
+        play_tactical_sound("radio", &tr("radio.plan_cleared"));
+    }
+
+    let cursor = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        });
+    if let Some(cursor) = cursor.filter(|_| plan.tool != PlanTool::Off) {
+        if mouse.just_pressed(MouseButton::Left) {
+            plan.drag_start = Some(cursor);
+        }
+        if mouse.just_released(MouseButton::Left) {
+            if let Some(start) = plan.drag_start.take() {
+                let mark = match plan.tool {
+                    PlanTool::Arrow if start.distance(cursor) > HEAD_LENGTH => {
+                        Some(PlanMark::Arrow {
+                            from: start,
+                            to: cursor,
+                        })
+                    }
+                    PlanTool::Zone => Some(PlanMark::Zone {
+                        center: start,
+                        radius: start.distance(cursor).max(MIN_ZONE_RADIUS),
+                    }),
+                    PlanTool::Label => Some(PlanMark::Label {
+                        at: cursor,
+                        text: plan.next_label(),
+                    }),
+                    _ => None,
+                };
+                if let Some(mark) = mark {
+                    plan.marks.push(mark);
+                    changed = true;
+                }
+            }
+        }
+        if mouse.just_pressed(MouseButton::Right) && plan.erase_near(cursor, ERASE_RADIUS) {
+            changed = true;
+        }
+    }
+
+    if changed {
+        publish(
+            &plan,
+            &mut campaign,
+            multiplayer_state.as_deref(),
+            network_manager.as_deref(),
+        );
+    }
+}
+
+#[derive(Component)]
+pub struct PlanLayerPart;
+
+// Redrawn as a whole whenever the plan changes
+pub fn plan_layer_system(
+    mut commands: Commands,
+    plan: Res<BattlePlan>,
+    part_query: Query<Entity, With<PlanLayerPart>>,
+) {
+    if !plan.is_changed() {
+        return;
+    }
+    for entity in part_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if plan.hidden {
+        return;
+    }
+
+    let own = plan.marks.iter().map(|mark| (mark, OWN_COLOR));
+    let team = plan.team_marks().map(|mark| (mark, TEAM_COLOR));
+    for (mark, color) in own.chain(team) {
+        let parts = match mark {
+            PlanMark::Arrow { from, to } => arrow_parts(*from, *to),
+            PlanMark::Zone { center, radius } => zone_parts(*center, *radius),
+            PlanMark::Label { at, text } => {
+                commands.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(
+                            text.clone(),
+                            TextStyle {
+                                font_size: LABEL_SIZE,
+                                color,
+                                ..default()
+                            },
+                        ),
+                        transform: Transform::from_translation(at.extend(PLAN_Z)),
+                        ..default()
+                    },
+                    PlanLayerPart,
+                ));
+                continue;
+            }
+        };
+        for (size, position, rotation) in parts {
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(position.extend(PLAN_Z))
+                        .with_rotation(Quat::from_rotation_z(rotation)),
+                    ..default()
+                },
+                PlanLayerPart,
+            ));
+        }
+    }
+}
+
+// A shaft from one end to the other and two strokes for the head, as (size,
+// center, rotation) like the order markers
+fn arrow_parts(from: Vec2, to: Vec2) -> Vec<(Vec2, Vec2, f32)> {
+    let along = to - from;
+    let angle = along.y.atan2(along.x);
+    let back = -along.normalize_or_zero() * HEAD_LENGTH * 0.5;
+    let head = std::f32::consts::FRAC_PI_4 * 3.0;
+    vec![
+        (
+            Vec2::new(along.length(), LINE_WIDTH),
+            (from + to) * 0.5,
+            angle,
+        ),
+        (
+            Vec2::new(HEAD_LENGTH, LINE_WIDTH),
+            to + back.rotate(Vec2::from_angle(-std::f32::consts::FRAC_PI_4)),
+            angle + head,
+        ),
+        (
+            Vec2::new(HEAD_LENGTH, LINE_WIDTH),
+            to + back.rotate(Vec2::from_angle(std::f32::consts::FRAC_PI_4)),
+            angle - head,
+        ),
+    ]
+}
+
+fn zone_parts(center: Vec2, radius: f32) -> Vec<(Vec2, Vec2, f32)> {
+    let dash = std::f32::consts::TAU * radius / ZONE_DASHES as f32 * 0.5;
+    (0..ZONE_DASHES)
+        .map(|i| {
+            let angle = i as f32 / ZONE_DASHES as f32 * std::f32::consts::TAU;
+            (
+                Vec2::new(LINE_WIDTH, dash),
+                center + Vec2::from_angle(angle) * radius,
+                angle,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erasing_takes_the_nearest_mark_in_reach() {
+        let mut plan = BattlePlan {
+            marks: vec![
+                PlanMark::Arrow {
+                    from: Vec2::ZERO,
+                    to: Vec2::new(200.0, 0.0),
+                },
+                PlanMark::Zone {
+                    center: Vec2::new(100.0, 100.0),
+                    radius: 50.0,
+                },
+            ],
+            ..default()
+        };
+        assert_eq!(plan.next_label(), "A");
+
+        // Halfway along the arrow is on it; the zone's middle is off its edge
+        assert!(!plan.erase_near(Vec2::new(100.0, 100.0), 10.0));
+        assert!(plan.erase_near(Vec2::new(100.0, 5.0), 10.0));
+        assert!(matches!(plan.marks[..], [PlanMark::Zone { .. }]));
+    }
+
+    #[test]
+    fn test_teammates_plans_show_for_the_mission_being_played() {
+        let mut plan = BattlePlan {
+            mission: Some(MissionId::InitialRaid),
+            ..default()
+        };
+        let mark = PlanMark::Label {
+            at: Vec2::ZERO,
+            text: "A".to_string(),
+        };
+        let (first, second) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        plan.receive(first, MissionId::InitialRaid, vec![mark.clone()]);
+        plan.receive(second, MissionId::UrbanWarfare, vec![mark.clone()]);
+        assert_eq!(plan.team_marks().count(), 1);
+
+        // A wiped plan comes through empty and takes theirs away
+        plan.receive(first, MissionId::InitialRaid, Vec::new());
+        assert_eq!(plan.team_marks().count(), 0);
+    }
+}
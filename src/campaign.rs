@@ -1,5 +1,11 @@
 use crate::components::GamePhase;
-use crate::components::{Faction, Unit, UnitType};
+use crate::components::{Faction, Unit};
+use crate::config::{mod_registry, BalanceConfig, PressureWeights};
+use crate::endings::PoliticalEnding;
+use crate::environmental_systems::WeatherType;
+use crate::localization::{tr, tr_args, try_tr};
+use crate::map::CityMap;
+use crate::objectives::{objective_type, MissionObjective, ObjectiveContext};
 use crate::resources::GameState;
 use crate::save::save_system::{CampaignProgress, DifficultyLevel, MissionId};
 use bevy::log::info;
@@ -14,11 +20,12 @@ pub struct Campaign {
     pub objectives_completed: u32,
     pub current_objectives: Vec<ObjectiveStatus>,
     pub political_pressure: PoliticalPressure,
+    pub time_limit_scale: f32, // Below 1 when a shorter-hold assist is running
 }
 
 // ==================== POLITICAL PRESSURE SYSTEM ====================
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct PoliticalPressure {
     pub civilian_impact: f32, // Civilian casualties and displacement (0.0-1.0)
     pub economic_disruption: f32, // Business closures, blocked roads (0.0-1.0)
@@ -42,13 +49,13 @@ impl Default for PoliticalPressure {
 }
 
 impl PoliticalPressure {
-    pub fn update_pressure(&mut self) {
+    pub fn update_pressure(&mut self, weights: &PressureWeights) {
         // Calculate total pressure as weighted average
-        self.total_pressure = (self.civilian_impact * 0.25
-            + self.economic_disruption * 0.20
-            + self.media_attention * 0.15
-            + self.political_families * 0.25
-            + self.military_morale * 0.15)
+        self.total_pressure = (self.civilian_impact * weights.civilian_impact
+            + self.economic_disruption * weights.economic_disruption
+            + self.media_attention * weights.media_attention
+            + self.political_families * weights.political_families
+            + self.military_morale * weights.military_morale)
             .clamp(0.0, 1.0);
     }
 
@@ -125,17 +132,29 @@ impl Default for Campaign {
             objectives_completed: 0,
             current_objectives: Vec::new(),
             political_pressure: PoliticalPressure::default(),
+            time_limit_scale: 1.0,
         }
     }
 }
 
+impl Campaign {
+    // The mission's time limit as it stands for this attempt
+    pub fn time_limit(&self, config: &MissionConfig) -> Option<f32> {
+        config
+            .time_limit
+            .map(|time_limit| time_limit * self.time_limit_scale)
+    }
+}
+
 // ==================== OBJECTIVE TRACKING ====================
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ObjectiveStatus {
     pub objective: MissionObjective,
     pub completed: bool,
     pub progress: f32, // 0.0 to 1.0
+    #[serde(default)]
+    pub completed_once: bool, // So on_complete only ever runs the first time
 }
 
 #[derive(Clone, Debug)]
@@ -151,6 +170,7 @@ pub enum VictoryType {
     TimeLimit,
     EnemiesEliminated,
     TargetSurvived,
+    Capitulation(PoliticalEnding), // The government backed down, the way it did
 }
 
 #[derive(Clone, Debug)]
@@ -165,199 +185,268 @@ pub enum DefeatType {
 
 pub struct MissionConfig {
     pub id: MissionId,
-    pub name: &'static str,
-    pub description: &'static str,
+    pub name: String,
+    pub description: String,
     pub time_limit: Option<f32>,
     pub enemy_spawn_rate: f32,
     pub difficulty_modifier: f32,
+    pub start_time: Option<f32>, // Hour of day the mission opens at; None keeps the running clock
+    pub base_play: bool,         // The army builds its reinforcements at staging areas
+    pub weather: Option<WeatherType>, // Held for the whole mission; None lets it change
+    pub camera_area: Option<String>, // Neighborhood the camera is kept over
     pub objectives: Vec<MissionObjective>,
 }
 
-#[derive(Clone, Debug)]
-pub enum MissionObjective {
-    SurviveTime(f32),
-    DefendTarget(String),
-    EliminateEnemies(u32),
-    ControlArea(String),
+// Targets and areas are named in the mission data; show a translation if there is one
+pub fn place_name(name: &str) -> String {
+    try_tr(&format!("place.{}", name)).unwrap_or_else(|| name.to_string())
+}
+
+// Names and descriptions come from the language packs, e.g. mission.InitialRaid.name
+fn mission_text(mission_id: &MissionId, field: &str) -> String {
+    tr(&format!("mission.{:?}.{}", mission_id, field))
 }
 
 impl MissionConfig {
+    // Built-in definition with any mod pack overrides and translations applied
     pub fn get_mission_config(mission_id: &MissionId) -> MissionConfig {
+        let mut config = Self::base_mission_config(mission_id);
+        mod_registry().apply_mission_overrides(&mut config);
+        config
+    }
+
+    fn base_mission_config(mission_id: &MissionId) -> MissionConfig {
         match mission_id {
             MissionId::InitialRaid => MissionConfig {
                 id: mission_id.clone(),
-                name: "Initial Raid",
-                description: "Government forces attempt to capture Ovidio. Defend the safehouse at all costs.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(300.0), // 5 minutes
                 enemy_spawn_rate: 1.0,
                 difficulty_modifier: 1.0,
+                start_time: Some(15.25), // 3:15 PM
+                base_play: false,
+                weather: Some(WeatherType::Clear), // A clear afternoon, as on the day
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::DefendTarget("Ovidio".to_string()),
-                    MissionObjective::SurviveTime(300.0),
+                    MissionObjective::new("DefendTarget", "Ovidio"),
+                    MissionObjective::new("SurviveTime", 300.0),
                 ],
             },
             MissionId::UrbanWarfare => MissionConfig {
                 id: mission_id.clone(),
-                name: "Urban Warfare",
-                description: "Combat spreads through Culiacán's streets. Control key intersections.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(450.0), // 7.5 minutes
                 enemy_spawn_rate: 1.2,
                 difficulty_modifier: 1.2,
+                start_time: Some(15.5), // 3:30 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::ControlArea("Downtown".to_string()),
-                    MissionObjective::EliminateEnemies(20),
+                    MissionObjective::new("ControlArea", "Downtown"),
+                    MissionObjective::new("EliminateEnemies", 20.0),
                 ],
             },
             MissionId::GovernmentResponse => MissionConfig {
                 id: mission_id.clone(),
-                name: "Government Response",
-                description: "Military escalates response. Show them the cost of this operation.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(600.0), // 10 minutes
                 enemy_spawn_rate: 1.5,
                 difficulty_modifier: 1.4,
+                start_time: Some(18.0), // 6:00 PM
+                base_play: true,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::SurviveTime(600.0),
-                    MissionObjective::EliminateEnemies(35),
+                    MissionObjective::new("SurviveTime", 600.0),
+                    MissionObjective::new("EliminateEnemies", 35.0),
                 ],
             },
             // Phase 2 Missions
             MissionId::LasFloresiDefense => MissionConfig {
                 id: mission_id.clone(),
-                name: "Las Flores Defense",
-                description: "Establish defensive perimeters in Las Flores neighborhood while protecting civilians.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(240.0), // 4 minutes
                 enemy_spawn_rate: 1.1,
                 difficulty_modifier: 1.1,
+                start_time: Some(15.75), // 3:45 PM
+                base_play: false,
+                weather: None,
+                camera_area: Some("Las Flores".to_string()),
                 objectives: vec![
-                    MissionObjective::ControlArea("Las Flores".to_string()),
-                    MissionObjective::DefendTarget("Ovidio".to_string()),
+                    MissionObjective::new("ControlArea", "Las Flores"),
+                    MissionObjective::new("DefendTarget", "Ovidio"),
                 ],
             },
             MissionId::TierraBlancaRoadblocks => MissionConfig {
                 id: mission_id.clone(),
-                name: "Tierra Blanca Roadblocks",
-                description: "Deploy coordinated roadblocks to cut off military reinforcement routes.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(360.0), // 6 minutes
                 enemy_spawn_rate: 1.15,
                 difficulty_modifier: 1.15,
+                start_time: Some(16.0), // 4:00 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::ControlArea("Highway Access".to_string()),
-                    MissionObjective::EliminateEnemies(15),
+                    MissionObjective::new("ControlArea", "Highway Access"),
+                    MissionObjective::new("EliminateEnemies", 15.0),
                 ],
             },
 
             // Phase 3 Missions
             MissionId::CentroUrbanFight => MissionConfig {
                 id: mission_id.clone(),
-                name: "Centro Urban Battle",
-                description: "Battle for downtown Culiacán. Control government buildings and key intersections.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(480.0), // 8 minutes
                 enemy_spawn_rate: 1.3,
                 difficulty_modifier: 1.25,
+                start_time: Some(16.5), // 4:30 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::ControlArea("City Center".to_string()),
-                    MissionObjective::EliminateEnemies(25),
+                    MissionObjective::new("ControlArea", "City Center"),
+                    MissionObjective::new("EliminateEnemies", 25.0),
                 ],
             },
             MissionId::LasQuintasSiege => MissionConfig {
                 id: mission_id.clone(),
-                name: "Las Quintas Siege",
-                description: "Secure wealthy Las Quintas district to apply pressure on political families.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(420.0), // 7 minutes
                 enemy_spawn_rate: 1.25,
                 difficulty_modifier: 1.3,
+                start_time: Some(17.0), // 5:00 PM
+                base_play: true,
+                weather: None,
+                camera_area: Some("Las Quintas".to_string()),
                 objectives: vec![
-                    MissionObjective::ControlArea("Las Quintas".to_string()),
-                    MissionObjective::SurviveTime(420.0),
+                    MissionObjective::new("ControlArea", "Las Quintas"),
+                    MissionObjective::new("SurviveTime", 420.0),
                 ],
             },
             MissionId::AirportAssault => MissionConfig {
                 id: mission_id.clone(),
-                name: "Airport Control",
-                description: "Control Bachigualato Airport to secure escape routes and limit air support.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(540.0), // 9 minutes
                 enemy_spawn_rate: 1.4,
                 difficulty_modifier: 1.35,
+                start_time: Some(17.5), // 5:30 PM
+                base_play: true,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::ControlArea("Airport".to_string()),
-                    MissionObjective::EliminateEnemies(30),
+                    MissionObjective::new("ControlArea", "Airport"),
+                    MissionObjective::new("EliminateEnemies", 30.0),
                 ],
             },
 
             // Phase 4 Missions
             MissionId::GovernmentResponse => MissionConfig {
                 id: mission_id.clone(),
-                name: "Government Counter-Offensive",
-                description: "Military escalation reaches peak. Survive overwhelming government response.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(600.0), // 10 minutes
                 enemy_spawn_rate: 1.6,
                 difficulty_modifier: 1.4,
+                start_time: Some(18.0), // 6:00 PM
+                base_play: true,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::SurviveTime(600.0),
-                    MissionObjective::EliminateEnemies(40),
-                    MissionObjective::DefendTarget("Ovidio".to_string()),
+                    MissionObjective::new("SurviveTime", 600.0),
+                    MissionObjective::new("EliminateEnemies", 40.0),
+                    MissionObjective::new("DefendTarget", "Ovidio"),
                 ],
             },
             MissionId::CivilianEvacuation => MissionConfig {
                 id: mission_id.clone(),
-                name: "Civilian Protection",
-                description: "Protect civilian evacuation zones while maintaining humanitarian corridors.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(480.0), // 8 minutes
                 enemy_spawn_rate: 1.3,
                 difficulty_modifier: 1.45,
+                start_time: Some(18.5), // 6:30 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::ControlArea("Evacuation Zone".to_string()),
-                    MissionObjective::DefendTarget("Civilians".to_string()),
+                    MissionObjective::new("ControlArea", "Evacuation Zone"),
+                    MissionObjective::new("DefendTarget", "Civilians"),
                 ],
             },
             MissionId::PoliticalNegotiation => MissionConfig {
                 id: mission_id.clone(),
-                name: "Political Pressure",
-                description: "Hold positions while behind-scenes political negotiations proceed.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(720.0), // 12 minutes
                 enemy_spawn_rate: 1.2,
                 difficulty_modifier: 1.5,
+                start_time: Some(19.0), // 7:00 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::SurviveTime(720.0),
-                    MissionObjective::ControlArea("Strategic Points".to_string()),
+                    MissionObjective::new("SurviveTime", 720.0),
+                    MissionObjective::new("ControlArea", "Strategic Points"),
                 ],
             },
 
             // Phase 5 Missions
             MissionId::CeasefireNegotiation => MissionConfig {
                 id: mission_id.clone(),
-                name: "Ceasefire Management",
-                description: "Presidential ceasefire order arrives. Manage transition while maintaining advantage.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: Some(300.0), // 5 minutes
                 enemy_spawn_rate: 0.8,
                 difficulty_modifier: 1.2,
+                start_time: Some(19.5), // 7:30 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::SurviveTime(300.0),
-                    MissionObjective::DefendTarget("Ovidio".to_string()),
+                    MissionObjective::new("SurviveTime", 300.0),
+                    MissionObjective::new("DefendTarget", "Ovidio"),
                 ],
             },
             MissionId::OrderedWithdrawal => MissionConfig {
                 id: mission_id.clone(),
-                name: "Ordered Withdrawal",
-                description: "Government forces ordered to withdraw. Ensure orderly retreat without casualties.",
-                time_limit: Some(240.0), // 4 minutes
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
+                time_limit: None, // Over once the convoy is through
                 enemy_spawn_rate: 0.6,
                 difficulty_modifier: 1.1,
+                start_time: Some(20.0), // 8:00 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::ControlArea("Withdrawal Routes".to_string()),
-                    MissionObjective::DefendTarget("Ovidio".to_string()),
+                    MissionObjective::new("EscortConvoy", "Withdrawal Routes"),
+                    MissionObjective::new("DefendTarget", "Ovidio"),
                 ],
             },
             MissionId::Resolution => MissionConfig {
                 id: mission_id.clone(),
-                name: "Victory Secured",
-                description: "Final mission complete. Ovidio's freedom secured through political pressure victory.",
+                name: mission_text(mission_id, "name"),
+                description: mission_text(mission_id, "description"),
                 time_limit: None, // No time limit - victory achieved
                 enemy_spawn_rate: 0.5,
                 difficulty_modifier: 1.0,
+                start_time: Some(20.5), // 8:30 PM
+                base_play: false,
+                weather: None,
+                camera_area: None,
                 objectives: vec![
-                    MissionObjective::DefendTarget("Ovidio".to_string()),
-                    MissionObjective::SurviveTime(180.0), // 3 minutes to secure victory
+                    MissionObjective::new("DefendTarget", "Ovidio"),
+                    MissionObjective::new("SurviveTime", 180.0), // 3 minutes to secure victory
                 ],
             },
         }
@@ -370,24 +459,19 @@ pub fn campaign_system(
     mut campaign: ResMut<Campaign>,
     game_state: Res<GameState>,
     unit_query: Query<&Unit>,
+    balance: Res<BalanceConfig>,
     time: Res<Time>,
 ) {
     campaign.mission_timer += time.delta_seconds();
 
-    // Map game phases to mission progression
-    let current_mission = match game_state.game_phase {
-        GamePhase::MainMenu
-        | GamePhase::SaveMenu
-        | GamePhase::LoadMenu
-        | GamePhase::MissionBriefing => campaign.progress.current_mission.clone(),
-        GamePhase::Preparation | GamePhase::InitialRaid => MissionId::InitialRaid,
-        GamePhase::BlockConvoy => MissionId::UrbanWarfare,
-        GamePhase::ApplyPressure => MissionId::GovernmentResponse,
-        GamePhase::HoldTheLine => MissionId::Resolution,
-        GamePhase::Victory | GamePhase::Defeat | GamePhase::GameOver => return, // No mission updates when game is over
-    };
-
-    campaign.progress.current_mission = current_mission.clone();
+    // The mission is the one picked on the campaign map; battle phases only pace it
+    if matches!(
+        game_state.game_phase,
+        GamePhase::Victory | GamePhase::Defeat | GamePhase::GameOver
+    ) {
+        return; // No mission updates when game is over
+    }
+    let current_mission = campaign.progress.current_mission.clone();
 
     // Update political pressure based on current mission and events
     update_political_pressure(
@@ -395,6 +479,7 @@ pub fn campaign_system(
         &current_mission,
         &game_state,
         &unit_query,
+        &balance.pressure,
         time.delta_seconds(),
     );
 
@@ -423,21 +508,6 @@ pub fn campaign_system(
             }
         }
     }
-
-    // Check for mission completion
-    if game_state.game_phase == GamePhase::GameOver && !game_state.ovidio_captured {
-        let mission_score = calculate_mission_score(&game_state, campaign.mission_timer);
-        let current_mission = campaign.progress.current_mission.clone();
-        let timer = campaign.mission_timer;
-        campaign
-            .progress
-            .complete_mission(current_mission, timer, mission_score);
-
-        info!(
-            "✅ Mission completed! Score: {}, Time: {:.1}s",
-            mission_score, campaign.mission_timer
-        );
-    }
 }
 
 fn update_political_pressure(
@@ -445,17 +515,20 @@ fn update_political_pressure(
     mission_id: &MissionId,
     game_state: &GameState,
     unit_query: &Query<&Unit>,
+    weights: &PressureWeights,
     delta_time: f32,
 ) {
     // Count casualties for pressure calculation
     let military_dead = unit_query
         .iter()
         .filter(|u| u.faction == Faction::Military && u.health <= 0.0)
-        .count();
+        .count()
+        + game_state.cleared_dead(&Faction::Military) as usize;
     let cartel_dead = unit_query
         .iter()
         .filter(|u| u.faction == Faction::Cartel && u.health <= 0.0)
-        .count();
+        .count()
+        + game_state.cleared_dead(&Faction::Cartel) as usize;
 
     // Mission-specific pressure increases
     match mission_id {
@@ -501,15 +574,7 @@ fn update_political_pressure(
     }
 
     // Update total pressure calculation
-    pressure.update_pressure();
-}
-
-fn calculate_mission_score(game_state: &GameState, completion_time: f32) -> u32 {
-    let base_score = game_state.cartel_score;
-    let time_bonus = (600.0 - completion_time.min(600.0)) as u32; // Bonus for faster completion
-    let survival_bonus = if !game_state.ovidio_captured { 500 } else { 0 };
-
-    base_score + time_bonus + survival_bonus
+    pressure.update_pressure(weights);
 }
 
 // ==================== DIFFICULTY SYSTEM ====================
@@ -531,29 +596,19 @@ pub fn difficulty_system(campaign: Res<Campaign>, _game_state: ResMut<GameState>
 
 pub fn get_mission_briefing(mission_id: &MissionId) -> String {
     let config = MissionConfig::get_mission_config(mission_id);
-    let mut briefing = format!("🎯 Mission: {}\n\n", config.name);
-    briefing.push_str(&format!("📝 {}\n\n", config.description));
+    let mut briefing = tr_args("briefing.title", &[("name", &config.name)]);
+    briefing.push_str(&format!("\n\n📝 {}\n\n", config.description));
 
-    briefing.push_str("🎯 Objectives:\n");
+    briefing.push_str(&tr("briefing.objectives"));
+    briefing.push('\n');
     for (i, objective) in config.objectives.iter().enumerate() {
-        match objective {
-            MissionObjective::SurviveTime(time) => {
-                briefing.push_str(&format!("{}. Survive for {:.0} seconds\n", i + 1, time));
-            }
-            MissionObjective::DefendTarget(target) => {
-                briefing.push_str(&format!("{}. Protect {}\n", i + 1, target));
-            }
-            MissionObjective::EliminateEnemies(count) => {
-                briefing.push_str(&format!("{}. Eliminate {} enemy units\n", i + 1, count));
-            }
-            MissionObjective::ControlArea(area) => {
-                briefing.push_str(&format!("{}. Control {}\n", i + 1, area));
-            }
-        }
+        briefing.push_str(&format!("{}. {}\n", i + 1, objective.description()));
     }
 
     if let Some(time_limit) = config.time_limit {
-        briefing.push_str(&format!("\n⏰ Time Limit: {:.0} seconds", time_limit));
+        let seconds = format!("{:.0}", time_limit);
+        briefing.push('\n');
+        briefing.push_str(&tr_args("briefing.time_limit", &[("seconds", &seconds)]));
     }
 
     briefing
@@ -564,12 +619,19 @@ pub fn get_mission_briefing(mission_id: &MissionId) -> String {
 pub fn evaluate_mission_objectives(
     campaign: &mut Campaign,
     game_state: &GameState,
-    unit_query: &Query<&Unit>,
+    unit_query: &Query<(&Unit, &Transform)>,
+    city_map: &CityMap,
+    checkpoints: &[(Faction, Vec2)],
 ) -> MissionResult {
     let mission_config = MissionConfig::get_mission_config(&campaign.progress.current_mission);
 
     // Initialize objectives if empty
     if campaign.current_objectives.is_empty() {
+        for objective in &mission_config.objectives {
+            if objective_type(&objective.kind).is_none() {
+                warn!("Unknown objective kind {}, it can't be completed", objective.kind);
+            }
+        }
         campaign.current_objectives = mission_config
             .objectives
             .iter()
@@ -577,29 +639,34 @@ pub fn evaluate_mission_objectives(
                 objective: obj.clone(),
                 completed: false,
                 progress: 0.0,
+                completed_once: false,
             })
             .collect();
     }
 
-    // Count units by faction
-    let cartel_units = unit_query
-        .iter()
-        .filter(|u| u.faction == Faction::Cartel && u.health > 0.0)
-        .count() as u32;
-    let military_units = unit_query
-        .iter()
-        .filter(|u| u.faction == Faction::Military && u.health > 0.0)
-        .count() as u32;
-    let dead_military = unit_query
+    let units: Vec<(&Unit, Vec2)> = unit_query
         .iter()
-        .filter(|u| u.faction == Faction::Military && u.health <= 0.0)
-        .count() as u32;
-    let ovidio_alive = unit_query
+        .map(|(u, transform)| (u, transform.translation.truncate()))
+        .collect();
+    // Road checkpoints count toward holding an area alongside the fighters
+    let control_positions: Vec<(Faction, Vec2)> = units
         .iter()
-        .any(|u| u.unit_type == UnitType::Ovidio && u.health > 0.0);
+        .filter(|(u, _)| u.health > 0.0)
+        .map(|(u, position)| (u.faction.clone(), *position))
+        .chain(checkpoints.iter().cloned())
+        .collect();
+    let context = ObjectiveContext {
+        game_state,
+        city_map,
+        pressure: &campaign.political_pressure,
+        units,
+        control_positions,
+    };
+    let cartel_units = context.living(&Faction::Cartel);
+    let military_units = context.living(&Faction::Military);
 
     // Check for immediate defeat conditions
-    if !ovidio_alive {
+    if context.ovidio().is_none() {
         return MissionResult::Defeat(DefeatType::TargetLost);
     }
 
@@ -607,43 +674,35 @@ pub fn evaluate_mission_objectives(
         return MissionResult::Defeat(DefeatType::AllUnitsDead);
     }
 
+    // An objective that can no longer be met, such as a convoy shot to pieces
+    let failed = campaign.current_objectives.iter().any(|status| {
+        objective_type(&status.objective.kind)
+            .is_some_and(|kind| kind.failed(&status.objective.target, &context))
+    });
+    if failed {
+        return MissionResult::Defeat(DefeatType::ObjectiveFailed);
+    }
+
     // Check time limit expiration
-    if let Some(time_limit) = mission_config.time_limit {
+    if let Some(time_limit) = campaign.time_limit(&mission_config) {
         if game_state.mission_timer >= time_limit {
             // For timed missions, surviving the time limit is victory
             return MissionResult::Victory(VictoryType::TimeLimit);
         }
     }
 
-    // Update objective progress
+    // Update objective progress; kinds nobody registered never complete
     let mut all_completed = true;
+    let mut first_completions = Vec::new();
 
     for objective_status in &mut campaign.current_objectives {
-        match &objective_status.objective {
-            MissionObjective::SurviveTime(target_time) => {
-                objective_status.progress = (game_state.mission_timer / target_time).min(1.0);
-                objective_status.completed = objective_status.progress >= 1.0;
-            }
-            MissionObjective::DefendTarget(target_name) => {
-                // For now, this is just keeping Ovidio alive
-                if target_name == "Ovidio" {
-                    objective_status.completed = ovidio_alive;
-                    objective_status.progress = if ovidio_alive { 1.0 } else { 0.0 };
-                }
-            }
-            MissionObjective::EliminateEnemies(target_count) => {
-                objective_status.progress = (dead_military as f32 / *target_count as f32).min(1.0);
-                objective_status.completed = dead_military >= *target_count;
-            }
-            MissionObjective::ControlArea(_area_name) => {
-                // Simplified: control area by having more cartel than military units
-                let control_ratio = if military_units > 0 {
-                    cartel_units as f32 / (cartel_units + military_units) as f32
-                } else {
-                    1.0
-                };
-                objective_status.progress = control_ratio;
-                objective_status.completed = control_ratio >= 0.7; // 70% control
+        if let Some(kind) = objective_type(&objective_status.objective.kind) {
+            let target = &objective_status.objective.target;
+            objective_status.progress = kind.progress(target, &context).clamp(0.0, 1.0);
+            objective_status.completed = kind.evaluate(target, &context);
+            if objective_status.completed && !objective_status.completed_once {
+                objective_status.completed_once = true;
+                first_completions.push((kind, target.clone()));
             }
         }
 
@@ -652,6 +711,11 @@ pub fn evaluate_mission_objectives(
         }
     }
 
+    for (kind, target) in first_completions {
+        campaign.objectives_completed += 1;
+        kind.on_complete(&target, campaign);
+    }
+
     // Check for victory conditions
     if all_completed {
         return MissionResult::Victory(VictoryType::AllObjectivesComplete);
@@ -670,33 +734,7 @@ pub fn get_objective_summary(campaign: &Campaign) -> String {
 
     for (i, obj_status) in campaign.current_objectives.iter().enumerate() {
         let status_icon = if obj_status.completed { "✅" } else { "🔄" };
-        let progress_text = match &obj_status.objective {
-            MissionObjective::SurviveTime(time) => {
-                format!("Survive {:.0}s ({:.1}%)", time, obj_status.progress * 100.0)
-            }
-            MissionObjective::DefendTarget(target) => {
-                format!(
-                    "Protect {} ({})",
-                    target,
-                    if obj_status.completed {
-                        "Safe"
-                    } else {
-                        "At Risk"
-                    }
-                )
-            }
-            MissionObjective::EliminateEnemies(count) => {
-                format!(
-                    "Eliminate {} enemies ({:.1}%)",
-                    count,
-                    obj_status.progress * 100.0
-                )
-            }
-            MissionObjective::ControlArea(area) => {
-                format!("Control {} ({:.1}%)", area, obj_status.progress * 100.0)
-            }
-        };
-
+        let progress_text = obj_status.objective.progress_text(obj_status);
         summary.push_str(&format!("{}. {} {}\n", i + 1, status_icon, progress_text));
     }
 
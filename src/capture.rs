@@ -0,0 +1,366 @@
+use crate::ai::unit_ai_system;
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::coordination::advanced_tactical_ai_system;
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::systems::pathfinding_system;
+use crate::ui::UiTheme;
+use crate::utils::{play_tactical_sound, queue_camera_shake, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== CAPTURE SEQUENCE PLUGIN ====================
+
+// Losing Ovidio used to be instant. Now special forces have to reach him and
+// restrain him uncontested (slower through a safehouse door), then an escort
+// walks him out to the nearest military extraction point. Until it gets
+// there the cartel can take him back - by killing the escort or by crowding
+// it out - and the mission is only lost once he's gone.
+
+const RESTRAIN_RADIUS: f32 = 30.0;
+const RESTRAIN_SECONDS: f32 = 4.0;
+const SHELTER_BREACH_FACTOR: f32 = 2.0; // Restraining him indoors takes this much longer
+const CONTEST_RADIUS: f32 = 80.0; // Any cartel defender this close stalls the restraint
+const ESCORT_LEASH: f32 = 25.0; // The escort waits when the prisoner falls this far behind
+const EXTRACTION_RADIUS: f32 = 30.0;
+const EXTRACTION_FALLBACK_DISTANCE: f32 = 500.0; // Maps without military spawn points
+const BANNER_PULSE_SPEED: f32 = 6.0;
+
+pub struct CaptureSequencePlugin;
+
+impl Plugin for CaptureSequencePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CaptureAttempt>()
+            .add_systems(
+                FixedUpdate,
+                (restraint_system, escort_system)
+                    .chain()
+                    .after(advanced_tactical_ai_system)
+                    .after(unit_ai_system)
+                    .before(pathfinding_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, capture_banner_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CaptureAttempt {
+    pub restraint: f32,               // 0..1 while special forces hold him
+    pub escort_progress: Option<f32>, // 0..1 of the way to extraction
+    escort_distance: f32,             // Distance to extraction when he was taken
+}
+
+#[derive(Component)]
+pub struct CaptureBanner;
+
+// ==================== RESTRAINT ====================
+
+pub fn restraint_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    city_map: Res<CityMap>,
+    mut attempt: ResMut<CaptureAttempt>,
+    mut principal_query: Query<
+        (Entity, &Unit, &Transform, &mut ProtectedPrincipal),
+        Without<Captive>,
+    >,
+    unit_query: Query<(Entity, &Unit, &Transform), Without<ProtectedPrincipal>>,
+) {
+    let Some((principal_entity, _, transform, mut principal)) = principal_query
+        .iter_mut()
+        .find(|(_, unit, ..)| unit.health > 0.0)
+    else {
+        return;
+    };
+    let position = transform.translation;
+
+    let captors: Vec<(Entity, Vec3)> = unit_query
+        .iter()
+        .filter(|(_, unit, t)| {
+            unit.unit_type == UnitType::SpecialForces
+                && unit.health > 0.0
+                && t.translation.distance(position) <= RESTRAIN_RADIUS
+        })
+        .map(|(entity, _, t)| (entity, t.translation))
+        .collect();
+    let defenders = unit_query
+        .iter()
+        .filter(|(_, unit, t)| {
+            unit.faction == Faction::Cartel
+                && unit.health > 0.0
+                && t.translation.distance(position) <= CONTEST_RADIUS
+        })
+        .count();
+
+    let was_free = attempt.restraint == 0.0;
+    attempt.restraint = restraint_step(
+        attempt.restraint,
+        captors.len(),
+        defenders,
+        principal.shelter.is_some(),
+        time.delta_seconds(),
+    );
+    if was_free && attempt.restraint > 0.0 {
+        play_tactical_sound("alarm", &tr("radio.capture_started"));
+    }
+    if attempt.restraint < 1.0 {
+        return;
+    }
+
+    // Restrained: the nearest captor takes him out
+    let Some(&(escort, escort_pos)) = captors
+        .iter()
+        .min_by(|a, b| a.1.distance(position).total_cmp(&b.1.distance(position)))
+    else {
+        return;
+    };
+    let extraction = extraction_point(
+        &city_map.spawn_positions(&Faction::Military),
+        position,
+        escort_pos,
+    );
+    commands
+        .entity(principal_entity)
+        .insert(Captive { escort, extraction });
+    principal.shelter = None;
+    principal.shelter_cover = 0.0;
+    principal.relocating_to = None;
+
+    attempt.restraint = 0.0;
+    attempt.escort_progress = Some(0.0);
+    attempt.escort_distance = position.distance(extraction).max(1.0);
+    play_tactical_sound("alarm", &tr("radio.capture_taken"));
+    queue_camera_shake(&mut commands, position, 0.8);
+}
+
+/// Restraint progress after `dt`: it builds while captors have him alone and
+/// slips back twice as fast once they don't.
+pub fn restraint_step(
+    progress: f32,
+    captors: usize,
+    defenders: usize,
+    sheltered: bool,
+    dt: f32,
+) -> f32 {
+    if captors > 0 && defenders == 0 {
+        let seconds = if sheltered {
+            RESTRAIN_SECONDS * SHELTER_BREACH_FACTOR
+        } else {
+            RESTRAIN_SECONDS
+        };
+        (progress + dt / seconds).min(1.0)
+    } else {
+        (progress - 2.0 * dt / RESTRAIN_SECONDS).max(0.0)
+    }
+}
+
+/// The military spawn point nearest the prisoner, or straight back the way
+/// the escort came on maps without one.
+pub fn extraction_point(military_spawns: &[Vec2], prisoner: Vec3, escort: Vec3) -> Vec3 {
+    military_spawns
+        .iter()
+        .map(|spawn| spawn.extend(0.0))
+        .min_by(|a, b| a.distance(prisoner).total_cmp(&b.distance(prisoner)))
+        .unwrap_or_else(|| {
+            let away = (escort - prisoner).normalize_or_zero();
+            let away = if away == Vec3::ZERO {
+                Vec3::NEG_Y
+            } else {
+                away
+            };
+            prisoner + away * EXTRACTION_FALLBACK_DISTANCE
+        })
+}
+
+// ==================== ESCORT ====================
+
+pub fn escort_system(
+    mut commands: Commands,
+    mut attempt: ResMut<CaptureAttempt>,
+    mut captive_query: Query<(Entity, &Transform, &mut Movement, &mut Captive)>,
+    mut unit_query: Query<(Entity, &Unit, &Transform, &mut Movement), Without<Captive>>,
+) {
+    for (captive_entity, transform, mut movement, mut captive) in captive_query.iter_mut() {
+        let position = transform.translation;
+        let count_near = |faction: Faction| {
+            unit_query
+                .iter()
+                .filter(|(_, unit, t, _)| {
+                    unit.faction == faction
+                        && unit.health > 0.0
+                        && t.translation.distance(position) <= CONTEST_RADIUS
+                })
+                .count()
+        };
+
+        let escort_alive = unit_query
+            .get(captive.escort)
+            .is_ok_and(|(_, unit, ..)| unit.health > 0.0);
+        if !escort_alive {
+            // Anyone else close enough picks him up
+            match unit_query
+                .iter()
+                .filter(|(_, unit, t, _)| {
+                    unit.faction == Faction::Military
+                        && unit.health > 0.0
+                        && t.translation.distance(position) <= RESTRAIN_RADIUS
+                })
+                .min_by(|a, b| {
+                    a.2.translation
+                        .distance(position)
+                        .total_cmp(&b.2.translation.distance(position))
+                }) {
+                Some((entity, ..)) => captive.escort = entity,
+                None => {
+                    release_captive(&mut commands, &mut attempt, captive_entity, &mut movement);
+                    continue;
+                }
+            }
+        }
+        // Outnumbered around their prisoner, the escort lets him go
+        if count_near(Faction::Cartel) > count_near(Faction::Military) {
+            release_captive(&mut commands, &mut attempt, captive_entity, &mut movement);
+            continue;
+        }
+
+        let Ok((_, _, escort_transform, mut escort_movement)) = unit_query.get_mut(captive.escort)
+        else {
+            continue;
+        };
+        let escort_pos = escort_transform.translation;
+
+        // Out: he's gone and the mission with him
+        if escort_pos.distance(captive.extraction) <= EXTRACTION_RADIUS
+            && position.distance(escort_pos) <= ESCORT_LEASH
+        {
+            escort_movement.target_position = None;
+            attempt.escort_progress = None;
+            commands.entity(captive_entity).despawn_recursive();
+            continue;
+        }
+
+        // The escort drags him along, waiting whenever he falls behind
+        escort_movement.target_position = if position.distance(escort_pos) > ESCORT_LEASH {
+            Some(position)
+        } else {
+            Some(captive.extraction)
+        };
+        movement.target_position = Some(escort_pos);
+        attempt.escort_progress = Some(
+            (1.0 - position.distance(captive.extraction) / attempt.escort_distance).clamp(0.0, 1.0),
+        );
+    }
+}
+
+fn release_captive(
+    commands: &mut Commands,
+    attempt: &mut CaptureAttempt,
+    captive: Entity,
+    movement: &mut Movement,
+) {
+    commands.entity(captive).remove::<Captive>();
+    movement.target_position = None;
+    attempt.escort_progress = None;
+    play_tactical_sound("radio", &tr("radio.capture_rescued"));
+}
+
+// ==================== CAPTURE BANNER ====================
+
+pub fn capture_banner_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    theme: Res<UiTheme>,
+    attempt: Res<CaptureAttempt>,
+    mut banner_query: Query<(&mut Text, &mut Visibility), With<CaptureBanner>>,
+) {
+    let message = match attempt.escort_progress {
+        Some(progress) => Some(tr_args(
+            "hud.capture_escort",
+            &[("percent", &((progress * 100.0).round() as u32))],
+        )),
+        None if attempt.restraint > 0.0 => Some(tr_args(
+            "hud.capture_restraining",
+            &[("percent", &((attempt.restraint * 100.0).round() as u32))],
+        )),
+        None => None,
+    };
+
+    let Ok((mut text, mut visibility)) = banner_query.get_single_mut() else {
+        if message.is_some() {
+            commands
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(70.0),
+                        width: Val::Percent(100.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    z_index: ZIndex::Global(50),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 28.0,
+                                color: theme.colors().target,
+                                ..default()
+                            },
+                        ),
+                        CaptureBanner,
+                    ));
+                });
+        }
+        return;
+    };
+
+    let Some(message) = message else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Inherited;
+    text.sections[0].value = message;
+
+    // Pulses so it can't be missed in a firefight
+    let pulse = (time.elapsed_seconds() * BANNER_PULSE_SPEED).sin().abs();
+    text.sections[0].style.color = theme.colors().target.with_a(0.55 + 0.45 * pulse);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restraint_builds_alone_and_slips_when_contested() {
+        let dt = 1.0;
+        let progress = restraint_step(0.0, 2, 0, false, dt);
+        assert!((progress - dt / RESTRAIN_SECONDS).abs() < 1e-5);
+
+        // A safehouse door slows them down
+        let indoors = restraint_step(0.0, 2, 0, true, dt);
+        assert!(indoors < progress);
+
+        // One defender in reach and it slips back twice as fast
+        assert!(
+            (restraint_step(0.5, 2, 1, false, dt) - (0.5 - 2.0 / RESTRAIN_SECONDS)).abs() < 1e-5
+        );
+        assert_eq!(restraint_step(0.1, 0, 0, false, dt), 0.0);
+        assert_eq!(restraint_step(0.99, 1, 0, false, dt), 1.0);
+    }
+
+    #[test]
+    fn test_extraction_at_nearest_spawn_or_back_the_way_they_came() {
+        let spawns = [Vec2::new(-400.0, 0.0), Vec2::new(0.0, 300.0)];
+        assert_eq!(
+            extraction_point(&spawns, Vec3::ZERO, Vec3::X),
+            Vec3::new(0.0, 300.0, 0.0)
+        );
+
+        let fallback = extraction_point(&[], Vec3::ZERO, Vec3::new(10.0, 0.0, 0.0));
+        assert_eq!(fallback, Vec3::new(EXTRACTION_FALLBACK_DISTANCE, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,452 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::{BalanceConfig, CheckpointBalance};
+use crate::diplomacy::Diplomacy;
+use crate::economy::{charge, CartelTreasury};
+use crate::hvt::TargetPackage;
+use crate::localization::{tr, tr_args};
+use crate::map::{CityMap, TileKind};
+use crate::resources::{GameState, IntelSystem};
+use crate::upgrades::BASE_RADIO_RANGE;
+use crate::utils::{play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+// ==================== ROAD CHECKPOINT PLUGIN ====================
+
+// Either side can put a checkpoint on a road: the cartel on the road tile
+// under the cursor (I), the army wherever its soldiers stand on a road
+// closest to the cartel. A checkpoint its own side mans stops enemy traffic
+// heading through it and an unmanned one slows it, through each unit's
+// StatModifiers. Everyone passing is reported - to the cartel intel network,
+// or to the soldiers in radio range and the hunt for Ovidio - and checkpoints
+// count toward holding a ControlArea. Left unmanned with enemies on it, a
+// checkpoint falls.
+
+const CONTROL_WEIGHT: usize = 2; // A checkpoint counts as this many fighters toward area control
+const SPACING: f32 = 3.0; // Checkpoint radii between two of the same side
+const REPORT_CONFIDENCE: f32 = 0.9;
+const CHECKPOINT_SIZE: Vec2 = Vec2::new(36.0, 12.0);
+
+pub struct RoadCheckpointPlugin;
+
+impl Plugin for RoadCheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (checkpoint_traffic_system, military_checkpoint_system)
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+/// Speed an enemy keeps inside a checkpoint: none heading in through a
+/// manned one, the slowed speed otherwise.
+pub fn checkpoint_speed(manned: bool, heading_in: bool, balance: &CheckpointBalance) -> f32 {
+    if manned && heading_in {
+        0.0
+    } else {
+        balance.slow_speed
+    }
+}
+
+/// Each checkpoint as CONTROL_WEIGHT fighters of its side, to add to the
+/// units counted for area control.
+pub fn control_presence<'a>(
+    checkpoints: impl Iterator<Item = (&'a Checkpoint, &'a Transform)>,
+) -> Vec<(Faction, Vec2)> {
+    checkpoints
+        .flat_map(|(checkpoint, transform)| {
+            std::iter::repeat_n(
+                (checkpoint.faction.clone(), transform.translation.truncate()),
+                CONTROL_WEIGHT,
+            )
+        })
+        .collect()
+}
+
+/// The army's next checkpoint: the candidate road spot closest to the cartel,
+/// clear of its existing checkpoints.
+pub fn pick_checkpoint_site(
+    candidates: &[Vec2],
+    existing: &[Vec2],
+    cartel: &[Vec2],
+    spacing: f32,
+) -> Option<Vec2> {
+    let nearest_cartel = |site: &Vec2| {
+        cartel
+            .iter()
+            .map(|position| position.distance(*site))
+            .fold(f32::MAX, f32::min)
+    };
+    candidates
+        .iter()
+        .filter(|site| {
+            existing
+                .iter()
+                .all(|other| other.distance(**site) >= spacing)
+        })
+        .min_by(|a, b| {
+            nearest_cartel(a)
+                .partial_cmp(&nearest_cartel(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .copied()
+}
+
+pub fn spawn_checkpoint(
+    commands: &mut Commands,
+    faction: Faction,
+    position: Vec2,
+    balance: &CheckpointBalance,
+) {
+    let color = match faction {
+        Faction::Cartel => Color::rgb(0.8, 0.3, 0.1),
+        _ => Color::rgb(0.2, 0.5, 0.2),
+    };
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(CHECKPOINT_SIZE),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(0.5)),
+            ..default()
+        },
+        Checkpoint {
+            faction,
+            radius: balance.radius,
+            overrun: 0.0,
+            logged: Vec::new(),
+            health: balance.health,
+        },
+    ));
+}
+
+// A road tile under the position, if there is one
+fn road_tile_center(city_map: &CityMap, position: Vec2) -> Option<Vec2> {
+    let (col, row) = city_map.tile_at(position)?;
+    (city_map.kind(col, row) == TileKind::Road).then(|| city_map.tile_center(col, row))
+}
+
+// ==================== TRAFFIC ====================
+
+// Type aliases to reduce complexity
+type TrafficUnit = (
+    Entity,
+    &'static Unit,
+    &'static Transform,
+    Option<&'static Movement>,
+    Option<&'static CheckpointDelay>,
+);
+
+#[allow(clippy::too_many_arguments)]
+pub fn checkpoint_traffic_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    mut intel: Option<ResMut<IntelSystem>>,
+    mut package: Option<ResMut<TargetPackage>>,
+    mut game_state: ResMut<GameState>,
+    mut checkpoint_query: Query<(Entity, &mut Checkpoint, &Transform)>,
+    unit_query: Query<TrafficUnit>,
+    mut radio_query: Query<(&Unit, &Transform, &mut Communication)>,
+) {
+    let balance = &balance.checkpoints;
+    let mut speeds: HashMap<Entity, f32> = HashMap::new();
+
+    for (checkpoint_entity, mut checkpoint, checkpoint_transform) in checkpoint_query.iter_mut() {
+        let center = checkpoint_transform.translation.truncate();
+        let inside = |transform: &Transform| {
+            transform.translation.truncate().distance(center) <= checkpoint.radius
+        };
+        let manned = unit_query.iter().any(|(_, unit, transform, _, _)| {
+            unit.faction == checkpoint.faction && unit.health > 0.0 && inside(transform)
+        });
+        let enemies: Vec<(Entity, &Unit, &Transform, Option<&Movement>)> = unit_query
+            .iter()
+            .filter(|(_, unit, transform, _, _)| {
+                unit.health > 0.0
+                    && diplomacy.is_hostile(&unit.faction, &checkpoint.faction)
+                    && inside(transform)
+            })
+            .map(|(entity, unit, transform, movement, _)| (entity, unit, transform, movement))
+            .collect();
+
+        // Unmanned with enemies standing on it long enough, or shelled to
+        // pieces, it falls
+        if !manned && !enemies.is_empty() {
+            checkpoint.overrun += time.delta_seconds();
+        } else {
+            checkpoint.overrun = 0.0;
+        }
+        if checkpoint.overrun >= balance.overrun_seconds || checkpoint.health <= 0.0 {
+            commands.entity(checkpoint_entity).despawn_recursive();
+            let message = if checkpoint.faction == Faction::Cartel {
+                "radio.checkpoint_lost"
+            } else {
+                game_state.military_checkpoints_taken += 1;
+                "radio.military_checkpoint_taken"
+            };
+            play_tactical_sound("radio", &tr(message));
+            continue;
+        }
+
+        for (entity, _, transform, movement) in &enemies {
+            let heading_in = movement
+                .and_then(|movement| movement.target_position)
+                .is_some_and(|target| {
+                    (target - transform.translation)
+                        .truncate()
+                        .dot(center - transform.translation.truncate())
+                        > 0.0
+                });
+            let speed = checkpoint_speed(manned, heading_in, balance);
+            speeds
+                .entry(*entity)
+                .and_modify(|slowest| *slowest = slowest.min(speed))
+                .or_insert(speed);
+        }
+
+        // Report everyone once on their way through
+        checkpoint
+            .logged
+            .retain(|logged| enemies.iter().any(|(entity, ..)| entity == logged));
+        let passing: Vec<EnemyContact> = enemies
+            .iter()
+            .filter(|(entity, ..)| !checkpoint.logged.contains(entity))
+            .map(|(_, unit, transform, _)| EnemyContact {
+                position: transform.translation,
+                enemy_type: unit.unit_type.clone(),
+                confidence: REPORT_CONFIDENCE,
+                last_seen: time.elapsed_seconds(),
+            })
+            .collect();
+        checkpoint
+            .logged
+            .extend(enemies.iter().map(|(entity, ..)| *entity));
+        if passing.is_empty() {
+            continue;
+        }
+
+        match checkpoint.faction {
+            Faction::Cartel => {
+                if let Some(intel) = intel.as_deref_mut() {
+                    intel
+                        .global_intel_network
+                        .reconnaissance_data
+                        .push(ReconReport {
+                            area_scanned: checkpoint_transform.translation,
+                            scan_radius: checkpoint.radius,
+                            enemies_spotted: passing.clone(),
+                            terrain_info: TerrainIntel {
+                                cover_points: Vec::new(),
+                                choke_points: vec![checkpoint_transform.translation],
+                                elevation_advantages: Vec::new(),
+                                escape_routes: Vec::new(),
+                            },
+                            scan_time: time.elapsed_seconds(),
+                        });
+                }
+                play_tactical_sound(
+                    "radio",
+                    &tr_args("radio.checkpoint_report", &[("count", &passing.len())]),
+                );
+            }
+            Faction::Military => {
+                if let Some(ovidio) = passing
+                    .iter()
+                    .find(|contact| contact.enemy_type == UnitType::Ovidio)
+                {
+                    if let Some(package) = package.as_deref_mut() {
+                        package.record_sighting(ovidio.position, REPORT_CONFIDENCE);
+                    }
+                }
+                for (unit, transform, mut communication) in radio_query.iter_mut() {
+                    if unit.faction != Faction::Military
+                        || transform.translation.truncate().distance(center) > BASE_RADIO_RANGE
+                    {
+                        continue;
+                    }
+                    communication.known_enemies.extend(passing.iter().cloned());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Anyone who cleared every checkpoint gets their speed back
+    for (entity, _, _, _, delay) in unit_query.iter() {
+        match (speeds.get(&entity), delay) {
+            (Some(&speed), Some(delay)) if delay.speed == speed => {}
+            (Some(&speed), _) => {
+                commands.entity(entity).insert(CheckpointDelay { speed });
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<CheckpointDelay>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+// ==================== SETTING UP CHECKPOINTS ====================
+
+pub fn military_checkpoint_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    mut since_check: Local<f32>,
+    checkpoint_query: Query<(&Checkpoint, &Transform)>,
+    unit_query: Query<(&Unit, &Transform)>,
+) {
+    let balance = &balance.checkpoints;
+    *since_check += time.delta_seconds();
+    if *since_check < balance.military_interval {
+        return;
+    }
+    *since_check = 0.0;
+
+    let existing: Vec<Vec2> = checkpoint_query
+        .iter()
+        .filter(|(checkpoint, _)| checkpoint.faction == Faction::Military)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    if existing.len() >= balance.max_per_side {
+        return;
+    }
+
+    let living: Vec<_> = unit_query
+        .iter()
+        .filter(|(unit, _)| unit.health > 0.0)
+        .collect();
+    let candidates: Vec<Vec2> = living
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Military)
+        .filter_map(|(_, transform)| road_tile_center(&city_map, transform.translation.truncate()))
+        .collect();
+    let cartel: Vec<Vec2> = living
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Cartel)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+
+    if let Some(site) =
+        pick_checkpoint_site(&candidates, &existing, &cartel, balance.radius * SPACING)
+    {
+        spawn_checkpoint(&mut commands, Faction::Military, site, balance);
+        play_tactical_sound("radio", &tr("radio.military_checkpoint"));
+    }
+}
+
+// I sets up a cartel checkpoint on the road tile under the cursor
+#[allow(clippy::too_many_arguments)]
+pub fn checkpoint_order_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    checkpoint_query: Query<(&Checkpoint, &Transform)>,
+) {
+    if !input.just_pressed(KeyCode::I) {
+        return;
+    }
+    let balance = &balance.checkpoints;
+
+    let Some(site) = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+        .and_then(|world_pos| road_tile_center(&city_map, world_pos))
+    else {
+        play_tactical_sound("radio", &tr("radio.checkpoint_not_road"));
+        return;
+    };
+
+    let own: Vec<Vec2> = checkpoint_query
+        .iter()
+        .filter(|(checkpoint, _)| checkpoint.faction == Faction::Cartel)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    if own.len() >= balance.max_per_side {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.checkpoint_limit", &[("max", &balance.max_per_side)]),
+        );
+        return;
+    }
+    if own
+        .iter()
+        .any(|other| other.distance(site) < balance.radius * SPACING)
+    {
+        play_tactical_sound("radio", &tr("radio.checkpoint_too_close"));
+        return;
+    }
+    if !charge(treasury.as_deref_mut(), balance.cost) {
+        return;
+    }
+
+    spawn_checkpoint(&mut commands, Faction::Cartel, site, balance);
+    play_tactical_sound("construction", &tr("radio.checkpoint_established"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manned_checkpoints_stop_traffic_heading_in() {
+        let balance = CheckpointBalance::default();
+        assert_eq!(checkpoint_speed(true, true, &balance), 0.0);
+        // Backing out, or nobody manning it: just slowed
+        assert_eq!(checkpoint_speed(true, false, &balance), balance.slow_speed);
+        assert_eq!(checkpoint_speed(false, true, &balance), balance.slow_speed);
+
+        let checkpoint = Checkpoint {
+            faction: Faction::Military,
+            radius: balance.radius,
+            overrun: 0.0,
+            logged: Vec::new(),
+            health: balance.health,
+        };
+        let transform = Transform::from_xyz(10.0, 20.0, 0.0);
+        let presence = control_presence(std::iter::once((&checkpoint, &transform)));
+        assert_eq!(presence.len(), CONTROL_WEIGHT);
+        assert!(presence
+            .iter()
+            .all(|(faction, position)| *faction == Faction::Military
+                && *position == Vec2::new(10.0, 20.0)));
+    }
+
+    #[test]
+    fn test_army_checkpoints_go_up_nearest_the_cartel() {
+        let candidates = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(200.0, 0.0),
+            Vec2::new(400.0, 0.0),
+        ];
+        let cartel = [Vec2::new(450.0, 0.0)];
+        assert_eq!(
+            pick_checkpoint_site(&candidates, &[], &cartel, 180.0),
+            Some(Vec2::new(400.0, 0.0))
+        );
+
+        // Too close to an existing one: the next best
+        assert_eq!(
+            pick_checkpoint_site(&candidates, &[Vec2::new(380.0, 0.0)], &cartel, 180.0),
+            Some(Vec2::new(200.0, 0.0))
+        );
+        assert_eq!(pick_checkpoint_site(&[], &[], &cartel, 180.0), None);
+    }
+}
@@ -0,0 +1,319 @@
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{BalanceConfig, CohesionBalance};
+use crate::coordination::{advanced_tactical_ai_system, radio_relay_system};
+use crate::localization::{tr, tr_args};
+use crate::utils::{play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+// ==================== SQUAD COHESION PLUGIN ====================
+
+// Fighters who keep fighting side by side get better at it. Backslash forms
+// the selected cartel fighters into a squad, and every second the squad
+// spends in a firefight with everyone close together builds its cohesion.
+// The higher it gets, the faster a member who has fallen out of position
+// gets back to the others, the less each blow to morale hurts, and past a
+// point the squad shares what any one of them has spotted. Pulling someone
+// out of a squad or adding someone to it starts its cohesion over, so
+// keeping a squad together through the mission is worth something. Losing a
+// member doesn't reset it, but a squad down to one fighter is no squad.
+
+const MIN_SQUAD_SIZE: usize = 2;
+const SAME_CONTACT_RADIUS: f32 = 20.0; // As in the radio relay
+
+pub struct SquadCohesionPlugin;
+
+impl Plugin for SquadCohesionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SquadRoster>()
+            .add_systems(OnEnter(AppState::InGame), squad_reset_system)
+            .add_systems(
+                FixedUpdate,
+                squad_cohesion_system
+                    .after(radio_relay_system)
+                    .before(advanced_tactical_ai_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RosterSquad {
+    pub id: u32,
+    pub members: Vec<Entity>,
+    pub fought_together: f32, // Seconds in a firefight with everyone close
+}
+
+#[derive(Resource, Default)]
+pub struct SquadRoster {
+    next_id: u32,
+    pub squads: Vec<RosterSquad>,
+}
+
+impl SquadRoster {
+    /// Makes `members` a squad, pulling them out of any they were in. Squads
+    /// that lose someone this way start their cohesion over; forming the
+    /// same squad again changes nothing.
+    pub fn form(&mut self, members: &[Entity]) -> u32 {
+        let same = |squad: &RosterSquad| {
+            squad.members.len() == members.len()
+                && squad.members.iter().all(|member| members.contains(member))
+        };
+        if let Some(squad) = self.squads.iter().find(|squad| same(squad)) {
+            return squad.id;
+        }
+
+        for squad in &mut self.squads {
+            let before = squad.members.len();
+            squad.members.retain(|member| !members.contains(member));
+            if squad.members.len() != before {
+                squad.fought_together = 0.0;
+            }
+        }
+        self.squads
+            .retain(|squad| squad.members.len() >= MIN_SQUAD_SIZE);
+        self.next_id += 1;
+        self.squads.push(RosterSquad {
+            id: self.next_id,
+            members: members.to_vec(),
+            fought_together: 0.0,
+        });
+        self.next_id
+    }
+
+    pub fn squad_of(&self, entity: Entity) -> Option<&RosterSquad> {
+        self.squads
+            .iter()
+            .find(|squad| squad.members.contains(&entity))
+    }
+}
+
+/// How far a squad has come from fighting together, 0 to 1.
+pub fn cohesion_level(fought_together: f32, balance: &CohesionBalance) -> f32 {
+    (fought_together / balance.full_cohesion_seconds.max(f32::EPSILON)).min(1.0)
+}
+
+/// Morale after a fighter at cohesion `level` shrugs off part of what they
+/// lost since `previous`.
+pub fn resisted_morale(previous: f32, current: f32, level: f32, balance: &CohesionBalance) -> f32 {
+    if current >= previous {
+        current
+    } else {
+        current + (previous - current) * balance.morale_resilience * level
+    }
+}
+
+fn merge_contacts(known: &mut Vec<EnemyContact>, shared: &[EnemyContact]) {
+    for contact in shared {
+        let seen = known
+            .iter()
+            .any(|known| known.position.distance(contact.position) < SAME_CONTACT_RADIUS);
+        if !seen {
+            known.push(contact.clone());
+        }
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn squad_reset_system(
+    mut commands: Commands,
+    mut roster: ResMut<SquadRoster>,
+    member_query: Query<Entity, With<SquadCohesion>>,
+) {
+    *roster = SquadRoster::default();
+    for entity in member_query.iter() {
+        commands.entity(entity).remove::<SquadCohesion>();
+    }
+}
+
+// Backslash: the selected fighters become a squad
+pub fn squad_order_system(
+    input: Res<Input<KeyCode>>,
+    mut roster: ResMut<SquadRoster>,
+    selected_query: Query<(Entity, &Unit), With<Selected>>,
+) {
+    if !input.just_pressed(KeyCode::Backslash) {
+        return;
+    }
+    let members: Vec<Entity> = selected_query
+        .iter()
+        .filter(|(_, unit)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(entity, _)| entity)
+        .collect();
+    if members.len() < MIN_SQUAD_SIZE {
+        play_tactical_sound("radio", &tr("radio.squad_too_small"));
+        return;
+    }
+
+    roster.form(&members);
+    play_tactical_sound(
+        "radio",
+        &tr_args("radio.squad_formed", &[("count", &members.len())]),
+    );
+}
+
+// Type aliases to reduce complexity
+type MemberQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        &'static Transform,
+        Option<&'static mut Communication>,
+        Option<&'static mut TacticalState>,
+        Option<&'static mut SquadCohesion>,
+    ),
+>;
+
+// Builds cohesion while each squad fights together and hands every member
+// what it's worth
+pub fn squad_cohesion_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    mut roster: ResMut<SquadRoster>,
+    mut member_query: MemberQuery,
+) {
+    let balance = &balance.cohesion;
+    let dt = time.delta_seconds();
+
+    // The fallen, the wounded and the captured drop out without a reset
+    for squad in &mut roster.squads {
+        squad.members.retain(|member| {
+            member_query
+                .get(*member)
+                .is_ok_and(|(_, unit, ..)| unit.health > 0.0)
+        });
+    }
+    roster
+        .squads
+        .retain(|squad| squad.members.len() >= MIN_SQUAD_SIZE);
+
+    let mut in_squad = HashSet::new();
+    for squad in &mut roster.squads {
+        let members: Vec<(Entity, Vec2)> = squad
+            .members
+            .iter()
+            .filter_map(|member| member_query.get(*member).ok())
+            .map(|(entity, _, transform, ..)| (entity, transform.translation.truncate()))
+            .collect();
+        let center =
+            members.iter().map(|(_, position)| *position).sum::<Vec2>() / members.len() as f32;
+
+        // Someone has fired lately, with nobody strayed off on their own
+        let together = members
+            .iter()
+            .all(|(_, position)| position.distance(center) <= balance.together_radius);
+        let fighting = members.iter().any(|(member, _)| {
+            member_query
+                .get(*member)
+                .is_ok_and(|(_, unit, ..)| !unit.attack_cooldown.finished())
+        });
+        if together && fighting {
+            squad.fought_together += dt;
+        }
+        let level = cohesion_level(squad.fought_together, balance);
+
+        let shared: Vec<EnemyContact> = if level >= balance.shared_vision_level {
+            members
+                .iter()
+                .filter_map(|(member, _)| member_query.get(*member).ok())
+                .filter_map(|(.., communication, _, _)| communication)
+                .flat_map(|communication| communication.known_enemies.iter().cloned())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        for (member, position) in members {
+            in_squad.insert(member);
+            let Ok((_, _, _, communication, tactical_state, cohesion)) =
+                member_query.get_mut(member)
+            else {
+                continue;
+            };
+            if let Some(mut communication) = communication.filter(|_| !shared.is_empty()) {
+                merge_contacts(&mut communication.known_enemies, &shared);
+            }
+            let regroup_speed = if position.distance(center) > balance.regroup_radius {
+                1.0 + balance.regroup_speed_bonus * level
+            } else {
+                1.0
+            };
+            let morale = tactical_state.map_or(1.0, |mut state| {
+                let previous = cohesion.as_ref().map_or(state.morale, |c| c.last_morale);
+                let morale = resisted_morale(previous, state.morale, level, balance);
+                if state.morale != morale {
+                    state.morale = morale;
+                }
+                morale
+            });
+
+            let updated = SquadCohesion {
+                squad: squad.id,
+                level,
+                regroup_speed,
+                last_morale: morale,
+            };
+            match cohesion {
+                Some(mut cohesion) => {
+                    if *cohesion != updated {
+                        *cohesion = updated;
+                    }
+                }
+                None => {
+                    commands.entity(member).insert(updated);
+                }
+            }
+        }
+    }
+
+    for (entity, .., cohesion) in member_query.iter() {
+        if cohesion.is_some() && !in_squad.contains(&entity) {
+            commands.entity(entity).remove::<SquadCohesion>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reforming_a_squad_starts_its_cohesion_over() {
+        let [a, b, c, d] = [1, 2, 3, 4].map(Entity::from_raw);
+        let mut roster = SquadRoster::default();
+        let first = roster.form(&[a, b, c]);
+        roster.squads[0].fought_together = 60.0;
+
+        // The same fighters again keep what they've built
+        assert_eq!(roster.form(&[c, a, b]), first);
+        assert_eq!(roster.squads[0].fought_together, 60.0);
+
+        // Taking one away resets the rest of the squad
+        let second = roster.form(&[c, d]);
+        assert_ne!(second, first);
+        assert_eq!(roster.squad_of(a).unwrap().fought_together, 0.0);
+        assert_eq!(roster.squad_of(c).unwrap().id, second);
+
+        // A squad left with one fighter is gone
+        roster.form(&[a, d]);
+        assert!(roster.squad_of(b).is_none());
+    }
+
+    #[test]
+    fn test_cohesion_softens_morale_losses() {
+        let balance = CohesionBalance::default();
+        assert_eq!(cohesion_level(0.0, &balance), 0.0);
+        assert_eq!(cohesion_level(1000.0, &balance), 1.0);
+
+        assert_eq!(resisted_morale(0.8, 0.4, 0.0, &balance), 0.4);
+        assert!((resisted_morale(0.8, 0.4, 1.0, &balance) - 0.6).abs() < 1e-6);
+        // Gains pass straight through
+        assert_eq!(resisted_morale(0.4, 0.8, 1.0, &balance), 0.8);
+    }
+}
@@ -0,0 +1,633 @@
+use crate::app_state::GameSet;
+use crate::campaign::Campaign;
+use crate::components::*;
+use crate::config::{BalanceConfig, UnitCatalog};
+use crate::damage::{damage_system, DamageEvent};
+use crate::economy::{controlled_neighborhoods, CartelTreasury};
+use crate::intel_recruitment::IntelRecruitment;
+use crate::localization::tr;
+use crate::map::CityMap;
+use crate::political_system::{ContentType, PoliticalState, SocialMediaInfluence, ViralContent};
+use crate::reinforcements::ReinforcementDesk;
+use crate::resources::GameAssets;
+use crate::sentiment::CivilianSentiment;
+use crate::spawners::spawn_unit;
+use crate::ui::UiTheme;
+use crate::upgrades::UpgradeWorkshop;
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, iso_to_world, play_tactical_sound,
+    update_dashboard_panel, SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== COMMANDER POWERS PLUGIN ====================
+
+// Faction-wide powers, separate from what any one unit can do. The cartel's
+// are in the C menu: a city-wide lookout alert, a blockade surge in front of
+// every safehouse and a propaganda burst. The army's commander calls a
+// curfew, a checkpoint sweep or drone surveillance when the situation asks
+// for it. Every power has a long cooldown, and each one is announced in a
+// banner across the top of the screen.
+
+const BANNER_SECONDS: f32 = 5.0;
+const BANNER_PULSE_SPEED: f32 = 4.0;
+const SPOTTING_BOOST: f32 = 2.0; // Lookout alert and drone surveillance
+const SURGE_OFFSET: f32 = 100.0; // How far out from a safehouse its blockade goes
+const SWEEP_RADIUS: f32 = 250.0; // Roadblocks this close to a soldier are torn down
+const PROPAGANDA_MEDIA: f32 = 0.5;
+const PROPAGANDA_SUPPORT: f32 = 0.05;
+const PROPAGANDA_REACH: u32 = 60_000; // Views of the video the burst puts out
+const PROPAGANDA_SENTIMENT: f32 = 0.5;
+const CURFEW_NEIGHBORHOODS: usize = 2; // Cartel turf before the army shuts the streets
+const DRONE_OUTNUMBERED: f32 = 1.5; // Cartel fighters per soldier before the drone goes up
+const MILITARY_OPENING_DELAY: f32 = 60.0; // The army's powers aren't ready at the start
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommanderPower {
+    LookoutAlert,
+    BlockadeSurge,
+    PropagandaBurst,
+    Curfew,
+    CheckpointSweep,
+    DroneSurveillance,
+}
+
+const POWER_COUNT: usize = 6;
+const CARTEL_POWERS: [(KeyCode, CommanderPower); 3] = [
+    (KeyCode::J, CommanderPower::LookoutAlert),
+    (KeyCode::K, CommanderPower::BlockadeSurge),
+    (KeyCode::L, CommanderPower::PropagandaBurst),
+];
+const MILITARY_POWERS: [CommanderPower; 3] = [
+    CommanderPower::Curfew,
+    CommanderPower::CheckpointSweep,
+    CommanderPower::DroneSurveillance,
+];
+
+impl CommanderPower {
+    pub fn faction(self) -> Faction {
+        match self {
+            CommanderPower::LookoutAlert
+            | CommanderPower::BlockadeSurge
+            | CommanderPower::PropagandaBurst => Faction::Cartel,
+            CommanderPower::Curfew
+            | CommanderPower::CheckpointSweep
+            | CommanderPower::DroneSurveillance => Faction::Military,
+        }
+    }
+
+    pub fn cooldown(self) -> f32 {
+        match self {
+            CommanderPower::LookoutAlert => 120.0,
+            CommanderPower::BlockadeSurge => 150.0,
+            CommanderPower::PropagandaBurst => 180.0,
+            CommanderPower::Curfew => 180.0,
+            CommanderPower::CheckpointSweep => 150.0,
+            CommanderPower::DroneSurveillance => 160.0,
+        }
+    }
+
+    // Seconds the effect lasts; instant powers have none
+    pub fn duration(self) -> f32 {
+        match self {
+            CommanderPower::LookoutAlert => 20.0,
+            CommanderPower::Curfew => 45.0,
+            CommanderPower::DroneSurveillance => 40.0,
+            CommanderPower::BlockadeSurge
+            | CommanderPower::PropagandaBurst
+            | CommanderPower::CheckpointSweep => 0.0,
+        }
+    }
+
+    // Localization key under hud.
+    fn name(self) -> &'static str {
+        match self {
+            CommanderPower::LookoutAlert => "power_lookout_alert",
+            CommanderPower::BlockadeSurge => "power_blockade_surge",
+            CommanderPower::PropagandaBurst => "power_propaganda_burst",
+            CommanderPower::Curfew => "power_curfew",
+            CommanderPower::CheckpointSweep => "power_checkpoint_sweep",
+            CommanderPower::DroneSurveillance => "power_drone_surveillance",
+        }
+    }
+}
+
+pub struct CommanderPowersPlugin;
+
+impl Plugin for CommanderPowersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CommanderPowers>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    commander_power_tick_system,
+                    military_commander_system.run_if(resource_exists::<GameAssets>()),
+                )
+                    .chain()
+                    .before(damage_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(
+                Update,
+                (commander_panel_system, commander_banner_system).in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct CommanderPowers {
+    pub menu_open: bool,
+    pub cooldowns: [f32; POWER_COUNT], // Seconds until each can be used again
+    pub active: [f32; POWER_COUNT],    // Seconds each lasting effect has left
+    pub announcements: u32,            // Bumped on every use, so the banner can spot new ones
+    pub last_used: Option<CommanderPower>,
+}
+
+impl Default for CommanderPowers {
+    fn default() -> Self {
+        let mut cooldowns = [0.0; POWER_COUNT];
+        for power in MILITARY_POWERS {
+            cooldowns[power as usize] = MILITARY_OPENING_DELAY;
+        }
+        Self {
+            menu_open: false,
+            cooldowns,
+            active: [0.0; POWER_COUNT],
+            announcements: 0,
+            last_used: None,
+        }
+    }
+}
+
+impl CommanderPowers {
+    pub fn is_ready(&self, power: CommanderPower) -> bool {
+        self.cooldowns[power as usize] <= 0.0
+    }
+
+    pub fn is_active(&self, power: CommanderPower) -> bool {
+        self.active[power as usize] > 0.0
+    }
+
+    // Starts the cooldown and any lasting effect; false if it isn't ready yet
+    pub fn use_power(&mut self, power: CommanderPower) -> bool {
+        if !self.is_ready(power) {
+            return false;
+        }
+        self.cooldowns[power as usize] = power.cooldown();
+        self.active[power as usize] = power.duration();
+        self.announcements += 1;
+        self.last_used = Some(power);
+        true
+    }
+
+    /// Contact pickup range multiplier a faction's powers give right now.
+    pub fn spotting(&self, faction: &Faction) -> f32 {
+        let boosted = match faction {
+            Faction::Cartel => self.is_active(CommanderPower::LookoutAlert),
+            Faction::Military => self.is_active(CommanderPower::DroneSurveillance),
+            _ => false,
+        };
+        if boosted {
+            SPOTTING_BOOST
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct CommanderUIPanel;
+
+#[derive(Component)]
+pub struct CommanderBanner;
+
+/// Where the blockade surge puts a roadblock: out from each safehouse toward
+/// the nearest soldier, or out the front door with none around.
+pub fn surge_positions(safehouses: &[Vec3], military: &[Vec3]) -> Vec<Vec3> {
+    safehouses
+        .iter()
+        .map(|&safehouse| {
+            let toward = military
+                .iter()
+                .min_by(|a, b| {
+                    a.distance(safehouse)
+                        .partial_cmp(&b.distance(safehouse))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .map(|&soldier| (soldier - safehouse).normalize_or_zero())
+                .filter(|direction| *direction != Vec3::ZERO)
+                .unwrap_or(Vec3::NEG_Y);
+            safehouse + toward * SURGE_OFFSET
+        })
+        .collect()
+}
+
+/// Whether the army's commander wants a power given the state of the city.
+pub fn military_wants(
+    power: CommanderPower,
+    cartel_neighborhoods: usize,
+    exposed_roadblocks: usize,
+    cartel_units: usize,
+    military_units: usize,
+) -> bool {
+    match power {
+        CommanderPower::Curfew => cartel_neighborhoods >= CURFEW_NEIGHBORHOODS,
+        CommanderPower::CheckpointSweep => exposed_roadblocks >= 2,
+        CommanderPower::DroneSurveillance => {
+            military_units > 0 && cartel_units as f32 >= military_units as f32 * DRONE_OUTNUMBERED
+        }
+        _ => false,
+    }
+}
+
+// ==================== COOLDOWNS ====================
+
+pub fn commander_power_tick_system(time: Res<Time>, mut powers: ResMut<CommanderPowers>) {
+    let dt = time.delta_seconds();
+    let powers = powers.as_mut();
+    for (cooldown, active) in powers.cooldowns.iter_mut().zip(powers.active.iter_mut()) {
+        *cooldown = (*cooldown - dt).max(0.0);
+        *active = (*active - dt).max(0.0);
+    }
+}
+
+// ==================== CARTEL POWERS ====================
+
+// C opens and closes the commander menu; while it's open the listed keys use
+// a power
+#[allow(clippy::too_many_arguments)]
+pub fn commander_input_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    balance: Res<BalanceConfig>,
+    mut powers: ResMut<CommanderPowers>,
+    mut campaign: ResMut<Campaign>,
+    mut political_state: Option<ResMut<PoliticalState>>,
+    mut sentiment: Option<ResMut<CivilianSentiment>>,
+    mut social_media: Option<ResMut<SocialMediaInfluence>>,
+    treasury: Option<ResMut<CartelTreasury>>,
+    desk: Option<ResMut<ReinforcementDesk>>,
+    workshop: Option<ResMut<UpgradeWorkshop>>,
+    network: Option<ResMut<IntelRecruitment>>,
+    unit_query: Query<(&Unit, &Transform)>,
+    safehouse_query: Query<&Transform, With<SafehouseInterior>>,
+) {
+    if input.just_pressed(KeyCode::C) {
+        powers.menu_open = !powers.menu_open;
+        // The recruitment, call-in, upgrade and intel network panels share the J/K/L keys
+        if powers.menu_open {
+            if let Some(mut treasury) = treasury {
+                treasury.panel_safehouse = None;
+            }
+            if let Some(mut desk) = desk {
+                desk.menu_open = false;
+            }
+            if let Some(mut workshop) = workshop {
+                workshop.panel_open = false;
+            }
+            if let Some(mut network) = network {
+                network.menu_open = false;
+            }
+        }
+    }
+    if !powers.menu_open {
+        return;
+    }
+
+    for (key, power) in CARTEL_POWERS {
+        if !input.just_pressed(key) {
+            continue;
+        }
+        if !powers.use_power(power) {
+            play_tactical_sound("radio", &tr("radio.power_not_ready"));
+            continue;
+        }
+        match power {
+            CommanderPower::BlockadeSurge => {
+                let safehouses: Vec<Vec3> = safehouse_query
+                    .iter()
+                    .map(|transform| transform.translation)
+                    .collect();
+                let military: Vec<Vec3> = unit_query
+                    .iter()
+                    .filter(|(unit, _)| unit.faction == Faction::Military && unit.health > 0.0)
+                    .map(|(_, transform)| transform.translation)
+                    .collect();
+                for position in surge_positions(&safehouses, &military) {
+                    spawn_unit(
+                        &mut commands,
+                        UnitType::Roadblock,
+                        Faction::Cartel,
+                        iso_to_world(position),
+                        &game_assets,
+                        &catalog,
+                    );
+                }
+            }
+            CommanderPower::PropagandaBurst => {
+                campaign
+                    .political_pressure
+                    .increase_media_attention(PROPAGANDA_MEDIA);
+                if let Some(political_state) = political_state.as_deref_mut() {
+                    political_state.public_support_cartel =
+                        (political_state.public_support_cartel + PROPAGANDA_SUPPORT).min(1.0);
+                    // Pictures of dead bystanders tie the army's hands for a while
+                    political_state.roe_relief += balance.roe.propaganda_relief;
+                }
+                if let Some(sentiment) = sentiment.as_deref_mut() {
+                    sentiment.shift_all(balance.sentiment.propaganda_shift);
+                }
+                // The burst's own video goes out on the feeds
+                if let Some(social_media) = social_media.as_deref_mut() {
+                    social_media.share(ViralContent {
+                        content_type: ContentType::CartelPropaganda,
+                        reach: PROPAGANDA_REACH,
+                        sentiment: PROPAGANDA_SENTIMENT,
+                        timestamp: political_state
+                            .as_deref()
+                            .map_or(0.0, |state| state.operation_duration),
+                        impact_multiplier: 1.0,
+                    });
+                }
+            }
+            // Lasting effects are read where they apply
+            _ => {}
+        }
+        play_tactical_sound("radio", &tr(&format!("radio.{}", power.name())));
+    }
+}
+
+// ==================== MILITARY POWERS ====================
+
+pub fn military_commander_system(
+    mut powers: ResMut<CommanderPowers>,
+    city_map: Res<CityMap>,
+    unit_query: Query<(Entity, &Unit, &Transform)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let ready: Vec<CommanderPower> = MILITARY_POWERS
+        .into_iter()
+        .filter(|power| powers.is_ready(*power))
+        .collect();
+    if ready.is_empty() {
+        return;
+    }
+
+    let units: Vec<(Faction, Vec2)> = unit_query
+        .iter()
+        .filter(|(_, unit, _)| unit.health > 0.0)
+        .map(|(_, unit, transform)| (unit.faction.clone(), transform.translation.truncate()))
+        .collect();
+    let soldiers: Vec<Vec2> = units
+        .iter()
+        .filter(|(faction, _)| *faction == Faction::Military)
+        .map(|(_, position)| *position)
+        .collect();
+    let exposed = |unit: &Unit, transform: &Transform| {
+        unit.faction == Faction::Cartel
+            && unit.unit_type == UnitType::Roadblock
+            && unit.health > 0.0
+            && soldiers
+                .iter()
+                .any(|soldier| soldier.distance(transform.translation.truncate()) <= SWEEP_RADIUS)
+    };
+    let exposed_roadblocks = unit_query
+        .iter()
+        .filter(|(_, unit, transform)| exposed(unit, transform))
+        .count();
+    let cartel_units = units
+        .iter()
+        .filter(|(faction, _)| *faction == Faction::Cartel)
+        .count();
+    let cartel_neighborhoods = controlled_neighborhoods(&city_map, &units);
+
+    // One power at a time, so the announcements don't pile up
+    let Some(power) = ready.into_iter().find(|power| {
+        military_wants(
+            *power,
+            cartel_neighborhoods,
+            exposed_roadblocks,
+            cartel_units,
+            soldiers.len(),
+        )
+    }) else {
+        return;
+    };
+    powers.use_power(power);
+
+    if power == CommanderPower::CheckpointSweep {
+        // Torn down through the damage pipeline, like any other lost roadblock
+        for (entity, unit, transform) in unit_query.iter() {
+            if exposed(unit, transform) {
+                damage_events.send(DamageEvent {
+                    attacker: None,
+                    target: entity,
+                    amount: unit.health,
+                    weapon: None,
+                });
+            }
+        }
+    }
+    play_tactical_sound("alarm", &tr(&format!("radio.{}", power.name())));
+}
+
+// ==================== COMMANDER UI ====================
+
+pub fn commander_panel_system(
+    mut commands: Commands,
+    powers: Res<CommanderPowers>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<CommanderUIPanel>>,
+    theme: Res<UiTheme>,
+) {
+    let panel_exists = !panel_query.is_empty();
+    if panel_exists && !powers.is_changed() && !theme.is_changed() {
+        return;
+    }
+
+    // Shown while the menu is open or any power is in effect
+    let any_active = powers.active.iter().any(|remaining| *remaining > 0.0);
+    let visibility = if powers.menu_open || any_active {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    let mut lines = vec![dashboard_line(tr("hud.commander_title"), 16.0, Color::GOLD)];
+    if powers.menu_open {
+        for (key, power) in CARTEL_POWERS {
+            let cooldown = powers.cooldowns[power as usize];
+            let mut line = format!("[{:?}] {}", key, tr(&format!("hud.{}", power.name())));
+            if cooldown > 0.0 {
+                line.push_str(&format!(" ⏱ {:.0}s", cooldown.ceil()));
+            }
+            lines.push(dashboard_line(
+                line,
+                11.0,
+                if cooldown > 0.0 {
+                    Color::GRAY
+                } else {
+                    Color::WHITE
+                },
+            ));
+        }
+    }
+    for power in CARTEL_POWERS
+        .map(|(_, power)| power)
+        .into_iter()
+        .chain(MILITARY_POWERS)
+        .filter(|power| powers.is_active(*power))
+    {
+        lines.push(dashboard_line(
+            format!(
+                "⚡ {} {:.0}s",
+                tr(&format!("hud.{}", power.name())),
+                powers.active[power as usize].ceil()
+            ),
+            10.0,
+            if power.faction() == Faction::Cartel {
+                Color::CYAN
+            } else {
+                Color::ORANGE
+            },
+        ));
+    }
+
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else if !panel_exists {
+        let mut panel = create_dashboard_panel(
+            Style {
+                left: Val::Px(10.0),
+                bottom: Val::Px(220.0),
+                width: Val::Px(280.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.85),
+            lines,
+            &theme,
+        );
+        panel.0.visibility = visibility;
+        commands.spawn((panel, CommanderUIPanel));
+    }
+}
+
+// Every use is announced across the top for a few seconds, in the color of
+// the side that used it
+pub fn commander_banner_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    theme: Res<UiTheme>,
+    powers: Res<CommanderPowers>,
+    mut shown: Local<(u32, f32)>, // Last announcement seen, seconds it has left
+    mut banner_query: Query<(&mut Text, &mut Visibility), With<CommanderBanner>>,
+) {
+    if powers.announcements != shown.0 {
+        *shown = (powers.announcements, BANNER_SECONDS);
+    }
+    shown.1 = (shown.1 - time.delta_seconds()).max(0.0);
+    let message = powers.last_used.filter(|_| shown.1 > 0.0);
+
+    let Ok((mut text, mut visibility)) = banner_query.get_single_mut() else {
+        if message.is_some() {
+            commands
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(110.0),
+                        width: Val::Percent(100.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    z_index: ZIndex::Global(50),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 24.0,
+                                color: Color::WHITE,
+                                ..default()
+                            },
+                        ),
+                        CommanderBanner,
+                    ));
+                });
+        }
+        return;
+    };
+
+    let Some(power) = message else {
+        if *visibility != Visibility::Hidden {
+            *visibility = Visibility::Hidden;
+        }
+        return;
+    };
+    *visibility = Visibility::Inherited;
+    text.sections[0].value = tr(&format!("hud.{}_announce", power.name()));
+
+    let color = if power.faction() == Faction::Cartel {
+        theme.colors().cartel
+    } else {
+        theme.colors().military
+    };
+    let pulse = (time.elapsed_seconds() * BANNER_PULSE_SPEED).sin().abs();
+    text.sections[0].style.color = color.with_a(0.6 + 0.4 * pulse);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_powers_wait_out_their_cooldowns() {
+        let mut powers = CommanderPowers::default();
+        assert!(powers.use_power(CommanderPower::LookoutAlert));
+        assert!(!powers.use_power(CommanderPower::LookoutAlert));
+        assert!(powers.is_active(CommanderPower::LookoutAlert));
+        assert_eq!(powers.spotting(&Faction::Cartel), SPOTTING_BOOST);
+        assert_eq!(powers.spotting(&Faction::Military), 1.0);
+
+        // The army has to wait for its opening delay
+        assert!(!powers.is_ready(CommanderPower::Curfew));
+        assert!(!military_wants(CommanderPower::Curfew, 1, 0, 0, 0));
+        assert!(military_wants(CommanderPower::Curfew, 2, 0, 0, 0));
+        assert!(military_wants(
+            CommanderPower::DroneSurveillance,
+            0,
+            0,
+            15,
+            10
+        ));
+        assert!(!military_wants(
+            CommanderPower::DroneSurveillance,
+            0,
+            0,
+            5,
+            0
+        ));
+    }
+
+    #[test]
+    fn test_surge_blocks_the_way_from_the_nearest_soldier() {
+        let safehouses = [Vec3::ZERO, Vec3::new(500.0, 0.0, 0.0)];
+        let military = [Vec3::new(0.0, 300.0, 0.0), Vec3::new(900.0, 0.0, 0.0)];
+        let positions = surge_positions(&safehouses, &military);
+        assert_eq!(positions.len(), 2);
+        assert!(positions[0].distance(Vec3::new(0.0, SURGE_OFFSET, 0.0)) < 1e-3);
+        assert!(positions[1].distance(Vec3::new(500.0 + SURGE_OFFSET, 0.0, 0.0)) < 1e-3);
+
+        // Nobody around: out the front door
+        assert_eq!(
+            surge_positions(&[Vec3::ZERO], &[]),
+            vec![Vec3::new(0.0, -SURGE_OFFSET, 0.0)]
+        );
+    }
+}
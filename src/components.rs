@@ -42,6 +42,7 @@ pub struct Unit {
     pub unit_type: UnitType,
     pub damage: f32,
     pub range: f32,
+    pub vision: f32,
     pub movement_speed: f32,
     pub target: Option<Entity>,
     pub attack_cooldown: Timer,
@@ -101,6 +102,7 @@ pub enum UpgradeType {
     ReinforcedArmor, // +20% health
     CombatStims,     // +15% speed
     RadioComms,      // Coordination bonuses
+    NightVision,     // Ignores darkness penalties
 }
 
 // ==================== MOVEMENT COMPONENTS ====================
@@ -120,7 +122,7 @@ pub struct Formation {
     pub formation_facing: f32, // Rotation in radians
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum FormationType {
     Line,      // Linear formation for defensive positions
     Circle,    // Defensive circle around high-value target
@@ -128,6 +130,10 @@ pub enum FormationType {
     Flanking,  // Split formation for flanking maneuvers
     Overwatch, // Supporting fire positions
     Retreat,   // Tactical withdrawal formation
+    Custom {
+        name: String,
+        offsets: Vec<[f32; 2]>, // Saved by the player, relative to the center with +y in front
+    },
 }
 
 // ==================== INTEL SYSTEM COMPONENTS ====================
@@ -141,7 +147,7 @@ pub struct IntelOperator {
     pub last_intel_time: f32,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum IntelType {
     Reconnaissance, // Scout units with enhanced vision
     RadioIntercept, // Monitor enemy communications
@@ -183,6 +189,8 @@ pub struct InformantTip {
     pub confidence: f32, // 0.0 to 1.0
     pub time_received: f32,
     pub urgency: TipUrgency,
+    pub source: Option<Entity>, // The informant who passed it on
+    pub planted: bool,          // Fed to a double agent by the army's counter-intel
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -194,7 +202,7 @@ pub enum TipType {
     CommandPost(Vec3),            // High-value target
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum TipUrgency {
     Low,      // General information
     Medium,   // Actionable intelligence
@@ -248,7 +256,7 @@ pub struct Squad {
     pub cohesion_radius: f32,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum SquadType {
     AssaultTeam,  // Aggressive front-line units
     SupportTeam,  // Covering fire and overwatch
@@ -275,6 +283,31 @@ pub struct Communication {
     pub received_orders: Vec<TacticalOrder>,
 }
 
+// Multipliers from the cartel's in-mission upgrades. Only the upgrade system
+// writes them; base unit stats stay as spawned
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct StatModifiers {
+    pub damage_dealt: f32,
+    pub damage_taken: f32,
+    pub radio_range: f32,
+    pub spotting: f32, // Range at which enemy contacts are picked up
+    pub movement: f32,
+    pub noise: f32, // How far the unit's shots are heard
+}
+
+impl Default for StatModifiers {
+    fn default() -> Self {
+        Self {
+            damage_dealt: 1.0,
+            damage_taken: 1.0,
+            radio_range: 1.0,
+            spotting: 1.0,
+            movement: 1.0,
+            noise: 1.0,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EnemyContact {
     pub position: Vec3,
@@ -313,7 +346,7 @@ pub struct TacticalState {
     pub morale: f32,            // 0.0 to 1.0, affects decision making
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum TacticalMode {
     Advancing,    // Moving toward objective
     Engaging,     // In active combat
@@ -325,6 +358,91 @@ pub enum TacticalMode {
     HoldPosition, // Maintaining defensive stance
 }
 
+// Player "hold area" order: engage enemies that come within leash_radius of
+// center, but never chase past it, and walk back to post once they're gone
+#[derive(Component, Clone, Debug)]
+pub struct HoldArea {
+    pub center: Vec3,
+    pub post: Vec3, // This unit's own spot inside the area
+    pub leash_radius: f32,
+}
+
+// Player "ambush" stance: hold still and hold fire until the army is close,
+// then open up together (see src/deployment.rs)
+#[derive(Component, Clone, Debug)]
+pub struct Ambush;
+
+// The unit the cartel can't afford to lose (Ovidio). While sheltered he sits
+// inside a safehouse and takes its cover; while relocating he's on the way to
+// another one. Either way his own AI stands down.
+#[derive(Component, Clone, Debug, Default)]
+pub struct ProtectedPrincipal {
+    pub shelter: Option<Entity>,
+    pub shelter_cover: f32,
+    pub relocating_to: Option<Entity>,
+}
+
+impl ProtectedPrincipal {
+    pub fn is_moved_by_detail(&self) -> bool {
+        self.shelter.is_some() || self.relocating_to.is_some()
+    }
+}
+
+// Member of the principal's protection detail; keeps itself between him and
+// the threats the detail can see
+#[derive(Component, Clone, Debug)]
+pub struct Bodyguard {
+    pub principal: Entity,
+}
+
+// A restrained principal being walked out by a special forces escort; the
+// cartel can still take him back until the escort reaches extraction
+#[derive(Component, Clone, Debug)]
+pub struct Captive {
+    pub escort: Entity,
+    pub extraction: Vec3,
+}
+
+// A downed fighter taken alive. The Unit is lifted off the entity while it's
+// held, so nothing targets or counts it, and handed back on exchange or rescue
+#[derive(Component, Clone)]
+pub struct Prisoner {
+    pub unit: Unit,
+    pub captor: Faction,
+    pub escort: Option<Entity>,
+    pub held: bool, // Reached the captor's holding point
+}
+
+// An elite fighter down and bleeding out. As with prisoners the Unit is held
+// here, so nothing targets or counts them until they're back on their feet
+#[derive(Component, Clone)]
+pub struct Wounded {
+    pub unit: Unit,
+    pub bleed_out: f32, // Seconds left before they die
+    pub treatment: f32, // Seconds a medic has worked on them
+    pub dragger: Option<Entity>,
+    pub destination: Option<Vec2>, // The cover they're being dragged to
+    pub rotation: Quat,            // How they stood before going down
+}
+
+// A cartel fighter in a squad the player formed, with how far the squad has
+// come from fighting together (0 to 1)
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct SquadCohesion {
+    pub squad: u32,
+    pub level: f32,
+    pub regroup_speed: f32, // Folded into the unit's StatModifiers
+    pub last_morale: f32,   // As of the last tick, to catch losses
+}
+
+// Military unit taking part in the hunt for the high-value target; its
+// waypoint comes from the target package, not its own behavior
+#[derive(Component, Clone, Debug)]
+pub struct HvtTasking {
+    pub group: usize,
+    pub waypoint: Vec3,
+}
+
 // ==================== UI COMPONENTS ====================
 
 #[derive(Component)]
@@ -371,6 +489,30 @@ pub struct MissionObjectives;
 #[derive(Component)]
 pub struct SaveLoadMenu;
 
+#[derive(Component)]
+pub struct ModsMenuScreen;
+
+#[derive(Component)]
+pub struct AccessibilityMenuScreen;
+
+#[derive(Component)]
+pub struct TelemetryMenuScreen;
+
+#[derive(Component)]
+pub struct LeaderboardMenuScreen;
+
+// Numbered option on a menu page, 0-based, for focus tracking and narration
+#[derive(Component)]
+pub struct MenuItem {
+    pub page: GamePhase,
+    pub index: usize,
+}
+
+#[derive(Component)]
+pub struct MenuTitle {
+    pub page: GamePhase,
+}
+
 #[derive(Component)]
 pub struct SaveButton;
 
@@ -385,6 +527,9 @@ pub struct SaveSlot {
 #[derive(Component)]
 pub struct NewGameButton;
 
+#[derive(Component)]
+pub struct ProfilePickerMenu;
+
 #[derive(Component)]
 pub struct MainMenuButton;
 
@@ -404,6 +549,11 @@ pub struct ContinueButton;
 #[derive(Component)]
 pub struct SelectionIndicator;
 
+#[derive(Component)]
+pub struct SelectionIndicatorPart {
+    pub alpha: f32, // Opacity applied to the selection color
+}
+
 #[derive(Component)]
 pub struct TargetIndicator;
 
@@ -466,7 +616,7 @@ pub struct UnitAbility {
     pub energy_cost: u32,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum AbilityType {
     // Cartel abilities
     BurstFire,       // Rapid fire attack
@@ -523,6 +673,230 @@ pub struct Objective {
     pub _health: f32,
 }
 
+// Building interior the principal can shelter in
+#[derive(Component, Clone, Debug)]
+pub struct SafehouseInterior {
+    pub name: String,
+    pub radius: f32,
+    pub cover: f32, // Share of incoming damage the walls soak
+}
+
+// Road checkpoint. Enemy traffic through it is slowed, or stopped while its
+// own side mans it, and everyone passing is reported
+#[derive(Component, Clone, Debug)]
+pub struct Checkpoint {
+    pub faction: Faction,
+    pub radius: f32,
+    pub overrun: f32,        // Seconds enemies have held it with nobody manning it
+    pub logged: Vec<Entity>, // Enemies already reported on their way through
+    pub health: f32,         // Worn down by shelling
+}
+
+// Set on a unit inside an enemy checkpoint, and folded into its StatModifiers
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct CheckpointDelay {
+    pub speed: f32,
+}
+
+// Burnt-out hulk of a destroyed vehicle, blocking the lane until it's stripped
+// or hauled away
+#[derive(Component, Clone, Debug)]
+pub struct Wreck {
+    pub salvage: f32,  // Cash still to be stripped from it
+    pub clearing: f32, // Engineer-seconds of clearing done
+}
+
+// Builds units one at a time, front of the queue first: cartel safehouses,
+// and army staging areas in missions with base play
+#[derive(Component, Clone, Debug)]
+pub struct ProductionQueue {
+    pub faction: Faction,
+    pub orders: Vec<ProductionOrder>,
+    pub rally_point: Option<Vec3>, // Finished units walk here and hold it
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProductionOrder {
+    pub unit_type: UnitType,
+    pub build_seconds: f32,
+    pub progress: f32, // Seconds of building done
+    pub cost: f32,     // Refunded in full if the order is cancelled
+}
+
+// Army forward base that the AI director's reinforcements are built at
+#[derive(Component, Clone, Debug)]
+pub struct StagingArea;
+
+// Progress of the order being built, over its producer
+#[derive(Component, Clone, Debug)]
+pub struct ProductionBar {
+    pub foreground: bool,
+}
+
+// Field work a unit type can do, listed per type in units.ron: blowing a hole
+// through a wall, sandbagging a position, laying a trap and defusing the
+// other side's
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum EngineeringAction {
+    Breach,
+    Fortify,
+    LayTrap,
+    Defuse,
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct EngineeringKit {
+    pub actions: Vec<EngineeringAction>,
+    pub cooldown: f32, // Seconds until the next breach, fortification or trap
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TrapKind {
+    SpikeStrip, // Army and police: shreds tyres, slowing whoever drives over it
+    Ied,        // Cartels: blows up under the first enemy to reach it
+}
+
+// A trap on the ground, marked for everyone to see
+#[derive(Component, Clone, Debug)]
+pub struct Trap {
+    pub kind: TrapKind,
+    pub faction: Faction,
+    pub defusing: f32, // Seconds of defusing work done on it
+}
+
+// Shredded tyres from a spike strip, folded into the unit's StatModifiers
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct Punctured {
+    pub speed: f32,
+    pub remaining: f32, // Seconds until it's patched up
+}
+
+// A sniper watching a cone from where it stands. Until it has settled the
+// cone isn't locked; once it is, each enemy walking in takes one guaranteed
+// shot, and is remembered until it leaves so it isn't given another
+#[derive(Component, Clone, Debug)]
+pub struct Overwatch {
+    pub post: Vec2,
+    pub facing: Vec2,    // Unit vector down the middle of the cone
+    pub half_angle: f32, // Radians
+    pub range: f32,
+    pub settling: f32, // Seconds until the cone is locked
+    pub covered: Vec<Entity>,
+}
+
+// One of the markers outlining a sniper's cone
+#[derive(Component)]
+pub struct OverwatchCone {
+    pub owner: Entity,
+    pub post: Vec2, // The overwatch it outlines, so a new one gets a new outline
+    pub facing: Vec2,
+}
+
+// A sniper that has just left its overwatch post, not yet ready to fire
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct Relocating {
+    pub remaining: f32,
+}
+
+// Ground a unit type can deny, listed per type in units.ron: riot units
+// throw tear gas, cartel enforcers set barricades alight
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum AreaDenialKind {
+    TearGas,
+    BurningBarricade,
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct AreaDenialKit {
+    pub kind: AreaDenialKind,
+    pub cooldown: f32, // Seconds until the next canister or barricade
+}
+
+// Gas or flames lingering on the ground, in transform space: hurts whoever
+// it affects while they stand in it, and its smoke blocks sight through it
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct AreaDenialZone {
+    pub kind: AreaDenialKind,
+    pub faction: Faction, // Whoever threw or lit it
+    pub center: Vec2,
+    pub radius: f32,
+    pub remaining: f32,
+}
+
+// Running from gas or flames; the unit's own AI waits until it is clear
+#[derive(Component, Clone, Debug)]
+pub struct Fleeing;
+
+// Switched on in the dark by units without night vision. Points at the
+// target the unit was given, or else the way it last moved.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct Flashlight {
+    pub facing: Vec2,
+    pub last_position: Vec2,
+}
+
+// Ordered to keep its flashlight off, trading sight for not being seen
+#[derive(Component, Clone, Debug)]
+pub struct LightsOut;
+
+// A vehicle of an escorted convoy, driven down the road by convoy.rs rather
+// than by orders or the AI
+#[derive(Component, Clone, Debug)]
+pub struct ConvoyVehicle {
+    pub next_waypoint: usize, // Index into the convoy's route
+}
+
+// A landing zone the army can put an air assault down on
+#[derive(Component, Clone, Debug)]
+pub struct LandingZone {
+    pub radius: f32, // Cartel units this close call the landing off
+}
+
+// A helicopter flying an air assault, driven by air_assault.rs rather than by
+// orders or the AI
+#[derive(Component, Clone, Debug)]
+pub struct AirAssault {
+    pub landing_zone: Vec2,
+    pub radius: f32,
+    pub stage: AssaultStage,
+    pub timer: f32, // Seconds spent in the current stage
+    pub roped: usize,
+    pub exit: Vec2,
+    pub angle: f32, // Around the landing zone, while circling
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssaultStage {
+    Inbound,
+    Circling,
+    FastRoping,
+    Departing,
+    Aborted,
+}
+
+// A mortar round on its way down, marked on the ground where it will land
+#[derive(Component, Clone, Debug)]
+pub struct IncomingRound {
+    pub faction: Faction, // Whoever fired it
+    pub impact: Vec2,
+    pub remaining: f32, // Seconds until it lands
+    pub whistled: bool,
+}
+
+// An enemy the cartel's intel is sizing up; what it finds shows on the
+// enemy's tooltip once ready_in runs out
+#[derive(Component, Clone, Debug)]
+pub struct ThreatAnalysis {
+    pub ready_in: f32,
+}
+
+// Sent to where gunfire or an explosion was last heard
+#[derive(Component, Clone, Debug)]
+pub struct Investigating {
+    pub position: Vec2,
+    pub remaining: f32, // Seconds before the trail goes cold
+}
+
 // ==================== ENUMS & TYPES ====================
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
@@ -531,9 +905,41 @@ pub enum Faction {
     Cartel,
     Military,
     Civilian,
-}
-
-#[derive(Clone, PartialEq, Debug)]
+    RivalCartel, // A competing cartel, for scenarios that bring one in
+    Police,      // Municipal and state police, alongside the army by default
+}
+
+impl Faction {
+    pub const ALL: [Faction; 5] = [
+        Faction::Cartel,
+        Faction::Military,
+        Faction::Civilian,
+        Faction::RivalCartel,
+        Faction::Police,
+    ];
+
+    pub fn index(&self) -> usize {
+        match self {
+            Faction::Cartel => 0,
+            Faction::Military => 1,
+            Faction::Civilian => 2,
+            Faction::RivalCartel => 3,
+            Faction::Police => 4,
+        }
+    }
+
+    // The side whose doctrine, abilities and unit colors a faction borrows.
+    // Who it fights is up to Diplomacy, not the side.
+    pub fn side(&self) -> Faction {
+        match self {
+            Faction::RivalCartel => Faction::Cartel,
+            Faction::Police => Faction::Military,
+            other => other.clone(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum UnitType {
     // Cartel units
     Sicario,
@@ -565,16 +971,23 @@ pub enum ObjectiveType {
 
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub enum GamePhase {
-    MainMenu,        // Main menu with save/load options
-    SaveMenu,        // Save game menu
-    LoadMenu,        // Load game menu
-    MissionBriefing, // Show mission briefing screen
-    Preparation,     // Initial setup
-    InitialRaid,     // Mission 1: Defend safehouse
-    BlockConvoy,     // Mission 2: Block extraction
-    ApplyPressure,   // Mission 3: Escalate pressure
-    HoldTheLine,     // Mission 4: Final showdown
-    Victory,         // Mission completed successfully
-    Defeat,          // Mission failed
-    GameOver,        // Final game over state
+    Loading,           // Asset preloading before any menu is shown
+    ProfileSelect,     // Profile picker shown before the main menu
+    MainMenu,          // Main menu with save/load options
+    SaveMenu,          // Save game menu
+    LoadMenu,          // Load game menu
+    ModsMenu,          // Installed mods, load order and conflicts
+    AccessibilityMenu, // Text size, modifier keys, motion and pacing options
+    TelemetryMenu,     // Gameplay data opt-in and viewer
+    LeaderboardMenu,   // Online leaderboards per mission and difficulty
+    CampaignMap,       // Campaign hub between missions
+    MissionBriefing,   // Show mission briefing screen
+    Preparation,       // Initial setup
+    InitialRaid,       // Mission 1: Defend safehouse
+    BlockConvoy,       // Mission 2: Block extraction
+    ApplyPressure,     // Mission 3: Escalate pressure
+    HoldTheLine,       // Mission 4: Final showdown
+    Victory,           // Mission completed successfully
+    Defeat,            // Mission failed
+    GameOver,          // Final game over state
 }
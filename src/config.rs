@@ -1,13 +1,130 @@
-// use bevy::prelude::*;  // Unused
+use crate::entity_budget::EntityBudget;
+use crate::map::MapChunks;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use std::collections::HashSet;
 
-pub fn setup_config_system() {
-    // Basis configuratie initialisatie
+pub mod balance;
+pub mod graphics;
+pub mod mods;
+pub mod profiles;
+pub mod units;
+pub use balance::*;
+pub use graphics::*;
+pub use mods::*;
+pub use profiles::*;
+pub use units::*;
+
+pub fn setup_config_system(mut commands: Commands) {
+    let profiles = load_config_profiles();
+    commands.insert_resource(profiles.active().graphics);
+    commands.insert_resource(profiles);
 }
 
-pub fn config_hotkeys_system() {
-    // Hotkey handling
+pub fn config_hotkeys_system(
+    input: Res<Input<KeyCode>>,
+    mut balance: ResMut<BalanceConfig>,
+    mut catalog: ResMut<UnitCatalog>,
+    mut profiles: ResMut<ConfigProfiles>,
+) {
+    // F9: force a balance and unit reload without waiting for the file watcher
+    if input.just_pressed(KeyCode::F9) {
+        reload_balance_config(&mut balance);
+        reload_unit_catalog(&mut catalog);
+    }
+
+    // Shift+F10: next config profile, kept for the next launch
+    if input.just_pressed(KeyCode::F10)
+        && input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+    {
+        info!("⚙️ Config profile: {}", profiles.cycle());
+        if let Err(e) = profiles.save() {
+            warn!("Failed to save config profiles: {}", e);
+        }
+    }
 }
 
-pub fn performance_monitor_system() {
-    // Prestatie monitoring
+// ==================== PROFILER OVERLAY ====================
+
+#[derive(Component)]
+pub struct ProfilerOverlay;
+
+#[allow(clippy::too_many_arguments)]
+pub fn performance_monitor_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    diagnostics: Res<DiagnosticsStore>,
+    sprites: Query<(&Handle<Image>, &ViewVisibility), With<Sprite>>,
+    atlas_sprites: Query<(&Handle<TextureAtlas>, &ViewVisibility), With<TextureAtlasSprite>>,
+    budget: Option<Res<EntityBudget>>,
+    chunks: Option<Res<MapChunks>>,
+    mut overlay_query: Query<(Entity, &mut Text), With<ProfilerOverlay>>,
+) {
+    // F10: toggle the profiler overlay (Shift+F10 switches config profile)
+    if input.just_pressed(KeyCode::F10)
+        && !input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+    {
+        match overlay_query.get_single() {
+            Ok((entity, _)) => commands.entity(entity).despawn(),
+            Err(_) => {
+                commands.spawn((
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 14.0,
+                            color: Color::LIME_GREEN,
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(40.0),
+                        top: Val::Px(10.0),
+                        ..default()
+                    }),
+                    ProfilerOverlay,
+                ));
+            }
+        }
+        return;
+    }
+
+    let Ok((_, mut text)) = overlay_query.get_single_mut() else {
+        return;
+    };
+
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or(0.0);
+
+    // Sprites are batched per texture, so distinct visible textures is the
+    // floor on sprite draw calls
+    let image_textures: HashSet<AssetId<Image>> = sprites
+        .iter()
+        .filter(|(_, visibility)| visibility.get())
+        .map(|(image, _)| image.id())
+        .collect();
+
+    let mut atlas_sprite_count = 0;
+    let atlas_textures: HashSet<AssetId<TextureAtlas>> = atlas_sprites
+        .iter()
+        .filter(|(_, visibility)| visibility.get())
+        .inspect(|_| atlas_sprite_count += 1)
+        .map(|(atlas, _)| atlas.id())
+        .collect();
+
+    text.sections[0].value = format!(
+        "FPS: {:.0}\nSprite textures: {} ({} sprites in {} atlas)",
+        fps,
+        image_textures.len() + atlas_textures.len(),
+        atlas_sprite_count,
+        atlas_textures.len()
+    );
+    if let Some(budget) = budget {
+        text.sections[0].value += &format!("\n{}", budget.summary());
+    }
+    if let Some(chunks) = chunks {
+        text.sections[0].value += &format!("\n{}", chunks.summary());
+    }
 }
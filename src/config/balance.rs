@@ -0,0 +1,1097 @@
+use crate::components::*;
+use crate::config::{mod_registry, reload_unit_catalog, UnitCatalog, UNIT_CATALOG_PATH};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+// ==================== GAMEPLAY BALANCE CONFIG ====================
+
+pub const BALANCE_CONFIG_PATH: &str = "assets/data/balance.toml";
+const BALANCE_POLL_INTERVAL: f32 = 1.0;
+
+// Every section falls back to the built-in values, so balance.toml only needs
+// to list the numbers being tuned.
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BalanceConfig {
+    pub intel: IntelBalance,
+    pub director: DirectorBalance,
+    pub pressure: PressureWeights,
+    pub simulation: SimulationBalance,
+    pub corpses: CorpseBalance,
+    pub economy: EconomyBalance,
+    pub production: ProductionBalance,
+    pub upgrades: UpgradeBalance,
+    pub checkpoints: CheckpointBalance,
+    pub wrecks: WreckBalance,
+    pub engineering: EngineeringBalance,
+    pub overwatch: OverwatchBalance,
+    pub accuracy: AccuracyBalance,
+    pub area_denial: AreaDenialBalance,
+    pub night_vision: NightVisionBalance,
+    pub noise: NoiseBalance,
+    pub convoy: ConvoyBalance,
+    pub air_assault: AirAssaultBalance,
+    pub indirect_fire: IndirectFireBalance,
+    pub wounded: WoundedBalance,
+    pub cohesion: CohesionBalance,
+    pub deployment: DeploymentBalance,
+    pub threat_assessment: ThreatAssessmentBalance,
+    pub sentiment: SentimentBalance,
+    pub endings: EndingsBalance,
+    pub roe: RoeBalance,
+    pub intel_recruitment: IntelRecruitmentBalance,
+    pub jamming: JammingBalance,
+    pub informants: InformantBalance,
+    pub order_of_battle: OrderOfBattleBalance,
+    pub scoring: ScoringBalance,
+    pub assists: AssistBalance,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntelBalance {
+    pub reconnaissance_range: f32,
+    pub radio_intercept_range: f32,
+    pub informant_range: f32,
+    pub counter_intel_range: f32,
+}
+
+impl Default for IntelBalance {
+    fn default() -> Self {
+        Self {
+            reconnaissance_range: 200.0,
+            radio_intercept_range: 100.0,
+            informant_range: 50.0,
+            counter_intel_range: 150.0,
+        }
+    }
+}
+
+impl IntelBalance {
+    pub fn detection_range(&self, intel_type: &IntelType) -> f32 {
+        match intel_type {
+            IntelType::Reconnaissance => self.reconnaissance_range,
+            IntelType::RadioIntercept => self.radio_intercept_range,
+            IntelType::Informant => self.informant_range,
+            IntelType::CounterIntel => self.counter_intel_range,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectorBalance {
+    pub performance_smoothing: f32, // Weight of the previous performance estimate
+    pub struggling_threshold: f32,
+    pub struggling_modifier: f32,
+    pub weak_threshold: f32,
+    pub weak_modifier: f32,
+    pub below_par_threshold: f32,
+    pub below_par_modifier: f32,
+    pub strong_threshold: f32,
+    pub strong_modifier: f32,
+    pub dominating_threshold: f32,
+    pub dominating_modifier: f32,
+    pub time_scaling_period: f32, // Mission seconds per time_scaling_bonus step
+    pub time_scaling_bonus: f32,
+    pub base_spawn_interval: f32,
+    pub intensity_spawn_threshold: f32,
+    pub imbalance_ratio: usize, // Cartel/military unit ratio that forces a spawn
+    pub aggressive_intensity: f32, // Personality scales on intensity and spawn interval
+    pub aggressive_spawn_interval: f32,
+    pub methodical_intensity: f32,
+    pub methodical_spawn_interval: f32,
+}
+
+impl Default for DirectorBalance {
+    fn default() -> Self {
+        Self {
+            performance_smoothing: 0.85,
+            struggling_threshold: 0.2,
+            struggling_modifier: 0.5,
+            weak_threshold: 0.4,
+            weak_modifier: 0.7,
+            below_par_threshold: 0.6,
+            below_par_modifier: 0.9,
+            strong_threshold: 0.65,
+            strong_modifier: 1.2,
+            dominating_threshold: 0.8,
+            dominating_modifier: 1.4,
+            time_scaling_period: 600.0,
+            time_scaling_bonus: 0.3,
+            base_spawn_interval: 60.0,
+            intensity_spawn_threshold: 1.5,
+            imbalance_ratio: 2,
+            aggressive_intensity: 1.25,
+            aggressive_spawn_interval: 0.7,
+            methodical_intensity: 0.85,
+            methodical_spawn_interval: 1.4,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PressureWeights {
+    pub civilian_impact: f32,
+    pub economic_disruption: f32,
+    pub media_attention: f32,
+    pub political_families: f32,
+    pub military_morale: f32,
+}
+
+impl Default for PressureWeights {
+    fn default() -> Self {
+        Self {
+            civilian_impact: 0.25,
+            economic_disruption: 0.20,
+            media_attention: 0.15,
+            political_families: 0.25,
+            military_morale: 0.15,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationBalance {
+    pub tick_rate: f32, // Fixed simulation ticks per second, independent of frame rate
+}
+
+impl Default for SimulationBalance {
+    fn default() -> Self {
+        Self { tick_rate: 30.0 }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorpseBalance {
+    pub persist_seconds: f32, // How long a body stays before fading out
+    pub fade_seconds: f32,
+    pub max_corpses: usize, // Oldest bodies fade early past this many
+    pub morale_radius: f32,
+    pub morale_shock: f32, // One-off hit to friendlies who see a comrade fall
+    pub morale_drain: f32, // Per second, per friendly body in view
+    pub optics_radius: f32,
+    pub optics_media_rate: f32, // Media attention per second, per body civilians can see
+}
+
+impl Default for CorpseBalance {
+    fn default() -> Self {
+        Self {
+            persist_seconds: 90.0,
+            fade_seconds: 3.0,
+            max_corpses: 40,
+            morale_radius: 150.0,
+            morale_shock: 0.1,
+            morale_drain: 0.01,
+            optics_radius: 200.0,
+            optics_media_rate: 0.005,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EconomyBalance {
+    pub starting_cash: f32,
+    pub neighborhood_income: f32, // Per minute, per neighborhood the cartel controls
+    pub objective_income: f32,    // Per minute for a fully held ControlArea objective
+    pub safehouse_upkeep: f32,    // Per minute, per safehouse
+    pub roadblock_cost: f32,
+    pub ability_cost: f32,           // Each cartel ability activation
+    pub build_seconds_per_cost: f32, // Recruit build time scales with unit cost
+    pub max_queue: usize,            // Recruits waiting per safehouse
+}
+
+impl Default for EconomyBalance {
+    fn default() -> Self {
+        Self {
+            starting_cash: 600.0,
+            neighborhood_income: 60.0,
+            objective_income: 90.0,
+            safehouse_upkeep: 15.0,
+            roadblock_cost: 50.0,
+            ability_cost: 20.0,
+            build_seconds_per_cost: 0.08,
+            max_queue: 4,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProductionBalance {
+    pub staging_build_seconds_per_cost: f32, // Army build time scales with unit cost
+    pub staging_max_queue: usize,            // Orders waiting per staging area
+}
+
+impl Default for ProductionBalance {
+    fn default() -> Self {
+        Self {
+            staging_build_seconds_per_cost: 0.04,
+            staging_max_queue: 6,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpgradeBalance {
+    pub radios_cost: f32,
+    pub radio_range_multiplier: f32, // Communication.radio_range with better radios
+    pub armor_plates_cost: f32,
+    pub armor_damage_taken: f32, // Share of incoming damage that gets through the plates
+    pub incendiary_cost: f32,
+    pub incendiary_damage: f32, // Outgoing damage multiplier
+    pub drone_cost: f32,
+    pub drone_spotting: f32, // Contact pickup range multiplier
+    pub suppressors_cost: f32,
+    pub suppressor_noise: f32, // How far shots are heard, as a share of unsuppressed
+    pub mortars_cost: f32,
+}
+
+impl Default for UpgradeBalance {
+    fn default() -> Self {
+        Self {
+            radios_cost: 150.0,
+            radio_range_multiplier: 1.75,
+            armor_plates_cost: 300.0,
+            armor_damage_taken: 0.8,
+            incendiary_cost: 350.0,
+            incendiary_damage: 1.25,
+            drone_cost: 250.0,
+            drone_spotting: 1.5,
+            suppressors_cost: 200.0,
+            suppressor_noise: 0.3,
+            mortars_cost: 400.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckpointBalance {
+    pub cost: f32, // Cartel checkpoints are paid for out of the treasury
+    pub radius: f32,
+    pub slow_speed: f32, // Enemy speed through an unmanned checkpoint
+    pub max_per_side: usize,
+    pub overrun_seconds: f32, // Held by enemies this long with nobody manning it, it falls
+    pub military_interval: f32, // Seconds between the army's checks for a new site
+    pub health: f32,          // How much shelling it takes before it falls
+}
+
+impl Default for CheckpointBalance {
+    fn default() -> Self {
+        Self {
+            cost: 80.0,
+            radius: 60.0,
+            slow_speed: 0.5,
+            max_per_side: 4,
+            overrun_seconds: 10.0,
+            military_interval: 45.0,
+            health: 150.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WreckBalance {
+    pub radius: f32, // Blocks the lane like a roadblock
+    pub vehicle_salvage: f32,
+    pub tank_salvage: f32,
+    pub work_radius: f32, // How close a fighter has to be to work on a wreck
+    pub salvage_rate: f32, // Cash per second, per sicario stripping a wreck
+    pub clear_seconds: f32, // Engineer-seconds for the army to haul one away
+}
+
+impl Default for WreckBalance {
+    fn default() -> Self {
+        Self {
+            radius: 50.0,
+            vehicle_salvage: 120.0,
+            tank_salvage: 250.0,
+            work_radius: 60.0,
+            salvage_rate: 8.0,
+            clear_seconds: 30.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineeringBalance {
+    pub reach: f32,       // How close a unit has to be to the spot it works on
+    pub cooldown: f32,    // Seconds a unit needs between jobs
+    pub breach_cost: f32, // Cartel jobs are paid for out of the treasury
+    pub fortify_cost: f32,
+    pub trap_cost: f32,
+    pub max_traps_per_side: usize,
+    pub trigger_radius: f32,
+    pub ied_radius: f32,
+    pub ied_damage: f32,
+    pub spike_damage: f32,
+    pub spike_vehicle_multiplier: f32, // Vehicles and tanks run on tyres and tracks
+    pub spike_speed: f32,              // Speed kept while punctured
+    pub spike_seconds: f32,
+    pub defuse_radius: f32,
+    pub defuse_seconds: f32, // Worker-seconds to make a trap safe
+}
+
+impl Default for EngineeringBalance {
+    fn default() -> Self {
+        Self {
+            reach: 90.0,
+            cooldown: 20.0,
+            breach_cost: 150.0,
+            fortify_cost: 40.0,
+            trap_cost: 60.0,
+            max_traps_per_side: 6,
+            trigger_radius: 24.0,
+            ied_radius: 70.0,
+            ied_damage: 90.0,
+            spike_damage: 15.0,
+            spike_vehicle_multiplier: 3.0,
+            spike_speed: 0.35,
+            spike_seconds: 8.0,
+            defuse_radius: 48.0,
+            defuse_seconds: 4.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverwatchBalance {
+    pub half_angle: f32,            // Degrees either side of where the sniper faces
+    pub settle_seconds: f32,        // From deploying to the cone being locked
+    pub first_shot_multiplier: f32, // On the guaranteed shot at a unit entering the cone
+    pub relocation_distance: f32,   // Moving this far off the post gives up overwatch
+    pub relocation_seconds: f32,    // Without firing after giving it up
+    pub flash_reveal_range: f32,    // How far away a muzzle flash can be made out
+}
+
+impl Default for OverwatchBalance {
+    fn default() -> Self {
+        Self {
+            half_angle: 25.0,
+            settle_seconds: 3.0,
+            first_shot_multiplier: 2.0,
+            relocation_distance: 12.0,
+            relocation_seconds: 4.0,
+            flash_reveal_range: 600.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccuracyBalance {
+    pub falloff_start: f32, // Share of a unit's reach it shoots at full accuracy
+    pub falloff_at_reach: f32, // Accuracy lost by the edge of its reach
+    pub moving_shooter: f32, // Accuracy lost firing on the move
+    pub moving_target: f32, // Accuracy lost against a target on the move
+    pub cover_weight: f32,  // Accuracy lost per unit of cover around the target
+    pub suppression: f32,   // Accuracy lost by a fully suppressed shooter
+    pub gassed: f32,        // Accuracy lost by a shooter choking on tear gas
+    pub min_hit_chance: f32,
+}
+
+impl Default for AccuracyBalance {
+    fn default() -> Self {
+        Self {
+            falloff_start: 0.5,
+            falloff_at_reach: 0.3,
+            moving_shooter: 0.25,
+            moving_target: 0.15,
+            cover_weight: 0.4,
+            suppression: 0.4,
+            gassed: 0.35,
+            min_hit_chance: 0.05,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AreaDenialBalance {
+    pub tear_gas_radius: f32,
+    pub tear_gas_seconds: f32,
+    pub tear_gas_damage: f32, // Per second, to units hostile to the thrower
+    pub tear_gas_range: f32,  // How far a canister can be thrown
+    pub tear_gas_cooldown: f32,
+    pub barricade_radius: f32,
+    pub barricade_seconds: f32,
+    pub barricade_damage: f32, // Per second, to anyone in the flames
+    pub barricade_range: f32,  // How far from the unit it can be lit
+    pub barricade_cooldown: f32,
+    pub barricade_cost: f32, // Out of the cartel treasury
+    pub retreat_morale: f32, // Units below this morale flee a zone that hurts them
+    pub path_penalty: f32,   // Extra tiles a route pays for each tile through a zone
+}
+
+impl Default for AreaDenialBalance {
+    fn default() -> Self {
+        Self {
+            tear_gas_radius: 80.0,
+            tear_gas_seconds: 12.0,
+            tear_gas_damage: 2.0,
+            tear_gas_range: 180.0,
+            tear_gas_cooldown: 30.0,
+            barricade_radius: 50.0,
+            barricade_seconds: 20.0,
+            barricade_damage: 8.0,
+            barricade_range: 120.0,
+            barricade_cooldown: 40.0,
+            barricade_cost: 75.0,
+            retreat_morale: 0.4,
+            path_penalty: 12.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NightVisionBalance {
+    pub flashlight_darkness: f32, // Darkness (0.0 to 1.0) at which flashlights go on
+    pub flashlight_range: f32,
+    pub flashlight_half_angle: f32, // Degrees either side of where the holder faces
+}
+
+impl Default for NightVisionBalance {
+    fn default() -> Self {
+        Self {
+            flashlight_darkness: 0.5,
+            flashlight_range: 180.0,
+            flashlight_half_angle: 20.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoiseBalance {
+    pub gunfire_radius: f32, // How far rifle and pistol shots are heard
+    pub heavy_weapon_radius: f32,
+    pub explosion_radius: f32,
+    pub patrol_size: usize, // Listeners sent after each noise
+    pub investigate_seconds: f32,
+    pub arrival_radius: f32,
+}
+
+impl Default for NoiseBalance {
+    fn default() -> Self {
+        Self {
+            gunfire_radius: 300.0,
+            heavy_weapon_radius: 450.0,
+            explosion_radius: 700.0,
+            patrol_size: 3,
+            investigate_seconds: 25.0,
+            arrival_radius: 40.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConvoyBalance {
+    pub vehicles: usize,
+    pub spacing: f32,     // Gap between vehicles at the start
+    pub halt_radius: f32, // An enemy this close to a vehicle stops the convoy
+    pub ambush_count: usize,
+    pub ambush_size: usize, // Soldiers per ambush
+    pub ambush_offset: f32, // How far ahead and off the road they wait
+    pub fail_health: f32,   // Share of the convoy's health below which it's lost
+}
+
+impl Default for ConvoyBalance {
+    fn default() -> Self {
+        Self {
+            vehicles: 3,
+            spacing: 80.0,
+            halt_radius: 150.0,
+            ambush_count: 2,
+            ambush_size: 4,
+            ambush_offset: 120.0,
+            fail_health: 0.4,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AirAssaultBalance {
+    pub interval: f32,          // Seconds between assaults the director sends
+    pub min_intensity: f32,     // Director intensity needed to send one
+    pub lz_radius: f32,         // Cartel units this close to the LZ call it off
+    pub approach_distance: f32, // How far out the helicopter comes in from
+    pub circle_radius: f32,
+    pub circle_seconds: f32,
+    pub rope_interval: f32, // Seconds between soldiers down the rope
+    pub squad_size: usize,
+}
+
+impl Default for AirAssaultBalance {
+    fn default() -> Self {
+        Self {
+            interval: 90.0,
+            min_intensity: 1.0,
+            lz_radius: 120.0,
+            approach_distance: 900.0,
+            circle_radius: 80.0,
+            circle_seconds: 6.0,
+            rope_interval: 1.5,
+            squad_size: 4,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndirectFireBalance {
+    pub rounds: usize,        // Per salvo
+    pub flight_seconds: f32,  // From the order to the first round landing
+    pub round_interval: f32,  // Between rounds of a salvo
+    pub whistle_seconds: f32, // The whistle is heard this long before a round lands
+    pub blast_radius: f32,
+    pub blast_damage: f32,     // At the point of impact, half that at the edge
+    pub structure_damage: f32, // To checkpoints, at the point of impact
+    pub military_min_intensity: f32, // Director intensity the army's mortars need
+    pub military_interval: f32,
+    pub military_scatter: f32, // Radius the rounds of a salvo spread over
+    pub cartel_cost: f32,
+    pub cartel_cooldown: f32,
+    pub cartel_scatter: f32,
+}
+
+impl Default for IndirectFireBalance {
+    fn default() -> Self {
+        Self {
+            rounds: 4,
+            flight_seconds: 4.0,
+            round_interval: 0.6,
+            whistle_seconds: 1.2,
+            blast_radius: 70.0,
+            blast_damage: 60.0,
+            structure_damage: 60.0,
+            military_min_intensity: 1.4,
+            military_interval: 60.0,
+            military_scatter: 60.0,
+            cartel_cost: 120.0,
+            cartel_cooldown: 45.0,
+            cartel_scatter: 110.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WoundedBalance {
+    pub bleed_out_seconds: f32,
+    pub covered_bleed_rate: f32, // Bleeding slows to this share behind cover
+    pub overkill_share: f32,     // Hit this far past zero (share of max health) and they're dead
+    pub drag_radius: f32,        // An ally this close picks them up
+    pub drag_speed: f32,
+    pub drag_leash: f32, // The ally waits when the wounded fall this far behind
+    pub cover_search_radius: f32,
+    pub stabilize_radius: f32,
+    pub stabilize_seconds: f32,
+    pub revived_health: f32, // Share of max health they get back up with
+}
+
+impl Default for WoundedBalance {
+    fn default() -> Self {
+        Self {
+            bleed_out_seconds: 30.0,
+            covered_bleed_rate: 0.5,
+            overkill_share: 0.5,
+            drag_radius: 40.0,
+            drag_speed: 55.0,
+            drag_leash: 25.0,
+            cover_search_radius: 160.0,
+            stabilize_radius: 40.0,
+            stabilize_seconds: 5.0,
+            revived_health: 0.3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CohesionBalance {
+    pub full_cohesion_seconds: f32, // Fighting side by side this long maxes it out
+    pub together_radius: f32,       // Of the squad's center, for it to count as together
+    pub regroup_radius: f32,        // Members further out than this hurry back
+    pub regroup_speed_bonus: f32,   // At full cohesion
+    pub shared_vision_level: f32,   // Cohesion at which contacts are pooled
+    pub morale_resilience: f32,     // Share of each morale loss shrugged off, at full cohesion
+}
+
+impl Default for CohesionBalance {
+    fn default() -> Self {
+        Self {
+            full_cohesion_seconds: 120.0,
+            together_radius: 150.0,
+            regroup_radius: 90.0,
+            regroup_speed_bonus: 0.3,
+            shared_vision_level: 0.5,
+            morale_resilience: 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeploymentBalance {
+    pub zone_radius: f32,           // Of each safehouse and Ovidio, where fighters may deploy
+    pub ambush_trigger_radius: f32, // The army this close to an ambusher springs it
+    pub ambush_spring_radius: f32,  // Ambushers this close to the one sprung open up too
+}
+
+impl Default for DeploymentBalance {
+    fn default() -> Self {
+        Self {
+            zone_radius: 200.0,
+            ambush_trigger_radius: 120.0,
+            ambush_spring_radius: 200.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThreatAssessmentBalance {
+    pub intel_cost: u32,        // Intel points (one per report gathered) per analysis
+    pub analysis_seconds: f32,  // Before the findings come in
+    pub squad_radius: f32,      // Soldiers this close to each other count as one squad
+}
+
+impl Default for ThreatAssessmentBalance {
+    fn default() -> Self {
+        Self {
+            intel_cost: 2,
+            analysis_seconds: 4.0,
+            squad_radius: 120.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SentimentBalance {
+    pub fighting_per_second: f32,  // Lost while gunfire is heard in the neighborhood
+    pub casualty_shift: f32,       // Per death there, against whoever did the killing
+    pub roadblock_per_second: f32, // Per checkpoint or roadblock, against the side that put it up
+    pub propaganda_shift: f32,     // Every neighborhood, per propaganda burst
+    pub friendly_threshold: f32,   // At or above: informants come forward, fighters are hidden
+    pub hostile_threshold: f32,    // At or below: the locals tip off the army
+    pub tip_interval: f32,         // Seconds between tips from each hostile neighborhood
+    pub tip_radius: f32,           // Soldiers this close to a tipped-off fighter go and look
+    pub hidden_sight: f32,         // Share of the army's sight left into a friendly neighborhood
+    pub support_drift: f32,        // Cartel public support per second, at the city's full goodwill
+}
+
+impl Default for SentimentBalance {
+    fn default() -> Self {
+        Self {
+            fighting_per_second: 0.004,
+            casualty_shift: 0.04,
+            roadblock_per_second: 0.001,
+            propaganda_shift: 0.15,
+            friendly_threshold: 0.4,
+            hostile_threshold: -0.4,
+            tip_interval: 20.0,
+            tip_radius: 700.0,
+            hidden_sight: 0.6,
+            support_drift: 0.0005,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EndingsBalance {
+    pub retreat_morale: f32,       // Military morale pressure that breaks the troops
+    pub withdrawal_families: f32,  // Political families pressure that orders a clean withdrawal
+    pub withdrawal_media: f32,     // Or media attention that does the same
+    pub escalation_civilian: f32,  // Civilian impact that lets the hardliners override
+    pub escalation_threshold: f32, // Added to the decision threshold by the override
+    pub escalation_wave: u32,      // Extra soldiers in the wave the override sends at once
+    pub withdrawal_seconds: f32,   // Each ending plays out this long before the mission is won
+    pub retreat_seconds: f32,
+    pub ceasefire_seconds: f32,
+    pub skirmish_radius: f32,     // Soldiers this close to a fighter keep up the fight
+    pub withdrawal_distance: f32, // How far from Ovidio withdrawing soldiers head
+}
+
+impl Default for EndingsBalance {
+    fn default() -> Self {
+        Self {
+            retreat_morale: 0.5,
+            withdrawal_families: 0.3,
+            withdrawal_media: 0.6,
+            escalation_civilian: 0.6,
+            escalation_threshold: 0.2,
+            escalation_wave: 6,
+            withdrawal_seconds: 8.0,
+            retreat_seconds: 12.0,
+            ceasefire_seconds: 25.0,
+            skirmish_radius: 250.0,
+            withdrawal_distance: 1500.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoeBalance {
+    pub limited_range: f32,       // Share of the army's weapon reach under Limited rules
+    pub civilian_radius: f32,     // Heavy weapons hold fire with civilians this close to the target
+    pub air_strike_interval: f32, // Seconds between air strikes once it's all out
+    pub air_strike_rounds: usize,
+    pub air_strike_scatter: f32,
+    pub propaganda_relief: f32, // Added to the pressure the response level is read from
+    pub exchange_relief: f32,   // Per prisoner swapped
+    pub relief_decay: f32,      // Relief wearing off per second
+}
+
+impl Default for RoeBalance {
+    fn default() -> Self {
+        Self {
+            limited_range: 0.75,
+            civilian_radius: 200.0,
+            air_strike_interval: 40.0,
+            air_strike_rounds: 10,
+            air_strike_scatter: 160.0,
+            propaganda_relief: 0.25,
+            exchange_relief: 0.1,
+            relief_decay: 0.004,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntelRecruitmentBalance {
+    pub lookout_cost: f32, // Cash
+    pub radio_tech_cost: f32,
+    pub informant_intel: u32, // Intel points; informants aren't bought with cash
+    pub lookout_upkeep: f32,  // Cash per minute, for as long as the operator works
+    pub radio_tech_upkeep: f32,
+    pub informant_upkeep: f32,
+    pub max_operators: usize, // Recruited operators at once
+    pub unpaid_grace: f32,    // Seconds of an empty treasury before an operator walks off
+}
+
+impl Default for IntelRecruitmentBalance {
+    fn default() -> Self {
+        Self {
+            lookout_cost: 60.0,
+            radio_tech_cost: 90.0,
+            informant_intel: 3,
+            lookout_upkeep: 6.0,
+            radio_tech_upkeep: 10.0,
+            informant_upkeep: 4.0,
+            max_operators: 6,
+            unpaid_grace: 20.0,
+        }
+    }
+}
+
+impl IntelRecruitmentBalance {
+    pub fn cash_cost(&self, intel_type: &IntelType) -> f32 {
+        match intel_type {
+            IntelType::Reconnaissance => self.lookout_cost,
+            IntelType::RadioIntercept => self.radio_tech_cost,
+            IntelType::Informant | IntelType::CounterIntel => 0.0,
+        }
+    }
+
+    pub fn intel_cost(&self, intel_type: &IntelType) -> u32 {
+        match intel_type {
+            IntelType::Informant => self.informant_intel,
+            _ => 0,
+        }
+    }
+
+    pub fn upkeep(&self, intel_type: &IntelType) -> f32 {
+        match intel_type {
+            IntelType::Reconnaissance => self.lookout_upkeep,
+            IntelType::RadioIntercept => self.radio_tech_upkeep,
+            IntelType::Informant => self.informant_upkeep,
+            IntelType::CounterIntel => 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JammingBalance {
+    pub deploy_interval: f32, // Seconds between jammers, from Moderate rules up
+    pub max_jammers: usize,
+    pub radius: f32,
+    pub strength: f32,      // Share of an intercept's reliability and words lost inside
+    pub scatter: f32,       // How far from the thickest knot of fighters a jammer parks
+    pub shimmer: f32,       // How far the field of a jammer not yet found wanders off it
+    pub bearing_range: f32, // How far a lookout can take a bearing on a jammer
+    pub bearing_error: f32, // Degrees either way
+    pub bearings_needed: usize,
+    pub fix_radius: f32, // How close to the jammer a fix has to be
+    pub fix_cost: u32,   // Intel points per attempt
+}
+
+impl Default for JammingBalance {
+    fn default() -> Self {
+        Self {
+            deploy_interval: 90.0,
+            max_jammers: 2,
+            radius: 280.0,
+            strength: 0.7,
+            scatter: 140.0,
+            shimmer: 90.0,
+            bearing_range: 700.0,
+            bearing_error: 4.0,
+            bearings_needed: 3,
+            fix_radius: 80.0,
+            fix_cost: 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InformantBalance {
+    pub min_reliability: f32,    // Informants' hidden reliability runs from this to 1
+    pub double_agent_below: f32, // Less reliable than this and counter-intel planted them
+    pub planted_chance: f32,     // Share of a double agent's tips that are false
+    pub vet_cost: u32,           // Intel points to vet a source
+    pub vet_radius: f32,         // How close to the cursor the source has to be
+    pub flee_speed: f32,         // A double agent found out runs for the army's lines
+    pub capture_radius: f32,
+    pub contest_radius: f32,    // Any soldier this close stops the capture
+    pub escape_radius: f32,     // This close to an army spawn point and he's gone
+    pub capture_intel: u32,     // Reports he gives up under questioning
+    pub escape_confidence: f32, // How good a fix on Ovidio he hands the army
+}
+
+impl Default for InformantBalance {
+    fn default() -> Self {
+        Self {
+            min_reliability: 0.3,
+            double_agent_below: 0.5,
+            planted_chance: 0.6,
+            vet_cost: 3,
+            vet_radius: 120.0,
+            flee_speed: 40.0,
+            capture_radius: 45.0,
+            contest_radius: 80.0,
+            escape_radius: 40.0,
+            capture_intel: 5,
+            escape_confidence: 0.9,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderOfBattleBalance {
+    pub fusion_interval: f32, // Seconds between passes over the new reports
+    pub decay: f32,           // Confidence an estimate loses a second without a report
+    pub forget_below: f32,    // Estimates less sure than this drop off the panel
+    pub corroboration: f32,   // Share of a second report's confidence added to the first
+    pub heavy_weight: f32,    // Soldiers' worth of each tank or helicopter, for the advisor
+    pub hint_strength: f32,   // Soldiers' worth in one area before the advisor speaks up
+    pub rows: usize,
+}
+
+impl Default for OrderOfBattleBalance {
+    fn default() -> Self {
+        Self {
+            fusion_interval: 1.0,
+            decay: 0.01,
+            forget_below: 0.1,
+            corroboration: 0.3,
+            heavy_weight: 3.0,
+            hint_strength: 8.0,
+            rows: 6,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringBalance {
+    pub speed_per_second: f32,   // Per second left on the mission clock
+    pub untimed_par: f32,        // Stands in for the time limit on missions without one
+    pub loss_penalty: f32,       // Per cartel fighter lost
+    pub civilian_safety: f32,    // Awarded in full with no civilian casualties
+    pub civilian_tolerance: u32, // Casualties that wipe the civilian bonus out
+    pub intel_per_report: f32,
+    pub intel_report_cap: u32,
+    pub bronze: f32, // Medal thresholds, scaled by each mission's difficulty
+    pub silver: f32,
+    pub gold: f32,
+    pub no_reinforcements_multiplier: f32,
+    pub permadeath_multiplier: f32,
+    pub extra_squad_multiplier: f32, // Retry assists lower the total
+    pub slower_escalation_multiplier: f32,
+    pub shorter_hold_multiplier: f32,
+}
+
+impl Default for ScoringBalance {
+    fn default() -> Self {
+        Self {
+            speed_per_second: 2.0,
+            untimed_par: 600.0,
+            loss_penalty: 25.0,
+            civilian_safety: 500.0,
+            civilian_tolerance: 10,
+            intel_per_report: 10.0,
+            intel_report_cap: 40,
+            bronze: 600.0,
+            silver: 1200.0,
+            gold: 1800.0,
+            no_reinforcements_multiplier: 1.25,
+            permadeath_multiplier: 1.5,
+            extra_squad_multiplier: 0.85,
+            slower_escalation_multiplier: 0.85,
+            shorter_hold_multiplier: 0.8,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssistBalance {
+    pub offer_after_defeats: u32, // Defeats on a mission before the retry assists are offered
+    pub extra_squad: Vec<UnitType>,
+    pub escalation_scale: f32, // AI director intensity with slower escalation on
+    pub hold_scale: f32,       // Share of the time limit left to outlast with a shorter hold
+}
+
+impl Default for AssistBalance {
+    fn default() -> Self {
+        Self {
+            offer_after_defeats: 2,
+            extra_squad: vec![UnitType::Sicario, UnitType::Sicario, UnitType::Enforcer],
+            escalation_scale: 0.6,
+            hold_scale: 0.7,
+        }
+    }
+}
+
+pub fn load_balance_config() -> BalanceConfig {
+    match read_balance_file(Path::new(BALANCE_CONFIG_PATH)) {
+        Ok(config) => {
+            info!("⚖️ Balance config loaded from {}", BALANCE_CONFIG_PATH);
+            config
+        }
+        Err(e) => {
+            warn!("Failed to load balance config: {}, using defaults", e);
+            BalanceConfig::default()
+        }
+    }
+}
+
+fn read_balance_file(path: &Path) -> Result<BalanceConfig, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)?;
+    let mut balance: toml::Value = toml::from_str(&contents)?;
+    // Mod packs layer their overrides over the base file
+    mod_registry().apply_balance_overrides(&mut balance);
+    Ok(balance.try_into()?)
+}
+
+// ==================== HOT RELOAD ====================
+
+#[derive(Resource)]
+pub struct BalanceWatcher {
+    poll_timer: Timer,
+    last_modified: Option<SystemTime>,
+    units_modified: Option<SystemTime>, // units.ron is watched alongside
+}
+
+impl Default for BalanceWatcher {
+    fn default() -> Self {
+        Self {
+            poll_timer: Timer::from_seconds(BALANCE_POLL_INTERVAL, TimerMode::Repeating),
+            last_modified: file_modified(BALANCE_CONFIG_PATH),
+            units_modified: file_modified(UNIT_CATALOG_PATH),
+        }
+    }
+}
+
+fn file_modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+pub fn reload_balance_config(balance: &mut BalanceConfig) {
+    // Keep the current values if the edited file doesn't parse
+    match read_balance_file(Path::new(BALANCE_CONFIG_PATH)) {
+        Ok(config) => {
+            *balance = config;
+            info!("⚖️ Balance config reloaded");
+        }
+        Err(e) => warn!("Balance config not reloaded: {}", e),
+    }
+}
+
+pub fn balance_hot_reload_system(
+    mut watcher: ResMut<BalanceWatcher>,
+    mut balance: ResMut<BalanceConfig>,
+    mut catalog: ResMut<UnitCatalog>,
+    time: Res<Time>,
+) {
+    watcher.poll_timer.tick(time.delta());
+    if !watcher.poll_timer.just_finished() {
+        return;
+    }
+
+    let modified = file_modified(BALANCE_CONFIG_PATH);
+    if modified.is_some() && modified != watcher.last_modified {
+        watcher.last_modified = modified;
+        reload_balance_config(&mut balance);
+    }
+
+    let modified = file_modified(UNIT_CATALOG_PATH);
+    if modified.is_some() && modified != watcher.units_modified {
+        watcher.units_modified = modified;
+        reload_unit_catalog(&mut catalog);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_balance_file_keeps_defaults() {
+        let config: BalanceConfig = toml::from_str(
+            r#"
+            [intel]
+            reconnaissance_range = 250.0
+
+            [director]
+            base_spawn_interval = 45.0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.intel.reconnaissance_range, 250.0);
+        assert_eq!(config.intel.radio_intercept_range, 100.0);
+        assert_eq!(config.director.base_spawn_interval, 45.0);
+        assert_eq!(config.director.imbalance_ratio, 2);
+    }
+
+    #[test]
+    fn test_shipped_balance_file_parses() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(BALANCE_CONFIG_PATH);
+        assert!(read_balance_file(&path).is_ok());
+    }
+}
@@ -1,8 +1,8 @@
+use crate::utils::{data_dir, DataKind};
 use bevy::prelude::*;
 use bevy::log::info;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
 
 // ==================== CONFIGURATION SYSTEM ====================
 
@@ -185,7 +185,6 @@ impl Default for AdvancedConfig {
 // ==================== CONFIGURATION MANAGEMENT ====================
 
 const CONFIG_FILE: &str = "config.json";
-const CONFIG_DIR: &str = ".culiacan-rts";
 
 impl GameConfig {
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
@@ -300,12 +299,7 @@ pub struct DifficultyModifiers {
 }
 
 fn get_config_path() -> std::path::PathBuf {
-    if let Some(home_dir) = dirs::home_dir() {
-        home_dir.join(CONFIG_DIR).join(CONFIG_FILE)
-    } else {
-        // Fallback to current directory
-        Path::new(CONFIG_FILE).to_path_buf()
-    }
+    data_dir(DataKind::Config).join(CONFIG_FILE)
 }
 
 // ==================== CONFIGURATION SYSTEM ====================
@@ -0,0 +1,136 @@
+use crate::utils::DECAL_CAPACITY;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ==================== GRAPHICS QUALITY ====================
+
+// How much the game draws, in one place. Every config profile carries a set
+// of graphics settings, normally one of the Low/Medium/High/Ultra presets,
+// and the active profile's are copied into the GraphicsSettings resource.
+// The particle pools, the decal layer, the weather, the street and
+// flashlight lighting and the pulsing sprites and campaign map animation all
+// take their budgets from that resource. The preset is picked on the accessibility page of the
+// main menu or with `config set quality` in the developer console.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GraphicsQuality {
+    Low,
+    Medium,
+    #[default]
+    High,
+    Ultra,
+}
+
+impl GraphicsQuality {
+    pub const ALL: [GraphicsQuality; 4] = [
+        GraphicsQuality::Low,
+        GraphicsQuality::Medium,
+        GraphicsQuality::High,
+        GraphicsQuality::Ultra,
+    ];
+
+    /// Console name, also the end of its localization key.
+    pub fn name(self) -> &'static str {
+        match self {
+            GraphicsQuality::Low => "low",
+            GraphicsQuality::Medium => "medium",
+            GraphicsQuality::High => "high",
+            GraphicsQuality::Ultra => "ultra",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|quality| quality.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|quality| *quality == self);
+        Self::ALL[index.map_or(0, |index| (index + 1) % Self::ALL.len())]
+    }
+
+    /// The preset's settings.
+    pub const fn settings(self) -> GraphicsSettings {
+        match self {
+            GraphicsQuality::Low => GraphicsSettings {
+                quality: self,
+                particle_pool_size: 150,
+                decal_capacity: 100,
+                weather_particles: 0.0,
+                dynamic_lighting: false,
+                ui_effects: false,
+            },
+            GraphicsQuality::Medium => GraphicsSettings {
+                quality: self,
+                particle_pool_size: 300,
+                decal_capacity: 300,
+                weather_particles: 0.5,
+                dynamic_lighting: true,
+                ui_effects: false,
+            },
+            GraphicsQuality::High => GraphicsSettings {
+                quality: self,
+                particle_pool_size: 500,
+                decal_capacity: DECAL_CAPACITY,
+                weather_particles: 1.0,
+                dynamic_lighting: true,
+                ui_effects: true,
+            },
+            GraphicsQuality::Ultra => GraphicsSettings {
+                quality: self,
+                particle_pool_size: 1000,
+                decal_capacity: 1500,
+                weather_particles: 1.5,
+                dynamic_lighting: true,
+                ui_effects: true,
+            },
+        }
+    }
+}
+
+/// Effect budgets every visual system reads.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphicsSettings {
+    pub quality: GraphicsQuality,  // The preset these started from
+    pub particle_pool_size: usize, // Combat particles and tracers alive at once
+    pub decal_capacity: usize,     // Blood, scorch marks and tire tracks kept on the ground
+    pub weather_particles: f32,    // Share of rain and fog drawn; 0 draws none
+    pub dynamic_lighting: bool,    // Streetlight pools and flashlight beams
+    pub ui_effects: bool,          // Pulsing unit sprites and the moving campaign map
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        GraphicsQuality::default().settings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_draw_more_at_each_step_up() {
+        let mut quality = GraphicsQuality::Low;
+        for _ in 1..GraphicsQuality::ALL.len() {
+            let (lower, higher) = (quality.settings(), quality.next().settings());
+            assert!(lower.particle_pool_size < higher.particle_pool_size);
+            assert!(lower.decal_capacity < higher.decal_capacity);
+            assert!(lower.weather_particles < higher.weather_particles);
+            assert!(higher.dynamic_lighting >= lower.dynamic_lighting);
+            assert!(higher.ui_effects >= lower.ui_effects);
+            quality = quality.next();
+        }
+        assert_eq!(quality, GraphicsQuality::Ultra);
+        assert_eq!(quality.next(), GraphicsQuality::Low);
+
+        assert_eq!(
+            GraphicsQuality::parse("Medium"),
+            Some(GraphicsQuality::Medium)
+        );
+        assert_eq!(GraphicsQuality::parse("epic"), None);
+        assert_eq!(GraphicsSettings::default().decal_capacity, DECAL_CAPACITY);
+    }
+}
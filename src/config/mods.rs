@@ -0,0 +1,543 @@
+use crate::campaign::MissionConfig;
+use crate::environmental_systems::WeatherType;
+use crate::localization::{current_language, flatten_strings};
+use crate::map::CityMap;
+use crate::objectives::MissionObjective;
+use crate::save::save_system::MissionId;
+use crate::ui::UnitAnimationDef;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// ==================== MOD LOADING ====================
+
+// Content packs live in assets/mods/<pack>/ next to a mod.toml manifest. A
+// pack can ship any of these, all optional:
+//   balance.toml              partial balance overrides, laid out like data/balance.toml
+//   units.toml                unit definition overrides by type, e.g. [Sicario] health
+//   unit_animations.toml      sprite sheets, replacing the base sheet per unit type
+//   missions.toml             [[mission]] overrides of names, timing, objectives,
+//                             pinned weather and the camera's neighborhood
+//   map.json                  a replacement city map
+//   localization/<lang>.toml  display strings by key, e.g. mission.InitialRaid.name,
+//                             for es-MX or just es
+//   scripts/*.rhai            mission scripts, loaded by the scripting runtime
+// Packs apply in ascending load_order (ties by id) so the later pack wins any
+// key two of them set. Every such overlap is logged and listed on the mods
+// screen. Packs are read once, on first use, since the balance and map loaders
+// run while the app is still being built.
+
+pub const MODS_DIR: &str = "assets/mods";
+const MANIFEST_FILE: &str = "mod.toml";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ModManifest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub load_order: i32,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MissionOverride {
+    pub id: MissionId,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub time_limit: Option<f32>,
+    pub enemy_spawn_rate: Option<f32>,
+    pub difficulty_modifier: Option<f32>,
+    pub start_time: Option<f32>,
+    pub base_play: Option<bool>,
+    pub weather: Option<WeatherType>,
+    pub camera_area: Option<String>,
+    pub objectives: Option<Vec<MissionObjective>>,
+}
+
+impl MissionOverride {
+    fn apply(&self, config: &mut MissionConfig) {
+        if let Some(name) = &self.name {
+            config.name = name.clone();
+        }
+        if let Some(description) = &self.description {
+            config.description = description.clone();
+        }
+        if let Some(time_limit) = self.time_limit {
+            config.time_limit = Some(time_limit);
+        }
+        if let Some(rate) = self.enemy_spawn_rate {
+            config.enemy_spawn_rate = rate;
+        }
+        if let Some(modifier) = self.difficulty_modifier {
+            config.difficulty_modifier = modifier;
+        }
+        if let Some(hour) = self.start_time {
+            config.start_time = Some(hour);
+        }
+        if let Some(base_play) = self.base_play {
+            config.base_play = base_play;
+        }
+        if let Some(weather) = self.weather {
+            config.weather = Some(weather);
+        }
+        if let Some(area) = &self.camera_area {
+            config.camera_area = Some(area.clone());
+        }
+        if let Some(objectives) = &self.objectives {
+            config.objectives = objectives.clone();
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MissionOverrideFile {
+    #[serde(default)]
+    mission: Vec<MissionOverride>,
+}
+
+#[derive(Deserialize)]
+struct UnitAnimationOverrideFile {
+    #[serde(default)]
+    unit: Vec<UnitAnimationDef>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ModPack {
+    pub manifest: ModManifest,
+    pub dir: PathBuf,
+    pub balance: Option<toml::Value>,
+    pub units: Option<toml::Value>,
+    pub unit_animations: Vec<UnitAnimationDef>,
+    pub missions: Vec<MissionOverride>,
+    pub map: Option<PathBuf>,
+    pub strings: HashMap<String, HashMap<String, String>>, // By language file name
+}
+
+impl ModPack {
+    pub fn new(manifest: ModManifest, dir: PathBuf) -> Self {
+        Self {
+            manifest,
+            dir,
+            balance: None,
+            units: None,
+            unit_animations: Vec::new(),
+            missions: Vec::new(),
+            map: None,
+            strings: HashMap::new(),
+        }
+    }
+
+    // Everything this pack sets, for conflict detection
+    fn content_keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        if let Some(balance) = &self.balance {
+            leaf_keys(balance, "balance", &mut keys);
+        }
+        if let Some(units) = &self.units {
+            leaf_keys(units, "units", &mut keys);
+        }
+        keys.extend(
+            self.unit_animations
+                .iter()
+                .map(|def| format!("unit animation {:?}", def.unit_type)),
+        );
+        keys.extend(
+            self.missions
+                .iter()
+                .map(|mission| format!("mission {:?}", mission.id)),
+        );
+        if self.map.is_some() {
+            keys.push("city map".to_string());
+        }
+        for (language, strings) in &self.strings {
+            keys.extend(
+                strings
+                    .keys()
+                    .map(|key| format!("string {} {}", language, key)),
+            );
+        }
+        keys
+    }
+
+    // One line for the mods screen
+    pub fn content_summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(balance) = &self.balance {
+            let mut keys = Vec::new();
+            leaf_keys(balance, "", &mut keys);
+            parts.push(format!("{} balance values", keys.len()));
+        }
+        if let Some(units) = &self.units {
+            let mut keys = Vec::new();
+            leaf_keys(units, "", &mut keys);
+            parts.push(format!("{} unit values", keys.len()));
+        }
+        if !self.unit_animations.is_empty() {
+            parts.push(format!("{} unit sheets", self.unit_animations.len()));
+        }
+        if !self.missions.is_empty() {
+            parts.push(format!("{} missions", self.missions.len()));
+        }
+        if self.map.is_some() {
+            parts.push("city map".to_string());
+        }
+        if !self.strings.is_empty() {
+            let count: usize = self.strings.values().map(HashMap::len).sum();
+            parts.push(format!("{} strings", count));
+        }
+        if parts.is_empty() {
+            "no content".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModConflict {
+    pub key: String,
+    pub mods: Vec<String>, // Pack ids in load order; the last one wins
+}
+
+impl ModConflict {
+    pub fn winner(&self) -> &str {
+        self.mods.last().map_or("", String::as_str)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ModRegistry {
+    pub active: Vec<ModPack>, // In load order
+    pub disabled: Vec<ModManifest>,
+    pub errors: Vec<String>, // Packs that failed to load
+    pub conflicts: Vec<ModConflict>,
+}
+
+impl ModRegistry {
+    pub fn from_packs(mut packs: Vec<ModPack>, mut errors: Vec<String>) -> Self {
+        packs.sort_by(|a, b| {
+            (a.manifest.load_order, &a.manifest.id).cmp(&(b.manifest.load_order, &b.manifest.id))
+        });
+
+        let mut registry = ModRegistry::default();
+        for pack in packs {
+            let duplicate = registry
+                .active
+                .iter()
+                .map(|active| &active.manifest.id)
+                .chain(registry.disabled.iter().map(|manifest| &manifest.id))
+                .any(|id| *id == pack.manifest.id);
+            if duplicate {
+                errors.push(format!(
+                    "{}: another pack already uses the id '{}'",
+                    pack.dir.display(),
+                    pack.manifest.id
+                ));
+            } else if pack.manifest.enabled {
+                registry.active.push(pack);
+            } else {
+                registry.disabled.push(pack.manifest);
+            }
+        }
+
+        registry.errors = errors;
+        registry.conflicts = find_conflicts(&registry.active);
+        registry
+    }
+
+    pub fn apply_balance_overrides(&self, base: &mut toml::Value) {
+        for balance in self.active.iter().filter_map(|pack| pack.balance.as_ref()) {
+            merge_toml(base, balance);
+        }
+    }
+
+    // `catalog` is the unit catalog as a table with a `units` map by type name
+    pub fn apply_unit_overrides(&self, catalog: &mut toml::Value) {
+        for units in self.active.iter().filter_map(|pack| pack.units.as_ref()) {
+            let mut table = toml::Table::new();
+            table.insert("units".to_string(), units.clone());
+            merge_toml(catalog, &toml::Value::Table(table));
+        }
+    }
+
+    pub fn unit_animation_overrides(&self) -> impl Iterator<Item = &UnitAnimationDef> {
+        self.active.iter().flat_map(|pack| &pack.unit_animations)
+    }
+
+    pub fn apply_mission_overrides(&self, config: &mut MissionConfig) {
+        let id = config.id.clone();
+        for mission in self
+            .active
+            .iter()
+            .flat_map(|pack| &pack.missions)
+            .filter(|mission| mission.id == id)
+        {
+            mission.apply(config);
+        }
+
+        // Translations go over whatever text the packs settled on
+        let language = current_language();
+        if let Some(name) = self.localize(&language, &format!("mission.{:?}.name", id)) {
+            config.name = name.to_string();
+        }
+        let description_key = format!("mission.{:?}.description", id);
+        if let Some(description) = self.localize(&language, &description_key) {
+            config.description = description.to_string();
+        }
+    }
+
+    pub fn map_path(&self) -> Option<&Path> {
+        self.active
+            .iter()
+            .rev()
+            .find_map(|pack| pack.map.as_deref())
+    }
+
+    // A pack's es.toml covers es-MX too; an exact match wins within a pack
+    pub fn localize(&self, language: &str, key: &str) -> Option<&str> {
+        let primary = language.split('-').next().unwrap_or(language);
+        self.active
+            .iter()
+            .rev()
+            .find_map(|pack| {
+                [language, primary]
+                    .iter()
+                    .find_map(|language| pack.strings.get(*language)?.get(key))
+            })
+            .map(String::as_str)
+    }
+
+    fn log_summary(&self) {
+        for pack in &self.active {
+            info!(
+                "🧩 Mod '{}' {} loaded ({})",
+                pack.manifest.name,
+                pack.manifest.version,
+                pack.content_summary()
+            );
+        }
+        for error in &self.errors {
+            warn!("Mod not loaded: {}", error);
+        }
+        for conflict in &self.conflicts {
+            warn!(
+                "Mod conflict on {}: {} (using {})",
+                conflict.key,
+                conflict.mods.join(", "),
+                conflict.winner()
+            );
+        }
+    }
+}
+
+// The installed packs, scanned on first use
+pub fn mod_registry() -> &'static ModRegistry {
+    static REGISTRY: OnceLock<ModRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let registry = load_mods(Path::new(MODS_DIR));
+        registry.log_summary();
+        registry
+    })
+}
+
+pub fn load_mods(dir: &Path) -> ModRegistry {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return ModRegistry::default();
+    };
+
+    let mut packs = Vec::new();
+    let mut errors = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.join(MANIFEST_FILE).is_file() {
+            continue;
+        }
+        match read_mod_pack(&path) {
+            Ok(pack) => packs.push(pack),
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    ModRegistry::from_packs(packs, errors)
+}
+
+fn read_mod_pack(dir: &Path) -> Result<ModPack, Box<dyn std::error::Error>> {
+    let manifest: ModManifest = toml::from_str(&fs::read_to_string(dir.join(MANIFEST_FILE))?)?;
+    let mut pack = ModPack::new(manifest, dir.to_path_buf());
+
+    let balance_path = dir.join("balance.toml");
+    if balance_path.is_file() {
+        pack.balance = Some(toml::from_str(&fs::read_to_string(balance_path)?)?);
+    }
+    let units_path = dir.join("units.toml");
+    if units_path.is_file() {
+        pack.units = Some(toml::from_str(&fs::read_to_string(units_path)?)?);
+    }
+
+    let animations_path = dir.join("unit_animations.toml");
+    if animations_path.is_file() {
+        let file: UnitAnimationOverrideFile =
+            toml::from_str(&fs::read_to_string(animations_path)?)?;
+        pack.unit_animations = file.unit;
+    }
+
+    let missions_path = dir.join("missions.toml");
+    if missions_path.is_file() {
+        let file: MissionOverrideFile = toml::from_str(&fs::read_to_string(missions_path)?)?;
+        pack.missions = file.mission;
+    }
+
+    // Check the map now rather than falling back to an empty battlefield later
+    let map_path = dir.join("map.json");
+    if map_path.is_file() {
+        CityMap::from_json(&fs::read_to_string(&map_path)?)?;
+        pack.map = Some(map_path);
+    }
+
+    // Every language the pack ships, so switching language needs no reload
+    if let Ok(entries) = fs::read_dir(dir.join("localization")) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let Some(language) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "toml")
+            {
+                let strings: toml::Value = toml::from_str(&fs::read_to_string(&path)?)?;
+                pack.strings
+                    .insert(language.to_string(), flatten_strings(&strings));
+            }
+        }
+    }
+
+    Ok(pack)
+}
+
+// Tables merge key by key; anything else in the overlay replaces the base value
+pub fn merge_toml(base: &mut toml::Value, overlay: &toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+// Dotted paths of every non-table value, e.g. balance.director.base_spawn_interval
+fn leaf_keys(value: &toml::Value, prefix: &str, keys: &mut Vec<String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                leaf_keys(value, &path, keys);
+            }
+        }
+        _ => keys.push(prefix.to_string()),
+    }
+}
+
+fn find_conflicts(packs: &[ModPack]) -> Vec<ModConflict> {
+    let mut owners: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for pack in packs {
+        for key in pack.content_keys() {
+            owners
+                .entry(key)
+                .or_default()
+                .push(pack.manifest.id.clone());
+        }
+    }
+    owners
+        .into_iter()
+        .filter(|(_, mods)| mods.len() > 1)
+        .map(|(key, mods)| ModConflict { key, mods })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack(id: &str, load_order: i32, balance: &str) -> ModPack {
+        let manifest = ModManifest {
+            id: id.to_string(),
+            name: id.to_string(),
+            version: "1.0".to_string(),
+            author: String::new(),
+            description: String::new(),
+            load_order,
+            enabled: true,
+        };
+        let mut pack = ModPack::new(manifest, PathBuf::from(id));
+        pack.balance = Some(toml::from_str(balance).unwrap());
+        pack
+    }
+
+    #[test]
+    fn test_later_packs_win_and_conflicts_are_reported() {
+        let registry = ModRegistry::from_packs(
+            vec![
+                pack("late", 20, "[director]\nbase_spawn_interval = 40.0"),
+                pack(
+                    "early",
+                    10,
+                    "[director]\nbase_spawn_interval = 50.0\nweak_modifier = 0.8",
+                ),
+            ],
+            Vec::new(),
+        );
+        assert_eq!(registry.active[0].manifest.id, "early");
+
+        let mut balance: toml::Value =
+            toml::from_str("[director]\nbase_spawn_interval = 60.0\nstrong_modifier = 1.2")
+                .unwrap();
+        registry.apply_balance_overrides(&mut balance);
+        let director = &balance["director"];
+        assert_eq!(director["base_spawn_interval"].as_float(), Some(40.0));
+        assert_eq!(director["weak_modifier"].as_float(), Some(0.8));
+        assert_eq!(director["strong_modifier"].as_float(), Some(1.2));
+
+        assert_eq!(registry.conflicts.len(), 1);
+        assert_eq!(
+            registry.conflicts[0].key,
+            "balance.director.base_spawn_interval"
+        );
+        assert_eq!(registry.conflicts[0].winner(), "late");
+    }
+
+    #[test]
+    fn test_example_pack_loads() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(MODS_DIR);
+        let registry = load_mods(&dir);
+        assert!(registry.errors.is_empty(), "{:?}", registry.errors);
+        assert!(registry
+            .disabled
+            .iter()
+            .any(|manifest| manifest.id == "example_pack"));
+    }
+}
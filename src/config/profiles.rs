@@ -0,0 +1,451 @@
+use super::graphics::{GraphicsQuality, GraphicsSettings};
+use crate::app_state::AppState;
+use crate::campaign::{Campaign, MissionConfig};
+use crate::environmental_systems::{EnvironmentalState, WeatherType};
+use crate::map::CityMap;
+use crate::resources::GameState;
+use crate::utils::{data_dir, DataKind, DecalLayer, EntityPools, ParticlePool};
+use bevy::prelude::*;
+use bevy::window::PresentMode;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+// ==================== CONFIG PROFILES ====================
+
+// Named sets of display settings to switch between: "potato PC" for old
+// laptops, "cinematic" for recording, "standard" in between. Each holds a
+// graphics quality preset, tweakable setting by setting, and how the frame
+// rate is paced in the foreground and the background. They live in
+// config_profiles.json in the config directory along with which one is
+// active. Shift+F10 cycles them; the developer console's config command
+// lists, switches and tweaks them and saves the result under a new name.
+//
+// Missions can pin settings of their own - weather that holds for the whole
+// mission, or a camera kept over one neighborhood. Those go on as the mission
+// starts and come off when it ends, leaving the weather as it was before.
+
+const CONFIG_PROFILES_FILE: &str = "config_profiles.json";
+const CAMERA_AREA_MARGIN: f32 = 150.0; // How far past a pinned neighborhood's edge the camera may pan
+const PINNED_WEATHER_INTENSITY: f32 = 0.8;
+const FPS_CAP_STEPS: [u32; 5] = [0, 30, 60, 120, 144]; // What the settings page cycles through
+
+pub const CONFIG_SETTINGS: [&str; 7] = [
+    "vsync",
+    "quality",
+    "particles",
+    "decals",
+    "fps",
+    "background_fps",
+    "background_pause",
+];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)] // Profiles saved before a setting existed get its default
+pub struct ConfigProfile {
+    pub name: String,
+    pub vsync: bool,
+    #[serde(flatten)] // Keeps the settings at the top level, where they were before presets
+    pub graphics: GraphicsSettings,
+    pub fps_cap: u32,           // 0 leaves the frame rate to vsync
+    pub background_fps: u32,    // Frames drawn while the window is in the background
+    pub background_pause: bool, // Single-player battles pause while the window is in the background
+}
+
+// Stands in if the active name matches no profile
+static FALLBACK_PROFILE: ConfigProfile = ConfigProfile {
+    name: String::new(),
+    vsync: true,
+    graphics: GraphicsQuality::High.settings(),
+    fps_cap: 0,
+    background_fps: 10,
+    background_pause: true,
+};
+
+impl Default for ConfigProfile {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl ConfigProfile {
+    pub fn standard() -> Self {
+        Self {
+            name: "standard".to_string(),
+            ..FALLBACK_PROFILE.clone()
+        }
+    }
+
+    pub fn potato() -> Self {
+        Self {
+            name: "potato PC".to_string(),
+            vsync: false, // A slow frame isn't held back to the next refresh
+            graphics: GraphicsQuality::Low.settings(),
+            fps_cap: 30,
+            background_fps: 5,
+            ..FALLBACK_PROFILE.clone()
+        }
+    }
+
+    pub fn cinematic() -> Self {
+        Self {
+            name: "cinematic".to_string(),
+            graphics: GraphicsQuality::Ultra.settings(),
+            ..FALLBACK_PROFILE.clone()
+        }
+    }
+
+    /// Changes one setting by its console name.
+    pub fn set(&mut self, setting: &str, value: &str) -> Result<(), String> {
+        let number = || {
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|number| *number >= 0.0)
+                .ok_or_else(|| format!("'{}' is not a positive number", value))
+        };
+        let switch = || match value.to_lowercase().as_str() {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            _ => Err(format!("expected on or off, got '{}'", value)),
+        };
+        match setting.to_lowercase().as_str() {
+            "vsync" => self.vsync = switch()?,
+            "quality" => {
+                self.graphics = GraphicsQuality::parse(value)
+                    .ok_or_else(|| format!("expected low, medium, high or ultra, got '{}'", value))?
+                    .settings()
+            }
+            "particles" => self.graphics.weather_particles = number()?.min(3.0),
+            "decals" => self.graphics.decal_capacity = number()? as usize,
+            "fps" if value.eq_ignore_ascii_case("off") => self.fps_cap = 0,
+            "fps" => self.fps_cap = number()? as u32,
+            "background_fps" => self.background_fps = (number()? as u32).max(1),
+            "background_pause" => self.background_pause = switch()?,
+            other => return Err(format!("unknown setting '{}'", other)),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigProfiles {
+    pub active: String,
+    pub profiles: Vec<ConfigProfile>,
+}
+
+impl Default for ConfigProfiles {
+    fn default() -> Self {
+        Self {
+            active: ConfigProfile::standard().name,
+            profiles: vec![
+                ConfigProfile::standard(),
+                ConfigProfile::potato(),
+                ConfigProfile::cinematic(),
+            ],
+        }
+    }
+}
+
+impl ConfigProfiles {
+    fn find(&self, name: &str) -> Option<usize> {
+        self.profiles
+            .iter()
+            .position(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn active(&self) -> &ConfigProfile {
+        self.find(&self.active)
+            .map_or(&FALLBACK_PROFILE, |index| &self.profiles[index])
+    }
+
+    pub fn switch(&mut self, name: &str) -> Result<(), String> {
+        let index = self
+            .find(name)
+            .ok_or_else(|| format!("no config profile named '{}'", name))?;
+        self.active = self.profiles[index].name.clone();
+        Ok(())
+    }
+
+    /// Switches to the profile after the active one, returning its name.
+    pub fn cycle(&mut self) -> &str {
+        let next = self
+            .find(&self.active)
+            .map_or(0, |index| (index + 1) % self.profiles.len());
+        if let Some(profile) = self.profiles.get(next) {
+            self.active = profile.name.clone();
+        }
+        &self.active
+    }
+
+    /// Changes a setting of the active profile.
+    pub fn set(&mut self, setting: &str, value: &str) -> Result<(), String> {
+        let index = self
+            .find(&self.active)
+            .ok_or("the active config profile is missing")?;
+        self.profiles[index].set(setting, value)
+    }
+
+    /// Puts the active profile on the next quality preset, returning it.
+    pub fn cycle_quality(&mut self) -> Result<GraphicsQuality, String> {
+        let next = self.active().graphics.quality.next();
+        self.set("quality", next.name())?;
+        Ok(next)
+    }
+
+    /// Puts the active profile on the next frame rate cap, returning it.
+    pub fn cycle_fps_cap(&mut self) -> Result<u32, String> {
+        let current = self.active().fps_cap;
+        let next = FPS_CAP_STEPS
+            .into_iter()
+            .find(|cap| *cap > current)
+            .unwrap_or(0);
+        self.set("fps", &next.to_string())?;
+        Ok(next)
+    }
+
+    /// Keeps the active profile's settings under `name`, replacing any
+    /// profile already called that, and switches to it.
+    pub fn save_as(&mut self, name: &str) {
+        let profile = ConfigProfile {
+            name: name.to_string(),
+            ..self.active().clone()
+        };
+        match self.find(name) {
+            Some(index) => self.profiles[index] = profile,
+            None => self.profiles.push(profile),
+        }
+        self.active = name.to_string();
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = data_dir(DataKind::Config).join(CONFIG_PROFILES_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+pub fn load_config_profiles() -> ConfigProfiles {
+    let path = data_dir(DataKind::Config).join(CONFIG_PROFILES_FILE);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return ConfigProfiles::default();
+    };
+    match serde_json::from_str::<ConfigProfiles>(&contents) {
+        Ok(mut profiles) => {
+            // The built-in profiles come back if a hand edit dropped them
+            for builtin in ConfigProfiles::default().profiles {
+                if profiles.find(&builtin.name).is_none() {
+                    profiles.profiles.push(builtin);
+                }
+            }
+            info!("⚙️ Config profile: {}", profiles.active().name);
+            profiles
+        }
+        Err(e) => {
+            warn!("Failed to read {:?}: {}, using defaults", path, e);
+            ConfigProfiles::default()
+        }
+    }
+}
+
+// ==================== MISSION OVERRIDES ====================
+
+/// Settings the mission being played has pinned.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct MissionOverrides {
+    pub weather: Option<WeatherType>,
+    pub camera_bounds: Option<Rect>,
+    weather_before: Option<(WeatherType, f32)>, // Put back when the mission ends
+}
+
+impl MissionOverrides {
+    pub fn clamp_camera(&self, position: Vec3) -> Vec3 {
+        match self.camera_bounds {
+            Some(bounds) => position
+                .truncate()
+                .clamp(bounds.min, bounds.max)
+                .extend(position.z),
+            None => position,
+        }
+    }
+
+    fn apply(&mut self, mission: &MissionConfig, city_map: &CityMap, env: &mut EnvironmentalState) {
+        if let Some(weather) = mission.weather {
+            self.weather_before = Some((env.weather_type, env.weather_intensity));
+            env.weather_type = weather;
+            env.weather_intensity = if weather == WeatherType::Clear {
+                0.0
+            } else {
+                PINNED_WEATHER_INTENSITY
+            };
+            env.update_gameplay_modifiers();
+        }
+        self.weather = mission.weather;
+        self.camera_bounds = mission
+            .camera_area
+            .as_deref()
+            .and_then(|area| city_map.find_neighborhood(area))
+            .and_then(|neighborhood| city_map.neighborhood_bounds(neighborhood.key))
+            .map(|bounds| bounds.inset(CAMERA_AREA_MARGIN));
+    }
+
+    fn revert(&mut self, env: &mut EnvironmentalState) {
+        if let Some((weather, intensity)) = self.weather_before.take() {
+            env.weather_type = weather;
+            env.weather_intensity = intensity;
+            env.update_gameplay_modifiers();
+        }
+        self.weather = None;
+        self.camera_bounds = None;
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+// Puts the active profile's settings into effect whenever it changes
+#[allow(clippy::too_many_arguments)]
+pub fn apply_config_profile_system(
+    mut commands: Commands,
+    profiles: Res<ConfigProfiles>,
+    mut graphics: ResMut<GraphicsSettings>,
+    decals: Option<ResMut<DecalLayer>>,
+    particle_pool: Option<ResMut<ParticlePool>>,
+    entity_pools: Option<ResMut<EntityPools>>,
+    mut windows: Query<&mut Window>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    let profile = profiles.active();
+    if *graphics != profile.graphics {
+        *graphics = profile.graphics;
+    }
+
+    let present_mode = if profile.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    for mut window in windows.iter_mut() {
+        if window.present_mode != present_mode {
+            window.present_mode = present_mode;
+        }
+    }
+    if let Some(mut decals) = decals {
+        for entity in decals.resize(graphics.decal_capacity) {
+            commands.entity(entity).despawn();
+        }
+    }
+    // Effects already in flight finish; new ones are dropped past the limit
+    if let Some(mut pool) = particle_pool {
+        pool.max_pool_size = graphics.particle_pool_size;
+    }
+    if let Some(mut pools) = entity_pools {
+        pools.tracers.set_max_size(graphics.particle_pool_size);
+    }
+}
+
+// Missions that pin settings put them on as they start and take them off as they end
+pub fn mission_overrides_system(
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    city_map: Res<CityMap>,
+    mut overrides: ResMut<MissionOverrides>,
+    mut env_state: ResMut<EnvironmentalState>,
+    mut was_in_game: Local<bool>,
+) {
+    let in_game = game_state.game_phase.app_state() == AppState::InGame;
+    let started = in_game && !*was_in_game;
+    let ended = !in_game && *was_in_game;
+    *was_in_game = in_game;
+
+    if ended {
+        overrides.revert(&mut env_state);
+    }
+    if started {
+        let mission = MissionConfig::get_mission_config(&campaign.progress.current_mission);
+        overrides.apply(&mission, &city_map, &mut env_state);
+        if let Some(weather) = overrides.weather {
+            info!("🌤️ {} holds the weather at {:?}", mission.name, weather);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::save_system::MissionId;
+
+    #[test]
+    fn test_profiles_switch_cycle_and_save_under_new_names() {
+        let mut profiles = ConfigProfiles::default();
+        assert_eq!(profiles.active().name, "standard");
+
+        profiles.switch("Potato pc").unwrap();
+        assert_eq!(profiles.active, "potato PC");
+        assert_eq!(profiles.active().graphics.weather_particles, 0.0);
+        assert_eq!(profiles.cycle_quality(), Ok(GraphicsQuality::Medium));
+        assert_eq!(
+            profiles.active().graphics,
+            GraphicsQuality::Medium.settings()
+        );
+        assert!(profiles.switch("ultra").is_err());
+
+        profiles.set("decals", "250").unwrap();
+        profiles.set("vsync", "on").unwrap();
+        assert!(profiles.set("particles", "lots").is_err());
+        assert!(profiles.set("quality", "epic").is_err());
+        assert_eq!(profiles.active().fps_cap, 30);
+        assert_eq!(profiles.cycle_fps_cap(), Ok(60));
+        profiles.set("fps", "200").unwrap();
+        assert_eq!(profiles.cycle_fps_cap(), Ok(0));
+        profiles.set("background_pause", "off").unwrap();
+        profiles.set("background_fps", "0").unwrap();
+        assert_eq!(profiles.active().background_fps, 1);
+        profiles.save_as("old laptop");
+        assert_eq!(profiles.active().graphics.decal_capacity, 250);
+        assert_eq!(profiles.active().graphics.quality, GraphicsQuality::Medium);
+        assert!(profiles.active().vsync);
+        assert!(!profiles.active().background_pause);
+        assert_eq!(profiles.profiles.len(), 4);
+
+        assert_eq!(profiles.cycle(), "standard");
+        assert_eq!(profiles.cycle(), "potato PC");
+
+        profiles.active = "deleted".to_string();
+        assert_eq!(profiles.active(), &FALLBACK_PROFILE);
+        assert_eq!(profiles.cycle(), "standard");
+    }
+
+    #[test]
+    fn test_pinned_weather_comes_off_when_the_mission_ends() {
+        let mut env = EnvironmentalState {
+            weather_type: WeatherType::HeavyRain,
+            weather_intensity: 0.6,
+            ..default()
+        };
+        let mut mission = MissionConfig::get_mission_config(&MissionId::InitialRaid);
+        mission.weather = Some(WeatherType::Clear);
+        mission.camera_area = None;
+        let mut overrides = MissionOverrides::default();
+
+        overrides.apply(&mission, &CityMap::default(), &mut env);
+        assert_eq!(env.weather_type, WeatherType::Clear);
+        assert_eq!(overrides.weather, Some(WeatherType::Clear));
+        assert_eq!(overrides.clamp_camera(Vec3::X), Vec3::X);
+
+        overrides.revert(&mut env);
+        assert_eq!(env.weather_type, WeatherType::HeavyRain);
+        assert_eq!(env.weather_intensity, 0.6);
+        assert_eq!(overrides.weather, None);
+
+        overrides.camera_bounds = Some(Rect::new(-10.0, -10.0, 10.0, 10.0));
+        assert_eq!(
+            overrides.clamp_camera(Vec3::new(50.0, -3.0, 999.0)),
+            Vec3::new(10.0, -3.0, 999.0)
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::components::*;
+use crate::config::mod_registry;
+use crate::unit_systems::{apply_base_stats, apply_weapon_upgrades};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// ==================== UNIT CATALOG ====================
+
+pub const UNIT_CATALOG_PATH: &str = "assets/data/units.ron";
+// Shipped copy, for types the file on disk leaves out or when it won't parse
+const BUILTIN_UNIT_CATALOG: &str = include_str!("../../assets/data/units.ron");
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AbilityDefinition {
+    pub ability: AbilityType,
+    pub cooldown: f32,
+    pub range: f32,
+    pub energy_cost: u32,
+}
+
+impl AbilityDefinition {
+    pub fn to_component(&self) -> UnitAbility {
+        UnitAbility {
+            ability_type: self.ability.clone(),
+            cooldown: Timer::from_seconds(self.cooldown, TimerMode::Once),
+            range: self.range,
+            energy_cost: self.energy_cost,
+        }
+    }
+}
+
+// Everything about a unit type before upgrades and veterancy are applied
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnitDefinition {
+    pub health: f32,
+    pub damage: f32,
+    pub range: f32,
+    pub movement_speed: f32,
+    pub vision: f32,
+    pub armor: ArmorType,
+    pub weapon: WeaponType,
+    #[serde(default)]
+    pub upgrades: Vec<UpgradeType>,
+    #[serde(default)]
+    pub abilities: Vec<AbilityDefinition>,
+    #[serde(default)]
+    pub engineering: Vec<EngineeringAction>,
+    #[serde(default)]
+    pub area_denial: Option<AreaDenialKind>,
+    pub sprite: String, // Asset path
+    pub cost: u32,
+}
+
+impl UnitDefinition {
+    pub fn equipment(&self) -> Equipment {
+        Equipment {
+            weapon: self.weapon.clone(),
+            armor: self.armor.clone(),
+            upgrades: self.upgrades.clone(),
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct UnitCatalog {
+    pub units: HashMap<UnitType, UnitDefinition>,
+}
+
+impl Default for UnitCatalog {
+    fn default() -> Self {
+        ron::from_str(BUILTIN_UNIT_CATALOG).expect("the shipped units.ron must parse")
+    }
+}
+
+impl UnitCatalog {
+    pub fn definition(&self, unit_type: &UnitType) -> &UnitDefinition {
+        // Loading fills in every type the file leaves out
+        &self.units[unit_type]
+    }
+
+    pub fn sprite_paths(&self) -> impl Iterator<Item = &str> {
+        self.units
+            .values()
+            .map(|definition| definition.sprite.as_str())
+    }
+}
+
+pub fn load_unit_catalog() -> UnitCatalog {
+    match read_unit_catalog_file(Path::new(UNIT_CATALOG_PATH)) {
+        Ok(catalog) => {
+            info!("🪖 Unit catalog loaded from {}", UNIT_CATALOG_PATH);
+            catalog
+        }
+        Err(e) => {
+            warn!("Failed to load unit catalog: {}, using built-in units", e);
+            UnitCatalog::default()
+        }
+    }
+}
+
+fn read_unit_catalog_file(path: &Path) -> Result<UnitCatalog, Box<dyn std::error::Error>> {
+    let mut catalog: UnitCatalog = ron::from_str(&fs::read_to_string(path)?)?;
+    for (unit_type, definition) in UnitCatalog::default().units {
+        catalog.units.entry(unit_type).or_insert(definition);
+    }
+
+    // Mod packs override single fields, keyed by unit type
+    let mut catalog = toml::Value::try_from(catalog)?;
+    mod_registry().apply_unit_overrides(&mut catalog);
+    Ok(catalog.try_into()?)
+}
+
+pub fn reload_unit_catalog(catalog: &mut UnitCatalog) {
+    // Keep the current definitions if the edited file doesn't parse
+    match read_unit_catalog_file(Path::new(UNIT_CATALOG_PATH)) {
+        Ok(loaded) => {
+            *catalog = loaded;
+            info!("🪖 Unit catalog reloaded");
+        }
+        Err(e) => warn!("Unit catalog not reloaded: {}", e),
+    }
+}
+
+pub fn apply_unit_catalog_system(
+    catalog: Res<UnitCatalog>,
+    mut units: Query<(&mut Unit, &mut Movement, Option<&mut PathfindingAgent>)>,
+) {
+    // Units spawned after a reload already pick up the new values
+    if !catalog.is_changed() || catalog.is_added() {
+        return;
+    }
+
+    for (mut unit, mut movement, pathfinding) in units.iter_mut() {
+        let health_fraction = if unit.max_health > 0.0 {
+            unit.health / unit.max_health
+        } else {
+            1.0
+        };
+
+        let mut rebalanced = unit.clone();
+        apply_base_stats(&mut rebalanced, catalog.definition(&unit.unit_type));
+        // Ovidio's loadout is already baked into his stats (see spawn_ovidio)
+        if unit.unit_type != UnitType::Ovidio {
+            apply_weapon_upgrades(&mut rebalanced);
+        }
+
+        unit.max_health = rebalanced.max_health;
+        unit.health = rebalanced.max_health * health_fraction;
+        unit.damage = rebalanced.damage;
+        unit.range = rebalanced.range;
+        unit.vision = rebalanced.vision;
+        unit.movement_speed = rebalanced.movement_speed;
+        movement.speed = rebalanced.movement_speed;
+        if let Some(mut pathfinding) = pathfinding {
+            pathfinding.max_speed = rebalanced.movement_speed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_catalog_defines_every_unit_type() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(UNIT_CATALOG_PATH);
+        let shipped: UnitCatalog = ron::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(shipped.units.len(), 13);
+
+        let engineer = shipped.definition(&UnitType::Engineer);
+        assert_eq!(engineer.abilities.len(), 2);
+        assert!(engineer.engineering.contains(&EngineeringAction::Breach));
+        assert_eq!(
+            shipped.definition(&UnitType::Soldier).area_denial,
+            Some(AreaDenialKind::TearGas)
+        );
+        assert_eq!(engineer.armor, ArmorType::TacticalVest);
+        assert_eq!(
+            shipped.definition(&UnitType::Roadblock).armor,
+            ArmorType::None
+        );
+    }
+
+    #[test]
+    fn test_catalog_survives_toml_overrides() {
+        let mut catalog = toml::Value::try_from(UnitCatalog::default()).unwrap();
+        let overrides: toml::Value = toml::from_str("[units.Tank]\nhealth = 450.0").unwrap();
+        crate::config::merge_toml(&mut catalog, &overrides);
+
+        let catalog: UnitCatalog = catalog.try_into().unwrap();
+        let tank = catalog.definition(&UnitType::Tank);
+        assert_eq!(tank.health, 450.0);
+        assert_eq!(tank.damage, 100.0);
+        assert_eq!(tank.abilities[0].ability, AbilityType::TankShell);
+    }
+}
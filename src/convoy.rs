@@ -0,0 +1,366 @@
+use crate::app_state::{AppState, GameSet};
+use crate::campaign::{Campaign, MissionConfig};
+use crate::components::*;
+use crate::config::{BalanceConfig, ConvoyBalance, UnitCatalog};
+use crate::diplomacy::Diplomacy;
+use crate::localization::{tr, tr_args};
+use crate::map::{area_center, CityMap, TileKind};
+use crate::resources::{ConvoyStatus, GameAssets, GameState};
+use crate::spawners::spawn_unit;
+use crate::utils::{iso_to_world, play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== CONVOY ESCORT PLUGIN ====================
+
+// Missions with an EscortConvoy objective send a column of cartel vehicles
+// down the road from where Ovidio holds out to the target area. The vehicles
+// keep to the roads and take no orders; the player's job is to screen them,
+// since the column stops dead while any enemy is close to one of its
+// vehicles. Army ambushes wait at points spread along the route and spring
+// ahead of the lead vehicle as it passes. How far the column has come and
+// how much of it is left go into GameState for the objective: it's met once
+// every vehicle still running is through, and failed once the column is too
+// shot up to go on.
+
+// Where the column sets out when the map marks no cartel spawn point
+const FALLBACK_START: Vec2 = Vec2::ZERO;
+const AMBUSH_SPREAD: f32 = 32.0; // Between soldiers of one ambush, along the road
+
+pub struct ConvoyPlugin;
+
+impl Plugin for ConvoyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Convoy>()
+            .add_systems(OnEnter(AppState::InGame), convoy_setup_system)
+            .add_systems(
+                FixedUpdate,
+                convoy_system
+                    .run_if(resource_exists::<GameAssets>())
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Convoy {
+    pub route: Vec<Vec2>,   // Turning points along the road, start to finish
+    pub ambushes: Vec<f32>, // Distances along the route with an ambush still waiting
+    pub starting_health: f32,
+    pub halted: bool,
+}
+
+/// Length of the road from the start to the end of the route.
+pub fn route_length(route: &[Vec2]) -> f32 {
+    route.windows(2).map(|leg| leg[0].distance(leg[1])).sum()
+}
+
+/// How far along the route a vehicle headed for `next_waypoint` has come.
+pub fn distance_along(route: &[Vec2], next_waypoint: usize, position: Vec2) -> f32 {
+    if next_waypoint == 0 {
+        return 0.0;
+    }
+    if next_waypoint >= route.len() {
+        return route_length(route);
+    }
+    route_length(&route[..next_waypoint]) + route[next_waypoint - 1].distance(position)
+}
+
+/// The point `distance` along the route, and which way the road runs
+/// there. Past the end, the end.
+pub fn point_along(route: &[Vec2], distance: f32) -> (Vec2, Vec2) {
+    let mut left = distance.max(0.0);
+    for leg in route.windows(2) {
+        let length = leg[0].distance(leg[1]);
+        let heading = (leg[1] - leg[0]).normalize_or_zero();
+        if left <= length {
+            return (leg[0] + heading * left, heading);
+        }
+        left -= length;
+    }
+    let heading = match route {
+        [.., before, last] => (*last - *before).normalize_or_zero(),
+        _ => Vec2::X,
+    };
+    (route.last().copied().unwrap_or_default(), heading)
+}
+
+/// Where along the route the ambushes wait, spread evenly between the ends.
+pub fn ambush_distances(route: &[Vec2], count: usize) -> Vec<f32> {
+    let length = route_length(route);
+    (1..=count)
+        .map(|i| length * i as f32 / (count + 1) as f32)
+        .collect()
+}
+
+/// Where the soldiers of an ambush sprung `distance` along the route wait:
+/// further down the road, on alternate sides of it.
+pub fn ambush_positions(route: &[Vec2], distance: f32, balance: &ConvoyBalance) -> Vec<Vec2> {
+    (0..balance.ambush_size)
+        .map(|i| {
+            let ahead = balance.ambush_offset + (i / 2) as f32 * AMBUSH_SPREAD;
+            let (point, heading) = point_along(route, distance + ahead);
+            let flank = if i % 2 == 0 { 1.0 } else { -1.0 };
+            point + heading.perp() * balance.ambush_offset * flank
+        })
+        .collect()
+}
+
+/// Sends the column out along `route` and lays its ambushes.
+pub fn spawn_convoy(
+    commands: &mut Commands,
+    route: Vec<Vec2>,
+    balance: &ConvoyBalance,
+    game_assets: &Res<GameAssets>,
+    catalog: &UnitCatalog,
+) -> Convoy {
+    let start = route[0];
+    let back = route
+        .get(1)
+        .map_or(Vec2::NEG_X, |next| (start - *next).normalize_or_zero());
+    for i in 0..balance.vehicles {
+        let position = start + back * balance.spacing * i as f32;
+        let entity = spawn_unit(
+            commands,
+            UnitType::Vehicle,
+            Faction::Cartel,
+            iso_to_world(position.extend(0.0)),
+            game_assets,
+            catalog,
+        );
+        commands
+            .entity(entity)
+            .remove::<(Movement, PathfindingAgent)>()
+            .insert(ConvoyVehicle { next_waypoint: 1 });
+    }
+
+    Convoy {
+        ambushes: ambush_distances(&route, balance.ambush_count),
+        starting_health: catalog.definition(&UnitType::Vehicle).health * balance.vehicles as f32,
+        halted: false,
+        route,
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+#[allow(clippy::too_many_arguments)]
+pub fn convoy_setup_system(
+    mut commands: Commands,
+    campaign: Res<Campaign>,
+    city_map: Res<CityMap>,
+    balance: Res<BalanceConfig>,
+    game_assets: Option<Res<GameAssets>>,
+    catalog: Res<UnitCatalog>,
+    mut convoy: ResMut<Convoy>,
+    mut game_state: ResMut<GameState>,
+    vehicle_query: Query<Entity, With<ConvoyVehicle>>,
+) {
+    for entity in vehicle_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    *convoy = Convoy::default();
+    game_state.convoy = None;
+
+    let mission_config = MissionConfig::get_mission_config(&campaign.progress.current_mission);
+    let Some(escort) = mission_config
+        .objectives
+        .iter()
+        .find(|objective| objective.kind == "EscortConvoy")
+    else {
+        return;
+    };
+    let Some(game_assets) = game_assets else {
+        return;
+    };
+
+    // Out to the area, or as far down the road as the map goes
+    let start = city_map
+        .spawn_positions(&Faction::Cartel)
+        .first()
+        .copied()
+        .unwrap_or(FALLBACK_START);
+    let end = area_center(&city_map, &escort.target.name()).or_else(|| {
+        city_map
+            .tiles()
+            .filter(|(_, _, kind)| *kind == TileKind::Road)
+            .map(|(col, row, _)| city_map.tile_center(col, row))
+            .max_by(|a, b| a.distance(start).total_cmp(&b.distance(start)))
+    });
+    let Some(route) = end.and_then(|end| city_map.find_road_path(start, end)) else {
+        warn!("No road for the convoy, the escort falls back to Ovidio reaching the area");
+        return;
+    };
+    *convoy = spawn_convoy(
+        &mut commands,
+        route,
+        &balance.convoy,
+        &game_assets,
+        &catalog,
+    );
+}
+
+type VehicleQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Unit,
+        &'static mut Transform,
+        &'static mut ConvoyVehicle,
+        Option<&'static StatModifiers>,
+    ),
+>;
+
+#[allow(clippy::too_many_arguments)]
+pub fn convoy_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    mut convoy: ResMut<Convoy>,
+    mut game_state: ResMut<GameState>,
+    mut vehicle_query: VehicleQuery,
+    unit_query: Query<(&Unit, &Transform), Without<ConvoyVehicle>>,
+) {
+    if convoy.route.len() < 2 {
+        return;
+    }
+    let balance = &balance.convoy;
+    let hostiles: Vec<Vec2> = unit_query
+        .iter()
+        .filter(|(unit, _)| {
+            unit.health > 0.0 && diplomacy.is_hostile(&Faction::Cartel, &unit.faction)
+        })
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+
+    // The whole column waits while anyone hostile is close to a vehicle
+    let halted = vehicle_query.iter().any(|(unit, transform, _, _)| {
+        let position = transform.translation.truncate();
+        unit.health > 0.0
+            && hostiles
+                .iter()
+                .any(|hostile| hostile.distance(position) <= balance.halt_radius)
+    });
+    if halted && !convoy.halted {
+        play_tactical_sound("radio", &tr("radio.convoy_halted"));
+    }
+    convoy.halted = halted;
+
+    let route = convoy.route.clone();
+    let mut health = 0.0;
+    let mut lead = 0.0f32;
+    let mut running = 0;
+    let mut through = 0;
+    for (unit, mut transform, mut vehicle, modifiers) in vehicle_query.iter_mut() {
+        if unit.health <= 0.0 {
+            continue;
+        }
+        health += unit.health;
+        running += 1;
+
+        if !halted {
+            let speed = unit.movement_speed * modifiers.map_or(1.0, |m| m.movement);
+            let mut step = speed * time.delta_seconds();
+            while step > 0.0 && vehicle.next_waypoint < route.len() {
+                let position = transform.translation.truncate();
+                let waypoint = route[vehicle.next_waypoint];
+                let remaining = position.distance(waypoint);
+                if remaining <= step {
+                    transform.translation = waypoint.extend(transform.translation.z);
+                    vehicle.next_waypoint += 1;
+                    step -= remaining;
+                } else {
+                    let moved = position + (waypoint - position) / remaining * step;
+                    transform.translation = moved.extend(transform.translation.z);
+                    step = 0.0;
+                }
+            }
+        }
+
+        if vehicle.next_waypoint >= route.len() {
+            through += 1;
+        }
+        let along = distance_along(
+            &route,
+            vehicle.next_waypoint,
+            transform.translation.truncate(),
+        );
+        lead = lead.max(along);
+    }
+
+    // Ambushes spring as the lead vehicle reaches them
+    let (sprung, waiting): (Vec<f32>, Vec<f32>) = convoy
+        .ambushes
+        .iter()
+        .partition(|distance| **distance <= lead);
+    convoy.ambushes = waiting;
+    for distance in sprung {
+        let positions = ambush_positions(&route, distance, balance);
+        for position in &positions {
+            spawn_unit(
+                &mut commands,
+                UnitType::Soldier,
+                Faction::Military,
+                iso_to_world(position.extend(0.0)),
+                &game_assets,
+                &catalog,
+            );
+        }
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.convoy_ambush", &[("count", &positions.len())]),
+        );
+    }
+
+    let share = health / convoy.starting_health.max(1.0);
+    let arrived = running > 0 && through == running;
+    if arrived
+        && !game_state
+            .convoy
+            .as_ref()
+            .is_some_and(|status| status.arrived)
+    {
+        play_tactical_sound("radio", &tr("radio.convoy_arrived"));
+    }
+    game_state.convoy = Some(ConvoyStatus {
+        progress: (lead / route_length(&route).max(1.0)).min(1.0),
+        health: share,
+        arrived,
+        lost: running == 0 || share < balance.fail_health,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_is_measured_along_the_road() {
+        let route = [Vec2::ZERO, Vec2::new(100.0, 0.0), Vec2::new(100.0, 100.0)];
+        assert_eq!(route_length(&route), 200.0);
+        assert_eq!(distance_along(&route, 1, Vec2::new(40.0, 0.0)), 40.0);
+        assert_eq!(distance_along(&route, 2, Vec2::new(100.0, 50.0)), 150.0);
+        assert_eq!(distance_along(&route, 3, Vec2::new(100.0, 100.0)), 200.0);
+    }
+
+    #[test]
+    fn test_ambushes_wait_down_the_road_on_both_sides() {
+        let route = [Vec2::ZERO, Vec2::new(600.0, 0.0), Vec2::new(600.0, 300.0)];
+        assert_eq!(ambush_distances(&route, 2), vec![300.0, 600.0]);
+        assert_eq!(
+            point_along(&route, 700.0),
+            (Vec2::new(600.0, 100.0), Vec2::Y)
+        );
+        assert_eq!(point_along(&route, 5000.0).0, route[2]);
+
+        let balance = ConvoyBalance::default();
+        let positions = ambush_positions(&route, 300.0, &balance);
+        assert_eq!(positions.len(), balance.ambush_size);
+        assert!(positions.iter().all(|position| position.x > 300.0));
+        assert!(positions.iter().any(|position| position.y > 0.0));
+        assert!(positions.iter().any(|position| position.y < 0.0));
+    }
+}
@@ -1,9 +1,14 @@
 use crate::components::*;
+use crate::diplomacy::Diplomacy;
+use crate::jamming::{jam_fields, jamming_at, JammerQuery};
+use crate::upgrades::BASE_RADIO_RANGE;
 use crate::utils::{
-    calculate_formation_position, find_optimal_formation_center, play_tactical_sound,
+    calculate_formation_position, find_optimal_formation_center, play_tactical_sound, RngStream,
+    SimRng,
 };
 use bevy::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::Rng;
 
 // ==================== SQUAD MANAGEMENT SYSTEM ====================
 
@@ -22,9 +27,13 @@ pub fn squad_management_system(
         With<Squad>,
     >,
     time: Res<Time>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
+    let _span = info_span!("squad_management_system").entered();
+    let rng = sim_rng.stream(RngStream::Coordination);
+
     // Create squads for unassigned units
-    create_new_squads(&mut commands, &unit_query);
+    create_new_squads(&mut commands, &unit_query, rng);
 
     // Update existing squads
     for (squad_entity, mut squad) in squad_query.iter_mut() {
@@ -43,13 +52,14 @@ pub fn squad_management_system(
         update_squad_leadership(&mut squad, &unit_squad_query);
 
         // Coordinate squad objective
-        coordinate_squad_objective(&mut squad, &unit_squad_query, time.elapsed_seconds());
+        coordinate_squad_objective(&mut squad, &unit_squad_query, time.elapsed_seconds(), rng);
     }
 }
 
 fn create_new_squads(
     commands: &mut Commands,
     unit_query: &Query<(Entity, &Unit, &Transform), Without<Squad>>,
+    rng: &mut StdRng,
 ) {
     let mut unassigned_cartel: Vec<(Entity, &Unit, &Transform)> = Vec::new();
     let mut unassigned_military: Vec<(Entity, &Unit, &Transform)> = Vec::new();
@@ -68,25 +78,26 @@ fn create_new_squads(
     }
 
     // Create cartel squads
-    create_faction_squads(commands, &unassigned_cartel, &Faction::Cartel);
+    create_faction_squads(commands, &unassigned_cartel, &Faction::Cartel, rng);
 
     // Create military squads
-    create_faction_squads(commands, &unassigned_military, &Faction::Military);
+    create_faction_squads(commands, &unassigned_military, &Faction::Military, rng);
 }
 
 fn create_faction_squads(
     commands: &mut Commands,
     units: &[(Entity, &Unit, &Transform)],
     faction: &Faction,
+    rng: &mut StdRng,
 ) {
     if units.len() < 2 {
         return;
     } // Need at least 2 units for a squad
 
-    let mut squad_id_counter = thread_rng().gen_range(1000..9999);
+    let mut squad_id_counter = rng.gen_range(1000..9999);
 
     // Group units into squads of 3-5 members
-    for chunk in units.chunks(thread_rng().gen_range(3..=5)) {
+    for chunk in units.chunks(rng.gen_range(3..=5)) {
         let squad_center = calculate_group_center(chunk);
 
         // Determine squad type based on unit composition
@@ -116,7 +127,7 @@ fn create_faction_squads(
                     morale: 0.8,
                 },
                 Communication {
-                    radio_range: 200.0,
+                    radio_range: BASE_RADIO_RANGE,
                     last_report_time: 0.0,
                     known_enemies: Vec::new(),
                     received_orders: Vec::new(),
@@ -263,13 +274,14 @@ fn coordinate_squad_objective(
         With<Squad>,
     >,
     current_time: f32,
+    rng: &mut StdRng,
 ) {
     // Update objective based on squad type and current situation
     match squad.squad_type {
-        SquadType::AssaultTeam => coordinate_assault_squad(squad, unit_query),
-        SquadType::SupportTeam => coordinate_support_squad(squad, unit_query),
+        SquadType::AssaultTeam => coordinate_assault_squad(squad, unit_query, rng),
+        SquadType::SupportTeam => coordinate_support_squad(squad, unit_query, rng),
         SquadType::SecurityTeam => coordinate_security_squad(squad, unit_query),
-        SquadType::ReconTeam => coordinate_recon_squad(squad, unit_query),
+        SquadType::ReconTeam => coordinate_recon_squad(squad, unit_query, rng),
     }
 }
 
@@ -285,6 +297,7 @@ fn coordinate_assault_squad(
         ),
         With<Squad>,
     >,
+    rng: &mut StdRng,
 ) {
     // Assault squads focus on aggressive advancement and flanking
     match &squad.current_objective {
@@ -294,7 +307,7 @@ fn coordinate_assault_squad(
 
             if distance_to_target < 50.0 {
                 // Close to target, switch to engaging or flanking
-                let flank_position = calculate_flanking_position(squad_center, *target);
+                let flank_position = calculate_flanking_position(squad_center, *target, rng);
                 squad.current_objective = SquadObjective::Flank(*target, flank_position);
             }
         }
@@ -314,12 +327,13 @@ fn coordinate_support_squad(
         ),
         With<Squad>,
     >,
+    rng: &mut StdRng,
 ) {
     // Support squads provide overwatch and suppressive fire
     let squad_center = calculate_squad_center(squad, unit_query);
 
     // Find good overwatch position
-    let overwatch_pos = find_overwatch_position(squad_center);
+    let overwatch_pos = find_overwatch_position(squad_center, rng);
     squad.current_objective = SquadObjective::Suppress(overwatch_pos);
 }
 
@@ -366,13 +380,14 @@ fn coordinate_recon_squad(
         ),
         With<Squad>,
     >,
+    rng: &mut StdRng,
 ) {
     // Recon squads advance carefully and gather intelligence
     let squad_center = calculate_squad_center(squad, unit_query);
     let advance_position = squad_center
         + Vec3::new(
-            thread_rng().gen_range(-100.0..100.0),
-            thread_rng().gen_range(-100.0..100.0),
+            rng.gen_range(-100.0..100.0),
+            rng.gen_range(-100.0..100.0),
             0.0,
         );
 
@@ -409,28 +424,19 @@ fn calculate_squad_center(
     }
 }
 
-fn calculate_flanking_position(squad_pos: Vec3, target_pos: Vec3) -> Vec3 {
+fn calculate_flanking_position(squad_pos: Vec3, target_pos: Vec3, rng: &mut StdRng) -> Vec3 {
     let to_target = (target_pos - squad_pos).normalize();
     let perpendicular = Vec3::new(-to_target.y, to_target.x, 0.0);
     let flank_distance = 120.0;
 
     // Choose left or right flank randomly
-    let direction = if thread_rng().gen_bool(0.5) {
-        1.0
-    } else {
-        -1.0
-    };
+    let direction = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
     target_pos + perpendicular * flank_distance * direction
 }
 
-fn find_overwatch_position(current_pos: Vec3) -> Vec3 {
+fn find_overwatch_position(current_pos: Vec3, rng: &mut StdRng) -> Vec3 {
     // Find elevated position with good field of view
-    current_pos
-        + Vec3::new(
-            thread_rng().gen_range(-80.0..80.0),
-            thread_rng().gen_range(-80.0..80.0),
-            0.0,
-        )
+    current_pos + Vec3::new(rng.gen_range(-80.0..80.0), rng.gen_range(-80.0..80.0), 0.0)
 }
 
 // ==================== FORMATION MOVEMENT SYSTEM ====================
@@ -440,6 +446,7 @@ pub fn formation_movement_system(
     squad_query: Query<&Squad>,
     time: Res<Time>,
 ) {
+    let _span = info_span!("formation_movement_system").entered();
     for (mut movement, transform, formation, squad) in unit_query.iter_mut() {
         let formation_position = calculate_formation_position(
             formation.formation_type.clone(),
@@ -475,15 +482,30 @@ fn calculate_formation_position_legacy(formation: &Formation, center: Vec3, faci
 
 // ==================== TACTICAL COMMUNICATION SYSTEM ====================
 
+const SPOTTING_RANGE: f32 = 150.0;
+
+// Type aliases to reduce complexity
+type CommunicatingUnit = (
+    Entity,
+    &'static Transform,
+    &'static mut Communication,
+    &'static TacticalState,
+    Option<&'static StatModifiers>,
+);
+
 pub fn communication_system(
-    mut unit_query: Query<(Entity, &Transform, &mut Communication, &TacticalState)>,
+    mut unit_query: Query<CommunicatingUnit>,
     enemy_query: Query<(Entity, &Transform, &Unit)>,
     time: Res<Time>,
 ) {
+    let _span = info_span!("communication_system").entered();
     let current_time = time.elapsed_seconds();
 
     // Update enemy contacts and share intelligence
-    for (entity, transform, mut comm, tactical_state) in unit_query.iter_mut() {
+    for (entity, transform, mut comm, tactical_state, modifiers) in unit_query.iter_mut() {
+        let spotting_range =
+            SPOTTING_RANGE * modifiers.map_or(1.0, |modifiers| modifiers.spotting);
+
         // Update enemy contact confidence and remove old contacts
         comm.known_enemies.retain_mut(|contact| {
             contact.last_seen += time.delta_seconds();
@@ -496,8 +518,12 @@ pub fn communication_system(
             let distance = transform.translation.distance(enemy_transform.translation);
 
             // Check if enemy is within detection range and not blocked
-            if distance < 150.0
-                && can_see_target(transform.translation, enemy_transform.translation)
+            if distance < spotting_range
+                && can_see_target(
+                    transform.translation,
+                    enemy_transform.translation,
+                    spotting_range,
+                )
             {
                 let existing_contact = comm
                     .known_enemies
@@ -521,20 +547,76 @@ pub fn communication_system(
             }
         }
 
-        // Intelligence sharing would be handled separately to avoid borrow conflicts
+        // Intelligence sharing is handled by radio_relay_system to avoid borrow conflicts
     }
 }
 
-fn can_see_target(observer_pos: Vec3, target_pos: Vec3) -> bool {
+fn can_see_target(observer_pos: Vec3, target_pos: Vec3, spotting_range: f32) -> bool {
     // Simplified line of sight check
     let distance = observer_pos.distance(target_pos);
     let height_diff = (target_pos.z - observer_pos.z).abs();
 
-    // Basic visibility rules
-    distance < 200.0 && height_diff < 10.0
+    // Basic visibility rules - never past plain sight, unless something's spotting from above
+    distance < spotting_range.max(200.0) && height_diff < 10.0
 }
 
-// Intelligence sharing would be implemented as a separate system to avoid borrow conflicts
+// ==================== RADIO RELAY ====================
+
+// Contacts are passed on to allies within radio range, a little less certain
+// than first-hand sightings. Allied factions share a net; neutral ones don't.
+// A radio inside a jammer's field neither sends nor hears
+pub fn radio_relay_system(
+    diplomacy: Res<Diplomacy>,
+    mut unit_query: Query<(&Transform, &Unit, &mut Communication)>,
+    jammer_query: JammerQuery,
+) {
+    let _span = info_span!("radio_relay_system").entered();
+    let fields = jam_fields(&jammer_query);
+    let jammed = |unit: &Unit, transform: &Transform| {
+        jamming_at(&fields, &unit.faction, transform.translation.truncate()) > 0.0
+    };
+
+    let reports: Vec<(Vec3, Faction, f32, Vec<EnemyContact>)> = unit_query
+        .iter()
+        .filter(|(transform, unit, comm)| {
+            unit.health > 0.0 && !comm.known_enemies.is_empty() && !jammed(unit, transform)
+        })
+        .map(|(transform, unit, comm)| {
+            (
+                transform.translation,
+                unit.faction.clone(),
+                comm.radio_range,
+                comm.known_enemies.clone(),
+            )
+        })
+        .collect();
+
+    for (transform, unit, mut comm) in unit_query.iter_mut() {
+        if unit.health <= 0.0 || jammed(unit, transform) {
+            continue;
+        }
+        for (position, faction, radio_range, contacts) in &reports {
+            // Either end's radio has to reach the other
+            if !diplomacy.is_friendly(faction, &unit.faction)
+                || transform.translation.distance(*position) > radio_range.max(comm.radio_range)
+            {
+                continue;
+            }
+            for contact in contacts {
+                let known = comm
+                    .known_enemies
+                    .iter()
+                    .any(|known| known.position.distance(contact.position) < 20.0);
+                if !known && contact.confidence * 0.8 > 0.1 {
+                    comm.known_enemies.push(EnemyContact {
+                        confidence: contact.confidence * 0.8,
+                        ..contact.clone()
+                    });
+                }
+            }
+        }
+    }
+}
 
 // ==================== ADVANCED TACTICAL AI SYSTEM ====================
 
@@ -547,11 +629,18 @@ pub fn advanced_tactical_ai_system(
         &mut TacticalState,
         &Communication,
         Option<&Formation>,
+        Option<&HoldArea>,
+        Option<&ProtectedPrincipal>,
+        Has<Bodyguard>,
+        Has<HvtTasking>,
     )>,
     squad_query: Query<&Squad>,
     time: Res<Time>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
+    let _span = info_span!("advanced_tactical_ai_system").entered();
     let current_time = time.elapsed_seconds();
+    let rng = sim_rng.stream(RngStream::Coordination);
 
     for (
         entity,
@@ -561,9 +650,21 @@ pub fn advanced_tactical_ai_system(
         mut tactical_state,
         communication,
         formation_opt,
+        hold_area,
+        principal,
+        is_bodyguard,
+        hvt_tasked,
     ) in unit_query.iter_mut()
     {
-        if unit.health <= 0.0 {
+        // Units holding an area are moved by their leash in unit_ai_system,
+        // the protection detail and its principal by protection.rs and the
+        // hunt for him by hvt.rs
+        if unit.health <= 0.0
+            || hold_area.is_some()
+            || is_bodyguard
+            || hvt_tasked
+            || principal.is_some_and(ProtectedPrincipal::is_moved_by_detail)
+        {
             continue;
         }
 
@@ -576,6 +677,7 @@ pub fn advanced_tactical_ai_system(
             &communication.known_enemies,
             unit.faction.clone(),
             tactical_state.suppression_level,
+            rng,
         );
 
         // Make tactical decision based on current state and situation
@@ -584,6 +686,7 @@ pub fn advanced_tactical_ai_system(
             &situation,
             tactical_state.morale,
             formation_opt,
+            rng,
         );
 
         // Execute tactical action
@@ -593,6 +696,7 @@ pub fn advanced_tactical_ai_system(
             &new_action,
             transform.translation,
             current_time,
+            rng,
         );
 
         // Update suppression and morale
@@ -615,6 +719,7 @@ fn analyze_tactical_situation(
     known_enemies: &[EnemyContact],
     faction: Faction,
     suppression_level: f32,
+    rng: &mut StdRng,
 ) -> TacticalSituation {
     let nearby_enemies: Vec<&EnemyContact> = known_enemies
         .iter()
@@ -630,20 +735,20 @@ fn analyze_tactical_situation(
         enemy_contacts: nearby_enemies.len(),
         closest_enemy_distance,
         under_fire: suppression_level > 0.3,
-        has_cover: check_cover_availability(unit_pos),
-        squad_support: check_squad_support(unit_pos),
+        has_cover: check_cover_availability(unit_pos, rng),
+        squad_support: check_squad_support(unit_pos, rng),
         retreat_path_clear: check_retreat_path(unit_pos, &nearby_enemies),
     }
 }
 
-fn check_cover_availability(pos: Vec3) -> bool {
+fn check_cover_availability(pos: Vec3, rng: &mut StdRng) -> bool {
     // Simplified cover check - in real implementation would check for obstacles
-    thread_rng().gen_bool(0.4) // 40% chance of having cover
+    rng.gen_bool(0.4) // 40% chance of having cover
 }
 
-fn check_squad_support(pos: Vec3) -> bool {
+fn check_squad_support(pos: Vec3, rng: &mut StdRng) -> bool {
     // Simplified squad support check
-    thread_rng().gen_bool(0.6) // 60% chance of having squad support nearby
+    rng.gen_bool(0.6) // 60% chance of having squad support nearby
 }
 
 fn check_retreat_path(pos: Vec3, enemies: &[&EnemyContact]) -> bool {
@@ -673,6 +778,7 @@ fn decide_tactical_action(
     situation: &TacticalSituation,
     morale: f32,
     formation: Option<&Formation>,
+    rng: &mut StdRng,
 ) -> TacticalAction {
     // Decision tree based on current state, situation, and morale
     match current_state {
@@ -701,7 +807,7 @@ fn decide_tactical_action(
                 }
             } else if situation.enemy_contacts > 1 && situation.squad_support {
                 // Coordinate with squad for flanking
-                if thread_rng().gen_bool(0.5) {
+                if rng.gen_bool(0.5) {
                     TacticalAction::FlankLeft(Vec3::ZERO)
                 } else {
                     TacticalAction::FlankRight(Vec3::ZERO)
@@ -773,15 +879,12 @@ fn execute_tactical_action(
     action: &TacticalAction,
     current_pos: Vec3,
     current_time: f32,
+    rng: &mut StdRng,
 ) {
     match action {
         TacticalAction::Advance(target) => {
-            let advance_pos = current_pos
-                + Vec3::new(
-                    thread_rng().gen_range(-50.0..50.0),
-                    thread_rng().gen_range(20.0..80.0),
-                    0.0,
-                );
+            let advance_pos =
+                current_pos + Vec3::new(rng.gen_range(-50.0..50.0), rng.gen_range(20.0..80.0), 0.0);
             movement.target_position = Some(advance_pos);
             change_tactical_state(tactical_state, TacticalMode::Advancing, current_time);
         }
@@ -789,8 +892,8 @@ fn execute_tactical_action(
         TacticalAction::Retreat(target) => {
             let retreat_pos = current_pos
                 + Vec3::new(
-                    thread_rng().gen_range(-80.0..80.0),
-                    thread_rng().gen_range(-120.0..-40.0),
+                    rng.gen_range(-80.0..80.0),
+                    rng.gen_range(-120.0..-40.0),
                     0.0,
                 );
             movement.target_position = Some(retreat_pos);
@@ -798,7 +901,7 @@ fn execute_tactical_action(
         }
 
         TacticalAction::TakeCover(_) => {
-            let cover_pos = find_nearest_cover(current_pos);
+            let cover_pos = find_nearest_cover(current_pos, rng);
             movement.target_position = Some(cover_pos);
             change_tactical_state(tactical_state, TacticalMode::HoldPosition, current_time);
         }
@@ -833,11 +936,7 @@ fn execute_tactical_action(
 
         TacticalAction::Regroup(_) => {
             let regroup_pos = current_pos
-                + Vec3::new(
-                    thread_rng().gen_range(-40.0..40.0),
-                    thread_rng().gen_range(-40.0..40.0),
-                    0.0,
-                );
+                + Vec3::new(rng.gen_range(-40.0..40.0), rng.gen_range(-40.0..40.0), 0.0);
             movement.target_position = Some(regroup_pos);
             change_tactical_state(tactical_state, TacticalMode::Regrouping, current_time);
         }
@@ -856,13 +955,9 @@ fn change_tactical_state(
     }
 }
 
-fn find_nearest_cover(pos: Vec3) -> Vec3 {
+fn find_nearest_cover(pos: Vec3, rng: &mut StdRng) -> Vec3 {
     // Simplified cover finding - move to nearby position
-    pos + Vec3::new(
-        thread_rng().gen_range(-30.0..30.0),
-        thread_rng().gen_range(-30.0..30.0),
-        0.0,
-    )
+    pos + Vec3::new(rng.gen_range(-30.0..30.0), rng.gen_range(-30.0..30.0), 0.0)
 }
 
 fn update_psychological_state(
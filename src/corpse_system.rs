@@ -0,0 +1,358 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::{BalanceConfig, CorpseBalance};
+use crate::damage::damage_system;
+use crate::map::{CivilianCar, MarketStall};
+use crate::political_system::PoliticalState;
+use crate::resources::GameState;
+use crate::ui::UnitAnimator;
+use crate::utils::{HitFlash, SimulationSet};
+use crate::wrecks::leaves_wreck;
+use bevy::prelude::*;
+
+// ==================== CORPSE SYSTEM PLUGIN ====================
+
+// Units that die stay on the map as bodies instead of standing there at zero
+// health. A body plays a short death sequence, weighs on the morale of nearby
+// friendlies and on the government's image when civilians can see it, then
+// fades out after balance.corpses.persist_seconds - or earlier once there are
+// more than max_corpses, oldest first.
+
+const DEATH_SEQUENCE_SECONDS: f32 = 0.6;
+const TOPPLE_ANGLE: f32 = std::f32::consts::FRAC_PI_2;
+const CORPSE_DARKEN: f32 = 0.45; // Tint multiplier once the sequence is over
+const MAX_MORALE_BODIES: f32 = 3.0; // Bodies in view past this add no extra drain
+
+// Type aliases to reduce complexity
+type DyingUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        &'static Transform,
+        Option<&'static Sprite>,
+        Option<&'static TextureAtlasSprite>,
+        Option<&'static HitFlash>,
+    ),
+    Without<Corpse>,
+>;
+type CivilianLifeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, Option<&'static MarketStall>),
+    Or<(With<CivilianCar>, With<MarketStall>)>,
+>;
+type CorpseVisualQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Corpse,
+        &'static mut Transform,
+        Option<&'static mut Sprite>,
+        Option<&'static mut TextureAtlasSprite>,
+        Has<UnitAnimator>,
+    ),
+>;
+
+pub struct CorpseSystemPlugin;
+
+impl Plugin for CorpseSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                register_corpses_system,
+                corpse_morale_system,
+                corpse_optics_system,
+                corpse_cleanup_system,
+            )
+                .chain()
+                .after(damage_system)
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        )
+        .add_systems(Update, death_sequence_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Component)]
+pub struct Corpse {
+    pub faction: Faction,
+    pub age: f32,
+    pub fade: Option<f32>, // Seconds into the fade-out, once it has started
+    sequence: f32,
+    base_color: Color,
+    base_rotation: Quat,
+}
+
+impl Corpse {
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+}
+
+// Turn freshly killed units into corpses
+pub fn register_corpses_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    dead_query: DyingUnitQuery,
+    mut friendly_query: Query<(&Unit, &Transform, &mut TacticalState), Without<Corpse>>,
+    political_state: Option<ResMut<PoliticalState>>,
+) {
+    let corpses = &balance.corpses;
+    let mut civilian_deaths = 0;
+
+    for (entity, unit, transform, sprite, atlas_sprite, hit_flash) in dead_query.iter() {
+        // Destroyed vehicles leave a wreck instead
+        if unit.health > 0.0 || leaves_wreck(&unit.unit_type) {
+            continue;
+        }
+
+        // Killed mid hit-flash: darken the real tint, not the flash
+        let base_color = match (hit_flash, sprite, atlas_sprite) {
+            (Some(hit_flash), _, _) => hit_flash.original,
+            (None, Some(sprite), _) => sprite.color,
+            (None, None, Some(atlas_sprite)) => atlas_sprite.color,
+            (None, None, None) => Color::WHITE,
+        };
+
+        // The idle pulse and walk bob would keep a body twitching
+        commands
+            .entity(entity)
+            .remove::<(AnimatedSprite, MovementAnimation)>()
+            .insert(Corpse {
+                faction: unit.faction.clone(),
+                age: 0.0,
+                fade: None,
+                sequence: 0.0,
+                base_color,
+                base_rotation: transform.rotation,
+            });
+
+        if unit.faction == Faction::Civilian {
+            civilian_deaths += 1;
+        }
+
+        // Seeing a comrade fall shakes everyone close by
+        let position = transform.translation.truncate();
+        for (friendly, friendly_transform, mut tactical_state) in friendly_query.iter_mut() {
+            if friendly.faction != unit.faction || friendly.health <= 0.0 {
+                continue;
+            }
+            let distance = friendly_transform.translation.truncate().distance(position);
+            if distance <= corpses.morale_radius {
+                tactical_state.morale = (tactical_state.morale - corpses.morale_shock).max(0.0);
+            }
+        }
+    }
+
+    if civilian_deaths > 0 {
+        if let Some(mut political_state) = political_state {
+            political_state.casualties_civilian += civilian_deaths;
+        }
+    }
+}
+
+// Bodies left lying around keep wearing down the friendlies that can see them
+pub fn corpse_morale_system(
+    balance: Res<BalanceConfig>,
+    corpse_query: Query<(&Corpse, &Transform)>,
+    mut unit_query: Query<(&Unit, &Transform, &mut TacticalState), Without<Corpse>>,
+    time: Res<Time>,
+) {
+    let corpses = &balance.corpses;
+    let dt = time.delta_seconds();
+
+    for (unit, transform, mut tactical_state) in unit_query.iter_mut() {
+        if unit.health <= 0.0 {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let bodies_in_view = corpse_query
+            .iter()
+            .filter(|(corpse, corpse_transform)| {
+                corpse.faction == unit.faction
+                    && !corpse.is_fading()
+                    && corpse_transform.translation.truncate().distance(position)
+                        <= corpses.morale_radius
+            })
+            .count() as f32;
+
+        if bodies_in_view > 0.0 {
+            let drain = corpses.morale_drain * bodies_in_view.min(MAX_MORALE_BODIES) * dt;
+            tactical_state.morale = (tactical_state.morale - drain).max(0.0);
+        }
+    }
+}
+
+// Bodies in sight of civilians - passers-by, drivers, open market stalls -
+// draw the press and cost the government public support
+pub fn corpse_optics_system(
+    balance: Res<BalanceConfig>,
+    political_state: Option<ResMut<PoliticalState>>,
+    corpse_query: Query<(&Corpse, &Transform)>,
+    civilian_unit_query: Query<(&Unit, &Transform), Without<Corpse>>,
+    civilian_life_query: CivilianLifeQuery,
+    time: Res<Time>,
+) {
+    let Some(mut political_state) = political_state else {
+        return;
+    };
+    let corpses = &balance.corpses;
+
+    let witnesses: Vec<Vec2> = civilian_unit_query
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Civilian && unit.health > 0.0)
+        .map(|(_, transform)| transform.translation.truncate())
+        .chain(
+            civilian_life_query
+                .iter()
+                .filter(|(_, stall)| stall.is_none_or(|stall| stall.open))
+                .map(|(transform, _)| transform.translation.truncate()),
+        )
+        .collect();
+    if witnesses.is_empty() {
+        return;
+    }
+
+    let exposure: f32 = corpse_query
+        .iter()
+        .filter(|(corpse, transform)| {
+            let position = transform.translation.truncate();
+            !corpse.is_fading()
+                && witnesses
+                    .iter()
+                    .any(|witness| witness.distance(position) <= corpses.optics_radius)
+        })
+        // A dead civilian is a far bigger story than a dead gunman
+        .map(|(corpse, _)| match corpse.faction {
+            Faction::Civilian => 3.0,
+            _ => 1.0,
+        })
+        .sum();
+
+    if exposure > 0.0 {
+        let attention = corpses.optics_media_rate * exposure * time.delta_seconds();
+        political_state.media_attention = (political_state.media_attention + attention).min(1.0);
+        political_state.public_support_government =
+            (political_state.public_support_government - attention * 0.5).max(0.0);
+    }
+}
+
+// Which bodies should start fading: anything past its persist time, plus the
+// oldest of the rest while there are more than the budget allows
+pub fn corpses_to_fade(ages: &[(Entity, f32)], corpses: &CorpseBalance) -> Vec<Entity> {
+    let mut by_age = ages.to_vec();
+    by_age.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let over_budget = by_age.len().saturating_sub(corpses.max_corpses);
+    by_age
+        .iter()
+        .enumerate()
+        .filter(|(index, (_, age))| *index < over_budget || *age >= corpses.persist_seconds)
+        .map(|(_, (entity, _))| *entity)
+        .collect()
+}
+
+pub fn corpse_cleanup_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut game_state: ResMut<GameState>,
+    mut corpse_query: Query<(Entity, &mut Corpse)>,
+    time: Res<Time>,
+) {
+    let corpses = &balance.corpses;
+    let dt = time.delta_seconds();
+    let mut resting = Vec::new();
+
+    for (entity, mut corpse) in corpse_query.iter_mut() {
+        corpse.age += dt;
+        match corpse.fade {
+            Some(fade) if fade + dt >= corpses.fade_seconds => {
+                game_state.record_cleared_corpse(&corpse.faction);
+                // Selection and target crosshairs are pooled children - hand
+                // them back to their pools rather than despawning them
+                commands.entity(entity).clear_children().despawn();
+            }
+            Some(fade) => corpse.fade = Some(fade + dt),
+            None => resting.push((entity, corpse.age)),
+        }
+    }
+
+    for entity in corpses_to_fade(&resting, corpses) {
+        if let Ok((_, mut corpse)) = corpse_query.get_mut(entity) {
+            corpse.fade = Some(0.0);
+        }
+    }
+}
+
+// Units with a sprite sheet play their die clip; everyone else topples over
+// and darkens. Fading bodies lose their alpha on the way out.
+pub fn death_sequence_system(
+    balance: Res<BalanceConfig>,
+    mut corpse_query: CorpseVisualQuery,
+    time: Res<Time>,
+) {
+    let fade_seconds = balance.corpses.fade_seconds.max(0.01);
+
+    for (mut corpse, mut transform, sprite, atlas_sprite, animated) in corpse_query.iter_mut() {
+        corpse.sequence = (corpse.sequence + time.delta_seconds()).min(DEATH_SEQUENCE_SECONDS);
+        let progress = corpse.sequence / DEATH_SEQUENCE_SECONDS;
+
+        if !animated {
+            // Ease out so the body drops fast and settles
+            let eased = 1.0 - (1.0 - progress).powi(2);
+            transform.rotation = Quat::from_rotation_z(TOPPLE_ANGLE * eased) * corpse.base_rotation;
+        }
+
+        let darken = 1.0 - (1.0 - CORPSE_DARKEN) * progress;
+        let alpha = corpse
+            .fade
+            .map_or(1.0, |fade| (1.0 - fade / fade_seconds).clamp(0.0, 1.0));
+        let base = corpse.base_color;
+        let color = Color::rgba(
+            base.r() * darken,
+            base.g() * darken,
+            base.b() * darken,
+            base.a() * alpha,
+        );
+
+        if let Some(mut sprite) = sprite {
+            sprite.color = color;
+        }
+        if let Some(mut atlas_sprite) = atlas_sprite {
+            atlas_sprite.color = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corpses_fade_when_expired_or_over_budget() {
+        let corpses = CorpseBalance {
+            persist_seconds: 60.0,
+            max_corpses: 2,
+            ..default()
+        };
+        let oldest = Entity::from_raw(1);
+        let middle = Entity::from_raw(2);
+        let newest = Entity::from_raw(3);
+
+        // Within budget and all fresh: nothing fades
+        assert!(corpses_to_fade(&[(oldest, 30.0), (newest, 5.0)], &corpses).is_empty());
+
+        // One over budget: the oldest goes first
+        let ages = [(newest, 5.0), (oldest, 30.0), (middle, 10.0)];
+        assert_eq!(corpses_to_fade(&ages, &corpses), vec![oldest]);
+
+        // Expired bodies fade even when there's room
+        assert_eq!(
+            corpses_to_fade(&[(oldest, 61.0), (newest, 5.0)], &corpses),
+            vec![oldest]
+        );
+    }
+}
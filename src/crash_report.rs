@@ -0,0 +1,451 @@
+use crate::campaign::Campaign;
+use crate::components::{Faction, Unit};
+use crate::localization::{tr, tr_args};
+use crate::logging::{recent_entries, try_lock, LogEntry};
+use crate::resources::GameState;
+use crate::utils::{data_dir, play_tactical_sound, DataKind};
+use bevy::prelude::*;
+use chrono::Utc;
+use reqwest::blocking::Client;
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+// ==================== CRASH REPORTS ====================
+
+// install() runs first thing in the windowed game, right after logging is
+// set up. It sets a panic hook that writes a report - panic message,
+// backtrace, the last log lines and the game state CrashReportPlugin keeps
+// current - to the crashes folder in the game's data directory (see
+// utils::data_dirs). On the next launch a dialog offers to open the report
+// or, with CRASH_REPORT_URL set, submit it.
+
+const REVIEWED_SUFFIX: &str = ".reviewed.txt"; // Reports the player has dealt with
+const RECENT_LOG_LINES: usize = 200;
+const CRASH_REPORT_URL_ENV_VAR: &str = "CRASH_REPORT_URL";
+const SUBMIT_TIMEOUT_SECONDS: u64 = 15;
+const CONTEXT_REFRESH_SECONDS: f32 = 1.0;
+
+static CRASH_CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext::new());
+
+// What the game was doing, copied out of the World for the panic hook
+#[derive(Clone, Debug, Default)]
+pub struct CrashContext {
+    pub mission: String,
+    pub phase: String,
+    pub seed: u64,
+    pub entities: usize,
+    pub units: [usize; 3], // Cartel, military, civilian
+}
+
+impl CrashContext {
+    const fn new() -> Self {
+        Self {
+            mission: String::new(),
+            phase: String::new(),
+            seed: 0,
+            entities: 0,
+            units: [0; 3],
+        }
+    }
+}
+
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_report(info) {
+            Ok(path) => eprintln!("💥 Crash report written to {}", path.display()),
+            Err(e) => eprintln!("💥 Couldn't write crash report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+fn crash_dir() -> PathBuf {
+    data_dir(DataKind::Crashes)
+}
+
+fn write_report(info: &PanicHookInfo) -> std::io::Result<PathBuf> {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(no message)".to_string());
+    let location = info
+        .location()
+        .map(|location| location.to_string())
+        .unwrap_or_default();
+    let context = try_lock(&CRASH_CONTEXT).map(|context| context.clone());
+    let log: Vec<String> = recent_entries(RECENT_LOG_LINES, None)
+        .iter()
+        .map(LogEntry::line)
+        .collect();
+
+    let report = format_report(
+        &format!("{} at {}", message, location),
+        context.as_ref(),
+        &Backtrace::force_capture().to_string(),
+        &log,
+    );
+
+    let dir = crash_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("crash-{}.txt", Utc::now().format("%Y%m%d-%H%M%S")));
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+pub fn format_report(
+    panic: &str,
+    context: Option<&CrashContext>,
+    backtrace: &str,
+    log: &[String],
+) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "Battle of Culiacán crash report");
+    let _ = writeln!(report, "Version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Time: {}", Utc::now().to_rfc3339());
+    let _ = writeln!(report, "Panic: {}", panic);
+
+    let _ = writeln!(report, "\n== Game state ==");
+    match context {
+        Some(context) => {
+            let [cartel, military, civilian] = context.units;
+            let _ = writeln!(report, "Mission: {}", context.mission);
+            let _ = writeln!(report, "Phase: {}", context.phase);
+            let _ = writeln!(report, "Seed: {}", context.seed);
+            let _ = writeln!(report, "Entities: {}", context.entities);
+            let _ = writeln!(
+                report,
+                "Units: {} cartel, {} military, {} civilian",
+                cartel, military, civilian
+            );
+        }
+        None => {
+            let _ = writeln!(report, "(unavailable)");
+        }
+    }
+
+    let _ = writeln!(report, "\n== Backtrace ==\n{}", backtrace.trim_end());
+    let _ = writeln!(report, "\n== Last {} log lines ==", log.len());
+    for line in log {
+        let _ = writeln!(report, "{}", line);
+    }
+    report
+}
+
+// ==================== CRASH REPORT PLUGIN ====================
+
+pub struct CrashReportPlugin;
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CrashReports>().add_systems(
+            Update,
+            (
+                crash_context_system,
+                crash_dialog_system,
+                crash_submit_result_system,
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct CrashReports {
+    pending: Option<PathBuf>, // Newest report from an earlier run not yet reviewed
+    submit_url: Option<String>,
+    submitting: bool,
+    results: Option<mpsc::UnboundedReceiver<Result<(), String>>>,
+}
+
+impl Default for CrashReports {
+    fn default() -> Self {
+        let submit_url = std::env::var(CRASH_REPORT_URL_ENV_VAR)
+            .ok()
+            .filter(|url| url.starts_with("https://"));
+        Self {
+            pending: newest_unreviewed_report(&crash_dir()),
+            submit_url,
+            submitting: false,
+            results: None,
+        }
+    }
+}
+
+pub fn newest_unreviewed_report(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            name.starts_with("crash-") && name.ends_with(".txt") && !name.ends_with(REVIEWED_SUFFIX)
+        })
+        .max() // Names sort by the time they were written
+}
+
+fn mark_reviewed(path: &Path) {
+    let reviewed = path
+        .with_extension("")
+        .with_extension(&REVIEWED_SUFFIX[1..]);
+    if let Err(e) = fs::rename(path, &reviewed) {
+        warn!("Couldn't mark crash report as reviewed: {}", e);
+    }
+}
+
+fn open_in_system_viewer(path: &Path) {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("explorer");
+        command.arg(path);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg(path);
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(path);
+        command
+    };
+    if let Err(e) = command.spawn() {
+        warn!("Couldn't open {}: {}", path.display(), e);
+    }
+}
+
+// Refreshes the game state a crash report would include
+pub fn crash_context_system(
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    entities: Query<Entity>,
+    units: Query<&Unit>,
+    time: Res<Time>,
+    mut since_refresh: Local<f32>,
+) {
+    *since_refresh += time.delta_seconds();
+    if *since_refresh < CONTEXT_REFRESH_SECONDS && !game_state.is_changed() {
+        return;
+    }
+    *since_refresh = 0.0;
+
+    let mut unit_counts = [0; 3];
+    for unit in units.iter() {
+        let index = match unit.faction.side() {
+            Faction::Cartel => 0,
+            Faction::Military => 1,
+            _ => 2,
+        };
+        unit_counts[index] += 1;
+    }
+
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        *context = CrashContext {
+            mission: format!("{:?}", campaign.progress.current_mission),
+            phase: format!("{:?}", game_state.game_phase),
+            seed: game_state.rng_seed,
+            entities: entities.iter().count(),
+            units: unit_counts,
+        };
+    }
+}
+
+#[derive(Component)]
+pub struct CrashDialog;
+
+// O opens the report, U submits it, X dismisses it
+pub fn crash_dialog_system(
+    mut commands: Commands,
+    mut reports: ResMut<CrashReports>,
+    input: Res<Input<KeyCode>>,
+    dialogs: Query<Entity, With<CrashDialog>>,
+) {
+    let Some(path) = reports.pending.clone() else {
+        for entity in dialogs.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    if input.just_pressed(KeyCode::O) {
+        open_in_system_viewer(&path);
+    } else if input.just_pressed(KeyCode::X) {
+        mark_reviewed(&path);
+        reports.pending = None;
+        return;
+    } else if input.just_pressed(KeyCode::U) && !reports.submitting {
+        if let Some(url) = reports.submit_url.clone() {
+            submit_report(&mut reports, url, path.clone());
+        }
+    }
+
+    if dialogs.is_empty() || reports.is_changed() {
+        for entity in dialogs.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        spawn_crash_dialog(&mut commands, &reports, &path);
+    }
+}
+
+fn submit_report(reports: &mut CrashReports, url: String, path: PathBuf) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    reports.results = Some(receiver);
+    reports.submitting = true;
+
+    std::thread::spawn(move || {
+        let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+            Client::builder()
+                .timeout(Duration::from_secs(SUBMIT_TIMEOUT_SECONDS))
+                .build()?
+                .post(url)
+                .header("Content-Type", "text/plain; charset=utf-8")
+                .body(fs::read(&path)?)
+                .send()?
+                .error_for_status()?;
+            Ok(())
+        })();
+        let _ = sender.send(result.map_err(|e| e.to_string()));
+    });
+}
+
+pub fn crash_submit_result_system(mut reports: ResMut<CrashReports>) {
+    let Some(result) = reports
+        .results
+        .as_mut()
+        .and_then(|receiver| receiver.try_recv().ok())
+    else {
+        return;
+    };
+    reports.results = None;
+    reports.submitting = false;
+
+    match result {
+        Ok(()) => {
+            if let Some(path) = reports.pending.take() {
+                mark_reviewed(&path);
+            }
+            play_tactical_sound("radio", &tr("crash_dialog.submitted"));
+        }
+        Err(e) => {
+            warn!("Crash report submission failed: {}", e);
+            play_tactical_sound("radio", &tr("crash_dialog.submit_failed"));
+        }
+    }
+}
+
+fn spawn_crash_dialog(commands: &mut Commands, reports: &CrashReports, path: &Path) {
+    let hint = match (&reports.submit_url, reports.submitting) {
+        (_, true) => tr("crash_dialog.submitting"),
+        (Some(_), false) => tr("crash_dialog.hint_submit"),
+        (None, false) => tr("crash_dialog.hint"),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(20.0),
+                    top: Val::Percent(30.0),
+                    width: Val::Percent(60.0),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.2, 0.0, 0.0, 0.95)),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            CrashDialog,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                tr("crash_dialog.title"),
+                TextStyle {
+                    font_size: 28.0,
+                    color: Color::rgb(1.0, 0.5, 0.4),
+                    ..default()
+                },
+            ));
+            parent.spawn(
+                TextBundle::from_section(
+                    tr_args(
+                        "crash_dialog.body",
+                        &[("path", &path.display().to_string())],
+                    ),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::vertical(Val::Px(12.0)),
+                    ..default()
+                }),
+            );
+            parent.spawn(TextBundle::from_section(
+                hint,
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::rgb(0.8, 0.8, 0.8),
+                    ..default()
+                },
+            ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_includes_state_backtrace_and_log() {
+        let context = CrashContext {
+            mission: "InitialRaid".to_string(),
+            phase: "BlockConvoy".to_string(),
+            seed: 42,
+            entities: 310,
+            units: [12, 30, 8],
+        };
+        let report = format_report(
+            "index out of bounds at src/systems.rs:10:5",
+            Some(&context),
+            "0: culiacan_rts::systems::movement_system",
+            &["INFO culiacan_rts: Wave 3 incoming".to_string()],
+        );
+
+        assert!(report.contains("Panic: index out of bounds at src/systems.rs:10:5"));
+        assert!(report.contains("Phase: BlockConvoy"));
+        assert!(report.contains("Seed: 42"));
+        assert!(report.contains("Units: 12 cartel, 30 military, 8 civilian"));
+        assert!(report.contains("movement_system"));
+        assert!(report.contains("Wave 3 incoming"));
+    }
+
+    #[test]
+    fn test_reviewed_reports_are_not_offered_again() {
+        let dir = std::env::temp_dir().join(format!("culiacan-crash-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let older = dir.join("crash-20240101-100000.txt");
+        let newer = dir.join("crash-20240102-100000.txt");
+        fs::write(&older, "old").unwrap();
+        fs::write(&newer, "new").unwrap();
+
+        assert_eq!(newest_unreviewed_report(&dir), Some(newer.clone()));
+        mark_reviewed(&newer);
+        assert_eq!(newest_unreviewed_report(&dir), Some(older.clone()));
+        mark_reviewed(&older);
+        assert_eq!(newest_unreviewed_report(&dir), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,438 @@
+use crate::app_state::GameSet;
+use crate::components::{Faction, GamePhase, Unit, UnitType};
+use crate::config::UnitCatalog;
+use crate::environmental_systems::{EnvironmentalState, WeatherType};
+use crate::localization::{tr, tr_args};
+use crate::map::{CityMap, Neighborhood};
+use crate::profile::ProfileManager;
+use crate::resources::{AiDirector, DirectorPersonality, GameAssets, GameState};
+use crate::scoring::MissionScore;
+use crate::spawners::spawn_unit;
+use crate::systems::spawn_ovidio;
+use crate::utils::{iso_to_world, play_tactical_sound, SimRng};
+use bevy::prelude::*;
+use chrono::{Datelike, NaiveDate, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::f32::consts::TAU;
+
+// ==================== DAILY CHALLENGE PLUGIN ====================
+
+// Once a day everyone gets the same skirmish. The district Ovidio is holed up
+// in, the fighters guarding him, the raid sent for him, the weather, the hour
+// and the AI director's personality are all drawn from a seed derived from
+// the UTC date, and the same seed drives the simulation RNG once the fight
+// starts. Each profile gets one attempt per day (T on the briefing screen),
+// the campaign doesn't advance, and the score goes on the day's own board.
+
+pub const DAILY_KEY: KeyCode = KeyCode::T;
+const DAILY_SEED_SALT: u64 = 0x0DA1_1C0D_E5EE_D000;
+const CARTEL_ROSTER: [UnitType; 5] = [
+    UnitType::Sicario,
+    UnitType::Enforcer,
+    UnitType::Sniper,
+    UnitType::HeavyGunner,
+    UnitType::Medic,
+];
+// Repeats weight the draw towards plain infantry
+const RAID_POOL: [UnitType; 7] = [
+    UnitType::Soldier,
+    UnitType::Soldier,
+    UnitType::Soldier,
+    UnitType::SpecialForces,
+    UnitType::SpecialForces,
+    UnitType::Vehicle,
+    UnitType::Engineer,
+];
+const WEATHERS: [WeatherType; 5] = [
+    WeatherType::Clear,
+    WeatherType::Overcast,
+    WeatherType::LightRain,
+    WeatherType::HeavyRain,
+    WeatherType::Fog,
+];
+const DEFENDER_RING: f32 = 60.0; // Cartel fighters stand this far from Ovidio
+const RAID_DISTANCE: f32 = 350.0; // Used when the map has no military spawn points
+const RAID_SPACING: f32 = 30.0;
+
+pub struct DailyChallengePlugin;
+
+impl Plugin for DailyChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DailyChallengeState>().add_systems(
+            Update,
+            (daily_challenge_setup_system, daily_result_system).in_set(GameSet::Session),
+        );
+    }
+}
+
+// ==================== DAILY CONFIGURATION ====================
+
+#[derive(Clone, Debug)]
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+    pub seed: u64,
+    pub district: Option<Neighborhood>, // None on a map without neighborhoods
+    pub weather: WeatherType,
+    pub weather_intensity: f32,
+    pub start_hour: f32,
+    pub cartel: Vec<UnitType>,
+    pub raid: Vec<UnitType>,
+    pub personality: DirectorPersonality,
+}
+
+impl DailyChallenge {
+    pub fn today(city_map: &CityMap) -> Self {
+        Self::for_date(Utc::now().date_naive(), city_map)
+    }
+
+    // The draws are made in a fixed order, so the same date on the same map
+    // always gives the same challenge
+    pub fn for_date(date: NaiveDate, city_map: &CityMap) -> Self {
+        let seed = daily_seed(date);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let district = city_map.neighborhoods.choose(&mut rng).cloned();
+        let weather = *WEATHERS.choose(&mut rng).unwrap();
+        let weather_intensity = rng.gen_range(0.5..1.0);
+        let start_hour = rng.gen_range(0..48) as f32 * 0.5;
+        let cartel_size = rng.gen_range(3..=5);
+        let cartel = (0..cartel_size)
+            .map(|_| CARTEL_ROSTER.choose(&mut rng).unwrap().clone())
+            .collect();
+        let raid_size = rng.gen_range(4..=7);
+        let raid = (0..raid_size)
+            .map(|_| RAID_POOL.choose(&mut rng).unwrap().clone())
+            .collect();
+        let personality = *DirectorPersonality::ALL.choose(&mut rng).unwrap();
+
+        Self {
+            date,
+            seed,
+            district,
+            weather,
+            weather_intensity,
+            start_hour,
+            cartel,
+            raid,
+            personality,
+        }
+    }
+
+    // Where Ovidio starts: the middle of the district, or the map's centre
+    pub fn center(&self, city_map: &CityMap) -> Vec2 {
+        self.district
+            .as_ref()
+            .and_then(|district| city_map.neighborhood_center(district.key))
+            .unwrap_or(Vec2::ZERO)
+    }
+
+    pub fn cartel_positions(&self, city_map: &CityMap) -> Vec<Vec2> {
+        let center = self.center(city_map);
+        let count = self.cartel.len().max(1) as f32;
+        (0..self.cartel.len())
+            .map(|i| center + Vec2::from_angle(i as f32 / count * TAU) * DEFENDER_RING)
+            .collect()
+    }
+
+    // Round-robin over the military spawn points, lining up behind each other
+    pub fn raid_positions(&self, city_map: &CityMap) -> Vec<Vec2> {
+        let mut entries = city_map.spawn_positions(&Faction::Military);
+        if entries.is_empty() {
+            let center = self.center(city_map);
+            entries = (0..4)
+                .map(|i| center + Vec2::from_angle(i as f32 * TAU / 4.0) * RAID_DISTANCE)
+                .collect();
+        }
+
+        (0..self.raid.len())
+            .map(|i| {
+                let rank = (i / entries.len()) as f32;
+                entries[i % entries.len()] + Vec2::new(rank * RAID_SPACING, 0.0)
+            })
+            .collect()
+    }
+
+    // Briefing lines describing the setup
+    pub fn lines(&self) -> Vec<String> {
+        let district = self
+            .district
+            .as_ref()
+            .map_or_else(|| tr("daily.whole_city"), |district| district.name.clone());
+        let hour = format!(
+            "{:02}:{:02}",
+            self.start_hour as u32,
+            (self.start_hour.fract() * 60.0) as u32
+        );
+        vec![
+            tr_args("daily.district", &[("name", &district)]),
+            tr_args(
+                "daily.conditions",
+                &[("weather", &weather_label(self.weather)), ("hour", &hour)],
+            ),
+            tr_args("daily.cartel", &[("units", &roster_label(&self.cartel))]),
+            tr_args("daily.raid", &[("units", &roster_label(&self.raid))]),
+            tr_args(
+                "daily.director",
+                &[("personality", &self.personality.label())],
+            ),
+        ]
+    }
+}
+
+pub fn daily_seed(date: NaiveDate) -> u64 {
+    // SplitMix64 finalizer so consecutive days get unrelated seeds
+    let mut z = (date.num_days_from_ce() as u64)
+        .wrapping_add(DAILY_SEED_SALT)
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn weather_label(weather: WeatherType) -> String {
+    tr(&format!("weather.{:?}", weather))
+}
+
+// e.g. "3x Sicario, 1x Sniper", in the order the types were first drawn
+fn roster_label(units: &[UnitType]) -> String {
+    let mut counts: Vec<(&UnitType, usize)> = Vec::new();
+    for unit_type in units {
+        match counts.iter_mut().find(|(counted, _)| *counted == unit_type) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((unit_type, 1)),
+        }
+    }
+    counts
+        .iter()
+        .map(|(unit_type, count)| format!("{}x {:?}", count, unit_type))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// ==================== DAILY STATE ====================
+
+#[derive(Resource, Default)]
+pub struct DailyChallengeState {
+    pub selected: bool, // Toggled on the briefing screen
+    running: Option<DailyChallenge>,
+    applied: bool, // Forces and conditions placed for the running challenge
+}
+
+impl DailyChallengeState {
+    pub fn start(&mut self, challenge: DailyChallenge) {
+        self.selected = false;
+        self.running = Some(challenge);
+        self.applied = false;
+    }
+
+    pub fn running(&self) -> Option<&DailyChallenge> {
+        self.running.as_ref()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    pub fn running_date(&self) -> Option<NaiveDate> {
+        self.running.as_ref().map(|challenge| challenge.date)
+    }
+}
+
+/// Whether a daily challenge is being played; false where no state exists.
+pub fn daily_running(daily: Option<&DailyChallengeState>) -> bool {
+    daily.is_some_and(DailyChallengeState::is_running)
+}
+
+/// Briefing lines for today's challenge: the option to play it, and its setup
+/// once selected.
+pub fn daily_briefing_lines(
+    daily: &DailyChallengeState,
+    challenge: &DailyChallenge,
+    available: bool,
+) -> Vec<String> {
+    let date = challenge.date.to_string();
+    if !available {
+        return vec![tr_args("daily.attempted", &[("date", &date)])];
+    }
+
+    let state = if daily.selected {
+        tr("briefing.challenge_on")
+    } else {
+        tr("briefing.challenge_off")
+    };
+    let mut lines = vec![tr_args(
+        "daily.option",
+        &[
+            ("key", &format!("{:?}", DAILY_KEY)),
+            ("date", &date),
+            ("state", &state),
+        ],
+    )];
+    if daily.selected {
+        lines.extend(challenge.lines());
+        lines.push(tr("daily.one_attempt"));
+    }
+    lines
+}
+
+// ==================== DAILY SYSTEMS ====================
+
+// Replaces whatever is on the map with the day's forces as the challenge
+// enters Preparation, and fixes the conditions every player gets
+#[allow(clippy::too_many_arguments)]
+pub fn daily_challenge_setup_system(
+    mut commands: Commands,
+    mut daily: ResMut<DailyChallengeState>,
+    mut game_state: ResMut<GameState>,
+    mut sim_rng: ResMut<SimRng>,
+    mut env_state: ResMut<EnvironmentalState>,
+    mut ai_director: ResMut<AiDirector>,
+    city_map: Res<CityMap>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    units: Query<Entity, With<Unit>>,
+) {
+    // Over once the player is back in the menus
+    if game_state.game_phase == GamePhase::MainMenu {
+        if daily.selected || daily.is_running() {
+            *daily = DailyChallengeState::default();
+            ai_director.personality = DirectorPersonality::Balanced;
+        }
+        return;
+    }
+    if game_state.game_phase != GamePhase::Preparation || daily.applied {
+        return;
+    }
+    let Some(challenge) = daily.running.clone() else {
+        return;
+    };
+    daily.applied = true;
+
+    *game_state = GameState {
+        game_phase: GamePhase::Preparation,
+        rng_seed: challenge.seed,
+        ..default()
+    };
+    sim_rng.reseed(challenge.seed);
+
+    env_state.set_hour(challenge.start_hour);
+    env_state.weather_type = challenge.weather;
+    env_state.weather_intensity = challenge.weather_intensity;
+    env_state.update_gameplay_modifiers();
+
+    *ai_director = AiDirector {
+        personality: challenge.personality,
+        ..default()
+    };
+
+    for entity in units.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let center = challenge.center(&city_map);
+    spawn_ovidio(&mut commands, center.extend(0.0), &game_assets, &catalog);
+    let cartel = challenge
+        .cartel
+        .iter()
+        .zip(challenge.cartel_positions(&city_map));
+    let raid = challenge
+        .raid
+        .iter()
+        .zip(challenge.raid_positions(&city_map));
+    for (faction, (unit_type, position)) in cartel
+        .map(|unit| (Faction::Cartel, unit))
+        .chain(raid.map(|unit| (Faction::Military, unit)))
+    {
+        spawn_unit(
+            &mut commands,
+            unit_type.clone(),
+            faction,
+            iso_to_world(position.extend(0.0)),
+            &game_assets,
+            &catalog,
+        );
+    }
+
+    play_tactical_sound(
+        "radio",
+        &tr_args(
+            "radio.daily_begin",
+            &[("date", &challenge.date.to_string())],
+        ),
+    );
+}
+
+// Keeps the day's result on the profile that played it
+pub fn daily_result_system(score: Res<MissionScore>, profiles: Option<ResMut<ProfileManager>>) {
+    if !score.is_changed() || score.mission.is_none() {
+        return;
+    }
+    let (Some(date), Some(mut profiles)) = (score.daily, profiles) else {
+        return;
+    };
+
+    if let Some(profile) = profiles.active.as_mut() {
+        profile.record_daily_result(date, score.breakdown.total, score.medal);
+        profiles.save_active();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::load_city_map;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_same_date_gives_the_same_challenge() {
+        let city_map = load_city_map();
+        let a = DailyChallenge::for_date(date(14), &city_map);
+        let b = DailyChallenge::for_date(date(14), &city_map);
+
+        assert_eq!(a.seed, b.seed);
+        assert_eq!(a.lines(), b.lines());
+        assert_eq!(a.raid_positions(&city_map), b.raid_positions(&city_map));
+        assert!((3..=5).contains(&a.cartel.len()));
+        assert!((4..=7).contains(&a.raid.len()));
+    }
+
+    #[test]
+    fn test_each_day_gets_its_own_seed() {
+        let city_map = load_city_map();
+        let seeds: Vec<u64> = (1..=28).map(|day| daily_seed(date(day))).collect();
+        for (i, seed) in seeds.iter().enumerate() {
+            assert!(!seeds[i + 1..].contains(seed));
+        }
+
+        // Not every day may differ in every respect, but a month can't all be alike
+        let first = DailyChallenge::for_date(date(1), &city_map).lines();
+        assert!((2..=28).any(|day| DailyChallenge::for_date(date(day), &city_map).lines() != first));
+    }
+
+    #[test]
+    fn test_forces_are_placed_one_per_unit() {
+        let city_map = load_city_map();
+        let challenge = DailyChallenge::for_date(date(14), &city_map);
+        let center = challenge.center(&city_map);
+
+        let defenders = challenge.cartel_positions(&city_map);
+        assert_eq!(defenders.len(), challenge.cartel.len());
+        assert!(defenders
+            .iter()
+            .all(|position| (position.distance(center) - DEFENDER_RING).abs() < 1e-3));
+        assert_eq!(
+            challenge.raid_positions(&city_map).len(),
+            challenge.raid.len()
+        );
+    }
+
+    #[test]
+    fn test_roster_label_counts_each_type() {
+        let units = [UnitType::Sicario, UnitType::Sniper, UnitType::Sicario];
+        assert_eq!(roster_label(&units), "2x Sicario, 1x Sniper");
+    }
+}
@@ -0,0 +1,238 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::localization::tr_args;
+use crate::political_system::PoliticalState;
+use crate::systems::{ability_effect_system, combat_system};
+use crate::utils::{
+    get_weapon_sound, play_tactical_sound, play_tactical_sound_at_position, queue_camera_shake,
+    queue_decal, queue_hit_flash, spawn_combat_particles, spawn_damage_indicator,
+    update_veterancy_level, DecalKind, EntityPools, SimulationSet,
+};
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+use std::collections::HashSet;
+
+// ==================== DAMAGE PIPELINE ====================
+
+// Combat, abilities and anything else that hurts a unit send a DamageEvent
+// instead of touching its health. damage_system applies them in order and
+// sends a DownedEvent for each unit the damage brings down, once. Systems that
+// can still save a downed unit - capture, wounding, god mode - run in
+// DownedSet next, and death_system then sends a DeathEvent for each unit that
+// stayed down. Everything that reacts to a hit or a kill - veterancy,
+// casualty counts, sounds, particles, decals, screen feedback - reads those
+// streams in its own system, so new reactions subscribe without reaching into
+// combat.
+//
+// Systems that look at health after combat run after damage_system.
+
+#[derive(Event, Clone, Debug)]
+pub struct DamageEvent {
+    pub attacker: Option<Entity>, // None for ability effects and other sourceless harm
+    pub target: Entity,
+    pub amount: f32, // After weapon, ability and armor modifiers
+    pub weapon: Option<WeaponType>,
+}
+
+// A unit brought down this tick, and the death it will be if nobody saves it
+#[derive(Event, Clone, Debug)]
+pub struct DownedEvent(pub DeathEvent);
+
+#[derive(Event, Clone, Debug)]
+pub struct DeathEvent {
+    pub entity: Entity,
+    pub killer: Option<Entity>,
+    pub faction: Faction,
+    pub unit_type: UnitType,
+    pub position: Vec3,
+}
+
+/// Systems that can keep a downed unit from dying, between damage_system and
+/// death_system.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DownedSet;
+
+pub struct DamagePipelinePlugin;
+
+impl Plugin for DamagePipelinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .add_event::<DownedEvent>()
+            .add_event::<DeathEvent>()
+            .configure_sets(
+                FixedUpdate,
+                DownedSet.after(damage_system).before(death_system),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    damage_system
+                        .after(combat_system)
+                        .after(ability_effect_system),
+                    death_system.after(damage_system),
+                    (
+                        veterancy_system,
+                        casualty_count_system,
+                        combat_feedback_system,
+                    )
+                        .after(death_system),
+                )
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn damage_system(
+    mut damage_events: EventReader<DamageEvent>,
+    mut downed_events: EventWriter<DownedEvent>,
+    mut unit_query: Query<(&mut Unit, &Transform)>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut unit, transform)) = unit_query.get_mut(event.target) else {
+            continue;
+        };
+        // Shots already in flight at a fallen unit don't kill it again
+        if unit.health <= 0.0 {
+            continue;
+        }
+        unit.health -= event.amount;
+        if unit.health <= 0.0 {
+            downed_events.send(DownedEvent(DeathEvent {
+                entity: event.target,
+                killer: event.attacker,
+                faction: unit.faction.clone(),
+                unit_type: unit.unit_type.clone(),
+                position: transform.translation,
+            }));
+        }
+    }
+}
+
+// Prisoners and the wounded are back above zero health, or no longer units,
+// by now; whoever is still down is dead
+pub fn death_system(
+    mut downed_events: EventReader<DownedEvent>,
+    mut death_events: EventWriter<DeathEvent>,
+    unit_query: Query<&Unit>,
+) {
+    for DownedEvent(death) in downed_events.read() {
+        if unit_query
+            .get(death.entity)
+            .is_ok_and(|unit| unit.health <= 0.0)
+        {
+            death_events.send(death.clone());
+        }
+    }
+}
+
+// Every fighter killed weighs on the government, whatever killed them;
+// roadblocks aren't people
+pub fn casualty_count_system(
+    mut death_events: EventReader<DeathEvent>,
+    mut political_state: Option<ResMut<PoliticalState>>,
+) {
+    for death in death_events.read() {
+        let Some(political_state) = political_state.as_deref_mut() else {
+            continue;
+        };
+        if death.unit_type == UnitType::Roadblock {
+            continue;
+        }
+        match death.faction {
+            Faction::Military => political_state.casualties_military += 1,
+            Faction::Cartel => political_state.casualties_cartel += 1,
+            _ => {}
+        }
+    }
+}
+
+pub fn veterancy_system(
+    mut death_events: EventReader<DeathEvent>,
+    mut unit_query: Query<&mut Unit>,
+) {
+    for event in death_events.read() {
+        let Some(killer) = event.killer else {
+            continue;
+        };
+        if let Ok(mut unit) = unit_query.get_mut(killer) {
+            unit.kills += 1;
+            unit.experience += 10;
+            update_veterancy_level(&mut unit);
+            play_tactical_sound(
+                "radio",
+                &tr_args(
+                    "radio.unit_gains_experience",
+                    &[("unit", &format!("{:?}", unit.unit_type))],
+                ),
+            );
+        }
+    }
+}
+
+// Gunfire, damage numbers, tracers, blood and impacts for every hit
+pub fn combat_feedback_system(
+    mut commands: Commands,
+    mut entity_pools: ResMut<EntityPools>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut death_events: EventReader<DeathEvent>,
+    transform_query: Query<&Transform>,
+) {
+    let killed: HashSet<Entity> = death_events.read().map(|event| event.entity).collect();
+
+    for event in damage_events.read() {
+        let Ok(target_transform) = transform_query.get(event.target) else {
+            continue;
+        };
+        let target_position = target_transform.translation;
+        let target_died = killed.contains(&event.target);
+
+        if let Some(weapon) = &event.weapon {
+            let origin = event
+                .attacker
+                .and_then(|attacker| transform_query.get(attacker).ok())
+                .map_or(target_position, |transform| transform.translation);
+            play_tactical_sound_at_position(
+                get_weapon_sound(weapon),
+                &format!("Combat: {} damage dealt", event.amount as u32),
+                origin,
+            );
+            if *weapon == WeaponType::RPG {
+                queue_camera_shake(&mut commands, target_position, 0.35);
+            }
+            spawn_combat_particles(&mut commands, &mut entity_pools, origin, target_position);
+        }
+
+        spawn_damage_indicator(
+            &mut commands,
+            &mut entity_pools,
+            target_position,
+            event.amount,
+        );
+        if !target_died {
+            queue_hit_flash(&mut commands, event.target);
+        }
+        let blood_scale = if target_died { 2.0 } else { 1.0 };
+        queue_decal(
+            &mut commands,
+            DecalKind::Blood,
+            target_position,
+            blood_scale,
+        );
+        if event.weapon.is_some() {
+            let stray_round = Vec3::new(
+                thread_rng().gen_range(-15.0..15.0),
+                thread_rng().gen_range(-15.0..15.0),
+                0.0,
+            );
+            queue_decal(
+                &mut commands,
+                DecalKind::BulletImpact,
+                target_position + stray_round,
+                1.0,
+            );
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use crate::ai::unit_ai_system;
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::BalanceConfig;
+use crate::coordination::advanced_tactical_ai_system;
+use crate::localization::{tr, tr_args};
+use crate::resources::GameState;
+use crate::systems::combat_system;
+use crate::tutorial::Tutorial;
+use crate::utils::{play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== DEPLOYMENT PLUGIN ====================
+
+// A mission played at the screen opens on a paused map. setup_game's layout
+// is only where the fighters start out: while planning, a move order puts
+// the selected fighters straight onto their formation slots instead of
+// walking them there, as long as the spot is within reach of a safehouse or
+// of Ovidio. Everything else the player would set up before the shooting -
+// squads, formations, hold areas, rally points and ambushes - works as
+// usual on the paused map. Enter commits the plan and starts the clock.
+// The tutorial and headless runs skip straight to the mission.
+//
+// Fighters set in ambush (Apostrophe) hold still and hold their fire until
+// the army comes within ambush_trigger_radius of one of them; then every
+// ambusher near that one opens up together.
+
+// Type aliases to reduce complexity
+type PlacementQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Unit, &'static mut Transform, &'static mut Movement),
+    (Without<SafehouseInterior>, Without<ProtectedPrincipal>),
+>;
+type AmbusherQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Unit,
+        &'static Transform,
+        Option<&'static mut Movement>,
+    ),
+    With<Ambush>,
+>;
+
+pub struct DeploymentPlugin;
+
+impl Plugin for DeploymentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DeploymentPlan>()
+            .add_systems(OnEnter(AppState::InGame), deployment_start_system)
+            .add_systems(OnExit(AppState::InGame), deployment_end_system)
+            .add_systems(Update, deployment_placement_system.in_set(GameSet::InGame))
+            .add_systems(
+                FixedUpdate,
+                ambush_system
+                    .after(unit_ai_system)
+                    .after(advanced_tactical_ai_system)
+                    .before(combat_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DeploymentPlan {
+    pub interactive: bool, // Only a player at the screen plans a deployment
+    pub planning: bool,
+    paused_here: bool,
+}
+
+impl DeploymentPlan {
+    pub fn interactive() -> Self {
+        Self {
+            interactive: true,
+            ..default()
+        }
+    }
+
+    /// Pauses the battle for planning, unless something else already has.
+    pub fn begin(&mut self, time: &mut Time<Virtual>) {
+        self.planning = true;
+        if !time.is_paused() {
+            time.pause();
+            self.paused_here = true;
+        }
+    }
+
+    /// Ends planning and lets the battle run, if the pause was ours.
+    pub fn commit(&mut self, time: &mut Time<Virtual>) {
+        self.planning = false;
+        if self.paused_here {
+            time.unpause();
+            self.paused_here = false;
+        }
+    }
+}
+
+/// Whether `point` is somewhere fighters may be deployed: within `radius` of
+/// one of the zone centers.
+pub fn in_deployment_zone(point: Vec2, zones: &[Vec2], radius: f32) -> bool {
+    zones.iter().any(|zone| zone.distance(point) <= radius)
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn deployment_start_system(
+    game_state: Res<GameState>,
+    tutorial: Option<Res<Tutorial>>,
+    mut plan: ResMut<DeploymentPlan>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let tutorial = tutorial.is_some_and(|tutorial| tutorial.holds_mission_clock());
+    if !plan.interactive || tutorial || game_state.game_phase != GamePhase::Preparation {
+        return;
+    }
+    plan.begin(&mut time);
+    play_tactical_sound("radio", &tr("radio.deployment_begin"));
+}
+
+// Leaving the mission mid-plan mustn't leave the game paused
+pub fn deployment_end_system(mut plan: ResMut<DeploymentPlan>, mut time: ResMut<Time<Virtual>>) {
+    if plan.planning {
+        plan.commit(&mut time);
+    }
+}
+
+// Enter: the plan is set, start the mission
+pub fn deployment_commit_system(
+    input: Res<Input<KeyCode>>,
+    mut plan: ResMut<DeploymentPlan>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if !plan.planning || !input.just_pressed(KeyCode::Return) {
+        return;
+    }
+    plan.commit(&mut time);
+    play_tactical_sound("radio", &tr("radio.deployment_committed"));
+}
+
+// Apostrophe: the selected fighters go into ambush, or come out of it
+pub fn ambush_order_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    selected_query: Query<(Entity, &Unit, Has<Ambush>), With<Selected>>,
+) {
+    if !input.just_pressed(KeyCode::Apostrophe) {
+        return;
+    }
+    let fighters: Vec<(Entity, bool)> = selected_query
+        .iter()
+        .filter(|(_, unit, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(entity, _, ambushing)| (entity, ambushing))
+        .collect();
+    if fighters.is_empty() {
+        return;
+    }
+
+    // A mixed selection all goes into ambush
+    let lifting = fighters.iter().all(|(_, ambushing)| *ambushing);
+    for (entity, _) in &fighters {
+        if lifting {
+            commands.entity(*entity).remove::<Ambush>();
+        } else {
+            commands.entity(*entity).insert(Ambush);
+        }
+    }
+    let message = if lifting {
+        "radio.ambush_lifted"
+    } else {
+        "radio.ambush_set"
+    };
+    play_tactical_sound("radio", &tr_args(message, &[("count", &fighters.len())]));
+}
+
+// While planning, move orders place fighters rather than walk them there
+pub fn deployment_placement_system(
+    plan: Res<DeploymentPlan>,
+    balance: Res<BalanceConfig>,
+    zone_query: Query<&Transform, Or<(With<SafehouseInterior>, With<ProtectedPrincipal>)>>,
+    mut unit_query: PlacementQuery,
+) {
+    if !plan.planning {
+        return;
+    }
+    let zones: Vec<Vec2> = zone_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+
+    let mut refused = false;
+    for (unit, mut transform, mut movement) in unit_query.iter_mut() {
+        if unit.faction != Faction::Cartel {
+            continue;
+        }
+        let Some(spot) = movement.target_position else {
+            continue;
+        };
+        movement.target_position = None;
+        if in_deployment_zone(spot.truncate(), &zones, balance.deployment.zone_radius) {
+            transform.translation.x = spot.x;
+            transform.translation.y = spot.y;
+        } else {
+            refused = true;
+        }
+    }
+    if refused {
+        play_tactical_sound("radio", &tr("radio.deployment_outside_zone"));
+    }
+}
+
+// Ambushers keep still and quiet until the army walks into one of them
+pub fn ambush_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut ambusher_query: AmbusherQuery,
+    enemy_query: Query<(&Unit, &Transform), Without<Ambush>>,
+) {
+    let balance = &balance.deployment;
+    let enemies: Vec<Vec2> = enemy_query
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Military && unit.health > 0.0)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+
+    let triggered: Vec<Vec2> = ambusher_query
+        .iter()
+        .map(|(_, _, transform, _)| transform.translation.truncate())
+        .filter(|position| {
+            enemies
+                .iter()
+                .any(|enemy| enemy.distance(*position) <= balance.ambush_trigger_radius)
+        })
+        .collect();
+
+    let mut sprung = 0;
+    for (entity, mut unit, transform, movement) in ambusher_query.iter_mut() {
+        let position = transform.translation.truncate();
+        if triggered
+            .iter()
+            .any(|trigger| trigger.distance(position) <= balance.ambush_spring_radius)
+        {
+            commands.entity(entity).remove::<Ambush>();
+            sprung += 1;
+            continue;
+        }
+        // Their own AI would give them away chasing whatever it sees
+        if unit.target.is_some() {
+            unit.target = None;
+        }
+        if let Some(mut movement) = movement.filter(|m| m.target_position.is_some()) {
+            movement.target_position = None;
+        }
+    }
+    if sprung > 0 {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.ambush_sprung", &[("count", &sprung)]),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deployment_zones_surround_their_centers() {
+        let zones = [Vec2::ZERO, Vec2::new(350.0, -200.0)];
+        assert!(in_deployment_zone(Vec2::new(100.0, 50.0), &zones, 150.0));
+        assert!(in_deployment_zone(Vec2::new(350.0, -60.0), &zones, 150.0));
+        assert!(!in_deployment_zone(Vec2::new(-300.0, 0.0), &zones, 150.0));
+        assert!(!in_deployment_zone(Vec2::ZERO, &[], 150.0));
+    }
+
+    #[test]
+    fn test_planning_only_lifts_a_pause_it_made() {
+        let mut time = Time::<Virtual>::default();
+        let mut plan = DeploymentPlan::interactive();
+        plan.begin(&mut time);
+        assert!(plan.planning && time.is_paused());
+        plan.commit(&mut time);
+        assert!(!plan.planning && !time.is_paused());
+
+        // Something else paused the battle first; it stays paused
+        time.pause();
+        plan.begin(&mut time);
+        plan.commit(&mut time);
+        assert!(time.is_paused());
+    }
+}
@@ -0,0 +1,388 @@
+use crate::campaign::Campaign;
+use crate::components::{Movement, Unit};
+use crate::headless::{build_headless_app, mission_finished, HeadlessOptions};
+use crate::political_system::PoliticalState;
+use crate::resources::{AiDirector, GameState};
+use crate::utils::{new_sim_seed, RngStream, SimRng};
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::schedule::{ScheduleLabel, Schedules};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+// ==================== DETERMINISM CHECK ====================
+
+// --check-determinism runs the same seeded headless mission twice in one
+// process and compares a checksum of the simulation after every tick - the
+// guarantee replays and lockstep multiplayer stand on. The state is summed in
+// sections (unit positions, unit state, each RNG stream, the mission
+// resources) so a divergence says what drifted, and the systems whose access
+// writes that section are reported as responsible. Each run gets fresh
+// HashMap seeds, so iteration-order bugs show up as well as stray thread_rng.
+
+// A slice of simulation state, and what systems must write to change it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SectionData {
+    UnitPositions,
+    UnitState,
+    UnitMovement,
+    Rng(RngStream),
+    GameState,
+    AiDirector,
+    Campaign,
+    PoliticalState,
+}
+
+fn sections() -> Vec<SectionData> {
+    [
+        SectionData::UnitPositions,
+        SectionData::UnitState,
+        SectionData::UnitMovement,
+    ]
+    .into_iter()
+    .chain(RngStream::ALL.into_iter().map(SectionData::Rng))
+    .chain([
+        SectionData::GameState,
+        SectionData::AiDirector,
+        SectionData::Campaign,
+        SectionData::PoliticalState,
+    ])
+    .collect()
+}
+
+impl SectionData {
+    fn name(self) -> String {
+        match self {
+            SectionData::UnitPositions => "unit positions".to_string(),
+            SectionData::UnitState => "unit state".to_string(),
+            SectionData::UnitMovement => "unit movement".to_string(),
+            SectionData::Rng(stream) => format!("{:?} RNG stream", stream),
+            SectionData::GameState => "game state".to_string(),
+            SectionData::AiDirector => "AI director".to_string(),
+            SectionData::Campaign => "campaign".to_string(),
+            SectionData::PoliticalState => "political state".to_string(),
+        }
+    }
+
+    fn component_id(self, world: &World) -> Option<ComponentId> {
+        let components = world.components();
+        match self {
+            SectionData::UnitPositions => components.component_id::<Transform>(),
+            SectionData::UnitState => components.component_id::<Unit>(),
+            SectionData::UnitMovement => components.component_id::<Movement>(),
+            SectionData::Rng(_) => components.resource_id::<SimRng>(),
+            SectionData::GameState => components.resource_id::<GameState>(),
+            SectionData::AiDirector => components.resource_id::<AiDirector>(),
+            SectionData::Campaign => components.resource_id::<Campaign>(),
+            SectionData::PoliticalState => components.resource_id::<PoliticalState>(),
+        }
+    }
+
+    fn checksum(self, world: &mut World) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            SectionData::UnitPositions => {
+                for (entity, transform) in sorted_units::<&Transform>(world) {
+                    entity.hash(&mut hasher);
+                    hash_floats(&mut hasher, &transform.translation.to_array());
+                }
+            }
+            SectionData::UnitState => {
+                for (entity, unit) in sorted_units::<&Unit>(world) {
+                    entity.hash(&mut hasher);
+                    hash_floats(
+                        &mut hasher,
+                        &[unit.health, unit.attack_cooldown.elapsed_secs()],
+                    );
+                    (unit.target, unit.kills, unit.experience).hash(&mut hasher);
+                }
+            }
+            SectionData::UnitMovement => {
+                for (entity, movement) in sorted_units::<&Movement>(world) {
+                    entity.hash(&mut hasher);
+                    if let Some(target) = movement.target_position {
+                        hash_floats(&mut hasher, &target.to_array());
+                    }
+                    hash_floats(&mut hasher, &[movement.speed]);
+                }
+            }
+            SectionData::Rng(stream) => {
+                if let Some(sim_rng) = world.get_resource::<SimRng>() {
+                    sim_rng.fingerprint(stream).hash(&mut hasher);
+                }
+            }
+            // Debug prints floats exactly and none of these hold a HashMap
+            SectionData::GameState => {
+                if let Some(game_state) = world.get_resource::<GameState>() {
+                    format!("{:?}", game_state).hash(&mut hasher);
+                }
+            }
+            SectionData::AiDirector => {
+                if let Some(director) = world.get_resource::<AiDirector>() {
+                    hash_floats(
+                        &mut hasher,
+                        &[
+                            director.intensity_level,
+                            director.last_spawn_time,
+                            director.player_performance,
+                        ],
+                    );
+                }
+            }
+            SectionData::Campaign => {
+                if let Some(campaign) = world.get_resource::<Campaign>() {
+                    hash_floats(&mut hasher, &[campaign.mission_timer]);
+                    campaign.objectives_completed.hash(&mut hasher);
+                    format!("{:?}", campaign.political_pressure).hash(&mut hasher);
+                }
+            }
+            SectionData::PoliticalState => {
+                if let Some(political_state) = world.get_resource::<PoliticalState>() {
+                    serde_json::to_string(political_state)
+                        .unwrap_or_default()
+                        .hash(&mut hasher);
+                }
+            }
+        }
+        hasher.finish()
+    }
+}
+
+fn sorted_units<'w, D: bevy::ecs::query::ReadOnlyWorldQuery>(
+    world: &'w mut World,
+) -> Vec<(Entity, D::Item<'w>)> {
+    let mut query = world.query_filtered::<(Entity, D), With<Unit>>();
+    let mut units: Vec<_> = query.iter(world).collect();
+    units.sort_by_key(|(entity, _)| *entity);
+    units
+}
+
+fn hash_floats(hasher: &mut DefaultHasher, values: &[f32]) {
+    for value in values {
+        value.to_bits().hash(hasher);
+    }
+}
+
+// ==================== RUNS ====================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Divergence {
+    pub tick: usize,
+    pub time: f32,
+    pub sections: Vec<String>,
+    pub responsible_systems: Vec<String>, // Systems that write the first drifting section
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeterminismReport {
+    pub seed: u64,
+    pub ticks: usize,
+    pub divergence: Option<Divergence>,
+}
+
+// One checksum per section per tick
+type RunTrace = Vec<Vec<u64>>;
+
+fn record_run(options: &HeadlessOptions) -> (RunTrace, f32) {
+    let mut app = build_headless_app(options);
+    app.finish();
+    app.cleanup();
+
+    let timestep = app.world.resource::<Time<Fixed>>().timestep().as_secs_f32();
+    let sections = sections();
+    let mut trace = Vec::new();
+    let mut elapsed = 0.0;
+
+    while elapsed < options.max_duration && !mission_finished(&app.world) {
+        app.update();
+        elapsed += timestep;
+        trace.push(
+            sections
+                .iter()
+                .map(|section| section.checksum(&mut app.world))
+                .collect(),
+        );
+    }
+    (trace, timestep)
+}
+
+// First tick where the runs disagree, and which sections differ there. A run
+// that stops early diverges where it stopped.
+pub fn first_divergence(a: &[Vec<u64>], b: &[Vec<u64>]) -> Option<(usize, Vec<usize>)> {
+    let differing = a.iter().zip(b).enumerate().find_map(|(tick, (a, b))| {
+        let sections: Vec<usize> = (0..a.len().max(b.len()))
+            .filter(|section| a.get(*section) != b.get(*section))
+            .collect();
+        (!sections.is_empty()).then_some((tick, sections))
+    });
+    differing.or_else(|| (a.len() != b.len()).then(|| (a.len().min(b.len()), Vec::new())))
+}
+
+// Systems in the simulation's schedules whose access writes the section.
+// Commands (spawning, inserting) don't show up as access, so a section that
+// drifts only through them has no suspects.
+fn section_writers(options: &HeadlessOptions, section: SectionData) -> Vec<String> {
+    let mut app = build_headless_app(options);
+    app.finish();
+    app.cleanup();
+
+    app.world
+        .resource_scope(|world, mut schedules: Mut<Schedules>| {
+            let labels = [
+                PreUpdate.intern(),
+                FixedUpdate.intern(),
+                Update.intern(),
+                PostUpdate.intern(),
+            ];
+            let mut writers = Vec::new();
+            for label in labels {
+                let Some(schedule) = schedules.get_mut(label) else {
+                    continue;
+                };
+                // Access is only known once systems are initialized
+                schedule.graph_mut().initialize(world);
+                let Some(id) = section.component_id(world) else {
+                    continue;
+                };
+                writers.extend(
+                    schedule
+                        .graph()
+                        .systems()
+                        .filter(|(_, system, _)| system.component_access().has_write(id))
+                        .map(|(_, system, _)| format!("{:?}: {}", label, system.name())),
+                );
+            }
+            writers
+        })
+}
+
+pub fn run_determinism_check(options: &HeadlessOptions) -> DeterminismReport {
+    let mut options = options.clone();
+    let seed = *options.seed.get_or_insert_with(new_sim_seed);
+
+    let (first, timestep) = record_run(&options);
+    let (second, _) = record_run(&options);
+    let sections = sections();
+
+    let divergence = first_divergence(&first, &second).map(|(tick, differing)| {
+        let responsible_systems = differing
+            .first()
+            .map(|section| section_writers(&options, sections[*section]))
+            .unwrap_or_default();
+        Divergence {
+            tick,
+            time: (tick + 1) as f32 * timestep,
+            sections: differing
+                .iter()
+                .map(|section| sections[*section].name())
+                .collect(),
+            responsible_systems,
+        }
+    });
+
+    DeterminismReport {
+        seed,
+        ticks: first.len().min(second.len()),
+        divergence,
+    }
+}
+
+// Prints a summary (and writes the JSON report with --output); Ok(false) means the runs diverged
+pub fn check_and_report(options: &HeadlessOptions) -> Result<bool, Box<dyn std::error::Error>> {
+    let report = run_determinism_check(options);
+    if let Some(path) = &options.output {
+        fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+
+    match &report.divergence {
+        None => {
+            println!(
+                "✅ Deterministic: seed {} matched for {} ticks",
+                report.seed, report.ticks
+            );
+            Ok(true)
+        }
+        Some(divergence) => {
+            println!(
+                "❌ Seed {} diverged at tick {} ({:.2}s)",
+                report.seed, divergence.tick, divergence.time
+            );
+            if divergence.sections.is_empty() {
+                println!("   One run ended the mission earlier than the other");
+            } else {
+                println!("   Drifted: {}", divergence.sections.join(", "));
+            }
+            for system in &divergence.responsible_systems {
+                println!("   Written by {}", system);
+            }
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ArmorType, Equipment, Faction, UnitType, VeterancyLevel, WeaponType};
+
+    #[test]
+    fn test_first_divergence_finds_tick_and_sections() {
+        let a = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let mut b = a.clone();
+        assert_eq!(first_divergence(&a, &b), None);
+
+        b[1][2] = 0;
+        b[2][0] = 0;
+        assert_eq!(first_divergence(&a, &b), Some((1, vec![2])));
+
+        // Identical until one run ends early
+        assert_eq!(first_divergence(&a, &a[..2]), Some((2, Vec::new())));
+    }
+
+    #[test]
+    fn test_section_checksums_follow_unit_state() {
+        let mut world = World::new();
+        world.insert_resource(SimRng::new(5));
+        let unit = world
+            .spawn((
+                Transform::from_xyz(10.0, 20.0, 0.0),
+                Unit {
+                    health: 100.0,
+                    max_health: 100.0,
+                    faction: Faction::Cartel,
+                    unit_type: UnitType::Sicario,
+                    damage: 30.0,
+                    range: 100.0,
+                    vision: 150.0,
+                    movement_speed: 40.0,
+                    target: None,
+                    attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                    experience: 0,
+                    kills: 0,
+                    veterancy_level: VeterancyLevel::Recruit,
+                    equipment: Equipment {
+                        weapon: WeaponType::BasicRifle,
+                        armor: ArmorType::None,
+                        upgrades: vec![],
+                    },
+                },
+            ))
+            .id();
+        let checksums = |world: &mut World| -> Vec<u64> {
+            sections()
+                .iter()
+                .map(|section| section.checksum(world))
+                .collect()
+        };
+
+        let before = checksums(&mut world);
+        assert_eq!(before, checksums(&mut world));
+
+        // Only the moved unit's section changes
+        world.get_mut::<Transform>(unit).unwrap().translation.x += 0.001;
+        let after = checksums(&mut world);
+        assert_eq!(first_divergence(&[before], &[after]), Some((0, vec![0])));
+    }
+}
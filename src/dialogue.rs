@@ -0,0 +1,519 @@
+use crate::components::{GamePhase, IsometricCamera, Unit, UnitType};
+use crate::config::UnitCatalog;
+use crate::localization::{tr, tr_args, try_tr};
+use crate::narration::{NarrationEvent, NarrationPriority};
+use crate::resources::GameState;
+use crate::scripting::parse_variant;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+// ==================== CONVERSATIONS ====================
+
+// In-engine conversations between characters, started by mission scripts with
+// conversation(id, lines) - mission intros in assets/scripts/briefings.rhai
+// turn the briefing into a talk between cartel lieutenants. Each line has a
+// speaker, text typed out a few characters at a time, an optional portrait,
+// an optional camera focus and optional choices; a picked choice goes back to
+// the scripts through on_dialogue_choice(id, index). The battle holds still
+// while a conversation plays. SPACE/ENTER finishes the line or moves on, 1-4
+// pick a choice and BACKSPACE skips ahead to the next choice or the end.
+
+const TYPEWRITER_CHARS_PER_SECOND: f32 = 45.0;
+const FOCUS_SPEED: f32 = 3.0; // Fraction of the gap to the focus closed per second
+const PORTRAIT_SIZE: f32 = 96.0;
+pub const MAX_CHOICES: usize = 4;
+const CHOICE_KEYS: [KeyCode; MAX_CHOICES] =
+    [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4];
+
+pub struct DialoguePlugin;
+
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Conversations>().add_systems(
+            Update,
+            (
+                conversation_input_system,
+                conversation_pause_system,
+                conversation_camera_system,
+                conversation_ui_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+// Where the camera looks while a line is shown
+#[derive(Clone, Debug, PartialEq)]
+pub enum CameraFocus {
+    Unit(UnitType), // The first living unit of that type
+    Point(Vec2),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DialogueLine {
+    pub speaker: String,
+    pub text: String,             // Literal text or a localization key
+    pub portrait: Option<String>, // A unit type, for its sprite, or an image path
+    pub focus: Option<CameraFocus>,
+    pub choices: Vec<String>, // Literal text or localization keys
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conversation {
+    pub id: String,
+    pub lines: Vec<DialogueLine>,
+}
+
+// The conversation on screen and how far into it the player is
+#[derive(Clone, Debug)]
+pub struct ConversationPlayback {
+    pub conversation: Conversation,
+    pub line: usize,
+    typed: f32, // Characters of the current line shown so far
+}
+
+#[derive(Resource, Default)]
+pub struct Conversations {
+    queue: VecDeque<Conversation>,
+    playing: Option<ConversationPlayback>,
+    choices: Vec<(String, usize)>, // Picked, waiting for on_dialogue_choice
+}
+
+impl Conversations {
+    // Conversations play one after another in the order scripts start them
+    pub fn start(&mut self, conversation: Conversation) {
+        if conversation.lines.is_empty() {
+            return;
+        }
+        self.queue.push_back(conversation);
+        if self.playing.is_none() {
+            self.next_conversation();
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing.is_some()
+    }
+
+    pub fn playback(&self) -> Option<&ConversationPlayback> {
+        self.playing.as_ref()
+    }
+
+    pub fn current_line(&self) -> Option<&DialogueLine> {
+        let playback = self.playing.as_ref()?;
+        playback.conversation.lines.get(playback.line)
+    }
+
+    // The current line's text as far as the typewriter has got
+    pub fn visible_text(&self) -> String {
+        let (Some(playback), Some(line)) = (self.playing.as_ref(), self.current_line()) else {
+            return String::new();
+        };
+        display_text(&line.text)
+            .chars()
+            .take(playback.typed as usize)
+            .collect()
+    }
+
+    pub fn is_typing(&self) -> bool {
+        let (Some(playback), Some(line)) = (self.playing.as_ref(), self.current_line()) else {
+            return false;
+        };
+        (playback.typed as usize) < display_text(&line.text).chars().count()
+    }
+
+    pub fn type_out(&mut self, seconds: f32) {
+        if let Some(playback) = self.playing.as_mut() {
+            playback.typed += seconds * TYPEWRITER_CHARS_PER_SECOND;
+        }
+    }
+
+    // SPACE/ENTER: finish typing the line, then move on unless it asks for a choice
+    pub fn advance(&mut self) {
+        if self.is_typing() {
+            self.finish_typing();
+        } else if self
+            .current_line()
+            .is_some_and(|line| line.choices.is_empty())
+        {
+            self.next_line();
+        }
+    }
+
+    pub fn choose(&mut self, index: usize) {
+        let Some(line) = self.current_line() else {
+            return;
+        };
+        if index >= line.choices.len() {
+            return;
+        }
+        if let Some(playback) = self.playing.as_ref() {
+            self.choices.push((playback.conversation.id.clone(), index));
+        }
+        self.next_line();
+    }
+
+    // BACKSPACE: jump to the next line that asks for a choice, or end the
+    // conversation; choices are never skipped over
+    pub fn skip(&mut self) {
+        let Some(playback) = self.playing.as_mut() else {
+            return;
+        };
+        let lines = &playback.conversation.lines;
+        let next_choice =
+            (playback.line + 1..lines.len()).find(|index| !lines[*index].choices.is_empty());
+        let on_choice = !lines[playback.line].choices.is_empty();
+        match next_choice {
+            Some(index) if !on_choice => {
+                playback.line = index;
+                playback.typed = 0.0;
+                self.finish_typing();
+            }
+            _ if on_choice => self.finish_typing(),
+            _ => self.next_conversation(),
+        }
+    }
+
+    // Handed to the scripts' on_dialogue_choice hook
+    pub fn take_choices(&mut self) -> Vec<(String, usize)> {
+        std::mem::take(&mut self.choices)
+    }
+
+    // Leaving the mission drops whatever was still to be said
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.playing = None;
+    }
+
+    fn finish_typing(&mut self) {
+        if let Some(playback) = self.playing.as_mut() {
+            playback.typed = f32::MAX;
+        }
+    }
+
+    fn next_line(&mut self) {
+        let Some(playback) = self.playing.as_mut() else {
+            return;
+        };
+        playback.line += 1;
+        playback.typed = 0.0;
+        if playback.line >= playback.conversation.lines.len() {
+            self.next_conversation();
+        }
+    }
+
+    fn next_conversation(&mut self) {
+        self.playing = self
+            .queue
+            .pop_front()
+            .map(|conversation| ConversationPlayback {
+                conversation,
+                line: 0,
+                typed: 0.0,
+            });
+    }
+}
+
+// Scripts may pass either literal text or a key from the language packs
+pub fn display_text(text: &str) -> String {
+    try_tr(text).unwrap_or_else(|| text.to_string())
+}
+
+pub fn conversation_input_system(
+    mut conversations: ResMut<Conversations>,
+    mut input: ResMut<Input<KeyCode>>,
+    game_state: Res<GameState>,
+    time: Res<Time<Real>>, // The battle is paused underneath
+) {
+    if !conversations.is_playing() {
+        return;
+    }
+    if game_state.game_phase == GamePhase::MainMenu {
+        conversations.clear();
+        return;
+    }
+    conversations.type_out(time.delta_seconds());
+
+    // The keys are the conversation's while it plays, not the battle's
+    if input.just_pressed(KeyCode::Back) {
+        input.clear_just_pressed(KeyCode::Back);
+        conversations.skip();
+    } else if input.any_just_pressed([KeyCode::Space, KeyCode::Return]) {
+        input.clear_just_pressed(KeyCode::Space);
+        input.clear_just_pressed(KeyCode::Return);
+        conversations.advance();
+    } else if let Some(index) = CHOICE_KEYS.iter().position(|key| input.just_pressed(*key)) {
+        input.clear_just_pressed(CHOICE_KEYS[index]);
+        conversations.choose(index);
+    }
+}
+
+// Holds the battle while a conversation plays, and only lets go of a pause
+// it made itself
+pub fn conversation_pause_system(
+    conversations: Res<Conversations>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut paused_here: Local<bool>,
+) {
+    let playing = conversations.is_playing();
+    if playing && !*paused_here && !virtual_time.is_paused() {
+        virtual_time.pause();
+        *paused_here = true;
+    } else if !playing && *paused_here {
+        virtual_time.unpause();
+        *paused_here = false;
+    }
+}
+
+pub fn conversation_camera_system(
+    conversations: Res<Conversations>,
+    time: Res<Time<Real>>,
+    unit_query: Query<(&Unit, &Transform), Without<IsometricCamera>>,
+    mut camera_query: Query<&mut Transform, With<IsometricCamera>>,
+) {
+    let Some(focus) = conversations
+        .current_line()
+        .and_then(|line| line.focus.as_ref())
+    else {
+        return;
+    };
+    let target = match focus {
+        CameraFocus::Point(point) => Some(*point),
+        CameraFocus::Unit(unit_type) => unit_query
+            .iter()
+            .find(|(unit, _)| unit.unit_type == *unit_type && unit.health > 0.0)
+            .map(|(_, transform)| transform.translation.truncate()),
+    };
+    let (Some(target), Ok(mut camera)) = (target, camera_query.get_single_mut()) else {
+        return;
+    };
+
+    let blend = (FOCUS_SPEED * time.delta_seconds()).min(1.0);
+    let next = camera.translation.truncate().lerp(target, blend);
+    camera.translation.x = next.x;
+    camera.translation.y = next.y;
+}
+
+#[derive(Component)]
+pub struct ConversationBox;
+
+#[derive(Component)]
+pub struct ConversationText;
+
+pub fn conversation_ui_system(
+    mut commands: Commands,
+    conversations: Res<Conversations>,
+    asset_server: Res<AssetServer>,
+    catalog: Res<UnitCatalog>,
+    box_query: Query<Entity, With<ConversationBox>>,
+    mut text_query: Query<&mut Text, With<ConversationText>>,
+    mut narration: EventWriter<NarrationEvent>,
+    mut shown: Local<Option<(String, usize)>>,
+) {
+    let current = conversations
+        .playback()
+        .map(|playback| (playback.conversation.id.clone(), playback.line));
+
+    // The typewriter only rewrites the text; a new line rebuilds the box
+    if *shown == current {
+        if let Ok(mut text) = text_query.get_single_mut() {
+            let visible = conversations.visible_text();
+            if text.sections[0].value != visible {
+                text.sections[0].value = visible;
+            }
+        }
+        return;
+    }
+    shown.clone_from(&current);
+    for entity in box_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(line) = conversations.current_line() else {
+        return;
+    };
+
+    let speaker = display_text(&line.speaker);
+    narration.send(NarrationEvent::new(
+        format!("{}: {}", speaker, display_text(&line.text)),
+        NarrationPriority::Update,
+    ));
+
+    // A unit type shows that unit's sprite; anything else is an image path
+    let portrait = line.portrait.as_ref().map(|portrait| {
+        let path = match parse_variant::<UnitType>(portrait) {
+            Some(unit_type) => catalog.definition(&unit_type).sprite.clone(),
+            None => portrait.clone(),
+        };
+        asset_server.load::<Image>(path)
+    });
+    let hint = if line.choices.is_empty() {
+        tr("conversation.continue")
+    } else {
+        tr_args("conversation.choose", &[("count", &line.choices.len())])
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(20.0),
+                    bottom: Val::Px(40.0),
+                    width: Val::Percent(60.0),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.85)),
+                z_index: ZIndex::Global(40),
+                ..default()
+            },
+            ConversationBox,
+        ))
+        .with_children(|parent| {
+            if let Some(image) = portrait {
+                parent.spawn(ImageBundle {
+                    style: Style {
+                        width: Val::Px(PORTRAIT_SIZE),
+                        height: Val::Px(PORTRAIT_SIZE),
+                        flex_shrink: 0.0,
+                        ..default()
+                    },
+                    image: UiImage::new(image),
+                    background_color: BackgroundColor(Color::rgba(1.0, 1.0, 1.0, 0.1)),
+                    ..default()
+                });
+            }
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        flex_grow: 1.0,
+                        row_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|column| {
+                    column.spawn(TextBundle::from_section(
+                        speaker,
+                        TextStyle {
+                            font_size: 20.0,
+                            color: Color::rgb(1.0, 0.8, 0.0),
+                            ..default()
+                        },
+                    ));
+                    column.spawn((
+                        TextBundle::from_section(
+                            conversations.visible_text(),
+                            TextStyle {
+                                font_size: 18.0,
+                                color: Color::WHITE,
+                                ..default()
+                            },
+                        ),
+                        ConversationText,
+                    ));
+                    for (index, choice) in line.choices.iter().enumerate() {
+                        column.spawn(TextBundle::from_section(
+                            format!("{}. {}", index + 1, display_text(choice)),
+                            TextStyle {
+                                font_size: 18.0,
+                                color: Color::rgb(0.6, 0.9, 1.0),
+                                ..default()
+                            },
+                        ));
+                    }
+                    column.spawn(TextBundle::from_section(
+                        hint,
+                        TextStyle {
+                            font_size: 14.0,
+                            color: Color::rgb(0.7, 0.7, 0.7),
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str, choices: &[&str]) -> DialogueLine {
+        DialogueLine {
+            speaker: "Lookout".to_string(),
+            text: text.to_string(),
+            portrait: None,
+            focus: None,
+            choices: choices.iter().map(|choice| choice.to_string()).collect(),
+        }
+    }
+
+    fn conversation(id: &str, lines: Vec<DialogueLine>) -> Conversation {
+        Conversation {
+            id: id.to_string(),
+            lines,
+        }
+    }
+
+    #[test]
+    fn test_lines_type_out_then_advance_and_conversations_queue() {
+        let mut conversations = Conversations::default();
+        conversations.start(conversation("empty", Vec::new()));
+        assert!(!conversations.is_playing());
+
+        conversations.start(conversation(
+            "intro",
+            vec![line("Trucks on the boulevard", &[]), line("Hold", &[])],
+        ));
+        conversations.start(conversation("later", vec![line("Again", &[])]));
+        assert_eq!(conversations.visible_text(), "");
+
+        conversations.type_out(5.0 / TYPEWRITER_CHARS_PER_SECOND);
+        assert_eq!(conversations.visible_text(), "Truck");
+        assert!(conversations.is_typing());
+
+        conversations.advance();
+        assert_eq!(conversations.visible_text(), "Trucks on the boulevard");
+        assert!(!conversations.is_typing());
+        conversations.advance();
+        assert_eq!(conversations.playback().unwrap().line, 1);
+
+        conversations.advance();
+        conversations.advance();
+        assert_eq!(conversations.playback().unwrap().conversation.id, "later");
+        conversations.skip();
+        assert!(!conversations.is_playing());
+    }
+
+    #[test]
+    fn test_choices_wait_for_an_answer_and_are_never_skipped() {
+        let mut conversations = Conversations::default();
+        conversations.start(conversation(
+            "plan",
+            vec![
+                line("They're coming", &[]),
+                line("Where do we go?", &["Boulevard", "Safehouse"]),
+                line("Move", &[]),
+            ],
+        ));
+
+        conversations.skip();
+        assert_eq!(conversations.playback().unwrap().line, 1);
+        assert!(!conversations.is_typing());
+
+        conversations.advance();
+        conversations.skip();
+        conversations.choose(2);
+        assert_eq!(conversations.playback().unwrap().line, 1, "still waiting");
+
+        conversations.choose(1);
+        assert_eq!(conversations.take_choices(), vec![("plan".to_string(), 1)]);
+        assert!(conversations.take_choices().is_empty());
+        assert_eq!(conversations.playback().unwrap().line, 2);
+
+        conversations.skip();
+        assert!(!conversations.is_playing());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::app_state::AppState;
+use crate::components::Faction;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ==================== DIPLOMACY ====================
+
+// Who fights whom. Every pair of factions has a stance: hostile factions
+// shoot at and hunt each other, neutral ones ignore each other, and allied
+// ones count as friends the way a faction's own units do. The defaults are
+// the historical two sides with civilians caught between them; the rival
+// cartel and the police join in when a scenario spawns them, and scripts can
+// change any stance mid-mission (rival cartels striking a truce, the police
+// refusing to back the army). Every briefing starts from the defaults again.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stance {
+    Hostile,
+    Neutral,
+    Allied,
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct Diplomacy {
+    stances: HashMap<(Faction, Faction), Stance>,
+}
+
+impl Default for Diplomacy {
+    fn default() -> Self {
+        let mut diplomacy = Self {
+            stances: HashMap::new(),
+        };
+        diplomacy.set_stance(&Faction::Cartel, &Faction::Military, Stance::Hostile);
+        diplomacy.set_stance(&Faction::Cartel, &Faction::Police, Stance::Hostile);
+        diplomacy.set_stance(&Faction::Cartel, &Faction::RivalCartel, Stance::Hostile);
+        diplomacy.set_stance(&Faction::Military, &Faction::RivalCartel, Stance::Hostile);
+        diplomacy.set_stance(&Faction::Police, &Faction::RivalCartel, Stance::Hostile);
+        diplomacy.set_stance(&Faction::Military, &Faction::Police, Stance::Allied);
+        diplomacy
+    }
+}
+
+impl Diplomacy {
+    // Stances are symmetric, so each pair is kept in one order
+    fn key(a: &Faction, b: &Faction) -> (Faction, Faction) {
+        let (index_a, index_b) = (a.index(), b.index());
+        if index_a <= index_b {
+            (a.clone(), b.clone())
+        } else {
+            (b.clone(), a.clone())
+        }
+    }
+
+    /// A faction is always allied with itself; unlisted pairs are neutral.
+    pub fn stance(&self, a: &Faction, b: &Faction) -> Stance {
+        if a == b {
+            return Stance::Allied;
+        }
+        self.stances
+            .get(&Self::key(a, b))
+            .copied()
+            .unwrap_or(Stance::Neutral)
+    }
+
+    pub fn set_stance(&mut self, a: &Faction, b: &Faction, stance: Stance) {
+        if a != b {
+            self.stances.insert(Self::key(a, b), stance);
+        }
+    }
+
+    pub fn is_hostile(&self, a: &Faction, b: &Faction) -> bool {
+        self.stance(a, b) == Stance::Hostile
+    }
+
+    pub fn is_friendly(&self, a: &Faction, b: &Faction) -> bool {
+        self.stance(a, b) == Stance::Allied
+    }
+}
+
+pub struct DiplomacyPlugin;
+
+impl Plugin for DiplomacyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Diplomacy>()
+            .add_systems(OnEnter(AppState::Briefing), reset_diplomacy_system);
+    }
+}
+
+pub fn reset_diplomacy_system(mut diplomacy: ResMut<Diplomacy>) {
+    *diplomacy = Diplomacy::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_stances_keep_the_historical_sides() {
+        let diplomacy = Diplomacy::default();
+        assert!(diplomacy.is_hostile(&Faction::Cartel, &Faction::Military));
+        assert!(diplomacy.is_hostile(&Faction::Military, &Faction::Cartel));
+        assert!(diplomacy.is_friendly(&Faction::Police, &Faction::Military));
+        assert!(diplomacy.is_friendly(&Faction::Cartel, &Faction::Cartel));
+        for faction in Faction::ALL {
+            assert!(!diplomacy.is_hostile(&faction, &Faction::Civilian));
+        }
+    }
+
+    #[test]
+    fn test_stances_change_for_both_sides() {
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.set_stance(&Faction::RivalCartel, &Faction::Cartel, Stance::Allied);
+        assert!(diplomacy.is_friendly(&Faction::Cartel, &Faction::RivalCartel));
+
+        diplomacy.set_stance(&Faction::Police, &Faction::Military, Stance::Neutral);
+        assert_eq!(
+            diplomacy.stance(&Faction::Military, &Faction::Police),
+            Stance::Neutral
+        );
+
+        // A faction can't turn on itself
+        diplomacy.set_stance(&Faction::Police, &Faction::Police, Stance::Hostile);
+        assert!(diplomacy.is_friendly(&Faction::Police, &Faction::Police));
+    }
+}
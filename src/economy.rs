@@ -0,0 +1,449 @@
+use crate::app_state::GameSet;
+use crate::campaign::Campaign;
+use crate::commander::{CommanderPower, CommanderPowers};
+use crate::components::*;
+use crate::config::{BalanceConfig, EconomyBalance, UnitCatalog};
+use crate::intel_recruitment::{IntelRecruitment, OperatorContract};
+use crate::localization::{tr, tr_args};
+use crate::map::{area_control, is_area_controlled, CityMap};
+use crate::reinforcements::ReinforcementDesk;
+use crate::scoring::{challenge_active, ChallengeModifier, ChallengeSettings};
+use crate::ui::UiTheme;
+use crate::upgrades::UpgradeWorkshop;
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, play_tactical_sound, update_dashboard_panel,
+    SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== CARTEL ECONOMY PLUGIN ====================
+
+// The cartel no longer gets its people for free. Cash comes in every minute
+// from the neighborhoods it holds - ControlArea objectives pay extra for as
+// much of them as it holds - and upkeep on safehouses and recruited intel
+// operators goes out. Reinforcement calls, roadblocks and abilities are paid
+// for, and new fighters are bought at a safehouse (B opens its recruitment
+// panel) and take time to arrive; the safehouse builds them from its
+// production queue (see production.rs).
+
+const PAYOUT_INTERVAL: f32 = 1.0;
+const RECRUIT_CANCEL_KEY: KeyCode = KeyCode::Delete;
+const RALLY_KEY: KeyCode = KeyCode::Y;
+const RECRUIT_KEYS: [(KeyCode, UnitType); 5] = [
+    (KeyCode::J, UnitType::Sicario),
+    (KeyCode::K, UnitType::Enforcer),
+    (KeyCode::L, UnitType::Sniper),
+    (KeyCode::M, UnitType::HeavyGunner),
+    (KeyCode::V, UnitType::Medic),
+];
+
+// Unit types the safehouse can recruit, in key order
+pub fn recruitable_units() -> impl Iterator<Item = UnitType> {
+    RECRUIT_KEYS.into_iter().map(|(_, unit_type)| unit_type)
+}
+
+pub struct CartelEconomyPlugin;
+
+impl Plugin for CartelEconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CartelTreasury>()
+            .add_systems(
+                FixedUpdate,
+                cartel_income_system
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, economy_panel_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Resource)]
+pub struct CartelTreasury {
+    pub cash: f32,
+    pub income_per_minute: f32, // Net of upkeep, as of the last payout
+    pub panel_safehouse: Option<Entity>, // Safehouse the recruitment panel is open at
+    since_payout: f32,
+}
+
+impl FromWorld for CartelTreasury {
+    fn from_world(world: &mut World) -> Self {
+        let starting_cash = world
+            .get_resource::<BalanceConfig>()
+            .map_or(EconomyBalance::default().starting_cash, |balance| {
+                balance.economy.starting_cash
+            });
+        Self {
+            cash: starting_cash,
+            income_per_minute: 0.0,
+            panel_safehouse: None,
+            since_payout: 0.0,
+        }
+    }
+}
+
+impl CartelTreasury {
+    pub fn try_spend(&mut self, amount: f32) -> bool {
+        if self.cash < amount {
+            return false;
+        }
+        self.cash -= amount;
+        true
+    }
+}
+
+#[derive(Component)]
+pub struct EconomyUIPanel;
+
+/// Pays for a cartel action out of the treasury, telling the player when it
+/// can't be afforded. Without a treasury (the bare simulation) it's free.
+pub fn charge(treasury: Option<&mut CartelTreasury>, amount: f32) -> bool {
+    let Some(treasury) = treasury else {
+        return true;
+    };
+    if treasury.try_spend(amount) {
+        return true;
+    }
+    play_tactical_sound(
+        "radio",
+        &tr_args("radio.insufficient_funds", &[("cost", &(amount as u32))]),
+    );
+    false
+}
+
+/// Net cash per minute: controlled neighborhoods, the held share of each
+/// ControlArea objective, less upkeep on every safehouse.
+pub fn income_per_minute(
+    controlled_neighborhoods: usize,
+    objective_control: &[f32],
+    safehouses: usize,
+    economy: &EconomyBalance,
+) -> f32 {
+    controlled_neighborhoods as f32 * economy.neighborhood_income
+        + objective_control.iter().sum::<f32>() * economy.objective_income
+        - safehouses as f32 * economy.safehouse_upkeep
+}
+
+pub fn controlled_neighborhoods(city_map: &CityMap, units: &[(Faction, Vec2)]) -> usize {
+    city_map
+        .neighborhoods
+        .iter()
+        .filter(|neighborhood| is_area_controlled(area_control(city_map, &neighborhood.name, units)))
+        .count()
+}
+
+pub fn build_seconds(cost: u32, economy: &EconomyBalance) -> f32 {
+    cost as f32 * economy.build_seconds_per_cost
+}
+
+// ==================== INCOME ====================
+
+#[allow(clippy::too_many_arguments)]
+pub fn cartel_income_system(
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    campaign: Res<Campaign>,
+    mut treasury: ResMut<CartelTreasury>,
+    powers: Option<Res<CommanderPowers>>,
+    unit_query: Query<(&Unit, &Transform)>,
+    safehouse_query: Query<(), With<SafehouseInterior>>,
+    contract_query: Query<&OperatorContract>,
+) {
+    // Only a payout counts as a change, so the panel isn't redrawn every tick
+    let pending = &mut treasury.bypass_change_detection().since_payout;
+    *pending += time.delta_seconds();
+    if *pending < PAYOUT_INTERVAL {
+        return;
+    }
+    let elapsed = std::mem::take(pending);
+
+    let units: Vec<(Faction, Vec2)> = unit_query
+        .iter()
+        .filter(|(unit, _)| unit.health > 0.0)
+        .map(|(unit, transform)| (unit.faction.clone(), transform.translation.truncate()))
+        .collect();
+    // Under a military curfew the streets are empty and the turf pays nothing
+    let controlled = if powers.is_some_and(|powers| powers.is_active(CommanderPower::Curfew)) {
+        0
+    } else {
+        controlled_neighborhoods(&city_map, &units)
+    };
+    let objective_control: Vec<f32> = campaign
+        .current_objectives
+        .iter()
+        .filter(|status| status.objective.kind == "ControlArea")
+        .map(|status| status.progress)
+        .collect();
+
+    let rate = income_per_minute(
+        controlled,
+        &objective_control,
+        safehouse_query.iter().count(),
+        &balance.economy,
+    ) - contract_query
+        .iter()
+        .map(|contract| contract.upkeep)
+        .sum::<f32>();
+    treasury.income_per_minute = rate;
+    treasury.cash = (treasury.cash + rate * elapsed / 60.0).max(0.0);
+}
+
+// ==================== RECRUITMENT ====================
+
+// B cycles the recruitment panel through the safehouses and closes it after
+// the last; while it's open the listed keys buy a recruit there, Delete takes
+// back the last one still queued for a full refund, and Y rallies the
+// safehouse's recruits to the cursor (Y with the cursor off the map clears it)
+#[allow(clippy::too_many_arguments)]
+pub fn recruitment_input_system(
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    mut treasury: ResMut<CartelTreasury>,
+    desk: Option<ResMut<ReinforcementDesk>>,
+    workshop: Option<ResMut<UpgradeWorkshop>>,
+    powers: Option<ResMut<CommanderPowers>>,
+    network: Option<ResMut<IntelRecruitment>>,
+    challenges: Option<Res<ChallengeSettings>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut safehouse_query: Query<(Entity, &mut ProductionQueue), With<SafehouseInterior>>,
+) {
+    let mut safehouses: Vec<Entity> = safehouse_query.iter().map(|(entity, _)| entity).collect();
+    safehouses.sort();
+
+    if input.just_pressed(KeyCode::B) {
+        treasury.panel_safehouse = match treasury.panel_safehouse {
+            None => safehouses.first().copied(),
+            Some(current) => safehouses
+                .iter()
+                .position(|&safehouse| safehouse == current)
+                .and_then(|index| safehouses.get(index + 1).copied()),
+        };
+        // The call-in, upgrade, commander and intel network menus share the J/K/L keys
+        if treasury.panel_safehouse.is_some() {
+            if let Some(mut desk) = desk {
+                desk.menu_open = false;
+            }
+            if let Some(mut workshop) = workshop {
+                workshop.panel_open = false;
+            }
+            if let Some(mut powers) = powers {
+                powers.menu_open = false;
+            }
+            if let Some(mut network) = network {
+                network.menu_open = false;
+            }
+        }
+    }
+    let Some(safehouse) = treasury.panel_safehouse else {
+        return;
+    };
+    let Ok((_, mut queue)) = safehouse_query.get_mut(safehouse) else {
+        treasury.panel_safehouse = None;
+        return;
+    };
+
+    if input.just_pressed(RECRUIT_CANCEL_KEY) {
+        if let Some(order) = queue.cancel_last() {
+            treasury.cash += order.cost;
+            play_tactical_sound(
+                "radio",
+                &tr_args(
+                    "radio.recruit_cancelled",
+                    &[
+                        ("unit", &format!("{:?}", order.unit_type)),
+                        ("refund", &(order.cost as u32)),
+                    ],
+                ),
+            );
+        }
+    }
+    if input.just_pressed(RALLY_KEY) {
+        queue.rally_point = windows
+            .get_single()
+            .ok()
+            .and_then(|window| window.cursor_position())
+            .zip(camera_query.get_single().ok())
+            .and_then(|(cursor_pos, (camera, camera_transform))| {
+                camera.viewport_to_world_2d(camera_transform, cursor_pos)
+            })
+            .map(|world_pos| world_pos.extend(0.0));
+        let message = if queue.rally_point.is_some() {
+            "radio.rally_point_set"
+        } else {
+            "radio.rally_point_cleared"
+        };
+        play_tactical_sound("radio", &tr(message));
+    }
+
+    let economy = &balance.economy;
+    for (key, unit_type) in RECRUIT_KEYS {
+        if !input.just_pressed(key) {
+            continue;
+        }
+        if challenge_active(challenges.as_deref(), ChallengeModifier::NoReinforcements) {
+            play_tactical_sound("radio", &tr("radio.challenge_no_reinforcements"));
+            continue;
+        }
+        if queue.orders.len() >= economy.max_queue {
+            play_tactical_sound("radio", &tr("radio.recruit_queue_full"));
+            continue;
+        }
+
+        let cost = catalog.definition(&unit_type).cost;
+        if !charge(Some(&mut *treasury), cost as f32) {
+            continue;
+        }
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.recruit_queued",
+                &[("unit", &format!("{:?}", unit_type))],
+            ),
+        );
+        queue.push(unit_type, build_seconds(cost, economy), cost as f32);
+    }
+}
+
+// ==================== ECONOMY PANEL ====================
+
+pub fn economy_panel_system(
+    mut commands: Commands,
+    treasury: Res<CartelTreasury>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    safehouse_query: Query<(&SafehouseInterior, Ref<ProductionQueue>)>,
+    mut panel_query: Query<&mut Text, With<EconomyUIPanel>>,
+    theme: Res<UiTheme>,
+) {
+    let panel_exists = !panel_query.is_empty();
+    let queues_changed = safehouse_query.iter().any(|(_, queue)| queue.is_changed());
+    if panel_exists && !treasury.is_changed() && !queues_changed && !theme.is_changed() {
+        return;
+    }
+
+    let mut lines = vec![
+        dashboard_line(tr("hud.economy_title"), 16.0, Color::GOLD),
+        dashboard_line(
+            tr_args(
+                "hud.economy_cash",
+                &[
+                    ("cash", &(treasury.cash as u32)),
+                    ("rate", &format!("{:+.0}", treasury.income_per_minute)),
+                ],
+            ),
+            12.0,
+            if treasury.income_per_minute < 0.0 {
+                Color::ORANGE
+            } else {
+                Color::GREEN
+            },
+        ),
+    ];
+
+    match treasury
+        .panel_safehouse
+        .and_then(|entity| safehouse_query.get(entity).ok())
+    {
+        Some((safehouse, queue)) => {
+            lines.push(dashboard_line(
+                tr_args("hud.recruit_at", &[("safehouse", &safehouse.name)]),
+                12.0,
+                Color::YELLOW,
+            ));
+            let rally = if queue.rally_point.is_some() {
+                tr("hud.rally_set")
+            } else {
+                tr("hud.rally_none")
+            };
+            lines.push(dashboard_line(
+                tr_args("hud.recruit_controls", &[("rally", &rally)]),
+                10.0,
+                Color::GRAY,
+            ));
+            for (key, unit_type) in RECRUIT_KEYS {
+                let cost = catalog.definition(&unit_type).cost;
+                lines.push(dashboard_line(
+                    format!(
+                        "[{:?}] {:?} - ${} ({:.0}s)",
+                        key,
+                        unit_type,
+                        cost,
+                        build_seconds(cost, &balance.economy)
+                    ),
+                    11.0,
+                    if treasury.cash >= cost as f32 {
+                        Color::WHITE
+                    } else {
+                        Color::GRAY
+                    },
+                ));
+            }
+        }
+        None => lines.push(dashboard_line(tr("hud.recruit_hint"), 10.0, Color::GRAY)),
+    }
+
+    for (_, queue) in safehouse_query.iter() {
+        for order in &queue.orders {
+            lines.push(dashboard_line(
+                format!("⏳ {:?} {:.0}s", order.unit_type, order.remaining()),
+                10.0,
+                Color::CYAN,
+            ));
+        }
+    }
+
+    if let Ok(mut text) = panel_query.get_single_mut() {
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else if !panel_exists {
+        commands.spawn((
+            create_dashboard_panel(
+                Style {
+                    right: Val::Px(10.0),
+                    top: Val::Px(300.0),
+                    width: Val::Px(280.0),
+                    ..default()
+                },
+                Color::rgba(0.0, 0.0, 0.0, 0.85),
+                lines,
+                &theme,
+            ),
+            EconomyUIPanel,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_income_pays_for_turf_and_objectives_less_upkeep() {
+        let economy = EconomyBalance::default();
+        let rate = income_per_minute(2, &[1.0, 0.5], 2, &economy);
+        let expected = 2.0 * economy.neighborhood_income + 1.5 * economy.objective_income
+            - 2.0 * economy.safehouse_upkeep;
+        assert!((rate - expected).abs() < 1e-4);
+
+        // Holding nothing, the safehouses still cost money
+        assert!(income_per_minute(0, &[], 2, &economy) < 0.0);
+    }
+
+    #[test]
+    fn test_spending_needs_the_cash() {
+        let mut treasury = CartelTreasury {
+            cash: 100.0,
+            income_per_minute: 0.0,
+            panel_safehouse: None,
+            since_payout: 0.0,
+        };
+        assert!(treasury.try_spend(60.0));
+        assert!(!treasury.try_spend(60.0));
+        assert_eq!(treasury.cash, 40.0);
+
+        // The bare simulation has no treasury and doesn't charge
+        assert!(charge(None, 1000.0));
+        assert!((build_seconds(100, &EconomyBalance::default()) - 8.0).abs() < 1e-4);
+    }
+}
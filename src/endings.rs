@@ -0,0 +1,311 @@
+use crate::ai::unit_ai_system;
+use crate::app_state::{AppState, GameSet};
+use crate::campaign::{MissionResult, PoliticalPressure};
+use crate::components::*;
+use crate::config::{BalanceConfig, EndingsBalance};
+use crate::coordination::advanced_tactical_ai_system;
+use crate::political_system::{government_decision_system, EventType, PoliticalState};
+use crate::resources::GameState;
+use crate::systems::combat_system;
+use crate::utils::SimulationSet;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ==================== POLITICAL ENDINGS PLUGIN ====================
+
+// Once decision pressure passes the government's threshold, how it backs
+// down depends on where the pressure came from:
+//   ChaoticRetreat      the troops' morale broke first: they fall back from
+//                       the nearest fighters in disorder, still shooting
+//   CleanWithdrawal     the political families or the press forced an order
+//                       from Mexico City: the army stops firing and leaves
+//   PartialCeasefire    anything else: the army pulls back from the quiet
+//                       streets, but soldiers in contact keep skirmishing
+//   EscalationOverride  the blood in the streets lets the hardliners
+//                       override the release, once a mission: the threshold
+//                       rises and the next wave comes at once, reinforced
+// The first three play out for a while before the mission is won, and the
+// victory screen closes with that ending's epilogue.
+
+// Type aliases to reduce complexity
+type EndingUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Unit,
+        &'static Transform,
+        Option<&'static mut Movement>,
+    ),
+>;
+
+pub struct PoliticalEndingsPlugin;
+
+impl Plugin for PoliticalEndingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), ending_reset_system)
+            .add_systems(
+                FixedUpdate,
+                ending_system
+                    .after(government_decision_system)
+                    .after(unit_ai_system)
+                    .after(advanced_tactical_ai_system)
+                    .before(combat_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoliticalEnding {
+    CleanWithdrawal,
+    ChaoticRetreat,
+    PartialCeasefire,
+    EscalationOverride,
+}
+
+impl PoliticalEnding {
+    /// The way out the government takes, from the mix of pressure on it.
+    pub fn from_pressure(
+        pressure: &PoliticalPressure,
+        escalated: bool,
+        balance: &EndingsBalance,
+    ) -> Self {
+        if pressure.military_morale >= balance.retreat_morale {
+            PoliticalEnding::ChaoticRetreat
+        } else if pressure.political_families >= balance.withdrawal_families
+            || pressure.media_attention >= balance.withdrawal_media
+        {
+            PoliticalEnding::CleanWithdrawal
+        } else if pressure.civilian_impact >= balance.escalation_civilian && !escalated {
+            PoliticalEnding::EscalationOverride
+        } else {
+            PoliticalEnding::PartialCeasefire
+        }
+    }
+
+    /// Whether the government gives Ovidio up in the end.
+    pub fn concedes(&self) -> bool {
+        *self != PoliticalEnding::EscalationOverride
+    }
+
+    /// How long the ending plays out before the mission is won.
+    pub fn seconds(&self, balance: &EndingsBalance) -> f32 {
+        match self {
+            PoliticalEnding::CleanWithdrawal => balance.withdrawal_seconds,
+            PoliticalEnding::ChaoticRetreat => balance.retreat_seconds,
+            PoliticalEnding::PartialCeasefire => balance.ceasefire_seconds,
+            PoliticalEnding::EscalationOverride => 0.0,
+        }
+    }
+
+    pub fn radio_key(&self) -> &'static str {
+        match self {
+            PoliticalEnding::CleanWithdrawal => "radio.ending_withdrawal",
+            PoliticalEnding::ChaoticRetreat => "radio.ending_retreat",
+            PoliticalEnding::PartialCeasefire => "radio.ending_ceasefire",
+            PoliticalEnding::EscalationOverride => "radio.ending_escalation",
+        }
+    }
+
+    pub fn epilogue_key(&self) -> &'static str {
+        match self {
+            PoliticalEnding::CleanWithdrawal => "ending.withdrawal",
+            PoliticalEnding::ChaoticRetreat => "ending.retreat",
+            PoliticalEnding::PartialCeasefire => "ending.ceasefire",
+            PoliticalEnding::EscalationOverride => "ending.escalation",
+        }
+    }
+
+    pub fn event_type(&self) -> EventType {
+        match self {
+            PoliticalEnding::EscalationOverride => EventType::OperationEscalation,
+            PoliticalEnding::PartialCeasefire => EventType::Ceasefire,
+            _ => EventType::PoliticalStatement,
+        }
+    }
+
+    // For the political event log, which isn't translated
+    pub fn description(&self) -> &'static str {
+        match self {
+            PoliticalEnding::CleanWithdrawal => {
+                "Government orders cessation of operation and release of target"
+            }
+            PoliticalEnding::ChaoticRetreat => {
+                "Troops abandon positions as the operation collapses"
+            }
+            PoliticalEnding::PartialCeasefire => {
+                "Government announces ceasefire; clashes continue in parts of the city"
+            }
+            PoliticalEnding::EscalationOverride => {
+                "Security cabinet overrides release order and reinforces the operation"
+            }
+        }
+    }
+}
+
+/// Where a soldier falling back from `from` heads: along the line away from
+/// it, at least `distance` out. Soldiers already farther stay put.
+pub fn fallback_point(position: Vec2, from: Vec2, distance: f32) -> Vec2 {
+    let offset = position - from;
+    let away = offset.try_normalize().unwrap_or(Vec2::X);
+    from + away * offset.length().max(distance)
+}
+
+// ==================== SYSTEMS ====================
+
+// A new mission starts without an ending, or the hardliners' raised bar
+pub fn ending_reset_system(
+    mut political_state: ResMut<PoliticalState>,
+    balance: Res<BalanceConfig>,
+) {
+    if political_state.escalated {
+        political_state.decision_threshold -= balance.endings.escalation_threshold;
+    }
+    political_state.ending = None;
+    political_state.ending_timer = 0.0;
+    political_state.escalated = false;
+}
+
+pub fn ending_system(
+    mut political_state: ResMut<PoliticalState>,
+    mut game_state: ResMut<GameState>,
+    balance: Res<BalanceConfig>,
+    time: Res<Time>,
+    mut spawner_query: Query<&mut WaveSpawner>,
+    mut unit_query: EndingUnitQuery,
+) {
+    let balance = &balance.endings;
+    let Some(ending) = political_state.ending else {
+        return;
+    };
+    if matches!(
+        game_state.game_phase,
+        GamePhase::Victory | GamePhase::Defeat
+    ) {
+        return;
+    }
+
+    // The override happens all at once, then the mission goes on
+    if ending == PoliticalEnding::EscalationOverride {
+        political_state.ending = None;
+        political_state.escalated = true;
+        political_state.decision_threshold += balance.escalation_threshold;
+        for mut spawner in spawner_query.iter_mut() {
+            spawner.units_in_wave += balance.escalation_wave;
+            let duration = spawner.next_wave_timer.duration();
+            spawner.next_wave_timer.set_elapsed(duration);
+        }
+        return;
+    }
+
+    political_state.ending_timer -= time.delta_seconds();
+    if let MissionResult::Victory(victory) = political_state.mission_result() {
+        political_state.government_capitulated = true;
+        game_state.game_phase = GamePhase::Victory;
+        info!("🏛️ Mission ends in {:?}", victory);
+        return;
+    }
+
+    let fighters: Vec<Vec2> = unit_query
+        .iter()
+        .filter(|(unit, _, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(_, transform, _)| transform.translation.truncate())
+        .collect();
+    let ovidio = unit_query
+        .iter()
+        .find(|(unit, _, _)| unit.unit_type == UnitType::Ovidio && unit.health > 0.0)
+        .map(|(_, transform, _)| transform.translation.truncate());
+    let Some(anchor) = ovidio.or_else(|| fighters.first().copied()) else {
+        return;
+    };
+
+    for (mut unit, transform, movement) in unit_query.iter_mut() {
+        if unit.faction != Faction::Military || unit.health <= 0.0 {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let nearest = fighters
+            .iter()
+            .copied()
+            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)))
+            .unwrap_or(anchor);
+
+        let point = match ending {
+            // Scattering from whoever is closest, and firing back as they go
+            PoliticalEnding::ChaoticRetreat => {
+                fallback_point(position, nearest, balance.withdrawal_distance)
+            }
+            PoliticalEnding::PartialCeasefire
+                if nearest.distance(position) <= balance.skirmish_radius =>
+            {
+                continue;
+            }
+            _ => {
+                if unit.target.is_some() {
+                    unit.target = None;
+                }
+                fallback_point(position, anchor, balance.withdrawal_distance)
+            }
+        };
+        if let Some(mut movement) = movement {
+            let target = Some(point.extend(0.0));
+            if movement.target_position != target {
+                movement.target_position = target;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_the_pressure_mix_picks_the_ending() {
+        let balance = EndingsBalance::default();
+        let pressure = |morale, families, civilian| PoliticalPressure {
+            military_morale: morale,
+            political_families: families,
+            civilian_impact: civilian,
+            ..default()
+        };
+        let ending = |pressure: &PoliticalPressure, escalated| {
+            PoliticalEnding::from_pressure(pressure, escalated, &balance)
+        };
+
+        assert_eq!(
+            ending(&pressure(0.7, 0.5, 0.9), false),
+            PoliticalEnding::ChaoticRetreat
+        );
+        assert_eq!(
+            ending(&pressure(0.1, 0.5, 0.9), false),
+            PoliticalEnding::CleanWithdrawal
+        );
+        assert_eq!(
+            ending(&pressure(0.1, 0.1, 0.9), false),
+            PoliticalEnding::EscalationOverride
+        );
+        // The hardliners only get to override once
+        assert_eq!(
+            ending(&pressure(0.1, 0.1, 0.9), true),
+            PoliticalEnding::PartialCeasefire
+        );
+        assert_eq!(
+            ending(&PoliticalPressure::default(), false),
+            PoliticalEnding::PartialCeasefire
+        );
+    }
+
+    #[test]
+    fn test_soldiers_fall_back_away_from_the_point() {
+        let from = Vec2::new(100.0, 100.0);
+        let point = fallback_point(Vec2::new(200.0, 100.0), from, 500.0);
+        assert!((point - Vec2::new(600.0, 100.0)).length() < 1e-3);
+        // Already far enough: stay put
+        let far = Vec2::new(100.0, -900.0);
+        assert!((fallback_point(far, from, 500.0) - far).length() < 1e-3);
+        // Right on top of it still picks a way out
+        assert!(fallback_point(from, from, 500.0).distance(from) > 499.0);
+    }
+}
@@ -0,0 +1,682 @@
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{BalanceConfig, EngineeringBalance};
+use crate::damage::DamageEvent;
+use crate::diplomacy::Diplomacy;
+use crate::economy::{charge, CartelTreasury};
+use crate::localization::tr;
+use crate::map::{CityMap, PropKind, TileKind};
+use crate::noise::queue_explosion_noise;
+use crate::utils::{
+    play_tactical_sound, play_tactical_sound_at_position, queue_camera_shake, queue_decal,
+    DecalKind, SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== ENGINEERING PLUGIN ====================
+
+// Engineers and enforcers work on the battlefield itself. A breach blows a
+// hole through a wall, turning the building tile into open ground, so routes
+// planned from then on can go through it. Sandbags go down as a prop on the
+// map and give cover like a parked car does. Traps are marked on the ground:
+// the army and police lay spike strips that shred tyres, the cartels IEDs
+// that blow up under the first enemy to reach them. Units that can defuse
+// see traps and don't set them off; standing next to an enemy trap they make
+// it safe. The cartel orders this at the cursor (Z, Shift+Z for a trap);
+// army and police engineers fortify and lay strips on their own once the
+// enemy is in sight. The map is put back as each mission starts.
+
+const TRAP_Z: f32 = 0.3;
+const MARKER_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 0.1);
+const SPIKE_STRIP_SIZE: Vec2 = Vec2::new(30.0, 6.0);
+const IED_SIZE: Vec2 = Vec2::splat(12.0);
+const SPIKE_STRIP_COLOR: Color = Color::rgb(0.6, 0.6, 0.65);
+const IED_COLOR: Color = Color::rgb(0.45, 0.3, 0.15);
+const TRAP_SPACING: f32 = 2.0; // Trigger radii between two traps of the same side
+
+pub struct EngineeringPlugin;
+
+impl Plugin for EngineeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EngineeringWorks>()
+            .add_systems(OnEnter(AppState::InGame), engineering_reset_system)
+            .add_systems(
+                FixedUpdate,
+                (engineering_timer_system, ai_engineering_system, trap_system)
+                    .chain()
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+// Changes made to the map this mission, so the next one starts from the
+// map as authored
+#[derive(Resource, Default)]
+pub struct EngineeringWorks {
+    pub breaches: Vec<(usize, usize)>,
+    pub fortifications: Vec<(usize, usize)>,
+}
+
+impl TrapKind {
+    pub fn for_faction(faction: &Faction) -> Self {
+        match faction.side() {
+            Faction::Cartel => TrapKind::Ied,
+            _ => TrapKind::SpikeStrip,
+        }
+    }
+}
+
+impl EngineeringKit {
+    pub fn can(&self, action: EngineeringAction) -> bool {
+        self.actions.contains(&action)
+    }
+
+    pub fn ready(&self) -> bool {
+        self.cooldown <= 0.0
+    }
+}
+
+/// Whether a unit walking into a trap sets it off: the trap's enemies do,
+/// unless they can defuse it and so know to step round it. Helicopters fly
+/// over and roadblocks don't move.
+pub fn sets_off(
+    trap: &Trap,
+    faction: &Faction,
+    unit_type: &UnitType,
+    kit: Option<&EngineeringKit>,
+    diplomacy: &Diplomacy,
+) -> bool {
+    diplomacy.is_hostile(&trap.faction, faction)
+        && !matches!(unit_type, UnitType::Helicopter | UnitType::Roadblock)
+        && !kit.is_some_and(|kit| kit.can(EngineeringAction::Defuse))
+}
+
+/// Damage a spike strip does to whatever runs over it.
+pub fn spike_damage(unit_type: &UnitType, balance: &EngineeringBalance) -> f32 {
+    match unit_type {
+        UnitType::Vehicle | UnitType::Tank => {
+            balance.spike_damage * balance.spike_vehicle_multiplier
+        }
+        _ => balance.spike_damage,
+    }
+}
+
+/// Blows a hole through the wall at `tile`; false if it isn't a wall with
+/// open ground beside it.
+pub fn breach_wall(
+    city_map: &mut CityMap,
+    works: &mut EngineeringWorks,
+    tile: (usize, usize),
+) -> bool {
+    if !city_map.is_breachable(tile) {
+        return false;
+    }
+    city_map.set_tile(tile, TileKind::Ground);
+    works.breaches.push(tile);
+    true
+}
+
+/// Sandbags the open tile, which must be free of other props.
+pub fn fortify_tile(
+    city_map: &mut CityMap,
+    works: &mut EngineeringWorks,
+    tile: (usize, usize),
+) -> bool {
+    if !city_map.kind(tile.0, tile.1).is_walkable()
+        || city_map.props.iter().any(|prop| prop.tile == tile)
+    {
+        return false;
+    }
+    city_map.place_prop(tile, PropKind::Sandbags);
+    works.fortifications.push(tile);
+    true
+}
+
+/// Puts the map back the way it was before any breach or sandbags.
+pub fn restore_map(city_map: &mut CityMap, works: &mut EngineeringWorks) {
+    for tile in works.fortifications.drain(..) {
+        city_map.remove_prop(tile, PropKind::Sandbags);
+    }
+    for tile in works.breaches.drain(..) {
+        city_map.set_tile(tile, TileKind::Building);
+    }
+}
+
+pub fn spawn_trap(commands: &mut Commands, faction: Faction, position: Vec2) {
+    let kind = TrapKind::for_faction(&faction);
+    let (color, size) = match kind {
+        TrapKind::SpikeStrip => (SPIKE_STRIP_COLOR, SPIKE_STRIP_SIZE),
+        TrapKind::Ied => (IED_COLOR, IED_SIZE),
+    };
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(TRAP_Z)),
+                ..default()
+            },
+            Trap {
+                kind,
+                faction,
+                defusing: 0.0,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(
+                    "⚠",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(MARKER_OFFSET),
+                ..default()
+            });
+        });
+}
+
+/// Why `faction` can't do `action` at `site`, as the localization key of
+/// the radio line saying so; None when nothing stands in the way.
+pub fn site_problem(
+    action: EngineeringAction,
+    faction: &Faction,
+    site: Vec2,
+    city_map: &CityMap,
+    traps: &[(Faction, Vec2)],
+    balance: &EngineeringBalance,
+) -> Option<&'static str> {
+    let tile = city_map.tile_at(site);
+    match action {
+        EngineeringAction::Breach => (!tile.is_some_and(|tile| city_map.is_breachable(tile)))
+            .then_some("radio.breach_blocked"),
+        EngineeringAction::Fortify => {
+            let open = tile.is_some_and(|tile| {
+                city_map.kind(tile.0, tile.1).is_walkable()
+                    && !city_map.props.iter().any(|prop| prop.tile == tile)
+            });
+            (!open).then_some("radio.fortify_blocked")
+        }
+        EngineeringAction::LayTrap => {
+            let own: Vec<Vec2> = traps
+                .iter()
+                .filter(|(owner, _)| owner == faction)
+                .map(|(_, position)| *position)
+                .collect();
+            if own.len() >= balance.max_traps_per_side {
+                Some("radio.trap_limit")
+            } else if !city_map.is_walkable(site)
+                || own
+                    .iter()
+                    .any(|other| other.distance(site) < balance.trigger_radius * TRAP_SPACING)
+            {
+                Some("radio.trap_blocked")
+            } else {
+                None
+            }
+        }
+        // Done by standing next to a trap, not ordered
+        EngineeringAction::Defuse => Some("radio.engineering_no_worker"),
+    }
+}
+
+/// Carries out a job site_problem found nothing wrong with. The map is only
+/// touched by jobs that change it, so it isn't redrawn for a trap.
+pub fn carry_out(
+    commands: &mut Commands,
+    action: EngineeringAction,
+    faction: &Faction,
+    site: Vec2,
+    city_map: &mut ResMut<CityMap>,
+    works: &mut EngineeringWorks,
+) {
+    let tile = city_map.tile_at(site);
+    match (action, tile) {
+        (EngineeringAction::Breach, Some(tile)) => {
+            breach_wall(city_map, works, tile);
+            queue_decal(commands, DecalKind::Scorch, site.extend(0.0), 1.2);
+            queue_camera_shake(commands, site.extend(0.0), 0.4);
+            queue_explosion_noise(commands, site.extend(0.0), faction.clone());
+        }
+        (EngineeringAction::Fortify, Some(tile)) => {
+            fortify_tile(city_map, works, tile);
+        }
+        (EngineeringAction::LayTrap, _) => spawn_trap(commands, faction.clone(), site),
+        _ => {}
+    }
+}
+
+// Routes already planned don't know about a new hole in a wall
+fn replan_routes(agents: &mut Query<&mut PathfindingAgent>) {
+    for mut agent in agents.iter_mut() {
+        agent.path.clear();
+    }
+}
+
+fn cursor_position(
+    windows: &Query<&Window>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+) -> Option<Vec2> {
+    windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn engineering_reset_system(
+    mut commands: Commands,
+    mut city_map: ResMut<CityMap>,
+    mut works: ResMut<EngineeringWorks>,
+    trap_query: Query<Entity, With<Trap>>,
+) {
+    if !works.breaches.is_empty() || !works.fortifications.is_empty() {
+        restore_map(&mut city_map, &mut works);
+    }
+    for entity in trap_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn engineering_timer_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut kit_query: Query<&mut EngineeringKit>,
+    mut punctured_query: Query<(Entity, &mut Punctured)>,
+) {
+    let dt = time.delta_seconds();
+    for mut kit in kit_query.iter_mut() {
+        if !kit.ready() {
+            kit.cooldown -= dt;
+        }
+    }
+    for (entity, mut punctured) in punctured_query.iter_mut() {
+        punctured.remaining -= dt;
+        if punctured.remaining <= 0.0 {
+            commands.entity(entity).remove::<Punctured>();
+        }
+    }
+}
+
+// Z: a breach if the cursor is on a wall, sandbags on open ground. Shift+Z
+// lays a trap at the cursor. The job goes to the first selected unit that
+// can do it, is within reach and has finished its last one
+#[allow(clippy::too_many_arguments)]
+pub fn engineering_order_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    mut city_map: ResMut<CityMap>,
+    mut works: ResMut<EngineeringWorks>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut worker_query: Query<(Entity, &Unit, &Transform, &mut EngineeringKit), With<Selected>>,
+    trap_query: Query<(&Trap, &Transform)>,
+    mut agent_query: Query<&mut PathfindingAgent>,
+) {
+    if !input.just_pressed(KeyCode::Z) {
+        return;
+    }
+    let balance = &balance.engineering;
+    let Some(cursor) = cursor_position(&windows, &camera_query) else {
+        return;
+    };
+
+    let shift = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
+    let wall = city_map
+        .tile_at(cursor)
+        .filter(|tile| city_map.kind(tile.0, tile.1) == TileKind::Building);
+    let (action, site, cost) = match wall {
+        _ if shift => (EngineeringAction::LayTrap, cursor, balance.trap_cost),
+        Some((col, row)) => (
+            EngineeringAction::Breach,
+            city_map.tile_center(col, row),
+            balance.breach_cost,
+        ),
+        None => (
+            EngineeringAction::Fortify,
+            city_map
+                .tile_at(cursor)
+                .map_or(cursor, |(col, row)| city_map.tile_center(col, row)),
+            balance.fortify_cost,
+        ),
+    };
+
+    let candidates: Vec<(Entity, bool)> = worker_query
+        .iter()
+        .filter(|(_, unit, transform, kit)| {
+            unit.faction == Faction::Cartel
+                && unit.health > 0.0
+                && kit.can(action)
+                && transform.translation.truncate().distance(site) <= balance.reach
+        })
+        .map(|(entity, _, _, kit)| (entity, kit.ready()))
+        .collect();
+    let Some(worker) = candidates
+        .iter()
+        .find(|(_, ready)| *ready)
+        .map(|(entity, _)| *entity)
+    else {
+        let reason = if candidates.is_empty() {
+            "radio.engineering_no_worker"
+        } else {
+            "radio.engineering_not_ready"
+        };
+        play_tactical_sound("radio", &tr(reason));
+        return;
+    };
+
+    let traps: Vec<(Faction, Vec2)> = trap_query
+        .iter()
+        .map(|(trap, transform)| (trap.faction.clone(), transform.translation.truncate()))
+        .collect();
+    if let Some(reason) = site_problem(action, &Faction::Cartel, site, &city_map, &traps, balance) {
+        play_tactical_sound("radio", &tr(reason));
+        return;
+    }
+    if !charge(treasury.as_deref_mut(), cost) {
+        return;
+    }
+
+    carry_out(
+        &mut commands,
+        action,
+        &Faction::Cartel,
+        site,
+        &mut city_map,
+        &mut works,
+    );
+    if let Ok((_, _, _, mut kit)) = worker_query.get_mut(worker) {
+        kit.cooldown = balance.cooldown;
+    }
+    let (sound, message) = match action {
+        EngineeringAction::Breach => {
+            replan_routes(&mut agent_query);
+            ("explosion", "radio.wall_breached")
+        }
+        EngineeringAction::Fortify => ("construction", "radio.position_fortified"),
+        _ => ("construction", "radio.ied_laid"),
+    };
+    play_tactical_sound_at_position(sound, &tr(message), site.extend(0.0));
+}
+
+// Army, police and rival engineers and enforcers with their enemy in sight
+// lay a trap on the road they're on, or sandbag where they stand
+#[allow(clippy::too_many_arguments)]
+pub fn ai_engineering_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    mut city_map: ResMut<CityMap>,
+    mut works: ResMut<EngineeringWorks>,
+    mut worker_query: Query<(&Unit, &Transform, &mut EngineeringKit)>,
+    unit_query: Query<(&Unit, &Transform)>,
+    trap_query: Query<(&Trap, &Transform)>,
+) {
+    let balance = &balance.engineering;
+    let mut traps: Vec<(Faction, Vec2)> = trap_query
+        .iter()
+        .map(|(trap, transform)| (trap.faction.clone(), transform.translation.truncate()))
+        .collect();
+
+    for (unit, transform, mut kit) in worker_query.iter_mut() {
+        if unit.faction == Faction::Cartel || unit.health <= 0.0 || !kit.ready() {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let enemy_in_sight = unit_query.iter().any(|(other, other_transform)| {
+            other.health > 0.0
+                && diplomacy.is_hostile(&unit.faction, &other.faction)
+                && other_transform.translation.truncate().distance(position) <= unit.vision
+        });
+        if !enemy_in_sight {
+            continue;
+        }
+
+        let Some((col, row)) = city_map.tile_at(position) else {
+            continue;
+        };
+        let action =
+            if city_map.kind(col, row) == TileKind::Road && kit.can(EngineeringAction::LayTrap) {
+                EngineeringAction::LayTrap
+            } else if kit.can(EngineeringAction::Fortify) {
+                EngineeringAction::Fortify
+            } else {
+                continue;
+            };
+        let site = city_map.tile_center(col, row);
+        if site_problem(action, &unit.faction, site, &city_map, &traps, balance).is_some() {
+            continue;
+        }
+        carry_out(
+            &mut commands,
+            action,
+            &unit.faction,
+            site,
+            &mut city_map,
+            &mut works,
+        );
+
+        kit.cooldown = balance.cooldown;
+        if action == EngineeringAction::LayTrap {
+            traps.push((unit.faction.clone(), site));
+            if diplomacy.is_hostile(&unit.faction, &Faction::Cartel) {
+                play_tactical_sound("radio", &tr("radio.enemy_trap_laid"));
+            }
+        }
+    }
+}
+
+// Traps go off under the first enemy to reach them, and are made safe by
+// enemies who can defuse them working next to them
+pub fn trap_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    mut trap_query: Query<(Entity, &mut Trap, &Transform)>,
+    unit_query: Query<(Entity, &Unit, &Transform, Option<&EngineeringKit>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let balance = &balance.engineering;
+    let dt = time.delta_seconds();
+
+    for (trap_entity, mut trap, trap_transform) in trap_query.iter_mut() {
+        let position = trap_transform.translation.truncate();
+        let distance = |transform: &Transform| transform.translation.truncate().distance(position);
+
+        let victims: Vec<(Entity, &Unit)> = unit_query
+            .iter()
+            .filter(|(_, unit, transform, kit)| {
+                unit.health > 0.0
+                    && sets_off(&trap, &unit.faction, &unit.unit_type, *kit, &diplomacy)
+                    && distance(transform) <= balance.trigger_radius
+            })
+            .map(|(entity, unit, _, _)| (entity, unit))
+            .collect();
+        if !victims.is_empty() {
+            commands.entity(trap_entity).despawn_recursive();
+            let cartel_hit = victims
+                .iter()
+                .any(|(_, unit)| unit.faction == Faction::Cartel);
+            match trap.kind {
+                TrapKind::Ied => {
+                    // Everyone caught in the blast, whichever side
+                    for (entity, unit, transform, _) in unit_query.iter() {
+                        if unit.health > 0.0 && distance(transform) <= balance.ied_radius {
+                            damage_events.send(DamageEvent {
+                                attacker: None,
+                                target: entity,
+                                amount: balance.ied_damage,
+                                weapon: None,
+                            });
+                        }
+                    }
+                    queue_decal(&mut commands, DecalKind::Scorch, position.extend(0.0), 1.5);
+                    queue_camera_shake(&mut commands, position.extend(0.0), 0.6);
+                    queue_explosion_noise(
+                        &mut commands,
+                        position.extend(0.0),
+                        trap.faction.clone(),
+                    );
+                    let message = if trap.faction == Faction::Cartel {
+                        "radio.ied_detonated"
+                    } else if cartel_hit {
+                        "radio.trap_hit"
+                    } else {
+                        "radio.ied_distant"
+                    };
+                    play_tactical_sound_at_position(
+                        "explosion",
+                        &tr(message),
+                        position.extend(0.0),
+                    );
+                }
+                TrapKind::SpikeStrip => {
+                    for (entity, unit) in &victims {
+                        damage_events.send(DamageEvent {
+                            attacker: None,
+                            target: *entity,
+                            amount: spike_damage(&unit.unit_type, balance),
+                            weapon: None,
+                        });
+                        commands.entity(*entity).insert(Punctured {
+                            speed: balance.spike_speed,
+                            remaining: balance.spike_seconds,
+                        });
+                    }
+                    if cartel_hit {
+                        play_tactical_sound("radio", &tr("radio.trap_hit"));
+                    }
+                }
+            }
+            continue;
+        }
+
+        let defusers = unit_query
+            .iter()
+            .filter(|(_, unit, transform, kit)| {
+                unit.health > 0.0
+                    && diplomacy.is_hostile(&trap.faction, &unit.faction)
+                    && kit.is_some_and(|kit| kit.can(EngineeringAction::Defuse))
+                    && distance(transform) <= balance.defuse_radius
+            })
+            .map(|(_, unit, _, _)| unit.faction.clone())
+            .collect::<Vec<_>>();
+        if defusers.is_empty() {
+            continue;
+        }
+        trap.defusing += defusers.len() as f32 * dt;
+        if trap.defusing >= balance.defuse_seconds {
+            commands.entity(trap_entity).despawn_recursive();
+            if defusers.contains(&Faction::Cartel) {
+                play_tactical_sound("radio", &tr("radio.trap_defused"));
+            } else if trap.faction == Faction::Cartel {
+                play_tactical_sound("radio", &tr("radio.ied_defused"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MAP: &str = r#"{
+        "name": "Test",
+        "tile_size": 10.0,
+        "tiles": ["...", "~#~", "..."],
+        "districts": ["AAA", "AAA", "AAA"],
+        "neighborhoods": [{ "key": "A", "name": "Centro" }]
+    }"#;
+
+    #[test]
+    fn test_breach_opens_a_route_through_the_wall() {
+        let mut map = CityMap::from_json(TEST_MAP).unwrap();
+        let mut works = EngineeringWorks::default();
+        let start = map.tile_center(1, 0).extend(0.0);
+        let end = map.tile_center(1, 2).extend(0.0);
+        assert_eq!(map.find_path(start, end), None);
+
+        // Only walls can be breached
+        assert!(!breach_wall(&mut map, &mut works, (1, 0)));
+        assert!(breach_wall(&mut map, &mut works, (1, 1)));
+        assert_eq!(*map.find_path(start, end).unwrap().last().unwrap(), end);
+
+        restore_map(&mut map, &mut works);
+        assert_eq!(map.kind(1, 1), TileKind::Building);
+        assert!(works.breaches.is_empty());
+    }
+
+    #[test]
+    fn test_sandbags_give_cover_until_the_map_is_restored() {
+        let mut map = CityMap::from_json(TEST_MAP).unwrap();
+        let mut works = EngineeringWorks::default();
+        let spot = map.tile_center(0, 0);
+        let before = map.cover_at(spot);
+
+        assert!(fortify_tile(&mut map, &mut works, (0, 0)));
+        assert!(map.cover_at(spot) > before);
+        assert!(!fortify_tile(&mut map, &mut works, (0, 0)));
+        assert!(!fortify_tile(&mut map, &mut works, (0, 1)));
+
+        restore_map(&mut map, &mut works);
+        assert_eq!(map.cover_at(spot), before);
+        assert!(map.props.is_empty());
+    }
+
+    #[test]
+    fn test_only_enemies_who_cannot_defuse_set_traps_off() {
+        let diplomacy = Diplomacy::default();
+        let trap = Trap {
+            kind: TrapKind::for_faction(&Faction::Cartel),
+            faction: Faction::Cartel,
+            defusing: 0.0,
+        };
+        assert_eq!(trap.kind, TrapKind::Ied);
+        assert_eq!(
+            TrapKind::for_faction(&Faction::Police),
+            TrapKind::SpikeStrip
+        );
+
+        let kit = EngineeringKit {
+            actions: vec![EngineeringAction::Defuse],
+            cooldown: 0.0,
+        };
+        let soldier = (Faction::Military, UnitType::Soldier);
+        assert!(sets_off(&trap, &soldier.0, &soldier.1, None, &diplomacy));
+        assert!(!sets_off(
+            &trap,
+            &soldier.0,
+            &soldier.1,
+            Some(&kit),
+            &diplomacy
+        ));
+        assert!(!sets_off(
+            &trap,
+            &Faction::Cartel,
+            &UnitType::Sicario,
+            None,
+            &diplomacy
+        ));
+        assert!(!sets_off(
+            &trap,
+            &Faction::Military,
+            &UnitType::Helicopter,
+            None,
+            &diplomacy
+        ));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::app_state::{AppState, GameSet};
+use crate::components::{DamageIndicator, ParticleEffect};
+use crate::config::{BalanceConfig, GraphicsSettings};
+use crate::corpse_system::Corpse;
+use crate::environmental_systems::WeatherParticle;
+use crate::map::{performance_budget, CityLifeDirector, CivilianCar, StrayDog};
+use crate::utils::{DecalLayer, PooledEntity};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+// ==================== ENTITY BUDGET PLUGIN ====================
+
+// Keeps short-lived clutter from piling up until the frame rate collapses.
+// Every frame the live particles, decals, corpses, floating text and ambient
+// city life are counted against a budget. Particles and floating text get
+// the graphics settings' allowance, shrunk as the frame rate drops; past it
+// the weather goes first, then the effects nearest the end of their lives.
+// Those are expired early rather than despawned, so their own systems clean
+// them up and hand pooled ones back. Stray dogs and then civilian cars go
+// when the city life budget shrinks. Decals and corpses already keep to
+// their caps (the corpse cap is part of the simulation), so they're only
+// counted. The F10 profiler shows the counts.
+
+const FLOATING_TEXT_BUDGET: usize = 128;
+
+pub struct EntityBudgetPlugin;
+
+impl Plugin for EntityBudgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityBudget>()
+            .add_systems(OnEnter(AppState::InGame), entity_budget_reset_system)
+            .add_systems(
+                Update,
+                (
+                    effect_budget_system,
+                    ambient_budget_system,
+                    lasting_budget_system,
+                )
+                    .in_set(GameSet::InGame),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetCategory {
+    Particles,
+    Decals,
+    Corpses,
+    FloatingText,
+    AmbientNpcs,
+}
+
+impl BudgetCategory {
+    pub const ALL: [BudgetCategory; 5] = [
+        BudgetCategory::Particles,
+        BudgetCategory::Decals,
+        BudgetCategory::Corpses,
+        BudgetCategory::FloatingText,
+        BudgetCategory::AmbientNpcs,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BudgetCategory::Particles => "Particles",
+            BudgetCategory::Decals => "Decals",
+            BudgetCategory::Corpses => "Corpses",
+            BudgetCategory::FloatingText => "Floating text",
+            BudgetCategory::AmbientNpcs => "Ambient NPCs",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BudgetLine {
+    pub live: usize,
+    pub limit: usize,
+    pub trimmed: usize, // Since the mission started
+}
+
+/// Live counts against their limits, by category.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct EntityBudget {
+    lines: [BudgetLine; 5],
+}
+
+impl EntityBudget {
+    pub fn line(&self, category: BudgetCategory) -> BudgetLine {
+        self.lines[category as usize]
+    }
+
+    fn record(&mut self, category: BudgetCategory, live: usize, limit: usize, trimmed: usize) {
+        let line = &mut self.lines[category as usize];
+        line.live = live - trimmed;
+        line.limit = limit;
+        line.trimmed += trimmed;
+    }
+
+    /// One line per category for the profiler overlay.
+    pub fn summary(&self) -> String {
+        BudgetCategory::ALL
+            .iter()
+            .map(|category| {
+                let line = self.line(*category);
+                format!(
+                    "{}: {}/{} ({} trimmed)",
+                    category.name(),
+                    line.live,
+                    line.limit,
+                    line.trimmed
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Something counted against a budget. Lower priorities are trimmed first,
+/// and within a priority the one furthest through its life.
+#[derive(Clone, Copy, Debug)]
+pub struct BudgetEntry {
+    pub entity: Entity,
+    pub priority: u8,
+    pub age: f32, // Share of its lifetime gone, 0 to 1
+}
+
+/// What has to go to bring `entries` down to `limit`.
+pub fn over_budget(mut entries: Vec<BudgetEntry>, limit: usize) -> Vec<Entity> {
+    let excess = entries.len().saturating_sub(limit);
+    if excess == 0 {
+        return Vec::new();
+    }
+    entries.sort_by(|a, b| a.priority.cmp(&b.priority).then(b.age.total_cmp(&a.age)));
+    entries
+        .iter()
+        .take(excess)
+        .map(|entry| entry.entity)
+        .collect()
+}
+
+fn fps_scale(diagnostics: Option<Res<DiagnosticsStore>>) -> f32 {
+    performance_budget(
+        diagnostics
+            .as_ref()
+            .and_then(|diagnostics| diagnostics.get(FrameTimeDiagnosticsPlugin::FPS))
+            .and_then(|fps| fps.smoothed()),
+    )
+}
+
+// ==================== SYSTEMS ====================
+
+const WEATHER_PRIORITY: u8 = 0;
+const EFFECT_PRIORITY: u8 = 1;
+
+pub fn entity_budget_reset_system(mut budget: ResMut<EntityBudget>) {
+    *budget = EntityBudget::default();
+}
+
+pub fn effect_budget_system(
+    mut budget: ResMut<EntityBudget>,
+    graphics: Option<Res<GraphicsSettings>>,
+    diagnostics: Option<Res<DiagnosticsStore>>,
+    mut effect_query: Query<(Entity, &mut ParticleEffect, Option<&PooledEntity>)>,
+    mut weather_query: Query<(Entity, &mut WeatherParticle)>,
+    mut text_query: Query<(Entity, &mut DamageIndicator, Option<&PooledEntity>)>,
+) {
+    let scale = fps_scale(diagnostics);
+    let pool_size = graphics.map_or(GraphicsSettings::default().particle_pool_size, |graphics| {
+        graphics.particle_pool_size
+    });
+    // Entities parked in a pool are hidden and don't count
+    let live = |pooled: Option<&PooledEntity>| pooled.map_or(true, |pooled| pooled.active);
+
+    let particles: Vec<BudgetEntry> = weather_query
+        .iter()
+        .map(|(entity, particle)| BudgetEntry {
+            entity,
+            priority: WEATHER_PRIORITY,
+            age: particle.lifetime / particle.max_lifetime.max(f32::EPSILON),
+        })
+        .chain(
+            effect_query
+                .iter()
+                .filter(|(_, _, pooled)| live(*pooled))
+                .map(|(entity, effect, _)| BudgetEntry {
+                    entity,
+                    priority: EFFECT_PRIORITY,
+                    age: effect.lifetime.percent(),
+                }),
+        )
+        .collect();
+    let count = particles.len();
+    let limit = (pool_size as f32 * scale) as usize;
+    let trimmed = over_budget(particles, limit);
+    for entity in &trimmed {
+        if let Ok((_, mut particle)) = weather_query.get_mut(*entity) {
+            particle.lifetime = particle.max_lifetime;
+        } else if let Ok((_, mut effect, _)) = effect_query.get_mut(*entity) {
+            let remaining = effect.lifetime.remaining();
+            effect.lifetime.tick(remaining);
+        }
+    }
+    budget.record(BudgetCategory::Particles, count, limit, trimmed.len());
+
+    let texts: Vec<BudgetEntry> = text_query
+        .iter()
+        .filter(|(_, _, pooled)| live(*pooled))
+        .map(|(entity, indicator, _)| BudgetEntry {
+            entity,
+            priority: EFFECT_PRIORITY,
+            age: indicator.lifetime.percent(),
+        })
+        .collect();
+    let count = texts.len();
+    let limit = (FLOATING_TEXT_BUDGET as f32 * scale) as usize;
+    let trimmed = over_budget(texts, limit);
+    for entity in &trimmed {
+        if let Ok((_, mut indicator, _)) = text_query.get_mut(*entity) {
+            let remaining = indicator.lifetime.remaining();
+            indicator.lifetime.tick(remaining);
+        }
+    }
+    budget.record(BudgetCategory::FloatingText, count, limit, trimmed.len());
+}
+
+// The city life director sizes the ambience to the frame rate; anything past
+// that goes, dogs before cars
+pub fn ambient_budget_system(
+    mut commands: Commands,
+    mut budget: ResMut<EntityBudget>,
+    director: Option<Res<CityLifeDirector>>,
+    dog_query: Query<Entity, With<StrayDog>>,
+    car_query: Query<Entity, With<CivilianCar>>,
+) {
+    let Some(director) = director else {
+        return;
+    };
+    let npcs: Vec<BudgetEntry> = dog_query
+        .iter()
+        .map(|entity| (entity, 0))
+        .chain(car_query.iter().map(|entity| (entity, 1)))
+        .map(|(entity, priority)| BudgetEntry {
+            entity,
+            priority,
+            age: 0.0,
+        })
+        .collect();
+    let count = npcs.len();
+    let limit = director.max_dogs() + director.max_cars();
+    let trimmed = over_budget(npcs, limit);
+    for entity in &trimmed {
+        commands.entity(*entity).despawn();
+    }
+    budget.record(BudgetCategory::AmbientNpcs, count, limit, trimmed.len());
+}
+
+pub fn lasting_budget_system(
+    mut budget: ResMut<EntityBudget>,
+    balance: Res<BalanceConfig>,
+    decals: Option<Res<DecalLayer>>,
+    corpse_query: Query<&Corpse>,
+) {
+    if let Some(decals) = decals {
+        budget.record(BudgetCategory::Decals, decals.len(), decals.capacity(), 0);
+    }
+    // Fading bodies are on their way out already
+    let corpses = corpse_query
+        .iter()
+        .filter(|corpse| !corpse.is_fading())
+        .count();
+    budget.record(
+        BudgetCategory::Corpses,
+        corpses,
+        balance.corpses.max_corpses,
+        0,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_priority_and_oldest_go_first_when_over_budget() {
+        let entry = |index: u32, priority: u8, age: f32| BudgetEntry {
+            entity: Entity::from_raw(index),
+            priority,
+            age,
+        };
+        let entries = vec![
+            entry(0, EFFECT_PRIORITY, 0.9),
+            entry(1, WEATHER_PRIORITY, 0.1),
+            entry(2, EFFECT_PRIORITY, 0.2),
+            entry(3, WEATHER_PRIORITY, 0.7),
+            entry(4, EFFECT_PRIORITY, 0.5),
+        ];
+        assert!(over_budget(entries.clone(), 5).is_empty());
+        assert_eq!(
+            over_budget(entries.clone(), 2),
+            vec![
+                Entity::from_raw(3),
+                Entity::from_raw(1),
+                Entity::from_raw(0)
+            ]
+        );
+        assert_eq!(over_budget(entries, 0).len(), 5);
+
+        let mut budget = EntityBudget::default();
+        budget.record(BudgetCategory::Particles, 12, 10, 2);
+        budget.record(BudgetCategory::Particles, 9, 10, 0);
+        assert_eq!(
+            budget.line(BudgetCategory::Particles),
+            BudgetLine {
+                live: 9,
+                limit: 10,
+                trimmed: 2
+            }
+        );
+        assert!(budget.summary().starts_with("Particles: 9/10 (2 trimmed)"));
+    }
+}
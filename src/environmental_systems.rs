@@ -1,9 +1,29 @@
+use crate::campaign::{Campaign, MissionConfig};
+use crate::components::{
+    Faction, Flashlight, GamePhase, LightsOut, Selected, Unit, UnitType, UpgradeType,
+};
+use crate::config::{BalanceConfig, GraphicsSettings, MissionOverrides, NightVisionBalance};
+use crate::daily::{daily_running, DailyChallengeState};
+use crate::localization::tr_args;
+use crate::map::{CityMap, PropKind};
+use crate::resources::GameState;
+use crate::utils::{play_tactical_sound, RngStream, SimRng};
 use bevy::log::info;
 use bevy::prelude::*;
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
 
-#[derive(Resource)]
+const BATTLE_CLOCK_SPEED: f32 = 3.0; // Game seconds per real second
+const TWILIGHT_LENGTH: f32 = 0.05; // Share of the day dusk and dawn take (~70 min)
+const NIGHT_VISION_RANGE: f32 = 0.55; // Vision left to unaided units in full darkness
+const NIGHT_ACCURACY: f32 = 0.7; // Hit chance left to unaided units in full darkness
+const HOLDER_GLOW: f32 = 12.0; // A flashlight lights up whoever holds it, this far out
+const TURN_DISTANCE: f32 = 1.0; // Moved at least this far in a tick to turn the beam
+const BEAM_COLOR: Color = Color::rgba(1.0, 0.95, 0.7, 0.15);
+const BEAM_Z: f32 = 0.35;
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct EnvironmentalState {
     pub time_of_day: f32, // 0.0 = midnight, 0.5 = noon, 1.0 = midnight
     pub weather_type: WeatherType,
@@ -14,7 +34,7 @@ pub struct EnvironmentalState {
     pub movement_modifier: f32,   // multiplier for movement speed
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub enum WeatherType {
     Clear,
     Overcast,
@@ -38,6 +58,13 @@ pub enum WeatherParticleType {
     Dust,
 }
 
+// Lamp posts light a pool around them; anyone standing in one can be seen
+// at full range even at night
+#[derive(Component)]
+pub struct Streetlight {
+    pub radius: f32,
+}
+
 #[derive(Resource)]
 pub struct EnvironmentalAmbientLight {
     pub base_color: Color,
@@ -56,7 +83,7 @@ impl Default for EnvironmentalAmbientLight {
 impl Default for EnvironmentalState {
     fn default() -> Self {
         Self {
-            time_of_day: 0.75, // Late afternoon (October 17, 2019 was around 3-4 PM)
+            time_of_day: 0.64, // Late afternoon (October 17, 2019 was around 3-4 PM)
             weather_type: WeatherType::Clear,
             weather_intensity: 0.0,
             wind_direction: 0.0,
@@ -68,6 +95,30 @@ impl Default for EnvironmentalState {
 }
 
 impl EnvironmentalState {
+    pub fn set_hour(&mut self, hour: f32) {
+        self.time_of_day = (hour / 24.0).rem_euclid(1.0);
+    }
+
+    // 0.0 in daylight, 1.0 at night, easing through dusk (6 PM) and dawn (6 AM)
+    pub fn darkness(&self) -> f32 {
+        let dusk = (self.time_of_day - 0.75) / TWILIGHT_LENGTH;
+        let dawn = (0.25 - self.time_of_day) / TWILIGHT_LENGTH;
+        dusk.max(dawn).clamp(0.0, 1.0)
+    }
+
+    // Vision range and accuracy multipliers from darkness. Night vision and
+    // targets standing under a streetlight cancel the penalty.
+    pub fn night_modifiers(&self, night_vision: bool, target_lit: bool) -> (f32, f32) {
+        if night_vision || target_lit {
+            return (1.0, 1.0);
+        }
+        let darkness = self.darkness();
+        (
+            1.0 - (1.0 - NIGHT_VISION_RANGE) * darkness,
+            1.0 - (1.0 - NIGHT_ACCURACY) * darkness,
+        )
+    }
+
     pub fn get_ambient_light_color(&self) -> Color {
         let time_factor = (self.time_of_day * 2.0 * PI).cos();
 
@@ -175,7 +226,7 @@ pub fn update_environmental_time(
     mut time_display_timer: Local<f32>,
 ) {
     // Time progresses slowly during battle
-    let time_speed = 0.01; // Very slow progression
+    let time_speed = BATTLE_CLOCK_SPEED / 86_400.0;
     let old_time = env_state.time_of_day;
     env_state.time_of_day = (env_state.time_of_day + time.delta_seconds() * time_speed) % 1.0;
     env_state.update_gameplay_modifiers();
@@ -226,6 +277,311 @@ pub fn update_environmental_time(
     }
 }
 
+pub fn has_night_vision(unit: &Unit) -> bool {
+    unit.equipment.upgrades.contains(&UpgradeType::NightVision)
+}
+
+pub fn is_lit(position: Vec3, streetlights: &[(Vec3, f32)]) -> bool {
+    streetlights
+        .iter()
+        .any(|(light, radius)| light.truncate().distance(position.truncate()) <= *radius)
+}
+
+// A flashlight's beam, in transform space
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LightCone {
+    pub origin: Vec2,
+    pub facing: Vec2,
+    pub range: f32,
+    pub half_angle: f32, // Radians
+}
+
+impl LightCone {
+    pub fn new(origin: Vec2, flashlight: &Flashlight, balance: &NightVisionBalance) -> Self {
+        Self {
+            origin,
+            facing: flashlight.facing,
+            range: balance.flashlight_range,
+            half_angle: balance.flashlight_half_angle.to_radians(),
+        }
+    }
+
+    /// Whoever stands in the beam is lit, and so is whoever holds it.
+    pub fn lights(&self, position: Vec2) -> bool {
+        let offset = position - self.origin;
+        offset.length() <= HOLDER_GLOW
+            || (offset.length() <= self.range
+                && offset.try_normalize().is_some_and(|direction| {
+                    direction.angle_between(self.facing).abs() <= self.half_angle
+                }))
+    }
+}
+
+pub fn in_beam(position: Vec3, beams: &[LightCone]) -> bool {
+    beams.iter().any(|beam| beam.lights(position.truncate()))
+}
+
+pub type LightQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        AnyOf<(&'static Streetlight, &'static Flashlight)>,
+    ),
+>;
+
+/// Streetlight pools and flashlight beams, as CombatConditions takes them.
+pub fn gather_lights(
+    light_query: &LightQuery,
+    balance: &NightVisionBalance,
+) -> (Vec<(Vec3, f32)>, Vec<LightCone>) {
+    let mut streetlights = Vec::new();
+    let mut beams = Vec::new();
+    for (transform, (streetlight, flashlight)) in light_query.iter() {
+        if let Some(streetlight) = streetlight {
+            streetlights.push((transform.translation, streetlight.radius));
+        }
+        if let Some(flashlight) = flashlight {
+            beams.push(LightCone::new(
+                transform.translation.truncate(),
+                flashlight,
+                balance,
+            ));
+        }
+    }
+    (streetlights, beams)
+}
+
+/// Units without night vision switch a flashlight on once it's dark enough,
+/// unless ordered to keep it off.
+pub fn wants_flashlight(
+    unit: &Unit,
+    darkness: f32,
+    lights_out: bool,
+    balance: &NightVisionBalance,
+) -> bool {
+    unit.health > 0.0
+        && unit.unit_type != UnitType::Roadblock
+        && !lights_out
+        && !has_night_vision(unit)
+        && darkness >= balance.flashlight_darkness
+}
+
+#[derive(Component)]
+pub struct FlashlightBeam {
+    pub owner: Entity,
+}
+
+fn beam_transform(origin: Vec3, facing: Vec2, range: f32) -> Transform {
+    Transform::from_translation((origin.truncate() + facing * range / 2.0).extend(BEAM_Z))
+        .with_rotation(Quat::from_rotation_z(facing.y.atan2(facing.x)))
+}
+
+// Flashlights go on as it gets dark and off at dawn, and turn with whoever
+// holds them
+pub fn flashlight_system(
+    mut commands: Commands,
+    env_state: Res<EnvironmentalState>,
+    balance: Res<BalanceConfig>,
+    mut unit_query: Query<(
+        Entity,
+        &Unit,
+        &Transform,
+        Option<&mut Flashlight>,
+        Has<LightsOut>,
+    )>,
+    target_query: Query<&Transform, With<Unit>>,
+) {
+    let darkness = env_state.darkness();
+    for (entity, unit, transform, flashlight, lights_out) in unit_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let on = wants_flashlight(unit, darkness, lights_out, &balance.night_vision);
+        let Some(mut flashlight) = flashlight else {
+            if on {
+                commands.entity(entity).insert(Flashlight {
+                    facing: Vec2::X,
+                    last_position: position,
+                });
+            }
+            continue;
+        };
+        if !on {
+            commands.entity(entity).remove::<Flashlight>();
+            continue;
+        }
+
+        let at_target = unit
+            .target
+            .and_then(|target| target_query.get(target).ok())
+            .and_then(|target| (target.translation.truncate() - position).try_normalize());
+        let moved = position - flashlight.last_position;
+        let walking = (moved.length() >= TURN_DISTANCE).then(|| moved.normalize());
+        if let Some(facing) = at_target.or(walking) {
+            flashlight.facing = facing;
+        }
+        flashlight.last_position = position;
+    }
+}
+
+// A faint beam is drawn ahead of each flashlight, unless dynamic lighting is off
+pub fn flashlight_beam_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    graphics: Option<Res<GraphicsSettings>>,
+    holder_query: Query<(Entity, &Transform, &Flashlight), Without<FlashlightBeam>>,
+    mut beam_query: Query<(Entity, &FlashlightBeam, &mut Transform), Without<Flashlight>>,
+) {
+    if !graphics.map_or(true, |graphics| graphics.dynamic_lighting) {
+        for (entity, ..) in beam_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let range = balance.night_vision.flashlight_range;
+    let mut drawn = Vec::new();
+    for (entity, beam, mut transform) in beam_query.iter_mut() {
+        let Ok((_, holder, flashlight)) = holder_query.get(beam.owner) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        *transform = beam_transform(holder.translation, flashlight.facing, range);
+        drawn.push(beam.owner);
+    }
+
+    let width = range
+        * balance
+            .night_vision
+            .flashlight_half_angle
+            .to_radians()
+            .tan();
+    for (owner, holder, flashlight) in holder_query.iter() {
+        if drawn.contains(&owner) {
+            continue;
+        }
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: BEAM_COLOR,
+                    custom_size: Some(Vec2::new(range, width)),
+                    ..default()
+                },
+                transform: beam_transform(holder.translation, flashlight.facing, range),
+                ..default()
+            },
+            FlashlightBeam { owner },
+        ));
+    }
+}
+
+// Slash: the selected cartel units keep their flashlights off, or switch
+// them back on if they all already were
+pub fn lights_out_order_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    unit_query: Query<(Entity, &Unit, Has<LightsOut>), With<Selected>>,
+) {
+    if !input.just_pressed(KeyCode::Slash) {
+        return;
+    }
+    let squad: Vec<(Entity, bool)> = unit_query
+        .iter()
+        .filter(|(_, unit, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(entity, _, lights_out)| (entity, lights_out))
+        .collect();
+    if squad.is_empty() {
+        return;
+    }
+
+    let lights_out = !squad.iter().all(|(_, lights_out)| *lights_out);
+    for (entity, _) in &squad {
+        if lights_out {
+            commands.entity(*entity).insert(LightsOut);
+        } else {
+            commands.entity(*entity).remove::<LightsOut>();
+        }
+    }
+    let message = if lights_out {
+        "radio.lights_out"
+    } else {
+        "radio.lights_on"
+    };
+    play_tactical_sound("radio", &tr_args(message, &[("count", &squad.len())]));
+}
+
+// Missions that pin a start time reset the clock when they begin
+pub fn mission_start_time_system(
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    mut env_state: ResMut<EnvironmentalState>,
+    mut last_phase: Local<Option<GamePhase>>,
+) {
+    let started = game_state.game_phase == GamePhase::Preparation
+        && last_phase.as_ref() != Some(&GamePhase::Preparation);
+    *last_phase = Some(game_state.game_phase.clone());
+    if !started {
+        return;
+    }
+
+    let mission_config = MissionConfig::get_mission_config(&campaign.progress.current_mission);
+    if let Some(hour) = mission_config.start_time {
+        env_state.set_hour(hour);
+        info!(
+            "🕒 {} begins at {:02}:{:02}",
+            mission_config.name,
+            hour as u32,
+            (hour.fract() * 60.0) as u32
+        );
+    }
+}
+
+pub fn spawn_streetlights(commands: &mut Commands, city_map: &CityMap) {
+    // Lamp posts come from the city map's Streetlight props
+    let positions: Vec<Vec3> = city_map
+        .prop_positions(PropKind::Streetlight)
+        .map(|position| position.extend(-0.5))
+        .collect();
+    let radius = 110.0;
+
+    for position in positions {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(1.0, 0.85, 0.5, 0.0),
+                    custom_size: Some(Vec2::splat(radius * 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            Streetlight { radius },
+        ));
+    }
+}
+
+// Light pools fade in as it gets dark; without dynamic lighting they stay unlit
+pub fn update_streetlights(
+    env_state: Res<EnvironmentalState>,
+    graphics: Option<Res<GraphicsSettings>>,
+    mut streetlight_query: Query<&mut Sprite, With<Streetlight>>,
+) {
+    let graphics_changed = graphics
+        .as_ref()
+        .is_some_and(|graphics| graphics.is_changed());
+    if !env_state.is_changed() && !graphics_changed {
+        return;
+    }
+    let lighting = graphics.map_or(true, |graphics| graphics.dynamic_lighting);
+    let alpha = if lighting {
+        0.25 * env_state.darkness()
+    } else {
+        0.0
+    };
+    for mut sprite in streetlight_query.iter_mut() {
+        sprite.color.set_a(alpha);
+    }
+}
+
 pub fn update_ambient_lighting(
     env_state: Res<EnvironmentalState>,
     mut ambient_light_res: ResMut<EnvironmentalAmbientLight>,
@@ -248,6 +604,7 @@ pub fn spawn_weather_particles(
     mut commands: Commands,
     env_state: Res<EnvironmentalState>,
     time: Res<Time>,
+    graphics: Option<Res<GraphicsSettings>>,
     mut particle_spawn_timer: Local<f32>,
 ) {
     *particle_spawn_timer += time.delta_seconds();
@@ -256,14 +613,17 @@ pub fn spawn_weather_particles(
         return;
     }
     *particle_spawn_timer = 0.0;
+    // The graphics settings decide how much of the weather is drawn
+    let density = graphics.map_or(1.0, |graphics| graphics.weather_particles);
 
     match env_state.weather_type {
         WeatherType::LightRain | WeatherType::HeavyRain => {
             let spawn_rate = match env_state.weather_type {
-                WeatherType::LightRain => 5,
-                WeatherType::HeavyRain => 15,
-                _ => 0,
+                WeatherType::LightRain => 5.0,
+                WeatherType::HeavyRain => 15.0,
+                _ => 0.0,
             };
+            let spawn_rate = (spawn_rate * density).round() as u32;
 
             for _ in 0..spawn_rate {
                 let mut rng = thread_rng();
@@ -296,7 +656,7 @@ pub fn spawn_weather_particles(
         }
         WeatherType::Fog => {
             let mut rng = thread_rng();
-            if rng.gen::<f32>() < 0.3 {
+            if rng.gen::<f32>() < 0.3 * density {
                 let x = rng.gen::<f32>() * 50.0 - 25.0;
                 let z = rng.gen::<f32>() * 50.0 - 25.0;
                 let y = rng.gen::<f32>() * 5.0 + 2.0;
@@ -371,12 +731,22 @@ pub fn update_weather_particles(
 pub fn trigger_weather_change(
     mut env_state: ResMut<EnvironmentalState>,
     time: Res<Time>,
+    daily: Option<Res<DailyChallengeState>>,
+    overrides: Option<Res<MissionOverrides>>,
+    mut sim_rng: ResMut<SimRng>,
     mut weather_timer: Local<f32>,
 ) {
+    // A daily challenge keeps the weather it was dealt, and a mission the weather it pins
+    if daily_running(daily.as_deref())
+        || overrides.is_some_and(|overrides| overrides.weather.is_some())
+    {
+        return;
+    }
+
     *weather_timer += time.delta_seconds();
 
     // Weather changes every 2-5 minutes during battle
-    let mut rng = thread_rng();
+    let rng = sim_rng.stream(RngStream::Environment);
     if *weather_timer > 120.0 + rng.gen::<f32>() * 180.0 {
         *weather_timer = 0.0;
 
@@ -415,3 +785,53 @@ pub fn trigger_weather_change(
         info!("📊 Tactical Impact: {}", tactical_info);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_darkness_follows_clock() {
+        let mut env_state = EnvironmentalState::default();
+
+        env_state.set_hour(15.25);
+        assert_eq!(env_state.darkness(), 0.0);
+        assert_eq!(env_state.night_modifiers(false, false), (1.0, 1.0));
+
+        env_state.set_hour(22.0);
+        assert_eq!(env_state.darkness(), 1.0);
+        env_state.set_hour(3.0);
+        assert_eq!(env_state.darkness(), 1.0);
+    }
+
+    #[test]
+    fn test_night_penalty_spares_night_vision_and_lit_targets() {
+        let mut env_state = EnvironmentalState::default();
+        env_state.set_hour(23.0);
+
+        let (vision, accuracy) = env_state.night_modifiers(false, false);
+        assert!(vision < 1.0 && accuracy < 1.0);
+        assert_eq!(env_state.night_modifiers(true, false), (1.0, 1.0));
+        assert_eq!(env_state.night_modifiers(false, true), (1.0, 1.0));
+
+        let lights = [(Vec3::new(100.0, 0.0, -0.5), 50.0)];
+        assert!(is_lit(Vec3::new(120.0, 10.0, 1.0), &lights));
+        assert!(!is_lit(Vec3::ZERO, &lights));
+    }
+
+    #[test]
+    fn test_flashlight_lights_its_cone_and_its_holder() {
+        let flashlight = Flashlight {
+            facing: Vec2::X,
+            last_position: Vec2::ZERO,
+        };
+        let beam = LightCone::new(Vec2::ZERO, &flashlight, &NightVisionBalance::default());
+        let beams = [beam];
+
+        assert!(in_beam(Vec3::new(150.0, 20.0, 1.0), &beams));
+        assert!(in_beam(Vec3::new(-5.0, 0.0, 1.0), &beams)); // The holder
+        assert!(!in_beam(Vec3::new(-110.0, 0.0, 1.0), &beams)); // Behind
+        assert!(!in_beam(Vec3::new(100.0, 100.0, 1.0), &beams)); // Off to the side
+        assert!(!in_beam(Vec3::new(400.0, 0.0, 1.0), &beams)); // Past its reach
+    }
+}
@@ -0,0 +1,144 @@
+use crate::app_state::AppState;
+use crate::config::ConfigProfiles;
+use crate::multiplayer::MultiplayerState;
+use crate::resources::GameState;
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use bevy::winit::{UpdateMode, WinitSettings};
+use std::time::{Duration, Instant};
+
+// ==================== FRAME PACING PLUGIN ====================
+
+// Keeps the game from burning a laptop battery. The active config profile
+// can cap the frame rate, and while the window is in the background or
+// minimized frames are only drawn a few times a second. A single-player
+// battle also pauses when the window loses focus and picks up again when it
+// comes back; a multiplayer match keeps running so nobody else waits.
+
+pub struct FramePacingPlugin;
+
+impl Plugin for FramePacingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WinitSettings>()
+            .init_resource::<BackgroundPause>()
+            .add_systems(Update, (background_rate_system, background_pause_system))
+            .add_systems(Last, frame_limiter_system);
+    }
+}
+
+/// A pause taken while the window was in the background.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct BackgroundPause {
+    paused_here: bool,
+}
+
+impl BackgroundPause {
+    /// Pauses the battle as focus goes, unless something else already has,
+    /// and lifts only that pause as focus comes back.
+    pub fn focus_changed(&mut self, focused: bool, allowed: bool, time: &mut Time<Virtual>) {
+        if !focused && allowed && !time.is_paused() {
+            time.pause();
+            self.paused_here = true;
+        } else if focused && self.paused_here {
+            time.unpause();
+            self.paused_here = false;
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_here
+    }
+}
+
+/// How long to wait before starting the next frame so no more than
+/// `fps_cap` are drawn a second. A cap of 0 never waits.
+pub fn frame_wait(frame_time: Duration, fps_cap: u32) -> Option<Duration> {
+    if fps_cap == 0 {
+        return None;
+    }
+    Duration::from_secs_f64(1.0 / fps_cap as f64).checked_sub(frame_time)
+}
+
+// ==================== SYSTEMS ====================
+
+// The background frame rate follows the active config profile
+pub fn background_rate_system(
+    profiles: Res<ConfigProfiles>,
+    mut winit_settings: ResMut<WinitSettings>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    let background_fps = profiles.active().background_fps.max(1);
+    winit_settings.unfocused_mode = UpdateMode::ReactiveLowPower {
+        wait: Duration::from_secs_f64(1.0 / background_fps as f64),
+    };
+}
+
+pub fn background_pause_system(
+    mut focus_events: EventReader<WindowFocused>,
+    profiles: Res<ConfigProfiles>,
+    game_state: Res<GameState>,
+    multiplayer: Option<Res<MultiplayerState>>,
+    mut background_pause: ResMut<BackgroundPause>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let Some(focused) = focus_events.read().last().map(|event| event.focused) else {
+        return;
+    };
+    let single_player = !multiplayer.is_some_and(|multiplayer| multiplayer.game_started);
+    let allowed = profiles.active().background_pause
+        && single_player
+        && game_state.game_phase.app_state() == AppState::InGame;
+
+    let was_paused = background_pause.is_paused();
+    background_pause.focus_changed(focused, allowed, &mut virtual_time);
+    if background_pause.is_paused() != was_paused {
+        info!(
+            "⏸️ Battle {} with the window in the background",
+            if was_paused { "resumed" } else { "paused" }
+        );
+    }
+}
+
+// Runs last so the wait covers the whole frame
+pub fn frame_limiter_system(
+    profiles: Res<ConfigProfiles>,
+    mut frame_start: Local<Option<Instant>>,
+) {
+    let fps_cap = profiles.active().fps_cap;
+    if let Some(wait) = frame_start.and_then(|start| frame_wait(start.elapsed(), fps_cap)) {
+        std::thread::sleep(wait);
+    }
+    *frame_start = Some(Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_wait_out_the_cap_and_background_pauses_lift_themselves() {
+        assert_eq!(frame_wait(Duration::from_millis(5), 0), None);
+        assert_eq!(frame_wait(Duration::from_millis(40), 30), None);
+        let wait = frame_wait(Duration::from_millis(10), 50).unwrap();
+        assert!(wait > Duration::from_millis(9) && wait < Duration::from_millis(11));
+
+        let mut time = Time::<Virtual>::default();
+        let mut background_pause = BackgroundPause::default();
+        background_pause.focus_changed(false, true, &mut time);
+        assert!(time.is_paused() && background_pause.is_paused());
+        background_pause.focus_changed(true, true, &mut time);
+        assert!(!time.is_paused());
+
+        // Not allowed (a multiplayer match, or switched off) keeps running
+        background_pause.focus_changed(false, false, &mut time);
+        assert!(!time.is_paused());
+
+        // A battle already paused by the player stays paused on return
+        time.pause();
+        background_pause.focus_changed(false, true, &mut time);
+        background_pause.focus_changed(true, true, &mut time);
+        assert!(time.is_paused());
+    }
+}
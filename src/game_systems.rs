@@ -1,22 +1,37 @@
 use crate::campaign::{
     evaluate_mission_objectives, Campaign, DefeatType, MissionResult, VictoryType,
 };
+use crate::checkpoints::control_presence;
 use crate::components::*;
+use crate::config::{BalanceConfig, UnitCatalog};
+use crate::economy::{charge, CartelTreasury};
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::production::deploy_military_unit;
 use crate::resources::*;
 use crate::spawners::spawn_unit;
-use crate::utils::play_tactical_sound;
+use crate::tutorial::Tutorial;
+use crate::utils::{iso_to_world, play_tactical_sound, RngStream, SimRng};
 use bevy::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 // ==================== WAVE SPAWNER SYSTEM ====================
 
+#[allow(clippy::too_many_arguments)]
 pub fn wave_spawner_system(
     time: Res<Time>,
     mut commands: Commands,
     mut wave_query: Query<&mut WaveSpawner>,
     mut game_state: ResMut<GameState>,
     game_assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    city_map: Res<CityMap>,
+    mut sim_rng: ResMut<SimRng>,
+    mut staging_query: Query<&mut ProductionQueue, With<StagingArea>>,
 ) {
+    let rng = sim_rng.stream(RngStream::Spawning);
+
     for mut spawner in wave_query.iter_mut() {
         spawner.next_wave_timer.tick(time.delta());
 
@@ -24,35 +39,40 @@ pub fn wave_spawner_system(
             spawner.wave_number += 1;
             game_state.current_wave = spawner.wave_number;
 
-            // Calculate spawn positions around the perimeter
+            // Enter at the map's military spawn points, else around the perimeter.
+            // Map positions are on screen, the rest of the wave is in world space
             let spawn_radius = 300.0;
-            let entry_points = [
-                Vec3::new(spawn_radius, 0.0, 0.0),  // Right
-                Vec3::new(-spawn_radius, 0.0, 0.0), // Left
-                Vec3::new(0.0, spawn_radius, 0.0),  // Top
-                Vec3::new(0.0, -spawn_radius, 0.0), // Bottom
-            ];
-
-            // Spawn military units for this wave
+            let mut entry_points: Vec<Vec3> = city_map
+                .spawn_positions(&Faction::Military)
+                .into_iter()
+                .map(|position| iso_to_world(position.extend(0.0)))
+                .collect();
+            if entry_points.is_empty() {
+                entry_points = vec![
+                    Vec3::new(spawn_radius, 0.0, 0.0),  // Right
+                    Vec3::new(-spawn_radius, 0.0, 0.0), // Left
+                    Vec3::new(0.0, spawn_radius, 0.0),  // Top
+                    Vec3::new(0.0, -spawn_radius, 0.0), // Bottom
+                ];
+            }
+
+            // Spawn military units for this wave, or queue them at the
+            // staging areas in missions with base play
             for i in 0..spawner.units_in_wave {
                 let entry_point = entry_points[i as usize % entry_points.len()];
-                let offset = Vec3::new(
-                    thread_rng().gen_range(-50.0..50.0),
-                    thread_rng().gen_range(-50.0..50.0),
-                    0.0,
-                );
+                let offset = Vec3::new(rng.gen_range(-50.0..50.0), rng.gen_range(-50.0..50.0), 0.0);
 
                 let unit_type = match spawner.wave_number {
                     1..=2 => UnitType::Soldier,
                     3..=4 => {
-                        if thread_rng().gen_bool(0.7) {
+                        if rng.gen_bool(0.7) {
                             UnitType::Soldier
                         } else {
                             UnitType::SpecialForces
                         }
                     }
                     _ => {
-                        if thread_rng().gen_bool(0.4) {
+                        if rng.gen_bool(0.4) {
                             UnitType::Vehicle
                         } else {
                             UnitType::SpecialForces
@@ -60,12 +80,14 @@ pub fn wave_spawner_system(
                     }
                 };
 
-                spawn_unit(
+                deploy_military_unit(
                     &mut commands,
+                    &mut staging_query,
                     unit_type,
-                    Faction::Military,
                     entry_point + offset,
                     &game_assets,
+                    &catalog,
+                    &balance.production,
                 );
             }
 
@@ -74,9 +96,12 @@ pub fn wave_spawner_system(
 
             play_tactical_sound(
                 "radio",
-                &format!(
-                    "Wave {} incoming! {} enemy units approaching from multiple directions",
-                    spawner.wave_number, spawner.units_in_wave
+                &tr_args(
+                    "radio.wave_incoming",
+                    &[
+                        ("wave", &spawner.wave_number),
+                        ("count", &spawner.units_in_wave),
+                    ],
                 ),
             );
         }
@@ -88,67 +113,83 @@ pub fn wave_spawner_system(
 pub fn game_phase_system(
     mut game_state: ResMut<GameState>,
     mut campaign: ResMut<Campaign>,
-    unit_query: Query<&Unit>,
+    unit_query: Query<(&Unit, &Transform)>,
+    checkpoint_query: Query<(&Checkpoint, &Transform)>,
+    city_map: Res<CityMap>,
+    tutorial: Res<Tutorial>,
     time: Res<Time>,
 ) {
-    game_state.mission_timer += time.delta_seconds();
+    let checkpoints = control_presence(checkpoint_query.iter());
+
+    // The tutorial keeps the mission in Preparation until it is done or skipped
+    if !tutorial.holds_mission_clock() {
+        game_state.mission_timer += time.delta_seconds();
+    }
 
     let cartel_units = unit_query
         .iter()
-        .filter(|u| u.faction == Faction::Cartel && u.health > 0.0)
+        .filter(|(u, _)| u.faction == Faction::Cartel && u.health > 0.0)
         .count();
     let military_units = unit_query
         .iter()
-        .filter(|u| u.faction == Faction::Military && u.health > 0.0)
+        .filter(|(u, _)| u.faction == Faction::Military && u.health > 0.0)
         .count();
     let ovidio_alive = unit_query
         .iter()
-        .any(|u| u.unit_type == UnitType::Ovidio && u.health > 0.0);
+        .any(|(u, _)| u.unit_type == UnitType::Ovidio && u.health > 0.0);
 
     // Phase transitions based on time and conditions
     match game_state.game_phase {
-        GamePhase::MainMenu | GamePhase::SaveMenu | GamePhase::LoadMenu => {
+        GamePhase::Loading
+        | GamePhase::ProfileSelect
+        | GamePhase::MainMenu
+        | GamePhase::SaveMenu
+        | GamePhase::LoadMenu
+        | GamePhase::ModsMenu
+        | GamePhase::AccessibilityMenu
+        | GamePhase::TelemetryMenu
+        | GamePhase::LeaderboardMenu => {
             // Handled by main_menu_system
         }
+        GamePhase::CampaignMap => {
+            // Handled by campaign_map_system
+        }
         GamePhase::MissionBriefing => {
             // Handled by mission_briefing_system
         }
         GamePhase::Preparation => {
             if game_state.mission_timer > 15.0 {
                 game_state.game_phase = GamePhase::InitialRaid;
-                play_tactical_sound(
-                    "radio",
-                    "Phase 1: Initial military raid beginning. Defend Ovidio at all costs!",
-                );
+                play_tactical_sound("radio", &tr("radio.phase_initial_raid"));
             }
         }
         GamePhase::InitialRaid => {
             if game_state.mission_timer > 120.0 {
                 game_state.game_phase = GamePhase::BlockConvoy;
-                play_tactical_sound(
-                    "radio",
-                    "Phase 2: Military convoy approaching. Block their advance!",
-                );
+                play_tactical_sound("radio", &tr("radio.phase_block_convoy"));
             }
         }
         GamePhase::BlockConvoy => {
             if game_state.mission_timer > 240.0 {
                 game_state.game_phase = GamePhase::ApplyPressure;
-                play_tactical_sound("radio", "Phase 3: Government pressure increasing. Show them the cost of this operation!");
+                play_tactical_sound("radio", &tr("radio.phase_apply_pressure"));
             }
         }
         GamePhase::ApplyPressure => {
             if game_state.mission_timer > 360.0 {
                 game_state.game_phase = GamePhase::HoldTheLine;
-                play_tactical_sound(
-                    "radio",
-                    "Phase 4: Final push. Hold the line until the government yields!",
-                );
+                play_tactical_sound("radio", &tr("radio.phase_hold_the_line"));
             }
         }
         GamePhase::HoldTheLine => {
             // Use comprehensive objective evaluation
-            evaluate_mission_and_transition(&mut game_state, &mut campaign, &unit_query);
+            evaluate_mission_and_transition(
+                &mut game_state,
+                &mut campaign,
+                &unit_query,
+                &city_map,
+                &checkpoints,
+            );
         }
         GamePhase::Victory => {
             // Victory screen - handled by victory_defeat_system
@@ -168,7 +209,13 @@ pub fn game_phase_system(
         | GamePhase::BlockConvoy
         | GamePhase::ApplyPressure
         | GamePhase::HoldTheLine => {
-            evaluate_mission_and_transition(&mut game_state, &mut campaign, &unit_query);
+            evaluate_mission_and_transition(
+                &mut game_state,
+                &mut campaign,
+                &unit_query,
+                &city_map,
+                &checkpoints,
+            );
         }
         _ => {}
     }
@@ -176,15 +223,17 @@ pub fn game_phase_system(
     // Update scores based on eliminated units
     let dead_cartel = unit_query
         .iter()
-        .filter(|u| u.faction == Faction::Cartel && u.health <= 0.0)
-        .count();
+        .filter(|(u, _)| u.faction == Faction::Cartel && u.health <= 0.0)
+        .count() as u32
+        + game_state.cleared_dead(&Faction::Cartel);
     let dead_military = unit_query
         .iter()
-        .filter(|u| u.faction == Faction::Military && u.health <= 0.0)
-        .count();
+        .filter(|(u, _)| u.faction == Faction::Military && u.health <= 0.0)
+        .count() as u32
+        + game_state.cleared_dead(&Faction::Military);
 
-    game_state.cartel_score = dead_military as u32 * 10;
-    game_state.military_score = dead_cartel as u32 * 10;
+    game_state.cartel_score = dead_military * 10;
+    game_state.military_score = dead_cartel * 10;
 }
 
 // ==================== MISSION SYSTEM ====================
@@ -205,7 +254,16 @@ pub fn mission_system(game_state: Res<GameState>, unit_query: Query<&Unit>, _tim
 
     // Mission-specific logic can be added here based on current phase
     match game_state.game_phase {
-        GamePhase::MainMenu | GamePhase::SaveMenu | GamePhase::LoadMenu => {
+        GamePhase::Loading
+        | GamePhase::ProfileSelect
+        | GamePhase::MainMenu
+        | GamePhase::SaveMenu
+        | GamePhase::LoadMenu
+        | GamePhase::ModsMenu
+        | GamePhase::AccessibilityMenu
+        | GamePhase::TelemetryMenu
+        | GamePhase::LeaderboardMenu
+        | GamePhase::CampaignMap => {
             // Menu phases - no mission logic
         }
         GamePhase::MissionBriefing => {
@@ -243,11 +301,15 @@ pub fn handle_input(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
     game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
     mut app_exit_events: EventWriter<bevy::app::AppExit>,
     windows: Query<&Window>,
     camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
     mut selected_units: Query<&mut Movement, (With<Selected>, With<Unit>)>,
     selected_query: Query<Entity, (With<Selected>, With<Unit>)>,
+    balance: Res<BalanceConfig>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
     // Right-click to move selected units
     if mouse_button_input.just_pressed(MouseButton::Right) {
@@ -279,7 +341,7 @@ pub fn handle_input(
                     if selected_count > 0 {
                         play_tactical_sound(
                             "radio",
-                            &format!("{} units moving to new position", selected_count),
+                            &tr_args("radio.units_moving", &[("count", &selected_count)]),
                         );
                     }
                 }
@@ -288,11 +350,14 @@ pub fn handle_input(
     }
 
     // Keyboard shortcuts
-    if input.just_pressed(KeyCode::Space) {
+    if input.just_pressed(KeyCode::Space)
+        && charge(treasury.as_deref_mut(), balance.economy.roadblock_cost)
+    {
         // Deploy roadblock at random position
+        let rng = sim_rng.stream(RngStream::Spawning);
         let roadblock_pos = Vec3::new(
-            thread_rng().gen_range(-150.0..150.0),
-            thread_rng().gen_range(-150.0..150.0),
+            rng.gen_range(-150.0..150.0),
+            rng.gen_range(-150.0..150.0),
             0.0,
         );
         spawn_unit(
@@ -301,77 +366,18 @@ pub fn handle_input(
             Faction::Cartel,
             roadblock_pos,
             &game_assets,
+            &catalog,
         );
-        play_tactical_sound(
-            "construction",
-            "Roadblock deployed! Blocking military advance",
-        );
+        play_tactical_sound("construction", &tr("radio.roadblock_deployed"));
         game_state.cartel_score += 5;
     }
 
-    if input.just_pressed(KeyCode::R) {
-        // Call cartel reinforcements
-        let spawn_positions = [
-            Vec3::new(-150.0, -40.0, 0.0),
-            Vec3::new(-100.0, -40.0, 0.0),
-            Vec3::new(-150.0, 40.0, 0.0),
-        ];
-
-        for (i, position) in spawn_positions.iter().enumerate() {
-            let unit_type = if i == 0 {
-                UnitType::Enforcer
-            } else {
-                UnitType::Sicario
-            };
-            spawn_unit(
-                &mut commands,
-                unit_type,
-                Faction::Cartel,
-                *position,
-                &game_assets,
-            );
-
-            // Spawn arrival particles
-            for _ in 0..8 {
-                let velocity = Vec3::new(
-                    thread_rng().gen_range(-120.0..120.0),
-                    thread_rng().gen_range(-120.0..120.0),
-                    0.0,
-                );
-
-                commands.spawn((
-                    SpriteBundle {
-                        sprite: Sprite {
-                            color: Color::GREEN,
-                            custom_size: Some(Vec2::new(4.0, 4.0)),
-                            ..default()
-                        },
-                        transform: Transform::from_translation(
-                            *position + Vec3::new(0.0, 0.0, 0.5),
-                        ),
-                        ..default()
-                    },
-                    ParticleEffect {
-                        lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-                        velocity,
-                    },
-                ));
-            }
-        }
-
-        play_tactical_sound(
-            "radio",
-            "Reinforcements arriving! 3 additional units deployed",
-        );
-        game_state.cartel_score += 15;
-    }
-
     // Tactical abilities
     if input.just_pressed(KeyCode::Q) {
         // Defensive stance for selected units
         for _entity in selected_query.iter() {
             // Add defensive bonus (could be implemented as a component)
-            play_tactical_sound("radio", "Units taking defensive positions");
+            play_tactical_sound("radio", &tr("radio.defensive_stance"));
         }
     }
 
@@ -379,26 +385,42 @@ pub fn handle_input(
         // Aggressive stance for selected units
         for _entity in selected_query.iter() {
             // Add aggressive bonus (could be implemented as a component)
-            play_tactical_sound("radio", "Units switching to aggressive tactics");
+            play_tactical_sound("radio", &tr("radio.aggressive_stance"));
         }
     }
 
     // Main menu access
     if input.just_pressed(KeyCode::Escape) {
         match game_state.game_phase {
-            GamePhase::MainMenu | GamePhase::SaveMenu | GamePhase::LoadMenu => {
+            GamePhase::Loading => {
+                // Handled by asset_loading_system
+            }
+            GamePhase::ProfileSelect => {
+                // Handled by profile_picker_system
+            }
+            GamePhase::MainMenu
+            | GamePhase::SaveMenu
+            | GamePhase::LoadMenu
+            | GamePhase::ModsMenu
+            | GamePhase::AccessibilityMenu
+            | GamePhase::TelemetryMenu
+            | GamePhase::LeaderboardMenu => {
                 // Already in menu or submenu - exit game
-                play_tactical_sound("radio", "Simulation terminated. Historical outcome: Government forces withdrew, Ovidio remained free.");
+                play_tactical_sound("radio", &tr("radio.simulation_terminated"));
                 info!(
                     "🏁 Game ended by user. Final score - Cartel: {}, Military: {}",
                     game_state.cartel_score, game_state.military_score
                 );
                 app_exit_events.send(bevy::app::AppExit);
             }
+            GamePhase::MissionBriefing => {
+                // Back to the campaign map to pick another mission
+                game_state.game_phase = GamePhase::CampaignMap;
+            }
             _ => {
                 // Go to main menu to access save/load
                 game_state.game_phase = GamePhase::MainMenu;
-                play_tactical_sound("radio", "Opening main menu...");
+                play_tactical_sound("radio", &tr("radio.opening_main_menu"));
             }
         }
     }
@@ -409,9 +431,12 @@ pub fn handle_input(
 fn evaluate_mission_and_transition(
     game_state: &mut GameState,
     campaign: &mut Campaign,
-    unit_query: &Query<&Unit>,
+    unit_query: &Query<(&Unit, &Transform)>,
+    city_map: &CityMap,
+    checkpoints: &[(Faction, Vec2)],
 ) {
-    let mission_result = evaluate_mission_objectives(campaign, game_state, unit_query);
+    let mission_result =
+        evaluate_mission_objectives(campaign, game_state, unit_query, city_map, checkpoints);
 
     match mission_result {
         MissionResult::Victory(victory_type) => {
@@ -423,21 +448,19 @@ fn evaluate_mission_and_transition(
                 VictoryType::TimeLimit => 1000,
                 VictoryType::EnemiesEliminated => 1200,
                 VictoryType::TargetSurvived => 800,
+                VictoryType::Capitulation(_) => 1000,
             };
             game_state.cartel_score += bonus_score;
 
             let victory_message = match victory_type {
-                VictoryType::AllObjectivesComplete => {
-                    "PERFECT VICTORY! All objectives completed successfully!"
-                }
-                VictoryType::TimeLimit => {
-                    "VICTORY! Successfully held the line against government forces!"
-                }
-                VictoryType::EnemiesEliminated => "DECISIVE VICTORY! All enemy forces eliminated!",
-                VictoryType::TargetSurvived => "VICTORY! Target survived the assault!",
+                VictoryType::AllObjectivesComplete => "radio.victory_all_objectives",
+                VictoryType::TimeLimit => "radio.victory_time_limit",
+                VictoryType::EnemiesEliminated => "radio.victory_enemies_eliminated",
+                VictoryType::TargetSurvived => "radio.victory_target_survived",
+                VictoryType::Capitulation(_) => "radio.victory_capitulation",
             };
 
-            play_tactical_sound("radio", victory_message);
+            play_tactical_sound("radio", &tr(victory_message));
             info!(
                 "🏆 Mission Victory: {:?} - Bonus: {}",
                 victory_type, bonus_score
@@ -451,19 +474,13 @@ fn evaluate_mission_and_transition(
             game_state.cartel_score += consolation_score;
 
             let defeat_message = match defeat_type {
-                DefeatType::TargetLost => {
-                    "MISSION FAILED! Ovidio has been captured by government forces!"
-                }
-                DefeatType::TimeExpired => {
-                    "MISSION FAILED! Time ran out before objectives were completed!"
-                }
-                DefeatType::AllUnitsDead => {
-                    "MISSION FAILED! All cartel forces have been eliminated!"
-                }
-                DefeatType::ObjectiveFailed => "MISSION FAILED! Critical objectives were not met!",
+                DefeatType::TargetLost => "radio.defeat_target_lost",
+                DefeatType::TimeExpired => "radio.defeat_time_expired",
+                DefeatType::AllUnitsDead => "radio.defeat_all_units_dead",
+                DefeatType::ObjectiveFailed => "radio.defeat_objective_failed",
             };
 
-            play_tactical_sound("radio", defeat_message);
+            play_tactical_sound("radio", &tr(defeat_message));
             info!(
                 "💀 Mission Defeat: {:?} - Consolation: {}",
                 defeat_type, consolation_score
@@ -0,0 +1,397 @@
+use crate::accessibility::AccessibilitySettings;
+use crate::ai::unit_ai_system;
+use crate::air_assault::AirAssaultPlugin;
+use crate::app_state::GameSet;
+use crate::area_denial::AreaDenialPlugin;
+use crate::assists::MissionAssistsPlugin;
+use crate::campaign::{campaign_system, Campaign};
+use crate::capture::CaptureSequencePlugin;
+use crate::checkpoints::RoadCheckpointPlugin;
+use crate::cohesion::SquadCohesionPlugin;
+use crate::commander::CommanderPowersPlugin;
+use crate::components::*;
+use crate::config::{BalanceConfig, UnitCatalog};
+use crate::convoy::ConvoyPlugin;
+use crate::game_systems::game_phase_system;
+use crate::hvt::HvtTargetingPlugin;
+use crate::indirect_fire::IndirectFirePlugin;
+use crate::informants::InformantVettingPlugin;
+use crate::corpse_system::CorpseSystemPlugin;
+use crate::daily::DailyChallengePlugin;
+use crate::deployment::DeploymentPlugin;
+use crate::dialogue::Conversations;
+use crate::economy::CartelEconomyPlugin;
+use crate::endings::PoliticalEndingsPlugin;
+use crate::engineering::EngineeringPlugin;
+use crate::intel_recruitment::IntelRecruitmentPlugin;
+use crate::intel_system::IntelSystemPlugin;
+use crate::jamming::JammingPlugin;
+use crate::order_of_battle::OrderOfBattlePlugin;
+use crate::overwatch::SniperOverwatchPlugin;
+use crate::political_system::{PoliticalState, PoliticalSystemPlugin};
+use crate::prisoners::PrisonerSystemPlugin;
+use crate::protection::OvidioProtectionPlugin;
+use crate::reinforcements::ReinforcementCallPlugin;
+use crate::resources::*;
+use crate::rules_of_engagement::RulesOfEngagementPlugin;
+use crate::scoring::MissionScoringPlugin;
+use crate::scripting::ScriptingPlugin;
+use crate::simulation::SimulationPlugin;
+use crate::spawners::spawn_unit;
+use crate::ui::UiTheme;
+use crate::upgrades::MissionUpgradesPlugin;
+use crate::systems::setup_game;
+use crate::threat_assessment::ThreatAssessmentPlugin;
+use crate::tutorial::Tutorial;
+use crate::utils::SimulationSet;
+use crate::wounded::WoundedPlugin;
+use crate::wrecks::WreckSalvagePlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// ==================== HEADLESS SIMULATION ====================
+
+// Runs a full mission without window, audio or input. Time is stepped one
+// simulation tick per update, so a mission finishes as fast as the CPU allows,
+// and scripted AI drives both factions.
+
+const DEFAULT_MAX_DURATION: f32 = 900.0; // Mission seconds before the run is called a draw
+const DEFAULT_SAMPLE_INTERVAL: f32 = 10.0;
+
+#[derive(Clone, Debug)]
+pub struct HeadlessOptions {
+    pub seed: Option<u64>,
+    pub max_duration: f32,
+    pub sample_interval: f32,
+    pub output: Option<PathBuf>,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            max_duration: DEFAULT_MAX_DURATION,
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+            output: None,
+        }
+    }
+}
+
+impl HeadlessOptions {
+    // Parses `--seed N --max-duration SECS --sample-interval SECS --output PATH`,
+    // ignoring --headless and --check-determinism themselves
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| format!("Missing value for {}", name))
+            };
+            match arg.as_str() {
+                "--headless" | "--check-determinism" | "--portable" => {}
+                "--seed" => {
+                    options.seed = Some(
+                        value("--seed")?
+                            .parse()
+                            .map_err(|_| "--seed expects an integer".to_string())?,
+                    );
+                }
+                "--max-duration" => {
+                    options.max_duration = value("--max-duration")?
+                        .parse()
+                        .map_err(|_| "--max-duration expects seconds".to_string())?;
+                }
+                "--sample-interval" => {
+                    options.sample_interval = value("--sample-interval")?
+                        .parse()
+                        .map_err(|_| "--sample-interval expects seconds".to_string())?;
+                }
+                "--output" => options.output = Some(PathBuf::from(value("--output")?)),
+                other => return Err(format!("Unknown headless option: {}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadlessWinner {
+    Cartel,
+    Military,
+    Draw,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Casualties {
+    pub cartel: u32,
+    pub military: u32,
+    pub civilian: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PressureSample {
+    pub time: f32,
+    pub total_pressure: f32,
+    pub political_will: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeadlessResult {
+    pub seed: u64,
+    pub winner: HeadlessWinner,
+    pub final_phase: GamePhase,
+    pub duration: f32,
+    pub casualties: Casualties,
+    pub pressure_timeline: Vec<PressureSample>,
+}
+
+pub fn run_headless(options: &HeadlessOptions) -> HeadlessResult {
+    let mut app = build_headless_app(options);
+    app.finish();
+    app.cleanup();
+
+    let timestep = app.world.resource::<Time<Fixed>>().timestep().as_secs_f32();
+    let mut pressure_timeline = Vec::new();
+    let mut elapsed = 0.0;
+    let mut next_sample = 0.0;
+
+    while elapsed < options.max_duration && !mission_finished(&app.world) {
+        app.update();
+        elapsed += timestep;
+
+        if elapsed >= next_sample {
+            pressure_timeline.push(sample_pressure(&app.world, elapsed));
+            next_sample += options.sample_interval.max(timestep);
+        }
+    }
+
+    collect_result(&mut app.world, elapsed, pressure_timeline)
+}
+
+pub fn run_and_report(
+    options: &HeadlessOptions,
+) -> Result<HeadlessResult, Box<dyn std::error::Error>> {
+    let result = run_headless(options);
+    let json = serde_json::to_string_pretty(&result)?;
+
+    match &options.output {
+        Some(path) => fs::write(path, json)?,
+        None => println!("{}", json),
+    }
+
+    Ok(result)
+}
+
+pub(crate) fn build_headless_app(options: &HeadlessOptions) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(SimulationPlugin)
+        .add_plugins(IntelSystemPlugin)
+        .add_plugins(IntelRecruitmentPlugin)
+        .add_plugins(JammingPlugin)
+        .add_plugins(InformantVettingPlugin)
+        .add_plugins(OrderOfBattlePlugin)
+        .add_plugins(PoliticalSystemPlugin)
+        .add_plugins(PoliticalEndingsPlugin)
+        .add_plugins(RulesOfEngagementPlugin)
+        .add_plugins(CorpseSystemPlugin)
+        .add_plugins(OvidioProtectionPlugin)
+        .add_plugins(CaptureSequencePlugin)
+        .add_plugins(HvtTargetingPlugin)
+        .add_plugins(PrisonerSystemPlugin)
+        .add_plugins(CartelEconomyPlugin)
+        .add_plugins(ReinforcementCallPlugin)
+        .add_plugins(MissionUpgradesPlugin)
+        .add_plugins(CommanderPowersPlugin)
+        .add_plugins(RoadCheckpointPlugin)
+        .add_plugins(WreckSalvagePlugin)
+        .add_plugins(EngineeringPlugin)
+        .add_plugins(SniperOverwatchPlugin)
+        .add_plugins(AreaDenialPlugin)
+        .add_plugins(ConvoyPlugin)
+        .add_plugins(AirAssaultPlugin)
+        .add_plugins(IndirectFirePlugin)
+        .add_plugins(WoundedPlugin)
+        .add_plugins(SquadCohesionPlugin)
+        .add_plugins(DeploymentPlugin)
+        .add_plugins(ThreatAssessmentPlugin)
+        .add_plugins(MissionScoringPlugin)
+        .add_plugins(DailyChallengePlugin)
+        .add_plugins(MissionAssistsPlugin)
+        .add_plugins(ScriptingPlugin);
+
+    let tick_rate = app
+        .world
+        .resource::<BalanceConfig>()
+        .simulation
+        .tick_rate
+        .max(1.0) as f64;
+    let fixed_time = Time::<Fixed>::from_hz(tick_rate);
+    let timestep = fixed_time.timestep();
+
+    let mut game_state = GameState {
+        game_phase: GamePhase::Preparation,
+        ..default()
+    };
+    if let Some(seed) = options.seed {
+        game_state.rng_seed = seed;
+    }
+
+    app.insert_resource(fixed_time)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(timestep))
+        .insert_resource(game_state)
+        // Sprites are never drawn, placeholder handles are enough
+        .insert_resource(GameAssets::default())
+        // Dashboard panels are still built, with the default theme
+        .init_resource::<UiTheme>()
+        // Political pacing reads the default accessibility options
+        .init_resource::<AccessibilitySettings>()
+        // Never started here, but the phase clock and script commands consult it
+        .init_resource::<Tutorial>()
+        // Conversations are queued by scripts but only played with a window
+        .init_resource::<Conversations>()
+        .add_systems(Startup, (setup_game, spawn_initial_raid_system).chain())
+        .add_systems(
+            FixedUpdate,
+            unit_ai_system
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        )
+        .add_systems(
+            Update,
+            (game_phase_system, campaign_system).in_set(GameSet::Session),
+        );
+
+    // Keep virtual time from clamping the manual step
+    app.world
+        .resource_mut::<Time<Virtual>>()
+        .set_max_delta(timestep.max(Duration::from_millis(250)));
+
+    app
+}
+
+// setup_game only places the cartel side and the first wave is 10s out; with
+// no military on the map the mission would be evaluated as won on the first tick
+fn spawn_initial_raid_system(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+) {
+    let raid = [
+        (UnitType::Soldier, Vec3::new(300.0, 40.0, 0.0)),
+        (UnitType::Soldier, Vec3::new(300.0, -40.0, 0.0)),
+        (UnitType::SpecialForces, Vec3::new(-300.0, 0.0, 0.0)),
+        (UnitType::Soldier, Vec3::new(0.0, -300.0, 0.0)),
+    ];
+
+    for (unit_type, position) in raid {
+        spawn_unit(
+            &mut commands,
+            unit_type,
+            Faction::Military,
+            position,
+            &game_assets,
+            &catalog,
+        );
+    }
+}
+
+pub(crate) fn mission_finished(world: &World) -> bool {
+    matches!(
+        world.resource::<GameState>().game_phase,
+        GamePhase::Victory | GamePhase::Defeat | GamePhase::GameOver
+    )
+}
+
+fn sample_pressure(world: &World, time: f32) -> PressureSample {
+    PressureSample {
+        time,
+        total_pressure: world
+            .resource::<Campaign>()
+            .political_pressure
+            .total_pressure,
+        political_will: world.resource::<PoliticalState>().political_will,
+    }
+}
+
+fn collect_result(
+    world: &mut World,
+    duration: f32,
+    pressure_timeline: Vec<PressureSample>,
+) -> HeadlessResult {
+    let game_state = world.resource::<GameState>();
+    let mut casualties = Casualties {
+        civilian: world.resource::<PoliticalState>().casualties_civilian,
+        cartel: game_state.cleared_dead(&Faction::Cartel),
+        military: game_state.cleared_dead(&Faction::Military),
+    };
+    let mut units = world.query::<&Unit>();
+    for unit in units.iter(world).filter(|unit| unit.health <= 0.0) {
+        match unit.faction {
+            Faction::Cartel => casualties.cartel += 1,
+            Faction::Military => casualties.military += 1,
+            _ => {}
+        }
+    }
+
+    let game_state = world.resource::<GameState>();
+    // The player side is the cartel, so mission victory is a cartel win
+    let winner = match game_state.game_phase {
+        GamePhase::Victory => HeadlessWinner::Cartel,
+        GamePhase::Defeat => HeadlessWinner::Military,
+        _ => HeadlessWinner::Draw,
+    };
+
+    HeadlessResult {
+        seed: game_state.rng_seed,
+        winner,
+        final_phase: game_state.game_phase.clone(),
+        duration,
+        casualties,
+        pressure_timeline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_headless_args() {
+        let options = HeadlessOptions::from_args(&args(&[
+            "--headless",
+            "--seed",
+            "1234",
+            "--max-duration",
+            "120",
+            "--output",
+            "result.json",
+        ]))
+        .unwrap();
+
+        assert_eq!(options.seed, Some(1234));
+        assert_eq!(options.max_duration, 120.0);
+        assert_eq!(options.sample_interval, DEFAULT_SAMPLE_INTERVAL);
+        assert_eq!(options.output, Some(PathBuf::from("result.json")));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_incomplete_args() {
+        assert!(HeadlessOptions::from_args(&args(&["--headless", "--fast"])).is_err());
+        assert!(HeadlessOptions::from_args(&args(&["--seed"])).is_err());
+        assert!(HeadlessOptions::from_args(&args(&["--seed", "abc"])).is_err());
+    }
+}
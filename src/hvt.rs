@@ -0,0 +1,423 @@
+use crate::ai::unit_ai_system;
+use crate::app_state::GameSet;
+use crate::capture::restraint_system;
+use crate::components::*;
+use crate::coordination::advanced_tactical_ai_system;
+use crate::localization::tr;
+use crate::systems::pathfinding_system;
+use crate::utils::{play_tactical_sound, RngStream, SimRng, SimulationSet};
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::{PI, TAU};
+
+// ==================== HIGH-VALUE TARGET PLUGIN ====================
+
+// The military used to know exactly where Ovidio was at all times. It now
+// works from a target package: a last known position fed by what its own
+// units see, blurred by cartel counter-intel operators near him and harder to
+// get while he's indoors. A fresh package gets a converging assault - the
+// force splits into groups that stage on different sides and go in together.
+// Once the package goes stale they fall back to a cordon around the last fix.
+
+const CONFIDENCE_DECAY: f32 = 0.03; // Per second without a sighting
+const STALE_SECONDS: f32 = 20.0;
+const STALE_CONFIDENCE: f32 = 0.25;
+const SHELTER_CONCEALMENT: f32 = 0.5; // Spotting range against him indoors
+const COUNTER_INTEL_RADIUS: f32 = 250.0;
+const COUNTER_INTEL_DEGRADATION: f32 = 0.25; // Per cartel counter-intel operator nearby
+const MAX_DEGRADATION: f32 = 0.75;
+const MAX_POSITION_ERROR: f32 = 150.0; // Sighting error at full degradation
+const REPLAN_DISTANCE: f32 = 120.0; // A fix this far from the objective means a new plan
+const MAX_ASSAULT_GROUPS: usize = 3;
+const MIN_GROUP_SIZE: usize = 2;
+const STAGING_RADIUS: f32 = 220.0;
+const STAGED_RADIUS: f32 = 60.0; // Group centre this close to its staging point is in place
+const STAGING_TIMEOUT: f32 = 30.0; // Go in anyway once staging takes this long
+const CORDON_RADIUS: f32 = 250.0;
+const WAYPOINT_SLACK: f32 = 10.0;
+
+// Type aliases to reduce complexity
+type HvtUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        &'static Transform,
+        &'static mut Movement,
+        Option<&'static mut HvtTasking>,
+    ),
+    Without<Captive>,
+>;
+
+pub struct HvtTargetingPlugin;
+
+impl Plugin for HvtTargetingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TargetPackage>().add_systems(
+            FixedUpdate,
+            (hvt_recon_system, hvt_assault_planning_system)
+                .chain()
+                .before(unit_ai_system)
+                .before(advanced_tactical_ai_system)
+                .before(restraint_system)
+                .before(pathfinding_system)
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct TargetPackage {
+    pub last_known: Option<Vec3>,
+    pub confidence: f32, // 0..1, how much the last fix is worth
+    pub age: f32,        // Seconds since the last sighting
+    pub posture: HvtPosture,
+}
+
+impl TargetPackage {
+    pub fn is_stale(&self) -> bool {
+        self.age > STALE_SECONDS || self.confidence < STALE_CONFIDENCE
+    }
+
+    // Where the military thinks he is, while that's still worth acting on
+    pub fn fresh_position(&self) -> Option<Vec3> {
+        self.last_known.filter(|_| !self.is_stale())
+    }
+
+    pub fn record_sighting(&mut self, position: Vec3, confidence: f32) {
+        self.last_known = Some(position);
+        self.confidence = confidence;
+        self.age = 0.0;
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum HvtPosture {
+    #[default]
+    Searching, // No package yet
+    Assault {
+        objective: Vec3,
+        approaches: Vec<f32>, // Bearing each group comes in on
+        staging_elapsed: f32,
+        converging: bool,
+    },
+    Cordon {
+        center: Vec3,
+    },
+}
+
+// ==================== TARGET PACKAGE ====================
+
+pub fn hvt_recon_system(
+    time: Res<Time>,
+    mut package: ResMut<TargetPackage>,
+    mut sim_rng: ResMut<SimRng>,
+    target_query: Query<(&Unit, &Transform, &ProtectedPrincipal)>,
+    military_query: Query<(&Unit, &Transform), Without<ProtectedPrincipal>>,
+    operator_query: Query<(&Transform, &IntelOperator)>,
+) {
+    let dt = time.delta_seconds();
+    package.age += dt;
+    package.confidence = (package.confidence - CONFIDENCE_DECAY * dt).max(0.0);
+
+    let Some((_, transform, principal)) = target_query.iter().find(|(unit, ..)| unit.health > 0.0)
+    else {
+        return;
+    };
+    let position = transform.translation;
+    let concealment = if principal.shelter.is_some() {
+        SHELTER_CONCEALMENT
+    } else {
+        1.0
+    };
+
+    // Best look any military unit gets at him this tick
+    let Some(quality) = military_query
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Military && unit.health > 0.0)
+        .filter_map(|(unit, t)| {
+            sighting_quality(t.translation.distance(position), unit.vision * concealment)
+        })
+        .max_by(f32::total_cmp)
+    else {
+        return;
+    };
+
+    let operators = operator_query
+        .iter()
+        .filter(|(t, operator)| {
+            operator.intel_type == IntelType::CounterIntel
+                && t.translation.distance(position) <= COUNTER_INTEL_RADIUS
+        })
+        .count();
+    let degradation = counter_intel_degradation(operators);
+    let rng = sim_rng.stream(RngStream::Ai);
+    let error = Vec3::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0), 0.0)
+        * degradation
+        * MAX_POSITION_ERROR;
+    package.record_sighting(position + error, quality * (1.0 - degradation));
+}
+
+// A clear look up close is worth twice one at the edge of sight
+fn sighting_quality(distance: f32, range: f32) -> Option<f32> {
+    (range > 0.0 && distance <= range).then(|| 1.0 - 0.5 * distance / range)
+}
+
+pub fn counter_intel_degradation(operators: usize) -> f32 {
+    (operators as f32 * COUNTER_INTEL_DEGRADATION).min(MAX_DEGRADATION)
+}
+
+// ==================== ASSAULT PLANNING ====================
+
+pub fn hvt_assault_planning_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut package: ResMut<TargetPackage>,
+    mut military_query: HvtUnitQuery,
+    captive_query: Query<&Captive>,
+) {
+    // Settle the posture first; a new one means every unit gets a new place
+    let regroup = match (package.fresh_position(), package.last_known) {
+        (Some(fix), _) => match &mut package.posture {
+            HvtPosture::Assault { objective, .. } if objective.distance(fix) <= REPLAN_DISTANCE => {
+                *objective = fix;
+                false
+            }
+            posture => {
+                if !matches!(posture, HvtPosture::Assault { .. }) {
+                    play_tactical_sound("radio", &tr("radio.hvt_assault"));
+                }
+                *posture = HvtPosture::Assault {
+                    objective: fix,
+                    approaches: Vec::new(),
+                    staging_elapsed: 0.0,
+                    converging: false,
+                };
+                true
+            }
+        },
+        (None, Some(_)) if matches!(package.posture, HvtPosture::Cordon { .. }) => false,
+        (None, Some(center)) => {
+            play_tactical_sound("radio", &tr("radio.hvt_cordon"));
+            package.posture = HvtPosture::Cordon { center };
+            true
+        }
+        (None, None) => return,
+    };
+
+    // Escorts answer to the capture sequence
+    let escorts: Vec<Entity> = captive_query.iter().map(|captive| captive.escort).collect();
+    let mut units: Vec<(Entity, Vec3)> = military_query
+        .iter()
+        .filter(|(entity, unit, ..)| {
+            unit.faction == Faction::Military && unit.health > 0.0 && !escorts.contains(entity)
+        })
+        .map(|(entity, _, t, ..)| (entity, t.translation))
+        .collect();
+    units.sort_by_key(|(entity, _)| *entity);
+    let positions: Vec<Vec3> = units.iter().map(|(_, position)| *position).collect();
+
+    // Group and waypoint for every unit, in the same order as `units`
+    let waypoints: Vec<(usize, Vec3)> = match &mut package.posture {
+        HvtPosture::Assault {
+            objective,
+            approaches,
+            staging_elapsed,
+            converging,
+        } => {
+            let objective = *objective;
+            let groups: Vec<usize> = if regroup || approaches.is_empty() {
+                let (planned, groups) = plan_assault_groups(objective, &positions);
+                *approaches = planned;
+                *staging_elapsed = 0.0;
+                *converging = false;
+                groups
+            } else {
+                // Keep standing assignments; reinforcements join the nearest group
+                units
+                    .iter()
+                    .map(|(entity, position)| {
+                        military_query
+                            .get(*entity)
+                            .ok()
+                            .and_then(|(.., tasking)| tasking.map(|tasking| tasking.group))
+                            .filter(|&group| group < approaches.len())
+                            .unwrap_or_else(|| {
+                                nearest_approach(approaches, bearing(objective, *position))
+                            })
+                    })
+                    .collect()
+            };
+
+            // Go in together once every group is in place, or when staging drags on
+            if !*converging {
+                *staging_elapsed += time.delta_seconds();
+                let all_staged = approaches.iter().enumerate().all(|(group, &approach)| {
+                    let members: Vec<Vec3> = groups
+                        .iter()
+                        .zip(&positions)
+                        .filter(|(&g, _)| g == group)
+                        .map(|(_, &position)| position)
+                        .collect();
+                    members.is_empty()
+                        || (members.iter().sum::<Vec3>() / members.len() as f32)
+                            .distance(at_bearing(objective, approach, STAGING_RADIUS))
+                            <= STAGED_RADIUS
+                });
+                if all_staged || *staging_elapsed >= STAGING_TIMEOUT {
+                    *converging = true;
+                    play_tactical_sound("radio", &tr("radio.hvt_converge"));
+                }
+            }
+
+            groups
+                .iter()
+                .zip(&units)
+                .map(|(&group, (entity, _))| {
+                    let approach = approaches.get(group).copied().unwrap_or_default();
+                    let standoff = if !*converging {
+                        STAGING_RADIUS
+                    } else {
+                        // Special forces go in for the grab; everyone else stops in range
+                        match military_query.get(*entity) {
+                            Ok((_, unit, ..)) if unit.unit_type != UnitType::SpecialForces => {
+                                unit.range * 0.8
+                            }
+                            _ => 0.0,
+                        }
+                    };
+                    (group, at_bearing(objective, approach, standoff))
+                })
+                .collect()
+        }
+        HvtPosture::Cordon { center } => (0..units.len())
+            .map(|slot| {
+                let angle = TAU * slot as f32 / units.len() as f32;
+                (0, at_bearing(*center, angle, CORDON_RADIUS))
+            })
+            .collect(),
+        HvtPosture::Searching => return,
+    };
+
+    for ((entity, _), (group, waypoint)) in units.into_iter().zip(waypoints) {
+        let Ok((_, _, transform, mut movement, tasking)) = military_query.get_mut(entity) else {
+            continue;
+        };
+        match tasking {
+            Some(mut tasking) => {
+                tasking.group = group;
+                tasking.waypoint = waypoint;
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(HvtTasking { group, waypoint });
+            }
+        }
+        movement.target_position =
+            (transform.translation.distance(waypoint) > WAYPOINT_SLACK).then_some(waypoint);
+    }
+}
+
+/// Splits the force into up to three groups that come at `objective` from
+/// evenly spaced sides, centred on the side most of it is already on. Units
+/// are dealt out in order around the target so nobody crosses the whole map
+/// to reach their group. Returns each group's approach bearing and each
+/// unit's group.
+pub fn plan_assault_groups(objective: Vec3, positions: &[Vec3]) -> (Vec<f32>, Vec<usize>) {
+    if positions.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+    let count = (positions.len() / MIN_GROUP_SIZE).clamp(1, MAX_ASSAULT_GROUPS);
+    let bearings: Vec<f32> = positions
+        .iter()
+        .map(|&position| bearing(objective, position))
+        .collect();
+
+    let (sin, cos) = bearings
+        .iter()
+        .fold((0.0, 0.0), |(sin, cos), b| (sin + b.sin(), cos + b.cos()));
+    let base = if sin.abs() + cos.abs() < 1e-3 {
+        bearings[0]
+    } else {
+        sin.atan2(cos)
+    };
+    let approaches: Vec<f32> = (0..count)
+        .map(|i| base + (i as f32 - (count - 1) as f32 / 2.0) * TAU / count as f32)
+        .collect();
+
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by(|&a, &b| {
+        wrap_angle(bearings[a] - base).total_cmp(&wrap_angle(bearings[b] - base))
+    });
+    let mut groups = vec![0; positions.len()];
+    for (rank, &unit) in order.iter().enumerate() {
+        groups[unit] = rank * count / positions.len();
+    }
+    (approaches, groups)
+}
+
+fn nearest_approach(approaches: &[f32], bearing: f32) -> usize {
+    (0..approaches.len())
+        .min_by(|&a, &b| {
+            wrap_angle(approaches[a] - bearing)
+                .abs()
+                .total_cmp(&wrap_angle(approaches[b] - bearing).abs())
+        })
+        .unwrap_or(0)
+}
+
+fn bearing(from: Vec3, to: Vec3) -> f32 {
+    let offset = to - from;
+    offset.y.atan2(offset.x)
+}
+
+fn at_bearing(center: Vec3, angle: f32, distance: f32) -> Vec3 {
+    center + Vec3::new(angle.cos(), angle.sin(), 0.0) * distance
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_goes_stale_and_counter_intel_blurs_it() {
+        let mut package = TargetPackage::default();
+        assert_eq!(package.fresh_position(), None);
+
+        package.record_sighting(Vec3::new(10.0, 0.0, 0.0), 0.9);
+        assert_eq!(package.fresh_position(), Some(Vec3::new(10.0, 0.0, 0.0)));
+        package.age = STALE_SECONDS + 1.0;
+        assert_eq!(package.fresh_position(), None);
+        assert!(package.last_known.is_some()); // Still good enough for a cordon
+
+        assert_eq!(counter_intel_degradation(0), 0.0);
+        assert_eq!(counter_intel_degradation(10), MAX_DEGRADATION);
+        assert!(sighting_quality(50.0, 100.0).unwrap() > sighting_quality(90.0, 100.0).unwrap());
+        assert_eq!(sighting_quality(150.0, 100.0), None);
+    }
+
+    #[test]
+    fn test_assault_groups_converge_from_different_sides() {
+        // Six units strung out east of the target, north to south
+        let positions: Vec<Vec3> = (0..6)
+            .map(|i| Vec3::new(300.0, 150.0 - i as f32 * 60.0, 0.0))
+            .collect();
+        let (approaches, groups) = plan_assault_groups(Vec3::ZERO, &positions);
+
+        assert_eq!(approaches.len(), 3);
+        // The middle group comes straight in from the east, the others a
+        // third of the way round either side
+        assert!(wrap_angle(approaches[1]).abs() < 0.2);
+        assert!((wrap_angle(approaches[2] - approaches[1]) - TAU / 3.0).abs() < 1e-4);
+        // Southern units take the clockwise group, northern the other
+        assert_eq!(groups, vec![2, 2, 1, 1, 0, 0]);
+        assert_eq!(nearest_approach(&approaches, 0.0), 1);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{BalanceConfig, IndirectFireBalance};
+use crate::damage::{damage_system, DamageEvent};
+use crate::economy::{charge, CartelTreasury};
+use crate::localization::{tr, tr_args};
+use crate::noise::queue_explosion_noise;
+use crate::political_system::PoliticalState;
+use crate::resources::AiDirector;
+use crate::rules_of_engagement::{civilian_positions, RoeLimits};
+use crate::upgrades::{MissionUpgrade, UpgradeWorkshop};
+use crate::utils::{
+    iso_to_world, play_tactical_sound, play_tactical_sound_at_position, queue_camera_shake,
+    queue_decal, spawn_explosion_particles, DecalKind, SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== INDIRECT FIRE PLUGIN ====================
+
+// Mortars give both sides a way to make ground dangerous from out of sight.
+// A salvo's rounds land one after another a few seconds after the order,
+// scattered around the aim point, and each landing spot is marked on the
+// ground as soon as it's fired, so there's time to get out from under it.
+// A whistle gives the last warning. Rounds hurt anyone caught in the blast,
+// whichever side, blow apart traps and wear down checkpoints. The army's
+// mortars open up on the thickest knot of cartel fighters once the
+// director's intensity runs high; the cartel's improvised mortars (an
+// upgrade) fire at the cursor (Semicolon) and scatter wider. Markers are
+// cleared as each mission starts.
+
+const MARKER_Z: f32 = 0.4;
+const MARKER_COLOR: Color = Color::rgba(1.0, 0.15, 0.1, 0.5);
+const MIN_MARKER_ALPHA: f32 = 0.1;
+const GOLDEN_ANGLE: f32 = 2.399_963; // Spreads a salvo evenly without lining rounds up
+
+pub struct IndirectFirePlugin;
+
+impl Plugin for IndirectFirePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MortarBattery>()
+            .add_systems(OnEnter(AppState::InGame), indirect_fire_reset_system)
+            .add_systems(
+                FixedUpdate,
+                (military_mortar_system, incoming_round_system)
+                    .chain()
+                    .before(damage_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, round_marker_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct MortarBattery {
+    pub military_cooldown: f32,
+    pub cartel_cooldown: f32,
+}
+
+/// Where each round of a salvo lands relative to the aim point: spread
+/// evenly over a circle of radius `scatter`.
+pub fn scatter_pattern(rounds: usize, scatter: f32) -> Vec<Vec2> {
+    (0..rounds)
+        .map(|i| {
+            let reach = scatter * ((i as f32 + 0.5) / rounds as f32).sqrt();
+            Vec2::from_angle(i as f32 * GOLDEN_ANGLE) * reach
+        })
+        .collect()
+}
+
+/// Share of a round's full damage done `distance` from where it lands.
+pub fn blast_share(distance: f32, radius: f32) -> f32 {
+    if distance > radius {
+        0.0
+    } else {
+        1.0 - 0.5 * distance / radius.max(f32::EPSILON)
+    }
+}
+
+/// The aim point for the army's mortars: the cartel fighter with the most
+/// others close around it.
+pub fn densest_group(positions: &[Vec2], radius: f32) -> Option<Vec2> {
+    let crowd = |position: &Vec2| {
+        positions
+            .iter()
+            .filter(|other| other.distance(*position) <= radius)
+            .count()
+    };
+    positions
+        .iter()
+        .copied()
+        .rev()
+        .max_by_key(|position| crowd(position))
+}
+
+/// Puts a salvo in the air at `target`, each round marked where it'll land.
+pub fn fire_salvo(
+    commands: &mut Commands,
+    faction: Faction,
+    target: Vec2,
+    scatter: f32,
+    balance: &IndirectFireBalance,
+) {
+    for (i, offset) in scatter_pattern(balance.rounds, scatter)
+        .into_iter()
+        .enumerate()
+    {
+        let impact = target + offset;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: MARKER_COLOR.with_a(MIN_MARKER_ALPHA),
+                    custom_size: Some(Vec2::splat(balance.blast_radius * 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(impact.extend(MARKER_Z)),
+                ..default()
+            },
+            IncomingRound {
+                faction: faction.clone(),
+                impact,
+                remaining: balance.flight_seconds + i as f32 * balance.round_interval,
+                whistled: false,
+            },
+        ));
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn indirect_fire_reset_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut battery: ResMut<MortarBattery>,
+    round_query: Query<Entity, With<IncomingRound>>,
+) {
+    for entity in round_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    *battery = MortarBattery {
+        military_cooldown: balance.indirect_fire.military_interval,
+        cartel_cooldown: 0.0,
+    };
+}
+
+// Semicolon: with the improvised mortars bought, a salvo on the cursor
+#[allow(clippy::too_many_arguments)]
+pub fn mortar_order_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    workshop: Res<UpgradeWorkshop>,
+    mut battery: ResMut<MortarBattery>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+) {
+    if !input.just_pressed(KeyCode::Semicolon) {
+        return;
+    }
+    let balance = &balance.indirect_fire;
+    if !workshop.owns(MissionUpgrade::Mortars) {
+        play_tactical_sound("radio", &tr("radio.mortars_locked"));
+        return;
+    }
+    if battery.cartel_cooldown > 0.0 {
+        play_tactical_sound("radio", &tr("radio.mortars_not_ready"));
+        return;
+    }
+    let Some(cursor) = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+    else {
+        return;
+    };
+    if !charge(treasury.as_deref_mut(), balance.cartel_cost) {
+        return;
+    }
+
+    fire_salvo(
+        &mut commands,
+        Faction::Cartel,
+        cursor,
+        balance.cartel_scatter,
+        balance,
+    );
+    battery.cartel_cooldown = balance.cartel_cooldown;
+    play_tactical_sound(
+        "radio",
+        &tr_args("radio.mortars_away", &[("rounds", &balance.rounds)]),
+    );
+}
+
+// The army's mortar line fires on the cartel while the director pushes hard,
+// and under Limited rules only where no civilians are close
+pub fn military_mortar_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    director: Res<AiDirector>,
+    political_state: Res<PoliticalState>,
+    mut battery: ResMut<MortarBattery>,
+    unit_query: Query<(&Unit, &Transform)>,
+) {
+    let roe = RoeLimits::of(&political_state.government_response_level, &balance.roe);
+    let balance = &balance.indirect_fire;
+    let dt = time.delta_seconds();
+    battery.cartel_cooldown = (battery.cartel_cooldown - dt).max(0.0);
+    battery.military_cooldown -= dt;
+    if battery.military_cooldown > 0.0 || director.intensity_level < balance.military_min_intensity
+    {
+        return;
+    }
+
+    let civilians = civilian_positions(unit_query.iter());
+    let cartel: Vec<Vec2> = unit_query
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(_, transform)| transform.translation.truncate())
+        .filter(|position| roe.clear_to_fire(*position, &civilians))
+        .collect();
+    let Some(target) = densest_group(&cartel, balance.blast_radius * 2.0) else {
+        return;
+    };
+    fire_salvo(
+        &mut commands,
+        Faction::Military,
+        target,
+        balance.military_scatter,
+        balance,
+    );
+    battery.military_cooldown = balance.military_interval;
+    play_tactical_sound("radio", &tr("radio.mortars_incoming"));
+}
+
+// Rounds whistle in and land. Helicopters are above it all.
+#[allow(clippy::too_many_arguments)]
+pub fn incoming_round_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    mut round_query: Query<(Entity, &mut IncomingRound)>,
+    unit_query: Query<(Entity, &Unit, &Transform)>,
+    mut checkpoint_query: Query<(&mut Checkpoint, &Transform)>,
+    trap_query: Query<(Entity, &Transform), With<Trap>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let balance = &balance.indirect_fire;
+    let dt = time.delta_seconds();
+
+    for (round_entity, mut round) in round_query.iter_mut() {
+        round.remaining -= dt;
+        let impact = round.impact.extend(0.0);
+        if !round.whistled && round.remaining <= balance.whistle_seconds {
+            round.whistled = true;
+            play_tactical_sound_at_position("incoming", "Incoming round", impact);
+        }
+        if round.remaining > 0.0 {
+            continue;
+        }
+        commands.entity(round_entity).despawn();
+
+        let share = |position: Vec3| {
+            blast_share(
+                position.truncate().distance(round.impact),
+                balance.blast_radius,
+            )
+        };
+        for (entity, unit, transform) in unit_query.iter() {
+            let share = share(transform.translation);
+            if unit.health > 0.0 && share > 0.0 && unit.unit_type != UnitType::Helicopter {
+                damage_events.send(DamageEvent {
+                    attacker: None,
+                    target: entity,
+                    amount: balance.blast_damage * share,
+                    weapon: None,
+                });
+            }
+        }
+        for (mut checkpoint, transform) in checkpoint_query.iter_mut() {
+            checkpoint.health -= balance.structure_damage * share(transform.translation);
+        }
+        for (entity, transform) in trap_query.iter() {
+            if share(transform.translation) > 0.0 {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+
+        spawn_explosion_particles(&mut commands, iso_to_world(impact), 60.0);
+        queue_decal(&mut commands, DecalKind::Scorch, impact, 1.2);
+        queue_camera_shake(&mut commands, impact, 0.4);
+        queue_explosion_noise(&mut commands, impact, round.faction.clone());
+        play_tactical_sound_at_position("explosion", "Mortar round impact", impact);
+    }
+}
+
+// Markers darken as their round comes down
+pub fn round_marker_system(
+    balance: Res<BalanceConfig>,
+    mut round_query: Query<(&IncomingRound, &mut Sprite), Changed<IncomingRound>>,
+) {
+    let balance = &balance.indirect_fire;
+    for (round, mut sprite) in round_query.iter_mut() {
+        let closing = 1.0 - (round.remaining / balance.flight_seconds.max(1.0)).clamp(0.0, 1.0);
+        sprite
+            .color
+            .set_a(MIN_MARKER_ALPHA + (MARKER_COLOR.a() - MIN_MARKER_ALPHA) * closing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_salvos_spread_over_the_scatter_circle() {
+        let pattern = scatter_pattern(6, 100.0);
+        assert_eq!(pattern.len(), 6);
+        assert!(pattern.iter().all(|offset| offset.length() <= 100.0));
+        // No two rounds on the same spot
+        for (i, a) in pattern.iter().enumerate() {
+            assert!(pattern[i + 1..].iter().all(|b| a.distance(*b) > 10.0));
+        }
+        assert!(scatter_pattern(0, 100.0).is_empty());
+    }
+
+    #[test]
+    fn test_blasts_fall_off_toward_the_edge() {
+        assert_eq!(blast_share(0.0, 70.0), 1.0);
+        assert_eq!(blast_share(70.0, 70.0), 0.5);
+        assert_eq!(blast_share(71.0, 70.0), 0.0);
+        assert!(blast_share(20.0, 70.0) > blast_share(40.0, 70.0));
+    }
+
+    #[test]
+    fn test_army_mortars_aim_at_the_thickest_group() {
+        let positions = [
+            Vec2::new(500.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 0.0),
+            Vec2::new(0.0, 40.0),
+        ];
+        assert_eq!(densest_group(&positions, 100.0), Some(Vec2::ZERO));
+        assert_eq!(densest_group(&[], 100.0), None);
+    }
+}
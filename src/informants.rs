@@ -0,0 +1,297 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::{BalanceConfig, InformantBalance};
+use crate::hvt::TargetPackage;
+use crate::intel_recruitment::IntelRecruitment;
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::prisoners::holding_point;
+use crate::resources::IntelSystem;
+use crate::utils::{play_tactical_sound, RngStream, SimRng, SimulationSet};
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// ==================== INFORMANT VETTING PLUGIN ====================
+
+// Not every informant is what they seem. Each has a hidden reliability, and
+// the least reliable were planted by the army's counter-intel: most of their
+// tips point the cartel at streets where nothing is happening. With the intel
+// network panel (U) open, N vets the informant nearest the cursor for a few
+// intel points and shows how far the source can be trusted from then on. A
+// vetted double agent stops reporting and runs for the army's lines; cartel
+// fighters who catch him with no soldier close by make him talk, and if he
+// gets away the army learns where Ovidio is.
+
+const DOUBLE_AGENT_COLOR: Color = Color::rgb(0.9, 0.25, 0.2);
+const PLANTED_OFFSET: std::ops::Range<f32> = 400.0..800.0; // How far from the truth a planted tip points
+
+pub struct InformantVettingPlugin;
+
+impl Plugin for InformantVettingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (informant_enlist_system, double_agent_flight_system)
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+/// What the network knows, and doesn't, about an informant.
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+pub struct InformantSource {
+    pub reliability: f32, // Hidden until vetted
+    pub vetted: bool,
+    pub double_agent: bool,
+}
+
+impl InformantSource {
+    pub fn roll(rng: &mut StdRng, balance: &InformantBalance) -> Self {
+        let reliability = rng.gen_range(balance.min_reliability..=1.0);
+        Self {
+            reliability,
+            vetted: false,
+            double_agent: reliability < balance.double_agent_below,
+        }
+    }
+}
+
+/// A double agent found out, running for the army's lines.
+#[derive(Component, Clone, Debug)]
+pub struct DoubleAgent {
+    pub escape: Vec3,
+}
+
+/// Moves a tip `offset` away from where it really points.
+pub fn plant_tip(tip: &mut InformantTip, offset: Vec3) {
+    tip.location += offset;
+    match &mut tip.tip_type {
+        TipType::PlannedAttack(position, _)
+        | TipType::WeakPoint(position)
+        | TipType::CommandPost(position) => *position += offset,
+        TipType::SupplyRoute(from, to) => {
+            *from += offset;
+            *to += offset;
+        }
+        TipType::EnemyPosition(_, _) => {}
+    }
+    tip.planted = true;
+}
+
+/// A random offset for a planted tip.
+pub fn planted_offset(rng: &mut StdRng) -> Vec3 {
+    let direction = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+    (direction * rng.gen_range(PLANTED_OFFSET)).extend(0.0)
+}
+
+/// Localization key and color for how far a tip's source can be trusted.
+pub fn source_label(source: Option<&InformantSource>) -> (String, Color) {
+    match source {
+        Some(source) if source.vetted && source.double_agent => {
+            (tr("hud.source_double_agent"), DOUBLE_AGENT_COLOR)
+        }
+        Some(source) if source.vetted => (
+            tr_args(
+                "hud.source_reliability",
+                &[("percent", &((source.reliability * 100.0).round() as u32))],
+            ),
+            Color::GREEN,
+        ),
+        _ => (tr("hud.source_unvetted"), Color::GRAY),
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+// Every informant gets a hidden reliability as they join the network
+pub fn informant_enlist_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut sim_rng: ResMut<SimRng>,
+    operator_query: Query<(Entity, &IntelOperator), Without<InformantSource>>,
+) {
+    let rng = sim_rng.stream(RngStream::Intel);
+    for (entity, operator) in operator_query.iter() {
+        if operator.intel_type == IntelType::Informant {
+            commands
+                .entity(entity)
+                .insert(InformantSource::roll(rng, &balance.informants));
+        }
+    }
+}
+
+// N with the network panel open vets the informant nearest the cursor
+#[allow(clippy::too_many_arguments)]
+pub fn vet_source_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    recruitment: Option<Res<IntelRecruitment>>,
+    mut intel: ResMut<IntelSystem>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut source_query: Query<(Entity, &Transform, &mut InformantSource, &mut Sprite)>,
+) {
+    if !input.just_pressed(KeyCode::N) || !recruitment.is_some_and(|network| network.menu_open) {
+        return;
+    }
+    let balance = &balance.informants;
+    let Some(cursor) = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+    else {
+        return;
+    };
+    let Some((entity, transform, mut source, mut sprite)) = source_query
+        .iter_mut()
+        .filter(|(_, transform, source, _)| {
+            !source.vetted
+                && transform.translation.truncate().distance(cursor) <= balance.vet_radius
+        })
+        .min_by(|a, b| {
+            let distance =
+                |transform: &Transform| transform.translation.truncate().distance(cursor);
+            distance(a.1).total_cmp(&distance(b.1))
+        })
+    else {
+        return;
+    };
+    if !intel.spend_intel(balance.vet_cost) {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.intel_points_short", &[("points", &balance.vet_cost)]),
+        );
+        return;
+    }
+
+    source.vetted = true;
+    play_tactical_sound(
+        "radio",
+        &tr_args(
+            "radio.informant_vetted",
+            &[("percent", &((source.reliability * 100.0).round() as u32))],
+        ),
+    );
+    if !source.double_agent {
+        return;
+    }
+
+    // Found out: his tips go in the bin and he runs
+    let position = transform.translation;
+    intel
+        .global_intel_network
+        .informant_reports
+        .retain(|tip| tip.source != Some(entity));
+    sprite.color = DOUBLE_AGENT_COLOR;
+    commands.entity(entity).insert(DoubleAgent {
+        escape: holding_point(
+            &city_map.spawn_positions(&Faction::Military),
+            position,
+            &Faction::Military,
+        ),
+    });
+    play_tactical_sound("radio", &tr("radio.double_agent_found"));
+}
+
+// A double agent on the run is taken by the fighters or gets away to the army
+pub fn double_agent_flight_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    mut intel: ResMut<IntelSystem>,
+    mut target_package: ResMut<TargetPackage>,
+    mut agent_query: Query<(Entity, &mut Transform, &DoubleAgent), Without<Unit>>,
+    unit_query: Query<(&Unit, &Transform), Without<DoubleAgent>>,
+) {
+    let balance = &balance.informants;
+    for (entity, mut transform, agent) in agent_query.iter_mut() {
+        let position = transform.translation;
+        let near = |faction: Faction, radius: f32| {
+            unit_query.iter().any(|(unit, unit_transform)| {
+                unit.faction == faction
+                    && unit.health > 0.0
+                    && unit_transform.translation.distance(position) <= radius
+            })
+        };
+
+        if near(Faction::Cartel, balance.capture_radius)
+            && !near(Faction::Military, balance.contest_radius)
+        {
+            intel.reports_gathered += balance.capture_intel;
+            commands.entity(entity).despawn_recursive();
+            play_tactical_sound(
+                "radio",
+                &tr_args(
+                    "radio.double_agent_captured",
+                    &[("reports", &balance.capture_intel)],
+                ),
+            );
+            continue;
+        }
+
+        if position.distance(agent.escape) <= balance.escape_radius {
+            if let Some((_, ovidio)) = unit_query
+                .iter()
+                .find(|(unit, _)| unit.unit_type == UnitType::Ovidio && unit.health > 0.0)
+            {
+                target_package.record_sighting(ovidio.translation, balance.escape_confidence);
+            }
+            commands.entity(entity).despawn_recursive();
+            play_tactical_sound("radio", &tr("radio.double_agent_escaped"));
+            continue;
+        }
+
+        // He has no Unit, so nothing else moves him
+        let step = (agent.escape - position).normalize_or_zero()
+            * balance.flee_speed
+            * time.delta_seconds();
+        transform.translation += step.clamp_length_max(position.distance(agent.escape));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_unreliable_sources_are_double_agents_and_their_tips_point_elsewhere() {
+        let balance = InformantBalance::default();
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let source = InformantSource::roll(&mut rng, &balance);
+            assert!((balance.min_reliability..=1.0).contains(&source.reliability));
+            assert_eq!(
+                source.double_agent,
+                source.reliability < balance.double_agent_below
+            );
+            assert!(!source.vetted);
+        }
+
+        let command_post = Vec3::new(100.0, 50.0, 0.0);
+        let mut tip = InformantTip {
+            tip_type: TipType::CommandPost(command_post),
+            location: command_post,
+            confidence: 0.8,
+            time_received: 0.0,
+            urgency: TipUrgency::High,
+            source: None,
+            planted: false,
+        };
+        let offset = planted_offset(&mut rng);
+        assert!(offset.length() > 399.0 && offset.length() < 801.0);
+        plant_tip(&mut tip, offset);
+        assert!(tip.planted);
+        assert_eq!(tip.location, command_post + offset);
+        assert_eq!(tip.tip_type, TipType::CommandPost(command_post + offset));
+    }
+}
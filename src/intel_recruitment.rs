@@ -0,0 +1,407 @@
+use crate::app_state::GameSet;
+use crate::commander::CommanderPowers;
+use crate::components::*;
+use crate::config::{BalanceConfig, IntelRecruitmentBalance};
+use crate::economy::{charge, CartelTreasury};
+use crate::informants::InformantSource;
+use crate::jamming::Jammer;
+use crate::localization::{tr, tr_args};
+use crate::map::{CityMap, TileKind};
+use crate::reinforcements::ReinforcementDesk;
+use crate::resources::{GameAssets, IntelSystem};
+use crate::spawners::spawn_intel_operator;
+use crate::ui::UiTheme;
+use crate::upgrades::UpgradeWorkshop;
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, iso_to_world, play_tactical_sound,
+    update_dashboard_panel, SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== INTEL RECRUITMENT PLUGIN ====================
+
+// The cartel's intel network grows by hand. U opens the network panel; while
+// it's open J/K/L put a lookout, a radio tech or an informant in the building
+// under the cursor. Lookouts and radio techs are paid for in cash, informants
+// in intel points, and every operator recruited this way draws upkeep from
+// the treasury's income. When the treasury runs dry the best-paid operator
+// walks off after a while. Operators the army's counter-intel finds are
+// compromised and lost (see intel_system.rs); the volunteers friendly
+// neighborhoods send, and the network a mission starts with, work for free.
+// The panel also lists the lookouts' bearings on hidden jammers, fixed with M
+// (see jamming.rs), and N vets an informant (see informants.rs).
+
+const RECRUIT_KEYS: [(KeyCode, IntelType); 3] = [
+    (KeyCode::J, IntelType::Reconnaissance),
+    (KeyCode::K, IntelType::RadioIntercept),
+    (KeyCode::L, IntelType::Informant),
+];
+
+pub struct IntelRecruitmentPlugin;
+
+impl Plugin for IntelRecruitmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IntelRecruitment>()
+            .add_systems(
+                FixedUpdate,
+                operator_upkeep_system
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, intel_network_panel_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct IntelRecruitment {
+    pub menu_open: bool,
+    pub unpaid: f32, // Seconds the treasury has been too broke to pay the operators
+}
+
+/// An operator the cartel recruited, and what it costs a minute to keep.
+#[derive(Component, Clone, Debug)]
+pub struct OperatorContract {
+    pub upkeep: f32,
+}
+
+/// Localization key under hud. for an operator's role.
+pub fn role_name(intel_type: &IntelType) -> &'static str {
+    match intel_type {
+        IntelType::Reconnaissance => "intel_lookout",
+        IntelType::RadioIntercept => "intel_radio_tech",
+        IntelType::Informant => "intel_informant",
+        IntelType::CounterIntel => "intel_counter",
+    }
+}
+
+/// Where an operator recruited at `cursor` sets up: the middle of the
+/// building under it. Anywhere else there's nowhere to hide one.
+pub fn operator_post(city_map: &CityMap, cursor: Vec2) -> Option<Vec2> {
+    let (col, row) = city_map.tile_at(cursor)?;
+    (city_map.kind(col, row) == TileKind::Building).then(|| city_map.tile_center(col, row))
+}
+
+/// The recruited operator to let go when the money runs out: the best paid.
+pub fn first_to_quit(contracts: &[(Entity, f32)]) -> Option<Entity> {
+    contracts
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| *entity)
+}
+
+fn cost_label(intel_type: &IntelType, balance: &IntelRecruitmentBalance) -> String {
+    match balance.intel_cost(intel_type) {
+        0 => format!("${}", balance.cash_cost(intel_type) as u32),
+        intel => tr_args("hud.intel_points_cost", &[("points", &intel)]),
+    }
+}
+
+// ==================== RECRUITMENT ====================
+
+// U opens and closes the network panel; while it's open the listed keys
+// recruit an operator into the building under the cursor
+#[allow(clippy::too_many_arguments)]
+pub fn intel_recruitment_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    game_assets: Res<GameAssets>,
+    mut recruitment: ResMut<IntelRecruitment>,
+    mut intel: ResMut<IntelSystem>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    desk: Option<ResMut<ReinforcementDesk>>,
+    workshop: Option<ResMut<UpgradeWorkshop>>,
+    powers: Option<ResMut<CommanderPowers>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    contract_query: Query<(), With<OperatorContract>>,
+) {
+    if input.just_pressed(KeyCode::U) {
+        recruitment.menu_open = !recruitment.menu_open;
+        // The recruitment, call-in, upgrade and commander menus share the J/K/L keys
+        if recruitment.menu_open {
+            if let Some(treasury) = treasury.as_deref_mut() {
+                treasury.panel_safehouse = None;
+            }
+            if let Some(mut desk) = desk {
+                desk.menu_open = false;
+            }
+            if let Some(mut workshop) = workshop {
+                workshop.panel_open = false;
+            }
+            if let Some(mut powers) = powers {
+                powers.menu_open = false;
+            }
+        }
+    }
+    if !recruitment.menu_open {
+        return;
+    }
+    let network = &balance.intel_recruitment;
+
+    for (key, intel_type) in RECRUIT_KEYS {
+        if !input.just_pressed(key) {
+            continue;
+        }
+        if contract_query.iter().count() >= network.max_operators {
+            play_tactical_sound("radio", &tr("radio.intel_network_full"));
+            continue;
+        }
+        let Some(post) = windows
+            .get_single()
+            .ok()
+            .and_then(|window| window.cursor_position())
+            .zip(camera_query.get_single().ok())
+            .and_then(|(cursor_pos, (camera, camera_transform))| {
+                camera.viewport_to_world_2d(camera_transform, cursor_pos)
+            })
+            .and_then(|cursor| operator_post(&city_map, cursor))
+        else {
+            play_tactical_sound("radio", &tr("radio.intel_needs_building"));
+            continue;
+        };
+
+        let intel_cost = network.intel_cost(&intel_type);
+        if intel.intel_points() < intel_cost {
+            play_tactical_sound(
+                "radio",
+                &tr_args("radio.intel_points_short", &[("points", &intel_cost)]),
+            );
+            continue;
+        }
+        if !charge(treasury.as_deref_mut(), network.cash_cost(&intel_type)) {
+            continue;
+        }
+        intel.spend_intel(intel_cost);
+
+        let operator = spawn_intel_operator(
+            &mut commands,
+            intel_type.clone(),
+            iso_to_world(post.extend(0.0)),
+            &game_assets,
+            &balance,
+        );
+        commands.entity(operator).insert(OperatorContract {
+            upkeep: network.upkeep(&intel_type),
+        });
+        // The panel's head count changes with it
+        recruitment.set_changed();
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.intel_recruited",
+                &[("role", &tr(&format!("hud.{}", role_name(&intel_type))))],
+            ),
+        );
+    }
+}
+
+// ==================== UPKEEP ====================
+
+// The operators' pay comes out of the treasury's income (see economy.rs);
+// with the treasury empty too long, the best-paid one walks off
+pub fn operator_upkeep_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    treasury: Option<Res<CartelTreasury>>,
+    mut recruitment: ResMut<IntelRecruitment>,
+    contract_query: Query<(Entity, &OperatorContract, &IntelOperator)>,
+) {
+    // Only a walkout counts as a change, so the panel isn't redrawn every tick
+    let unpaid = &mut recruitment.bypass_change_detection().unpaid;
+    let broke = treasury.is_some_and(|treasury| treasury.cash <= 0.0);
+    if !broke || contract_query.is_empty() {
+        *unpaid = 0.0;
+        return;
+    }
+    *unpaid += time.delta_seconds();
+    if *unpaid < balance.intel_recruitment.unpaid_grace {
+        return;
+    }
+
+    let contracts: Vec<(Entity, f32)> = contract_query
+        .iter()
+        .map(|(entity, contract, _)| (entity, contract.upkeep))
+        .collect();
+    let Some(quitter) = first_to_quit(&contracts) else {
+        return;
+    };
+    if let Ok((_, _, operator)) = contract_query.get(quitter) {
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.intel_operator_quit",
+                &[(
+                    "role",
+                    &tr(&format!("hud.{}", role_name(&operator.intel_type))),
+                )],
+            ),
+        );
+    }
+    commands.entity(quitter).despawn_recursive();
+    recruitment.unpaid = 0.0;
+}
+
+// ==================== NETWORK PANEL ====================
+
+#[derive(Component)]
+pub struct IntelNetworkUIPanel;
+
+#[allow(clippy::too_many_arguments)]
+pub fn intel_network_panel_system(
+    mut commands: Commands,
+    recruitment: Res<IntelRecruitment>,
+    balance: Res<BalanceConfig>,
+    intel: Res<IntelSystem>,
+    operator_query: Query<(&IntelOperator, Option<&OperatorContract>)>,
+    source_query: Query<&InformantSource>,
+    jammer_query: Query<Ref<Jammer>>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<IntelNetworkUIPanel>>,
+    theme: Res<UiTheme>,
+) {
+    let panel_exists = !panel_query.is_empty();
+    let bearings_changed = jammer_query.iter().any(|jammer| jammer.is_changed());
+    if panel_exists
+        && !recruitment.is_changed()
+        && !intel.is_changed()
+        && !bearings_changed
+        && !theme.is_changed()
+    {
+        return;
+    }
+    if !recruitment.menu_open && !panel_exists {
+        return;
+    }
+
+    let jamming = &balance.jamming;
+    let informants = &balance.informants;
+    let balance = &balance.intel_recruitment;
+    let mut lines = vec![
+        dashboard_line(tr("hud.intel_network_title"), 16.0, Color::GOLD),
+        dashboard_line(
+            tr_args("hud.intel_points", &[("points", &intel.intel_points())]),
+            11.0,
+            Color::CYAN,
+        ),
+    ];
+    for (key, intel_type) in RECRUIT_KEYS {
+        let working = operator_query
+            .iter()
+            .filter(|(operator, _)| operator.intel_type == intel_type)
+            .count();
+        lines.push(dashboard_line(
+            tr_args(
+                "hud.intel_recruit_line",
+                &[
+                    ("key", &format!("{:?}", key)),
+                    ("role", &tr(&format!("hud.{}", role_name(&intel_type)))),
+                    ("cost", &cost_label(&intel_type, balance)),
+                    ("upkeep", &(balance.upkeep(&intel_type) as u32)),
+                    ("count", &working),
+                ],
+            ),
+            11.0,
+            Color::WHITE,
+        ));
+    }
+    let upkeep: f32 = operator_query
+        .iter()
+        .filter_map(|(_, contract)| contract.map(|contract| contract.upkeep))
+        .sum();
+    lines.push(dashboard_line(
+        tr_args("hud.intel_upkeep", &[("upkeep", &(upkeep as u32))]),
+        10.0,
+        Color::ORANGE,
+    ));
+    lines.push(dashboard_line(
+        tr("hud.intel_recruit_hint"),
+        10.0,
+        Color::GRAY,
+    ));
+    if source_query.iter().any(|source| !source.vetted) {
+        lines.push(dashboard_line(
+            tr_args("hud.informant_vet_hint", &[("cost", &informants.vet_cost)]),
+            10.0,
+            Color::GRAY,
+        ));
+    }
+
+    // Lookouts' bearings on the jammers still hidden
+    let hidden: Vec<usize> = jammer_query
+        .iter()
+        .filter(|jammer| !jammer.located)
+        .map(|jammer| jammer.bearings.len())
+        .collect();
+    for bearings in &hidden {
+        lines.push(dashboard_line(
+            tr_args(
+                "hud.jammer_bearings",
+                &[("bearings", bearings), ("needed", &jamming.bearings_needed)],
+            ),
+            11.0,
+            Color::rgb(0.55, 0.75, 1.0),
+        ));
+    }
+    if !hidden.is_empty() {
+        lines.push(dashboard_line(
+            tr_args("hud.jammer_fix_hint", &[("cost", &jamming.fix_cost)]),
+            10.0,
+            Color::GRAY,
+        ));
+    }
+
+    let visibility = if recruitment.menu_open {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else {
+        let mut panel = create_dashboard_panel(
+            Style {
+                right: Val::Px(10.0),
+                bottom: Val::Px(220.0),
+                width: Val::Px(280.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.85),
+            lines,
+            &theme,
+        );
+        panel.0.visibility = visibility;
+        commands.spawn((panel, IntelNetworkUIPanel));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_the_best_paid_operator_quits_first() {
+        let contracts = [
+            (Entity::from_raw(1), 5.0),
+            (Entity::from_raw(2), 12.0),
+            (Entity::from_raw(3), 8.0),
+        ];
+        assert_eq!(first_to_quit(&contracts), Some(Entity::from_raw(2)));
+        assert_eq!(first_to_quit(&[]), None);
+    }
+
+    #[test]
+    fn test_informants_cost_intel_and_the_rest_cost_cash() {
+        let balance = IntelRecruitmentBalance::default();
+        assert_eq!(balance.cash_cost(&IntelType::Informant), 0.0);
+        assert!(balance.intel_cost(&IntelType::Informant) > 0);
+        for intel_type in [IntelType::Reconnaissance, IntelType::RadioIntercept] {
+            assert!(balance.cash_cost(&intel_type) > 0.0);
+            assert_eq!(balance.intel_cost(&intel_type), 0);
+        }
+    }
+}
@@ -1,6 +1,19 @@
+use crate::app_state::GameSet;
 use crate::components::*;
+use crate::config::BalanceConfig;
+use crate::informants::{plant_tip, planted_offset, source_label, DoubleAgent, InformantSource};
+use crate::intel_recruitment::role_name;
+use crate::jamming::{garble, jam_fields, jamming_at, JammerQuery};
+use crate::localization::{tr, tr_args};
+use crate::order_of_battle::{order_of_battle_lines, OrderOfBattle};
 use crate::resources::*;
+use crate::ui::UiTheme;
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, play_tactical_sound, spawn_pooled_text,
+    update_dashboard_panel, EntityPools, FloatingText, PoolKind, RngStream, SimRng, SimulationSet,
+};
 use bevy::prelude::*;
+use rand::rngs::StdRng;
 use rand::Rng;
 
 // ==================== INTEL SYSTEM SETUP ====================
@@ -9,18 +22,21 @@ pub struct IntelSystemPlugin;
 
 impl Plugin for IntelSystemPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<IntelSystem>().add_systems(
-            Update,
-            (
-                radio_intercept_system,
-                informant_network_system,
-                reconnaissance_system,
-                counter_intel_system,
-                intel_ui_system,
-                process_intel_reports,
+        app.init_resource::<IntelSystem>()
+            .init_resource::<EntityPools>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    radio_intercept_system,
+                    informant_network_system,
+                    reconnaissance_system,
+                    counter_intel_system,
+                    process_intel_reports,
+                )
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::InGame),
             )
-                .run_if(not_in_menu_phase),
-        );
+            .add_systems(Update, intel_ui_system.in_set(GameSet::InGame));
     }
 }
 
@@ -29,13 +45,17 @@ impl Plugin for IntelSystemPlugin {
 pub fn radio_intercept_system(
     time: Res<Time>,
     mut intel_system: ResMut<IntelSystem>,
-    mut intel_operators: Query<&mut IntelOperator>,
+    mut intel_operators: Query<(&Transform, &mut IntelOperator)>,
     military_units: Query<(&Transform, &Unit), (With<Unit>, Without<IntelOperator>)>,
+    jammer_query: JammerQuery,
+    mut sim_rng: ResMut<SimRng>,
 ) {
-    let mut rng = rand::thread_rng();
+    let _span = info_span!("radio_intercept_system").entered();
+    let rng = sim_rng.stream(RngStream::Intel);
+    let fields = jam_fields(&jammer_query);
 
     // Process radio intercept operators
-    for mut operator in intel_operators.iter_mut() {
+    for (operator_transform, mut operator) in intel_operators.iter_mut() {
         if operator.intel_type == IntelType::RadioIntercept {
             operator.intel_cooldown.tick(time.delta());
 
@@ -54,13 +74,30 @@ pub fn radio_intercept_system(
 
                 if intercept_roll < (intel_system.intercept_chance - jamming_penalty) {
                     // Generate realistic radio intercept
-                    if let Some(intercept) =
-                        generate_radio_intercept(&military_units, &mut rng, time.elapsed_seconds())
+                    if let Some(mut intercept) =
+                        generate_radio_intercept(&military_units, rng, time.elapsed_seconds())
                     {
+                        // Heard through a jammer's static, at either end
+                        let jammed = jamming_at(
+                            &fields,
+                            &Faction::Cartel,
+                            operator_transform.translation.truncate(),
+                        )
+                        .max(jamming_at(
+                            &fields,
+                            &Faction::Cartel,
+                            intercept.source_position.truncate(),
+                        ));
+                        if jammed > 0.0 {
+                            intercept.reliability *= 1.0 - jammed;
+                            intercept.content = garble(&intercept.content, jammed, rng);
+                        }
+
                         intel_system
                             .global_intel_network
                             .active_intercepts
                             .push(intercept);
+                        intel_system.reports_gathered += 1;
 
                         // Limit intercept history to prevent memory bloat
                         if intel_system.global_intel_network.active_intercepts.len() > 20 {
@@ -78,7 +115,7 @@ pub fn radio_intercept_system(
 
 fn generate_radio_intercept(
     military_units: &Query<(&Transform, &Unit), (With<Unit>, Without<IntelOperator>)>,
-    rng: &mut rand::rngs::ThreadRng,
+    rng: &mut StdRng,
     current_time: f32,
 ) -> Option<RadioIntercept> {
     if military_units.is_empty() {
@@ -159,13 +196,19 @@ fn format_radio_message(message_type: &RadioMessageType) -> String {
 
 pub fn informant_network_system(
     time: Res<Time>,
+    balance: Res<BalanceConfig>,
     mut intel_system: ResMut<IntelSystem>,
-    mut intel_operators: Query<&mut IntelOperator>,
+    mut intel_operators: Query<
+        (Entity, &mut IntelOperator, Option<&InformantSource>),
+        Without<DoubleAgent>,
+    >,
     military_units: Query<(&Transform, &Unit), With<Unit>>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
-    let mut rng = rand::thread_rng();
+    let _span = info_span!("informant_network_system").entered();
+    let rng = sim_rng.stream(RngStream::Intel);
 
-    for mut operator in intel_operators.iter_mut() {
+    for (entity, mut operator, source) in intel_operators.iter_mut() {
         if operator.intel_type == IntelType::Informant {
             operator.intel_cooldown.tick(time.delta());
 
@@ -177,13 +220,21 @@ pub fn informant_network_system(
                 // Generate informant tip
                 if rng.gen::<f32>() < 0.4 {
                     // 40% chance per check
-                    if let Some(tip) =
-                        generate_informant_tip(&military_units, &mut rng, time.elapsed_seconds())
+                    if let Some(mut tip) =
+                        generate_informant_tip(&military_units, rng, time.elapsed_seconds())
                     {
+                        tip.source = Some(entity);
+                        // A double agent passes on what counter-intel feeds him
+                        if source.is_some_and(|source| source.double_agent)
+                            && rng.gen::<f32>() < balance.informants.planted_chance
+                        {
+                            plant_tip(&mut tip, planted_offset(rng));
+                        }
                         intel_system
                             .global_intel_network
                             .informant_reports
                             .push(tip);
+                        intel_system.reports_gathered += 1;
 
                         // Limit tip history
                         if intel_system.global_intel_network.informant_reports.len() > 15 {
@@ -201,7 +252,7 @@ pub fn informant_network_system(
 
 fn generate_informant_tip(
     military_units: &Query<(&Transform, &Unit), With<Unit>>,
-    rng: &mut rand::rngs::ThreadRng,
+    rng: &mut StdRng,
     current_time: f32,
 ) -> Option<InformantTip> {
     if military_units.is_empty() {
@@ -235,6 +286,8 @@ fn generate_informant_tip(
         confidence: rng.gen_range(0.5..0.9),
         time_received: current_time,
         urgency,
+        source: None,
+        planted: false,
     })
 }
 
@@ -245,7 +298,11 @@ pub fn reconnaissance_system(
     mut intel_system: ResMut<IntelSystem>,
     mut intel_operators: Query<(&Transform, &mut IntelOperator)>,
     enemy_units: Query<(&Transform, &Unit), (With<Unit>, Without<IntelOperator>)>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
+    let _span = info_span!("reconnaissance_system").entered();
+    let rng = sim_rng.stream(RngStream::Intel);
+
     for (operator_transform, mut operator) in intel_operators.iter_mut() {
         if operator.intel_type == IntelType::Reconnaissance {
             operator.intel_cooldown.tick(time.delta());
@@ -279,7 +336,7 @@ pub fn reconnaissance_system(
                         area_scanned: operator_transform.translation,
                         scan_radius: operator.detection_range,
                         enemies_spotted,
-                        terrain_info: generate_terrain_intel(operator_transform.translation),
+                        terrain_info: generate_terrain_intel(operator_transform.translation, rng),
                         scan_time: time.elapsed_seconds(),
                     };
 
@@ -287,6 +344,7 @@ pub fn reconnaissance_system(
                         .global_intel_network
                         .reconnaissance_data
                         .push(recon_report);
+                    intel_system.reports_gathered += 1;
 
                     // Limit recon history
                     if intel_system.global_intel_network.reconnaissance_data.len() > 25 {
@@ -305,9 +363,7 @@ fn calculate_detection_confidence(distance: f32, max_range: f32) -> f32 {
     (1.0 - (distance / max_range)).max(0.3)
 }
 
-fn generate_terrain_intel(position: Vec3) -> TerrainIntel {
-    let mut rng = rand::thread_rng();
-
+fn generate_terrain_intel(position: Vec3, rng: &mut StdRng) -> TerrainIntel {
     TerrainIntel {
         cover_points: (0..rng.gen_range(2..6))
             .map(|_| {
@@ -340,16 +396,26 @@ fn generate_terrain_intel(position: Vec3) -> TerrainIntel {
 // ==================== COUNTER INTELLIGENCE SYSTEM ====================
 
 pub fn counter_intel_system(
+    mut commands: Commands,
     time: Res<Time>,
     mut intel_system: ResMut<IntelSystem>,
-    intel_operators: Query<(Entity, &Transform, &IntelOperator)>,
+    intel_operators: Query<(Entity, &Transform, &IntelOperator), Without<DoubleAgent>>,
     military_units: Query<(Entity, &Transform, &Unit), With<Unit>>,
+    mut sim_rng: ResMut<SimRng>,
 ) {
-    let mut rng = rand::thread_rng();
+    let _span = info_span!("counter_intel_system").entered();
+    let rng = sim_rng.stream(RngStream::Intel);
+    let mut compromised = Vec::new();
 
     // Military counter-intelligence tries to detect cartel intel operations
-    for (military_entity, military_transform, _military_unit) in military_units.iter() {
+    for (military_entity, military_transform, military_unit) in military_units.iter() {
+        if military_unit.faction != Faction::Military {
+            continue;
+        }
         for (intel_entity, intel_transform, intel_operator) in intel_operators.iter() {
+            if compromised.contains(&intel_entity) {
+                continue;
+            }
             let distance = military_transform
                 .translation
                 .distance(intel_transform.translation);
@@ -396,6 +462,20 @@ pub fn counter_intel_system(
                     intel_system.jamming_active = true;
                     intel_system.jamming_strength = rng.gen_range(0.3..0.8);
                 }
+
+                // A blown operator is arrested or runs, and the network loses them
+                compromised.push(intel_entity);
+                commands.entity(intel_entity).despawn_recursive();
+                play_tactical_sound(
+                    "radio",
+                    &tr_args(
+                        "radio.intel_operator_compromised",
+                        &[(
+                            "role",
+                            &tr(&format!("hud.{}", role_name(&intel_operator.intel_type))),
+                        )],
+                    ),
+                );
             }
         }
     }
@@ -405,6 +485,7 @@ pub fn counter_intel_system(
 
 pub fn process_intel_reports(
     mut commands: Commands,
+    mut entity_pools: ResMut<EntityPools>,
     intel_system: Res<IntelSystem>,
     time: Res<Time>,
 ) {
@@ -417,6 +498,7 @@ pub fn process_intel_reports(
                 // Spawn warning indicator for incoming airstrike
                 spawn_intel_indicator(
                     &mut commands,
+                    &mut entity_pools,
                     *position,
                     "⚠️ AIR STRIKE INCOMING",
                     Color::RED,
@@ -427,6 +509,7 @@ pub fn process_intel_reports(
                     // Less than 2 minutes
                     spawn_intel_indicator(
                         &mut commands,
+                        &mut entity_pools,
                         *position,
                         "🚁 REINFORCEMENTS NEAR",
                         Color::ORANGE,
@@ -448,6 +531,7 @@ pub fn process_intel_reports(
                     if *eta < 90.0 {
                         spawn_intel_indicator(
                             &mut commands,
+                            &mut entity_pools,
                             *position,
                             "🎯 IMMINENT ATTACK",
                             Color::RED,
@@ -457,6 +541,7 @@ pub fn process_intel_reports(
                 TipType::CommandPost(position) => {
                     spawn_intel_indicator(
                         &mut commands,
+                        &mut entity_pools,
                         *position,
                         "🏢 HVT IDENTIFIED",
                         Color::YELLOW,
@@ -468,24 +553,26 @@ pub fn process_intel_reports(
     }
 }
 
-fn spawn_intel_indicator(commands: &mut Commands, position: Vec3, text: &str, color: Color) {
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                text,
-                TextStyle {
-                    font_size: 20.0,
-                    color,
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(position + Vec3::new(0.0, 30.0, 0.0)),
-            ..default()
-        },
-        DamageIndicator {
-            lifetime: Timer::from_seconds(5.0, TimerMode::Once),
+fn spawn_intel_indicator(
+    commands: &mut Commands,
+    entity_pools: &mut EntityPools,
+    position: Vec3,
+    text: &str,
+    color: Color,
+) {
+    spawn_pooled_text(
+        commands,
+        entity_pools,
+        PoolKind::Indicator,
+        FloatingText {
+            text: text.to_string(),
+            position: position + Vec3::new(0.0, 30.0, 0.0),
+            color,
+            font_size: 20.0,
+            lifetime: 5.0,
+            velocity: Vec3::new(0.0, 30.0, 0.0),
         },
-    ));
+    );
 }
 
 // ==================== INTEL UI SYSTEM ====================
@@ -493,21 +580,30 @@ fn spawn_intel_indicator(commands: &mut Commands, position: Vec3, text: &str, co
 pub fn intel_ui_system(
     mut commands: Commands,
     intel_system: Res<IntelSystem>,
-    existing_ui: Query<Entity, With<IntelUIPanel>>,
+    order_of_battle: Res<OrderOfBattle>,
+    balance: Res<BalanceConfig>,
+    time: Res<Time>,
+    source_query: Query<Ref<InformantSource>>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<IntelUIPanel>>,
+    theme: Res<UiTheme>,
 ) {
-    // Remove existing intel UI
-    for entity in existing_ui.iter() {
-        commands.entity(entity).despawn_recursive();
+    let panel_exists = !panel_query.is_empty();
+    let sources_changed = source_query.iter().any(|source| source.is_changed());
+    if panel_exists
+        && !intel_system.is_changed()
+        && !order_of_battle.is_changed()
+        && !sources_changed
+        && !theme.is_changed()
+    {
+        return;
     }
 
-    // Create intel panel
-    let recent_intercepts = intel_system
-        .global_intel_network
-        .active_intercepts
-        .iter()
-        .rev()
-        .take(3)
-        .collect::<Vec<_>>();
+    // The army's strength as the reports add up, rather than the reports
+    let battle_lines = order_of_battle_lines(
+        &order_of_battle,
+        &balance.order_of_battle,
+        time.elapsed_seconds(),
+    );
 
     let recent_tips = intel_system
         .global_intel_network
@@ -515,121 +611,84 @@ pub fn intel_ui_system(
         .iter()
         .rev()
         .take(2)
+        .map(|tip| {
+            let source = tip.source.and_then(|entity| source_query.get(entity).ok());
+            (tip, source_label(source.as_deref()))
+        })
         .collect::<Vec<_>>();
 
-    if !recent_intercepts.is_empty() || !recent_tips.is_empty() {
-        spawn_intel_ui_panel(&mut commands, &recent_intercepts, &recent_tips);
+    // Only show the intel panel once there's something to report
+    let has_intel = !battle_lines.is_empty() || !recent_tips.is_empty();
+    let visibility = if has_intel {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let lines = intel_panel_lines(battle_lines, &recent_tips);
+
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else if !panel_exists {
+        let mut panel = create_dashboard_panel(
+            Style {
+                left: Val::Px(10.0),
+                top: Val::Px(150.0),
+                width: Val::Px(300.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.8),
+            lines,
+            &theme,
+        );
+        panel.0.visibility = visibility;
+        commands.spawn((panel, IntelUIPanel));
     }
 }
 
 #[derive(Component)]
 pub struct IntelUIPanel;
 
-fn spawn_intel_ui_panel(
-    commands: &mut Commands,
-    intercepts: &[&RadioIntercept],
-    tips: &[&InformantTip],
-) {
-    commands
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    left: Val::Px(10.0),
-                    top: Val::Px(150.0),
-                    width: Val::Px(300.0),
-                    height: Val::Auto,
-                    flex_direction: FlexDirection::Column,
-                    padding: UiRect::all(Val::Px(10.0)),
-                    ..default()
-                },
-                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8)),
-                ..default()
-            },
-            IntelUIPanel,
-        ))
-        .with_children(|parent| {
-            // Intel panel title
-            parent.spawn(TextBundle::from_section(
-                "📡 INTELLIGENCE",
-                TextStyle {
-                    font_size: 16.0,
-                    color: Color::CYAN,
-                    ..default()
-                },
-            ));
-
-            // Radio intercepts
-            if !intercepts.is_empty() {
-                parent.spawn(TextBundle::from_section(
-                    "📻 RADIO CHATTER:",
-                    TextStyle {
-                        font_size: 12.0,
-                        color: Color::WHITE,
-                        ..default()
-                    },
-                ));
-
-                for intercept in intercepts {
-                    let reliability_color = if intercept.reliability > 0.8 {
-                        Color::GREEN
-                    } else if intercept.reliability > 0.6 {
-                        Color::YELLOW
-                    } else {
-                        Color::ORANGE
-                    };
-
-                    parent.spawn(TextBundle::from_section(
-                        format!("• {}", intercept.content),
-                        TextStyle {
-                            font_size: 10.0,
-                            color: reliability_color,
-                            ..default()
-                        },
-                    ));
+fn intel_panel_lines(
+    battle_lines: Vec<TextSection>,
+    tips: &[(&InformantTip, (String, Color))],
+) -> Vec<TextSection> {
+    // Intel panel title
+    let mut lines = vec![dashboard_line("📡 INTELLIGENCE", 16.0, Color::CYAN)];
+
+    // Estimated enemy order of battle
+    lines.extend(battle_lines);
+
+    // Informant tips
+    if !tips.is_empty() {
+        lines.push(dashboard_line("👤 INFORMANTS:", 12.0, Color::WHITE));
+
+        for (tip, (source, source_color)) in tips {
+            let urgency_color = match tip.urgency {
+                TipUrgency::Critical => Color::RED,
+                TipUrgency::High => Color::ORANGE,
+                TipUrgency::Medium => Color::YELLOW,
+                TipUrgency::Low => Color::WHITE,
+            };
+
+            let tip_text = match &tip.tip_type {
+                TipType::EnemyPosition(unit_type, count) => {
+                    format!("• {} {:?} spotted", count, unit_type)
                 }
-            }
-
-            // Informant tips
-            if !tips.is_empty() {
-                parent.spawn(TextBundle::from_section(
-                    "👤 INFORMANTS:",
-                    TextStyle {
-                        font_size: 12.0,
-                        color: Color::WHITE,
-                        ..default()
-                    },
-                ));
-
-                for tip in tips {
-                    let urgency_color = match tip.urgency {
-                        TipUrgency::Critical => Color::RED,
-                        TipUrgency::High => Color::ORANGE,
-                        TipUrgency::Medium => Color::YELLOW,
-                        TipUrgency::Low => Color::WHITE,
-                    };
+                TipType::PlannedAttack(_, eta) => {
+                    format!("• Attack planned in {:.0}s", eta)
+                }
+                TipType::WeakPoint(_) => "• Weak point identified".to_string(),
+                TipType::CommandPost(_) => "• Command post located".to_string(),
+                TipType::SupplyRoute(_, _) => "• Supply route discovered".to_string(),
+            };
 
-                    let tip_text = match &tip.tip_type {
-                        TipType::EnemyPosition(unit_type, count) => {
-                            format!("• {} {:?} spotted", count, unit_type)
-                        }
-                        TipType::PlannedAttack(_, eta) => {
-                            format!("• Attack planned in {:.0}s", eta)
-                        }
-                        TipType::WeakPoint(_) => "• Weak point identified".to_string(),
-                        TipType::CommandPost(_) => "• Command post located".to_string(),
-                        TipType::SupplyRoute(_, _) => "• Supply route discovered".to_string(),
-                    };
+            lines.push(dashboard_line(tip_text, 10.0, urgency_color));
+            lines.push(dashboard_line(format!("  {}", source), 9.0, *source_color));
+        }
+    }
 
-                    parent.spawn(TextBundle::from_section(
-                        tip_text,
-                        TextStyle {
-                            font_size: 10.0,
-                            color: urgency_color,
-                            ..default()
-                        },
-                    ));
-                }
-            }
-        });
+    lines
 }
@@ -0,0 +1,524 @@
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{BalanceConfig, JammingBalance, UnitCatalog};
+use crate::indirect_fire::densest_group;
+use crate::intel_recruitment::IntelRecruitment;
+use crate::localization::{tr, tr_args};
+use crate::political_system::{
+    government_decision_system, GovernmentResponseLevel, PoliticalState,
+};
+use crate::resources::{GameAssets, IntelSystem};
+use crate::spawners::spawn_unit;
+use crate::utils::{iso_to_world, play_tactical_sound, RngStream, SimRng, SimulationSet};
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::f32::consts::TAU;
+
+// ==================== JAMMING PLUGIN ====================
+
+// From Moderate rules up, the army parks jammer vehicles near wherever the
+// cartel is thickest. Inside a jammer's field intercepts come through
+// garbled (see intel_system.rs) and cartel radios stop passing contacts on
+// (see coordination.rs). The field shows on the map as shimmering static,
+// wandering around the jammer until it's found. To find one the cartel needs
+// three lookouts within reach of it: each takes a bearing, drawn as a line
+// from the lookout, and with the intel network panel (U) open M fixes the
+// jammer at the cursor where the lines cross. A close enough fix puts the
+// vehicle on the map for the fighters to shoot up; a miss costs the intel
+// points all the same.
+
+const FIELD_Z: f32 = 0.46;
+const FIELD_COLOR: Color = Color::rgba(0.55, 0.75, 1.0, 0.22);
+const BEARING_COLOR: Color = Color::rgba(1.0, 0.85, 0.3, 0.6);
+const BEARING_WIDTH: f32 = 3.0;
+
+pub struct JammingPlugin;
+
+impl Plugin for JammingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JammerDesk>()
+            .add_systems(OnEnter(AppState::InGame), jamming_reset_system)
+            .add_systems(
+                FixedUpdate,
+                (
+                    jammer_deployment_system.after(government_decision_system),
+                    triangulation_system,
+                    jammer_destroyed_system,
+                )
+                    .chain()
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(
+                Update,
+                (jam_field_visual_system, bearing_line_system).in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct JammerDesk {
+    pub cooldown: f32, // Seconds before the army parks its next jammer
+}
+
+/// A jammer vehicle, and the bearings the cartel's lookouts have on it.
+/// Until it's found it has no Unit and can't be seen or shot.
+#[derive(Component, Clone, Debug)]
+pub struct Jammer {
+    pub radius: f32,
+    pub strength: f32,
+    pub located: bool,
+    pub bearings: Vec<Bearing>,
+    pub seed: f32, // Keeps each field's shimmer apart from the others'
+}
+
+/// A lookout's line on a jammer: from where it stands, toward the jammer,
+/// a few degrees off.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bearing {
+    pub lookout: Entity,
+    pub origin: Vec2,
+    pub angle: f32,
+}
+
+/// Where a jammer's field reaches, and how hard it jams there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JamField {
+    pub center: Vec2,
+    pub radius: f32,
+    pub strength: f32,
+}
+
+#[derive(Component)]
+pub struct JamFieldSprite;
+
+#[derive(Component)]
+pub struct BearingLine;
+
+// Type aliases to reduce complexity
+pub type JammerQuery<'w, 's> =
+    Query<'w, 's, (&'static Transform, &'static Jammer, Option<&'static Unit>)>;
+
+/// The fields of the jammers still working: not yet found, or found and
+/// not yet destroyed.
+pub fn jam_fields(jammer_query: &JammerQuery) -> Vec<JamField> {
+    jammer_query
+        .iter()
+        .filter(|(_, _, unit)| unit.is_none_or(|unit| unit.health > 0.0))
+        .map(|(transform, jammer, _)| JamField {
+            center: transform.translation.truncate(),
+            radius: jammer.radius,
+            strength: jammer.strength,
+        })
+        .collect()
+}
+
+/// How hard a radio of `faction` at `position` is jammed: by the strongest
+/// field it stands in. The army's own radios hop frequencies and get through.
+pub fn jamming_at(fields: &[JamField], faction: &Faction, position: Vec2) -> f32 {
+    if faction.side() == Faction::Military {
+        return 0.0;
+    }
+    fields
+        .iter()
+        .filter(|field| field.center.distance(position) <= field.radius)
+        .map(|field| field.strength)
+        .fold(0.0, f32::max)
+}
+
+/// A message heard through static: about `strength` of its words lost.
+pub fn garble(content: &str, strength: f32, rng: &mut StdRng) -> String {
+    content
+        .split(' ')
+        .map(|word| {
+            if rng.gen::<f32>() < strength {
+                "~".repeat(word.chars().count().max(1))
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The angle a lookout at `from` reads toward `to`, `error` radians off.
+pub fn bearing_angle(from: Vec2, to: Vec2, error: f32) -> f32 {
+    let direction = to - from;
+    direction.y.atan2(direction.x) + error
+}
+
+/// Where a jammer the army parks goes: near the thickest knot of `cartel`
+/// fighters, up to `scatter` off it.
+pub fn jammer_site(cartel: &[Vec2], balance: &JammingBalance, rng: &mut StdRng) -> Option<Vec2> {
+    let center = densest_group(cartel, balance.radius)?;
+    let angle = rng.gen_range(0.0..TAU);
+    let distance = rng.gen_range(0.0..=balance.scatter);
+    Some(center + Vec2::from_angle(angle) * distance)
+}
+
+fn spawn_field(commands: &mut Commands, owner: Entity, radius: f32) {
+    let field = commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: FIELD_COLOR,
+                    custom_size: Some(Vec2::splat(radius * 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, FIELD_Z),
+                ..default()
+            },
+            JamFieldSprite,
+        ))
+        .id();
+    commands.entity(owner).add_child(field);
+}
+
+/// Parks a jammer, hidden, at `position`.
+pub fn spawn_jammer(
+    commands: &mut Commands,
+    position: Vec2,
+    seed: f32,
+    balance: &JammingBalance,
+) -> Entity {
+    let jammer = commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_translation(position.extend(0.0))),
+            Jammer {
+                radius: balance.radius,
+                strength: balance.strength,
+                located: false,
+                bearings: Vec::new(),
+                seed,
+            },
+        ))
+        .id();
+    spawn_field(commands, jammer, balance.radius);
+    jammer
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn jamming_reset_system(
+    mut commands: Commands,
+    mut desk: ResMut<JammerDesk>,
+    balance: Res<BalanceConfig>,
+    hidden_query: Query<Entity, (With<Jammer>, Without<Unit>)>,
+    line_query: Query<Entity, With<BearingLine>>,
+) {
+    for entity in hidden_query.iter().chain(line_query.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+    desk.cooldown = balance.jamming.deploy_interval;
+}
+
+// Once the rules allow it, the army parks a jammer where the cartel is thickest
+pub fn jammer_deployment_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    political_state: Res<PoliticalState>,
+    mut desk: ResMut<JammerDesk>,
+    mut sim_rng: ResMut<SimRng>,
+    unit_query: Query<(&Unit, &Transform)>,
+    jammer_query: Query<(), With<Jammer>>,
+) {
+    let balance = &balance.jamming;
+    if political_state.government_response_level == GovernmentResponseLevel::Limited {
+        return;
+    }
+    desk.cooldown -= time.delta_seconds();
+    if desk.cooldown > 0.0 || jammer_query.iter().count() >= balance.max_jammers {
+        return;
+    }
+
+    let cartel: Vec<Vec2> = unit_query
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    let rng = sim_rng.stream(RngStream::Intel);
+    let Some(site) = jammer_site(&cartel, balance, rng) else {
+        return;
+    };
+    let seed = rng.gen_range(0.0..TAU);
+    spawn_jammer(&mut commands, site, seed, balance);
+    desk.cooldown = balance.deploy_interval;
+    play_tactical_sound("radio", &tr("radio.jammer_deployed"));
+}
+
+// Lookouts within reach of a hidden jammer each take a bearing on it; a
+// lookout that's gone takes its bearing with it
+pub fn triangulation_system(
+    balance: Res<BalanceConfig>,
+    mut sim_rng: ResMut<SimRng>,
+    lookout_query: Query<(Entity, &Transform, &IntelOperator)>,
+    mut jammer_query: Query<(&Transform, &mut Jammer)>,
+) {
+    let balance = &balance.jamming;
+    let rng = sim_rng.stream(RngStream::Intel);
+    let lookouts: Vec<(Entity, Vec2)> = lookout_query
+        .iter()
+        .filter(|(_, _, operator)| operator.intel_type == IntelType::Reconnaissance)
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+        .collect();
+
+    for (transform, mut jammer) in jammer_query.iter_mut() {
+        if jammer.located {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let standing = |lookout: Entity| lookouts.iter().any(|(entity, _)| *entity == lookout);
+        if !jammer
+            .bearings
+            .iter()
+            .all(|bearing| standing(bearing.lookout))
+        {
+            jammer.bearings.retain(|bearing| standing(bearing.lookout));
+        }
+        for (lookout, origin) in &lookouts {
+            if origin.distance(position) > balance.bearing_range
+                || jammer
+                    .bearings
+                    .iter()
+                    .any(|bearing| bearing.lookout == *lookout)
+            {
+                continue;
+            }
+            let error = rng
+                .gen_range(-balance.bearing_error..=balance.bearing_error)
+                .to_radians();
+            jammer.bearings.push(Bearing {
+                lookout: *lookout,
+                origin: *origin,
+                angle: bearing_angle(*origin, position, error),
+            });
+        }
+    }
+}
+
+// A found jammer shot up stops jamming
+pub fn jammer_destroyed_system(
+    mut commands: Commands,
+    jammer_query: Query<(Entity, &Unit), With<Jammer>>,
+    field_query: Query<(Entity, &Parent), With<JamFieldSprite>>,
+) {
+    for (entity, unit) in jammer_query.iter() {
+        if unit.health > 0.0 {
+            continue;
+        }
+        commands.entity(entity).remove::<Jammer>();
+        for (field, parent) in field_query.iter() {
+            if parent.get() == entity {
+                commands.entity(field).despawn_recursive();
+            }
+        }
+        play_tactical_sound("radio", &tr("radio.jammer_destroyed"));
+    }
+}
+
+// M with the intel network panel open: fix the jammer at the cursor, where
+// the lookouts' bearings cross
+#[allow(clippy::too_many_arguments)]
+pub fn jammer_fix_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    game_assets: Res<GameAssets>,
+    recruitment: Option<Res<IntelRecruitment>>,
+    mut intel: ResMut<IntelSystem>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    jammer_query: Query<(Entity, &Transform, &Jammer), Without<Unit>>,
+) {
+    if !input.just_pressed(KeyCode::M) || !recruitment.is_some_and(|network| network.menu_open) {
+        return;
+    }
+    let balance = &balance.jamming;
+    let Some(cursor) = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+    else {
+        return;
+    };
+    if jammer_query.is_empty() {
+        play_tactical_sound("radio", &tr("radio.jammer_none"));
+        return;
+    }
+    let Some((entity, transform, jammer)) = jammer_query
+        .iter()
+        .filter(|(_, _, jammer)| jammer.bearings.len() >= balance.bearings_needed)
+        .min_by(|a, b| {
+            let distance =
+                |transform: &Transform| transform.translation.truncate().distance(cursor);
+            distance(a.1).total_cmp(&distance(b.1))
+        })
+    else {
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.jammer_needs_lookouts",
+                &[("count", &balance.bearings_needed)],
+            ),
+        );
+        return;
+    };
+    if !intel.spend_intel(balance.fix_cost) {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.intel_points_short", &[("points", &balance.fix_cost)]),
+        );
+        return;
+    }
+    if transform.translation.truncate().distance(cursor) > balance.fix_radius {
+        play_tactical_sound("radio", &tr("radio.jammer_fix_missed"));
+        return;
+    }
+
+    // Found: the vehicle is out in the open, parked where it jams
+    let vehicle = spawn_unit(
+        &mut commands,
+        UnitType::Vehicle,
+        Faction::Military,
+        iso_to_world(transform.translation),
+        &game_assets,
+        &catalog,
+    );
+    commands
+        .entity(vehicle)
+        .remove::<(Movement, PathfindingAgent)>()
+        .insert(Jammer {
+            located: true,
+            bearings: Vec::new(),
+            ..jammer.clone()
+        });
+    spawn_field(&mut commands, vehicle, jammer.radius);
+    commands.entity(entity).despawn_recursive();
+    play_tactical_sound("radio", &tr("radio.jammer_located"));
+}
+
+// The static shimmers around a jammer not yet found, and settles on one
+// that has been
+pub fn jam_field_visual_system(
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    jammer_query: Query<&Jammer>,
+    mut field_query: Query<(&Parent, &mut Transform, &mut Sprite), With<JamFieldSprite>>,
+) {
+    let t = time.elapsed_seconds();
+    for (parent, mut transform, mut sprite) in field_query.iter_mut() {
+        let Ok(jammer) = jammer_query.get(parent.get()) else {
+            continue;
+        };
+        let (offset, alpha) = if jammer.located {
+            (Vec2::ZERO, FIELD_COLOR.a())
+        } else {
+            let wander = Vec2::new((t * 0.7 + jammer.seed).sin(), (t * 0.5 + jammer.seed).cos());
+            let flicker = 0.6 + 0.4 * (t * 9.0 + jammer.seed).sin().abs();
+            (wander * balance.jamming.shimmer, FIELD_COLOR.a() * flicker)
+        };
+        transform.translation = offset.extend(FIELD_Z);
+        sprite.color.set_a(alpha);
+    }
+}
+
+// Redraws the lookouts' bearing lines whenever a jammer's bearings change
+pub fn bearing_line_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    jammer_query: Query<Ref<Jammer>>,
+    mut removed: RemovedComponents<Jammer>,
+    line_query: Query<Entity, With<BearingLine>>,
+) {
+    let gone = removed.read().count() > 0;
+    if !gone && !jammer_query.iter().any(|jammer| jammer.is_changed()) {
+        return;
+    }
+    for entity in line_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let length = balance.jamming.bearing_range;
+    for jammer in jammer_query.iter().filter(|jammer| !jammer.located) {
+        for bearing in &jammer.bearings {
+            let direction = Vec2::from_angle(bearing.angle);
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: BEARING_COLOR,
+                        custom_size: Some(Vec2::new(length, BEARING_WIDTH)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(
+                        (bearing.origin + direction * length * 0.5).extend(FIELD_Z),
+                    )
+                    .with_rotation(Quat::from_rotation_z(bearing.angle)),
+                    ..default()
+                },
+                BearingLine,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_only_cartel_radios_inside_a_field_are_jammed() {
+        let fields = [
+            JamField {
+                center: Vec2::ZERO,
+                radius: 100.0,
+                strength: 0.5,
+            },
+            JamField {
+                center: Vec2::new(50.0, 0.0),
+                radius: 100.0,
+                strength: 0.8,
+            },
+        ];
+        assert_eq!(
+            jamming_at(&fields, &Faction::Cartel, Vec2::new(40.0, 0.0)),
+            0.8
+        );
+        assert_eq!(
+            jamming_at(&fields, &Faction::Cartel, Vec2::new(-90.0, 0.0)),
+            0.5
+        );
+        assert_eq!(
+            jamming_at(&fields, &Faction::Cartel, Vec2::new(0.0, 500.0)),
+            0.0
+        );
+        assert_eq!(jamming_at(&fields, &Faction::Military, Vec2::ZERO), 0.0);
+        assert_eq!(jamming_at(&fields, &Faction::Police, Vec2::ZERO), 0.0);
+    }
+
+    #[test]
+    fn test_garbling_loses_words_to_static() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let message = "Convoy moving north on Obregon";
+        assert_eq!(garble(message, 0.0, &mut rng), message);
+        assert_eq!(
+            garble(message, 1.0, &mut rng),
+            "~~~~~~ ~~~~~~ ~~~~~ ~~ ~~~~~~~"
+        );
+    }
+
+    #[test]
+    fn test_bearings_point_at_the_jammer() {
+        let angle = bearing_angle(Vec2::ZERO, Vec2::new(0.0, 10.0), 0.0);
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+        let off = bearing_angle(Vec2::ZERO, Vec2::new(10.0, 0.0), 0.1);
+        assert!((off - 0.1).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,670 @@
+use crate::auth::{
+    AuthSession, GameAuthIntegration, LeaderboardEntry, SubmitScoreRequest, SubmitScoreResponse,
+};
+use crate::campaign::Campaign;
+use crate::localization::{tr, tr_args};
+use crate::profile::{DailyAttempt, PlayerProfile};
+use crate::save::save_system::{CampaignProgress, DifficultyLevel, MissionId};
+use crate::scoring::{ChallengeModifier, Medal, MissionScore};
+use crate::utils::play_tactical_sound;
+use bevy::prelude::*;
+use chrono::NaiveDate;
+use reqwest::Client;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+// ==================== LEADERBOARD PLUGIN ====================
+
+// Won missions are posted to the game API's leaderboards with the signed-in
+// player's auth token - score, time, medal and challenge modifiers, one board
+// per mission and difficulty, plus one per daily challenge - and the top
+// entries are fetched back for the victory screen and the Leaderboards page
+// (0 in the main menu). Signed out, or when the server can't be reached, both
+// show the player's own best.
+
+pub const LEADERBOARD_TOP_ENTRIES: u32 = 10;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Leaderboards>().add_systems(
+            Update,
+            (leaderboard_submit_system, leaderboard_event_system).chain(),
+        );
+    }
+}
+
+// ==================== LEADERBOARD RESOURCES ====================
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BoardKey {
+    Mission {
+        mission: MissionId,
+        difficulty: DifficultyLevel,
+    },
+    Daily(NaiveDate),
+}
+
+impl BoardKey {
+    pub fn new(mission: MissionId, difficulty: DifficultyLevel) -> Self {
+        BoardKey::Mission {
+            mission,
+            difficulty,
+        }
+    }
+
+    pub fn for_campaign(progress: &CampaignProgress) -> Self {
+        Self::new(progress.current_mission.clone(), progress.difficulty_level)
+    }
+
+    // The board a scored win goes on
+    pub fn for_score(score: &MissionScore, progress: &CampaignProgress) -> Option<Self> {
+        match (score.daily, &score.mission) {
+            (Some(date), Some(_)) => Some(BoardKey::Daily(date)),
+            (None, Some(mission)) => Some(Self::new(mission.clone(), progress.difficulty_level)),
+            (_, None) => None,
+        }
+    }
+
+    // The mission and difficulty fields on the API, e.g. InitialRaid and
+    // Veteran, or Daily and the date
+    fn segments(&self) -> (String, String) {
+        match self {
+            BoardKey::Mission {
+                mission,
+                difficulty,
+            } => (format!("{:?}", mission), format!("{:?}", difficulty)),
+            BoardKey::Daily(date) => ("Daily".to_string(), date.to_string()),
+        }
+    }
+
+    fn path(&self) -> String {
+        let (mission, difficulty) = self.segments();
+        format!("{}/{}", mission, difficulty)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoardStatus {
+    Loading,
+    Online(Vec<LeaderboardEntry>),
+    Offline, // Signed out or the request failed
+}
+
+#[derive(Resource)]
+pub struct Leaderboards {
+    pub viewing: BoardKey, // Board shown on the Leaderboards page
+    mission_board: (MissionId, DifficultyLevel), // Picked on the page, kept while a daily is shown
+    boards: HashMap<BoardKey, BoardStatus>,
+    ranks: HashMap<BoardKey, u32>, // Our place after the latest submission
+    commands: Option<mpsc::UnboundedSender<LeaderboardCommand>>,
+    events: Option<mpsc::UnboundedReceiver<LeaderboardEvent>>,
+}
+
+impl Default for Leaderboards {
+    fn default() -> Self {
+        Self {
+            viewing: BoardKey::new(MissionId::InitialRaid, DifficultyLevel::Veteran),
+            mission_board: (MissionId::InitialRaid, DifficultyLevel::Veteran),
+            boards: HashMap::new(),
+            ranks: HashMap::new(),
+            commands: None,
+            events: None,
+        }
+    }
+}
+
+impl Leaderboards {
+    pub fn status(&self, key: &BoardKey) -> Option<&BoardStatus> {
+        self.boards.get(key)
+    }
+
+    pub fn rank(&self, key: &BoardKey) -> Option<u32> {
+        self.ranks.get(key).copied()
+    }
+
+    pub fn refresh(&mut self, auth_session: &AuthSession, key: BoardKey) {
+        let sent = self.send(auth_session, |token| LeaderboardCommand::Fetch {
+            token,
+            key: key.clone(),
+        });
+        self.set_pending(key, sent);
+    }
+
+    // The board is fetched again once the score is in
+    pub fn submit(
+        &mut self,
+        auth_session: &AuthSession,
+        key: BoardKey,
+        request: SubmitScoreRequest,
+    ) {
+        let sent = self.send(auth_session, |token| LeaderboardCommand::Submit {
+            token,
+            key: key.clone(),
+            request,
+        });
+        self.set_pending(key, sent);
+    }
+
+    pub fn mission_board(&self) -> (&MissionId, DifficultyLevel) {
+        (&self.mission_board.0, self.mission_board.1)
+    }
+
+    pub fn view(&mut self, auth_session: &AuthSession, key: BoardKey) {
+        if let BoardKey::Mission {
+            mission,
+            difficulty,
+        } = &key
+        {
+            self.mission_board = (mission.clone(), *difficulty);
+        }
+        self.viewing = key.clone();
+        self.refresh(auth_session, key);
+    }
+
+    pub fn view_next_mission(&mut self, auth_session: &AuthSession) {
+        let (mission, difficulty) = self.mission_board.clone();
+        let key = BoardKey::new(next_in(&MissionId::ALL, &mission), difficulty);
+        self.view(auth_session, key);
+    }
+
+    pub fn view_next_difficulty(&mut self, auth_session: &AuthSession) {
+        let (mission, difficulty) = self.mission_board.clone();
+        let key = BoardKey::new(mission, next_in(&DifficultyLevel::ALL, &difficulty));
+        self.view(auth_session, key);
+    }
+
+    // Between today's daily board and the mission board picked before
+    pub fn toggle_daily(&mut self, auth_session: &AuthSession, today: NaiveDate) {
+        let key = match self.viewing {
+            BoardKey::Daily(_) => {
+                let (mission, difficulty) = self.mission_board.clone();
+                BoardKey::new(mission, difficulty)
+            }
+            BoardKey::Mission { .. } => BoardKey::Daily(today),
+        };
+        self.view(auth_session, key);
+    }
+
+    fn set_pending(&mut self, key: BoardKey, sent: bool) {
+        let status = if sent {
+            BoardStatus::Loading
+        } else {
+            BoardStatus::Offline
+        };
+        self.boards.insert(key, status);
+    }
+
+    // False when signed out, so the caller falls back to the local best
+    fn send(
+        &mut self,
+        auth_session: &AuthSession,
+        command: impl FnOnce(String) -> LeaderboardCommand,
+    ) -> bool {
+        let Some(token) = auth_session.access_token() else {
+            return false;
+        };
+
+        let sender = self.commands.get_or_insert_with(|| {
+            let (command_tx, command_rx) = mpsc::unbounded_channel();
+            let (event_tx, event_rx) = mpsc::unbounded_channel();
+            spawn_leaderboard_worker(
+                GameAuthIntegration::new().get_api_base_url(),
+                command_rx,
+                event_tx,
+            );
+            self.events = Some(event_rx);
+            command_tx
+        });
+
+        if sender.send(command(token.to_string())).is_err() {
+            // Worker died; a fresh one is started on the next request
+            self.commands = None;
+            self.events = None;
+            return false;
+        }
+        true
+    }
+}
+
+fn next_in<T: Clone + PartialEq>(all: &[T], current: &T) -> T {
+    let index = all.iter().position(|item| item == current).unwrap_or(0);
+    all[(index + 1) % all.len()].clone()
+}
+
+// ==================== LEADERBOARD WORKER ====================
+
+pub enum LeaderboardCommand {
+    Submit {
+        token: String,
+        key: BoardKey,
+        request: SubmitScoreRequest,
+    },
+    Fetch {
+        token: String,
+        key: BoardKey,
+    },
+}
+
+pub enum LeaderboardEvent {
+    Rank {
+        key: BoardKey,
+        rank: u32,
+    },
+    Board {
+        key: BoardKey,
+        entries: Vec<LeaderboardEntry>,
+    },
+    Failed {
+        key: BoardKey,
+        error: String,
+    },
+}
+
+fn spawn_leaderboard_worker(
+    base_url: String,
+    mut commands: mpsc::UnboundedReceiver<LeaderboardCommand>,
+    events: mpsc::UnboundedSender<LeaderboardEvent>,
+) {
+    // Same approach as the social worker: HTTP needs a tokio reactor that Bevy doesn't provide
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                warn!("Failed to start leaderboard worker: {}", e);
+                return;
+            }
+        };
+
+        runtime.block_on(async move {
+            let http_client = Client::new();
+
+            while let Some(command) = commands.recv().await {
+                let key = match &command {
+                    LeaderboardCommand::Submit { key, .. }
+                    | LeaderboardCommand::Fetch { key, .. } => key.clone(),
+                };
+                let batch = handle_leaderboard_command(&http_client, &base_url, command)
+                    .await
+                    .unwrap_or_else(|e| {
+                        vec![LeaderboardEvent::Failed {
+                            key,
+                            error: e.to_string(),
+                        }]
+                    });
+
+                if batch.into_iter().any(|event| events.send(event).is_err()) {
+                    break;
+                }
+            }
+        });
+    });
+}
+
+async fn handle_leaderboard_command(
+    http_client: &Client,
+    base_url: &str,
+    command: LeaderboardCommand,
+) -> Result<Vec<LeaderboardEvent>, reqwest::Error> {
+    match command {
+        LeaderboardCommand::Submit {
+            token,
+            key,
+            request,
+        } => {
+            let response = http_client
+                .post(format!("{}/leaderboards/scores", base_url))
+                .bearer_auth(&token)
+                .json(&request)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<SubmitScoreResponse>()
+                .await?;
+            let entries = fetch_board(http_client, base_url, &token, &key).await?;
+
+            Ok(vec![
+                LeaderboardEvent::Rank {
+                    key: key.clone(),
+                    rank: response.rank,
+                },
+                LeaderboardEvent::Board { key, entries },
+            ])
+        }
+        LeaderboardCommand::Fetch { token, key } => {
+            let entries = fetch_board(http_client, base_url, &token, &key).await?;
+            Ok(vec![LeaderboardEvent::Board { key, entries }])
+        }
+    }
+}
+
+async fn fetch_board(
+    http_client: &Client,
+    base_url: &str,
+    token: &str,
+    key: &BoardKey,
+) -> Result<Vec<LeaderboardEntry>, reqwest::Error> {
+    http_client
+        .get(format!("{}/leaderboards/{}", base_url, key.path()))
+        .query(&[("limit", LEADERBOARD_TOP_ENTRIES)])
+        .bearer_auth(token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Vec<LeaderboardEntry>>()
+        .await
+}
+
+// ==================== LEADERBOARD SYSTEMS ====================
+
+// Posts each newly scored mission once
+pub fn leaderboard_submit_system(
+    mut leaderboards: ResMut<Leaderboards>,
+    score: Res<MissionScore>,
+    campaign: Res<Campaign>,
+    auth_session: Res<AuthSession>,
+) {
+    if !score.is_changed() {
+        return;
+    }
+    let Some(key) = BoardKey::for_score(&score, &campaign.progress) else {
+        return;
+    };
+
+    let request = submission(&score, &key);
+    leaderboards.submit(&auth_session, key, request);
+}
+
+pub fn submission(score: &MissionScore, key: &BoardKey) -> SubmitScoreRequest {
+    let (mission, difficulty) = key.segments();
+    SubmitScoreRequest {
+        mission,
+        difficulty,
+        score: score.breakdown.total,
+        completion_time: score.completion_time,
+        medal: score.medal.map(|medal| format!("{:?}", medal)),
+        challenges: score
+            .challenges
+            .iter()
+            .map(|challenge| format!("{:?}", challenge))
+            .collect(),
+    }
+}
+
+pub fn leaderboard_event_system(mut leaderboards: ResMut<Leaderboards>) {
+    // Pages redraw when the boards change, so draining an empty channel mustn't count
+    let mut events = Vec::new();
+    if let Some(receiver) = leaderboards.bypass_change_detection().events.as_mut() {
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+    }
+
+    for event in events {
+        match event {
+            LeaderboardEvent::Rank { key, rank } => {
+                play_tactical_sound(
+                    "radio",
+                    &tr_args("radio.leaderboard_rank", &[("rank", &rank)]),
+                );
+                leaderboards.ranks.insert(key, rank);
+            }
+            LeaderboardEvent::Board { key, entries } => {
+                leaderboards
+                    .boards
+                    .insert(key, BoardStatus::Online(entries));
+            }
+            LeaderboardEvent::Failed { key, error } => {
+                warn!("Leaderboard request failed: {}", error);
+                leaderboards.boards.insert(key, BoardStatus::Offline);
+            }
+        }
+    }
+}
+
+// ==================== BOARD TEXT ====================
+
+/// Text lines for a board: its top entries when online, the player's own best
+/// otherwise - the campaign's for a mission, the profile's for a daily.
+pub fn board_lines(
+    leaderboards: &Leaderboards,
+    key: &BoardKey,
+    progress: &CampaignProgress,
+    profile: Option<&PlayerProfile>,
+    limit: usize,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    match leaderboards.status(key) {
+        Some(BoardStatus::Online(entries)) if entries.is_empty() => {
+            lines.push(tr("leaderboard.empty"));
+        }
+        Some(BoardStatus::Online(entries)) => {
+            lines.extend(entries.iter().take(limit).map(entry_line));
+        }
+        Some(BoardStatus::Loading) => lines.push(tr("leaderboard.loading")),
+        Some(BoardStatus::Offline) | None => {
+            lines.push(tr("leaderboard.offline"));
+            lines.push(match key {
+                BoardKey::Mission { mission, .. } => local_best_line(progress, mission),
+                BoardKey::Daily(date) => {
+                    daily_result_line(profile.and_then(|profile| profile.daily_attempt(*date)))
+                }
+            });
+        }
+    }
+    if let Some(rank) = leaderboards.rank(key) {
+        lines.push(tr_args("leaderboard.your_rank", &[("rank", &rank)]));
+    }
+    lines
+}
+
+fn entry_line(entry: &LeaderboardEntry) -> String {
+    let line = tr_args(
+        "leaderboard.entry",
+        &[
+            ("rank", &entry.rank),
+            ("name", &entry.username),
+            ("score", &entry.score),
+            ("seconds", &format!("{:.0}", entry.completion_time)),
+            ("medal", &medal_label(entry.medal.as_deref())),
+        ],
+    );
+    if entry.challenges.is_empty() {
+        return line;
+    }
+
+    let challenges: Vec<String> = entry
+        .challenges
+        .iter()
+        .map(|name| {
+            ChallengeModifier::ALL
+                .into_iter()
+                .find(|challenge| format!("{:?}", challenge) == *name)
+                .map_or_else(|| name.clone(), |challenge| challenge.label())
+        })
+        .collect();
+    tr_args(
+        "leaderboard.entry_challenges",
+        &[("entry", &line), ("challenges", &challenges.join(", "))],
+    )
+}
+
+// Local results aren't kept per difficulty, so this is the best on any
+fn local_best_line(progress: &CampaignProgress, mission: &MissionId) -> String {
+    let Some(record) = progress.best_records.get(mission) else {
+        return tr("leaderboard.no_local_best");
+    };
+    let seconds = progress
+        .best_times
+        .get(mission)
+        .map_or_else(|| "-".to_string(), |time| format!("{:.0}", time));
+    tr_args(
+        "leaderboard.local_best",
+        &[
+            ("score", &record.score),
+            (
+                "medal",
+                &record
+                    .medal
+                    .map_or_else(|| tr("score.no_medal"), |medal| medal.label()),
+            ),
+            ("seconds", &seconds),
+        ],
+    )
+}
+
+fn daily_result_line(attempt: Option<&DailyAttempt>) -> String {
+    match attempt.and_then(|attempt| attempt.score.map(|score| (score, attempt.medal))) {
+        Some((score, medal)) => tr_args(
+            "leaderboard.daily_result",
+            &[
+                ("score", &score),
+                (
+                    "medal",
+                    &medal.map_or_else(|| tr("score.no_medal"), |medal| medal.label()),
+                ),
+            ],
+        ),
+        None => tr("leaderboard.no_daily_result"),
+    }
+}
+
+fn medal_label(name: Option<&str>) -> String {
+    [Medal::Bronze, Medal::Silver, Medal::Gold]
+        .into_iter()
+        .find(|medal| Some(format!("{:?}", medal).as_str()) == name)
+        .map_or_else(|| tr("score.no_medal"), |medal| medal.label())
+}
+
+pub fn difficulty_label(difficulty: DifficultyLevel) -> String {
+    tr(&format!("difficulty.{:?}", difficulty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::MissionRecord;
+
+    fn entry(rank: u32, challenges: &[&str]) -> LeaderboardEntry {
+        LeaderboardEntry {
+            rank,
+            username: format!("player{}", rank),
+            score: 2000 - rank * 100,
+            completion_time: 240.0,
+            medal: Some("Gold".to_string()),
+            challenges: challenges.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_offline_board_falls_back_to_the_local_best() {
+        let leaderboards = Leaderboards::default();
+        let key = BoardKey::new(MissionId::InitialRaid, DifficultyLevel::Veteran);
+        let mut progress = CampaignProgress::default();
+
+        let lines = board_lines(&leaderboards, &key, &progress, None, 5);
+        assert_eq!(
+            lines,
+            vec![tr("leaderboard.offline"), tr("leaderboard.no_local_best")]
+        );
+
+        progress.best_records.insert(
+            MissionId::InitialRaid,
+            MissionRecord {
+                score: 1500,
+                medal: Some(Medal::Silver),
+                challenges: Vec::new(),
+                assists: Vec::new(),
+            },
+        );
+        let lines = board_lines(&leaderboards, &key, &progress, None, 5);
+        assert!(lines[1].contains("1500"));
+    }
+
+    #[test]
+    fn test_online_board_lists_top_entries_and_our_rank() {
+        let mut leaderboards = Leaderboards::default();
+        let key = BoardKey::new(MissionId::InitialRaid, DifficultyLevel::Elite);
+        let entries = (1..=8).map(|rank| entry(rank, &[])).collect();
+        leaderboards
+            .boards
+            .insert(key.clone(), BoardStatus::Online(entries));
+        leaderboards.ranks.insert(key.clone(), 3);
+
+        let lines = board_lines(&leaderboards, &key, &CampaignProgress::default(), None, 5);
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].contains("player1"));
+        assert!(lines[5].contains('3'));
+
+        // Other difficulties are separate boards
+        let other = BoardKey::new(MissionId::InitialRaid, DifficultyLevel::Recruit);
+        assert_eq!(leaderboards.status(&other), None);
+    }
+
+    #[test]
+    fn test_entries_name_their_challenges() {
+        let line = entry_line(&entry(1, &["Permadeath"]));
+        assert!(line.contains(&ChallengeModifier::Permadeath.label()));
+    }
+
+    #[test]
+    fn test_signed_out_requests_mark_the_board_offline() {
+        let mut leaderboards = Leaderboards::default();
+        let auth_session = AuthSession::default();
+
+        leaderboards.view_next_difficulty(&auth_session);
+        assert_eq!(
+            leaderboards.viewing,
+            BoardKey::new(MissionId::InitialRaid, DifficultyLevel::Elite)
+        );
+        assert_eq!(
+            leaderboards.status(&leaderboards.viewing),
+            Some(&BoardStatus::Offline)
+        );
+
+        leaderboards.view_next_difficulty(&auth_session);
+        leaderboards.view_next_mission(&auth_session);
+        assert_eq!(
+            leaderboards.viewing,
+            BoardKey::new(MissionId::UrbanWarfare, DifficultyLevel::Recruit)
+        );
+    }
+
+    #[test]
+    fn test_daily_board_keeps_the_mission_board_picked() {
+        let mut leaderboards = Leaderboards::default();
+        let auth_session = AuthSession::default();
+        let today = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        leaderboards.view_next_mission(&auth_session);
+
+        leaderboards.toggle_daily(&auth_session, today);
+        assert_eq!(leaderboards.viewing, BoardKey::Daily(today));
+        assert_eq!(
+            submission(&MissionScore::default(), &leaderboards.viewing).difficulty,
+            "2024-03-14"
+        );
+
+        // Offline, the profile's result for the day stands in
+        let mut profile = PlayerProfile::new_local("Chapitos").unwrap();
+        profile.start_daily(today);
+        profile.record_daily_result(today, 1200, None);
+        let lines = board_lines(
+            &leaderboards,
+            &BoardKey::Daily(today),
+            &CampaignProgress::default(),
+            Some(&profile),
+            5,
+        );
+        assert!(lines[1].contains("1200"));
+
+        leaderboards.toggle_daily(&auth_session, today);
+        assert_eq!(
+            leaderboards.viewing,
+            BoardKey::new(MissionId::UrbanWarfare, DifficultyLevel::Veteran)
+        );
+    }
+}
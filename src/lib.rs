@@ -0,0 +1,86 @@
+// ==================== BATTLE OF CULIACÁN RTS LIBRARY ====================
+// Game modules shared by the windowed client (main.rs), the headless
+// simulation runner and the benchmarks.
+// =========================================================================
+
+pub mod accessibility;
+pub mod ai;
+pub mod air_assault;
+pub mod app_state;
+pub mod area_denial;
+pub mod assists;
+pub mod audio;
+pub mod auth;
+pub mod battle_plan;
+pub mod campaign;
+pub mod capture;
+pub mod checkpoints;
+pub mod cohesion;
+pub mod commander;
+pub mod components;
+pub mod config;
+pub mod convoy;
+pub mod coordination;
+pub mod corpse_system;
+pub mod crash_report;
+pub mod daily;
+pub mod damage;
+pub mod deployment;
+pub mod determinism;
+pub mod dialogue;
+pub mod diplomacy;
+pub mod economy;
+pub mod endings;
+pub mod engineering;
+pub mod entity_budget;
+pub mod environmental_systems;
+pub mod frame_pacing;
+pub mod game_systems;
+pub mod headless;
+pub mod hvt;
+pub mod indirect_fire;
+pub mod informants;
+pub mod intel_recruitment;
+pub mod intel_system;
+pub mod jamming;
+pub mod leaderboard;
+pub mod loading;
+pub mod localization;
+pub mod logging;
+pub mod map;
+pub mod multiplayer;
+pub mod narration;
+pub mod noise;
+pub mod objectives;
+pub mod order_of_battle;
+pub mod overwatch;
+pub mod political_system;
+pub mod prisoners;
+pub mod production;
+pub mod profile;
+pub mod protection;
+pub mod reinforcements;
+pub mod resources;
+pub mod rules_of_engagement;
+pub mod save;
+pub mod scoring;
+pub mod scripting;
+pub mod sentiment;
+pub mod simulation;
+pub mod social_feed;
+pub mod spawners;
+pub mod steam;
+pub mod systems;
+pub mod telemetry;
+pub mod test_harness;
+pub mod threat_assessment;
+pub mod tutorial;
+pub mod ui;
+pub mod unit_systems;
+pub mod upgrades;
+pub mod utils;
+pub mod wounded;
+pub mod wrecks;
+
+pub use headless::{run_headless, HeadlessOptions, HeadlessResult};
+pub use simulation::SimulationPlugin;
@@ -0,0 +1,317 @@
+use crate::audio::AudioManager;
+use crate::components::GamePhase;
+use crate::profile::ProfileManager;
+use crate::resources::{GameAssets, GameState};
+use crate::ui::{UiTheme, UnitAnimationLibrary};
+use crate::utils::create_menu_container;
+use bevy::app::AppExit;
+use bevy::asset::{LoadState, UntypedHandle};
+use bevy::prelude::*;
+
+// ==================== ASSET LOADING PLUGIN ====================
+
+// The game starts in GamePhase::Loading. Handles created by setup_assets and
+// setup_audio_system are tracked until the asset server settles them, and the
+// menus only open once every required asset is resident.
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AssetLoadingState>()
+            .add_systems(
+                PostStartup,
+                (collect_tracked_assets_system, spawn_loading_screen_system),
+            )
+            .add_systems(Update, asset_loading_system.run_if(in_loading_phase));
+    }
+}
+
+pub struct TrackedAsset {
+    pub path: String,
+    pub handle: UntypedHandle,
+    pub required: bool, // Sprites are required; audio falls back to console output
+}
+
+#[derive(Resource, Default)]
+pub struct AssetLoadingState {
+    pub tracked: Vec<TrackedAsset>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadingProgress {
+    pub total: usize,
+    pub loaded: usize,
+    pub missing_required: Vec<String>,
+    pub missing_optional: Vec<String>,
+}
+
+impl LoadingProgress {
+    pub fn from_states<'a>(
+        states: impl Iterator<Item = (&'a TrackedAsset, Option<LoadState>)>,
+    ) -> Self {
+        let mut progress = Self::default();
+        for (asset, state) in states {
+            progress.total += 1;
+            match state {
+                Some(LoadState::Loaded) => progress.loaded += 1,
+                Some(LoadState::Failed) if asset.required => {
+                    progress.missing_required.push(asset.path.clone())
+                }
+                Some(LoadState::Failed) => progress.missing_optional.push(asset.path.clone()),
+                _ => {}
+            }
+        }
+        progress
+    }
+
+    pub fn settled(&self) -> usize {
+        self.loaded + self.missing_required.len() + self.missing_optional.len()
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.settled() as f32 / self.total as f32
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.settled() == self.total
+    }
+}
+
+#[derive(Component)]
+pub struct LoadingScreen;
+
+#[derive(Component)]
+pub struct LoadingProgressFill;
+
+#[derive(Component)]
+pub struct LoadingStatusText;
+
+pub fn in_loading_phase(game_state: Res<GameState>) -> bool {
+    game_state.game_phase == GamePhase::Loading
+}
+
+// ==================== LOADING SYSTEMS ====================
+
+fn collect_tracked_assets_system(
+    mut loading_state: ResMut<AssetLoadingState>,
+    game_assets: Option<Res<GameAssets>>,
+    audio_manager: Option<Res<AudioManager>>,
+    animation_library: Option<Res<UnitAnimationLibrary>>,
+) {
+    let mut track = |handle: UntypedHandle, required: bool| {
+        if let Some(path) = handle.path() {
+            loading_state.tracked.push(TrackedAsset {
+                path: path.to_string(),
+                handle,
+                required,
+            });
+        }
+    };
+
+    if let Some(assets) = game_assets {
+        for sprite in [
+            &assets.sicario_sprite,
+            &assets.enforcer_sprite,
+            &assets.ovidio_sprite,
+            &assets.soldier_sprite,
+            &assets.special_forces_sprite,
+            &assets.vehicle_sprite,
+            &assets.roadblock_sprite,
+            &assets.safehouse_sprite,
+        ] {
+            track(sprite.clone().untyped(), true);
+        }
+    }
+
+    // Units without a sheet keep their static sprite
+    if let Some(library) = animation_library {
+        for sheet in library.sheet_images() {
+            track(sheet.clone().untyped(), false);
+        }
+    }
+
+    if let Some(audio) = audio_manager {
+        for sound in audio
+            .combat_sounds
+            .values()
+            .chain(audio.ui_sounds.values())
+            .chain(audio.ambient_sounds.values())
+            .chain(audio.radio_sounds.values())
+            .chain(audio.background_music.values())
+            .chain(audio.music_stems.values())
+        {
+            track(sound.clone().untyped(), false);
+        }
+    }
+
+    info!("⏳ Preloading {} assets...", loading_state.tracked.len());
+}
+
+fn spawn_loading_screen_system(mut commands: Commands, theme: Res<UiTheme>) {
+    let (container, title) = create_menu_container("BATTLE OF CULIACÁN", &theme);
+
+    commands
+        .spawn((container, LoadingScreen))
+        .with_children(|parent| {
+            parent.spawn(title);
+
+            // Progress bar track and fill
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(400.0),
+                        height: Val::Px(20.0),
+                        border: UiRect::all(Val::Px(2.0)),
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                    border_color: Color::WHITE.into(),
+                    ..default()
+                })
+                .with_children(|track| {
+                    track.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: Color::rgb(0.8, 0.6, 0.2).into(),
+                            ..default()
+                        },
+                        LoadingProgressFill,
+                    ));
+                });
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "Loading assets...",
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                ),
+                LoadingStatusText,
+            ));
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn asset_loading_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    loading_state: Res<AssetLoadingState>,
+    mut game_state: ResMut<GameState>,
+    profiles: Res<ProfileManager>,
+    input: Res<Input<KeyCode>>,
+    screen_query: Query<Entity, With<LoadingScreen>>,
+    mut fill_query: Query<&mut Style, With<LoadingProgressFill>>,
+    mut text_query: Query<&mut Text, With<LoadingStatusText>>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut reported: Local<bool>,
+) {
+    let progress = LoadingProgress::from_states(
+        loading_state
+            .tracked
+            .iter()
+            .map(|asset| (asset, asset_server.get_load_state(asset.handle.id()))),
+    );
+
+    if let Ok(mut style) = fill_query.get_single_mut() {
+        style.width = Val::Percent(progress.fraction() * 100.0);
+    }
+
+    // Missing sprites would leave units invisible - stop here and say which files
+    if !progress.missing_required.is_empty() {
+        if !*reported {
+            *reported = true;
+            error!(
+                "❌ Missing required assets: {}",
+                progress.missing_required.join(", ")
+            );
+            if let Ok(mut text) = text_query.get_single_mut() {
+                text.sections[0].value = format!(
+                    "❌ Missing required assets:\n{}\n\nCheck the assets/ directory. Press ESC to quit.",
+                    progress.missing_required.join("\n")
+                );
+                text.sections[0].style.color = Color::rgb(1.0, 0.3, 0.3);
+            }
+        }
+        if input.just_pressed(KeyCode::Escape) {
+            app_exit_events.send(AppExit);
+        }
+        return;
+    }
+
+    if !progress.is_complete() {
+        if let Ok(mut text) = text_query.get_single_mut() {
+            text.sections[0].value = format!(
+                "Loading assets... {}/{}",
+                progress.settled(),
+                progress.total
+            );
+        }
+        return;
+    }
+
+    if !progress.missing_optional.is_empty() {
+        warn!(
+            "🔇 Missing audio assets, falling back to console audio: {}",
+            progress.missing_optional.join(", ")
+        );
+    }
+
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    game_state.game_phase = if profiles.active.is_some() {
+        GamePhase::MainMenu
+    } else {
+        GamePhase::ProfileSelect
+    };
+    info!("✅ {} assets loaded", progress.loaded);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracked(path: &str, required: bool) -> TrackedAsset {
+        TrackedAsset {
+            path: path.to_string(),
+            handle: Handle::<Image>::default().untyped(),
+            required,
+        }
+    }
+
+    #[test]
+    fn test_loading_progress_separates_required_failures() {
+        let assets = [
+            tracked("sprites/units/sicario.png", true),
+            tracked("sprites/units/soldier.png", true),
+            tracked("audio/ui/warning.ogg", false),
+            tracked("audio/music/battle_theme.ogg", false),
+        ];
+        let states = [
+            Some(LoadState::Loaded),
+            Some(LoadState::Failed),
+            Some(LoadState::Failed),
+            Some(LoadState::Loading),
+        ];
+
+        let progress = LoadingProgress::from_states(assets.iter().zip(states));
+
+        assert_eq!(progress.loaded, 1);
+        assert_eq!(progress.missing_required, vec!["sprites/units/soldier.png"]);
+        assert_eq!(progress.missing_optional, vec!["audio/ui/warning.ogg"]);
+        assert_eq!(progress.fraction(), 0.75);
+        assert!(!progress.is_complete());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::config::mod_registry;
+use crate::profile::ProfileManager;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+// ==================== LOCALIZATION ====================
+
+// Display text lives in assets/localization/<language>.toml, nested tables
+// flattened to dotted keys (menu.new_campaign) with {name} placeholders filled
+// in by tr_args. Lookups try the active mod packs' strings first, then the
+// active language pack, then en-US, and finally show the key itself so a
+// missing string is obvious on screen. The language comes from the active
+// profile and can be switched from the main menu.
+
+pub const LOCALIZATION_DIR: &str = "assets/localization";
+pub const DEFAULT_LANGUAGE: &str = "en-US";
+// Language code and the name shown in the menu, in cycling order
+pub const LANGUAGES: [(&str, &str); 2] = [("en-US", "English"), ("es-MX", "Español (México)")];
+
+// Shipped copies, for when the files on disk are missing or won't parse
+const BUILTIN_PACKS: [(&str, &str); 2] = [
+    ("en-US", include_str!("../assets/localization/en-US.toml")),
+    ("es-MX", include_str!("../assets/localization/es-MX.toml")),
+];
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_language_setting_system);
+    }
+}
+
+#[derive(Default)]
+struct Localizer {
+    language: String,
+    strings: HashMap<String, String>,
+    fallback: HashMap<String, String>, // en-US
+}
+
+impl Localizer {
+    fn new(language: &str) -> Self {
+        let mut localizer = Self {
+            fallback: load_language_pack(DEFAULT_LANGUAGE),
+            ..default()
+        };
+        localizer.set_language(language);
+        localizer
+    }
+
+    fn set_language(&mut self, language: &str) {
+        let language = supported_language(language);
+        self.strings = if language == DEFAULT_LANGUAGE {
+            HashMap::new()
+        } else {
+            load_language_pack(language)
+        };
+        self.language = language.to_string();
+    }
+
+    fn lookup(&self, key: &str) -> Option<String> {
+        mod_registry()
+            .localize(&self.language, key)
+            .or_else(|| self.strings.get(key).map(String::as_str))
+            .or_else(|| self.fallback.get(key).map(String::as_str))
+            .map(str::to_string)
+    }
+}
+
+fn localizer() -> &'static RwLock<Localizer> {
+    static LOCALIZER: OnceLock<RwLock<Localizer>> = OnceLock::new();
+    LOCALIZER.get_or_init(|| RwLock::new(Localizer::new(&system_language())))
+}
+
+fn load_language_pack(language: &str) -> HashMap<String, String> {
+    let path = Path::new(LOCALIZATION_DIR).join(format!("{}.toml", language));
+    match read_language_pack(&path) {
+        Ok(strings) => strings,
+        Err(e) => {
+            warn!(
+                "Failed to load language pack {}: {}, using built-in strings",
+                path.display(),
+                e
+            );
+            let builtin = BUILTIN_PACKS
+                .iter()
+                .find(|(code, _)| *code == language)
+                .map_or("", |(_, pack)| pack);
+            toml::from_str(builtin)
+                .map(|pack| flatten_strings(&pack))
+                .unwrap_or_default()
+        }
+    }
+}
+
+fn read_language_pack(path: &Path) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    let pack: toml::Value = toml::from_str(&fs::read_to_string(path)?)?;
+    Ok(flatten_strings(&pack))
+}
+
+// Every string in a nested table by its dotted key, e.g. menu.title
+pub fn flatten_strings(value: &toml::Value) -> HashMap<String, String> {
+    fn collect(value: &toml::Value, prefix: &str, strings: &mut HashMap<String, String>) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, value) in table {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    collect(value, &path, strings);
+                }
+            }
+            toml::Value::String(text) => {
+                strings.insert(prefix.to_string(), text.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let mut strings = HashMap::new();
+    collect(value, "", &mut strings);
+    strings
+}
+
+// ==================== LOOKUPS ====================
+
+pub fn tr(key: &str) -> String {
+    try_tr(key).unwrap_or_else(|| key.to_string())
+}
+
+// tr with {name} placeholders replaced, e.g. tr_args("hud.wave", &[("wave", &3)])
+pub fn tr_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    format_message(&tr(key), args)
+}
+
+// For data-driven keys (place names, mod content) that may have no string
+pub fn try_tr(key: &str) -> Option<String> {
+    localizer().read().ok()?.lookup(key)
+}
+
+pub fn format_message(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+}
+
+// ==================== LANGUAGE SELECTION ====================
+
+pub fn current_language() -> String {
+    localizer()
+        .read()
+        .map(|localizer| localizer.language.clone())
+        .unwrap_or_else(|_| DEFAULT_LANGUAGE.to_string())
+}
+
+pub fn set_language(language: &str) {
+    if let Ok(mut localizer) = localizer().write() {
+        if localizer.language != supported_language(language) {
+            localizer.set_language(language);
+            info!("🌐 Language set to {}", language_name(&localizer.language));
+        }
+    }
+}
+
+// Maps "es", "es_MX.UTF-8" or "es-mx" onto a shipped pack; anything else is en-US
+pub fn supported_language(language: &str) -> &'static str {
+    let primary = language
+        .split(['_', '.', '-'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LANGUAGES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| code.split('-').next() == Some(primary.as_str()))
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
+
+pub fn language_name(language: &str) -> &'static str {
+    let language = supported_language(language);
+    LANGUAGES
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map_or("English", |(_, name)| name)
+}
+
+pub fn next_language(language: &str) -> &'static str {
+    let language = supported_language(language);
+    let index = LANGUAGES
+        .iter()
+        .position(|(code, _)| *code == language)
+        .unwrap_or(0);
+    LANGUAGES[(index + 1) % LANGUAGES.len()].0
+}
+
+// CULIACAN_LANG wins over the system locale; new profiles start with this
+pub fn system_language() -> String {
+    let locale = std::env::var("CULIACAN_LANG")
+        .or_else(|_| std::env::var("LANG"))
+        .unwrap_or_default();
+    supported_language(&locale).to_string()
+}
+
+// Copies the active profile's language
+pub fn apply_language_setting_system(profiles: Res<ProfileManager>) {
+    if !profiles.is_changed() {
+        return;
+    }
+    if let Some(profile) = profiles.active.as_ref() {
+        set_language(&profile.settings.language);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_packs_define_the_same_keys() {
+        let packs: Vec<HashMap<String, String>> = BUILTIN_PACKS
+            .iter()
+            .map(|(_, pack)| flatten_strings(&toml::from_str(pack).unwrap()))
+            .collect();
+        let english = &packs[0];
+        assert!(english.contains_key("menu.new_campaign"));
+
+        for (pack, (code, _)) in packs.iter().zip(BUILTIN_PACKS).skip(1) {
+            let mut missing: Vec<&String> = english
+                .keys()
+                .filter(|key| !pack.contains_key(*key))
+                .collect();
+            let mut extra: Vec<&String> = pack
+                .keys()
+                .filter(|key| !english.contains_key(*key))
+                .collect();
+            missing.sort();
+            extra.sort();
+            assert!(missing.is_empty(), "{} is missing {:?}", code, missing);
+            assert!(extra.is_empty(), "{} has unknown keys {:?}", code, extra);
+        }
+    }
+
+    #[test]
+    fn test_placeholders_and_language_matching() {
+        let message = format_message(
+            "Wave {wave} incoming! {count} units, wave {wave}",
+            &[("wave", &3), ("count", &"12")],
+        );
+        assert_eq!(message, "Wave 3 incoming! 12 units, wave 3");
+
+        assert_eq!(supported_language("es_MX.UTF-8"), "es-MX");
+        assert_eq!(supported_language("es"), "es-MX");
+        assert_eq!(supported_language("C"), "en-US");
+        assert_eq!(next_language("es-MX"), "en-US");
+    }
+}
@@ -0,0 +1,451 @@
+use bevy::prelude::*;
+use bevy::utils::tracing::field::{Field, Visit};
+use bevy::utils::tracing::{Event, Level, Subscriber};
+use chrono::Local;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock, TryLockError};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+// ==================== LOGGING ====================
+
+// install() replaces Bevy's LogPlugin in the windowed game. The filter starts
+// from RUST_LOG (or LogPlugin's default) and per-module levels can be changed
+// while playing with the console's log command. Every event that passes the
+// filter also lands in a ring buffer, tagged with the subsystem that logged
+// it - by module, or by the system span it was logged in - which feeds the
+// log viewer panel and crash reports.
+
+const LOG_BUFFER_LINES: usize = 500;
+const DEFAULT_LOG_FILTER: &str = "info,wgpu=error,naga=warn"; // Same as LogPlugin
+const VIEWER_LINES: usize = 18;
+
+static LOG_BUFFER: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+static LOG_SEQUENCE: AtomicU64 = AtomicU64::new(0); // Bumped per buffered entry
+static LOG_LEVELS: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+static BASE_FILTER: OnceLock<String> = OnceLock::new();
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogSubsystem {
+    Ai,
+    Intel,
+    Political,
+    Net,
+    Other,
+}
+
+impl LogSubsystem {
+    pub const FILTERABLE: [LogSubsystem; 4] = [
+        LogSubsystem::Ai,
+        LogSubsystem::Intel,
+        LogSubsystem::Political,
+        LogSubsystem::Net,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogSubsystem::Ai => "ai",
+            LogSubsystem::Intel => "intel",
+            LogSubsystem::Political => "political",
+            LogSubsystem::Net => "net",
+            LogSubsystem::Other => "other",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::FILTERABLE
+            .into_iter()
+            .find(|subsystem| subsystem.name().eq_ignore_ascii_case(name))
+    }
+
+    // Module paths the subsystem covers, as they appear in log targets
+    pub fn modules(self) -> &'static [&'static str] {
+        match self {
+            LogSubsystem::Ai => &["culiacan_rts::ai", "culiacan_rts::coordination"],
+            LogSubsystem::Intel => &["culiacan_rts::intel_system"],
+            LogSubsystem::Political => &["culiacan_rts::political_system"],
+            LogSubsystem::Net => &[
+                "culiacan_rts::multiplayer",
+                "culiacan_rts::auth",
+                "culiacan_rts::steam",
+            ],
+            LogSubsystem::Other => &[],
+        }
+    }
+
+    pub fn from_target(target: &str) -> Self {
+        Self::FILTERABLE
+            .into_iter()
+            .find(|subsystem| {
+                subsystem.modules().iter().any(|module| {
+                    target
+                        .strip_prefix(module)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+                })
+            })
+            .unwrap_or(LogSubsystem::Other)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub time: String,
+    pub level: Level,
+    pub target: String,
+    pub span: Option<&'static str>, // Outermost span, normally the system
+    pub subsystem: LogSubsystem,
+    pub message: String,
+}
+
+impl LogEntry {
+    pub fn line(&self) -> String {
+        match self.span {
+            Some(span) => format!(
+                "{} {} [{}] {}: {}",
+                self.time, self.level, span, self.target, self.message
+            ),
+            None => format!(
+                "{} {} {}: {}",
+                self.time, self.level, self.target, self.message
+            ),
+        }
+    }
+}
+
+pub fn install() {
+    let base = EnvFilter::try_from_default_env()
+        .map(|filter| filter.to_string())
+        .unwrap_or_else(|_| DEFAULT_LOG_FILTER.to_string());
+    let (filter, handle) = reload::Layer::new(EnvFilter::new(&base));
+    let _ = BASE_FILTER.set(base);
+
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(LogBufferLayer);
+    match subscriber.try_init() {
+        Ok(()) => {
+            let _ = FILTER_HANDLE.set(handle);
+        }
+        Err(e) => eprintln!(
+            "Logging was already set up, the log viewer and crash reports will be empty: {}",
+            e
+        ),
+    }
+}
+
+// Logging can happen while a lock is held (or during a panic), so nothing here
+// waits for one
+pub(crate) fn try_lock<T>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+    match mutex.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+pub fn recent_entries(count: usize, subsystem: Option<LogSubsystem>) -> Vec<LogEntry> {
+    let Some(buffer) = try_lock(&LOG_BUFFER) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<LogEntry> = buffer
+        .iter()
+        .rev()
+        .filter(|entry| subsystem.is_none_or(|subsystem| entry.subsystem == subsystem))
+        .take(count)
+        .cloned()
+        .collect();
+    entries.reverse();
+    entries
+}
+
+// ==================== RUNTIME LEVELS ====================
+
+// Base filter plus the per-module overrides, which are more specific than its
+// global level
+pub fn filter_directives(base: &str, levels: &BTreeMap<String, String>) -> String {
+    std::iter::once(base.to_string())
+        .chain(
+            levels
+                .iter()
+                .map(|(module, level)| format!("{}={}", module, level)),
+        )
+        .filter(|directive| !directive.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn valid_module(module: &str) -> bool {
+    !module.is_empty()
+        && module
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+// Module is a subsystem name (ai, intel, political, net) or a module path
+pub fn set_log_level(module: &str, level: &str) -> Result<String, String> {
+    let level = level
+        .parse::<LevelFilter>()
+        .map_err(|_| {
+            format!(
+                "unknown level '{}' - off, error, warn, info, debug or trace",
+                level
+            )
+        })?
+        .to_string()
+        .to_lowercase();
+    let modules: Vec<String> = match LogSubsystem::from_name(module) {
+        Some(subsystem) => subsystem.modules().iter().map(|m| m.to_string()).collect(),
+        None if valid_module(module) => vec![module.to_string()],
+        None => return Err(format!("'{}' isn't a subsystem or module path", module)),
+    };
+
+    let mut levels = LOG_LEVELS.lock().map_err(|e| e.to_string())?;
+    for module in modules {
+        levels.insert(module, level.clone());
+    }
+    apply_filter(&levels)
+}
+
+pub fn reset_log_levels() -> Result<String, String> {
+    let mut levels = LOG_LEVELS.lock().map_err(|e| e.to_string())?;
+    levels.clear();
+    apply_filter(&levels)
+}
+
+pub fn current_filter() -> String {
+    let base = BASE_FILTER.get().map(String::as_str).unwrap_or_default();
+    LOG_LEVELS
+        .lock()
+        .map(|levels| filter_directives(base, &levels))
+        .unwrap_or_default()
+}
+
+fn apply_filter(levels: &BTreeMap<String, String>) -> Result<String, String> {
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or("logging isn't set up in this mode")?;
+    let base = BASE_FILTER.get().map(String::as_str).unwrap_or_default();
+    let directives = filter_directives(base, levels);
+    let filter = EnvFilter::try_new(&directives).map_err(|e| e.to_string())?;
+    handle.reload(filter).map_err(|e| e.to_string())?;
+    Ok(directives)
+}
+
+// ==================== LOG CAPTURE ====================
+
+struct LogBufferLayer;
+
+impl<S> Layer<S> for LogBufferLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut message = String::new();
+        event.record(&mut MessageVisitor(&mut message));
+
+        // Shared helpers (sound cues, spawners) log under their own module, so
+        // the system span they were called from decides the subsystem
+        let span = ctx
+            .event_scope(event)
+            .and_then(|scope| scope.from_root().next())
+            .map(|span| span.metadata());
+        let subsystem = match LogSubsystem::from_target(metadata.target()) {
+            LogSubsystem::Other => span
+                .map(|span| LogSubsystem::from_target(span.target()))
+                .unwrap_or(LogSubsystem::Other),
+            subsystem => subsystem,
+        };
+
+        let entry = LogEntry {
+            time: Local::now().format("%H:%M:%S").to_string(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            span: span.map(|span| span.name()),
+            subsystem,
+            message,
+        };
+        if let Some(mut buffer) = try_lock(&LOG_BUFFER) {
+            if buffer.len() >= LOG_BUFFER_LINES {
+                buffer.pop_front();
+            }
+            buffer.push_back(entry);
+            LOG_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+struct MessageVisitor<'a>(&'a mut String);
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+// ==================== LOG VIEWER ====================
+
+pub struct LoggingPlugin;
+
+impl Plugin for LoggingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LogViewer>()
+            .add_systems(Update, log_viewer_ui_system);
+    }
+}
+
+// Opened and filtered from the console: logs [ai|intel|political|net|all|off]
+#[derive(Resource, Default)]
+pub struct LogViewer {
+    pub open: bool,
+    pub subsystem: Option<LogSubsystem>, // None shows everything
+    shown_sequence: u64,
+}
+
+#[derive(Component)]
+pub struct LogViewerPanel;
+
+#[derive(Component)]
+pub struct LogViewerText;
+
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::ERROR => Color::rgb(1.0, 0.35, 0.35),
+        Level::WARN => Color::rgb(1.0, 0.8, 0.3),
+        Level::INFO => Color::rgb(0.85, 0.85, 0.85),
+        _ => Color::rgb(0.55, 0.6, 0.7),
+    }
+}
+
+fn viewer_sections(viewer: &LogViewer) -> Vec<TextSection> {
+    let style = |color| TextStyle {
+        font_size: 14.0,
+        color,
+        ..default()
+    };
+    let title = format!(
+        "LOG [{}] - logs <ai|intel|political|net|all|off>\n",
+        viewer.subsystem.map(LogSubsystem::name).unwrap_or("all")
+    );
+    std::iter::once(TextSection::new(title, style(Color::rgb(0.6, 1.0, 0.6))))
+        .chain(
+            recent_entries(VIEWER_LINES, viewer.subsystem)
+                .into_iter()
+                .map(|entry| {
+                    TextSection::new(
+                        format!("{}\n", entry.line()),
+                        style(level_color(entry.level)),
+                    )
+                }),
+        )
+        .collect()
+}
+
+pub fn log_viewer_ui_system(
+    mut commands: Commands,
+    mut viewer: ResMut<LogViewer>,
+    panel_query: Query<Entity, With<LogViewerPanel>>,
+    mut text_query: Query<&mut Text, With<LogViewerText>>,
+) {
+    if !viewer.open {
+        for entity in panel_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let sequence = LOG_SEQUENCE.load(Ordering::Relaxed);
+    if !viewer.is_changed() && sequence == viewer.shown_sequence {
+        return;
+    }
+    viewer.bypass_change_detection().shown_sequence = sequence;
+
+    let sections = viewer_sections(&viewer);
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections = sections;
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(0.0),
+                    bottom: Val::Px(0.0),
+                    width: Val::Percent(60.0),
+                    height: Val::Percent(35.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+                z_index: ZIndex::Global(99), // Under the console
+                ..default()
+            },
+            LogViewerPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle::from_sections(sections), LogViewerText));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsystem_from_target() {
+        assert_eq!(
+            LogSubsystem::from_target("culiacan_rts::ai"),
+            LogSubsystem::Ai
+        );
+        assert_eq!(
+            LogSubsystem::from_target("culiacan_rts::multiplayer::social_system"),
+            LogSubsystem::Net
+        );
+        assert_eq!(
+            LogSubsystem::from_target("culiacan_rts::political_system"),
+            LogSubsystem::Political
+        );
+        // Prefixes only match whole module names
+        assert_eq!(
+            LogSubsystem::from_target("culiacan_rts::ai_extra"),
+            LogSubsystem::Other
+        );
+        assert_eq!(LogSubsystem::from_target("wgpu_core"), LogSubsystem::Other);
+        assert_eq!(LogSubsystem::from_name("Intel"), Some(LogSubsystem::Intel));
+    }
+
+    #[test]
+    fn test_filter_directives_append_overrides() {
+        let mut levels = BTreeMap::new();
+        assert_eq!(filter_directives("info", &levels), "info");
+
+        levels.insert("culiacan_rts::ai".to_string(), "debug".to_string());
+        levels.insert("culiacan_rts::auth".to_string(), "warn".to_string());
+        let directives = filter_directives("info,wgpu=error", &levels);
+        assert_eq!(
+            directives,
+            "info,wgpu=error,culiacan_rts::ai=debug,culiacan_rts::auth=warn"
+        );
+        assert!(EnvFilter::try_new(&directives).is_ok());
+        assert!(set_log_level("ai", "loud").is_err());
+    }
+}
@@ -6,84 +6,256 @@
 // attempt to capture Ovidio Guzmán López in Culiacán, Mexico.
 // =====================================================================
 
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::log::LogPlugin;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::AudioPlugin as KiraAudioPlugin;
 
 // Import our modular components
-mod ai;
-mod audio;
-mod auth;
-mod campaign;
-mod components;
-mod config;
-mod coordination;
-mod environmental_systems;
-mod game_systems;
-mod intel_system;
-mod multiplayer;
-mod political_system;
-mod resources;
-mod save;
-mod spawners;
-mod systems;
-mod ui;
-mod unit_systems;
-mod utils;
+use culiacan_rts::{
+    accessibility, ai, air_assault, app_state, area_denial, assists, audio, auth, battle_plan, campaign, capture, checkpoints, cohesion, commander, config, convoy, corpse_system, crash_report,
+    daily, deployment, determinism, dialogue, economy, endings, engineering, entity_budget, environmental_systems, frame_pacing, game_systems, headless, hvt, indirect_fire, informants, intel_recruitment, intel_system, jamming, leaderboard, loading, localization, logging,
+    map, multiplayer, narration, order_of_battle, overwatch, political_system, prisoners, profile, protection, reinforcements, resources, rules_of_engagement, save, scoring, scripting, social_feed, steam, systems,
+    telemetry, threat_assessment, tutorial, ui, upgrades, utils, wounded, wrecks, SimulationPlugin,
+};
 
-use ai::{ai_director_system, difficulty_settings_system};
+use accessibility::AccessibilityPlugin;
+use ai::difficulty_settings_system;
+use air_assault::AirAssaultPlugin;
+use app_state::{AppState, GameSet};
+use area_denial::{area_denial_order_system, AreaDenialPlugin};
+use assists::MissionAssistsPlugin;
 use audio::{
-    background_music_system, radio_chatter_system, setup_audio_system, spatial_audio_system,
+    adaptive_music_system, ambience_system, apply_audio_settings_system, background_music_system,
+    radio_chatter_system, reverb_reflection_system, setup_audio_system, spatial_audio_system,
+    tactical_sound_system, AudioManager,
 };
-use campaign::{campaign_system, Campaign};
-use config::{config_hotkeys_system, performance_monitor_system, setup_config_system};
-use coordination::{
-    advanced_tactical_ai_system,
-    communication_system,
-    formation_movement_system,
-    // squad_management_system,  // Temporarily disabled
+use auth::AuthSessionPlugin;
+use battle_plan::{battle_plan_input_system, plan_tool_inactive, BattlePlanPlugin};
+use campaign::campaign_system;
+use capture::CaptureSequencePlugin;
+use checkpoints::{checkpoint_order_system, RoadCheckpointPlugin};
+use cohesion::{squad_order_system, SquadCohesionPlugin};
+use commander::{commander_input_system, CommanderPowersPlugin};
+use config::{
+    apply_config_profile_system, apply_unit_catalog_system, balance_hot_reload_system,
+    config_hotkeys_system, performance_monitor_system, setup_config_system, BalanceWatcher,
 };
+use convoy::ConvoyPlugin;
+// use coordination::squad_management_system;  // Temporarily disabled
+use corpse_system::CorpseSystemPlugin;
+use crash_report::CrashReportPlugin;
+use daily::DailyChallengePlugin;
+use deployment::{ambush_order_system, deployment_commit_system, DeploymentPlan, DeploymentPlugin};
+use dialogue::DialoguePlugin;
+use economy::{recruitment_input_system, CartelEconomyPlugin};
+use endings::PoliticalEndingsPlugin;
+use engineering::{engineering_order_system, EngineeringPlugin};
+use entity_budget::EntityBudgetPlugin;
 use environmental_systems::{
-    spawn_weather_particles, trigger_weather_change, update_ambient_lighting,
-    update_environmental_time, update_weather_particles, EnvironmentalAmbientLight,
-    EnvironmentalState,
+    flashlight_beam_system, lights_out_order_system, spawn_weather_particles,
+    trigger_weather_change, update_ambient_lighting, update_environmental_time,
+    update_streetlights, update_weather_particles, EnvironmentalAmbientLight,
 };
+use frame_pacing::FramePacingPlugin;
 use game_systems::*;
+use headless::HeadlessOptions;
+use hvt::HvtTargetingPlugin;
+use indirect_fire::{mortar_order_system, IndirectFirePlugin};
+use informants::{vet_source_system, InformantVettingPlugin};
+use intel_recruitment::{intel_recruitment_system, IntelRecruitmentPlugin};
 use intel_system::IntelSystemPlugin;
+use jamming::{jammer_fix_system, JammingPlugin};
+use leaderboard::LeaderboardPlugin;
+use loading::LoadingPlugin;
+use localization::LocalizationPlugin;
+use logging::LoggingPlugin;
+use map::{
+    editor_inactive, spawn_city_map_system, CityLifePlugin, MapChunkPlugin, MapEditorPlugin,
+};
 // use multiplayer::MultiplayerSystemPlugin;  // Temporarily disabled
+use multiplayer::SocialSystemPlugin;
+use narration::NarrationPlugin;
+use order_of_battle::OrderOfBattlePlugin;
+use overwatch::{overwatch_order_system, SniperOverwatchPlugin};
 use political_system::PoliticalSystemPlugin;
-use resources::{not_in_menu_phase, *};
+use prisoners::{prisoner_exchange_system, PrisonerSystemPlugin};
+use profile::ProfileSystemPlugin;
+use protection::{bodyguard_assignment_system, panic_relocation_system, OvidioProtectionPlugin};
+use reinforcements::{reinforcement_call_system, ReinforcementCallPlugin};
+use resources::*;
+use rules_of_engagement::RulesOfEngagementPlugin;
+use save::{CloudSyncPlugin, SaveSystemPlugin};
+use scoring::MissionScoringPlugin;
+use scripting::ScriptingPlugin;
+use social_feed::{social_feed_toggle_system, SocialFeedPlugin};
+use steam::SteamPlugin;
 use systems::*;
+use telemetry::TelemetryPlugin;
+use threat_assessment::{analyze_target_system, ThreatAssessmentPlugin};
+use tutorial::TutorialPlugin;
 use ui::*;
+use upgrades::{upgrade_input_system, MissionUpgradesPlugin};
 use utils::{
     // adaptive_ai_scheduler_system, optimized_unit_ai_system,  // Temporarily disabled
+    atlas_sprite_system,
+    build_sprite_atlas_system,
+    obstacle_grid_system,
     setup_ai_optimizer,
     setup_particle_pool,
     update_pooled_particles_system,
+    DecalPlugin,
+    ObstacleGrid,
+    ScreenFeedbackPlugin,
+    SimulationSet,
+    SpriteAtlas,
 };
+use wounded::WoundedPlugin;
+use wrecks::WreckSalvagePlugin;
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // --portable keeps saves, config and reports in a data folder beside the binary
+    utils::set_portable(args.iter().any(|arg| arg == "--portable"));
+    // --check-determinism runs the same seeded mission twice and diffs every tick
+    if args.iter().any(|arg| arg == "--check-determinism") {
+        let options = match HeadlessOptions::from_args(&args) {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        };
+        match determinism::check_and_report(&options) {
+            Ok(true) => return,
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Determinism check failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    // --headless runs a scripted mission without window or audio and prints a JSON result
+    if args.iter().any(|arg| arg == "--headless") {
+        let options = match HeadlessOptions::from_args(&args) {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        };
+        if let Err(e) = headless::run_and_report(&options) {
+            eprintln!("Headless run failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Logging and the panic hook, before anything that could crash
+    logging::install();
+    // Files from builds that kept them in ~/.culiacan-rts, before anything reads them
+    match utils::migrate_legacy_data() {
+        Ok(0) => {}
+        Ok(moved) => info!("📦 Moved {} files out of ~/.culiacan-rts", moved),
+        Err(e) => warn!("Could not move files from ~/.culiacan-rts: {}", e),
+    }
+    crash_report::install();
+
     App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Battle of Culiacán - El Culiacanazo RTS".into(),
-                resolution: (1400.0, 900.0).into(),
-                resizable: true,
-                present_mode: bevy::window::PresentMode::AutoVsync,
-                mode: bevy::window::WindowMode::Windowed,
-                visible: true,
-                ..default()
-            }),
-            ..default()
-        }))
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: "Battle of Culiacán - El Culiacanazo RTS".into(),
+                        resolution: (1400.0, 900.0).into(),
+                        resizable: true,
+                        present_mode: bevy::window::PresentMode::AutoVsync,
+                        mode: bevy::window::WindowMode::Windowed,
+                        visible: true,
+                        ..default()
+                    }),
+                    ..default()
+                })
+                .disable::<LogPlugin>(), // logging::install sets up logging
+        )
+        .add_plugins(CrashReportPlugin)
+        .add_plugins(LoggingPlugin)
+        .add_plugins(FrameTimeDiagnosticsPlugin)
         .add_plugins(KiraAudioPlugin)
+        .add_plugins(SimulationPlugin)
+        .add_plugins(LoadingPlugin)
+        .add_plugins(AuthSessionPlugin)
         .add_plugins(IntelSystemPlugin)
+        .add_plugins(IntelRecruitmentPlugin)
+        .add_plugins(JammingPlugin)
+        .add_plugins(InformantVettingPlugin)
+        .add_plugins(OrderOfBattlePlugin)
         .add_plugins(PoliticalSystemPlugin)
+        .add_plugins(PoliticalEndingsPlugin)
+        .add_plugins(RulesOfEngagementPlugin)
+        .add_plugins(CorpseSystemPlugin)
+        .add_plugins(OvidioProtectionPlugin)
+        .add_plugins(CaptureSequencePlugin)
+        .add_plugins(HvtTargetingPlugin)
+        .add_plugins(PrisonerSystemPlugin)
+        .add_plugins(CartelEconomyPlugin)
+        .add_plugins(ReinforcementCallPlugin)
+        .add_plugins(MissionUpgradesPlugin)
+        .add_plugins(CommanderPowersPlugin)
+        .add_plugins(RoadCheckpointPlugin)
+        .add_plugins(WreckSalvagePlugin)
+        .add_plugins(EngineeringPlugin)
+        .add_plugins(SniperOverwatchPlugin)
+        .add_plugins(AreaDenialPlugin)
+        .add_plugins(ConvoyPlugin)
+        .add_plugins(AirAssaultPlugin)
+        .add_plugins(IndirectFirePlugin)
+        .add_plugins(WoundedPlugin)
+        .add_plugins(SquadCohesionPlugin)
+        // Missions played at the screen open with a paused deployment
+        .insert_resource(DeploymentPlan::interactive())
+        .add_plugins(DeploymentPlugin)
+        .add_plugins(BattlePlanPlugin)
+        .add_plugins(ThreatAssessmentPlugin)
+        .add_plugins(SocialFeedPlugin)
+        .add_plugins(MissionScoringPlugin)
+        .add_plugins(DailyChallengePlugin)
+        .add_plugins(MissionAssistsPlugin)
+        .add_plugins(ProfileSystemPlugin)
+        .add_plugins(LocalizationPlugin)
+        .add_plugins(UiThemePlugin)
+        .add_plugins(AccessibilityPlugin)
+        .add_plugins(NarrationPlugin)
+        .add_plugins(SocialSystemPlugin)
+        .add_plugins(SaveSystemPlugin)
+        .add_plugins(LeaderboardPlugin)
+        .add_plugins(CloudSyncPlugin)
+        .add_plugins(SteamPlugin) // After CloudSyncPlugin so Steam Cloud can replace its backend
+        .add_plugins(CityLifePlugin)
+        .add_plugins(MapEditorPlugin)
+        .add_plugins(MapChunkPlugin)
+        .add_plugins(DecalPlugin)
+        .add_plugins(ScreenFeedbackPlugin)
+        .add_plugins(FramePacingPlugin)
+        .add_plugins(EntityBudgetPlugin)
+        .add_plugins(UnitAnimationPlugin)
+        .add_plugins(ScriptingPlugin)
+        .add_plugins(TutorialPlugin)
+        .add_plugins(DialoguePlugin)
+        .add_plugins(TelemetryPlugin)
+        .add_plugins(DevConsolePlugin)
+        .add_plugins(OrderFeedbackPlugin)
+        .add_plugins(UnitInfoPlugin)
+        .add_plugins(CasterToolsPlugin)
+        .add_plugins(CampaignMapPlugin)
         //.add_plugins(MultiplayerSystemPlugin)  // Temporarily disabled until implemented
-        .init_resource::<GameState>()
-        .init_resource::<AiDirector>()
-        .init_resource::<Campaign>()
-        .init_resource::<EnvironmentalState>()
         .init_resource::<EnvironmentalAmbientLight>()
+        .init_resource::<BalanceWatcher>()
+        .init_resource::<IndicatorPool>()
+        .init_resource::<FormationDrag>()
+        .init_resource::<MenuFocus>()
+        .init_resource::<SpriteAtlas>()
+        .init_resource::<ObstacleGrid>()
         .add_systems(
             Startup,
             (
@@ -93,6 +265,7 @@ fn main() {
                 setup_audio_system,
                 setup_particle_pool,
                 setup_ai_optimizer,
+                spawn_city_map_system,
             ),
         )
         .add_systems(
@@ -100,79 +273,135 @@ fn main() {
             setup_game
                 .run_if(resource_exists::<GameAssets>())
                 .run_if(not(resource_exists::<GameSetupComplete>()))
-                .run_if(not_in_menu_phase),
+                // AppState only follows the phase from the first frame on
+                .run_if(|game_state: Res<GameState>| {
+                    game_state.game_phase.app_state() == AppState::InGame
+                }),
+        )
+        .add_systems(
+            Update,
+            (balance_hot_reload_system, apply_unit_catalog_system).chain(),
+        )
+        .add_systems(Update, apply_config_profile_system)
+        .add_systems(
+            Update,
+            (build_sprite_atlas_system, atlas_sprite_system)
+                .chain()
+                .run_if(resource_exists::<GameAssets>()),
         )
-        .add_systems(Update, main_menu_system)
-        .add_systems(Update, mission_briefing_system)
-        .add_systems(Update, victory_defeat_system)
         .add_systems(
             Update,
             (
-                camera_control_system,
-                unit_selection_system,
-                selection_indicator_system,
-                target_indicator_system,
-                minimap_system,
-                mission_system,
-                campaign_system,
-                ai_director_system,
+                apply_audio_settings_system,
+                obstacle_grid_system,
+                tactical_sound_system,
+                ambience_system,
+                adaptive_music_system,
+                reverb_reflection_system,
             )
-                .run_if(resource_exists::<GameSetupComplete>()),
+                .chain()
+                .run_if(resource_exists::<AudioManager>()),
         )
         .add_systems(
             Update,
-            wave_spawner_system.run_if(resource_exists::<GameSetupComplete>()),
+            (
+                menu_focus_system,
+                main_menu_system,
+                menu_focus_highlight_system,
+            )
+                .chain(),
         )
+        .add_systems(Update, mission_briefing_system)
+        .add_systems(Update, victory_defeat_system)
         .add_systems(
             Update,
             (
-                formation_movement_system,
-                communication_system,
-                advanced_tactical_ai_system,
-                pathfinding_system,
-                movement_system,
+                camera_control_system,
+                unit_selection_system
+                    .run_if(editor_inactive)
+                    .run_if(plan_tool_inactive),
+                custom_formation_hotkey_system.in_set(GameSet::InGame),
+                hold_area_order_system.run_if(editor_inactive),
+                bodyguard_assignment_system.in_set(GameSet::InGame),
+                panic_relocation_system.in_set(GameSet::InGame),
+                prisoner_exchange_system.in_set(GameSet::InGame),
+                recruitment_input_system.in_set(GameSet::InGame),
+                reinforcement_call_system.in_set(GameSet::InGame),
+                upgrade_input_system.in_set(GameSet::InGame),
+                commander_input_system.in_set(GameSet::InGame),
+                intel_recruitment_system.in_set(GameSet::InGame),
+                checkpoint_order_system.in_set(GameSet::InGame),
+                (
+                    engineering_order_system,
+                    overwatch_order_system,
+                    area_denial_order_system,
+                    lights_out_order_system,
+                    mortar_order_system,
+                    squad_order_system,
+                    ambush_order_system,
+                    deployment_commit_system,
+                    battle_plan_input_system,
+                    analyze_target_system,
+                    social_feed_toggle_system,
+                    jammer_fix_system,
+                    vet_source_system,
+                )
+                    .in_set(GameSet::InGame)
+                    .run_if(editor_inactive),
+                selection_indicator_system,
+                target_indicator_system,
+                minimap_system,
+                mission_system,
+                campaign_system,
                 difficulty_settings_system,
             )
-                .run_if(resource_exists::<GameSetupComplete>()),
+                .in_set(GameSet::Session),
         )
         .add_systems(
             Update,
             (
-                combat_system,
                 ability_system,
-                ability_effect_system,
                 health_bar_system,
                 update_pooled_particles_system,
                 damage_indicator_system,
+                particle_system,
                 sprite_animation_system,
                 movement_animation_system,
             )
-                .run_if(resource_exists::<GameSetupComplete>()),
+                .in_set(GameSet::Session),
         )
         .add_systems(
             Update,
             (
                 ui_update_system,
                 game_phase_system,
-                handle_input,
+                handle_input.run_if(editor_inactive),
                 background_music_system,
                 radio_chatter_system,
                 spatial_audio_system,
             )
-                .run_if(resource_exists::<GameSetupComplete>()),
+                .in_set(GameSet::Session),
         )
         .add_systems(
             Update,
             (
                 update_environmental_time,
                 update_ambient_lighting,
+                update_streetlights,
+                flashlight_beam_system,
                 spawn_weather_particles,
                 update_weather_particles,
-                trigger_weather_change,
                 config_hotkeys_system,
                 performance_monitor_system,
             )
-                .run_if(resource_exists::<GameSetupComplete>()),
+                .in_set(GameSet::Session),
+        )
+        // Weather changes movement and combat, so it turns over on the simulation tick
+        .add_systems(
+            FixedUpdate,
+            trigger_weather_change
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
         )
         .run();
 }
@@ -0,0 +1,586 @@
+use crate::app_state::GameSet;
+use crate::components::Unit;
+use crate::map::{CityMap, PropKind, TileKind};
+use crate::utils::{DecalEvent, DecalKind};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use rand::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+// ==================== CITY LIFE ====================
+
+// Non-combat ambience on top of the city map: civilian traffic on the roads,
+// stray dogs and market stalls. Streetlights are handled by the environment
+// systems. None of it affects the simulation, so it only runs in the client.
+
+const MAX_CARS: f32 = 12.0; // At full performance budget
+const MAX_DOGS: f32 = 6.0;
+const SPAWN_INTERVAL: f32 = 1.5;
+const TARGET_FPS: f64 = 55.0; // Full density at or above this
+const LOW_FPS: f64 = 30.0; // Minimum density at or below this
+const MIN_BUDGET: f32 = 0.25;
+
+const CAR_SPEED: f32 = 70.0;
+const CAR_ACCELERATION: f32 = 40.0;
+const CAR_BRAKING: f32 = 120.0;
+const CAR_FLEE_SPEED: f32 = 110.0; // Reversing back out the way they came
+const HALT_RADIUS: f32 = 320.0; // Traffic stops when fighting is this close
+const FLEE_RADIUS: f32 = 140.0;
+const HALT_PATIENCE: f32 = 4.0; // Seconds stopped near a firefight before turning back
+
+const DOG_SPEED: f32 = 35.0;
+const DOG_FLEE_SPEED: f32 = 120.0;
+const DOG_WANDER_RADIUS: f32 = 90.0;
+const DOG_FLEE_RADIUS: f32 = 250.0;
+const DOG_CALM_RADIUS: f32 = 400.0;
+
+const STALL_SHUTTER_RADIUS: f32 = 300.0;
+
+const CAR_Z: f32 = -2.0;
+const DOG_Z: f32 = -1.5;
+const STALL_Z: f32 = -7.0;
+
+pub struct CityLifePlugin;
+
+impl Plugin for CityLifePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CityLifeDirector>()
+            .add_systems(Startup, spawn_market_stalls)
+            .add_systems(
+                Update,
+                (
+                    city_life_director_system,
+                    civilian_car_system,
+                    stray_dog_system,
+                    market_stall_system,
+                )
+                    .in_set(GameSet::InGame),
+            );
+    }
+}
+
+// ==================== ROAD SPLINES ====================
+
+// Catmull-Rom curve through consecutive road tile centres, so cars round the
+// corners instead of snapping through them. Parameter t runs from 0 to
+// segments(), one unit per tile.
+#[derive(Clone, Debug)]
+pub struct RoadSpline {
+    points: Vec<Vec2>,
+}
+
+impl RoadSpline {
+    pub fn new(points: Vec<Vec2>) -> Self {
+        Self { points }
+    }
+
+    pub fn segments(&self) -> f32 {
+        self.points.len().saturating_sub(1) as f32
+    }
+
+    pub fn position(&self, t: f32) -> Vec2 {
+        let last = self.points.len() - 1;
+        if last == 0 {
+            return self.points[0];
+        }
+        let t = t.clamp(0.0, self.segments());
+        let i = (t.floor() as usize).min(last - 1);
+        let u = t - i as f32;
+
+        let p0 = self.points[i.saturating_sub(1)];
+        let p1 = self.points[i];
+        let p2 = self.points[i + 1];
+        let p3 = self.points[(i + 2).min(last)];
+
+        0.5 * (2.0 * p1
+            + (p2 - p0) * u
+            + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * u * u
+            + (3.0 * p1 - p0 - 3.0 * p2 + p3) * u * u * u)
+    }
+
+    pub fn direction(&self, t: f32) -> Vec2 {
+        (self.position(t + 0.05) - self.position(t - 0.05)).normalize_or_zero()
+    }
+}
+
+// Road tiles on the edge of the map, where traffic enters and leaves
+pub fn road_entrances(city_map: &CityMap) -> Vec<(usize, usize)> {
+    city_map
+        .tiles()
+        .filter(|(col, row, kind)| {
+            *kind == TileKind::Road
+                && (*col == 0
+                    || *row == 0
+                    || *col == city_map.width - 1
+                    || *row == city_map.height - 1)
+        })
+        .map(|(col, row, _)| (col, row))
+        .collect()
+}
+
+// Shortest drive between two road tiles, staying on the road
+pub fn road_route(
+    city_map: &CityMap,
+    from: (usize, usize),
+    to: (usize, usize),
+) -> Option<RoadSpline> {
+    let is_road = |(col, row): (usize, usize)| {
+        col < city_map.width && row < city_map.height && city_map.kind(col, row) == TileKind::Road
+    };
+    if !is_road(from) || !is_road(to) {
+        return None;
+    }
+
+    let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(tile) = queue.pop_front() {
+        if tile == to {
+            break;
+        }
+        let (col, row) = tile;
+        let neighbors = [
+            (col.wrapping_sub(1), row),
+            (col + 1, row),
+            (col, row.wrapping_sub(1)),
+            (col, row + 1),
+        ];
+        for next in neighbors {
+            if next != from && is_road(next) && !came_from.contains_key(&next) {
+                came_from.insert(next, tile);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    if from != to && !came_from.contains_key(&to) {
+        return None;
+    }
+
+    let mut tiles = vec![to];
+    while let Some(previous) = came_from.get(tiles.last().unwrap()) {
+        tiles.push(*previous);
+    }
+    tiles.reverse();
+
+    Some(RoadSpline::new(
+        tiles
+            .into_iter()
+            .map(|(col, row)| city_map.tile_center(col, row))
+            .collect(),
+    ))
+}
+
+// ==================== AMBIENT DIRECTOR ====================
+
+#[derive(Resource)]
+pub struct CityLifeDirector {
+    pub budget: f32, // 0-1 share of the maximum density the frame rate allows
+    spawn_timer: Timer,
+}
+
+impl Default for CityLifeDirector {
+    fn default() -> Self {
+        Self {
+            budget: 1.0,
+            spawn_timer: Timer::from_seconds(SPAWN_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl CityLifeDirector {
+    pub fn max_cars(&self) -> usize {
+        (MAX_CARS * self.budget).round() as usize
+    }
+
+    pub fn max_dogs(&self) -> usize {
+        (MAX_DOGS * self.budget).round() as usize
+    }
+}
+
+// Ambience is the first thing to go when the frame rate drops
+pub fn performance_budget(fps: Option<f64>) -> f32 {
+    match fps {
+        Some(fps) => {
+            let headroom = ((fps - LOW_FPS) / (TARGET_FPS - LOW_FPS)).clamp(0.0, 1.0) as f32;
+            MIN_BUDGET + (1.0 - MIN_BUDGET) * headroom
+        }
+        None => 1.0,
+    }
+}
+
+// Where shots are being exchanged right now
+fn combat_hotspots(unit_query: &Query<(&Unit, &Transform)>) -> Vec<Vec2> {
+    unit_query
+        .iter()
+        .filter(|(unit, _)| unit.health > 0.0 && unit.target.is_some())
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect()
+}
+
+fn nearest_fight(position: Vec2, hotspots: &[Vec2]) -> Option<(Vec2, f32)> {
+    hotspots
+        .iter()
+        .map(|hotspot| (*hotspot, hotspot.distance(position)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn city_life_director_system(
+    mut commands: Commands,
+    mut director: ResMut<CityLifeDirector>,
+    city_map: Res<CityMap>,
+    diagnostics: Option<Res<DiagnosticsStore>>,
+    unit_query: Query<(&Unit, &Transform)>,
+    car_query: Query<(), With<CivilianCar>>,
+    dog_query: Query<(), With<StrayDog>>,
+    time: Res<Time>,
+) {
+    let fps = diagnostics
+        .as_ref()
+        .and_then(|diagnostics| diagnostics.get(FrameTimeDiagnosticsPlugin::FPS))
+        .and_then(|fps| fps.smoothed());
+    // Dogs don't leave on their own; the entity budget trims them when this shrinks
+    director.budget = performance_budget(fps);
+
+    if !director.spawn_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let hotspots = combat_hotspots(&unit_query);
+    let mut rng = thread_rng();
+
+    if car_query.iter().count() < director.max_cars() {
+        let entrances = road_entrances(&city_map);
+        let ends: Vec<_> = entrances.choose_multiple(&mut rng, 2).copied().collect();
+        if let [from, to] = ends[..] {
+            // No new traffic into a street that's already under fire
+            let entry = city_map.tile_center(from.0, from.1);
+            let safe = nearest_fight(entry, &hotspots).is_none_or(|(_, d)| d > HALT_RADIUS);
+            if let Some(spline) = road_route(&city_map, from, to).filter(|_| safe) {
+                spawn_civilian_car(&mut commands, spline, &mut rng);
+            }
+        }
+    }
+
+    if dog_query.iter().count() < director.max_dogs() {
+        let open_tiles: Vec<Vec2> = city_map
+            .tiles()
+            .filter(|(_, _, kind)| matches!(kind, TileKind::Ground | TileKind::Park))
+            .map(|(col, row, _)| city_map.tile_center(col, row))
+            .filter(|position| {
+                nearest_fight(*position, &hotspots).is_none_or(|(_, d)| d > DOG_CALM_RADIUS)
+            })
+            .collect();
+        if let Some(home) = open_tiles.choose(&mut rng) {
+            spawn_stray_dog(&mut commands, *home);
+        }
+    }
+}
+
+// ==================== CIVILIAN TRAFFIC ====================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CarState {
+    Driving,
+    Halted,
+    Fleeing,
+}
+
+#[derive(Component)]
+pub struct CivilianCar {
+    route: RoadSpline,
+    progress: f32, // Spline parameter
+    speed: f32,
+    pub state: CarState,
+    halted_for: f32,
+}
+
+// Drivers stop when shooting breaks out down the street and turn back if it
+// gets close or doesn't let up. Once fleeing they don't come back.
+pub fn car_reaction(state: CarState, halted_for: f32, nearest_fight: Option<f32>) -> CarState {
+    if state == CarState::Fleeing {
+        return CarState::Fleeing;
+    }
+    match nearest_fight {
+        Some(distance) if distance < FLEE_RADIUS => CarState::Fleeing,
+        Some(distance) if distance < HALT_RADIUS => {
+            if halted_for > HALT_PATIENCE {
+                CarState::Fleeing
+            } else {
+                CarState::Halted
+            }
+        }
+        _ => CarState::Driving,
+    }
+}
+
+fn spawn_civilian_car(commands: &mut Commands, route: RoadSpline, rng: &mut ThreadRng) {
+    let colors = [
+        Color::rgb(0.75, 0.75, 0.78),
+        Color::rgb(0.2, 0.25, 0.45),
+        Color::rgb(0.85, 0.85, 0.8),
+        Color::rgb(0.5, 0.1, 0.1),
+        Color::rgb(0.15, 0.15, 0.15),
+    ];
+    let start = route.position(0.0);
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: *colors.choose(rng).unwrap(),
+                custom_size: Some(Vec2::new(28.0, 14.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(start.extend(CAR_Z)),
+            ..default()
+        },
+        CivilianCar {
+            route,
+            progress: 0.0,
+            speed: CAR_SPEED * rng.gen_range(0.8..1.2),
+            state: CarState::Driving,
+            halted_for: 0.0,
+        },
+    ));
+}
+
+pub fn civilian_car_system(
+    mut commands: Commands,
+    mut car_query: Query<(Entity, &mut CivilianCar, &mut Transform), Without<Unit>>,
+    unit_query: Query<(&Unit, &Transform)>,
+    city_map: Res<CityMap>,
+    mut decal_events: EventWriter<DecalEvent>,
+    time: Res<Time>,
+) {
+    let hotspots = combat_hotspots(&unit_query);
+    let delta = time.delta_seconds();
+
+    for (entity, mut car, mut transform) in car_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let fight = nearest_fight(position, &hotspots).map(|(_, distance)| distance);
+        let previous_state = car.state;
+        car.state = car_reaction(car.state, car.halted_for, fight);
+
+        // Skid marks where a driver slams on the brakes
+        if previous_state == CarState::Driving && car.state != CarState::Driving {
+            let heading = car.route.direction(car.progress);
+            decal_events.send(DecalEvent {
+                kind: DecalKind::TireTrack,
+                position,
+                rotation: Some(heading.y.atan2(heading.x)),
+                scale: 1.5,
+            });
+        }
+
+        let (target_speed, rate) = match car.state {
+            CarState::Driving => (CAR_SPEED, CAR_ACCELERATION),
+            CarState::Halted => (0.0, CAR_BRAKING),
+            CarState::Fleeing => (-CAR_FLEE_SPEED, CAR_BRAKING),
+        };
+        car.speed += (target_speed - car.speed).clamp(-rate * delta, rate * delta);
+        car.halted_for = if car.state == CarState::Halted {
+            car.halted_for + delta
+        } else {
+            0.0
+        };
+
+        car.progress += car.speed * delta / city_map.tile_size;
+        let left_map = car.progress >= car.route.segments()
+            || (car.progress <= 0.0 && car.state == CarState::Fleeing);
+        if left_map {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let direction = car.route.direction(car.progress);
+        transform.translation = car.route.position(car.progress).extend(CAR_Z);
+        if direction != Vec2::ZERO {
+            transform.rotation = Quat::from_rotation_z(direction.y.atan2(direction.x));
+        }
+    }
+}
+
+// ==================== STRAY DOGS ====================
+
+#[derive(Component)]
+pub struct StrayDog {
+    home: Vec2,
+    target: Vec2,
+    pause: f32,
+    pub fleeing: bool,
+}
+
+fn spawn_stray_dog(commands: &mut Commands, home: Vec2) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.55, 0.4, 0.25),
+                custom_size: Some(Vec2::new(10.0, 6.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(home.extend(DOG_Z)),
+            ..default()
+        },
+        StrayDog {
+            home,
+            target: home,
+            pause: 0.0,
+            fleeing: false,
+        },
+    ));
+}
+
+pub fn stray_dog_system(
+    mut dog_query: Query<(&mut StrayDog, &mut Transform), Without<Unit>>,
+    unit_query: Query<(&Unit, &Transform)>,
+    city_map: Res<CityMap>,
+    time: Res<Time>,
+) {
+    let hotspots = combat_hotspots(&unit_query);
+    let delta = time.delta_seconds();
+    let mut rng = thread_rng();
+
+    for (mut dog, mut transform) in dog_query.iter_mut() {
+        let position = transform.translation.truncate();
+
+        match nearest_fight(position, &hotspots) {
+            Some((fight, distance)) if distance < DOG_FLEE_RADIUS => {
+                // Bolt directly away from the gunfire
+                let away = (position - fight).normalize_or_zero();
+                dog.target = position + away * DOG_WANDER_RADIUS;
+                dog.fleeing = true;
+            }
+            Some((_, distance)) if dog.fleeing && distance < DOG_CALM_RADIUS => {}
+            _ if dog.fleeing => {
+                // Settle wherever it ended up
+                dog.fleeing = false;
+                dog.home = position;
+                dog.target = position;
+            }
+            _ => {}
+        }
+
+        let speed = if dog.fleeing {
+            DOG_FLEE_SPEED
+        } else {
+            DOG_SPEED
+        };
+        let to_target = dog.target - position;
+        if to_target.length() > 2.0 {
+            let step = to_target.normalize() * speed * delta;
+            let next = position + step.clamp_length_max(to_target.length());
+            if city_map.is_walkable(next) {
+                transform.translation = next.extend(DOG_Z);
+                continue;
+            }
+            dog.target = position; // Walked into a wall - pick somewhere else
+        }
+
+        dog.pause -= delta;
+        if dog.pause <= 0.0 && !dog.fleeing {
+            let offset = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+            dog.target = dog.home + offset * DOG_WANDER_RADIUS;
+            dog.pause = rng.gen_range(1.0..4.0);
+        }
+    }
+}
+
+// ==================== MARKET STALLS ====================
+
+#[derive(Component)]
+pub struct MarketStall {
+    pub open: bool,
+}
+
+pub fn spawn_market_stalls(mut commands: Commands, city_map: Res<CityMap>) {
+    for position in city_map.prop_positions(PropKind::MarketStall) {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.85, 0.35, 0.2),
+                    custom_size: Some(Vec2::new(36.0, 24.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(STALL_Z)),
+                ..default()
+            },
+            MarketStall { open: true },
+        ));
+    }
+}
+
+// Vendors pull the shutters down once the shooting reaches the market and
+// don't reopen for the rest of the mission
+pub fn market_stall_system(
+    mut stall_query: Query<(&mut MarketStall, &mut Sprite, &Transform)>,
+    unit_query: Query<(&Unit, &Transform)>,
+) {
+    let hotspots = combat_hotspots(&unit_query);
+
+    for (mut stall, mut sprite, transform) in stall_query.iter_mut() {
+        if !stall.open {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        if nearest_fight(position, &hotspots).is_some_and(|(_, d)| d < STALL_SHUTTER_RADIUS) {
+            stall.open = false;
+            sprite.color = Color::rgb(0.4, 0.4, 0.42);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_road_route_stays_on_road() {
+        let map = CityMap::from_json(
+            r#"{
+                "name": "Test",
+                "tile_size": 10.0,
+                "tiles": ["=...", "====", "...="],
+                "districts": ["....", "....", "...."],
+                "neighborhoods": []
+            }"#,
+        )
+        .unwrap();
+
+        let entrances = road_entrances(&map);
+        assert!(entrances.contains(&(0, 0)) && entrances.contains(&(3, 2)));
+
+        let route = road_route(&map, (0, 0), (3, 2)).unwrap();
+        assert_eq!(route.segments(), 5.0);
+        assert_eq!(route.position(0.0), map.tile_center(0, 0));
+        assert_eq!(route.position(route.segments()), map.tile_center(3, 2));
+        assert!(road_route(&map, (0, 0), (1, 0)).is_none());
+    }
+
+    #[test]
+    fn test_traffic_reacts_to_fighting() {
+        assert_eq!(
+            car_reaction(CarState::Driving, 0.0, None),
+            CarState::Driving
+        );
+        assert_eq!(
+            car_reaction(CarState::Driving, 0.0, Some(200.0)),
+            CarState::Halted
+        );
+        assert_eq!(
+            car_reaction(CarState::Halted, 5.0, Some(200.0)),
+            CarState::Fleeing
+        );
+        assert_eq!(
+            car_reaction(CarState::Driving, 0.0, Some(50.0)),
+            CarState::Fleeing
+        );
+        assert_eq!(
+            car_reaction(CarState::Fleeing, 0.0, None),
+            CarState::Fleeing
+        );
+
+        // Density drops with the frame rate but never to nothing
+        assert_eq!(performance_budget(Some(60.0)), 1.0);
+        assert_eq!(performance_budget(Some(20.0)), MIN_BUDGET);
+    }
+}
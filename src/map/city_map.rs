@@ -0,0 +1,876 @@
+use crate::components::Faction;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+// ==================== CITY MAP ====================
+
+// Culiacán as a grid of tiles centred on the world origin. Row 0 is the top
+// (north) edge. Anything outside the grid is treated as open ground.
+
+const BUILDING_COVER: f32 = 0.3; // Damage reduction standing against a wall
+const PARK_COVER: f32 = 0.15; // Trees and hedges
+const AREA_CONTROL_THRESHOLD: f32 = 0.7; // Cartel share needed to hold an area
+const NO_DISTRICT: char = '.'; // Written for tiles outside every neighborhood
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileKind {
+    Ground,
+    Road,
+    Building,
+    Water,
+    Park,
+}
+
+impl TileKind {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(TileKind::Ground),
+            '=' => Some(TileKind::Road),
+            '#' => Some(TileKind::Building),
+            '~' => Some(TileKind::Water),
+            '"' => Some(TileKind::Park),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            TileKind::Ground => '.',
+            TileKind::Road => '=',
+            TileKind::Building => '#',
+            TileKind::Water => '~',
+            TileKind::Park => '"',
+        }
+    }
+
+    pub fn is_walkable(&self) -> bool {
+        !matches!(self, TileKind::Building | TileKind::Water)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropKind {
+    Streetlight,
+    Car,
+    Barrier,
+    Tree,
+    MarketStall,
+    Sandbags, // Thrown up by engineers during a mission
+}
+
+impl PropKind {
+    pub fn cover(&self) -> f32 {
+        match self {
+            PropKind::Streetlight => 0.0,
+            PropKind::Car | PropKind::Barrier => 0.4,
+            PropKind::Tree => 0.2,
+            PropKind::MarketStall => 0.3,
+            PropKind::Sandbags => 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MapProp {
+    pub kind: PropKind,
+    pub tile: (usize, usize), // Column, row
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Neighborhood {
+    pub key: char, // Letter used in the districts layer
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>, // Other names missions use for the same area
+}
+
+impl Neighborhood {
+    pub fn matches(&self, area_name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(area_name)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(area_name))
+    }
+}
+
+// Where a faction's reinforcements enter the map
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpawnPoint {
+    pub faction: Faction,
+    pub tile: (usize, usize),
+}
+
+// A named area missions can ask a side to hold, overriding any neighborhood
+// of the same name
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaptureZone {
+    pub name: String,
+    pub tile: (usize, usize),
+    pub radius: usize, // In tiles
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectiveMarker {
+    pub label: String,
+    pub tile: (usize, usize),
+}
+
+#[derive(Serialize, Deserialize)]
+struct CityMapFile {
+    name: String,
+    tile_size: f32,
+    tiles: Vec<String>,
+    districts: Vec<String>,
+    neighborhoods: Vec<Neighborhood>,
+    #[serde(default)]
+    props: Vec<MapProp>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spawn_points: Vec<SpawnPoint>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    capture_zones: Vec<CaptureZone>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    objective_markers: Vec<ObjectiveMarker>,
+}
+
+#[derive(Resource, Clone, Default)]
+pub struct CityMap {
+    pub name: String,
+    pub tile_size: f32,
+    pub width: usize,
+    pub height: usize,
+    pub neighborhoods: Vec<Neighborhood>,
+    pub props: Vec<MapProp>,
+    pub spawn_points: Vec<SpawnPoint>,
+    pub capture_zones: Vec<CaptureZone>,
+    pub objective_markers: Vec<ObjectiveMarker>,
+    tiles: Vec<TileKind>,
+    districts: Vec<Option<usize>>, // Index into neighborhoods
+    cover: Vec<f32>,
+}
+
+impl CityMap {
+    pub fn from_json(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let file: CityMapFile = serde_json::from_str(contents)?;
+
+        let height = file.tiles.len();
+        let width = file.tiles.first().map_or(0, |row| row.chars().count());
+        if width == 0 || file.tile_size <= 0.0 {
+            return Err("city map has no tiles".into());
+        }
+        if file.districts.len() != height {
+            return Err("districts layer height differs from tiles".into());
+        }
+
+        let mut tiles = Vec::with_capacity(width * height);
+        let mut districts = Vec::with_capacity(width * height);
+        for (row, (tile_row, district_row)) in file.tiles.iter().zip(&file.districts).enumerate() {
+            if tile_row.chars().count() != width || district_row.chars().count() != width {
+                return Err(format!("row {} is not {} tiles wide", row, width).into());
+            }
+            for c in tile_row.chars() {
+                tiles.push(
+                    TileKind::from_char(c)
+                        .ok_or_else(|| format!("unknown tile '{}' in row {}", c, row))?,
+                );
+            }
+            for key in district_row.chars() {
+                districts.push(file.neighborhoods.iter().position(|n| n.key == key));
+            }
+        }
+
+        let off_map = |tile: &(usize, usize)| tile.0 >= width || tile.1 >= height;
+        if let Some(prop) = file.props.iter().find(|prop| off_map(&prop.tile)) {
+            return Err(format!("{:?} prop at {:?} is off the map", prop.kind, prop.tile).into());
+        }
+        let marker_tiles = file
+            .spawn_points
+            .iter()
+            .map(|spawn| spawn.tile)
+            .chain(file.capture_zones.iter().map(|zone| zone.tile))
+            .chain(file.objective_markers.iter().map(|marker| marker.tile));
+        if let Some(tile) = marker_tiles.into_iter().find(|tile| off_map(tile)) {
+            return Err(format!("marker at {:?} is off the map", tile).into());
+        }
+
+        let mut map = Self {
+            name: file.name,
+            tile_size: file.tile_size,
+            width,
+            height,
+            neighborhoods: file.neighborhoods,
+            props: file.props,
+            spawn_points: file.spawn_points,
+            capture_zones: file.capture_zones,
+            objective_markers: file.objective_markers,
+            tiles,
+            districts,
+            cover: Vec::new(),
+        };
+        map.cover = map.compute_cover();
+        Ok(map)
+    }
+
+    // Back to the format from_json reads, e.g. for the map editor
+    pub fn to_json(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let rows = |cell: &dyn Fn(usize) -> char| -> Vec<String> {
+            (0..self.height)
+                .map(|row| {
+                    (0..self.width)
+                        .map(|col| cell(self.index(col, row)))
+                        .collect()
+                })
+                .collect()
+        };
+        let file = CityMapFile {
+            name: self.name.clone(),
+            tile_size: self.tile_size,
+            tiles: rows(&|i| self.tiles[i].to_char()),
+            districts: rows(&|i| {
+                self.districts[i].map_or(NO_DISTRICT, |n| self.neighborhoods[n].key)
+            }),
+            neighborhoods: self.neighborhoods.clone(),
+            props: self.props.clone(),
+            spawn_points: self.spawn_points.clone(),
+            capture_zones: self.capture_zones.clone(),
+            objective_markers: self.objective_markers.clone(),
+        };
+        Ok(serde_json::to_string_pretty(&file)?)
+    }
+
+    fn index(&self, col: usize, row: usize) -> usize {
+        row * self.width + col
+    }
+
+    pub fn tile_at(&self, position: Vec2) -> Option<(usize, usize)> {
+        if self.width == 0 {
+            return None;
+        }
+        let col = (position.x / self.tile_size + self.width as f32 / 2.0).floor();
+        let row = (self.height as f32 / 2.0 - position.y / self.tile_size).floor();
+        if col < 0.0 || row < 0.0 || col >= self.width as f32 || row >= self.height as f32 {
+            return None;
+        }
+        Some((col as usize, row as usize))
+    }
+
+    pub fn tile_center(&self, col: usize, row: usize) -> Vec2 {
+        Vec2::new(
+            (col as f32 + 0.5 - self.width as f32 / 2.0) * self.tile_size,
+            (self.height as f32 / 2.0 - row as f32 - 0.5) * self.tile_size,
+        )
+    }
+
+    pub fn kind(&self, col: usize, row: usize) -> TileKind {
+        self.tiles[self.index(col, row)]
+    }
+
+    // ==================== EDITING ====================
+
+    // Each returns whether anything changed, so callers only record real edits
+
+    pub fn set_tile(&mut self, (col, row): (usize, usize), kind: TileKind) -> bool {
+        let index = self.index(col, row);
+        if self.tiles[index] == kind {
+            return false;
+        }
+        self.tiles[index] = kind;
+        // Props can't stand inside a building or in the river
+        if !kind.is_walkable() {
+            self.props.retain(|prop| prop.tile != (col, row));
+        }
+        self.cover = self.compute_cover();
+        true
+    }
+
+    pub fn place_prop(&mut self, tile: (usize, usize), kind: PropKind) -> bool {
+        if !self.kind(tile.0, tile.1).is_walkable()
+            || self
+                .props
+                .iter()
+                .any(|prop| prop.tile == tile && prop.kind == kind)
+        {
+            return false;
+        }
+        self.props.retain(|prop| prop.tile != tile);
+        self.props.push(MapProp { kind, tile });
+        self.cover = self.compute_cover();
+        true
+    }
+
+    // Removes every prop and marker on the tile
+    pub fn clear_tile(&mut self, tile: (usize, usize)) -> bool {
+        let before = self.props.len()
+            + self.spawn_points.len()
+            + self.capture_zones.len()
+            + self.objective_markers.len();
+        self.props.retain(|prop| prop.tile != tile);
+        self.spawn_points.retain(|spawn| spawn.tile != tile);
+        self.capture_zones.retain(|zone| zone.tile != tile);
+        self.objective_markers.retain(|marker| marker.tile != tile);
+        let after = self.props.len()
+            + self.spawn_points.len()
+            + self.capture_zones.len()
+            + self.objective_markers.len();
+
+        if after == before {
+            return false;
+        }
+        self.cover = self.compute_cover();
+        true
+    }
+
+    pub fn remove_prop(&mut self, tile: (usize, usize), kind: PropKind) -> bool {
+        let before = self.props.len();
+        self.props
+            .retain(|prop| prop.tile != tile || prop.kind != kind);
+        if self.props.len() == before {
+            return false;
+        }
+        self.cover = self.compute_cover();
+        true
+    }
+
+    // A wall with open ground straight beside it, that a breach can open up
+    pub fn is_breachable(&self, (col, row): (usize, usize)) -> bool {
+        self.kind(col, row) == TileKind::Building
+            && self
+                .neighbors(col, row)
+                .any(|(c, r)| (c == col || r == row) && self.kind(c, r).is_walkable())
+    }
+
+    pub fn spawn_positions(&self, faction: &Faction) -> Vec<Vec2> {
+        self.spawn_points
+            .iter()
+            .filter(|spawn| spawn.faction == *faction)
+            .map(|spawn| self.tile_center(spawn.tile.0, spawn.tile.1))
+            .collect()
+    }
+
+    pub fn find_capture_zone(&self, area_name: &str) -> Option<&CaptureZone> {
+        self.capture_zones
+            .iter()
+            .find(|zone| zone.name.eq_ignore_ascii_case(area_name))
+    }
+
+    pub fn in_capture_zone(&self, zone: &CaptureZone, position: Vec2) -> bool {
+        let center = self.tile_center(zone.tile.0, zone.tile.1);
+        center.distance(position) <= (zone.radius as f32 + 0.5) * self.tile_size
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = (usize, usize, TileKind)> + '_ {
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(|(i, kind)| (i % self.width, i / self.width, *kind))
+    }
+
+    pub fn is_walkable(&self, position: Vec2) -> bool {
+        self.tile_at(position)
+            .is_none_or(|(col, row)| self.kind(col, row).is_walkable())
+    }
+
+    // Damage reduction for a unit standing here
+    pub fn cover_at(&self, position: Vec2) -> f32 {
+        self.tile_at(position)
+            .map_or(0.0, |(col, row)| self.cover[self.index(col, row)])
+    }
+
+    /// Whether nothing solid stands between two points: walls block sight,
+    /// the tiles the two ends stand on don't count.
+    pub fn has_line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        let (start, end) = (self.tile_at(from), self.tile_at(to));
+        let steps = (from.distance(to) / (self.tile_size * 0.5)).ceil() as usize;
+        (1..steps).all(|step| {
+            let tile = self.tile_at(from.lerp(to, step as f32 / steps as f32));
+            tile.is_none_or(|tile| {
+                Some(tile) == start
+                    || Some(tile) == end
+                    || self.kind(tile.0, tile.1) != TileKind::Building
+            })
+        })
+    }
+
+    pub fn neighborhood_at(&self, position: Vec2) -> Option<&Neighborhood> {
+        let (col, row) = self.tile_at(position)?;
+        self.districts[self.index(col, row)].map(|i| &self.neighborhoods[i])
+    }
+
+    pub fn find_neighborhood(&self, area_name: &str) -> Option<&Neighborhood> {
+        self.neighborhoods.iter().find(|n| n.matches(area_name))
+    }
+
+    // The walkable tile nearest the middle of a district
+    pub fn neighborhood_center(&self, key: char) -> Option<Vec2> {
+        let district = self.neighborhoods.iter().position(|n| n.key == key)?;
+        let tiles: Vec<Vec2> = self
+            .tiles()
+            .filter(|(col, row, kind)| {
+                kind.is_walkable() && self.districts[self.index(*col, *row)] == Some(district)
+            })
+            .map(|(col, row, _)| self.tile_center(col, row))
+            .collect();
+        let middle = tiles.iter().copied().sum::<Vec2>() / tiles.len().max(1) as f32;
+        tiles.into_iter().min_by(|a, b| {
+            a.distance_squared(middle)
+                .total_cmp(&b.distance_squared(middle))
+        })
+    }
+
+    // The box around a district's tiles, edges included
+    pub fn neighborhood_bounds(&self, key: char) -> Option<Rect> {
+        let district = self.neighborhoods.iter().position(|n| n.key == key)?;
+        let half_tile = Vec2::splat(self.tile_size / 2.0);
+        self.tiles()
+            .filter(|(col, row, _)| self.districts[self.index(*col, *row)] == Some(district))
+            .map(|(col, row, _)| self.tile_center(col, row))
+            .fold(None, |bounds: Option<Rect>, center| {
+                let tile = Rect::from_corners(center - half_tile, center + half_tile);
+                Some(bounds.map_or(tile, |bounds| bounds.union(tile)))
+            })
+    }
+
+    // Building tile centres, for anything that needs to know what blocks sight
+    pub fn building_tiles(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.tiles()
+            .filter(|(_, _, kind)| *kind == TileKind::Building)
+            .map(|(col, row, _)| self.tile_center(col, row))
+    }
+
+    pub fn prop_positions(&self, kind: PropKind) -> impl Iterator<Item = Vec2> + '_ {
+        self.props
+            .iter()
+            .filter(move |prop| prop.kind == kind)
+            .map(|prop| self.tile_center(prop.tile.0, prop.tile.1))
+    }
+
+    fn neighbors(&self, col: usize, row: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (-1i32..=1)
+            .flat_map(|dy| (-1i32..=1).map(move |dx| (dx, dy)))
+            .filter(|(dx, dy)| *dx != 0 || *dy != 0)
+            .filter_map(move |(dx, dy)| {
+                let col = col.checked_add_signed(dx as isize)?;
+                let row = row.checked_add_signed(dy as isize)?;
+                (col < self.width && row < self.height).then_some((col, row))
+            })
+    }
+
+    fn compute_cover(&self) -> Vec<f32> {
+        let mut prop_cover: HashMap<(usize, usize), f32> = HashMap::new();
+        for prop in &self.props {
+            let (col, row) = prop.tile;
+            for tile in std::iter::once((col, row)).chain(self.neighbors(col, row)) {
+                let cover = prop_cover.entry(tile).or_insert(0.0);
+                *cover = cover.max(prop.kind.cover());
+            }
+        }
+
+        self.tiles()
+            .map(|(col, row, kind)| {
+                if !kind.is_walkable() {
+                    return 0.0;
+                }
+                let mut cover = if kind == TileKind::Park {
+                    PARK_COVER
+                } else {
+                    0.0
+                };
+                if self
+                    .neighbors(col, row)
+                    .any(|(c, r)| self.kind(c, r) == TileKind::Building)
+                {
+                    cover = cover.max(BUILDING_COVER);
+                }
+                cover.max(prop_cover.get(&(col, row)).copied().unwrap_or(0.0))
+            })
+            .collect()
+    }
+
+    // Nearest walkable tile to a blocked one, e.g. when ordered into a building
+    fn nearest_walkable(&self, col: usize, row: usize) -> Option<(usize, usize)> {
+        self.nearest_tile(col, row, 3, TileKind::is_walkable)
+    }
+
+    fn nearest_tile(
+        &self,
+        col: usize,
+        row: usize,
+        max_radius: usize,
+        passable: impl Fn(&TileKind) -> bool,
+    ) -> Option<(usize, usize)> {
+        (0..=max_radius).find_map(|radius| {
+            self.tiles()
+                .filter(|(c, r, kind)| {
+                    passable(kind) && c.abs_diff(col).max(r.abs_diff(row)) == radius
+                })
+                .min_by_key(|(c, r, _)| c.abs_diff(col) + r.abs_diff(row))
+                .map(|(c, r, _)| (c, r))
+        })
+    }
+
+    // A* over walkable tiles (see search). Returns waypoints after the start, ending at `end` (or the closest
+    // walkable tile to it); None when either point is off the map or no
+    // route exists.
+    pub fn find_path(&self, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        self.find_path_avoiding(start, end, &[], 0.0)
+    }
+
+    // As find_path, with each tile whose center lies in one of the `hazards`
+    // (center, radius) costing `penalty` extra tiles to cross, so routes go
+    // around them unless the way round is longer still.
+    pub fn find_path_avoiding(
+        &self,
+        start: Vec3,
+        end: Vec3,
+        hazards: &[(Vec2, f32)],
+        penalty: f32,
+    ) -> Option<Vec<Vec3>> {
+        let start_tile = self.tile_at(start.truncate())?;
+        let requested_goal = self.tile_at(end.truncate())?;
+        let goal = if self.kind(requested_goal.0, requested_goal.1).is_walkable() {
+            requested_goal
+        } else {
+            self.nearest_walkable(requested_goal.0, requested_goal.1)?
+        };
+        self.search(
+            (start_tile, goal),
+            end.z,
+            TileKind::is_walkable,
+            hazards,
+            penalty,
+        )
+        .map(|mut waypoints| {
+            if goal == requested_goal {
+                if let Some(last) = waypoints.last_mut() {
+                    *last = end;
+                }
+            }
+            waypoints
+        })
+    }
+
+    /// The route a vehicle takes between two points keeping to the roads:
+    /// from the road tile nearest `start` to the one nearest `end`, as the
+    /// points where it turns, both ends included. None off the map, on a
+    /// map without roads or when the two roads don't meet.
+    pub fn find_road_path(&self, start: Vec2, end: Vec2) -> Option<Vec<Vec2>> {
+        let on_road = |kind: &TileKind| *kind == TileKind::Road;
+        let radius = self.width.max(self.height);
+        let (col, row) = self.tile_at(start)?;
+        let start_tile = self.nearest_tile(col, row, radius, on_road)?;
+        let (col, row) = self.tile_at(end)?;
+        let goal = self.nearest_tile(col, row, radius, on_road)?;
+
+        let rest = self.search((start_tile, goal), 0.0, on_road, &[], 0.0)?;
+        let mut route = vec![self.tile_center(start_tile.0, start_tile.1)];
+        route.extend(rest.into_iter().map(|point| point.truncate()));
+        route.dedup();
+        Some(route)
+    }
+
+    // A* from one tile to another over the tiles `passable` allows, with
+    // diagonal moves that don't cut corners. Returns the tile centers where
+    // the route turns, after the start, at the given height.
+    fn search(
+        &self,
+        (start_tile, goal): ((usize, usize), (usize, usize)),
+        z: f32,
+        passable: impl Fn(&TileKind) -> bool,
+        hazards: &[(Vec2, f32)],
+        penalty: f32,
+    ) -> Option<Vec<Vec3>> {
+        let heuristic = |(col, row): (usize, usize)| {
+            let dx = col.abs_diff(goal.0) as f32;
+            let dy = row.abs_diff(goal.1) as f32;
+            dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy)
+        };
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut cost: HashMap<(usize, usize), f32> = HashMap::new();
+        cost.insert(start_tile, 0.0);
+        open.push(PathNode {
+            tile: start_tile,
+            estimate: heuristic(start_tile),
+        });
+
+        while let Some(PathNode { tile, .. }) = open.pop() {
+            if tile == goal {
+                break;
+            }
+            let tile_cost = cost[&tile];
+            for next in self.neighbors(tile.0, tile.1) {
+                if !passable(&self.kind(next.0, next.1)) {
+                    continue;
+                }
+                let diagonal = next.0 != tile.0 && next.1 != tile.1;
+                if diagonal
+                    && (!passable(&self.kind(next.0, tile.1))
+                        || !passable(&self.kind(tile.0, next.1)))
+                {
+                    continue;
+                }
+                let step = if diagonal {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.0
+                };
+                let center = self.tile_center(next.0, next.1);
+                let hazardous = hazards
+                    .iter()
+                    .any(|(hazard, radius)| center.distance(*hazard) <= *radius);
+                let next_cost = tile_cost + step + if hazardous { penalty } else { 0.0 };
+                if cost.get(&next).is_none_or(|known| next_cost < *known) {
+                    cost.insert(next, next_cost);
+                    came_from.insert(next, tile);
+                    open.push(PathNode {
+                        tile: next,
+                        estimate: next_cost + heuristic(next),
+                    });
+                }
+            }
+        }
+
+        if goal != start_tile && !came_from.contains_key(&goal) {
+            return None;
+        }
+
+        // Walk back from the goal, keeping only the tiles where the route turns
+        let mut tiles = vec![goal];
+        while let Some(previous) = came_from.get(tiles.last().unwrap()) {
+            if *previous == start_tile {
+                break;
+            }
+            tiles.push(*previous);
+        }
+        tiles.reverse();
+
+        let mut waypoints: Vec<Vec3> = Vec::with_capacity(tiles.len());
+        let mut previous = start_tile;
+        for (i, tile) in tiles.iter().enumerate() {
+            let direction = (
+                tile.0 as i64 - previous.0 as i64,
+                tile.1 as i64 - previous.1 as i64,
+            );
+            let straight_on = tiles.get(i + 1).is_some_and(|next| {
+                (next.0 as i64 - tile.0 as i64, next.1 as i64 - tile.1 as i64) == direction
+            });
+            if !straight_on {
+                waypoints.push(self.tile_center(tile.0, tile.1).extend(z));
+            }
+            previous = *tile;
+        }
+        Some(waypoints)
+    }
+}
+
+#[derive(PartialEq)]
+struct PathNode {
+    tile: (usize, usize),
+    estimate: f32,
+}
+
+impl Eq for PathNode {}
+
+impl Ord for PathNode {
+    // Reversed so BinaryHeap pops the cheapest estimate first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl PartialOrd for PathNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Whether a position lies inside the named area - a capture zone, else a
+// neighborhood. Areas the map doesn't define cover the whole battlefield.
+pub fn in_area(city_map: &CityMap, area_name: &str, position: Vec2) -> bool {
+    match (
+        city_map.find_capture_zone(area_name),
+        city_map.find_neighborhood(area_name),
+    ) {
+        (Some(zone), _) => city_map.in_capture_zone(zone, position),
+        (None, Some(area)) => city_map
+            .neighborhood_at(position)
+            .is_some_and(|n| n.key == area.key),
+        (None, None) => true,
+    }
+}
+
+// Where the named area is, for anything headed there: the middle of a
+// capture zone, else of a neighborhood. None for areas the map doesn't define.
+pub fn area_center(city_map: &CityMap, area_name: &str) -> Option<Vec2> {
+    match city_map.find_capture_zone(area_name) {
+        Some(zone) => Some(city_map.tile_center(zone.tile.0, zone.tile.1)),
+        None => city_map.neighborhood_center(city_map.find_neighborhood(area_name)?.key),
+    }
+}
+
+// Cartel share of the living fighters inside the named area
+pub fn area_control(city_map: &CityMap, area_name: &str, units: &[(Faction, Vec2)]) -> f32 {
+    let (mut cartel, mut military) = (0, 0);
+    for (faction, _) in units
+        .iter()
+        .filter(|(_, position)| in_area(city_map, area_name, *position))
+    {
+        match faction {
+            Faction::Cartel => cartel += 1,
+            Faction::Military => military += 1,
+            _ => {}
+        }
+    }
+
+    match (cartel, military) {
+        (0, _) => 0.0,
+        (_, 0) => 1.0,
+        _ => cartel as f32 / (cartel + military) as f32,
+    }
+}
+
+pub fn is_area_controlled(control: f32) -> bool {
+    control >= AREA_CONTROL_THRESHOLD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MAP: &str = r#"{
+        "name": "Test",
+        "tile_size": 10.0,
+        "tiles": [
+            "....",
+            ".##.",
+            ".##.",
+            "...."
+        ],
+        "districts": [
+            "AABB",
+            "AABB",
+            "AABB",
+            "AABB"
+        ],
+        "neighborhoods": [
+            { "key": "A", "name": "West", "aliases": ["Downtown"] },
+            { "key": "B", "name": "East" }
+        ],
+        "props": [{ "kind": "Car", "tile": [0, 3] }]
+    }"#;
+
+    #[test]
+    fn test_city_map_layout_and_cover() {
+        let map = CityMap::from_json(TEST_MAP).unwrap();
+
+        assert_eq!((map.width, map.height), (4, 4));
+        assert_eq!(map.tile_at(Vec2::new(-15.0, 15.0)), Some((0, 0)));
+        assert_eq!(map.tile_center(0, 0), Vec2::new(-15.0, 15.0));
+        assert!(!map.is_walkable(Vec2::new(-5.0, 5.0)));
+        assert!(map.is_walkable(Vec2::new(500.0, 500.0)));
+
+        assert_eq!(map.cover_at(Vec2::new(-15.0, 15.0)), BUILDING_COVER);
+        assert_eq!(map.cover_at(map.tile_center(0, 3)), 0.4);
+        assert_eq!(
+            map.neighborhood_at(Vec2::new(15.0, 0.0)).unwrap().name,
+            "East"
+        );
+        assert_eq!(map.find_neighborhood("downtown").unwrap().name, "West");
+
+        let center = map.neighborhood_center('A').unwrap();
+        assert!(map.is_walkable(center));
+        assert_eq!(map.neighborhood_at(center).unwrap().name, "West");
+        assert_eq!(map.neighborhood_center('Z'), None);
+    }
+
+    #[test]
+    fn test_find_path_goes_around_buildings() {
+        let map = CityMap::from_json(TEST_MAP).unwrap();
+        let start = map.tile_center(0, 1).extend(0.0);
+        let end = map.tile_center(3, 2).extend(0.0);
+
+        let path = map.find_path(start, end).unwrap();
+        assert_eq!(*path.last().unwrap(), end);
+        assert!(path.iter().all(|point| map.is_walkable(point.truncate())));
+
+        // Ordered into a building: stop at the nearest open tile
+        let inside = map.tile_center(1, 1).extend(0.0);
+        let path = map.find_path(start, inside).unwrap();
+        assert!(map.is_walkable(path.last().unwrap().truncate()));
+    }
+
+    #[test]
+    fn test_routes_go_around_hazards() {
+        let map = CityMap::from_json(TEST_MAP).unwrap();
+        let start = map.tile_center(0, 1).extend(0.0);
+        let end = map.tile_center(3, 2).extend(0.0);
+        let top = [(map.tile_center(1, 0), 5.0), (map.tile_center(2, 0), 5.0)];
+        let bottom = [(map.tile_center(1, 3), 5.0), (map.tile_center(2, 3), 5.0)];
+
+        let path = map.find_path_avoiding(start, end, &top, 10.0).unwrap();
+        assert!(path.iter().all(|point| point.y < map.tile_center(0, 0).y));
+        let path = map.find_path_avoiding(start, end, &bottom, 10.0).unwrap();
+        assert!(path.iter().all(|point| point.y > map.tile_center(0, 3).y));
+    }
+
+    #[test]
+    fn test_road_paths_keep_to_the_road() {
+        let map = CityMap::from_json(
+            r#"{
+                "name": "Roads",
+                "tile_size": 10.0,
+                "tiles": ["=...", "=.==", "===.", "...."],
+                "districts": ["....", "....", "....", "...."],
+                "neighborhoods": []
+            }"#,
+        )
+        .unwrap();
+
+        // Snapped to the nearest road at both ends, turning at each corner
+        let route = map
+            .find_road_path(map.tile_center(1, 0), map.tile_center(3, 1))
+            .unwrap();
+        assert_eq!(route.first(), Some(&map.tile_center(0, 0)));
+        assert_eq!(route.last(), Some(&map.tile_center(3, 1)));
+        let on_road = |point: &Vec2| {
+            map.tile_at(*point)
+                .is_some_and(|(col, row)| map.kind(col, row) == TileKind::Road)
+        };
+        assert!(route.iter().all(on_road));
+        assert_eq!(route.len(), 5);
+
+        let plain = CityMap::from_json(TEST_MAP).unwrap();
+        assert_eq!(plain.find_road_path(Vec2::ZERO, Vec2::ZERO), None);
+    }
+
+    #[test]
+    fn test_area_control_counts_units_inside_neighborhood() {
+        let map = CityMap::from_json(TEST_MAP).unwrap();
+        let west = map.tile_center(0, 0);
+        let east = map.tile_center(3, 0);
+        let units = [
+            (Faction::Cartel, west),
+            (Faction::Cartel, west),
+            (Faction::Military, east),
+        ];
+
+        assert_eq!(area_control(&map, "West", &units), 1.0);
+        assert_eq!(area_control(&map, "East", &units), 0.0);
+        assert!((area_control(&map, "Airport", &units) - 2.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_walls_block_line_of_sight() {
+        let map = CityMap::from_json(TEST_MAP).unwrap();
+        let west = map.tile_center(0, 1);
+        let east = map.tile_center(3, 1);
+        assert!(!map.has_line_of_sight(west, east));
+        assert!(map.has_line_of_sight(map.tile_center(0, 0), map.tile_center(3, 0)));
+
+        // Standing in a doorway doesn't hide you from the street outside
+        assert!(map.has_line_of_sight(west, map.tile_center(1, 1)));
+    }
+}
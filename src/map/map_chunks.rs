@@ -0,0 +1,371 @@
+use crate::components::IsometricCamera;
+use crate::map::{CityMap, MapProp, PropKind, TileKind};
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
+use std::ops::Range;
+use std::sync::Arc;
+
+// ==================== MAP CHUNK STREAMING ====================
+
+// The city's tiles and props are drawn in square chunks, and only around the
+// camera. Chunks in view, plus a ring of prefetched ones around them, have
+// their sprites built on the async compute pool and spawned as they finish,
+// so a large scenario map doesn't stall the first frame or keep tens of
+// thousands of sprites alive. Chunks further than UNLOAD_RING from the view
+// are despawned. The map data itself stays whole, since pathfinding and the
+// simulation need all of it. The F10 profiler shows the chunk counts.
+
+pub const CHUNK_TILES: usize = 16; // Tiles along a chunk's side
+const PREFETCH_RING: i32 = 1; // Chunks loaded beyond the edge of the view
+const UNLOAD_RING: i32 = 3; // Loaded chunks this far past the view are dropped
+const TILE_Z: f32 = -9.0; // Above the background plane, below everything else
+const PROP_Z: f32 = -8.0;
+
+pub struct MapChunkPlugin;
+
+impl Plugin for MapChunkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapChunks>().add_systems(
+            Update,
+            (
+                map_chunk_source_system,
+                map_chunk_stream_system,
+                map_chunk_spawn_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// A sprite drawn by a chunk.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChunkSprite {
+    pub position: Vec3,
+    pub color: Color,
+    pub size: Vec2,
+}
+
+enum ChunkState {
+    Loading(Task<Vec<ChunkSprite>>),
+    Loaded(Vec<Entity>),
+}
+
+#[derive(Component)]
+pub struct ChunkTile;
+
+/// The map as chunk tasks see it, with its props already sorted into chunks.
+pub struct ChunkSource {
+    pub map: CityMap,
+    props: HashMap<IVec2, Vec<MapProp>>,
+}
+
+impl ChunkSource {
+    pub fn new(map: CityMap) -> Self {
+        let mut props: HashMap<IVec2, Vec<MapProp>> = HashMap::default();
+        for prop in &map.props {
+            props
+                .entry(chunk_of(prop.tile))
+                .or_default()
+                .push(prop.clone());
+        }
+        Self { map, props }
+    }
+}
+
+/// Which chunks are loaded or on their way.
+#[derive(Resource, Default)]
+pub struct MapChunks {
+    source: Option<Arc<ChunkSource>>, // What tasks build from, shared between them
+    chunks: HashMap<IVec2, ChunkState>,
+}
+
+impl MapChunks {
+    pub fn loaded(&self) -> usize {
+        self.chunks
+            .values()
+            .filter(|state| matches!(state, ChunkState::Loaded(_)))
+            .count()
+    }
+
+    pub fn loading(&self) -> usize {
+        self.chunks.len() - self.loaded()
+    }
+
+    pub fn sprites(&self) -> usize {
+        self.chunks
+            .values()
+            .map(|state| match state {
+                ChunkState::Loaded(entities) => entities.len(),
+                ChunkState::Loading(_) => 0,
+            })
+            .sum()
+    }
+
+    /// A line for the profiler overlay.
+    pub fn summary(&self) -> String {
+        format!(
+            "Map chunks: {} loaded, {} loading, {} sprites",
+            self.loaded(),
+            self.loading(),
+            self.sprites()
+        )
+    }
+
+    // Dropping a task cancels it
+    fn unload(&mut self, commands: &mut Commands, chunk: IVec2) {
+        if let Some(ChunkState::Loaded(entities)) = self.chunks.remove(&chunk) {
+            for entity in entities {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+fn tile_color(kind: TileKind) -> Option<Color> {
+    match kind {
+        TileKind::Ground => None, // The background plane shows through
+        TileKind::Road => Some(Color::rgb(0.32, 0.32, 0.34)),
+        TileKind::Building => Some(Color::rgb(0.55, 0.45, 0.38)),
+        TileKind::Water => Some(Color::rgb(0.2, 0.35, 0.5)),
+        TileKind::Park => Some(Color::rgb(0.3, 0.5, 0.25)),
+    }
+}
+
+fn prop_sprite(kind: PropKind) -> Option<(Color, Vec2)> {
+    match kind {
+        PropKind::Streetlight => None, // Drawn as light pools by the environment systems
+        PropKind::Car => Some((Color::rgb(0.6, 0.15, 0.15), Vec2::new(30.0, 16.0))),
+        PropKind::Barrier => Some((Color::rgb(0.8, 0.7, 0.2), Vec2::new(36.0, 8.0))),
+        PropKind::Tree => Some((Color::rgb(0.15, 0.35, 0.15), Vec2::splat(22.0))),
+        PropKind::MarketStall => None, // Spawned by the city life layer, which shutters them
+        PropKind::Sandbags => Some((Color::rgb(0.7, 0.62, 0.42), Vec2::new(34.0, 12.0))),
+    }
+}
+
+pub fn chunk_of((col, row): (usize, usize)) -> IVec2 {
+    IVec2::new((col / CHUNK_TILES) as i32, (row / CHUNK_TILES) as i32)
+}
+
+// The columns or rows of a map `len` tiles across that chunk `index` covers
+fn chunk_span(index: i32, len: usize) -> Range<usize> {
+    if index < 0 {
+        return 0..0;
+    }
+    let start = (index as usize * CHUNK_TILES).min(len);
+    start..(start + CHUNK_TILES).min(len)
+}
+
+/// The tiles and props a chunk draws.
+pub fn build_chunk(source: &ChunkSource, chunk: IVec2) -> Vec<ChunkSprite> {
+    let city_map = &source.map;
+    let tile_size = Vec2::splat(city_map.tile_size);
+    let cols = chunk_span(chunk.x, city_map.width);
+    let tiles = chunk_span(chunk.y, city_map.height)
+        .flat_map(|row| cols.clone().map(move |col| (col, row)))
+        .filter_map(|(col, row)| {
+            tile_color(city_map.kind(col, row)).map(|color| ChunkSprite {
+                position: city_map.tile_center(col, row).extend(TILE_Z),
+                color,
+                size: tile_size,
+            })
+        });
+    let props = source
+        .props
+        .get(&chunk)
+        .into_iter()
+        .flatten()
+        .filter_map(|prop| {
+            prop_sprite(prop.kind).map(|(color, size)| ChunkSprite {
+                position: city_map
+                    .tile_center(prop.tile.0, prop.tile.1)
+                    .extend(PROP_Z),
+                color,
+                size,
+            })
+        });
+    tiles.chain(props).collect()
+}
+
+/// The chunks covering `view`, grown by `ring` chunks on every side and kept
+/// to the map. None if the view is off the map entirely.
+pub fn chunks_in_view(city_map: &CityMap, view: Rect, ring: i32) -> Option<IRect> {
+    if city_map.width == 0 || city_map.height == 0 {
+        return None;
+    }
+    // Same layout as CityMap::tile_at, without its bounds check
+    let col = |x: f32| (x / city_map.tile_size + city_map.width as f32 / 2.0).floor() as i32;
+    let row = |y: f32| (city_map.height as f32 / 2.0 - y / city_map.tile_size).floor() as i32;
+    let tiles = IRect::new(
+        col(view.min.x),
+        row(view.max.y),
+        col(view.max.x),
+        row(view.min.y),
+    );
+    let map = IRect::new(0, 0, city_map.width as i32 - 1, city_map.height as i32 - 1);
+    if tiles.max.cmplt(map.min).any() || tiles.min.cmpgt(map.max).any() {
+        return None;
+    }
+
+    let last = chunk_of((city_map.width - 1, city_map.height - 1));
+    let chunk = |tile: IVec2| tile.clamp(IVec2::ZERO, map.max) / CHUNK_TILES as i32;
+    let chunks = IRect::from_corners(chunk(tiles.min), chunk(tiles.max)).inflate(ring);
+    Some(chunks.intersect(IRect::from_corners(IVec2::ZERO, last)))
+}
+
+/// How many chunks `chunk` lies outside `area`, 0 if inside.
+pub fn chunk_distance(area: IRect, chunk: IVec2) -> i32 {
+    let outside = (area.min - chunk).max(chunk - area.max).max(IVec2::ZERO);
+    outside.max_element()
+}
+
+// ==================== SYSTEMS ====================
+
+// A new or edited map is drawn afresh
+pub fn map_chunk_source_system(
+    mut commands: Commands,
+    city_map: Res<CityMap>,
+    mut chunks: ResMut<MapChunks>,
+) {
+    if !city_map.is_changed() {
+        return;
+    }
+    let loaded: Vec<IVec2> = chunks.chunks.keys().copied().collect();
+    for chunk in loaded {
+        chunks.unload(&mut commands, chunk);
+    }
+    chunks.source = Some(Arc::new(ChunkSource::new(city_map.clone())));
+}
+
+pub fn map_chunk_stream_system(
+    mut commands: Commands,
+    mut chunks: ResMut<MapChunks>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Transform, With<IsometricCamera>>,
+) {
+    let Some(source) = chunks.source.clone() else {
+        return;
+    };
+    let (Ok(window), Ok(camera)) = (windows.get_single(), camera_query.get_single()) else {
+        return;
+    };
+    let half_view = Vec2::new(window.width(), window.height()) / 2.0 * camera.scale.x;
+    let center = camera.translation.truncate();
+    let view = Rect::from_corners(center - half_view, center + half_view);
+    let visible = chunks_in_view(&source.map, view, 0);
+
+    // Anything that has drifted far from the view goes, loaded or not
+    let distant: Vec<IVec2> = chunks
+        .chunks
+        .keys()
+        .filter(|chunk| visible.map_or(true, |area| chunk_distance(area, **chunk) > UNLOAD_RING))
+        .copied()
+        .collect();
+    for chunk in distant {
+        chunks.unload(&mut commands, chunk);
+    }
+
+    let Some(wanted) = chunks_in_view(&source.map, view, PREFETCH_RING) else {
+        return;
+    };
+    let pool = AsyncComputeTaskPool::get();
+    for x in wanted.min.x..=wanted.max.x {
+        for y in wanted.min.y..=wanted.max.y {
+            let chunk = IVec2::new(x, y);
+            if !chunks.chunks.contains_key(&chunk) {
+                let source = source.clone();
+                let task = pool.spawn(async move { build_chunk(&source, chunk) });
+                chunks.chunks.insert(chunk, ChunkState::Loading(task));
+            }
+        }
+    }
+}
+
+pub fn map_chunk_spawn_system(mut commands: Commands, mut chunks: ResMut<MapChunks>) {
+    for state in chunks.chunks.values_mut() {
+        let ChunkState::Loading(task) = state else {
+            continue;
+        };
+        let Some(sprites) = future::block_on(future::poll_once(task)) else {
+            continue;
+        };
+        let entities = sprites
+            .into_iter()
+            .map(|sprite| {
+                commands
+                    .spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: sprite.color,
+                                custom_size: Some(sprite.size),
+                                ..default()
+                            },
+                            transform: Transform::from_translation(sprite.position),
+                            ..default()
+                        },
+                        ChunkTile,
+                    ))
+                    .id()
+            })
+            .collect();
+        *state = ChunkState::Loaded(entities);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // All road, so every tile draws
+    fn test_map(size: usize) -> CityMap {
+        let json = serde_json::json!({
+            "name": "Test",
+            "tile_size": 10.0,
+            "tiles": vec!["=".repeat(size); size],
+            "districts": vec![".".repeat(size); size],
+            "neighborhoods": [],
+        });
+        CityMap::from_json(&json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_chunks_follow_the_view_and_cover_every_tile_once() {
+        let mut city_map = test_map(40);
+        assert!(city_map.place_prop((20, 3), PropKind::Car));
+        let chunks_per_side = 40_usize.div_ceil(CHUNK_TILES) as i32;
+
+        // A small view in the middle of the map needs the chunks under it
+        let view = Rect::from_center_size(Vec2::ZERO, Vec2::splat(20.0));
+        let visible = chunks_in_view(&city_map, view, 0).unwrap();
+        assert_eq!(visible, IRect::new(1, 1, 1, 1));
+        let prefetched = chunks_in_view(&city_map, view, PREFETCH_RING).unwrap();
+        assert_eq!(prefetched, IRect::new(0, 0, 2, 2));
+
+        // The ring stops at the map's edge, and views off the map need nothing
+        let corner = Rect::from_center_size(Vec2::new(-195.0, 195.0), Vec2::splat(5.0));
+        assert_eq!(
+            chunks_in_view(&city_map, corner, PREFETCH_RING),
+            Some(IRect::new(0, 0, 1, 1))
+        );
+        let away = Rect::from_center_size(Vec2::splat(5000.0), Vec2::splat(20.0));
+        assert_eq!(chunks_in_view(&city_map, away, 0), None);
+
+        assert_eq!(chunk_distance(visible, IVec2::new(1, 1)), 0);
+        assert_eq!(chunk_distance(visible, IVec2::new(5, 0)), 4);
+
+        let source = ChunkSource::new(city_map);
+        let mut drawn = 0;
+        for x in 0..chunks_per_side {
+            for y in 0..chunks_per_side {
+                drawn += build_chunk(&source, IVec2::new(x, y)).len();
+            }
+        }
+        assert_eq!(drawn, 40 * 40 + 1);
+        let car = build_chunk(&source, IVec2::new(1, 0));
+        assert!(car.iter().any(|sprite| sprite.position.z == PROP_Z));
+        assert!(build_chunk(&source, IVec2::new(-1, 0)).is_empty());
+        assert!(build_chunk(&source, IVec2::new(0, chunks_per_side)).is_empty());
+    }
+}
@@ -0,0 +1,487 @@
+use crate::app_state::GameSet;
+use crate::components::{Faction, IsometricCamera};
+use crate::map::{
+    city_map_path, redraw_city_map_system, save_city_map, CaptureZone, CityMap, ObjectiveMarker,
+    PropKind, SpawnPoint, TileKind,
+};
+use bevy::prelude::*;
+
+// ==================== MAP EDITOR ====================
+
+// F12 pauses the battle and opens the editor on the live city map. Tab and
+// Shift+Tab pick a tool, left click places (tiles paint while the button is
+// held), right click clears props and markers from a tile. Ctrl+Z / Ctrl+Y
+// undo and redo, Ctrl+S writes the map back to the file missions load.
+
+const HISTORY_LIMIT: usize = 100;
+const DEFAULT_ZONE_RADIUS: usize = 2; // Tiles
+const OVERLAY_Z: f32 = 5.0; // Above units, so markers stay visible while editing
+
+pub struct MapEditorPlugin;
+
+impl Plugin for MapEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapEditor>().add_systems(
+            Update,
+            (
+                map_editor_toggle_system,
+                map_editor_input_system.run_if(editor_active),
+                redraw_city_map_system,
+                map_editor_overlay_system,
+            )
+                .chain()
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EditorTool {
+    Tile(TileKind),
+    Prop(PropKind),
+    CartelSpawn,
+    MilitarySpawn,
+    CaptureZone,
+    ObjectiveMarker,
+}
+
+const EDITOR_TOOLS: [EditorTool; 14] = [
+    EditorTool::Tile(TileKind::Road),
+    EditorTool::Tile(TileKind::Building),
+    EditorTool::Tile(TileKind::Park),
+    EditorTool::Tile(TileKind::Water),
+    EditorTool::Tile(TileKind::Ground),
+    EditorTool::Prop(PropKind::Barrier),
+    EditorTool::Prop(PropKind::Car),
+    EditorTool::Prop(PropKind::Tree),
+    EditorTool::Prop(PropKind::Streetlight),
+    EditorTool::Prop(PropKind::MarketStall),
+    EditorTool::CartelSpawn,
+    EditorTool::MilitarySpawn,
+    EditorTool::CaptureZone,
+    EditorTool::ObjectiveMarker,
+];
+
+impl EditorTool {
+    pub fn label(&self) -> String {
+        match self {
+            EditorTool::Tile(kind) => format!("Paint {:?}", kind),
+            EditorTool::Prop(kind) => format!("Place {:?}", kind),
+            EditorTool::CartelSpawn => "Cartel spawn point".to_string(),
+            EditorTool::MilitarySpawn => "Military spawn point".to_string(),
+            EditorTool::CaptureZone => "Capture zone".to_string(),
+            EditorTool::ObjectiveMarker => "Objective marker".to_string(),
+        }
+    }
+
+    // Tiles paint along a drag; everything else is placed once per click
+    fn paints(&self) -> bool {
+        matches!(self, EditorTool::Tile(_))
+    }
+
+    pub fn apply(&self, city_map: &mut CityMap, tile: (usize, usize)) -> bool {
+        let walkable = city_map.kind(tile.0, tile.1).is_walkable();
+        match self {
+            EditorTool::Tile(kind) => city_map.set_tile(tile, *kind),
+            EditorTool::Prop(kind) => city_map.place_prop(tile, *kind),
+            EditorTool::CartelSpawn | EditorTool::MilitarySpawn => {
+                let faction = if *self == EditorTool::CartelSpawn {
+                    Faction::Cartel
+                } else {
+                    Faction::Military
+                };
+                if !walkable || city_map.spawn_points.iter().any(|spawn| spawn.tile == tile) {
+                    return false;
+                }
+                city_map.spawn_points.push(SpawnPoint { faction, tile });
+                true
+            }
+            EditorTool::CaptureZone => {
+                if city_map.capture_zones.iter().any(|zone| zone.tile == tile) {
+                    return false;
+                }
+                let name = format!("Zone {}", city_map.capture_zones.len() + 1);
+                city_map.capture_zones.push(CaptureZone {
+                    name,
+                    tile,
+                    radius: DEFAULT_ZONE_RADIUS,
+                });
+                true
+            }
+            EditorTool::ObjectiveMarker => {
+                if city_map
+                    .objective_markers
+                    .iter()
+                    .any(|marker| marker.tile == tile)
+                {
+                    return false;
+                }
+                let label = format!("Objective {}", city_map.objective_markers.len() + 1);
+                city_map
+                    .objective_markers
+                    .push(ObjectiveMarker { label, tile });
+                true
+            }
+        }
+    }
+}
+
+// ==================== UNDO / REDO ====================
+
+// Whole-map snapshots: a city map is a few thousand tiles, so copying it per
+// edit is cheaper than tracking the inverse of every kind of change
+#[derive(Default)]
+pub struct EditHistory {
+    undo: Vec<CityMap>,
+    redo: Vec<CityMap>,
+}
+
+impl EditHistory {
+    // Call with the map as it was before an edit
+    pub fn record(&mut self, before: CityMap) {
+        if self.undo.len() >= HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push(before);
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self, city_map: &mut CityMap) -> bool {
+        let Some(previous) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(std::mem::replace(city_map, previous));
+        true
+    }
+
+    pub fn redo(&mut self, city_map: &mut CityMap) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(std::mem::replace(city_map, next));
+        true
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct MapEditor {
+    pub active: bool,
+    pub unsaved_changes: bool,
+    tool_index: usize,
+    history: EditHistory,
+    stroke_start: Option<CityMap>, // Map as the current drag found it, until it changes something
+    hovered_tile: Option<(usize, usize)>,
+}
+
+impl MapEditor {
+    pub fn tool(&self) -> EditorTool {
+        EDITOR_TOOLS[self.tool_index]
+    }
+}
+
+pub fn editor_active(editor: Option<Res<MapEditor>>) -> bool {
+    editor.is_some_and(|editor| editor.active)
+}
+
+pub fn editor_inactive(editor: Option<Res<MapEditor>>) -> bool {
+    !editor_active(editor)
+}
+
+// ==================== EDITOR SYSTEMS ====================
+
+pub fn map_editor_toggle_system(
+    input: Res<Input<KeyCode>>,
+    mut editor: ResMut<MapEditor>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if !input.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    editor.active = !editor.active;
+    editor.stroke_start = None;
+    // The battle holds still while the map changes under it
+    if editor.active {
+        virtual_time.pause();
+        info!("🛠️ Map editor opened - {}", editor.tool().label());
+    } else {
+        virtual_time.unpause();
+        if editor.unsaved_changes {
+            warn!("Map editor closed with unsaved changes (Ctrl+S saves)");
+        }
+    }
+}
+
+pub fn map_editor_input_system(
+    input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut editor: ResMut<MapEditor>,
+    mut city_map: ResMut<CityMap>,
+) {
+    let ctrl = input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    if input.just_pressed(KeyCode::Tab) {
+        let count = EDITOR_TOOLS.len();
+        editor.tool_index = if shift {
+            (editor.tool_index + count - 1) % count
+        } else {
+            (editor.tool_index + 1) % count
+        };
+        info!("🛠️ {}", editor.tool().label());
+    }
+
+    if ctrl && input.just_pressed(KeyCode::Z) {
+        let editor = &mut *editor;
+        let changed = if shift {
+            editor.history.redo(&mut city_map)
+        } else {
+            editor.history.undo(&mut city_map)
+        };
+        editor.unsaved_changes |= changed;
+        return;
+    }
+    if ctrl && input.just_pressed(KeyCode::Y) {
+        let editor = &mut *editor;
+        editor.unsaved_changes |= editor.history.redo(&mut city_map);
+        return;
+    }
+    if ctrl && input.just_pressed(KeyCode::S) {
+        // Edits to a modded map go back into its pack
+        let path = city_map_path();
+        match save_city_map(&city_map, &path) {
+            Ok(()) => {
+                editor.unsaved_changes = false;
+                info!("💾 Map '{}' saved to {}", city_map.name, path.display());
+            }
+            Err(e) => error!("Failed to save map: {}", e),
+        }
+        return;
+    }
+
+    // Which tile the cursor is over. Hover and drag state skip change
+    // detection, which the overlay uses to know when to rebuild.
+    let hovered = windows.get_single().ok().and_then(|window| {
+        let (camera, camera_transform) = camera_query.get_single().ok()?;
+        let cursor = window.cursor_position()?;
+        let world = camera.viewport_to_world_2d(camera_transform, cursor)?;
+        city_map.tile_at(world)
+    });
+    let editor_state = editor.bypass_change_detection();
+    editor_state.hovered_tile = hovered;
+    if mouse_input.just_pressed(MouseButton::Left) {
+        editor_state.stroke_start = Some(city_map.clone());
+    } else if mouse_input.just_released(MouseButton::Left) {
+        editor_state.stroke_start = None;
+    }
+    let Some(tile) = hovered else {
+        return;
+    };
+
+    let tool = editor.tool();
+    let placing = if tool.paints() {
+        mouse_input.pressed(MouseButton::Left)
+    } else {
+        mouse_input.just_pressed(MouseButton::Left)
+    };
+
+    if placing {
+        // Only flag the map changed when the edit does something, so an idle
+        // drag doesn't trigger a redraw every frame
+        if tool.apply(city_map.bypass_change_detection(), tile) {
+            city_map.set_changed();
+            // A whole drag is one undo step
+            if let Some(before) = editor.stroke_start.take() {
+                editor.history.record(before);
+            }
+            editor.unsaved_changes = true;
+        }
+    } else if mouse_input.just_pressed(MouseButton::Right) {
+        let mut edited = city_map.clone();
+        if edited.clear_tile(tile) {
+            editor
+                .history
+                .record(std::mem::replace(&mut *city_map, edited));
+            editor.unsaved_changes = true;
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct EditorOverlay;
+
+#[derive(Component)]
+pub struct EditorCursor;
+
+#[derive(Component)]
+pub struct EditorHud;
+
+// Spawn points and the hovered tile only show while editing; the HUD names
+// the current tool
+pub fn map_editor_overlay_system(
+    mut commands: Commands,
+    editor: Res<MapEditor>,
+    city_map: Res<CityMap>,
+    overlay_query: Query<Entity, With<EditorOverlay>>,
+    mut cursor_query: Query<(&mut Transform, &mut Visibility), With<EditorCursor>>,
+    mut hud_query: Query<&mut Text, With<EditorHud>>,
+) {
+    if editor.is_changed() || city_map.is_changed() {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        if editor.active {
+            spawn_editor_overlay(&mut commands, &editor, &city_map);
+        }
+        return;
+    }
+
+    if let Ok((mut transform, mut visibility)) = cursor_query.get_single_mut() {
+        match editor.hovered_tile {
+            Some((col, row)) => {
+                transform.translation = city_map.tile_center(col, row).extend(OVERLAY_Z);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    if let Ok(mut text) = hud_query.get_single_mut() {
+        text.sections[0].value = editor_hud_text(&editor);
+    }
+}
+
+fn editor_hud_text(editor: &MapEditor) -> String {
+    format!(
+        "MAP EDITOR{}\nTool: {}\nTab/Shift+Tab: tool  LMB: place  RMB: clear\nCtrl+Z/Ctrl+Y: undo/redo  Ctrl+S: save  F12: exit",
+        if editor.unsaved_changes { " *" } else { "" },
+        editor.tool().label()
+    )
+}
+
+fn spawn_editor_overlay(commands: &mut Commands, editor: &MapEditor, city_map: &CityMap) {
+    let marker = |color: Color, size: f32, position: Vec2| {
+        (
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(size)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(OVERLAY_Z)),
+                ..default()
+            },
+            EditorOverlay,
+        )
+    };
+
+    for spawn in &city_map.spawn_points {
+        let color = match spawn.faction {
+            Faction::Cartel => Color::rgba(0.9, 0.2, 0.2, 0.8),
+            _ => Color::rgba(0.2, 0.6, 0.2, 0.8),
+        };
+        commands.spawn(marker(
+            color,
+            city_map.tile_size * 0.6,
+            city_map.tile_center(spawn.tile.0, spawn.tile.1),
+        ));
+    }
+
+    commands.spawn((
+        marker(
+            Color::rgba(1.0, 1.0, 1.0, 0.25),
+            city_map.tile_size,
+            Vec2::ZERO,
+        ),
+        EditorCursor,
+    ));
+
+    commands.spawn((
+        TextBundle::from_section(
+            editor_hud_text(editor),
+            TextStyle {
+                font_size: 16.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            right: Val::Px(20.0),
+            top: Val::Px(20.0),
+            ..default()
+        })
+        .with_background_color(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+        EditorOverlay,
+        EditorHud,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MAP: &str = r#"{
+        "name": "Test",
+        "tile_size": 10.0,
+        "tiles": ["...", "...", "..."],
+        "districts": ["AAA", "AAA", "AAA"],
+        "neighborhoods": [{ "key": "A", "name": "Centro" }]
+    }"#;
+
+    #[test]
+    fn test_edit_history_undo_redo() {
+        let mut map = CityMap::from_json(TEST_MAP).unwrap();
+        let mut history = EditHistory::default();
+
+        let before = map.clone();
+        assert!(EditorTool::Tile(TileKind::Road).apply(&mut map, (1, 1)));
+        history.record(before);
+        let before = map.clone();
+        assert!(EditorTool::MilitarySpawn.apply(&mut map, (0, 0)));
+        history.record(before);
+
+        assert!(history.undo(&mut map));
+        assert!(map.spawn_points.is_empty());
+        assert!(history.undo(&mut map));
+        assert_eq!(map.kind(1, 1), TileKind::Ground);
+        assert!(!history.undo(&mut map));
+
+        assert!(history.redo(&mut map));
+        assert_eq!(map.kind(1, 1), TileKind::Road);
+        // A fresh edit drops the redo branch
+        history.record(map.clone());
+        assert!(!history.redo(&mut map));
+    }
+
+    #[test]
+    fn test_edited_map_round_trips_through_json() {
+        let mut map = CityMap::from_json(TEST_MAP).unwrap();
+        EditorTool::Tile(TileKind::Building).apply(&mut map, (2, 0));
+        EditorTool::Prop(PropKind::Barrier).apply(&mut map, (0, 2));
+        EditorTool::CartelSpawn.apply(&mut map, (0, 0));
+        EditorTool::CaptureZone.apply(&mut map, (1, 1));
+        EditorTool::ObjectiveMarker.apply(&mut map, (2, 2));
+        // Nothing can be placed inside a building
+        assert!(!EditorTool::Prop(PropKind::Car).apply(&mut map, (2, 0)));
+
+        let loaded = CityMap::from_json(&map.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.kind(2, 0), TileKind::Building);
+        assert_eq!(loaded.props.len(), 1);
+        assert_eq!(
+            loaded.spawn_positions(&Faction::Cartel),
+            vec![loaded.tile_center(0, 0)]
+        );
+        assert_eq!(loaded.capture_zones[0].name, "Zone 1");
+        assert_eq!(loaded.objective_markers[0].tile, (2, 2));
+        assert_eq!(
+            loaded
+                .neighborhood_at(loaded.tile_center(1, 1))
+                .unwrap()
+                .name,
+            "Centro"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use crate::config::mod_registry;
+use crate::map::CityMap;
+use bevy::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ==================== CITY MAP LOADING ====================
+
+pub const CITY_MAP_PATH: &str = "assets/maps/culiacan_city.json";
+
+const ZONE_Z: f32 = -8.8; // Tinted over the tiles, under decals
+const MARKER_Z: f32 = -7.5;
+
+// The map missions play on: the last mod pack that ships one, else the base map
+pub fn city_map_path() -> PathBuf {
+    mod_registry()
+        .map_path()
+        .map_or_else(|| PathBuf::from(CITY_MAP_PATH), Path::to_path_buf)
+}
+
+pub fn load_city_map() -> CityMap {
+    match read_city_map_file(&city_map_path()) {
+        Ok(map) => {
+            info!(
+                "🗺️ City map '{}' loaded ({}x{} tiles, {} neighborhoods)",
+                map.name,
+                map.width,
+                map.height,
+                map.neighborhoods.len()
+            );
+            map
+        }
+        Err(e) => {
+            warn!("Failed to load city map: {}, using an empty battlefield", e);
+            CityMap::default()
+        }
+    }
+}
+
+fn read_city_map_file(path: &Path) -> Result<CityMap, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)?;
+    CityMap::from_json(&contents)
+}
+
+// Writes to a temporary file first so a failed save can't truncate the map
+pub fn save_city_map(city_map: &CityMap, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let json = city_map.to_json()?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+// ==================== CITY MAP RENDERING ====================
+
+// Tiles and props are streamed in chunks around the camera (see map_chunks);
+// the capture zones and objective markers drawn here are few and always shown
+
+#[derive(Component)]
+pub struct MapTile;
+
+pub fn spawn_city_map_system(mut commands: Commands, city_map: Res<CityMap>) {
+    draw_city_map(&mut commands, &city_map);
+}
+
+// Redraw the overlays after the map editor changes the map
+pub fn redraw_city_map_system(
+    mut commands: Commands,
+    city_map: Res<CityMap>,
+    tile_query: Query<Entity, With<MapTile>>,
+) {
+    if !city_map.is_changed() || city_map.is_added() {
+        return;
+    }
+    for entity in tile_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    draw_city_map(&mut commands, &city_map);
+}
+
+fn draw_city_map(commands: &mut Commands, city_map: &CityMap) {
+    for zone in &city_map.capture_zones {
+        let diameter = (zone.radius as f32 * 2.0 + 1.0) * city_map.tile_size;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(0.9, 0.75, 0.2, 0.15),
+                    custom_size: Some(Vec2::splat(diameter)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    city_map
+                        .tile_center(zone.tile.0, zone.tile.1)
+                        .extend(ZONE_Z),
+                ),
+                ..default()
+            },
+            MapTile,
+        ));
+    }
+
+    for marker in &city_map.objective_markers {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(1.0, 0.85, 0.1),
+                    custom_size: Some(Vec2::splat(14.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    city_map
+                        .tile_center(marker.tile.0, marker.tile.1)
+                        .extend(MARKER_Z),
+                )
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+                ..default()
+            },
+            MapTile,
+        ));
+    }
+}
@@ -0,0 +1,11 @@
+pub mod city_life;
+pub mod city_map;
+pub mod map_chunks;
+pub mod map_editor;
+pub mod map_systems;
+
+pub use city_life::*;
+pub use city_map::*;
+pub use map_chunks::*;
+pub use map_editor::*;
+pub use map_systems::*;
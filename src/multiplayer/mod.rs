@@ -1,3 +1,5 @@
 pub mod multiplayer_system;
+pub mod social_system;
 
 pub use multiplayer_system::*;
+pub use social_system::*;
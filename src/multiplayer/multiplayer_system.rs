@@ -1,7 +1,13 @@
 use crate::auth::models::User;
+use crate::auth::AuthSession;
+use crate::battle_plan::{BattlePlan, PlanMark};
 use crate::campaign::VictoryType;
 use crate::components::*;
+use crate::profile::{ProfileManager, DEFAULT_ELO};
 use crate::resources::*;
+use crate::save::save_system::MissionId;
+use crate::ui::UiTheme;
+use crate::utils::{create_dashboard_panel, dashboard_line, update_dashboard_panel};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -64,12 +70,47 @@ impl Default for MultiplayerState {
     }
 }
 
+impl MultiplayerState {
+    /// Whether `other` plays on the same side as `player`. Observers see
+    /// every side.
+    pub fn teammates(&self, player: Uuid, other: Uuid) -> bool {
+        match (
+            self.player_assignments.get(&player),
+            self.player_assignments.get(&other),
+        ) {
+            (Some(PlayerRole::Observer), Some(_)) => true,
+            (Some(role), Some(other_role)) => {
+                role.side().is_some() && role.side() == other_role.side()
+            }
+            _ => false,
+        }
+    }
+
+    /// Average rating of the players on the other side from `player`, or
+    /// `None` when there's no one to rate the match against.
+    pub fn opponent_elo(&self, player: Uuid) -> Option<i32> {
+        let side = self.player_assignments.get(&player)?.side()?;
+        let ratings: Vec<i32> = self
+            .player_assignments
+            .iter()
+            .filter(|(_, role)| role.side().is_some_and(|other| other != side))
+            .filter_map(|(id, _)| self.connected_players.get(id))
+            .map(|info| info.elo_rating)
+            .collect();
+        if ratings.is_empty() {
+            return None;
+        }
+        Some(ratings.iter().sum::<i32>() / ratings.len() as i32)
+    }
+}
+
 #[derive(Resource)]
 pub struct NetworkManager {
     pub message_sender: Option<mpsc::UnboundedSender<NetworkMessage>>,
     pub message_receiver: Option<mpsc::UnboundedReceiver<NetworkMessage>>,
     pub player_id: Uuid,
     pub auth_token: Option<String>,
+    pub offline_mode: bool,
 }
 
 impl Default for NetworkManager {
@@ -79,6 +120,7 @@ impl Default for NetworkManager {
             message_receiver: None,
             player_id: Uuid::new_v4(),
             auth_token: None,
+            offline_mode: true,
         }
     }
 }
@@ -108,6 +150,12 @@ pub struct PlayerInfo {
     pub ping: u32,
     pub ready: bool,
     pub faction_preference: Option<Faction>,
+    #[serde(default = "default_elo")] // Clients that don't send a rating
+    pub elo_rating: i32,
+}
+
+fn default_elo() -> i32 {
+    DEFAULT_ELO
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -119,6 +167,19 @@ pub enum PlayerRole {
     Observer,            // Watch-only mode
 }
 
+impl PlayerRole {
+    // Intelligence here is the government's; observers take no side
+    pub fn side(&self) -> Option<Faction> {
+        match self {
+            PlayerRole::CartelCommander => Some(Faction::Cartel),
+            PlayerRole::MilitaryCommander
+            | PlayerRole::GovernmentAdvisor
+            | PlayerRole::IntelligenceOfficer => Some(Faction::Military),
+            PlayerRole::Observer => None,
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq)]
 pub enum PlayerConnectionStatus {
     Connected,
@@ -165,6 +226,13 @@ pub enum NetworkMessage {
         decision: PoliticalDecision,
     },
 
+    // A player's battle plan for a mission, whole, each time it changes
+    BattlePlan {
+        player_id: Uuid,
+        mission: MissionId,
+        marks: Vec<PlanMark>,
+    },
+
     // Communication
     ChatMessage {
         player_id: Uuid,
@@ -281,14 +349,37 @@ pub enum Formation {
 pub fn multiplayer_lobby_system(
     mut multiplayer_state: ResMut<MultiplayerState>,
     mut network_manager: ResMut<NetworkManager>,
+    mut battle_plan: Option<ResMut<BattlePlan>>,
+    mut profiles: ResMut<ProfileManager>,
     time: Res<Time>,
 ) {
+    let _span = info_span!("multiplayer_lobby_system").entered();
     multiplayer_state.sync_interval.tick(time.delta());
 
     // Process incoming network messages
+    let own_id = network_manager.player_id;
     if let Some(receiver) = &mut network_manager.message_receiver {
         while let Ok(message) = receiver.try_recv() {
             process_network_message(&mut multiplayer_state, &message);
+
+            if let NetworkMessage::GameEnd { result } = &message {
+                record_match_result(&multiplayer_state, own_id, result, &mut profiles);
+            }
+
+            // Only teammates' plans go on the map
+            if let (
+                NetworkMessage::BattlePlan {
+                    player_id,
+                    mission,
+                    marks,
+                },
+                Some(battle_plan),
+            ) = (message, battle_plan.as_deref_mut())
+            {
+                if multiplayer_state.teammates(own_id, player_id) {
+                    battle_plan.receive(player_id, mission, marks);
+                }
+            }
         }
     }
 
@@ -328,8 +419,12 @@ fn process_network_message(multiplayer_state: &mut MultiplayerState, message: &N
             if *success {
                 multiplayer_state.connection_status = ConnectionStatus::Connected;
             } else {
-                multiplayer_state.connection_status =
-                    ConnectionStatus::Error("Authentication failed".to_string());
+                // Fall back to local play rather than leaving the lobby in an error state
+                warn!(
+                    "Authentication failed for player {}, continuing in offline mode",
+                    player_id
+                );
+                multiplayer_state.connection_status = ConnectionStatus::Disconnected;
             }
         }
 
@@ -337,6 +432,41 @@ fn process_network_message(multiplayer_state: &mut MultiplayerState, message: &N
     }
 }
 
+// The match goes on the active profile's record; observers aren't rated
+fn record_match_result(
+    multiplayer_state: &MultiplayerState,
+    player_id: Uuid,
+    result: &GameResult,
+    profiles: &mut ProfileManager,
+) {
+    let Some(side) = multiplayer_state
+        .player_assignments
+        .get(&player_id)
+        .and_then(PlayerRole::side)
+    else {
+        return;
+    };
+    let (Some(opponent_elo), Some(stats)) = (
+        multiplayer_state.opponent_elo(player_id),
+        result.player_stats.get(&player_id),
+    ) else {
+        warn!("Match ended without ratings or stats for this player, not recorded");
+        return;
+    };
+    let Some(profile) = profiles.active.as_mut() else {
+        return;
+    };
+
+    let won = result.winner == Some(side);
+    profile.multiplayer.record_match(won, opponent_elo, stats);
+    info!(
+        "👤 Match recorded: {} (rating {})",
+        if won { "win" } else { "loss" },
+        profile.multiplayer.elo_rating
+    );
+    profiles.save_active();
+}
+
 fn assign_player_role(multiplayer_state: &mut MultiplayerState, player_id: Uuid) {
     let existing_roles: Vec<PlayerRole> = multiplayer_state
         .player_assignments
@@ -412,6 +542,7 @@ pub fn player_connection_system(
     mut network_manager: ResMut<NetworkManager>,
     time: Res<Time>,
 ) {
+    let _span = info_span!("player_connection_system").entered();
     // Monitor connection health
     let mut disconnected_players = Vec::new();
 
@@ -457,6 +588,7 @@ pub fn game_sync_system(
     unit_query: Query<(Entity, &Transform, &Unit)>,
     time: Res<Time>,
 ) {
+    let _span = info_span!("game_sync_system").entered();
     if !multiplayer_state.sync_interval.finished() {
         return;
     }
@@ -498,6 +630,7 @@ pub fn player_input_sync_system(
     keys: Res<Input<KeyCode>>,
     mouse_button_input: Res<Input<MouseButton>>,
 ) {
+    let _span = info_span!("player_input_sync_system").entered();
     if !multiplayer_state.game_started {
         return;
     }
@@ -524,159 +657,141 @@ pub fn player_input_sync_system(
 pub fn multiplayer_ui_system(
     mut commands: Commands,
     multiplayer_state: Res<MultiplayerState>,
-    existing_ui: Query<Entity, With<MultiplayerUIPanel>>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<MultiplayerUIPanel>>,
+    theme: Res<UiTheme>,
 ) {
-    // Remove existing multiplayer UI
-    for entity in existing_ui.iter() {
-        commands.entity(entity).despawn_recursive();
+    let panel_exists = !panel_query.is_empty();
+    if panel_exists && !multiplayer_state.is_changed() && !theme.is_changed() {
+        return;
     }
 
-    // Create multiplayer status panel
-    if multiplayer_state.connected_players.len() > 1
+    // Only show the multiplayer status panel while a session is active
+    let session_active = multiplayer_state.connected_players.len() > 1
         || !matches!(
             multiplayer_state.connection_status,
             ConnectionStatus::Disconnected
-        )
-    {
-        spawn_multiplayer_ui_panel(&mut commands, &multiplayer_state);
+        );
+    let visibility = if session_active {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let lines = multiplayer_panel_lines(&multiplayer_state);
+
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else if !panel_exists {
+        let mut panel = create_dashboard_panel(
+            Style {
+                left: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                width: Val::Px(300.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.8),
+            lines,
+            &theme,
+        );
+        panel.0.visibility = visibility;
+        commands.spawn((panel, MultiplayerUIPanel));
     }
 }
 
 #[derive(Component)]
 pub struct MultiplayerUIPanel;
 
-fn spawn_multiplayer_ui_panel(commands: &mut Commands, multiplayer_state: &MultiplayerState) {
-    commands
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    left: Val::Px(10.0),
-                    bottom: Val::Px(10.0),
-                    width: Val::Px(300.0),
-                    height: Val::Auto,
-                    flex_direction: FlexDirection::Column,
-                    padding: UiRect::all(Val::Px(10.0)),
-                    ..default()
-                },
-                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8)),
-                ..default()
-            },
-            MultiplayerUIPanel,
-        ))
-        .with_children(|parent| {
-            // Multiplayer status title
-            parent.spawn(TextBundle::from_section(
-                "🌐 MULTIPLAYER",
-                TextStyle {
-                    font_size: 16.0,
-                    color: Color::CYAN,
-                    ..default()
-                },
-            ));
-
-            // Connection status
-            let status_color = match multiplayer_state.connection_status {
-                ConnectionStatus::Connected | ConnectionStatus::Hosting => Color::GREEN,
-                ConnectionStatus::Connecting => Color::YELLOW,
-                ConnectionStatus::Error(_) => Color::RED,
-                ConnectionStatus::Disconnected => Color::GRAY,
-            };
-
-            let status_text = match &multiplayer_state.connection_status {
-                ConnectionStatus::Disconnected => "Offline",
-                ConnectionStatus::Connecting => "Connecting...",
-                ConnectionStatus::Connected => "Connected",
-                ConnectionStatus::Hosting => "Hosting",
-                ConnectionStatus::Error(msg) => msg,
-            };
-
-            parent.spawn(TextBundle::from_section(
-                format!("Status: {}", status_text),
-                TextStyle {
-                    font_size: 12.0,
-                    color: status_color,
-                    ..default()
-                },
-            ));
-
-            // Game mode
-            let mode_text = match multiplayer_state.game_mode {
-                MultiplayerGameMode::Asymmetric => "Asymmetric (2v2)",
-                MultiplayerGameMode::Historical => "Historical",
-                MultiplayerGameMode::Cooperative => "Cooperative",
-                MultiplayerGameMode::Competitive => "Competitive",
-            };
-
-            parent.spawn(TextBundle::from_section(
-                format!("Mode: {}", mode_text),
-                TextStyle {
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                    ..default()
-                },
-            ));
-
-            // Connected players
-            parent.spawn(TextBundle::from_section(
-                format!(
-                    "Players: {}/{}",
-                    multiplayer_state.connected_players.len(),
-                    multiplayer_state.max_players
-                ),
-                TextStyle {
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                    ..default()
-                },
-            ));
-
-            // Player list
-            for (player_id, player_info) in &multiplayer_state.connected_players {
-                let role = multiplayer_state
-                    .player_assignments
-                    .get(player_id)
-                    .map(|r| format!("{:?}", r))
-                    .unwrap_or_else(|| "Unassigned".to_string());
-
-                let ping_color = if player_info.ping < 100 {
-                    Color::GREEN
-                } else if player_info.ping < 300 {
-                    Color::YELLOW
-                } else {
-                    Color::RED
-                };
-
-                let ready_indicator = if player_info.ready { "✓" } else { "○" };
-
-                parent.spawn(TextBundle::from_section(
-                    format!(
-                        "{} {} ({}ms) - {}",
-                        ready_indicator, player_info.username, player_info.ping, role
-                    ),
-                    TextStyle {
-                        font_size: 10.0,
-                        color: ping_color,
-                        ..default()
-                    },
-                ));
-            }
+fn multiplayer_panel_lines(multiplayer_state: &MultiplayerState) -> Vec<TextSection> {
+    // Multiplayer status title
+    let mut lines = vec![dashboard_line("🌐 MULTIPLAYER", 16.0, Color::CYAN)];
 
-            // Session info
-            if multiplayer_state.is_host {
-                parent.spawn(TextBundle::from_section(
-                    format!(
-                        "Session: {}",
-                        multiplayer_state.session_id.to_string()[..8].to_uppercase()
-                    ),
-                    TextStyle {
-                        font_size: 9.0,
-                        color: Color::GRAY,
-                        ..default()
-                    },
-                ));
-            }
-        });
+    // Connection status
+    let status_color = match multiplayer_state.connection_status {
+        ConnectionStatus::Connected | ConnectionStatus::Hosting => Color::GREEN,
+        ConnectionStatus::Connecting => Color::YELLOW,
+        ConnectionStatus::Error(_) => Color::RED,
+        ConnectionStatus::Disconnected => Color::GRAY,
+    };
+
+    let status_text = match &multiplayer_state.connection_status {
+        ConnectionStatus::Disconnected => "Offline",
+        ConnectionStatus::Connecting => "Connecting...",
+        ConnectionStatus::Connected => "Connected",
+        ConnectionStatus::Hosting => "Hosting",
+        ConnectionStatus::Error(msg) => msg,
+    };
+
+    lines.push(dashboard_line(
+        format!("Status: {}", status_text),
+        12.0,
+        status_color,
+    ));
+
+    // Game mode
+    let mode_text = match multiplayer_state.game_mode {
+        MultiplayerGameMode::Asymmetric => "Asymmetric (2v2)",
+        MultiplayerGameMode::Historical => "Historical",
+        MultiplayerGameMode::Cooperative => "Cooperative",
+        MultiplayerGameMode::Competitive => "Competitive",
+    };
+
+    lines.push(dashboard_line(
+        format!("Mode: {}", mode_text),
+        12.0,
+        Color::WHITE,
+    ));
+
+    // Connected players
+    lines.push(dashboard_line(
+        format!(
+            "Players: {}/{}",
+            multiplayer_state.connected_players.len(),
+            multiplayer_state.max_players
+        ),
+        12.0,
+        Color::WHITE,
+    ));
+
+    // Player list
+    for (player_id, player_info) in &multiplayer_state.connected_players {
+        let role = multiplayer_state
+            .player_assignments
+            .get(player_id)
+            .map(|r| format!("{:?}", r))
+            .unwrap_or_else(|| "Unassigned".to_string());
+
+        let ping_color = if player_info.ping < 100 {
+            Color::GREEN
+        } else if player_info.ping < 300 {
+            Color::YELLOW
+        } else {
+            Color::RED
+        };
+
+        let ready_indicator = if player_info.ready { "✓" } else { "○" };
+
+        lines.push(dashboard_line(
+            format!(
+                "{} {} ({}ms) - {}",
+                ready_indicator, player_info.username, player_info.ping, role
+            ),
+            10.0,
+            ping_color,
+        ));
+    }
+
+    // Friends join through lobby invites rather than a shared session ID
+    if multiplayer_state.is_host {
+        lines.push(dashboard_line(
+            "F6: Invite online friends",
+            9.0,
+            Color::GRAY,
+        ));
+    }
+
+    lines
 }
 
 // ==================== AUTHENTICATION INTEGRATION ====================
@@ -684,12 +799,20 @@ fn spawn_multiplayer_ui_panel(commands: &mut Commands, multiplayer_state: &Multi
 pub fn authenticate_multiplayer_session(
     network_manager: &mut NetworkManager,
     user: &User,
+    auth_session: &AuthSession,
 ) -> Result<(), String> {
-    // Generate session token using existing auth system
-    let token = format!("mp_{}_{}", user.id, Uuid::new_v4());
-    network_manager.auth_token = Some(token.clone());
     network_manager.player_id = user.id;
 
+    // Without a valid access token the session stays local instead of failing
+    let Some(token) = auth_session.access_token().map(str::to_string) else {
+        warn!("No valid auth token for {}, playing offline", user.username);
+        network_manager.auth_token = None;
+        network_manager.offline_mode = true;
+        return Ok(());
+    };
+    network_manager.auth_token = Some(token.clone());
+    network_manager.offline_mode = false;
+
     // Send authentication request
     if let Some(sender) = &network_manager.message_sender {
         sender
@@ -0,0 +1,537 @@
+use crate::auth::{
+    AuthSession, FriendPresence, GameAuthIntegration, LobbyInviteInfo, PresenceRequest,
+    RespondInviteRequest, SendInviteRequest,
+};
+use crate::localization::{tr, tr_args};
+use crate::multiplayer::{ConnectionStatus, MultiplayerState, NetworkManager, NetworkMessage};
+use crate::profile::ProfileManager;
+use crate::utils::play_tactical_sound;
+use bevy::prelude::*;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+// ==================== SOCIAL SYSTEM PLUGIN ====================
+
+pub struct SocialSystemPlugin;
+
+impl Plugin for SocialSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SocialState>().add_systems(
+            Update,
+            (
+                social_polling_system,
+                social_event_system,
+                lobby_invite_input_system,
+                invite_toast_ui_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+// ==================== SOCIAL RESOURCES ====================
+
+const SOCIAL_POLL_INTERVAL: f32 = 15.0;
+
+#[derive(Resource)]
+pub struct SocialState {
+    pub presence: HashMap<Uuid, FriendPresence>,
+    pub pending_invites: Vec<LobbyInviteInfo>,
+    poll_timer: Timer,
+    commands: Option<mpsc::UnboundedSender<SocialCommand>>,
+    events: Option<mpsc::UnboundedReceiver<SocialEvent>>,
+    announced_invites: HashSet<Uuid>,
+    toast_dirty: bool,
+}
+
+impl Default for SocialState {
+    fn default() -> Self {
+        let mut poll_timer = Timer::from_seconds(SOCIAL_POLL_INTERVAL, TimerMode::Repeating);
+        // Poll right away once we come online
+        poll_timer.set_elapsed(poll_timer.duration());
+
+        Self {
+            presence: HashMap::new(),
+            pending_invites: Vec::new(),
+            poll_timer,
+            commands: None,
+            events: None,
+            announced_invites: HashSet::new(),
+            toast_dirty: false,
+        }
+    }
+}
+
+impl SocialState {
+    pub fn is_online(&self, user_id: Uuid) -> bool {
+        self.presence.get(&user_id).is_some_and(|p| p.online)
+    }
+
+    pub fn request_add_friend(&mut self, auth_session: &AuthSession, username: &str) {
+        self.send(auth_session, |token| SocialCommand::FindUser {
+            token,
+            username: username.to_string(),
+        });
+    }
+
+    pub fn invite_friend(
+        &mut self,
+        auth_session: &AuthSession,
+        friend_id: Uuid,
+        session_id: Uuid,
+        lobby_name: &str,
+    ) {
+        self.send(auth_session, |token| SocialCommand::SendInvite {
+            token,
+            request: SendInviteRequest {
+                to_user_id: friend_id,
+                session_id,
+                lobby_name: lobby_name.to_string(),
+            },
+        });
+    }
+
+    fn send(&mut self, auth_session: &AuthSession, command: impl FnOnce(String) -> SocialCommand) {
+        let Some(token) = auth_session.access_token() else {
+            warn!("Social features unavailable while offline");
+            return;
+        };
+
+        let sender = self.commands.get_or_insert_with(|| {
+            let (command_tx, command_rx) = mpsc::unbounded_channel();
+            let (event_tx, event_rx) = mpsc::unbounded_channel();
+            spawn_social_worker(
+                GameAuthIntegration::new().get_api_base_url(),
+                command_rx,
+                event_tx,
+            );
+            self.events = Some(event_rx);
+            command_tx
+        });
+
+        if sender.send(command(token.to_string())).is_err() {
+            // Worker died; a fresh one is started on the next request
+            self.commands = None;
+            self.events = None;
+        }
+    }
+
+    fn take_invite(&mut self, invite_index: usize) -> Option<LobbyInviteInfo> {
+        if invite_index >= self.pending_invites.len() {
+            return None;
+        }
+
+        self.toast_dirty = true;
+        Some(self.pending_invites.remove(invite_index))
+    }
+}
+
+// ==================== SOCIAL WORKER ====================
+
+pub enum SocialCommand {
+    Poll {
+        token: String,
+        friend_ids: Vec<Uuid>,
+    },
+    FindUser {
+        token: String,
+        username: String,
+    },
+    SendInvite {
+        token: String,
+        request: SendInviteRequest,
+    },
+    RespondInvite {
+        token: String,
+        invite_id: Uuid,
+        accept: bool,
+    },
+}
+
+pub enum SocialEvent {
+    Presence(Vec<FriendPresence>),
+    Invites(Vec<LobbyInviteInfo>),
+    FriendFound { user_id: Uuid, username: String },
+    InviteSent,
+    InviteAccepted { session_id: Uuid },
+    Error(String),
+}
+
+#[derive(Deserialize)]
+struct FoundUser {
+    id: Uuid,
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct InviteResponse {
+    session_id: Uuid,
+}
+
+fn spawn_social_worker(
+    base_url: String,
+    mut commands: mpsc::UnboundedReceiver<SocialCommand>,
+    events: mpsc::UnboundedSender<SocialEvent>,
+) {
+    // Same approach as token renewal: HTTP needs a tokio reactor that Bevy doesn't provide
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                let _ = events.send(SocialEvent::Error(format!(
+                    "Failed to start social worker: {}",
+                    e
+                )));
+                return;
+            }
+        };
+
+        runtime.block_on(async move {
+            let http_client = Client::new();
+
+            while let Some(command) = commands.recv().await {
+                let result = handle_social_command(&http_client, &base_url, command).await;
+                let batch = result.unwrap_or_else(|e| vec![SocialEvent::Error(e.to_string())]);
+
+                if batch.into_iter().any(|event| events.send(event).is_err()) {
+                    break;
+                }
+            }
+        });
+    });
+}
+
+async fn handle_social_command(
+    http_client: &Client,
+    base_url: &str,
+    command: SocialCommand,
+) -> Result<Vec<SocialEvent>, reqwest::Error> {
+    match command {
+        SocialCommand::Poll { token, friend_ids } => {
+            let presence = http_client
+                .post(format!("{}/social/presence", base_url))
+                .bearer_auth(&token)
+                .json(&PresenceRequest {
+                    user_ids: friend_ids,
+                })
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Vec<FriendPresence>>()
+                .await?;
+
+            let invites = http_client
+                .get(format!("{}/social/invites", base_url))
+                .bearer_auth(&token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Vec<LobbyInviteInfo>>()
+                .await?;
+
+            Ok(vec![
+                SocialEvent::Presence(presence),
+                SocialEvent::Invites(invites),
+            ])
+        }
+        SocialCommand::FindUser { token, username } => {
+            let user = http_client
+                .get(format!("{}/social/users/{}", base_url, username))
+                .bearer_auth(&token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<FoundUser>()
+                .await?;
+
+            Ok(vec![SocialEvent::FriendFound {
+                user_id: user.id,
+                username: user.username,
+            }])
+        }
+        SocialCommand::SendInvite { token, request } => {
+            http_client
+                .post(format!("{}/social/invites", base_url))
+                .bearer_auth(&token)
+                .json(&request)
+                .send()
+                .await?
+                .error_for_status()?;
+
+            Ok(vec![SocialEvent::InviteSent])
+        }
+        SocialCommand::RespondInvite {
+            token,
+            invite_id,
+            accept,
+        } => {
+            let response = http_client
+                .post(format!("{}/social/invites/{}/respond", base_url, invite_id))
+                .bearer_auth(&token)
+                .json(&RespondInviteRequest { accept })
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<InviteResponse>()
+                .await?;
+
+            if accept {
+                Ok(vec![SocialEvent::InviteAccepted {
+                    session_id: response.session_id,
+                }])
+            } else {
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+// ==================== SOCIAL SYSTEMS ====================
+
+pub fn social_polling_system(
+    mut social: ResMut<SocialState>,
+    auth_session: Res<AuthSession>,
+    profiles: Res<ProfileManager>,
+    time: Res<Time>,
+) {
+    let _span = info_span!("social_polling_system").entered();
+    if !auth_session.is_online() {
+        return;
+    }
+
+    social.poll_timer.tick(time.delta());
+    if !social.poll_timer.just_finished() {
+        return;
+    }
+
+    let friend_ids = profiles
+        .active
+        .as_ref()
+        .map(|profile| profile.friends.iter().map(|f| f.user_id).collect())
+        .unwrap_or_default();
+
+    social.send(&auth_session, |token| SocialCommand::Poll {
+        token,
+        friend_ids,
+    });
+}
+
+pub fn social_event_system(
+    mut social: ResMut<SocialState>,
+    mut profiles: ResMut<ProfileManager>,
+    mut multiplayer_state: Option<ResMut<MultiplayerState>>,
+    network_manager: Option<Res<NetworkManager>>,
+) {
+    let _span = info_span!("social_event_system").entered();
+    let mut events = Vec::new();
+    if let Some(receiver) = social.events.as_mut() {
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+    }
+
+    for event in events {
+        match event {
+            SocialEvent::Presence(presence) => {
+                social.presence = presence.into_iter().map(|p| (p.user_id, p)).collect();
+            }
+            SocialEvent::Invites(invites) => {
+                for invite in &invites {
+                    if social.announced_invites.insert(invite.id) {
+                        play_tactical_sound(
+                            "radio",
+                            &tr_args(
+                                "radio.lobby_invite_received",
+                                &[
+                                    ("name", &invite.from_username),
+                                    ("lobby", &invite.lobby_name),
+                                ],
+                            ),
+                        );
+                    }
+                }
+                social.pending_invites = invites;
+                social.toast_dirty = true;
+            }
+            SocialEvent::FriendFound { user_id, username } => {
+                if let Some(profile) = profiles.active.as_mut() {
+                    if profile.add_friend(user_id, &username) {
+                        info!("👥 Added {} to friends", username);
+                        profiles.save_active();
+                    }
+                }
+            }
+            SocialEvent::InviteSent => {
+                play_tactical_sound("radio", &tr("radio.lobby_invite_sent"));
+            }
+            SocialEvent::InviteAccepted { session_id } => match multiplayer_state.as_mut() {
+                Some(state) => {
+                    join_invited_session(state, network_manager.as_deref(), session_id);
+                }
+                None => warn!(
+                    "Multiplayer is disabled; cannot join session {}",
+                    session_id
+                ),
+            },
+            SocialEvent::Error(e) => warn!("Social request failed: {}", e),
+        }
+    }
+}
+
+pub fn join_invited_session(
+    multiplayer_state: &mut MultiplayerState,
+    network_manager: Option<&NetworkManager>,
+    session_id: Uuid,
+) {
+    multiplayer_state.session_id = session_id;
+    multiplayer_state.is_host = false;
+    multiplayer_state.game_started = false;
+    multiplayer_state.connected_players.clear();
+    multiplayer_state.player_assignments.clear();
+    multiplayer_state.connection_status = ConnectionStatus::Connecting;
+
+    if let Some(token) = network_manager.and_then(|nm| nm.auth_token.clone()) {
+        if let Some(sender) = network_manager.and_then(|nm| nm.message_sender.as_ref()) {
+            let _ = sender.send(NetworkMessage::AuthRequest { token });
+        }
+    }
+
+    play_tactical_sound("radio", &tr("radio.lobby_joining"));
+}
+
+pub fn lobby_invite_input_system(
+    mut social: ResMut<SocialState>,
+    auth_session: Res<AuthSession>,
+    profiles: Res<ProfileManager>,
+    multiplayer_state: Option<Res<MultiplayerState>>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let _span = info_span!("lobby_invite_input_system").entered();
+    // F6 - Invite all online friends to the lobby we're hosting
+    if keyboard.just_pressed(KeyCode::F6) {
+        match (multiplayer_state.as_deref(), profiles.active.as_ref()) {
+            (Some(state), Some(profile)) if state.is_host => {
+                let online_friends: Vec<Uuid> = profile
+                    .friends
+                    .iter()
+                    .map(|friend| friend.user_id)
+                    .filter(|id| social.is_online(*id))
+                    .collect();
+
+                if online_friends.is_empty() {
+                    play_tactical_sound("radio", &tr("radio.lobby_no_friends_online"));
+                }
+
+                let lobby_name = format!("{}'s lobby", profile.user.username);
+                for friend_id in online_friends {
+                    social.invite_friend(&auth_session, friend_id, state.session_id, &lobby_name);
+                }
+            }
+            _ => play_tactical_sound("radio", &tr("radio.lobby_invite_needs_host")),
+        }
+    }
+
+    // F7 accepts and F8 declines the oldest pending invite
+    let accept = keyboard.just_pressed(KeyCode::F7);
+    if accept || keyboard.just_pressed(KeyCode::F8) {
+        if let Some(invite) = social.take_invite(0) {
+            social.send(&auth_session, |token| SocialCommand::RespondInvite {
+                token,
+                invite_id: invite.id,
+                accept,
+            });
+        }
+    }
+}
+
+// ==================== INVITE TOAST UI ====================
+
+#[derive(Component)]
+pub struct InviteToast;
+
+pub fn invite_toast_ui_system(
+    mut commands: Commands,
+    mut social: ResMut<SocialState>,
+    existing_toasts: Query<Entity, With<InviteToast>>,
+) {
+    if !social.toast_dirty {
+        return;
+    }
+    social.toast_dirty = false;
+
+    for entity in existing_toasts.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some(invite) = social.pending_invites.first() else {
+        return;
+    };
+
+    let extra_invites = social.pending_invites.len() - 1;
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(10.0),
+                    top: Val::Px(10.0),
+                    width: Val::Px(320.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.1, 0.2, 0.9)),
+                ..default()
+            },
+            InviteToast,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "📨 LOBBY INVITE",
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::CYAN,
+                    ..default()
+                },
+            ));
+
+            parent.spawn(TextBundle::from_section(
+                format!(
+                    "{} invited you to '{}'",
+                    invite.from_username, invite.lobby_name
+                ),
+                TextStyle {
+                    font_size: 13.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            if extra_invites > 0 {
+                parent.spawn(TextBundle::from_section(
+                    format!("+{} more pending", extra_invites),
+                    TextStyle {
+                        font_size: 11.0,
+                        color: Color::GRAY,
+                        ..default()
+                    },
+                ));
+            }
+
+            parent.spawn(TextBundle::from_section(
+                "F7: Accept & join | F8: Decline",
+                TextStyle {
+                    font_size: 12.0,
+                    color: Color::YELLOW,
+                    ..default()
+                },
+            ));
+        });
+}
@@ -0,0 +1,370 @@
+use crate::accessibility::AccessibilitySettings;
+use crate::campaign::Campaign;
+use crate::components::*;
+use crate::localization::{current_language, tr, tr_args};
+use crate::resources::GameState;
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::{Audio, AudioControl};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+
+// ==================== NARRATION ====================
+
+// Speaks focused menu items, objective updates and critical alerts for
+// low-vision players. Speech goes through the platform's text-to-speech
+// command, which CULIACAN_TTS replaces ("program arg..."; the line is appended).
+// Clip mode plays recordings from assets/audio/narration/<language>/<key>.ogg
+// and falls back to speech for lines that have none.
+
+const NARRATION_CLIP_DIR: &str = "audio/narration";
+const TTS_ENV_VAR: &str = "CULIACAN_TTS";
+const MAX_PENDING_LINES: usize = 8;
+const OVIDIO_CRITICAL_HEALTH: f32 = 0.3; // Health ratio that triggers the alert
+
+pub struct NarrationPlugin;
+
+impl Plugin for NarrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Narrator>()
+            .add_event::<NarrationEvent>()
+            .add_systems(Update, (narration_alert_system, narration_system).chain());
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NarrationMode {
+    #[default]
+    Off,
+    Speech,
+    Clips, // Pre-recorded lines where available, speech otherwise
+}
+
+impl NarrationMode {
+    pub fn next(self) -> Self {
+        match self {
+            NarrationMode::Off => NarrationMode::Speech,
+            NarrationMode::Speech => NarrationMode::Clips,
+            NarrationMode::Clips => NarrationMode::Off,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NarrationPriority {
+    Focus,    // Menu navigation, replaced by the next move
+    Update,   // Objective progress
+    Critical, // Cuts off anything less urgent
+}
+
+impl NarrationPriority {
+    // A line cuts off what is playing when it matters more, or when both are
+    // menu focus so fast navigation never lags behind
+    pub fn interrupts(self, current: NarrationPriority) -> bool {
+        self > current || (self == NarrationPriority::Focus && current == NarrationPriority::Focus)
+    }
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct NarrationEvent {
+    pub text: String,
+    pub clip: Option<String>, // Localization key of a recorded line
+    pub priority: NarrationPriority,
+}
+
+impl NarrationEvent {
+    pub fn new(text: impl Into<String>, priority: NarrationPriority) -> Self {
+        Self {
+            text: text.into(),
+            clip: None,
+            priority,
+        }
+    }
+
+    // A fixed localized line, which may have a recording under its key
+    pub fn keyed(key: &str, priority: NarrationPriority) -> Self {
+        Self {
+            text: tr(key),
+            clip: Some(key.to_string()),
+            priority,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Narrator {
+    speaking: Option<(Child, NarrationPriority)>,
+    pending: VecDeque<NarrationEvent>,
+    tts_unavailable: bool, // Warned once; lines then only go to the log
+}
+
+impl Narrator {
+    // Priority of the speech still playing, if any
+    fn speaking_priority(&mut self) -> Option<NarrationPriority> {
+        if let Some((child, priority)) = self.speaking.as_mut() {
+            if matches!(child.try_wait(), Ok(None)) {
+                return Some(*priority);
+            }
+        }
+        self.speaking = None;
+        None
+    }
+
+    fn stop(&mut self) {
+        if let Some((mut child, _)) = self.speaking.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    // Lines that can't interrupt wait their turn; only the latest focus line is kept
+    fn hold(&mut self, line: NarrationEvent) {
+        if line.priority == NarrationPriority::Focus {
+            self.pending
+                .retain(|pending| pending.priority != NarrationPriority::Focus);
+        }
+        if self.pending.len() >= MAX_PENDING_LINES {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(line);
+    }
+
+    fn next_pending(&mut self) -> Option<NarrationEvent> {
+        let index = self
+            .pending
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, line)| (line.priority, std::cmp::Reverse(*index)))
+            .map(|(index, _)| index)?;
+        self.pending.remove(index)
+    }
+}
+
+pub fn narration_system(
+    settings: Res<AccessibilitySettings>,
+    mut events: EventReader<NarrationEvent>,
+    mut narrator: ResMut<Narrator>,
+    asset_server: Res<AssetServer>,
+    audio: Option<Res<Audio>>,
+) {
+    if settings.narration == NarrationMode::Off {
+        events.clear();
+        narrator.stop();
+        narrator.pending.clear();
+        return;
+    }
+
+    for line in events.read() {
+        match narrator.speaking_priority() {
+            Some(current) if !line.priority.interrupts(current) => narrator.hold(line.clone()),
+            _ => {
+                narrator.stop();
+                speak(
+                    &mut narrator,
+                    line,
+                    settings.narration,
+                    &asset_server,
+                    audio.as_deref(),
+                );
+            }
+        }
+    }
+
+    if narrator.speaking_priority().is_none() {
+        if let Some(line) = narrator.next_pending() {
+            speak(
+                &mut narrator,
+                &line,
+                settings.narration,
+                &asset_server,
+                audio.as_deref(),
+            );
+        }
+    }
+}
+
+fn speak(
+    narrator: &mut Narrator,
+    line: &NarrationEvent,
+    mode: NarrationMode,
+    asset_server: &AssetServer,
+    audio: Option<&Audio>,
+) {
+    let text = spoken_text(&line.text);
+    if text.is_empty() {
+        return;
+    }
+    info!("🔈 [NARRATION] {}", text);
+
+    if mode == NarrationMode::Clips {
+        if let (Some(key), Some(audio)) = (line.clip.as_ref(), audio) {
+            let path = format!("{}/{}/{}.ogg", NARRATION_CLIP_DIR, current_language(), key);
+            if Path::new("assets").join(&path).exists() {
+                audio.play(asset_server.load(path));
+                return;
+            }
+        }
+    }
+
+    if narrator.tts_unavailable {
+        return;
+    }
+    match tts_command(&text)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => narrator.speaking = Some((child, line.priority)),
+        Err(e) => {
+            warn!(
+                "Text-to-speech unavailable ({}), set {} to a speech command",
+                e, TTS_ENV_VAR
+            );
+            narrator.tts_unavailable = true;
+        }
+    }
+}
+
+fn tts_command(text: &str) -> Command {
+    if let Ok(custom) = std::env::var(TTS_ENV_VAR) {
+        let mut parts = custom.split_whitespace();
+        if let Some(program) = parts.next() {
+            let mut command = Command::new(program);
+            command.args(parts).arg(text);
+            return command;
+        }
+    }
+
+    if cfg!(target_os = "windows") {
+        // The line travels in an environment variable so it needs no quoting
+        let mut command = Command::new("powershell");
+        command
+            .args([
+                "-NoProfile",
+                "-Command",
+                "Add-Type -AssemblyName System.Speech; \
+                 (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($env:CULIACAN_NARRATION_LINE)",
+            ])
+            .env("CULIACAN_NARRATION_LINE", text);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("say");
+        command.arg(text);
+        command
+    } else {
+        let voice = if current_language().starts_with("es") {
+            "es-419"
+        } else {
+            "en-us"
+        };
+        let mut command = Command::new("espeak-ng");
+        command.args(["-v", voice, text]);
+        command
+    }
+}
+
+// Drops emoji and other symbols the menus use as decoration
+pub fn spoken_text(text: &str) -> String {
+    let kept: String = text
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || ".,:;!?%'()-/".contains(*c))
+        .collect();
+    kept.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// ==================== ALERTS ====================
+
+// Objective completions, Ovidio falling to critical health and the mission result
+pub fn narration_alert_system(
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    units: Query<&Unit>,
+    mut narration: EventWriter<NarrationEvent>,
+    mut last_phase: Local<Option<GamePhase>>,
+    mut completed: Local<Vec<bool>>,
+    mut ovidio_critical: Local<bool>,
+) {
+    if last_phase.as_ref() != Some(&game_state.game_phase) {
+        match game_state.game_phase {
+            GamePhase::Victory => narration.send(NarrationEvent::keyed(
+                "narration.victory",
+                NarrationPriority::Critical,
+            )),
+            GamePhase::Defeat => narration.send(NarrationEvent::keyed(
+                "narration.defeat",
+                NarrationPriority::Critical,
+            )),
+            _ => {}
+        }
+        *last_phase = Some(game_state.game_phase.clone());
+    }
+
+    // Objectives are rebuilt at mission start; only announce flips within one list
+    let now: Vec<bool> = campaign
+        .current_objectives
+        .iter()
+        .map(|status| status.completed)
+        .collect();
+    if now.len() == completed.len() {
+        for (status, was_completed) in campaign.current_objectives.iter().zip(completed.iter()) {
+            if status.completed && !was_completed {
+                narration.send(NarrationEvent::new(
+                    tr_args(
+                        "narration.objective_complete",
+                        &[("objective", &status.objective.description())],
+                    ),
+                    NarrationPriority::Update,
+                ));
+            }
+        }
+    }
+    *completed = now;
+
+    let critical = units.iter().any(|unit| {
+        unit.unit_type == UnitType::Ovidio
+            && unit.health > 0.0
+            && unit.health / unit.max_health < OVIDIO_CRITICAL_HEALTH
+    });
+    if critical && !*ovidio_critical {
+        narration.send(NarrationEvent::keyed(
+            "narration.ovidio_critical",
+            NarrationPriority::Critical,
+        ));
+    }
+    *ovidio_critical = critical;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spoken_text_drops_decoration() {
+        assert_eq!(
+            spoken_text("🏛️ BATTLE OF CULIACÁN 🏛️\nEl Culiacanazo RTS"),
+            "BATTLE OF CULIACÁN El Culiacanazo RTS"
+        );
+        assert_eq!(spoken_text("1. Text size: 125%"), "1. Text size: 125%");
+    }
+
+    #[test]
+    fn test_focus_lines_replace_each_other_but_wait_behind_alerts() {
+        use NarrationPriority::*;
+        assert!(Focus.interrupts(Focus));
+        assert!(Critical.interrupts(Update));
+        assert!(!Update.interrupts(Update));
+        assert!(!Focus.interrupts(Critical));
+
+        let mut narrator = Narrator::default();
+        narrator.hold(NarrationEvent::new("2. Load Campaign", Focus));
+        narrator.hold(NarrationEvent::new("Objective complete", Update));
+        narrator.hold(NarrationEvent::new("3. Save Current Game", Focus));
+        assert_eq!(narrator.pending.len(), 2);
+        assert_eq!(narrator.next_pending().unwrap().priority, Update);
+        assert_eq!(
+            narrator.next_pending().unwrap().text,
+            "3. Save Current Game"
+        );
+    }
+}
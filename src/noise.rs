@@ -0,0 +1,269 @@
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{BalanceConfig, NoiseBalance};
+use crate::diplomacy::Diplomacy;
+use crate::systems::combat_system;
+use crate::utils::SimulationSet;
+use bevy::prelude::*;
+
+// ==================== NOISE PLUGIN ====================
+
+// Gunfire and explosions carry. Each shot and blast sends a NoiseEvent with
+// how far it can be heard, and army, police and rival units in earshot that
+// have no enemy in sight send a patrol - the few nearest of them - to where
+// it was last heard. They give up once there, once they run into the enemy
+// or when the trail goes cold. Heavy weapons and explosions carry further
+// than rifles; the cartel's suppressors (an upgrade, see upgrades.rs) cut how
+// far its fighters' shots are heard, so a squad that keeps quiet can get
+// close without drawing everyone in the area.
+
+pub struct NoisePlugin;
+
+impl Plugin for NoisePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NoiseEvent>()
+            .add_systems(OnEnter(AppState::InGame), noise_reset_system)
+            .add_systems(
+                FixedUpdate,
+                investigation_system
+                    .after(combat_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct NoiseEvent {
+    pub position: Vec2,
+    pub radius: f32,      // How far away it can be heard
+    pub faction: Faction, // Whoever made it
+}
+
+/// How far a shot from `weapon` is heard, before suppressors.
+pub fn weapon_noise(weapon: &WeaponType, balance: &NoiseBalance) -> f32 {
+    match weapon {
+        WeaponType::RPG | WeaponType::TankCannon => balance.explosion_radius,
+        WeaponType::HeavyMachineGun
+        | WeaponType::LMG
+        | WeaponType::VehicleWeapons
+        | WeaponType::HelicopterWeapons => balance.heavy_weapon_radius,
+        WeaponType::MedicBag => 0.0,
+        _ => balance.gunfire_radius,
+    }
+}
+
+fn send_noise(world: &mut World, event: NoiseEvent) {
+    if event.radius <= 0.0 {
+        return;
+    }
+    if let Some(mut events) = world.get_resource_mut::<Events<NoiseEvent>>() {
+        events.send(event);
+    }
+}
+
+// For helpers that only have Commands. Does nothing when no noise plugin is
+// running.
+pub fn queue_noise(commands: &mut Commands, position: Vec3, radius: f32, faction: Faction) {
+    let event = NoiseEvent {
+        position: position.truncate(),
+        radius,
+        faction,
+    };
+    commands.add(move |world: &mut World| send_noise(world, event));
+}
+
+/// A blast, heard as far as explosions carry.
+pub fn queue_explosion_noise(commands: &mut Commands, position: Vec3, faction: Faction) {
+    commands.add(move |world: &mut World| {
+        let radius = world
+            .get_resource::<BalanceConfig>()
+            .map_or(0.0, |balance| balance.noise.explosion_radius);
+        let event = NoiseEvent {
+            position: position.truncate(),
+            radius,
+            faction,
+        };
+        send_noise(world, event);
+    });
+}
+
+/// The listeners sent to look into a noise: the nearest `patrol_size` of
+/// those in earshot.
+pub fn patrol_for(
+    noise: &NoiseEvent,
+    listeners: &[(Entity, Vec2)],
+    patrol_size: usize,
+) -> Vec<Entity> {
+    let mut in_earshot: Vec<(Entity, f32)> = listeners
+        .iter()
+        .map(|(entity, position)| (*entity, position.distance(noise.position)))
+        .filter(|(_, distance)| *distance <= noise.radius)
+        .collect();
+    in_earshot.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    in_earshot
+        .into_iter()
+        .take(patrol_size)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn noise_reset_system(
+    mut commands: Commands,
+    investigator_query: Query<Entity, With<Investigating>>,
+) {
+    for entity in investigator_query.iter() {
+        commands.entity(entity).remove::<Investigating>();
+    }
+}
+
+// Units held to an area, guarding or hunting a principal, or running from gas
+// stay on their task
+type ListenerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        &'static Transform,
+        &'static mut Movement,
+        Option<&'static mut PathfindingAgent>,
+        Option<&'static mut Investigating>,
+    ),
+    (
+        Without<HoldArea>,
+        Without<Bodyguard>,
+        Without<HvtTasking>,
+        Without<Fleeing>,
+        Without<ProtectedPrincipal>,
+    ),
+>;
+
+pub fn investigation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    mut noise_events: EventReader<NoiseEvent>,
+    mut listener_query: ListenerQuery,
+    unit_query: Query<(&Unit, &Transform)>,
+) {
+    let balance = &balance.noise;
+    let dt = time.delta_seconds();
+    let sees_enemy = |unit: &Unit, position: Vec3| {
+        unit_query.iter().any(|(other, transform)| {
+            other.health > 0.0
+                && diplomacy.is_hostile(&unit.faction, &other.faction)
+                && position.distance(transform.translation) <= unit.vision
+        })
+    };
+
+    // Patrols already out walk on, until they get there, run into the enemy
+    // or give up
+    for (entity, unit, transform, mut movement, _, investigating) in listener_query.iter_mut() {
+        let Some(mut investigating) = investigating else {
+            continue;
+        };
+        investigating.remaining -= dt;
+        let arrived = transform
+            .translation
+            .truncate()
+            .distance(investigating.position)
+            <= balance.arrival_radius;
+        if unit.health <= 0.0
+            || arrived
+            || investigating.remaining <= 0.0
+            || sees_enemy(unit, transform.translation)
+        {
+            commands.entity(entity).remove::<Investigating>();
+            continue;
+        }
+        movement.target_position = Some(investigating.position.extend(transform.translation.z));
+    }
+
+    // The cartel's own fighters are the player's to move
+    let listeners: Vec<(Entity, &Unit, Vec2)> = listener_query
+        .iter()
+        .filter(|(_, unit, transform, _, _, _)| {
+            unit.health > 0.0
+                && unit.faction != Faction::Cartel
+                && unit.movement_speed > 0.0
+                && !sees_enemy(unit, transform.translation)
+        })
+        .map(|(entity, unit, transform, _, _, _)| (entity, unit, transform.translation.truncate()))
+        .collect();
+    let mut sent = Vec::new();
+    for noise in noise_events.read() {
+        let hostile: Vec<(Entity, Vec2)> = listeners
+            .iter()
+            .filter(|(_, unit, _)| diplomacy.is_hostile(&unit.faction, &noise.faction))
+            .map(|(entity, _, position)| (*entity, *position))
+            .collect();
+        for entity in patrol_for(noise, &hostile, balance.patrol_size) {
+            sent.push((entity, noise.position));
+        }
+    }
+
+    // The latest noise wins
+    for (entity, position) in sent {
+        let Ok((_, _, transform, mut movement, agent, investigating)) =
+            listener_query.get_mut(entity)
+        else {
+            continue;
+        };
+        movement.target_position = Some(position.extend(transform.translation.z));
+        if let Some(mut agent) = agent {
+            agent.path.clear();
+        }
+        match investigating {
+            Some(mut investigating) => {
+                investigating.position = position;
+                investigating.remaining = balance.investigate_seconds;
+            }
+            None => {
+                commands.entity(entity).insert(Investigating {
+                    position,
+                    remaining: balance.investigate_seconds,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_the_nearest_listeners_in_earshot_are_sent() {
+        let noise = NoiseEvent {
+            position: Vec2::ZERO,
+            radius: 300.0,
+            faction: Faction::Cartel,
+        };
+        let listeners = [
+            (Entity::from_raw(1), Vec2::new(250.0, 0.0)),
+            (Entity::from_raw(2), Vec2::new(0.0, 100.0)),
+            (Entity::from_raw(3), Vec2::new(400.0, 0.0)), // Out of earshot
+            (Entity::from_raw(4), Vec2::new(-200.0, 0.0)),
+        ];
+        assert_eq!(
+            patrol_for(&noise, &listeners, 2),
+            vec![Entity::from_raw(2), Entity::from_raw(4)]
+        );
+        assert_eq!(patrol_for(&noise, &listeners, 5).len(), 3);
+    }
+
+    #[test]
+    fn test_heavy_weapons_carry_further_than_rifles() {
+        let balance = NoiseBalance::default();
+        let rifle = weapon_noise(&WeaponType::AssaultRifle, &balance);
+        assert!(weapon_noise(&WeaponType::LMG, &balance) > rifle);
+        assert!(
+            weapon_noise(&WeaponType::RPG, &balance) > weapon_noise(&WeaponType::LMG, &balance)
+        );
+        assert_eq!(weapon_noise(&WeaponType::MedicBag, &balance), 0.0);
+    }
+}
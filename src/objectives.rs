@@ -0,0 +1,537 @@
+use crate::campaign::{place_name, Campaign, ObjectiveStatus, PoliticalPressure};
+use crate::components::{Faction, Unit, UnitType};
+use crate::localization::{tr, tr_args};
+use crate::map::{area_control, in_area, is_area_controlled, CityMap};
+use crate::resources::GameState;
+use bevy::prelude::*;
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+// ==================== OBJECTIVE TYPES ====================
+
+// A mission objective is a kind plus one target, written { ControlArea =
+// "Downtown" } or { SurviveTime = 300.0 } in mission data and saves. What a
+// kind means lives behind the ObjectiveType trait, looked up by name in a
+// registry: a new kind is one more impl and a register_objective_type call,
+// and campaign.rs never has to hear about it. Kinds nobody registered - a
+// typo in a mod pack, say - are logged and never complete.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MissionObjective {
+    pub kind: String,
+    pub target: ObjectiveTarget,
+}
+
+// Either a number (seconds, a count, a threshold) or a name (a unit, an area)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ObjectiveTarget {
+    Amount(f32),
+    Name(String),
+}
+
+impl ObjectiveTarget {
+    pub fn amount(&self) -> f32 {
+        match self {
+            ObjectiveTarget::Amount(amount) => *amount,
+            ObjectiveTarget::Name(name) => name.parse().unwrap_or(0.0),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for ObjectiveTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjectiveTarget::Amount(amount) if amount.fract() == 0.0 => write!(f, "{:.0}", amount),
+            ObjectiveTarget::Amount(amount) => write!(f, "{}", amount),
+            ObjectiveTarget::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl From<f32> for ObjectiveTarget {
+    fn from(amount: f32) -> Self {
+        ObjectiveTarget::Amount(amount)
+    }
+}
+
+impl From<&str> for ObjectiveTarget {
+    fn from(name: &str) -> Self {
+        ObjectiveTarget::Name(name.to_string())
+    }
+}
+
+impl MissionObjective {
+    pub fn new(kind: &str, target: impl Into<ObjectiveTarget>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            target: target.into(),
+        }
+    }
+
+    // Briefing line, e.g. "Protect Ovidio"
+    pub fn description(&self) -> String {
+        match objective_type(&self.kind) {
+            Some(objective_type) => objective_type.describe(&self.target),
+            None => format!("{} {}", self.kind, self.target),
+        }
+    }
+
+    // Objective panel line, with the progress so far
+    pub fn progress_text(&self, status: &ObjectiveStatus) -> String {
+        match objective_type(&self.kind) {
+            Some(objective_type) => objective_type.describe_progress(&self.target, status),
+            None => self.description(),
+        }
+    }
+}
+
+// Written as a one-entry map, { Kind = target }
+impl Serialize for MissionObjective {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.kind, &self.target)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MissionObjective {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ObjectiveVisitor;
+
+        impl<'de> Visitor<'de> for ObjectiveVisitor {
+            type Value = MissionObjective;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an objective like {{ SurviveTime = 300.0 }}")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let (kind, target) = map
+                    .next_entry::<String, ObjectiveTarget>()?
+                    .ok_or_else(|| de::Error::custom("objective has no kind"))?;
+                if map.next_key::<String>()?.is_some() {
+                    return Err(de::Error::custom("objective has more than one kind"));
+                }
+                Ok(MissionObjective { kind, target })
+            }
+        }
+
+        deserializer.deserialize_map(ObjectiveVisitor)
+    }
+}
+
+// What an objective can look at while it's being evaluated
+pub struct ObjectiveContext<'a> {
+    pub game_state: &'a GameState,
+    pub city_map: &'a CityMap,
+    pub pressure: &'a PoliticalPressure,
+    pub units: Vec<(&'a Unit, Vec2)>, // Everyone on the map, dead or alive
+    pub control_positions: Vec<(Faction, Vec2)>, // Living fighters plus road checkpoints
+}
+
+impl ObjectiveContext<'_> {
+    pub fn living(&self, faction: &Faction) -> usize {
+        self.units
+            .iter()
+            .filter(|(unit, _)| unit.faction == *faction && unit.health > 0.0)
+            .count()
+    }
+
+    // Bodies still on the map plus the ones already cleared away
+    pub fn dead(&self, faction: &Faction) -> u32 {
+        self.units
+            .iter()
+            .filter(|(unit, _)| unit.faction == *faction && unit.health <= 0.0)
+            .count() as u32
+            + self.game_state.cleared_dead(faction)
+    }
+
+    pub fn ovidio(&self) -> Option<Vec2> {
+        self.units
+            .iter()
+            .find(|(unit, _)| unit.unit_type == UnitType::Ovidio && unit.health > 0.0)
+            .map(|(_, position)| *position)
+    }
+}
+
+pub trait ObjectiveType: Send + Sync {
+    /// Progress toward the objective, from 0.0 to 1.0.
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32;
+
+    /// Whether the objective is met; by default once progress is full.
+    fn evaluate(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> bool {
+        self.progress(target, context) >= 1.0
+    }
+
+    /// Briefing line, e.g. "Protect Ovidio".
+    fn describe(&self, target: &ObjectiveTarget) -> String;
+
+    /// Whether the objective can no longer be met, losing the mission; by
+    /// default never.
+    fn failed(&self, _target: &ObjectiveTarget, _context: &ObjectiveContext) -> bool {
+        false
+    }
+
+    /// Objective panel line; by default the briefing line and a percentage.
+    fn describe_progress(&self, target: &ObjectiveTarget, status: &ObjectiveStatus) -> String {
+        format!(
+            "{} ({:.1}%)",
+            self.describe(target),
+            status.progress * 100.0
+        )
+    }
+
+    /// Runs once, the first time the objective is met.
+    fn on_complete(&self, _target: &ObjectiveTarget, _campaign: &mut Campaign) {}
+}
+
+// ==================== REGISTRY ====================
+
+type ObjectiveRegistry = HashMap<String, Arc<dyn ObjectiveType>>;
+
+fn registry() -> &'static RwLock<ObjectiveRegistry> {
+    static REGISTRY: OnceLock<RwLock<ObjectiveRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let builtin: [(&str, Arc<dyn ObjectiveType>); 7] = [
+            ("SurviveTime", Arc::new(SurviveTime)),
+            ("DefendTarget", Arc::new(DefendTarget)),
+            ("EliminateEnemies", Arc::new(EliminateEnemies)),
+            ("ControlArea", Arc::new(ControlArea)),
+            ("EscortConvoy", Arc::new(EscortConvoy)),
+            ("DestroyStructure", Arc::new(DestroyStructure)),
+            ("KeepPressureBelow", Arc::new(KeepPressureBelow)),
+        ];
+        RwLock::new(
+            builtin
+                .into_iter()
+                .map(|(kind, objective_type)| (kind.to_string(), objective_type))
+                .collect(),
+        )
+    })
+}
+
+// Adds a kind, or replaces the built-in one of the same name
+pub fn register_objective_type(kind: &str, objective_type: impl ObjectiveType + 'static) {
+    if let Ok(mut registry) = registry().write() {
+        registry.insert(kind.to_string(), Arc::new(objective_type));
+    }
+}
+
+pub fn objective_type(kind: &str) -> Option<Arc<dyn ObjectiveType>> {
+    registry().read().ok()?.get(kind).cloned()
+}
+
+// ==================== BUILT-IN OBJECTIVES ====================
+
+// { SurviveTime = seconds }: hold out until the mission clock gets there
+pub struct SurviveTime;
+
+impl ObjectiveType for SurviveTime {
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32 {
+        (context.game_state.mission_timer / target.amount()).min(1.0)
+    }
+
+    fn describe(&self, target: &ObjectiveTarget) -> String {
+        tr_args("objective.survive", &[("seconds", &target)])
+    }
+
+    fn describe_progress(&self, target: &ObjectiveTarget, status: &ObjectiveStatus) -> String {
+        let percent = format!("{:.1}", status.progress * 100.0);
+        tr_args(
+            "objective.survive_progress",
+            &[("seconds", &target), ("percent", &percent)],
+        )
+    }
+}
+
+// { DefendTarget = name }: for now only Ovidio is tracked
+pub struct DefendTarget;
+
+impl ObjectiveType for DefendTarget {
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32 {
+        if target.name() == "Ovidio" && context.ovidio().is_some() {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn describe(&self, target: &ObjectiveTarget) -> String {
+        tr_args(
+            "objective.defend",
+            &[("target", &place_name(&target.name()))],
+        )
+    }
+
+    fn describe_progress(&self, target: &ObjectiveTarget, status: &ObjectiveStatus) -> String {
+        let safety = if status.completed {
+            tr("objective.target_safe")
+        } else {
+            tr("objective.target_at_risk")
+        };
+        tr_args(
+            "objective.defend_progress",
+            &[("target", &place_name(&target.name())), ("status", &safety)],
+        )
+    }
+}
+
+// { EliminateEnemies = count }: soldiers killed, bodies cleared away included
+pub struct EliminateEnemies;
+
+impl ObjectiveType for EliminateEnemies {
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32 {
+        (context.dead(&Faction::Military) as f32 / target.amount()).min(1.0)
+    }
+
+    fn describe(&self, target: &ObjectiveTarget) -> String {
+        tr_args("objective.eliminate", &[("count", &target)])
+    }
+
+    fn describe_progress(&self, target: &ObjectiveTarget, status: &ObjectiveStatus) -> String {
+        let percent = format!("{:.1}", status.progress * 100.0);
+        tr_args(
+            "objective.eliminate_progress",
+            &[("count", &target), ("percent", &percent)],
+        )
+    }
+}
+
+// { ControlArea = area }: the cartel's share of the fighters inside the
+// neighborhood on the city map
+pub struct ControlArea;
+
+impl ObjectiveType for ControlArea {
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32 {
+        area_control(context.city_map, &target.name(), &context.control_positions)
+    }
+
+    fn evaluate(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> bool {
+        is_area_controlled(self.progress(target, context))
+    }
+
+    fn describe(&self, target: &ObjectiveTarget) -> String {
+        tr_args(
+            "objective.control",
+            &[("area", &place_name(&target.name()))],
+        )
+    }
+
+    fn describe_progress(&self, target: &ObjectiveTarget, status: &ObjectiveStatus) -> String {
+        let percent = format!("{:.1}", status.progress * 100.0);
+        tr_args(
+            "objective.control_progress",
+            &[("area", &place_name(&target.name())), ("percent", &percent)],
+        )
+    }
+}
+
+// { EscortConvoy = area }: get Ovidio's convoy into the area alive. With a
+// scripted convoy on the road (see convoy.rs), how far it has come and
+// whether enough of it is left; otherwise Ovidio himself reaching the area.
+pub struct EscortConvoy;
+
+impl ObjectiveType for EscortConvoy {
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32 {
+        if let Some(convoy) = &context.game_state.convoy {
+            return convoy.progress;
+        }
+        match context.ovidio() {
+            Some(position) if in_area(context.city_map, &target.name(), position) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn evaluate(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> bool {
+        match &context.game_state.convoy {
+            Some(convoy) => convoy.arrived && !convoy.lost,
+            None => self.progress(target, context) >= 1.0,
+        }
+    }
+
+    fn failed(&self, _target: &ObjectiveTarget, context: &ObjectiveContext) -> bool {
+        context
+            .game_state
+            .convoy
+            .as_ref()
+            .is_some_and(|convoy| convoy.lost)
+    }
+
+    fn describe(&self, target: &ObjectiveTarget) -> String {
+        tr_args("objective.escort", &[("area", &place_name(&target.name()))])
+    }
+
+    fn describe_progress(&self, target: &ObjectiveTarget, status: &ObjectiveStatus) -> String {
+        let percent = format!("{:.1}", status.progress * 100.0);
+        tr_args(
+            "objective.escort_progress",
+            &[("area", &place_name(&target.name())), ("percent", &percent)],
+        )
+    }
+}
+
+// { DestroyStructure = count }: take down that many army road checkpoints
+pub struct DestroyStructure;
+
+impl ObjectiveType for DestroyStructure {
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32 {
+        (context.game_state.military_checkpoints_taken as f32 / target.amount().max(1.0)).min(1.0)
+    }
+
+    fn describe(&self, target: &ObjectiveTarget) -> String {
+        tr_args("objective.destroy", &[("count", &target)])
+    }
+
+    // Footage of a burning army checkpoint gets around
+    fn on_complete(&self, _target: &ObjectiveTarget, campaign: &mut Campaign) {
+        campaign.political_pressure.increase_media_attention(0.1);
+    }
+}
+
+// { KeepPressureBelow = share }: keep total political pressure under it
+pub struct KeepPressureBelow;
+
+impl ObjectiveType for KeepPressureBelow {
+    fn progress(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> f32 {
+        let total = context.pressure.total_pressure;
+        if total < target.amount() {
+            1.0
+        } else {
+            (target.amount() / total).min(1.0)
+        }
+    }
+
+    fn evaluate(&self, target: &ObjectiveTarget, context: &ObjectiveContext) -> bool {
+        context.pressure.total_pressure < target.amount()
+    }
+
+    fn describe(&self, target: &ObjectiveTarget) -> String {
+        let percent = format!("{:.0}", target.amount() * 100.0);
+        tr_args("objective.pressure_below", &[("percent", &percent)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ConvoyStatus;
+
+    #[test]
+    fn test_objectives_read_and_write_as_one_entry_maps() {
+        #[derive(Deserialize)]
+        struct Mission {
+            objectives: Vec<MissionObjective>,
+        }
+        let mission: Mission = toml::from_str(
+            r#"objectives = [{ DefendTarget = "Ovidio" }, { SurviveTime = 360.0 }, { EliminateEnemies = 20 }]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            mission.objectives,
+            vec![
+                MissionObjective::new("DefendTarget", "Ovidio"),
+                MissionObjective::new("SurviveTime", 360.0),
+                MissionObjective::new("EliminateEnemies", 20.0),
+            ]
+        );
+
+        // Saves from before the registry still load
+        let json = serde_json::to_string(&mission.objectives[1]).unwrap();
+        assert_eq!(json, r#"{"SurviveTime":360.0}"#);
+        let loaded: MissionObjective = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, mission.objectives[1]);
+    }
+
+    #[test]
+    fn test_registered_kinds_are_evaluated_by_name() {
+        struct AlwaysDone;
+        impl ObjectiveType for AlwaysDone {
+            fn progress(&self, _: &ObjectiveTarget, _: &ObjectiveContext) -> f32 {
+                1.0
+            }
+            fn describe(&self, target: &ObjectiveTarget) -> String {
+                format!("Done with {}", target)
+            }
+        }
+
+        assert!(objective_type("NoSuchKind").is_none());
+        register_objective_type("AlwaysDone", AlwaysDone);
+        let objective = MissionObjective::new("AlwaysDone", "it");
+        assert_eq!(objective.description(), "Done with it");
+
+        let game_state = GameState::default();
+        let city_map = CityMap::default();
+        let pressure = PoliticalPressure::default();
+        let context = ObjectiveContext {
+            game_state: &game_state,
+            city_map: &city_map,
+            pressure: &pressure,
+            units: Vec::new(),
+            control_positions: Vec::new(),
+        };
+        let kind = objective_type("AlwaysDone").unwrap();
+        assert!(kind.evaluate(&objective.target, &context));
+
+        let pressure_cap = MissionObjective::new("KeepPressureBelow", 0.5);
+        let keep_below = objective_type("KeepPressureBelow").unwrap();
+        assert_eq!(
+            keep_below.evaluate(&pressure_cap.target, &context),
+            pressure.total_pressure < 0.5
+        );
+    }
+
+    #[test]
+    fn test_escort_follows_the_convoy_on_the_road() {
+        let escort = objective_type("EscortConvoy").unwrap();
+        let target = ObjectiveTarget::from("Withdrawal Routes");
+        let city_map = CityMap::default();
+        let pressure = PoliticalPressure::default();
+        let evaluate = |convoy: ConvoyStatus| {
+            let game_state = GameState {
+                convoy: Some(convoy),
+                ..default()
+            };
+            let context = ObjectiveContext {
+                game_state: &game_state,
+                city_map: &city_map,
+                pressure: &pressure,
+                units: Vec::new(),
+                control_positions: Vec::new(),
+            };
+            (
+                escort.progress(&target, &context),
+                escort.evaluate(&target, &context),
+                escort.failed(&target, &context),
+            )
+        };
+
+        let halfway = ConvoyStatus {
+            progress: 0.5,
+            health: 1.0,
+            arrived: false,
+            lost: false,
+        };
+        assert_eq!(evaluate(halfway.clone()), (0.5, false, false));
+        let through = ConvoyStatus {
+            progress: 1.0,
+            arrived: true,
+            ..halfway.clone()
+        };
+        assert_eq!(evaluate(through), (1.0, true, false));
+        let shot_up = ConvoyStatus {
+            health: 0.2,
+            lost: true,
+            ..halfway
+        };
+        assert_eq!(evaluate(shot_up), (0.5, false, true));
+    }
+}
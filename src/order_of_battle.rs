@@ -0,0 +1,396 @@
+use crate::app_state::{AppState, GameSet};
+use crate::campaign::place_name;
+use crate::components::*;
+use crate::config::{BalanceConfig, OrderOfBattleBalance};
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::resources::IntelSystem;
+use crate::utils::{dashboard_line, play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== ORDER OF BATTLE PLUGIN ====================
+
+// The intel panel reads as an estimate of the army's order of battle rather
+// than a list of raw reports. Troop movements and air support heard on the
+// radio, informants' sightings and what the lookouts count are folded
+// together by unit type and neighborhood: each row gives the estimated
+// count with a band around it, how sure the network is and how long since
+// anyone reported on it. A second report on the same force makes the
+// estimate surer; left alone it fades and is forgotten. Garbled intercepts
+// and planted tips feed it too, only less surely. Once the army is strong
+// enough somewhere, the advisor says where on the radio and on the panel.
+
+pub struct OrderOfBattlePlugin;
+
+impl Plugin for OrderOfBattlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OrderOfBattle>()
+            .add_systems(OnEnter(AppState::InGame), order_of_battle_reset_system)
+            .add_systems(
+                FixedUpdate,
+                order_of_battle_fusion_system
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct OrderOfBattle {
+    pub estimates: Vec<ForceEstimate>,
+    pub fused_until: f32, // Reports from before this are already in the estimates
+    pub advice: Option<String>, // Where the advisor last said the army is strongest
+}
+
+/// What the network thinks the army has of one unit type in one area.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForceEstimate {
+    pub area: Option<String>, // Neighborhood name; None out past the districts
+    pub unit_type: UnitType,
+    pub count: f32,
+    pub confidence: f32,
+    pub seen: f32, // When the last report on it came in
+}
+
+impl ForceEstimate {
+    /// Folds another report into the estimate, the surer report pulling
+    /// the count harder.
+    pub fn fuse(&mut self, count: f32, confidence: f32, time: f32, corroboration: f32) {
+        let weight = self.confidence + confidence;
+        if weight > 0.0 {
+            self.count = (self.count * self.confidence + count * confidence) / weight;
+        }
+        self.confidence = (self.confidence.max(confidence)
+            + corroboration * self.confidence.min(confidence))
+        .min(1.0);
+        self.seen = self.seen.max(time);
+    }
+
+    /// The range the real count likely lies in, wider the less sure.
+    pub fn band(&self) -> (u32, u32) {
+        let spread = self.count * (1.0 - self.confidence);
+        (
+            (self.count - spread).max(0.0).floor() as u32,
+            (self.count + spread).ceil() as u32,
+        )
+    }
+
+    /// Soldiers' worth, as the advisor weighs it.
+    pub fn strength(&self, balance: &OrderOfBattleBalance) -> f32 {
+        let weight = match self.unit_type {
+            UnitType::Tank | UnitType::Helicopter => balance.heavy_weight,
+            _ => 1.0,
+        };
+        self.count * self.confidence * weight
+    }
+}
+
+/// One report's worth of the army: what, how many, where and how surely.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sighting {
+    pub position: Vec3,
+    pub unit_type: UnitType,
+    pub count: f32,
+    pub confidence: f32,
+    pub time: f32,
+}
+
+fn army_unit(unit_type: &UnitType) -> bool {
+    matches!(
+        unit_type,
+        UnitType::Soldier
+            | UnitType::SpecialForces
+            | UnitType::Vehicle
+            | UnitType::Tank
+            | UnitType::Helicopter
+            | UnitType::Engineer
+    )
+}
+
+/// The army sightings in the reports that came in after `since`.
+pub fn sightings(intel: &IntelSystem, since: f32) -> Vec<Sighting> {
+    let network = &intel.global_intel_network;
+    let mut sightings = Vec::new();
+
+    for intercept in &network.active_intercepts {
+        if intercept.intercept_time <= since {
+            continue;
+        }
+        let (position, unit_type, count) = match &intercept.message_type {
+            RadioMessageType::TroopMovement(position, count) => {
+                (*position, UnitType::Soldier, *count as f32)
+            }
+            RadioMessageType::AirSupport(position) => (*position, UnitType::Helicopter, 1.0),
+            _ => continue,
+        };
+        sightings.push(Sighting {
+            position,
+            unit_type,
+            count,
+            confidence: intercept.reliability,
+            time: intercept.intercept_time,
+        });
+    }
+
+    for tip in &network.informant_reports {
+        if tip.time_received <= since {
+            continue;
+        }
+        if let TipType::EnemyPosition(unit_type, count) = &tip.tip_type {
+            if army_unit(unit_type) {
+                sightings.push(Sighting {
+                    position: tip.location,
+                    unit_type: unit_type.clone(),
+                    count: *count as f32,
+                    confidence: tip.confidence,
+                    time: tip.time_received,
+                });
+            }
+        }
+    }
+
+    // A lookout's count is a count: one sighting per type seen in the sweep
+    for report in &network.reconnaissance_data {
+        if report.scan_time <= since {
+            continue;
+        }
+        let mut counted: Vec<(UnitType, Vec3, f32, f32)> = Vec::new();
+        for contact in &report.enemies_spotted {
+            if !army_unit(&contact.enemy_type) {
+                continue;
+            }
+            match counted
+                .iter_mut()
+                .find(|(unit_type, ..)| *unit_type == contact.enemy_type)
+            {
+                Some((_, position, count, confidence)) => {
+                    *position += contact.position;
+                    *count += 1.0;
+                    *confidence += contact.confidence;
+                }
+                None => counted.push((
+                    contact.enemy_type.clone(),
+                    contact.position,
+                    1.0,
+                    contact.confidence,
+                )),
+            }
+        }
+        for (unit_type, position, count, confidence) in counted {
+            sightings.push(Sighting {
+                position: position / count,
+                unit_type,
+                count,
+                confidence: confidence / count,
+                time: report.scan_time,
+            });
+        }
+    }
+
+    sightings
+}
+
+/// The area where the army is strongest, if it's strong enough there to
+/// warn about, and how many soldiers' worth it has.
+pub fn advisor_hint(
+    estimates: &[ForceEstimate],
+    balance: &OrderOfBattleBalance,
+) -> Option<(Option<String>, f32)> {
+    let mut areas: Vec<(Option<String>, f32)> = Vec::new();
+    for estimate in estimates {
+        match areas.iter_mut().find(|(area, _)| *area == estimate.area) {
+            Some((_, strength)) => *strength += estimate.strength(balance),
+            None => areas.push((estimate.area.clone(), estimate.strength(balance))),
+        }
+    }
+    areas
+        .into_iter()
+        .filter(|(_, strength)| *strength >= balance.hint_strength)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// The name to show for an estimate's area.
+pub fn area_label(area: &Option<String>) -> String {
+    area.as_deref()
+        .map(place_name)
+        .unwrap_or_else(|| tr("hud.oob_outskirts"))
+}
+
+/// The intel panel's order of battle section, strongest forces first;
+/// nothing until there's an estimate.
+pub fn order_of_battle_lines(
+    order_of_battle: &OrderOfBattle,
+    balance: &OrderOfBattleBalance,
+    now: f32,
+) -> Vec<TextSection> {
+    if order_of_battle.estimates.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![dashboard_line(tr("hud.oob_title"), 12.0, Color::WHITE)];
+
+    let mut estimates: Vec<&ForceEstimate> = order_of_battle.estimates.iter().collect();
+    estimates.sort_by(|a, b| b.strength(balance).total_cmp(&a.strength(balance)));
+    for estimate in estimates.into_iter().take(balance.rows) {
+        let (low, high) = estimate.band();
+        let color = if estimate.confidence > 0.8 {
+            Color::GREEN
+        } else if estimate.confidence > 0.6 {
+            Color::YELLOW
+        } else {
+            Color::ORANGE
+        };
+        lines.push(dashboard_line(
+            tr_args(
+                "hud.oob_row",
+                &[
+                    ("unit", &format!("{:?}", estimate.unit_type)),
+                    ("place", &area_label(&estimate.area)),
+                    ("low", &low),
+                    ("high", &high),
+                    ("percent", &((estimate.confidence * 100.0).round() as u32)),
+                    ("age", &((now - estimate.seen).max(0.0) as u32)),
+                ],
+            ),
+            10.0,
+            color,
+        ));
+    }
+
+    if let Some((area, strength)) = advisor_hint(&order_of_battle.estimates, balance) {
+        lines.push(dashboard_line(
+            tr_args(
+                "hud.oob_advisor",
+                &[
+                    ("place", &area_label(&area)),
+                    ("strength", &(strength.round() as u32)),
+                ],
+            ),
+            10.0,
+            Color::GOLD,
+        ));
+    }
+    lines
+}
+
+// ==================== SYSTEMS ====================
+
+// Reports from an earlier mission don't count toward this one's estimate
+pub fn order_of_battle_reset_system(mut order_of_battle: ResMut<OrderOfBattle>, time: Res<Time>) {
+    *order_of_battle = OrderOfBattle {
+        fused_until: time.elapsed_seconds(),
+        ..default()
+    };
+}
+
+pub fn order_of_battle_fusion_system(
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    intel: Res<IntelSystem>,
+    mut order_of_battle: ResMut<OrderOfBattle>,
+    mut timer: Local<f32>,
+) {
+    let balance = &balance.order_of_battle;
+    *timer += time.delta_seconds();
+    if *timer < balance.fusion_interval {
+        return;
+    }
+    let elapsed = std::mem::take(&mut *timer);
+    let order_of_battle = &mut *order_of_battle;
+
+    // Unconfirmed, every estimate fades
+    for estimate in &mut order_of_battle.estimates {
+        estimate.confidence -= balance.decay * elapsed;
+    }
+    order_of_battle
+        .estimates
+        .retain(|estimate| estimate.confidence >= balance.forget_below);
+
+    for sighting in sightings(&intel, order_of_battle.fused_until) {
+        order_of_battle.fused_until = order_of_battle.fused_until.max(sighting.time);
+        let area = city_map
+            .neighborhood_at(sighting.position.truncate())
+            .map(|neighborhood| neighborhood.name.clone());
+        match order_of_battle
+            .estimates
+            .iter_mut()
+            .find(|estimate| estimate.area == area && estimate.unit_type == sighting.unit_type)
+        {
+            Some(estimate) => estimate.fuse(
+                sighting.count,
+                sighting.confidence,
+                sighting.time,
+                balance.corroboration,
+            ),
+            None => order_of_battle.estimates.push(ForceEstimate {
+                area,
+                unit_type: sighting.unit_type,
+                count: sighting.count,
+                confidence: sighting.confidence,
+                seen: sighting.time,
+            }),
+        }
+    }
+
+    // The advisor speaks up when the army's main weight moves
+    let hint = advisor_hint(&order_of_battle.estimates, balance);
+    let advice = hint.as_ref().map(|(area, _)| area_label(area));
+    if advice != order_of_battle.advice {
+        if let Some((area, strength)) = &hint {
+            play_tactical_sound(
+                "radio",
+                &tr_args(
+                    "radio.oob_advisor",
+                    &[
+                        ("place", &area_label(area)),
+                        ("strength", &(strength.round() as u32)),
+                    ],
+                ),
+            );
+        }
+        order_of_battle.advice = advice;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(area: &str, unit_type: UnitType, count: f32, confidence: f32) -> ForceEstimate {
+        ForceEstimate {
+            area: Some(area.to_string()),
+            unit_type,
+            count,
+            confidence,
+            seen: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_a_second_report_is_folded_in_and_firms_up_the_estimate() {
+        let mut soldiers = estimate("Centro", UnitType::Soldier, 4.0, 0.5);
+        assert_eq!(soldiers.band(), (2, 6));
+
+        soldiers.fuse(8.0, 0.5, 10.0, 0.3);
+        assert!((soldiers.count - 6.0).abs() < 1e-4);
+        assert!((soldiers.confidence - 0.65).abs() < 1e-4);
+        assert_eq!(soldiers.seen, 10.0);
+        assert_eq!(soldiers.band(), (3, 9));
+    }
+
+    #[test]
+    fn test_the_advisor_names_the_area_the_army_is_strongest_in() {
+        let balance = OrderOfBattleBalance::default();
+        let estimates = vec![
+            estimate("Centro", UnitType::Soldier, 5.0, 0.8),
+            estimate("Tierra Blanca", UnitType::Soldier, 2.0, 0.9),
+            estimate("Tierra Blanca", UnitType::Tank, 3.0, 0.9),
+        ];
+        let (area, strength) = advisor_hint(&estimates, &balance).unwrap();
+        assert_eq!(area.as_deref(), Some("Tierra Blanca"));
+        assert!((strength - 9.9).abs() < 1e-4);
+
+        // Nowhere strong enough to be worth a word
+        assert!(advisor_hint(&estimates[..2], &balance).is_none());
+    }
+}
@@ -0,0 +1,521 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::{BalanceConfig, OverwatchBalance};
+use crate::damage::{damage_system, DamageEvent};
+use crate::diplomacy::Diplomacy;
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::noise::{queue_noise, weapon_noise};
+use crate::systems::combat_system;
+use crate::utils::{
+    calculate_damage_modifier, iso_to_world, play_tactical_sound, spawn_muzzle_flash, SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== SNIPER OVERWATCH PLUGIN ====================
+
+// A sniper on overwatch watches a cone out to its range from where it
+// stands, outlined on the ground. Once it has settled the cone is locked,
+// and each enemy that walks into it takes a guaranteed shot as the rifle is
+// ready: cover doesn't help and it hits harder than a normal shot. Leaving
+// the post gives overwatch up, and the sniper can't fire for a while after.
+// Every sniper shot flashes: enemies who can see the shooter learn where it
+// is, and their own snipers turn on it. The cartel deploys its snipers at the
+// cursor (Period); army, police and rival snipers go on overwatch on their
+// own, facing the nearest enemy, whenever they stand still with one in sight.
+
+const SHOT_DAMAGE: f32 = 25.0; // What a connecting shot starts from, as in combat
+const CONE_MARKER_SIZE: Vec2 = Vec2::splat(4.0);
+const CONE_MARKER_SPACING: f32 = 30.0;
+const CONE_ARC_MARKERS: usize = 8;
+const CONE_Z: f32 = 0.4;
+const SETTLING_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.25);
+const LOCKED_COLOR: Color = Color::rgba(1.0, 0.2, 0.1, 0.5);
+const CONTACT_MATCH_RADIUS: f32 = 20.0; // As in communication_system
+
+pub struct SniperOverwatchPlugin;
+
+impl Plugin for SniperOverwatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                (
+                    overwatch_relocation_system,
+                    ai_overwatch_system,
+                    overwatch_fire_system,
+                )
+                    .chain()
+                    .before(combat_system),
+                muzzle_flash_system
+                    .after(combat_system)
+                    .before(damage_system),
+            )
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        )
+        .add_systems(Update, overwatch_cone_system.in_set(GameSet::Session));
+    }
+}
+
+impl Overwatch {
+    /// Overwatch from `post` facing `toward`; None if the two are the same
+    /// spot and there is no way to face.
+    pub fn new(post: Vec2, toward: Vec2, range: f32, balance: &OverwatchBalance) -> Option<Self> {
+        let facing = (toward - post).try_normalize()?;
+        Some(Self {
+            post,
+            facing,
+            half_angle: balance.half_angle.to_radians(),
+            range,
+            settling: balance.settle_seconds,
+            covered: Vec::new(),
+        })
+    }
+
+    pub fn locked(&self) -> bool {
+        self.settling <= 0.0
+    }
+
+    pub fn covers(&self, position: Vec2) -> bool {
+        let offset = position - self.post;
+        offset.length() <= self.range
+            && offset.try_normalize().is_some_and(|direction| {
+                direction.angle_between(self.facing).abs() <= self.half_angle
+            })
+    }
+
+    /// Where the markers outlining the cone go: down both edges and along
+    /// the far arc.
+    pub fn outline(&self) -> Vec<Vec2> {
+        let edges = (self.range / CONE_MARKER_SPACING) as usize;
+        let mut points = Vec::new();
+        for side in [-1.0, 1.0] {
+            let direction = Vec2::from_angle(side * self.half_angle).rotate(self.facing);
+            points.extend(
+                (1..=edges).map(|step| self.post + direction * step as f32 * CONE_MARKER_SPACING),
+            );
+        }
+        points.extend((1..CONE_ARC_MARKERS).map(|step| {
+            let angle =
+                -self.half_angle + 2.0 * self.half_angle * step as f32 / CONE_ARC_MARKERS as f32;
+            self.post + Vec2::from_angle(angle).rotate(self.facing) * self.range
+        }));
+        points
+    }
+}
+
+/// The guaranteed shot at a unit walking into a locked cone: a normal hit
+/// with the same rifle, multiplied up, that cover doesn't soften.
+pub fn overwatch_shot_damage(weapon: &WeaponType, balance: &OverwatchBalance) -> f32 {
+    SHOT_DAMAGE * calculate_damage_modifier(weapon) * balance.first_shot_multiplier
+}
+
+// ==================== SYSTEMS ====================
+
+// Period: the selected cartel snipers go on overwatch facing the cursor,
+// stopping where they are
+pub fn overwatch_order_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    mut sniper_query: Query<(Entity, &Unit, &Transform, Option<&mut Movement>), With<Selected>>,
+) {
+    if !input.just_pressed(KeyCode::Period) {
+        return;
+    }
+    let Some(cursor) = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+    else {
+        return;
+    };
+
+    let mut deployed = 0;
+    for (entity, unit, transform, movement) in sniper_query.iter_mut() {
+        if unit.faction != Faction::Cartel
+            || unit.unit_type != UnitType::Sniper
+            || unit.health <= 0.0
+        {
+            continue;
+        }
+        let post = transform.translation.truncate();
+        let Some(overwatch) = Overwatch::new(post, cursor, unit.range, &balance.overwatch) else {
+            continue;
+        };
+        commands.entity(entity).insert(overwatch);
+        if let Some(mut movement) = movement {
+            movement.target_position = None;
+        }
+        deployed += 1;
+    }
+
+    if deployed == 0 {
+        play_tactical_sound("radio", &tr("radio.overwatch_no_sniper"));
+    } else {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.overwatch_set", &[("count", &deployed)]),
+        );
+    }
+}
+
+// Cones settle into place; a sniper that has moved off its post gives
+// overwatch up and holds its fire until it has set up again
+pub fn overwatch_relocation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    mut overwatch_query: Query<(Entity, &Transform, &mut Overwatch)>,
+    mut relocating_query: Query<(Entity, &mut Unit, &mut Relocating)>,
+) {
+    let balance = &balance.overwatch;
+    let dt = time.delta_seconds();
+
+    for (entity, transform, mut overwatch) in overwatch_query.iter_mut() {
+        if transform.translation.truncate().distance(overwatch.post) > balance.relocation_distance {
+            commands
+                .entity(entity)
+                .remove::<Overwatch>()
+                .insert(Relocating {
+                    remaining: balance.relocation_seconds,
+                });
+        } else if !overwatch.locked() {
+            overwatch.settling -= dt;
+        }
+    }
+
+    for (entity, mut unit, mut relocating) in relocating_query.iter_mut() {
+        unit.attack_cooldown.reset();
+        relocating.remaining -= dt;
+        if relocating.remaining <= 0.0 {
+            commands.entity(entity).remove::<Relocating>();
+        }
+    }
+}
+
+// Army, police and rival snipers standing still with an enemy in sight
+// cover the nearest one
+pub fn ai_overwatch_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    sniper_query: Query<
+        (Entity, &Unit, &Transform, Option<&Movement>),
+        (Without<Overwatch>, Without<Relocating>),
+    >,
+    unit_query: Query<(&Unit, &Transform)>,
+) {
+    let balance = &balance.overwatch;
+    for (entity, unit, transform, movement) in sniper_query.iter() {
+        if unit.faction == Faction::Cartel
+            || unit.unit_type != UnitType::Sniper
+            || unit.health <= 0.0
+        {
+            continue;
+        }
+        let post = transform.translation.truncate();
+        let moving = movement
+            .and_then(|movement| movement.target_position)
+            .is_some_and(|target| target.truncate().distance(post) > balance.relocation_distance);
+        if moving {
+            continue;
+        }
+
+        let nearest = unit_query
+            .iter()
+            .filter(|(other, _)| {
+                other.health > 0.0 && diplomacy.is_hostile(&unit.faction, &other.faction)
+            })
+            .map(|(_, other_transform)| other_transform.translation.truncate())
+            .filter(|position| position.distance(post) <= unit.vision)
+            .min_by(|a, b| a.distance(post).total_cmp(&b.distance(post)));
+        if let Some(overwatch) =
+            nearest.and_then(|enemy| Overwatch::new(post, enemy, unit.range, balance))
+        {
+            commands.entity(entity).insert(overwatch);
+        }
+    }
+}
+
+// Each enemy walking into a locked cone takes the guaranteed shot once the
+// rifle is ready, and not again until it has left the cone. Smoke hides
+// whoever is behind it.
+pub fn overwatch_fire_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    zone_query: Query<&AreaDenialZone>,
+    mut unit_query: Query<(
+        Entity,
+        &mut Unit,
+        &Transform,
+        Option<&mut Overwatch>,
+        Has<Captive>,
+    )>,
+    modifier_query: Query<&StatModifiers>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    // Nobody shoots a restrained prisoner
+    let targets: Vec<(Entity, Faction, Vec2)> = unit_query
+        .iter()
+        .filter(|(_, unit, _, _, is_captive)| unit.health > 0.0 && !is_captive)
+        .map(|(entity, unit, transform, _, _)| {
+            (
+                entity,
+                unit.faction.clone(),
+                transform.translation.truncate(),
+            )
+        })
+        .collect();
+
+    for (entity, mut unit, transform, overwatch, _) in unit_query.iter_mut() {
+        let Some(mut overwatch) = overwatch else {
+            continue;
+        };
+        if unit.health <= 0.0 || !overwatch.locked() {
+            continue;
+        }
+        let inside: Vec<Entity> = targets
+            .iter()
+            .filter(|(_, faction, position)| {
+                diplomacy.is_hostile(&unit.faction, faction)
+                    && overwatch.covers(*position)
+                    && !zone_query
+                        .iter()
+                        .any(|zone| zone.blocks_sight(overwatch.post, *position))
+            })
+            .map(|(target, _, _)| *target)
+            .collect();
+        overwatch.covered.retain(|target| inside.contains(target));
+        if !unit.attack_cooldown.finished() {
+            continue;
+        }
+        let Some(target) = inside
+            .into_iter()
+            .find(|target| !overwatch.covered.contains(target))
+        else {
+            continue;
+        };
+
+        overwatch.covered.push(target);
+        unit.attack_cooldown.reset();
+        let weapon = unit.equipment.weapon.clone();
+        let amount = overwatch_shot_damage(&weapon, &balance.overwatch)
+            * modifier_query
+                .get(entity)
+                .map_or(1.0, |modifiers| modifiers.damage_dealt)
+            * modifier_query
+                .get(target)
+                .map_or(1.0, |modifiers| modifiers.damage_taken);
+        let loudness = weapon_noise(&weapon, &balance.noise)
+            * modifier_query
+                .get(entity)
+                .map_or(1.0, |modifiers| modifiers.noise);
+        queue_noise(
+            &mut commands,
+            transform.translation,
+            loudness,
+            unit.faction.clone(),
+        );
+        damage_events.send(DamageEvent {
+            attacker: Some(entity),
+            target,
+            amount,
+            weapon: Some(weapon),
+        });
+    }
+}
+
+// A sniper's shot gives it away to the enemies who can see where it came
+// from, past walls and smoke: they mark the spot, and their snipers fire back
+pub fn muzzle_flash_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    diplomacy: Res<Diplomacy>,
+    city_map: Res<CityMap>,
+    zone_query: Query<&AreaDenialZone>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut unit_query: Query<(Entity, &mut Unit, &Transform, Option<&mut Communication>)>,
+    mut announced: Local<Vec<Entity>>,
+) {
+    let mut shots: Vec<(Entity, Faction, Vec3)> = Vec::new();
+    for event in damage_events.read() {
+        let Some(shooter) = event.attacker else {
+            continue;
+        };
+        let Ok((_, unit, transform, _)) = unit_query.get(shooter) else {
+            continue;
+        };
+        if unit.unit_type == UnitType::Sniper
+            && unit.health > 0.0
+            && !shots.iter().any(|(entity, _, _)| *entity == shooter)
+        {
+            shots.push((shooter, unit.faction.clone(), transform.translation));
+        }
+    }
+
+    for (shooter, shooter_faction, flash) in shots {
+        spawn_muzzle_flash(&mut commands, iso_to_world(flash));
+
+        let mut seen_by_cartel = false;
+        let mut seen_by_sniper = false;
+        for (_, mut unit, transform, communication) in unit_query.iter_mut() {
+            let position = transform.translation;
+            if unit.health <= 0.0
+                || !diplomacy.is_hostile(&unit.faction, &shooter_faction)
+                || position.distance(flash) > balance.overwatch.flash_reveal_range
+                || !city_map.has_line_of_sight(position.truncate(), flash.truncate())
+                || zone_query
+                    .iter()
+                    .any(|zone| zone.blocks_sight(position.truncate(), flash.truncate()))
+            {
+                continue;
+            }
+
+            seen_by_cartel |= unit.faction == Faction::Cartel;
+            if unit.unit_type == UnitType::Sniper {
+                unit.target = Some(shooter);
+                seen_by_sniper = true;
+            }
+            let Some(mut communication) = communication else {
+                continue;
+            };
+            if let Some(contact) = communication
+                .known_enemies
+                .iter_mut()
+                .find(|contact| contact.position.distance(flash) < CONTACT_MATCH_RADIUS)
+            {
+                contact.position = flash;
+                contact.confidence = 1.0;
+                contact.last_seen = 0.0;
+            } else {
+                communication.known_enemies.push(EnemyContact {
+                    position: flash,
+                    enemy_type: UnitType::Sniper,
+                    confidence: 1.0,
+                    last_seen: 0.0,
+                });
+            }
+        }
+
+        // Each sniper is called out once
+        if announced.contains(&shooter) {
+            continue;
+        }
+        let message = if shooter_faction == Faction::Cartel && seen_by_sniper {
+            "radio.sniper_compromised"
+        } else if shooter_faction != Faction::Cartel && seen_by_cartel {
+            "radio.enemy_sniper_spotted"
+        } else {
+            continue;
+        };
+        announced.push(shooter);
+        play_tactical_sound("radio", &tr(message));
+    }
+}
+
+// Keeps each sniper's cone outlined, dim while it settles and red once it is
+// locked, and clears the outline when overwatch is given up
+pub fn overwatch_cone_system(
+    mut commands: Commands,
+    sniper_query: Query<(Entity, &Unit, &Overwatch)>,
+    mut cone_query: Query<(Entity, &OverwatchCone, &mut Sprite)>,
+) {
+    let mut outlined = Vec::new();
+    for (entity, cone, mut sprite) in cone_query.iter_mut() {
+        match sniper_query.get(cone.owner) {
+            Ok((_, unit, overwatch))
+                if unit.health > 0.0
+                    && cone.post == overwatch.post
+                    && cone.facing == overwatch.facing =>
+            {
+                sprite.color = if overwatch.locked() {
+                    LOCKED_COLOR
+                } else {
+                    SETTLING_COLOR
+                };
+                outlined.push(cone.owner);
+            }
+            _ => commands.entity(entity).despawn(),
+        }
+    }
+
+    for (owner, unit, overwatch) in sniper_query.iter() {
+        if unit.health <= 0.0 || outlined.contains(&owner) {
+            continue;
+        }
+        for point in overwatch.outline() {
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: SETTLING_COLOR,
+                        custom_size: Some(CONE_MARKER_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(point.extend(CONE_Z)),
+                    ..default()
+                },
+                OverwatchCone {
+                    owner,
+                    post: overwatch.post,
+                    facing: overwatch.facing,
+                },
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cone_covers_what_is_in_front_and_in_range() {
+        let balance = OverwatchBalance::default();
+        let overwatch = Overwatch::new(Vec2::ZERO, Vec2::new(10.0, 0.0), 200.0, &balance).unwrap();
+        assert!(!overwatch.locked());
+
+        assert!(overwatch.covers(Vec2::new(150.0, 20.0)));
+        assert!(!overwatch.covers(Vec2::new(250.0, 0.0)));
+        assert!(!overwatch.covers(Vec2::new(50.0, 100.0)));
+        assert!(!overwatch.covers(Vec2::new(-100.0, 0.0)));
+
+        // Nowhere to face when pointed at its own feet
+        assert!(Overwatch::new(Vec2::ONE, Vec2::ONE, 200.0, &balance).is_none());
+    }
+
+    #[test]
+    fn test_outline_stays_inside_the_cone() {
+        let balance = OverwatchBalance::default();
+        let overwatch = Overwatch::new(
+            Vec2::new(50.0, 50.0),
+            Vec2::new(50.0, 400.0),
+            240.0,
+            &balance,
+        )
+        .unwrap();
+        let outline = overwatch.outline();
+        assert!(!outline.is_empty());
+        // Markers sit on the cone's boundary, so allow for rounding
+        for point in outline {
+            let offset = point - overwatch.post;
+            assert!(offset.length() <= overwatch.range + 1e-3);
+            assert!(offset.angle_between(overwatch.facing).abs() <= overwatch.half_angle + 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_overwatch_shot_beats_a_normal_hit() {
+        let balance = OverwatchBalance::default();
+        let normal = SHOT_DAMAGE * calculate_damage_modifier(&WeaponType::CartelSniperRifle);
+        assert!(overwatch_shot_damage(&WeaponType::CartelSniperRifle, &balance) > normal);
+    }
+}
@@ -1,6 +1,20 @@
+use crate::accessibility::AccessibilitySettings;
+use crate::app_state::GameSet;
+use crate::campaign::{Campaign, MissionResult, VictoryType};
 use crate::components::*;
+use crate::config::BalanceConfig;
+use crate::endings::PoliticalEnding;
+use crate::localization::{tr, tr_args};
+use crate::prisoners::{negotiations_open, PrisonerLedger};
 use crate::resources::*;
+use crate::rules_of_engagement::roe_key;
+use crate::ui::UiTheme;
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, play_tactical_sound, update_dashboard_panel, RngStream,
+    SimRng, SimulationSet,
+};
 use bevy::prelude::*;
+use rand::rngs::StdRng;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -14,17 +28,18 @@ impl Plugin for PoliticalSystemPlugin {
         app.init_resource::<PoliticalState>()
             .init_resource::<SocialMediaInfluence>()
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
                     political_pressure_system,
                     government_decision_system,
                     public_opinion_system,
                     media_coverage_system,
                     international_pressure_system,
-                    political_ui_system,
                 )
-                    .run_if(not_in_menu_phase),
-            );
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::InGame),
+            )
+            .add_systems(Update, political_ui_system.in_set(GameSet::InGame));
     }
 }
 
@@ -41,12 +56,26 @@ pub struct PoliticalState {
     pub casualties_civilian: u32,
     pub casualties_military: u32,
     pub casualties_cartel: u32,
+    #[serde(default)]
+    pub wounded_military: u32, // Brought down alive, whether or not they pulled through
+    #[serde(default)]
+    pub wounded_cartel: u32,
     pub infrastructure_damage: f32, // Economic impact
     pub operation_duration: f32,    // Time elapsed in seconds
     pub decision_threshold: f32,    // Threshold for government capitulation
     pub active_politicians: Vec<Politician>,
     pub recent_events: Vec<PoliticalEvent>,
     pub government_response_level: GovernmentResponseLevel,
+    #[serde(default)]
+    pub government_capitulated: bool, // Victory came from political pressure
+    #[serde(default)]
+    pub ending: Option<PoliticalEnding>, // How the government is backing down
+    #[serde(default)]
+    pub ending_timer: f32, // Seconds left before the ending has played out
+    #[serde(default)]
+    pub escalated: bool, // The hardliners overrode a release this mission
+    #[serde(default)]
+    pub roe_relief: f32, // Political actions holding the response level down
 }
 
 impl Default for PoliticalState {
@@ -61,6 +90,8 @@ impl Default for PoliticalState {
             casualties_civilian: 0,
             casualties_military: 0,
             casualties_cartel: 0,
+            wounded_military: 0,
+            wounded_cartel: 0,
             infrastructure_damage: 0.0,
             operation_duration: 0.0,
             decision_threshold: 0.3,
@@ -96,6 +127,33 @@ impl Default for PoliticalState {
             ],
             recent_events: Vec::new(),
             government_response_level: GovernmentResponseLevel::Limited,
+            government_capitulated: false,
+            ending: None,
+            ending_timer: 0.0,
+            escalated: false,
+            roe_relief: 0.0,
+        }
+    }
+}
+
+impl PoliticalState {
+    /// The mission as politics sees it: won once a way out the government
+    /// took has played out.
+    pub fn mission_result(&self) -> MissionResult {
+        match self.ending {
+            Some(ending) if ending.concedes() && self.ending_timer <= 0.0 => {
+                MissionResult::Victory(VictoryType::Capitulation(ending))
+            }
+            _ => MissionResult::InProgress,
+        }
+    }
+
+    /// What the victory screen tells of how it ended, if politics had a say.
+    pub fn epilogue_key(&self) -> Option<&'static str> {
+        match self.ending {
+            Some(ending) if ending.concedes() => Some(ending.epilogue_key()),
+            _ if self.escalated => Some(PoliticalEnding::EscalationOverride.epilogue_key()),
+            _ => None,
         }
     }
 }
@@ -142,7 +200,7 @@ pub enum EventType {
     Ceasefire,
 }
 
-#[derive(Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum GovernmentResponseLevel {
     Limited,    // Minimal force, quick withdrawal
     Moderate,   // Standard military response
@@ -160,6 +218,8 @@ pub struct SocialMediaInfluence {
     pub hashtag_trends: HashMap<String, f32>,
     pub international_coverage: f32, // 0.0 to 1.0
     pub journalist_presence: u32,
+    #[serde(default)]
+    pub content_shared: u32, // Every viral post so far, including those aged out of viral_videos
 }
 
 impl Default for SocialMediaInfluence {
@@ -176,6 +236,19 @@ impl Default for SocialMediaInfluence {
             hashtag_trends,
             international_coverage: 0.05,
             journalist_presence: 3,
+            content_shared: 0,
+        }
+    }
+}
+
+impl SocialMediaInfluence {
+    pub fn share(&mut self, content: ViralContent) {
+        self.viral_videos.push(content);
+        self.content_shared += 1;
+
+        // Limit viral content history
+        if self.viral_videos.len() > 10 {
+            self.viral_videos.remove(0);
         }
     }
 }
@@ -207,10 +280,13 @@ pub fn political_pressure_system(
     mut social_media: ResMut<SocialMediaInfluence>,
     game_state: Res<GameState>,
     unit_query: Query<&Unit>,
+    mut sim_rng: ResMut<SimRng>,
+    accessibility: Res<AccessibilitySettings>,
 ) {
-    let dt = time.delta_seconds();
+    let _span = info_span!("political_pressure_system").entered();
+    let dt = time.delta_seconds() * accessibility.political_time_scale();
     political_state.operation_duration += dt;
-    let mut rng = rand::thread_rng();
+    let rng = sim_rng.stream(RngStream::Political);
 
     // Calculate current situation metrics
     let cartel_units = unit_query
@@ -226,8 +302,10 @@ pub fn political_pressure_system(
     let duration_pressure = (political_state.operation_duration / 3600.0).min(2.0); // Max 2 hours
 
     // Update political will based on various factors
+    // Wounded soldiers make the news too, if less than the dead
     let casualty_pressure = (political_state.casualties_civilian as f32 * 0.05)
-        + (political_state.casualties_military as f32 * 0.03);
+        + (political_state.casualties_military as f32 * 0.03)
+        + (political_state.wounded_military as f32 * 0.01);
 
     let media_pressure = political_state.media_attention * 0.02;
     let duration_fatigue = duration_pressure * 0.01;
@@ -254,7 +332,7 @@ pub fn political_pressure_system(
 
     // Social media viral content generation
     if rng.gen::<f32>() < intensity_factor * dt * 0.1 {
-        generate_viral_content(&mut social_media, &political_state, &mut rng);
+        generate_viral_content(&mut social_media, &political_state, rng);
     }
 
     // Update hashtag trends
@@ -285,7 +363,7 @@ pub fn political_pressure_system(
 fn generate_viral_content(
     social_media: &mut SocialMediaInfluence,
     political_state: &PoliticalState,
-    rng: &mut rand::rngs::ThreadRng,
+    rng: &mut StdRng,
 ) {
     let content_types = [
         ContentType::CombatFootage,
@@ -315,12 +393,7 @@ fn generate_viral_content(
         impact_multiplier: rng.gen_range(1.0..3.0),
     };
 
-    social_media.viral_videos.push(viral_content);
-
-    // Limit viral content history
-    if social_media.viral_videos.len() > 10 {
-        social_media.viral_videos.remove(0);
-    }
+    social_media.share(viral_content);
 }
 
 fn update_hashtag_trends(
@@ -361,9 +434,12 @@ fn update_hashtag_trends(
 
 pub fn government_decision_system(
     mut political_state: ResMut<PoliticalState>,
-    mut game_state: ResMut<GameState>,
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    balance: Res<BalanceConfig>,
     time: Res<Time>,
 ) {
+    let _span = info_span!("government_decision_system").entered();
     // Calculate weighted decision factors
     let president = political_state
         .active_politicians
@@ -376,31 +452,39 @@ pub fn government_decision_system(
         + (1.0 - political_state.government_stability) * 0.3
         + (1.0 - president.support_for_operation) * 0.3;
 
-    // Check for government capitulation
-    if decision_pressure > political_state.decision_threshold {
-        // Historical accuracy: Government decided to release Ovidio
-        if !matches!(game_state.game_phase, GamePhase::Victory)
-            && !matches!(game_state.game_phase, GamePhase::Defeat)
-        {
-            // Add historical decision event
-            let event = PoliticalEvent {
-                event_type: EventType::PoliticalStatement,
-                timestamp: time.elapsed_seconds(),
-                impact_score: 1.0,
-                description: "Government orders cessation of operation and release of target"
-                    .to_string(),
-                media_coverage: 1.0,
-            };
-
-            political_state.recent_events.push(event);
-
-            // Trigger victory condition (historically accurate outcome)
-            game_state.game_phase = GamePhase::Victory;
-        }
+    // Past its threshold the government looks for a way out; which one it
+    // takes depends on where the pressure came from (see endings.rs)
+    if decision_pressure > political_state.decision_threshold
+        && political_state.ending.is_none()
+        && !matches!(
+            game_state.game_phase,
+            GamePhase::Victory | GamePhase::Defeat
+        )
+    {
+        let ending = PoliticalEnding::from_pressure(
+            &campaign.political_pressure,
+            political_state.escalated,
+            &balance.endings,
+        );
+        let event = PoliticalEvent {
+            event_type: ending.event_type(),
+            timestamp: time.elapsed_seconds(),
+            impact_score: 1.0,
+            description: ending.description().to_string(),
+            media_coverage: 1.0,
+        };
+        political_state.recent_events.push(event);
+        play_tactical_sound("radio", &tr(ending.radio_key()));
+        political_state.ending = Some(ending);
+        political_state.ending_timer = ending.seconds(&balance.endings);
     }
 
-    // Update government response level based on pressure and duration
-    political_state.government_response_level = match decision_pressure {
+    // Update government response level based on pressure and duration, and
+    // whatever relief political actions have bought (see rules_of_engagement.rs)
+    political_state.roe_relief =
+        (political_state.roe_relief - balance.roe.relief_decay * time.delta_seconds()).max(0.0);
+    let response_pressure = decision_pressure + political_state.roe_relief;
+    political_state.government_response_level = match response_pressure {
         p if p < 0.2 => GovernmentResponseLevel::AllOut,
         p if p < 0.4 => GovernmentResponseLevel::Aggressive,
         p if p < 0.6 => GovernmentResponseLevel::Moderate,
@@ -414,8 +498,10 @@ pub fn public_opinion_system(
     mut political_state: ResMut<PoliticalState>,
     social_media: Res<SocialMediaInfluence>,
     time: Res<Time>,
+    accessibility: Res<AccessibilitySettings>,
 ) {
-    let dt = time.delta_seconds();
+    let _span = info_span!("public_opinion_system").entered();
+    let dt = time.delta_seconds() * accessibility.political_time_scale();
 
     // Social media influence on public opinion
     let social_impact = social_media
@@ -430,7 +516,8 @@ pub fn public_opinion_system(
 
     // Casualties heavily influence public opinion
     let casualty_impact = (political_state.casualties_civilian as f32 * 0.02)
-        + (political_state.casualties_military as f32 * 0.01);
+        + (political_state.casualties_military as f32 * 0.01)
+        + (political_state.wounded_military as f32 * 0.003);
 
     // Update public support
     political_state.public_support_government +=
@@ -450,9 +537,12 @@ pub fn media_coverage_system(
     mut political_state: ResMut<PoliticalState>,
     mut social_media: ResMut<SocialMediaInfluence>,
     time: Res<Time>,
+    mut sim_rng: ResMut<SimRng>,
+    accessibility: Res<AccessibilitySettings>,
 ) {
-    let dt = time.delta_seconds();
-    let mut rng = rand::thread_rng();
+    let _span = info_span!("media_coverage_system").entered();
+    let dt = time.delta_seconds() * accessibility.political_time_scale();
+    let rng = sim_rng.stream(RngStream::Political);
 
     // Media coverage increases with dramatic events
     let coverage_factors = political_state.infrastructure_damage * 0.2
@@ -481,8 +571,12 @@ pub fn international_pressure_system(
     mut political_state: ResMut<PoliticalState>,
     social_media: Res<SocialMediaInfluence>,
     time: Res<Time>,
+    mut sim_rng: ResMut<SimRng>,
+    accessibility: Res<AccessibilitySettings>,
 ) {
-    let dt = time.delta_seconds();
+    let _span = info_span!("international_pressure_system").entered();
+    let dt = time.delta_seconds() * accessibility.political_time_scale();
+    let rng = sim_rng.stream(RngStream::Political);
 
     // International attention increases pressure
     let international_factors = social_media.international_coverage * 0.5
@@ -493,7 +587,7 @@ pub fn international_pressure_system(
     political_state.international_pressure = political_state.international_pressure.clamp(0.0, 1.0);
 
     // Generate international pressure events
-    if political_state.international_pressure > 0.6 && rand::thread_rng().gen::<f32>() < dt * 0.05 {
+    if political_state.international_pressure > 0.6 && rng.gen::<f32>() < dt * 0.05 {
         let event = PoliticalEvent {
             event_type: EventType::InternationalCriticism,
             timestamp: time.elapsed_seconds(),
@@ -517,205 +611,219 @@ pub fn political_ui_system(
     mut commands: Commands,
     political_state: Res<PoliticalState>,
     social_media: Res<SocialMediaInfluence>,
-    existing_ui: Query<Entity, With<PoliticalUIPanel>>,
+    prisoners: Option<Res<PrisonerLedger>>,
+    mut panel_query: Query<&mut Text, With<PoliticalUIPanel>>,
+    theme: Res<UiTheme>,
 ) {
-    // Remove existing political UI
-    for entity in existing_ui.iter() {
-        commands.entity(entity).despawn_recursive();
+    let panel_exists = !panel_query.is_empty();
+    if panel_exists
+        && !political_state.is_changed()
+        && !social_media.is_changed()
+        && !theme.is_changed()
+        && !prisoners.as_ref().is_some_and(|ledger| ledger.is_changed())
+    {
+        return;
     }
 
-    // Create political status panel
-    spawn_political_ui_panel(&mut commands, &political_state, &social_media);
-}
-
-#[derive(Component)]
-pub struct PoliticalUIPanel;
+    let lines = political_panel_lines(&political_state, &social_media, prisoners.as_deref());
 
-fn spawn_political_ui_panel(
-    commands: &mut Commands,
-    political_state: &PoliticalState,
-    social_media: &SocialMediaInfluence,
-) {
-    commands
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
+    if let Ok(mut text) = panel_query.get_single_mut() {
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else if !panel_exists {
+        // Create political status panel once; later frames only rewrite its text
+        commands.spawn((
+            create_dashboard_panel(
+                Style {
                     right: Val::Px(10.0),
                     top: Val::Px(10.0),
                     width: Val::Px(280.0),
-                    height: Val::Auto,
-                    flex_direction: FlexDirection::Column,
-                    padding: UiRect::all(Val::Px(10.0)),
                     ..default()
                 },
-                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.85)),
-                ..default()
-            },
+                Color::rgba(0.0, 0.0, 0.0, 0.85),
+                lines,
+                &theme,
+            ),
             PoliticalUIPanel,
-        ))
-        .with_children(|parent| {
-            // Political status title
-            parent.spawn(TextBundle::from_section(
-                "🏛️ POLITICAL STATUS",
-                TextStyle {
-                    font_size: 16.0,
-                    color: Color::GOLD,
-                    ..default()
-                },
-            ));
-
-            // Government stability
-            let stability_color = if political_state.government_stability > 0.7 {
-                Color::GREEN
-            } else if political_state.government_stability > 0.4 {
-                Color::YELLOW
-            } else {
-                Color::RED
-            };
-
-            parent.spawn(TextBundle::from_section(
-                format!(
-                    "Stability: {:.1}%",
-                    political_state.government_stability * 100.0
-                ),
-                TextStyle {
-                    font_size: 12.0,
-                    color: stability_color,
-                    ..default()
-                },
-            ));
+        ));
+    }
+}
 
-            // Political will
-            let will_color = if political_state.political_will > 0.6 {
-                Color::GREEN
-            } else if political_state.political_will > 0.3 {
-                Color::YELLOW
-            } else {
-                Color::RED
-            };
-
-            parent.spawn(TextBundle::from_section(
-                format!(
-                    "Political Will: {:.1}%",
-                    political_state.political_will * 100.0
-                ),
-                TextStyle {
-                    font_size: 12.0,
-                    color: will_color,
-                    ..default()
-                },
-            ));
+#[derive(Component)]
+pub struct PoliticalUIPanel;
 
-            // Public support
-            parent.spawn(TextBundle::from_section(
-                format!(
-                    "Public Support: {:.1}%",
-                    political_state.public_support_government * 100.0
-                ),
-                TextStyle {
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                    ..default()
-                },
-            ));
+fn political_panel_lines(
+    political_state: &PoliticalState,
+    social_media: &SocialMediaInfluence,
+    prisoners: Option<&PrisonerLedger>,
+) -> Vec<TextSection> {
+    // Political status title
+    let mut lines = vec![dashboard_line("🏛️ POLITICAL STATUS", 16.0, Color::GOLD)];
+
+    // Government stability
+    let stability_color = if political_state.government_stability > 0.7 {
+        Color::GREEN
+    } else if political_state.government_stability > 0.4 {
+        Color::YELLOW
+    } else {
+        Color::RED
+    };
 
-            // Media attention
-            let media_color = if political_state.media_attention > 0.7 {
-                Color::RED
-            } else if political_state.media_attention > 0.4 {
-                Color::ORANGE
-            } else {
-                Color::WHITE
-            };
-
-            parent.spawn(TextBundle::from_section(
-                format!(
-                    "Media Attention: {:.1}%",
-                    political_state.media_attention * 100.0
-                ),
-                TextStyle {
-                    font_size: 12.0,
-                    color: media_color,
-                    ..default()
-                },
-            ));
+    lines.push(dashboard_line(
+        format!(
+            "Stability: {:.1}%",
+            political_state.government_stability * 100.0
+        ),
+        12.0,
+        stability_color,
+    ));
+
+    // Political will
+    let will_color = if political_state.political_will > 0.6 {
+        Color::GREEN
+    } else if political_state.political_will > 0.3 {
+        Color::YELLOW
+    } else {
+        Color::RED
+    };
 
-            // International pressure
-            parent.spawn(TextBundle::from_section(
-                format!(
-                    "Intl. Pressure: {:.1}%",
-                    political_state.international_pressure * 100.0
-                ),
-                TextStyle {
-                    font_size: 12.0,
-                    color: Color::ORANGE,
-                    ..default()
-                },
-            ));
+    lines.push(dashboard_line(
+        format!(
+            "Political Will: {:.1}%",
+            political_state.political_will * 100.0
+        ),
+        12.0,
+        will_color,
+    ));
+
+    // Public support
+    lines.push(dashboard_line(
+        format!(
+            "Public Support: {:.1}%",
+            political_state.public_support_government * 100.0
+        ),
+        12.0,
+        Color::WHITE,
+    ));
+
+    // Media attention
+    let media_color = if political_state.media_attention > 0.7 {
+        Color::RED
+    } else if political_state.media_attention > 0.4 {
+        Color::ORANGE
+    } else {
+        Color::WHITE
+    };
 
-            // Casualties
-            if political_state.casualties_civilian > 0 || political_state.casualties_military > 0 {
-                parent.spawn(TextBundle::from_section(
-                    format!(
-                        "Casualties: {}C {}M",
-                        political_state.casualties_civilian, political_state.casualties_military
-                    ),
-                    TextStyle {
-                        font_size: 12.0,
-                        color: Color::RED,
-                        ..default()
-                    },
-                ));
-            }
+    lines.push(dashboard_line(
+        format!(
+            "Media Attention: {:.1}%",
+            political_state.media_attention * 100.0
+        ),
+        12.0,
+        media_color,
+    ));
+
+    // International pressure
+    lines.push(dashboard_line(
+        format!(
+            "Intl. Pressure: {:.1}%",
+            political_state.international_pressure * 100.0
+        ),
+        12.0,
+        Color::ORANGE,
+    ));
+
+    // The rules the army is fighting under, and what they allow
+    let level = &political_state.government_response_level;
+    let roe_color = match level {
+        GovernmentResponseLevel::Limited => Color::GREEN,
+        GovernmentResponseLevel::Moderate => Color::YELLOW,
+        GovernmentResponseLevel::Aggressive => Color::ORANGE,
+        GovernmentResponseLevel::AllOut => Color::RED,
+    };
+    lines.push(dashboard_line(
+        tr_args("hud.roe", &[("rules", &tr(roe_key(level)))]),
+        12.0,
+        roe_color,
+    ));
+    lines.push(dashboard_line(
+        tr(&format!("{}_hint", roe_key(level))),
+        10.0,
+        Color::GRAY,
+    ));
+
+    // Casualties
+    if political_state.casualties_civilian > 0
+        || political_state.casualties_military > 0
+        || political_state.wounded_military > 0
+    {
+        lines.push(dashboard_line(
+            format!(
+                "Casualties: {}C {}M ({} wounded)",
+                political_state.casualties_civilian,
+                political_state.casualties_military,
+                political_state.wounded_military
+            ),
+            12.0,
+            Color::RED,
+        ));
+    }
 
-            // Operation duration
-            let hours = (political_state.operation_duration / 3600.0) as u32;
-            let minutes = ((political_state.operation_duration % 3600.0) / 60.0) as u32;
-            parent.spawn(TextBundle::from_section(
-                format!("Duration: {}h {}m", hours, minutes),
-                TextStyle {
-                    font_size: 12.0,
-                    color: Color::GRAY,
-                    ..default()
-                },
+    // Prisoners held by each side, and the exchange while talks are open
+    if let Some(prisoners) = prisoners.filter(|p| p.soldiers_held + p.sicarios_held > 0) {
+        lines.push(dashboard_line(
+            tr_args(
+                "hud.prisoners",
+                &[
+                    ("soldiers", &prisoners.soldiers_held),
+                    ("sicarios", &prisoners.sicarios_held),
+                ],
+            ),
+            12.0,
+            Color::ORANGE,
+        ));
+        if prisoners.exchange_count() > 0
+            && negotiations_open(&political_state.government_response_level)
+        {
+            lines.push(dashboard_line(
+                tr("hud.prisoner_exchange_hint"),
+                11.0,
+                Color::CYAN,
             ));
+        }
+    }
 
-            // Top trending hashtag
-            if let Some((hashtag, trend_value)) = social_media
-                .hashtag_trends
-                .iter()
-                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
-            {
-                parent.spawn(TextBundle::from_section(
-                    format!("Trending: {} ({:.1}%)", hashtag, trend_value * 100.0),
-                    TextStyle {
-                        font_size: 10.0,
-                        color: Color::CYAN,
-                        ..default()
-                    },
-                ));
-            }
+    // Operation duration
+    let hours = (political_state.operation_duration / 3600.0) as u32;
+    let minutes = ((political_state.operation_duration % 3600.0) / 60.0) as u32;
+    lines.push(dashboard_line(
+        format!("Duration: {}h {}m", hours, minutes),
+        12.0,
+        Color::GRAY,
+    ));
+
+    // Top trending hashtag
+    if let Some((hashtag, trend_value)) = social_media
+        .hashtag_trends
+        .iter()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+    {
+        lines.push(dashboard_line(
+            format!("Trending: {} ({:.1}%)", hashtag, trend_value * 100.0),
+            10.0,
+            Color::CYAN,
+        ));
+    }
 
-            // Recent important events
-            if let Some(event) = political_state.recent_events.last() {
-                parent.spawn(TextBundle::from_section(
-                    "📰 LATEST:",
-                    TextStyle {
-                        font_size: 11.0,
-                        color: Color::YELLOW,
-                        ..default()
-                    },
-                ));
-
-                parent.spawn(TextBundle::from_section(
-                    format!("• {}", event.description),
-                    TextStyle {
-                        font_size: 9.0,
-                        color: Color::WHITE,
-                        ..default()
-                    },
-                ));
-            }
-        });
+    // Recent important events
+    if let Some(event) = political_state.recent_events.last() {
+        lines.push(dashboard_line("📰 LATEST:", 11.0, Color::YELLOW));
+        lines.push(dashboard_line(
+            format!("• {}", event.description),
+            9.0,
+            Color::WHITE,
+        ));
+    }
+
+    lines
 }
@@ -0,0 +1,466 @@
+use crate::ai::unit_ai_system;
+use crate::app_state::GameSet;
+use crate::campaign::Campaign;
+use crate::components::*;
+use crate::config::BalanceConfig;
+use crate::coordination::advanced_tactical_ai_system;
+use crate::corpse_system::{register_corpses_system, Corpse};
+use crate::damage::DownedSet;
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::political_system::{EventType, GovernmentResponseLevel, PoliticalEvent, PoliticalState};
+use crate::scoring::{challenge_active, ChallengeModifier, ChallengeSettings};
+use crate::systems::pathfinding_system;
+use crate::utils::{play_tactical_sound, RngStream, SimRng, SimulationSet};
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashSet;
+
+// ==================== PRISONER SYSTEM PLUGIN ====================
+
+// Fighters who go down next to the enemy aren't always finished off. Some are
+// taken alive, walked back to the captor's holding point and kept there. The
+// cartel holding soldiers keeps grinding down troop morale; every sicario the
+// army holds is one man fewer answering the call for reinforcements. While the
+// government is willing to talk (a Limited or Moderate response) the political
+// panel offers a one-for-one exchange, and either side can still break a
+// prisoner out by reaching him with no captor around.
+
+const CAPTURE_RADIUS: f32 = 45.0; // An enemy this close can drag the downed man off
+const CAPTURE_CHANCE: f32 = 0.35;
+const CONTEST_RADIUS: f32 = 80.0; // Any captor this close stops a rescue
+const RESCUE_RADIUS: f32 = 40.0;
+const ESCORT_PICKUP_RADIUS: f32 = 60.0;
+const ESCORT_LEASH: f32 = 25.0; // The escort waits when the prisoner falls this far behind
+const HOLDING_RADIUS: f32 = 30.0;
+const DRAG_SPEED: f32 = 55.0; // Prisoners have no Unit, so the escort system moves them
+const RELEASED_HEALTH: f32 = 0.4; // Fraction of max health prisoners come back with
+const SOLDIER_CAPTURE_SHOCK: f32 = 0.5;
+const HELD_PRESSURE_INTERVAL: f32 = 10.0;
+const HELD_PRESSURE_PER_SOLDIER: f32 = 0.1;
+const EXCHANGE_MORALE_RELIEF: f32 = 0.05; // Per soldier brought home
+const EXCHANGE_WILL_COST: f32 = 0.02; // Dealing with the cartel costs the government face
+const CARTEL_HOLDING_FALLBACK: Vec3 = Vec3::new(-150.0, 0.0, 0.0);
+const MILITARY_HOLDING_FALLBACK: Vec3 = Vec3::new(300.0, 0.0, 0.0);
+
+// Type aliases to reduce complexity
+type DownedUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static mut Unit, &'static Transform),
+    (
+        Without<Corpse>,
+        Without<ProtectedPrincipal>,
+        Without<Wounded>,
+    ),
+>;
+type CaptorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        &'static Transform,
+        &'static mut Movement,
+    ),
+    (
+        Without<Prisoner>,
+        Without<ProtectedPrincipal>,
+        Without<Captive>,
+    ),
+>;
+
+pub struct PrisonerSystemPlugin;
+
+impl Plugin for PrisonerSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PrisonerLedger>().add_systems(
+            FixedUpdate,
+            (
+                incapacitation_system
+                    .in_set(DownedSet)
+                    .before(register_corpses_system),
+                prisoner_escort_system
+                    .after(advanced_tactical_ai_system)
+                    .after(unit_ai_system)
+                    .before(pathfinding_system),
+            )
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PrisonerLedger {
+    pub soldiers_held: u32, // Military prisoners at the cartel holding point
+    pub sicarios_held: u32, // Cartel prisoners at the military holding point
+}
+
+impl PrisonerLedger {
+    pub fn exchange_count(&self) -> u32 {
+        self.soldiers_held.min(self.sicarios_held)
+    }
+
+    /// Reinforcement sicarios that don't turn up because their comrades are in
+    /// army hands; the enforcer leading the call always comes.
+    pub fn withheld_reinforcements(&self, sicario_slots: usize) -> usize {
+        (self.sicarios_held as usize).min(sicario_slots)
+    }
+}
+
+/// Only a government that's talking will trade prisoners.
+pub fn negotiations_open(level: &GovernmentResponseLevel) -> bool {
+    matches!(
+        level,
+        GovernmentResponseLevel::Limited | GovernmentResponseLevel::Moderate
+    )
+}
+
+fn can_be_taken(unit_type: &UnitType) -> bool {
+    matches!(
+        unit_type,
+        UnitType::Sicario
+            | UnitType::Enforcer
+            | UnitType::Sniper
+            | UnitType::HeavyGunner
+            | UnitType::Medic
+            | UnitType::Soldier
+            | UnitType::SpecialForces
+            | UnitType::Engineer
+    )
+}
+
+/// Where a captor faction keeps its prisoners: its spawn point nearest the
+/// capture, or a fixed fallback on maps without one.
+pub fn holding_point(captor_spawns: &[Vec2], from: Vec3, captor: &Faction) -> Vec3 {
+    captor_spawns
+        .iter()
+        .map(|spawn| spawn.extend(0.0))
+        .min_by(|a, b| a.distance(from).total_cmp(&b.distance(from)))
+        .unwrap_or(match captor {
+            Faction::Cartel => CARTEL_HOLDING_FALLBACK,
+            _ => MILITARY_HOLDING_FALLBACK,
+        })
+}
+
+// ==================== INCAPACITATION ====================
+
+// Runs between combat and corpse registration, so a fighter taken alive never
+// becomes a body, and before death_system, so he's no kill either
+pub fn incapacitation_system(
+    mut commands: Commands,
+    mut sim_rng: ResMut<SimRng>,
+    mut campaign: ResMut<Campaign>,
+    challenges: Option<Res<ChallengeSettings>>,
+    mut downed_query: DownedUnitQuery,
+) {
+    // Under permadeath a downed sicario is dead, never a prisoner to trade back
+    let permadeath = challenge_active(challenges.as_deref(), ChallengeModifier::Permadeath);
+    let standing: Vec<(Faction, Vec3)> = downed_query
+        .iter()
+        .filter(|(_, unit, _)| unit.health > 0.0)
+        .map(|(_, unit, transform)| (unit.faction.clone(), transform.translation))
+        .collect();
+    let rng = sim_rng.stream(RngStream::Combat);
+
+    for (entity, mut unit, transform) in downed_query.iter_mut() {
+        if unit.health > 0.0 || !can_be_taken(&unit.unit_type) {
+            continue;
+        }
+        let captor = match unit.faction {
+            Faction::Cartel if permadeath => continue,
+            Faction::Cartel => Faction::Military,
+            Faction::Military => Faction::Cartel,
+            _ => continue,
+        };
+
+        // The enemy has to be standing over him, closer than his own side
+        let position = transform.translation;
+        let nearest = |faction: &Faction| {
+            standing
+                .iter()
+                .filter(|(f, _)| f == faction)
+                .map(|(_, p)| p.distance(position))
+                .min_by(|a, b| a.total_cmp(b))
+        };
+        let Some(enemy_distance) = nearest(&captor) else {
+            continue;
+        };
+        if enemy_distance > CAPTURE_RADIUS
+            || nearest(&unit.faction).is_some_and(|d| d < enemy_distance)
+            || rng.gen::<f32>() >= CAPTURE_CHANCE
+        {
+            continue;
+        }
+
+        // Kept above zero so the corpse system leaves him alone this tick
+        unit.health = 1.0;
+        let mut held_unit = unit.clone();
+        held_unit.target = None;
+        commands
+            .entity(entity)
+            .remove::<(Unit, Selected, HoldArea, Bodyguard, HvtTasking)>()
+            .insert(Prisoner {
+                unit: held_unit,
+                captor: captor.clone(),
+                escort: None,
+                held: false,
+            });
+
+        if captor == Faction::Cartel {
+            campaign
+                .political_pressure
+                .reduce_military_morale(SOLDIER_CAPTURE_SHOCK);
+            play_tactical_sound("radio", &tr("radio.prisoner_soldier_taken"));
+        } else {
+            play_tactical_sound("radio", &tr("radio.prisoner_sicario_taken"));
+        }
+    }
+}
+
+// ==================== ESCORT AND HOLDING ====================
+
+#[allow(clippy::too_many_arguments)]
+pub fn prisoner_escort_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    city_map: Res<CityMap>,
+    mut ledger: ResMut<PrisonerLedger>,
+    mut campaign: ResMut<Campaign>,
+    mut held_pressure: Local<f32>,
+    mut prisoner_query: Query<(Entity, &mut Transform, &mut Prisoner)>,
+    mut unit_query: CaptorQuery,
+) {
+    let mut escorts: HashSet<Entity> = prisoner_query
+        .iter()
+        .filter_map(|(_, _, prisoner)| prisoner.escort)
+        .collect();
+    let (mut soldiers_held, mut sicarios_held) = (0, 0);
+
+    for (entity, mut transform, mut prisoner) in prisoner_query.iter_mut() {
+        let position = transform.translation;
+        let count_near = |faction: &Faction, radius: f32| {
+            unit_query
+                .iter()
+                .filter(|(_, unit, t, _)| {
+                    unit.faction == *faction
+                        && unit.health > 0.0
+                        && t.translation.distance(position) <= radius
+                })
+                .count()
+        };
+
+        // His own side reached him and nobody is guarding him
+        if count_near(&prisoner.unit.faction, RESCUE_RADIUS) > 0
+            && count_near(&prisoner.captor, CONTEST_RADIUS) == 0
+        {
+            if let Some(escort) = prisoner.escort {
+                if let Ok((.., mut movement)) = unit_query.get_mut(escort) {
+                    movement.target_position = None;
+                }
+            }
+            let mut unit = prisoner.unit.clone();
+            unit.health = unit.max_health * RELEASED_HEALTH;
+            commands.entity(entity).remove::<Prisoner>().insert(unit);
+            play_tactical_sound("radio", &tr("radio.prisoner_rescued"));
+            continue;
+        }
+
+        if prisoner.held {
+            match prisoner.captor {
+                Faction::Cartel => soldiers_held += 1,
+                _ => sicarios_held += 1,
+            }
+            continue;
+        }
+
+        // Anyone on the captor's side close enough takes over a lost escort
+        let escort_alive = prisoner.escort.is_some_and(|escort| {
+            unit_query
+                .get(escort)
+                .is_ok_and(|(_, unit, ..)| unit.health > 0.0)
+        });
+        if !escort_alive {
+            prisoner.escort = unit_query
+                .iter()
+                .filter(|(e, unit, t, _)| {
+                    unit.faction == prisoner.captor
+                        && unit.health > 0.0
+                        && !escorts.contains(e)
+                        && t.translation.distance(position) <= ESCORT_PICKUP_RADIUS
+                })
+                .min_by(|a, b| {
+                    a.2.translation
+                        .distance(position)
+                        .total_cmp(&b.2.translation.distance(position))
+                })
+                .map(|(e, ..)| e);
+            escorts.extend(prisoner.escort);
+        }
+        let Some(escort) = prisoner.escort else {
+            continue;
+        };
+        let Ok((_, _, escort_transform, mut escort_movement)) = unit_query.get_mut(escort) else {
+            continue;
+        };
+        let escort_pos = escort_transform.translation;
+        let holding = holding_point(
+            &city_map.spawn_positions(&prisoner.captor),
+            position,
+            &prisoner.captor,
+        );
+
+        if escort_pos.distance(holding) <= HOLDING_RADIUS
+            && position.distance(escort_pos) <= ESCORT_LEASH
+        {
+            escort_movement.target_position = None;
+            prisoner.escort = None;
+            prisoner.held = true;
+            continue;
+        }
+
+        // The escort walks him in, waiting whenever he falls behind
+        escort_movement.target_position = if position.distance(escort_pos) > ESCORT_LEASH {
+            Some(position)
+        } else {
+            Some(holding)
+        };
+        let step = (escort_pos - position).normalize_or_zero() * DRAG_SPEED * time.delta_seconds();
+        if position.distance(escort_pos) > ESCORT_LEASH * 0.5 {
+            transform.translation += step;
+        }
+    }
+
+    if ledger.soldiers_held != soldiers_held || ledger.sicarios_held != sicarios_held {
+        ledger.soldiers_held = soldiers_held;
+        ledger.sicarios_held = sicarios_held;
+    }
+
+    // Soldiers in cartel hands keep wearing the troops down
+    *held_pressure += time.delta_seconds();
+    if *held_pressure >= HELD_PRESSURE_INTERVAL {
+        *held_pressure = 0.0;
+        if soldiers_held > 0 {
+            campaign
+                .political_pressure
+                .reduce_military_morale(HELD_PRESSURE_PER_SOLDIER * soldiers_held as f32);
+        }
+    }
+}
+
+// ==================== EXCHANGE ====================
+
+#[allow(clippy::too_many_arguments)]
+pub fn prisoner_exchange_system(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    city_map: Res<CityMap>,
+    balance: Res<BalanceConfig>,
+    mut ledger: ResMut<PrisonerLedger>,
+    mut campaign: ResMut<Campaign>,
+    mut political_state: ResMut<PoliticalState>,
+    mut prisoner_query: Query<(Entity, &mut Transform, &Prisoner)>,
+) {
+    if !input.just_pressed(KeyCode::X) {
+        return;
+    }
+    if !negotiations_open(&political_state.government_response_level) {
+        play_tactical_sound("radio", &tr("radio.prisoner_exchange_refused"));
+        return;
+    }
+    let swaps = ledger.exchange_count();
+    if swaps == 0 {
+        play_tactical_sound("radio", &tr("radio.prisoner_exchange_none"));
+        return;
+    }
+
+    let cartel_holding = holding_point(
+        &city_map.spawn_positions(&Faction::Cartel),
+        Vec3::ZERO,
+        &Faction::Cartel,
+    );
+    let (mut soldiers, mut sicarios) = (0, 0);
+    for (entity, mut transform, prisoner) in prisoner_query.iter_mut() {
+        if !prisoner.held {
+            continue;
+        }
+        if prisoner.captor == Faction::Cartel && soldiers < swaps {
+            // Handed over at the line and driven off the map
+            soldiers += 1;
+            commands.entity(entity).despawn_recursive();
+        } else if prisoner.captor == Faction::Military && sicarios < swaps {
+            sicarios += 1;
+            let mut unit = prisoner.unit.clone();
+            unit.health = unit.max_health * RELEASED_HEALTH;
+            transform.translation = cartel_holding;
+            commands.entity(entity).remove::<Prisoner>().insert(unit);
+        }
+    }
+
+    ledger.soldiers_held -= soldiers;
+    ledger.sicarios_held -= sicarios;
+    let pressure = &mut campaign.political_pressure;
+    pressure.military_morale =
+        (pressure.military_morale - EXCHANGE_MORALE_RELIEF * soldiers as f32).max(0.0);
+    political_state.political_will =
+        (political_state.political_will - EXCHANGE_WILL_COST * swaps as f32).max(0.0);
+    political_state.roe_relief += balance.roe.exchange_relief * swaps as f32;
+    political_state.recent_events.push(PoliticalEvent {
+        event_type: EventType::Ceasefire,
+        timestamp: time.elapsed_seconds(),
+        impact_score: 0.4,
+        description: format!(
+            "Prisoner exchange: {} soldiers for {} sicarios",
+            soldiers, sicarios
+        ),
+        media_coverage: 0.6,
+    });
+    play_tactical_sound(
+        "radio",
+        &tr_args("radio.prisoner_exchange", &[("count", &swaps)]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holding_at_nearest_spawn_or_faction_fallback() {
+        let spawns = [Vec2::new(-400.0, 0.0), Vec2::new(0.0, 300.0)];
+        assert_eq!(
+            holding_point(&spawns, Vec3::new(0.0, 100.0, 0.0), &Faction::Military),
+            Vec3::new(0.0, 300.0, 0.0)
+        );
+        assert_eq!(
+            holding_point(&[], Vec3::ZERO, &Faction::Cartel),
+            CARTEL_HOLDING_FALLBACK
+        );
+        assert_eq!(
+            holding_point(&[], Vec3::ZERO, &Faction::Military),
+            MILITARY_HOLDING_FALLBACK
+        );
+    }
+
+    #[test]
+    fn test_exchange_is_one_for_one_and_held_sicarios_thin_reinforcements() {
+        let ledger = PrisonerLedger {
+            soldiers_held: 3,
+            sicarios_held: 1,
+        };
+        assert_eq!(ledger.exchange_count(), 1);
+        assert_eq!(ledger.withheld_reinforcements(2), 1);
+
+        let crowded = PrisonerLedger {
+            soldiers_held: 0,
+            sicarios_held: 5,
+        };
+        assert_eq!(crowded.exchange_count(), 0);
+        assert_eq!(crowded.withheld_reinforcements(2), 2);
+
+        assert!(negotiations_open(&GovernmentResponseLevel::Moderate));
+        assert!(!negotiations_open(&GovernmentResponseLevel::AllOut));
+    }
+}
@@ -0,0 +1,296 @@
+use crate::app_state::{AppState, GameSet};
+use crate::campaign::{Campaign, MissionConfig};
+use crate::components::*;
+use crate::config::{ProductionBalance, UnitCatalog};
+use crate::localization::tr_args;
+use crate::map::CityMap;
+use crate::resources::GameAssets;
+use crate::spawners::spawn_unit;
+use crate::ui::HOLD_AREA_LEASH_RADIUS;
+use crate::utils::{iso_to_world, play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== PRODUCTION PLUGIN ====================
+
+// Units that join mid-mission are built, one at a time, at a producer: the
+// cartel's safehouses, where the player buys recruits (see economy.rs), and
+// in missions with base play the army's staging areas, which the AI director
+// and wave spawner queue their reinforcements at instead of conjuring them
+// at the map edge. A bar over each producer shows how far along the unit at
+// the front of its queue is. Finished units walk out of the front door, and
+// with a rally point set they head there and hold it.
+
+const EXIT_OFFSET: Vec3 = Vec3::new(0.0, -24.0, 0.0); // On screen, at the door and on the pad
+const BAR_SIZE: Vec2 = Vec2::new(56.0, 5.0);
+const BAR_OFFSET: Vec3 = Vec3::new(0.0, 44.0, 0.5);
+const STAGING_AREA_SIZE: Vec2 = Vec2::new(80.0, 56.0);
+const STAGING_AREA_COLOR: Color = Color::rgb(0.25, 0.35, 0.2);
+// Used when the map marks no military spawn points
+const FALLBACK_STAGING_AREAS: [Vec2; 2] = [Vec2::new(320.0, 0.0), Vec2::new(-320.0, 0.0)];
+
+pub struct ProductionPlugin;
+
+impl Plugin for ProductionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), staging_area_setup_system)
+            .add_systems(
+                FixedUpdate,
+                production_system
+                    .run_if(resource_exists::<GameAssets>())
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, production_bar_system.in_set(GameSet::Session));
+    }
+}
+
+impl ProductionQueue {
+    pub fn new(faction: Faction) -> Self {
+        Self {
+            faction,
+            orders: Vec::new(),
+            rally_point: None,
+        }
+    }
+
+    pub fn push(&mut self, unit_type: UnitType, build_seconds: f32, cost: f32) {
+        self.orders.push(ProductionOrder {
+            unit_type,
+            build_seconds,
+            progress: 0.0,
+            cost,
+        });
+    }
+
+    /// Takes back the most recently queued order, for a refund.
+    pub fn cancel_last(&mut self) -> Option<ProductionOrder> {
+        self.orders.pop()
+    }
+
+    /// Share of the front order built so far; None with nothing queued.
+    pub fn progress(&self) -> Option<f32> {
+        self.orders
+            .first()
+            .map(|order| (order.progress / order.build_seconds.max(f32::EPSILON)).min(1.0))
+    }
+
+    /// Builds the front order for `dt` seconds, handing it over once done.
+    pub fn advance(&mut self, dt: f32) -> Option<ProductionOrder> {
+        let order = self.orders.first_mut()?;
+        order.progress += dt;
+        (order.progress >= order.build_seconds).then(|| self.orders.remove(0))
+    }
+}
+
+impl ProductionOrder {
+    pub fn remaining(&self) -> f32 {
+        (self.build_seconds - self.progress).max(0.0)
+    }
+}
+
+pub fn staging_build_seconds(cost: u32, production: &ProductionBalance) -> f32 {
+    cost as f32 * production.staging_build_seconds_per_cost
+}
+
+/// Brings on a new army unit: queued at the least busy staging area in
+/// missions with base play, or straight onto the map at `position` in the
+/// rest. False when every staging area's queue is full and the unit is
+/// dropped.
+pub fn deploy_military_unit(
+    commands: &mut Commands,
+    staging_areas: &mut Query<&mut ProductionQueue, With<StagingArea>>,
+    unit_type: UnitType,
+    position: Vec3,
+    game_assets: &Res<GameAssets>,
+    catalog: &UnitCatalog,
+    production: &ProductionBalance,
+) -> bool {
+    if staging_areas.is_empty() {
+        spawn_unit(
+            commands,
+            unit_type,
+            Faction::Military,
+            position,
+            game_assets,
+            catalog,
+        );
+        return true;
+    }
+
+    let Some(mut queue) = staging_areas
+        .iter_mut()
+        .filter(|queue| queue.orders.len() < production.staging_max_queue)
+        .min_by_key(|queue| queue.orders.len())
+    else {
+        return false;
+    };
+    let cost = catalog.definition(&unit_type).cost;
+    queue.push(unit_type, staging_build_seconds(cost, production), 0.0);
+    true
+}
+
+/// Background and fill of a producer's progress bar, as its children.
+pub fn spawn_production_bar(parent: &mut ChildBuilder) {
+    for (foreground, color) in [(false, Color::rgb(0.1, 0.1, 0.1)), (true, Color::CYAN)] {
+        parent.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(BAR_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    BAR_OFFSET + Vec3::Z * if foreground { 0.1 } else { 0.0 },
+                ),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ProductionBar { foreground },
+        ));
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+// Staging areas belong to one attempt: cleared as a mission starts, and set
+// up again at the military spawn points if it wants base play
+pub fn staging_area_setup_system(
+    mut commands: Commands,
+    campaign: Res<Campaign>,
+    city_map: Res<CityMap>,
+    staging_query: Query<Entity, With<StagingArea>>,
+) {
+    for entity in staging_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !MissionConfig::get_mission_config(&campaign.progress.current_mission).base_play {
+        return;
+    }
+
+    let mut positions = city_map.spawn_positions(&Faction::Military);
+    if positions.is_empty() {
+        positions = FALLBACK_STAGING_AREAS.to_vec();
+    }
+    for position in positions {
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: STAGING_AREA_COLOR,
+                        custom_size: Some(STAGING_AREA_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(position.extend(-0.2)),
+                    ..default()
+                },
+                StagingArea,
+                ProductionQueue::new(Faction::Military),
+            ))
+            .with_children(spawn_production_bar);
+    }
+}
+
+pub fn production_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    mut producer_query: Query<(&Transform, &mut ProductionQueue)>,
+) {
+    for (transform, mut queue) in producer_query.iter_mut() {
+        if queue.orders.is_empty() {
+            continue;
+        }
+        let Some(order) = queue.advance(time.delta_seconds()) else {
+            continue;
+        };
+
+        let exit = iso_to_world(transform.translation.truncate().extend(0.0) + EXIT_OFFSET);
+        let entity = spawn_unit(
+            &mut commands,
+            order.unit_type.clone(),
+            queue.faction.clone(),
+            exit,
+            &game_assets,
+            &catalog,
+        );
+        if let Some(rally_point) = queue.rally_point {
+            commands.entity(entity).insert(HoldArea {
+                center: rally_point,
+                post: rally_point,
+                leash_radius: HOLD_AREA_LEASH_RADIUS,
+            });
+        }
+        if queue.faction == Faction::Cartel {
+            play_tactical_sound(
+                "radio",
+                &tr_args(
+                    "radio.recruit_ready",
+                    &[("unit", &format!("{:?}", order.unit_type))],
+                ),
+            );
+        }
+    }
+}
+
+pub fn production_bar_system(
+    producer_query: Query<(&ProductionQueue, &Children), Changed<ProductionQueue>>,
+    mut bar_query: Query<(&ProductionBar, &mut Sprite, &mut Transform, &mut Visibility)>,
+) {
+    for (queue, children) in producer_query.iter() {
+        let progress = queue.progress();
+        for &child in children.iter() {
+            let Ok((bar, mut sprite, mut transform, mut visibility)) = bar_query.get_mut(child)
+            else {
+                continue;
+            };
+            *visibility = if progress.is_some() {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if bar.foreground {
+                // Fills from the left edge
+                let width = BAR_SIZE.x * progress.unwrap_or(0.0);
+                sprite.custom_size = Some(Vec2::new(width, BAR_SIZE.y));
+                transform.translation.x = BAR_OFFSET.x - (BAR_SIZE.x - width) / 2.0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_builds_in_order() {
+        let mut queue = ProductionQueue::new(Faction::Cartel);
+        assert_eq!(queue.progress(), None);
+        queue.push(UnitType::Sicario, 4.0, 100.0);
+        queue.push(UnitType::Sniper, 2.0, 200.0);
+
+        assert!(queue.advance(3.0).is_none());
+        assert!((queue.progress().unwrap() - 0.75).abs() < 1e-6);
+        let built = queue.advance(1.0).unwrap();
+        assert_eq!(built.unit_type, UnitType::Sicario);
+
+        // Only the front order was being built
+        assert_eq!(queue.orders[0].progress, 0.0);
+        assert!((queue.orders[0].remaining() - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cancel_takes_back_the_newest_order() {
+        let mut queue = ProductionQueue::new(Faction::Cartel);
+        queue.push(UnitType::Sicario, 4.0, 100.0);
+        queue.push(UnitType::Sniper, 2.0, 200.0);
+        queue.advance(1.0);
+
+        let cancelled = queue.cancel_last().unwrap();
+        assert_eq!(cancelled.unit_type, UnitType::Sniper);
+        assert_eq!(cancelled.cost, 200.0);
+        assert_eq!(queue.orders.len(), 1);
+        assert!((queue.orders[0].progress - 1.0).abs() < 1e-6);
+    }
+}
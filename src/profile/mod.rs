@@ -0,0 +1,3 @@
+pub mod profile_system;
+
+pub use profile_system::*;
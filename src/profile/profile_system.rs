@@ -0,0 +1,989 @@
+use crate::accessibility::AccessibilitySettings;
+use crate::auth::models::{User, UserRole};
+use crate::auth::AuthSession;
+use crate::campaign::Campaign;
+use crate::components::{FormationType, GamePhase, ProfilePickerMenu};
+use crate::localization::{system_language, tr, tr_args};
+use crate::multiplayer::{PlayerStats, SocialState};
+use crate::resources::GameState;
+use crate::save::save_system::{CampaignProgress, DifficultyLevel};
+use crate::scoring::Medal;
+use crate::ui::ColorPalette;
+use crate::utils::{data_dir, play_tactical_sound, DataKind};
+use bevy::prelude::*;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+// ==================== PROFILE SYSTEM PLUGIN ====================
+
+pub struct ProfileSystemPlugin;
+
+impl Plugin for ProfileSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProfileManager>()
+            .init_resource::<ProfilePickerState>()
+            .add_systems(Startup, setup_profile_system)
+            .add_systems(
+                Update,
+                (profile_picker_system, profile_progress_sync_system),
+            );
+    }
+}
+
+// ==================== PROFILE STORAGE ====================
+
+const PROFILE_FILE: &str = "profile.json";
+const LAST_PROFILE_FILE: &str = "last_profile";
+const MAX_LISTED_PROFILES: usize = 9;
+pub const DEFAULT_ELO: i32 = 1200;
+const ELO_K_FACTOR: f32 = 32.0;
+pub const CUSTOM_FORMATION_SLOTS: usize = 9; // Recalled with the number keys
+const DAILY_HISTORY_DAYS: usize = 30;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub user: User,
+    pub campaign_progress: CampaignProgress,
+    pub multiplayer: MultiplayerRecord,
+    pub settings: ProfileSettings,
+    #[serde(default)]
+    pub friends: Vec<FriendEntry>,
+    #[serde(default)]
+    pub custom_formations: Vec<CustomFormation>,
+    #[serde(default)]
+    pub daily_attempts: Vec<DailyAttempt>,
+}
+
+// A daily challenge started on this profile; the result stays empty unless it was won
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyAttempt {
+    pub date: NaiveDate,
+    pub score: Option<u32>,
+    pub medal: Option<Medal>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CustomFormation {
+    pub slot: usize,
+    pub name: String,
+    pub offsets: Vec<[f32; 2]>,
+}
+
+impl CustomFormation {
+    pub fn formation_type(&self) -> FormationType {
+        FormationType::Custom {
+            name: self.name.clone(),
+            offsets: self.offsets.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FriendEntry {
+    pub user_id: Uuid,
+    pub username: String,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiplayerRecord {
+    pub elo_rating: i32,
+    pub matches_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub total_damage_dealt: f32,
+    pub total_units_lost: u32,
+    pub total_objectives_completed: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)] // Profiles saved before a setting existed get its default
+pub struct ProfileSettings {
+    pub difficulty_level: DifficultyLevel,
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub radio_volume: f32,
+    pub music_volume: f32,
+    pub ambience_volume: f32,
+    pub show_tutorial_hints: bool,
+    pub screen_effects: bool, // Camera shake and hit flashes
+    pub language: String,     // e.g. es-MX, see localization::LANGUAGES
+    pub color_palette: ColorPalette,
+    pub high_contrast: bool,
+    pub accessibility: AccessibilitySettings,
+    pub telemetry: bool, // Opted in to recording gameplay data
+}
+
+#[derive(Clone, Debug)]
+pub struct ProfileSummary {
+    pub id: Uuid,
+    pub username: String,
+    pub last_played: Option<DateTime<Utc>>,
+    pub completed_missions: usize,
+    pub elo_rating: i32,
+}
+
+impl Default for MultiplayerRecord {
+    fn default() -> Self {
+        Self {
+            elo_rating: DEFAULT_ELO,
+            matches_played: 0,
+            wins: 0,
+            losses: 0,
+            total_damage_dealt: 0.0,
+            total_units_lost: 0,
+            total_objectives_completed: 0,
+        }
+    }
+}
+
+impl Default for ProfileSettings {
+    fn default() -> Self {
+        Self {
+            difficulty_level: DifficultyLevel::Veteran,
+            master_volume: 0.8,
+            sfx_volume: 0.8,
+            radio_volume: 0.9,
+            music_volume: 0.6,
+            ambience_volume: 0.5,
+            show_tutorial_hints: true,
+            screen_effects: true,
+            language: system_language(),
+            color_palette: ColorPalette::Standard,
+            high_contrast: false,
+            accessibility: AccessibilitySettings::default(),
+            telemetry: false,
+        }
+    }
+}
+
+impl MultiplayerRecord {
+    pub fn record_match(&mut self, won: bool, opponent_elo: i32, stats: &PlayerStats) {
+        self.elo_rating = calculate_elo(self.elo_rating, opponent_elo, won);
+        self.matches_played += 1;
+        if won {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+
+        self.total_damage_dealt += stats.damage_dealt;
+        self.total_units_lost += stats.units_lost;
+        self.total_objectives_completed += stats.objectives_completed;
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        if self.matches_played == 0 {
+            0.0
+        } else {
+            self.wins as f32 / self.matches_played as f32
+        }
+    }
+}
+
+pub fn calculate_elo(rating: i32, opponent_rating: i32, won: bool) -> i32 {
+    let expected = 1.0 / (1.0 + 10f32.powf((opponent_rating - rating) as f32 / 400.0));
+    let actual = if won { 1.0 } else { 0.0 };
+
+    rating + (ELO_K_FACTOR * (actual - expected)).round() as i32
+}
+
+impl PlayerProfile {
+    pub fn new_local(username: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let username = username.trim();
+        if username.len() < 3 || username.len() > 50 {
+            return Err("Profile name must be between 3 and 50 characters".into());
+        }
+
+        let now = Utc::now();
+        let user = User {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            email: String::new(),
+            password_hash: String::new(), // Local profiles are not password protected
+            role: UserRole::Player,
+            is_verified: false,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            last_login: Some(now),
+        };
+
+        Ok(Self {
+            user,
+            campaign_progress: CampaignProgress::default(),
+            multiplayer: MultiplayerRecord::default(),
+            settings: ProfileSettings::default(),
+            friends: Vec::new(),
+            custom_formations: Vec::new(),
+            daily_attempts: Vec::new(),
+        })
+    }
+
+    pub fn add_friend(&mut self, user_id: Uuid, username: &str) -> bool {
+        if user_id == self.user.id || self.is_friend(user_id) {
+            return false;
+        }
+
+        self.friends.push(FriendEntry {
+            user_id,
+            username: username.to_string(),
+            added_at: Utc::now(),
+        });
+        true
+    }
+
+    pub fn remove_friend(&mut self, user_id: Uuid) -> bool {
+        let before = self.friends.len();
+        self.friends.retain(|friend| friend.user_id != user_id);
+        self.friends.len() != before
+    }
+
+    pub fn is_friend(&self, user_id: Uuid) -> bool {
+        self.friends.iter().any(|friend| friend.user_id == user_id)
+    }
+
+    // Replaces whatever the slot held before
+    pub fn save_custom_formation(
+        &mut self,
+        slot: usize,
+        offsets: Vec<[f32; 2]>,
+    ) -> Option<&CustomFormation> {
+        if !(1..=CUSTOM_FORMATION_SLOTS).contains(&slot) || offsets.is_empty() {
+            return None;
+        }
+
+        self.custom_formations
+            .retain(|formation| formation.slot != slot);
+        self.custom_formations.push(CustomFormation {
+            slot,
+            name: format!("Formation {}", slot),
+            offsets,
+        });
+        self.custom_formations
+            .sort_by_key(|formation| formation.slot);
+        self.custom_formation(slot)
+    }
+
+    pub fn custom_formation(&self, slot: usize) -> Option<&CustomFormation> {
+        self.custom_formations
+            .iter()
+            .find(|formation| formation.slot == slot)
+    }
+
+    pub fn daily_attempt(&self, date: NaiveDate) -> Option<&DailyAttempt> {
+        self.daily_attempts
+            .iter()
+            .find(|attempt| attempt.date == date)
+    }
+
+    // One attempt per day: false if the day's challenge was already started
+    pub fn start_daily(&mut self, date: NaiveDate) -> bool {
+        if self.daily_attempt(date).is_some() {
+            return false;
+        }
+
+        self.daily_attempts.push(DailyAttempt {
+            date,
+            score: None,
+            medal: None,
+        });
+        if self.daily_attempts.len() > DAILY_HISTORY_DAYS {
+            self.daily_attempts.remove(0);
+        }
+        true
+    }
+
+    pub fn record_daily_result(&mut self, date: NaiveDate, score: u32, medal: Option<Medal>) {
+        if let Some(attempt) = self
+            .daily_attempts
+            .iter_mut()
+            .find(|attempt| attempt.date == date)
+        {
+            attempt.score = Some(score);
+            attempt.medal = medal;
+        }
+    }
+
+    pub fn summary(&self) -> ProfileSummary {
+        ProfileSummary {
+            id: self.user.id,
+            username: self.user.username.clone(),
+            last_played: self.user.last_login,
+            completed_missions: self.campaign_progress.completed_missions.len(),
+            elo_rating: self.multiplayer.elo_rating,
+        }
+    }
+
+    pub fn directory(&self) -> PathBuf {
+        get_profile_dir(self.user.id)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let profile_dir = self.directory();
+        fs::create_dir_all(&profile_dir)?;
+
+        let profile_json = serde_json::to_string_pretty(self)?;
+        fs::write(profile_dir.join(PROFILE_FILE), profile_json)?;
+
+        Ok(())
+    }
+
+    pub fn load(profile_id: Uuid) -> Result<Self, Box<dyn std::error::Error>> {
+        let profile_path = get_profile_dir(profile_id).join(PROFILE_FILE);
+        let profile_json = fs::read_to_string(&profile_path)?;
+        let profile: PlayerProfile = serde_json::from_str(&profile_json)?;
+
+        Ok(profile)
+    }
+}
+
+pub fn list_profiles() -> Vec<ProfileSummary> {
+    let mut profiles = Vec::new();
+
+    let Ok(entries) = fs::read_dir(get_profiles_root()) else {
+        return profiles;
+    };
+
+    for entry in entries.flatten() {
+        let Some(profile_id) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| Uuid::parse_str(name).ok())
+        else {
+            continue;
+        };
+
+        match PlayerProfile::load(profile_id) {
+            Ok(profile) => profiles.push(profile.summary()),
+            Err(e) => warn!("Skipping unreadable profile {}: {}", profile_id, e),
+        }
+    }
+
+    // Most recently played first
+    profiles.sort_by(|a, b| b.last_played.cmp(&a.last_played));
+    profiles.truncate(MAX_LISTED_PROFILES);
+    profiles
+}
+
+pub fn delete_profile(profile_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
+    let profile_dir = get_profile_dir(profile_id);
+    if profile_dir.exists() {
+        fs::remove_dir_all(&profile_dir)?;
+        info!("🗑️ Deleted profile {}", profile_id);
+    }
+
+    Ok(())
+}
+
+fn get_last_profile_id() -> Option<Uuid> {
+    fs::read_to_string(get_profiles_root().join(LAST_PROFILE_FILE))
+        .ok()
+        .and_then(|id| Uuid::parse_str(id.trim()).ok())
+}
+
+fn set_last_profile_id(profile_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
+    let profiles_root = get_profiles_root();
+    fs::create_dir_all(&profiles_root)?;
+    fs::write(
+        profiles_root.join(LAST_PROFILE_FILE),
+        profile_id.to_string(),
+    )?;
+    Ok(())
+}
+
+fn get_profiles_root() -> PathBuf {
+    data_dir(DataKind::Profiles)
+}
+
+pub fn get_profile_dir(profile_id: Uuid) -> PathBuf {
+    get_profiles_root().join(profile_id.to_string())
+}
+
+// ==================== PROFILE RESOURCES ====================
+
+#[derive(Resource, Default)]
+pub struct ProfileManager {
+    pub active: Option<PlayerProfile>,
+    pub available: Vec<ProfileSummary>,
+}
+
+impl ProfileManager {
+    pub fn active_username(&self) -> Option<&str> {
+        self.active.as_ref().map(|p| p.user.username.as_str())
+    }
+
+    pub fn refresh(&mut self) {
+        self.available = list_profiles();
+    }
+
+    pub fn save_active(&self) {
+        if let Some(profile) = &self.active {
+            if let Err(e) = profile.save() {
+                error!("Failed to save profile '{}': {}", profile.user.username, e);
+            }
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ProfilePickerState {
+    pub naming: bool,
+    pub adding_friend: bool,
+    pub signing_in: bool, // The buffer holds a password
+    pub name_buffer: String,
+    pub needs_redraw: bool,
+}
+
+fn activate_profile(manager: &mut ProfileManager, campaign: &mut Campaign, profile: PlayerProfile) {
+    let mut profile = profile;
+    let now = Utc::now();
+    profile.user.last_login = Some(now);
+    profile.user.updated_at = now;
+
+    // The difficulty is a player setting, whatever the progress was saved with
+    campaign.progress = profile.campaign_progress.clone();
+    campaign.progress.difficulty_level = profile.settings.difficulty_level;
+
+    if let Err(e) = profile.save() {
+        error!("Failed to save profile '{}': {}", profile.user.username, e);
+    }
+    if let Err(e) = set_last_profile_id(profile.user.id) {
+        warn!("Failed to remember last profile: {}", e);
+    }
+
+    info!("👤 Active profile: {}", profile.user.username);
+    manager.active = Some(profile);
+    manager.refresh();
+}
+
+// ==================== PROFILE SYSTEMS ====================
+
+// Without an active profile the loading screen hands off to the profile picker
+pub fn setup_profile_system(mut manager: ResMut<ProfileManager>, mut campaign: ResMut<Campaign>) {
+    manager.refresh();
+
+    if let Some(profile_id) = get_last_profile_id() {
+        match PlayerProfile::load(profile_id) {
+            Ok(profile) => activate_profile(&mut manager, &mut campaign, profile),
+            Err(e) => warn!("Failed to load last profile {}: {}", profile_id, e),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn profile_picker_system(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut manager: ResMut<ProfileManager>,
+    mut picker: ResMut<ProfilePickerState>,
+    mut campaign: ResMut<Campaign>,
+    mut social: ResMut<SocialState>,
+    mut auth_session: ResMut<AuthSession>,
+    mut input: ResMut<Input<KeyCode>>,
+    mut char_events: EventReader<ReceivedCharacter>,
+    menu_query: Query<Entity, With<ProfilePickerMenu>>,
+    mut shown_auth: Local<(bool, bool)>,
+) {
+    if game_state.game_phase != GamePhase::ProfileSelect {
+        for entity in menu_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        char_events.clear();
+        return;
+    }
+
+    if picker.naming {
+        for event in char_events.read() {
+            if !event.char.is_control() && picker.name_buffer.len() < 50 {
+                picker.name_buffer.push(event.char);
+                picker.needs_redraw = true;
+            }
+        }
+
+        if input.just_pressed(KeyCode::Back) {
+            picker.name_buffer.pop();
+            picker.needs_redraw = true;
+        } else if input.just_pressed(KeyCode::Escape) {
+            picker.naming = false;
+            picker.adding_friend = false;
+            picker.signing_in = false;
+            picker.name_buffer.clear();
+            picker.needs_redraw = true;
+        } else if input.just_pressed(KeyCode::Return) && picker.signing_in {
+            // Signs in as the active commander; the result comes over the radio
+            if let Some(username) = manager.active_username() {
+                auth_session.sign_in(username, &picker.name_buffer);
+                play_tactical_sound("radio", &tr("radio.signing_in"));
+            }
+            picker.naming = false;
+            picker.signing_in = false;
+            picker.name_buffer.clear();
+            picker.needs_redraw = true;
+        } else if input.just_pressed(KeyCode::Return) && picker.adding_friend {
+            // The lookup is async; the friend is stored once the server confirms the username
+            let username = picker.name_buffer.trim().to_string();
+            if !username.is_empty() {
+                social.request_add_friend(&auth_session, &username);
+                play_tactical_sound(
+                    "radio",
+                    &tr_args("radio.friend_lookup", &[("name", &username)]),
+                );
+            }
+            picker.naming = false;
+            picker.adding_friend = false;
+            picker.name_buffer.clear();
+            picker.needs_redraw = true;
+        } else if input.just_pressed(KeyCode::Return) {
+            match PlayerProfile::new_local(&picker.name_buffer) {
+                Ok(profile) => {
+                    play_tactical_sound(
+                        "radio",
+                        &tr_args(
+                            "radio.commander_registered",
+                            &[("name", &profile.user.username)],
+                        ),
+                    );
+                    end_other_session(&mut auth_session, &profile.user.username);
+                    activate_profile(&mut manager, &mut campaign, profile);
+                    picker.naming = false;
+                    picker.name_buffer.clear();
+                    game_state.game_phase = GamePhase::MainMenu;
+                }
+                Err(e) => {
+                    warn!("Profile creation failed: {}", e);
+                    play_tactical_sound("radio", &tr("radio.commander_name_invalid"));
+                }
+            }
+            picker.needs_redraw = true;
+        }
+    } else {
+        char_events.clear();
+
+        const SLOT_KEYS: [KeyCode; MAX_LISTED_PROFILES] = [
+            KeyCode::Key1,
+            KeyCode::Key2,
+            KeyCode::Key3,
+            KeyCode::Key4,
+            KeyCode::Key5,
+            KeyCode::Key6,
+            KeyCode::Key7,
+            KeyCode::Key8,
+            KeyCode::Key9,
+        ];
+
+        let selected = SLOT_KEYS
+            .iter()
+            .position(|key| input.just_pressed(*key))
+            .and_then(|index| manager.available.get(index).map(|p| (index, p.id)));
+
+        if let Some((index, profile_id)) = selected {
+            // Consume the key so the main menu doesn't treat it as a menu choice
+            input.clear_just_pressed(SLOT_KEYS[index]);
+
+            match PlayerProfile::load(profile_id) {
+                Ok(profile) => {
+                    play_tactical_sound(
+                        "radio",
+                        &tr_args(
+                            "radio.commander_welcome_back",
+                            &[("name", &profile.user.username)],
+                        ),
+                    );
+                    end_other_session(&mut auth_session, &profile.user.username);
+                    activate_profile(&mut manager, &mut campaign, profile);
+                    game_state.game_phase = GamePhase::MainMenu;
+                }
+                Err(e) => {
+                    error!("Failed to load profile: {}", e);
+                    manager.refresh();
+                    picker.needs_redraw = true;
+                }
+            }
+        } else if input.just_pressed(KeyCode::N) {
+            picker.naming = true;
+            picker.name_buffer.clear();
+            picker.needs_redraw = true;
+        } else if input.just_pressed(KeyCode::F) && manager.active.is_some() {
+            if auth_session.is_online() {
+                picker.naming = true;
+                picker.adding_friend = true;
+                picker.name_buffer.clear();
+                picker.needs_redraw = true;
+            } else {
+                play_tactical_sound("radio", &tr("radio.friend_needs_sign_in"));
+            }
+        } else if input.just_pressed(KeyCode::L) && manager.active.is_some() {
+            if auth_session.is_online() {
+                auth_session.sign_out();
+                play_tactical_sound("radio", &tr("radio.signed_out"));
+            } else if !auth_session.is_signing_in() {
+                picker.naming = true;
+                picker.signing_in = true;
+                picker.name_buffer.clear();
+            }
+            picker.needs_redraw = true;
+        } else if input.just_pressed(KeyCode::Escape) && manager.active.is_some() {
+            game_state.game_phase = GamePhase::MainMenu;
+        }
+    }
+
+    // Sign-in finishes in the background, so the status line redraws itself
+    let auth_status = (auth_session.is_online(), auth_session.is_signing_in());
+    if picker.needs_redraw || *shown_auth != auth_status || menu_query.is_empty() {
+        *shown_auth = auth_status;
+        for entity in menu_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        if game_state.game_phase == GamePhase::ProfileSelect {
+            create_profile_picker_ui(&mut commands, &manager, &picker, &social, &auth_session);
+        }
+        picker.needs_redraw = false;
+    }
+}
+
+// A session signed in for another commander doesn't carry over to this one
+fn end_other_session(auth_session: &mut AuthSession, username: &str) {
+    if auth_session
+        .user
+        .as_ref()
+        .is_some_and(|user| user.username != username)
+    {
+        auth_session.sign_out();
+    }
+}
+
+pub fn profile_progress_sync_system(campaign: Res<Campaign>, mut manager: ResMut<ProfileManager>) {
+    let Some(profile) = manager.active.as_mut() else {
+        return;
+    };
+
+    // Persist only when the campaign actually advanced, a plan was drawn or
+    // the difficulty changed (loading a save brings its own), not on every
+    // timer tick
+    let stored = &profile.campaign_progress;
+    if stored.total_score == campaign.progress.total_score
+        && stored.completed_missions.len() == campaign.progress.completed_missions.len()
+        && stored.battle_plans == campaign.progress.battle_plans
+        && profile.settings.difficulty_level == campaign.progress.difficulty_level
+    {
+        return;
+    }
+
+    profile.campaign_progress = campaign.progress.clone();
+    profile.settings.difficulty_level = campaign.progress.difficulty_level;
+    profile.user.updated_at = Utc::now();
+    manager.save_active();
+    info!("👤 Campaign progress saved to profile");
+}
+
+// ==================== PROFILE PICKER UI ====================
+
+fn create_profile_picker_ui(
+    commands: &mut Commands,
+    manager: &ProfileManager,
+    picker: &ProfilePickerState,
+    social: &SocialState,
+    auth_session: &AuthSession,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.95)),
+                ..default()
+            },
+            ProfilePickerMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    "👤 SELECT COMMANDER",
+                    TextStyle {
+                        font_size: 48.0,
+                        color: Color::rgb(1.0, 0.8, 0.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(40.0)),
+                    ..default()
+                }),
+            );
+
+            if manager.available.is_empty() {
+                parent.spawn(TextBundle::from_section(
+                    "No profiles found",
+                    TextStyle {
+                        font_size: 24.0,
+                        color: Color::rgb(0.5, 0.5, 0.5),
+                        ..default()
+                    },
+                ));
+            }
+
+            for (i, profile) in manager.available.iter().enumerate() {
+                let is_active = manager
+                    .active
+                    .as_ref()
+                    .is_some_and(|active| active.user.id == profile.id);
+
+                let last_played = profile
+                    .last_played
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "Never".to_string());
+
+                parent.spawn(
+                    TextBundle::from_section(
+                        format!(
+                            "{}. {} | Missions: {} | ELO: {} | Last played: {}",
+                            i + 1,
+                            profile.username,
+                            profile.completed_missions,
+                            profile.elo_rating,
+                            last_played
+                        ),
+                        TextStyle {
+                            font_size: 24.0,
+                            color: if is_active {
+                                Color::rgb(0.0, 1.0, 0.0)
+                            } else {
+                                Color::WHITE
+                            },
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    }),
+                );
+            }
+
+            if let Some(active) = manager.active.as_ref().filter(|p| !p.friends.is_empty()) {
+                let friends = active
+                    .friends
+                    .iter()
+                    .map(|friend| {
+                        let status = if social.is_online(friend.user_id) {
+                            "online"
+                        } else {
+                            "offline"
+                        };
+                        format!("{} ({})", friend.username, status)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                parent.spawn(
+                    TextBundle::from_section(
+                        format!("👥 Friends: {}", friends),
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb(0.6, 0.9, 0.6),
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    }),
+                );
+            }
+
+            if let Some(active) = manager.active.as_ref() {
+                let status = match &auth_session.user {
+                    Some(user) if auth_session.is_online() => {
+                        tr_args("profile_menu.signed_in", &[("name", &user.username)])
+                    }
+                    _ if auth_session.is_signing_in() => tr("profile_menu.signing_in"),
+                    _ => tr_args("profile_menu.offline", &[("name", &active.user.username)]),
+                };
+
+                parent.spawn(
+                    TextBundle::from_section(
+                        status,
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb(0.6, 0.8, 1.0),
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    }),
+                );
+            }
+
+            let prompt = if picker.signing_in {
+                // Only the length of the password is shown
+                let password = "*".repeat(picker.name_buffer.chars().count());
+                tr_args("profile_menu.password", &[("password", &password)])
+            } else if picker.adding_friend {
+                format!("Friend's username: {}_", picker.name_buffer)
+            } else if picker.naming {
+                format!("New commander name: {}_", picker.name_buffer)
+            } else {
+                "N. Create New Profile".to_string()
+            };
+
+            parent.spawn(
+                TextBundle::from_section(
+                    prompt,
+                    TextStyle {
+                        font_size: 28.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+
+            let instructions = tr(if picker.signing_in {
+                "profile_menu.hint_password"
+            } else if picker.naming {
+                "profile_menu.hint_naming"
+            } else if manager.active.is_some() {
+                "profile_menu.hint"
+            } else {
+                "profile_menu.hint_no_profile"
+            });
+
+            parent.spawn(
+                TextBundle::from_section(
+                    instructions,
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(40.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elo_even_match() {
+        assert_eq!(calculate_elo(1200, 1200, true), 1216);
+        assert_eq!(calculate_elo(1200, 1200, false), 1184);
+    }
+
+    #[test]
+    fn test_elo_upset_gains_more() {
+        let upset_gain = calculate_elo(1000, 1400, true) - 1000;
+        let expected_gain = calculate_elo(1400, 1000, true) - 1400;
+        assert!(upset_gain > expected_gain);
+    }
+
+    #[test]
+    fn test_record_match_updates_totals() {
+        let mut record = MultiplayerRecord::default();
+        let stats = PlayerStats {
+            units_controlled: 12,
+            units_lost: 4,
+            damage_dealt: 350.0,
+            objectives_completed: 2,
+            political_influence_used: 0.0,
+        };
+
+        record.record_match(true, DEFAULT_ELO, &stats);
+        record.record_match(false, DEFAULT_ELO, &stats);
+
+        assert_eq!(record.matches_played, 2);
+        assert_eq!(record.wins, 1);
+        assert_eq!(record.losses, 1);
+        assert_eq!(record.total_units_lost, 8);
+        assert_eq!(record.total_objectives_completed, 4);
+        assert!((record.win_rate() - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_local_profile_name_validation() {
+        assert!(PlayerProfile::new_local("ab").is_err());
+        assert!(PlayerProfile::new_local("   ").is_err());
+
+        let profile = PlayerProfile::new_local("  El Mayo  ").unwrap();
+        assert_eq!(profile.user.username, "El Mayo");
+        assert!(profile.user.password_hash.is_empty());
+        assert_eq!(profile.multiplayer.elo_rating, DEFAULT_ELO);
+    }
+
+    #[test]
+    fn test_friends_list_dedup() {
+        let mut profile = PlayerProfile::new_local("Chapitos").unwrap();
+        let friend_id = Uuid::new_v4();
+
+        assert!(profile.add_friend(friend_id, "Mayito"));
+        assert!(!profile.add_friend(friend_id, "Mayito"));
+        assert!(!profile.add_friend(profile.user.id, "Self"));
+        assert_eq!(profile.friends.len(), 1);
+
+        assert!(profile.remove_friend(friend_id));
+        assert!(!profile.is_friend(friend_id));
+    }
+
+    #[test]
+    fn test_one_daily_attempt_per_day() {
+        let mut profile = PlayerProfile::new_local("Chapitos").unwrap();
+        let today = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+
+        assert!(profile.start_daily(today));
+        assert!(!profile.start_daily(today));
+        profile.record_daily_result(today, 1800, Some(Medal::Gold));
+        assert_eq!(profile.daily_attempt(today).unwrap().score, Some(1800));
+
+        // Only the most recent days are kept
+        for day in 1..=DAILY_HISTORY_DAYS as u64 {
+            assert!(profile.start_daily(today + chrono::Days::new(day)));
+        }
+        assert_eq!(profile.daily_attempts.len(), DAILY_HISTORY_DAYS);
+        assert!(profile.daily_attempt(today).is_none());
+    }
+
+    #[test]
+    fn test_custom_formation_slots_replace() {
+        let mut profile = PlayerProfile::new_local("Chapitos").unwrap();
+        assert!(profile.save_custom_formation(0, vec![[0.0, 0.0]]).is_none());
+        assert!(profile.save_custom_formation(2, Vec::new()).is_none());
+
+        profile.save_custom_formation(3, vec![[0.0, 10.0], [0.0, -10.0]]);
+        profile.save_custom_formation(1, vec![[5.0, 0.0]]);
+        let saved = profile.save_custom_formation(3, vec![[-20.0, 0.0], [20.0, 0.0]]);
+        assert_eq!(saved.unwrap().name, "Formation 3");
+
+        let slots: Vec<usize> = profile.custom_formations.iter().map(|f| f.slot).collect();
+        assert_eq!(slots, vec![1, 3]);
+        assert_eq!(
+            profile.custom_formation(3).unwrap().formation_type(),
+            FormationType::Custom {
+                name: "Formation 3".to_string(),
+                offsets: vec![[-20.0, 0.0], [20.0, 0.0]],
+            }
+        );
+    }
+}
@@ -0,0 +1,498 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::localization::{tr, tr_args};
+use crate::systems::{combat_system, movement_system};
+use crate::utils::{play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+// ==================== OVIDIO PROTECTION PLUGIN ====================
+
+// Ovidio used to be just a unit that must not die. Now he can shelter inside a
+// safehouse, where the walls soak most of the fire aimed at him; selected
+// units can be made his protection detail (G), which keeps a screen between
+// him and every threat it can see; and when a safehouse is about to fall he
+// can make a panic run to another one (P).
+
+const THREAT_AWARENESS_RADIUS: f32 = 400.0; // Threats farther out don't shape the screen
+const SCREEN_RADIUS: f32 = 45.0; // How far out the detail stands
+const SCREEN_SPREAD: f32 = 0.45; // Radians between guards covering the same threat
+const SCREEN_SLACK: f32 = 8.0; // Guards closer than this to their spot stay put
+const SAFEHOUSE_RISK_RADIUS: f32 = 200.0;
+const SAFEHOUSE_RISK_THREATS: usize = 3; // Threats this close before the radio warns
+const RELOCATION_COOLDOWN: f32 = 45.0;
+const SHELTERED_ALPHA: f32 = 0.45; // He's indoors - drawn faded
+
+// Type aliases to reduce complexity
+type PrincipalSpriteQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ProtectedPrincipal,
+        Option<&'static mut Sprite>,
+        Option<&'static mut TextureAtlasSprite>,
+    ),
+    Changed<ProtectedPrincipal>,
+>;
+type UnguardedThreatQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Unit, &'static Transform),
+    (Without<Bodyguard>, Without<ProtectedPrincipal>),
+>;
+type SelectedGuardQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Unit, Has<Bodyguard>),
+    (With<Selected>, Without<ProtectedPrincipal>),
+>;
+
+pub struct OvidioProtectionPlugin;
+
+impl Plugin for OvidioProtectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProtectionDetail>()
+            .add_systems(
+                FixedUpdate,
+                (safehouse_shelter_system, bodyguard_screen_system)
+                    .chain()
+                    .before(movement_system)
+                    .before(combat_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, shelter_visual_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Resource)]
+pub struct ProtectionDetail {
+    pub relocation_cooldown: Timer,
+    compromised_warning: Option<Entity>, // Safehouse the radio already warned about
+}
+
+impl Default for ProtectionDetail {
+    fn default() -> Self {
+        // The first relocation is available straight away
+        let mut relocation_cooldown = Timer::from_seconds(RELOCATION_COOLDOWN, TimerMode::Once);
+        relocation_cooldown.tick(relocation_cooldown.duration());
+        Self {
+            relocation_cooldown,
+            compromised_warning: None,
+        }
+    }
+}
+
+// ==================== SAFEHOUSE INTERIOR ====================
+
+pub fn safehouse_shelter_system(
+    mut principal_query: Query<
+        (&Unit, &Transform, &mut Movement, &mut ProtectedPrincipal),
+        Without<Captive>,
+    >,
+    safehouse_query: Query<(Entity, &Transform, &SafehouseInterior), Without<Unit>>,
+    threat_query: Query<(&Unit, &Transform), Without<ProtectedPrincipal>>,
+    mut detail: ResMut<ProtectionDetail>,
+) {
+    let threats = hostile_positions(&threat_query);
+
+    for (unit, transform, mut movement, mut principal) in principal_query.iter_mut() {
+        if unit.health <= 0.0 {
+            continue;
+        }
+        let position = transform.translation;
+
+        // On the run: head for the destination and move in on arrival
+        if let Some(destination) = principal.relocating_to {
+            match safehouse_query.get(destination) {
+                Ok((_, safehouse_transform, interior)) => {
+                    let entrance = safehouse_transform.translation;
+                    if position.distance(entrance) <= interior.radius * 0.5 {
+                        principal.relocating_to = None;
+                        principal.shelter = Some(destination);
+                        principal.shelter_cover = interior.cover;
+                        movement.target_position = None;
+                        play_tactical_sound(
+                            "radio",
+                            &tr_args("radio.ovidio_sheltered", &[("safehouse", &interior.name)]),
+                        );
+                    } else {
+                        movement.target_position = Some(entrance);
+                    }
+                }
+                Err(_) => principal.relocating_to = None,
+            }
+            continue;
+        }
+
+        // Walking out (or the building going away) ends the shelter
+        if let Some(shelter) = principal.shelter {
+            let Ok((_, safehouse_transform, interior)) = safehouse_query.get(shelter) else {
+                principal.shelter = None;
+                principal.shelter_cover = 0.0;
+                continue;
+            };
+            let entrance = safehouse_transform.translation;
+            if position.distance(entrance) > interior.radius {
+                principal.shelter = None;
+                principal.shelter_cover = 0.0;
+                continue;
+            }
+
+            let nearby_threats = threats
+                .iter()
+                .filter(|&&threat| threat.distance(entrance) <= SAFEHOUSE_RISK_RADIUS)
+                .count();
+            if nearby_threats >= SAFEHOUSE_RISK_THREATS {
+                if detail.compromised_warning != Some(shelter) {
+                    detail.compromised_warning = Some(shelter);
+                    play_tactical_sound(
+                        "radio",
+                        &tr_args(
+                            "radio.safehouse_surrounded",
+                            &[("safehouse", &interior.name)],
+                        ),
+                    );
+                }
+            } else if nearby_threats == 0 {
+                detail.compromised_warning = None;
+            }
+            continue;
+        }
+
+        // Walking into a safehouse takes him indoors
+        if let Some((entity, _, interior)) = safehouse_query
+            .iter()
+            .find(|(_, t, interior)| position.distance(t.translation) <= interior.radius)
+        {
+            principal.shelter = Some(entity);
+            principal.shelter_cover = interior.cover;
+            movement.target_position = None;
+        }
+    }
+}
+
+pub fn shelter_visual_system(mut principal_query: PrincipalSpriteQuery) {
+    for (principal, sprite, atlas_sprite) in principal_query.iter_mut() {
+        let alpha = if principal.shelter.is_some() {
+            SHELTERED_ALPHA
+        } else {
+            1.0
+        };
+        if let Some(mut sprite) = sprite {
+            sprite.color.set_a(alpha);
+        }
+        if let Some(mut atlas_sprite) = atlas_sprite {
+            atlas_sprite.color.set_a(alpha);
+        }
+    }
+}
+
+// ==================== PROTECTION DETAIL ====================
+
+pub fn bodyguard_screen_system(
+    mut commands: Commands,
+    principal_query: Query<(Entity, &Unit, &Transform), With<ProtectedPrincipal>>,
+    mut guard_query: Query<
+        (Entity, &Unit, &Transform, &mut Movement, &Bodyguard),
+        Without<ProtectedPrincipal>,
+    >,
+    threat_query: UnguardedThreatQuery,
+) {
+    // Group the detail by who it's guarding; a dead principal stands it down
+    let mut details: HashMap<Entity, Vec<Entity>> = HashMap::new();
+    for (entity, unit, _, _, bodyguard) in guard_query.iter() {
+        if unit.health <= 0.0 {
+            continue;
+        }
+        match principal_query.get(bodyguard.principal) {
+            Ok((_, principal_unit, _)) if principal_unit.health > 0.0 => {
+                details.entry(bodyguard.principal).or_default().push(entity);
+            }
+            _ => {
+                commands.entity(entity).remove::<Bodyguard>();
+            }
+        }
+    }
+
+    for (principal_entity, mut guards) in details {
+        let Ok((_, _, principal_transform)) = principal_query.get(principal_entity) else {
+            continue;
+        };
+        let principal_pos = principal_transform.translation;
+
+        // Known threats are the ones the principal or a guard can see
+        let observers: Vec<(Vec3, f32)> = std::iter::once(principal_entity)
+            .chain(guards.iter().copied())
+            .filter_map(|entity| {
+                principal_query
+                    .get(entity)
+                    .map(|(_, unit, transform)| (transform.translation, unit.vision))
+                    .or_else(|_| {
+                        guard_query
+                            .get(entity)
+                            .map(|(_, unit, transform, _, _)| (transform.translation, unit.vision))
+                    })
+                    .ok()
+            })
+            .collect();
+        let threats: Vec<Vec3> = threat_query
+            .iter()
+            .filter(|(unit, _)| unit.faction == Faction::Military && unit.health > 0.0)
+            .map(|(_, transform)| transform.translation)
+            .filter(|&threat| {
+                threat.distance(principal_pos) <= THREAT_AWARENESS_RADIUS
+                    && observers
+                        .iter()
+                        .any(|&(eye, vision)| eye.distance(threat) <= vision)
+            })
+            .collect();
+
+        // Stable slot order so guards don't swap places every tick
+        guards.sort();
+        let slots = screen_positions(principal_pos, &threats, guards.len());
+        for (guard, slot) in guards.into_iter().zip(slots) {
+            if let Ok((_, _, transform, mut movement, _)) = guard_query.get_mut(guard) {
+                movement.target_position =
+                    (transform.translation.distance(slot) > SCREEN_SLACK).then_some(slot);
+            }
+        }
+    }
+}
+
+/// Where each of `count` guards should stand. Guards take the nearest threats
+/// in turn, standing on the line from the principal towards them; extra guards
+/// on the same threat fan out to either side. With no threats the detail
+/// rings him.
+pub fn screen_positions(principal: Vec3, threats: &[Vec3], count: usize) -> Vec<Vec3> {
+    let at_bearing =
+        |angle: f32| principal + Vec3::new(angle.cos(), angle.sin(), 0.0) * SCREEN_RADIUS;
+
+    if threats.is_empty() {
+        return (0..count)
+            .map(|i| at_bearing(std::f32::consts::TAU * i as f32 / count as f32))
+            .collect();
+    }
+
+    let mut bearings: Vec<(f32, f32)> = threats
+        .iter()
+        .map(|&threat| {
+            let offset = threat - principal;
+            (offset.length(), offset.y.atan2(offset.x))
+        })
+        .collect();
+    bearings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    (0..count)
+        .map(|i| {
+            let (_, bearing) = bearings[i % bearings.len()];
+            // 0, +1, -1, +2, -2 ... spreads around the same threat
+            let rank = i / bearings.len();
+            let side = if rank % 2 == 1 { 1.0 } else { -1.0 };
+            let fan = rank.div_ceil(2) as f32 * SCREEN_SPREAD * side;
+            at_bearing(bearing + fan)
+        })
+        .collect()
+}
+
+pub fn bodyguard_assignment_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    selected_query: SelectedGuardQuery,
+    principal_query: Query<(Entity, &Unit), With<ProtectedPrincipal>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::G) {
+        return;
+    }
+
+    let Some((principal, _)) = principal_query.iter().find(|(_, unit)| unit.health > 0.0) else {
+        return;
+    };
+    let candidates: Vec<(Entity, bool)> = selected_query
+        .iter()
+        .filter(|(_, unit, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(entity, _, is_bodyguard)| (entity, is_bodyguard))
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+
+    // G on a selection that's already all detail stands it down
+    if candidates.iter().all(|&(_, is_bodyguard)| is_bodyguard) {
+        for &(entity, _) in &candidates {
+            commands.entity(entity).remove::<Bodyguard>();
+        }
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.detail_released", &[("count", &candidates.len())]),
+        );
+        return;
+    }
+
+    for &(entity, _) in &candidates {
+        commands
+            .entity(entity)
+            .remove::<HoldArea>()
+            .insert(Bodyguard { principal });
+    }
+    play_tactical_sound(
+        "radio",
+        &tr_args("radio.detail_assigned", &[("count", &candidates.len())]),
+    );
+}
+
+// ==================== PANIC RELOCATION ====================
+
+pub fn panic_relocation_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut detail: ResMut<ProtectionDetail>,
+    mut principal_query: Query<
+        (&Unit, &Transform, &mut Movement, &mut ProtectedPrincipal),
+        Without<Captive>,
+    >,
+    safehouse_query: Query<(Entity, &Transform, &SafehouseInterior), Without<Unit>>,
+    threat_query: Query<(&Unit, &Transform), Without<ProtectedPrincipal>>,
+) {
+    detail.relocation_cooldown.tick(time.delta());
+    if !keyboard_input.just_pressed(KeyCode::P) {
+        return;
+    }
+
+    let Some((_, transform, mut movement, mut principal)) = principal_query
+        .iter_mut()
+        .find(|(unit, ..)| unit.health > 0.0)
+    else {
+        return;
+    };
+
+    if !detail.relocation_cooldown.finished() {
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.relocation_cooldown",
+                &[(
+                    "seconds",
+                    &format!("{:.0}", detail.relocation_cooldown.remaining_secs()),
+                )],
+            ),
+        );
+        return;
+    }
+
+    let safehouses: Vec<(Entity, Vec3)> = safehouse_query
+        .iter()
+        .map(|(entity, t, _)| (entity, t.translation))
+        .collect();
+    let current = principal.shelter.or(principal.relocating_to);
+    let threats = hostile_positions(&threat_query);
+    let Some(destination) = safest_safehouse(transform.translation, current, &safehouses, &threats)
+    else {
+        play_tactical_sound("radio", &tr("radio.relocation_no_safehouse"));
+        return;
+    };
+    let Ok((_, safehouse_transform, interior)) = safehouse_query.get(destination) else {
+        return;
+    };
+
+    principal.shelter = None;
+    principal.shelter_cover = 0.0;
+    principal.relocating_to = Some(destination);
+    movement.target_position = Some(safehouse_transform.translation);
+    detail.relocation_cooldown.reset();
+    detail.compromised_warning = None;
+    play_tactical_sound(
+        "radio",
+        &tr_args("radio.relocating", &[("safehouse", &interior.name)]),
+    );
+}
+
+/// The safehouse other than `current` with the fewest threats around it,
+/// nearest first on a tie.
+pub fn safest_safehouse(
+    from: Vec3,
+    current: Option<Entity>,
+    safehouses: &[(Entity, Vec3)],
+    threats: &[Vec3],
+) -> Option<Entity> {
+    safehouses
+        .iter()
+        .filter(|(entity, _)| Some(*entity) != current)
+        .min_by(|a, b| {
+            let risk = |position: Vec3| {
+                threats
+                    .iter()
+                    .filter(|&&threat| threat.distance(position) <= SAFEHOUSE_RISK_RADIUS)
+                    .count()
+            };
+            risk(a.1)
+                .cmp(&risk(b.1))
+                .then(from.distance(a.1).total_cmp(&from.distance(b.1)))
+        })
+        .map(|(entity, _)| *entity)
+}
+
+fn hostile_positions(
+    threat_query: &Query<(&Unit, &Transform), Without<ProtectedPrincipal>>,
+) -> Vec<Vec3> {
+    threat_query
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Military && unit.health > 0.0)
+        .map(|(_, transform)| transform.translation)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_stands_between_principal_and_threats() {
+        let principal = Vec3::ZERO;
+        let threats = [Vec3::new(200.0, 0.0, 0.0), Vec3::new(0.0, -300.0, 0.0)];
+
+        let slots = screen_positions(principal, &threats, 3);
+        assert_eq!(slots.len(), 3);
+        // Nearest threat first, then the next, then a second guard fanned
+        // out beside the nearest
+        assert!(slots[0].x > SCREEN_RADIUS - 0.1 && slots[0].y.abs() < 0.1);
+        assert!(slots[1].y < -(SCREEN_RADIUS - 0.1) && slots[1].x.abs() < 0.1);
+        assert!(slots[2].x > 0.0 && slots[2].y.abs() > 1.0);
+        for slot in &slots {
+            assert!((slot.distance(principal) - SCREEN_RADIUS).abs() < 0.1);
+        }
+
+        // No threats in sight: the detail rings him
+        let ring = screen_positions(principal, &[], 4);
+        assert!((ring[0] + ring[2]).length() < 0.1);
+        assert!((ring[1] + ring[3]).length() < 0.1);
+    }
+
+    #[test]
+    fn test_relocation_picks_the_quietest_other_safehouse() {
+        let mut world = World::new();
+        let current = world.spawn_empty().id();
+        let besieged = world.spawn_empty().id();
+        let quiet = world.spawn_empty().id();
+        let safehouses = [
+            (current, Vec3::ZERO),
+            (besieged, Vec3::new(100.0, 0.0, 0.0)),
+            (quiet, Vec3::new(600.0, 0.0, 0.0)),
+        ];
+        let threats = [Vec3::new(120.0, 10.0, 0.0)];
+
+        assert_eq!(
+            safest_safehouse(Vec3::ZERO, Some(current), &safehouses, &threats),
+            Some(quiet)
+        );
+        // With nothing around, the nearest one wins
+        assert_eq!(
+            safest_safehouse(Vec3::ZERO, Some(current), &safehouses, &[]),
+            Some(besieged)
+        );
+        assert_eq!(
+            safest_safehouse(Vec3::ZERO, Some(current), &safehouses[..1], &[]),
+            None
+        );
+    }
+}
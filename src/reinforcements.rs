@@ -0,0 +1,436 @@
+use crate::app_state::GameSet;
+use crate::commander::CommanderPowers;
+use crate::components::*;
+use crate::config::UnitCatalog;
+use crate::economy::{charge, CartelTreasury};
+use crate::intel_recruitment::IntelRecruitment;
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::prisoners::PrisonerLedger;
+use crate::resources::{GameAssets, GameState};
+use crate::scoring::{challenge_active, ChallengeModifier, ChallengeSettings};
+use crate::spawners::spawn_unit;
+use crate::ui::UiTheme;
+use crate::upgrades::UpgradeWorkshop;
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, iso_to_world, play_tactical_sound,
+    update_dashboard_panel, SimulationSet,
+};
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+// ==================== REINFORCEMENT CALL-IN PLUGIN ====================
+
+// The army gets waves; now the cartel can call its own people in. R opens the
+// call-in menu, each package is paid for out of the treasury at a premium over
+// safehouse recruiting, and it rolls in after a delay at whichever cartel entry
+// point on the map edge has the fewest soldiers near it. Every package has its
+// own cooldown, so cash isn't the only limit.
+
+const CALL_IN_PREMIUM: f32 = 1.2; // Paying for speed over recruiting at a safehouse
+const THREAT_RADIUS: f32 = 250.0; // Soldiers this close make an entry point hot
+const ARRIVAL_SPACING: f32 = 30.0;
+const EDGE_ENTRIES: [Vec3; 4] = [
+    Vec3::new(-450.0, 0.0, 0.0),
+    Vec3::new(0.0, 350.0, 0.0),
+    Vec3::new(0.0, -350.0, 0.0),
+    Vec3::new(450.0, 0.0, 0.0),
+];
+
+pub struct CallInPackage {
+    pub key: KeyCode,
+    pub name: &'static str, // Localization key under hud.
+    pub units: &'static [UnitType],
+    pub arrival: f32,  // Seconds from the call to arrival
+    pub cooldown: f32, // Seconds before it can be called again
+}
+
+pub const CALL_IN_PACKAGES: [CallInPackage; 3] = [
+    CallInPackage {
+        key: KeyCode::J,
+        name: "call_in_sicario_squad",
+        units: &[
+            UnitType::Enforcer,
+            UnitType::Sicario,
+            UnitType::Sicario,
+            UnitType::Sicario,
+        ],
+        arrival: 20.0,
+        cooldown: 45.0,
+    },
+    CallInPackage {
+        key: KeyCode::K,
+        name: "call_in_technical",
+        units: &[UnitType::HeavyGunner, UnitType::Sicario],
+        arrival: 10.0,
+        cooldown: 60.0,
+    },
+    CallInPackage {
+        key: KeyCode::L,
+        name: "call_in_blockade_crew",
+        units: &[UnitType::Enforcer, UnitType::Roadblock, UnitType::Roadblock],
+        arrival: 15.0,
+        cooldown: 50.0,
+    },
+];
+
+pub struct ReinforcementCallPlugin;
+
+impl Plugin for ReinforcementCallPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReinforcementDesk>()
+            .add_systems(
+                FixedUpdate,
+                reinforcement_arrival_system
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session)
+                    .run_if(resource_exists::<GameAssets>()),
+            )
+            .add_systems(Update, reinforcement_panel_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ReinforcementDesk {
+    pub menu_open: bool,
+    pub cooldowns: [f32; CALL_IN_PACKAGES.len()], // Seconds until each can be called again
+    pub inbound: Vec<InboundCall>,
+}
+
+pub struct InboundCall {
+    pub package: usize, // Index into CALL_IN_PACKAGES
+    pub entry: Vec3,
+    pub eta: f32,
+}
+
+#[derive(Component)]
+pub struct ReinforcementUIPanel;
+
+/// What a package costs: its units at catalog price, plus the call-in premium.
+pub fn package_cost(package: &CallInPackage, catalog: &UnitCatalog) -> u32 {
+    let units: u32 = package
+        .units
+        .iter()
+        .map(|unit_type| catalog.definition(unit_type).cost)
+        .sum();
+    (units as f32 * CALL_IN_PREMIUM).round() as u32
+}
+
+/// The entry point with the fewest soldiers within THREAT_RADIUS, first listed
+/// on a tie.
+pub fn safest_entry(entries: &[Vec3], military: &[Vec3]) -> Option<Vec3> {
+    entries.iter().copied().min_by_key(|entry| {
+        military
+            .iter()
+            .filter(|soldier| soldier.distance(*entry) <= THREAT_RADIUS)
+            .count()
+    })
+}
+
+// ==================== CALL-IN MENU ====================
+
+#[allow(clippy::too_many_arguments)]
+pub fn reinforcement_call_system(
+    input: Res<Input<KeyCode>>,
+    catalog: Res<UnitCatalog>,
+    city_map: Res<CityMap>,
+    mut desk: ResMut<ReinforcementDesk>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    workshop: Option<ResMut<UpgradeWorkshop>>,
+    powers: Option<ResMut<CommanderPowers>>,
+    network: Option<ResMut<IntelRecruitment>>,
+    mut game_state: ResMut<GameState>,
+    challenges: Option<Res<ChallengeSettings>>,
+    unit_query: Query<(&Unit, &Transform)>,
+) {
+    if input.just_pressed(KeyCode::R) {
+        desk.menu_open = !desk.menu_open;
+        // The recruitment, upgrade, commander and intel network menus share the J/K/L keys
+        if desk.menu_open {
+            if let Some(treasury) = treasury.as_deref_mut() {
+                treasury.panel_safehouse = None;
+            }
+            if let Some(mut workshop) = workshop {
+                workshop.panel_open = false;
+            }
+            if let Some(mut powers) = powers {
+                powers.menu_open = false;
+            }
+            if let Some(mut network) = network {
+                network.menu_open = false;
+            }
+        }
+    }
+    if !desk.menu_open {
+        return;
+    }
+
+    for (index, package) in CALL_IN_PACKAGES.iter().enumerate() {
+        if !input.just_pressed(package.key) {
+            continue;
+        }
+        if challenge_active(challenges.as_deref(), ChallengeModifier::NoReinforcements) {
+            play_tactical_sound("radio", &tr("radio.challenge_no_reinforcements"));
+            continue;
+        }
+        let name = tr(&format!("hud.{}", package.name));
+        if desk.cooldowns[index] > 0.0 {
+            play_tactical_sound(
+                "radio",
+                &tr_args(
+                    "radio.call_in_cooldown",
+                    &[
+                        ("name", &name),
+                        ("seconds", &(desk.cooldowns[index].ceil() as u32)),
+                    ],
+                ),
+            );
+            continue;
+        }
+        if !charge(
+            treasury.as_deref_mut(),
+            package_cost(package, &catalog) as f32,
+        ) {
+            continue;
+        }
+
+        let mut entries: Vec<Vec3> = city_map
+            .spawn_positions(&Faction::Cartel)
+            .into_iter()
+            .map(|position| position.extend(0.0))
+            .collect();
+        if entries.is_empty() {
+            entries = EDGE_ENTRIES.to_vec();
+        }
+        let military: Vec<Vec3> = unit_query
+            .iter()
+            .filter(|(unit, _)| unit.faction == Faction::Military && unit.health > 0.0)
+            .map(|(_, transform)| transform.translation)
+            .collect();
+        let Some(entry) = safest_entry(&entries, &military) else {
+            continue;
+        };
+
+        desk.cooldowns[index] = package.cooldown;
+        desk.inbound.push(InboundCall {
+            package: index,
+            entry,
+            eta: package.arrival,
+        });
+        game_state.cartel_score += 15;
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.call_in_placed",
+                &[("name", &name), ("seconds", &(package.arrival as u32))],
+            ),
+        );
+    }
+}
+
+// ==================== ARRIVAL ====================
+
+pub fn reinforcement_arrival_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    prisoners: Option<Res<PrisonerLedger>>,
+    mut desk: ResMut<ReinforcementDesk>,
+) {
+    let dt = time.delta_seconds();
+    if desk.inbound.is_empty() && desk.cooldowns.iter().all(|cooldown| *cooldown <= 0.0) {
+        return;
+    }
+    for cooldown in desk.cooldowns.iter_mut() {
+        *cooldown = (*cooldown - dt).max(0.0);
+    }
+    for call in desk.inbound.iter_mut() {
+        call.eta -= dt;
+    }
+
+    let (arrived, inbound): (Vec<_>, Vec<_>) =
+        desk.inbound.drain(..).partition(|call| call.eta <= 0.0);
+    desk.inbound = inbound;
+
+    for call in arrived {
+        let package = &CALL_IN_PACKAGES[call.package];
+
+        // Every sicario in army hands is one fewer answering the call
+        let sicarios = package
+            .units
+            .iter()
+            .filter(|unit_type| **unit_type == UnitType::Sicario)
+            .count();
+        let mut withheld = prisoners
+            .as_ref()
+            .map_or(0, |ledger| ledger.withheld_reinforcements(sicarios));
+
+        for (i, unit_type) in package.units.iter().enumerate() {
+            if *unit_type == UnitType::Sicario && withheld > 0 {
+                withheld -= 1;
+                continue;
+            }
+            let angle = i as f32 / package.units.len() as f32 * std::f32::consts::TAU;
+            let position = call.entry + Vec3::new(angle.cos(), angle.sin(), 0.0) * ARRIVAL_SPACING;
+            spawn_unit(
+                &mut commands,
+                unit_type.clone(),
+                Faction::Cartel,
+                iso_to_world(position),
+                &game_assets,
+                &catalog,
+            );
+            spawn_arrival_particles(&mut commands, position);
+        }
+
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.call_in_arrived",
+                &[("name", &tr(&format!("hud.{}", package.name)))],
+            ),
+        );
+    }
+}
+
+fn spawn_arrival_particles(commands: &mut Commands, position: Vec3) {
+    for _ in 0..8 {
+        let velocity = Vec3::new(
+            thread_rng().gen_range(-120.0..120.0),
+            thread_rng().gen_range(-120.0..120.0),
+            0.0,
+        );
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::GREEN,
+                    custom_size: Some(Vec2::new(4.0, 4.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position + Vec3::new(0.0, 0.0, 0.5)),
+                ..default()
+            },
+            ParticleEffect {
+                lifetime: Timer::from_seconds(2.0, TimerMode::Once),
+                velocity,
+            },
+        ));
+    }
+}
+
+// ==================== CALL-IN PANEL ====================
+
+pub fn reinforcement_panel_system(
+    mut commands: Commands,
+    desk: Res<ReinforcementDesk>,
+    catalog: Res<UnitCatalog>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<ReinforcementUIPanel>>,
+    theme: Res<UiTheme>,
+) {
+    let panel_exists = !panel_query.is_empty();
+    if panel_exists && !desk.is_changed() && !theme.is_changed() {
+        return;
+    }
+
+    // Only shown while the menu is open or a call is on its way
+    let visibility = if desk.menu_open || !desk.inbound.is_empty() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    let mut lines = vec![dashboard_line(tr("hud.call_in_title"), 16.0, Color::GOLD)];
+    if desk.menu_open {
+        for (index, package) in CALL_IN_PACKAGES.iter().enumerate() {
+            let cooldown = desk.cooldowns[index];
+            let mut line = format!(
+                "[{:?}] {} - ${} ({:.0}s)",
+                package.key,
+                tr(&format!("hud.{}", package.name)),
+                package_cost(package, &catalog),
+                package.arrival
+            );
+            if cooldown > 0.0 {
+                line.push_str(&format!(" ⏱ {:.0}s", cooldown.ceil()));
+            }
+            lines.push(dashboard_line(
+                line,
+                11.0,
+                if cooldown > 0.0 {
+                    Color::GRAY
+                } else {
+                    Color::WHITE
+                },
+            ));
+        }
+    }
+    for call in &desk.inbound {
+        lines.push(dashboard_line(
+            tr_args(
+                "hud.call_in_inbound",
+                &[
+                    (
+                        "name",
+                        &tr(&format!("hud.{}", CALL_IN_PACKAGES[call.package].name)),
+                    ),
+                    ("seconds", &(call.eta.max(0.0).ceil() as u32)),
+                ],
+            ),
+            10.0,
+            Color::CYAN,
+        ));
+    }
+
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else if !panel_exists {
+        let mut panel = create_dashboard_panel(
+            Style {
+                right: Val::Px(10.0),
+                bottom: Val::Px(220.0),
+                width: Val::Px(280.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.85),
+            lines,
+            &theme,
+        );
+        panel.0.visibility = visibility;
+        commands.spawn((panel, ReinforcementUIPanel));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safest_entry_avoids_soldiers() {
+        let entries = [Vec3::new(-400.0, 0.0, 0.0), Vec3::new(400.0, 0.0, 0.0)];
+        let military = [Vec3::new(-380.0, 10.0, 0.0), Vec3::new(-350.0, -20.0, 0.0)];
+        assert_eq!(safest_entry(&entries, &military), Some(entries[1]));
+
+        // All quiet: the first listed entry
+        assert_eq!(safest_entry(&entries, &[]), Some(entries[0]));
+        assert_eq!(safest_entry(&[], &military), None);
+    }
+
+    #[test]
+    fn test_packages_cost_more_than_their_units() {
+        let catalog = UnitCatalog::default();
+        for package in &CALL_IN_PACKAGES {
+            let units: u32 = package
+                .units
+                .iter()
+                .map(|unit_type| catalog.definition(unit_type).cost)
+                .sum();
+            assert!(package_cost(package, &catalog) >= units);
+            assert!(package.arrival < package.cooldown);
+        }
+    }
+}
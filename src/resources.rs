@@ -1,7 +1,11 @@
-use crate::components::GamePhase;
+use crate::components::{Faction, GamePhase};
+use crate::config::DirectorBalance;
+use crate::localization::tr;
+use crate::utils::new_sim_seed;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::AudioSource as KiraAudioSource;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ==================== SETUP RESOURCES ====================
 
@@ -10,7 +14,7 @@ pub struct GameSetupComplete;
 
 // ==================== ASSET RESOURCES ====================
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct GameAssets {
     // Individual sprite handles
     pub sicario_sprite: Handle<Image>,
@@ -21,6 +25,7 @@ pub struct GameAssets {
     pub vehicle_sprite: Handle<Image>,
     pub roadblock_sprite: Handle<Image>,
     pub safehouse_sprite: Handle<Image>,
+    pub unit_sprites: HashMap<String, Handle<Image>>, // By the paths in units.ron
 
     // Future expansion assets
     pub _health_bar_bg: Handle<Image>,
@@ -31,6 +36,13 @@ pub struct GameAssets {
     pub _radio_chatter: Handle<KiraAudioSource>,
 }
 
+impl GameAssets {
+    // Sprites added by a catalog reload aren't loaded until the next start
+    pub fn unit_sprite(&self, path: &str) -> Handle<Image> {
+        self.unit_sprites.get(path).cloned().unwrap_or_default()
+    }
+}
+
 // ==================== GAME STATE RESOURCES ====================
 
 #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
@@ -41,6 +53,18 @@ pub struct GameState {
     pub military_score: u32,
     pub game_phase: GamePhase,
     pub ovidio_captured: bool,
+    #[serde(default = "new_sim_seed")] // Older saves get a fresh seed
+    pub rng_seed: u64,
+    // Bodies faded out by the corpse cleanup still count as casualties
+    #[serde(default)]
+    pub cleared_cartel_dead: u32,
+    #[serde(default)]
+    pub cleared_military_dead: u32,
+    #[serde(default)]
+    pub military_checkpoints_taken: u32,
+    // Missions with a convoy to escort, see convoy.rs
+    #[serde(default)]
+    pub convoy: Option<ConvoyStatus>,
 }
 
 impl Default for GameState {
@@ -50,20 +74,92 @@ impl Default for GameState {
             current_wave: 0,
             cartel_score: 0,
             military_score: 0,
-            game_phase: GamePhase::MainMenu,
+            game_phase: GamePhase::Loading,
             ovidio_captured: false,
+            rng_seed: new_sim_seed(),
+            cleared_cartel_dead: 0,
+            cleared_military_dead: 0,
+            military_checkpoints_taken: 0,
+            convoy: None,
         }
     }
 }
 
+impl GameState {
+    pub fn cleared_dead(&self, faction: &Faction) -> u32 {
+        match faction {
+            Faction::Cartel => self.cleared_cartel_dead,
+            Faction::Military => self.cleared_military_dead,
+            _ => 0,
+        }
+    }
+
+    pub fn record_cleared_corpse(&mut self, faction: &Faction) {
+        match faction {
+            Faction::Cartel => self.cleared_cartel_dead += 1,
+            Faction::Military => self.cleared_military_dead += 1,
+            _ => {}
+        }
+    }
+}
+
+// How far a scripted convoy has come and how much of it is left
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConvoyStatus {
+    pub progress: f32, // Share of the route the lead vehicle has covered
+    pub health: f32,   // Share of the convoy's starting health left
+    pub arrived: bool, // Every vehicle still running is through
+    pub lost: bool,    // Too shot up to go on
+}
+
 // ==================== AI DIRECTOR RESOURCE ====================
 
-#[derive(Resource)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct AiDirector {
     pub intensity_level: f32,
     pub last_spawn_time: f32,
     pub player_performance: f32,
     pub adaptive_difficulty: bool,
+    #[serde(default)]
+    pub personality: DirectorPersonality,
+}
+
+// How hard the director pushes on top of the phase and adaptive scaling;
+// campaign missions stay Balanced, daily challenges draw one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DirectorPersonality {
+    #[default]
+    Balanced,
+    Aggressive, // Hits harder and sends reinforcements sooner
+    Methodical, // Fewer, slower waves
+}
+
+impl DirectorPersonality {
+    pub const ALL: [DirectorPersonality; 3] = [
+        DirectorPersonality::Balanced,
+        DirectorPersonality::Aggressive,
+        DirectorPersonality::Methodical,
+    ];
+
+    pub fn intensity_scale(&self, tuning: &DirectorBalance) -> f32 {
+        match self {
+            DirectorPersonality::Balanced => 1.0,
+            DirectorPersonality::Aggressive => tuning.aggressive_intensity,
+            DirectorPersonality::Methodical => tuning.methodical_intensity,
+        }
+    }
+
+    pub fn spawn_interval_scale(&self, tuning: &DirectorBalance) -> f32 {
+        match self {
+            DirectorPersonality::Balanced => 1.0,
+            DirectorPersonality::Aggressive => tuning.aggressive_spawn_interval,
+            DirectorPersonality::Methodical => tuning.methodical_spawn_interval,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        tr(&format!("director.{:?}", self))
+    }
 }
 
 // ==================== INTEL SYSTEM RESOURCE ====================
@@ -77,6 +173,24 @@ pub struct IntelSystem {
     pub intercept_chance: f32, // Base chance to intercept radio messages
     pub informant_reliability: f32, // Base reliability of informant tips
     pub counter_intel_level: f32, // Enemy counter-intelligence strength
+    pub reports_gathered: u32, // Intercepts, tips and recon, never trimmed like the lists
+    pub intel_spent: u32,      // Of the intel points the reports earned
+}
+
+impl IntelSystem {
+    /// Intel points left to spend: one for every report gathered.
+    pub fn intel_points(&self) -> u32 {
+        self.reports_gathered.saturating_sub(self.intel_spent)
+    }
+
+    /// Spends `cost` intel points, if there are that many left.
+    pub fn spend_intel(&mut self, cost: u32) -> bool {
+        if self.intel_points() < cost {
+            return false;
+        }
+        self.intel_spent += cost;
+        true
+    }
 }
 
 impl Default for IntelSystem {
@@ -94,6 +208,8 @@ impl Default for IntelSystem {
             intercept_chance: 0.3,
             informant_reliability: 0.7,
             counter_intel_level: 0.4,
+            reports_gathered: 0,
+            intel_spent: 0,
         }
     }
 }
@@ -105,6 +221,7 @@ impl Default for AiDirector {
             last_spawn_time: 0.0,
             player_performance: 0.5, // 0.0 = struggling, 1.0 = dominating
             adaptive_difficulty: true,
+            personality: DirectorPersonality::Balanced,
         }
     }
 }
@@ -117,16 +234,3 @@ pub struct SaveData {
     pub timestamp: String,
     pub version: String,
 }
-
-// ==================== CONDITION FUNCTIONS ====================
-
-pub fn not_in_menu_phase(game_state: Res<GameState>) -> bool {
-    !matches!(
-        game_state.game_phase,
-        GamePhase::MainMenu
-            | GamePhase::SaveMenu
-            | GamePhase::LoadMenu
-            | GamePhase::Victory
-            | GamePhase::Defeat
-    )
-}
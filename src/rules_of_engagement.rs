@@ -0,0 +1,236 @@
+use crate::ai::unit_ai_system;
+use crate::app_state::{AppState, GameSet};
+use crate::components::*;
+use crate::config::{BalanceConfig, IndirectFireBalance, RoeBalance};
+use crate::coordination::advanced_tactical_ai_system;
+use crate::endings::ending_system;
+use crate::indirect_fire::{densest_group, fire_salvo};
+use crate::localization::tr;
+use crate::political_system::{
+    government_decision_system, GovernmentResponseLevel, PoliticalState,
+};
+use crate::systems::combat_system;
+use crate::utils::{play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== RULES OF ENGAGEMENT PLUGIN ====================
+
+// What the government's response level lets the army do on the ground:
+//   Limited     weapon reach shrinks, and tanks, helicopters and mortars
+//               hold fire on anyone with civilians standing close by
+//   Moderate    the army fights as it was trained to, and Aggressive the same
+//   AllOut      air strikes on the thickest knot of fighters, and armor
+//               that isn't fighting pushes straight for Ovidio
+// Political actions buy relief that pushes the level down for a while: a
+// propaganda burst, or a prisoner exchange. The political panel shows the
+// rules the army is under.
+
+// Type aliases to reduce complexity
+type ArmorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Unit,
+        &'static Transform,
+        Option<&'static mut Movement>,
+    ),
+>;
+
+pub struct RulesOfEngagementPlugin;
+
+impl Plugin for RulesOfEngagementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AirStrikes>()
+            .add_systems(OnEnter(AppState::InGame), roe_reset_system)
+            .add_systems(
+                FixedUpdate,
+                (air_strike_system, armor_push_system)
+                    .after(government_decision_system)
+                    .after(unit_ai_system)
+                    .after(advanced_tactical_ai_system)
+                    .before(ending_system)
+                    .before(combat_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AirStrikes {
+    pub cooldown: f32,
+}
+
+/// What the rules let the army's weapons do, as combat reads it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoeLimits {
+    pub range_scale: f32,     // Share of the army's weapon reach
+    pub civilian_radius: f32, // Heavy weapons hold fire with civilians this close to the target
+}
+
+impl RoeLimits {
+    pub fn of(level: &GovernmentResponseLevel, balance: &RoeBalance) -> Self {
+        match level {
+            GovernmentResponseLevel::Limited => Self {
+                range_scale: balance.limited_range,
+                civilian_radius: balance.civilian_radius,
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Whether a heavy weapon may fire on `target` with civilians at `civilians`.
+    pub fn clear_to_fire(&self, target: Vec2, civilians: &[Vec2]) -> bool {
+        self.civilian_radius <= 0.0
+            || civilians
+                .iter()
+                .all(|civilian| civilian.distance(target) > self.civilian_radius)
+    }
+}
+
+impl Default for RoeLimits {
+    fn default() -> Self {
+        Self {
+            range_scale: 1.0,
+            civilian_radius: 0.0,
+        }
+    }
+}
+
+/// The army's weapons the rules keep away from civilians.
+pub fn heavy_weapon(unit_type: &UnitType) -> bool {
+    matches!(unit_type, UnitType::Tank | UnitType::Helicopter)
+}
+
+/// Where the living civilians are, for the heavy weapons to hold fire around.
+pub fn civilian_positions<'a>(units: impl Iterator<Item = (&'a Unit, &'a Transform)>) -> Vec<Vec2> {
+    units
+        .filter(|(unit, _)| unit.faction == Faction::Civilian && unit.health > 0.0)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect()
+}
+
+/// Localization key under roe. for the rules at `level`; the key with
+/// _hint appended says what they allow.
+pub fn roe_key(level: &GovernmentResponseLevel) -> &'static str {
+    match level {
+        GovernmentResponseLevel::Limited => "roe.limited",
+        GovernmentResponseLevel::Moderate => "roe.moderate",
+        GovernmentResponseLevel::Aggressive => "roe.aggressive",
+        GovernmentResponseLevel::AllOut => "roe.all_out",
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+// Each mission starts on the clock for its first air strike, without relief
+pub fn roe_reset_system(
+    mut strikes: ResMut<AirStrikes>,
+    mut political_state: ResMut<PoliticalState>,
+    balance: Res<BalanceConfig>,
+) {
+    strikes.cooldown = balance.roe.air_strike_interval;
+    political_state.roe_relief = 0.0;
+}
+
+// With the gloves off, jets bomb wherever the cartel is thickest
+pub fn air_strike_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    political_state: Res<PoliticalState>,
+    mut strikes: ResMut<AirStrikes>,
+    unit_query: Query<(&Unit, &Transform)>,
+) {
+    if political_state.government_response_level != GovernmentResponseLevel::AllOut {
+        return;
+    }
+    strikes.cooldown -= time.delta_seconds();
+    if strikes.cooldown > 0.0 {
+        return;
+    }
+
+    let cartel: Vec<Vec2> = unit_query
+        .iter()
+        .filter(|(unit, _)| unit.faction == Faction::Cartel && unit.health > 0.0)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    let strike = IndirectFireBalance {
+        rounds: balance.roe.air_strike_rounds,
+        ..balance.indirect_fire.clone()
+    };
+    let Some(target) = densest_group(&cartel, strike.blast_radius * 2.0) else {
+        return;
+    };
+    fire_salvo(
+        &mut commands,
+        Faction::Military,
+        target,
+        balance.roe.air_strike_scatter,
+        &strike,
+    );
+    strikes.cooldown = balance.roe.air_strike_interval;
+    play_tactical_sound("radio", &tr("radio.air_strike_incoming"));
+}
+
+// Armor not already in a fight drives on Ovidio while it's all out
+pub fn armor_push_system(political_state: Res<PoliticalState>, mut unit_query: ArmorQuery) {
+    if political_state.government_response_level != GovernmentResponseLevel::AllOut
+        || political_state.ending.is_some()
+    {
+        return;
+    }
+    let Some(ovidio) = unit_query
+        .iter()
+        .find(|(unit, _, _)| unit.unit_type == UnitType::Ovidio && unit.health > 0.0)
+        .map(|(_, transform, _)| transform.translation)
+    else {
+        return;
+    };
+
+    for (unit, _, movement) in unit_query.iter_mut() {
+        if unit.faction != Faction::Military
+            || !matches!(unit.unit_type, UnitType::Tank | UnitType::Vehicle)
+            || unit.health <= 0.0
+            || unit.target.is_some()
+        {
+            continue;
+        }
+        if let Some(mut movement) = movement {
+            if movement.target_position != Some(ovidio) {
+                movement.target_position = Some(ovidio);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limited_rules_keep_heavy_weapons_off_civilians() {
+        let balance = RoeBalance::default();
+        let civilians = [Vec2::new(100.0, 0.0)];
+        let limited = RoeLimits::of(&GovernmentResponseLevel::Limited, &balance);
+        assert_eq!(limited.range_scale, balance.limited_range);
+        assert!(!limited.clear_to_fire(Vec2::ZERO, &civilians));
+        assert!(limited.clear_to_fire(
+            Vec2::new(100.0 + balance.civilian_radius * 2.0, 0.0),
+            &civilians
+        ));
+
+        // Anything harder than Limited fires regardless
+        for level in [
+            GovernmentResponseLevel::Moderate,
+            GovernmentResponseLevel::Aggressive,
+            GovernmentResponseLevel::AllOut,
+        ] {
+            let limits = RoeLimits::of(&level, &balance);
+            assert_eq!(limits, RoeLimits::default());
+            assert!(limits.clear_to_fire(Vec2::ZERO, &civilians));
+        }
+        assert!(heavy_weapon(&UnitType::Tank));
+        assert!(!heavy_weapon(&UnitType::Soldier));
+    }
+}
@@ -0,0 +1,354 @@
+use crate::auth::AuthSession;
+use crate::save::save_file::{decode_save, write_save_file};
+use crate::save::save_system::{get_save_path, EnhancedSaveData, MAX_SAVE_SLOTS};
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use std::env;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+// ==================== CLOUD SAVE BACKEND ====================
+
+pub type CloudSyncError = Box<dyn std::error::Error + Send + Sync>;
+
+const SAVE_TIMESTAMP_HEADER: &str = "X-Save-Timestamp";
+const CLOUD_REQUEST_TIMEOUT_SECONDS: u64 = 10;
+
+pub struct CloudSave {
+    pub slot: usize,
+    pub timestamp: DateTime<Utc>,
+    pub data: Vec<u8>, // Encoded save file, exactly as written to disk
+}
+
+// Backends are called from a worker thread (and on exit), so they can block
+pub trait CloudSaveBackend: Send + Sync {
+    fn upload(&self, token: &str, save: &CloudSave) -> Result<(), CloudSyncError>;
+    fn download(&self, token: &str, slot: usize) -> Result<Option<CloudSave>, CloudSyncError>;
+
+    // Platform storage (Steam Cloud) is tied to the platform account instead
+    fn needs_sign_in(&self) -> bool {
+        true
+    }
+}
+
+pub struct HttpCloudBackend {
+    client: Client,
+    endpoint: String,
+}
+
+impl HttpCloudBackend {
+    pub fn new(endpoint: impl Into<String>) -> Result<Self, CloudSyncError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(CLOUD_REQUEST_TIMEOUT_SECONDS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn slot_url(&self, slot: usize) -> String {
+        format!("{}/slots/{}", self.endpoint, slot)
+    }
+}
+
+impl CloudSaveBackend for HttpCloudBackend {
+    fn upload(&self, token: &str, save: &CloudSave) -> Result<(), CloudSyncError> {
+        self.client
+            .put(self.slot_url(save.slot))
+            .bearer_auth(token)
+            .header(SAVE_TIMESTAMP_HEADER, save.timestamp.to_rfc3339())
+            .body(save.data.clone())
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    fn download(&self, token: &str, slot: usize) -> Result<Option<CloudSave>, CloudSyncError> {
+        let response = self
+            .client
+            .get(self.slot_url(slot))
+            .bearer_auth(token)
+            .send()?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+
+        let timestamp = response
+            .headers()
+            .get(SAVE_TIMESTAMP_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&Utc))
+            .ok_or("Cloud save is missing its timestamp")?;
+
+        Ok(Some(CloudSave {
+            slot,
+            timestamp,
+            data: response.bytes()?.to_vec(),
+        }))
+    }
+}
+
+// ==================== CONFLICT RESOLUTION ====================
+
+#[derive(Debug, PartialEq)]
+pub enum SyncAction {
+    Upload,
+    Download,
+    UpToDate,
+}
+
+// Newest save wins; cloud sync never deletes a save that only exists on one side
+pub fn reconcile(local: Option<DateTime<Utc>>, remote: Option<DateTime<Utc>>) -> SyncAction {
+    match (local, remote) {
+        (Some(local), Some(remote)) if local > remote => SyncAction::Upload,
+        (Some(local), Some(remote)) if remote > local => SyncAction::Download,
+        (Some(_), None) => SyncAction::Upload,
+        (None, Some(_)) => SyncAction::Download,
+        _ => SyncAction::UpToDate,
+    }
+}
+
+// When an encoded save file was written, from the save itself
+pub fn save_timestamp(data: &[u8]) -> Option<DateTime<Utc>> {
+    let save_data: EnhancedSaveData = serde_json::from_slice(&decode_save(data).ok()?).ok()?;
+    DateTime::parse_from_rfc3339(&save_data.timestamp)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+fn read_local_save(slot: usize) -> Option<CloudSave> {
+    let data = fs::read(get_save_path(slot)).ok()?;
+    let timestamp = save_timestamp(&data)?;
+
+    Some(CloudSave {
+        slot,
+        timestamp,
+        data,
+    })
+}
+
+fn store_cloud_save(save: &CloudSave) -> Result<(), CloudSyncError> {
+    // Never let a corrupted download replace a local save
+    let json = decode_save(&save.data)?;
+    serde_json::from_slice::<EnhancedSaveData>(&json)?;
+
+    let save_path = get_save_path(save.slot);
+    if let Some(parent_dir) = save_path.parent() {
+        fs::create_dir_all(parent_dir)?;
+    }
+    write_save_file(&save_path, &json)?;
+    Ok(())
+}
+
+fn sync_slot(
+    backend: &dyn CloudSaveBackend,
+    token: &str,
+    slot: usize,
+) -> Result<SyncAction, CloudSyncError> {
+    let local = read_local_save(slot);
+    let remote = backend.download(token, slot)?;
+
+    let action = reconcile(
+        local.as_ref().map(|save| save.timestamp),
+        remote.as_ref().map(|save| save.timestamp),
+    );
+
+    match (&action, local, remote) {
+        (SyncAction::Upload, Some(local), _) => backend.upload(token, &local)?,
+        (SyncAction::Download, _, Some(remote)) => store_cloud_save(&remote)?,
+        _ => {}
+    }
+
+    Ok(action)
+}
+
+// Exit only ever uploads, and only a local save newer than the cloud's - if the
+// startup sync never got to a slot, a newer cloud save must survive until next launch
+fn upload_if_newer(
+    backend: &dyn CloudSaveBackend,
+    token: &str,
+    slot: usize,
+) -> Result<bool, CloudSyncError> {
+    let Some(local) = read_local_save(slot) else {
+        return Ok(false);
+    };
+    let remote = backend.download(token, slot)?;
+
+    if reconcile(Some(local.timestamp), remote.map(|save| save.timestamp)) != SyncAction::Upload {
+        return Ok(false);
+    }
+
+    backend.upload(token, &local)?;
+    Ok(true)
+}
+
+// ==================== CLOUD SYNC RESOURCE ====================
+
+#[derive(Resource)]
+pub struct CloudSync {
+    backend: Option<Arc<dyn CloudSaveBackend>>,
+    startup_sync_done: bool,
+    results: Option<mpsc::UnboundedReceiver<String>>,
+}
+
+impl Default for CloudSync {
+    fn default() -> Self {
+        // Cloud sync is opt-in; without an endpoint saves stay local-only
+        let backend = env::var("CLOUD_SAVE_URL").ok().and_then(|endpoint| {
+            match HttpCloudBackend::new(endpoint) {
+                Ok(backend) => Some(Arc::new(backend) as Arc<dyn CloudSaveBackend>),
+                Err(e) => {
+                    warn!("Cloud save backend unavailable: {}", e);
+                    None
+                }
+            }
+        });
+
+        Self {
+            backend,
+            startup_sync_done: false,
+            results: None,
+        }
+    }
+}
+
+impl CloudSync {
+    pub fn with_backend(backend: Arc<dyn CloudSaveBackend>) -> Self {
+        Self {
+            backend: Some(backend),
+            startup_sync_done: false,
+            results: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.backend.is_some()
+    }
+}
+
+pub struct CloudSyncPlugin;
+
+impl Plugin for CloudSyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CloudSync>()
+            .add_systems(Update, cloud_sync_startup_system)
+            .add_systems(Last, cloud_sync_on_exit_system);
+    }
+}
+
+// ==================== CLOUD SYNC SYSTEMS ====================
+
+fn sync_token(backend: &dyn CloudSaveBackend, auth_session: &AuthSession) -> Option<String> {
+    if !backend.needs_sign_in() {
+        return Some(String::new());
+    }
+    auth_session.access_token().map(str::to_string)
+}
+
+pub fn cloud_sync_startup_system(
+    mut cloud_sync: ResMut<CloudSync>,
+    auth_session: Res<AuthSession>,
+) {
+    if let Some(receiver) = cloud_sync.results.as_mut() {
+        while let Ok(message) = receiver.try_recv() {
+            info!("☁️ {}", message);
+        }
+    }
+
+    if cloud_sync.startup_sync_done {
+        return;
+    }
+
+    let Some(backend) = cloud_sync.backend.clone() else {
+        return;
+    };
+
+    // Wait until we're signed in; offline play keeps using local saves
+    let Some(token) = sync_token(backend.as_ref(), &auth_session) else {
+        return;
+    };
+
+    cloud_sync.startup_sync_done = true;
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    cloud_sync.results = Some(receiver);
+
+    std::thread::spawn(move || {
+        for slot in 0..MAX_SAVE_SLOTS {
+            let message = match sync_slot(backend.as_ref(), &token, slot) {
+                Ok(SyncAction::Upload) => format!("Uploaded save slot {}", slot),
+                Ok(SyncAction::Download) => format!("Downloaded newer save for slot {}", slot),
+                Ok(SyncAction::UpToDate) => continue,
+                Err(e) => format!("Cloud sync failed for slot {}: {}", slot, e),
+            };
+
+            if sender.send(message).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+pub fn cloud_sync_on_exit_system(
+    mut exit_events: EventReader<AppExit>,
+    cloud_sync: Res<CloudSync>,
+    auth_session: Res<AuthSession>,
+) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+
+    let Some(backend) = cloud_sync.backend.as_ref() else {
+        return;
+    };
+    let Some(token) = sync_token(backend.as_ref(), &auth_session) else {
+        return;
+    };
+
+    // Blocking is fine here - the app is shutting down and the requests have a timeout
+    for slot in 0..MAX_SAVE_SLOTS {
+        match upload_if_newer(backend.as_ref(), &token, slot) {
+            Ok(true) => info!("☁️ Uploaded save slot {} on exit", slot),
+            Ok(false) => {}
+            Err(e) => {
+                warn!("☁️ Cloud upload failed, keeping local save only: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+
+    #[test]
+    fn test_reconcile_newest_wins() {
+        let now = Utc::now();
+        let earlier = now - ChronoDuration::minutes(5);
+
+        assert_eq!(reconcile(Some(now), Some(earlier)), SyncAction::Upload);
+        assert_eq!(reconcile(Some(earlier), Some(now)), SyncAction::Download);
+        assert_eq!(reconcile(Some(now), Some(now)), SyncAction::UpToDate);
+    }
+
+    #[test]
+    fn test_reconcile_one_sided() {
+        let now = Utc::now();
+
+        assert_eq!(reconcile(Some(now), None), SyncAction::Upload);
+        assert_eq!(reconcile(None, Some(now)), SyncAction::Download);
+        assert_eq!(reconcile(None, None), SyncAction::UpToDate);
+    }
+}
@@ -1,3 +1,9 @@
+pub mod cloud_sync;
+pub mod save_file;
 pub mod save_system;
+pub mod world_snapshot;
 
+pub use cloud_sync::*;
+pub use save_file::*;
 pub use save_system::*;
+pub use world_snapshot::*;
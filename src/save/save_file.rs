@@ -0,0 +1,144 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+// ==================== SAVE FILE FORMAT ====================
+//
+// [gzip-compressed JSON][CRC32 of compressed bytes, u32 LE][FOOTER_MAGIC]
+//
+// Older saves are plain JSON and are still accepted on load.
+
+const FOOTER_MAGIC: &[u8; 4] = b"CRTS";
+const FOOTER_LEN: usize = 8;
+
+#[derive(Debug)]
+pub enum SaveFileError {
+    Io(std::io::Error),
+    ChecksumMismatch { expected: u32, actual: u32 },
+    Corrupted(String),
+}
+
+impl fmt::Display for SaveFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveFileError::Io(e) => write!(f, "Save file I/O error: {}", e),
+            SaveFileError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Save file checksum mismatch (expected {:08x}, got {:08x})",
+                expected, actual
+            ),
+            SaveFileError::Corrupted(msg) => write!(f, "Save file corrupted: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SaveFileError {}
+
+impl From<std::io::Error> for SaveFileError {
+    fn from(err: std::io::Error) -> Self {
+        SaveFileError::Io(err)
+    }
+}
+
+pub fn encode_save(json: &[u8]) -> Result<Vec<u8>, SaveFileError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json)?;
+    let mut bytes = encoder.finish()?;
+
+    let checksum = crc32fast::hash(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    bytes.extend_from_slice(FOOTER_MAGIC);
+    Ok(bytes)
+}
+
+pub fn decode_save(bytes: &[u8]) -> Result<Vec<u8>, SaveFileError> {
+    if !bytes.ends_with(FOOTER_MAGIC) {
+        // Legacy uncompressed saves were plain JSON objects
+        return match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') => Ok(bytes.to_vec()),
+            _ => Err(SaveFileError::Corrupted("missing save footer".to_string())),
+        };
+    }
+
+    if bytes.len() < FOOTER_LEN {
+        return Err(SaveFileError::Corrupted("truncated save file".to_string()));
+    }
+
+    let (payload, footer) = bytes.split_at(bytes.len() - FOOTER_LEN);
+    let expected = u32::from_le_bytes([footer[0], footer[1], footer[2], footer[3]]);
+    let actual = crc32fast::hash(payload);
+    if expected != actual {
+        return Err(SaveFileError::ChecksumMismatch { expected, actual });
+    }
+
+    let mut json = Vec::new();
+    GzDecoder::new(payload)
+        .read_to_end(&mut json)
+        .map_err(|e| SaveFileError::Corrupted(e.to_string()))?;
+    Ok(json)
+}
+
+// ==================== SAVE FILE I/O ====================
+
+pub fn backup_path(save_path: &Path) -> PathBuf {
+    save_path.with_extension("bak")
+}
+
+pub fn write_save_file(save_path: &Path, json: &[u8]) -> Result<(), SaveFileError> {
+    let bytes = encode_save(json)?;
+
+    // Write and sync a temp file first so a failed or interrupted write
+    // leaves the current save untouched
+    let temp_path = save_path.with_extension("tmp");
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(&bytes)?;
+    file.sync_all()?;
+    drop(file);
+
+    // Keep the previous save around so a bad write can be recovered on load
+    if save_path.exists() {
+        fs::rename(save_path, backup_path(save_path))?;
+    }
+    fs::rename(&temp_path, save_path)?;
+    Ok(())
+}
+
+pub fn read_save_file(save_path: &Path) -> Result<Vec<u8>, SaveFileError> {
+    decode_save(&fs::read(save_path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let json = br#"{"version":"2.0.0"}"#;
+        let encoded = encode_save(json).unwrap();
+
+        assert!(encoded.ends_with(FOOTER_MAGIC));
+        assert_eq!(decode_save(&encoded).unwrap(), json);
+    }
+
+    #[test]
+    fn test_detects_corruption() {
+        let mut encoded = encode_save(br#"{"version":"2.0.0"}"#).unwrap();
+        encoded[4] ^= 0xFF;
+
+        assert!(matches!(
+            decode_save(&encoded),
+            Err(SaveFileError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_accepts_legacy_json() {
+        let json = b"  {\"version\":\"1.0.0\"}";
+        assert_eq!(decode_save(json).unwrap(), json);
+        assert!(decode_save(b"garbage").is_err());
+    }
+}
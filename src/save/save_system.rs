@@ -1,18 +1,50 @@
+use crate::app_state::{AppState, GameSet};
+use crate::battle_plan::PlanMark;
+use crate::campaign::Campaign;
 use crate::components::GamePhase;
-use crate::resources::{GameState, SaveData};
+use crate::localization::tr;
+use crate::resources::{GameAssets, GameState, SaveData};
+use crate::save::save_file::{backup_path, read_save_file, write_save_file};
+use crate::save::world_snapshot::{
+    world_restore_system, PendingWorldRestore, WorldSnapshot, WorldSnapshotSources,
+};
+use crate::scoring::{Medal, MissionRecord};
+use crate::utils::{data_dir, play_tactical_sound, DataKind};
 use bevy::prelude::*;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
 
+// ==================== SAVE SYSTEM PLUGIN ====================
+
+pub struct SaveSystemPlugin;
+
+impl Plugin for SaveSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveGameEvent>()
+            .add_event::<LoadGameEvent>()
+            .init_resource::<AutoSaveTimer>()
+            .add_systems(Update, (handle_save_events, handle_load_events))
+            .add_systems(Update, auto_save_system.in_set(GameSet::InGame))
+            // Outside GameSet, which waits for the GameSetupComplete this inserts
+            .add_systems(
+                Update,
+                world_restore_system
+                    .run_if(resource_exists::<PendingWorldRestore>())
+                    .run_if(resource_exists::<GameAssets>())
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
 // ==================== ENHANCED SAVE SYSTEM ====================
 
-const SAVE_DIR: &str = ".culiacan-rts/saves";
-const MAX_SAVE_SLOTS: usize = 10;
+pub const MAX_SAVE_SLOTS: usize = 10;
 
 pub fn save_game_to_slot(
     game_state: &GameState,
     campaign: &CampaignProgress,
+    world: Option<WorldSnapshot>,
     slot: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if slot >= MAX_SAVE_SLOTS {
@@ -22,6 +54,7 @@ pub fn save_game_to_slot(
     let save_data = EnhancedSaveData {
         game_state: game_state.clone(),
         campaign_progress: campaign.clone(),
+        world,
         timestamp: Utc::now().to_rfc3339(),
         version: "2.0.0".to_string(),
         slot_number: slot,
@@ -36,8 +69,8 @@ pub fn save_game_to_slot(
         fs::create_dir_all(parent_dir)?;
     }
 
-    let save_json = serde_json::to_string_pretty(&save_data)?;
-    fs::write(&save_path, save_json)?;
+    let save_json = serde_json::to_vec(&save_data)?;
+    write_save_file(&save_path, &save_json)?;
 
     info!("✅ Game saved to slot {} at: {:?}", slot, save_path);
     Ok(())
@@ -49,8 +82,21 @@ pub fn load_game_from_slot(slot: usize) -> Result<EnhancedSaveData, Box<dyn std:
     }
 
     let save_path = get_save_path(slot);
-    let save_json = fs::read_to_string(&save_path)?;
-    let save_data: EnhancedSaveData = serde_json::from_str(&save_json)?;
+    let save_data = match read_save_data(&save_path) {
+        Ok(save_data) => save_data,
+        Err(e) => {
+            // Fall back to the save this slot held before its last write
+            let backup = backup_path(&save_path);
+            if !backup.exists() {
+                return Err(e);
+            }
+
+            warn!("⚠️ Save slot {} is corrupted ({}); trying backup", slot, e);
+            let save_data = read_save_data(&backup)?;
+            play_tactical_sound("radio", &tr("radio.save_restored_backup"));
+            save_data
+        }
+    };
 
     info!(
         "✅ Game loaded from slot {} ({})",
@@ -59,6 +105,11 @@ pub fn load_game_from_slot(slot: usize) -> Result<EnhancedSaveData, Box<dyn std:
     Ok(save_data)
 }
 
+fn read_save_data(path: &std::path::Path) -> Result<EnhancedSaveData, Box<dyn std::error::Error>> {
+    let save_json = read_save_file(path)?;
+    Ok(serde_json::from_slice(&save_json)?)
+}
+
 pub fn get_save_slot_info(slot: usize) -> Option<SaveSlotInfo> {
     if slot >= MAX_SAVE_SLOTS {
         return None;
@@ -104,21 +155,15 @@ pub fn delete_save_slot(slot: usize) -> Result<(), Box<dyn std::error::Error>> {
     let save_path = get_save_path(slot);
     if save_path.exists() {
         fs::remove_file(&save_path)?;
+        let _ = fs::remove_file(backup_path(&save_path));
         info!("🗑️ Deleted save slot {}", slot);
     }
 
     Ok(())
 }
 
-fn get_save_path(slot: usize) -> std::path::PathBuf {
-    if let Some(home_dir) = dirs::home_dir() {
-        home_dir
-            .join(SAVE_DIR)
-            .join(format!("save_slot_{}.json", slot))
-    } else {
-        // Fallback to current directory
-        std::path::Path::new(&format!("save_slot_{}.json", slot)).to_path_buf()
-    }
+pub(crate) fn get_save_path(slot: usize) -> std::path::PathBuf {
+    data_dir(DataKind::Saves).join(format!("save_slot_{}.json", slot))
 }
 
 fn get_mission_display_name(mission_id: &MissionId) -> String {
@@ -143,7 +188,7 @@ fn get_mission_display_name(mission_id: &MissionId) -> String {
 // Legacy save system compatibility
 pub fn save_game(game_state: &GameState) -> Result<(), Box<dyn std::error::Error>> {
     let campaign = CampaignProgress::default(); // Use default if no campaign available
-    save_game_to_slot(game_state, &campaign, 0) // Save to slot 0
+    save_game_to_slot(game_state, &campaign, None, 0) // Save to slot 0
 }
 
 pub fn load_game() -> Result<SaveData, Box<dyn std::error::Error>> {
@@ -163,10 +208,12 @@ pub fn has_save_file() -> bool {
 
 // ==================== ENHANCED SAVE DATA STRUCTURES ====================
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct EnhancedSaveData {
     pub game_state: GameState,
     pub campaign_progress: CampaignProgress,
+    #[serde(default)]
+    pub world: Option<WorldSnapshot>, // Absent in saves from before world snapshots
     pub timestamp: String,
     pub version: String,
     pub slot_number: usize,
@@ -219,6 +266,12 @@ pub struct CampaignProgress {
     pub difficulty_level: DifficultyLevel,
     pub total_score: u32,
     pub best_times: std::collections::HashMap<MissionId, f32>,
+    #[serde(default)] // Profiles from before mission scoring
+    pub best_records: std::collections::HashMap<MissionId, MissionRecord>,
+    #[serde(default)] // Defeats since each mission was last won
+    pub defeats: std::collections::HashMap<MissionId, u32>,
+    #[serde(default)] // The player's own battle plan drawn for each mission
+    pub battle_plans: std::collections::HashMap<MissionId, Vec<PlanMark>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -247,13 +300,40 @@ pub enum MissionId {
     Resolution,           // Final mission - securing victory
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl MissionId {
+    // In campaign order
+    pub const ALL: [MissionId; 13] = [
+        MissionId::InitialRaid,
+        MissionId::UrbanWarfare,
+        MissionId::LasFloresiDefense,
+        MissionId::TierraBlancaRoadblocks,
+        MissionId::CentroUrbanFight,
+        MissionId::LasQuintasSiege,
+        MissionId::AirportAssault,
+        MissionId::GovernmentResponse,
+        MissionId::CivilianEvacuation,
+        MissionId::PoliticalNegotiation,
+        MissionId::CeasefireNegotiation,
+        MissionId::OrderedWithdrawal,
+        MissionId::Resolution,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DifficultyLevel {
     Recruit, // Easy - reduced enemy spawns, longer timers
     Veteran, // Normal - balanced gameplay
     Elite,   // Hard - increased difficulty, more enemies
 }
 
+impl DifficultyLevel {
+    pub const ALL: [DifficultyLevel; 3] = [
+        DifficultyLevel::Recruit,
+        DifficultyLevel::Veteran,
+        DifficultyLevel::Elite,
+    ];
+}
+
 impl Default for CampaignProgress {
     fn default() -> Self {
         Self {
@@ -262,6 +342,9 @@ impl Default for CampaignProgress {
             difficulty_level: DifficultyLevel::Veteran,
             total_score: 0,
             best_times: std::collections::HashMap::new(),
+            best_records: std::collections::HashMap::new(),
+            defeats: std::collections::HashMap::new(),
+            battle_plans: std::collections::HashMap::new(),
         }
     }
 }
@@ -282,6 +365,7 @@ impl CampaignProgress {
         }
 
         self.total_score += score;
+        self.defeats.remove(&mission_id);
 
         // Advance to next mission following historical timeline
         self.current_mission = match mission_id {
@@ -308,6 +392,28 @@ impl CampaignProgress {
         };
     }
 
+    // Keeps whichever result scored higher
+    pub fn record_mission(&mut self, mission_id: &MissionId, record: MissionRecord) {
+        match self.best_records.get(mission_id) {
+            Some(best) if best.score >= record.score => {}
+            _ => {
+                self.best_records.insert(mission_id.clone(), record);
+            }
+        }
+    }
+
+    pub fn record_defeat(&mut self, mission_id: &MissionId) {
+        *self.defeats.entry(mission_id.clone()).or_insert(0) += 1;
+    }
+
+    pub fn defeats(&self, mission_id: &MissionId) -> u32 {
+        self.defeats.get(mission_id).copied().unwrap_or(0)
+    }
+
+    pub fn best_medal(&self, mission_id: &MissionId) -> Option<Medal> {
+        self.best_records.get(mission_id)?.medal
+    }
+
     pub fn is_mission_unlocked(&self, mission_id: &MissionId) -> bool {
         match mission_id {
             // Phase 1 - Always unlocked
@@ -393,26 +499,60 @@ pub struct SaveGameEvent;
 #[derive(Event)]
 pub struct LoadGameEvent;
 
-pub fn handle_save_events(mut save_events: EventReader<SaveGameEvent>, game_state: Res<GameState>) {
+pub fn handle_save_events(
+    mut save_events: EventReader<SaveGameEvent>,
+    mut game_state: ResMut<GameState>,
+    campaign: Res<Campaign>,
+    snapshot_sources: WorldSnapshotSources,
+) {
     for _ in save_events.read() {
-        if let Err(e) = save_game(&game_state) {
-            error!("Failed to save game: {}", e);
+        let world = snapshot_sources.capture();
+        match save_game_to_slot(&game_state, &campaign.progress, Some(world), 0) {
+            Ok(()) => {
+                play_tactical_sound("radio", &tr("radio.game_saved"));
+                game_state.game_phase = GamePhase::MainMenu;
+            }
+            Err(e) => {
+                error!("Failed to save game: {}", e);
+                play_tactical_sound("radio", &tr("radio.save_failed"));
+            }
         }
     }
 }
 
 pub fn handle_load_events(
+    mut commands: Commands,
     mut load_events: EventReader<LoadGameEvent>,
     mut game_state: ResMut<GameState>,
+    mut campaign: ResMut<Campaign>,
 ) {
     for _ in load_events.read() {
-        match load_game() {
+        match load_game_from_slot(0) {
             Ok(save_data) => {
                 *game_state = save_data.game_state;
+                campaign.progress = save_data.campaign_progress;
+
+                // Saves are made from the menu, so resume the mission rather than the menu;
+                // the timer-driven phase checks catch up to the right mission phase
+                if matches!(
+                    game_state.game_phase,
+                    GamePhase::MainMenu | GamePhase::SaveMenu | GamePhase::LoadMenu
+                ) {
+                    game_state.game_phase = GamePhase::Preparation;
+                }
+
+                match save_data.world {
+                    Some(world) => commands.insert_resource(PendingWorldRestore(world)),
+                    None => warn!("Save predates world snapshots; only game state restored"),
+                }
+
                 info!("Game state loaded successfully");
+                play_tactical_sound("radio", &tr("radio.game_loaded"));
             }
             Err(e) => {
                 error!("Failed to load game: {}", e);
+                play_tactical_sound("radio", &tr("radio.load_failed"));
+                game_state.game_phase = GamePhase::MainMenu;
             }
         }
     }
@@ -438,6 +578,8 @@ impl Default for AutoSaveTimer {
 pub fn auto_save_system(
     mut auto_save_timer: ResMut<AutoSaveTimer>,
     game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    snapshot_sources: WorldSnapshotSources,
     time: Res<Time>,
 ) {
     if !auto_save_timer.enabled {
@@ -449,7 +591,8 @@ pub fn auto_save_system(
     if auto_save_timer.timer.just_finished() {
         // Only auto-save if game is in progress
         if game_state.game_phase != GamePhase::GameOver {
-            if let Err(e) = save_game(&game_state) {
+            let world = snapshot_sources.capture();
+            if let Err(e) = save_game_to_slot(&game_state, &campaign.progress, Some(world), 0) {
                 warn!("Auto-save failed: {}", e);
             } else {
                 info!("🔄 Auto-save completed");
@@ -0,0 +1,885 @@
+use crate::campaign::{Campaign, ObjectiveStatus, PoliticalPressure};
+use crate::components::*;
+use crate::config::{BalanceConfig, UnitCatalog};
+use crate::environmental_systems::EnvironmentalState;
+use crate::informants::InformantSource;
+use crate::localization::tr;
+use crate::political_system::{PoliticalState, SocialMediaInfluence};
+use crate::resources::{AiDirector, GameAssets, GameSetupComplete, IntelSystem};
+use crate::spawners::{spawn_intel_operator, spawn_unit};
+use crate::systems::{spawn_ovidio, spawn_safehouses};
+use crate::upgrades::BASE_RADIO_RANGE;
+use crate::utils::{iso_to_world, play_tactical_sound};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ==================== WORLD SNAPSHOT DATA ====================
+
+// Bevy's math types aren't serializable without the "serialize" feature, so
+// positions are stored as plain arrays and entity links as indices into `units`.
+pub type SavedVec3 = [f32; 3];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub units: Vec<UnitSnapshot>,
+    pub intel_operators: Vec<IntelOperatorSnapshot>,
+    pub squads: Vec<SquadSnapshot>,
+    pub wave_spawner: Option<WaveSpawnerSnapshot>,
+    pub campaign: CampaignSnapshot,
+    pub ai_director: AiDirector,
+    pub political_state: PoliticalState,
+    // Absent in saves from before the information war and the weather were kept
+    #[serde(default)]
+    pub intel: Option<IntelSnapshot>,
+    #[serde(default)]
+    pub social_media: Option<SocialMediaInfluence>,
+    #[serde(default)]
+    pub environment: Option<EnvironmentalState>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UnitSnapshot {
+    pub unit_type: UnitType,
+    pub faction: Faction,
+    pub position: SavedVec3,
+    pub health: f32,
+    pub max_health: f32,
+    pub damage: f32,
+    pub range: f32,
+    pub movement_speed: f32,
+    pub experience: u32,
+    pub kills: u32,
+    pub veterancy_level: VeterancyLevel,
+    pub equipment: Equipment,
+    pub target: Option<usize>,
+    pub move_target: Option<SavedVec3>,
+    pub formation: Option<FormationSnapshot>,
+    pub tactical_state: Option<TacticalStateSnapshot>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FormationSnapshot {
+    pub formation_type: FormationType,
+    pub position_in_formation: usize,
+    pub squad_id: u32,
+    pub formation_center: SavedVec3,
+    pub formation_facing: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TacticalStateSnapshot {
+    pub current_state: TacticalMode,
+    pub suppression_level: f32,
+    pub morale: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IntelOperatorSnapshot {
+    pub intel_type: IntelType,
+    pub position: SavedVec3,
+    #[serde(default)]
+    pub source: Option<InformantSource>, // An informant's reliability, once rolled
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SquadSnapshot {
+    pub id: u32,
+    pub leader: Option<usize>,
+    pub members: Vec<usize>,
+    pub squad_type: SquadType,
+    pub current_objective: SquadObjectiveSnapshot,
+    pub rally_point: Option<SavedVec3>,
+    pub cohesion_radius: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SquadObjectiveSnapshot {
+    Advance(SavedVec3),
+    Flank(SavedVec3, SavedVec3),
+    Defend(SavedVec3),
+    Retreat(SavedVec3),
+    Support(usize),
+    Suppress(SavedVec3),
+    Regroup(SavedVec3),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WaveSpawnerSnapshot {
+    pub wave_number: u32,
+    pub units_in_wave: u32,
+    pub timer_elapsed: f32,
+}
+
+// The reports the network has gathered, with their times relative to
+// `saved_at` so they keep their age across a reload. Counter-intel alerts
+// point at entities that don't survive one, and aren't kept.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IntelSnapshot {
+    pub saved_at: f32,
+    pub intercepts: Vec<InterceptSnapshot>,
+    pub tips: Vec<TipSnapshot>,
+    pub recon: Vec<ReconSnapshot>,
+    pub jamming_active: bool,
+    pub jamming_strength: f32,
+    pub intercept_chance: f32,
+    pub informant_reliability: f32,
+    pub counter_intel_level: f32,
+    pub reports_gathered: u32,
+    pub intel_spent: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InterceptSnapshot {
+    pub message_type: RadioMessageSnapshot,
+    pub source_position: SavedVec3,
+    pub intercept_time: f32,
+    pub reliability: f32,
+    pub content: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum RadioMessageSnapshot {
+    TroopMovement(SavedVec3, u32),
+    AirSupport(SavedVec3),
+    SupplyDrop(SavedVec3),
+    Retreat(SavedVec3),
+    Reinforcements(SavedVec3, f32),
+    StatusUpdate(String),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TipSnapshot {
+    pub tip_type: TipTypeSnapshot,
+    pub location: SavedVec3,
+    pub confidence: f32,
+    pub time_received: f32,
+    pub urgency: TipUrgency,
+    pub source: Option<usize>, // Index into `intel_operators`
+    pub planted: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum TipTypeSnapshot {
+    EnemyPosition(UnitType, u32),
+    PlannedAttack(SavedVec3, f32),
+    WeakPoint(SavedVec3),
+    SupplyRoute(SavedVec3, SavedVec3),
+    CommandPost(SavedVec3),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReconSnapshot {
+    pub area_scanned: SavedVec3,
+    pub scan_radius: f32,
+    pub enemies_spotted: Vec<ContactSnapshot>,
+    pub cover_points: Vec<SavedVec3>,
+    pub choke_points: Vec<SavedVec3>,
+    pub elevation_advantages: Vec<SavedVec3>,
+    pub escape_routes: Vec<SavedVec3>,
+    pub scan_time: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ContactSnapshot {
+    pub position: SavedVec3,
+    pub enemy_type: UnitType,
+    pub confidence: f32,
+    pub last_seen: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CampaignSnapshot {
+    pub mission_timer: f32,
+    pub objectives_completed: u32,
+    pub current_objectives: Vec<ObjectiveStatus>,
+    pub political_pressure: PoliticalPressure,
+}
+
+// ==================== SNAPSHOT CAPTURE ====================
+
+#[derive(SystemParam)]
+pub struct WorldSnapshotSources<'w, 's> {
+    units: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Transform,
+            &'static Unit,
+            Option<&'static Movement>,
+            Option<&'static Formation>,
+            Option<&'static TacticalState>,
+        ),
+    >,
+    intel_operators: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Transform,
+            &'static IntelOperator,
+            Option<&'static InformantSource>,
+        ),
+    >,
+    squads: Query<'w, 's, &'static Squad>,
+    wave_spawners: Query<'w, 's, &'static WaveSpawner>,
+    campaign: Res<'w, Campaign>,
+    ai_director: Res<'w, AiDirector>,
+    political_state: Res<'w, PoliticalState>,
+    intel: Res<'w, IntelSystem>,
+    social_media: Res<'w, SocialMediaInfluence>,
+    environment: Res<'w, EnvironmentalState>,
+    time: Res<'w, Time>,
+}
+
+impl<'w, 's> WorldSnapshotSources<'w, 's> {
+    pub fn capture(&self) -> WorldSnapshot {
+        let index_of: HashMap<Entity, usize> = self
+            .units
+            .iter()
+            .enumerate()
+            .map(|(index, (entity, ..))| (entity, index))
+            .collect();
+
+        let units = self
+            .units
+            .iter()
+            .map(
+                |(_, transform, unit, movement, formation, tactical_state)| UnitSnapshot {
+                    unit_type: unit.unit_type.clone(),
+                    faction: unit.faction.clone(),
+                    position: transform.translation.to_array(),
+                    health: unit.health,
+                    max_health: unit.max_health,
+                    damage: unit.damage,
+                    range: unit.range,
+                    movement_speed: unit.movement_speed,
+                    experience: unit.experience,
+                    kills: unit.kills,
+                    veterancy_level: unit.veterancy_level.clone(),
+                    equipment: unit.equipment.clone(),
+                    target: unit
+                        .target
+                        .and_then(|target| index_of.get(&target).copied()),
+                    move_target: movement
+                        .and_then(|movement| movement.target_position)
+                        .map(|target| target.to_array()),
+                    formation: formation.map(|formation| FormationSnapshot {
+                        formation_type: formation.formation_type.clone(),
+                        position_in_formation: formation.position_in_formation,
+                        squad_id: formation.squad_id,
+                        formation_center: formation.formation_center.to_array(),
+                        formation_facing: formation.formation_facing,
+                    }),
+                    tactical_state: tactical_state.map(|state| TacticalStateSnapshot {
+                        current_state: state.current_state.clone(),
+                        suppression_level: state.suppression_level,
+                        morale: state.morale,
+                    }),
+                },
+            )
+            .collect();
+
+        let intel_operators = self
+            .intel_operators
+            .iter()
+            .map(|(_, transform, operator, source)| IntelOperatorSnapshot {
+                intel_type: operator.intel_type.clone(),
+                position: transform.translation.to_array(),
+                source: source.cloned(),
+            })
+            .collect();
+        let operator_index: HashMap<Entity, usize> = self
+            .intel_operators
+            .iter()
+            .enumerate()
+            .map(|(index, (entity, ..))| (entity, index))
+            .collect();
+
+        let squads = self
+            .squads
+            .iter()
+            .filter_map(|squad| {
+                let members: Vec<usize> = squad
+                    .members
+                    .iter()
+                    .filter_map(|member| index_of.get(member).copied())
+                    .collect();
+
+                // Squads whose members all died are rebuilt by the squad system anyway
+                if members.is_empty() {
+                    return None;
+                }
+
+                Some(SquadSnapshot {
+                    id: squad.id,
+                    leader: squad
+                        .leader
+                        .and_then(|leader| index_of.get(&leader).copied()),
+                    members,
+                    squad_type: squad.squad_type.clone(),
+                    current_objective: snapshot_objective(
+                        &squad.current_objective,
+                        &index_of,
+                        squad.rally_point,
+                    ),
+                    rally_point: squad.rally_point.map(|point| point.to_array()),
+                    cohesion_radius: squad.cohesion_radius,
+                })
+            })
+            .collect();
+
+        let wave_spawner = self
+            .wave_spawners
+            .iter()
+            .next()
+            .map(|spawner| WaveSpawnerSnapshot {
+                wave_number: spawner.wave_number,
+                units_in_wave: spawner.units_in_wave,
+                timer_elapsed: spawner.next_wave_timer.elapsed_secs(),
+            });
+
+        WorldSnapshot {
+            units,
+            intel_operators,
+            squads,
+            wave_spawner,
+            campaign: CampaignSnapshot {
+                mission_timer: self.campaign.mission_timer,
+                objectives_completed: self.campaign.objectives_completed,
+                current_objectives: self.campaign.current_objectives.clone(),
+                political_pressure: self.campaign.political_pressure.clone(),
+            },
+            ai_director: self.ai_director.clone(),
+            political_state: self.political_state.clone(),
+            intel: Some(snapshot_intel(
+                &self.intel,
+                &operator_index,
+                self.time.elapsed_seconds(),
+            )),
+            social_media: Some(self.social_media.clone()),
+            environment: Some(self.environment.clone()),
+        }
+    }
+}
+
+fn snapshot_intel(
+    intel: &IntelSystem,
+    operator_index: &HashMap<Entity, usize>,
+    saved_at: f32,
+) -> IntelSnapshot {
+    let network = &intel.global_intel_network;
+    IntelSnapshot {
+        saved_at,
+        intercepts: network
+            .active_intercepts
+            .iter()
+            .map(|intercept| InterceptSnapshot {
+                message_type: snapshot_message(&intercept.message_type),
+                source_position: intercept.source_position.to_array(),
+                intercept_time: intercept.intercept_time,
+                reliability: intercept.reliability,
+                content: intercept.content.clone(),
+            })
+            .collect(),
+        tips: network
+            .informant_reports
+            .iter()
+            .map(|tip| TipSnapshot {
+                tip_type: snapshot_tip_type(&tip.tip_type),
+                location: tip.location.to_array(),
+                confidence: tip.confidence,
+                time_received: tip.time_received,
+                urgency: tip.urgency.clone(),
+                source: tip
+                    .source
+                    .and_then(|source| operator_index.get(&source).copied()),
+                planted: tip.planted,
+            })
+            .collect(),
+        recon: network
+            .reconnaissance_data
+            .iter()
+            .map(|report| ReconSnapshot {
+                area_scanned: report.area_scanned.to_array(),
+                scan_radius: report.scan_radius,
+                enemies_spotted: report
+                    .enemies_spotted
+                    .iter()
+                    .map(|contact| ContactSnapshot {
+                        position: contact.position.to_array(),
+                        enemy_type: contact.enemy_type.clone(),
+                        confidence: contact.confidence,
+                        last_seen: contact.last_seen,
+                    })
+                    .collect(),
+                cover_points: snapshot_points(&report.terrain_info.cover_points),
+                choke_points: snapshot_points(&report.terrain_info.choke_points),
+                elevation_advantages: snapshot_points(&report.terrain_info.elevation_advantages),
+                escape_routes: snapshot_points(&report.terrain_info.escape_routes),
+                scan_time: report.scan_time,
+            })
+            .collect(),
+        jamming_active: intel.jamming_active,
+        jamming_strength: intel.jamming_strength,
+        intercept_chance: intel.intercept_chance,
+        informant_reliability: intel.informant_reliability,
+        counter_intel_level: intel.counter_intel_level,
+        reports_gathered: intel.reports_gathered,
+        intel_spent: intel.intel_spent,
+    }
+}
+
+// Report times move by `shift`, so a report keeps its age across a reload
+fn restore_intel(
+    intel: &mut IntelSystem,
+    snapshot: &IntelSnapshot,
+    operators: &[Entity],
+    shift: f32,
+) {
+    let network = &mut intel.global_intel_network;
+    network.active_intercepts = snapshot
+        .intercepts
+        .iter()
+        .map(|intercept| RadioIntercept {
+            message_type: restore_message(&intercept.message_type),
+            source_position: Vec3::from_array(intercept.source_position),
+            intercept_time: intercept.intercept_time + shift,
+            reliability: intercept.reliability,
+            content: intercept.content.clone(),
+        })
+        .collect();
+    network.informant_reports = snapshot
+        .tips
+        .iter()
+        .map(|tip| InformantTip {
+            tip_type: restore_tip_type(&tip.tip_type),
+            location: Vec3::from_array(tip.location),
+            confidence: tip.confidence,
+            time_received: tip.time_received + shift,
+            urgency: tip.urgency.clone(),
+            source: tip.source.and_then(|index| operators.get(index).copied()),
+            planted: tip.planted,
+        })
+        .collect();
+    network.reconnaissance_data = snapshot
+        .recon
+        .iter()
+        .map(|report| ReconReport {
+            area_scanned: Vec3::from_array(report.area_scanned),
+            scan_radius: report.scan_radius,
+            enemies_spotted: report
+                .enemies_spotted
+                .iter()
+                .map(|contact| EnemyContact {
+                    position: Vec3::from_array(contact.position),
+                    enemy_type: contact.enemy_type.clone(),
+                    confidence: contact.confidence,
+                    last_seen: contact.last_seen + shift,
+                })
+                .collect(),
+            terrain_info: TerrainIntel {
+                cover_points: restore_points(&report.cover_points),
+                choke_points: restore_points(&report.choke_points),
+                elevation_advantages: restore_points(&report.elevation_advantages),
+                escape_routes: restore_points(&report.escape_routes),
+            },
+            scan_time: report.scan_time + shift,
+        })
+        .collect();
+    network.counter_intel_alerts.clear();
+
+    intel.jamming_active = snapshot.jamming_active;
+    intel.jamming_strength = snapshot.jamming_strength;
+    intel.intercept_chance = snapshot.intercept_chance;
+    intel.informant_reliability = snapshot.informant_reliability;
+    intel.counter_intel_level = snapshot.counter_intel_level;
+    intel.reports_gathered = snapshot.reports_gathered;
+    intel.intel_spent = snapshot.intel_spent;
+}
+
+fn snapshot_points(points: &[Vec3]) -> Vec<SavedVec3> {
+    points.iter().map(|point| point.to_array()).collect()
+}
+
+fn restore_points(points: &[SavedVec3]) -> Vec<Vec3> {
+    points.iter().copied().map(Vec3::from_array).collect()
+}
+
+fn snapshot_message(message: &RadioMessageType) -> RadioMessageSnapshot {
+    match message {
+        RadioMessageType::TroopMovement(pos, count) => {
+            RadioMessageSnapshot::TroopMovement(pos.to_array(), *count)
+        }
+        RadioMessageType::AirSupport(pos) => RadioMessageSnapshot::AirSupport(pos.to_array()),
+        RadioMessageType::SupplyDrop(pos) => RadioMessageSnapshot::SupplyDrop(pos.to_array()),
+        RadioMessageType::Retreat(pos) => RadioMessageSnapshot::Retreat(pos.to_array()),
+        RadioMessageType::Reinforcements(pos, eta) => {
+            RadioMessageSnapshot::Reinforcements(pos.to_array(), *eta)
+        }
+        RadioMessageType::StatusUpdate(msg) => RadioMessageSnapshot::StatusUpdate(msg.clone()),
+    }
+}
+
+fn restore_message(message: &RadioMessageSnapshot) -> RadioMessageType {
+    match message {
+        RadioMessageSnapshot::TroopMovement(pos, count) => {
+            RadioMessageType::TroopMovement(Vec3::from_array(*pos), *count)
+        }
+        RadioMessageSnapshot::AirSupport(pos) => {
+            RadioMessageType::AirSupport(Vec3::from_array(*pos))
+        }
+        RadioMessageSnapshot::SupplyDrop(pos) => {
+            RadioMessageType::SupplyDrop(Vec3::from_array(*pos))
+        }
+        RadioMessageSnapshot::Retreat(pos) => RadioMessageType::Retreat(Vec3::from_array(*pos)),
+        RadioMessageSnapshot::Reinforcements(pos, eta) => {
+            RadioMessageType::Reinforcements(Vec3::from_array(*pos), *eta)
+        }
+        RadioMessageSnapshot::StatusUpdate(msg) => RadioMessageType::StatusUpdate(msg.clone()),
+    }
+}
+
+fn snapshot_tip_type(tip_type: &TipType) -> TipTypeSnapshot {
+    match tip_type {
+        TipType::EnemyPosition(unit_type, count) => {
+            TipTypeSnapshot::EnemyPosition(unit_type.clone(), *count)
+        }
+        TipType::PlannedAttack(pos, eta) => TipTypeSnapshot::PlannedAttack(pos.to_array(), *eta),
+        TipType::WeakPoint(pos) => TipTypeSnapshot::WeakPoint(pos.to_array()),
+        TipType::SupplyRoute(from, to) => {
+            TipTypeSnapshot::SupplyRoute(from.to_array(), to.to_array())
+        }
+        TipType::CommandPost(pos) => TipTypeSnapshot::CommandPost(pos.to_array()),
+    }
+}
+
+fn restore_tip_type(tip_type: &TipTypeSnapshot) -> TipType {
+    match tip_type {
+        TipTypeSnapshot::EnemyPosition(unit_type, count) => {
+            TipType::EnemyPosition(unit_type.clone(), *count)
+        }
+        TipTypeSnapshot::PlannedAttack(pos, eta) => {
+            TipType::PlannedAttack(Vec3::from_array(*pos), *eta)
+        }
+        TipTypeSnapshot::WeakPoint(pos) => TipType::WeakPoint(Vec3::from_array(*pos)),
+        TipTypeSnapshot::SupplyRoute(from, to) => {
+            TipType::SupplyRoute(Vec3::from_array(*from), Vec3::from_array(*to))
+        }
+        TipTypeSnapshot::CommandPost(pos) => TipType::CommandPost(Vec3::from_array(*pos)),
+    }
+}
+
+fn snapshot_objective(
+    objective: &SquadObjective,
+    index_of: &HashMap<Entity, usize>,
+    rally_point: Option<Vec3>,
+) -> SquadObjectiveSnapshot {
+    match objective {
+        SquadObjective::Advance(pos) => SquadObjectiveSnapshot::Advance(pos.to_array()),
+        SquadObjective::Flank(from, target) => {
+            SquadObjectiveSnapshot::Flank(from.to_array(), target.to_array())
+        }
+        SquadObjective::Defend(pos) => SquadObjectiveSnapshot::Defend(pos.to_array()),
+        SquadObjective::Retreat(pos) => SquadObjectiveSnapshot::Retreat(pos.to_array()),
+        SquadObjective::Support(entity) => match index_of.get(entity) {
+            Some(index) => SquadObjectiveSnapshot::Support(*index),
+            // The supported unit is gone - fall back to regrouping
+            None => SquadObjectiveSnapshot::Regroup(rally_point.unwrap_or(Vec3::ZERO).to_array()),
+        },
+        SquadObjective::Suppress(pos) => SquadObjectiveSnapshot::Suppress(pos.to_array()),
+        SquadObjective::Regroup(pos) => SquadObjectiveSnapshot::Regroup(pos.to_array()),
+    }
+}
+
+fn restore_objective(objective: &SquadObjectiveSnapshot, entities: &[Entity]) -> SquadObjective {
+    match objective {
+        SquadObjectiveSnapshot::Advance(pos) => SquadObjective::Advance(Vec3::from_array(*pos)),
+        SquadObjectiveSnapshot::Flank(from, target) => {
+            SquadObjective::Flank(Vec3::from_array(*from), Vec3::from_array(*target))
+        }
+        SquadObjectiveSnapshot::Defend(pos) => SquadObjective::Defend(Vec3::from_array(*pos)),
+        SquadObjectiveSnapshot::Retreat(pos) => SquadObjective::Retreat(Vec3::from_array(*pos)),
+        SquadObjectiveSnapshot::Support(index) => SquadObjective::Support(entities[*index]),
+        SquadObjectiveSnapshot::Suppress(pos) => SquadObjective::Suppress(Vec3::from_array(*pos)),
+        SquadObjectiveSnapshot::Regroup(pos) => SquadObjective::Regroup(Vec3::from_array(*pos)),
+    }
+}
+
+// ==================== SNAPSHOT RESTORE ====================
+
+// Inserted when a save is loaded; applied once game assets are available
+#[derive(Resource)]
+pub struct PendingWorldRestore(pub WorldSnapshot);
+
+#[allow(clippy::too_many_arguments)]
+pub fn world_restore_system(
+    mut commands: Commands,
+    pending: Res<PendingWorldRestore>,
+    game_assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    time: Res<Time>,
+    mut campaign: ResMut<Campaign>,
+    mut ai_director: ResMut<AiDirector>,
+    mut political_state: ResMut<PoliticalState>,
+    mut intel: ResMut<IntelSystem>,
+    mut social_media: ResMut<SocialMediaInfluence>,
+    mut environment: ResMut<EnvironmentalState>,
+    stale_entities: Query<
+        Entity,
+        Or<(
+            With<Unit>,
+            With<IntelOperator>,
+            With<Squad>,
+            With<HealthBar>,
+            With<WaveSpawner>,
+        )>,
+    >,
+    objectives: Query<(), With<Objective>>,
+) {
+    let snapshot = &pending.0;
+
+    for entity in stale_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // Spawn every unit first so targets and squad links can be resolved by index
+    let entities: Vec<Entity> = snapshot
+        .units
+        .iter()
+        .map(|saved| {
+            let position = Vec3::from_array(saved.position);
+            if saved.unit_type == UnitType::Ovidio {
+                spawn_ovidio(&mut commands, position, &game_assets, &catalog)
+            } else {
+                spawn_unit(
+                    &mut commands,
+                    saved.unit_type.clone(),
+                    saved.faction.clone(),
+                    iso_to_world(position),
+                    &game_assets,
+                    &catalog,
+                )
+            }
+        })
+        .collect();
+
+    for (saved, entity) in snapshot.units.iter().zip(&entities) {
+        let mut unit_commands = commands.entity(*entity);
+
+        unit_commands.insert((
+            Unit {
+                health: saved.health,
+                max_health: saved.max_health,
+                faction: saved.faction.clone(),
+                unit_type: saved.unit_type.clone(),
+                damage: saved.damage,
+                range: saved.range,
+                vision: catalog.definition(&saved.unit_type).vision,
+                movement_speed: saved.movement_speed,
+                target: saved.target.map(|index| entities[index]),
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                experience: saved.experience,
+                kills: saved.kills,
+                veterancy_level: saved.veterancy_level.clone(),
+                equipment: saved.equipment.clone(),
+            },
+            Movement {
+                target_position: saved.move_target.map(Vec3::from_array),
+                speed: saved.movement_speed,
+            },
+        ));
+
+        if let Some(formation) = &saved.formation {
+            unit_commands.insert((
+                Formation {
+                    formation_type: formation.formation_type.clone(),
+                    position_in_formation: formation.position_in_formation,
+                    squad_id: formation.squad_id,
+                    formation_center: Vec3::from_array(formation.formation_center),
+                    formation_facing: formation.formation_facing,
+                },
+                Communication {
+                    radio_range: BASE_RADIO_RANGE,
+                    last_report_time: 0.0,
+                    known_enemies: Vec::new(),
+                    received_orders: Vec::new(),
+                },
+            ));
+        }
+
+        if let Some(state) = &saved.tactical_state {
+            unit_commands.insert(TacticalState {
+                current_state: state.current_state.clone(),
+                state_timer: 0.0,
+                last_state_change: 0.0,
+                suppression_level: state.suppression_level,
+                morale: state.morale,
+            });
+        }
+    }
+
+    let operators: Vec<Entity> = snapshot
+        .intel_operators
+        .iter()
+        .map(|operator| {
+            let entity = spawn_intel_operator(
+                &mut commands,
+                operator.intel_type.clone(),
+                iso_to_world(Vec3::from_array(operator.position)),
+                &game_assets,
+                &balance,
+            );
+            if let Some(source) = &operator.source {
+                commands.entity(entity).insert(source.clone());
+            }
+            entity
+        })
+        .collect();
+
+    for squad in &snapshot.squads {
+        commands.spawn(Squad {
+            id: squad.id,
+            leader: squad.leader.map(|index| entities[index]),
+            members: squad.members.iter().map(|index| entities[*index]).collect(),
+            squad_type: squad.squad_type.clone(),
+            current_objective: restore_objective(&squad.current_objective, &entities),
+            rally_point: squad.rally_point.map(Vec3::from_array),
+            cohesion_radius: squad.cohesion_radius,
+        });
+    }
+
+    let mut next_wave_timer = Timer::from_seconds(10.0, TimerMode::Repeating);
+    let (wave_number, units_in_wave) = match &snapshot.wave_spawner {
+        Some(wave) => {
+            next_wave_timer.set_elapsed(std::time::Duration::from_secs_f32(wave.timer_elapsed));
+            (wave.wave_number, wave.units_in_wave)
+        }
+        None => (0, 2),
+    };
+    commands.spawn(WaveSpawner {
+        next_wave_timer,
+        wave_number,
+        units_in_wave,
+    });
+
+    if objectives.is_empty() {
+        spawn_safehouses(&mut commands, &game_assets);
+    }
+
+    campaign.mission_timer = snapshot.campaign.mission_timer;
+    campaign.objectives_completed = snapshot.campaign.objectives_completed;
+    campaign.current_objectives = snapshot.campaign.current_objectives.clone();
+    campaign.political_pressure = snapshot.campaign.political_pressure.clone();
+    *ai_director = snapshot.ai_director.clone();
+    *political_state = snapshot.political_state.clone();
+    if let Some(saved) = &snapshot.intel {
+        restore_intel(
+            &mut intel,
+            saved,
+            &operators,
+            time.elapsed_seconds() - saved.saved_at,
+        );
+    }
+    if let Some(saved) = &snapshot.social_media {
+        *social_media = saved.clone();
+    }
+    if let Some(saved) = &snapshot.environment {
+        *environment = saved.clone();
+    }
+
+    commands.insert_resource(GameSetupComplete);
+    commands.remove_resource::<PendingWorldRestore>();
+
+    info!(
+        "✅ World restored: {} units, {} squads",
+        snapshot.units.len(),
+        snapshot.squads.len()
+    );
+    play_tactical_sound("radio", &tr("radio.world_restored"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_support_objective_falls_back_to_rally_point() {
+        let index_of = HashMap::new();
+        let objective = SquadObjective::Support(Entity::from_raw(42));
+
+        match snapshot_objective(&objective, &index_of, Some(Vec3::new(1.0, 2.0, 0.0))) {
+            SquadObjectiveSnapshot::Regroup(pos) => assert_eq!(pos, [1.0, 2.0, 0.0]),
+            _ => panic!("Missing support target should fall back to regroup"),
+        }
+    }
+
+    #[test]
+    fn test_intel_reports_survive_a_save_with_their_age_and_source() {
+        let informant = Entity::from_raw(7);
+        let mut intel = IntelSystem::default();
+        intel.reports_gathered = 12;
+        intel.intel_spent = 5;
+        intel
+            .global_intel_network
+            .informant_reports
+            .push(InformantTip {
+                tip_type: TipType::CommandPost(Vec3::new(10.0, 20.0, 0.0)),
+                location: Vec3::new(10.0, 20.0, 0.0),
+                confidence: 0.7,
+                time_received: 100.0,
+                urgency: TipUrgency::High,
+                source: Some(informant),
+                planted: true,
+            });
+        intel
+            .global_intel_network
+            .active_intercepts
+            .push(RadioIntercept {
+                message_type: RadioMessageType::TroopMovement(Vec3::new(5.0, 5.0, 0.0), 4),
+                source_position: Vec3::new(5.0, 5.0, 0.0),
+                intercept_time: 110.0,
+                reliability: 0.8,
+                content: "Alpha team moving".to_string(),
+            });
+        let index_of = HashMap::from([(informant, 0)]);
+        let json = serde_json::to_string(&snapshot_intel(&intel, &index_of, 120.0)).unwrap();
+        let saved: IntelSnapshot = serde_json::from_str(&json).unwrap();
+
+        // Loaded into a session whose clock reads 20 when the restore runs
+        let restored_informant = Entity::from_raw(3);
+        let mut restored = IntelSystem::default();
+        restore_intel(
+            &mut restored,
+            &saved,
+            &[restored_informant],
+            20.0 - saved.saved_at,
+        );
+        assert_eq!(restored.intel_points(), 7);
+        let tip = &restored.global_intel_network.informant_reports[0];
+        assert_eq!(tip.source, Some(restored_informant));
+        assert!(tip.planted);
+        assert!((tip.time_received - 0.0).abs() < 1e-4);
+        assert_eq!(
+            tip.tip_type,
+            TipType::CommandPost(Vec3::new(10.0, 20.0, 0.0))
+        );
+        let intercept = &restored.global_intel_network.active_intercepts[0];
+        assert!((intercept.intercept_time - 10.0).abs() < 1e-4);
+        assert_eq!(
+            intercept.message_type,
+            RadioMessageType::TroopMovement(Vec3::new(5.0, 5.0, 0.0), 4)
+        );
+    }
+
+    #[test]
+    fn test_iso_round_trip() {
+        let world = Vec3::new(120.0, -45.0, 0.0);
+        let restored = iso_to_world(crate::utils::world_to_iso(world));
+
+        assert!((restored - world).length() < 0.01);
+    }
+}
@@ -0,0 +1,393 @@
+use crate::assists::{MissionAssist, MissionAssists};
+use crate::campaign::{Campaign, MissionConfig};
+use crate::components::*;
+use crate::config::{BalanceConfig, ScoringBalance};
+use crate::daily::DailyChallengeState;
+use crate::localization::{tr, tr_args};
+use crate::political_system::PoliticalState;
+use crate::resources::{GameState, IntelSystem};
+use crate::save::save_system::MissionId;
+use crate::utils::play_tactical_sound;
+use bevy::prelude::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// ==================== MISSION SCORING PLUGIN ====================
+
+// A won mission is scored line by line - kills, time left, fighters lost,
+// civilians kept out of it, intel gathered - so the victory screen can show
+// where the points came from. Challenge modifiers picked on the briefing
+// screen make the mission harder and multiply the total, retry assists lower
+// it, and the total earns
+// a bronze, silver or gold medal against thresholds scaled to the mission's
+// difficulty. The best result per mission is kept in the campaign progress;
+// a daily challenge is scored the same way but kept out of the campaign.
+
+pub struct MissionScoringPlugin;
+
+impl Plugin for MissionScoringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChallengeSettings>()
+            .init_resource::<MissionScore>()
+            .add_systems(Update, mission_scoring_system);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Medal {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl Medal {
+    pub fn label(&self) -> String {
+        match self {
+            Medal::Bronze => tr("score.medal_bronze"),
+            Medal::Silver => tr("score.medal_silver"),
+            Medal::Gold => tr("score.medal_gold"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChallengeModifier {
+    NoReinforcements, // No call-ins and no safehouse recruits
+    Permadeath,       // Downed fighters are never taken alive, so never come back
+}
+
+impl ChallengeModifier {
+    pub const ALL: [ChallengeModifier; 2] = [
+        ChallengeModifier::NoReinforcements,
+        ChallengeModifier::Permadeath,
+    ];
+
+    // Toggled on the briefing screen
+    pub fn key(&self) -> KeyCode {
+        match self {
+            ChallengeModifier::NoReinforcements => KeyCode::N,
+            ChallengeModifier::Permadeath => KeyCode::P,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            ChallengeModifier::NoReinforcements => tr("score.challenge_no_reinforcements"),
+            ChallengeModifier::Permadeath => tr("score.challenge_permadeath"),
+        }
+    }
+
+    pub fn multiplier(&self, balance: &ScoringBalance) -> f32 {
+        match self {
+            ChallengeModifier::NoReinforcements => balance.no_reinforcements_multiplier,
+            ChallengeModifier::Permadeath => balance.permadeath_multiplier,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ChallengeSettings {
+    pub active: Vec<ChallengeModifier>,
+}
+
+impl ChallengeSettings {
+    pub fn is_active(&self, modifier: ChallengeModifier) -> bool {
+        self.active.contains(&modifier)
+    }
+
+    pub fn toggle(&mut self, modifier: ChallengeModifier) {
+        if let Some(index) = self.active.iter().position(|m| *m == modifier) {
+            self.active.remove(index);
+        } else {
+            self.active.push(modifier);
+        }
+    }
+
+    // Modifiers stack multiplicatively
+    pub fn multiplier(&self, balance: &ScoringBalance) -> f32 {
+        self.active
+            .iter()
+            .map(|modifier| modifier.multiplier(balance))
+            .product()
+    }
+}
+
+/// Whether a challenge modifier is on; false where no settings exist.
+pub fn challenge_active(settings: Option<&ChallengeSettings>, modifier: ChallengeModifier) -> bool {
+    settings.is_some_and(|settings| settings.is_active(modifier))
+}
+
+// ==================== SCORE BREAKDOWN ====================
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    pub combat: u32,
+    pub speed: u32,
+    pub losses: u32, // Subtracted
+    pub civilian_safety: u32,
+    pub intel: u32,
+    pub multiplier: f32,
+    pub total: u32,
+}
+
+// What a won mission is scored from
+pub struct MissionTally {
+    pub combat_score: u32,
+    pub completion_time: f32,
+    pub time_limit: Option<f32>,
+    pub cartel_lost: u32,
+    pub civilian_casualties: u32,
+    pub intel_reports: u32,
+}
+
+pub fn score_breakdown(
+    tally: &MissionTally,
+    multiplier: f32,
+    balance: &ScoringBalance,
+) -> ScoreBreakdown {
+    let par = tally.time_limit.unwrap_or(balance.untimed_par);
+    let speed = ((par - tally.completion_time).max(0.0) * balance.speed_per_second) as u32;
+    let losses = (tally.cartel_lost as f32 * balance.loss_penalty) as u32;
+    let tolerance = balance.civilian_tolerance.max(1) as f32;
+    let civilian_safety = (balance.civilian_safety
+        * (1.0 - tally.civilian_casualties as f32 / tolerance).max(0.0))
+        as u32;
+    let intel = (tally.intel_reports.min(balance.intel_report_cap) as f32
+        * balance.intel_per_report) as u32;
+
+    let subtotal = (tally.combat_score + speed + civilian_safety + intel).saturating_sub(losses);
+    ScoreBreakdown {
+        combat: tally.combat_score,
+        speed,
+        losses,
+        civilian_safety,
+        intel,
+        multiplier,
+        total: (subtotal as f32 * multiplier).round() as u32,
+    }
+}
+
+/// The best medal a total earns, against thresholds scaled by the mission's
+/// difficulty.
+pub fn medal_for(total: u32, difficulty: f32, balance: &ScoringBalance) -> Option<Medal> {
+    let total = total as f32;
+    if total >= balance.gold * difficulty {
+        Some(Medal::Gold)
+    } else if total >= balance.silver * difficulty {
+        Some(Medal::Silver)
+    } else if total >= balance.bronze * difficulty {
+        Some(Medal::Bronze)
+    } else {
+        None
+    }
+}
+
+// The best result for a mission, kept in the campaign progress
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MissionRecord {
+    pub score: u32,
+    pub medal: Option<Medal>,
+    #[serde(default)]
+    pub challenges: Vec<ChallengeModifier>,
+    #[serde(default)]
+    pub assists: Vec<MissionAssist>,
+}
+
+// ==================== SCORING SYSTEM ====================
+
+// The latest won mission's score, for the victory screen
+#[derive(Resource, Default)]
+pub struct MissionScore {
+    pub mission: Option<MissionId>,
+    pub breakdown: ScoreBreakdown,
+    pub medal: Option<Medal>,
+    pub challenges: Vec<ChallengeModifier>,
+    pub assists: Vec<MissionAssist>,
+    pub completion_time: f32,
+    pub daily: Option<NaiveDate>, // Set when the win was a daily challenge
+}
+
+impl MissionScore {
+    // Breakdown lines for the victory screen
+    pub fn lines(&self) -> Vec<String> {
+        let breakdown = &self.breakdown;
+        let mut lines = vec![
+            tr_args("score.combat", &[("points", &breakdown.combat)]),
+            tr_args("score.speed", &[("points", &breakdown.speed)]),
+            tr_args("score.losses", &[("points", &breakdown.losses)]),
+            tr_args(
+                "score.civilian_safety",
+                &[("points", &breakdown.civilian_safety)],
+            ),
+            tr_args("score.intel", &[("points", &breakdown.intel)]),
+        ];
+        for challenge in &self.challenges {
+            lines.push(tr_args(
+                "score.challenge_line",
+                &[("name", &challenge.label())],
+            ));
+        }
+        for assist in &self.assists {
+            lines.push(tr_args("score.assist_line", &[("name", &assist.label())]));
+        }
+        if self.challenges.is_empty() && self.assists.is_empty() {
+            lines.push(tr("score.no_challenges"));
+        } else {
+            let multiplier = format!("{:.2}", breakdown.multiplier);
+            lines.push(tr_args("score.multiplier", &[("multiplier", &multiplier)]));
+        }
+        lines
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mission_scoring_system(
+    game_state: Res<GameState>,
+    mut campaign: ResMut<Campaign>,
+    mut score: ResMut<MissionScore>,
+    challenges: Res<ChallengeSettings>,
+    balance: Res<BalanceConfig>,
+    daily: Option<Res<DailyChallengeState>>,
+    political_state: Option<Res<PoliticalState>>,
+    intel_system: Option<Res<IntelSystem>>,
+    assists: Option<Res<MissionAssists>>,
+    unit_query: Query<&Unit>,
+) {
+    if game_state.game_phase != GamePhase::Victory {
+        // Scored again on the next win
+        if game_state.game_phase == GamePhase::Preparation {
+            score.mission = None;
+        }
+        return;
+    }
+    if score.mission.is_some() {
+        return;
+    }
+
+    let mission_id = campaign.progress.current_mission.clone();
+    let config = MissionConfig::get_mission_config(&mission_id);
+    let tally = MissionTally {
+        combat_score: game_state.cartel_score,
+        completion_time: game_state.mission_timer,
+        time_limit: campaign.time_limit(&config),
+        cartel_lost: unit_query
+            .iter()
+            .filter(|unit| unit.faction == Faction::Cartel && unit.health <= 0.0)
+            .count() as u32
+            + game_state.cleared_dead(&Faction::Cartel),
+        civilian_casualties: political_state.map_or(0, |state| state.casualties_civilian),
+        intel_reports: intel_system.map_or(0, |intel| intel.reports_gathered),
+    };
+    let multiplier = challenges.multiplier(&balance.scoring)
+        * assists
+            .as_deref()
+            .map_or(1.0, |assists| assists.multiplier(&balance.scoring));
+    let assists = assists.map_or_else(Vec::new, |assists| assists.running.clone());
+    let breakdown = score_breakdown(&tally, multiplier, &balance.scoring);
+    let medal = medal_for(
+        breakdown.total,
+        config.difficulty_modifier,
+        &balance.scoring,
+    );
+
+    let timer = game_state.mission_timer;
+    let daily = daily.and_then(|daily| daily.running_date());
+    if daily.is_none() {
+        campaign.progress.record_mission(
+            &mission_id,
+            MissionRecord {
+                score: breakdown.total,
+                medal,
+                challenges: challenges.active.clone(),
+                assists: assists.clone(),
+            },
+        );
+        campaign
+            .progress
+            .complete_mission(mission_id.clone(), timer, breakdown.total);
+    }
+
+    match medal {
+        Some(medal) => play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.medal_earned",
+                &[("medal", &medal.label()), ("score", &breakdown.total)],
+            ),
+        ),
+        None => info!("✅ Mission scored {} - no medal", breakdown.total),
+    }
+
+    *score = MissionScore {
+        mission: Some(mission_id),
+        breakdown,
+        medal,
+        challenges: challenges.active.clone(),
+        assists,
+        completion_time: timer,
+        daily,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally() -> MissionTally {
+        MissionTally {
+            combat_score: 400,
+            completion_time: 200.0,
+            time_limit: Some(300.0),
+            cartel_lost: 4,
+            civilian_casualties: 5,
+            intel_reports: 100,
+        }
+    }
+
+    #[test]
+    fn test_breakdown_adds_up() {
+        let balance = ScoringBalance::default();
+        let breakdown = score_breakdown(&tally(), 1.0, &balance);
+
+        assert_eq!(breakdown.speed, 200); // 100s left at 2 points
+        assert_eq!(breakdown.losses, 100);
+        assert_eq!(breakdown.civilian_safety, 250); // Half the tolerance used
+        assert_eq!(breakdown.intel, 400); // Capped at 40 reports
+        assert_eq!(breakdown.total, 400 + 200 + 250 + 400 - 100);
+    }
+
+    #[test]
+    fn test_challenges_multiply_the_total() {
+        let balance = ScoringBalance::default();
+        let mut settings = ChallengeSettings::default();
+        settings.toggle(ChallengeModifier::NoReinforcements);
+        settings.toggle(ChallengeModifier::Permadeath);
+        let multiplier = settings.multiplier(&balance);
+        assert!((multiplier - 1.25 * 1.5).abs() < 1e-6);
+
+        let plain = score_breakdown(&tally(), 1.0, &balance);
+        let challenged = score_breakdown(&tally(), multiplier, &balance);
+        assert_eq!(
+            challenged.total,
+            (plain.total as f32 * multiplier).round() as u32
+        );
+
+        settings.toggle(ChallengeModifier::Permadeath);
+        assert!(!settings.is_active(ChallengeModifier::Permadeath));
+        assert!(challenge_active(
+            Some(&settings),
+            ChallengeModifier::NoReinforcements
+        ));
+        assert!(!challenge_active(None, ChallengeModifier::NoReinforcements));
+    }
+
+    #[test]
+    fn test_medal_thresholds_scale_with_difficulty() {
+        let balance = ScoringBalance::default();
+        assert_eq!(medal_for(500, 1.0, &balance), None);
+        assert_eq!(medal_for(600, 1.0, &balance), Some(Medal::Bronze));
+        assert_eq!(medal_for(1800, 1.0, &balance), Some(Medal::Gold));
+        // A gold on an easy mission is only silver on a hard one
+        assert_eq!(medal_for(1800, 1.5, &balance), Some(Medal::Silver));
+    }
+}
@@ -0,0 +1,540 @@
+use crate::app_state::AppState;
+use crate::campaign::{Campaign, ObjectiveStatus, PoliticalPressure};
+use crate::components::*;
+use crate::config::{BalanceConfig, UnitCatalog};
+use crate::corpse_system::Corpse;
+use crate::dialogue::{display_text, Conversation, Conversations};
+use crate::diplomacy::{Diplomacy, Stance};
+use crate::localization::tr;
+use crate::map::{is_area_controlled, CityMap};
+use crate::objectives::MissionObjective;
+use crate::political_system::PoliticalState;
+use crate::resources::{GameAssets, GameState};
+use crate::spawners::spawn_unit;
+use crate::tutorial::{Tutorial, TutorialAction, TutorialHighlight};
+use crate::utils::{iso_to_world, play_tactical_sound};
+use bevy::prelude::*;
+use serde::de::{DeserializeOwned, IntoDeserializer};
+use std::collections::{HashMap, VecDeque};
+
+#[cfg(feature = "scripting")]
+use crate::utils::SimulationSet;
+#[cfg(feature = "scripting")]
+use crate::{app_state::GameSet, corpse_system::register_corpses_system};
+
+#[cfg(feature = "scripting")]
+pub mod runtime;
+#[cfg(feature = "scripting")]
+pub use runtime::*;
+
+// ==================== MISSION SCRIPTING ====================
+
+// Rhai scripts in assets/scripts/ and in each active mod pack's scripts/
+// folder react to what happens in a mission without recompiling the game.
+// A script defines any of these hooks:
+//   on_phase_change(from, to)    phase names, e.g. "Preparation", "InitialRaid"
+//   on_unit_killed(unit)         #{ faction, type, x, y }
+//   on_zone_captured(zone, faction)
+//   on_tick(mission_time)        once a second during a mission
+//   on_player_action(action)     tutorial only, e.g. "CameraMoved", "Continue"
+//   on_dialogue_choice(id, index) a choice picked in conversation `id`, from 0
+// and can call:
+//   mission(), phase(), mission_time(), unit_count(faction), units(faction),
+//   objectives(), pressure(kind), tutorial()     - read a snapshot of the game
+//   spawn_unit(faction, type, x, y), order_move(faction, [type,] x, y),
+//   add_pressure(kind, amount), shift_threshold(amount),
+//   set_stance(faction, faction, stance), show_dialogue(speaker, text),
+//   conversation(id, lines), show_prompt(key, highlight), end_tutorial()
+// show_dialogue is a radio line over the battle; conversation plays lines of
+// #{ speaker, text, portrait, focus, choices } with the battle paused (see
+// src/dialogue.rs). Stances are "Hostile", "Neutral" or "Allied" (see
+// src/diplomacy.rs). shift_threshold moves the pressure the government can
+// take before it gives Ovidio up; a negative amount brings that closer.
+// Scripts never touch the world directly: calls are queued as ScriptCommands
+// and applied after the hooks have run. `this` is a map that persists between
+// calls, for state a script wants to keep.
+// Without the `scripting` feature the hooks are never collected and scripts
+// are ignored.
+
+const SCRIPT_TICK_SECONDS: f32 = 1.0;
+const DIALOGUE_SECONDS: f32 = 5.0;
+// How far scripts may move the government's breaking point
+const MIN_DECISION_THRESHOLD: f32 = 0.1;
+const MAX_DECISION_THRESHOLD: f32 = 0.9;
+
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScriptDialogue>()
+            .add_systems(Update, script_dialogue_system);
+
+        #[cfg(feature = "scripting")]
+        app.insert_resource(ScriptRuntime::load())
+            .init_resource::<ScriptTracker>()
+            .init_resource::<ScriptHookQueue>()
+            .init_resource::<ScriptCommandQueue>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    collect_script_hooks_system,
+                    run_script_hooks_system,
+                    apply_script_commands_system,
+                )
+                    .chain()
+                    .after(register_corpses_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+// Faction, unit type and similar names as scripts write them, e.g. "Sicario"
+pub fn parse_variant<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+        name.into_deserializer();
+    T::deserialize(deserializer).ok()
+}
+
+// ==================== HOOKS ====================
+
+#[derive(Clone, Debug)]
+pub enum ScriptHook {
+    PhaseChange {
+        from: GamePhase,
+        to: GamePhase,
+    },
+    UnitKilled {
+        faction: Faction,
+        unit_type: UnitType,
+        position: Vec2,
+    },
+    ZoneCaptured {
+        zone: String,
+        faction: Faction,
+    },
+    Tick {
+        mission_time: f32,
+    },
+    PlayerAction {
+        action: TutorialAction,
+    },
+    DialogueChoice {
+        conversation: String,
+        choice: usize,
+    },
+}
+
+impl ScriptHook {
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            ScriptHook::PhaseChange { .. } => "on_phase_change",
+            ScriptHook::UnitKilled { .. } => "on_unit_killed",
+            ScriptHook::ZoneCaptured { .. } => "on_zone_captured",
+            ScriptHook::Tick { .. } => "on_tick",
+            ScriptHook::PlayerAction { .. } => "on_player_action",
+            ScriptHook::DialogueChoice { .. } => "on_dialogue_choice",
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ScriptHookQueue(pub Vec<ScriptHook>);
+
+#[derive(Resource, Default)]
+pub struct ScriptTracker {
+    last_phase: Option<GamePhase>,
+    zone_holders: HashMap<String, Faction>,
+    tick_timer: f32,
+}
+
+// A zone changes hands once one side has a clear majority of the fighters in
+// it; contested or empty zones stay with whoever held them last
+pub fn zone_holder(cartel: usize, military: usize) -> Option<Faction> {
+    let fighters = (cartel + military) as f32;
+    if fighters == 0.0 {
+        None
+    } else if is_area_controlled(cartel as f32 / fighters) {
+        Some(Faction::Cartel)
+    } else if is_area_controlled(military as f32 / fighters) {
+        Some(Faction::Military)
+    } else {
+        None
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn collect_script_hooks_system(
+    game_state: Res<GameState>,
+    mut tutorial: ResMut<Tutorial>,
+    mut conversations: ResMut<Conversations>,
+    city_map: Res<CityMap>,
+    mut tracker: ResMut<ScriptTracker>,
+    mut hooks: ResMut<ScriptHookQueue>,
+    killed_query: Query<(&Unit, &Transform), Added<Corpse>>,
+    unit_query: Query<(&Unit, &Transform), Without<Corpse>>,
+    time: Res<Time>,
+) {
+    let phase = game_state.game_phase.clone();
+    match tracker.last_phase.replace(phase.clone()) {
+        Some(from) if from != phase => hooks.0.push(ScriptHook::PhaseChange { from, to: phase }),
+        _ => {}
+    }
+
+    for (unit, transform) in killed_query.iter() {
+        hooks.0.push(ScriptHook::UnitKilled {
+            faction: unit.faction.clone(),
+            unit_type: unit.unit_type.clone(),
+            position: transform.translation.truncate(),
+        });
+    }
+
+    for zone in &city_map.capture_zones {
+        let (mut cartel, mut military) = (0, 0);
+        for (unit, transform) in unit_query.iter() {
+            if unit.health <= 0.0
+                || !city_map.in_capture_zone(zone, transform.translation.truncate())
+            {
+                continue;
+            }
+            match unit.faction {
+                Faction::Cartel => cartel += 1,
+                Faction::Military => military += 1,
+                _ => {}
+            }
+        }
+
+        let Some(holder) = zone_holder(cartel, military) else {
+            continue;
+        };
+        if tracker.zone_holders.get(&zone.name) != Some(&holder) {
+            tracker
+                .zone_holders
+                .insert(zone.name.clone(), holder.clone());
+            hooks.0.push(ScriptHook::ZoneCaptured {
+                zone: zone.name.clone(),
+                faction: holder,
+            });
+        }
+    }
+
+    if game_state.game_phase.app_state() == AppState::InGame {
+        tracker.tick_timer += time.delta_seconds();
+        if tracker.tick_timer >= SCRIPT_TICK_SECONDS {
+            tracker.tick_timer -= SCRIPT_TICK_SECONDS;
+            hooks.0.push(ScriptHook::Tick {
+                mission_time: game_state.mission_timer,
+            });
+        }
+    }
+
+    for action in tutorial.take_actions() {
+        hooks.0.push(ScriptHook::PlayerAction { action });
+    }
+    for (conversation, choice) in conversations.take_choices() {
+        hooks.0.push(ScriptHook::DialogueChoice {
+            conversation,
+            choice,
+        });
+    }
+}
+
+// ==================== WORLD SNAPSHOT ====================
+
+#[derive(Clone, Debug)]
+pub struct ScriptUnitView {
+    pub faction: Faction,
+    pub unit_type: UnitType,
+    pub position: Vec2,
+    pub health: f32,
+}
+
+// What the query functions see while a batch of hooks runs
+#[derive(Clone, Debug, Default)]
+pub struct ScriptWorldView {
+    pub mission: String,
+    pub phase: String,
+    pub mission_time: f32,
+    pub units: Vec<ScriptUnitView>,
+    pub objectives: Vec<ObjectiveStatus>,
+    pub pressure: PoliticalPressure,
+    pub tutorial: bool,
+}
+
+impl ScriptWorldView {
+    pub fn capture(
+        game_state: &GameState,
+        campaign: &Campaign,
+        unit_query: &Query<(&Unit, &Transform), Without<Corpse>>,
+    ) -> Self {
+        Self {
+            mission: format!("{:?}", campaign.progress.current_mission),
+            phase: format!("{:?}", game_state.game_phase),
+            mission_time: game_state.mission_timer,
+            units: unit_query
+                .iter()
+                .filter(|(unit, _)| unit.health > 0.0)
+                .map(|(unit, transform)| ScriptUnitView {
+                    faction: unit.faction.clone(),
+                    unit_type: unit.unit_type.clone(),
+                    position: transform.translation.truncate(),
+                    health: unit.health,
+                })
+                .collect(),
+            objectives: campaign.current_objectives.clone(),
+            pressure: campaign.political_pressure.clone(),
+            tutorial: false,
+        }
+    }
+}
+
+pub fn objective_kind(objective: &MissionObjective) -> (String, String) {
+    (objective.kind.clone(), objective.target.to_string())
+}
+
+// Pressure components scripts may nudge; pressure("total") is read-only
+pub const ADJUSTABLE_PRESSURES: [&str; 5] = [
+    "civilian_impact",
+    "economic_disruption",
+    "media_attention",
+    "political_families",
+    "military_morale",
+];
+
+pub fn pressure_value(pressure: &PoliticalPressure, kind: &str) -> Option<f32> {
+    match kind {
+        "civilian_impact" => Some(pressure.civilian_impact),
+        "economic_disruption" => Some(pressure.economic_disruption),
+        "media_attention" => Some(pressure.media_attention),
+        "political_families" => Some(pressure.political_families),
+        "military_morale" => Some(pressure.military_morale),
+        "total" => Some(pressure.total_pressure),
+        _ => None,
+    }
+}
+
+pub fn pressure_component<'a>(
+    pressure: &'a mut PoliticalPressure,
+    kind: &str,
+) -> Option<&'a mut f32> {
+    match kind {
+        "civilian_impact" => Some(&mut pressure.civilian_impact),
+        "economic_disruption" => Some(&mut pressure.economic_disruption),
+        "media_attention" => Some(&mut pressure.media_attention),
+        "political_families" => Some(&mut pressure.political_families),
+        "military_morale" => Some(&mut pressure.military_morale),
+        _ => None,
+    }
+}
+
+// ==================== SCRIPT COMMANDS ====================
+
+#[derive(Clone, Debug)]
+pub enum ScriptCommand {
+    SpawnUnit {
+        faction: Faction,
+        unit_type: UnitType,
+        position: Vec2,
+    },
+    OrderMove {
+        faction: Faction,
+        unit_type: Option<UnitType>,
+        target: Vec2,
+    },
+    AddPressure {
+        kind: String,
+        amount: f32,
+    },
+    ShiftThreshold {
+        amount: f32,
+    },
+    SetStance {
+        a: Faction,
+        b: Faction,
+        stance: Stance,
+    },
+    ShowDialogue {
+        speaker: String,
+        text: String,
+    },
+    StartConversation(Conversation),
+    ShowPrompt {
+        key: String,
+        highlight: TutorialHighlight,
+    },
+    EndTutorial,
+}
+
+#[derive(Resource, Default)]
+pub struct ScriptCommandQueue(pub Vec<ScriptCommand>);
+
+#[allow(clippy::too_many_arguments)]
+pub fn apply_script_commands_system(
+    mut commands: Commands,
+    mut queue: ResMut<ScriptCommandQueue>,
+    game_assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    mut campaign: ResMut<Campaign>,
+    mut dialogue: ResMut<ScriptDialogue>,
+    mut conversations: ResMut<Conversations>,
+    mut tutorial: ResMut<Tutorial>,
+    mut diplomacy: ResMut<Diplomacy>,
+    mut political_state: Option<ResMut<PoliticalState>>,
+    mut unit_query: Query<(&Unit, &mut Movement), Without<Corpse>>,
+) {
+    for command in queue.0.drain(..) {
+        match command {
+            ScriptCommand::SpawnUnit {
+                faction,
+                unit_type,
+                position,
+            } => {
+                // Scripts work in on-screen coordinates, the spawner in world ones
+                let world_position = iso_to_world(position.extend(0.0));
+                spawn_unit(
+                    &mut commands,
+                    unit_type,
+                    faction,
+                    world_position,
+                    &game_assets,
+                    &catalog,
+                );
+            }
+            ScriptCommand::OrderMove {
+                faction,
+                unit_type,
+                target,
+            } => {
+                for (unit, mut movement) in unit_query.iter_mut() {
+                    let matches_type = unit_type.as_ref().is_none_or(|t| *t == unit.unit_type);
+                    if unit.faction == faction && unit.health > 0.0 && matches_type {
+                        movement.target_position = Some(target.extend(0.0));
+                    }
+                }
+            }
+            ScriptCommand::AddPressure { kind, amount } => {
+                let pressure = &mut campaign.political_pressure;
+                if let Some(value) = pressure_component(pressure, &kind) {
+                    *value = (*value + amount).clamp(0.0, 1.0);
+                    pressure.update_pressure(&balance.pressure);
+                }
+            }
+            ScriptCommand::ShiftThreshold { amount } => {
+                if let Some(state) = political_state.as_mut() {
+                    state.decision_threshold = (state.decision_threshold + amount)
+                        .clamp(MIN_DECISION_THRESHOLD, MAX_DECISION_THRESHOLD);
+                }
+            }
+            ScriptCommand::SetStance { a, b, stance } => diplomacy.set_stance(&a, &b, stance),
+            ScriptCommand::ShowDialogue { speaker, text } => {
+                let (speaker, text) = (display_text(&speaker), display_text(&text));
+                play_tactical_sound("radio", &format!("{}: {}", speaker, text));
+                dialogue.lines.push_back((speaker, text));
+            }
+            ScriptCommand::StartConversation(conversation) => conversations.start(conversation),
+            ScriptCommand::ShowPrompt { key, highlight } => tutorial.show(key, highlight),
+            ScriptCommand::EndTutorial => {
+                if tutorial.active {
+                    tutorial.finish();
+                    dialogue
+                        .lines
+                        .push_back((tr("tutorial.speaker"), tr("tutorial.complete")));
+                }
+            }
+        }
+    }
+}
+
+// ==================== DIALOGUE BOX ====================
+
+#[derive(Resource, Default)]
+pub struct ScriptDialogue {
+    lines: VecDeque<(String, String)>, // Speaker and text, shown one at a time
+    remaining: f32,
+}
+
+#[derive(Component)]
+pub struct DialogueBox;
+
+pub fn script_dialogue_system(
+    mut commands: Commands,
+    mut dialogue: ResMut<ScriptDialogue>,
+    box_query: Query<Entity, With<DialogueBox>>,
+    time: Res<Time>,
+) {
+    if dialogue.remaining > 0.0 {
+        dialogue.remaining -= time.delta_seconds();
+        if dialogue.remaining > 0.0 {
+            return;
+        }
+    }
+
+    for entity in box_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some((speaker, text)) = dialogue.lines.pop_front() else {
+        return;
+    };
+    dialogue.remaining = DIALOGUE_SECONDS;
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(25.0),
+                    bottom: Val::Px(40.0),
+                    width: Val::Percent(50.0),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+                ..default()
+            },
+            DialogueBox,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                speaker,
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::rgb(1.0, 0.8, 0.0),
+                    ..default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zones_need_a_clear_majority_and_names_parse() {
+        assert_eq!(zone_holder(0, 0), None);
+        assert_eq!(zone_holder(4, 0), Some(Faction::Cartel));
+        assert_eq!(zone_holder(1, 5), Some(Faction::Military));
+        assert_eq!(zone_holder(3, 3), None);
+
+        assert_eq!(
+            parse_variant::<Faction>("Military"),
+            Some(Faction::Military)
+        );
+        assert_eq!(
+            parse_variant::<UnitType>("Sicario"),
+            Some(UnitType::Sicario)
+        );
+        assert_eq!(parse_variant::<Faction>("Police"), Some(Faction::Police));
+        assert_eq!(parse_variant::<Faction>("Journalists"), None);
+    }
+}
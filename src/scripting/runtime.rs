@@ -0,0 +1,658 @@
+use super::*;
+use crate::config::mod_registry;
+use crate::dialogue::{CameraFocus, DialogueLine, MAX_CHOICES};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+// ==================== RHAI RUNTIME ====================
+
+pub const SCRIPTS_DIR: &str = "assets/scripts";
+
+// Limits on what a single hook call may do, so a broken loop or a runaway
+// spawner can't hang or flood the game
+const MAX_OPERATIONS: u64 = 200_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_COLLECTION_SIZE: usize = 4096;
+const MAX_SPAWNS_PER_TICK: usize = 20;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+// Shared between the runtime and the functions registered on the engine
+#[derive(Default)]
+struct ScriptBridge {
+    view: ScriptWorldView,
+    commands: Vec<ScriptCommand>,
+    spawns: usize,
+}
+
+struct LoadedScript {
+    name: String,
+    ast: AST,
+    state: Dynamic, // `this` inside hooks
+}
+
+#[derive(Resource)]
+pub struct ScriptRuntime {
+    engine: Engine,
+    scripts: Vec<LoadedScript>,
+    bridge: Arc<Mutex<ScriptBridge>>,
+}
+
+impl ScriptRuntime {
+    // Base game scripts first, then each active mod pack's in load order
+    pub fn load() -> Self {
+        let bridge = Arc::new(Mutex::new(ScriptBridge::default()));
+        let engine = build_engine(&bridge);
+
+        let mut dirs = vec![PathBuf::from(SCRIPTS_DIR)];
+        dirs.extend(
+            mod_registry()
+                .active
+                .iter()
+                .map(|pack| pack.dir.join("scripts")),
+        );
+
+        let mut scripts = Vec::new();
+        for path in dirs.iter().flat_map(|dir| script_files(dir)) {
+            match compile_script(&engine, &path) {
+                Ok(script) => scripts.push(script),
+                Err(e) => warn!("Script {} not loaded: {}", path.display(), e),
+            }
+        }
+        if !scripts.is_empty() {
+            info!("📜 {} mission scripts loaded", scripts.len());
+        }
+
+        Self {
+            engine,
+            scripts,
+            bridge,
+        }
+    }
+
+    // Runs every script's handler for each hook and hands back what they asked for
+    pub fn run_hooks(
+        &mut self,
+        view: ScriptWorldView,
+        hooks: impl Iterator<Item = ScriptHook>,
+    ) -> Vec<ScriptCommand> {
+        {
+            let mut bridge = self.bridge.lock().unwrap();
+            bridge.view = view;
+            bridge.spawns = 0;
+        }
+
+        for hook in hooks {
+            let name = hook.function_name();
+            let args = hook_arguments(&hook);
+            for script in &mut self.scripts {
+                if !script.ast.iter_functions().any(|f| f.name == name) {
+                    continue;
+                }
+                let options = CallFnOptions::new()
+                    .eval_ast(false)
+                    .bind_this_ptr(&mut script.state);
+                let result = self.engine.call_fn_with_options::<Dynamic>(
+                    options,
+                    &mut Scope::new(),
+                    &script.ast,
+                    name,
+                    args.clone(),
+                );
+                if let Err(e) = result {
+                    warn!("Script {} failed in {}: {}", script.name, name, e);
+                }
+            }
+        }
+
+        std::mem::take(&mut self.bridge.lock().unwrap().commands)
+    }
+
+    // One-off code from the developer console, with the same API as the hooks
+    pub fn eval_snippet(
+        &mut self,
+        view: ScriptWorldView,
+        code: &str,
+    ) -> (Result<String, String>, Vec<ScriptCommand>) {
+        {
+            let mut bridge = self.bridge.lock().unwrap();
+            bridge.view = view;
+            bridge.spawns = 0;
+        }
+
+        let result = self
+            .engine
+            .eval::<Dynamic>(code)
+            .map(|value| value.to_string())
+            .map_err(|e| e.to_string());
+        let commands = std::mem::take(&mut self.bridge.lock().unwrap().commands);
+        (result, commands)
+    }
+}
+
+pub fn run_script_hooks_system(
+    mut runtime: ResMut<ScriptRuntime>,
+    mut hooks: ResMut<ScriptHookQueue>,
+    mut command_queue: ResMut<ScriptCommandQueue>,
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    tutorial: Res<Tutorial>,
+    unit_query: Query<(&Unit, &Transform), Without<Corpse>>,
+) {
+    if hooks.0.is_empty() {
+        return;
+    }
+    if runtime.scripts.is_empty() {
+        hooks.0.clear();
+        return;
+    }
+
+    let view = ScriptWorldView {
+        tutorial: tutorial.active,
+        ..ScriptWorldView::capture(&game_state, &campaign, &unit_query)
+    };
+    let commands = runtime.run_hooks(view, hooks.0.drain(..));
+    command_queue.0.extend(commands);
+}
+
+fn script_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    files.sort();
+    files
+}
+
+fn compile_script(
+    engine: &Engine,
+    path: &Path,
+) -> Result<LoadedScript, Box<dyn std::error::Error>> {
+    let source = fs::read_to_string(path)?;
+    let ast = engine.compile(&source)?;
+    Ok(LoadedScript {
+        name: path.display().to_string(),
+        ast,
+        state: Dynamic::from_map(Map::new()),
+    })
+}
+
+fn hook_arguments(hook: &ScriptHook) -> Vec<Dynamic> {
+    match hook {
+        ScriptHook::PhaseChange { from, to } => {
+            vec![format!("{:?}", from).into(), format!("{:?}", to).into()]
+        }
+        ScriptHook::UnitKilled {
+            faction,
+            unit_type,
+            position,
+        } => {
+            let mut unit = Map::new();
+            unit.insert("faction".into(), format!("{:?}", faction).into());
+            unit.insert("type".into(), format!("{:?}", unit_type).into());
+            unit.insert("x".into(), (position.x as f64).into());
+            unit.insert("y".into(), (position.y as f64).into());
+            vec![Dynamic::from_map(unit)]
+        }
+        ScriptHook::ZoneCaptured { zone, faction } => {
+            vec![zone.clone().into(), format!("{:?}", faction).into()]
+        }
+        ScriptHook::Tick { mission_time } => vec![(*mission_time as f64).into()],
+        ScriptHook::PlayerAction { action } => vec![format!("{:?}", action).into()],
+        ScriptHook::DialogueChoice {
+            conversation,
+            choice,
+        } => vec![conversation.clone().into(), (*choice as i64).into()],
+    }
+}
+
+// ==================== SCRIPT API ====================
+
+fn parse_name<T: serde::de::DeserializeOwned>(kind: &str, name: &str) -> ScriptResult<T> {
+    parse_variant(name).ok_or_else(|| format!("unknown {} '{}'", kind, name).into())
+}
+
+// Scripts may pass whole numbers where floats are expected
+fn number(value: &Dynamic) -> ScriptResult<f32> {
+    value
+        .as_float()
+        .or_else(|_| value.as_int().map(|int| int as f64))
+        .map(|number| number as f32)
+        .map_err(|type_name| format!("expected a number, got {}", type_name).into())
+}
+
+// One conversation line, #{ speaker, text, portrait, focus, choices }: speaker
+// and text are required, focus is a unit type, an [x, y] point or () for none
+// and choices an array of up to MAX_CHOICES strings
+fn dialogue_line(value: Dynamic) -> ScriptResult<DialogueLine> {
+    let map = value
+        .try_cast::<Map>()
+        .ok_or("each conversation line must be a map")?;
+    let string = |key: &str| -> ScriptResult<Option<String>> {
+        map.get(key)
+            .map(|value| value.clone().into_string())
+            .transpose()
+            .map_err(|type_name| format!("'{}' must be a string, got {}", key, type_name).into())
+    };
+
+    let focus = match map.get("focus") {
+        None => None,
+        Some(value) if value.is_unit() => None,
+        Some(value) if value.is_string() => {
+            let unit_type = value.clone().into_string()?;
+            Some(CameraFocus::Unit(parse_name("unit type", &unit_type)?))
+        }
+        Some(value) => {
+            let point = value
+                .clone()
+                .try_cast::<Array>()
+                .filter(|point| point.len() == 2)
+                .ok_or("focus must be a unit type or [x, y]")?;
+            Some(CameraFocus::Point(Vec2::new(
+                number(&point[0])?,
+                number(&point[1])?,
+            )))
+        }
+    };
+
+    let choices = match map.get("choices") {
+        None => Vec::new(),
+        Some(value) => value
+            .clone()
+            .try_cast::<Array>()
+            .ok_or("choices must be an array")?
+            .into_iter()
+            .map(|choice| choice.into_string())
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    if choices.len() > MAX_CHOICES {
+        return Err(format!("a line can offer at most {} choices", MAX_CHOICES).into());
+    }
+
+    Ok(DialogueLine {
+        speaker: string("speaker")?.ok_or("a conversation line needs a speaker")?,
+        text: string("text")?.ok_or("a conversation line needs a text")?,
+        portrait: string("portrait")?,
+        focus,
+        choices,
+    })
+}
+
+fn build_engine(bridge: &Arc<Mutex<ScriptBridge>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_COLLECTION_SIZE);
+    engine.set_max_array_size(MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(MAX_COLLECTION_SIZE);
+    // No reaching outside the scripts folder or around the API
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.on_print(|text| info!("📜 {}", text));
+
+    // Queries read the snapshot taken before the hooks ran
+    let b = bridge.clone();
+    engine.register_fn("mission", move || b.lock().unwrap().view.mission.clone());
+    let b = bridge.clone();
+    engine.register_fn("phase", move || b.lock().unwrap().view.phase.clone());
+    let b = bridge.clone();
+    engine.register_fn("mission_time", move || {
+        b.lock().unwrap().view.mission_time as f64
+    });
+
+    let b = bridge.clone();
+    engine.register_fn("tutorial", move || b.lock().unwrap().view.tutorial);
+
+    let b = bridge.clone();
+    engine.register_fn("unit_count", move |faction: &str| -> ScriptResult<i64> {
+        let faction: Faction = parse_name("faction", faction)?;
+        let bridge = b.lock().unwrap();
+        Ok(bridge
+            .view
+            .units
+            .iter()
+            .filter(|unit| unit.faction == faction)
+            .count() as i64)
+    });
+
+    let b = bridge.clone();
+    engine.register_fn("units", move |faction: &str| -> ScriptResult<Array> {
+        let faction: Faction = parse_name("faction", faction)?;
+        let bridge = b.lock().unwrap();
+        Ok(bridge
+            .view
+            .units
+            .iter()
+            .filter(|unit| unit.faction == faction)
+            .map(|unit| {
+                let mut map = Map::new();
+                map.insert("type".into(), format!("{:?}", unit.unit_type).into());
+                map.insert("x".into(), (unit.position.x as f64).into());
+                map.insert("y".into(), (unit.position.y as f64).into());
+                map.insert("health".into(), (unit.health as f64).into());
+                Dynamic::from_map(map)
+            })
+            .collect())
+    });
+
+    let b = bridge.clone();
+    engine.register_fn("objectives", move || -> Array {
+        let bridge = b.lock().unwrap();
+        bridge
+            .view
+            .objectives
+            .iter()
+            .map(|status| {
+                let (kind, target) = objective_kind(&status.objective);
+                let mut map = Map::new();
+                map.insert("kind".into(), kind.into());
+                map.insert("target".into(), target.into());
+                map.insert("completed".into(), status.completed.into());
+                map.insert("progress".into(), (status.progress as f64).into());
+                Dynamic::from_map(map)
+            })
+            .collect()
+    });
+
+    let b = bridge.clone();
+    engine.register_fn("pressure", move |kind: &str| -> ScriptResult<f64> {
+        let bridge = b.lock().unwrap();
+        pressure_value(&bridge.view.pressure, kind)
+            .map(|value| value as f64)
+            .ok_or_else(|| format!("unknown pressure '{}'", kind).into())
+    });
+
+    // Actions are queued and applied once every hook has run
+    let b = bridge.clone();
+    engine.register_fn(
+        "spawn_unit",
+        move |faction: &str, unit_type: &str, x: Dynamic, y: Dynamic| -> ScriptResult<()> {
+            let faction = parse_name("faction", faction)?;
+            let unit_type = parse_name("unit type", unit_type)?;
+            let position = Vec2::new(number(&x)?, number(&y)?);
+            let mut bridge = b.lock().unwrap();
+            if bridge.spawns >= MAX_SPAWNS_PER_TICK {
+                return Err(format!("more than {} spawns in one tick", MAX_SPAWNS_PER_TICK).into());
+            }
+            bridge.spawns += 1;
+            bridge.commands.push(ScriptCommand::SpawnUnit {
+                faction,
+                unit_type,
+                position,
+            });
+            Ok(())
+        },
+    );
+
+    let b = bridge.clone();
+    engine.register_fn(
+        "order_move",
+        move |faction: &str, x: Dynamic, y: Dynamic| -> ScriptResult<()> {
+            let command = ScriptCommand::OrderMove {
+                faction: parse_name("faction", faction)?,
+                unit_type: None,
+                target: Vec2::new(number(&x)?, number(&y)?),
+            };
+            b.lock().unwrap().commands.push(command);
+            Ok(())
+        },
+    );
+    let b = bridge.clone();
+    engine.register_fn(
+        "order_move",
+        move |faction: &str, unit_type: &str, x: Dynamic, y: Dynamic| -> ScriptResult<()> {
+            let command = ScriptCommand::OrderMove {
+                faction: parse_name("faction", faction)?,
+                unit_type: Some(parse_name("unit type", unit_type)?),
+                target: Vec2::new(number(&x)?, number(&y)?),
+            };
+            b.lock().unwrap().commands.push(command);
+            Ok(())
+        },
+    );
+
+    let b = bridge.clone();
+    engine.register_fn(
+        "add_pressure",
+        move |kind: &str, amount: Dynamic| -> ScriptResult<()> {
+            if !ADJUSTABLE_PRESSURES.contains(&kind) {
+                return Err(format!("'{}' is not a pressure that can be changed", kind).into());
+            }
+            let command = ScriptCommand::AddPressure {
+                kind: kind.to_string(),
+                amount: number(&amount)?,
+            };
+            b.lock().unwrap().commands.push(command);
+            Ok(())
+        },
+    );
+
+    let b = bridge.clone();
+    engine.register_fn(
+        "shift_threshold",
+        move |amount: Dynamic| -> ScriptResult<()> {
+            let command = ScriptCommand::ShiftThreshold {
+                amount: number(&amount)?,
+            };
+            b.lock().unwrap().commands.push(command);
+            Ok(())
+        },
+    );
+
+    let b = bridge.clone();
+    engine.register_fn(
+        "set_stance",
+        move |faction_a: &str, faction_b: &str, stance: &str| -> ScriptResult<()> {
+            let command = ScriptCommand::SetStance {
+                a: parse_name("faction", faction_a)?,
+                b: parse_name("faction", faction_b)?,
+                stance: parse_name("stance", stance)?,
+            };
+            b.lock().unwrap().commands.push(command);
+            Ok(())
+        },
+    );
+
+    let b = bridge.clone();
+    engine.register_fn("show_dialogue", move |speaker: &str, text: &str| {
+        b.lock()
+            .unwrap()
+            .commands
+            .push(ScriptCommand::ShowDialogue {
+                speaker: speaker.to_string(),
+                text: text.to_string(),
+            });
+    });
+
+    let b = bridge.clone();
+    engine.register_fn(
+        "conversation",
+        move |id: &str, lines: Array| -> ScriptResult<()> {
+            let lines = lines
+                .into_iter()
+                .map(dialogue_line)
+                .collect::<ScriptResult<Vec<_>>>()?;
+            let command = ScriptCommand::StartConversation(Conversation {
+                id: id.to_string(),
+                lines,
+            });
+            b.lock().unwrap().commands.push(command);
+            Ok(())
+        },
+    );
+
+    let b = bridge.clone();
+    engine.register_fn(
+        "show_prompt",
+        move |key: &str, highlight: &str| -> ScriptResult<()> {
+            let command = ScriptCommand::ShowPrompt {
+                key: key.to_string(),
+                highlight: parse_name("highlight", highlight)?,
+            };
+            b.lock().unwrap().commands.push(command);
+            Ok(())
+        },
+    );
+    let b = bridge.clone();
+    engine.register_fn("end_tutorial", move || {
+        b.lock().unwrap().commands.push(ScriptCommand::EndTutorial);
+    });
+
+    engine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_queue_commands_and_keep_state() {
+        let bridge = Arc::new(Mutex::new(ScriptBridge::default()));
+        let engine = build_engine(&bridge);
+        let ast = engine
+            .compile(
+                r#"
+                fn on_tick(time) {
+                    if this.spawned == () && time > 10.0 {
+                        spawn_unit("Military", "Soldier", 100, 50.5);
+                        this.spawned = true;
+                    }
+                }
+                "#,
+            )
+            .unwrap();
+        let mut runtime = ScriptRuntime {
+            engine,
+            scripts: vec![LoadedScript {
+                name: "test".to_string(),
+                ast,
+                state: Dynamic::from_map(Map::new()),
+            }],
+            bridge,
+        };
+
+        let tick = |mission_time| ScriptHook::Tick { mission_time };
+        let commands = runtime.run_hooks(ScriptWorldView::default(), [tick(5.0)].into_iter());
+        assert!(commands.is_empty());
+        let commands = runtime.run_hooks(ScriptWorldView::default(), [tick(11.0)].into_iter());
+        assert!(matches!(
+            commands.as_slice(),
+            [ScriptCommand::SpawnUnit {
+                faction: Faction::Military,
+                ..
+            }]
+        ));
+        // Only once, thanks to `this`
+        let commands = runtime.run_hooks(ScriptWorldView::default(), [tick(12.0)].into_iter());
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn test_conversations_parse_their_lines() {
+        let bridge = Arc::new(Mutex::new(ScriptBridge::default()));
+        let engine = build_engine(&bridge);
+        engine
+            .run(
+                r#"
+                conversation("intro", [
+                    #{ speaker: "Lookout", text: "Trucks!", portrait: "Sicario", focus: "Ovidio" },
+                    #{ speaker: "Lookout", text: "Where?", focus: [10, 20.5], choices: ["Here", "There"] },
+                ]);
+                "#,
+            )
+            .unwrap();
+        let commands = std::mem::take(&mut bridge.lock().unwrap().commands);
+        let [ScriptCommand::StartConversation(conversation)] = commands.as_slice() else {
+            panic!("expected one conversation, got {:?}", commands);
+        };
+        assert_eq!(conversation.id, "intro");
+        assert_eq!(conversation.lines[0].portrait.as_deref(), Some("Sicario"));
+        assert_eq!(
+            conversation.lines[0].focus,
+            Some(CameraFocus::Unit(UnitType::Ovidio))
+        );
+        assert_eq!(
+            conversation.lines[1].focus,
+            Some(CameraFocus::Point(Vec2::new(10.0, 20.5)))
+        );
+        assert_eq!(conversation.lines[1].choices, vec!["Here", "There"]);
+
+        assert!(engine
+            .run(r#"conversation("bad", [#{ text: "No speaker" }]);"#)
+            .is_err());
+        assert!(engine
+            .run(r#"conversation("bad", [#{ speaker: "A", text: "B", focus: "Nowhere" }]);"#)
+            .is_err());
+        assert!(bridge.lock().unwrap().commands.is_empty());
+    }
+
+    #[test]
+    fn test_phone_calls_remember_a_hard_line() {
+        let bridge = Arc::new(Mutex::new(ScriptBridge::default()));
+        let engine = build_engine(&bridge);
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(SCRIPTS_DIR)
+            .join("phone_calls.rhai");
+        let script = compile_script(&engine, &path).unwrap();
+        let mut runtime = ScriptRuntime {
+            engine,
+            scripts: vec![script],
+            bridge,
+        };
+        let mut hook = |hook| runtime.run_hooks(ScriptWorldView::default(), [hook].into_iter());
+        let phase = |from, to| ScriptHook::PhaseChange { from, to };
+        let answer = |conversation: &str, choice| ScriptHook::DialogueChoice {
+            conversation: conversation.to_string(),
+            choice,
+        };
+
+        let commands = hook(phase(GamePhase::InitialRaid, GamePhase::BlockConvoy));
+        assert!(matches!(
+            commands.as_slice(),
+            [ScriptCommand::StartConversation(call)] if call.id == "call_lawyer"
+        ));
+        // Leaning on the prosecutors: the families feel it, the government digs in
+        let commands = hook(answer("call_lawyer", 1));
+        assert!(commands.iter().any(|command| matches!(
+            command,
+            ScriptCommand::AddPressure { kind, amount }
+                if kind == "political_families" && *amount > 0.0
+        )));
+        assert!(commands.iter().any(|command| matches!(
+            command,
+            ScriptCommand::ShiftThreshold { amount } if *amount > 0.0
+        )));
+
+        let commands = hook(phase(GamePhase::ApplyPressure, GamePhase::HoldTheLine));
+        let [ScriptCommand::StartConversation(call)] = commands.as_slice() else {
+            panic!("expected the family's call, got {:?}", commands);
+        };
+        assert_eq!(call.lines[0].text, "calls.family_1_hard");
+        assert_eq!(call.lines[1].choices.len(), 2);
+
+        // A new mission starts the arc over
+        hook(phase(GamePhase::MissionBriefing, GamePhase::Preparation));
+        let commands = hook(phase(GamePhase::ApplyPressure, GamePhase::HoldTheLine));
+        let [ScriptCommand::StartConversation(call)] = commands.as_slice() else {
+            panic!("expected the family's call, got {:?}", commands);
+        };
+        assert_eq!(call.lines[0].text, "calls.family_1");
+    }
+
+    #[test]
+    fn test_shipped_scripts_compile() {
+        let bridge = Arc::new(Mutex::new(ScriptBridge::default()));
+        let engine = build_engine(&bridge);
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(SCRIPTS_DIR);
+        for path in script_files(&dir) {
+            assert!(compile_script(&engine, &path).is_ok(), "{}", path.display());
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use crate::app_state::{AppState, GameSet};
+use crate::campaign::place_name;
+use crate::components::*;
+use crate::config::{BalanceConfig, SentimentBalance};
+use crate::damage::{death_system, DeathEvent};
+use crate::hvt::TargetPackage;
+use crate::localization::tr_args;
+use crate::map::CityMap;
+use crate::noise::{queue_noise, NoiseEvent};
+use crate::political_system::PoliticalState;
+use crate::resources::GameAssets;
+use crate::spawners::spawn_intel_operator;
+use crate::utils::{iso_to_world, play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+// ==================== CIVILIAN SENTIMENT PLUGIN ====================
+
+// Every neighborhood on the city map keeps its own goodwill toward the
+// cartel. Gunfire in its streets wears it down, each death there turns it
+// against whoever did the killing, checkpoints and roadblocks against
+// whoever put them up, and a propaganda burst (commander.rs) warms the whole
+// city. A neighborhood that turns friendly sends an informant to the intel
+// network and covers for the cartel, so the army can't see far into its
+// streets; one that turns hostile points the army to the fighters in it
+// and, if he's there, to Ovidio. The city's average goodwill slowly moves
+// the cartel's public support in the political layer.
+
+const TIP_CONFIDENCE: f32 = 0.6; // What a tip about Ovidio is worth to the target package
+
+pub struct CivilianSentimentPlugin;
+
+impl Plugin for CivilianSentimentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CivilianSentiment>()
+            .add_systems(OnEnter(AppState::InGame), sentiment_reset_system)
+            .add_systems(
+                FixedUpdate,
+                (
+                    sentiment_system,
+                    informant_recruitment_system.run_if(resource_exists::<GameAssets>()),
+                    local_tip_system,
+                )
+                    .chain()
+                    .after(death_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Standing {
+    Friendly,
+    #[default]
+    Neutral,
+    Hostile,
+}
+
+/// Where a neighborhood with `goodwill` stands with the cartel.
+pub fn standing(goodwill: f32, balance: &SentimentBalance) -> Standing {
+    if goodwill >= balance.friendly_threshold {
+        Standing::Friendly
+    } else if goodwill <= balance.hostile_threshold {
+        Standing::Hostile
+    } else {
+        Standing::Neutral
+    }
+}
+
+/// Which way goodwill toward the cartel moves when `faction` brings the
+/// war to a neighborhood: up when it's the army's doing, down when it's
+/// the cartel's.
+pub fn blame(faction: &Faction) -> f32 {
+    match faction.side() {
+        Faction::Military => 1.0,
+        Faction::Cartel => -1.0,
+        _ => 0.0,
+    }
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct CivilianSentiment {
+    pub goodwill: HashMap<char, f32>, // By neighborhood key, -1 hostile to 1 friendly
+    standings: HashMap<char, Standing>,
+    informants: HashSet<char>, // Neighborhoods that have already sent one
+    tip_timer: f32,
+}
+
+impl CivilianSentiment {
+    pub fn new(city_map: &CityMap) -> Self {
+        Self {
+            goodwill: city_map
+                .neighborhoods
+                .iter()
+                .map(|neighborhood| (neighborhood.key, 0.0))
+                .collect(),
+            ..default()
+        }
+    }
+
+    pub fn goodwill(&self, key: char) -> f32 {
+        self.goodwill.get(&key).copied().unwrap_or(0.0)
+    }
+
+    pub fn shift(&mut self, key: char, amount: f32) {
+        let goodwill = self.goodwill.entry(key).or_insert(0.0);
+        *goodwill = (*goodwill + amount).clamp(-1.0, 1.0);
+    }
+
+    /// Moves every neighborhood's goodwill at once, as propaganda does.
+    pub fn shift_all(&mut self, amount: f32) {
+        for goodwill in self.goodwill.values_mut() {
+            *goodwill = (*goodwill + amount).clamp(-1.0, 1.0);
+        }
+    }
+
+    pub fn standing(&self, key: char) -> Standing {
+        self.standings.get(&key).copied().unwrap_or_default()
+    }
+
+    pub fn average(&self) -> f32 {
+        self.goodwill.values().sum::<f32>() / self.goodwill.len().max(1) as f32
+    }
+
+    /// Share of the army's sight left looking at `position`: `hidden_sight`
+    /// in a neighborhood that covers for the cartel, all of it elsewhere.
+    pub fn army_sight(&self, city_map: &CityMap, position: Vec2, hidden_sight: f32) -> f32 {
+        let covered = city_map
+            .neighborhood_at(position)
+            .is_some_and(|neighborhood| self.standing(neighborhood.key) == Standing::Friendly);
+        if covered {
+            hidden_sight
+        } else {
+            1.0
+        }
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn sentiment_reset_system(city_map: Res<CityMap>, mut sentiment: ResMut<CivilianSentiment>) {
+    *sentiment = CivilianSentiment::new(&city_map);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn sentiment_system(
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    mut sentiment: ResMut<CivilianSentiment>,
+    political_state: Option<ResMut<PoliticalState>>,
+    mut noise_events: EventReader<NoiseEvent>,
+    mut death_events: EventReader<DeathEvent>,
+    unit_query: Query<(&Unit, &Transform)>,
+    checkpoint_query: Query<(&Checkpoint, &Transform)>,
+) {
+    let balance = &balance.sentiment;
+    let dt = time.delta_seconds();
+    let key_at = |position: Vec2| {
+        city_map
+            .neighborhood_at(position)
+            .map(|neighborhood| neighborhood.key)
+    };
+
+    let under_fire: HashSet<char> = noise_events
+        .read()
+        .filter_map(|noise| key_at(noise.position))
+        .collect();
+    for key in under_fire {
+        sentiment.shift(key, -balance.fighting_per_second * dt);
+    }
+
+    for death in death_events.read() {
+        let Some(key) = key_at(death.position.truncate()) else {
+            continue;
+        };
+        // Nobody saw who did it; the cartel's war gets the blame
+        let killer = death
+            .killer
+            .and_then(|killer| unit_query.get(killer).ok())
+            .map_or(Faction::Cartel, |(unit, _)| unit.faction.clone());
+        sentiment.shift(key, blame(&killer) * balance.casualty_shift);
+    }
+
+    let roadblocks = checkpoint_query
+        .iter()
+        .map(|(checkpoint, transform)| (checkpoint.faction.clone(), transform))
+        .chain(
+            unit_query
+                .iter()
+                .filter(|(unit, _)| unit.unit_type == UnitType::Roadblock && unit.health > 0.0)
+                .map(|(unit, transform)| (unit.faction.clone(), transform)),
+        );
+    for (faction, transform) in roadblocks {
+        if let Some(key) = key_at(transform.translation.truncate()) {
+            sentiment.shift(key, blame(&faction) * balance.roadblock_per_second * dt);
+        }
+    }
+
+    let average = sentiment.average();
+    if let Some(mut political_state) = political_state.filter(|_| average != 0.0) {
+        political_state.public_support_cartel = (political_state.public_support_cartel
+            + balance.support_drift * average * dt)
+            .clamp(0.0, 1.0);
+    }
+
+    // Word gets round when a neighborhood changes sides
+    for neighborhood in &city_map.neighborhoods {
+        let now = standing(sentiment.goodwill(neighborhood.key), balance);
+        let before = sentiment.standings.insert(neighborhood.key, now);
+        if before.unwrap_or_default() == now {
+            continue;
+        }
+        let message = match now {
+            Standing::Friendly => "radio.sentiment_friendly",
+            Standing::Hostile => "radio.sentiment_hostile",
+            Standing::Neutral => continue,
+        };
+        play_tactical_sound(
+            "radio",
+            &tr_args(message, &[("place", &place_name(&neighborhood.name))]),
+        );
+    }
+}
+
+// A friendly neighborhood sends the intel network one informant
+pub fn informant_recruitment_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    game_assets: Res<GameAssets>,
+    mut sentiment: ResMut<CivilianSentiment>,
+) {
+    for neighborhood in &city_map.neighborhoods {
+        if sentiment.standing(neighborhood.key) != Standing::Friendly
+            || sentiment.informants.contains(&neighborhood.key)
+        {
+            continue;
+        }
+        let Some(center) = city_map.neighborhood_center(neighborhood.key) else {
+            continue;
+        };
+        sentiment.informants.insert(neighborhood.key);
+        spawn_intel_operator(
+            &mut commands,
+            IntelType::Informant,
+            iso_to_world(center.extend(0.0)),
+            &game_assets,
+            &balance,
+        );
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.sentiment_informant",
+                &[("place", &place_name(&neighborhood.name))],
+            ),
+        );
+    }
+}
+
+// Hostile neighborhoods point the army to the cartel fighters in them
+pub fn local_tip_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    mut sentiment: ResMut<CivilianSentiment>,
+    mut package: Option<ResMut<TargetPackage>>,
+    unit_query: Query<(&Unit, &Transform, Has<ProtectedPrincipal>)>,
+) {
+    let balance = &balance.sentiment;
+    sentiment.tip_timer += time.delta_seconds();
+    if sentiment.tip_timer < balance.tip_interval {
+        return;
+    }
+    sentiment.tip_timer = 0.0;
+
+    for neighborhood in &city_map.neighborhoods {
+        if sentiment.standing(neighborhood.key) != Standing::Hostile {
+            continue;
+        }
+        let fighters: Vec<_> = unit_query
+            .iter()
+            .filter(|(unit, transform, _)| {
+                unit.faction.side() == Faction::Cartel
+                    && unit.health > 0.0
+                    && unit.unit_type != UnitType::Roadblock
+                    && city_map
+                        .neighborhood_at(transform.translation.truncate())
+                        .is_some_and(|at| at.key == neighborhood.key)
+            })
+            .collect();
+        let Some((unit, transform, _)) = fighters.first() else {
+            continue;
+        };
+        queue_noise(
+            &mut commands,
+            transform.translation,
+            balance.tip_radius,
+            unit.faction.clone(),
+        );
+        let ovidio = fighters.iter().find(|(.., principal)| *principal);
+        if let (Some(package), Some((_, transform, _))) = (package.as_deref_mut(), ovidio) {
+            package.record_sighting(transform.translation, TIP_CONFIDENCE);
+        }
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.sentiment_tip",
+                &[("place", &place_name(&neighborhood.name))],
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::load_city_map;
+
+    #[test]
+    fn test_goodwill_sets_where_a_neighborhood_stands() {
+        let balance = SentimentBalance::default();
+        assert_eq!(standing(0.0, &balance), Standing::Neutral);
+        assert_eq!(
+            standing(balance.friendly_threshold, &balance),
+            Standing::Friendly
+        );
+        assert_eq!(standing(-1.0, &balance), Standing::Hostile);
+    }
+
+    #[test]
+    fn test_the_side_that_brings_the_war_takes_the_blame() {
+        let mut sentiment = CivilianSentiment::default();
+        sentiment.shift('F', blame(&Faction::Police) * 0.3);
+        sentiment.shift('T', blame(&Faction::RivalCartel) * 0.3);
+        sentiment.shift('C', blame(&Faction::Civilian) * 0.3);
+        assert_eq!(sentiment.goodwill('F'), 0.3);
+        assert_eq!(sentiment.goodwill('T'), -0.3);
+        assert_eq!(sentiment.goodwill('C'), 0.0);
+
+        // Goodwill stays within its bounds
+        sentiment.shift_all(5.0);
+        assert!(sentiment.goodwill.values().all(|goodwill| *goodwill == 1.0));
+    }
+
+    #[test]
+    fn test_friendly_neighborhoods_hide_the_cartel_from_the_army() {
+        let city_map = load_city_map();
+        let mut sentiment = CivilianSentiment::new(&city_map);
+        let flores = city_map.neighborhood_center('F').unwrap();
+        let centro = city_map.neighborhood_center('C').unwrap();
+        sentiment.standings.insert('F', Standing::Friendly);
+        assert_eq!(sentiment.army_sight(&city_map, flores, 0.6), 0.6);
+        assert_eq!(sentiment.army_sight(&city_map, centro, 0.6), 1.0);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::ai::ai_director_system;
+use crate::app_state::{AppStatePlugin, GameSet};
+use crate::campaign::Campaign;
+use crate::config::{
+    load_balance_config, load_unit_catalog, mission_overrides_system, MissionOverrides,
+};
+use crate::coordination::{
+    advanced_tactical_ai_system, communication_system, formation_movement_system,
+    radio_relay_system,
+};
+use crate::damage::DamagePipelinePlugin;
+use crate::diplomacy::DiplomacyPlugin;
+use crate::environmental_systems::{
+    flashlight_system, mission_start_time_system, EnvironmentalState,
+};
+use crate::game_systems::wave_spawner_system;
+use crate::map::load_city_map;
+use crate::noise::NoisePlugin;
+use crate::production::ProductionPlugin;
+use crate::resources::*;
+use crate::sentiment::CivilianSentimentPlugin;
+use crate::systems::{ability_effect_system, combat_system, movement_system, pathfinding_system};
+use crate::utils::{sim_rng_seed_system, EntityPools, FixedTimestepPlugin, SimRng, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== SIMULATION PLUGIN ====================
+
+// Gameplay state and the fixed-tick simulation systems, shared by the
+// windowed client and the headless runner. Rendering, input, audio and UI
+// are registered by whoever hosts the simulation.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((FixedTimestepPlugin, AppStatePlugin, DamagePipelinePlugin))
+            .add_plugins((
+                DiplomacyPlugin,
+                ProductionPlugin,
+                NoisePlugin,
+                CivilianSentimentPlugin,
+            ))
+            .init_resource::<GameState>()
+            .init_resource::<SimRng>()
+            .init_resource::<AiDirector>()
+            .init_resource::<Campaign>()
+            .init_resource::<EnvironmentalState>()
+            .init_resource::<EntityPools>()
+            .init_resource::<MissionOverrides>()
+            .insert_resource(load_balance_config())
+            .insert_resource(load_unit_catalog())
+            .insert_resource(load_city_map())
+            .add_systems(
+                PreUpdate,
+                (
+                    sim_rng_seed_system,
+                    mission_start_time_system,
+                    mission_overrides_system,
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    ai_director_system,
+                    wave_spawner_system,
+                    formation_movement_system,
+                    communication_system,
+                    radio_relay_system.after(communication_system),
+                    advanced_tactical_ai_system,
+                    pathfinding_system,
+                    movement_system,
+                    flashlight_system.before(combat_system),
+                    combat_system,
+                    ability_effect_system,
+                )
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
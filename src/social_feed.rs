@@ -0,0 +1,308 @@
+use crate::app_state::GameSet;
+use crate::campaign::place_name;
+use crate::damage::DeathEvent;
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::noise::NoiseEvent;
+use crate::political_system::{ContentType, SocialMediaInfluence, ViralContent};
+use crate::ui::UiTheme;
+use crate::utils::{create_dashboard_panel, dashboard_line, update_dashboard_panel};
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+// ==================== SOCIAL FEED PLUGIN ====================
+
+// The political layer's social media, seen the way Culiacán saw it that
+// afternoon: a phone-style feed (Scroll Lock) of the posts going viral. Each
+// video the viral-content generator puts out becomes a post about the
+// neighborhood where the shooting or the dying was last, tagged with what's
+// trending and an icon for how it makes the cartel look. A propaganda burst
+// from the commander menu puts the cartel's own video on the feed, where it
+// works on public opinion like any other.
+
+const MAX_POSTS: usize = 6;
+const TRENDING_SHOWN: usize = 3;
+
+// Accounts the posts come from, picked by each post's reach
+const HANDLES: [&str; 6] = [
+    "@culichi_al100",
+    "@noticias_sinaloa",
+    "@reportero_cln",
+    "@vecina_de_la_tierra",
+    "@lavozdelbarrio",
+    "@mx_ultimahora",
+];
+
+pub struct SocialFeedPlugin;
+
+impl Plugin for SocialFeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SocialFeed>().add_systems(
+            Update,
+            (
+                social_feed_system.in_set(GameSet::Session),
+                social_feed_panel_system.in_set(GameSet::InGame),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeedPost {
+    pub handle: &'static str,
+    pub text: String,
+    pub hashtag: Option<String>,
+    pub sentiment: f32,
+    pub reach: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct SocialFeed {
+    pub posts: VecDeque<FeedPost>, // Newest first
+    pub open: bool,
+    seen: u32,               // SocialMediaInfluence::content_shared already posted
+    hotspot: Option<String>, // Where the shooting was last heard
+}
+
+impl SocialFeed {
+    fn post(&mut self, post: FeedPost) {
+        self.posts.push_front(post);
+        self.posts.truncate(MAX_POSTS);
+    }
+}
+
+fn post_key(content_type: &ContentType) -> &'static str {
+    match content_type {
+        ContentType::CombatFootage => "feed.post_combat",
+        ContentType::CivilianFleeing => "feed.post_fleeing",
+        ContentType::PropertyDamage => "feed.post_damage",
+        ContentType::PoliticalSpeech => "feed.post_speech",
+        ContentType::ProtestFootage => "feed.post_protest",
+        ContentType::CartelPropaganda => "feed.post_propaganda",
+    }
+}
+
+/// How a post makes the cartel look, at a glance.
+pub fn sentiment_icon(sentiment: f32) -> &'static str {
+    if sentiment <= -0.5 {
+        "😡"
+    } else if sentiment < -0.1 {
+        "😟"
+    } else if sentiment <= 0.1 {
+        "😐"
+    } else {
+        "👍"
+    }
+}
+
+/// Views as a phone shows them: 950, 12.3K, 1.2M.
+pub fn format_reach(reach: u32) -> String {
+    match reach {
+        0..=999 => reach.to_string(),
+        1_000..=999_999 => format!("{:.1}K", reach as f32 / 1_000.0),
+        _ => format!("{:.1}M", reach as f32 / 1_000_000.0),
+    }
+}
+
+/// The `count` hashtags trending hardest, ties in name order so the list
+/// doesn't flicker.
+pub fn trending(hashtags: &HashMap<String, f32>, count: usize) -> Vec<(&str, f32)> {
+    let mut trending: Vec<(&str, f32)> = hashtags
+        .iter()
+        .map(|(hashtag, trend)| (hashtag.as_str(), *trend))
+        .collect();
+    trending.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    trending.truncate(count);
+    trending
+}
+
+/// A viral video as it shows up on the feed, set in `place`.
+pub fn compose_post(content: &ViralContent, place: &str, hashtag: Option<&str>) -> FeedPost {
+    FeedPost {
+        handle: HANDLES[content.reach as usize % HANDLES.len()],
+        text: tr_args(post_key(&content.content_type), &[("place", &place)]),
+        hashtag: hashtag.map(str::to_string),
+        sentiment: content.sentiment,
+        reach: content.reach,
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+// Scroll Lock: take out the phone, or put it away
+pub fn social_feed_toggle_system(input: Res<Input<KeyCode>>, mut feed: ResMut<SocialFeed>) {
+    if input.just_pressed(KeyCode::Scroll) {
+        feed.open = !feed.open;
+    }
+}
+
+// Turns new viral content into posts about wherever the fighting is
+pub fn social_feed_system(
+    mut feed: ResMut<SocialFeed>,
+    social_media: Option<Res<SocialMediaInfluence>>,
+    city_map: Res<CityMap>,
+    mut noise_events: EventReader<NoiseEvent>,
+    mut death_events: EventReader<DeathEvent>,
+) {
+    let heard = noise_events
+        .read()
+        .map(|noise| noise.position)
+        .chain(death_events.read().map(|death| death.position.truncate()))
+        .filter_map(|position| city_map.neighborhood_at(position))
+        .last();
+    if let Some(neighborhood) = heard.filter(|n| feed.hotspot.as_ref() != Some(&n.name)) {
+        feed.hotspot = Some(neighborhood.name.clone());
+    }
+
+    let Some(social_media) = social_media else {
+        return;
+    };
+    let new = social_media.content_shared.saturating_sub(feed.seen) as usize;
+    if new == 0 {
+        return;
+    }
+    feed.seen = social_media.content_shared;
+
+    let place = feed
+        .hotspot
+        .as_deref()
+        .map_or_else(|| city_map.name.clone(), place_name);
+    let hashtag = trending(&social_media.hashtag_trends, 1)
+        .first()
+        .map(|(hashtag, _)| hashtag.to_string());
+    let start = social_media.viral_videos.len().saturating_sub(new);
+    for content in &social_media.viral_videos[start..] {
+        feed.post(compose_post(content, &place, hashtag.as_deref()));
+    }
+}
+
+#[derive(Component)]
+pub struct SocialFeedPanel;
+
+pub fn social_feed_panel_system(
+    mut commands: Commands,
+    feed: Res<SocialFeed>,
+    social_media: Option<Res<SocialMediaInfluence>>,
+    theme: Res<UiTheme>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<SocialFeedPanel>>,
+) {
+    let panel_exists = !panel_query.is_empty();
+    let trends_changed = social_media
+        .as_ref()
+        .is_some_and(|social_media| social_media.is_changed());
+    if panel_exists && !feed.is_changed() && !trends_changed && !theme.is_changed() {
+        return;
+    }
+    if !feed.open && !panel_exists {
+        return;
+    }
+
+    let mut lines = vec![dashboard_line(tr("feed.title"), 14.0, Color::CYAN)];
+    if let Some(social_media) = social_media.as_deref() {
+        let trends: Vec<String> = trending(&social_media.hashtag_trends, TRENDING_SHOWN)
+            .into_iter()
+            .map(|(hashtag, _)| hashtag.to_string())
+            .collect();
+        lines.push(dashboard_line(
+            tr_args("feed.trending", &[("hashtags", &trends.join(" "))]),
+            10.0,
+            Color::GOLD,
+        ));
+    }
+    if feed.posts.is_empty() {
+        lines.push(dashboard_line(tr("feed.empty"), 11.0, Color::GRAY));
+    }
+    for post in &feed.posts {
+        lines.push(dashboard_line(
+            format!(
+                "{} {}  👁 {}",
+                sentiment_icon(post.sentiment),
+                post.handle,
+                format_reach(post.reach)
+            ),
+            10.0,
+            Color::GRAY,
+        ));
+        let text = match &post.hashtag {
+            Some(hashtag) => format!("{} {}", post.text, hashtag),
+            None => post.text.clone(),
+        };
+        lines.push(dashboard_line(text, 11.0, Color::WHITE));
+    }
+
+    let visibility = if feed.open {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        update_dashboard_panel(&mut text, lines, &theme);
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+    } else {
+        // A phone held up in the bottom-right corner
+        commands.spawn((
+            create_dashboard_panel(
+                Style {
+                    right: Val::Px(10.0),
+                    bottom: Val::Px(220.0),
+                    width: Val::Px(250.0),
+                    ..default()
+                },
+                Color::rgba(0.05, 0.05, 0.08, 0.92),
+                lines,
+                &theme,
+            ),
+            SocialFeedPanel,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reach_and_sentiment_read_like_a_phone() {
+        assert_eq!(format_reach(950), "950");
+        assert_eq!(format_reach(12_340), "12.3K");
+        assert_eq!(format_reach(1_200_000), "1.2M");
+        assert_eq!(sentiment_icon(-0.8), "😡");
+        assert_eq!(sentiment_icon(-0.3), "😟");
+        assert_eq!(sentiment_icon(0.0), "😐");
+        assert_eq!(sentiment_icon(0.5), "👍");
+    }
+
+    #[test]
+    fn test_trending_lists_the_hottest_hashtags_first() {
+        let hashtags = HashMap::from([
+            ("#Mexico".to_string(), 0.2),
+            ("#Culiacan".to_string(), 0.6),
+            ("#OvidioGuzman".to_string(), 0.2),
+            ("#EndTheViolence".to_string(), 0.1),
+        ]);
+        let names: Vec<&str> = trending(&hashtags, 3)
+            .into_iter()
+            .map(|(hashtag, _)| hashtag)
+            .collect();
+        assert_eq!(names, ["#Culiacan", "#Mexico", "#OvidioGuzman"]);
+    }
+
+    #[test]
+    fn test_the_feed_keeps_only_the_latest_posts() {
+        let mut feed = SocialFeed::default();
+        for reach in 0..(MAX_POSTS as u32 + 2) {
+            feed.post(FeedPost {
+                handle: HANDLES[0],
+                text: String::new(),
+                hashtag: None,
+                sentiment: 0.0,
+                reach,
+            });
+        }
+        assert_eq!(feed.posts.len(), MAX_POSTS);
+        assert_eq!(feed.posts[0].reach, MAX_POSTS as u32 + 1);
+    }
+}
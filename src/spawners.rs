@@ -1,7 +1,8 @@
 use crate::components::*;
+use crate::config::{BalanceConfig, UnitCatalog};
 use crate::resources::*;
 use crate::unit_systems::{
-    apply_weapon_upgrades, configure_unit_stats, get_unit_abilities, get_unit_color, get_unit_emoji,
+    apply_weapon_upgrades, configure_unit_stats, get_unit_color, get_unit_emoji,
 };
 use crate::utils::world_to_iso;
 use bevy::log::info;
@@ -15,7 +16,10 @@ pub fn spawn_unit(
     faction: Faction,
     position: Vec3,
     game_assets: &Res<GameAssets>,
-) {
+    catalog: &UnitCatalog,
+) -> Entity {
+    let definition = catalog.definition(&unit_type);
+
     // Create base unit with default stats
     let mut unit = Unit {
         health: 100.0,
@@ -24,6 +28,7 @@ pub fn spawn_unit(
         unit_type: unit_type.clone(),
         damage: 30.0,
         range: 100.0,
+        vision: 150.0,
         movement_speed: 40.0,
         target: None,
         attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
@@ -37,14 +42,14 @@ pub fn spawn_unit(
         },
     };
 
-    // Configure unit stats based on type and faction
-    configure_unit_stats(&mut unit, &unit_type, &faction);
+    // Configure unit stats and loadout from the catalog
+    configure_unit_stats(&mut unit, definition);
 
     // Apply weapon upgrades
     apply_weapon_upgrades(&mut unit);
 
     // Get visual properties
-    let sprite_handle = get_sprite_handle(&unit_type, game_assets);
+    let sprite_handle = game_assets.unit_sprite(&definition.sprite);
     let unit_color = get_unit_color(&unit_type, &faction);
     let emoji = get_unit_emoji(&unit_type);
 
@@ -95,9 +100,20 @@ pub fn spawn_unit(
     }
 
     // Add unit abilities based on type
-    let abilities = get_unit_abilities(&unit_type);
-    for ability in abilities {
-        commands.entity(entity).insert(ability);
+    for ability in &definition.abilities {
+        commands.entity(entity).insert(ability.to_component());
+    }
+    if !definition.engineering.is_empty() {
+        commands.entity(entity).insert(EngineeringKit {
+            actions: definition.engineering.clone(),
+            cooldown: 0.0,
+        });
+    }
+    if let Some(kind) = definition.area_denial {
+        commands.entity(entity).insert(AreaDenialKit {
+            kind,
+            cooldown: 0.0,
+        });
     }
 
     // Emoji overlay for clear unit identification
@@ -116,24 +132,8 @@ pub fn spawn_unit(
 
     // Add health bar
     spawn_health_bar(commands, entity, iso_position);
-}
 
-fn get_sprite_handle(unit_type: &UnitType, game_assets: &Res<GameAssets>) -> Handle<Image> {
-    match unit_type {
-        UnitType::Sicario => game_assets.sicario_sprite.clone(),
-        UnitType::Enforcer => game_assets.enforcer_sprite.clone(),
-        UnitType::Sniper => game_assets.sicario_sprite.clone(), // Reuse for now
-        UnitType::HeavyGunner => game_assets.enforcer_sprite.clone(), // Reuse for now
-        UnitType::Medic => game_assets.sicario_sprite.clone(),  // Reuse for now
-        UnitType::Ovidio => game_assets.ovidio_sprite.clone(),
-        UnitType::Roadblock => game_assets.roadblock_sprite.clone(),
-        UnitType::Soldier => game_assets.soldier_sprite.clone(),
-        UnitType::SpecialForces => game_assets.special_forces_sprite.clone(),
-        UnitType::Tank => game_assets.vehicle_sprite.clone(), // Reuse for now
-        UnitType::Helicopter => game_assets.vehicle_sprite.clone(), // Reuse for now
-        UnitType::Engineer => game_assets.soldier_sprite.clone(), // Reuse for now
-        UnitType::Vehicle => game_assets.vehicle_sprite.clone(),
-    }
+    entity
 }
 
 pub fn spawn_health_bar(commands: &mut Commands, owner: Entity, position: Vec3) {
@@ -179,12 +179,14 @@ pub fn spawn_intel_operator(
     intel_type: IntelType,
     position: Vec3,
     game_assets: &Res<GameAssets>,
+    balance: &BalanceConfig,
 ) -> Entity {
-    let (detection_range, stealth_level, cooldown_duration) = match intel_type {
-        IntelType::Reconnaissance => (200.0, 0.8, 15.0), // High stealth, long range
-        IntelType::RadioIntercept => (100.0, 0.6, 8.0),  // Medium stealth, faster intercepts
-        IntelType::Informant => (50.0, 0.9, 30.0),       // Very stealthy, slow reports
-        IntelType::CounterIntel => (150.0, 0.4, 20.0),   // Low stealth, detection focus
+    let detection_range = balance.intel.detection_range(&intel_type);
+    let (stealth_level, cooldown_duration) = match intel_type {
+        IntelType::Reconnaissance => (0.8, 15.0), // High stealth, long range
+        IntelType::RadioIntercept => (0.6, 8.0),  // Medium stealth, faster intercepts
+        IntelType::Informant => (0.9, 30.0),      // Very stealthy, slow reports
+        IntelType::CounterIntel => (0.4, 20.0),   // Low stealth, detection focus
     };
 
     let (color, emoji) = match intel_type {
@@ -253,7 +255,11 @@ pub fn spawn_intel_operator(
     entity
 }
 
-pub fn spawn_cartel_intel_network(commands: &mut Commands, game_assets: &Res<GameAssets>) {
+pub fn spawn_cartel_intel_network(
+    commands: &mut Commands,
+    game_assets: &Res<GameAssets>,
+    balance: &BalanceConfig,
+) {
     // Spawn a basic intel network for the cartel
 
     // Radio intercept operator (hidden in safehouse area)
@@ -262,6 +268,7 @@ pub fn spawn_cartel_intel_network(commands: &mut Commands, game_assets: &Res<Gam
         IntelType::RadioIntercept,
         Vec3::new(-50.0, 0.0, -30.0),
         game_assets,
+        balance,
     );
 
     // Reconnaissance scout (mobile, high stealth)
@@ -270,6 +277,7 @@ pub fn spawn_cartel_intel_network(commands: &mut Commands, game_assets: &Res<Gam
         IntelType::Reconnaissance,
         Vec3::new(0.0, 0.0, 50.0),
         game_assets,
+        balance,
     );
 
     // Informant network (civilian contact)
@@ -278,6 +286,7 @@ pub fn spawn_cartel_intel_network(commands: &mut Commands, game_assets: &Res<Gam
         IntelType::Informant,
         Vec3::new(80.0, 0.0, 20.0),
         game_assets,
+        balance,
     );
 
     info!(
@@ -0,0 +1,236 @@
+use crate::campaign::{Campaign, MissionConfig};
+use crate::components::{Faction, GamePhase, Unit, UnitType};
+use crate::localization::{tr, tr_args};
+use crate::multiplayer::{join_invited_session, MultiplayerState, NetworkManager};
+use crate::narration::spoken_text;
+use crate::political_system::PoliticalState;
+use crate::resources::GameState;
+use bevy::prelude::*;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+#[cfg(feature = "steamworks")]
+pub mod runtime;
+
+// ==================== STEAM ====================
+
+// Optional Steamworks integration behind the steamworks feature: campaign
+// achievements, rich presence with the current mission and phase, Steam Cloud
+// as the save backend, and Steam lobbies that carry our multiplayer session
+// id so accepting a friend's invite joins their session. Without the feature,
+// or when the Steam client isn't running, nothing here does anything.
+
+pub struct SteamPlugin;
+
+impl Plugin for SteamPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Steam>().add_systems(
+            Update,
+            (
+                steam_achievement_system,
+                steam_presence_system,
+                steam_lobby_system,
+            )
+                .chain(),
+        );
+
+        // Replaces Steam and the cloud save backend when the client is running
+        #[cfg(feature = "steamworks")]
+        runtime::connect(app);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SteamAchievement {
+    FirstRoadblock,
+    CleanPressureVictory, // Government backs down with no civilian casualties
+}
+
+impl SteamAchievement {
+    // Names configured on the Steamworks partner site
+    pub fn api_name(self) -> &'static str {
+        match self {
+            SteamAchievement::FirstRoadblock => "FIRST_ROADBLOCK",
+            SteamAchievement::CleanPressureVictory => "PRESSURE_VICTORY_NO_CIVILIANS",
+        }
+    }
+}
+
+// The Steamworks client as the game uses it; calls never block a frame
+pub trait SteamPlatform: Send + Sync {
+    fn unlock_achievement(&self, achievement: SteamAchievement);
+    fn set_presence(&self, status: &str);
+    fn host_lobby(&self, session_id: Uuid);
+    fn open_invite_dialog(&self);
+    fn take_joined_sessions(&mut self) -> Vec<Uuid>; // From accepted lobby invites
+}
+
+#[derive(Resource, Default)]
+pub struct Steam {
+    platform: Option<Box<dyn SteamPlatform>>,
+    unlocked: HashSet<SteamAchievement>, // This session; Steam keeps the real record
+    presence: String,
+    hosted_session: Option<Uuid>,
+}
+
+impl Steam {
+    pub fn with_platform(platform: Box<dyn SteamPlatform>) -> Self {
+        Self {
+            platform: Some(platform),
+            ..default()
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.platform.is_some()
+    }
+
+    fn unlock(&mut self, achievement: SteamAchievement) {
+        let Some(platform) = self.platform.as_ref() else {
+            return;
+        };
+        if self.unlocked.insert(achievement) {
+            info!("🏅 Achievement unlocked: {}", achievement.api_name());
+            platform.unlock_achievement(achievement);
+        }
+    }
+}
+
+// A pressure victory is the government capitulating, not the military being wiped out
+pub fn is_clean_pressure_victory(phase: &GamePhase, political_state: &PoliticalState) -> bool {
+    *phase == GamePhase::Victory
+        && political_state.government_capitulated
+        && political_state.casualties_civilian == 0
+}
+
+pub fn steam_achievement_system(
+    mut steam: ResMut<Steam>,
+    game_state: Res<GameState>,
+    political_state: Res<PoliticalState>,
+    new_units: Query<&Unit, Added<Unit>>,
+) {
+    let _span = info_span!("steam_achievement_system").entered();
+    if !steam.is_running() {
+        return;
+    }
+
+    if new_units
+        .iter()
+        .any(|unit| unit.unit_type == UnitType::Roadblock && unit.faction == Faction::Cartel)
+    {
+        steam.unlock(SteamAchievement::FirstRoadblock);
+    }
+    if is_clean_pressure_victory(&game_state.game_phase, &political_state) {
+        steam.unlock(SteamAchievement::CleanPressureVictory);
+    }
+}
+
+// Menus show as such; anything from the briefing on names the mission and phase
+pub fn presence_text(phase: &GamePhase, campaign: &Campaign) -> String {
+    match phase {
+        GamePhase::Loading
+        | GamePhase::ProfileSelect
+        | GamePhase::MainMenu
+        | GamePhase::SaveMenu
+        | GamePhase::LoadMenu
+        | GamePhase::ModsMenu
+        | GamePhase::AccessibilityMenu
+        | GamePhase::TelemetryMenu
+        | GamePhase::LeaderboardMenu
+        | GamePhase::CampaignMap => tr("steam.presence_menu"),
+        _ => {
+            let mission = MissionConfig::get_mission_config(&campaign.progress.current_mission);
+            tr_args(
+                "steam.presence_mission",
+                &[
+                    ("mission", &mission.name),
+                    ("phase", &spoken_text(&tr(&format!("phase.{:?}", phase)))),
+                ],
+            )
+        }
+    }
+}
+
+pub fn steam_presence_system(
+    mut steam: ResMut<Steam>,
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+) {
+    let _span = info_span!("steam_presence_system").entered();
+    if !steam.is_running() {
+        return;
+    }
+    let presence = presence_text(&game_state.game_phase, &campaign);
+    if presence == steam.presence {
+        return;
+    }
+    if let Some(platform) = steam.platform.as_ref() {
+        platform.set_presence(&presence);
+    }
+    steam.presence = presence;
+}
+
+// Hosting opens a Steam lobby for the session; F6 also brings up Steam's own
+// invite dialog, and accepted Steam invites join through the social system's path
+pub fn steam_lobby_system(
+    mut steam: ResMut<Steam>,
+    mut multiplayer_state: Option<ResMut<MultiplayerState>>,
+    network_manager: Option<Res<NetworkManager>>,
+    keyboard: Res<Input<KeyCode>>,
+) {
+    let _span = info_span!("steam_lobby_system").entered();
+    let Some(platform) = steam.platform.as_mut() else {
+        return;
+    };
+    let joined = platform.take_joined_sessions();
+
+    if let Some(state) = multiplayer_state.as_deref() {
+        let hosting = state.is_host.then_some(state.session_id);
+        if hosting.is_some() && keyboard.just_pressed(KeyCode::F6) {
+            platform.open_invite_dialog();
+        }
+        if hosting != steam.hosted_session {
+            if let (Some(session_id), Some(platform)) = (hosting, steam.platform.as_ref()) {
+                platform.host_lobby(session_id);
+            }
+            steam.hosted_session = hosting;
+        }
+    }
+
+    for session_id in joined {
+        match multiplayer_state.as_deref_mut() {
+            Some(state) => join_invited_session(state, network_manager.as_deref(), session_id),
+            None => warn!("Steam invite accepted but multiplayer is unavailable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pressure_victory_needs_capitulation_and_no_civilian_casualties() {
+        let mut political_state = PoliticalState::default();
+        assert!(!is_clean_pressure_victory(
+            &GamePhase::Victory,
+            &political_state
+        ));
+
+        political_state.government_capitulated = true;
+        assert!(is_clean_pressure_victory(
+            &GamePhase::Victory,
+            &political_state
+        ));
+        assert!(!is_clean_pressure_victory(
+            &GamePhase::HoldTheLine,
+            &political_state
+        ));
+
+        political_state.casualties_civilian = 1;
+        assert!(!is_clean_pressure_victory(
+            &GamePhase::Victory,
+            &political_state
+        ));
+    }
+}
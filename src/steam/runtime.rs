@@ -0,0 +1,201 @@
+use super::*;
+use crate::save::{save_timestamp, CloudSave, CloudSaveBackend, CloudSync, CloudSyncError};
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use steamworks::{
+    CallbackHandle, Client, ClientManager, GameLobbyJoinRequested, LobbyId, LobbyType, SingleClient,
+};
+use tokio::sync::mpsc;
+
+// ==================== STEAMWORKS RUNTIME ====================
+
+const LOBBY_SESSION_KEY: &str = "session_id";
+const LOBBY_MAX_MEMBERS: u32 = 4;
+const CONNECT_LOBBY_ARG: &str = "+connect_lobby"; // Passed when launched from an invite
+
+// Starts the Steam client if it's running; otherwise the game plays without it
+pub fn connect(app: &mut App) {
+    let (client, single) = match Client::init() {
+        Ok(client) => client,
+        Err(e) => {
+            info!("Steam not available ({}), playing without it", e);
+            return;
+        }
+    };
+    client.user_stats().request_current_stats();
+
+    // An explicit CLOUD_SAVE_URL keeps the HTTP backend
+    if std::env::var("CLOUD_SAVE_URL").is_err() {
+        app.insert_resource(CloudSync::with_backend(Arc::new(SteamCloudBackend {
+            client: client.clone(),
+        })));
+    }
+
+    app.insert_resource(Steam::with_platform(Box::new(SteamworksPlatform::new(
+        client,
+    ))))
+    .insert_non_send_resource(single)
+    .add_systems(PreUpdate, steam_callback_system);
+}
+
+// Callbacks, including the join requests below, only fire from here
+fn steam_callback_system(single: NonSend<SingleClient<ClientManager>>) {
+    single.run_callbacks();
+}
+
+struct SteamworksPlatform {
+    client: Client,
+    lobby: Arc<Mutex<Option<LobbyId>>>,
+    joined: mpsc::UnboundedReceiver<Uuid>,
+    _join_requests: CallbackHandle,
+}
+
+impl SteamworksPlatform {
+    fn new(client: Client) -> Self {
+        let (sender, joined) = mpsc::unbounded_channel();
+
+        let join_client = client.clone();
+        let join_sender = sender.clone();
+        let join_requests = client.register_callback(move |request: GameLobbyJoinRequested| {
+            join_lobby(&join_client, request.lobby_steam_id, join_sender.clone());
+        });
+
+        let mut args = std::env::args();
+        if args.any(|arg| arg == CONNECT_LOBBY_ARG) {
+            if let Some(lobby) = args.next().and_then(|id| id.parse::<u64>().ok()) {
+                join_lobby(&client, LobbyId::from_raw(lobby), sender);
+            }
+        }
+
+        Self {
+            client,
+            lobby: Arc::new(Mutex::new(None)),
+            joined,
+            _join_requests: join_requests,
+        }
+    }
+}
+
+// Joins the Steam lobby and hands over the session id its host stored there
+fn join_lobby(client: &Client, lobby: LobbyId, sender: mpsc::UnboundedSender<Uuid>) {
+    let lobby_client = client.clone();
+    client.matchmaking().join_lobby(lobby, move |result| {
+        let session_id = result.ok().and_then(|lobby| {
+            lobby_client
+                .matchmaking()
+                .lobby_data(lobby, LOBBY_SESSION_KEY)
+                .and_then(|value| value.to_string().parse::<Uuid>().ok())
+        });
+        match session_id {
+            Some(session_id) => {
+                let _ = sender.send(session_id);
+            }
+            None => warn!("Steam lobby has no multiplayer session to join"),
+        }
+    });
+}
+
+impl SteamPlatform for SteamworksPlatform {
+    fn unlock_achievement(&self, achievement: SteamAchievement) {
+        let user_stats = self.client.user_stats();
+        let stored = user_stats
+            .achievement(achievement.api_name())
+            .set()
+            .and_then(|_| user_stats.store_stats());
+        if stored.is_err() {
+            warn!(
+                "Steam rejected achievement {} - stats not loaded yet?",
+                achievement.api_name()
+            );
+        }
+    }
+
+    // The partner-site localization maps steam_display "#Status" to "%status%"
+    fn set_presence(&self, status: &str) {
+        let friends = self.client.friends();
+        friends.set_rich_presence("status", Some(status));
+        friends.set_rich_presence("steam_display", Some("#Status"));
+    }
+
+    fn host_lobby(&self, session_id: Uuid) {
+        let client = self.client.clone();
+        let hosted = self.lobby.clone();
+        self.client.matchmaking().create_lobby(
+            LobbyType::FriendsOnly,
+            LOBBY_MAX_MEMBERS,
+            move |result| match result {
+                Ok(lobby) => {
+                    client.matchmaking().set_lobby_data(
+                        lobby,
+                        LOBBY_SESSION_KEY,
+                        &session_id.to_string(),
+                    );
+                    if let Ok(mut hosted) = hosted.lock() {
+                        *hosted = Some(lobby);
+                    }
+                }
+                Err(e) => warn!("Couldn't create Steam lobby: {:?}", e),
+            },
+        );
+    }
+
+    fn open_invite_dialog(&self) {
+        let lobby = self.lobby.lock().ok().and_then(|lobby| *lobby);
+        match lobby {
+            Some(lobby) => self.client.friends().activate_invite_dialog(lobby),
+            None => warn!("Steam lobby isn't ready yet"),
+        }
+    }
+
+    fn take_joined_sessions(&mut self) -> Vec<Uuid> {
+        let mut sessions = Vec::new();
+        while let Ok(session_id) = self.joined.try_recv() {
+            sessions.push(session_id);
+        }
+        sessions
+    }
+}
+
+// ==================== STEAM CLOUD ====================
+
+// Each save slot is one file in the app's Steam Cloud storage
+struct SteamCloudBackend {
+    client: Client,
+}
+
+fn cloud_file_name(slot: usize) -> String {
+    format!("save_slot_{}.sav", slot)
+}
+
+impl CloudSaveBackend for SteamCloudBackend {
+    fn upload(&self, _token: &str, save: &CloudSave) -> Result<(), CloudSyncError> {
+        let mut writer = self
+            .client
+            .remote_storage()
+            .file(&cloud_file_name(save.slot))
+            .write();
+        writer.write_all(&save.data)?;
+        Ok(())
+    }
+
+    fn download(&self, _token: &str, slot: usize) -> Result<Option<CloudSave>, CloudSyncError> {
+        let file = self.client.remote_storage().file(&cloud_file_name(slot));
+        if !file.exists() {
+            return Ok(None);
+        }
+
+        let mut data = Vec::new();
+        file.read().read_to_end(&mut data)?;
+        let timestamp = save_timestamp(&data).ok_or("Steam Cloud save is unreadable")?;
+
+        Ok(Some(CloudSave {
+            slot,
+            timestamp,
+            data,
+        }))
+    }
+
+    fn needs_sign_in(&self) -> bool {
+        false
+    }
+}
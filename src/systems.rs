@@ -1,17 +1,35 @@
 use crate::components::*;
-use crate::environmental_systems::EnvironmentalState;
+use crate::config::{BalanceConfig, UnitCatalog};
+use crate::damage::DamageEvent;
+use crate::diplomacy::Diplomacy;
+use crate::economy::{charge, CartelTreasury};
+use crate::environmental_systems::{
+    gather_lights, spawn_streetlights, EnvironmentalState, LightQuery,
+};
+use crate::localization::tr;
+use crate::map::CityMap;
+use crate::noise::{queue_noise, weapon_noise};
+use crate::political_system::PoliticalState;
+use crate::production::spawn_production_bar;
 use crate::resources::*;
+use crate::rules_of_engagement::{civilian_positions, RoeLimits};
+use crate::sentiment::CivilianSentiment;
 use crate::spawners::{spawn_cartel_intel_network, spawn_health_bar, spawn_unit};
 use crate::utils::{
-    apply_combat_damage, clear_invalid_targets, execute_ability_simple,
-    find_combat_pairs_optimized, get_ability_cooldown, get_ability_range, get_default_ability,
-    play_tactical_sound, world_to_iso,
+    clear_invalid_targets, execute_ability_simple, find_combat_pairs, get_ability_cooldown,
+    get_ability_range, get_default_ability, play_tactical_sound, queue_decal, resolve_hit,
+    world_to_iso, CombatConditions, DecalKind, FiringStance, RngStream, SimRng,
 };
 use bevy::prelude::*;
+use rand::Rng;
 
 // ==================== SETUP SYSTEMS ====================
 
-pub fn setup_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn setup_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    catalog: Res<UnitCatalog>,
+) {
     info!("🔧 Loading sprite assets...");
     // Load individual sprite files with proper error handling
     let assets = GameAssets {
@@ -23,6 +41,10 @@ pub fn setup_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
         vehicle_sprite: asset_server.load("sprites/units/vehicle.png"),
         roadblock_sprite: asset_server.load("sprites/units/roadblock.png"),
         safehouse_sprite: asset_server.load("sprites/units/safehouse.png"),
+        unit_sprites: catalog
+            .sprite_paths()
+            .map(|path| (path.to_string(), asset_server.load(path.to_string())))
+            .collect(),
         _health_bar_bg: Handle::default(),
         _health_bar_fill: Handle::default(),
         _main_font: Handle::default(),
@@ -156,11 +178,22 @@ pub fn setup_ui(mut commands: Commands, _asset_server: Res<AssetServer>) {
     info!("✅ UI elements created successfully!");
 }
 
-pub fn setup_game(mut commands: Commands, game_assets: Res<GameAssets>) {
+pub fn setup_game(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    catalog: Res<UnitCatalog>,
+    city_map: Res<CityMap>,
+) {
     info!("🎮 Initializing Battle of Culiacán simulation...");
 
     // Spawn Ovidio (High Value Target) at center for visibility
-    spawn_ovidio(&mut commands, Vec3::new(0.0, 0.0, 0.0), &game_assets);
+    spawn_ovidio(
+        &mut commands,
+        Vec3::new(0.0, 0.0, 0.0),
+        &game_assets,
+        &catalog,
+    );
 
     // Spawn initial cartel defenders around the center
     for i in 0..3 {
@@ -170,32 +203,15 @@ pub fn setup_game(mut commands: Commands, game_assets: Res<GameAssets>) {
             Faction::Cartel,
             Vec3::new(-100.0 + i as f32 * 100.0, -50.0, 0.0),
             &game_assets,
+            &catalog,
         );
     }
 
     // Deploy intel network
-    spawn_cartel_intel_network(&mut commands, &game_assets);
+    spawn_cartel_intel_network(&mut commands, &game_assets, &balance);
 
-    // Spawn safehouse objective with enhanced graphics
-    let safehouse_pos = Vec3::new(0.0, 100.0, 0.0);
-    commands.spawn((
-        SpriteBundle {
-            sprite: Sprite {
-                color: Color::rgb(0.6, 0.4, 0.2),
-                custom_size: Some(Vec2::new(64.0, 64.0)),
-                ..default()
-            },
-            texture: game_assets.safehouse_sprite.clone(),
-            transform: Transform::from_translation(safehouse_pos),
-            ..default()
-        },
-        Objective {
-            objective_type: ObjectiveType::Safehouse,
-            _position: safehouse_pos,
-            _radius: 50.0,
-            _health: 100.0,
-        },
-    ));
+    // Spawn safehouse objectives with enhanced graphics
+    spawn_safehouses(&mut commands, &game_assets);
 
     // Wave spawner
     commands.spawn(WaveSpawner {
@@ -204,14 +220,60 @@ pub fn setup_game(mut commands: Commands, game_assets: Res<GameAssets>) {
         units_in_wave: 2,
     });
 
+    // Streetlights light up pools of visibility at night
+    spawn_streetlights(&mut commands, &city_map);
+
     // Mark setup as complete
     commands.insert_resource(GameSetupComplete);
 
-    play_tactical_sound("radio", "Command: Operation initiated. Ovidio's location confirmed. All units, hold your positions!");
+    play_tactical_sound("radio", &tr("radio.operation_start"));
     info!("✅ Game setup completed! Press SPACE for roadblocks, R for reinforcements, ESC to end.");
 }
 
-fn spawn_ovidio(commands: &mut Commands, position: Vec3, game_assets: &Res<GameAssets>) {
+// Where Ovidio can shelter; the first is where the raid finds him
+const SAFEHOUSES: [(&str, Vec3); 2] = [
+    ("Tres Ríos house", Vec3::new(0.0, 100.0, 0.0)),
+    ("Las Quintas house", Vec3::new(350.0, -200.0, 0.0)),
+];
+
+pub fn spawn_safehouses(commands: &mut Commands, game_assets: &Res<GameAssets>) {
+    for (name, safehouse_pos) in SAFEHOUSES {
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgb(0.6, 0.4, 0.2),
+                        custom_size: Some(Vec2::new(64.0, 64.0)),
+                        ..default()
+                    },
+                    texture: game_assets.safehouse_sprite.clone(),
+                    transform: Transform::from_translation(safehouse_pos),
+                    ..default()
+                },
+                Objective {
+                    objective_type: ObjectiveType::Safehouse,
+                    _position: safehouse_pos,
+                    _radius: 50.0,
+                    _health: 100.0,
+                },
+                SafehouseInterior {
+                    name: name.to_string(),
+                    radius: 50.0,
+                    cover: 0.6,
+                },
+                ProductionQueue::new(Faction::Cartel),
+            ))
+            .with_children(spawn_production_bar);
+    }
+}
+
+pub fn spawn_ovidio(
+    commands: &mut Commands,
+    position: Vec3,
+    game_assets: &Res<GameAssets>,
+    catalog: &UnitCatalog,
+) -> Entity {
+    let stats = catalog.definition(&UnitType::Ovidio);
     let entity = commands
         .spawn((
             SpriteBundle {
@@ -219,46 +281,51 @@ fn spawn_ovidio(commands: &mut Commands, position: Vec3, game_assets: &Res<GameA
                     custom_size: Some(Vec2::new(48.0, 48.0)),
                     ..default()
                 },
-                texture: game_assets.ovidio_sprite.clone(),
+                texture: game_assets.unit_sprite(&stats.sprite),
                 transform: Transform::from_translation(position),
                 ..default()
             },
             Unit {
-                health: 200.0,
-                max_health: 200.0,
+                health: stats.health,
+                max_health: stats.health,
                 faction: Faction::Cartel,
                 unit_type: UnitType::Ovidio,
-                damage: 35.0,
-                range: 160.0,
-                movement_speed: 60.0,
+                damage: stats.damage,
+                range: stats.range,
+                vision: stats.vision,
+                movement_speed: stats.movement_speed,
                 target: None,
                 attack_cooldown: Timer::from_seconds(0.8, TimerMode::Once),
                 experience: 0,
                 kills: 0,
                 veterancy_level: VeterancyLevel::Elite,
-                equipment: Equipment {
-                    weapon: WeaponType::AssaultRifle,
-                    armor: ArmorType::HeavyArmor,
-                    upgrades: vec![UpgradeType::ScopedSight, UpgradeType::ReinforcedArmor],
-                },
+                equipment: stats.equipment(),
             },
             Movement {
                 target_position: None,
-                speed: 60.0,
+                speed: stats.movement_speed,
             },
+            ProtectedPrincipal::default(),
         ))
         .id();
 
     // Add health bar for Ovidio
     spawn_health_bar(commands, entity, position);
+
+    entity
 }
 
 // ==================== PATHFINDING SYSTEM ====================
 
+#[allow(clippy::too_many_arguments)]
 pub fn pathfinding_system(
     mut unit_query: Query<(&mut Transform, &mut Movement, &mut PathfindingAgent, &Unit)>,
     obstacle_query: Query<&Transform, (With<Obstacle>, Without<Unit>)>,
     other_units_query: Query<&Transform, (With<Unit>, Without<PathfindingAgent>)>,
+    zone_query: Query<&AreaDenialZone>,
+    city_map: Res<CityMap>,
+    diplomacy: Res<Diplomacy>,
+    balance: Res<BalanceConfig>,
     time: Res<Time>,
 ) {
     for (mut transform, mut movement, mut pathfinding, unit) in unit_query.iter_mut() {
@@ -267,10 +334,25 @@ pub fn pathfinding_system(
         if let Some(target_pos) = movement.target_position {
             let current_pos = transform.translation;
 
-            // Generate simple path if needed
+            // Route through the city streets, keeping out of zones that would
+            // hurt the unit, or straight across open ground off the map
             if pathfinding.path.is_empty() || pathfinding.current_waypoint >= pathfinding.path.len()
             {
-                pathfinding.path = generate_simple_path(current_pos, target_pos, &obstacle_query);
+                let hazards: Vec<(Vec2, f32)> = zone_query
+                    .iter()
+                    .filter(|zone| zone.affects(&unit.faction, &diplomacy))
+                    .map(|zone| (zone.center, zone.radius))
+                    .collect();
+                pathfinding.path = city_map
+                    .find_path_avoiding(
+                        current_pos,
+                        target_pos,
+                        &hazards,
+                        balance.area_denial.path_penalty,
+                    )
+                    .unwrap_or_else(|| {
+                        generate_simple_path(current_pos, target_pos, &obstacle_query)
+                    });
                 pathfinding.current_waypoint = 0;
                 pathfinding.stuck_timer = 0.0;
             }
@@ -401,16 +483,19 @@ fn calculate_avoidance_force(
 pub fn movement_system(
     time: Res<Time>,
     environmental_state: Res<EnvironmentalState>,
-    mut unit_query: Query<(&mut Transform, &Movement, &Unit)>,
+    mut unit_query: Query<(&mut Transform, &Movement, &Unit, Option<&StatModifiers>)>,
     mut path_events: EventWriter<PathingEvent>
 ) {
-    for (mut transform, movement, unit) in unit_query.iter_mut() {
+    for (mut transform, movement, unit, modifiers) in unit_query.iter_mut() {
         if let Some(target_pos) = movement.target_position {
             let current_pos = transform.translation;
             let direction = (target_pos - current_pos).normalize();
 
             // Apply environmental movement modifier (weather affects movement speed)
             let environmental_speed = unit.movement_speed * environmental_state.movement_modifier;
+            // Held up at an enemy checkpoint
+            let environmental_speed =
+                environmental_speed * modifiers.map_or(1.0, |modifiers| modifiers.movement);
             let move_delta = direction * environmental_speed * time.delta_seconds();
 
             // Check if we're close enough to the target
@@ -423,32 +508,132 @@ pub fn movement_system(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn combat_system(
     mut commands: Commands,
     mut unit_query: Query<(Entity, &mut Unit, &Transform)>,
     immutable_unit_query: Query<(Entity, &Unit, &Transform), Without<AbilityEffect>>,
     effect_query: Query<&AbilityEffect>,
+    principal_query: Query<(&ProtectedPrincipal, Has<Captive>)>,
+    modifier_query: Query<&StatModifiers>,
     environmental_state: Res<EnvironmentalState>,
+    light_query: LightQuery,
+    zone_query: Query<&AreaDenialZone>,
+    (city_map, sentiment, political_state): (
+        Res<CityMap>,
+        Res<CivilianSentiment>,
+        Res<PoliticalState>,
+    ),
+    diplomacy: Res<Diplomacy>,
+    stance_query: Query<(Option<&Movement>, Option<&TacticalState>, Has<Ambush>)>,
+    balance: Res<BalanceConfig>,
+    mut sim_rng: ResMut<SimRng>,
     time: Res<Time>,
-    mut combat_events: EventWriter<CombatEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
 ) {
-    // Find combat pairs and calculate damage - prioritize assigned targets (optimized)
-    let combat_events = find_combat_pairs_optimized(
-        &immutable_unit_query,
-        environmental_state.visibility_modifier,
-    );
+    let (streetlights, beams) = gather_lights(&light_query, &balance.night_vision);
+    let zones: Vec<AreaDenialZone> = zone_query.iter().cloned().collect();
+    let units: Vec<_> = immutable_unit_query.iter().collect();
+    let civilians =
+        civilian_positions(units.iter().map(|(_, unit, transform)| (*unit, *transform)));
+    let conditions = CombatConditions {
+        environment: &environmental_state,
+        streetlights: &streetlights,
+        beams: &beams,
+        city_map: &city_map,
+        diplomacy: &diplomacy,
+        zones: &zones,
+        sentiment: &sentiment,
+        hidden_sight: balance.sentiment.hidden_sight,
+        roe: RoeLimits::of(&political_state.government_response_level, &balance.roe),
+        civilians: &civilians,
+    };
 
-    // Apply combat damage and effects
-    for (attacker, target) in combat_events {
-        let damage = 25.0; // Base damage value
-        apply_combat_damage(
-            &mut commands,
-            attacker,
-            target,
-            damage,
-            &mut unit_query,
-            &effect_query,
-        );
+    // Find combat pairs within vision range - prioritize assigned targets
+    let combat_pairs = find_combat_pairs(&units, &conditions);
+
+    // Hits land through the damage pipeline, after this system
+    for (attacker, target, _) in combat_pairs {
+        // Nobody shoots a restrained prisoner, and ambushers wait to be sprung
+        if principal_query
+            .get(target)
+            .is_ok_and(|(_, is_captive)| is_captive)
+            || stance_query
+                .get(attacker)
+                .is_ok_and(|(.., ambushing)| ambushing)
+        {
+            continue;
+        }
+
+        // Range, movement, cover, suppression, darkness and gas all spoil the aim
+        let stance = |entity: Entity, transform: &Transform| {
+            let (movement, tactical_state, _) = stance_query.get(entity).unwrap_or_default();
+            (
+                transform.translation,
+                FiringStance::of(transform, movement, tactical_state),
+            )
+        };
+        let (accuracy, cover, target_position) = match (
+            immutable_unit_query.get(attacker),
+            immutable_unit_query.get(target),
+        ) {
+            (Ok((_, attacker_unit, attacker_transform)), Ok((_, _, target_transform))) => {
+                // Every shot is heard, hit or miss, less far through a suppressor
+                let loudness = weapon_noise(&attacker_unit.equipment.weapon, &balance.noise)
+                    * modifier_query
+                        .get(attacker)
+                        .map_or(1.0, |modifiers| modifiers.noise);
+                queue_noise(
+                    &mut commands,
+                    attacker_transform.translation,
+                    loudness,
+                    attacker_unit.faction.clone(),
+                );
+                (
+                    conditions
+                        .hit_chance(
+                            attacker_unit,
+                            stance(attacker, attacker_transform),
+                            stance(target, target_transform),
+                            &balance.accuracy,
+                        )
+                        .total(&balance.accuracy),
+                    conditions.cover(target_transform.translation),
+                    target_transform.translation,
+                )
+            }
+            _ => (1.0, 0.0, Vec3::ZERO),
+        };
+        if accuracy < 1.0 && sim_rng.stream(RngStream::Combat).gen::<f32>() > accuracy {
+            // A miss still costs the shot, and chips the ground near the target
+            if let Ok((_, mut attacker_unit, _)) = unit_query.get_mut(attacker) {
+                attacker_unit.attack_cooldown.reset();
+            }
+            let scatter = Vec3::new(
+                rand::thread_rng().gen_range(-20.0..20.0),
+                rand::thread_rng().gen_range(-20.0..20.0),
+                0.0,
+            );
+            queue_decal(&mut commands, DecalKind::BulletImpact, target_position + scatter, 1.0);
+            continue;
+        }
+
+        // Inside a safehouse the walls count for more than the street outside
+        let cover = principal_query
+            .get(target)
+            .map_or(cover, |(principal, _)| cover.max(principal.shelter_cover));
+        let damage = 25.0 * (1.0 - cover); // Base damage value, reduced by cover
+        // Upgrades bought for the mission: incendiary rounds out, armor plates in
+        let damage = damage
+            * modifier_query
+                .get(attacker)
+                .map_or(1.0, |modifiers| modifiers.damage_dealt)
+            * modifier_query
+                .get(target)
+                .map_or(1.0, |modifiers| modifiers.damage_taken);
+        if let Some(hit) = resolve_hit(attacker, target, damage, &mut unit_query, &effect_query) {
+            damage_events.send(hit);
+        }
     }
 
     // Clear invalid targets (dead units) and update attack cooldowns
@@ -462,6 +647,7 @@ pub fn combat_system(
 
 // ==================== ABILITY SYSTEM ====================
 
+#[allow(clippy::too_many_arguments)]
 pub fn ability_system(
     mut commands: Commands,
     input: Res<Input<KeyCode>>,
@@ -472,6 +658,9 @@ pub fn ability_system(
     selected_query: Query<Entity, With<Selected>>,
     time: Res<Time>,
     game_assets: Res<GameAssets>,
+    catalog: Res<UnitCatalog>,
+    balance: Res<BalanceConfig>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
 ) {
     // Update ability cooldowns
     for (_, _, _, ability) in unit_queries.p0().iter_mut() {
@@ -488,6 +677,9 @@ pub fn ability_system(
             &mut unit_queries,
             0,
             &game_assets,
+            &catalog,
+            treasury.as_deref_mut(),
+            balance.economy.ability_cost,
         );
     }
     if input.just_pressed(KeyCode::E) {
@@ -497,10 +689,14 @@ pub fn ability_system(
             &mut unit_queries,
             1,
             &game_assets,
+            &catalog,
+            treasury.as_deref_mut(),
+            balance.economy.ability_cost,
         );
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn activate_ability_for_selected(
     commands: &mut Commands,
     selected_query: &Query<Entity, With<Selected>>,
@@ -510,6 +706,9 @@ fn activate_ability_for_selected(
     )>,
     ability_index: usize,
     game_assets: &Res<GameAssets>,
+    catalog: &UnitCatalog,
+    mut treasury: Option<&mut CartelTreasury>,
+    ability_cost: f32,
 ) {
     // Collect enemy data first
     let enemy_data: Vec<(Entity, Vec3, UnitType, f32)> = unit_queries
@@ -529,6 +728,15 @@ fn activate_ability_for_selected(
         if let Ok((entity, transform, mut unit, ability)) =
             unit_queries.p0().get_mut(selected_entity)
         {
+            // Cartel abilities are paid for out of the treasury
+            let ready = ability.as_ref().is_none_or(|ability| ability.cooldown.finished());
+            if ready
+                && unit.faction == Faction::Cartel
+                && !charge(treasury.as_deref_mut(), ability_cost)
+            {
+                continue;
+            }
+
             if let Some(mut ability) = ability {
                 if ability.cooldown.finished() {
                     let ability_type = ability.ability_type.clone();
@@ -540,6 +748,7 @@ fn activate_ability_for_selected(
                         ability_type,
                         &enemy_data,
                         game_assets,
+                        catalog,
                     );
                     ability.cooldown.reset();
                 }
@@ -564,6 +773,7 @@ fn activate_ability_for_selected(
                         ability_type,
                         &enemy_data,
                         game_assets,
+                        catalog,
                     );
                 }
             }
@@ -575,6 +785,7 @@ pub fn ability_effect_system(
     mut commands: Commands,
     mut effect_query: Query<(Entity, &mut Unit, &mut AbilityEffect)>,
     time: Res<Time>,
+    mut damage_events: EventWriter<DamageEvent>,
 ) {
     for (entity, mut unit, mut effect) in effect_query.iter_mut() {
         effect.duration.tick(time.delta());
@@ -593,7 +804,12 @@ pub fn ability_effect_system(
             EffectType::Stunned => {
                 // Apply instant damage if this is damage effect
                 if effect.strength > 0.0 {
-                    unit.health -= effect.strength;
+                    damage_events.send(DamageEvent {
+                        attacker: None,
+                        target: entity,
+                        amount: effect.strength,
+                        weapon: None,
+                    });
                     effect.strength = 0.0; // Prevent multiple applications
                 }
             }
@@ -611,7 +827,12 @@ pub fn ability_effect_system(
             EffectType::ArmorPiercing => {
                 // Apply instant damage bypassing armor
                 if effect.strength > 0.0 {
-                    unit.health -= effect.strength;
+                    damage_events.send(DamageEvent {
+                        attacker: None,
+                        target: entity,
+                        amount: effect.strength,
+                        weapon: None,
+                    });
                     effect.strength = 0.0; // Prevent multiple applications
                 }
             }
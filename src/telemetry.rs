@@ -0,0 +1,465 @@
+use crate::accessibility::AccessibilitySettings;
+use crate::campaign::Campaign;
+use crate::components::GamePhase;
+use crate::map::MapEditor;
+use crate::narration::NarrationMode;
+use crate::profile::ProfileManager;
+use crate::resources::{AiDirector, GameState};
+use crate::save::{LoadGameEvent, SaveGameEvent};
+use crate::tutorial::Tutorial;
+use crate::ui::DevConsole;
+use crate::utils::{data_dir, DataKind};
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use chrono::Utc;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use uuid::Uuid;
+
+// ==================== TELEMETRY ====================
+
+// Opt-in gameplay data for balance decisions: mission outcomes with the
+// difficulty they were played on, time spent in each phase and which features
+// get used. Events go to a local JSONL log tagged only with a random id per
+// launch - no profile, name or machine details. With TELEMETRY_URL set to an
+// https:// endpoint, new lines are uploaded on exit. The Gameplay Data page
+// (9 in the main menu) turns it on and shows what has been recorded.
+
+const TELEMETRY_LOG_FILE: &str = "events.jsonl";
+const UPLOADED_MARKER_FILE: &str = "uploaded"; // Bytes of the log already sent
+const TELEMETRY_URL_ENV_VAR: &str = "TELEMETRY_URL";
+const UPLOAD_TIMEOUT_SECONDS: u64 = 10;
+
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Telemetry>()
+            .add_systems(
+                Update,
+                (
+                    apply_telemetry_settings_system,
+                    telemetry_phase_system,
+                    telemetry_feature_system,
+                )
+                    .chain(),
+            )
+            .add_systems(Last, telemetry_upload_on_exit_system);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    MissionOutcome {
+        mission: String,
+        victory: bool,
+        seconds: f32,
+        intensity: f32, // AI director intensity when the mission ended
+        adaptive: bool,
+    },
+    PhaseTime {
+        phase: String,
+        seconds: f32,
+    },
+    FeatureUsed {
+        feature: String,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TelemetryRecord {
+    pub session: Uuid,
+    pub timestamp: String, // RFC 3339, like save files
+    #[serde(flatten)]
+    pub event: TelemetryEvent,
+}
+
+#[derive(Resource)]
+pub struct Telemetry {
+    pub enabled: bool, // Mirrors the active profile's opt-in
+    session: Uuid,
+    dir: PathBuf,
+    upload_url: Option<String>,
+    features_seen: HashSet<&'static str>, // Each feature is logged once per launch
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        let dir = data_dir(DataKind::Telemetry);
+
+        // Gameplay data only ever leaves the machine encrypted
+        let upload_url = std::env::var(TELEMETRY_URL_ENV_VAR).ok().filter(|url| {
+            let https = url.starts_with("https://");
+            if !https {
+                warn!(
+                    "Ignoring {}: uploads need an https:// URL",
+                    TELEMETRY_URL_ENV_VAR
+                );
+            }
+            https
+        });
+
+        Self {
+            enabled: false,
+            session: Uuid::new_v4(),
+            dir,
+            upload_url,
+            features_seen: HashSet::new(),
+        }
+    }
+}
+
+impl Telemetry {
+    pub fn uploads_enabled(&self) -> bool {
+        self.upload_url.is_some()
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.dir.join(TELEMETRY_LOG_FILE)
+    }
+
+    pub fn record(&mut self, event: TelemetryEvent) {
+        if !self.enabled {
+            return;
+        }
+        let record = TelemetryRecord {
+            session: self.session,
+            timestamp: Utc::now().to_rfc3339(),
+            event,
+        };
+        if let Err(e) = self.append(&record) {
+            warn!("Couldn't write telemetry event: {}", e);
+        }
+    }
+
+    pub fn record_feature(&mut self, feature: &'static str) {
+        if self.enabled && self.features_seen.insert(feature) {
+            self.record(TelemetryEvent::FeatureUsed {
+                feature: feature.to_string(),
+            });
+        }
+    }
+
+    fn append(&self, record: &TelemetryRecord) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(&self.dir)?;
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_path())?;
+        writeln!(log, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    // Everything recorded so far; lines that don't parse are skipped
+    pub fn read_log(&self) -> Vec<TelemetryRecord> {
+        fs::read_to_string(self.log_path())
+            .map(|log| {
+                log.lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        let _ = fs::remove_file(self.log_path());
+        let _ = fs::remove_file(self.dir.join(UPLOADED_MARKER_FILE));
+        self.features_seen.clear();
+        info!("📊 Telemetry log cleared");
+    }
+
+    // Sends the part of the log not uploaded yet. Blocks, so only used on exit.
+    fn upload_pending(&self) -> Result<usize, Box<dyn std::error::Error>> {
+        let Some(url) = self.upload_url.as_ref() else {
+            return Ok(0);
+        };
+        let log = match fs::read(self.log_path()) {
+            Ok(log) => log,
+            Err(_) => return Ok(0),
+        };
+        let marker_path = self.dir.join(UPLOADED_MARKER_FILE);
+        let uploaded = fs::read_to_string(&marker_path)
+            .ok()
+            .and_then(|marker| marker.trim().parse::<usize>().ok())
+            .filter(|uploaded| *uploaded <= log.len())
+            .unwrap_or(0);
+        if uploaded == log.len() {
+            return Ok(0);
+        }
+
+        Client::builder()
+            .timeout(Duration::from_secs(UPLOAD_TIMEOUT_SECONDS))
+            .build()?
+            .post(url)
+            .header("Content-Type", "application/x-ndjson")
+            .body(log[uploaded..].to_vec())
+            .send()?
+            .error_for_status()?;
+
+        fs::write(marker_path, log.len().to_string())?;
+        Ok(log.len() - uploaded)
+    }
+}
+
+// ==================== SUMMARY ====================
+
+// What the Gameplay Data page shows
+#[derive(Debug, Default, PartialEq)]
+pub struct TelemetrySummary {
+    pub sessions: usize,
+    pub missions: usize,
+    pub victories: usize,
+    pub average_intensity: f32,
+    pub adaptive_share: f32, // Fraction of missions on adaptive difficulty
+    pub phase_seconds: Vec<(String, f32)>, // Average time per phase, in log order
+    pub features: Vec<(String, usize)>, // Sessions that used each feature, most first
+}
+
+impl TelemetrySummary {
+    pub fn from_records(records: &[TelemetryRecord]) -> Self {
+        let mut summary = TelemetrySummary {
+            sessions: records
+                .iter()
+                .map(|record| record.session)
+                .collect::<HashSet<_>>()
+                .len(),
+            ..default()
+        };
+
+        let mut phase_totals: Vec<(String, f32, usize)> = Vec::new();
+        let mut feature_counts: BTreeMap<String, usize> = BTreeMap::new();
+        let mut adaptive_missions = 0;
+        for record in records {
+            match &record.event {
+                TelemetryEvent::MissionOutcome {
+                    victory,
+                    intensity,
+                    adaptive,
+                    ..
+                } => {
+                    summary.missions += 1;
+                    summary.victories += usize::from(*victory);
+                    summary.average_intensity += intensity;
+                    adaptive_missions += usize::from(*adaptive);
+                }
+                TelemetryEvent::PhaseTime { phase, seconds } => {
+                    match phase_totals.iter_mut().find(|(name, _, _)| name == phase) {
+                        Some((_, total, count)) => {
+                            *total += seconds;
+                            *count += 1;
+                        }
+                        None => phase_totals.push((phase.clone(), *seconds, 1)),
+                    }
+                }
+                TelemetryEvent::FeatureUsed { feature } => {
+                    *feature_counts.entry(feature.clone()).or_default() += 1;
+                }
+            }
+        }
+
+        if summary.missions > 0 {
+            summary.average_intensity /= summary.missions as f32;
+            summary.adaptive_share = adaptive_missions as f32 / summary.missions as f32;
+        }
+        summary.phase_seconds = phase_totals
+            .into_iter()
+            .map(|(phase, total, count)| (phase, total / count as f32))
+            .collect();
+        summary.features = feature_counts.into_iter().collect();
+        summary
+            .features
+            .sort_by_key(|(_, sessions)| std::cmp::Reverse(*sessions));
+        summary
+    }
+}
+
+// ==================== TELEMETRY SYSTEMS ====================
+
+// Copies the active profile's opt-in; without a profile nothing is recorded
+pub fn apply_telemetry_settings_system(
+    profiles: Res<ProfileManager>,
+    mut telemetry: ResMut<Telemetry>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    let enabled = profiles
+        .active
+        .as_ref()
+        .is_some_and(|profile| profile.settings.telemetry);
+    if telemetry.enabled != enabled {
+        telemetry.enabled = enabled;
+    }
+}
+
+fn is_mission_phase(phase: &GamePhase) -> bool {
+    matches!(
+        phase,
+        GamePhase::Preparation
+            | GamePhase::InitialRaid
+            | GamePhase::BlockConvoy
+            | GamePhase::ApplyPressure
+            | GamePhase::HoldTheLine
+    )
+}
+
+// Time spent in each mission phase and how each mission ended
+pub fn telemetry_phase_system(
+    mut telemetry: ResMut<Telemetry>,
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    ai_director: Res<AiDirector>,
+    time: Res<Time>,
+    mut current: Local<Option<(GamePhase, f32)>>,
+) {
+    let now = time.elapsed_seconds();
+    let phase = &game_state.game_phase;
+    if current.as_ref().is_some_and(|(shown, _)| shown == phase) {
+        return;
+    }
+
+    if let Some((previous, started)) = current.take() {
+        if is_mission_phase(&previous) {
+            telemetry.record(TelemetryEvent::PhaseTime {
+                phase: format!("{:?}", previous),
+                seconds: now - started,
+            });
+        }
+    }
+    if matches!(phase, GamePhase::Victory | GamePhase::Defeat) {
+        telemetry.record(TelemetryEvent::MissionOutcome {
+            mission: format!("{:?}", campaign.progress.current_mission),
+            victory: *phase == GamePhase::Victory,
+            seconds: game_state.mission_timer,
+            intensity: ai_director.intensity_level,
+            adaptive: ai_director.adaptive_difficulty,
+        });
+    }
+    *current = Some((phase.clone(), now));
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn telemetry_feature_system(
+    mut telemetry: ResMut<Telemetry>,
+    game_state: Res<GameState>,
+    accessibility: Res<AccessibilitySettings>,
+    tutorial: Res<Tutorial>,
+    editor: Option<Res<MapEditor>>,
+    console: Option<Res<DevConsole>>,
+    mut save_events: EventReader<SaveGameEvent>,
+    mut load_events: EventReader<LoadGameEvent>,
+) {
+    let saved = save_events.read().count() > 0;
+    let loaded = load_events.read().count() > 0;
+    if !telemetry.enabled {
+        return;
+    }
+
+    let features = [
+        (saved, "save_game"),
+        (loaded, "load_game"),
+        (tutorial.active, "tutorial"),
+        (editor.is_some_and(|editor| editor.active), "map_editor"),
+        (console.is_some_and(|console| console.open), "dev_console"),
+        (game_state.game_phase == GamePhase::ModsMenu, "mods_menu"),
+        (
+            game_state.game_phase == GamePhase::AccessibilityMenu,
+            "accessibility_menu",
+        ),
+        (accessibility.narration != NarrationMode::Off, "narration"),
+        (accessibility.slow_mode, "slow_mode"),
+    ];
+    for (used, feature) in features {
+        if used {
+            telemetry.record_feature(feature);
+        }
+    }
+}
+
+pub fn telemetry_upload_on_exit_system(
+    mut exit_events: EventReader<AppExit>,
+    telemetry: Res<Telemetry>,
+) {
+    if exit_events.read().next().is_none() || !telemetry.enabled {
+        return;
+    }
+
+    // Blocking is fine here - the app is shutting down and the request has a timeout
+    match telemetry.upload_pending() {
+        Ok(0) => {}
+        Ok(bytes) => info!("📊 Uploaded {} bytes of gameplay data", bytes),
+        Err(e) => warn!("📊 Telemetry upload failed, will retry next time: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(session: Uuid, event: TelemetryEvent) -> TelemetryRecord {
+        TelemetryRecord {
+            session,
+            timestamp: Utc::now().to_rfc3339(),
+            event,
+        }
+    }
+
+    #[test]
+    fn test_records_round_trip_as_flat_json_lines() {
+        let line = serde_json::to_string(&record(
+            Uuid::nil(),
+            TelemetryEvent::FeatureUsed {
+                feature: "tutorial".to_string(),
+            },
+        ))
+        .unwrap();
+        assert!(line.contains("\"event\":\"feature_used\""));
+        assert!(line.contains("\"feature\":\"tutorial\""));
+
+        let parsed: TelemetryRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            parsed.event,
+            TelemetryEvent::FeatureUsed {
+                feature: "tutorial".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_summary_averages_phases_and_counts_outcomes() {
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let phase = |seconds| TelemetryEvent::PhaseTime {
+            phase: "InitialRaid".to_string(),
+            seconds,
+        };
+        let outcome = |victory, adaptive| TelemetryEvent::MissionOutcome {
+            mission: "InitialRaid".to_string(),
+            victory,
+            seconds: 300.0,
+            intensity: 1.0,
+            adaptive,
+        };
+        let records = vec![
+            record(first, phase(100.0)),
+            record(first, outcome(true, true)),
+            record(second, phase(200.0)),
+            record(second, outcome(false, false)),
+        ];
+
+        let summary = TelemetrySummary::from_records(&records);
+        assert_eq!(summary.sessions, 2);
+        assert_eq!(summary.missions, 2);
+        assert_eq!(summary.victories, 1);
+        assert_eq!(summary.adaptive_share, 0.5);
+        assert_eq!(
+            summary.phase_seconds,
+            vec![("InitialRaid".to_string(), 150.0)]
+        );
+    }
+}
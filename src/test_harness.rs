@@ -0,0 +1,250 @@
+use crate::campaign::{evaluate_mission_objectives, Campaign, MissionResult};
+use crate::checkpoints::control_presence;
+use crate::components::*;
+use crate::config::UnitCatalog;
+use crate::damage::DamageEvent;
+use crate::headless::{build_headless_app, mission_finished, HeadlessOptions};
+use crate::map::CityMap;
+use crate::political_system::PoliticalState;
+use crate::resources::{GameAssets, GameState};
+use crate::spawners::spawn_unit;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+
+// ==================== MISSION TEST HARNESS ====================
+
+// The headless app, driven one fixed tick at a time, for tests that need the
+// real gameplay plugins rather than a hand-built World. Startup has already
+// run when a harness is handed out, so Ovidio, the cartel defenders and the
+// opening raid are on the map and the mission sits in Preparation.
+
+pub const HARNESS_SEED: u64 = 0x00C0_FFEE;
+
+pub struct MissionHarness {
+    app: App,
+    timestep: f32,
+}
+
+impl Default for MissionHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MissionHarness {
+    pub fn new() -> Self {
+        Self::with_options(&HeadlessOptions {
+            seed: Some(HARNESS_SEED),
+            ..default()
+        })
+    }
+
+    pub fn with_options(options: &HeadlessOptions) -> Self {
+        let mut app = build_headless_app(options);
+        app.finish();
+        app.cleanup();
+        let timestep = app.world.resource::<Time<Fixed>>().timestep().as_secs_f32();
+
+        // Runs Startup, so setup_game has placed everyone before the first assertion
+        app.update();
+
+        Self { app, timestep }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    // ---------- Advancing time ----------
+
+    // One update is exactly one simulation tick
+    pub fn tick(&mut self) {
+        self.app.update();
+    }
+
+    pub fn ticks(&mut self, count: usize) {
+        for _ in 0..count {
+            self.app.update();
+        }
+    }
+
+    pub fn run_for(&mut self, seconds: f32) {
+        self.ticks((seconds / self.timestep).ceil() as usize);
+    }
+
+    // Ticks until the condition holds or the time runs out; true if it held
+    pub fn run_until(&mut self, max_seconds: f32, condition: impl Fn(&World) -> bool) -> bool {
+        let mut elapsed = 0.0;
+        while !condition(&self.app.world) {
+            if elapsed >= max_seconds {
+                return false;
+            }
+            self.app.update();
+            elapsed += self.timestep;
+        }
+        true
+    }
+
+    pub fn run_until_finished(&mut self, max_seconds: f32) -> bool {
+        self.run_until(max_seconds, mission_finished)
+    }
+
+    // ---------- State ----------
+
+    pub fn phase(&self) -> GamePhase {
+        self.game_state().game_phase.clone()
+    }
+
+    pub fn game_state(&self) -> &GameState {
+        self.app.world.resource::<GameState>()
+    }
+
+    pub fn game_state_mut(&mut self) -> Mut<GameState> {
+        self.app.world.resource_mut::<GameState>()
+    }
+
+    pub fn campaign(&self) -> &Campaign {
+        self.app.world.resource::<Campaign>()
+    }
+
+    pub fn campaign_mut(&mut self) -> Mut<Campaign> {
+        self.app.world.resource_mut::<Campaign>()
+    }
+
+    pub fn political_state_mut(&mut self) -> Mut<PoliticalState> {
+        self.app.world.resource_mut::<PoliticalState>()
+    }
+
+    // Jumps the mission clock, e.g. to just short of a phase boundary
+    pub fn set_mission_timer(&mut self, seconds: f32) {
+        self.game_state_mut().mission_timer = seconds;
+    }
+
+    pub fn living(&mut self, faction: Faction) -> usize {
+        let mut units = self.app.world.query::<&Unit>();
+        units
+            .iter(&self.app.world)
+            .filter(|unit| unit.faction == faction && unit.health > 0.0)
+            .count()
+    }
+
+    // ---------- Setting up situations ----------
+
+    pub fn spawn_unit(&mut self, unit_type: UnitType, faction: Faction, position: Vec3) -> Entity {
+        self.app.world.run_system_once(
+            move |mut commands: Commands,
+                  game_assets: Res<GameAssets>,
+                  catalog: Res<UnitCatalog>| {
+                spawn_unit(
+                    &mut commands,
+                    unit_type.clone(),
+                    faction.clone(),
+                    position,
+                    &game_assets,
+                    &catalog,
+                )
+            },
+        )
+    }
+
+    // Drops every unit matching the filter to zero health; the bodies stay
+    // on the map and count as casualties like any other
+    pub fn kill_where(&mut self, filter: impl Fn(&Unit) -> bool) {
+        let mut units = self.app.world.query::<&mut Unit>();
+        for mut unit in units.iter_mut(&mut self.app.world) {
+            if filter(&unit) {
+                unit.health = 0.0;
+            }
+        }
+    }
+
+    pub fn kill_all(&mut self, faction: Faction) {
+        self.kill_where(|unit| unit.faction == faction);
+    }
+
+    // A hit sent through the damage pipeline, as combat would; it lands on
+    // the next tick
+    pub fn damage(&mut self, target: Entity, amount: f32, attacker: Option<Entity>) {
+        self.app
+            .world
+            .resource_mut::<Events<DamageEvent>>()
+            .send(DamageEvent {
+                attacker,
+                target,
+                amount,
+                weapon: None,
+            });
+    }
+
+    pub fn find_unit(&mut self, filter: impl Fn(&Unit) -> bool) -> Option<Entity> {
+        let mut units = self.app.world.query::<(Entity, &Unit)>();
+        units
+            .iter(&self.app.world)
+            .find(|(_, unit)| filter(unit))
+            .map(|(entity, _)| entity)
+    }
+
+    // Runs the objective evaluation once against the current world, without
+    // the phase change game_phase_system would make from the result
+    pub fn evaluate_objectives(&mut self) -> MissionResult {
+        self.app.world.run_system_once(
+            |mut campaign: ResMut<Campaign>,
+             game_state: Res<GameState>,
+             units: Query<(&Unit, &Transform)>,
+             checkpoints: Query<(&Checkpoint, &Transform)>,
+             city_map: Res<CityMap>| {
+                let checkpoints = control_presence(checkpoints.iter());
+                evaluate_mission_objectives(
+                    &mut campaign,
+                    &game_state,
+                    &units,
+                    &city_map,
+                    &checkpoints,
+                )
+            },
+        )
+    }
+
+    // ---------- Assertions ----------
+
+    #[track_caller]
+    pub fn assert_phase(&self, expected: GamePhase) {
+        assert_eq!(
+            self.phase(),
+            expected,
+            "mission timer at {:.1}s",
+            self.game_state().mission_timer
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_objective_completed(&self, kind: &str) {
+        let status = self
+            .campaign()
+            .current_objectives
+            .iter()
+            .find(|status| status.objective.kind == kind)
+            .unwrap_or_else(|| panic!("no {} objective in the current mission", kind));
+        assert!(
+            status.completed,
+            "{} objective at {:.1}% progress",
+            kind,
+            status.progress * 100.0
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_pressure_below(&self, threshold: f32) {
+        let total = self.campaign().political_pressure.total_pressure;
+        assert!(
+            total < threshold,
+            "political pressure {:.2} is not below {:.2}",
+            total,
+            threshold
+        );
+    }
+}
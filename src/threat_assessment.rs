@@ -0,0 +1,388 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::BalanceConfig;
+use crate::localization::{tr, tr_args};
+use crate::resources::IntelSystem;
+use crate::ui::{find_enemy_at_position, UiTheme};
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, play_tactical_sound, update_dashboard_panel,
+    SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== THREAT ASSESSMENT PLUGIN ====================
+
+// Hovering an enemy brings up a tooltip, but all it says at first is that
+// it's hostile. Middle-clicking it spends intel points - earned one for
+// every intercept, tip and recon report - to have it analyzed, and a few
+// seconds later the tooltip shows its type, health, armor, how many soldiers
+// are with it and what it's most likely after. Recon pays for itself this
+// way: the more the network reports, the more of the raid can be read.
+
+const TOOLTIP_OFFSET: f32 = 18.0; // From the cursor, so it doesn't cover the target
+
+pub struct ThreatAssessmentPlugin;
+
+impl Plugin for ThreatAssessmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            threat_analysis_system
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        )
+        .add_systems(Update, threat_tooltip_system.in_set(GameSet::InGame));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LikelyObjective {
+    HuntingOvidio,
+    Withdrawing,
+    Fighting,
+    Investigating,
+    Holding,
+    Advancing,
+}
+
+impl LikelyObjective {
+    fn key(self) -> &'static str {
+        match self {
+            LikelyObjective::HuntingOvidio => "hud.threat_objective_hunting",
+            LikelyObjective::Withdrawing => "hud.threat_objective_withdrawing",
+            LikelyObjective::Fighting => "hud.threat_objective_fighting",
+            LikelyObjective::Investigating => "hud.threat_objective_investigating",
+            LikelyObjective::Holding => "hud.threat_objective_holding",
+            LikelyObjective::Advancing => "hud.threat_objective_advancing",
+        }
+    }
+}
+
+/// What an enemy is most likely after, read off what it's doing.
+pub fn likely_objective(
+    hunting_hvt: bool,
+    fleeing: bool,
+    has_target: bool,
+    investigating: bool,
+    mode: Option<&TacticalMode>,
+) -> LikelyObjective {
+    if hunting_hvt {
+        LikelyObjective::HuntingOvidio
+    } else if fleeing || mode == Some(&TacticalMode::Retreating) {
+        LikelyObjective::Withdrawing
+    } else if has_target
+        || matches!(
+            mode,
+            Some(TacticalMode::Engaging | TacticalMode::Suppressed)
+        )
+    {
+        LikelyObjective::Fighting
+    } else if investigating {
+        LikelyObjective::Investigating
+    } else if matches!(
+        mode,
+        Some(TacticalMode::HoldPosition | TacticalMode::Overwatch)
+    ) {
+        LikelyObjective::Holding
+    } else {
+        LikelyObjective::Advancing
+    }
+}
+
+/// How many of `allies` (the unit itself among them) are within `radius`
+/// of `position`.
+pub fn squad_size(position: Vec2, allies: &[Vec2], radius: f32) -> usize {
+    allies
+        .iter()
+        .filter(|ally| ally.distance(position) <= radius)
+        .count()
+}
+
+// ==================== SYSTEMS ====================
+
+// Middle click on an enemy: spend intel to have it analyzed
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_target_system(
+    mut commands: Commands,
+    mouse: Res<Input<MouseButton>>,
+    balance: Res<BalanceConfig>,
+    mut intel: Option<ResMut<IntelSystem>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    unit_query: Query<(Entity, &Transform, &Unit, Option<&Selected>)>,
+    analysis_query: Query<(), With<ThreatAnalysis>>,
+) {
+    if !mouse.just_pressed(MouseButton::Middle) {
+        return;
+    }
+    let balance = &balance.threat_assessment;
+    let Some(target) = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+        .and_then(|cursor| find_enemy_at_position(cursor.extend(0.0), &unit_query))
+    else {
+        return;
+    };
+    if analysis_query.contains(target) {
+        play_tactical_sound("radio", &tr("radio.threat_already_analyzed"));
+        return;
+    }
+    let affordable = intel
+        .as_deref_mut()
+        .is_some_and(|intel| intel.spend_intel(balance.intel_cost));
+    if !affordable {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.threat_no_intel", &[("cost", &balance.intel_cost)]),
+        );
+        return;
+    }
+
+    commands.entity(target).insert(ThreatAnalysis {
+        ready_in: balance.analysis_seconds,
+    });
+    play_tactical_sound("radio", &tr("radio.threat_analyzing"));
+}
+
+pub fn threat_analysis_system(
+    time: Res<Time>,
+    mut analysis_query: Query<(&Unit, &mut ThreatAnalysis)>,
+) {
+    let dt = time.delta_seconds();
+    for (unit, mut analysis) in analysis_query.iter_mut() {
+        if analysis.ready_in <= 0.0 {
+            continue;
+        }
+        analysis.ready_in -= dt;
+        if analysis.ready_in <= 0.0 && unit.health > 0.0 {
+            play_tactical_sound(
+                "radio",
+                &tr_args(
+                    "radio.threat_analyzed",
+                    &[("unit", &format!("{:?}", unit.unit_type))],
+                ),
+            );
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ThreatTooltip;
+
+// Type aliases to reduce complexity
+type HoveredQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        &'static Transform,
+        Option<&'static ThreatAnalysis>,
+        Option<&'static TacticalState>,
+        Has<HvtTasking>,
+        Has<Fleeing>,
+        Has<Investigating>,
+    ),
+>;
+
+// What's known about the enemy under the cursor
+#[allow(clippy::too_many_arguments)]
+pub fn threat_tooltip_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    theme: Res<UiTheme>,
+    intel: Option<Res<IntelSystem>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    unit_query: HoveredQuery,
+    mut tooltip_query: Query<(&mut Text, &mut Style, &mut Visibility), With<ThreatTooltip>>,
+) {
+    let balance = &balance.threat_assessment;
+    let cursor = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|cursor_pos| {
+            let (camera, camera_transform) = camera_query.get_single().ok()?;
+            let world = camera.viewport_to_world_2d(camera_transform, cursor_pos)?;
+            Some((cursor_pos, world))
+        });
+    let hovered = cursor.and_then(|(screen, world)| {
+        unit_query
+            .iter()
+            .filter(|(_, unit, transform, ..)| {
+                unit.faction == Faction::Military
+                    && unit.health > 0.0
+                    && transform.translation.truncate().distance(world) < 50.0
+            })
+            .min_by(|a, b| {
+                let a = a.2.translation.truncate().distance(world);
+                let b = b.2.translation.truncate().distance(world);
+                a.total_cmp(&b)
+            })
+            .map(|hovered| (screen, hovered))
+    });
+
+    let tooltip = hovered.map(|(screen, hovered)| {
+        let (_, unit, transform, analysis, tactical_state, hunting, fleeing, investigating) =
+            hovered;
+        let mut lines = vec![dashboard_line(
+            tr("hud.threat_hostile"),
+            13.0,
+            Color::ORANGE_RED,
+        )];
+        match analysis {
+            None => {
+                let points = intel.as_deref().map_or(0, IntelSystem::intel_points);
+                lines.push(dashboard_line(
+                    tr_args(
+                        "hud.threat_analyze_hint",
+                        &[("cost", &balance.intel_cost), ("points", &points)],
+                    ),
+                    11.0,
+                    Color::GRAY,
+                ));
+            }
+            Some(analysis) if analysis.ready_in > 0.0 => {
+                lines.push(dashboard_line(
+                    tr_args(
+                        "hud.threat_analyzing",
+                        &[("seconds", &format!("{:.0}", analysis.ready_in.ceil()))],
+                    ),
+                    11.0,
+                    Color::YELLOW,
+                ));
+            }
+            Some(_) => {
+                let position = transform.translation.truncate();
+                let allies: Vec<Vec2> = unit_query
+                    .iter()
+                    .filter(|(_, other, ..)| other.faction == unit.faction && other.health > 0.0)
+                    .map(|(_, _, other_transform, ..)| other_transform.translation.truncate())
+                    .collect();
+                let objective = likely_objective(
+                    hunting,
+                    fleeing,
+                    unit.target.is_some(),
+                    investigating,
+                    tactical_state.map(|state| &state.mode),
+                );
+                let facts = [
+                    tr_args(
+                        "hud.threat_type",
+                        &[("unit", &format!("{:?}", unit.unit_type))],
+                    ),
+                    tr_args(
+                        "hud.threat_health",
+                        &[
+                            ("health", &format!("{:.0}", unit.health)),
+                            ("max", &format!("{:.0}", unit.max_health)),
+                        ],
+                    ),
+                    tr_args(
+                        "hud.threat_armor",
+                        &[("armor", &format!("{:?}", unit.equipment.armor))],
+                    ),
+                    tr_args(
+                        "hud.threat_squad",
+                        &[(
+                            "count",
+                            &squad_size(position, &allies, balance.squad_radius),
+                        )],
+                    ),
+                    tr_args(
+                        "hud.threat_objective",
+                        &[("objective", &tr(objective.key()))],
+                    ),
+                ];
+                lines.extend(
+                    facts
+                        .into_iter()
+                        .map(|fact| dashboard_line(fact, 11.0, Color::WHITE)),
+                );
+            }
+        }
+        (screen, lines)
+    });
+
+    let visibility = if tooltip.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if let Ok((mut text, mut style, mut tooltip_visibility)) = tooltip_query.get_single_mut() {
+        if *tooltip_visibility != visibility {
+            *tooltip_visibility = visibility;
+        }
+        if let Some((screen, lines)) = tooltip {
+            style.left = Val::Px(screen.x + TOOLTIP_OFFSET);
+            style.top = Val::Px(screen.y + TOOLTIP_OFFSET);
+            update_dashboard_panel(&mut text, lines, &theme);
+        }
+    } else if let Some((screen, lines)) = tooltip {
+        let panel = create_dashboard_panel(
+            Style {
+                left: Val::Px(screen.x + TOOLTIP_OFFSET),
+                top: Val::Px(screen.y + TOOLTIP_OFFSET),
+                width: Val::Px(240.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.85),
+            lines,
+            &theme,
+        );
+        commands.spawn((panel, ThreatTooltip));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_likely_objective_reads_what_the_enemy_is_doing() {
+        let engaging = Some(&TacticalMode::Engaging);
+        let holding = Some(&TacticalMode::HoldPosition);
+        assert_eq!(
+            likely_objective(true, false, true, false, engaging),
+            LikelyObjective::HuntingOvidio
+        );
+        assert_eq!(
+            likely_objective(false, true, true, false, engaging),
+            LikelyObjective::Withdrawing
+        );
+        assert_eq!(
+            likely_objective(false, false, false, false, engaging),
+            LikelyObjective::Fighting
+        );
+        assert_eq!(
+            likely_objective(false, false, false, true, holding),
+            LikelyObjective::Investigating
+        );
+        assert_eq!(
+            likely_objective(false, false, false, false, holding),
+            LikelyObjective::Holding
+        );
+        assert_eq!(
+            likely_objective(false, false, false, false, None),
+            LikelyObjective::Advancing
+        );
+    }
+
+    #[test]
+    fn test_squad_size_counts_the_soldiers_close_by() {
+        let allies = [
+            Vec2::ZERO,
+            Vec2::new(50.0, 0.0),
+            Vec2::new(0.0, 100.0),
+            Vec2::new(500.0, 0.0),
+        ];
+        assert_eq!(squad_size(Vec2::ZERO, &allies, 120.0), 3);
+        assert_eq!(squad_size(Vec2::new(500.0, 0.0), &allies, 120.0), 1);
+    }
+}
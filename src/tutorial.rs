@@ -0,0 +1,362 @@
+use crate::accessibility::FormationModifier;
+use crate::components::{FormationType, GamePhase, IsometricCamera, MiniMap, Selected};
+use crate::intel_system::IntelUIPanel;
+use crate::localization::tr;
+use crate::narration::{NarrationEvent, NarrationPriority};
+use crate::political_system::PoliticalUIPanel;
+use crate::profile::ProfileManager;
+use crate::resources::GameState;
+use crate::ui::UiTheme;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ==================== TUTORIAL ====================
+
+// The tutorial is started from the main menu and driven by
+// assets/scripts/tutorial.rhai: the script shows a prompt for each step and
+// advances when on_player_action reports the action the step asks for. This
+// module detects those actions, draws the prompt, highlights the HUD element
+// it points at and holds the mission clock so no attack starts mid-lesson.
+// BACKSPACE skips the rest.
+
+const HIGHLIGHT_BORDER: f32 = 3.0;
+const HIGHLIGHT_PULSE_SPEED: f32 = 4.0; // Radians per second
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Tutorial>().add_systems(
+            Update,
+            (
+                apply_tutorial_settings_system,
+                tutorial_action_system,
+                tutorial_prompt_system,
+                tutorial_highlight_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+// What a step can wait for; scripts see these names in on_player_action
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutorialAction {
+    CameraMoved,
+    UnitSelected,
+    FormationMove, // Move order while a formation modifier is active
+    AbilityUsed,
+    Continue, // ENTER on steps that only explain something
+}
+
+// HUD element a prompt points at, by the name scripts pass to show_prompt
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TutorialHighlight {
+    #[default]
+    None,
+    Minimap,
+    IntelPanel,
+    PoliticalPanel,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TutorialPrompt {
+    pub key: String, // Localization key of the prompt text
+    pub highlight: TutorialHighlight,
+}
+
+#[derive(Resource)]
+pub struct Tutorial {
+    pub active: bool,
+    pub prompt: Option<TutorialPrompt>,
+    pub show_highlights: bool, // The profile's tutorial hints setting
+    actions: Vec<TutorialAction>,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self {
+            active: false,
+            prompt: None,
+            show_highlights: true,
+            actions: Vec::new(),
+        }
+    }
+}
+
+impl Tutorial {
+    // The steps live in a script, so there is no tutorial without scripting
+    pub fn start(&mut self) -> bool {
+        if !cfg!(feature = "scripting") {
+            warn!("The tutorial needs the scripting feature");
+            return false;
+        }
+        self.active = true;
+        self.prompt = None;
+        self.actions.clear();
+        true
+    }
+
+    pub fn finish(&mut self) {
+        self.active = false;
+        self.prompt = None;
+        self.actions.clear();
+    }
+
+    pub fn show(&mut self, key: String, highlight: TutorialHighlight) {
+        if self.active {
+            self.prompt = Some(TutorialPrompt { key, highlight });
+        }
+    }
+
+    // While the tutorial runs the mission timer stands still
+    pub fn holds_mission_clock(&self) -> bool {
+        self.active
+    }
+
+    pub fn highlight(&self) -> TutorialHighlight {
+        if !self.show_highlights {
+            return TutorialHighlight::None;
+        }
+        self.prompt
+            .as_ref()
+            .map_or(TutorialHighlight::None, |prompt| prompt.highlight)
+    }
+
+    fn record(&mut self, action: TutorialAction) {
+        if self.active && !self.actions.contains(&action) {
+            self.actions.push(action);
+        }
+    }
+
+    // Handed to the scripts' on_player_action hook
+    pub fn take_actions(&mut self) -> Vec<TutorialAction> {
+        std::mem::take(&mut self.actions)
+    }
+}
+
+#[derive(Component)]
+pub struct TutorialPromptBox;
+
+// Copies the active profile's tutorial hints setting
+pub fn apply_tutorial_settings_system(
+    profiles: Res<ProfileManager>,
+    mut tutorial: ResMut<Tutorial>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    if let Some(profile) = profiles.active.as_ref() {
+        tutorial.show_highlights = profile.settings.show_tutorial_hints;
+    }
+}
+
+// Border and visibility a highlighted HUD element had before
+#[derive(Component)]
+pub struct TutorialHighlighted {
+    border: UiRect,
+    visibility: Visibility,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn tutorial_action_system(
+    mut tutorial: ResMut<Tutorial>,
+    game_state: Res<GameState>,
+    input: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    formation_modifier: Res<FormationModifier>,
+    newly_selected: Query<(), Added<Selected>>,
+    selected: Query<(), With<Selected>>,
+    camera: Query<&Transform, With<IsometricCamera>>,
+    mut last_camera: Local<Option<Transform>>,
+) {
+    let camera_transform = camera.get_single().ok().copied();
+    let camera_moved = last_camera.is_some() && *last_camera != camera_transform;
+    *last_camera = camera_transform;
+
+    if !tutorial.active {
+        return;
+    }
+    // Leaving for the main menu abandons the tutorial
+    if game_state.game_phase == GamePhase::MainMenu {
+        tutorial.finish();
+        return;
+    }
+    if input.just_pressed(KeyCode::Back) {
+        info!("Tutorial skipped");
+        tutorial.finish();
+        return;
+    }
+
+    if camera_moved {
+        tutorial.record(TutorialAction::CameraMoved);
+    }
+    if !newly_selected.is_empty() {
+        tutorial.record(TutorialAction::UnitSelected);
+    }
+    let has_selection = !selected.is_empty();
+    if has_selection
+        && mouse.just_pressed(MouseButton::Right)
+        && formation_modifier.formation() != FormationType::Line
+    {
+        tutorial.record(TutorialAction::FormationMove);
+    }
+    if has_selection && input.any_just_pressed([KeyCode::Q, KeyCode::E]) {
+        tutorial.record(TutorialAction::AbilityUsed);
+    }
+    if input.just_pressed(KeyCode::Return) {
+        tutorial.record(TutorialAction::Continue);
+    }
+}
+
+pub fn tutorial_prompt_system(
+    mut commands: Commands,
+    tutorial: Res<Tutorial>,
+    prompt_query: Query<Entity, With<TutorialPromptBox>>,
+    mut narration: EventWriter<NarrationEvent>,
+    mut shown: Local<Option<TutorialPrompt>>,
+) {
+    // Recorded actions change the resource too; only a new prompt redraws
+    if *shown == tutorial.prompt {
+        return;
+    }
+    shown.clone_from(&tutorial.prompt);
+    for entity in prompt_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(prompt) = tutorial.prompt.as_ref() else {
+        return;
+    };
+
+    let text = tr(&prompt.key);
+    narration.send(NarrationEvent::new(text.clone(), NarrationPriority::Update));
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(25.0),
+                    top: Val::Px(60.0),
+                    width: Val::Percent(50.0),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.85)),
+                ..default()
+            },
+            TutorialPromptBox,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                tr("tutorial.hint"),
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::rgb(0.7, 0.7, 0.7),
+                    ..default()
+                },
+            ));
+        });
+}
+
+type HighlightTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Style,
+        &'static mut Visibility,
+        Option<&'static TutorialHighlighted>,
+        AnyOf<(
+            &'static MiniMap,
+            &'static IntelUIPanel,
+            &'static PoliticalUIPanel,
+        )>,
+    ),
+>;
+
+// Pulses a border around the element the current prompt points at, showing it
+// even if it would otherwise be hidden, and puts it back when the step is done
+pub fn tutorial_highlight_system(
+    mut commands: Commands,
+    tutorial: Res<Tutorial>,
+    theme: Res<UiTheme>,
+    time: Res<Time>,
+    mut targets: HighlightTargetQuery,
+) {
+    let highlight = tutorial.highlight();
+    let pulse = 0.6 + 0.4 * (time.elapsed_seconds() * HIGHLIGHT_PULSE_SPEED).sin();
+    let border_color = theme.colors().selection.with_a(pulse);
+
+    for (entity, mut style, mut visibility, highlighted, (minimap, intel, political)) in
+        targets.iter_mut()
+    {
+        let target = match (minimap, intel, political) {
+            (Some(_), _, _) => TutorialHighlight::Minimap,
+            (_, Some(_), _) => TutorialHighlight::IntelPanel,
+            _ => TutorialHighlight::PoliticalPanel,
+        };
+
+        match highlighted {
+            _ if target == highlight => {
+                if highlighted.is_none() {
+                    commands.entity(entity).insert(TutorialHighlighted {
+                        border: style.border,
+                        visibility: *visibility,
+                    });
+                    style.border = UiRect::all(Val::Px(HIGHLIGHT_BORDER));
+                }
+                if *visibility == Visibility::Hidden {
+                    *visibility = Visibility::Inherited;
+                }
+                commands.entity(entity).insert(BorderColor(border_color));
+            }
+            Some(previous) => {
+                style.border = previous.border;
+                *visibility = previous.visibility;
+                commands
+                    .entity(entity)
+                    .insert(BorderColor(Color::NONE))
+                    .remove::<TutorialHighlighted>();
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actions_only_count_while_the_tutorial_runs() {
+        let mut tutorial = Tutorial::default();
+        tutorial.record(TutorialAction::CameraMoved);
+        assert!(tutorial.take_actions().is_empty());
+
+        assert_eq!(tutorial.start(), cfg!(feature = "scripting"));
+        if !tutorial.active {
+            return;
+        }
+        tutorial.record(TutorialAction::Continue);
+        tutorial.record(TutorialAction::Continue);
+        assert_eq!(tutorial.take_actions(), vec![TutorialAction::Continue]);
+        assert!(tutorial.holds_mission_clock());
+
+        tutorial.show("tutorial.minimap".to_string(), TutorialHighlight::Minimap);
+        assert_eq!(tutorial.highlight(), TutorialHighlight::Minimap);
+        tutorial.show_highlights = false;
+        assert_eq!(tutorial.highlight(), TutorialHighlight::None);
+        tutorial.finish();
+        assert_eq!(tutorial.highlight(), TutorialHighlight::None);
+        assert!(!tutorial.holds_mission_clock());
+    }
+}
@@ -3,15 +3,29 @@
 
 pub mod ui_animations;
 pub mod ui_camera; // Camera control system
+pub mod ui_campaign_map; // Campaign hub between missions
+pub mod ui_caster; // Spectator timeline, force graphs and camera views
+pub mod ui_console; // Drop-down developer console
 pub mod ui_core; // Core UI updates, health bars, damage indicators, particles
 pub mod ui_menus; // Main menu, mission briefing, victory/defeat screens
 pub mod ui_minimap; // Minimap system
+pub mod ui_orders; // Order markers and path preview
 pub mod ui_selection; // Unit selection and target indicators // Sprite and movement animations
+pub mod ui_theme; // Colorblind palettes and high-contrast mode
+pub mod ui_unit_animation; // Sprite sheet animation for units
+pub mod ui_unit_info; // Selected unit's kit and hit chances
 
 // Re-export all systems for easy access
 pub use ui_animations::*;
 pub use ui_camera::*;
+pub use ui_campaign_map::*;
+pub use ui_caster::*;
+pub use ui_console::*;
 pub use ui_core::*;
 pub use ui_menus::*;
 pub use ui_minimap::*;
+pub use ui_orders::*;
 pub use ui_selection::*;
+pub use ui_theme::*;
+pub use ui_unit_animation::*;
+pub use ui_unit_info::*;
@@ -1,4 +1,5 @@
 use crate::components::*;
+use crate::config::GraphicsSettings;
 use bevy::prelude::*;
 
 // ==================== ANIMATION SYSTEMS ====================
@@ -6,7 +7,16 @@ use bevy::prelude::*;
 pub fn sprite_animation_system(
     mut animated_query: Query<(&mut Transform, &mut AnimatedSprite)>,
     time: Res<Time>,
+    graphics: Option<Res<GraphicsSettings>>,
 ) {
+    // Lower quality presets leave sprites still at their base scale
+    if !graphics.map_or(true, |graphics| graphics.ui_effects) {
+        for (mut transform, animated_sprite) in animated_query.iter_mut() {
+            transform.scale = animated_sprite.base_scale;
+        }
+        return;
+    }
+
     for (mut transform, mut animated_sprite) in animated_query.iter_mut() {
         animated_sprite.animation_timer.tick(time.delta());
 
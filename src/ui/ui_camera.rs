@@ -1,4 +1,5 @@
 use crate::components::*;
+use crate::config::MissionOverrides;
 use bevy::prelude::*;
 
 // ==================== CAMERA CONTROL SYSTEM ====================
@@ -7,9 +8,10 @@ pub fn camera_control_system(
     mut camera_query: Query<(&mut Transform, &mut IsometricCamera), With<Camera>>,
     input: Res<Input<KeyCode>>,
     mut scroll_events: EventReader<bevy::input::mouse::MouseWheel>,
-    time: Res<Time>,
+    time: Res<Time<Real>>, // Keeps panning while the battle is paused
     mut windows: Query<&mut Window>,
     mut stored_window_size: Local<Vec2>,
+    overrides: Option<Res<MissionOverrides>>,
 ) {
     // Robust camera control with error handling
     let Ok((mut transform, camera)) = camera_query.get_single_mut() else {
@@ -37,6 +39,13 @@ pub fn camera_control_system(
     if movement != Vec3::ZERO {
         transform.translation += movement.normalize() * camera.pan_speed * time.delta_seconds();
     }
+    // Missions can keep the camera over one neighborhood
+    if let Some(overrides) = overrides {
+        let clamped = overrides.clamp_camera(transform.translation);
+        if clamped != transform.translation {
+            transform.translation = clamped;
+        }
+    }
 
     // Mouse wheel zoom
     for scroll in scroll_events.read() {
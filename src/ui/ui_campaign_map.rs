@@ -0,0 +1,754 @@
+use crate::campaign::{Campaign, MissionConfig};
+use crate::components::*;
+use crate::config::{BalanceConfig, GraphicsSettings, UnitCatalog};
+use crate::economy::recruitable_units;
+use crate::localization::{tr, tr_args};
+use crate::narration::{NarrationEvent, NarrationPriority};
+use crate::resources::GameState;
+use crate::save::save_system::{CampaignProgress, MissionId};
+use crate::scoring::Medal;
+use crate::ui::ui_theme::UiTheme;
+use crate::upgrades::MissionUpgrade;
+use crate::utils::play_tactical_sound;
+use bevy::prelude::*;
+
+// ==================== CAMPAIGN MAP ====================
+
+// The hub between missions: a stylized overview of Culiacán with a node for
+// every mission where it was fought, locked, open or won with its medal. Each
+// win holds a pulsing patch of territory that grows with the medal, and front
+// lines march from the latest win towards the mission it opened up. The
+// roster and upgrade panels show what the cartel can field, and SPACE opens
+// the briefing for the selected mission.
+
+const ROSTER_KEY: KeyCode = KeyCode::R;
+const UPGRADES_KEY: KeyCode = KeyCode::U;
+
+const MAP_SIZE: Vec2 = Vec2::new(800.0, 520.0);
+const SIDE_PANEL_WIDTH: f32 = 300.0;
+const NODE_SIZE: f32 = 22.0;
+const RIVER_DOT_SPACING: f32 = 10.0;
+const RIVER_DOT_SIZE: f32 = 6.0;
+const FRONT_DOT_SPACING: f32 = 16.0;
+const FRONT_DOT_SIZE: f32 = 5.0;
+const FRONT_SPEED: f32 = 24.0; // Pixels per second the dots march
+const ZONE_PULSE_SPEED: f32 = 2.0; // Radians per second
+const ZONE_PULSE: f32 = 0.12; // Fraction the territory grows and shrinks by
+
+// Humaya and Tamazula meet at Tres Ríos and leave as the Culiacán river
+const RIVERS: [&[Vec2]; 3] = [
+    &[
+        Vec2::new(0.30, 0.0),
+        Vec2::new(0.38, 0.18),
+        Vec2::new(0.48, 0.38),
+    ],
+    &[
+        Vec2::new(0.92, 0.0),
+        Vec2::new(0.70, 0.22),
+        Vec2::new(0.48, 0.38),
+    ],
+    &[
+        Vec2::new(0.48, 0.38),
+        Vec2::new(0.30, 0.50),
+        Vec2::new(0.0, 0.62),
+    ],
+];
+
+pub struct CampaignMapPlugin;
+
+impl Plugin for CampaignMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CampaignHub>().add_systems(
+            Update,
+            (
+                campaign_map_input_system,
+                campaign_map_ui_system,
+                campaign_map_animation_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+// ==================== MAP LAYOUT ====================
+
+// Where each mission was fought, as a fraction of the map from its top-left
+// corner. Stylized: close to where a local would point, not to a survey.
+pub fn mission_site(mission: &MissionId) -> Vec2 {
+    match mission {
+        MissionId::InitialRaid => Vec2::new(0.50, 0.26), // Tres Ríos
+        MissionId::UrbanWarfare => Vec2::new(0.46, 0.45),
+        MissionId::LasFloresiDefense => Vec2::new(0.24, 0.36),
+        MissionId::TierraBlancaRoadblocks => Vec2::new(0.74, 0.44),
+        MissionId::CentroUrbanFight => Vec2::new(0.50, 0.58),
+        MissionId::LasQuintasSiege => Vec2::new(0.68, 0.68),
+        MissionId::AirportAssault => Vec2::new(0.16, 0.80), // Bachigualato
+        MissionId::GovernmentResponse => Vec2::new(0.82, 0.18), // Ninth Military Zone
+        MissionId::CivilianEvacuation => Vec2::new(0.36, 0.68),
+        MissionId::PoliticalNegotiation => Vec2::new(0.60, 0.52), // Government palace
+        MissionId::CeasefireNegotiation => Vec2::new(0.28, 0.22),
+        MissionId::OrderedWithdrawal => Vec2::new(0.88, 0.74), // Highway out east
+        MissionId::Resolution => Vec2::new(0.62, 0.10),
+    }
+}
+
+fn site_pixels(mission: &MissionId) -> Vec2 {
+    mission_site(mission) * MAP_SIZE
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SiteState {
+    Locked,
+    Open,
+    Won(Option<Medal>),
+}
+
+pub fn site_state(progress: &CampaignProgress, mission: &MissionId) -> SiteState {
+    if progress.completed_missions.contains(mission) {
+        SiteState::Won(progress.best_medal(mission))
+    } else if progress.is_mission_unlocked(mission) {
+        SiteState::Open
+    } else {
+        SiteState::Locked
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrontLine {
+    pub from: MissionId,
+    pub to: MissionId,
+    pub advancing: bool, // Still being pushed, rather than ground already held
+}
+
+// Routes between consecutive missions the cartel has fought its way along
+pub fn front_lines(progress: &CampaignProgress) -> Vec<FrontLine> {
+    MissionId::ALL
+        .windows(2)
+        .filter_map(|pair| {
+            if !matches!(site_state(progress, &pair[0]), SiteState::Won(_)) {
+                return None;
+            }
+            let advancing = match site_state(progress, &pair[1]) {
+                SiteState::Open => true,
+                SiteState::Won(_) => false,
+                SiteState::Locked => return None,
+            };
+            Some(FrontLine {
+                from: pair[0].clone(),
+                to: pair[1].clone(),
+                advancing,
+            })
+        })
+        .collect()
+}
+
+// Better results hold more of the district
+pub fn territory_radius(medal: Option<Medal>) -> f32 {
+    match medal {
+        None => 24.0,
+        Some(Medal::Bronze) => 32.0,
+        Some(Medal::Silver) => 40.0,
+        Some(Medal::Gold) => 48.0,
+    }
+}
+
+// The playable mission `step` places from the selected one, wrapping around
+pub fn step_selection(progress: &CampaignProgress, step: isize) -> MissionId {
+    let playable: Vec<&MissionId> = MissionId::ALL
+        .iter()
+        .filter(|mission| progress.is_mission_unlocked(mission))
+        .collect();
+    let current = playable
+        .iter()
+        .position(|mission| **mission == progress.current_mission)
+        .unwrap_or(0) as isize;
+    let index = (current + step).rem_euclid(playable.len() as isize);
+    playable[index as usize].clone()
+}
+
+// ==================== HUB STATE ====================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HubPanel {
+    #[default]
+    Mission,
+    Roster,
+    Upgrades,
+}
+
+#[derive(Resource, Default)]
+pub struct CampaignHub {
+    pub panel: HubPanel,
+}
+
+impl CampaignHub {
+    // A panel's key opens it, and closes it again back to the mission details
+    pub fn toggle(&mut self, panel: HubPanel) {
+        self.panel = if self.panel == panel {
+            HubPanel::Mission
+        } else {
+            panel
+        };
+    }
+}
+
+#[derive(Component)]
+pub struct CampaignMapScreen;
+
+#[derive(Component)]
+pub struct FrontLineDot {
+    from: Vec2,
+    to: Vec2,
+    offset: f32, // Distance along the line at time zero
+}
+
+#[derive(Component)]
+pub struct TerritoryZone {
+    center: Vec2,
+    radius: f32,
+    phase: f32, // Keeps neighbouring zones from pulsing in step
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn campaign_map_input_system(
+    mut game_state: ResMut<GameState>,
+    mut input: ResMut<Input<KeyCode>>,
+    mut campaign: ResMut<Campaign>,
+    mut hub: ResMut<CampaignHub>,
+    mut narration: EventWriter<NarrationEvent>,
+    mut opened: Local<bool>,
+) {
+    if game_state.game_phase != GamePhase::CampaignMap {
+        *opened = false;
+        return;
+    }
+    // The key that brought the map up is still pressed on its first frame
+    if !*opened {
+        *opened = true;
+        return;
+    }
+
+    let step = if input.any_just_pressed([KeyCode::Right, KeyCode::Down, KeyCode::Tab]) {
+        1
+    } else if input.any_just_pressed([KeyCode::Left, KeyCode::Up]) {
+        -1
+    } else {
+        0
+    };
+    if step != 0 {
+        let mission = step_selection(&campaign.progress, step);
+        if mission != campaign.progress.current_mission {
+            let name = MissionConfig::get_mission_config(&mission).name;
+            narration.send(NarrationEvent::new(name, NarrationPriority::Focus));
+            campaign.progress.current_mission = mission;
+        }
+    }
+
+    if input.just_pressed(ROSTER_KEY) {
+        hub.toggle(HubPanel::Roster);
+    } else if input.just_pressed(UPGRADES_KEY) {
+        hub.toggle(HubPanel::Upgrades);
+    }
+
+    if input.any_just_pressed([KeyCode::Space, KeyCode::Return]) {
+        // The briefing starts the mission on the same keys
+        input.clear_just_pressed(KeyCode::Space);
+        input.clear_just_pressed(KeyCode::Return);
+        hub.panel = HubPanel::Mission;
+        game_state.game_phase = GamePhase::MissionBriefing;
+        let mission = MissionConfig::get_mission_config(&campaign.progress.current_mission);
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.campaign_map_briefing", &[("name", &mission.name)]),
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn campaign_map_ui_system(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    hub: Res<CampaignHub>,
+    catalog: Res<UnitCatalog>,
+    balance: Res<BalanceConfig>,
+    theme: Res<UiTheme>,
+    screen_query: Query<Entity, With<CampaignMapScreen>>,
+    mut drawn: Local<Option<(MissionId, usize, HubPanel)>>,
+) {
+    if game_state.game_phase != GamePhase::CampaignMap {
+        for entity in screen_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        *drawn = None;
+        return;
+    }
+
+    // Rebuilt when the selection, the campaign or the open panel changes;
+    // the animation moves the existing nodes in between
+    let state = (
+        campaign.progress.current_mission.clone(),
+        campaign.progress.completed_missions.len(),
+        hub.panel,
+    );
+    if drawn.as_ref() == Some(&state) && !theme.is_changed() {
+        return;
+    }
+    *drawn = Some(state);
+
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let side_lines = match hub.panel {
+        HubPanel::Mission => mission_lines(&campaign.progress),
+        HubPanel::Roster => roster_lines(&catalog),
+        HubPanel::Upgrades => upgrade_lines(&balance),
+    };
+    create_campaign_map_ui(&mut commands, &campaign.progress, &side_lines, &theme);
+}
+
+pub fn campaign_map_animation_system(
+    time: Res<Time<Real>>,
+    graphics: Option<Res<GraphicsSettings>>,
+    mut dots: Query<(&FrontLineDot, &mut Style)>,
+    mut zones: Query<(&TerritoryZone, &mut Style), Without<FrontLineDot>>,
+) {
+    // Without UI effects the map holds still as it was first drawn
+    let animate = graphics.map_or(true, |graphics| graphics.ui_effects);
+    let now = if animate { time.elapsed_seconds() } else { 0.0 };
+    for (dot, mut style) in dots.iter_mut() {
+        let position = front_dot_position(dot, now);
+        style.left = Val::Px(position.x - FRONT_DOT_SIZE / 2.0);
+        style.top = Val::Px(position.y - FRONT_DOT_SIZE / 2.0);
+    }
+    for (zone, mut style) in zones.iter_mut() {
+        set_zone_style(zone, now, &mut style);
+    }
+}
+
+fn front_dot_position(dot: &FrontLineDot, now: f32) -> Vec2 {
+    let length = dot.from.distance(dot.to).max(1.0);
+    let travelled = (dot.offset + now * FRONT_SPEED) % length;
+    dot.from.lerp(dot.to, travelled / length)
+}
+
+fn set_zone_style(zone: &TerritoryZone, now: f32, style: &mut Style) {
+    let pulse = 1.0 + ZONE_PULSE * (now * ZONE_PULSE_SPEED + zone.phase).sin();
+    let size = zone.radius * 2.0 * pulse;
+    style.left = Val::Px(zone.center.x - size / 2.0);
+    style.top = Val::Px(zone.center.y - size / 2.0);
+    style.width = Val::Px(size);
+    style.height = Val::Px(size);
+}
+
+// ==================== SIDE PANEL ====================
+
+fn mission_lines(progress: &CampaignProgress) -> Vec<(String, f32, Color)> {
+    let mission = &progress.current_mission;
+    let config = MissionConfig::get_mission_config(mission);
+    let state = match site_state(progress, mission) {
+        SiteState::Locked => tr("campaign_map.locked"),
+        SiteState::Open => tr("campaign_map.open"),
+        SiteState::Won(medal) => tr_args(
+            "campaign_map.won",
+            &[(
+                "medal",
+                &medal.map_or_else(|| tr("score.no_medal"), |medal| medal.label()),
+            )],
+        ),
+    };
+    let mut lines = vec![
+        (config.name, 22.0, Color::GOLD),
+        (state, 16.0, Color::WHITE),
+        (config.description, 14.0, Color::rgb(0.8, 0.8, 0.8)),
+    ];
+    if let Some(record) = progress.best_records.get(mission) {
+        lines.push((
+            tr_args("campaign_map.best", &[("score", &record.score)]),
+            14.0,
+            Color::rgb(0.9, 0.8, 0.4),
+        ));
+    }
+    lines.push((tr("campaign_map.panel_hint"), 14.0, Color::GRAY));
+    lines
+}
+
+fn roster_lines(catalog: &UnitCatalog) -> Vec<(String, f32, Color)> {
+    let mut lines = vec![(tr("campaign_map.roster_title"), 22.0, Color::GOLD)];
+    for unit_type in recruitable_units() {
+        let definition = catalog.definition(&unit_type);
+        lines.push((
+            tr_args(
+                "campaign_map.roster_unit",
+                &[
+                    ("unit", &format!("{:?}", unit_type)),
+                    ("cost", &definition.cost),
+                    ("health", &(definition.health as u32)),
+                    ("damage", &(definition.damage as u32)),
+                ],
+            ),
+            14.0,
+            Color::WHITE,
+        ));
+    }
+    lines.push((tr("campaign_map.roster_about"), 14.0, Color::GRAY));
+    lines
+}
+
+fn upgrade_lines(balance: &BalanceConfig) -> Vec<(String, f32, Color)> {
+    let mut lines = vec![(tr("campaign_map.upgrades_title"), 22.0, Color::GOLD)];
+    for upgrade in MissionUpgrade::ALL {
+        lines.push((
+            tr_args(
+                "campaign_map.upgrade",
+                &[
+                    ("name", &upgrade.label()),
+                    ("cost", &(upgrade.cost(&balance.upgrades) as u32)),
+                ],
+            ),
+            14.0,
+            Color::WHITE,
+        ));
+    }
+    lines.push((tr("campaign_map.upgrades_about"), 14.0, Color::GRAY));
+    lines
+}
+
+// ==================== UI ====================
+
+fn site_color(state: SiteState, theme: &UiTheme) -> Color {
+    match state {
+        SiteState::Locked => Color::rgb(0.35, 0.35, 0.35),
+        SiteState::Open => Color::rgb(1.0, 0.85, 0.3),
+        SiteState::Won(Some(Medal::Gold)) => Color::GOLD,
+        SiteState::Won(Some(Medal::Silver)) => Color::SILVER,
+        SiteState::Won(Some(Medal::Bronze)) => Color::rgb(0.8, 0.5, 0.2),
+        SiteState::Won(None) => theme.faction_color(&Faction::Cartel),
+    }
+}
+
+fn dot_bundle(center: Vec2, size: f32, color: Color) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(center.x - size / 2.0),
+            top: Val::Px(center.y - size / 2.0),
+            width: Val::Px(size),
+            height: Val::Px(size),
+            ..default()
+        },
+        background_color: BackgroundColor(color),
+        ..default()
+    }
+}
+
+// Evenly spaced points from one end of a line to the other
+fn line_points(from: Vec2, to: Vec2, spacing: f32) -> impl Iterator<Item = Vec2> {
+    let steps = (from.distance(to) / spacing).ceil().max(1.0) as usize;
+    (0..=steps).map(move |step| from.lerp(to, step as f32 / steps as f32))
+}
+
+fn create_campaign_map_ui(
+    commands: &mut Commands,
+    progress: &CampaignProgress,
+    side_lines: &[(String, f32, Color)],
+    theme: &UiTheme,
+) {
+    let cartel = theme.faction_color(&Faction::Cartel);
+    let won = MissionId::ALL
+        .iter()
+        .filter(|mission| progress.completed_missions.contains(mission))
+        .count();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(12.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(
+                    theme.panel_background(Color::rgba(0.02, 0.03, 0.05, 0.97)),
+                ),
+                ..default()
+            },
+            CampaignMapScreen,
+        ))
+        .with_children(|screen| {
+            screen.spawn(TextBundle::from_section(
+                tr("campaign_map.title"),
+                TextStyle {
+                    font_size: 36.0,
+                    color: theme.text_color(Color::rgb(1.0, 0.8, 0.2)),
+                    ..default()
+                },
+            ));
+            screen.spawn(TextBundle::from_section(
+                tr_args(
+                    "campaign_map.progress",
+                    &[("won", &won), ("total", &MissionId::ALL.len())],
+                ),
+                TextStyle {
+                    font_size: 18.0,
+                    color: theme.text_color(Color::rgb(0.8, 0.8, 0.8)),
+                    ..default()
+                },
+            ));
+
+            screen
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(16.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(MAP_SIZE.x),
+                            height: Val::Px(MAP_SIZE.y),
+                            overflow: Overflow::clip(),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::rgb(0.10, 0.13, 0.11)),
+                        ..default()
+                    })
+                    .with_children(|map| spawn_map_contents(map, progress, cartel, theme));
+
+                    row.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(SIDE_PANEL_WIDTH),
+                            padding: UiRect::all(Val::Px(12.0)),
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(8.0),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(
+                            theme.panel_background(Color::rgba(0.0, 0.0, 0.0, 0.6)),
+                        ),
+                        ..default()
+                    })
+                    .with_children(|panel| {
+                        for (line, font_size, color) in side_lines {
+                            panel.spawn(TextBundle::from_section(
+                                line.clone(),
+                                TextStyle {
+                                    font_size: *font_size,
+                                    color: theme.text_color(*color),
+                                    ..default()
+                                },
+                            ));
+                        }
+                    });
+                });
+
+            screen.spawn(TextBundle::from_section(
+                tr("campaign_map.hint"),
+                TextStyle {
+                    font_size: 16.0,
+                    color: theme.text_color(Color::rgb(0.7, 0.7, 0.7)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_map_contents(
+    map: &mut ChildBuilder,
+    progress: &CampaignProgress,
+    cartel: Color,
+    theme: &UiTheme,
+) {
+    for river in RIVERS {
+        for segment in river.windows(2) {
+            let (from, to) = (segment[0] * MAP_SIZE, segment[1] * MAP_SIZE);
+            for point in line_points(from, to, RIVER_DOT_SPACING) {
+                map.spawn(dot_bundle(
+                    point,
+                    RIVER_DOT_SIZE,
+                    Color::rgba(0.25, 0.45, 0.7, 0.8),
+                ));
+            }
+        }
+    }
+
+    // Ground held, under everything else
+    for (index, mission) in MissionId::ALL.iter().enumerate() {
+        let SiteState::Won(medal) = site_state(progress, mission) else {
+            continue;
+        };
+        let zone = TerritoryZone {
+            center: site_pixels(mission),
+            radius: territory_radius(medal),
+            phase: index as f32,
+        };
+        let mut bundle = dot_bundle(zone.center, zone.radius * 2.0, cartel.with_a(0.18));
+        set_zone_style(&zone, 0.0, &mut bundle.style);
+        map.spawn((bundle, zone));
+    }
+
+    for line in front_lines(progress) {
+        let (from, to) = (site_pixels(&line.from), site_pixels(&line.to));
+        if line.advancing {
+            let count = (from.distance(to) / FRONT_DOT_SPACING).floor().max(1.0) as usize;
+            for index in 0..count {
+                let dot = FrontLineDot {
+                    from,
+                    to,
+                    offset: index as f32 * FRONT_DOT_SPACING,
+                };
+                let position = front_dot_position(&dot, 0.0);
+                map.spawn((dot_bundle(position, FRONT_DOT_SIZE, cartel), dot));
+            }
+        } else {
+            for point in line_points(from, to, FRONT_DOT_SPACING) {
+                map.spawn(dot_bundle(point, FRONT_DOT_SIZE, cartel.with_a(0.35)));
+            }
+        }
+    }
+
+    for (index, mission) in MissionId::ALL.iter().enumerate() {
+        let state = site_state(progress, mission);
+        let site = site_pixels(mission);
+        let selected = *mission == progress.current_mission;
+        let mut node = dot_bundle(site, NODE_SIZE, site_color(state, theme));
+        node.style.justify_content = JustifyContent::Center;
+        node.style.align_items = AlignItems::Center;
+        node.style.border = UiRect::all(Val::Px(if selected { 3.0 } else { 1.0 }));
+        node.border_color = BorderColor(if selected {
+            theme.colors().selection
+        } else {
+            Color::BLACK
+        });
+        map.spawn(node).with_children(|node| {
+            node.spawn(TextBundle::from_section(
+                (index + 1).to_string(),
+                TextStyle {
+                    font_size: 11.0,
+                    color: Color::BLACK,
+                    ..default()
+                },
+            ));
+        });
+
+        // Names sit beside their node, on the left near the east edge
+        let offset = NODE_SIZE / 2.0 + 4.0;
+        let mut label_style = Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(site.y - 7.0),
+            ..default()
+        };
+        if mission_site(mission).x > 0.75 {
+            label_style.right = Val::Px(MAP_SIZE.x - site.x + offset);
+        } else {
+            label_style.left = Val::Px(site.x + offset);
+        }
+        let color = match state {
+            SiteState::Locked => Color::rgb(0.5, 0.5, 0.5),
+            _ if selected => Color::WHITE,
+            _ => Color::rgb(0.85, 0.85, 0.85),
+        };
+        map.spawn(
+            TextBundle::from_section(
+                MissionConfig::get_mission_config(mission).name,
+                TextStyle {
+                    font_size: 12.0,
+                    color: theme.text_color(color),
+                    ..default()
+                },
+            )
+            .with_style(label_style),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won(missions: &[MissionId]) -> CampaignProgress {
+        let mut progress = CampaignProgress::default();
+        for mission in missions {
+            progress.complete_mission(mission.clone(), 120.0, 100);
+        }
+        progress
+    }
+
+    #[test]
+    fn test_every_mission_has_its_own_site_on_the_map() {
+        for (index, mission) in MissionId::ALL.iter().enumerate() {
+            let site = mission_site(mission);
+            assert!((0.0..=1.0).contains(&site.x) && (0.0..=1.0).contains(&site.y));
+            for other in &MissionId::ALL[index + 1..] {
+                assert!(
+                    site_pixels(mission).distance(site_pixels(other)) > NODE_SIZE * 2.0,
+                    "{:?} and {:?} overlap",
+                    mission,
+                    other
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_front_lines_advance_from_the_latest_win() {
+        assert!(front_lines(&CampaignProgress::default()).is_empty());
+
+        let progress = won(&[MissionId::InitialRaid, MissionId::UrbanWarfare]);
+        assert_eq!(
+            front_lines(&progress),
+            vec![
+                FrontLine {
+                    from: MissionId::InitialRaid,
+                    to: MissionId::UrbanWarfare,
+                    advancing: false,
+                },
+                FrontLine {
+                    from: MissionId::UrbanWarfare,
+                    to: MissionId::LasFloresiDefense,
+                    advancing: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selection_steps_through_playable_missions_only() {
+        let progress = CampaignProgress::default();
+        assert_eq!(step_selection(&progress, 1), MissionId::InitialRaid);
+
+        let progress = won(&[MissionId::InitialRaid]);
+        assert_eq!(progress.current_mission, MissionId::UrbanWarfare);
+        assert_eq!(step_selection(&progress, 1), MissionId::InitialRaid);
+        assert_eq!(step_selection(&progress, -1), MissionId::InitialRaid);
+        assert_eq!(
+            site_state(&progress, &MissionId::LasFloresiDefense),
+            SiteState::Locked
+        );
+    }
+
+    #[test]
+    fn test_better_medals_hold_more_ground() {
+        let radii: Vec<f32> = [
+            None,
+            Some(Medal::Bronze),
+            Some(Medal::Silver),
+            Some(Medal::Gold),
+        ]
+        .into_iter()
+        .map(territory_radius)
+        .collect();
+        assert!(radii.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
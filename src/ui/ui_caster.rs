@@ -0,0 +1,875 @@
+use crate::app_state::{AppState, GameSet};
+use crate::campaign::Campaign;
+use crate::components::*;
+use crate::config::UnitCatalog;
+use crate::localization::{tr, tr_args};
+use crate::resources::GameState;
+use crate::ui::ui_theme::UiTheme;
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy::window::PrimaryWindow;
+use std::collections::HashMap;
+
+// ==================== CASTER TOOLS ====================
+
+// For anyone covering a match rather than playing it: a battle timeline of
+// kills, checkpoint captures, objective flips and phase changes that can be
+// scrubbed event by event, army-value and production graphs per faction,
+// cameras that follow either side or the latest action, and a
+// picture-in-picture view of the whole fight. Nothing here changes the
+// simulation, so the tools work the same for a player, an observer or a
+// replayed mission.
+
+pub const CASTER_TOGGLE_KEY: KeyCode = KeyCode::Home;
+const SCRUB_BACK_KEY: KeyCode = KeyCode::BracketLeft;
+const SCRUB_FORWARD_KEY: KeyCode = KeyCode::BracketRight;
+const GRAPH_KEY: KeyCode = KeyCode::PageUp;
+const VIEW_KEY: KeyCode = KeyCode::PageDown;
+const PIP_KEY: KeyCode = KeyCode::Insert;
+
+pub const TIMELINE_SAMPLE_INTERVAL: f32 = 5.0; // Seconds of mission time per graph sample
+const TIMELINE_WIDTH: f32 = 560.0;
+const GRAPH_SAMPLES: usize = 24; // Two minutes of history at the sample interval
+const GRAPH_HEIGHT: f32 = 60.0;
+const FOLLOW_SPEED: f32 = 4.0; // Fraction of the gap closed per second
+const PIP_SIZE: Vec2 = Vec2::new(240.0, 180.0);
+const PIP_MARGIN: f32 = 20.0;
+const PIP_TOP: f32 = 190.0; // Below the minimap
+const PIP_ZOOM: f32 = 4.0;
+
+pub struct CasterToolsPlugin;
+
+impl Plugin for CasterToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BattleTimeline>()
+            .init_resource::<CasterTools>()
+            .add_systems(
+                Update,
+                (
+                    timeline_record_system,
+                    caster_input_system,
+                    caster_camera_system,
+                    caster_pip_system,
+                    caster_overlay_system,
+                )
+                    .chain()
+                    .in_set(GameSet::InGame),
+            )
+            .add_systems(
+                Update,
+                caster_cleanup_system
+                    .in_set(GameSet::Session)
+                    .run_if(not(in_state(AppState::InGame))),
+            );
+    }
+}
+
+// ==================== BATTLE TIMELINE ====================
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimelineEventKind {
+    Kill {
+        victim: Faction,
+        unit_type: UnitType,
+    },
+    CheckpointTaken,
+    ObjectiveFlip {
+        objective: String,
+        completed: bool,
+    },
+    PhaseChange(GamePhase),
+}
+
+#[derive(Clone, Debug)]
+pub struct TimelineEvent {
+    pub time: f32,
+    pub kind: TimelineEventKind,
+    pub position: Option<Vec2>,
+}
+
+impl TimelineEvent {
+    pub fn description(&self) -> String {
+        match &self.kind {
+            TimelineEventKind::Kill { victim, unit_type } => tr_args(
+                "caster.kill",
+                &[
+                    ("faction", &faction_label(victim)),
+                    ("unit", &format!("{:?}", unit_type)),
+                ],
+            ),
+            TimelineEventKind::CheckpointTaken => tr("caster.checkpoint"),
+            TimelineEventKind::ObjectiveFlip {
+                objective,
+                completed,
+            } => {
+                let key = if *completed {
+                    "caster.objective_done"
+                } else {
+                    "caster.objective_lost"
+                };
+                tr_args(key, &[("objective", objective)])
+            }
+            TimelineEventKind::PhaseChange(phase) => tr(&format!("phase.{:?}", phase)),
+        }
+    }
+
+    // The side the event is good news for, used to colour its marker
+    pub fn beneficiary(&self) -> Option<Faction> {
+        match &self.kind {
+            TimelineEventKind::Kill { victim, .. } => match victim {
+                Faction::Cartel => Some(Faction::Military),
+                Faction::Military | Faction::Police => Some(Faction::Cartel),
+                Faction::Civilian | Faction::RivalCartel => None,
+            },
+            TimelineEventKind::CheckpointTaken => Some(Faction::Military),
+            TimelineEventKind::ObjectiveFlip { completed, .. } => Some(if *completed {
+                Faction::Cartel
+            } else {
+                Faction::Military
+            }),
+            TimelineEventKind::PhaseChange(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ForceSample {
+    pub time: f32,
+    pub army_value: u32,
+    pub production: u32, // Cost of units fielded since the previous sample
+}
+
+#[derive(Resource, Default)]
+pub struct BattleTimeline {
+    pub events: Vec<TimelineEvent>,
+    pub samples: HashMap<Faction, Vec<ForceSample>>,
+    pending_production: HashMap<Faction, u32>,
+    last_sample: Option<f32>,
+}
+
+impl BattleTimeline {
+    pub fn record(&mut self, time: f32, kind: TimelineEventKind, position: Option<Vec2>) {
+        self.events.push(TimelineEvent {
+            time,
+            kind,
+            position,
+        });
+    }
+
+    pub fn add_production(&mut self, faction: &Faction, cost: u32) {
+        *self.pending_production.entry(faction.clone()).or_default() += cost;
+    }
+
+    // Takes a sample per faction once the interval has passed; returns whether it did
+    pub fn sample(&mut self, time: f32, army_values: &HashMap<Faction, u32>) -> bool {
+        if self
+            .last_sample
+            .is_some_and(|last| time - last < TIMELINE_SAMPLE_INTERVAL)
+        {
+            return false;
+        }
+        self.last_sample = Some(time);
+
+        for faction in [Faction::Cartel, Faction::Military] {
+            let sample = ForceSample {
+                time,
+                army_value: army_values.get(&faction).copied().unwrap_or(0),
+                production: self.pending_production.remove(&faction).unwrap_or(0),
+            };
+            self.samples.entry(faction).or_default().push(sample);
+        }
+        true
+    }
+
+    // The last sample taken at or before the given time
+    pub fn sample_at(&self, faction: &Faction, time: f32) -> Option<ForceSample> {
+        self.samples
+            .get(faction)?
+            .iter()
+            .take_while(|sample| sample.time <= time)
+            .last()
+            .copied()
+    }
+
+    pub fn recent_samples(&self, faction: &Faction, count: usize) -> &[ForceSample] {
+        let samples = self.samples.get(faction).map_or(&[][..], Vec::as_slice);
+        &samples[samples.len().saturating_sub(count)..]
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// What the recorder saw last frame, to turn state changes into events
+#[derive(Default)]
+pub struct TimelineTracker {
+    seeded: bool,
+    last_time: f32,
+    living: HashMap<Entity, (Faction, UnitType, Vec2)>,
+    objectives: Vec<(String, bool)>,
+    checkpoints_taken: u32,
+    phase: Option<GamePhase>,
+}
+
+pub fn timeline_record_system(
+    mut timeline: ResMut<BattleTimeline>,
+    mut tracker: Local<TimelineTracker>,
+    game_state: Res<GameState>,
+    campaign: Res<Campaign>,
+    catalog: Res<UnitCatalog>,
+    unit_query: Query<(Entity, &Unit, &Transform)>,
+) {
+    let time = game_state.mission_timer;
+    let objectives: Vec<(String, bool)> = campaign
+        .current_objectives
+        .iter()
+        .map(|status| (status.objective.description(), status.completed))
+        .collect();
+
+    // A new mission, a restart or a loaded save: start over without
+    // reporting the whole field as produced or killed
+    let restarted = time < tracker.last_time
+        || objectives.len() != tracker.objectives.len()
+        || objectives
+            .iter()
+            .zip(&tracker.objectives)
+            .any(|((now, _), (before, _))| now != before);
+    if !tracker.seeded || restarted {
+        timeline.clear();
+        *tracker = TimelineTracker {
+            seeded: true,
+            last_time: time,
+            living: living_units(&unit_query),
+            objectives,
+            checkpoints_taken: game_state.military_checkpoints_taken,
+            phase: Some(game_state.game_phase.clone()),
+        };
+        return;
+    }
+    tracker.last_time = time;
+
+    let living = living_units(&unit_query);
+    for (entity, (faction, unit_type, position)) in &tracker.living {
+        if !living.contains_key(entity) {
+            timeline.record(
+                time,
+                TimelineEventKind::Kill {
+                    victim: faction.clone(),
+                    unit_type: unit_type.clone(),
+                },
+                Some(*position),
+            );
+        }
+    }
+    for (entity, (faction, unit_type, _)) in &living {
+        if !tracker.living.contains_key(entity) {
+            timeline.add_production(faction, catalog.definition(unit_type).cost);
+        }
+    }
+    tracker.living = living;
+
+    for ((objective, completed), (_, was_completed)) in objectives.iter().zip(&tracker.objectives) {
+        if completed != was_completed {
+            timeline.record(
+                time,
+                TimelineEventKind::ObjectiveFlip {
+                    objective: objective.clone(),
+                    completed: *completed,
+                },
+                None,
+            );
+        }
+    }
+    tracker.objectives = objectives;
+
+    if game_state.military_checkpoints_taken > tracker.checkpoints_taken {
+        timeline.record(time, TimelineEventKind::CheckpointTaken, None);
+    }
+    tracker.checkpoints_taken = game_state.military_checkpoints_taken;
+
+    if tracker.phase.as_ref() != Some(&game_state.game_phase) {
+        timeline.record(
+            time,
+            TimelineEventKind::PhaseChange(game_state.game_phase.clone()),
+            None,
+        );
+        tracker.phase = Some(game_state.game_phase.clone());
+    }
+
+    let mut army_values: HashMap<Faction, u32> = HashMap::new();
+    for (faction, unit_type, _) in tracker.living.values() {
+        *army_values.entry(faction.clone()).or_default() += catalog.definition(unit_type).cost;
+    }
+    timeline.sample(time, &army_values);
+}
+
+fn living_units(
+    unit_query: &Query<(Entity, &Unit, &Transform)>,
+) -> HashMap<Entity, (Faction, UnitType, Vec2)> {
+    unit_query
+        .iter()
+        .filter(|(_, unit, _)| unit.health > 0.0)
+        .map(|(entity, unit, transform)| {
+            (
+                entity,
+                (
+                    unit.faction.clone(),
+                    unit.unit_type.clone(),
+                    transform.translation.truncate(),
+                ),
+            )
+        })
+        .collect()
+}
+
+// ==================== CASTER CONTROLS ====================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphMode {
+    #[default]
+    Off,
+    ArmyValue,
+    Production,
+}
+
+impl GraphMode {
+    pub fn next(self) -> Self {
+        match self {
+            GraphMode::Off => GraphMode::ArmyValue,
+            GraphMode::ArmyValue => GraphMode::Production,
+            GraphMode::Production => GraphMode::Off,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CasterView {
+    #[default]
+    Free,
+    Cartel,
+    Military,
+    Action,
+}
+
+impl CasterView {
+    pub fn next(self) -> Self {
+        match self {
+            CasterView::Free => CasterView::Cartel,
+            CasterView::Cartel => CasterView::Military,
+            CasterView::Military => CasterView::Action,
+            CasterView::Action => CasterView::Free,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CasterTools {
+    pub enabled: bool,
+    pub graph: GraphMode,
+    pub view: CasterView,
+    pub pip: bool,
+    pub scrub: Option<usize>, // Index into the timeline; None follows the live edge
+}
+
+impl CasterTools {
+    // Steps through the timeline's events; stepping past the newest returns to live
+    pub fn scrub_step(&mut self, forward: bool, event_count: usize) {
+        if event_count == 0 {
+            self.scrub = None;
+            return;
+        }
+        self.scrub = match (self.scrub, forward) {
+            (None, false) => Some(event_count - 1),
+            (None, true) => None,
+            (Some(index), false) => Some(index.saturating_sub(1)),
+            (Some(index), true) if index + 1 < event_count => Some(index + 1),
+            (Some(_), true) => None,
+        };
+    }
+}
+
+pub fn caster_input_system(
+    input: Res<Input<KeyCode>>,
+    timeline: Res<BattleTimeline>,
+    mut tools: ResMut<CasterTools>,
+) {
+    if input.just_pressed(CASTER_TOGGLE_KEY) {
+        tools.enabled = !tools.enabled;
+        if !tools.enabled {
+            *tools = CasterTools::default();
+        }
+        return;
+    }
+    if !tools.enabled {
+        return;
+    }
+
+    if input.just_pressed(SCRUB_BACK_KEY) {
+        tools.scrub_step(false, timeline.events.len());
+    }
+    if input.just_pressed(SCRUB_FORWARD_KEY) {
+        tools.scrub_step(true, timeline.events.len());
+    }
+    if input.just_pressed(GRAPH_KEY) {
+        tools.graph = tools.graph.next();
+    }
+    if input.just_pressed(VIEW_KEY) {
+        tools.view = tools.view.next();
+    }
+    if input.just_pressed(PIP_KEY) {
+        tools.pip = !tools.pip;
+    }
+
+    // The timeline restarted under the scrubber
+    if tools
+        .scrub
+        .is_some_and(|index| index >= timeline.events.len())
+    {
+        tools.scrub = None;
+    }
+}
+
+// ==================== CAMERA VIEWS ====================
+
+// Marks the picture-in-picture camera so nothing mistakes it for the main one
+#[derive(Component)]
+pub struct CasterPipCamera;
+
+pub fn caster_camera_system(
+    tools: Res<CasterTools>,
+    timeline: Res<BattleTimeline>,
+    time: Res<Time<Real>>,
+    unit_query: Query<(&Unit, &Transform), Without<IsometricCamera>>,
+    mut camera_query: Query<&mut Transform, With<IsometricCamera>>,
+) {
+    if !tools.enabled {
+        return;
+    }
+    let Ok(mut camera) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let target = match (tools.scrub, tools.view) {
+        (Some(index), _) => timeline.events.get(index).and_then(|event| event.position),
+        (None, CasterView::Free) => None,
+        (None, CasterView::Cartel) => unit_query
+            .iter()
+            .find(|(unit, _)| unit.unit_type == UnitType::Ovidio && unit.health > 0.0)
+            .map(|(_, transform)| transform.translation.truncate())
+            .or_else(|| faction_centroid(unit_query.iter(), Some(&Faction::Cartel))),
+        (None, CasterView::Military) => {
+            faction_centroid(unit_query.iter(), Some(&Faction::Military))
+        }
+        (None, CasterView::Action) => timeline
+            .events
+            .iter()
+            .rev()
+            .find_map(|event| event.position),
+    };
+
+    if let Some(target) = target {
+        let blend = (FOLLOW_SPEED * time.delta_seconds()).min(1.0);
+        let current = camera.translation.truncate();
+        let next = current.lerp(target, blend);
+        camera.translation.x = next.x;
+        camera.translation.y = next.y;
+    }
+}
+
+// Centre of the living units of one faction, or of everyone for None
+fn faction_centroid<'a>(
+    units: impl Iterator<Item = (&'a Unit, &'a Transform)>,
+    faction: Option<&Faction>,
+) -> Option<Vec2> {
+    let positions: Vec<Vec2> = units
+        .filter(|(unit, _)| unit.health > 0.0)
+        .filter(|(unit, _)| faction.is_none() || faction == Some(&unit.faction))
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    if positions.is_empty() {
+        return None;
+    }
+    Some(positions.iter().sum::<Vec2>() / positions.len() as f32)
+}
+
+pub fn caster_pip_system(
+    mut commands: Commands,
+    tools: Res<CasterTools>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    unit_query: Query<(&Unit, &Transform), Without<CasterPipCamera>>,
+    mut pip_query: Query<(Entity, &mut Camera, &mut Transform), With<CasterPipCamera>>,
+) {
+    if !(tools.enabled && tools.pip) {
+        for (entity, _, _) in pip_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    // Pinned below the minimap, in physical pixels for the viewport
+    let scale = window.scale_factor() as f32;
+    let size = (PIP_SIZE * scale).as_uvec2();
+    let left = (window.width() - PIP_MARGIN - PIP_SIZE.x).max(0.0) * scale;
+    let viewport = Viewport {
+        physical_position: UVec2::new(left as u32, (PIP_TOP * scale) as u32),
+        physical_size: size,
+        ..default()
+    };
+    let center = faction_centroid(unit_query.iter(), None).unwrap_or(Vec2::ZERO);
+
+    if let Ok((_, mut camera, mut transform)) = pip_query.get_single_mut() {
+        if camera
+            .viewport
+            .as_ref()
+            .map(|current| current.physical_size)
+            != Some(size)
+        {
+            camera.viewport = Some(viewport);
+        }
+        transform.translation.x = center.x;
+        transform.translation.y = center.y;
+        return;
+    }
+
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1, // Drawn over the main camera
+                viewport: Some(viewport),
+                ..default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::None,
+            },
+            transform: Transform::from_xyz(center.x, center.y, 999.9)
+                .with_scale(Vec3::splat(PIP_ZOOM)),
+            ..default()
+        },
+        UiCameraConfig { show_ui: false },
+        CasterPipCamera,
+    ));
+}
+
+// ==================== CASTER OVERLAY ====================
+
+#[derive(Component)]
+pub struct CasterOverlay;
+
+pub fn caster_overlay_system(
+    mut commands: Commands,
+    tools: Res<CasterTools>,
+    timeline: Res<BattleTimeline>,
+    game_state: Res<GameState>,
+    theme: Res<UiTheme>,
+    overlay_query: Query<Entity, With<CasterOverlay>>,
+    mut last_drawn: Local<(usize, usize)>,
+) {
+    // The overlay is rebuilt whenever something on it changes: a control, a
+    // new event or a new graph sample
+    let drawn = (
+        timeline.events.len(),
+        timeline.samples.get(&Faction::Cartel).map_or(0, Vec::len),
+    );
+    if !tools.is_changed() && *last_drawn == drawn && !theme.is_changed() {
+        return;
+    }
+    *last_drawn = drawn;
+
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if !tools.enabled {
+        return;
+    }
+
+    let now = game_state.mission_timer.max(1.0);
+    let scrubbed = tools.scrub.and_then(|index| timeline.events.get(index));
+    let text_style = |color: Color| TextStyle {
+        font_size: 14.0,
+        color,
+        ..default()
+    };
+
+    let status = match scrubbed {
+        Some(event) => tr_args(
+            "caster.scrub",
+            &[
+                ("time", &clock(event.time)),
+                ("event", &event.description()),
+            ],
+        ),
+        None => tr_args("caster.live", &[("time", &clock(game_state.mission_timer))]),
+    };
+    let forces_time = scrubbed.map_or(game_state.mission_timer, |event| event.time);
+    let army_value = |faction: &Faction| {
+        timeline
+            .sample_at(faction, forces_time)
+            .map_or(0, |sample| sample.army_value)
+    };
+    let forces = tr_args(
+        "caster.forces",
+        &[
+            ("cartel", &army_value(&Faction::Cartel)),
+            ("military", &army_value(&Faction::Military)),
+        ],
+    );
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.0),
+                    top: Val::Px(10.0),
+                    margin: UiRect::left(Val::Px(-(TIMELINE_WIDTH / 2.0 + 10.0))),
+                    width: Val::Px(TIMELINE_WIDTH + 20.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(
+                    theme.panel_background(Color::rgba(0.0, 0.0, 0.0, 0.75)),
+                ),
+                z_index: ZIndex::Global(50),
+                ..default()
+            },
+            CasterOverlay,
+        ))
+        .with_children(|panel| {
+            panel.spawn(TextBundle::from_section(
+                tr_args("caster.title", &[("view", &view_label(tools.view))]),
+                text_style(theme.text_color(Color::GOLD)),
+            ));
+
+            // Timeline bar: one tick per event, placed by mission time
+            panel
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(TIMELINE_WIDTH),
+                        height: Val::Px(14.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::rgba(1.0, 1.0, 1.0, 0.15)),
+                    ..default()
+                })
+                .with_children(|bar| {
+                    for (index, event) in timeline.events.iter().enumerate() {
+                        let selected = tools.scrub == Some(index);
+                        let color = event
+                            .beneficiary()
+                            .map_or(Color::GRAY, |faction| theme.faction_color(&faction));
+                        bar.spawn(NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(timeline_x(event.time, now)),
+                                width: Val::Px(if selected { 4.0 } else { 2.0 }),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(if selected {
+                                Color::WHITE
+                            } else {
+                                color
+                            }),
+                            ..default()
+                        });
+                    }
+                });
+
+            panel.spawn(TextBundle::from_section(
+                status,
+                text_style(theme.text_color(Color::WHITE)),
+            ));
+            panel.spawn(TextBundle::from_section(
+                forces,
+                text_style(theme.text_color(Color::SILVER)),
+            ));
+
+            if tools.graph != GraphMode::Off {
+                spawn_graph(panel, &timeline, tools.graph, &theme);
+            }
+
+            panel.spawn(TextBundle::from_section(
+                tr("caster.hint"),
+                text_style(theme.text_color(Color::GRAY)),
+            ));
+        });
+}
+
+// Side-by-side bars per sample, cartel then military, scaled to the tallest
+fn spawn_graph(
+    panel: &mut ChildBuilder,
+    timeline: &BattleTimeline,
+    mode: GraphMode,
+    theme: &UiTheme,
+) {
+    let factions = [Faction::Cartel, Faction::Military];
+    let value = |sample: &ForceSample| match mode {
+        GraphMode::Production => sample.production,
+        _ => sample.army_value,
+    };
+    let peak = factions
+        .iter()
+        .flat_map(|faction| timeline.recent_samples(faction, GRAPH_SAMPLES))
+        .map(value)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let title = match mode {
+        GraphMode::Production => "caster.graph_production",
+        _ => "caster.graph_army",
+    };
+
+    panel.spawn(TextBundle::from_section(
+        tr_args(title, &[("peak", &peak)]),
+        TextStyle {
+            font_size: 12.0,
+            color: theme.text_color(Color::SILVER),
+            ..default()
+        },
+    ));
+    panel
+        .spawn(NodeBundle {
+            style: Style {
+                height: Val::Px(GRAPH_HEIGHT),
+                align_items: AlignItems::FlexEnd,
+                column_gap: Val::Px(1.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|graph| {
+            let cartel = timeline.recent_samples(&Faction::Cartel, GRAPH_SAMPLES);
+            let military = timeline.recent_samples(&Faction::Military, GRAPH_SAMPLES);
+            for (cartel_sample, military_sample) in cartel.iter().zip(military) {
+                for (faction, sample) in factions.iter().zip([cartel_sample, military_sample]) {
+                    let height = value(sample) as f32 / peak as f32 * GRAPH_HEIGHT;
+                    graph.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(8.0),
+                            height: Val::Px(height.max(1.0)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(theme.faction_color(faction)),
+                        ..default()
+                    });
+                }
+            }
+        });
+}
+
+pub fn caster_cleanup_system(
+    mut commands: Commands,
+    overlay_query: Query<Entity, Or<(With<CasterOverlay>, With<CasterPipCamera>)>>,
+) {
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Offset along the timeline bar; the bar always spans the mission so far
+pub fn timeline_x(time: f32, now: f32) -> f32 {
+    (time / now.max(f32::EPSILON)).clamp(0.0, 1.0) * TIMELINE_WIDTH
+}
+
+fn clock(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn faction_label(faction: &Faction) -> String {
+    tr(&format!("faction.{:?}", faction))
+}
+
+fn view_label(view: CasterView) -> String {
+    tr(&format!("caster.view_{:?}", view))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_follow_the_interval_and_collect_production() {
+        let mut timeline = BattleTimeline::default();
+        let values = HashMap::from([(Faction::Cartel, 300), (Faction::Military, 500)]);
+
+        assert!(timeline.sample(0.0, &values));
+        timeline.add_production(&Faction::Military, 120);
+        timeline.add_production(&Faction::Military, 80);
+        assert!(!timeline.sample(TIMELINE_SAMPLE_INTERVAL - 1.0, &values));
+        assert!(timeline.sample(TIMELINE_SAMPLE_INTERVAL, &values));
+
+        let latest = timeline.sample_at(&Faction::Military, 100.0).unwrap();
+        assert_eq!(latest.time, TIMELINE_SAMPLE_INTERVAL);
+        assert_eq!(latest.production, 200);
+        assert_eq!(latest.army_value, 500);
+        assert_eq!(
+            timeline
+                .sample_at(&Faction::Military, 1.0)
+                .unwrap()
+                .production,
+            0
+        );
+        assert!(timeline.sample_at(&Faction::Cartel, -1.0).is_none());
+        assert_eq!(timeline.recent_samples(&Faction::Cartel, 1).len(), 1);
+        assert!(timeline.recent_samples(&Faction::Civilian, 5).is_empty());
+
+        timeline.clear();
+        assert!(timeline.samples.is_empty());
+        assert!(timeline.sample(0.0, &values));
+    }
+
+    #[test]
+    fn test_scrubbing_walks_the_events_and_returns_to_live() {
+        let mut tools = CasterTools::default();
+
+        tools.scrub_step(false, 3);
+        assert_eq!(tools.scrub, Some(2));
+        tools.scrub_step(false, 3);
+        tools.scrub_step(false, 3);
+        tools.scrub_step(false, 3);
+        assert_eq!(tools.scrub, Some(0), "stops at the first event");
+
+        tools.scrub_step(true, 3);
+        tools.scrub_step(true, 3);
+        assert_eq!(tools.scrub, Some(2));
+        tools.scrub_step(true, 3);
+        assert_eq!(tools.scrub, None, "stepping past the newest goes live");
+
+        tools.scrub_step(false, 0);
+        assert_eq!(tools.scrub, None);
+    }
+
+    #[test]
+    fn test_event_markers_are_coloured_for_the_side_they_help() {
+        let kill = TimelineEvent {
+            time: 30.0,
+            kind: TimelineEventKind::Kill {
+                victim: Faction::Military,
+                unit_type: UnitType::Soldier,
+            },
+            position: Some(Vec2::new(10.0, 20.0)),
+        };
+        assert_eq!(kill.beneficiary(), Some(Faction::Cartel));
+
+        let flip = TimelineEvent {
+            time: 60.0,
+            kind: TimelineEventKind::ObjectiveFlip {
+                objective: "Hold".to_string(),
+                completed: false,
+            },
+            position: None,
+        };
+        assert_eq!(flip.beneficiary(), Some(Faction::Military));
+
+        assert_eq!(timeline_x(30.0, 60.0), TIMELINE_WIDTH / 2.0);
+        assert_eq!(timeline_x(90.0, 60.0), TIMELINE_WIDTH);
+    }
+}
@@ -0,0 +1,888 @@
+use crate::app_state::GameSet;
+use crate::campaign::Campaign;
+use crate::components::*;
+use crate::config::{BalanceConfig, ConfigProfiles, UnitCatalog, CONFIG_SETTINGS};
+use crate::corpse_system::{register_corpses_system, Corpse};
+use crate::damage::DownedSet;
+use crate::environmental_systems::{EnvironmentalState, WeatherType};
+use crate::logging::{current_filter, reset_log_levels, set_log_level, LogSubsystem, LogViewer};
+use crate::resources::{GameAssets, GameState};
+use crate::scripting::{parse_variant, pressure_component, ADJUSTABLE_PRESSURES};
+use crate::spawners::spawn_unit;
+use crate::utils::{iso_to_world, SimulationSet};
+use bevy::ecs::system::SystemState;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+
+#[cfg(feature = "scripting")]
+use crate::scripting::{ScriptCommandQueue, ScriptRuntime, ScriptWorldView};
+
+// ==================== DEVELOPER CONSOLE ====================
+
+// ` drops down a console for poking at the running game: spawning units,
+// cheats, phase jumps and pressure tweaks. While it is open it swallows the
+// keyboard so typing doesn't move the camera or fire hotkeys.
+
+const CONSOLE_LOG_LINES: usize = 200;
+const CONSOLE_VISIBLE_LINES: usize = 14;
+const CONSOLE_HISTORY: usize = 50;
+const MAX_CONSOLE_SPAWNS: u32 = 50;
+const CONSOLE_SPAWN_SPACING: f32 = 24.0;
+
+pub const CONSOLE_COMMANDS: [(&str, &str); 12] = [
+    ("help", "help - list commands"),
+    (
+        "spawn",
+        "spawn <faction> <type> [count] [x y] - at the camera unless placed",
+    ),
+    ("god", "god [on|off] - cartel units take no damage"),
+    ("fog", "fog [on|off] - force or clear fog"),
+    ("phase", "phase <name> - jump to a game phase"),
+    (
+        "pressure",
+        "pressure <kind> <0-1> - set a political pressure component",
+    ),
+    ("stats", "stats - entity, archetype and unit counts"),
+    (
+        "config",
+        "config [use <name> | save <name> | set <setting> <value>] - config profiles",
+    ),
+    (
+        "script",
+        "script <code> - run a Rhai snippet with the mission API",
+    ),
+    (
+        "log",
+        "log [<subsystem|module> <level> | reset] - show or change log levels",
+    ),
+    (
+        "logs",
+        "logs [ai|intel|political|net|all|off] - log viewer panel",
+    ),
+    ("clear", "clear - empty the console"),
+];
+
+const LOG_SUBSYSTEM_NAMES: [&str; 4] = ["ai", "intel", "political", "net"];
+const LOG_LEVEL_NAMES: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+
+const FACTION_NAMES: [&str; 3] = ["Cartel", "Military", "Civilian"];
+
+const UNIT_TYPE_NAMES: [&str; 13] = [
+    "Sicario",
+    "Enforcer",
+    "Roadblock",
+    "Sniper",
+    "HeavyGunner",
+    "Medic",
+    "Soldier",
+    "SpecialForces",
+    "Vehicle",
+    "Tank",
+    "Helicopter",
+    "Engineer",
+    "Ovidio",
+];
+
+const GAME_PHASE_NAMES: [&str; 16] = [
+    "Loading",
+    "ProfileSelect",
+    "MainMenu",
+    "SaveMenu",
+    "LoadMenu",
+    "ModsMenu",
+    "AccessibilityMenu",
+    "MissionBriefing",
+    "Preparation",
+    "InitialRaid",
+    "BlockConvoy",
+    "ApplyPressure",
+    "HoldTheLine",
+    "Victory",
+    "Defeat",
+    "GameOver",
+];
+
+pub struct DevConsolePlugin;
+
+impl Plugin for DevConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DevConsole>()
+            .init_resource::<DevCheats>()
+            .add_systems(PreUpdate, console_input_system.after(InputSystem))
+            .add_systems(Update, (console_execute_system, console_ui_system).chain())
+            .add_systems(
+                FixedUpdate,
+                god_mode_system
+                    .in_set(DownedSet)
+                    .before(register_corpses_system)
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DevConsole {
+    pub open: bool,
+    pub input: String,
+    log: VecDeque<String>,
+    history: Vec<String>,
+    history_index: Option<usize>, // Entry being browsed with the arrow keys
+    pending: Vec<String>,         // Submitted lines waiting for world access
+}
+
+impl DevConsole {
+    pub fn print(&mut self, line: &str) {
+        if self.log.len() >= CONSOLE_LOG_LINES {
+            self.log.pop_front();
+        }
+        self.log.push_back(line.to_string());
+    }
+
+    fn submit(&mut self) {
+        let line = std::mem::take(&mut self.input).trim().to_string();
+        self.history_index = None;
+        if line.is_empty() {
+            return;
+        }
+
+        self.print(&format!("> {}", line));
+        if self.history.last() != Some(&line) {
+            if self.history.len() >= CONSOLE_HISTORY {
+                self.history.remove(0);
+            }
+            self.history.push(line.clone());
+        }
+        self.pending.push(line);
+    }
+
+    // Up walks back through earlier commands, Down forward to an empty line
+    fn browse_history(&mut self, back: bool) {
+        let index = match (self.history_index, back) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => Some(index + 1).filter(|next| *next < self.history.len()),
+        };
+        self.history_index = index;
+        self.input = index
+            .map(|index| self.history[index].clone())
+            .unwrap_or_default();
+    }
+
+    fn autocomplete(&mut self) {
+        let (head, matches) = complete_console_input(&self.input);
+        match matches.as_slice() {
+            [] => {}
+            [single] => self.input = format!("{}{} ", head, single),
+            _ => {
+                self.input = format!("{}{}", head, common_prefix(&matches));
+                self.print(&matches.join("  "));
+            }
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DevCheats {
+    pub god_mode: bool,
+}
+
+#[derive(Component)]
+pub struct ConsoleOverlay;
+
+#[derive(Component)]
+pub struct ConsoleText;
+
+// ==================== COMMANDS ====================
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleCommand {
+    Help,
+    Spawn {
+        faction: Faction,
+        unit_type: UnitType,
+        count: u32,
+        position: Option<Vec2>,
+    },
+    God(Option<bool>), // None toggles
+    Fog(Option<bool>),
+    Phase(GamePhase),
+    Pressure {
+        kind: String,
+        value: f32,
+    },
+    Stats,
+    Script(String),
+    LogLevel(Option<(String, String)>), // None shows the current filter
+    LogReset,
+    LogView {
+        open: bool,
+        subsystem: Option<LogSubsystem>, // None shows every subsystem
+    },
+    ConfigList,
+    ConfigUse(String),
+    ConfigSave(String),
+    ConfigSet {
+        setting: String,
+        value: String,
+    },
+    Clear,
+}
+
+pub fn parse_console_command(line: &str) -> Result<ConsoleCommand, String> {
+    let line = line.trim();
+    let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let args: Vec<&str> = rest.split_whitespace().collect();
+
+    match name.to_lowercase().as_str() {
+        "help" => Ok(ConsoleCommand::Help),
+        "clear" => Ok(ConsoleCommand::Clear),
+        "stats" => Ok(ConsoleCommand::Stats),
+        "god" => Ok(ConsoleCommand::God(parse_switch(args.first())?)),
+        "fog" => Ok(ConsoleCommand::Fog(parse_switch(args.first())?)),
+        "phase" => Ok(ConsoleCommand::Phase(parse_name(
+            &GAME_PHASE_NAMES,
+            args.first(),
+            "phase",
+        )?)),
+        "pressure" => {
+            let kind = args.first().ok_or("missing pressure kind")?;
+            let kind = ADJUSTABLE_PRESSURES
+                .iter()
+                .find(|name| name.eq_ignore_ascii_case(kind))
+                .ok_or_else(|| format!("unknown pressure '{}'", kind))?;
+            let value = args.get(1).ok_or("missing pressure value")?;
+            let value: f32 = value
+                .parse()
+                .map_err(|_| format!("'{}' is not a number", value))?;
+            Ok(ConsoleCommand::Pressure {
+                kind: kind.to_string(),
+                value: value.clamp(0.0, 1.0),
+            })
+        }
+        "spawn" => {
+            let faction = parse_name(&FACTION_NAMES, args.first(), "faction")?;
+            let unit_type = parse_name(&UNIT_TYPE_NAMES, args.get(1), "unit type")?;
+            let numbers = args[2.min(args.len())..]
+                .iter()
+                .map(|arg| arg.parse::<f32>())
+                .collect::<Result<Vec<f32>, _>>()
+                .map_err(|_| "count and position must be numbers".to_string())?;
+            let (count, position) = match numbers.as_slice() {
+                [] => (1.0, None),
+                [count] => (*count, None),
+                [x, y] => (1.0, Some(Vec2::new(*x, *y))),
+                [count, x, y] => (*count, Some(Vec2::new(*x, *y))),
+                _ => return Err("usage: spawn <faction> <type> [count] [x y]".into()),
+            };
+            if count < 1.0 || count > MAX_CONSOLE_SPAWNS as f32 || count.fract() != 0.0 {
+                return Err(format!("count must be 1-{}", MAX_CONSOLE_SPAWNS));
+            }
+            Ok(ConsoleCommand::Spawn {
+                faction,
+                unit_type,
+                count: count as u32,
+                position,
+            })
+        }
+        "script" if !rest.trim().is_empty() => Ok(ConsoleCommand::Script(rest.trim().into())),
+        "script" => Err("usage: script <code>".into()),
+        "log" => match args.as_slice() {
+            [] => Ok(ConsoleCommand::LogLevel(None)),
+            [reset] if reset.eq_ignore_ascii_case("reset") => Ok(ConsoleCommand::LogReset),
+            [module, level] => Ok(ConsoleCommand::LogLevel(Some((
+                module.to_string(),
+                level.to_lowercase(),
+            )))),
+            _ => Err("usage: log [<subsystem|module> <level> | reset]".into()),
+        },
+        "logs" => match args.first().map(|arg| arg.to_lowercase()).as_deref() {
+            None | Some("all") => Ok(ConsoleCommand::LogView {
+                open: true,
+                subsystem: None,
+            }),
+            Some("off") => Ok(ConsoleCommand::LogView {
+                open: false,
+                subsystem: None,
+            }),
+            Some(name) => Ok(ConsoleCommand::LogView {
+                open: true,
+                subsystem: Some(
+                    LogSubsystem::from_name(name)
+                        .ok_or_else(|| format!("unknown subsystem '{}'", name))?,
+                ),
+            }),
+        },
+        "config" => match args.as_slice() {
+            [] => Ok(ConsoleCommand::ConfigList),
+            // Profile names can have spaces, e.g. potato PC
+            [action, name @ ..] if action.eq_ignore_ascii_case("use") && !name.is_empty() => {
+                Ok(ConsoleCommand::ConfigUse(name.join(" ")))
+            }
+            [action, name @ ..] if action.eq_ignore_ascii_case("save") && !name.is_empty() => {
+                Ok(ConsoleCommand::ConfigSave(name.join(" ")))
+            }
+            [action, setting, value] if action.eq_ignore_ascii_case("set") => {
+                Ok(ConsoleCommand::ConfigSet {
+                    setting: setting.to_lowercase(),
+                    value: value.to_string(),
+                })
+            }
+            _ => Err("usage: config [use <name> | save <name> | set <setting> <value>]".into()),
+        },
+        other => Err(format!("unknown command '{}' - try help", other)),
+    }
+}
+
+// Names are matched case-insensitively, then parsed like script arguments
+fn parse_name<T: DeserializeOwned>(
+    names: &[&str],
+    arg: Option<&&str>,
+    what: &str,
+) -> Result<T, String> {
+    let arg = arg.ok_or_else(|| format!("missing {}", what))?;
+    names
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(arg))
+        .and_then(|name| parse_variant(name))
+        .ok_or_else(|| format!("unknown {} '{}'", what, arg))
+}
+
+fn parse_switch(arg: Option<&&str>) -> Result<Option<bool>, String> {
+    match arg.map(|arg| arg.to_lowercase()).as_deref() {
+        None => Ok(None),
+        Some("on") => Ok(Some(true)),
+        Some("off") => Ok(Some(false)),
+        Some(other) => Err(format!("expected on or off, got '{}'", other)),
+    }
+}
+
+// ==================== AUTOCOMPLETION ====================
+
+fn argument_choices(command: &str, position: usize) -> Vec<&'static str> {
+    match (command, position) {
+        (_, 0) => CONSOLE_COMMANDS.iter().map(|(name, _)| *name).collect(),
+        ("spawn", 1) => FACTION_NAMES.to_vec(),
+        ("spawn", 2) => UNIT_TYPE_NAMES.to_vec(),
+        ("god" | "fog", 1) => vec!["on", "off"],
+        ("phase", 1) => GAME_PHASE_NAMES.to_vec(),
+        ("log", 1) => [LOG_SUBSYSTEM_NAMES.as_slice(), &["reset"]].concat(),
+        ("log", 2) => LOG_LEVEL_NAMES.to_vec(),
+        ("logs", 1) => [LOG_SUBSYSTEM_NAMES.as_slice(), &["all", "off"]].concat(),
+        ("pressure", 1) => ADJUSTABLE_PRESSURES.to_vec(),
+        ("config", 1) => vec!["use", "save", "set"],
+        ("config", 2) => CONFIG_SETTINGS.to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+// The input up to the word being typed, and what that word could become
+pub fn complete_console_input(input: &str) -> (String, Vec<&'static str>) {
+    let words: Vec<&str> = input.split(' ').collect();
+    let word = words.last().copied().unwrap_or_default();
+    let head = input[..input.len() - word.len()].to_string();
+    let command = words[0].to_lowercase();
+
+    let prefix = word.to_lowercase();
+    let matches = argument_choices(&command, words.len() - 1)
+        .into_iter()
+        .filter(|choice| choice.to_lowercase().starts_with(&prefix))
+        .collect();
+    (head, matches)
+}
+
+fn common_prefix(words: &[&str]) -> String {
+    let first = words[0];
+    let mut length = first.len();
+    for word in &words[1..] {
+        length = first
+            .chars()
+            .zip(word.chars())
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count()
+            .min(length);
+    }
+    first[..length].to_string()
+}
+
+// ==================== SYSTEMS ====================
+
+pub fn console_input_system(
+    mut console: ResMut<DevConsole>,
+    mut keyboard: ResMut<Input<KeyCode>>,
+    mut char_events: EventReader<ReceivedCharacter>,
+) {
+    if keyboard.just_pressed(KeyCode::Grave) {
+        console.open = !console.open;
+        char_events.clear();
+        keyboard.reset_all();
+        return;
+    }
+    if !console.open {
+        return;
+    }
+
+    for event in char_events.read() {
+        if !event.char.is_control() && !matches!(event.char, '`' | '~') {
+            console.input.push(event.char);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Return) {
+        console.submit();
+    } else if keyboard.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    } else if keyboard.just_pressed(KeyCode::Tab) {
+        console.autocomplete();
+    } else if keyboard.just_pressed(KeyCode::Up) {
+        console.browse_history(true);
+    } else if keyboard.just_pressed(KeyCode::Down) {
+        console.browse_history(false);
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        console.open = false;
+    }
+
+    // Typing mustn't pan the camera, pause the game or fire hotkeys
+    keyboard.reset_all();
+}
+
+// Commands need the whole world, so they run here rather than in the input system
+pub fn console_execute_system(world: &mut World) {
+    if world.resource::<DevConsole>().pending.is_empty() {
+        return;
+    }
+
+    let pending = std::mem::take(&mut world.resource_mut::<DevConsole>().pending);
+    for line in pending {
+        let output = match parse_console_command(&line) {
+            Ok(ConsoleCommand::Clear) => {
+                world.resource_mut::<DevConsole>().log.clear();
+                continue;
+            }
+            Ok(command) => execute_console_command(world, command),
+            Err(e) => Err(e),
+        };
+
+        let mut console = world.resource_mut::<DevConsole>();
+        match output {
+            Ok(text) => text.lines().for_each(|line| console.print(line)),
+            Err(e) => console.print(&format!("error: {}", e)),
+        }
+    }
+}
+
+fn execute_console_command(world: &mut World, command: ConsoleCommand) -> Result<String, String> {
+    match command {
+        ConsoleCommand::Help => Ok(CONSOLE_COMMANDS
+            .iter()
+            .map(|(_, usage)| *usage)
+            .collect::<Vec<_>>()
+            .join("\n")),
+        ConsoleCommand::Spawn {
+            faction,
+            unit_type,
+            count,
+            position,
+        } => spawn_from_console(world, faction, unit_type, count, position),
+        ConsoleCommand::God(enabled) => {
+            let mut cheats = world.resource_mut::<DevCheats>();
+            cheats.god_mode = enabled.unwrap_or(!cheats.god_mode);
+            Ok(format!("God mode {}", on_off(cheats.god_mode)))
+        }
+        ConsoleCommand::Fog(enabled) => {
+            let mut env_state = world.resource_mut::<EnvironmentalState>();
+            let fog = enabled.unwrap_or(env_state.weather_type != WeatherType::Fog);
+            // Holds until the next random weather change
+            if fog {
+                env_state.weather_type = WeatherType::Fog;
+                env_state.weather_intensity = 1.0;
+            } else {
+                env_state.weather_type = WeatherType::Clear;
+                env_state.weather_intensity = 0.0;
+            }
+            env_state.update_gameplay_modifiers();
+            Ok(format!("Fog {}", on_off(fog)))
+        }
+        ConsoleCommand::Phase(phase) => {
+            world.resource_mut::<GameState>().game_phase = phase.clone();
+            Ok(format!("Phase set to {:?}", phase))
+        }
+        ConsoleCommand::Pressure { kind, value } => {
+            world.resource_scope(|world, mut campaign: Mut<Campaign>| {
+                let balance = world.resource::<BalanceConfig>();
+                let pressure = &mut campaign.political_pressure;
+                if let Some(component) = pressure_component(pressure, &kind) {
+                    *component = value;
+                }
+                pressure.update_pressure(&balance.pressure);
+                Ok(format!(
+                    "{} = {:.2} (total pressure {:.2})",
+                    kind, value, pressure.total_pressure
+                ))
+            })
+        }
+        ConsoleCommand::Stats => Ok(ecs_stats(world)),
+        ConsoleCommand::Script(code) => run_script_snippet(world, &code),
+        ConsoleCommand::LogLevel(None) => Ok(format!("Log filter: {}", current_filter())),
+        ConsoleCommand::LogLevel(Some((module, level))) => {
+            set_log_level(&module, &level).map(|filter| format!("Log filter: {}", filter))
+        }
+        ConsoleCommand::LogReset => {
+            reset_log_levels().map(|filter| format!("Log filter: {}", filter))
+        }
+        ConsoleCommand::LogView { open, subsystem } => {
+            let mut viewer = world
+                .get_resource_mut::<LogViewer>()
+                .ok_or("the log viewer isn't running")?;
+            viewer.open = open;
+            viewer.subsystem = subsystem;
+            Ok(match (open, subsystem) {
+                (false, _) => "Log viewer closed".to_string(),
+                (true, None) => "Log viewer showing all subsystems".to_string(),
+                (true, Some(subsystem)) => format!("Log viewer showing {}", subsystem.name()),
+            })
+        }
+        ConsoleCommand::ConfigList => {
+            let profiles = world
+                .get_resource::<ConfigProfiles>()
+                .ok_or("config profiles aren't loaded")?;
+            Ok(profiles
+                .profiles
+                .iter()
+                .map(|profile| {
+                    format!(
+                        "{} {}: {} quality, vsync {}, fps cap {}, particles {:.1}, decals {}",
+                        if profile.name == profiles.active {
+                            "*"
+                        } else {
+                            " "
+                        },
+                        profile.name,
+                        profile.graphics.quality.name(),
+                        on_off(profile.vsync),
+                        profile.fps_cap,
+                        profile.graphics.weather_particles,
+                        profile.graphics.decal_capacity
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        ConsoleCommand::ConfigUse(name) => change_config_profiles(world, |profiles| {
+            profiles.switch(&name)?;
+            Ok(format!("Config profile {}", profiles.active))
+        }),
+        ConsoleCommand::ConfigSave(name) => change_config_profiles(world, |profiles| {
+            profiles.save_as(&name);
+            Ok(format!("Saved config profile {}", name))
+        }),
+        ConsoleCommand::ConfigSet { setting, value } => change_config_profiles(world, |profiles| {
+            profiles.set(&setting, &value)?;
+            Ok(format!("{} {} = {}", profiles.active, setting, value))
+        }),
+        ConsoleCommand::Clear => Ok(String::new()),
+    }
+}
+
+// Changes are written straight away so they're there on the next launch
+fn change_config_profiles(
+    world: &mut World,
+    change: impl FnOnce(&mut ConfigProfiles) -> Result<String, String>,
+) -> Result<String, String> {
+    let mut profiles = world
+        .get_resource_mut::<ConfigProfiles>()
+        .ok_or("config profiles aren't loaded")?;
+    let output = change(&mut *profiles)?;
+    profiles
+        .save()
+        .map_err(|e| format!("couldn't save config profiles: {}", e))?;
+    Ok(output)
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn spawn_from_console(
+    world: &mut World,
+    faction: Faction,
+    unit_type: UnitType,
+    count: u32,
+    position: Option<Vec2>,
+) -> Result<String, String> {
+    if !world.contains_resource::<GameAssets>() {
+        return Err("assets are not loaded yet".into());
+    }
+
+    // Positions are on-screen coordinates, like the ones scripts use
+    let centre = position.unwrap_or_else(|| {
+        world
+            .query_filtered::<&Transform, With<IsometricCamera>>()
+            .iter(world)
+            .next()
+            .map_or(Vec2::ZERO, |transform| transform.translation.truncate())
+    });
+
+    let mut state: SystemState<(Commands, Res<GameAssets>, Res<UnitCatalog>)> =
+        SystemState::new(world);
+    let (mut commands, game_assets, catalog) = state.get_mut(world);
+    for index in 0..count {
+        let offset = Vec2::new((index % 5) as f32, (index / 5) as f32) * CONSOLE_SPAWN_SPACING;
+        spawn_unit(
+            &mut commands,
+            unit_type.clone(),
+            faction.clone(),
+            iso_to_world((centre + offset).extend(0.0)),
+            &game_assets,
+            &catalog,
+        );
+    }
+    state.apply(world);
+
+    Ok(format!(
+        "Spawned {} {:?} {:?} at ({:.0}, {:.0})",
+        count, faction, unit_type, centre.x, centre.y
+    ))
+}
+
+fn ecs_stats(world: &mut World) -> String {
+    let (mut cartel, mut military, mut civilian, mut corpses) = (0, 0, 0, 0);
+    for (unit, is_corpse) in world.query::<(&Unit, Has<Corpse>)>().iter(world) {
+        if is_corpse {
+            corpses += 1;
+            continue;
+        }
+        match unit.faction {
+            Faction::Cartel | Faction::RivalCartel => cartel += 1,
+            Faction::Military | Faction::Police => military += 1,
+            Faction::Civilian => civilian += 1,
+        }
+    }
+
+    let game_state = world.resource::<GameState>();
+    format!(
+        "Entities: {}  Archetypes: {}  Component types: {}\n\
+         Units: {} cartel, {} military, {} civilian, {} corpses\n\
+         Phase: {:?}  Mission time: {:.0}s",
+        world.entities().len(),
+        world.archetypes().len(),
+        world.components().len(),
+        cartel,
+        military,
+        civilian,
+        corpses,
+        game_state.game_phase,
+        game_state.mission_timer
+    )
+}
+
+#[cfg(feature = "scripting")]
+fn run_script_snippet(world: &mut World, code: &str) -> Result<String, String> {
+    if !world.contains_resource::<ScriptRuntime>() {
+        return Err("the script runtime is not loaded".into());
+    }
+
+    let mut state: SystemState<(
+        Res<GameState>,
+        Res<Campaign>,
+        Query<(&Unit, &Transform), Without<Corpse>>,
+    )> = SystemState::new(world);
+    let (game_state, campaign, unit_query) = state.get(world);
+    let view = ScriptWorldView::capture(&game_state, &campaign, &unit_query);
+
+    let (result, commands) = world
+        .resource_mut::<ScriptRuntime>()
+        .eval_snippet(view, code);
+    // Queued calls are applied on the next simulation tick
+    world
+        .resource_mut::<ScriptCommandQueue>()
+        .0
+        .extend(commands);
+    result
+}
+
+#[cfg(not(feature = "scripting"))]
+fn run_script_snippet(_world: &mut World, _code: &str) -> Result<String, String> {
+    Err("built without the scripting feature".into())
+}
+
+pub fn god_mode_system(cheats: Res<DevCheats>, mut unit_query: Query<&mut Unit, Without<Corpse>>) {
+    if !cheats.god_mode {
+        return;
+    }
+    for mut unit in unit_query.iter_mut() {
+        if unit.faction == Faction::Cartel && unit.health < unit.max_health {
+            unit.health = unit.max_health;
+        }
+    }
+}
+
+pub fn console_ui_system(
+    mut commands: Commands,
+    console: Res<DevConsole>,
+    overlay_query: Query<Entity, With<ConsoleOverlay>>,
+    mut text_query: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    if !console.open {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let skip = console.log.len().saturating_sub(CONSOLE_VISIBLE_LINES);
+    let mut content: Vec<&str> = console.log.iter().skip(skip).map(String::as_str).collect();
+    let prompt = format!("> {}_", console.input);
+    content.push(&prompt);
+    let content = content.join("\n");
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = content;
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(40.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.85)),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            ConsoleOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    content,
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::rgb(0.6, 1.0, 0.6),
+                        ..default()
+                    },
+                ),
+                ConsoleText,
+            ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_console_commands_parse() {
+        assert_eq!(
+            parse_console_command("spawn military tank 3 100 -50"),
+            Ok(ConsoleCommand::Spawn {
+                faction: Faction::Military,
+                unit_type: UnitType::Tank,
+                count: 3,
+                position: Some(Vec2::new(100.0, -50.0)),
+            })
+        );
+        assert_eq!(
+            parse_console_command("phase holdtheline"),
+            Ok(ConsoleCommand::Phase(GamePhase::HoldTheLine))
+        );
+        assert_eq!(
+            parse_console_command("pressure media_attention 2"),
+            Ok(ConsoleCommand::Pressure {
+                kind: "media_attention".into(),
+                value: 1.0,
+            })
+        );
+        assert_eq!(parse_console_command("god"), Ok(ConsoleCommand::God(None)));
+        assert!(parse_console_command("spawn cartel sicario 0").is_err());
+        assert!(parse_console_command("fog maybe").is_err());
+        assert_eq!(
+            parse_console_command("log AI debug"),
+            Ok(ConsoleCommand::LogLevel(Some((
+                "AI".into(),
+                "debug".into()
+            ))))
+        );
+        assert_eq!(
+            parse_console_command("logs net"),
+            Ok(ConsoleCommand::LogView {
+                open: true,
+                subsystem: Some(LogSubsystem::Net),
+            })
+        );
+        assert!(parse_console_command("logs audio").is_err());
+        assert_eq!(
+            parse_console_command("config use potato PC"),
+            Ok(ConsoleCommand::ConfigUse("potato PC".into()))
+        );
+        assert_eq!(
+            parse_console_command("config set Decals 200"),
+            Ok(ConsoleCommand::ConfigSet {
+                setting: "decals".into(),
+                value: "200".into(),
+            })
+        );
+        assert_eq!(
+            parse_console_command("config set quality Ultra"),
+            Ok(ConsoleCommand::ConfigSet {
+                setting: "quality".into(),
+                value: "Ultra".into(),
+            })
+        );
+        assert!(parse_console_command("config save").is_err());
+        assert!(parse_console_command("teleport").is_err());
+    }
+
+    #[test]
+    fn test_autocomplete_and_history() {
+        assert_eq!(complete_console_input("sp"), (String::new(), vec!["spawn"]));
+        assert_eq!(
+            complete_console_input("spawn cartel sp"),
+            ("spawn cartel ".to_string(), vec!["SpecialForces"])
+        );
+
+        let mut console = DevConsole {
+            input: "phase h".into(),
+            ..default()
+        };
+        console.autocomplete();
+        assert_eq!(console.input, "phase HoldTheLine ");
+        console.input = "spawn military s".into();
+        console.autocomplete();
+        assert_eq!(console.input, "spawn military S");
+
+        console.input = "stats".into();
+        console.submit();
+        console.input = "god on".into();
+        console.submit();
+        console.browse_history(true);
+        assert_eq!(console.input, "god on");
+        console.browse_history(true);
+        assert_eq!(console.input, "stats");
+        console.browse_history(false);
+        console.browse_history(false);
+        assert_eq!(console.input, "");
+        assert_eq!(console.pending, vec!["stats", "god on"]);
+    }
+}
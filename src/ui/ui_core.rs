@@ -1,5 +1,8 @@
 use crate::components::*;
+use crate::localization::{tr, tr_args};
 use crate::resources::*;
+use crate::ui::ui_theme::{is_health_bar_foreground, UiTheme};
+use crate::utils::{EntityPools, ParticleVelocity, PooledEntity};
 use bevy::prelude::*;
 
 // Type aliases to reduce complexity
@@ -50,28 +53,19 @@ pub fn ui_update_system(
     // Update status text
     if let Ok(mut text) = status_query.get_single_mut() {
         let status = if !ovidio_alive {
-            "❌ MISSION FAILED: Ovidio captured!"
+            tr("hud.ovidio_captured")
         } else if game_state.game_phase == GamePhase::GameOver {
-            "✅ MISSION SUCCESS: Government retreats!"
+            tr("hud.government_retreats")
         } else {
-            match game_state.game_phase {
-                GamePhase::MainMenu => "🎮 Main Menu",
-                GamePhase::SaveMenu => "💾 Save Game",
-                GamePhase::LoadMenu => "📂 Load Game",
-                GamePhase::MissionBriefing => "📋 Mission Briefing",
-                GamePhase::Preparation => "🔄 Phase: Preparation",
-                GamePhase::InitialRaid => "⚔️ Phase: Initial Raid",
-                GamePhase::BlockConvoy => "🚧 Phase: Block Convoy",
-                GamePhase::ApplyPressure => "🔥 Phase: Apply Pressure",
-                GamePhase::HoldTheLine => "🛡️ Phase: Hold The Line",
-                GamePhase::Victory => "🏆 VICTORY!",
-                GamePhase::Defeat => "💀 DEFEAT!",
-                GamePhase::GameOver => "🏁 Mission Complete",
-            }
+            tr(&format!("phase.{:?}", game_state.game_phase))
         };
-        text.sections[0].value = format!(
-            "{}\nCartel: {} | Military: {}",
-            status, cartel_count, military_count
+        text.sections[0].value = tr_args(
+            "hud.status",
+            &[
+                ("phase", &status),
+                ("cartel", &cartel_count),
+                ("military", &military_count),
+            ],
         );
     } else {
         warn!("StatusText UI element not found");
@@ -79,33 +73,44 @@ pub fn ui_update_system(
 
     // Update wave text
     if let Ok(mut text) = wave_query.get_single_mut() {
-        text.sections[0].value = format!(
-            "Wave: {} - Timer: {:.1}s",
-            game_state.current_wave, game_state.mission_timer
+        text.sections[0].value = tr_args(
+            "hud.wave",
+            &[
+                ("wave", &game_state.current_wave),
+                ("seconds", &format!("{:.1}", game_state.mission_timer)),
+            ],
         );
     }
 
     // Update score text
     if let Ok(mut text) = score_query.get_single_mut() {
-        text.sections[0].value = format!(
-            "Score: Cartel {} - Military {}",
-            game_state.cartel_score, game_state.military_score
+        text.sections[0].value = tr_args(
+            "hud.score",
+            &[
+                ("cartel", &game_state.cartel_score),
+                ("military", &game_state.military_score),
+            ],
         );
     }
 
     // Update difficulty display
     if let Ok(mut text) = difficulty_query.get_single_mut() {
         let adaptive_status = if ai_director.adaptive_difficulty {
-            "AUTO"
+            tr("hud.difficulty_auto")
         } else {
-            "MANUAL"
+            tr("hud.difficulty_manual")
         };
         // Adaptive difficulty status determined based on system configuration
-        text.sections[0].value = format!(
-            "Difficulty: {:.1} ({}) | Performance: {:.0}%\nD=Toggle | F1-F4=Set Level",
-            ai_director.intensity_level,
-            adaptive_status,
-            ai_director.player_performance * 100.0
+        text.sections[0].value = tr_args(
+            "hud.difficulty",
+            &[
+                ("intensity", &format!("{:.1}", ai_director.intensity_level)),
+                ("mode", &adaptive_status),
+                (
+                    "performance",
+                    &format!("{:.0}", ai_director.player_performance * 100.0),
+                ),
+            ],
         );
     }
     // Creative toevoeging: indien in debug mode, log extra informatie voor een beter overzicht
@@ -122,6 +127,7 @@ pub fn health_bar_system(
     mut commands: Commands,
     unit_query: Query<(Entity, &Unit, &Transform), Changed<Unit>>,
     mut health_bar_query: HealthBarQuery,
+    theme: Res<UiTheme>,
 ) {
     // Update health bars when units change
     for (unit_entity, unit, unit_transform) in unit_query.iter() {
@@ -132,24 +138,15 @@ pub fn health_bar_system(
                 // Update position
                 bar_transform.translation = unit_transform.translation + health_bar.offset;
 
-                // Update health bar color and width based on health percentage
+                // Foreground shows health through color and width, background stays dark
                 let health_percent = unit.health / unit.max_health;
-                let bar_color = if health_percent > 0.6 {
-                    Color::rgb(0.2, 0.8, 0.2) // Green
-                } else if health_percent > 0.3 {
-                    Color::rgb(0.8, 0.8, 0.2) // Yellow
-                } else {
-                    Color::rgb(0.8, 0.2, 0.2) // Red
-                };
-
-                bar_sprite.color = bar_color;
-
-                // Adjust bar width based on health (only for foreground bars)
-                if health_bar.offset.z > 0.15 {
-                    // Foreground bar
+                if is_health_bar_foreground(health_bar) {
+                    bar_sprite.color = theme.health_color(health_percent);
                     if let Some(ref mut size) = bar_sprite.custom_size {
                         size.x = 50.0 * health_percent;
                     }
+                } else {
+                    bar_sprite.color = theme.colors().health_background;
                 }
 
                 // Remove health bar if unit is dead
@@ -176,20 +173,30 @@ pub fn health_bar_system(
 
 pub fn damage_indicator_system(
     mut commands: Commands,
+    mut entity_pools: ResMut<EntityPools>,
     mut damage_query: Query<(
         Entity,
         &mut Transform,
         &mut DamageIndicator,
-        Option<&ParticleEffect>,
+        Option<&ParticleVelocity>,
+        Option<&mut PooledEntity>,
+        &mut Visibility,
     )>,
     time: Res<Time>,
 ) {
-    for (entity, mut transform, mut indicator, particle_effect) in damage_query.iter_mut() {
+    for (entity, mut transform, mut indicator, velocity, pooled, mut visibility) in
+        damage_query.iter_mut()
+    {
+        // Parked in the pool until the next spawn reconfigures it
+        if pooled.as_ref().is_some_and(|pooled| !pooled.active) {
+            continue;
+        }
+
         indicator.lifetime.tick(time.delta());
 
-        // Use particle effect velocity if available, otherwise default upward movement
-        if let Some(particle) = particle_effect {
-            transform.translation += particle.velocity * time.delta_seconds();
+        // Use the spawn velocity if available, otherwise default upward movement
+        if let Some(velocity) = velocity {
+            transform.translation += velocity.0 * time.delta_seconds();
         } else {
             transform.translation.y += 30.0 * time.delta_seconds();
         }
@@ -200,17 +207,34 @@ pub fn damage_indicator_system(
 
         // Remove when expired
         if indicator.lifetime.finished() {
-            commands.entity(entity).despawn();
+            match pooled {
+                Some(mut pooled) => {
+                    *visibility = Visibility::Hidden;
+                    entity_pools.release(entity, &mut pooled);
+                }
+                None => commands.entity(entity).despawn(),
+            }
         }
     }
 }
 
 pub fn particle_system(
     mut commands: Commands,
-    mut particle_query: Query<(Entity, &mut Transform, &mut ParticleEffect)>,
+    mut entity_pools: ResMut<EntityPools>,
+    mut particle_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut ParticleEffect,
+        Option<&mut PooledEntity>,
+        &mut Visibility,
+    )>,
     time: Res<Time>,
 ) {
-    for (entity, mut transform, mut particle) in particle_query.iter_mut() {
+    for (entity, mut transform, mut particle, pooled, mut visibility) in particle_query.iter_mut() {
+        if pooled.as_ref().is_some_and(|pooled| !pooled.active) {
+            continue;
+        }
+
         particle.lifetime.tick(time.delta());
 
         // Move particle
@@ -218,7 +242,13 @@ pub fn particle_system(
 
         // Remove when expired
         if particle.lifetime.finished() {
-            commands.entity(entity).despawn();
+            match pooled {
+                Some(mut pooled) => {
+                    *visibility = Visibility::Hidden;
+                    entity_pools.release(entity, &mut pooled);
+                }
+                None => commands.entity(entity).despawn(),
+            }
         }
     }
 }
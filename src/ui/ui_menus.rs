@@ -1,7 +1,27 @@
+use crate::accessibility::{AccessibilitySettings, ModifierKeyMode};
+use crate::assists::{assist_option_lines, assists_offered, MissionAssist, MissionAssists};
+use crate::auth::AuthSession;
 use crate::campaign::{get_objective_summary, Campaign, MissionConfig};
 use crate::components::*;
+use crate::config::{
+    mod_registry, BalanceConfig, ConfigProfile, ConfigProfiles, ModRegistry, ScoringBalance,
+    MODS_DIR,
+};
+use crate::daily::{daily_briefing_lines, DailyChallenge, DailyChallengeState, DAILY_KEY};
+use crate::leaderboard::{board_lines, difficulty_label, BoardKey, Leaderboards};
+use crate::localization::{
+    current_language, language_name, next_language, set_language, tr, tr_args,
+};
+use crate::map::CityMap;
+use crate::narration::{NarrationEvent, NarrationMode, NarrationPriority};
+use crate::political_system::PoliticalState;
+use crate::profile::{PlayerProfile, ProfileManager, ProfileSettings};
 use crate::resources::*;
-use crate::save::save_system::{has_save_file, load_game, save_game};
+use crate::save::save_system::{has_save_file, DifficultyLevel, LoadGameEvent, SaveGameEvent};
+use crate::scoring::{ChallengeModifier, ChallengeSettings, MissionRecord, MissionScore};
+use crate::telemetry::{Telemetry, TelemetrySummary};
+use crate::tutorial::Tutorial;
+use crate::ui::UiTheme;
 use crate::utils::play_tactical_sound;
 use bevy::prelude::*;
 
@@ -9,11 +29,17 @@ use bevy::prelude::*;
 
 // ==================== MISSION BRIEFING SYSTEM ====================
 
+#[allow(clippy::too_many_arguments)]
 pub fn mission_briefing_system(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
     campaign: Res<Campaign>,
     input: Res<Input<KeyCode>>,
+    mut challenges: ResMut<ChallengeSettings>,
+    mut daily: ResMut<DailyChallengeState>,
+    mut profiles: ResMut<ProfileManager>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
     briefing_query: Query<Entity, With<MissionBriefing>>,
 ) {
     // Only show briefing when in MissionBriefing phase
@@ -23,12 +49,40 @@ pub fn mission_briefing_system(
             commands.entity(entity).despawn_recursive();
         }
 
+        // Today's challenge is open to a profile that hasn't tried it yet
+        let today = DailyChallenge::today(&city_map);
+        let available = profiles
+            .active
+            .as_ref()
+            .is_some_and(|profile| profile.daily_attempt(today.date).is_none());
+        if input.just_pressed(DAILY_KEY) && available {
+            daily.selected = !daily.selected;
+        }
+
+        // Challenge modifiers can only be changed before the mission starts,
+        // and a daily challenge is played without them
+        for modifier in ChallengeModifier::ALL {
+            if input.just_pressed(modifier.key()) && !daily.selected {
+                challenges.toggle(modifier);
+            }
+        }
+        if daily.selected {
+            challenges.active.clear();
+        }
+
         // Get current mission config
         let mission_config =
             crate::campaign::MissionConfig::get_mission_config(&campaign.progress.current_mission);
 
         // Create mission briefing UI
-        create_mission_briefing_ui(&mut commands, &mission_config);
+        create_mission_briefing_ui(
+            &mut commands,
+            &mission_config,
+            campaign.progress.best_records.get(&mission_config.id),
+            &challenges,
+            &balance.scoring,
+            &daily_briefing_lines(&daily, &today, available),
+        );
 
         // Check for input to start mission
         if input.just_pressed(KeyCode::Space) || input.just_pressed(KeyCode::Return) {
@@ -37,11 +91,20 @@ pub fn mission_briefing_system(
                 commands.entity(entity).despawn_recursive();
             }
 
+            // The attempt counts from the moment the challenge starts
+            if daily.selected {
+                if let Some(profile) = profiles.active.as_mut() {
+                    profile.start_daily(today.date);
+                    profiles.save_active();
+                }
+                daily.start(today);
+            }
+
             // Start the actual mission
             game_state.game_phase = GamePhase::Preparation;
             play_tactical_sound(
                 "radio",
-                &format!("Mission: {} - Begin operation!", mission_config.name),
+                &tr_args("radio.mission_begin", &[("name", &mission_config.name)]),
             );
         }
     } else {
@@ -52,34 +115,212 @@ pub fn mission_briefing_system(
     }
 }
 
+// ==================== MENU FOCUS ====================
+
+// Arrow keys and Tab move a focus highlight through the numbered options of
+// the current menu page, and Enter picks the focused option like its number
+// key. Focus changes are narrated, as is an option whose text changes in place.
+
+const OPTION_KEYS: [KeyCode; 10] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Key0, // Tenth option
+];
+const FOCUS_HIGHLIGHT_ALPHA: f32 = 0.3;
+const VOLUME_STEPS: f32 = 10.0; // The volume option moves in tenths
+
+#[derive(Resource, Default)]
+pub struct MenuFocus {
+    page: Option<GamePhase>,
+    index: usize,
+    option_count: usize, // Options on the page, 0 outside menus
+    spoken: String,      // Text last narrated for the focused option
+}
+
+impl MenuFocus {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+pub fn menu_focus_system(
+    game_state: Res<GameState>,
+    input: Res<Input<KeyCode>>,
+    mut focus: ResMut<MenuFocus>,
+    items: Query<(&MenuItem, &Text)>,
+    titles: Query<(&MenuTitle, &Text)>,
+    mut narration: EventWriter<NarrationEvent>,
+) {
+    let page = &game_state.game_phase;
+    if focus.page.as_ref() != Some(page) {
+        focus.page = Some(page.clone());
+        focus.index = 0;
+        focus.spoken.clear();
+    }
+
+    // Pages are redrawn through Commands, so the previous page can linger a frame
+    let options: Vec<(usize, String)> = items
+        .iter()
+        .filter(|(item, _)| item.page == *page)
+        .map(|(item, text)| (item.index, text_content(text)))
+        .collect();
+    focus.option_count = options
+        .iter()
+        .map(|(index, _)| index + 1)
+        .max()
+        .unwrap_or(0);
+    if focus.option_count == 0 {
+        return;
+    }
+
+    let backwards = input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if input.just_pressed(KeyCode::Down) || (input.just_pressed(KeyCode::Tab) && !backwards) {
+        focus.index = (focus.index + 1) % focus.option_count;
+    } else if input.just_pressed(KeyCode::Up) || (input.just_pressed(KeyCode::Tab) && backwards) {
+        focus.index = (focus.index + focus.option_count - 1) % focus.option_count;
+    }
+    focus.index = focus.index.min(focus.option_count - 1);
+
+    let Some((_, focused)) = options.into_iter().find(|(index, _)| *index == focus.index) else {
+        return;
+    };
+    if focused == focus.spoken {
+        return;
+    }
+    // Entering a page announces its title first
+    let line = match titles.iter().find(|(title, _)| title.page == *page) {
+        Some((_, title)) if focus.spoken.is_empty() => {
+            format!("{}. {}", text_content(title), focused)
+        }
+        _ => focused.clone(),
+    };
+    narration.send(NarrationEvent::new(line, NarrationPriority::Focus));
+    focus.spoken = focused;
+}
+
+pub fn menu_focus_highlight_system(
+    focus: Res<MenuFocus>,
+    theme: Res<UiTheme>,
+    mut items: Query<(&MenuItem, &mut BackgroundColor)>,
+) {
+    let highlight = theme.colors().selection.with_a(FOCUS_HIGHLIGHT_ALPHA);
+    for (item, mut background) in items.iter_mut() {
+        let focused = focus.page.as_ref() == Some(&item.page) && item.index == focus.index;
+        background.0 = if focused { highlight } else { Color::NONE };
+    }
+}
+
+// 1-based option picked by its number key, or by Enter on the focused option.
+// Enter is consumed so the next page doesn't see it in the same frame.
+fn selected_option(
+    input: &mut Input<KeyCode>,
+    focus: &MenuFocus,
+    page: &GamePhase,
+) -> Option<usize> {
+    if let Some(index) = OPTION_KEYS.iter().position(|key| input.just_pressed(*key)) {
+        return Some(index + 1);
+    }
+    let focused = focus.page.as_ref() == Some(page) && focus.index < focus.option_count;
+    if focused && input.just_pressed(KeyCode::Return) {
+        input.clear_just_pressed(KeyCode::Return);
+        return Some(focus.index + 1);
+    }
+    None
+}
+
+fn text_content(text: &Text) -> String {
+    text.sections
+        .iter()
+        .map(|section| section.value.as_str())
+        .collect()
+}
+
 // ==================== MAIN MENU SYSTEM ====================
 
+type MenuScreenQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Has<ModsMenuScreen>,
+        Has<AccessibilityMenuScreen>,
+        Has<TelemetryMenuScreen>,
+        Has<LeaderboardMenuScreen>,
+    ),
+    With<SaveLoadMenu>,
+>;
+
+#[allow(clippy::too_many_arguments)]
 pub fn main_menu_system(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
-    input: Res<Input<KeyCode>>,
-    menu_query: Query<Entity, With<SaveLoadMenu>>,
+    mut input: ResMut<Input<KeyCode>>,
+    focus: Res<MenuFocus>,
+    mut profiles: ResMut<ProfileManager>,
+    mut accessibility: ResMut<AccessibilitySettings>,
+    mut config_profiles: ResMut<ConfigProfiles>,
+    mut tutorial: ResMut<Tutorial>,
+    mut telemetry: ResMut<Telemetry>,
+    mut leaderboards: ResMut<Leaderboards>,
+    auth_session: Res<AuthSession>,
+    mut campaign: ResMut<Campaign>,
+    city_map: Res<CityMap>,
+    mut save_events: EventWriter<SaveGameEvent>,
+    mut load_events: EventWriter<LoadGameEvent>,
+    menu_query: MenuScreenQuery,
 ) {
+    let option = selected_option(&mut input, &focus, &game_state.game_phase);
     match game_state.game_phase {
         GamePhase::MainMenu => {
             // Remove any existing menu UI
-            for entity in menu_query.iter() {
+            for (entity, ..) in menu_query.iter() {
                 commands.entity(entity).despawn_recursive();
             }
 
             // Create main menu UI
-            create_main_menu_ui(&mut commands);
+            create_main_menu_ui(&mut commands, profiles.active_username());
 
             // Handle input
-            if input.just_pressed(KeyCode::Key1) {
-                game_state.game_phase = GamePhase::MissionBriefing;
-                play_tactical_sound("radio", "New campaign starting!");
-            } else if input.just_pressed(KeyCode::Key2) && has_save_file() {
+            if option == Some(1) {
+                game_state.game_phase = GamePhase::CampaignMap;
+                play_tactical_sound("radio", &tr("radio.new_campaign"));
+            } else if option == Some(2) && has_save_file() {
                 game_state.game_phase = GamePhase::LoadMenu;
-                play_tactical_sound("radio", "Accessing saved campaigns...");
-            } else if input.just_pressed(KeyCode::Key3) {
+                play_tactical_sound("radio", &tr("radio.load_menu"));
+            } else if option == Some(3) {
                 game_state.game_phase = GamePhase::SaveMenu;
-                play_tactical_sound("radio", "Opening save menu...");
+                play_tactical_sound("radio", &tr("radio.save_menu"));
+            } else if option == Some(4) {
+                game_state.game_phase = GamePhase::ProfileSelect;
+                play_tactical_sound("radio", &tr("radio.switch_profile"));
+            } else if option == Some(5) {
+                game_state.game_phase = GamePhase::ModsMenu;
+            } else if option == Some(6) {
+                // Cycle the language and keep the choice in the profile
+                let language = next_language(&current_language());
+                set_language(language);
+                if let Some(profile) = profiles.active.as_mut() {
+                    profile.settings.language = language.to_string();
+                    profiles.save_active();
+                }
+            } else if option == Some(7) {
+                game_state.game_phase = GamePhase::AccessibilityMenu;
+            } else if option == Some(8) && tutorial.start() {
+                game_state.game_phase = GamePhase::MissionBriefing;
+                play_tactical_sound("radio", &tr("radio.tutorial_start"));
+            } else if option == Some(9) {
+                game_state.game_phase = GamePhase::TelemetryMenu;
+            } else if option == Some(10) {
+                // Opens on the board for where the campaign stands
+                leaderboards.view(&auth_session, BoardKey::for_campaign(&campaign.progress));
+                game_state.game_phase = GamePhase::LeaderboardMenu;
             }
         }
         GamePhase::SaveMenu => {
@@ -90,15 +331,9 @@ pub fn main_menu_system(
 
             if input.just_pressed(KeyCode::Escape) {
                 game_state.game_phase = GamePhase::MainMenu;
-            } else if input.just_pressed(KeyCode::Key1) {
+            } else if option == Some(1) {
                 // Save to slot 1
-                if let Err(e) = save_game(&game_state) {
-                    error!("Failed to save game: {}", e);
-                    play_tactical_sound("radio", "Save failed!");
-                } else {
-                    play_tactical_sound("radio", "Game saved successfully!");
-                    game_state.game_phase = GamePhase::MainMenu;
-                }
+                save_events.send(SaveGameEvent);
             }
         }
         GamePhase::LoadMenu => {
@@ -109,27 +344,141 @@ pub fn main_menu_system(
 
             if input.just_pressed(KeyCode::Escape) {
                 game_state.game_phase = GamePhase::MainMenu;
-            } else if input.just_pressed(KeyCode::Key1) && has_save_file() {
+            } else if option == Some(1) && has_save_file() {
                 // Load from slot 1
-                match load_game() {
-                    Ok(save_data) => {
-                        *game_state = save_data.game_state;
-                        play_tactical_sound(
-                            "radio",
-                            "Game loaded successfully! Resuming operation...",
-                        );
-                    }
-                    Err(e) => {
-                        error!("Failed to load game: {}", e);
-                        play_tactical_sound("radio", "Load failed!");
-                        game_state.game_phase = GamePhase::MainMenu;
+                load_events.send(LoadGameEvent);
+            }
+        }
+        GamePhase::ModsMenu => {
+            // The main menu redraws itself every frame, so swap it out here
+            if !menu_query.iter().any(|(_, mods_screen, ..)| mods_screen) {
+                for (entity, ..) in menu_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+                create_mods_menu_ui(&mut commands, mod_registry());
+            }
+
+            if input.just_pressed(KeyCode::Escape) {
+                game_state.game_phase = GamePhase::MainMenu;
+            }
+        }
+        GamePhase::AccessibilityMenu => {
+            if input.just_pressed(KeyCode::Escape) {
+                game_state.game_phase = GamePhase::MainMenu;
+            } else if let Some(changed) = display_option_input(option, &mut config_profiles) {
+                // Display options belong to the config profile, not the player's
+                match changed {
+                    Ok(()) => {
+                        if let Err(e) = config_profiles.save() {
+                            warn!("Failed to save config profiles: {}", e);
+                        }
                     }
+                    Err(e) => warn!("Can't change display setting: {}", e),
+                }
+            } else if let Some(changed) = accessibility_option_input(option, &accessibility) {
+                *accessibility = changed.clone();
+                if let Some(profile) = profiles.active.as_mut() {
+                    profile.settings.accessibility = changed;
+                    profiles.save_active();
+                }
+            } else if let Some(changed) = profiles
+                .active
+                .as_ref()
+                .and_then(|profile| game_option_input(option, &profile.settings))
+            {
+                // Volume and tutorial highlights follow the profile on their own
+                campaign.progress.difficulty_level = changed.difficulty_level;
+                if let Some(profile) = profiles.active.as_mut() {
+                    profile.settings = changed;
+                    profiles.save_active();
+                }
+            }
+
+            // Redrawn only when an option changes
+            let showing = menu_query
+                .iter()
+                .any(|(_, _, accessibility_screen, ..)| accessibility_screen);
+            if !showing
+                || accessibility.is_changed()
+                || config_profiles.is_changed()
+                || profiles.is_changed()
+            {
+                for (entity, ..) in menu_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+                let game_settings = profiles
+                    .active
+                    .as_ref()
+                    .map(|profile| profile.settings.clone())
+                    .unwrap_or_default();
+                create_accessibility_menu_ui(
+                    &mut commands,
+                    &accessibility,
+                    config_profiles.active(),
+                    &game_settings,
+                );
+            }
+        }
+        GamePhase::TelemetryMenu => {
+            // Recording events changes Telemetry constantly, so redraw only on input
+            let mut redraw = !menu_query
+                .iter()
+                .any(|(_, _, _, telemetry_screen, _)| telemetry_screen);
+            if input.just_pressed(KeyCode::Escape) {
+                game_state.game_phase = GamePhase::MainMenu;
+            } else if option == Some(1) {
+                if let Some(profile) = profiles.active.as_mut() {
+                    profile.settings.telemetry = !profile.settings.telemetry;
+                    telemetry.enabled = profile.settings.telemetry;
+                    profiles.save_active();
+                    redraw = true;
+                }
+            } else if option == Some(2) {
+                telemetry.clear();
+                redraw = true;
+            }
+
+            if redraw {
+                for (entity, ..) in menu_query.iter() {
+                    commands.entity(entity).despawn_recursive();
                 }
+                create_telemetry_menu_ui(&mut commands, &telemetry);
+            }
+        }
+        GamePhase::LeaderboardMenu => {
+            if input.just_pressed(KeyCode::Escape) {
+                game_state.game_phase = GamePhase::MainMenu;
+            } else if option == Some(1) {
+                leaderboards.view_next_mission(&auth_session);
+            } else if option == Some(2) {
+                leaderboards.view_next_difficulty(&auth_session);
+            } else if option == Some(3) {
+                let key = leaderboards.viewing.clone();
+                leaderboards.refresh(&auth_session, key);
+            } else if option == Some(4) {
+                let today = DailyChallenge::today(&city_map).date;
+                leaderboards.toggle_daily(&auth_session, today);
+            }
+
+            // Redrawn when a board arrives or the view changes
+            let showing = menu_query
+                .iter()
+                .any(|(.., leaderboard_screen)| leaderboard_screen);
+            if !showing || leaderboards.is_changed() {
+                for (entity, ..) in menu_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+                create_leaderboard_menu_ui(
+                    &mut commands,
+                    &leaderboards,
+                    &campaign,
+                    profiles.active.as_ref(),
+                );
             }
         }
         _ => {
             // Clean up any lingering menu UI when not in menu phases
-            for entity in menu_query.iter() {
+            for (entity, ..) in menu_query.iter() {
                 commands.entity(entity).despawn_recursive();
             }
         }
@@ -138,10 +487,19 @@ pub fn main_menu_system(
 
 // ==================== VICTORY/DEFEAT SYSTEM ====================
 
+const RETRY_KEY: KeyCode = KeyCode::R;
+
+#[allow(clippy::too_many_arguments)]
 pub fn victory_defeat_system(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
     campaign: Res<Campaign>,
+    political_state: Res<PoliticalState>,
+    score: Res<MissionScore>,
+    leaderboards: Res<Leaderboards>,
+    profiles: Res<ProfileManager>,
+    mut assists: ResMut<MissionAssists>,
+    balance: Res<BalanceConfig>,
     input: Res<Input<KeyCode>>,
     result_query: Query<Entity, Or<(With<VictoryScreen>, With<DefeatScreen>)>>,
 ) {
@@ -152,15 +510,28 @@ pub fn victory_defeat_system(
                 commands.entity(entity).despawn_recursive();
             }
 
+            // How politics ended it, or else how history did
+            let epilogue = political_state
+                .epilogue_key()
+                .map_or_else(|| tr("result.victory_history"), tr);
+
             // Create victory screen
-            create_victory_screen(&mut commands, &game_state, &campaign);
+            create_victory_screen(
+                &mut commands,
+                &game_state,
+                &campaign,
+                &epilogue,
+                &score,
+                &leaderboards,
+                profiles.active.as_ref(),
+            );
 
             // Handle input to continue
             if input.just_pressed(KeyCode::Space) || input.just_pressed(KeyCode::Return) {
                 advance_campaign_or_end(&mut game_state, &campaign);
             } else if input.just_pressed(KeyCode::Escape) {
                 game_state.game_phase = GamePhase::MainMenu;
-                play_tactical_sound("radio", "Returning to main menu...");
+                play_tactical_sound("radio", &tr("radio.main_menu"));
             }
         }
         GamePhase::Defeat => {
@@ -169,17 +540,34 @@ pub fn victory_defeat_system(
                 commands.entity(entity).despawn_recursive();
             }
 
+            // Repeated defeats on a mission open up assists for the retry
+            let mission = campaign.progress.current_mission.clone();
+            let assist_lines = if assists_offered(&campaign.progress, &mission, &balance.assists) {
+                for assist in MissionAssist::ALL {
+                    if input.just_pressed(assist.key()) {
+                        assists.toggle(&mission, assist);
+                    }
+                }
+                assist_option_lines(&assists, &campaign.progress, &balance.scoring)
+            } else {
+                Vec::new()
+            };
+
             // Create defeat screen
-            create_defeat_screen(&mut commands, &game_state, &campaign);
+            create_defeat_screen(&mut commands, &game_state, &campaign, &assist_lines);
 
             // Handle input to continue
             if input.just_pressed(KeyCode::Space) || input.just_pressed(KeyCode::Return) {
-                // On defeat, return to main menu or retry
-                game_state.game_phase = GamePhase::MainMenu;
-                play_tactical_sound("radio", "Operation terminated. Regrouping...");
+                // On defeat, back to the campaign map to regroup
+                game_state.game_phase = GamePhase::CampaignMap;
+                play_tactical_sound("radio", &tr("radio.regrouping"));
+            } else if input.just_pressed(RETRY_KEY) {
+                // Straight back into the same mission's briefing
+                game_state.game_phase = GamePhase::MissionBriefing;
+                play_tactical_sound("radio", &tr("radio.retrying"));
             } else if input.just_pressed(KeyCode::Escape) {
                 game_state.game_phase = GamePhase::MainMenu;
-                play_tactical_sound("radio", "Returning to main menu...");
+                play_tactical_sound("radio", &tr("radio.main_menu"));
             }
         }
         _ => {
@@ -196,6 +584,10 @@ pub fn victory_defeat_system(
 fn create_mission_briefing_ui(
     commands: &mut Commands,
     mission_config: &crate::campaign::MissionConfig,
+    best: Option<&MissionRecord>,
+    challenges: &ChallengeSettings,
+    scoring: &ScoringBalance,
+    daily_lines: &[String],
 ) {
     // Main briefing container
     commands
@@ -221,7 +613,10 @@ fn create_mission_briefing_ui(
             // Mission title
             parent.spawn((
                 TextBundle::from_section(
-                    format!("🎯 MISSION: {}", mission_config.name.to_uppercase()),
+                    tr_args(
+                        "briefing.title",
+                        &[("name", &mission_config.name.to_uppercase())],
+                    ),
                     TextStyle {
                         font_size: 48.0,
                         color: Color::rgb(1.0, 0.8, 0.0),
@@ -243,7 +638,7 @@ fn create_mission_briefing_ui(
             // Mission description
             parent.spawn((
                 TextBundle::from_section(
-                    mission_config.description,
+                    mission_config.description.clone(),
                     TextStyle {
                         font_size: 24.0,
                         color: Color::WHITE,
@@ -269,7 +664,7 @@ fn create_mission_briefing_ui(
             // Objectives section
             parent.spawn((
                 TextBundle::from_section(
-                    "📋 OBJECTIVES:",
+                    tr("briefing.objectives"),
                     TextStyle {
                         font_size: 28.0,
                         color: Color::rgb(0.3, 0.8, 1.0),
@@ -281,20 +676,7 @@ fn create_mission_briefing_ui(
 
             // List objectives
             for (i, objective) in mission_config.objectives.iter().enumerate() {
-                let objective_text = match objective {
-                    crate::campaign::MissionObjective::SurviveTime(time) => {
-                        format!("{}. Survive for {:.0} seconds", i + 1, time)
-                    }
-                    crate::campaign::MissionObjective::DefendTarget(target) => {
-                        format!("{}. Protect {}", i + 1, target)
-                    }
-                    crate::campaign::MissionObjective::EliminateEnemies(count) => {
-                        format!("{}. Eliminate {} enemy units", i + 1, count)
-                    }
-                    crate::campaign::MissionObjective::ControlArea(area) => {
-                        format!("{}. Control {}", i + 1, area)
-                    }
-                };
+                let objective_text = format!("{}. {}", i + 1, objective.description());
 
                 parent.spawn(
                     TextBundle::from_section(
@@ -323,7 +705,10 @@ fn create_mission_briefing_ui(
                 });
 
                 parent.spawn(TextBundle::from_section(
-                    format!("⏰ Time Limit: {time_limit:.0} seconds"),
+                    tr_args(
+                        "briefing.time_limit",
+                        &[("seconds", &format!("{time_limit:.0}"))],
+                    ),
                     TextStyle {
                         font_size: 18.0,
                         color: Color::rgb(1.0, 0.5, 0.5),
@@ -332,6 +717,113 @@ fn create_mission_briefing_ui(
                 ));
             }
 
+            // Best result so far
+            if let Some(best) = best {
+                let medal = best
+                    .medal
+                    .map_or_else(|| tr("score.no_medal"), |medal| medal.label());
+                parent.spawn(
+                    TextBundle::from_section(
+                        tr_args(
+                            "briefing.best_result",
+                            &[("medal", &medal), ("score", &best.score)],
+                        ),
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb(1.0, 0.8, 0.0),
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    }),
+                );
+            }
+
+            // Challenge modifiers
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("briefing.challenges"),
+                    TextStyle {
+                        font_size: 22.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+            for modifier in ChallengeModifier::ALL {
+                let active = challenges.is_active(modifier);
+                let state = if active {
+                    tr("briefing.challenge_on")
+                } else {
+                    tr("briefing.challenge_off")
+                };
+                let multiplier = format!("{:.2}", modifier.multiplier(scoring));
+                parent.spawn(
+                    TextBundle::from_section(
+                        tr_args(
+                            "briefing.challenge_option",
+                            &[
+                                ("key", &format!("{:?}", modifier.key())),
+                                ("name", &modifier.label()),
+                                ("multiplier", &multiplier),
+                                ("state", &state),
+                            ],
+                        ),
+                        TextStyle {
+                            font_size: 18.0,
+                            color: if active {
+                                Color::rgb(1.0, 0.6, 0.2)
+                            } else {
+                                Color::rgb(0.7, 0.7, 0.7)
+                            },
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(6.0)),
+                        ..default()
+                    }),
+                );
+            }
+
+            // Today's daily challenge
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("daily.heading"),
+                    TextStyle {
+                        font_size: 22.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+            for line in daily_lines {
+                parent.spawn(
+                    TextBundle::from_section(
+                        line.clone(),
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::rgb(0.7, 0.7, 0.7),
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(6.0)),
+                        ..default()
+                    }),
+                );
+            }
+
             // Instructions
             parent.spawn(NodeBundle {
                 style: Style {
@@ -342,7 +834,7 @@ fn create_mission_briefing_ui(
             });
 
             parent.spawn(TextBundle::from_section(
-                "Press SPACE or ENTER to begin mission",
+                tr("briefing.start_hint"),
                 TextStyle {
                     font_size: 22.0,
                     color: Color::rgb(0.0, 1.0, 0.0),
@@ -352,7 +844,7 @@ fn create_mission_briefing_ui(
         });
 }
 
-fn create_main_menu_ui(commands: &mut Commands) {
+fn create_main_menu_ui(commands: &mut Commands, active_profile: Option<&str>) {
     commands
         .spawn((
             NodeBundle {
@@ -374,9 +866,9 @@ fn create_main_menu_ui(commands: &mut Commands) {
         ))
         .with_children(|parent| {
             // Game title
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    "🏛️ BATTLE OF CULIACÁN 🏛️\nEl Culiacanazo RTS",
+                    tr("menu.title"),
                     TextStyle {
                         font_size: 56.0,
                         color: Color::rgb(1.0, 0.8, 0.0),
@@ -387,12 +879,32 @@ fn create_main_menu_ui(commands: &mut Commands) {
                     margin: UiRect::bottom(Val::Px(50.0)),
                     ..default()
                 }),
-            );
+                MenuTitle {
+                    page: GamePhase::MainMenu,
+                },
+            ));
+
+            if let Some(username) = active_profile {
+                parent.spawn(
+                    TextBundle::from_section(
+                        tr_args("menu.commander", &[("name", &username)]),
+                        TextStyle {
+                            font_size: 22.0,
+                            color: Color::rgb(0.3, 0.8, 1.0),
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    }),
+                );
+            }
 
             // Menu options
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    "1. New Campaign",
+                    tr("menu.new_campaign"),
                     TextStyle {
                         font_size: 32.0,
                         color: Color::WHITE,
@@ -403,19 +915,23 @@ fn create_main_menu_ui(commands: &mut Commands) {
                     margin: UiRect::all(Val::Px(10.0)),
                     ..default()
                 }),
-            );
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 0,
+                },
+            ));
 
             let load_color = if has_save_file() {
                 Color::WHITE
             } else {
                 Color::rgb(0.5, 0.5, 0.5)
             };
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
                     if has_save_file() {
-                        "2. Load Campaign"
+                        tr("menu.load_campaign")
                     } else {
-                        "2. Load Campaign (No Save Found)"
+                        tr("menu.load_campaign_no_save")
                     },
                     TextStyle {
                         font_size: 32.0,
@@ -427,11 +943,15 @@ fn create_main_menu_ui(commands: &mut Commands) {
                     margin: UiRect::all(Val::Px(10.0)),
                     ..default()
                 }),
-            );
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 1,
+                },
+            ));
 
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    "3. Save Current Game",
+                    tr("menu.save_game"),
                     TextStyle {
                         font_size: 32.0,
                         color: Color::WHITE,
@@ -442,94 +962,241 @@ fn create_main_menu_ui(commands: &mut Commands) {
                     margin: UiRect::all(Val::Px(10.0)),
                     ..default()
                 }),
-            );
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 2,
+                },
+            ));
 
-            // Instructions
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    "Press 1-3 to select option",
+                    tr("menu.switch_profile"),
                     TextStyle {
-                        font_size: 20.0,
-                        color: Color::rgb(0.7, 0.7, 0.7),
+                        font_size: 32.0,
+                        color: Color::WHITE,
                         ..default()
                     },
                 )
                 .with_style(Style {
-                    margin: UiRect::top(Val::Px(40.0)),
+                    margin: UiRect::all(Val::Px(10.0)),
                     ..default()
                 }),
-            );
-        });
-}
-
-fn create_save_menu_ui(commands: &mut Commands) {
-    commands
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    left: Val::Px(0.0),
-                    top: Val::Px(0.0),
-                    width: Val::Percent(100.0),
-                    height: Val::Percent(100.0),
-                    flex_direction: FlexDirection::Column,
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 3,
                 },
-                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.9)),
-                ..default()
-            },
-            SaveLoadMenu,
-        ))
-        .with_children(|parent| {
-            parent.spawn(
+            ));
+
+            parent.spawn((
                 TextBundle::from_section(
-                    "💾 SAVE GAME",
+                    tr("menu.mods"),
                     TextStyle {
-                        font_size: 48.0,
-                        color: Color::rgb(0.3, 0.8, 1.0),
+                        font_size: 32.0,
+                        color: Color::WHITE,
                         ..default()
                     },
                 )
                 .with_style(Style {
-                    margin: UiRect::bottom(Val::Px(40.0)),
+                    margin: UiRect::all(Val::Px(10.0)),
                     ..default()
                 }),
-            );
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 4,
+                },
+            ));
 
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    "1. Save Slot 1",
+                    tr_args(
+                        "menu.language",
+                        &[("language", &language_name(&current_language()))],
+                    ),
                     TextStyle {
-                        font_size: 28.0,
+                        font_size: 32.0,
                         color: Color::WHITE,
                         ..default()
                     },
                 )
                 .with_style(Style {
-                    margin: UiRect::all(Val::Px(15.0)),
+                    margin: UiRect::all(Val::Px(10.0)),
                     ..default()
                 }),
-            );
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 5,
+                },
+            ));
 
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    "Press 1 to save, ESC to cancel",
+                    tr("menu.accessibility"),
                     TextStyle {
-                        font_size: 18.0,
-                        color: Color::rgb(0.7, 0.7, 0.7),
+                        font_size: 32.0,
+                        color: Color::WHITE,
                         ..default()
                     },
                 )
                 .with_style(Style {
-                    margin: UiRect::top(Val::Px(30.0)),
+                    margin: UiRect::all(Val::Px(10.0)),
                     ..default()
                 }),
-            );
-        });
-}
-
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 6,
+                },
+            ));
+
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("menu.tutorial"),
+                    TextStyle {
+                        font_size: 32.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }),
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 7,
+                },
+            ));
+
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("menu.telemetry"),
+                    TextStyle {
+                        font_size: 32.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }),
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 8,
+                },
+            ));
+
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("menu.leaderboards"),
+                    TextStyle {
+                        font_size: 32.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }),
+                MenuItem {
+                    page: GamePhase::MainMenu,
+                    index: 9,
+                },
+            ));
+
+            // Instructions
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("menu.hint"),
+                    TextStyle {
+                        font_size: 20.0,
+                        color: Color::rgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(40.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
+fn create_save_menu_ui(commands: &mut Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.9)),
+                ..default()
+            },
+            SaveLoadMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("save_menu.title"),
+                    TextStyle {
+                        font_size: 48.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(40.0)),
+                    ..default()
+                }),
+                MenuTitle {
+                    page: GamePhase::SaveMenu,
+                },
+            ));
+
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("save_menu.slot"),
+                    TextStyle {
+                        font_size: 28.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(15.0)),
+                    ..default()
+                }),
+                MenuItem {
+                    page: GamePhase::SaveMenu,
+                    index: 0,
+                },
+            ));
+
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("save_menu.hint"),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
 fn create_load_menu_ui(commands: &mut Commands) {
     commands
         .spawn((
@@ -551,9 +1218,521 @@ fn create_load_menu_ui(commands: &mut Commands) {
             SaveLoadMenu,
         ))
         .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("load_menu.title"),
+                    TextStyle {
+                        font_size: 48.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(40.0)),
+                    ..default()
+                }),
+                MenuTitle {
+                    page: GamePhase::LoadMenu,
+                },
+            ));
+
+            let load_text = if has_save_file() {
+                tr("load_menu.slot_available")
+            } else {
+                tr("load_menu.slot_empty")
+            };
+
+            let load_color = if has_save_file() {
+                Color::WHITE
+            } else {
+                Color::rgb(0.5, 0.5, 0.5)
+            };
+
+            parent.spawn((
+                TextBundle::from_section(
+                    load_text,
+                    TextStyle {
+                        font_size: 28.0,
+                        color: load_color,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(15.0)),
+                    ..default()
+                }),
+                MenuItem {
+                    page: GamePhase::LoadMenu,
+                    index: 0,
+                },
+            ));
+
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("load_menu.hint"),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
+const MAX_LISTED_CONFLICTS: usize = 8;
+
+fn create_mods_menu_ui(commands: &mut Commands, registry: &ModRegistry) {
+    let dim = Color::rgb(0.7, 0.7, 0.7);
+    let mut lines: Vec<(String, f32, Color)> = Vec::new();
+
+    if registry.active.is_empty() {
+        lines.push((
+            tr_args("mods_menu.none_active", &[("dir", &MODS_DIR)]),
+            24.0,
+            dim,
+        ));
+    }
+    for (index, pack) in registry.active.iter().enumerate() {
+        let manifest = &pack.manifest;
+        lines.push((
+            tr_args(
+                "mods_menu.pack",
+                &[
+                    ("index", &(index + 1)),
+                    ("name", &manifest.name),
+                    ("version", &manifest.version),
+                    ("id", &manifest.id),
+                    ("load_order", &manifest.load_order),
+                ],
+            ),
+            26.0,
+            Color::WHITE,
+        ));
+        lines.push((pack.content_summary(), 18.0, dim));
+    }
+
+    if !registry.disabled.is_empty() {
+        let names: Vec<&str> = registry
+            .disabled
+            .iter()
+            .map(|manifest| manifest.name.as_str())
+            .collect();
+        let names = names.join(", ");
+        lines.push((
+            tr_args("mods_menu.disabled", &[("names", &names)]),
+            20.0,
+            dim,
+        ));
+    }
+
+    if !registry.conflicts.is_empty() {
+        lines.push((
+            tr_args(
+                "mods_menu.conflicts",
+                &[("count", &registry.conflicts.len())],
+            ),
+            24.0,
+            Color::rgb(1.0, 0.7, 0.2),
+        ));
+        for conflict in registry.conflicts.iter().take(MAX_LISTED_CONFLICTS) {
+            lines.push((
+                format!("{}: {}", conflict.key, conflict.mods.join(" → ")),
+                18.0,
+                Color::rgb(1.0, 0.85, 0.6),
+            ));
+        }
+        if registry.conflicts.len() > MAX_LISTED_CONFLICTS {
+            lines.push((
+                tr_args(
+                    "mods_menu.more_conflicts",
+                    &[("count", &(registry.conflicts.len() - MAX_LISTED_CONFLICTS))],
+                ),
+                18.0,
+                dim,
+            ));
+        }
+    }
+
+    for error in &registry.errors {
+        lines.push((format!("❌ {}", error), 18.0, Color::rgb(1.0, 0.4, 0.4)));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.9)),
+                ..default()
+            },
+            SaveLoadMenu,
+            ModsMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("mods_menu.title"),
+                    TextStyle {
+                        font_size: 48.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+
+            for (text, font_size, color) in lines {
+                parent.spawn(
+                    TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font_size,
+                            color,
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    }),
+                );
+            }
+
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("mods_menu.hint"),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: dim,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
+// Options 1-6 on the accessibility page; returns the settings after the change
+// Display options follow the accessibility ones, as the game has no other
+// settings page
+fn display_option_input(
+    option: Option<usize>,
+    profiles: &mut ConfigProfiles,
+) -> Option<Result<(), String>> {
+    let result = match option? {
+        7 => profiles.cycle_quality().map(|_| ()),
+        8 => profiles.cycle_fps_cap().map(|_| ()),
+        9 => {
+            let pause = !profiles.active().background_pause;
+            profiles.set("background_pause", if pause { "on" } else { "off" })
+        }
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn accessibility_option_input(
+    option: Option<usize>,
+    current: &AccessibilitySettings,
+) -> Option<AccessibilitySettings> {
+    let mut settings = current.clone();
+    match option? {
+        1 => settings.text_scale = settings.next_text_scale(),
+        2 => {
+            settings.modifier_keys = match settings.modifier_keys {
+                ModifierKeyMode::Hold => ModifierKeyMode::Toggle,
+                ModifierKeyMode::Toggle => ModifierKeyMode::Hold,
+            }
+        }
+        3 => settings.reduced_motion = !settings.reduced_motion,
+        4 => settings.extended_decision_timers = !settings.extended_decision_timers,
+        5 => settings.slow_mode = !settings.slow_mode,
+        6 => settings.narration = settings.narration.next(),
+        _ => return None,
+    }
+    Some(settings)
+}
+
+// Options 10-12 are the player's game settings, kept in the profile. Only the
+// first has a number key; the rest are reached with the arrow keys
+fn game_option_input(option: Option<usize>, current: &ProfileSettings) -> Option<ProfileSettings> {
+    let mut settings = current.clone();
+    match option? {
+        10 => {
+            let all = DifficultyLevel::ALL;
+            let index = all
+                .iter()
+                .position(|level| *level == settings.difficulty_level)
+                .unwrap_or(0);
+            settings.difficulty_level = all[(index + 1) % all.len()];
+        }
+        11 => settings.master_volume = next_volume(settings.master_volume),
+        12 => settings.show_tutorial_hints = !settings.show_tutorial_hints,
+        _ => return None,
+    }
+    Some(settings)
+}
+
+// Up a tenth at a time, back to silent after full volume
+fn next_volume(volume: f32) -> f32 {
+    let step = (volume * VOLUME_STEPS).round() + 1.0;
+    if step > VOLUME_STEPS {
+        0.0
+    } else {
+        step / VOLUME_STEPS
+    }
+}
+
+fn create_accessibility_menu_ui(
+    commands: &mut Commands,
+    settings: &AccessibilitySettings,
+    display: &ConfigProfile,
+    game: &ProfileSettings,
+) {
+    let on_off = |enabled: bool| {
+        if enabled {
+            tr("accessibility_menu.on")
+        } else {
+            tr("accessibility_menu.off")
+        }
+    };
+    let modifier_keys = match settings.modifier_keys {
+        ModifierKeyMode::Hold => tr("accessibility_menu.hold"),
+        ModifierKeyMode::Toggle => tr("accessibility_menu.toggle"),
+    };
+    let narration = match settings.narration {
+        NarrationMode::Off => tr("accessibility_menu.off"),
+        NarrationMode::Speech => tr("accessibility_menu.speech"),
+        NarrationMode::Clips => tr("accessibility_menu.clips"),
+    };
+    let percent = format!("{:.0}", settings.text_scale * 100.0);
+    let quality = tr(&format!(
+        "accessibility_menu.{}",
+        display.graphics.quality.name()
+    ));
+    let fps_cap = match display.fps_cap {
+        0 => tr("accessibility_menu.uncapped"),
+        cap => cap.to_string(),
+    };
+    let volume = format!("{:.0}", game.master_volume * 100.0);
+    let options = [
+        tr_args("accessibility_menu.text_scale", &[("percent", &percent)]),
+        tr_args(
+            "accessibility_menu.modifier_keys",
+            &[("mode", &modifier_keys)],
+        ),
+        tr_args(
+            "accessibility_menu.reduced_motion",
+            &[("state", &on_off(settings.reduced_motion))],
+        ),
+        tr_args(
+            "accessibility_menu.extended_timers",
+            &[("state", &on_off(settings.extended_decision_timers))],
+        ),
+        tr_args(
+            "accessibility_menu.slow_mode",
+            &[("state", &on_off(settings.slow_mode))],
+        ),
+        tr_args("accessibility_menu.narration", &[("mode", &narration)]),
+        tr_args("accessibility_menu.graphics", &[("quality", &quality)]),
+        tr_args("accessibility_menu.fps_cap", &[("fps", &fps_cap)]),
+        tr_args(
+            "accessibility_menu.background_pause",
+            &[("state", &on_off(display.background_pause))],
+        ),
+        tr_args(
+            "accessibility_menu.difficulty",
+            &[("difficulty", &difficulty_label(game.difficulty_level))],
+        ),
+        tr_args("accessibility_menu.volume", &[("percent", &volume)]),
+        tr_args(
+            "accessibility_menu.tutorial_hints",
+            &[("state", &on_off(game.show_tutorial_hints))],
+        ),
+    ];
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.9)),
+                ..default()
+            },
+            SaveLoadMenu,
+            AccessibilityMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("accessibility_menu.title"),
+                    TextStyle {
+                        font_size: 48.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                }),
+                MenuTitle {
+                    page: GamePhase::AccessibilityMenu,
+                },
+            ));
+
+            for (index, option) in options.into_iter().enumerate() {
+                parent.spawn((
+                    TextBundle::from_section(
+                        option,
+                        TextStyle {
+                            font_size: 28.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    }),
+                    MenuItem {
+                        page: GamePhase::AccessibilityMenu,
+                        index,
+                    },
+                ));
+            }
+
             parent.spawn(
                 TextBundle::from_section(
-                    "📂 LOAD GAME",
+                    tr("accessibility_menu.hint"),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.7, 0.7, 0.7),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
+// Opt-in switch, a way to wipe the log, and a summary of what's in it
+fn create_telemetry_menu_ui(commands: &mut Commands, telemetry: &Telemetry) {
+    let state = if telemetry.enabled {
+        tr("accessibility_menu.on")
+    } else {
+        tr("accessibility_menu.off")
+    };
+    let options = [
+        tr_args("telemetry_menu.share", &[("state", &state)]),
+        tr("telemetry_menu.clear"),
+    ];
+
+    let summary = TelemetrySummary::from_records(&telemetry.read_log());
+    let mut details = vec![
+        tr(if telemetry.uploads_enabled() {
+            "telemetry_menu.upload_on"
+        } else {
+            "telemetry_menu.upload_off"
+        }),
+        tr_args(
+            "telemetry_menu.missions",
+            &[
+                ("missions", &summary.missions.to_string()),
+                ("victories", &summary.victories.to_string()),
+                ("sessions", &summary.sessions.to_string()),
+            ],
+        ),
+    ];
+    if summary.missions > 0 {
+        details.push(tr_args(
+            "telemetry_menu.difficulty",
+            &[
+                ("intensity", &format!("{:.1}", summary.average_intensity)),
+                (
+                    "adaptive",
+                    &format!("{:.0}", summary.adaptive_share * 100.0),
+                ),
+            ],
+        ));
+    }
+    for (phase, seconds) in &summary.phase_seconds {
+        details.push(tr_args(
+            "telemetry_menu.phase_time",
+            &[
+                ("phase", &tr(&format!("phase.{}", phase))),
+                ("seconds", &format!("{:.0}", seconds)),
+            ],
+        ));
+    }
+    if !summary.features.is_empty() {
+        let features: Vec<String> = summary
+            .features
+            .iter()
+            .map(|(feature, sessions)| format!("{} ({})", feature, sessions))
+            .collect();
+        details.push(tr_args(
+            "telemetry_menu.features",
+            &[("features", &features.join(", "))],
+        ));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.9)),
+                ..default()
+            },
+            SaveLoadMenu,
+            TelemetryMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("telemetry_menu.title"),
                     TextStyle {
                         font_size: 48.0,
                         color: Color::rgb(0.3, 0.8, 1.0),
@@ -561,41 +1740,216 @@ fn create_load_menu_ui(commands: &mut Commands) {
                     },
                 )
                 .with_style(Style {
-                    margin: UiRect::bottom(Val::Px(40.0)),
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                }),
+                MenuTitle {
+                    page: GamePhase::TelemetryMenu,
+                },
+            ));
+
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("telemetry_menu.about"),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.0)),
                     ..default()
                 }),
             );
 
-            let load_text = if has_save_file() {
-                "1. Load Slot 1 (Available)"
-            } else {
-                "1. Load Slot 1 (Empty)"
-            };
+            for (index, option) in options.into_iter().enumerate() {
+                parent.spawn((
+                    TextBundle::from_section(
+                        option,
+                        TextStyle {
+                            font_size: 28.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    }),
+                    MenuItem {
+                        page: GamePhase::TelemetryMenu,
+                        index,
+                    },
+                ));
+            }
 
-            let load_color = if has_save_file() {
-                Color::WHITE
-            } else {
-                Color::rgb(0.5, 0.5, 0.5)
-            };
+            for line in details {
+                parent.spawn(
+                    TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font_size: 20.0,
+                            color: Color::rgb(0.85, 0.85, 0.85),
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    }),
+                );
+            }
 
             parent.spawn(
                 TextBundle::from_section(
-                    load_text,
+                    tr("telemetry_menu.hint"),
                     TextStyle {
-                        font_size: 28.0,
-                        color: load_color,
+                        font_size: 18.0,
+                        color: Color::rgb(0.7, 0.7, 0.7),
                         ..default()
                     },
                 )
                 .with_style(Style {
-                    margin: UiRect::all(Val::Px(15.0)),
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+        });
+}
+
+fn create_leaderboard_menu_ui(
+    commands: &mut Commands,
+    leaderboards: &Leaderboards,
+    campaign: &Campaign,
+    profile: Option<&PlayerProfile>,
+) {
+    let viewing = &leaderboards.viewing;
+    let (mission, difficulty) = leaderboards.mission_board();
+    let mission = MissionConfig::get_mission_config(mission);
+    let daily_option = match viewing {
+        BoardKey::Daily(_) => tr("leaderboard_menu.mission_boards"),
+        BoardKey::Mission { .. } => tr("leaderboard_menu.daily"),
+    };
+    let options = [
+        tr_args("leaderboard_menu.mission", &[("name", &mission.name)]),
+        tr_args(
+            "leaderboard_menu.difficulty",
+            &[("difficulty", &difficulty_label(difficulty))],
+        ),
+        tr("leaderboard_menu.refresh"),
+        daily_option,
+    ];
+    let mut lines = Vec::new();
+    if let BoardKey::Daily(date) = viewing {
+        lines.push(tr_args(
+            "leaderboard_menu.daily_board",
+            &[("date", &date.to_string())],
+        ));
+    }
+    lines.extend(board_lines(
+        leaderboards,
+        viewing,
+        &campaign.progress,
+        profile,
+        crate::leaderboard::LEADERBOARD_TOP_ENTRIES as usize,
+    ));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.9)),
+                ..default()
+            },
+            SaveLoadMenu,
+            LeaderboardMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    tr("leaderboard_menu.title"),
+                    TextStyle {
+                        font_size: 48.0,
+                        color: Color::rgb(0.3, 0.8, 1.0),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                }),
+                MenuTitle {
+                    page: GamePhase::LeaderboardMenu,
+                },
+            ));
+
+            parent.spawn(
+                TextBundle::from_section(
+                    tr("leaderboard_menu.about"),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::rgb(0.8, 0.8, 0.8),
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.0)),
                     ..default()
                 }),
             );
 
+            for (index, option) in options.into_iter().enumerate() {
+                parent.spawn((
+                    TextBundle::from_section(
+                        option,
+                        TextStyle {
+                            font_size: 28.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    }),
+                    MenuItem {
+                        page: GamePhase::LeaderboardMenu,
+                        index,
+                    },
+                ));
+            }
+
+            for line in lines {
+                parent.spawn(
+                    TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font_size: 20.0,
+                            color: Color::rgb(0.85, 0.85, 0.85),
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        margin: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    }),
+                );
+            }
+
             parent.spawn(
                 TextBundle::from_section(
-                    "Press 1 to load, ESC to cancel",
+                    tr("leaderboard_menu.hint"),
                     TextStyle {
                         font_size: 18.0,
                         color: Color::rgb(0.7, 0.7, 0.7),
@@ -610,7 +1964,15 @@ fn create_load_menu_ui(commands: &mut Commands) {
         });
 }
 
-fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campaign: &Campaign) {
+fn create_victory_screen(
+    commands: &mut Commands,
+    game_state: &GameState,
+    campaign: &Campaign,
+    epilogue: &str,
+    score: &MissionScore,
+    leaderboards: &Leaderboards,
+    profile: Option<&PlayerProfile>,
+) {
     commands.spawn((
         NodeBundle {
             style: Style {
@@ -632,7 +1994,7 @@ fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campai
         // Victory title
         parent.spawn((
             TextBundle::from_section(
-                "🏆 ¡VICTORIA! 🏆",
+                tr("result.victory_title"),
                 TextStyle {
                     font_size: 64.0,
                     color: Color::rgb(1.0, 0.8, 0.0),
@@ -642,10 +2004,14 @@ fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campai
             MissionResultText,
         ));
 
-        // Mission name
-        let mission_config = MissionConfig::get_mission_config(&campaign.progress.current_mission);
+        // Mission name; once scored, the campaign has already moved on to the next one
+        let mission_id = score
+            .mission
+            .as_ref()
+            .unwrap_or(&campaign.progress.current_mission);
+        let mission_config = MissionConfig::get_mission_config(mission_id);
         parent.spawn(TextBundle::from_section(
-            format!("Mission: {} Complete", mission_config.name),
+            tr_args("result.mission_complete", &[("name", &mission_config.name)]),
             TextStyle {
                 font_size: 32.0,
                 color: Color::WHITE,
@@ -658,7 +2024,7 @@ fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campai
 
         // Historical context
         parent.spawn(TextBundle::from_section(
-            "Historical Outcome: The Sinaloa Cartel successfully\npressured the Mexican government to release Ovidio Guzmán.\nThis event became known as 'El Culiacanazo' or 'Black Thursday'.",
+            epilogue,
             TextStyle {
                 font_size: 20.0,
                 color: Color::rgb(0.9, 0.9, 0.9),
@@ -672,7 +2038,7 @@ fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campai
 
         // Objectives summary
         parent.spawn(TextBundle::from_section(
-            "📊 MISSION OBJECTIVES:",
+            tr("result.objectives"),
             TextStyle {
                 font_size: 24.0,
                 color: Color::rgb(0.3, 0.8, 1.0),
@@ -695,12 +2061,47 @@ fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campai
             ..default()
         }));
 
+        // Score breakdown and medal
+        if score.mission.is_some() {
+            parent.spawn(TextBundle::from_section(
+                score.lines().join("\n"),
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::top(Val::Px(20.0)),
+                ..default()
+            }));
+
+            let medal = score
+                .medal
+                .map_or_else(|| tr("score.no_medal"), |medal| medal.label());
+            parent.spawn(TextBundle::from_section(
+                tr_args("result.medal", &[("medal", &medal)]),
+                TextStyle {
+                    font_size: 28.0,
+                    color: Color::rgb(1.0, 0.8, 0.0),
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::top(Val::Px(20.0)),
+                ..default()
+            }));
+        }
+
         // Score summary
+        let total = if score.mission.is_some() {
+            score.breakdown.total
+        } else {
+            game_state.cartel_score
+        };
         parent.spawn(TextBundle::from_section(
-            format!("Final Score: {} | Time: {:.1}s",
-                game_state.cartel_score,
-                game_state.mission_timer
-            ),
+            tr_args("result.victory_score", &[
+                ("score", &total),
+                ("seconds", &format!("{:.1}", game_state.mission_timer)),
+            ]),
             TextStyle {
                 font_size: 22.0,
                 color: Color::rgb(0.0, 1.0, 0.0),
@@ -711,9 +2112,26 @@ fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campai
             ..default()
         }));
 
+        // Where the score stands on its leaderboard
+        if let Some(key) = BoardKey::for_score(score, &campaign.progress) {
+            let mut lines = vec![tr("leaderboard.title")];
+            lines.extend(board_lines(leaderboards, &key, &campaign.progress, profile, 5));
+            parent.spawn(TextBundle::from_section(
+                lines.join("\n"),
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::rgb(0.3, 0.8, 1.0),
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::top(Val::Px(20.0)),
+                ..default()
+            }));
+        }
+
         // Continue instructions
         parent.spawn(TextBundle::from_section(
-            "Press SPACE to continue | ESC for main menu",
+            tr("result.victory_hint"),
             TextStyle {
                 font_size: 18.0,
                 color: Color::rgb(0.7, 0.7, 0.7),
@@ -726,7 +2144,12 @@ fn create_victory_screen(commands: &mut Commands, game_state: &GameState, campai
     });
 }
 
-fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaign: &Campaign) {
+fn create_defeat_screen(
+    commands: &mut Commands,
+    game_state: &GameState,
+    campaign: &Campaign,
+    assist_lines: &[(String, bool)],
+) {
     commands.spawn((
         NodeBundle {
             style: Style {
@@ -748,7 +2171,7 @@ fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaig
         // Defeat title
         parent.spawn((
             TextBundle::from_section(
-                "💀 MISIÓN FALLIDA 💀",
+                tr("result.defeat_title"),
                 TextStyle {
                     font_size: 64.0,
                     color: Color::rgb(1.0, 0.3, 0.3),
@@ -761,7 +2184,7 @@ fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaig
         // Mission name
         let mission_config = MissionConfig::get_mission_config(&campaign.progress.current_mission);
         parent.spawn(TextBundle::from_section(
-            format!("Mission: {} Failed", mission_config.name),
+            tr_args("result.mission_failed", &[("name", &mission_config.name)]),
             TextStyle {
                 font_size: 32.0,
                 color: Color::WHITE,
@@ -774,7 +2197,7 @@ fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaig
 
         // Failure context
         parent.spawn(TextBundle::from_section(
-            "The government forces succeeded in their objective.\nHowever, this simulation helps understand the complex\ndynamics that led to the actual historical outcome.",
+            tr("result.defeat_context"),
             TextStyle {
                 font_size: 20.0,
                 color: Color::rgb(0.9, 0.9, 0.9),
@@ -788,7 +2211,7 @@ fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaig
 
         // Objectives summary
         parent.spawn(TextBundle::from_section(
-            "📊 MISSION OBJECTIVES:",
+            tr("result.objectives"),
             TextStyle {
                 font_size: 24.0,
                 color: Color::rgb(0.3, 0.8, 1.0),
@@ -813,10 +2236,10 @@ fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaig
 
         // Score summary
         parent.spawn(TextBundle::from_section(
-            format!("Final Score: {} | Survived: {:.1}s",
-                game_state.cartel_score,
-                game_state.mission_timer
-            ),
+            tr_args("result.defeat_score", &[
+                ("score", &game_state.cartel_score),
+                ("seconds", &format!("{:.1}", game_state.mission_timer)),
+            ]),
             TextStyle {
                 font_size: 22.0,
                 color: Color::rgb(1.0, 0.5, 0.5),
@@ -827,9 +2250,30 @@ fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaig
             ..default()
         }));
 
+        // Retry assists, once offered
+        for (index, (line, selected)) in assist_lines.iter().enumerate() {
+            parent.spawn(TextBundle::from_section(
+                line.clone(),
+                TextStyle {
+                    font_size: if index == 0 { 20.0 } else { 18.0 },
+                    color: if index == 0 {
+                        Color::rgb(0.3, 0.8, 1.0)
+                    } else if *selected {
+                        Color::rgb(0.4, 1.0, 0.5)
+                    } else {
+                        Color::rgb(0.7, 0.7, 0.7)
+                    },
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::top(Val::Px(if index == 0 { 24.0 } else { 6.0 })),
+                ..default()
+            }));
+        }
+
         // Continue instructions
         parent.spawn(TextBundle::from_section(
-            "Press SPACE to try again | ESC for main menu",
+            tr("result.defeat_hint"),
             TextStyle {
                 font_size: 18.0,
                 color: Color::rgb(0.7, 0.7, 0.7),
@@ -843,10 +2287,9 @@ fn create_defeat_screen(commands: &mut Commands, game_state: &GameState, campaig
 }
 
 fn advance_campaign_or_end(game_state: &mut GameState, _campaign: &Campaign) {
-    // For now, return to main menu after victory
-    // In the future, this could advance to the next mission
-    game_state.game_phase = GamePhase::MainMenu;
-    play_tactical_sound("radio", "Mission complete. Ready for next operation...");
+    // The campaign map shows the win and opens the next mission
+    game_state.game_phase = GamePhase::CampaignMap;
+    play_tactical_sound("radio", &tr("radio.mission_complete"));
 
     // Reset mission timer for potential replay
     game_state.mission_timer = 0.0;
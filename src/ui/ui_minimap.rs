@@ -1,67 +1,88 @@
 use crate::components::*;
+use crate::ui::ui_theme::UiTheme;
 use bevy::prelude::*;
-
-// Type aliases to reduce complexity
-type MiniMapIconQuery<'a> = Query<
-    'a,
-    'a,
-    (Entity, &'a mut Style, &'a MiniMapIcon, &'static Transform),
-    (With<MiniMapIcon>, Changed<Transform>),
->;
+use std::collections::HashMap;
 
 // ==================== MINIMAP SYSTEM ====================
 
 pub fn minimap_system(
     mut commands: Commands,
-    unit_query: Query<(&Transform, &Unit), Without<MiniMapIcon>>,
-    minimap_icon_query: MiniMapIconQuery,
+    mut unit_icons: Local<HashMap<Entity, Entity>>, // Unit -> minimap icon
+    unit_query: Query<(Entity, &Transform, &Unit)>,
+    mut icon_query: Query<(&mut Style, &mut BackgroundColor), With<MiniMapIcon>>,
     minimap_query: Query<Entity, With<MiniMap>>,
+    theme: Res<UiTheme>,
 ) {
-    if let Ok(minimap_entity) = minimap_query.get_single() {
-        // Clear old icons
-        // Clear only icons for units die niet meer bestaan
-        for (entity, _, icon, _) in minimap_icon_query.iter() {
-            if !unit_query.iter().any(|(_, u)| u.health > 0.0 && u.faction == icon.faction) {
-                commands.entity(entity).despawn();
+    let Ok(minimap_entity) = minimap_query.get_single() else {
+        return;
+    };
+
+    // Remove icons for units that died or no longer exist
+    unit_icons.retain(|unit_entity, icon_entity| {
+        let alive = unit_query
+            .get(*unit_entity)
+            .is_ok_and(|(_, _, unit)| unit.health > 0.0);
+        if !alive {
+            if let Some(icon_commands) = commands.get_entity(*icon_entity) {
+                icon_commands.despawn_recursive();
             }
         }
+        alive
+    });
 
-        // Create new icons for all living units
-        for (transform, unit) in unit_query.iter() {
-            if unit.health <= 0.0 {
-                continue;
-            }
+    for (unit_entity, transform, unit) in unit_query.iter() {
+        if unit.health <= 0.0 {
+            continue;
+        }
 
-            // Scale world position to minimap coordinates (200x150 minimap)
-            let minimap_x = (transform.translation.x / 1000.0) * 100.0 + 100.0; // Center at 100
-            let minimap_y = (transform.translation.y / 750.0) * 75.0 + 75.0; // Center at 75
+        // Scale world position to minimap coordinates (200x150 minimap)
+        let minimap_x = (transform.translation.x / 1000.0) * 100.0 + 100.0; // Center at 100
+        let minimap_y = (transform.translation.y / 750.0) * 75.0 + 75.0; // Center at 75
 
-            let icon_color = match unit.faction {
-                Faction::Cartel => Color::RED,
-                Faction::Military => Color::GREEN,
-                _ => Color::WHITE,
-            };
+        let icon_color = theme.faction_color(&unit.faction);
+        let icon_size = Val::Px(theme.minimap_icon_size());
 
-            commands.entity(minimap_entity).with_children(|parent| {
-                parent.spawn((
-                    NodeBundle {
-                        style: Style {
-                            position_type: PositionType::Absolute,
-                            left: Val::Px(minimap_x),
-                            top: Val::Px(minimap_y),
-                            width: Val::Px(4.0),
-                            height: Val::Px(4.0),
-                            ..default()
-                        },
-                        background_color: BackgroundColor(icon_color),
+        // Existing icons are moved in place; only write when something changed
+        // so the UI layout isn't recomputed for idle units
+        if let Some(icon) = unit_icons.get(&unit_entity) {
+            if let Ok((mut style, mut background)) = icon_query.get_mut(*icon) {
+                if style.left != Val::Px(minimap_x) || style.top != Val::Px(minimap_y) {
+                    style.left = Val::Px(minimap_x);
+                    style.top = Val::Px(minimap_y);
+                }
+                if background.0 != icon_color {
+                    background.0 = icon_color;
+                }
+                if style.width != icon_size {
+                    style.width = icon_size;
+                    style.height = icon_size;
+                }
+                continue;
+            }
+        }
+
+        // New unit (or the minimap was rebuilt): spawn its icon once
+        let icon = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(minimap_x),
+                        top: Val::Px(minimap_y),
+                        width: icon_size,
+                        height: icon_size,
                         ..default()
                     },
-                    MiniMapIcon {
-                        unit_type: unit.unit_type.clone(),
-                        faction: unit.faction.clone(),
-                    },
-                ));
-            });
-        }
+                    background_color: BackgroundColor(icon_color),
+                    ..default()
+                },
+                MiniMapIcon {
+                    unit_type: unit.unit_type.clone(),
+                    faction: unit.faction.clone(),
+                },
+            ))
+            .id();
+        commands.entity(minimap_entity).add_child(icon);
+        unit_icons.insert(unit_entity, icon);
     }
 }
@@ -0,0 +1,480 @@
+use crate::accessibility::FormationModifier;
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::map::CityMap;
+use crate::ui::ui_selection::{
+    find_enemy_at_position, formation_slots, FormationDrag, HOLD_AREA_LEASH_RADIUS,
+};
+use crate::ui::ui_theme::UiTheme;
+use crate::utils::EntityPool;
+use bevy::prelude::*;
+
+// ==================== ORDER FEEDBACK ====================
+
+// Right-click orders were only confirmed by radio text. Each order now drops a
+// short marker where it lands - an arrow for moves, a crosshair for attacks -
+// and while a formation modifier (Ctrl/Alt) is held, dots trace the route each
+// selected unit would take to the cursor.
+
+const MARKER_LIFETIME: f32 = 0.8;
+const MARKER_Z: f32 = 2.0;
+const PREVIEW_Z: f32 = -8.0; // Just above decals, under units
+const PREVIEW_DOT_SPACING: f32 = 18.0;
+const PREVIEW_REFRESH_SECONDS: f32 = 0.25; // Units move, so routes go stale
+const PREVIEW_MAX_DOTS: usize = 400;
+const GHOST_MAX_SPRITES: usize = 64;
+
+pub struct OrderFeedbackPlugin;
+
+impl Plugin for OrderFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<OrderIssuedEvent>()
+            .init_resource::<PathPreview>()
+            .init_resource::<FormationGhosts>()
+            .add_systems(
+                Update,
+                (
+                    order_marker_spawn_system,
+                    order_marker_animation_system,
+                    path_preview_system,
+                    formation_ghost_system,
+                )
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderKind {
+    Move,
+    Attack,
+    Hold,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct OrderIssuedEvent {
+    pub kind: OrderKind,
+    pub position: Vec3,
+}
+
+#[derive(Component)]
+pub struct OrderMarker {
+    kind: OrderKind,
+    origin: Vec3,
+    age: f32,
+}
+
+#[derive(Component)]
+struct OrderMarkerPart;
+
+// Pooled flat sprites for previews that are redrawn as a whole
+struct PreviewSprites {
+    pool: EntityPool,
+    active: Vec<Entity>,
+}
+
+impl PreviewSprites {
+    fn new(max_size: usize) -> Self {
+        Self {
+            pool: EntityPool::new(max_size),
+            active: Vec::new(),
+        }
+    }
+
+    fn clear(&mut self, commands: &mut Commands) {
+        for sprite in self.active.drain(..) {
+            if let Some(mut sprite_commands) = commands.get_entity(sprite) {
+                sprite_commands.insert(Visibility::Hidden);
+            }
+            self.pool.release(sprite);
+        }
+    }
+
+    // False once the pool is exhausted
+    fn place(&mut self, commands: &mut Commands, sprite: Sprite, transform: Transform) -> bool {
+        let Some(entity) = self.pool.acquire(commands, |commands| {
+            commands.spawn(SpriteBundle::default()).id()
+        }) else {
+            return false;
+        };
+        commands
+            .entity(entity)
+            .insert((sprite, transform, Visibility::Inherited));
+        self.active.push(entity);
+        true
+    }
+}
+
+#[derive(Resource)]
+pub struct PathPreview {
+    dots: PreviewSprites,
+    refresh: Timer,
+    last_cursor: Option<Vec3>,
+}
+
+impl Default for PathPreview {
+    fn default() -> Self {
+        Self {
+            dots: PreviewSprites::new(PREVIEW_MAX_DOTS),
+            refresh: Timer::from_seconds(PREVIEW_REFRESH_SECONDS, TimerMode::Repeating),
+            last_cursor: None,
+        }
+    }
+}
+
+impl PathPreview {
+    fn clear(&mut self, commands: &mut Commands) {
+        self.dots.clear(commands);
+        self.last_cursor = None;
+    }
+}
+
+// Where each selected unit will stand, shown while a right-click drag sets
+// the formation's facing
+#[derive(Resource)]
+pub struct FormationGhosts {
+    sprites: PreviewSprites,
+}
+
+impl Default for FormationGhosts {
+    fn default() -> Self {
+        Self {
+            sprites: PreviewSprites::new(GHOST_MAX_SPRITES),
+        }
+    }
+}
+
+// ==================== ORDER MARKERS ====================
+
+fn order_marker_spawn_system(
+    mut commands: Commands,
+    mut order_events: EventReader<OrderIssuedEvent>,
+    theme: Res<UiTheme>,
+) {
+    for event in order_events.read() {
+        let colors = theme.colors();
+        let (color, parts) = match event.kind {
+            OrderKind::Move => (colors.move_order, move_arrow_parts()),
+            OrderKind::Attack => (colors.target, attack_crosshair_parts()),
+            OrderKind::Hold => (colors.move_order, hold_ring_parts()),
+        };
+
+        let origin = event.position.truncate().extend(MARKER_Z);
+        commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_translation(origin)),
+                OrderMarker {
+                    kind: event.kind,
+                    origin,
+                    age: 0.0,
+                },
+            ))
+            .with_children(|parent| {
+                for (size, offset, rotation) in parts {
+                    parent.spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color,
+                                custom_size: Some(size),
+                                ..default()
+                            },
+                            transform: Transform::from_translation(offset.extend(0.0))
+                                .with_rotation(Quat::from_rotation_z(rotation)),
+                            ..default()
+                        },
+                        OrderMarkerPart,
+                    ));
+                }
+            });
+    }
+}
+
+// Arrow pointing down at the destination, over a short ground dash
+fn move_arrow_parts() -> Vec<(Vec2, Vec2, f32)> {
+    let head = std::f32::consts::FRAC_PI_4;
+    vec![
+        (Vec2::new(4.0, 20.0), Vec2::new(0.0, 18.0), 0.0),
+        (Vec2::new(4.0, 12.0), Vec2::new(-4.0, 6.0), head),
+        (Vec2::new(4.0, 12.0), Vec2::new(4.0, 6.0), -head),
+        (Vec2::new(24.0, 3.0), Vec2::new(0.0, -4.0), 0.0),
+    ]
+}
+
+// Four ticks around the target, leaving its center clear
+fn attack_crosshair_parts() -> Vec<(Vec2, Vec2, f32)> {
+    [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y]
+        .into_iter()
+        .map(|direction| {
+            let size = if direction.x != 0.0 {
+                Vec2::new(12.0, 3.0)
+            } else {
+                Vec2::new(3.0, 12.0)
+            };
+            (size, direction * 16.0, 0.0)
+        })
+        .collect()
+}
+
+// Dashes tracing the leash, so the player sees how far the units will go
+fn hold_ring_parts() -> Vec<(Vec2, Vec2, f32)> {
+    let dashes = 16;
+    (0..dashes)
+        .map(|i| {
+            let angle = i as f32 / dashes as f32 * std::f32::consts::TAU;
+            (
+                Vec2::new(4.0, 18.0),
+                Vec2::from_angle(angle) * HOLD_AREA_LEASH_RADIUS,
+                angle,
+            )
+        })
+        .collect()
+}
+
+// Full strength for the first half, then fading out
+fn marker_alpha(age: f32) -> f32 {
+    let progress = (age / MARKER_LIFETIME).clamp(0.0, 1.0);
+    (1.0 - (progress * 2.0 - 1.0).max(0.0)).clamp(0.0, 1.0)
+}
+
+fn order_marker_animation_system(
+    mut commands: Commands,
+    mut marker_query: Query<(Entity, &mut OrderMarker, &mut Transform, &Children)>,
+    mut part_query: Query<&mut Sprite, With<OrderMarkerPart>>,
+    time: Res<Time>,
+) {
+    for (entity, mut marker, mut transform, children) in marker_query.iter_mut() {
+        marker.age += time.delta_seconds();
+        if marker.age >= MARKER_LIFETIME {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let progress = marker.age / MARKER_LIFETIME;
+        match marker.kind {
+            // Drops onto the spot and settles
+            OrderKind::Move => {
+                let drop = (1.0 - progress * 3.0).max(0.0);
+                transform.translation = marker.origin + Vec3::Y * 16.0 * drop * drop;
+            }
+            // The ring just fades; its size is the information
+            OrderKind::Hold => {}
+            // Closes in on the target while turning a quarter
+            OrderKind::Attack => {
+                transform.scale = Vec3::splat(1.0 + 0.6 * (1.0 - progress).powi(2));
+                transform.rotation = Quat::from_rotation_z(progress * std::f32::consts::FRAC_PI_2);
+            }
+        }
+
+        let alpha = marker_alpha(marker.age);
+        for &child in children.iter() {
+            if let Ok(mut sprite) = part_query.get_mut(child) {
+                sprite.color.set_a(alpha);
+            }
+        }
+    }
+}
+
+// ==================== PATH PREVIEW ====================
+
+#[allow(clippy::too_many_arguments)]
+fn path_preview_system(
+    mut commands: Commands,
+    mut preview: ResMut<PathPreview>,
+    (formation_modifier, formation_drag): (Res<FormationModifier>, Res<FormationDrag>),
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    unit_query: Query<(Entity, &Transform, &Unit, Option<&Selected>)>,
+    selected_query: Query<Entity, With<Selected>>,
+    city_map: Option<Res<CityMap>>,
+    theme: Res<UiTheme>,
+    time: Res<Time>,
+) {
+    let cursor = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+        .map(|world_pos| world_pos.extend(0.0));
+
+    let selected_units: Vec<Entity> = selected_query.iter().collect();
+    let (Some(cursor), Some(city_map)) = (cursor, city_map) else {
+        preview.clear(&mut commands);
+        return;
+    };
+    if !formation_modifier.is_active() || selected_units.is_empty() {
+        preview.clear(&mut commands);
+        return;
+    }
+
+    // Re-route when the cursor crosses into another tile or the routes age
+    let refresh_due = preview.refresh.tick(time.delta()).just_finished();
+    let cursor_moved = preview
+        .last_cursor
+        .is_none_or(|last| last.distance(cursor) > city_map.tile_size * 0.5);
+    if !refresh_due && !cursor_moved {
+        return;
+    }
+    preview.clear(&mut commands);
+    preview.last_cursor = Some(cursor);
+
+    // Same destinations the right-click would hand out, including a drag in progress
+    let colors = theme.colors();
+    let order_point = formation_drag.start.unwrap_or(cursor);
+    let facing = formation_drag.facing();
+    let target_enemy = facing
+        .is_none()
+        .then(|| find_enemy_at_position(order_point, &unit_query))
+        .flatten();
+    let (color, destinations): (Color, Vec<Vec3>) = match target_enemy {
+        Some(enemy) => {
+            let enemy_position = unit_query
+                .get(enemy)
+                .map(|(_, transform, _, _)| transform.translation)
+                .unwrap_or(order_point);
+            (colors.target, vec![enemy_position; selected_units.len()])
+        }
+        None => {
+            let formation = formation_modifier.formation();
+            let destinations =
+                formation_slots(&formation, order_point, facing, selected_units.len());
+            (colors.move_order, destinations)
+        }
+    };
+
+    for (unit_entity, destination) in selected_units.iter().zip(destinations) {
+        let Ok((_, transform, _, _)) = unit_query.get(*unit_entity) else {
+            continue;
+        };
+        let start = transform.translation;
+        let Some(waypoints) = city_map.find_path(start, destination) else {
+            continue;
+        };
+
+        let route: Vec<Vec3> = std::iter::once(start).chain(waypoints).collect();
+        for position in dots_along(&route, PREVIEW_DOT_SPACING) {
+            let sprite = Sprite {
+                color: color.with_a(0.7),
+                custom_size: Some(Vec2::splat(4.0)),
+                ..default()
+            };
+            let transform = Transform::from_translation(position.truncate().extend(PREVIEW_Z));
+            if !preview.dots.place(&mut commands, sprite, transform) {
+                return;
+            }
+        }
+    }
+}
+
+// ==================== FORMATION GHOSTS ====================
+
+fn formation_ghost_system(
+    mut commands: Commands,
+    mut ghosts: ResMut<FormationGhosts>,
+    formation_drag: Res<FormationDrag>,
+    formation_modifier: Res<FormationModifier>,
+    selected_query: Query<Entity, With<Selected>>,
+    theme: Res<UiTheme>,
+) {
+    // Redrawn every frame the drag changes, and once more to clear it
+    if !formation_drag.is_changed() && !formation_modifier.is_changed() {
+        return;
+    }
+    ghosts.sprites.clear(&mut commands);
+
+    let unit_count = selected_query.iter().count();
+    let (Some(start), Some(current), Some(facing)) = (
+        formation_drag.start,
+        formation_drag.current,
+        formation_drag.facing(),
+    ) else {
+        return;
+    };
+    if unit_count == 0 {
+        return;
+    }
+
+    let color = theme.colors().move_order;
+    let ghost_rotation = Quat::from_rotation_z(facing - std::f32::consts::FRAC_PI_2);
+
+    // The drag itself, from the order point towards the facing
+    let drag = (current - start).truncate();
+    let drag_line = Sprite {
+        color: color.with_a(0.5),
+        custom_size: Some(Vec2::new(drag.length(), 2.0)),
+        ..default()
+    };
+    let drag_transform =
+        Transform::from_translation((start.truncate() + drag * 0.5).extend(MARKER_Z))
+            .with_rotation(Quat::from_rotation_z(facing));
+    ghosts
+        .sprites
+        .place(&mut commands, drag_line, drag_transform);
+
+    // Wider than deep, so each ghost shows which way its unit will face
+    for slot in formation_slots(
+        &formation_modifier.formation(),
+        start,
+        Some(facing),
+        unit_count,
+    ) {
+        let ghost = Sprite {
+            color: color.with_a(0.35),
+            custom_size: Some(Vec2::new(22.0, 12.0)),
+            ..default()
+        };
+        let transform = Transform::from_translation(slot.truncate().extend(MARKER_Z))
+            .with_rotation(ghost_rotation);
+        if !ghosts.sprites.place(&mut commands, ghost, transform) {
+            return;
+        }
+    }
+}
+
+// Evenly spaced points along a polyline, skipping its start (the unit itself)
+fn dots_along(route: &[Vec3], spacing: f32) -> Vec<Vec3> {
+    let mut dots = Vec::new();
+    let mut carried = 0.0;
+    for segment in route.windows(2) {
+        let (from, to) = (segment[0], segment[1]);
+        let length = from.distance(to);
+        let mut travelled = spacing - carried;
+        while travelled <= length {
+            dots.push(from.lerp(to, travelled / length));
+            travelled += spacing;
+        }
+        carried = length - (travelled - spacing);
+    }
+    dots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dots_are_evenly_spaced_across_corners() {
+        let route = [
+            Vec3::ZERO,
+            Vec3::new(25.0, 0.0, 0.0),
+            Vec3::new(25.0, 35.0, 0.0),
+        ];
+        let dots = dots_along(&route, 10.0);
+
+        // 60 units of route, one dot every 10 after the start
+        assert_eq!(dots.len(), 6);
+        assert!(dots[2].distance(Vec3::new(25.0, 5.0, 0.0)) < 1e-4);
+        assert!(dots[5].distance(Vec3::new(25.0, 35.0, 0.0)) < 1e-4);
+        assert!(dots_along(&[Vec3::ZERO], 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_marker_fades_over_second_half() {
+        assert_eq!(marker_alpha(0.0), 1.0);
+        assert_eq!(marker_alpha(MARKER_LIFETIME * 0.5), 1.0);
+        assert!(marker_alpha(MARKER_LIFETIME * 0.75) < 1.0);
+        assert_eq!(marker_alpha(MARKER_LIFETIME), 0.0);
+    }
+}
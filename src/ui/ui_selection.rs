@@ -1,7 +1,16 @@
+use crate::accessibility::FormationModifier;
 use crate::components::*;
-use crate::utils::play_tactical_sound;
+use crate::localization::{tr, tr_args};
+use crate::profile::{ProfileManager, CUSTOM_FORMATION_SLOTS};
+use crate::ui::ui_orders::{OrderIssuedEvent, OrderKind};
+use crate::ui::ui_theme::UiTheme;
+use crate::utils::{
+    capture_formation_offsets, custom_formation_offset, find_optimal_formation_center,
+    play_tactical_sound, EntityPool,
+};
 use bevy::ecs::system::ParamSet;
 use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
 
 // Type aliases to reduce complexity
 type UnitSelectionQueries<'w, 's> = ParamSet<
@@ -13,17 +22,14 @@ type UnitSelectionQueries<'w, 's> = ParamSet<
     ),
 >;
 
-type SelectedUnitQuery<'a> =
-    Query<'a, 'a, (Entity, &'a Transform, &'a Selected), (With<Unit>, Changed<Selected>)>;
+type SelectedUnitsQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static Transform), (With<Unit>, With<Selected>)>;
 
-type SelectionIndicatorQuery<'a> =
-    Query<'a, 'a, (Entity, &'a mut Transform), (With<SelectionIndicator>, Without<Unit>)>;
-
-type TargetIndicatorQuery<'a> =
-    Query<'a, 'a, (Entity, &'a mut Transform), (With<TargetIndicator>, Without<Unit>)>;
+type NewlySelectedQuery<'a> = Query<'a, 'a, (Entity, &'a Selected), (With<Unit>, Added<Selected>)>;
 
 // ==================== UNIT SELECTION SYSTEM ====================
 
+#[allow(clippy::too_many_arguments)]
 pub fn unit_selection_system(
     mut commands: Commands,
     input: (Res<Input<MouseButton>>, Res<Input<KeyCode>>),
@@ -34,6 +40,9 @@ pub fn unit_selection_system(
     mut unit_queries: UnitSelectionQueries,
     mut movement_query: Query<&mut Movement>,
     selected_query: Query<Entity, With<Selected>>,
+    theme: Res<UiTheme>,
+    formation_modifier: Res<FormationModifier>,
+    (mut formation_drag, mut order_events): (ResMut<FormationDrag>, EventWriter<OrderIssuedEvent>),
 ) {
     let (mouse_button_input, keyboard_input) = input;
     let (windows, camera_query) = ui_queries;
@@ -80,7 +89,7 @@ pub fn unit_selection_system(
                 if let Some((entity, already_selected)) = closest_unit {
                     if !already_selected {
                         commands.entity(entity).insert(Selected {
-                            selection_color: Color::CYAN,
+                            selection_color: theme.colors().selection,
                         });
                     }
                 }
@@ -88,186 +97,439 @@ pub fn unit_selection_system(
         }
     }
 
-    // Handle right-click commands (movement or attack)
+    // Right-click orders: press marks the spot, dragging sets the facing and
+    // the order goes out on release
+    let cursor_world = camera_query
+        .get_single()
+        .ok()
+        .zip(window.cursor_position())
+        .and_then(|((camera, camera_transform), cursor_pos)| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+        .map(|world_pos| Vec3::new(world_pos.x, world_pos.y, 0.0));
+
     if mouse_button_input.just_pressed(MouseButton::Right) {
-        if let Ok((camera, camera_transform)) = camera_query.get_single() {
-            if let Some(cursor_pos) = window.cursor_position() {
-                if let Some(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-                    let target_pos = Vec3::new(world_pos.x, world_pos.y, 0.0);
-
-                    // Collect selected units
-                    let selected_units: Vec<Entity> = selected_query.iter().collect();
-
-                    if !selected_units.is_empty() {
-                        // Check if right-clicking on an enemy unit for attack command
-                        let target_enemy = find_enemy_at_position(target_pos, &unit_queries.p0());
-
-                        if let Some(enemy_entity) = target_enemy {
-                            // Attack command: assign enemy as target
-                            assign_attack_targets(
-                                &selected_units,
-                                enemy_entity,
-                                &mut unit_queries.p1(),
-                            );
-                            play_tactical_sound(
-                                "radio",
-                                &format!("{} units ordered to attack target", selected_units.len()),
-                            );
-                        } else {
-                            // Movement command: formation movement
-                            let formation_type = if keyboard_input.pressed(KeyCode::ControlLeft) {
-                                FormationType::Wedge
-                            } else if keyboard_input.pressed(KeyCode::AltLeft) {
-                                FormationType::Circle
-                            } else {
-                                FormationType::Line
-                            };
-
-                            assign_formation_positions(
-                                &selected_units,
-                                target_pos,
-                                formation_type.clone(),
-                                &mut movement_query,
-                            );
-                            play_tactical_sound(
-                                "movement",
-                                &format!(
-                                    "{} units moving in {:?} formation",
-                                    selected_units.len(),
-                                    formation_type
-                                ),
-                            );
-                        }
-                    }
-                }
-            }
+        formation_drag.start = cursor_world;
+        formation_drag.current = cursor_world;
+    }
+    if mouse_button_input.pressed(MouseButton::Right) && cursor_world.is_some() {
+        formation_drag.current = cursor_world;
+    }
+    if !mouse_button_input.just_released(MouseButton::Right) {
+        return;
+    }
+
+    let facing = formation_drag.facing();
+    let Some(target_pos) = formation_drag.start.take() else {
+        return;
+    };
+    formation_drag.current = None;
+
+    // Collect selected units
+    let selected_units: Vec<Entity> = selected_query.iter().collect();
+    if selected_units.is_empty() {
+        return;
+    }
+
+    // A new order releases units from any area they were holding or anyone
+    // they were guarding
+    for &unit_entity in &selected_units {
+        commands
+            .entity(unit_entity)
+            .remove::<HoldArea>()
+            .remove::<Bodyguard>();
+    }
+
+    // Check if right-clicking on an enemy unit for attack command; a drag
+    // always means a move
+    let target_enemy = facing
+        .is_none()
+        .then(|| find_enemy_at_position(target_pos, &unit_queries.p0()))
+        .flatten();
+
+    if let Some(enemy_entity) = target_enemy {
+        if let Ok((_, enemy_transform, _, _)) = unit_queries.p0().get(enemy_entity) {
+            order_events.send(OrderIssuedEvent {
+                kind: OrderKind::Attack,
+                position: enemy_transform.translation,
+            });
+        }
+
+        // Attack command: assign enemy as target
+        assign_attack_targets(&selected_units, enemy_entity, &mut unit_queries.p1());
+        play_tactical_sound(
+            "radio",
+            &format!("{} units ordered to attack target", selected_units.len()),
+        );
+    } else {
+        // Movement command: formation from Ctrl/Alt, held or toggled
+        let formation_type = formation_modifier.formation();
+
+        assign_formation_positions(
+            &selected_units,
+            target_pos,
+            formation_type.clone(),
+            facing,
+            &mut movement_query,
+        );
+        order_events.send(OrderIssuedEvent {
+            kind: OrderKind::Move,
+            position: target_pos,
+        });
+        play_tactical_sound(
+            "movement",
+            &format!(
+                "{} units moving in {:?} formation",
+                selected_units.len(),
+                formation_type
+            ),
+        );
+    }
+}
+
+// ==================== FORMATION DRAG ====================
+
+const FORMATION_DRAG_THRESHOLD: f32 = 20.0; // Shorter drags count as a click
+
+// Right mouse button held down on the map: where the order will land and
+// which way the formation will face
+#[derive(Resource, Default)]
+pub struct FormationDrag {
+    pub start: Option<Vec3>,
+    pub current: Option<Vec3>,
+}
+
+impl FormationDrag {
+    // Angle of the drag on the map plane, once it is long enough to mean one
+    pub fn facing(&self) -> Option<f32> {
+        let drag = (self.current? - self.start?).truncate();
+        (drag.length() >= FORMATION_DRAG_THRESHOLD).then(|| drag.y.atan2(drag.x))
+    }
+}
+
+// ==================== HOLD AREA ORDER ====================
+
+pub const HOLD_AREA_LEASH_RADIUS: f32 = 150.0;
+
+// H anchors the selected units around the cursor (or where they stand, with
+// the cursor off the map) in the current formation. They fight anything that
+// enters the leash radius and return to their posts after; any move or attack
+// order cancels it.
+pub fn hold_area_order_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsometricCamera>>,
+    selected_query: SelectedUnitsQuery,
+    formation_modifier: Res<FormationModifier>,
+    mut order_events: EventWriter<OrderIssuedEvent>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::H) {
+        return;
+    }
+    let selected_units: Vec<(Entity, Vec3)> = selected_query
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation))
+        .collect();
+    if selected_units.is_empty() {
+        return;
+    }
+
+    let cursor_world = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, cursor_pos)
+        })
+        .map(|world_pos| Vec3::new(world_pos.x, world_pos.y, 0.0));
+    let center = cursor_world.unwrap_or_else(|| {
+        let positions: Vec<Vec3> = selected_units
+            .iter()
+            .map(|(_, position)| *position)
+            .collect();
+        find_optimal_formation_center(&positions)
+    });
+
+    let posts = formation_slots(
+        &formation_modifier.formation(),
+        center,
+        None,
+        selected_units.len(),
+    );
+    for ((unit_entity, _), post) in selected_units.iter().zip(posts) {
+        commands.entity(*unit_entity).insert(HoldArea {
+            center,
+            post,
+            leash_radius: HOLD_AREA_LEASH_RADIUS,
+        });
+    }
+
+    order_events.send(OrderIssuedEvent {
+        kind: OrderKind::Hold,
+        position: center,
+    });
+    play_tactical_sound(
+        "radio",
+        &format!("{} units holding the area", selected_units.len()),
+    );
+}
+
+// ==================== CUSTOM FORMATIONS ====================
+
+const FORMATION_SLOT_KEYS: [KeyCode; CUSTOM_FORMATION_SLOTS] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+// Shift+number saves how the selected units stand to that slot of the active
+// profile; the number alone uses it for the next move orders, and pressing it
+// again goes back to the line
+pub fn custom_formation_hotkey_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    selected_query: Query<&Transform, (With<Unit>, With<Selected>)>,
+    mut profile_manager: ResMut<ProfileManager>,
+    mut formation_modifier: ResMut<FormationModifier>,
+) {
+    let Some(slot) = FORMATION_SLOT_KEYS
+        .iter()
+        .position(|key| keyboard_input.just_pressed(*key))
+        .map(|index| index + 1)
+    else {
+        return;
+    };
+    let Some(profile) = profile_manager.active.as_mut() else {
+        warn!("Custom formations are stored in a profile, and none is active");
+        return;
+    };
+
+    if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        let positions: Vec<Vec3> = selected_query
+            .iter()
+            .map(|transform| transform.translation)
+            .collect();
+        if positions.len() < 2 {
+            play_tactical_sound("radio", &tr("radio.formation_needs_two"));
+            return;
         }
+
+        let Some(saved) =
+            profile.save_custom_formation(slot, capture_formation_offsets(&positions))
+        else {
+            return;
+        };
+        let (formation, name) = (saved.formation_type(), saved.name.clone());
+        profile_manager.save_active();
+        formation_modifier.set_custom(Some(formation));
+        play_tactical_sound(
+            "radio",
+            &tr_args(
+                "radio.formation_saved",
+                &[("name", &name), ("count", &positions.len())],
+            ),
+        );
+        return;
+    }
+
+    let Some(saved) = profile.custom_formation(slot) else {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.formation_empty_slot", &[("slot", &slot)]),
+        );
+        return;
+    };
+    let formation = saved.formation_type();
+    if formation_modifier.custom() == Some(&formation) {
+        formation_modifier.set_custom(None);
+        play_tactical_sound("radio", &tr("radio.formation_line"));
+    } else {
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.formation_next_moves", &[("name", &saved.name)]),
+        );
+        formation_modifier.set_custom(Some(formation));
     }
 }
 
+// ==================== INDICATOR POOL ====================
+
+// Indicators are parented to the unit they mark, so they follow it without
+// per-frame transform updates and are only touched when selection or targets change.
+#[derive(Resource, Default)]
+pub struct IndicatorPool {
+    selection_indicators: EntityPool,
+    target_indicators: EntityPool,
+    active_selection: HashMap<Entity, Entity>, // Selected unit -> indicator
+    active_targets: HashMap<Entity, Entity>,   // Targeted unit -> indicator
+}
+
+// Detach an indicator from its unit and hide it. Indicators despawned along
+// with their unit are skipped by the pool on the next acquire.
+fn release_indicator(commands: &mut Commands, pool: &mut EntityPool, indicator: Entity) {
+    if let Some(mut indicator_commands) = commands.get_entity(indicator) {
+        indicator_commands
+            .remove_parent()
+            .insert(Visibility::Hidden);
+    }
+    pool.release(indicator);
+}
+
 pub fn selection_indicator_system(
     mut commands: Commands,
-    selected_query: SelectedUnitQuery,
-    indicator_query: SelectionIndicatorQuery,
+    mut indicator_pool: ResMut<IndicatorPool>,
+    newly_selected: NewlySelectedQuery,
+    mut deselected: RemovedComponents<Selected>,
+    indicator_children: Query<&Children, With<SelectionIndicator>>,
+    mut indicator_parts: Query<(&mut Sprite, &SelectionIndicatorPart)>,
 ) {
-    // Remove old indicators
-    for (entity, _) in indicator_query.iter() {
-        commands.entity(entity).despawn();
+    let pool = &mut *indicator_pool;
+
+    for unit_entity in deselected.read() {
+        if let Some(indicator) = pool.active_selection.remove(&unit_entity) {
+            release_indicator(&mut commands, &mut pool.selection_indicators, indicator);
+        }
     }
 
-    // Create enhanced selection indicators for selected units
-    for (_, transform, selected) in selected_query.iter() {
-        // Outer selection ring (animated)
-        commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::rgba(
-                        selected.selection_color.r(),
-                        selected.selection_color.g(),
-                        selected.selection_color.b(),
-                        0.6,
-                    ),
-                    custom_size: Some(Vec2::new(48.0, 48.0)),
-                    ..default()
-                },
-                transform: Transform::from_translation(
-                    transform.translation + Vec3::new(0.0, 0.0, -0.1),
-                ),
-                ..default()
-            },
-            SelectionIndicator,
-        ));
+    for (unit_entity, selected) in newly_selected.iter() {
+        let Some(indicator) = pool
+            .selection_indicators
+            .acquire(&mut commands, |commands| {
+                spawn_selection_indicator(commands, selected.selection_color)
+            })
+        else {
+            continue;
+        };
 
-        // Inner selection ring (solid)
-        commands.spawn((
+        // Recolor in case this indicator was last used with a different selection color
+        if let Ok(children) = indicator_children.get(indicator) {
+            for &child in children.iter() {
+                if let Ok((mut sprite, part)) = indicator_parts.get_mut(child) {
+                    sprite.color = selected.selection_color.with_a(part.alpha);
+                }
+            }
+        }
+        commands.entity(indicator).insert(Visibility::Inherited);
+
+        commands.entity(unit_entity).add_child(indicator);
+        pool.active_selection.insert(unit_entity, indicator);
+    }
+}
+
+fn spawn_selection_indicator(commands: &mut Commands, selection_color: Color) -> Entity {
+    let part = |alpha: f32, size: Vec2, offset: Vec3| {
+        (
             SpriteBundle {
                 sprite: Sprite {
-                    color: Color::rgba(
-                        selected.selection_color.r(),
-                        selected.selection_color.g(),
-                        selected.selection_color.b(),
-                        0.8,
-                    ),
-                    custom_size: Some(Vec2::new(40.0, 2.0)), // Thin ring
+                    color: selection_color.with_a(alpha),
+                    custom_size: Some(size),
                     ..default()
                 },
-                transform: Transform::from_translation(
-                    transform.translation + Vec3::new(0.0, -25.0, 0.1),
-                ),
+                transform: Transform::from_translation(offset),
                 ..default()
             },
-            SelectionIndicator,
-        ));
-
-        // Selection corners for better visibility
-        for (x, y) in [(-15.0, 15.0), (15.0, 15.0), (-15.0, -15.0), (15.0, -15.0)] {
-            commands.spawn((
-                SpriteBundle {
-                    sprite: Sprite {
-                        color: selected.selection_color,
-                        custom_size: Some(Vec2::new(8.0, 8.0)),
-                        ..default()
-                    },
-                    transform: Transform::from_translation(
-                        transform.translation + Vec3::new(x, y, 0.2),
-                    ),
-                    ..default()
-                },
-                SelectionIndicator,
+            SelectionIndicatorPart { alpha },
+        )
+    };
+
+    commands
+        .spawn((SpatialBundle::default(), SelectionIndicator))
+        .with_children(|parent| {
+            // Outer selection ring (animated)
+            parent.spawn(part(0.6, Vec2::new(48.0, 48.0), Vec3::new(0.0, 0.0, -0.1)));
+
+            // Inner selection ring (solid)
+            parent.spawn(part(
+                0.8,
+                Vec2::new(40.0, 2.0), // Thin ring
+                Vec3::new(0.0, -25.0, 0.1),
             ));
-        }
-    }
+
+            // Selection corners for better visibility
+            for (x, y) in [(-15.0, 15.0), (15.0, 15.0), (-15.0, -15.0), (15.0, -15.0)] {
+                parent.spawn(part(1.0, Vec2::new(8.0, 8.0), Vec3::new(x, y, 0.2)));
+            }
+        })
+        .id()
 }
 
 pub fn target_indicator_system(
     mut commands: Commands,
-    unit_query: Query<(&Unit, &Transform)>,
-    target_indicator_query: TargetIndicatorQuery,
+    mut indicator_pool: ResMut<IndicatorPool>,
+    unit_query: Query<&Unit>,
+    changed_units: Query<(), Changed<Unit>>,
+    theme: Res<UiTheme>,
 ) {
-    // Remove old target indicators
-    for (entity, _) in target_indicator_query.iter() {
-        commands.entity(entity).despawn();
+    // Targets are stored on Unit, so nothing can have changed if no Unit did
+    if changed_units.is_empty() {
+        return;
     }
 
-    // Create target indicators for units with assigned targets
-    for (unit, _) in unit_query.iter() {
-        if let Some(target_entity) = unit.target {
-            // Find the target's position
-            if let Ok((_, target_transform)) = unit_query.get(target_entity) {
-                // Create a red crosshair indicator on the target
-                commands.spawn((
-                    SpriteBundle {
-                        sprite: Sprite {
-                            color: Color::rgb(1.0, 0.2, 0.2),
-                            custom_size: Some(Vec2::new(40.0, 4.0)),
-                            ..default()
-                        },
-                        transform: Transform::from_translation(
-                            target_transform.translation + Vec3::new(0.0, 0.0, 0.3),
-                        ),
-                        ..default()
-                    },
-                    TargetIndicator,
-                ));
-
-                // Vertical crosshair
-                commands.spawn((
-                    SpriteBundle {
-                        sprite: Sprite {
-                            color: Color::rgb(1.0, 0.2, 0.2),
-                            custom_size: Some(Vec2::new(4.0, 40.0)),
-                            ..default()
-                        },
-                        transform: Transform::from_translation(
-                            target_transform.translation + Vec3::new(0.0, 0.0, 0.3),
-                        ),
+    let pool = &mut *indicator_pool;
+
+    let targeted: HashSet<Entity> = unit_query
+        .iter()
+        .filter_map(|unit| unit.target)
+        .filter(|target| unit_query.contains(*target))
+        .collect();
+
+    // Free crosshairs on units nobody is targeting anymore
+    let stale_targets: Vec<Entity> = pool
+        .active_targets
+        .keys()
+        .filter(|target| !targeted.contains(target))
+        .copied()
+        .collect();
+
+    for target in stale_targets {
+        if let Some(indicator) = pool.active_targets.remove(&target) {
+            release_indicator(&mut commands, &mut pool.target_indicators, indicator);
+        }
+    }
+
+    // One crosshair per target, however many units are engaging it
+    for target in targeted {
+        if pool.active_targets.contains_key(&target) {
+            continue;
+        }
+
+        let Some(indicator) = pool.target_indicators.acquire(&mut commands, |commands| {
+            spawn_target_indicator(commands, theme.colors().target)
+        }) else {
+            continue;
+        };
+        commands.entity(indicator).insert(Visibility::Inherited);
+
+        commands.entity(target).add_child(indicator);
+        pool.active_targets.insert(target, indicator);
+    }
+}
+
+fn spawn_target_indicator(commands: &mut Commands, target_color: Color) -> Entity {
+    commands
+        .spawn((SpatialBundle::default(), TargetIndicator))
+        .with_children(|parent| {
+            // Crosshair on the target
+            for size in [Vec2::new(40.0, 4.0), Vec2::new(4.0, 40.0)] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: target_color,
+                        custom_size: Some(size),
                         ..default()
                     },
-                    TargetIndicator,
-                ));
+                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.3)),
+                    ..default()
+                });
             }
-        }
-    }
+        })
+        .id()
 }
 
 // ==================== HELPER FUNCTIONS ====================
@@ -276,72 +538,88 @@ fn assign_formation_positions(
     selected_units: &[Entity],
     target_center: Vec3,
     formation_type: FormationType,
+    facing: Option<f32>,
     movement_query: &mut Query<&mut Movement>,
 ) {
-    if selected_units.is_empty() {
-        return;
+    let slots = formation_slots(&formation_type, target_center, facing, selected_units.len());
+    for (&unit_entity, slot) in selected_units.iter().zip(slots) {
+        if let Ok(mut movement) = movement_query.get_mut(unit_entity) {
+            movement.target_position = Some(slot);
+        }
     }
+}
+
+// Destinations for unit_count units. Formations are laid out facing +y (the
+// wedge leader in front), so a facing angle turns +y onto that direction.
+pub(crate) fn formation_slots(
+    formation_type: &FormationType,
+    center: Vec3,
+    facing: Option<f32>,
+    unit_count: usize,
+) -> Vec<Vec3> {
+    let rotation = facing
+        .map(|angle| Quat::from_rotation_z(angle - std::f32::consts::FRAC_PI_2))
+        .unwrap_or(Quat::IDENTITY);
+    (0..unit_count)
+        .map(|i| center + rotation * formation_slot_offset(formation_type, i, unit_count))
+        .collect()
+}
 
-    let unit_count = selected_units.len();
+// Where the i-th of unit_count selected units stands relative to the order point
+fn formation_slot_offset(formation_type: &FormationType, i: usize, unit_count: usize) -> Vec3 {
     let spacing = 60.0; // Distance between units in formation
 
-    for (i, &unit_entity) in selected_units.iter().enumerate() {
-        if let Ok(mut movement) = movement_query.get_mut(unit_entity) {
-            let formation_offset = match formation_type {
-                FormationType::Line => {
-                    // Horizontal line formation
-                    let x_offset = (i as f32 - (unit_count as f32 - 1.0) / 2.0) * spacing;
-                    Vec3::new(x_offset, 0.0, 0.0)
-                }
-                FormationType::Circle => {
-                    // Circular formation
-                    let angle = (i as f32 / unit_count as f32) * 2.0 * std::f32::consts::PI;
-                    let radius =
-                        spacing * (unit_count as f32 / (2.0 * std::f32::consts::PI)).max(1.0);
-                    Vec3::new(angle.cos() * radius, angle.sin() * radius, 0.0)
-                }
-                FormationType::Wedge => {
-                    // V-shaped wedge formation
-                    if i == 0 {
-                        Vec3::ZERO // Leader at front
-                    } else {
-                        let side = if i % 2 == 1 { -1.0 } else { 1.0 };
-                        let row = i.div_ceil(2);
-                        Vec3::new(side * spacing * 0.7, -(row as f32) * spacing * 0.5, 0.0)
-                    }
-                }
-                FormationType::Flanking => {
-                    // Split formation for flanking
-                    let side = if i < unit_count / 2 { -1.0 } else { 1.0 };
-                    let pos_in_side = if i < unit_count / 2 {
-                        i
-                    } else {
-                        i - unit_count / 2
-                    };
-                    Vec3::new(
-                        side * spacing * 1.5,
-                        (pos_in_side as f32) * spacing * 0.5,
-                        0.0,
-                    )
-                }
-                FormationType::Overwatch => {
-                    // Supporting positions with good fields of fire
-                    let x_offset = (i as f32 - (unit_count as f32 - 1.0) / 2.0) * spacing * 1.2;
-                    Vec3::new(x_offset, spacing * 0.8, 0.0)
-                }
-                FormationType::Retreat => {
-                    // Staggered withdrawal formation
-                    let x_offset = (i as f32 - (unit_count as f32 - 1.0) / 2.0) * spacing * 0.8;
-                    Vec3::new(x_offset, -(i as f32 * spacing * 0.3), 0.0)
-                }
+    match formation_type {
+        FormationType::Line => {
+            // Horizontal line formation
+            let x_offset = (i as f32 - (unit_count as f32 - 1.0) / 2.0) * spacing;
+            Vec3::new(x_offset, 0.0, 0.0)
+        }
+        FormationType::Circle => {
+            // Circular formation
+            let angle = (i as f32 / unit_count as f32) * 2.0 * std::f32::consts::PI;
+            let radius = spacing * (unit_count as f32 / (2.0 * std::f32::consts::PI)).max(1.0);
+            Vec3::new(angle.cos() * radius, angle.sin() * radius, 0.0)
+        }
+        FormationType::Wedge => {
+            // V-shaped wedge formation
+            if i == 0 {
+                Vec3::ZERO // Leader at front
+            } else {
+                let side = if i % 2 == 1 { -1.0 } else { 1.0 };
+                let row = i.div_ceil(2);
+                Vec3::new(side * spacing * 0.7, -(row as f32) * spacing * 0.5, 0.0)
+            }
+        }
+        FormationType::Flanking => {
+            // Split formation for flanking
+            let side = if i < unit_count / 2 { -1.0 } else { 1.0 };
+            let pos_in_side = if i < unit_count / 2 {
+                i
+            } else {
+                i - unit_count / 2
             };
-
-            movement.target_position = Some(target_center + formation_offset);
+            Vec3::new(
+                side * spacing * 1.5,
+                (pos_in_side as f32) * spacing * 0.5,
+                0.0,
+            )
+        }
+        FormationType::Overwatch => {
+            // Supporting positions with good fields of fire
+            let x_offset = (i as f32 - (unit_count as f32 - 1.0) / 2.0) * spacing * 1.2;
+            Vec3::new(x_offset, spacing * 0.8, 0.0)
         }
+        FormationType::Retreat => {
+            // Staggered withdrawal formation
+            let x_offset = (i as f32 - (unit_count as f32 - 1.0) / 2.0) * spacing * 0.8;
+            Vec3::new(x_offset, -(i as f32 * spacing * 0.3), 0.0)
+        }
+        FormationType::Custom { offsets, .. } => custom_formation_offset(offsets, i),
     }
 }
 
-fn find_enemy_at_position(
+pub(crate) fn find_enemy_at_position(
     position: Vec3,
     unit_query: &Query<(Entity, &Transform, &Unit, Option<&Selected>)>,
 ) -> Option<Entity> {
@@ -377,3 +655,33 @@ fn assign_attack_targets(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formation_drag_turns_the_formation() {
+        let mut drag = FormationDrag {
+            start: Some(Vec3::ZERO),
+            current: Some(Vec3::new(5.0, 5.0, 0.0)),
+        };
+        assert_eq!(drag.facing(), None);
+
+        // Dragging east turns a line facing north into one running north-south
+        drag.current = Some(Vec3::new(100.0, 0.0, 0.0));
+        let slots = formation_slots(&FormationType::Line, Vec3::ZERO, drag.facing(), 3);
+        for slot in &slots {
+            assert!(slot.x.abs() < 1e-3);
+        }
+        assert!((slots[0].y - 60.0).abs() < 1e-3);
+
+        // The wedge's leader ends up on the side the drag points to
+        let wedge = formation_slots(&FormationType::Wedge, Vec3::ZERO, drag.facing(), 5);
+        let rearmost = wedge[1..]
+            .iter()
+            .map(|slot| slot.x)
+            .fold(f32::MIN, f32::max);
+        assert!(wedge[0].x > rearmost);
+    }
+}
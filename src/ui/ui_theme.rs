@@ -0,0 +1,368 @@
+use crate::components::*;
+use crate::profile::ProfileManager;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ==================== UI THEME ====================
+
+// Every color that tells factions or health apart comes from UiTheme, so the
+// colorblind palettes and high-contrast mode reach the minimap, health bars,
+// selection and target indicators and the ui_builders panels in one place.
+// Both settings live in the player's profile.
+
+const HIGH_CONTRAST_MIN_LIGHTNESS: f32 = 0.7; // Text lightness floor on black panels
+const MINIMAP_ICON_SIZE: f32 = 4.0;
+const HIGH_CONTRAST_MINIMAP_ICON_SIZE: f32 = 6.0;
+
+pub struct UiThemePlugin;
+
+impl Plugin for UiThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiTheme>().add_systems(
+            Update,
+            (
+                ui_theme_hotkey_system,
+                apply_ui_theme_settings_system,
+                retheme_world_system,
+                themed_background_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    Deuteranopia, // Red-green, also suits protanopia
+    Tritanopia,   // Blue-yellow
+}
+
+impl ColorPalette {
+    pub const ALL: [ColorPalette; 3] = [
+        ColorPalette::Standard,
+        ColorPalette::Deuteranopia,
+        ColorPalette::Tritanopia,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|palette| *palette == self);
+        Self::ALL[index.map_or(0, |index| (index + 1) % Self::ALL.len())]
+    }
+
+    pub fn colors(self) -> Palette {
+        match self {
+            ColorPalette::Standard => STANDARD_PALETTE,
+            ColorPalette::Deuteranopia => DEUTERANOPIA_PALETTE,
+            ColorPalette::Tritanopia => TRITANOPIA_PALETTE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    pub cartel: Color,
+    pub military: Color,
+    pub civilian: Color,
+    pub rival_cartel: Color,
+    pub police: Color,
+    pub health_high: Color,
+    pub health_mid: Color,
+    pub health_low: Color,
+    pub health_background: Color,
+    pub selection: Color,
+    pub target: Color,
+    pub move_order: Color,
+    pub objective_complete: Color,
+    pub objective_active: Color,
+}
+
+const STANDARD_PALETTE: Palette = Palette {
+    cartel: Color::RED,
+    military: Color::GREEN,
+    civilian: Color::WHITE,
+    rival_cartel: Color::rgb(0.6, 0.2, 0.8),
+    police: Color::rgb(0.2, 0.4, 1.0),
+    health_high: Color::rgb(0.2, 0.8, 0.2),
+    health_mid: Color::rgb(0.8, 0.8, 0.2),
+    health_low: Color::rgb(0.8, 0.2, 0.2),
+    health_background: Color::rgb(0.3, 0.1, 0.1),
+    selection: Color::CYAN,
+    target: Color::rgb(1.0, 0.2, 0.2),
+    move_order: Color::rgb(0.3, 0.9, 0.3),
+    objective_complete: Color::rgb(0.2, 0.8, 0.2),
+    objective_active: Color::rgb(0.8, 0.8, 0.2),
+};
+
+// Okabe-Ito colors: orange against blue, and health that steps through
+// lightness as well as hue
+const DEUTERANOPIA_PALETTE: Palette = Palette {
+    cartel: Color::rgb(0.9, 0.62, 0.0),
+    military: Color::rgb(0.0, 0.45, 0.7),
+    civilian: Color::WHITE,
+    rival_cartel: Color::rgb(0.8, 0.47, 0.65),
+    police: Color::rgb(0.34, 0.71, 0.91),
+    health_high: Color::rgb(0.34, 0.71, 0.91),
+    health_mid: Color::rgb(0.94, 0.89, 0.26),
+    health_low: Color::rgb(0.84, 0.37, 0.0),
+    health_background: Color::rgb(0.15, 0.15, 0.15),
+    selection: Color::WHITE,
+    target: Color::rgb(0.8, 0.47, 0.65),
+    move_order: Color::rgb(0.34, 0.71, 0.91),
+    objective_complete: Color::rgb(0.34, 0.71, 0.91),
+    objective_active: Color::rgb(0.94, 0.89, 0.26),
+};
+
+// Red against teal, avoiding the blue-yellow axis
+const TRITANOPIA_PALETTE: Palette = Palette {
+    cartel: Color::rgb(0.84, 0.15, 0.1),
+    military: Color::rgb(0.0, 0.62, 0.6),
+    civilian: Color::WHITE,
+    rival_cartel: Color::rgb(0.95, 0.6, 0.75),
+    police: Color::rgb(0.0, 0.35, 0.35),
+    health_high: Color::rgb(0.0, 0.62, 0.6),
+    health_mid: Color::rgb(0.95, 0.6, 0.75),
+    health_low: Color::rgb(0.84, 0.15, 0.1),
+    health_background: Color::rgb(0.15, 0.15, 0.15),
+    selection: Color::WHITE,
+    target: Color::rgb(1.0, 0.0, 1.0),
+    move_order: Color::rgb(0.0, 0.62, 0.6),
+    objective_complete: Color::rgb(0.0, 0.62, 0.6),
+    objective_active: Color::rgb(0.95, 0.6, 0.75),
+};
+
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct UiTheme {
+    pub palette: ColorPalette,
+    pub high_contrast: bool,
+}
+
+impl UiTheme {
+    pub fn colors(&self) -> Palette {
+        let mut colors = self.palette.colors();
+        if self.high_contrast {
+            colors.health_background = Color::BLACK;
+            colors.civilian = Color::WHITE;
+        }
+        colors
+    }
+
+    pub fn faction_color(&self, faction: &Faction) -> Color {
+        let colors = self.colors();
+        match faction {
+            Faction::Cartel => colors.cartel,
+            Faction::Military => colors.military,
+            Faction::Civilian => colors.civilian,
+            Faction::RivalCartel => colors.rival_cartel,
+            Faction::Police => colors.police,
+        }
+    }
+
+    pub fn health_color(&self, health_ratio: f32) -> Color {
+        let colors = self.colors();
+        if health_ratio > 0.6 {
+            colors.health_high
+        } else if health_ratio > 0.3 {
+            colors.health_mid
+        } else {
+            colors.health_low
+        }
+    }
+
+    pub fn objective_color(&self, completed: bool) -> Color {
+        let colors = self.colors();
+        if completed {
+            colors.objective_complete
+        } else {
+            colors.objective_active
+        }
+    }
+
+    // High contrast puts panels on solid black
+    pub fn panel_background(&self, base: Color) -> Color {
+        if self.high_contrast {
+            Color::BLACK
+        } else {
+            base
+        }
+    }
+
+    pub fn border_color(&self, base: Color) -> Color {
+        if self.high_contrast {
+            Color::WHITE
+        } else {
+            base
+        }
+    }
+
+    // Keeps the hue so colored lines still mean something, but never dim
+    pub fn text_color(&self, base: Color) -> Color {
+        if !self.high_contrast {
+            return base;
+        }
+        let mut color = base.as_hsla();
+        color.set_l(color.l().max(HIGH_CONTRAST_MIN_LIGHTNESS));
+        color.as_rgba()
+    }
+
+    pub fn minimap_icon_size(&self) -> f32 {
+        if self.high_contrast {
+            HIGH_CONTRAST_MINIMAP_ICON_SIZE
+        } else {
+            MINIMAP_ICON_SIZE
+        }
+    }
+}
+
+// Selection rings and target crosshairs, with whether each is a crosshair
+type ThemedIndicatorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Children, Has<TargetIndicator>),
+    Or<(With<SelectionIndicator>, With<TargetIndicator>)>,
+>;
+
+// Panels built by ui_builders remember their unthemed color here
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ThemedBackground(pub Color);
+
+// Copies the active profile's palette and contrast settings
+pub fn apply_ui_theme_settings_system(profiles: Res<ProfileManager>, mut theme: ResMut<UiTheme>) {
+    if !profiles.is_changed() {
+        return;
+    }
+    if let Some(profile) = profiles.active.as_ref() {
+        let settings = UiTheme {
+            palette: profile.settings.color_palette,
+            high_contrast: profile.settings.high_contrast,
+        };
+        // Only touch the resource when something differs, so themed UI isn't rebuilt
+        theme.set_if_neq(settings);
+    }
+}
+
+// F5: cycle color palettes, Shift+F5: toggle high contrast; both saved to the profile
+pub fn ui_theme_hotkey_system(
+    input: Res<Input<KeyCode>>,
+    mut profiles: ResMut<ProfileManager>,
+    mut theme: ResMut<UiTheme>,
+) {
+    if !input.just_pressed(KeyCode::F5) {
+        return;
+    }
+
+    if input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        theme.high_contrast = !theme.high_contrast;
+        info!(
+            "High contrast {}",
+            if theme.high_contrast { "on" } else { "off" }
+        );
+    } else {
+        theme.palette = theme.palette.next();
+        info!("Color palette: {:?}", theme.palette);
+    }
+
+    if let Some(profile) = profiles.active.as_mut() {
+        profile.settings.color_palette = theme.palette;
+        profile.settings.high_contrast = theme.high_contrast;
+        profiles.save_active();
+    }
+}
+
+// Recolors what was drawn before the theme changed; new health bars, icons and
+// indicators already pick up the current theme
+pub fn retheme_world_system(
+    theme: Res<UiTheme>,
+    units: Query<&Unit>,
+    mut health_bars: Query<(&mut Sprite, &HealthBar)>,
+    mut selections: Query<&mut Selected>,
+    indicators: ThemedIndicatorQuery,
+    mut indicator_parts: Query<(&mut Sprite, Option<&SelectionIndicatorPart>), Without<HealthBar>>,
+) {
+    if !theme.is_changed() || theme.is_added() {
+        return;
+    }
+    let colors = theme.colors();
+
+    for (mut sprite, health_bar) in health_bars.iter_mut() {
+        let Ok(unit) = units.get(health_bar.owner) else {
+            continue;
+        };
+        sprite.color = if is_health_bar_foreground(health_bar) {
+            theme.health_color(unit.health / unit.max_health)
+        } else {
+            colors.health_background
+        };
+    }
+
+    for mut selected in selections.iter_mut() {
+        selected.selection_color = colors.selection;
+    }
+
+    // Pooled indicators too, parked ones included
+    for (children, is_target) in indicators.iter() {
+        for &child in children.iter() {
+            let Ok((mut sprite, selection_part)) = indicator_parts.get_mut(child) else {
+                continue;
+            };
+            match selection_part {
+                Some(part) => sprite.color = colors.selection.with_a(part.alpha),
+                None if is_target => sprite.color = colors.target,
+                None => {}
+            }
+        }
+    }
+}
+
+pub fn themed_background_system(
+    theme: Res<UiTheme>,
+    mut panels: Query<(&mut BackgroundColor, &ThemedBackground)>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    for (mut background, themed) in panels.iter_mut() {
+        background.0 = theme.panel_background(themed.0);
+    }
+}
+
+// Health bars are a background/foreground pair from spawn_health_bar, at z 0.5 and 0.6
+pub fn is_health_bar_foreground(health_bar: &HealthBar) -> bool {
+    health_bar.offset.z > 0.55
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colorblind_palettes_avoid_red_green_factions() {
+        let theme = UiTheme {
+            palette: ColorPalette::Deuteranopia,
+            high_contrast: false,
+        };
+        let cartel = theme.faction_color(&Faction::Cartel);
+        let military = theme.faction_color(&Faction::Military);
+        // Orange against blue: the two differ most in the blue channel
+        assert!((cartel.b() - military.b()).abs() > 0.5);
+        assert_ne!(theme.health_color(1.0), theme.health_color(0.1));
+        assert_eq!(ColorPalette::Tritanopia.next(), ColorPalette::Standard);
+    }
+
+    #[test]
+    fn test_high_contrast_keeps_hue_and_brightens_text() {
+        let theme = UiTheme {
+            palette: ColorPalette::Standard,
+            high_contrast: true,
+        };
+        let dim_red = theme.text_color(Color::rgb(0.4, 0.0, 0.0)).as_hsla();
+        assert!(dim_red.l() >= HIGH_CONTRAST_MIN_LIGHTNESS - 0.01);
+        assert!(dim_red.h() < 1.0 || dim_red.h() > 359.0);
+        assert_eq!(
+            theme.panel_background(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+            Color::BLACK
+        );
+        assert_eq!(theme.colors().health_background, Color::BLACK);
+    }
+}
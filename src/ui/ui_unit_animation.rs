@@ -0,0 +1,378 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::mod_registry;
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+// ==================== UNIT SPRITE SHEET ANIMATION ====================
+
+// Frame-based idle/walk/shoot/die animation from one sprite sheet per unit
+// type, defined in unit_animations.toml. A unit keeps its static sprite (and
+// the pulse from sprite_animation_system) until its sheet has loaded, so
+// missing art degrades to the old look instead of an invisible unit.
+
+pub const UNIT_ANIMATIONS_PATH: &str = "assets/data/unit_animations.toml";
+
+// Type alias to reduce complexity
+type UnanimatedUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        Option<&'static Sprite>,
+        Option<&'static TextureAtlasSprite>,
+    ),
+    Without<UnitAnimator>,
+>;
+
+pub struct UnitAnimationPlugin;
+
+impl Plugin for UnitAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_unit_animations).add_systems(
+            Update,
+            (attach_unit_animator_system, unit_animation_system)
+                .chain()
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct AnimationClip {
+    pub row: usize,
+    pub frames: usize,
+    pub fps: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct UnitAnimationDef {
+    pub unit_type: UnitType,
+    pub sheet: String, // Relative to assets/
+    pub frame_size: [f32; 2],
+    pub columns: usize,
+    pub rows: usize,
+    pub idle: AnimationClip,
+    pub walk: AnimationClip,
+    pub shoot: AnimationClip,
+    pub die: AnimationClip,
+}
+
+impl UnitAnimationDef {
+    pub fn clip(&self, state: AnimationState) -> &AnimationClip {
+        match state {
+            AnimationState::Idle => &self.idle,
+            AnimationState::Walk => &self.walk,
+            AnimationState::Shoot => &self.shoot,
+            AnimationState::Die => &self.die,
+        }
+    }
+
+    pub fn frame_index(&self, state: AnimationState, frame: usize) -> usize {
+        let clip = self.clip(state);
+        clip.row * self.columns + frame.min(clip.frames.saturating_sub(1))
+    }
+}
+
+#[derive(Deserialize)]
+struct UnitAnimationFile {
+    #[serde(default)]
+    unit: Vec<UnitAnimationDef>,
+}
+
+struct LoadedSheet {
+    def: UnitAnimationDef,
+    image: Handle<Image>,
+    atlas: Handle<TextureAtlas>,
+}
+
+#[derive(Resource, Default)]
+pub struct UnitAnimationLibrary {
+    sheets: Vec<LoadedSheet>,
+}
+
+impl UnitAnimationLibrary {
+    pub fn sheet_images(&self) -> impl Iterator<Item = &Handle<Image>> {
+        self.sheets.iter().map(|sheet| &sheet.image)
+    }
+
+    fn sheet_for(&self, unit_type: &UnitType) -> Option<&LoadedSheet> {
+        self.sheets
+            .iter()
+            .find(|sheet| sheet.def.unit_type == *unit_type)
+    }
+}
+
+pub fn load_unit_animation_defs() -> Vec<UnitAnimationDef> {
+    let mut defs = match read_unit_animation_file(Path::new(UNIT_ANIMATIONS_PATH)) {
+        Ok(defs) => {
+            info!(
+                "🎞️ Unit animations loaded for {} unit types from {}",
+                defs.len(),
+                UNIT_ANIMATIONS_PATH
+            );
+            defs
+        }
+        Err(e) => {
+            warn!(
+                "Failed to load unit animations: {}, using static sprites",
+                e
+            );
+            Vec::new()
+        }
+    };
+
+    // A mod's sheet replaces the base one for the same unit type
+    for def in mod_registry().unit_animation_overrides() {
+        defs.retain(|base| base.unit_type != def.unit_type);
+        defs.push(def.clone());
+    }
+    defs
+}
+
+fn read_unit_animation_file(
+    path: &Path,
+) -> Result<Vec<UnitAnimationDef>, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)?;
+    let file: UnitAnimationFile = toml::from_str(&contents)?;
+    Ok(file.unit)
+}
+
+pub fn setup_unit_animations(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let sheets = load_unit_animation_defs()
+        .into_iter()
+        .map(|def| {
+            let image: Handle<Image> = asset_server.load(def.sheet.clone());
+            let atlas = texture_atlases.add(TextureAtlas::from_grid(
+                image.clone(),
+                Vec2::from(def.frame_size),
+                def.columns,
+                def.rows,
+                None,
+                None,
+            ));
+            LoadedSheet { def, image, atlas }
+        })
+        .collect();
+
+    commands.insert_resource(UnitAnimationLibrary { sheets });
+}
+
+// ==================== ANIMATION STATE MACHINE ====================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimationState {
+    Idle,
+    Walk,
+    Shoot,
+    Die,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnitSignals {
+    pub dead: bool,
+    pub fired: bool,
+    pub moving: bool,
+}
+
+// Death is final. A shot plays through before the unit goes back to idling or
+// walking, unless it fires again, which restarts the clip.
+pub fn next_animation_state(
+    current: AnimationState,
+    clip_finished: bool,
+    signals: UnitSignals,
+) -> AnimationState {
+    if signals.dead || current == AnimationState::Die {
+        return AnimationState::Die;
+    }
+    if signals.fired || (current == AnimationState::Shoot && !clip_finished) {
+        return AnimationState::Shoot;
+    }
+    if signals.moving {
+        AnimationState::Walk
+    } else {
+        AnimationState::Idle
+    }
+}
+
+#[derive(Component)]
+pub struct UnitAnimator {
+    pub state: AnimationState,
+    pub frame: usize,
+    frame_timer: f32,
+    weapon_ready: bool, // Cooldown finished last frame - a reset since means a shot
+}
+
+impl UnitAnimator {
+    fn set_state(&mut self, state: AnimationState) {
+        self.state = state;
+        self.frame = 0;
+        self.frame_timer = 0.0;
+    }
+}
+
+// Swap units onto their sheet once it has finished loading
+pub fn attach_unit_animator_system(
+    mut commands: Commands,
+    library: Res<UnitAnimationLibrary>,
+    asset_server: Res<AssetServer>,
+    unit_query: UnanimatedUnitQuery,
+) {
+    for (entity, unit, sprite, atlas_sprite) in unit_query.iter() {
+        let Some(sheet) = library.sheet_for(&unit.unit_type) else {
+            continue;
+        };
+        if asset_server.get_load_state(&sheet.image) != Some(LoadState::Loaded) {
+            continue;
+        }
+
+        // Keep the faction tint and on-screen size the spawner gave the unit
+        let (color, custom_size) = match (sprite, atlas_sprite) {
+            (Some(sprite), _) => (sprite.color, sprite.custom_size),
+            (None, Some(atlas_sprite)) => (atlas_sprite.color, atlas_sprite.custom_size),
+            (None, None) => (Color::WHITE, None),
+        };
+
+        commands
+            .entity(entity)
+            .remove::<(Sprite, Handle<Image>, AnimatedSprite)>()
+            .insert((
+                TextureAtlasSprite {
+                    color,
+                    custom_size,
+                    index: sheet.def.frame_index(AnimationState::Idle, 0),
+                    ..default()
+                },
+                sheet.atlas.clone(),
+                UnitAnimator {
+                    state: AnimationState::Idle,
+                    frame: 0,
+                    frame_timer: 0.0,
+                    weapon_ready: unit.attack_cooldown.finished(),
+                },
+            ));
+    }
+}
+
+pub fn unit_animation_system(
+    library: Res<UnitAnimationLibrary>,
+    mut unit_query: Query<(&Unit, &Movement, &mut UnitAnimator, &mut TextureAtlasSprite)>,
+    time: Res<Time>,
+) {
+    for (unit, movement, mut animator, mut sprite) in unit_query.iter_mut() {
+        let Some(sheet) = library.sheet_for(&unit.unit_type) else {
+            continue;
+        };
+
+        let weapon_ready = unit.attack_cooldown.finished();
+        let signals = UnitSignals {
+            dead: unit.health <= 0.0,
+            fired: animator.weapon_ready && !weapon_ready,
+            moving: movement.target_position.is_some(),
+        };
+        animator.weapon_ready = weapon_ready;
+
+        let clip = sheet.def.clip(animator.state);
+        let clip_finished = animator.frame + 1 >= clip.frames;
+        let next = next_animation_state(animator.state, clip_finished, signals);
+        if next != animator.state || signals.fired {
+            animator.set_state(next);
+        }
+
+        // Advance the clip; idle and walk loop, shoot and die hold their last frame
+        let clip = sheet.def.clip(animator.state);
+        animator.frame_timer += time.delta_seconds();
+        let frame_length = 1.0 / clip.fps.max(0.01);
+        while animator.frame_timer >= frame_length {
+            animator.frame_timer -= frame_length;
+            animator.frame = match animator.state {
+                AnimationState::Idle | AnimationState::Walk => (animator.frame + 1) % clip.frames,
+                AnimationState::Shoot | AnimationState::Die => {
+                    (animator.frame + 1).min(clip.frames - 1)
+                }
+            };
+        }
+
+        let index = sheet.def.frame_index(animator.state, animator.frame);
+        if sprite.index != index {
+            sprite.index = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_animation_state_transitions() {
+        let idle = UnitSignals::default();
+        let moving = UnitSignals {
+            moving: true,
+            ..default()
+        };
+        let fired = UnitSignals {
+            fired: true,
+            moving: true,
+            ..default()
+        };
+        let dead = UnitSignals {
+            dead: true,
+            ..default()
+        };
+
+        assert_eq!(
+            next_animation_state(AnimationState::Idle, true, moving),
+            AnimationState::Walk
+        );
+        assert_eq!(
+            next_animation_state(AnimationState::Walk, true, fired),
+            AnimationState::Shoot
+        );
+        // The shot plays out even while walking, then hands back
+        assert_eq!(
+            next_animation_state(AnimationState::Shoot, false, moving),
+            AnimationState::Shoot
+        );
+        assert_eq!(
+            next_animation_state(AnimationState::Shoot, true, idle),
+            AnimationState::Idle
+        );
+        assert_eq!(
+            next_animation_state(AnimationState::Walk, false, dead),
+            AnimationState::Die
+        );
+        assert_eq!(
+            next_animation_state(AnimationState::Die, true, moving),
+            AnimationState::Die
+        );
+    }
+
+    #[test]
+    fn test_unit_animation_definitions_parse() {
+        let contents = fs::read_to_string(UNIT_ANIMATIONS_PATH).unwrap();
+        let file: UnitAnimationFile = toml::from_str(&contents).unwrap();
+        assert!(!file.unit.is_empty());
+
+        for def in &file.unit {
+            for state in [
+                AnimationState::Idle,
+                AnimationState::Walk,
+                AnimationState::Shoot,
+                AnimationState::Die,
+            ] {
+                let clip = def.clip(state);
+                assert!(clip.frames > 0 && clip.frames <= def.columns);
+                assert!(clip.row < def.rows);
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::BalanceConfig;
+use crate::diplomacy::Diplomacy;
+use crate::environmental_systems::{gather_lights, EnvironmentalState, LightQuery};
+use crate::localization::{tr, tr_args};
+use crate::map::CityMap;
+use crate::political_system::PoliticalState;
+use crate::rules_of_engagement::{civilian_positions, RoeLimits};
+use crate::sentiment::CivilianSentiment;
+use crate::ui::ui_theme::UiTheme;
+use crate::utils::{
+    base_accuracy, create_dashboard_panel, dashboard_line, update_dashboard_panel,
+    CombatConditions, FiringStance, HitChance,
+};
+use bevy::prelude::*;
+
+// ==================== UNIT INFO PANEL ====================
+
+// With a unit selected, a panel shows what it carries and its odds of
+// hitting: against the target it was given, or else the nearest enemy in
+// reach. Each factor costing it accuracy is listed with the share it takes,
+// so it's clear whether to close in, stand still, find a better angle, get
+// out of the gas or wait out the suppression. Fighters in a squad also show
+// how far its cohesion has come.
+
+pub struct UnitInfoPlugin;
+
+impl Plugin for UnitInfoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, unit_info_panel_system.in_set(GameSet::InGame));
+    }
+}
+
+#[derive(Component)]
+pub struct UnitInfoPanel;
+
+/// Each factor costing a shot accuracy, as its localization key under hud.
+/// and the share of the hit chance it takes away.
+pub fn accuracy_penalties(chance: &HitChance) -> Vec<(&'static str, f32)> {
+    [
+        ("unit_info_range", chance.range),
+        ("unit_info_moving", chance.shooter_moving),
+        ("unit_info_target_moving", chance.target_moving),
+        ("unit_info_cover", chance.cover),
+        ("unit_info_suppressed", chance.suppression),
+        ("unit_info_darkness", chance.darkness),
+        ("unit_info_gassed", chance.gassed),
+    ]
+    .into_iter()
+    .filter(|(_, multiplier)| *multiplier < 1.0)
+    .map(|(key, multiplier)| (key, 1.0 - multiplier))
+    .collect()
+}
+
+fn percent(share: f32) -> String {
+    format!("{:.0}", share * 100.0)
+}
+
+// ==================== SYSTEMS ====================
+
+#[allow(clippy::too_many_arguments)]
+pub fn unit_info_panel_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    environment: Res<EnvironmentalState>,
+    city_map: Res<CityMap>,
+    diplomacy: Res<Diplomacy>,
+    sentiment: Res<CivilianSentiment>,
+    political_state: Res<PoliticalState>,
+    theme: Res<UiTheme>,
+    selected_query: Query<Entity, With<Selected>>,
+    unit_query: Query<(
+        Entity,
+        &Unit,
+        &Transform,
+        Option<&Movement>,
+        Option<&TacticalState>,
+    )>,
+    cohesion_query: Query<&SquadCohesion>,
+    light_query: LightQuery,
+    zone_query: Query<&AreaDenialZone>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<UnitInfoPanel>>,
+) {
+    let selected = selected_query
+        .iter()
+        .filter_map(|entity| unit_query.get(entity).ok())
+        .find(|(_, unit, _, _, _)| unit.health > 0.0);
+
+    let lines = selected.map(|(entity, unit, transform, movement, tactical_state)| {
+        let (streetlights, beams) = gather_lights(&light_query, &balance.night_vision);
+        let zones: Vec<AreaDenialZone> = zone_query.iter().cloned().collect();
+        let civilians = civilian_positions(
+            unit_query
+                .iter()
+                .map(|(_, unit, transform, _, _)| (unit, transform)),
+        );
+        let conditions = CombatConditions {
+            environment: &environment,
+            streetlights: &streetlights,
+            beams: &beams,
+            city_map: &city_map,
+            diplomacy: &diplomacy,
+            zones: &zones,
+            sentiment: &sentiment,
+            hidden_sight: balance.sentiment.hidden_sight,
+            roe: RoeLimits::of(&political_state.government_response_level, &balance.roe),
+            civilians: &civilians,
+        };
+        let position = transform.translation;
+
+        // The ordered target, or else whoever it would open fire on
+        let enemies: Vec<_> = unit_query
+            .iter()
+            .filter(|(_, other, _, _, _)| other.health > 0.0 && conditions.hostile(unit, other))
+            .collect();
+        let target = enemies
+            .iter()
+            .find(|(entity, _, _, _, _)| Some(*entity) == unit.target)
+            .or_else(|| {
+                enemies
+                    .iter()
+                    .filter(|(_, _, other_transform, _, _)| {
+                        conditions.in_sight(unit, position, other_transform.translation)
+                    })
+                    .min_by(|(_, _, a, _, _), (_, _, b, _, _)| {
+                        position
+                            .distance(a.translation)
+                            .total_cmp(&position.distance(b.translation))
+                    })
+            })
+            .copied();
+
+        let mut lines = vec![
+            dashboard_line(
+                tr_args(
+                    "hud.unit_info_title",
+                    &[("unit", &format!("{:?}", unit.unit_type))],
+                ),
+                16.0,
+                Color::GOLD,
+            ),
+            dashboard_line(
+                tr_args(
+                    "hud.unit_info_stats",
+                    &[
+                        ("health", &format!("{:.0}", unit.health)),
+                        ("max", &format!("{:.0}", unit.max_health)),
+                        ("weapon", &format!("{:?}", unit.equipment.weapon)),
+                        ("range", &format!("{:.0}", unit.range)),
+                    ],
+                ),
+                11.0,
+                Color::WHITE,
+            ),
+            dashboard_line(
+                tr_args(
+                    "hud.unit_info_accuracy",
+                    &[("percent", &percent(base_accuracy(&unit.equipment.weapon)))],
+                ),
+                11.0,
+                Color::WHITE,
+            ),
+        ];
+        if let Ok(cohesion) = cohesion_query.get(entity) {
+            lines.push(dashboard_line(
+                tr_args(
+                    "hud.unit_info_cohesion",
+                    &[
+                        ("squad", &cohesion.squad),
+                        ("percent", &percent(cohesion.level)),
+                    ],
+                ),
+                11.0,
+                Color::CYAN,
+            ));
+        }
+
+        let Some((_, enemy, enemy_transform, enemy_movement, enemy_state)) = target else {
+            lines.push(dashboard_line(
+                tr("hud.unit_info_no_target"),
+                11.0,
+                Color::GRAY,
+            ));
+            return lines;
+        };
+        let chance = conditions.hit_chance(
+            unit,
+            (
+                position,
+                FiringStance::of(transform, movement, tactical_state),
+            ),
+            (
+                enemy_transform.translation,
+                FiringStance::of(enemy_transform, enemy_movement, enemy_state),
+            ),
+            &balance.accuracy,
+        );
+        lines.push(dashboard_line(
+            tr_args(
+                "hud.unit_info_target",
+                &[
+                    ("enemy", &format!("{:?}", enemy.unit_type)),
+                    (
+                        "distance",
+                        &format!("{:.0}", position.distance(enemy_transform.translation)),
+                    ),
+                    ("percent", &percent(chance.total(&balance.accuracy))),
+                ],
+            ),
+            12.0,
+            Color::YELLOW,
+        ));
+        for (reason, share) in accuracy_penalties(&chance) {
+            lines.push(dashboard_line(
+                tr_args(
+                    "hud.unit_info_penalty",
+                    &[
+                        ("percent", &percent(share)),
+                        ("reason", &tr(&format!("hud.{}", reason))),
+                    ],
+                ),
+                11.0,
+                Color::ORANGE,
+            ));
+        }
+        lines
+    });
+
+    let visibility = if lines.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+        if let Some(lines) = lines {
+            update_dashboard_panel(&mut text, lines, &theme);
+        }
+    } else if let Some(lines) = lines {
+        let mut panel = create_dashboard_panel(
+            Style {
+                left: Val::Px(10.0),
+                bottom: Val::Px(420.0),
+                width: Val::Px(300.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.85),
+            lines,
+            &theme,
+        );
+        panel.0.visibility = visibility;
+        commands.spawn((panel, UnitInfoPanel));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_factors_that_cost_accuracy_are_listed() {
+        let chance = HitChance {
+            base: 0.85,
+            range: 0.9,
+            shooter_moving: 1.0,
+            target_moving: 1.0,
+            cover: 0.8,
+            suppression: 1.0,
+            darkness: 1.0,
+            gassed: 1.0,
+        };
+        let penalties = accuracy_penalties(&chance);
+        let keys: Vec<&str> = penalties.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, vec!["unit_info_range", "unit_info_cover"]);
+        assert!((penalties[1].1 - 0.2).abs() < 1e-6);
+        assert_eq!(percent(penalties[1].1), "20");
+    }
+}
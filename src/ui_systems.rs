@@ -539,20 +539,7 @@ fn create_mission_briefing_ui(commands: &mut Commands, mission_config: &crate::c
 
         // List objectives
         for (i, objective) in mission_config.objectives.iter().enumerate() {
-            let objective_text = match objective {
-                crate::campaign::MissionObjective::SurviveTime(time) => {
-                    format!("{}. Survive for {:.0} seconds", i + 1, time)
-                },
-                crate::campaign::MissionObjective::DefendTarget(target) => {
-                    format!("{}. Protect {}", i + 1, target)
-                },
-                crate::campaign::MissionObjective::EliminateEnemies(count) => {
-                    format!("{}. Eliminate {} enemy units", i + 1, count)
-                },
-                crate::campaign::MissionObjective::ControlArea(area) => {
-                    format!("{}. Control {}", i + 1, area)
-                },
-            };
+            let objective_text = format!("{}. {}", i + 1, objective.description());
 
             parent.spawn(TextBundle::from_section(
                 objective_text,
@@ -1,232 +1,22 @@
 use crate::components::*;
+use crate::config::UnitDefinition;
 use bevy::prelude::*;
 
 // ==================== UNIT CONFIGURATION SYSTEM ====================
 
-pub fn configure_unit_stats(unit: &mut Unit, unit_type: &UnitType, faction: &Faction) {
-    match unit_type {
-        // Cartel units
-        UnitType::Sicario => {
-            unit.health = 80.0;
-            unit.max_health = 80.0;
-            unit.damage = 25.0;
-            unit.range = 120.0;
-            unit.movement_speed = 45.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::AssaultRifle,
-                armor: ArmorType::LightVest,
-                upgrades: vec![],
-            };
-        }
-        UnitType::Enforcer => {
-            unit.health = 120.0;
-            unit.max_health = 120.0;
-            unit.damage = 35.0;
-            unit.range = 100.0;
-            unit.movement_speed = 35.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::HeavyMachineGun,
-                armor: ArmorType::TacticalVest,
-                upgrades: vec![],
-            };
-        }
-        UnitType::Sniper => {
-            unit.health = 60.0;
-            unit.max_health = 60.0;
-            unit.damage = 80.0; // High damage
-            unit.range = 250.0; // Very long range
-            unit.movement_speed = 25.0; // Slow movement
-            unit.equipment = Equipment {
-                weapon: WeaponType::CartelSniperRifle,
-                armor: ArmorType::LightVest,
-                upgrades: vec![UpgradeType::ScopedSight],
-            };
-        }
-        UnitType::HeavyGunner => {
-            unit.health = 150.0;
-            unit.max_health = 150.0;
-            unit.damage = 45.0;
-            unit.range = 140.0;
-            unit.movement_speed = 20.0; // Very slow
-            unit.equipment = Equipment {
-                weapon: WeaponType::LMG,
-                armor: ArmorType::HeavyArmor,
-                upgrades: vec![UpgradeType::ExtendedMag],
-            };
-        }
-        UnitType::Medic => {
-            unit.health = 70.0;
-            unit.max_health = 70.0;
-            unit.damage = 15.0; // Low combat ability
-            unit.range = 80.0;
-            unit.movement_speed = 40.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::MedicBag,
-                armor: ArmorType::LightVest,
-                upgrades: vec![UpgradeType::RadioComms],
-            };
-        }
-        UnitType::Ovidio => {
-            unit.health = 200.0;
-            unit.max_health = 200.0;
-            unit.damage = 40.0;
-            unit.range = 110.0;
-            unit.movement_speed = 30.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::AssaultRifle,
-                armor: ArmorType::HeavyArmor,
-                upgrades: vec![UpgradeType::ReinforcedArmor, UpgradeType::RadioComms],
-            };
-        }
-
-        // Military units
-        UnitType::Soldier => {
-            unit.health = 100.0;
-            unit.max_health = 100.0;
-            unit.damage = 30.0;
-            unit.range = 130.0;
-            unit.movement_speed = 40.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::StandardIssue,
-                armor: ArmorType::TacticalVest,
-                upgrades: vec![],
-            };
-        }
-        UnitType::SpecialForces => {
-            unit.health = 130.0;
-            unit.max_health = 130.0;
-            unit.damage = 45.0;
-            unit.range = 150.0;
-            unit.movement_speed = 50.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::TacticalRifle,
-                armor: ArmorType::TacticalVest,
-                upgrades: vec![UpgradeType::ScopedSight, UpgradeType::CombatStims],
-            };
-        }
-        UnitType::Tank => {
-            unit.health = 300.0;
-            unit.max_health = 300.0;
-            unit.damage = 100.0; // Very high damage
-            unit.range = 200.0;
-            unit.movement_speed = 15.0; // Very slow
-            unit.equipment = Equipment {
-                weapon: WeaponType::TankCannon,
-                armor: ArmorType::VehicleArmor,
-                upgrades: vec![UpgradeType::ReinforcedArmor],
-            };
-        }
-        UnitType::Helicopter => {
-            unit.health = 80.0; // Fragile but fast
-            unit.max_health = 80.0;
-            unit.damage = 60.0;
-            unit.range = 180.0;
-            unit.movement_speed = 80.0; // Very fast
-            unit.equipment = Equipment {
-                weapon: WeaponType::HelicopterWeapons,
-                armor: ArmorType::None,
-                upgrades: vec![UpgradeType::ScopedSight],
-            };
-        }
-        UnitType::Engineer => {
-            unit.health = 90.0;
-            unit.max_health = 90.0;
-            unit.damage = 20.0; // Low combat ability
-            unit.range = 100.0;
-            unit.movement_speed = 35.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::EngineerTools,
-                armor: ArmorType::TacticalVest,
-                upgrades: vec![UpgradeType::RadioComms],
-            };
-        }
-        UnitType::Vehicle => {
-            unit.health = 180.0;
-            unit.max_health = 180.0;
-            unit.damage = 50.0;
-            unit.range = 160.0;
-            unit.movement_speed = 25.0;
-            unit.equipment = Equipment {
-                weapon: WeaponType::VehicleWeapons,
-                armor: ArmorType::VehicleArmor,
-                upgrades: vec![],
-            };
-        }
-        UnitType::Roadblock => {
-            unit.health = 50.0;
-            unit.max_health = 50.0;
-            unit.damage = 0.0; // Defensive structure
-            unit.range = 0.0;
-            unit.movement_speed = 0.0; // Immobile
-            unit.equipment = Equipment {
-                weapon: WeaponType::BasicRifle,
-                armor: ArmorType::None,
-                upgrades: vec![],
-            };
-        }
-    }
+// Base stats and loadout come from the unit catalog (data/units.ron)
+pub fn configure_unit_stats(unit: &mut Unit, definition: &UnitDefinition) {
+    apply_base_stats(unit, definition);
+    unit.equipment = definition.equipment();
 }
 
-pub fn get_unit_abilities(unit_type: &UnitType) -> Vec<UnitAbility> {
-    match unit_type {
-        UnitType::Sniper => vec![UnitAbility {
-            ability_type: AbilityType::PrecisionShot,
-            cooldown: Timer::from_seconds(8.0, TimerMode::Once),
-            range: 300.0,
-            energy_cost: 40,
-        }],
-        UnitType::HeavyGunner => vec![UnitAbility {
-            ability_type: AbilityType::SuppressiveFire,
-            cooldown: Timer::from_seconds(12.0, TimerMode::Once),
-            range: 160.0,
-            energy_cost: 50,
-        }],
-        UnitType::Medic => vec![UnitAbility {
-            ability_type: AbilityType::FieldMedic,
-            cooldown: Timer::from_seconds(6.0, TimerMode::Once),
-            range: 100.0,
-            energy_cost: 30,
-        }],
-        UnitType::Tank => vec![UnitAbility {
-            ability_type: AbilityType::TankShell,
-            cooldown: Timer::from_seconds(15.0, TimerMode::Once),
-            range: 250.0,
-            energy_cost: 60,
-        }],
-        UnitType::Helicopter => vec![UnitAbility {
-            ability_type: AbilityType::StrafeRun,
-            cooldown: Timer::from_seconds(20.0, TimerMode::Once),
-            range: 200.0,
-            energy_cost: 70,
-        }],
-        UnitType::Engineer => vec![
-            UnitAbility {
-                ability_type: AbilityType::DeployBarricade,
-                cooldown: Timer::from_seconds(25.0, TimerMode::Once),
-                range: 50.0,
-                energy_cost: 40,
-            },
-            UnitAbility {
-                ability_type: AbilityType::RepairVehicle,
-                cooldown: Timer::from_seconds(10.0, TimerMode::Once),
-                range: 80.0,
-                energy_cost: 35,
-            },
-        ],
-        UnitType::Enforcer => vec![UnitAbility {
-            ability_type: AbilityType::BurstFire,
-            cooldown: Timer::from_seconds(6.0, TimerMode::Once),
-            range: 120.0,
-            energy_cost: 25,
-        }],
-        UnitType::SpecialForces => vec![UnitAbility {
-            ability_type: AbilityType::FragGrenade,
-            cooldown: Timer::from_seconds(10.0, TimerMode::Once),
-            range: 140.0,
-            energy_cost: 35,
-        }],
-        _ => vec![], // Default units have no special abilities
-    }
+pub fn apply_base_stats(unit: &mut Unit, definition: &UnitDefinition) {
+    unit.health = definition.health;
+    unit.max_health = definition.health;
+    unit.damage = definition.damage;
+    unit.range = definition.range;
+    unit.vision = definition.vision;
+    unit.movement_speed = definition.movement_speed;
 }
 
 pub fn get_unit_emoji(unit_type: &UnitType) -> &'static str {
@@ -263,6 +53,8 @@ pub fn get_unit_color(unit_type: &UnitType, faction: &Faction) -> Color {
             UnitType::SpecialForces => Color::rgb(0.0, 1.0, 0.0), // Bright green
             _ => Color::GREEN,
         },
+        Faction::RivalCartel => Color::PURPLE,
+        Faction::Police => Color::BLUE,
         _ => Color::WHITE,
     }
 }
@@ -288,6 +80,9 @@ pub fn apply_weapon_upgrades(unit: &mut Unit) {
             UpgradeType::RadioComms => {
                 // Communication bonuses applied elsewhere
             }
+            UpgradeType::NightVision => {
+                // Darkness penalties skipped in combat
+            }
         }
     }
 }
@@ -0,0 +1,357 @@
+use crate::app_state::GameSet;
+use crate::commander::CommanderPowers;
+use crate::components::*;
+use crate::config::{BalanceConfig, UpgradeBalance};
+use crate::economy::{charge, CartelTreasury};
+use crate::intel_recruitment::IntelRecruitment;
+use crate::localization::{tr, tr_args};
+use crate::reinforcements::ReinforcementDesk;
+use crate::ui::UiTheme;
+use crate::utils::{
+    create_dashboard_panel, dashboard_line, play_tactical_sound, update_dashboard_panel,
+    SimulationSet,
+};
+use bevy::prelude::*;
+
+// ==================== MISSION UPGRADES PLUGIN ====================
+
+// Cash can also go on kit that lasts the rest of the mission: better radios,
+// armor plates, incendiary rounds, suppressors and access to a surveillance
+// drone. T opens
+// the upgrade panel. Nothing edits a unit's own stats - each purchase feeds a
+// StatModifiers component that this plugin keeps on every fighter, and combat
+// and communication read the multipliers from there. Improvised mortars are
+// the exception: they unlock the cartel's own indirect fire (see
+// indirect_fire.rs) rather than changing anyone's stats.
+
+pub const BASE_RADIO_RANGE: f32 = 200.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MissionUpgrade {
+    Radios,
+    ArmorPlates,
+    IncendiaryRounds,
+    DroneAccess,
+    Suppressors,
+    Mortars,
+}
+
+const UPGRADE_KEYS: [(KeyCode, MissionUpgrade); 6] = [
+    (KeyCode::J, MissionUpgrade::Radios),
+    (KeyCode::K, MissionUpgrade::ArmorPlates),
+    (KeyCode::L, MissionUpgrade::IncendiaryRounds),
+    (KeyCode::M, MissionUpgrade::DroneAccess),
+    (KeyCode::N, MissionUpgrade::Suppressors),
+    (KeyCode::O, MissionUpgrade::Mortars),
+];
+
+impl MissionUpgrade {
+    pub const ALL: [MissionUpgrade; 6] = [
+        MissionUpgrade::Radios,
+        MissionUpgrade::ArmorPlates,
+        MissionUpgrade::IncendiaryRounds,
+        MissionUpgrade::DroneAccess,
+        MissionUpgrade::Suppressors,
+        MissionUpgrade::Mortars,
+    ];
+
+    pub fn cost(self, balance: &UpgradeBalance) -> f32 {
+        match self {
+            MissionUpgrade::Radios => balance.radios_cost,
+            MissionUpgrade::ArmorPlates => balance.armor_plates_cost,
+            MissionUpgrade::IncendiaryRounds => balance.incendiary_cost,
+            MissionUpgrade::DroneAccess => balance.drone_cost,
+            MissionUpgrade::Suppressors => balance.suppressors_cost,
+            MissionUpgrade::Mortars => balance.mortars_cost,
+        }
+    }
+
+    // Localization key under hud.
+    fn name(self) -> &'static str {
+        match self {
+            MissionUpgrade::Radios => "upgrade_radios",
+            MissionUpgrade::ArmorPlates => "upgrade_armor_plates",
+            MissionUpgrade::IncendiaryRounds => "upgrade_incendiary",
+            MissionUpgrade::DroneAccess => "upgrade_drone",
+            MissionUpgrade::Suppressors => "upgrade_suppressors",
+            MissionUpgrade::Mortars => "upgrade_mortars",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr(&format!("hud.{}", self.name()))
+    }
+}
+
+pub struct MissionUpgradesPlugin;
+
+impl Plugin for MissionUpgradesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UpgradeWorkshop>()
+            .add_systems(
+                FixedUpdate,
+                stat_modifier_system
+                    .in_set(SimulationSet::Simulate)
+                    .in_set(GameSet::Session),
+            )
+            .add_systems(Update, upgrade_panel_system.in_set(GameSet::Session));
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct UpgradeWorkshop {
+    pub panel_open: bool,
+    pub purchased: Vec<MissionUpgrade>,
+}
+
+impl UpgradeWorkshop {
+    pub fn owns(&self, upgrade: MissionUpgrade) -> bool {
+        self.purchased.contains(&upgrade)
+    }
+}
+
+#[derive(Component)]
+pub struct UpgradeUIPanel;
+
+/// The multipliers a cartel fighter carries for the upgrades bought so far.
+pub fn stat_modifiers(purchased: &[MissionUpgrade], balance: &UpgradeBalance) -> StatModifiers {
+    let mut modifiers = StatModifiers::default();
+    for upgrade in purchased {
+        match upgrade {
+            MissionUpgrade::Radios => modifiers.radio_range *= balance.radio_range_multiplier,
+            MissionUpgrade::ArmorPlates => modifiers.damage_taken *= balance.armor_damage_taken,
+            MissionUpgrade::IncendiaryRounds => modifiers.damage_dealt *= balance.incendiary_damage,
+            MissionUpgrade::DroneAccess => modifiers.spotting *= balance.drone_spotting,
+            MissionUpgrade::Suppressors => modifiers.noise *= balance.suppressor_noise,
+            MissionUpgrade::Mortars => {}
+        }
+    }
+    modifiers
+}
+
+// ==================== STAT MODIFIERS ====================
+
+// Type aliases to reduce complexity
+type ModifiedUnit = (
+    Entity,
+    &'static Unit,
+    Option<&'static mut StatModifiers>,
+    Option<&'static mut Communication>,
+    Option<&'static CheckpointDelay>,
+    Option<&'static Punctured>,
+    Option<&'static SquadCohesion>,
+);
+
+// The one place modifiers are applied: every fighter, including ones that
+// arrive after a purchase, carries the current set for its side - cartel
+// upgrades plus either side's commander spotting powers - and its own delay
+// at an enemy checkpoint or from a spike strip, or its hurry back to its
+// squad. Radio range is derived from it rather than stacked onto the unit
+pub fn stat_modifier_system(
+    mut commands: Commands,
+    workshop: Res<UpgradeWorkshop>,
+    balance: Res<BalanceConfig>,
+    powers: Option<Res<CommanderPowers>>,
+    mut unit_query: Query<ModifiedUnit>,
+) {
+    let spotting = |faction: &Faction| {
+        powers
+            .as_ref()
+            .map_or(1.0, |powers| powers.spotting(faction))
+    };
+    let mut cartel = stat_modifiers(&workshop.purchased, &balance.upgrades);
+    cartel.spotting *= spotting(&Faction::Cartel);
+    let military = StatModifiers {
+        spotting: spotting(&Faction::Military),
+        ..default()
+    };
+
+    for (entity, unit, current, communication, delay, punctured, cohesion) in unit_query.iter_mut()
+    {
+        let mut modifiers = match unit.faction {
+            Faction::Cartel => cartel.clone(),
+            Faction::Military => military.clone(),
+            _ => continue,
+        };
+        if let Some(delay) = delay {
+            modifiers.movement *= delay.speed;
+        }
+        if let Some(punctured) = punctured {
+            modifiers.movement *= punctured.speed;
+        }
+        if let Some(cohesion) = cohesion {
+            modifiers.movement *= cohesion.regroup_speed;
+        }
+        match current {
+            Some(mut current) => {
+                if *current != modifiers {
+                    *current = modifiers.clone();
+                }
+            }
+            None => {
+                commands.entity(entity).insert(modifiers.clone());
+            }
+        }
+        if let Some(mut communication) = communication {
+            let radio_range = BASE_RADIO_RANGE * modifiers.radio_range;
+            if communication.radio_range != radio_range {
+                communication.radio_range = radio_range;
+            }
+        }
+    }
+}
+
+// ==================== UPGRADE PANEL ====================
+
+// T opens and closes the upgrade panel; while it's open the listed keys buy
+// an upgrade for the rest of the mission
+pub fn upgrade_input_system(
+    input: Res<Input<KeyCode>>,
+    balance: Res<BalanceConfig>,
+    mut workshop: ResMut<UpgradeWorkshop>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    desk: Option<ResMut<ReinforcementDesk>>,
+    powers: Option<ResMut<CommanderPowers>>,
+    network: Option<ResMut<IntelRecruitment>>,
+) {
+    if input.just_pressed(KeyCode::T) {
+        workshop.panel_open = !workshop.panel_open;
+        // The recruitment panel, call-in, commander and intel network menus share the J/K/L keys
+        if workshop.panel_open {
+            if let Some(treasury) = treasury.as_deref_mut() {
+                treasury.panel_safehouse = None;
+            }
+            if let Some(mut desk) = desk {
+                desk.menu_open = false;
+            }
+            if let Some(mut powers) = powers {
+                powers.menu_open = false;
+            }
+            if let Some(mut network) = network {
+                network.menu_open = false;
+            }
+        }
+    }
+    if !workshop.panel_open {
+        return;
+    }
+
+    for (key, upgrade) in UPGRADE_KEYS {
+        if !input.just_pressed(key) {
+            continue;
+        }
+        let name = upgrade.label();
+        if workshop.owns(upgrade) {
+            play_tactical_sound("radio", &tr_args("radio.upgrade_owned", &[("name", &name)]));
+            continue;
+        }
+        if !charge(treasury.as_deref_mut(), upgrade.cost(&balance.upgrades)) {
+            continue;
+        }
+        workshop.purchased.push(upgrade);
+        play_tactical_sound(
+            "radio",
+            &tr_args("radio.upgrade_purchased", &[("name", &name)]),
+        );
+    }
+}
+
+pub fn upgrade_panel_system(
+    mut commands: Commands,
+    workshop: Res<UpgradeWorkshop>,
+    balance: Res<BalanceConfig>,
+    treasury: Option<Res<CartelTreasury>>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<UpgradeUIPanel>>,
+    theme: Res<UiTheme>,
+) {
+    let panel_exists = !panel_query.is_empty();
+    let cash_changed = treasury
+        .as_ref()
+        .is_some_and(|treasury| treasury.is_changed());
+    if panel_exists && !workshop.is_changed() && !cash_changed && !theme.is_changed() {
+        return;
+    }
+
+    let visibility = if workshop.panel_open {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    let cash = treasury.map_or(f32::MAX, |treasury| treasury.cash);
+    let mut lines = vec![dashboard_line(tr("hud.upgrades_title"), 16.0, Color::GOLD)];
+    for (key, upgrade) in UPGRADE_KEYS {
+        let name = upgrade.label();
+        let cost = upgrade.cost(&balance.upgrades);
+        let (line, color) = if workshop.owns(upgrade) {
+            (format!("✓ {}", name), Color::GREEN)
+        } else {
+            (
+                format!("[{:?}] {} - ${}", key, name, cost as u32),
+                if cash >= cost {
+                    Color::WHITE
+                } else {
+                    Color::GRAY
+                },
+            )
+        };
+        lines.push(dashboard_line(line, 11.0, color));
+    }
+
+    if let Ok((mut text, mut panel_visibility)) = panel_query.get_single_mut() {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+        update_dashboard_panel(&mut text, lines, &theme);
+    } else if !panel_exists {
+        let mut panel = create_dashboard_panel(
+            Style {
+                right: Val::Px(10.0),
+                bottom: Val::Px(420.0),
+                width: Val::Px(280.0),
+                ..default()
+            },
+            Color::rgba(0.0, 0.0, 0.0, 0.85),
+            lines,
+            &theme,
+        );
+        panel.0.visibility = visibility;
+        commands.spawn((panel, UpgradeUIPanel));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modifiers_follow_purchases() {
+        let balance = UpgradeBalance::default();
+        assert_eq!(stat_modifiers(&[], &balance), StatModifiers::default());
+
+        let modifiers = stat_modifiers(
+            &[MissionUpgrade::Radios, MissionUpgrade::ArmorPlates],
+            &balance,
+        );
+        assert_eq!(modifiers.radio_range, balance.radio_range_multiplier);
+        assert_eq!(modifiers.damage_taken, balance.armor_damage_taken);
+        assert_eq!(modifiers.damage_dealt, 1.0);
+        assert_eq!(modifiers.spotting, 1.0);
+    }
+
+    #[test]
+    fn test_every_upgrade_has_a_price_and_a_key() {
+        let balance = UpgradeBalance::default();
+        for (_, upgrade) in UPGRADE_KEYS {
+            assert!(upgrade.cost(&balance) > 0.0);
+            // An unlock rather than a stat change
+            if upgrade == MissionUpgrade::Mortars {
+                continue;
+            }
+            assert_ne!(
+                stat_modifiers(&[upgrade], &balance),
+                StatModifiers::default()
+            );
+        }
+    }
+}
@@ -1,6 +1,11 @@
 use crate::components::*;
+use crate::config::UnitCatalog;
+use crate::noise::queue_explosion_noise;
 use crate::spawners::spawn_unit;
-use crate::utils::play_tactical_sound;
+use crate::utils::{
+    play_tactical_sound, play_tactical_sound_at_position, queue_camera_shake, queue_decal,
+    DecalKind,
+};
 use bevy::prelude::*;
 
 // ==================== ABILITY HELPER FUNCTIONS ====================
@@ -55,10 +60,11 @@ pub fn execute_ability_simple(
     commands: &mut Commands,
     caster_entity: Entity,
     caster_position: Vec3,
-    _caster_unit: &mut Unit,
+    caster_unit: &mut Unit,
     ability_type: AbilityType,
     enemy_data: &[(Entity, Vec3, UnitType, f32)],
     game_assets: &Res<crate::resources::GameAssets>,
+    catalog: &UnitCatalog,
 ) {
     match ability_type {
         AbilityType::BurstFire => {
@@ -100,13 +106,25 @@ pub fn execute_ability_simple(
                 Faction::Cartel,
                 backup_pos,
                 game_assets,
+                catalog,
             );
             play_tactical_sound("ability", "Backup called! Reinforcement unit arriving");
         }
         AbilityType::FragGrenade => {
             // Create area damage around target location
-            create_explosion_effect_simple(commands, caster_position, 60.0, 40.0, enemy_data);
-            play_tactical_sound("ability", "Frag grenade thrown! Area damage inflicted");
+            create_explosion_effect_simple(
+                commands,
+                caster_position,
+                60.0,
+                40.0,
+                &caster_unit.faction,
+                enemy_data,
+            );
+            play_tactical_sound_at_position(
+                "explosion",
+                "Frag grenade thrown! Area damage inflicted",
+                caster_position,
+            );
         }
         AbilityType::AirStrike => {
             // Delayed area bombardment
@@ -186,8 +204,19 @@ pub fn execute_ability_simple(
         }
         AbilityType::TankShell => {
             // Massive area damage
-            create_explosion_effect_simple(commands, caster_position, 100.0, 80.0, enemy_data);
-            play_tactical_sound("ability", "Tank shell fired! Devastating area damage");
+            create_explosion_effect_simple(
+                commands,
+                caster_position,
+                100.0,
+                80.0,
+                &caster_unit.faction,
+                enemy_data,
+            );
+            play_tactical_sound_at_position(
+                "explosion",
+                "Tank shell fired! Devastating area damage",
+                caster_position,
+            );
         }
         AbilityType::StrafeRun => {
             // Linear area attack
@@ -212,6 +241,7 @@ pub fn execute_ability_simple(
                 Faction::Military,
                 barricade_pos,
                 game_assets,
+                catalog,
             );
             play_tactical_sound("ability", "Barricade deployed! Defensive cover established");
         }
@@ -232,6 +262,7 @@ pub fn create_explosion_effect_simple(
     position: Vec3,
     radius: f32,
     damage: f32,
+    faction: &Faction,
     enemy_data: &[(Entity, Vec3, UnitType, f32)],
 ) {
     // Visual explosion effect
@@ -257,6 +288,9 @@ pub fn create_explosion_effect_simple(
             },
         ));
     }
+    queue_decal(commands, DecalKind::Scorch, position, radius / 60.0);
+    queue_camera_shake(commands, position, radius / 120.0);
+    queue_explosion_noise(commands, position, faction.clone());
 
     // Apply damage to enemies in range
     for (enemy_entity, enemy_position, _, enemy_health) in enemy_data.iter() {
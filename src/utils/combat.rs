@@ -1,5 +1,14 @@
 use crate::components::*;
-use crate::utils::play_tactical_sound;
+use crate::config::AccuracyBalance;
+use crate::damage::DamageEvent;
+use crate::diplomacy::Diplomacy;
+use crate::environmental_systems::{
+    has_night_vision, in_beam, is_lit, EnvironmentalState, LightCone,
+};
+use crate::map::CityMap;
+use crate::rules_of_engagement::{heavy_weapon, RoeLimits};
+use crate::sentiment::CivilianSentiment;
+use crate::utils::{spawn_pooled_text, spawn_pooled_tracer, EntityPools, FloatingText, PoolKind};
 use bevy::prelude::*;
 use rand::{thread_rng, Rng};
 
@@ -16,6 +25,19 @@ pub fn calculate_damage_modifier(weapon_type: &WeaponType) -> f32 {
     }
 }
 
+// How often a weapon hits a still target in the open, close in
+pub fn base_accuracy(weapon_type: &WeaponType) -> f32 {
+    match weapon_type {
+        WeaponType::CartelSniperRifle | WeaponType::MilitarySniperRifle => 0.95,
+        WeaponType::TacticalRifle => 0.9,
+        WeaponType::AssaultRifle | WeaponType::StandardIssue => 0.85,
+        WeaponType::BasicRifle | WeaponType::HelicopterWeapons => 0.8,
+        WeaponType::HeavyMachineGun | WeaponType::LMG | WeaponType::VehicleWeapons => 0.7,
+        WeaponType::RPG | WeaponType::TankCannon => 0.65,
+        WeaponType::MedicBag | WeaponType::EngineerTools => 0.6,
+    }
+}
+
 pub fn calculate_ability_damage_modifier(
     effect_option: Result<&AbilityEffect, bevy::ecs::query::QueryEntityError>,
 ) -> f32 {
@@ -59,9 +81,177 @@ pub fn update_veterancy_level(unit: &mut Unit) {
     };
 }
 
+const ARRIVAL_DISTANCE: f32 = 5.0; // As in movement_system
+
+// What a unit is doing that throws its aim off, or makes it harder to hit
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FiringStance {
+    pub moving: bool,
+    pub suppression: f32, // 0.0 to 1.0, from TacticalState
+}
+
+impl FiringStance {
+    pub fn of(
+        transform: &Transform,
+        movement: Option<&Movement>,
+        tactical_state: Option<&TacticalState>,
+    ) -> Self {
+        Self {
+            moving: movement
+                .and_then(|movement| movement.target_position)
+                .is_some_and(|target| target.distance(transform.translation) > ARRIVAL_DISTANCE),
+            suppression: tactical_state.map_or(0.0, |state| state.suppression_level),
+        }
+    }
+}
+
+// A shot's chance to hit, factor by factor: the weapon's own accuracy and a
+// multiplier for each thing working against it, 1.0 where it doesn't apply
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HitChance {
+    pub base: f32,
+    pub range: f32,
+    pub shooter_moving: f32,
+    pub target_moving: f32,
+    pub cover: f32,
+    pub suppression: f32,
+    pub darkness: f32,
+    pub gassed: f32,
+}
+
+impl HitChance {
+    pub fn total(&self, balance: &AccuracyBalance) -> f32 {
+        let chance = self.base
+            * self.range
+            * self.shooter_moving
+            * self.target_moving
+            * self.cover
+            * self.suppression
+            * self.darkness
+            * self.gassed;
+        chance.clamp(balance.min_hit_chance, 1.0)
+    }
+}
+
+/// Accuracy left at `distance` with `reach`: full up to falloff_start of the
+/// reach, then dropping evenly to lose falloff_at_reach at its edge.
+pub fn range_falloff(distance: f32, reach: f32, balance: &AccuracyBalance) -> f32 {
+    let share = distance / reach.max(f32::EPSILON);
+    let start = balance.falloff_start.clamp(0.0, 0.99);
+    let past = ((share - start) / (1.0 - start)).clamp(0.0, 1.0);
+    1.0 - balance.falloff_at_reach * past
+}
+
+// Weather, darkness, streetlights and flashlights, terrain, gas and smoke, the
+// locals and the army's rules of engagement as they affect one unit engaging
+// another
+pub struct CombatConditions<'a> {
+    pub environment: &'a EnvironmentalState,
+    pub streetlights: &'a [(Vec3, f32)],
+    pub beams: &'a [LightCone],
+    pub city_map: &'a CityMap,
+    pub diplomacy: &'a Diplomacy,
+    pub zones: &'a [AreaDenialZone],
+    pub sentiment: &'a CivilianSentiment,
+    pub hidden_sight: f32, // What the army sees of a neighborhood covering for the cartel
+    pub roe: RoeLimits,
+    pub civilians: &'a [Vec2],
+}
+
+impl CombatConditions<'_> {
+    fn night_modifiers(&self, unit: &Unit, target_position: Vec3) -> (f32, f32) {
+        self.environment.night_modifiers(
+            has_night_vision(unit),
+            is_lit(target_position, self.streetlights) || in_beam(target_position, self.beams),
+        )
+    }
+
+    // Weapon reach, cut short when weather or darkness hide the target, or
+    // the locals hide it from the army, or the army's rules hold it back
+    pub fn vision_range(&self, unit: &Unit, target_position: Vec3) -> f32 {
+        let sight = unit.vision
+            * self.environment.visibility_modifier
+            * self.night_modifiers(unit, target_position).0;
+        if unit.faction.side() == Faction::Military {
+            let sight = sight
+                * self.sentiment.army_sight(
+                    self.city_map,
+                    target_position.truncate(),
+                    self.hidden_sight,
+                );
+            unit.range.min(sight) * self.roe.range_scale
+        } else {
+            unit.range.min(sight)
+        }
+    }
+
+    // Tanks and helicopters kept off targets with civilians around them
+    pub fn holds_fire(&self, unit: &Unit, target_position: Vec3) -> bool {
+        unit.faction.side() == Faction::Military
+            && heavy_weapon(&unit.unit_type)
+            && !self
+                .roe
+                .clear_to_fire(target_position.truncate(), self.civilians)
+    }
+
+    // Gas or smoke from a burning barricade between the two
+    pub fn obscured(&self, from: Vec3, to: Vec3) -> bool {
+        self.zones
+            .iter()
+            .any(|zone| zone.blocks_sight(from.truncate(), to.truncate()))
+    }
+
+    pub fn in_sight(&self, unit: &Unit, from: Vec3, to: Vec3) -> bool {
+        from.distance(to) <= self.vision_range(unit, to) && !self.obscured(from, to)
+    }
+
+    // Standing in tear gas thrown by the other side
+    fn gassed(&self, unit: &Unit, position: Vec3) -> bool {
+        self.zones.iter().any(|zone| {
+            zone.kind == AreaDenialKind::TearGas
+                && zone.contains(position.truncate())
+                && zone.affects(&unit.faction, self.diplomacy)
+        })
+    }
+
+    pub fn hit_chance(
+        &self,
+        unit: &Unit,
+        (shooter_position, shooter): (Vec3, FiringStance),
+        (target_position, target): (Vec3, FiringStance),
+        balance: &AccuracyBalance,
+    ) -> HitChance {
+        let penalty = |applies: bool, amount: f32| if applies { 1.0 - amount } else { 1.0 };
+        HitChance {
+            base: base_accuracy(&unit.equipment.weapon),
+            range: range_falloff(
+                shooter_position.distance(target_position),
+                self.vision_range(unit, target_position),
+                balance,
+            ),
+            shooter_moving: penalty(shooter.moving, balance.moving_shooter),
+            target_moving: penalty(target.moving, balance.moving_target),
+            cover: 1.0 - balance.cover_weight * self.cover(target_position),
+            suppression: 1.0 - balance.suppression * shooter.suppression.clamp(0.0, 1.0),
+            darkness: self.night_modifiers(unit, target_position).1,
+            gassed: penalty(self.gassed(unit, shooter_position), balance.gassed),
+        }
+    }
+
+    // Share of incoming damage soaked by walls, cars and barriers around the target
+    pub fn cover(&self, target_position: Vec3) -> f32 {
+        self.city_map.cover_at(target_position.truncate())
+    }
+
+    // Only factions at war open fire on each other
+    pub fn hostile(&self, unit: &Unit, other: &Unit) -> bool {
+        self.diplomacy.is_hostile(&unit.faction, &other.faction)
+    }
+}
+
 pub fn find_combat_pairs(
     units: &[(Entity, &Unit, &Transform)],
-    visibility_modifier: f32,
+    conditions: &CombatConditions,
 ) -> Vec<(Entity, Entity, f32)> {
     let mut combat_events = Vec::new();
 
@@ -70,9 +260,6 @@ pub fn find_combat_pairs(
             continue;
         }
 
-        // Apply environmental visibility modifier to detection range
-        let effective_range_a = unit_a.range * visibility_modifier;
-
         // Try to attack assigned target first
         if let Some(target_entity) = unit_a.target {
             if let Some((_, target_unit, target_transform)) =
@@ -80,11 +267,13 @@ pub fn find_combat_pairs(
             {
                 // Check if target is valid (alive, enemy faction, in range)
                 if target_unit.health > 0.0
-                    && target_unit.faction != unit_a.faction
-                    && transform_a
-                        .translation
-                        .distance(target_transform.translation)
-                        <= effective_range_a
+                    && conditions.hostile(unit_a, target_unit)
+                    && !conditions.holds_fire(unit_a, target_transform.translation)
+                    && conditions.in_sight(
+                        unit_a,
+                        transform_a.translation,
+                        target_transform.translation,
+                    )
                 {
                     combat_events.push((*entity_a, target_entity, unit_a.damage));
                     continue; // Skip general combat for this unit
@@ -95,18 +284,29 @@ pub fn find_combat_pairs(
         // General combat - attack nearest enemy if no specific target
         for (entity_b, unit_b, transform_b) in units.iter().skip(i + 1) {
             // Only enemies can fight
-            if unit_a.faction == unit_b.faction || unit_b.health <= 0.0 {
+            if !conditions.hostile(unit_a, unit_b) || unit_b.health <= 0.0 {
+                continue;
+            }
+
+            // Gas and smoke hide each from the other
+            if conditions.obscured(transform_a.translation, transform_b.translation) {
                 continue;
             }
 
             let distance = transform_a.translation.distance(transform_b.translation);
-            let effective_range_b = unit_b.range * visibility_modifier;
+            let effective_range_a = conditions.vision_range(unit_a, transform_b.translation);
+            let effective_range_b = conditions.vision_range(unit_b, transform_a.translation);
 
-            // Check if units are in range to attack each other (weather reduces detection range)
-            if distance <= effective_range_a {
+            // Check if units are in range to attack each other (weather and darkness cut range)
+            if distance <= effective_range_a
+                && !conditions.holds_fire(unit_a, transform_b.translation)
+            {
                 combat_events.push((*entity_a, *entity_b, unit_a.damage));
             }
-            if distance <= effective_range_b && unit_b.attack_cooldown.finished() {
+            if distance <= effective_range_b
+                && unit_b.attack_cooldown.finished()
+                && !conditions.holds_fire(unit_b, transform_a.translation)
+            {
                 combat_events.push((*entity_b, *entity_a, unit_b.damage));
             }
         }
@@ -115,78 +315,34 @@ pub fn find_combat_pairs(
     combat_events
 }
 
-pub fn apply_combat_damage(
-    commands: &mut Commands,
+// Works out what a shot that connects does: weapon, ability and armor
+// modifiers, and the shooter's cooldown. The hit itself lands through the
+// damage pipeline.
+pub fn resolve_hit(
     attacker: Entity,
     target: Entity,
     base_damage: f32,
     unit_query: &mut Query<(Entity, &mut Unit, &Transform)>,
     effect_query: &Query<&AbilityEffect>,
-) -> bool {
-    // Get immutable data first
-    let (attacker_transform, attacker_weapon) =
-        if let Ok((_, unit, transform)) = unit_query.get(attacker) {
-            (transform.translation, unit.equipment.weapon.clone())
-        } else {
-            return false;
-        };
-
-    let target_transform = if let Ok((_, _, transform)) = unit_query.get(target) {
-        transform.translation
-    } else {
-        return false;
-    };
-
-    // Calculate damage modifiers
-    let damage_modifier = calculate_damage_modifier(&attacker_weapon);
-    let ability_damage_modifier = calculate_ability_damage_modifier(effect_query.get(attacker));
-    let final_damage = base_damage * damage_modifier * ability_damage_modifier;
-
-    // Update attacker cooldown and stats
-    if let Ok((_, mut attacker_unit, _)) = unit_query.get_mut(attacker) {
-        attacker_unit.attack_cooldown.reset();
+) -> Option<DamageEvent> {
+    if unit_query.get(target).is_err() {
+        return None;
     }
+    let (_, mut attacker_unit, _) = unit_query.get_mut(attacker).ok()?;
+    let weapon = attacker_unit.equipment.weapon.clone();
+    attacker_unit.attack_cooldown.reset();
 
-    // Apply damage to target (accounting for damage reduction effects)
-    let target_died = if let Ok((_, mut target_unit, _)) = unit_query.get_mut(target) {
-        let damage_reduction = calculate_damage_reduction(effect_query.get(target));
-        let reduced_damage = final_damage * damage_reduction;
-        target_unit.health -= reduced_damage;
-        let died = target_unit.health <= 0.0;
-
-        // Audio feedback
-        let weapon_sound = get_weapon_sound(&attacker_weapon);
-        play_tactical_sound(
-            weapon_sound,
-            &format!("Combat: {} damage dealt", reduced_damage as u32),
-        );
-
-        died
-    } else {
-        false
-    };
-
-    // Update attacker if target died
-    if target_died {
-        if let Ok((_, mut attacker_unit, _)) = unit_query.get_mut(attacker) {
-            attacker_unit.kills += 1;
-            attacker_unit.experience += 10;
-            update_veterancy_level(&mut attacker_unit);
-            play_tactical_sound(
-                "radio",
-                &format!(
-                    "{:?} gains experience from elimination",
-                    attacker_unit.unit_type
-                ),
-            );
-        }
-    }
-
-    // Create visual effects
-    spawn_damage_indicator(commands, target_transform, final_damage);
-    spawn_combat_particles(commands, attacker_transform, target_transform);
-
-    target_died
+    // Calculate damage modifiers, then what the target's effects soak up
+    let damage_modifier = calculate_damage_modifier(&weapon);
+    let ability_damage_modifier = calculate_ability_damage_modifier(effect_query.get(attacker));
+    let damage_reduction = calculate_damage_reduction(effect_query.get(target));
+
+    Some(DamageEvent {
+        attacker: Some(attacker),
+        target,
+        amount: base_damage * damage_modifier * ability_damage_modifier * damage_reduction,
+        weapon: Some(weapon),
+    })
 }
 
 pub fn clear_invalid_targets(unit_query: &mut Query<(Entity, &mut Unit, &Transform)>) {
@@ -205,7 +361,12 @@ pub fn clear_invalid_targets(unit_query: &mut Query<(Entity, &mut Unit, &Transfo
     }
 }
 
-pub fn spawn_damage_indicator(commands: &mut Commands, position: Vec3, damage: f32) {
+pub fn spawn_damage_indicator(
+    commands: &mut Commands,
+    entity_pools: &mut EntityPools,
+    position: Vec3,
+    damage: f32,
+) {
     // Determine color and size based on damage amount
     let (color, font_size) = if damage >= 50.0 {
         (Color::rgb(1.0, 0.2, 0.2), 28.0) // High damage - large red
@@ -219,31 +380,27 @@ pub fn spawn_damage_indicator(commands: &mut Commands, position: Vec3, damage: f
     let offset_x = thread_rng().gen_range(-10.0..10.0);
     let start_pos = position + Vec3::new(offset_x, 35.0, 1.0);
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                format!("-{}", damage as u32),
-                TextStyle {
-                    font_size,
-                    color,
-                    ..default()
-                },
-            ),
-            transform: Transform::from_translation(start_pos),
-            ..default()
-        },
-        DamageIndicator {
-            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-        },
-        // Add velocity for floating upward animation
-        ParticleEffect {
-            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
+    spawn_pooled_text(
+        commands,
+        entity_pools,
+        PoolKind::FloatingText,
+        FloatingText {
+            text: format!("-{}", damage as u32),
+            position: start_pos,
+            color,
+            font_size,
+            lifetime: 2.0,
             velocity: Vec3::new(0.0, 30.0, 0.0), // Float upward
         },
-    ));
+    );
 }
 
-pub fn spawn_combat_particles(commands: &mut Commands, attacker_pos: Vec3, target_pos: Vec3) {
+pub fn spawn_combat_particles(
+    commands: &mut Commands,
+    entity_pools: &mut EntityPools,
+    attacker_pos: Vec3,
+    target_pos: Vec3,
+) {
     let direction = (target_pos - attacker_pos).normalize();
     let distance = attacker_pos.distance(target_pos);
 
@@ -284,21 +441,20 @@ pub fn spawn_combat_particles(commands: &mut Commands, attacker_pos: Vec3, targe
             _ => Color::rgb(0.7, 0.7, 0.7), // Gray smoke
         };
 
-        commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color,
-                    custom_size: Some(Vec2::new(4.0, 4.0)),
-                    ..default()
-                },
-                transform: Transform::from_translation(particle_pos + Vec3::new(0.0, 0.0, 0.6)),
+        spawn_pooled_tracer(
+            commands,
+            entity_pools,
+            Sprite {
+                color,
+                custom_size: Some(Vec2::new(4.0, 4.0)),
                 ..default()
             },
+            particle_pos + Vec3::new(0.0, 0.0, 0.6),
             ParticleEffect {
                 lifetime: Timer::from_seconds(thread_rng().gen_range(0.2..0.5), TimerMode::Once),
                 velocity,
             },
-        ));
+        );
     }
 
     // Impact spark at target position
@@ -326,3 +482,60 @@ pub fn spawn_combat_particles(commands: &mut Commands, attacker_pos: Vec3, targe
         ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clear_shot() -> HitChance {
+        HitChance {
+            base: base_accuracy(&WeaponType::AssaultRifle),
+            range: 1.0,
+            shooter_moving: 1.0,
+            target_moving: 1.0,
+            cover: 1.0,
+            suppression: 1.0,
+            darkness: 1.0,
+            gassed: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_accuracy_falls_off_past_the_start_of_the_falloff() {
+        let balance = AccuracyBalance::default();
+        let reach = 200.0;
+        assert_eq!(range_falloff(0.0, reach, &balance), 1.0);
+        assert_eq!(
+            range_falloff(reach * balance.falloff_start, reach, &balance),
+            1.0
+        );
+        let at_edge = range_falloff(reach, reach, &balance);
+        assert!((at_edge - (1.0 - balance.falloff_at_reach)).abs() < 1e-6);
+        assert!(range_falloff(reach * 0.9, reach, &balance) > at_edge);
+    }
+
+    #[test]
+    fn test_every_penalty_lowers_the_chance_to_hit() {
+        let balance = AccuracyBalance::default();
+        let clear = clear_shot().total(&balance);
+        assert!(base_accuracy(&WeaponType::CartelSniperRifle) > clear);
+
+        let on_the_move = HitChance {
+            shooter_moving: 1.0 - balance.moving_shooter,
+            ..clear_shot()
+        };
+        let in_cover = HitChance {
+            cover: 1.0 - balance.cover_weight * 0.5,
+            ..clear_shot()
+        };
+        assert!(on_the_move.total(&balance) < clear);
+        assert!(in_cover.total(&balance) < clear);
+
+        // Never quite hopeless
+        let hopeless = HitChance {
+            base: 0.0,
+            ..clear_shot()
+        };
+        assert_eq!(hopeless.total(&balance), balance.min_hit_chance);
+    }
+}
@@ -0,0 +1,198 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// ==================== DATA DIRECTORIES ====================
+
+// Where the game keeps what it writes. Saves, profiles, telemetry and crash
+// reports go under the platform's data directory (~/.local/share on Linux,
+// %APPDATA% on Windows, ~/Library/Application Support on macOS) and the
+// config under its config directory, each in a culiacan-rts folder. With
+// --portable everything goes in a data folder beside the executable instead,
+// for running off a USB stick. Older builds wrote to ~/.culiacan-rts; the
+// first launch after an update moves those files across.
+
+const APP_DIR: &str = "culiacan-rts";
+const PORTABLE_DIR: &str = "data";
+const LEGACY_DIR: &str = ".culiacan-rts";
+
+static PORTABLE: AtomicBool = AtomicBool::new(false);
+
+/// What a directory holds, and so where it lives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataKind {
+    Saves,
+    Profiles,
+    Telemetry,
+    Crashes,
+    Config,
+}
+
+impl DataKind {
+    pub const ALL: [DataKind; 5] = [
+        DataKind::Saves,
+        DataKind::Profiles,
+        DataKind::Telemetry,
+        DataKind::Crashes,
+        DataKind::Config,
+    ];
+
+    /// The folder under the app directory; the config sits at its root.
+    fn folder(self) -> Option<&'static str> {
+        match self {
+            DataKind::Saves => Some("saves"),
+            DataKind::Profiles => Some("profiles"),
+            DataKind::Telemetry => Some("telemetry"),
+            DataKind::Crashes => Some("crashes"),
+            DataKind::Config => None,
+        }
+    }
+}
+
+/// Keeps everything beside the executable for the rest of the run.
+pub fn set_portable(portable: bool) {
+    PORTABLE.store(portable, Ordering::Relaxed);
+}
+
+pub fn is_portable() -> bool {
+    PORTABLE.load(Ordering::Relaxed)
+}
+
+/// The directory files of `kind` are read from and written to.
+pub fn data_dir(kind: DataKind) -> PathBuf {
+    let root = if is_portable() {
+        std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|dir| dir.join(PORTABLE_DIR)))
+    } else if kind == DataKind::Config {
+        dirs::config_dir().map(|dir| dir.join(APP_DIR))
+    } else {
+        dirs::data_dir().map(|dir| dir.join(APP_DIR))
+    };
+
+    match (root, kind.folder()) {
+        (Some(root), Some(folder)) => root.join(folder),
+        (Some(root), None) => root,
+        // Fallback to current directory
+        (None, Some(folder)) => PathBuf::from(folder),
+        (None, None) => PathBuf::new(),
+    }
+}
+
+/// Moves files left in ~/.culiacan-rts by older builds to where they live
+/// now, returning how many were moved. Anything already at the new location
+/// is left alone.
+pub fn migrate_legacy_data() -> io::Result<usize> {
+    let Some(legacy) = dirs::home_dir().map(|home| home.join(LEGACY_DIR)) else {
+        return Ok(0);
+    };
+    migrate_from(&legacy, data_dir)
+}
+
+fn migrate_from(legacy: &Path, target: impl Fn(DataKind) -> PathBuf) -> io::Result<usize> {
+    if !legacy.is_dir() {
+        return Ok(0);
+    }
+
+    let mut moved = 0;
+    for kind in DataKind::ALL {
+        let to = target(kind);
+        match kind.folder() {
+            Some(folder) => {
+                let from = legacy.join(folder);
+                if !from.is_dir() || from == to {
+                    continue;
+                }
+                for entry in fs::read_dir(&from)? {
+                    let entry = entry?;
+                    moved += move_entry(&entry.path(), &to.join(entry.file_name()))? as usize;
+                }
+                let _ = fs::remove_dir(&from); // Only goes if it was emptied
+            }
+            None => {
+                let from = legacy.join("config.json");
+                let to = to.join("config.json");
+                if from != to {
+                    moved += move_entry(&from, &to)? as usize;
+                }
+            }
+        }
+    }
+    let _ = fs::remove_dir(legacy);
+    Ok(moved)
+}
+
+fn move_entry(from: &Path, to: &Path) -> io::Result<bool> {
+    if !from.exists() || to.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_err() {
+        // Renames fail across drives, so copy and then remove the original
+        copy_all(from, to)?;
+        if from.is_dir() {
+            fs::remove_dir_all(from)?;
+        } else {
+            fs::remove_file(from)?;
+        }
+    }
+    Ok(true)
+}
+
+fn copy_all(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_all(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_files_move_across_without_overwriting_newer_ones() {
+        let root = std::env::temp_dir().join(format!("culiacan-data-test-{}", std::process::id()));
+        let legacy = root.join("legacy");
+        let new = root.join("new");
+        fs::create_dir_all(legacy.join("saves")).unwrap();
+        fs::create_dir_all(legacy.join("profiles/abc")).unwrap();
+        fs::write(legacy.join("saves/save_slot_1.json"), "old save").unwrap();
+        fs::write(legacy.join("saves/save_slot_2.json"), "old save").unwrap();
+        fs::write(legacy.join("profiles/abc/profile.json"), "profile").unwrap();
+        fs::write(legacy.join("config.json"), "config").unwrap();
+        fs::create_dir_all(new.join("saves")).unwrap();
+        fs::write(new.join("saves/save_slot_2.json"), "new save").unwrap();
+
+        let target = |kind: DataKind| match kind.folder() {
+            Some(folder) => new.join(folder),
+            None => new.clone(),
+        };
+        assert_eq!(migrate_from(&legacy, target).unwrap(), 3);
+        assert_eq!(
+            fs::read_to_string(new.join("saves/save_slot_1.json")).unwrap(),
+            "old save"
+        );
+        assert_eq!(
+            fs::read_to_string(new.join("saves/save_slot_2.json")).unwrap(),
+            "new save"
+        );
+        assert!(new.join("profiles/abc/profile.json").exists());
+        assert!(new.join("config.json").exists());
+
+        // The clashing save stays behind; a second run has nothing to do
+        assert!(legacy.join("saves/save_slot_2.json").exists());
+        assert_eq!(migrate_from(&legacy, target).unwrap(), 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::app_state::GameSet;
+use crate::components::{Unit, UnitType};
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+use std::collections::{HashMap, VecDeque};
+
+// ==================== DECALS ====================
+
+// Marks the fight leaves on the ground: bullet impacts, scorch marks, blood and
+// tire tracks. Every decal is an untextured sprite on the same z layer, so they
+// batch into one draw call. The layer is a ring buffer - once it is full the
+// oldest mark is moved to the new spot instead of spawning another entity.
+
+pub const DECAL_CAPACITY: usize = 600; // Marks kept on the ground unless a config profile says otherwise
+const DECAL_Z: f32 = -8.5; // On top of the map tiles, under props
+const TIRE_TRACK_SPACING: f32 = 14.0; // Distance a vehicle covers between track marks
+
+pub struct DecalPlugin;
+
+impl Plugin for DecalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DecalEvent>()
+            .init_resource::<DecalLayer>()
+            .add_systems(
+                Update,
+                (tire_track_system.in_set(GameSet::Session), decal_system).chain(),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecalKind {
+    BulletImpact,
+    Scorch,
+    Blood,
+    TireTrack,
+}
+
+impl DecalKind {
+    fn color(&self) -> Color {
+        match self {
+            DecalKind::BulletImpact => Color::rgba(0.1, 0.1, 0.1, 0.8),
+            DecalKind::Scorch => Color::rgba(0.05, 0.04, 0.03, 0.7),
+            DecalKind::Blood => Color::rgba(0.45, 0.02, 0.02, 0.75),
+            DecalKind::TireTrack => Color::rgba(0.08, 0.08, 0.08, 0.45),
+        }
+    }
+
+    fn size(&self) -> Vec2 {
+        match self {
+            DecalKind::BulletImpact => Vec2::splat(3.0),
+            DecalKind::Scorch => Vec2::splat(40.0),
+            DecalKind::Blood => Vec2::new(12.0, 9.0),
+            DecalKind::TireTrack => Vec2::new(TIRE_TRACK_SPACING, 10.0),
+        }
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DecalEvent {
+    pub kind: DecalKind,
+    pub position: Vec2,
+    pub rotation: Option<f32>, // Random when None
+    pub scale: f32,
+}
+
+// For helpers that only have Commands. Does nothing when no decal layer is
+// running, e.g. in headless simulations.
+pub fn queue_decal(commands: &mut Commands, kind: DecalKind, position: Vec3, scale: f32) {
+    let event = DecalEvent {
+        kind,
+        position: position.truncate(),
+        rotation: None,
+        scale,
+    };
+    commands.add(move |world: &mut World| {
+        if let Some(mut events) = world.get_resource_mut::<Events<DecalEvent>>() {
+            events.send(event);
+        }
+    });
+}
+
+#[derive(Component)]
+pub struct Decal {
+    pub kind: DecalKind,
+}
+
+#[derive(Resource)]
+pub struct DecalLayer {
+    pub capacity: usize,
+    entities: VecDeque<Entity>, // Oldest first
+}
+
+impl Default for DecalLayer {
+    fn default() -> Self {
+        Self::with_capacity(DECAL_CAPACITY)
+    }
+}
+
+impl DecalLayer {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entities: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // The oldest decal to recycle once the layer is full, None while there's room
+    pub fn recycle_oldest(&mut self) -> Option<Entity> {
+        if self.entities.len() >= self.capacity {
+            self.entities.pop_front()
+        } else {
+            None
+        }
+    }
+
+    pub fn push(&mut self, entity: Entity) {
+        self.entities.push_back(entity);
+    }
+
+    // Sets a new capacity, handing back the oldest marks that no longer fit
+    pub fn resize(&mut self, capacity: usize) -> Vec<Entity> {
+        self.capacity = capacity;
+        let excess = self.entities.len().saturating_sub(capacity);
+        self.entities.drain(..excess).collect()
+    }
+}
+
+pub fn decal_system(
+    mut commands: Commands,
+    mut layer: ResMut<DecalLayer>,
+    mut events: EventReader<DecalEvent>,
+    mut decal_query: Query<(&mut Decal, &mut Sprite, &mut Transform)>,
+) {
+    let mut rng = thread_rng();
+
+    for event in events.read() {
+        let rotation = event
+            .rotation
+            .unwrap_or_else(|| rng.gen_range(0.0..std::f32::consts::TAU));
+        let size = event.kind.size() * event.scale;
+        let transform = Transform::from_translation(event.position.extend(DECAL_Z))
+            .with_rotation(Quat::from_rotation_z(rotation));
+
+        if let Some(entity) = layer.recycle_oldest() {
+            if let Ok((mut decal, mut sprite, mut decal_transform)) = decal_query.get_mut(entity) {
+                decal.kind = event.kind;
+                sprite.color = event.kind.color();
+                sprite.custom_size = Some(size);
+                *decal_transform = transform;
+                layer.push(entity);
+                continue;
+            }
+            // Spawned earlier this frame and not queryable yet - replace it
+            commands.entity(entity).despawn();
+        }
+
+        let entity = commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: event.kind.color(),
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    transform,
+                    ..default()
+                },
+                Decal { kind: event.kind },
+            ))
+            .id();
+        layer.push(entity);
+    }
+}
+
+// Vehicles and tanks leave a trail of track marks as they move
+pub fn tire_track_system(
+    unit_query: Query<(Entity, &Unit, &Transform)>,
+    mut decal_events: EventWriter<DecalEvent>,
+    mut last_marks: Local<HashMap<Entity, Vec2>>,
+) {
+    last_marks.retain(|entity, _| unit_query.contains(*entity));
+
+    for (entity, unit, transform) in unit_query.iter() {
+        if !matches!(unit.unit_type, UnitType::Vehicle | UnitType::Tank) || unit.health <= 0.0 {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let last = *last_marks.entry(entity).or_insert(position);
+        let travelled = position - last;
+
+        if travelled.length() >= TIRE_TRACK_SPACING {
+            decal_events.send(DecalEvent {
+                kind: DecalKind::TireTrack,
+                position: last + travelled * 0.5,
+                rotation: Some(travelled.y.atan2(travelled.x)),
+                scale: if unit.unit_type == UnitType::Tank {
+                    1.6
+                } else {
+                    1.0
+                },
+            });
+            last_marks.insert(entity, position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decal_layer_recycles_oldest_when_full() {
+        let mut layer = DecalLayer::with_capacity(2);
+        let first = Entity::from_raw(1);
+        let second = Entity::from_raw(2);
+
+        assert_eq!(layer.recycle_oldest(), None);
+        layer.push(first);
+        assert_eq!(layer.recycle_oldest(), None);
+        layer.push(second);
+
+        // Full: the first mark is handed back for reuse, the layer never grows
+        assert_eq!(layer.recycle_oldest(), Some(first));
+        layer.push(first);
+        assert_eq!(layer.len(), 2);
+        assert_eq!(layer.recycle_oldest(), Some(second));
+
+        // Shrinking drops the oldest marks first
+        layer.push(second);
+        assert_eq!(layer.resize(1), vec![first]);
+        assert_eq!(layer.recycle_oldest(), Some(second));
+    }
+}
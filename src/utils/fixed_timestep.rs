@@ -0,0 +1,149 @@
+use crate::components::Unit;
+use crate::config::BalanceConfig;
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+type UntrackedUnitQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static Transform), (With<Unit>, Without<InterpolatedTransform>)>;
+
+// ==================== FIXED-TIMESTEP SIMULATION ====================
+
+// Gameplay systems run in FixedUpdate inside SimulationSet::Simulate, where
+// Res<Time> reports the fixed tick instead of the render frame delta. Units
+// are snapped back to their simulated position before each tick and the
+// rendered transform is interpolated between the last two ticks.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    Prepare,
+    Simulate,
+    Record,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InterpolatedTransform {
+    pub previous: Vec3,
+    pub current: Vec3,
+    rendered: Vec3,
+}
+
+impl InterpolatedTransform {
+    pub fn new(position: Vec3) -> Self {
+        Self {
+            previous: position,
+            current: position,
+            rendered: position,
+        }
+    }
+
+    pub fn lerp(&self, alpha: f32) -> Vec3 {
+        self.previous.lerp(self.current, alpha.clamp(0.0, 1.0))
+    }
+}
+
+pub struct FixedTimestepPlugin;
+
+impl Plugin for FixedTimestepPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            FixedUpdate,
+            (
+                SimulationSet::Prepare,
+                SimulationSet::Simulate,
+                SimulationSet::Record,
+            )
+                .chain(),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                (
+                    track_simulated_units_system,
+                    restore_simulated_transforms_system,
+                )
+                    .chain()
+                    .in_set(SimulationSet::Prepare),
+                record_simulated_transforms_system.in_set(SimulationSet::Record),
+            ),
+        )
+        .add_systems(Update, apply_tick_rate_system)
+        .add_systems(
+            PostUpdate,
+            interpolate_transforms_system.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+pub fn apply_tick_rate_system(balance: Res<BalanceConfig>, mut fixed_time: ResMut<Time<Fixed>>) {
+    if !balance.is_changed() {
+        return;
+    }
+
+    let tick_rate = balance.simulation.tick_rate.max(1.0) as f64;
+    let timestep = 1.0 / tick_rate;
+    if (fixed_time.timestep().as_secs_f64() - timestep).abs() > f64::EPSILON {
+        fixed_time.set_timestep_hz(tick_rate);
+        info!("⏱️ Simulation tick rate set to {} Hz", tick_rate);
+    }
+}
+
+fn track_simulated_units_system(mut commands: Commands, new_units: UntrackedUnitQuery) {
+    for (entity, transform) in new_units.iter() {
+        commands
+            .entity(entity)
+            .insert(InterpolatedTransform::new(transform.translation));
+    }
+}
+
+fn restore_simulated_transforms_system(
+    mut units: Query<(&mut Transform, &mut InterpolatedTransform)>,
+) {
+    for (mut transform, mut interpolated) in units.iter_mut() {
+        if transform.translation != interpolated.rendered {
+            // Moved outside the simulation (spawn placement, save load) - no smoothing
+            *interpolated = InterpolatedTransform::new(transform.translation);
+        } else {
+            transform.translation = interpolated.current;
+        }
+        interpolated.previous = interpolated.current;
+    }
+}
+
+fn record_simulated_transforms_system(mut units: Query<(&Transform, &mut InterpolatedTransform)>) {
+    for (transform, mut interpolated) in units.iter_mut() {
+        interpolated.current = transform.translation;
+        interpolated.rendered = transform.translation;
+    }
+}
+
+fn interpolate_transforms_system(
+    fixed_time: Res<Time<Fixed>>,
+    mut units: Query<(&mut Transform, &mut InterpolatedTransform)>,
+) {
+    let alpha = fixed_time.overstep_percentage();
+
+    for (mut transform, mut interpolated) in units.iter_mut() {
+        // Anything that moved the unit since the last tick wins over interpolation
+        if transform.translation != interpolated.rendered {
+            continue;
+        }
+        let position = interpolated.lerp(alpha);
+        transform.translation = position;
+        interpolated.rendered = position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lerp_between_ticks() {
+        let mut interpolated = InterpolatedTransform::new(Vec3::ZERO);
+        interpolated.current = Vec3::new(10.0, 0.0, 0.0);
+
+        assert_eq!(interpolated.lerp(0.0), Vec3::ZERO);
+        assert_eq!(interpolated.lerp(0.5), Vec3::new(5.0, 0.0, 0.0));
+        // Overstep past a full tick never extrapolates
+        assert_eq!(interpolated.lerp(1.5), Vec3::new(10.0, 0.0, 0.0));
+    }
+}
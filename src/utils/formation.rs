@@ -1,6 +1,6 @@
 use crate::components::{Faction, FormationType, UnitType};
 use bevy::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 // ==================== FORMATION UTILITIES ====================
 
@@ -21,8 +21,10 @@ pub fn calculate_formation_position(
         }
         FormationType::Circle => {
             let radius = 60.0;
-            let angle =
-                (position_in_formation as f32 / unit_count as f32) * std::f32::consts::PI * 2.0;
+            // A squad that just lost its last member still reports a count of 0
+            let angle = (position_in_formation as f32 / unit_count.max(1) as f32)
+                * std::f32::consts::PI
+                * 2.0;
             Vec3::new(angle.cos() * radius, angle.sin() * radius, 0.0)
         }
         FormationType::Wedge => {
@@ -47,6 +49,9 @@ pub fn calculate_formation_position(
             let spacing = 35.0;
             Vec3::new((position_in_formation as f32 - 1.0) * spacing, 20.0, 0.0)
         }
+        FormationType::Custom { offsets, .. } => {
+            custom_formation_offset(&offsets, position_in_formation)
+        }
     };
 
     // Rotate offset by formation facing direction
@@ -63,11 +68,12 @@ pub fn calculate_formation_position(
 pub fn calculate_formation_spacing(unit_count: usize, formation_type: FormationType) -> f32 {
     let base_spacing = match formation_type {
         FormationType::Line => 40.0,
-        FormationType::Circle => 60.0 / unit_count as f32,
+        FormationType::Circle => 60.0 / unit_count.max(1) as f32,
         FormationType::Wedge => 35.0,
         FormationType::Flanking => 50.0,
         FormationType::Overwatch => 50.0,
         FormationType::Retreat => 35.0,
+        FormationType::Custom { offsets, .. } => custom_formation_spacing(&offsets),
     };
 
     // Adjust spacing based on unit count to prevent overcrowding
@@ -75,6 +81,57 @@ pub fn calculate_formation_spacing(unit_count: usize, formation_type: FormationT
     base_spacing * crowding_factor
 }
 
+/// Offset of a unit in a player-saved formation. Units beyond the saved
+/// arrangement repeat it in further ranks behind.
+pub fn custom_formation_offset(offsets: &[[f32; 2]], position_in_formation: usize) -> Vec3 {
+    if offsets.is_empty() {
+        return Vec3::ZERO;
+    }
+
+    let [x, y] = offsets[position_in_formation % offsets.len()];
+    let rank = (position_in_formation / offsets.len()) as f32;
+    let (min_y, max_y) = offsets
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), [_, y]| {
+            (min.min(*y), max.max(*y))
+        });
+    let rank_depth = max_y - min_y + custom_formation_spacing(offsets);
+    Vec3::new(x, y - rank * rank_depth, 0.0)
+}
+
+/// Average distance from each saved position to its nearest neighbour
+fn custom_formation_spacing(offsets: &[[f32; 2]]) -> f32 {
+    if offsets.len() < 2 {
+        return 40.0;
+    }
+
+    let total: f32 = offsets
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            offsets
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, b)| Vec2::from(*a).distance(Vec2::from(*b)))
+                .fold(f32::MAX, f32::min)
+        })
+        .sum();
+    (total / offsets.len() as f32).max(1.0)
+}
+
+/// Arrangement of a group relative to its center, front (highest y) first,
+/// for saving as a custom formation
+pub fn capture_formation_offsets(positions: &[Vec3]) -> Vec<[f32; 2]> {
+    let center = find_optimal_formation_center(positions);
+    let mut offsets: Vec<[f32; 2]> = positions
+        .iter()
+        .map(|position| (*position - center).truncate().to_array())
+        .collect();
+    offsets.sort_by(|a, b| b[1].total_cmp(&a[1]).then(a[0].total_cmp(&b[0])));
+    offsets
+}
+
 /// Find optimal formation center for a group of units
 pub fn find_optimal_formation_center(positions: &[Vec3]) -> Vec3 {
     if positions.is_empty() {
@@ -92,7 +149,11 @@ pub fn calculate_flanking_position(
     obstacles: &[Vec3],
     preferred_distance: f32,
 ) -> Vec3 {
-    let to_target = (target_pos - attacker_pos).normalize();
+    // Flanks lie on the map plane; an attacker on top of the target picks any side
+    let to_target = (target_pos - attacker_pos)
+        .truncate()
+        .try_normalize()
+        .unwrap_or(Vec2::Y);
     let right_flank = Vec3::new(-to_target.y, to_target.x, 0.0);
     let left_flank = Vec3::new(to_target.y, -to_target.x, 0.0);
 
@@ -121,12 +182,13 @@ pub fn calculate_retreat_position(
     unit_pos: Vec3,
     threat_positions: &[Vec3],
     retreat_distance: f32,
+    rng: &mut impl Rng,
 ) -> Vec3 {
     if threat_positions.is_empty() {
         return unit_pos
             + Vec3::new(
-                thread_rng().gen_range(-retreat_distance..retreat_distance),
-                thread_rng().gen_range(-retreat_distance..retreat_distance),
+                rng.gen_range(-retreat_distance..retreat_distance),
+                rng.gen_range(-retreat_distance..retreat_distance),
                 0.0,
             );
     }
@@ -137,7 +199,9 @@ pub fn calculate_retreat_position(
         .min_by_key(|&&pos| (unit_pos.distance(pos) * 1000.0) as i32)
         .unwrap();
 
-    let escape_direction = (unit_pos - *closest_threat).normalize();
+    let escape_direction = (unit_pos - *closest_threat)
+        .try_normalize()
+        .unwrap_or(Vec3::X);
     let retreat_pos = unit_pos + escape_direction * retreat_distance;
 
     // Avoid moving too close to other threats
@@ -155,7 +219,10 @@ pub fn avoid_threat_clusters(
     for &threat_pos in threat_positions {
         let distance = adjusted_pos.distance(threat_pos);
         if distance < avoidance_radius {
-            let push_direction = (adjusted_pos - threat_pos).normalize();
+            // Standing right on a threat still gets pushed out, just in an arbitrary direction
+            let push_direction = (adjusted_pos - threat_pos)
+                .try_normalize()
+                .unwrap_or(Vec3::X);
             let push_strength = avoidance_radius - distance;
             adjusted_pos += push_direction * push_strength;
         }
@@ -227,7 +294,7 @@ pub fn calculate_formation_facing(
     threat_positions: &[Vec3],
 ) -> f32 {
     if let Some(objective) = objective_pos {
-        let to_objective = (objective - formation_center).normalize();
+        let to_objective = objective - formation_center;
         to_objective.y.atan2(to_objective.x)
     } else if !threat_positions.is_empty() {
         // Face towards the nearest threat
@@ -235,7 +302,7 @@ pub fn calculate_formation_facing(
             .iter()
             .min_by_key(|&&pos| (formation_center.distance(pos) * 1000.0) as i32)
             .unwrap();
-        let to_threat = (*nearest_threat - formation_center).normalize();
+        let to_threat = *nearest_threat - formation_center;
         to_threat.y.atan2(to_threat.x)
     } else {
         0.0 // Default facing north
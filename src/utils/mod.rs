@@ -1,14 +1,20 @@
 // ==================== UTILITY MODULES ====================
 
-use bevy::log::info;
+use bevy::log::debug;
 
 pub mod abilities;
 pub mod ai_optimizer;
 pub mod combat;
+pub mod data_dirs;
+pub mod decals;
+pub mod fixed_timestep;
 pub mod formation;
 pub mod particle_pool;
 pub mod particles;
+pub mod screen_feedback;
+pub mod sim_rng;
 pub mod spatial;
+pub mod sprite_atlas;
 pub mod ui_builders;
 pub mod unit_queries;
 
@@ -16,59 +22,107 @@ pub mod unit_queries;
 pub use abilities::*;
 pub use ai_optimizer::*;
 pub use combat::*;
+pub use data_dirs::*;
+pub use decals::*;
+pub use fixed_timestep::*;
 pub use formation::*;
 pub use particle_pool::*;
 pub use particles::*;
+pub use screen_feedback::*;
+pub use sim_rng::*;
 pub use spatial::*;
+pub use sprite_atlas::*;
 pub use ui_builders::*;
 pub use unit_queries::*;
 
 // ==================== SHARED UTILITY FUNCTIONS ====================
 
 use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+// ==================== TACTICAL SOUND QUEUE ====================
+
+// Gameplay helpers call play_tactical_sound without access to the audio
+// resources, so requests are queued here and played by tactical_sound_system.
+const MAX_QUEUED_TACTICAL_SOUNDS: usize = 64; // Headless runs never drain the queue
+
+#[derive(Clone, Debug)]
+pub struct TacticalSoundRequest {
+    pub sound_type: String,
+    pub message: String,
+    pub position: Option<Vec3>,
+}
+
+static TACTICAL_SOUND_QUEUE: Mutex<VecDeque<TacticalSoundRequest>> = Mutex::new(VecDeque::new());
+
+fn queue_tactical_sound(sound_type: &str, message: &str, position: Option<Vec3>) {
+    if let Ok(mut queue) = TACTICAL_SOUND_QUEUE.lock() {
+        if queue.len() >= MAX_QUEUED_TACTICAL_SOUNDS {
+            queue.pop_front();
+        }
+        queue.push_back(TacticalSoundRequest {
+            sound_type: sound_type.to_string(),
+            message: message.to_string(),
+            position,
+        });
+    }
+}
+
+pub fn drain_tactical_sounds() -> Vec<TacticalSoundRequest> {
+    TACTICAL_SOUND_QUEUE
+        .lock()
+        .map(|mut queue| queue.drain(..).collect())
+        .unwrap_or_default()
+}
 
 pub fn play_tactical_sound(sound_type: &str, message: &str) {
-    // Console-based tactical audio system for atmospheric feedback
-    // This is now a fallback system for when the enhanced audio system is not available
+    // The debug line doubles as the fallback when audio files are missing;
+    // it stays out of the default log so real diagnostics aren't buried
     match sound_type {
-        "radio" => info!("📻 [RADIO] {message}"),
-        "gunfire" => info!("🔫 [GUNFIRE] {message}"),
-        "explosion" => info!("💥 [EXPLOSION] {message}"),
-        "vehicle" => info!("🚗 [VEHICLE] {message}"),
-        "ability" => info!("⚡ [ABILITY] {message}"),
-        _ => info!("🔊 [AUDIO] {message}"),
+        "radio" => debug!("📻 [RADIO] {message}"),
+        "gunfire" => debug!("🔫 [GUNFIRE] {message}"),
+        "explosion" => debug!("💥 [EXPLOSION] {message}"),
+        "vehicle" => debug!("🚗 [VEHICLE] {message}"),
+        "ability" => debug!("⚡ [ABILITY] {message}"),
+        _ => debug!("🔊 [AUDIO] {message}"),
     }
+    queue_tactical_sound(sound_type, message, None);
 }
 
-pub fn play_tactical_sound_at_position(sound_type: &str, message: &str, _position: Vec3) {
-    // Enhanced version that includes position information
-    // For now, fallback to console output
+pub fn play_tactical_sound_at_position(sound_type: &str, message: &str, position: Vec3) {
+    // Same as play_tactical_sound, but panned and attenuated relative to the camera
     match sound_type {
-        "radio" => info!(
+        "radio" => debug!(
             "📻 [RADIO] {} (at {:.1}, {:.1})",
-            message, _position.x, _position.y
+            message, position.x, position.y
         ),
-        "gunfire" => info!(
+        "gunfire" => debug!(
             "🔫 [GUNFIRE] {} (at {:.1}, {:.1})",
-            message, _position.x, _position.y
+            message, position.x, position.y
         ),
-        "explosion" => info!(
+        "explosion" => debug!(
             "💥 [EXPLOSION] {} (at {:.1}, {:.1})",
-            message, _position.x, _position.y
+            message, position.x, position.y
         ),
-        "vehicle" => info!(
+        "vehicle" => debug!(
             "🚗 [VEHICLE] {} (at {:.1}, {:.1})",
-            message, _position.x, _position.y
+            message, position.x, position.y
         ),
-        "ability" => info!(
+        "helicopter" => debug!(
+            "🚁 [HELICOPTER] {} (at {:.1}, {:.1})",
+            message, position.x, position.y
+        ),
+        "ability" => debug!(
             "⚡ [ABILITY] {} (at {:.1}, {:.1})",
-            message, _position.x, _position.y
+            message, position.x, position.y
         ),
-        _ => info!(
+        _ => debug!(
             "🔊 [AUDIO] {} (at {:.1}, {:.1})",
-            message, _position.x, _position.y
+            message, position.x, position.y
         ),
     }
+    queue_tactical_sound(sound_type, message, Some(position));
 }
 
 pub fn world_to_iso(world_pos: Vec3) -> Vec3 {
@@ -78,6 +132,17 @@ pub fn world_to_iso(world_pos: Vec3) -> Vec3 {
     Vec3::new(iso_x, iso_y, world_pos.z)
 }
 
+pub fn iso_to_world(iso_pos: Vec3) -> Vec3 {
+    // Inverse of world_to_iso, for respawning units at a stored screen position
+    let sum = iso_pos.y / 0.5; // x + y
+    let difference = iso_pos.x / 0.866; // x - y
+    Vec3::new(
+        (sum + difference) * 0.5,
+        (sum - difference) * 0.5,
+        iso_pos.z,
+    )
+}
+
 // ==================== MISSING UTILITY FUNCTIONS ====================
 
 use crate::components::{Faction, Unit};
@@ -122,9 +187,12 @@ pub fn calculate_unit_ratio(
 }
 
 pub fn calculate_flanking_position(unit_pos: Vec3, target_pos: Vec3, distance: f32) -> Vec3 {
-    // Calculate a flanking position perpendicular to the unit-target line
-    let direction = (target_pos - unit_pos).normalize();
-    let perpendicular = Vec3::new(-direction.z, direction.y, direction.x);
+    // Calculate a flanking position perpendicular to the unit-target line on the map plane
+    let direction = (target_pos - unit_pos)
+        .truncate()
+        .try_normalize()
+        .unwrap_or(Vec2::Y);
+    let perpendicular = Vec3::new(-direction.y, direction.x, 0.0);
     target_pos + perpendicular * distance
 }
 
@@ -3,17 +3,17 @@ use bevy::prelude::*;
 use std::collections::VecDeque;
 
 // Type aliases to reduce complexity
-type PooledParticleQuery<'a> = Query<
-    'a,
-    'a,
+type PooledParticleQuery<'w, 's> = Query<
+    'w,
+    's,
     (
         Entity,
-        &'a mut Transform,
-        &'a mut PooledParticle,
-        &'a ParticleVelocity,
-        &'a mut Visibility,
-        Option<&'a mut Sprite>,
-        Option<&'a mut Text>,
+        &'static mut Transform,
+        &'static mut PooledParticle,
+        &'static ParticleVelocity,
+        &'static mut Visibility,
+        Option<&'static mut Sprite>,
+        Option<&'static mut Text>,
     ),
 >;
 
@@ -266,6 +266,189 @@ pub fn spawn_optimized_damage_numbers(
     }
 }
 
+// ==================== GENERIC ENTITY POOLS ====================
+
+// Free list for one kind of short-lived entity. Released entities stay
+// spawned but hidden, and are reconfigured by the caller on reuse.
+pub struct EntityPool {
+    free: VecDeque<Entity>,
+    in_use: usize,
+    max_size: usize,
+}
+
+impl EntityPool {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            free: VecDeque::new(),
+            in_use: 0,
+            max_size,
+        }
+    }
+
+    // Reuses a free entity or spawns one. Returns None once max_size entities
+    // are in use, so callers drop the effect instead of growing the world.
+    pub fn acquire(
+        &mut self,
+        commands: &mut Commands,
+        spawn: impl FnOnce(&mut Commands) -> Entity,
+    ) -> Option<Entity> {
+        if self.in_use >= self.max_size {
+            return None;
+        }
+
+        // Entities can be despawned behind the pool's back (e.g. with a parent)
+        let reused = std::iter::from_fn(|| self.free.pop_front())
+            .find(|entity| commands.get_entity(*entity).is_some());
+        let entity = reused.unwrap_or_else(|| spawn(commands));
+
+        self.in_use += 1;
+        Some(entity)
+    }
+
+    pub fn release(&mut self, entity: Entity) {
+        if self.free.len() < self.max_size {
+            self.free.push_back(entity);
+        }
+        self.in_use = self.in_use.saturating_sub(1);
+    }
+
+    // Lowering the limit leaves entities already out alone; new requests are
+    // refused until enough of them come back
+    pub fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+    }
+
+    pub fn in_use(&self) -> usize {
+        self.in_use
+    }
+
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+}
+
+// Unbounded, for entities that must always be available
+impl Default for EntityPool {
+    fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PoolKind {
+    FloatingText, // Damage numbers
+    Tracer,       // Bullet trail sprites
+    Indicator,    // Intel markers over the map
+}
+
+// Marks an entity owned by EntityPools; systems that expire it release it
+// back to its pool instead of despawning
+#[derive(Component)]
+pub struct PooledEntity {
+    pub kind: PoolKind,
+    pub active: bool,
+}
+
+#[derive(Resource)]
+pub struct EntityPools {
+    pub floating_text: EntityPool,
+    pub tracers: EntityPool,
+    pub indicators: EntityPool,
+}
+
+impl Default for EntityPools {
+    fn default() -> Self {
+        Self {
+            floating_text: EntityPool::new(128),
+            tracers: EntityPool::new(512),
+            indicators: EntityPool::new(32),
+        }
+    }
+}
+
+impl EntityPools {
+    pub fn pool_mut(&mut self, kind: PoolKind) -> &mut EntityPool {
+        match kind {
+            PoolKind::FloatingText => &mut self.floating_text,
+            PoolKind::Tracer => &mut self.tracers,
+            PoolKind::Indicator => &mut self.indicators,
+        }
+    }
+
+    pub fn release(&mut self, entity: Entity, pooled: &mut PooledEntity) {
+        // Guard against double release when two systems expire the same entity
+        if pooled.active {
+            pooled.active = false;
+            self.pool_mut(pooled.kind).release(entity);
+        }
+    }
+}
+
+pub struct FloatingText {
+    pub text: String,
+    pub position: Vec3,
+    pub color: Color,
+    pub font_size: f32,
+    pub lifetime: f32,
+    pub velocity: Vec3,
+}
+
+// Floating text is a DamageIndicator driven by damage_indicator_system
+pub fn spawn_pooled_text(
+    commands: &mut Commands,
+    pools: &mut EntityPools,
+    kind: PoolKind,
+    floating_text: FloatingText,
+) -> Option<Entity> {
+    let entity = pools.pool_mut(kind).acquire(commands, |commands| {
+        commands.spawn(Text2dBundle::default()).id()
+    })?;
+
+    commands.entity(entity).insert((
+        Text::from_section(
+            floating_text.text,
+            TextStyle {
+                font_size: floating_text.font_size,
+                color: floating_text.color,
+                ..default()
+            },
+        ),
+        Transform::from_translation(floating_text.position),
+        Visibility::Visible,
+        DamageIndicator {
+            lifetime: Timer::from_seconds(floating_text.lifetime, TimerMode::Once),
+        },
+        ParticleVelocity(floating_text.velocity),
+        PooledEntity { kind, active: true },
+    ));
+    Some(entity)
+}
+
+// Tracers are ParticleEffect sprites driven by particle_system
+pub fn spawn_pooled_tracer(
+    commands: &mut Commands,
+    pools: &mut EntityPools,
+    sprite: Sprite,
+    position: Vec3,
+    effect: ParticleEffect,
+) -> Option<Entity> {
+    let entity = pools.tracers.acquire(commands, |commands| {
+        commands.spawn(SpriteBundle::default()).id()
+    })?;
+
+    commands.entity(entity).insert((
+        sprite,
+        Transform::from_translation(position),
+        Visibility::Visible,
+        effect,
+        PooledEntity {
+            kind: PoolKind::Tracer,
+            active: true,
+        },
+    ));
+    Some(entity)
+}
+
 // Resource initialization system
 pub fn setup_particle_pool(mut commands: Commands) {
     commands.insert_resource(ParticlePool::default());
@@ -274,3 +457,50 @@ pub fn setup_particle_pool(mut commands: Commands) {
         ParticlePool::default().max_pool_size
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::CommandQueue;
+
+    #[test]
+    fn test_entity_pool_reuses_released_entities() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut pool = EntityPool::new(2);
+
+        let first = pool.acquire(&mut commands, |commands| commands.spawn_empty().id());
+        let second = pool.acquire(&mut commands, |commands| commands.spawn_empty().id());
+        assert!(first.is_some() && second.is_some());
+        // Exhausted until something is released
+        assert!(pool
+            .acquire(&mut commands, |commands| commands.spawn_empty().id())
+            .is_none());
+
+        pool.release(first.unwrap());
+        let reused = pool.acquire(&mut commands, |_| panic!("should reuse"));
+        assert_eq!(reused, first);
+        assert_eq!(pool.in_use(), 2);
+
+        queue.apply(&mut world);
+    }
+
+    #[test]
+    fn test_entity_pool_skips_despawned_entities() {
+        let mut world = World::new();
+        let despawned = world.spawn_empty().id();
+        world.despawn(despawned);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut pool = EntityPool::default();
+        pool.release(despawned);
+
+        let entity = pool
+            .acquire(&mut commands, |commands| commands.spawn_empty().id())
+            .unwrap();
+        assert_ne!(entity, despawned);
+        assert_eq!(pool.free_count(), 0);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::app_state::GameSet;
+use crate::components::{IsometricCamera, Unit};
+use crate::profile::ProfileManager;
+use crate::ui::camera_control_system;
+use bevy::prelude::*;
+
+// ==================== SCREEN FEEDBACK ====================
+
+// Camera shake for nearby explosions and a short flash on units that take a
+// hit. Gameplay code only sends events; the camera and sprite systems here
+// consume them, and do nothing but drain them while the player has screen
+// effects turned off in their profile settings.
+
+const SHAKE_RANGE: f32 = 600.0; // Explosions further from the view center than this don't shake
+const MAX_SHAKE_OFFSET: f32 = 12.0; // World units at full trauma and 1x zoom
+const TRAUMA_DECAY: f32 = 1.8; // Per second
+const HIT_FLASH_SECONDS: f32 = 0.1;
+const HIT_FLASH_COLOR: Color = Color::rgb(1.0, 0.35, 0.35);
+// With reduced motion on: a fraction of the shake and a faint tint
+const REDUCED_SHAKE_SCALE: f32 = 0.3;
+const REDUCED_HIT_FLASH_COLOR: Color = Color::rgb(1.0, 0.8, 0.8);
+
+pub struct ScreenFeedbackPlugin;
+
+impl Plugin for ScreenFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CameraShakeEvent>()
+            .add_event::<HitFlashEvent>()
+            .init_resource::<ScreenFeedbackSettings>()
+            .init_resource::<CameraShake>()
+            .add_systems(
+                Update,
+                (
+                    screen_feedback_hotkey_system,
+                    apply_screen_feedback_settings_system,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    camera_shake_system.after(camera_control_system),
+                    hit_flash_system,
+                )
+                    .after(apply_screen_feedback_settings_system)
+                    .in_set(GameSet::Session),
+            );
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CameraShakeEvent {
+    pub origin: Vec2,
+    pub strength: f32, // Trauma added at point blank, 0.0 to 1.0
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HitFlashEvent {
+    pub target: Entity,
+}
+
+// For helpers that only have Commands. Does nothing when the feedback layer
+// isn't running, e.g. in headless simulations.
+pub fn queue_camera_shake(commands: &mut Commands, origin: Vec3, strength: f32) {
+    let event = CameraShakeEvent {
+        origin: origin.truncate(),
+        strength,
+    };
+    commands.add(move |world: &mut World| {
+        if let Some(mut events) = world.get_resource_mut::<Events<CameraShakeEvent>>() {
+            events.send(event);
+        }
+    });
+}
+
+pub fn queue_hit_flash(commands: &mut Commands, target: Entity) {
+    commands.add(move |world: &mut World| {
+        if let Some(mut events) = world.get_resource_mut::<Events<HitFlashEvent>>() {
+            events.send(HitFlashEvent { target });
+        }
+    });
+}
+
+#[derive(Resource)]
+pub struct ScreenFeedbackSettings {
+    pub enabled: bool,
+    pub reduced: bool, // Accessibility reduced motion
+}
+
+impl Default for ScreenFeedbackSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reduced: false,
+        }
+    }
+}
+
+impl ScreenFeedbackSettings {
+    fn shake_scale(&self) -> f32 {
+        if self.reduced {
+            REDUCED_SHAKE_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    fn flash_color(&self) -> Color {
+        if self.reduced {
+            REDUCED_HIT_FLASH_COLOR
+        } else {
+            HIT_FLASH_COLOR
+        }
+    }
+}
+
+// Copies the active profile's screen effects toggle and reduced motion option
+pub fn apply_screen_feedback_settings_system(
+    profiles: Res<ProfileManager>,
+    mut settings: ResMut<ScreenFeedbackSettings>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    if let Some(profile) = profiles.active.as_ref() {
+        settings.enabled = profile.settings.screen_effects;
+        settings.reduced = profile.settings.accessibility.reduced_motion;
+    }
+}
+
+// F4: toggle screen effects and keep the choice in the profile
+pub fn screen_feedback_hotkey_system(
+    input: Res<Input<KeyCode>>,
+    mut profiles: ResMut<ProfileManager>,
+    mut settings: ResMut<ScreenFeedbackSettings>,
+) {
+    if !input.just_pressed(KeyCode::F4) {
+        return;
+    }
+
+    settings.enabled = !settings.enabled;
+    info!(
+        "Screen effects {}",
+        if settings.enabled { "on" } else { "off" }
+    );
+    if let Some(profile) = profiles.active.as_mut() {
+        profile.settings.screen_effects = settings.enabled;
+        profiles.save_active();
+    }
+}
+
+// ==================== CAMERA SHAKE ====================
+
+#[derive(Resource, Default)]
+pub struct CameraShake {
+    pub trauma: f32,
+    applied: Vec2, // Offset added to the camera last frame
+    elapsed: f32,
+}
+
+// Trauma an explosion adds, fading linearly to nothing at SHAKE_RANGE
+pub fn shake_trauma(strength: f32, distance: f32) -> f32 {
+    strength * (1.0 - distance / SHAKE_RANGE).clamp(0.0, 1.0)
+}
+
+pub fn camera_shake_system(
+    settings: Res<ScreenFeedbackSettings>,
+    mut shake: ResMut<CameraShake>,
+    mut shake_events: EventReader<CameraShakeEvent>,
+    mut camera_query: Query<&mut Transform, With<IsometricCamera>>,
+    time: Res<Time>,
+) {
+    let Ok(mut transform) = camera_query.get_single_mut() else {
+        shake_events.clear();
+        return;
+    };
+
+    // Take last frame's shake back out so panning works from the real position
+    transform.translation -= shake.applied.extend(0.0);
+    shake.applied = Vec2::ZERO;
+
+    if !settings.enabled {
+        shake_events.clear();
+        shake.trauma = 0.0;
+        return;
+    }
+
+    let view_center = transform.translation.truncate();
+    for event in shake_events.read() {
+        let trauma = shake_trauma(event.strength, view_center.distance(event.origin))
+            * settings.shake_scale();
+        shake.trauma = (shake.trauma + trauma).min(1.0);
+    }
+    if shake.trauma <= 0.0 {
+        return;
+    }
+
+    // Squared trauma keeps small knocks subtle; layered sines give a smooth jitter
+    shake.elapsed += time.delta_seconds();
+    let t = shake.elapsed;
+    let jitter = Vec2::new(
+        (t * 47.0).sin() + (t * 23.0).cos() * 0.5,
+        (t * 53.0).cos() + (t * 29.0).sin() * 0.5,
+    ) / 1.5;
+    let offset = jitter * MAX_SHAKE_OFFSET * shake.trauma.powi(2) * transform.scale.x;
+
+    transform.translation += offset.extend(0.0);
+    shake.applied = offset;
+    shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_seconds()).max(0.0);
+}
+
+// ==================== HIT FLASH ====================
+
+#[derive(Component)]
+pub struct HitFlash {
+    timer: Timer,
+    pub original: Color, // Tint to restore once the flash is over
+}
+
+// Type aliases to reduce complexity
+type FlashingSpriteQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Option<&'static Unit>,
+        Option<&'static mut Sprite>,
+        Option<&'static mut TextureAtlasSprite>,
+        &'static mut HitFlash,
+    ),
+>;
+type SpriteColorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static mut Sprite>,
+        Option<&'static mut TextureAtlasSprite>,
+    ),
+    Without<HitFlash>,
+>;
+
+pub fn hit_flash_system(
+    mut commands: Commands,
+    settings: Res<ScreenFeedbackSettings>,
+    mut flash_events: EventReader<HitFlashEvent>,
+    mut flashing_query: FlashingSpriteQuery,
+    mut sprite_query: SpriteColorQuery,
+    time: Res<Time>,
+) {
+    if settings.enabled {
+        for event in flash_events.read() {
+            // A second hit mid-flash just extends it
+            if let Ok((_, _, _, _, mut flash)) = flashing_query.get_mut(event.target) {
+                flash.timer.reset();
+                continue;
+            }
+            let Ok((sprite, atlas_sprite)) = sprite_query.get_mut(event.target) else {
+                continue;
+            };
+
+            let flash_color = settings.flash_color();
+            let original = match (sprite, atlas_sprite) {
+                (Some(mut sprite), _) => std::mem::replace(&mut sprite.color, flash_color),
+                (None, Some(mut atlas_sprite)) => {
+                    std::mem::replace(&mut atlas_sprite.color, flash_color)
+                }
+                (None, None) => continue,
+            };
+            commands.entity(event.target).insert(HitFlash {
+                timer: Timer::from_seconds(HIT_FLASH_SECONDS, TimerMode::Once),
+                original,
+            });
+        }
+    } else {
+        flash_events.clear();
+    }
+
+    for (entity, unit, sprite, atlas_sprite, mut flash) in flashing_query.iter_mut() {
+        flash.timer.tick(time.delta());
+        let dead = unit.is_some_and(|unit| unit.health <= 0.0);
+        if !flash.timer.finished() && settings.enabled && !dead {
+            continue;
+        }
+
+        // Dead units leave their tint to the corpse fade
+        if !dead {
+            if let Some(mut sprite) = sprite {
+                sprite.color = flash.original;
+            }
+            if let Some(mut atlas_sprite) = atlas_sprite {
+                atlas_sprite.color = flash.original;
+            }
+        }
+        commands.entity(entity).remove::<HitFlash>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shake_trauma_falls_off_with_distance() {
+        assert_eq!(shake_trauma(0.8, 0.0), 0.8);
+        assert!(shake_trauma(0.8, 100.0) > shake_trauma(0.8, 400.0));
+        assert_eq!(shake_trauma(0.8, SHAKE_RANGE), 0.0);
+        assert_eq!(shake_trauma(0.8, SHAKE_RANGE * 2.0), 0.0);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::resources::GameState;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::collections::HashMap;
+use std::env;
+
+// ==================== DETERMINISTIC SIMULATION RNG ====================
+
+// Every gameplay subsystem draws from its own stream so adding a roll in one
+// system doesn't shift the sequence seen by the others. Purely cosmetic
+// randomness (particles, damage number jitter) stays on thread_rng.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    Ai,
+    Coordination,
+    Intel,
+    Political,
+    Spawning,
+    Environment,
+    Combat,
+}
+
+impl RngStream {
+    pub const ALL: [RngStream; 7] = [
+        RngStream::Ai,
+        RngStream::Coordination,
+        RngStream::Intel,
+        RngStream::Political,
+        RngStream::Spawning,
+        RngStream::Environment,
+        RngStream::Combat,
+    ];
+}
+
+#[derive(Resource)]
+pub struct SimRng {
+    seed: u64,
+    streams: HashMap<RngStream, StdRng>,
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        Self::new(new_sim_seed())
+    }
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Restart every stream from a new seed, e.g. after loading a save. Only
+    // the seed is saved, not how far each stream had got, so a loaded game
+    // replays the rolls from the start of the mission rather than continuing
+    // the original run's sequence
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.streams.clear();
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        let seed = self.seed;
+        self.streams
+            .entry(stream)
+            .or_insert_with(|| StdRng::seed_from_u64(stream_seed(seed, stream)))
+    }
+
+    // The stream's next roll, without consuming it; 0 for a stream not used yet
+    pub fn fingerprint(&self, stream: RngStream) -> u64 {
+        self.streams
+            .get(&stream)
+            .map(|rng| rng.clone().next_u64())
+            .unwrap_or(0)
+    }
+}
+
+fn stream_seed(seed: u64, stream: RngStream) -> u64 {
+    // SplitMix64 finalizer so neighbouring streams get unrelated seeds
+    let mut z = seed.wrapping_add((stream as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// SIM_SEED pins the seed for reproducing bug reports
+pub fn new_sim_seed() -> u64 {
+    env::var("SIM_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(rand::random)
+}
+
+// Keeps the RNG on the seed stored in GameState (new game, loaded save or
+// a seed received from a multiplayer host)
+pub fn sim_rng_seed_system(game_state: Res<GameState>, mut sim_rng: ResMut<SimRng>) {
+    if game_state.rng_seed != sim_rng.seed() {
+        sim_rng.reseed(game_state.rng_seed);
+        info!("🎲 Simulation RNG seeded with {}", game_state.rng_seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_same_seed_replays_same_rolls() {
+        let mut a = SimRng::new(42);
+        let mut b = SimRng::new(42);
+        let rolls_a: Vec<u32> = (0..8).map(|_| a.stream(RngStream::Ai).gen()).collect();
+        let rolls_b: Vec<u32> = (0..8).map(|_| b.stream(RngStream::Ai).gen()).collect();
+        assert_eq!(rolls_a, rolls_b);
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let mut a = SimRng::new(7);
+        let mut b = SimRng::new(7);
+        // Extra rolls on one stream must not shift another stream's sequence
+        for _ in 0..5 {
+            a.stream(RngStream::Political).gen::<f32>();
+        }
+        let intel_a: u64 = a.stream(RngStream::Intel).gen();
+        let intel_b: u64 = b.stream(RngStream::Intel).gen();
+        assert_eq!(intel_a, intel_b);
+    }
+
+    #[test]
+    fn test_reseed_restarts_streams() {
+        let mut rng = SimRng::new(1);
+        let first: u64 = rng.stream(RngStream::Spawning).gen();
+        rng.reseed(1);
+        assert_eq!(first, rng.stream(RngStream::Spawning).gen::<u64>());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::components::*;
+use crate::map::CityMap;
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // Implementatie van spatial grid zoals gedefinieerd in je code
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -80,4 +81,164 @@ impl SpatialGrid {
             .or_default()
             .push((entity, position, max_range));
     }
+
+    // Every unit within radius, visiting only the cells the radius can reach
+    pub fn find_nearby_units(&self, position: Vec3, radius: f32) -> Vec<Entity> {
+        let min = GridCell::from_position(position - Vec3::splat(radius), self.cell_size);
+        let max = GridCell::from_position(position + Vec3::splat(radius), self.cell_size);
+
+        let mut nearby = Vec::new();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                let Some(units) = self.units.get(&GridCell { x, y }) else {
+                    continue;
+                };
+                nearby.extend(
+                    units
+                        .iter()
+                        .filter(|(_, unit_pos, _)| unit_pos.distance(position) <= radius)
+                        .map(|(entity, _, _)| *entity),
+                );
+            }
+        }
+        nearby
+    }
+}
+
+// ==================== OBSTACLE GRID ====================
+
+// Coarse occupancy of blocking geometry on the map plane: city buildings plus
+// roadblocks and other obstacles. Rebuilt whenever one appears, moves or goes
+// away; audio uses it for line-of-sight.
+#[derive(Resource)]
+pub struct ObstacleGrid {
+    pub cell_size: f32,
+    pub blocked: HashSet<GridCell>,
+}
+
+impl Default for ObstacleGrid {
+    fn default() -> Self {
+        Self {
+            cell_size: 40.0,
+            blocked: HashSet::new(),
+        }
+    }
+}
+
+impl ObstacleGrid {
+    // Units live on the x/y plane, unlike SpatialGrid which buckets on x/z
+    pub fn cell_at(&self, position: Vec2) -> GridCell {
+        GridCell {
+            x: (position.x / self.cell_size).floor() as i32,
+            y: (position.y / self.cell_size).floor() as i32,
+        }
+    }
+
+    pub fn rebuild(&mut self, obstacles: impl Iterator<Item = (Vec2, f32)>) {
+        self.blocked.clear();
+        for (center, radius) in obstacles {
+            let min = self.cell_at(center - Vec2::splat(radius));
+            let max = self.cell_at(center + Vec2::splat(radius));
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    let cell_center = (Vec2::new(x as f32, y as f32) + 0.5) * self.cell_size;
+                    if cell_center.distance(center) <= radius {
+                        self.blocked.insert(GridCell { x, y });
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_blocked(&self, position: Vec2) -> bool {
+        self.blocked.contains(&self.cell_at(position))
+    }
+
+    // Blocked cells crossed on the way from one point to another. The cells
+    // the endpoints stand in don't count, so a sound at a roadblock isn't muffled.
+    pub fn blocked_cells_between(&self, from: Vec2, to: Vec2) -> usize {
+        let start = self.cell_at(from);
+        let end = self.cell_at(to);
+        let steps = (from.distance(to) / (self.cell_size * 0.5)).ceil() as usize;
+
+        let mut crossed = HashSet::new();
+        for i in 1..steps {
+            let cell = self.cell_at(from.lerp(to, i as f32 / steps as f32));
+            if cell != start && cell != end && self.blocked.contains(&cell) {
+                crossed.insert(cell);
+            }
+        }
+        crossed.len()
+    }
+
+    // Share of the surrounding cells that are blocked - high in narrow streets
+    pub fn enclosure(&self, position: Vec2) -> f32 {
+        let center = self.cell_at(position);
+        let blocked = center
+            .get_neighbors()
+            .iter()
+            .filter(|cell| **cell != center && self.blocked.contains(cell))
+            .count();
+        blocked as f32 / 8.0
+    }
+}
+
+pub fn obstacle_grid_system(
+    mut grid: ResMut<ObstacleGrid>,
+    obstacle_query: Query<(&Transform, &Obstacle)>,
+    changed_query: Query<(), (With<Obstacle>, Or<(Added<Obstacle>, Changed<Transform>)>)>,
+    mut removed: RemovedComponents<Obstacle>,
+    city_map: Res<CityMap>,
+) {
+    let removed_any = removed.read().count() > 0;
+    if changed_query.is_empty() && !removed_any && !city_map.is_changed() {
+        return;
+    }
+
+    // Circumscribed radius, so every building tile claims at least one cell
+    let building_radius = city_map.tile_size * std::f32::consts::FRAC_1_SQRT_2;
+    grid.rebuild(
+        obstacle_query
+            .iter()
+            .map(|(transform, obstacle)| (transform.translation.truncate(), obstacle.radius))
+            .chain(
+                city_map
+                    .building_tiles()
+                    .map(|center| (center, building_radius)),
+            ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obstacle_grid_line_of_sight() {
+        let mut grid = ObstacleGrid::default();
+        grid.rebuild([(Vec2::new(200.0, 0.0), 50.0)].into_iter());
+
+        assert!(grid.is_blocked(Vec2::new(200.0, 0.0)));
+        assert!(grid.blocked_cells_between(Vec2::ZERO, Vec2::new(400.0, 0.0)) > 0);
+        assert_eq!(
+            grid.blocked_cells_between(Vec2::new(0.0, 300.0), Vec2::new(400.0, 300.0)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_obstacle_grid_enclosure() {
+        let mut grid = ObstacleGrid::default();
+        assert_eq!(grid.enclosure(Vec2::ZERO), 0.0);
+
+        // Walls on both sides of a street running along y
+        grid.rebuild(
+            [
+                (Vec2::new(-60.0, 20.0), 60.0),
+                (Vec2::new(100.0, 20.0), 60.0),
+            ]
+            .into_iter(),
+        );
+        assert!(grid.enclosure(Vec2::new(20.0, 20.0)) >= 0.5);
+    }
 }
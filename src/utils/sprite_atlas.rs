@@ -0,0 +1,144 @@
+use crate::resources::GameAssets;
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy::sprite::TextureAtlasBuilder;
+use std::collections::HashMap;
+
+// ==================== SPRITE ATLAS ====================
+
+// Unit and structure art is packed into a single atlas once it has loaded, so
+// every unit shares one texture and large battles render in a few batches.
+// Particles and health bars are untextured and already share the default image.
+
+const ATLAS_MAX_SIZE: f32 = 2048.0;
+
+#[derive(Resource, Default)]
+pub struct SpriteAtlas {
+    pub atlas: Handle<TextureAtlas>,
+    indices: HashMap<AssetId<Image>, usize>,
+    packed: bool, // Packing was attempted; failures fall back to individual textures
+}
+
+impl SpriteAtlas {
+    pub fn is_packed(&self) -> bool {
+        self.packed
+    }
+
+    pub fn index_of(&self, image: &Handle<Image>) -> Option<usize> {
+        self.indices.get(&image.id()).copied()
+    }
+}
+
+fn atlas_source_images(game_assets: &GameAssets) -> [Handle<Image>; 8] {
+    [
+        game_assets.sicario_sprite.clone(),
+        game_assets.enforcer_sprite.clone(),
+        game_assets.ovidio_sprite.clone(),
+        game_assets.soldier_sprite.clone(),
+        game_assets.special_forces_sprite.clone(),
+        game_assets.vehicle_sprite.clone(),
+        game_assets.roadblock_sprite.clone(),
+        game_assets.safehouse_sprite.clone(),
+    ]
+}
+
+pub fn build_sprite_atlas_system(
+    mut sprite_atlas: ResMut<SpriteAtlas>,
+    game_assets: Res<GameAssets>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    if sprite_atlas.packed {
+        return;
+    }
+
+    let sources = atlas_source_images(&game_assets);
+
+    // Wait until every source image has either loaded or failed to load
+    let still_loading = sources.iter().any(|handle| {
+        images.get(handle).is_none()
+            && asset_server.get_load_state(handle) != Some(LoadState::Failed)
+    });
+    if still_loading {
+        return;
+    }
+
+    let mut builder = TextureAtlasBuilder::default().max_size(Vec2::splat(ATLAS_MAX_SIZE));
+    for handle in &sources {
+        if let Some(image) = images.get(handle) {
+            builder.add_texture(handle.id(), image);
+        }
+    }
+
+    match builder.finish(&mut images) {
+        Ok(atlas) => {
+            sprite_atlas.indices = sources
+                .iter()
+                .filter_map(|handle| {
+                    atlas
+                        .get_texture_index(handle.id())
+                        .map(|index| (handle.id(), index))
+                })
+                .collect();
+            info!(
+                "🧩 Sprite atlas packed: {} textures into {:.0}x{:.0}",
+                sprite_atlas.indices.len(),
+                atlas.size.x,
+                atlas.size.y
+            );
+            sprite_atlas.atlas = texture_atlases.add(atlas);
+        }
+        Err(e) => warn!(
+            "Sprite atlas packing failed, using individual textures: {:?}",
+            e
+        ),
+    }
+
+    sprite_atlas.packed = true;
+}
+
+// Spawners keep creating plain sprites; any sprite whose image lives in the
+// atlas is swapped to the atlas so it batches with the rest.
+pub fn atlas_sprite_system(
+    mut commands: Commands,
+    sprite_atlas: Res<SpriteAtlas>,
+    all_sprites: Query<(Entity, &Sprite, &Handle<Image>)>,
+    new_sprites: Query<(Entity, &Sprite, &Handle<Image>), Added<Handle<Image>>>,
+) {
+    if !sprite_atlas.is_packed() {
+        return;
+    }
+
+    let mut convert = |entity: Entity, sprite: &Sprite, image: &Handle<Image>| {
+        let Some(index) = sprite_atlas.index_of(image) else {
+            return;
+        };
+
+        commands
+            .entity(entity)
+            .remove::<(Sprite, Handle<Image>)>()
+            .insert((
+                TextureAtlasSprite {
+                    color: sprite.color,
+                    index,
+                    flip_x: sprite.flip_x,
+                    flip_y: sprite.flip_y,
+                    custom_size: sprite.custom_size,
+                    anchor: sprite.anchor,
+                },
+                sprite_atlas.atlas.clone(),
+            ));
+    };
+
+    // Sprites spawned before the atlas was ready are converted in one pass
+    if sprite_atlas.is_changed() {
+        for (entity, sprite, image) in all_sprites.iter() {
+            convert(entity, sprite, image);
+        }
+    } else {
+        for (entity, sprite, image) in new_sprites.iter() {
+            convert(entity, sprite, image);
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use crate::ui::ui_theme::{ThemedBackground, UiTheme};
 use bevy::prelude::*;
 
 // ==================== UI BUILDER UTILITIES ====================
 
+// Every builder takes the UiTheme so panels, bars and text follow the player's
+// palette and high-contrast setting
+
 /// Create a standard menu container with title
-pub fn create_menu_container(title: &str) -> (NodeBundle, TextBundle) {
+pub fn create_menu_container(title: &str, theme: &UiTheme) -> (NodeBundle, TextBundle) {
     let container = NodeBundle {
         style: Style {
             width: Val::Percent(100.0),
@@ -13,7 +17,9 @@ pub fn create_menu_container(title: &str) -> (NodeBundle, TextBundle) {
             align_items: AlignItems::Center,
             ..default()
         },
-        background_color: Color::rgba(0.0, 0.0, 0.0, 0.8).into(),
+        background_color: theme
+            .panel_background(Color::rgba(0.0, 0.0, 0.0, 0.8))
+            .into(),
         ..default()
     };
 
@@ -34,7 +40,11 @@ pub fn create_menu_container(title: &str) -> (NodeBundle, TextBundle) {
 }
 
 /// Create a standard button with text
-pub fn create_button_with_text(text: &str, color: Color) -> (NodeBundle, TextBundle) {
+pub fn create_button_with_text(
+    text: &str,
+    color: Color,
+    theme: &UiTheme,
+) -> (NodeBundle, TextBundle) {
     let button = NodeBundle {
         style: Style {
             width: Val::Px(200.0),
@@ -45,7 +55,7 @@ pub fn create_button_with_text(text: &str, color: Color) -> (NodeBundle, TextBun
             border: UiRect::all(Val::Px(2.0)),
             ..default()
         },
-        background_color: color.into(),
+        background_color: theme.panel_background(color).into(),
         border_color: Color::WHITE.into(),
         ..default()
     };
@@ -63,12 +73,12 @@ pub fn create_button_with_text(text: &str, color: Color) -> (NodeBundle, TextBun
 }
 
 /// Create a text section with specified properties
-pub fn create_text_section(text: &str, size: f32, color: Color) -> TextBundle {
+pub fn create_text_section(text: &str, size: f32, color: Color, theme: &UiTheme) -> TextBundle {
     TextBundle::from_section(
         text,
         TextStyle {
             font_size: size,
-            color,
+            color: theme.text_color(color),
             ..default()
         },
     )
@@ -79,7 +89,7 @@ pub fn create_text_section(text: &str, size: f32, color: Color) -> TextBundle {
 }
 
 /// Create an info panel with title and content
-pub fn create_info_panel(title: &str, content: &str) -> NodeBundle {
+pub fn create_info_panel(title: &str, content: &str, theme: &UiTheme) -> NodeBundle {
     NodeBundle {
         style: Style {
             width: Val::Px(300.0),
@@ -90,8 +100,10 @@ pub fn create_info_panel(title: &str, content: &str) -> NodeBundle {
             border: UiRect::all(Val::Px(1.0)),
             ..default()
         },
-        background_color: Color::rgba(0.1, 0.1, 0.1, 0.9).into(),
-        border_color: Color::rgb(0.4, 0.4, 0.4).into(),
+        background_color: theme
+            .panel_background(Color::rgba(0.1, 0.1, 0.1, 0.9))
+            .into(),
+        border_color: theme.border_color(Color::rgb(0.4, 0.4, 0.4)).into(),
         ..default()
     }
 }
@@ -102,6 +114,7 @@ pub fn create_progress_bar(
     max: f32,
     width: f32,
     height: f32,
+    theme: &UiTheme,
 ) -> (NodeBundle, NodeBundle) {
     let progress_ratio = if max > 0.0 { current / max } else { 0.0 };
 
@@ -112,7 +125,7 @@ pub fn create_progress_bar(
             border: UiRect::all(Val::Px(1.0)),
             ..default()
         },
-        background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+        background_color: theme.panel_background(Color::rgb(0.2, 0.2, 0.2)).into(),
         border_color: Color::WHITE.into(),
         ..default()
     };
@@ -123,7 +136,7 @@ pub fn create_progress_bar(
             height: Val::Percent(100.0),
             ..default()
         },
-        background_color: Color::rgb(0.2, 0.8, 0.2).into(),
+        background_color: theme.colors().health_high.into(),
         ..default()
     };
 
@@ -136,6 +149,7 @@ pub fn create_health_bar(
     max_health: f32,
     width: f32,
     height: f32,
+    theme: &UiTheme,
 ) -> (NodeBundle, NodeBundle) {
     let health_ratio = if max_health > 0.0 {
         current_health / max_health
@@ -143,13 +157,7 @@ pub fn create_health_bar(
         0.0
     };
 
-    let health_color = if health_ratio > 0.6 {
-        Color::rgb(0.2, 0.8, 0.2) // Green
-    } else if health_ratio > 0.3 {
-        Color::rgb(0.8, 0.8, 0.2) // Yellow
-    } else {
-        Color::rgb(0.8, 0.2, 0.2) // Red
-    };
+    let health_color = theme.health_color(health_ratio);
 
     let background = NodeBundle {
         style: Style {
@@ -158,8 +166,8 @@ pub fn create_health_bar(
             border: UiRect::all(Val::Px(1.0)),
             ..default()
         },
-        background_color: Color::rgb(0.3, 0.1, 0.1).into(),
-        border_color: Color::rgb(0.5, 0.5, 0.5).into(),
+        background_color: theme.colors().health_background.into(),
+        border_color: theme.border_color(Color::rgb(0.5, 0.5, 0.5)).into(),
         ..default()
     };
 
@@ -177,7 +185,7 @@ pub fn create_health_bar(
 }
 
 /// Create a list container
-pub fn create_list_container() -> NodeBundle {
+pub fn create_list_container(theme: &UiTheme) -> NodeBundle {
     NodeBundle {
         style: Style {
             width: Val::Percent(100.0),
@@ -186,7 +194,9 @@ pub fn create_list_container() -> NodeBundle {
             padding: UiRect::all(Val::Px(10.0)),
             ..default()
         },
-        background_color: Color::rgba(0.0, 0.0, 0.0, 0.1).into(),
+        background_color: theme
+            .panel_background(Color::rgba(0.0, 0.0, 0.0, 0.1))
+            .into(),
         ..default()
     }
 }
@@ -247,7 +257,7 @@ pub fn create_status_indicator(status: &str, color: Color) -> (NodeBundle, TextB
 }
 
 /// Create a tooltip container
-pub fn create_tooltip(text: &str) -> NodeBundle {
+pub fn create_tooltip(text: &str, theme: &UiTheme) -> NodeBundle {
     NodeBundle {
         style: Style {
             position_type: PositionType::Absolute,
@@ -255,15 +265,17 @@ pub fn create_tooltip(text: &str) -> NodeBundle {
             border: UiRect::all(Val::Px(1.0)),
             ..default()
         },
-        background_color: Color::rgba(0.0, 0.0, 0.0, 0.9).into(),
-        border_color: Color::rgb(0.6, 0.6, 0.6).into(),
+        background_color: theme
+            .panel_background(Color::rgba(0.0, 0.0, 0.0, 0.9))
+            .into(),
+        border_color: theme.border_color(Color::rgb(0.6, 0.6, 0.6)).into(),
         visibility: Visibility::Hidden,
         ..default()
     }
 }
 
 /// Create a minimap container
-pub fn create_minimap_container(size: f32) -> NodeBundle {
+pub fn create_minimap_container(size: f32, theme: &UiTheme) -> NodeBundle {
     NodeBundle {
         style: Style {
             width: Val::Px(size),
@@ -274,19 +286,21 @@ pub fn create_minimap_container(size: f32) -> NodeBundle {
             border: UiRect::all(Val::Px(2.0)),
             ..default()
         },
-        background_color: Color::rgba(0.0, 0.0, 0.0, 0.7).into(),
+        background_color: theme
+            .panel_background(Color::rgba(0.0, 0.0, 0.0, 0.7))
+            .into(),
         border_color: Color::WHITE.into(),
         ..default()
     }
 }
 
 /// Create a mission objective item
-pub fn create_objective_item(text: &str, completed: bool) -> (NodeBundle, TextBundle) {
-    let color = if completed {
-        Color::rgb(0.2, 0.8, 0.2) // Green for completed
-    } else {
-        Color::rgb(0.8, 0.8, 0.2) // Yellow for active
-    };
+pub fn create_objective_item(
+    text: &str,
+    completed: bool,
+    theme: &UiTheme,
+) -> (NodeBundle, TextBundle) {
+    let color = theme.objective_color(completed);
 
     let checkbox = NodeBundle {
         style: Style {
@@ -297,7 +311,7 @@ pub fn create_objective_item(text: &str, completed: bool) -> (NodeBundle, TextBu
             ..default()
         },
         background_color: if completed {
-            Color::rgb(0.2, 0.8, 0.2).into()
+            color.into()
         } else {
             Color::rgba(0.0, 0.0, 0.0, 0.0).into()
         },
@@ -316,3 +330,66 @@ pub fn create_objective_item(text: &str, completed: bool) -> (NodeBundle, TextBu
 
     (checkbox, objective_text)
 }
+
+// ==================== DASHBOARD PANELS ====================
+
+/// Create a single line of dashboard panel text
+pub fn dashboard_line(text: impl Into<String>, size: f32, color: Color) -> TextSection {
+    TextSection::new(
+        text,
+        TextStyle {
+            font_size: size,
+            color,
+            ..default()
+        },
+    )
+}
+
+/// Create a persistent HUD panel whose lines are updated in place by `update_dashboard_panel`;
+/// spawn the ThemedBackground with it so the background follows theme changes
+pub fn create_dashboard_panel(
+    position: Style,
+    background: Color,
+    lines: Vec<TextSection>,
+    theme: &UiTheme,
+) -> (TextBundle, ThemedBackground) {
+    let panel = TextBundle {
+        text: Text::from_sections(join_dashboard_lines(lines, theme)),
+        style: Style {
+            position_type: PositionType::Absolute,
+            height: Val::Auto,
+            padding: UiRect::all(Val::Px(10.0)),
+            ..position
+        },
+        background_color: theme.panel_background(background).into(),
+        ..default()
+    };
+    (panel, ThemedBackground(background))
+}
+
+/// Replace a dashboard panel's lines, leaving the Text untouched if nothing changed
+pub fn update_dashboard_panel(text: &mut Mut<Text>, lines: Vec<TextSection>, theme: &UiTheme) {
+    let lines = join_dashboard_lines(lines, theme);
+
+    let unchanged = text.sections.len() == lines.len()
+        && text.sections.iter().zip(&lines).all(|(current, new)| {
+            current.value == new.value
+                && current.style.color == new.style.color
+                && current.style.font_size == new.style.font_size
+        });
+
+    if !unchanged {
+        text.sections = lines;
+    }
+}
+
+fn join_dashboard_lines(mut lines: Vec<TextSection>, theme: &UiTheme) -> Vec<TextSection> {
+    let line_count = lines.len();
+    for line in lines.iter_mut().take(line_count.saturating_sub(1)) {
+        line.value.push('\n');
+    }
+    for line in lines.iter_mut() {
+        line.style.color = theme.text_color(line.style.color);
+    }
+    lines
+}
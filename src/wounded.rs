@@ -0,0 +1,365 @@
+use crate::ai::unit_ai_system;
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::{BalanceConfig, WoundedBalance};
+use crate::coordination::advanced_tactical_ai_system;
+use crate::corpse_system::{register_corpses_system, Corpse};
+use crate::damage::{damage_system, DamageEvent, DownedSet};
+use crate::localization::tr;
+use crate::map::CityMap;
+use crate::political_system::PoliticalState;
+use crate::prisoners::incapacitation_system;
+use crate::systems::pathfinding_system;
+use crate::utils::{play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+// ==================== WOUNDED PLUGIN ====================
+
+// Elite fighters - enforcers, snipers, heavy gunners, medics and special
+// forces - don't always die when they go down. Unless the hit went well past
+// what it took to drop them, they're left wounded and bleeding out: nobody
+// shoots at them any more, but they die when the clock runs out. Bleeding
+// slows behind cover, and an idle comrade within reach drags them to the best
+// cover nearby. A medic of their own side who stays with them long enough
+// gets them back on their feet. Soldiers brought down wounded weigh on the
+// government less than the dead do; those who bleed out count as killed.
+
+const DOWNED_TILT: f32 = std::f32::consts::FRAC_PI_2; // Drawn lying on their side
+const ARRIVAL_RADIUS: f32 = 12.0;
+
+// Type aliases to reduce complexity
+type DowningQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Unit,
+        &'static mut Transform,
+        Option<&'static mut Movement>,
+    ),
+    (
+        Without<Corpse>,
+        Without<ProtectedPrincipal>,
+        Without<Wounded>,
+    ),
+>;
+type RescuerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Unit,
+        &'static Transform,
+        &'static mut Movement,
+    ),
+    (
+        Without<Wounded>,
+        Without<Prisoner>,
+        Without<ProtectedPrincipal>,
+        Without<Captive>,
+    ),
+>;
+
+pub struct WoundedPlugin;
+
+impl Plugin for WoundedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                // Fighters the enemy takes alive are prisoners, not wounded
+                wounding_system
+                    .in_set(DownedSet)
+                    .after(incapacitation_system)
+                    .before(register_corpses_system),
+                // A fighter bleeding out dies through the damage pipeline
+                wounded_care_system
+                    .after(advanced_tactical_ai_system)
+                    .after(unit_ai_system)
+                    .before(pathfinding_system)
+                    .before(damage_system),
+            )
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+/// Whether a fighter who just went down is left wounded rather than dead.
+pub fn goes_down_wounded(
+    unit_type: &UnitType,
+    health: f32,
+    max_health: f32,
+    balance: &WoundedBalance,
+) -> bool {
+    matches!(
+        unit_type,
+        UnitType::Enforcer
+            | UnitType::Sniper
+            | UnitType::HeavyGunner
+            | UnitType::Medic
+            | UnitType::SpecialForces
+    ) && health <= 0.0
+        && health > -max_health * balance.overkill_share
+}
+
+/// The best cover within `radius` of `from`, if it's better than what's
+/// there already; the nearest of equally good spots.
+pub fn cover_spot(city_map: &CityMap, from: Vec2, radius: f32) -> Option<Vec2> {
+    let here = city_map.cover_at(from);
+    city_map
+        .tiles()
+        .map(|(col, row, _)| city_map.tile_center(col, row))
+        .filter(|spot| spot.distance(from) <= radius && city_map.cover_at(*spot) > here)
+        .max_by(|a, b| {
+            city_map
+                .cover_at(*a)
+                .total_cmp(&city_map.cover_at(*b))
+                .then(from.distance(*b).total_cmp(&from.distance(*a)))
+        })
+}
+
+/// Seconds off the bleed-out clock for a tick spent lying in `cover`.
+pub fn bleed(dt: f32, cover: f32, balance: &WoundedBalance) -> f32 {
+    if cover > 0.0 {
+        dt * balance.covered_bleed_rate
+    } else {
+        dt
+    }
+}
+
+fn release(ally_query: &mut RescuerQuery, dragger: Option<Entity>) {
+    if let Some(Ok((.., mut movement))) = dragger.map(|dragger| ally_query.get_mut(dragger)) {
+        movement.target_position = None;
+    }
+}
+
+// ==================== SYSTEMS ====================
+
+// Runs between combat and corpse registration, so a wounded fighter never
+// becomes a body while there's still time to save them, and before
+// death_system, so going down wounded is no kill
+pub fn wounding_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut political_state: Option<ResMut<PoliticalState>>,
+    mut downed_query: DowningQuery,
+) {
+    let balance = &balance.wounded;
+    for (entity, mut unit, mut transform, movement) in downed_query.iter_mut() {
+        if !goes_down_wounded(&unit.unit_type, unit.health, unit.max_health, balance) {
+            continue;
+        }
+
+        // Kept above zero so the corpse system leaves them alone this tick
+        unit.health = 1.0;
+        let mut held_unit = unit.clone();
+        held_unit.target = None;
+        if let Some(mut movement) = movement {
+            movement.target_position = None;
+        }
+        let rotation = transform.rotation;
+        transform.rotate_z(DOWNED_TILT);
+        commands
+            .entity(entity)
+            .remove::<(Unit, Selected, HoldArea, Bodyguard, HvtTasking)>()
+            .insert(Wounded {
+                unit: held_unit,
+                bleed_out: balance.bleed_out_seconds,
+                treatment: 0.0,
+                dragger: None,
+                destination: None,
+                rotation,
+            });
+
+        if let Some(political_state) = political_state.as_deref_mut() {
+            match unit.faction {
+                Faction::Military => political_state.wounded_military += 1,
+                Faction::Cartel => political_state.wounded_cartel += 1,
+                _ => {}
+            }
+        }
+        if unit.faction == Faction::Cartel {
+            play_tactical_sound("radio", &tr("radio.wounded_down"));
+        }
+    }
+}
+
+// Bleeding out, being dragged to cover and being patched up by a medic
+pub fn wounded_care_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    balance: Res<BalanceConfig>,
+    city_map: Res<CityMap>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut wounded_query: Query<(Entity, &mut Transform, &mut Wounded)>,
+    mut ally_query: RescuerQuery,
+) {
+    let balance = &balance.wounded;
+    let dt = time.delta_seconds();
+    let mut draggers: HashSet<Entity> = wounded_query
+        .iter()
+        .filter_map(|(_, _, wounded)| wounded.dragger)
+        .collect();
+
+    for (entity, mut transform, mut wounded) in wounded_query.iter_mut() {
+        // The dead keep their Wounded, so they're neither taken prisoner nor
+        // brought down a second time before the corpse system gets to them
+        if wounded.bleed_out <= 0.0 {
+            continue;
+        }
+        let position = transform.translation;
+        let faction = wounded.unit.faction.clone();
+
+        let medic_at_hand = ally_query.iter().any(|(_, unit, t, _)| {
+            unit.faction == faction
+                && unit.unit_type == UnitType::Medic
+                && unit.health > 0.0
+                && t.translation.distance(position) <= balance.stabilize_radius
+        });
+        if medic_at_hand {
+            wounded.treatment += dt;
+        }
+        if wounded.treatment >= balance.stabilize_seconds {
+            release(&mut ally_query, wounded.dragger);
+            let mut unit = wounded.unit.clone();
+            unit.health = unit.max_health * balance.revived_health;
+            transform.rotation = wounded.rotation;
+            commands.entity(entity).remove::<Wounded>().insert(unit);
+            if faction == Faction::Cartel {
+                play_tactical_sound("radio", &tr("radio.wounded_stabilized"));
+            }
+            continue;
+        }
+
+        wounded.bleed_out -= bleed(dt, city_map.cover_at(position.truncate()), balance);
+        if wounded.bleed_out <= 0.0 {
+            release(&mut ally_query, wounded.dragger);
+            wounded.dragger = None;
+            // Back as a unit only to take the last of his health, so the death
+            // is counted like any other
+            let unit = wounded.unit.clone();
+            damage_events.send(DamageEvent {
+                attacker: None,
+                target: entity,
+                amount: unit.health,
+                weapon: None,
+            });
+            transform.rotation = wounded.rotation;
+            commands.entity(entity).insert(unit);
+            if faction == Faction::Cartel {
+                play_tactical_sound("radio", &tr("radio.wounded_bled_out"));
+            }
+            continue;
+        }
+
+        // An idle comrade close by picks them up, if there's better cover
+        // to drag them to
+        let dragger_alive = wounded.dragger.is_some_and(|dragger| {
+            ally_query
+                .get(dragger)
+                .is_ok_and(|(_, unit, ..)| unit.health > 0.0)
+        });
+        if !dragger_alive {
+            wounded.dragger = None;
+            let nearest = ally_query
+                .iter()
+                .filter(|(e, unit, t, movement)| {
+                    unit.faction == faction
+                        && unit.health > 0.0
+                        && unit.movement_speed > 0.0
+                        && movement.target_position.is_none()
+                        && !draggers.contains(e)
+                        && t.translation.distance(position) <= balance.drag_radius
+                })
+                .min_by(|a, b| {
+                    a.2.translation
+                        .distance(position)
+                        .total_cmp(&b.2.translation.distance(position))
+                })
+                .map(|(e, ..)| e);
+            wounded.destination = nearest.and_then(|_| {
+                cover_spot(&city_map, position.truncate(), balance.cover_search_radius)
+            });
+            if wounded.destination.is_some() {
+                wounded.dragger = nearest;
+                draggers.extend(nearest);
+            }
+        }
+        let (Some(dragger), Some(destination)) = (wounded.dragger, wounded.destination) else {
+            continue;
+        };
+        let Ok((_, _, dragger_transform, mut dragger_movement)) = ally_query.get_mut(dragger)
+        else {
+            continue;
+        };
+        let dragger_pos = dragger_transform.translation;
+        let destination = destination.extend(position.z);
+
+        // Laid down in cover once they're there
+        if dragger_pos.truncate().distance(destination.truncate()) <= ARRIVAL_RADIUS
+            && position.distance(dragger_pos) <= balance.drag_leash
+        {
+            dragger_movement.target_position = None;
+            transform.translation = destination;
+            wounded.dragger = None;
+            wounded.destination = None;
+            continue;
+        }
+
+        // The dragger pulls them along, waiting whenever they fall behind
+        dragger_movement.target_position = if position.distance(dragger_pos) > balance.drag_leash {
+            Some(position)
+        } else {
+            Some(destination)
+        };
+        if position.distance(dragger_pos) > balance.drag_leash * 0.5 {
+            transform.translation +=
+                (dragger_pos - position).normalize_or_zero() * balance.drag_speed * dt;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_elites_not_blown_apart_go_down_wounded() {
+        let balance = WoundedBalance::default();
+        let wounded = |unit_type: UnitType, health: f32| {
+            goes_down_wounded(&unit_type, health, 100.0, &balance)
+        };
+        assert!(wounded(UnitType::Enforcer, 0.0));
+        assert!(wounded(UnitType::Medic, -20.0));
+        assert!(!wounded(UnitType::Enforcer, -60.0)); // Past overkill_share
+        assert!(!wounded(UnitType::Sicario, -5.0));
+        assert!(!wounded(UnitType::Sniper, 10.0));
+    }
+
+    #[test]
+    fn test_dragged_to_the_nearest_best_cover_and_bleeding_slower_there() {
+        let map = CityMap::from_json(
+            r#"{
+                "name": "Cover",
+                "tile_size": 10.0,
+                "tiles": [".......", "\"...#.."],
+                "districts": [".......", "......."],
+                "neighborhoods": []
+            }"#,
+        )
+        .unwrap();
+        let open = map.tile_center(0, 0);
+        let spot = cover_spot(&map, open, 100.0).unwrap();
+        assert!(map.cover_at(spot) > map.cover_at(open));
+        assert!(map.cover_at(spot) >= map.cover_at(map.tile_center(0, 1)));
+        // Nothing to gain once they're there, or with no cover in reach
+        assert_eq!(cover_spot(&map, spot, 100.0), None);
+        assert_eq!(cover_spot(&map, open, 5.0), None);
+
+        let balance = WoundedBalance::default();
+        assert_eq!(bleed(1.0, 0.0, &balance), 1.0);
+        assert!(bleed(1.0, map.cover_at(spot), &balance) < 1.0);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::app_state::GameSet;
+use crate::components::*;
+use crate::config::{BalanceConfig, WreckBalance};
+use crate::corpse_system::Corpse;
+use crate::damage::damage_system;
+use crate::economy::CartelTreasury;
+use crate::localization::tr;
+use crate::resources::GameState;
+use crate::utils::{play_tactical_sound, SimulationSet};
+use bevy::prelude::*;
+
+// ==================== WRECK AND SALVAGE PLUGIN ====================
+
+// A destroyed vehicle or tank doesn't lie around as a body - it leaves a
+// burnt-out hulk that blocks the lane like a roadblock, so units steer round
+// it. Sicarios standing next to a wreck strip it for cartel cash until there's
+// nothing left worth taking; army engineers haul it away after enough work.
+// Either way the lane opens up again.
+
+const WRECK_COLOR: Color = Color::rgb(0.18, 0.16, 0.14);
+const VEHICLE_WRECK_SIZE: Vec2 = Vec2::new(40.0, 22.0);
+const TANK_WRECK_SIZE: Vec2 = Vec2::new(52.0, 30.0);
+
+pub struct WreckSalvagePlugin;
+
+impl Plugin for WreckSalvagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (register_wrecks_system, wreck_work_system)
+                .chain()
+                .after(damage_system)
+                .in_set(SimulationSet::Simulate)
+                .in_set(GameSet::Session),
+        );
+    }
+}
+
+/// Whether a unit of this type leaves a wreck rather than a corpse.
+pub fn leaves_wreck(unit_type: &UnitType) -> bool {
+    matches!(unit_type, UnitType::Vehicle | UnitType::Tank)
+}
+
+/// Cash a crew of sicarios strips from a wreck this tick, never more than it
+/// has left.
+pub fn salvage_taken(remaining: f32, workers: usize, balance: &WreckBalance, dt: f32) -> f32 {
+    (workers as f32 * balance.salvage_rate * dt).min(remaining.max(0.0))
+}
+
+pub fn spawn_wreck(
+    commands: &mut Commands,
+    unit_type: &UnitType,
+    position: Vec2,
+    balance: &WreckBalance,
+) {
+    let (size, salvage) = match unit_type {
+        UnitType::Tank => (TANK_WRECK_SIZE, balance.tank_salvage),
+        _ => (VEHICLE_WRECK_SIZE, balance.vehicle_salvage),
+    };
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: WRECK_COLOR,
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(0.4)),
+            ..default()
+        },
+        Wreck {
+            salvage,
+            clearing: 0.0,
+        },
+        Obstacle {
+            radius: balance.radius,
+        },
+    ));
+}
+
+// Swap freshly destroyed vehicles for wrecks. The vehicle itself goes
+// straight away, so it's counted with the cleared dead
+pub fn register_wrecks_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut game_state: ResMut<GameState>,
+    unit_query: Query<(Entity, &Unit, &Transform), Without<Corpse>>,
+) {
+    for (entity, unit, transform) in unit_query.iter() {
+        if unit.health > 0.0 || !leaves_wreck(&unit.unit_type) {
+            continue;
+        }
+        game_state.record_cleared_corpse(&unit.faction);
+        // Selection and target crosshairs are pooled children
+        commands.entity(entity).clear_children().despawn();
+        spawn_wreck(
+            &mut commands,
+            &unit.unit_type,
+            transform.translation.truncate(),
+            &balance.wrecks,
+        );
+    }
+}
+
+// Sicarios next to a wreck strip it for cash; engineers next to one clear it
+pub fn wreck_work_system(
+    mut commands: Commands,
+    balance: Res<BalanceConfig>,
+    mut treasury: Option<ResMut<CartelTreasury>>,
+    mut wreck_query: Query<(Entity, &mut Wreck, &Transform)>,
+    unit_query: Query<(&Unit, &Transform), Without<Wreck>>,
+    time: Res<Time>,
+) {
+    let balance = &balance.wrecks;
+    let dt = time.delta_seconds();
+
+    for (entity, mut wreck, wreck_transform) in wreck_query.iter_mut() {
+        let position = wreck_transform.translation.truncate();
+        let workers = |unit_type: UnitType, faction: Faction| {
+            unit_query
+                .iter()
+                .filter(|(unit, transform)| {
+                    unit.health > 0.0
+                        && unit.unit_type == unit_type
+                        && unit.faction == faction
+                        && transform.translation.truncate().distance(position)
+                            <= balance.work_radius
+                })
+                .count()
+        };
+        let salvagers = workers(UnitType::Sicario, Faction::Cartel);
+        let engineers = workers(UnitType::Engineer, Faction::Military);
+
+        if salvagers > 0 {
+            let taken = salvage_taken(wreck.salvage, salvagers, balance, dt);
+            wreck.salvage -= taken;
+            if let Some(treasury) = treasury.as_deref_mut() {
+                treasury.cash += taken;
+            }
+            if wreck.salvage <= 0.0 {
+                commands.entity(entity).despawn();
+                play_tactical_sound("radio", &tr("radio.wreck_stripped"));
+                continue;
+            }
+        }
+
+        wreck.clearing += engineers as f32 * dt;
+        if wreck.clearing >= balance.clear_seconds {
+            commands.entity(entity).despawn();
+            play_tactical_sound("radio", &tr("radio.wreck_cleared"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_vehicles_and_tanks_leave_wrecks() {
+        assert!(leaves_wreck(&UnitType::Vehicle));
+        assert!(leaves_wreck(&UnitType::Tank));
+        assert!(!leaves_wreck(&UnitType::Sicario));
+        assert!(!leaves_wreck(&UnitType::Helicopter));
+        assert!(!leaves_wreck(&UnitType::Roadblock));
+    }
+
+    #[test]
+    fn test_salvage_never_exceeds_what_is_left() {
+        let balance = WreckBalance::default();
+        let taken = salvage_taken(100.0, 2, &balance, 1.0);
+        assert_eq!(taken, 2.0 * balance.salvage_rate);
+        assert_eq!(salvage_taken(3.0, 4, &balance, 1.0), 3.0);
+        assert_eq!(salvage_taken(50.0, 0, &balance, 1.0), 0.0);
+    }
+}
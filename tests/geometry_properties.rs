@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use culiacan_rts::components::FormationType;
+use culiacan_rts::utils::formation::{
+    avoid_threat_clusters, calculate_flanking_position as calculate_flank_around_obstacles,
+    calculate_formation_facing, calculate_formation_position, calculate_formation_spacing,
+    calculate_retreat_position,
+};
+use culiacan_rts::utils::{calculate_flanking_position, iso_to_world, world_to_iso, SpatialGrid};
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// ==================== GEOMETRY PROPERTIES ====================
+
+// Formation, isometric and spatial helpers feed every AI decision. A NaN or a
+// runaway offset from any of them doesn't fail loudly - units just freeze or
+// teleport - so these check the invariants over random inputs.
+
+const MAP_EXTENT: f32 = 10_000.0;
+
+fn map_position() -> impl Strategy<Value = Vec3> {
+    (
+        -MAP_EXTENT..MAP_EXTENT,
+        -MAP_EXTENT..MAP_EXTENT,
+        0.0f32..100.0,
+    )
+        .prop_map(|(x, y, z)| Vec3::new(x, y, z))
+}
+
+// Offsets on the map plane, for threats close enough to matter
+fn nearby_offset(range: f32) -> impl Strategy<Value = Vec3> {
+    (-range..range, -range..range).prop_map(|(x, y)| Vec3::new(x, y, 0.0))
+}
+
+fn formation_type() -> impl Strategy<Value = FormationType> {
+    prop_oneof![
+        Just(FormationType::Line),
+        Just(FormationType::Circle),
+        Just(FormationType::Wedge),
+        Just(FormationType::Flanking),
+        Just(FormationType::Overwatch),
+        Just(FormationType::Retreat),
+    ]
+}
+
+// f32 keeps about seven digits, so tolerances grow with the coordinates involved
+fn tolerance(scale: f32) -> f32 {
+    1e-4 * (1.0 + scale.abs())
+}
+
+proptest! {
+    #[test]
+    fn test_formation_positions_are_finite_and_bounded(
+        formation in formation_type(),
+        unit_count in 0usize..40,
+        index in 0usize..40,
+        center in map_position(),
+        facing in -10.0f32..10.0,
+    ) {
+        let position =
+            calculate_formation_position(formation.clone(), index, center, facing, unit_count);
+        prop_assert!(position.is_finite(), "{:?} gave {:?}", formation, position);
+        prop_assert_eq!(position.z, center.z);
+
+        // No formation spreads further than its widest row or column
+        let offset = (position - center).length();
+        let bound = 80.0 + 50.0 * index.max(unit_count) as f32;
+        prop_assert!(offset <= bound + tolerance(MAP_EXTENT), "offset {} > {}", offset, bound);
+
+        // Facing only rotates the offset
+        let unrotated =
+            calculate_formation_position(formation.clone(), index, center, 0.0, unit_count);
+        let unrotated_offset = (unrotated - center).length();
+        prop_assert!((offset - unrotated_offset).abs() <= tolerance(MAP_EXTENT));
+
+        if formation == FormationType::Circle && unit_count > 0 {
+            prop_assert!((offset - 60.0).abs() <= tolerance(MAP_EXTENT));
+        }
+
+        let spacing = calculate_formation_spacing(unit_count, formation);
+        prop_assert!(spacing.is_finite() && spacing > 0.0);
+    }
+
+    #[test]
+    fn test_iso_projection_round_trips(position in map_position()) {
+        let iso = world_to_iso(position);
+        prop_assert!(iso.is_finite());
+        prop_assert_eq!(iso.z, position.z);
+
+        let back = iso_to_world(iso);
+        prop_assert!(
+            back.distance(position) <= tolerance(MAP_EXTENT),
+            "{:?} -> {:?}",
+            position,
+            back
+        );
+    }
+
+    #[test]
+    fn test_flanking_positions_are_perpendicular_at_range(
+        unit in map_position(),
+        target in map_position(),
+        distance in 1.0f32..500.0,
+        obstacles in prop::collection::vec(map_position(), 0..10),
+    ) {
+        let to_target = (target - unit).truncate().normalize();
+        for flank in [
+            calculate_flanking_position(unit, target, distance),
+            calculate_flank_around_obstacles(unit, target, &obstacles, distance),
+        ] {
+            prop_assert!(flank.is_finite());
+            prop_assert_eq!(flank.z, target.z);
+
+            let offset = (flank - target).truncate();
+            prop_assert!((offset.length() - distance).abs() <= tolerance(MAP_EXTENT));
+            prop_assert!(offset.normalize().dot(to_target).abs() <= 1e-2);
+        }
+
+        // A unit already on top of its target still gets somewhere to go
+        let stacked = calculate_flanking_position(target, target, distance);
+        prop_assert!(stacked.is_finite());
+        prop_assert!((stacked.distance(target) - distance).abs() <= tolerance(MAP_EXTENT));
+        let stacked = calculate_flank_around_obstacles(target, target, &obstacles, distance);
+        prop_assert!(stacked.is_finite());
+    }
+
+    #[test]
+    fn test_avoidance_is_finite_and_bounded(
+        desired in map_position(),
+        offsets in prop::collection::vec(nearby_offset(300.0), 0..8),
+        radius in 1.0f32..200.0,
+        retreat_distance in 1.0f32..300.0,
+    ) {
+        let threats: Vec<Vec3> = offsets.iter().map(|offset| desired + *offset).collect();
+
+        // Each threat pushes at most out to its own radius
+        let adjusted = avoid_threat_clusters(desired, &threats, radius);
+        prop_assert!(adjusted.is_finite());
+        prop_assert!(
+            adjusted.distance(desired) <= radius * threats.len() as f32 + tolerance(MAP_EXTENT)
+        );
+
+        if let Some(threat) = threats.first() {
+            let alone = avoid_threat_clusters(desired, &[*threat], radius);
+            prop_assert!(alone.distance(*threat) >= radius - tolerance(MAP_EXTENT));
+        }
+        let on_threat = avoid_threat_clusters(desired, &[desired], radius);
+        prop_assert!(on_threat.is_finite());
+        prop_assert!((on_threat.distance(desired) - radius).abs() <= tolerance(MAP_EXTENT));
+
+        for threats in [threats.as_slice(), &[desired]] {
+            let mut rng = StdRng::seed_from_u64(0);
+            let retreat = calculate_retreat_position(desired, threats, retreat_distance, &mut rng);
+            prop_assert!(retreat.is_finite());
+        }
+
+        for objective in [None, Some(desired), threats.first().copied()] {
+            let facing = calculate_formation_facing(desired, objective, &threats);
+            prop_assert!(facing.is_finite() && facing.abs() <= std::f32::consts::PI);
+        }
+    }
+
+    #[test]
+    fn test_spatial_grid_matches_brute_force(
+        positions in prop::collection::vec(
+            (-500.0f32..500.0, -500.0f32..500.0, -500.0f32..500.0)
+                .prop_map(|(x, y, z)| Vec3::new(x, y, z)),
+            0..200,
+        ),
+        cell_size in 10.0f32..200.0,
+        query in (-600.0f32..600.0, -600.0f32..600.0, -600.0f32..600.0)
+            .prop_map(|(x, y, z)| Vec3::new(x, y, z)),
+        radius in 0.0f32..300.0,
+    ) {
+        let mut grid = SpatialGrid::new(cell_size);
+        for (i, position) in positions.iter().enumerate() {
+            grid.insert_unit(Entity::from_raw(i as u32), *position, 100.0);
+        }
+
+        let mut found = grid.find_nearby_units(query, radius);
+        found.sort();
+        let expected: Vec<Entity> = positions
+            .iter()
+            .enumerate()
+            .filter(|(_, position)| position.distance(query) <= radius)
+            .map(|(i, _)| Entity::from_raw(i as u32))
+            .collect();
+        prop_assert_eq!(found, expected);
+    }
+}
@@ -0,0 +1,1758 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use chrono::NaiveDate;
+use culiacan_rts::air_assault::AirAssaultEvent;
+use culiacan_rts::area_denial::spawn_area_denial_zone;
+use culiacan_rts::assists::{MissionAssist, MissionAssists};
+use culiacan_rts::campaign::{DefeatType, MissionResult, ObjectiveStatus, VictoryType};
+use culiacan_rts::checkpoints::spawn_checkpoint;
+use culiacan_rts::cohesion::SquadRoster;
+use culiacan_rts::components::{
+    AirAssault, Ambush, AreaDenialKind, AreaDenialKit, AreaDenialZone, AssaultStage, Checkpoint,
+    Communication, ConvoyVehicle, EnemyContact, Faction, Fleeing, GamePhase, HoldArea,
+    IncomingRound, IntelOperator, IntelType, Investigating, LandingZone, LightsOut, Movement,
+    Overwatch, ProductionQueue, RadioMessageType, ReconReport, SafehouseInterior, SquadCohesion,
+    StagingArea, StatModifiers, TacticalMode, TacticalState, TerrainIntel, ThreatAnalysis, Trap,
+    Unit, UnitType, Wounded,
+};
+use culiacan_rts::config::{
+    AirAssaultBalance, AreaDenialBalance, BalanceConfig, CheckpointBalance, IndirectFireBalance,
+    InformantBalance, IntelRecruitmentBalance, JammingBalance, MissionOverrides,
+    OrderOfBattleBalance, OverwatchBalance, RoeBalance,
+};
+use culiacan_rts::convoy::Convoy;
+use culiacan_rts::corpse_system::Corpse;
+use culiacan_rts::daily::{DailyChallenge, DailyChallengeState};
+use culiacan_rts::damage::DeathEvent;
+use culiacan_rts::deployment::{deployment_start_system, DeploymentPlan};
+use culiacan_rts::diplomacy::{Diplomacy, Stance};
+use culiacan_rts::economy::CartelTreasury;
+use culiacan_rts::endings::PoliticalEnding;
+use culiacan_rts::engineering::spawn_trap;
+use culiacan_rts::environmental_systems::{EnvironmentalState, WeatherType};
+use culiacan_rts::indirect_fire::fire_salvo;
+use culiacan_rts::informants::{DoubleAgent, InformantSource};
+use culiacan_rts::intel_recruitment::OperatorContract;
+use culiacan_rts::jamming::{spawn_jammer, Jammer};
+use culiacan_rts::map::{CityMap, TileKind};
+use culiacan_rts::objectives::MissionObjective;
+use culiacan_rts::order_of_battle::OrderOfBattle;
+use culiacan_rts::overwatch::overwatch_shot_damage;
+use culiacan_rts::political_system::{GovernmentResponseLevel, PoliticalState};
+use culiacan_rts::resources::{ConvoyStatus, GameAssets, GameState, IntelSystem};
+use culiacan_rts::rules_of_engagement::AirStrikes;
+use culiacan_rts::save::save_system::MissionId;
+use culiacan_rts::scoring::MissionScore;
+use culiacan_rts::sentiment::{CivilianSentiment, Standing};
+use culiacan_rts::spawners::spawn_intel_operator;
+use culiacan_rts::test_harness::MissionHarness;
+use culiacan_rts::upgrades::{MissionUpgrade, UpgradeWorkshop};
+use culiacan_rts::utils::iso_to_world;
+
+// ==================== MISSION LOGIC ====================
+
+// End-to-end checks on objective evaluation and phase changes, run through
+// the real plugins on the headless app rather than against hand-built state.
+
+fn only_objective(harness: &mut MissionHarness, objective: MissionObjective) {
+    harness.campaign_mut().current_objectives = vec![ObjectiveStatus {
+        objective,
+        completed: false,
+        progress: 0.0,
+        completed_once: false,
+    }];
+}
+
+#[test]
+fn test_mission_opens_in_preparation_with_objectives_in_progress() {
+    let mut harness = MissionHarness::new();
+    harness.assert_phase(GamePhase::Preparation);
+    assert!(
+        harness.living(Faction::Military) > 0,
+        "the opening raid is on the map"
+    );
+
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::InProgress
+    ));
+    harness.assert_objective_completed("DefendTarget");
+    let survive = harness
+        .campaign()
+        .current_objectives
+        .iter()
+        .find(|status| status.objective.kind == "SurviveTime")
+        .unwrap();
+    assert!(!survive.completed);
+}
+
+#[test]
+fn test_phases_advance_on_the_mission_clock() {
+    let steps = [
+        (GamePhase::Preparation, 15.0, GamePhase::InitialRaid),
+        (GamePhase::InitialRaid, 120.0, GamePhase::BlockConvoy),
+        (GamePhase::BlockConvoy, 240.0, GamePhase::ApplyPressure),
+    ];
+
+    for (from, boundary, to) in steps {
+        let mut harness = MissionHarness::new();
+        harness.game_state_mut().game_phase = from.clone();
+        harness.set_mission_timer(boundary - 1.0);
+        harness.tick();
+        harness.assert_phase(from);
+
+        harness.run_for(1.1);
+        harness.assert_phase(to);
+    }
+}
+
+#[test]
+fn test_losing_ovidio_is_a_defeat() {
+    let mut harness = MissionHarness::new();
+    harness.kill_where(|unit| unit.unit_type == UnitType::Ovidio);
+
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::Defeat(DefeatType::TargetLost)
+    ));
+    harness.tick();
+    harness.assert_phase(GamePhase::Defeat);
+}
+
+#[test]
+fn test_losing_every_defender_is_a_defeat() {
+    let mut harness = MissionHarness::new();
+    harness
+        .kill_where(|unit| unit.faction == Faction::Cartel && unit.unit_type != UnitType::Ovidio);
+
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::Defeat(DefeatType::AllUnitsDead)
+    ));
+}
+
+#[test]
+fn test_wiping_out_the_raid_is_a_victory() {
+    let mut harness = MissionHarness::new();
+    harness.kill_all(Faction::Military);
+
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::Victory(VictoryType::EnemiesEliminated)
+    ));
+    harness.tick();
+    harness.assert_phase(GamePhase::Victory);
+}
+
+#[test]
+fn test_outlasting_the_time_limit_is_a_victory() {
+    let mut harness = MissionHarness::new();
+    harness.set_mission_timer(300.0);
+
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::Victory(VictoryType::TimeLimit)
+    ));
+}
+
+#[test]
+fn test_pressure_objective_completes_once() {
+    let mut harness = MissionHarness::new();
+    only_objective(
+        &mut harness,
+        MissionObjective::new("KeepPressureBelow", 0.5),
+    );
+    harness.campaign_mut().political_pressure.total_pressure = 0.2;
+    let completed_before = harness.campaign().objectives_completed;
+
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::Victory(VictoryType::AllObjectivesComplete)
+    ));
+    harness.assert_objective_completed("KeepPressureBelow");
+    harness.assert_pressure_below(0.5);
+
+    harness.evaluate_objectives();
+    assert_eq!(
+        harness.campaign().objectives_completed,
+        completed_before + 1
+    );
+}
+
+#[test]
+fn test_pressure_objective_fails_over_the_threshold() {
+    let mut harness = MissionHarness::new();
+    only_objective(
+        &mut harness,
+        MissionObjective::new("KeepPressureBelow", 0.5),
+    );
+    harness.campaign_mut().political_pressure.total_pressure = 0.8;
+
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::InProgress
+    ));
+    let status = &harness.campaign().current_objectives[0];
+    assert!(!status.completed);
+    assert!((status.progress - 0.5 / 0.8).abs() < 1e-4);
+}
+
+#[test]
+fn test_government_capitulates_past_its_decision_threshold() {
+    let mut harness = MissionHarness::new();
+    harness.political_state_mut().decision_threshold = 0.0;
+    let ceasefire = harness
+        .world()
+        .resource::<BalanceConfig>()
+        .endings
+        .ceasefire_seconds;
+
+    // Nothing in the pressure mix stands out: a ceasefire, which takes a while to hold
+    harness.run_for(1.0);
+    assert_eq!(
+        harness.world().resource::<PoliticalState>().ending,
+        Some(PoliticalEnding::PartialCeasefire)
+    );
+    assert_ne!(harness.phase(), GamePhase::Victory);
+
+    assert!(harness.run_until(ceasefire, |world| {
+        world.resource::<GameState>().game_phase == GamePhase::Victory
+    }));
+    let political_state = harness.world().resource::<PoliticalState>();
+    assert!(political_state.government_capitulated);
+    assert!(matches!(
+        political_state.mission_result(),
+        MissionResult::Victory(VictoryType::Capitulation(PoliticalEnding::PartialCeasefire))
+    ));
+}
+
+#[test]
+fn test_hardliners_override_once_and_broken_troops_retreat_from_the_fighters() {
+    let mut harness = MissionHarness::new();
+    let balance = harness.world().resource::<BalanceConfig>().endings.clone();
+    harness.political_state_mut().decision_threshold = 0.1;
+    harness.campaign_mut().political_pressure.civilian_impact = 0.9;
+
+    // The bloodshed lets the hardliners override the release: the bar goes up
+    harness.run_for(1.0);
+    let political_state = harness.world().resource::<PoliticalState>();
+    assert!(political_state.escalated);
+    assert_eq!(political_state.ending, None);
+    assert!(
+        (political_state.decision_threshold - (0.1 + balance.escalation_threshold)).abs() < 1e-4
+    );
+    assert_ne!(harness.phase(), GamePhase::Victory);
+
+    // Once the troops' morale breaks as well, they fall back from the nearest fighter
+    let fighter_at = Vec2::new(3000.0, -3000.0);
+    place(&mut harness, UnitType::Sicario, Faction::Cartel, fighter_at);
+    let soldier = place(
+        &mut harness,
+        UnitType::Soldier,
+        Faction::Military,
+        fighter_at + Vec2::new(50.0, 0.0),
+    );
+    harness.campaign_mut().political_pressure.military_morale = 0.9;
+    harness.political_state_mut().decision_threshold = 0.0;
+    harness.tick();
+
+    assert_eq!(
+        harness.world().resource::<PoliticalState>().ending,
+        Some(PoliticalEnding::ChaoticRetreat)
+    );
+    let target = harness
+        .world()
+        .get::<Movement>(soldier)
+        .unwrap()
+        .target_position
+        .unwrap();
+    assert!(target.truncate().distance(fighter_at) >= balance.withdrawal_distance - 1.0);
+    assert!(target.x > fighter_at.x);
+
+    assert!(harness.run_until(balance.retreat_seconds + 1.0, |world| {
+        world.resource::<GameState>().game_phase == GamePhase::Victory
+    }));
+    assert_eq!(
+        harness.world().resource::<PoliticalState>().epilogue_key(),
+        Some("ending.retreat")
+    );
+}
+
+#[test]
+fn test_limited_rules_hold_the_tanks_near_civilians_and_all_out_sends_the_armor_in() {
+    let mut harness = MissionHarness::new();
+    // A propaganda burst's worth of relief and then some: Limited rules
+    harness.political_state_mut().roe_relief = 1.0;
+    let fighter_at = Vec2::new(5000.0, 5000.0);
+    let fighter = place(&mut harness, UnitType::Sicario, Faction::Cartel, fighter_at);
+    place(
+        &mut harness,
+        UnitType::Tank,
+        Faction::Military,
+        fighter_at + Vec2::new(60.0, 0.0),
+    );
+    let bystander = place(
+        &mut harness,
+        UnitType::Sicario,
+        Faction::Civilian,
+        fighter_at + Vec2::new(0.0, 40.0),
+    );
+    let hurt = |harness: &MissionHarness, entity: Entity| {
+        let unit = harness.world().get::<Unit>(entity).unwrap();
+        unit.health < unit.max_health
+    };
+    let rules = |harness: &MissionHarness| {
+        let political_state = harness.world().resource::<PoliticalState>();
+        political_state.government_response_level.clone()
+    };
+
+    // The tank holds fire while the bystander is beside the fighter...
+    harness.run_for(3.0);
+    assert_eq!(rules(&harness), GovernmentResponseLevel::Limited);
+    assert!(!hurt(&harness, fighter));
+
+    // ...and opens up once the street is clear
+    harness
+        .world_mut()
+        .get_mut::<Transform>(bystander)
+        .unwrap()
+        .translation = Vec3::new(-5000.0, 5000.0, 0.0);
+    assert!(harness.run_until(5.0, |world| {
+        let unit = world.get::<Unit>(fighter).unwrap();
+        unit.health < unit.max_health
+    }));
+
+    // With the government's will unbroken it's all out: armor pushes on
+    // Ovidio and the jets come over
+    {
+        let mut political_state = harness.political_state_mut();
+        political_state.roe_relief = 0.0;
+        political_state.political_will = 1.0;
+        political_state.government_stability = 1.0;
+        for politician in political_state.active_politicians.iter_mut() {
+            politician.support_for_operation = 1.0;
+        }
+    }
+    harness.world_mut().resource_mut::<AirStrikes>().cooldown = 0.0;
+    let tank = place(
+        &mut harness,
+        UnitType::Tank,
+        Faction::Military,
+        Vec2::new(-5000.0, -5000.0),
+    );
+    harness.tick();
+
+    assert_eq!(rules(&harness), GovernmentResponseLevel::AllOut);
+    let ovidio = harness
+        .find_unit(|unit| unit.unit_type == UnitType::Ovidio)
+        .unwrap();
+    let ovidio_at = harness
+        .world()
+        .get::<Transform>(ovidio)
+        .unwrap()
+        .translation;
+    let heading = harness
+        .world()
+        .get::<Movement>(tank)
+        .unwrap()
+        .target_position;
+    assert!(heading.is_some_and(|heading| heading.distance(ovidio_at) < 10.0));
+    assert!(harness.world().resource::<AirStrikes>().cooldown > 0.0);
+    let mut rounds = harness.world_mut().query::<&IncomingRound>();
+    assert!(rounds.iter(harness.world()).count() >= RoeBalance::default().air_strike_rounds);
+}
+
+#[test]
+fn test_won_mission_is_scored_and_kept_in_the_campaign() {
+    let mut harness = MissionHarness::new();
+    harness.kill_all(Faction::Military);
+    harness.ticks(2);
+    harness.assert_phase(GamePhase::Victory);
+
+    let score = harness.world().resource::<MissionScore>();
+    assert_eq!(score.mission, Some(MissionId::InitialRaid));
+    let total = score.breakdown.total;
+    let record = &harness.campaign().progress.best_records[&MissionId::InitialRaid];
+    assert_eq!(record.score, total);
+    assert!(harness
+        .campaign()
+        .progress
+        .completed_missions
+        .contains(&MissionId::InitialRaid));
+}
+
+#[test]
+fn test_daily_challenge_sets_the_field_and_stays_out_of_the_campaign() {
+    let mut harness = MissionHarness::new();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+    let challenge = DailyChallenge::for_date(date, harness.world().resource::<CityMap>());
+    let (seed, raid) = (challenge.seed, challenge.raid.len());
+    harness
+        .world_mut()
+        .resource_mut::<DailyChallengeState>()
+        .start(challenge);
+
+    harness.tick();
+    assert_eq!(harness.game_state().rng_seed, seed);
+    assert_eq!(harness.living(Faction::Military), raid);
+
+    harness.kill_all(Faction::Military);
+    harness.ticks(2);
+    harness.assert_phase(GamePhase::Victory);
+    assert_eq!(harness.world().resource::<MissionScore>().daily, Some(date));
+    assert!(harness.campaign().progress.best_records.is_empty());
+    assert!(harness.campaign().progress.completed_missions.is_empty());
+}
+
+#[test]
+fn test_defeats_are_counted_and_an_extra_squad_joins_the_retry() {
+    let mut harness = MissionHarness::new();
+    harness.kill_where(|unit| unit.unit_type == UnitType::Ovidio);
+    harness.ticks(2);
+    harness.assert_phase(GamePhase::Defeat);
+    assert_eq!(
+        harness.campaign().progress.defeats(&MissionId::InitialRaid),
+        1
+    );
+
+    let mut harness = MissionHarness::new();
+    let defenders = harness.living(Faction::Cartel);
+    harness
+        .world_mut()
+        .resource_mut::<MissionAssists>()
+        .toggle(&MissionId::InitialRaid, MissionAssist::ExtraSquad);
+    harness.game_state_mut().game_phase = GamePhase::MissionBriefing;
+    harness.tick();
+    harness.game_state_mut().game_phase = GamePhase::Preparation;
+    harness.tick();
+
+    assert!(harness.living(Faction::Cartel) > defenders);
+    let assists = harness.world().resource::<MissionAssists>();
+    assert!(assists.is_running(MissionAssist::ExtraSquad));
+}
+
+#[test]
+fn test_damage_events_kill_once_and_credit_the_shooter() {
+    let mut harness = MissionHarness::new();
+    // Far out of anyone's range, so every kill it scores comes from here
+    let shooter = harness.spawn_unit(
+        UnitType::Soldier,
+        Faction::Military,
+        Vec3::new(5000.0, 5000.0, 0.0),
+    );
+    let ovidio = harness
+        .find_unit(|unit| unit.unit_type == UnitType::Ovidio)
+        .unwrap();
+
+    harness.damage(ovidio, 1000.0, Some(shooter));
+    harness.damage(ovidio, 1000.0, Some(shooter));
+    harness.tick();
+
+    harness.assert_phase(GamePhase::Defeat);
+    assert_eq!(harness.world().get::<Unit>(shooter).unwrap().kills, 1);
+}
+
+#[test]
+fn test_a_fighter_left_wounded_is_no_death_and_no_kill() {
+    let mut harness = MissionHarness::new();
+    // Far from everyone, so nobody else takes him prisoner or drags him off
+    let shooter = place(
+        &mut harness,
+        UnitType::Soldier,
+        Faction::Military,
+        Vec2::new(5000.0, 5000.0),
+    );
+    let enforcer = place(
+        &mut harness,
+        UnitType::Enforcer,
+        Faction::Cartel,
+        Vec2::new(-5000.0, 5000.0),
+    );
+    harness
+        .world_mut()
+        .get_mut::<Unit>(enforcer)
+        .unwrap()
+        .health = 1.0;
+
+    harness.damage(enforcer, 10.0, Some(shooter));
+    harness.tick();
+
+    assert!(wounded(&harness, enforcer));
+    assert_eq!(harness.world().get::<Unit>(shooter).unwrap().kills, 0);
+    let deaths = harness.world().resource::<Events<DeathEvent>>();
+    assert!(deaths
+        .get_reader()
+        .read(deaths)
+        .all(|death| death.entity != enforcer));
+}
+
+#[test]
+fn test_only_hostile_factions_open_fire() {
+    // A standoff far from the battle, run once at war and once at a truce
+    let standoff = |stance: Stance| {
+        let mut harness = MissionHarness::new();
+        harness.world_mut().resource_mut::<Diplomacy>().set_stance(
+            &Faction::RivalCartel,
+            &Faction::Police,
+            stance,
+        );
+        let sicario = harness.spawn_unit(
+            UnitType::Sicario,
+            Faction::RivalCartel,
+            Vec3::new(5000.0, 5000.0, 0.0),
+        );
+        let officer = harness.spawn_unit(
+            UnitType::Soldier,
+            Faction::Police,
+            Vec3::new(5040.0, 5000.0, 0.0),
+        );
+        harness.run_for(5.0);
+        [sicario, officer].map(|entity| {
+            let unit = harness.world().get::<Unit>(entity).unwrap();
+            unit.health < unit.max_health
+        })
+    };
+
+    assert!(standoff(Stance::Hostile).contains(&true));
+    assert_eq!(standoff(Stance::Neutral), [false, false]);
+}
+
+#[test]
+fn test_base_play_builds_units_at_staging_areas_and_safehouses() {
+    let mut harness = MissionHarness::new();
+    harness.campaign_mut().progress.current_mission = MissionId::LasQuintasSiege;
+    harness.game_state_mut().game_phase = GamePhase::MissionBriefing;
+    harness.tick();
+    harness.game_state_mut().game_phase = GamePhase::Preparation;
+    harness.tick();
+
+    let mut staging_areas = harness
+        .world_mut()
+        .query_filtered::<&mut ProductionQueue, With<StagingArea>>();
+    assert!(staging_areas.iter(harness.world()).count() > 0);
+    for mut queue in staging_areas.iter_mut(harness.world_mut()) {
+        queue.push(UnitType::Soldier, 1.0, 0.0);
+    }
+
+    let rally_point = Vec3::new(120.0, 40.0, 0.0);
+    let mut safehouses = harness
+        .world_mut()
+        .query_filtered::<&mut ProductionQueue, With<SafehouseInterior>>();
+    {
+        let mut queue = safehouses.iter_mut(harness.world_mut()).next().unwrap();
+        queue.rally_point = Some(rally_point);
+        queue.push(UnitType::Sicario, 1.0, 100.0);
+    }
+
+    let soldiers = harness.living(Faction::Military);
+    harness.run_for(1.5);
+    assert!(harness.living(Faction::Military) > soldiers);
+    let mut holding = harness.world_mut().query::<&HoldArea>();
+    assert!(holding
+        .iter(harness.world())
+        .any(|hold_area| hold_area.center == rally_point));
+}
+
+#[test]
+fn test_traps_go_off_under_enemies_and_engineers_defuse_them() {
+    let mut harness = MissionHarness::new();
+    // Two spots far from the battle and each other, with nobody moving
+    let soldier = harness.spawn_unit(
+        UnitType::Soldier,
+        Faction::Military,
+        Vec3::new(5000.0, 5000.0, 0.0),
+    );
+    let engineer = harness.spawn_unit(
+        UnitType::Engineer,
+        Faction::Military,
+        Vec3::new(-5000.0, 5000.0, 0.0),
+    );
+    for (entity, offset) in [(soldier, Vec2::ZERO), (engineer, Vec2::new(30.0, 0.0))] {
+        harness
+            .world_mut()
+            .get_mut::<Unit>(entity)
+            .unwrap()
+            .movement_speed = 0.0;
+        let position = harness
+            .world()
+            .get::<Transform>(entity)
+            .unwrap()
+            .translation
+            .truncate()
+            + offset;
+        harness
+            .world_mut()
+            .run_system_once(move |mut commands: Commands| {
+                spawn_trap(&mut commands, Faction::Cartel, position)
+            });
+    }
+
+    harness.run_for(5.0);
+    let mut traps = harness.world_mut().query::<&Trap>();
+    assert_eq!(traps.iter(harness.world()).count(), 0);
+    let hurt = |entity: Entity| {
+        let unit = harness.world().get::<Unit>(entity).unwrap();
+        unit.health < unit.max_health
+    };
+    assert!(hurt(soldier));
+    assert!(!hurt(engineer));
+}
+
+#[test]
+fn test_overwatch_shot_gives_the_sniper_away() {
+    let mut harness = MissionHarness::new();
+    // Far from the battle: an army sniper covering the street east of it, a
+    // sicario walking into the cone and a cartel sniper well behind, out of
+    // sight until the army sniper fires
+    let sniper = harness.spawn_unit(UnitType::Sniper, Faction::Military, Vec3::ZERO);
+    let sicario = harness.spawn_unit(UnitType::Sicario, Faction::Cartel, Vec3::ZERO);
+    let counter_sniper = harness.spawn_unit(UnitType::Sniper, Faction::Cartel, Vec3::ZERO);
+    let post = Vec2::new(5000.0, 5000.0);
+    for (entity, offset) in [
+        (sniper, Vec2::ZERO),
+        (sicario, Vec2::new(200.0, 0.0)),
+        (counter_sniper, Vec2::new(-500.0, 0.0)),
+    ] {
+        let world = harness.world_mut();
+        world.get_mut::<Unit>(entity).unwrap().movement_speed = 0.0;
+        world.get_mut::<Transform>(entity).unwrap().translation = (post + offset).extend(0.0);
+    }
+    let mut overwatch =
+        Overwatch::new(post, post + Vec2::X, 250.0, &OverwatchBalance::default()).unwrap();
+    overwatch.settling = 0.0;
+    harness.world_mut().entity_mut(sniper).insert(overwatch);
+
+    let fired = harness.run_until(5.0, |world| {
+        world
+            .get::<Overwatch>(sniper)
+            .is_some_and(|overwatch| overwatch.covered.contains(&sicario))
+    });
+    assert!(fired);
+    harness.tick();
+    let unit = harness.world().get::<Unit>(sicario).unwrap();
+    let expected = overwatch_shot_damage(
+        &harness
+            .world()
+            .get::<Unit>(sniper)
+            .unwrap()
+            .equipment
+            .weapon,
+        &OverwatchBalance::default(),
+    );
+    assert!(unit.max_health - unit.health >= expected - 1e-3);
+
+    // The muzzle flash showed the cartel sniper where to shoot
+    let counter = harness.world().get::<Unit>(counter_sniper).unwrap();
+    assert_eq!(counter.target, Some(sniper));
+}
+
+#[test]
+fn test_tear_gas_chokes_the_enemy_and_drives_off_the_shaken() {
+    let mut harness = MissionHarness::new();
+    // Far from the battle: a shaken sicario and a soldier rooted to the spot,
+    // side by side in army tear gas
+    let sicario = harness.spawn_unit(UnitType::Sicario, Faction::Cartel, Vec3::ZERO);
+    let soldier = harness.spawn_unit(UnitType::Soldier, Faction::Military, Vec3::ZERO);
+    let center = Vec2::new(5000.0, 5000.0);
+    for (entity, offset) in [
+        (sicario, Vec2::new(10.0, 0.0)),
+        (soldier, Vec2::new(-10.0, 0.0)),
+    ] {
+        harness
+            .world_mut()
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation = (center + offset).extend(0.0);
+    }
+    harness
+        .world_mut()
+        .get_mut::<Unit>(soldier)
+        .unwrap()
+        .movement_speed = 0.0;
+    harness
+        .world_mut()
+        .entity_mut(sicario)
+        .insert(TacticalState {
+            current_state: TacticalMode::HoldPosition,
+            state_timer: 0.0,
+            last_state_change: 0.0,
+            suppression_level: 0.0,
+            morale: 0.1,
+        });
+    let zone = AreaDenialZone::new(
+        AreaDenialKind::TearGas,
+        Faction::Military,
+        center,
+        &AreaDenialBalance::default(),
+    );
+    let edge = center.x + zone.radius;
+    harness
+        .world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            spawn_area_denial_zone(&mut commands, zone.clone())
+        });
+
+    harness.run_for(0.5);
+    let hurt = |entity: Entity| {
+        let unit = harness.world().get::<Unit>(entity).unwrap();
+        unit.health < unit.max_health
+    };
+    // The gas chokes only the cartel, and its smoke keeps the two from
+    // trading shots
+    assert!(hurt(sicario));
+    assert!(!hurt(soldier));
+
+    // The sicario runs for the nearest edge of the cloud
+    assert!(harness.world().get::<Fleeing>(sicario).is_some());
+    let destination = harness
+        .world()
+        .get::<Movement>(sicario)
+        .unwrap()
+        .target_position
+        .unwrap();
+    assert!(destination.x > edge);
+}
+
+#[test]
+fn test_flashlights_give_away_the_patrol_but_not_the_squad_gone_dark() {
+    let mut harness = MissionHarness::new();
+    harness.tick();
+    harness
+        .world_mut()
+        .resource_mut::<EnvironmentalState>()
+        .set_hour(0.0);
+
+    // Far from the battle at midnight: a soldier rooted to the spot, its
+    // flashlight pointing away from a sicario creeping up behind it with its
+    // own switched off
+    let soldier = harness.spawn_unit(UnitType::Soldier, Faction::Military, Vec3::ZERO);
+    let sicario = harness.spawn_unit(UnitType::Sicario, Faction::Cartel, Vec3::ZERO);
+    let post = Vec2::new(5000.0, 5000.0);
+    for (entity, offset) in [(soldier, 0.0), (sicario, -110.0)] {
+        harness
+            .world_mut()
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation = (post + Vec2::new(offset, 0.0)).extend(0.0);
+        harness
+            .world_mut()
+            .get_mut::<Unit>(entity)
+            .unwrap()
+            .movement_speed = 0.0;
+    }
+    harness
+        .world_mut()
+        .entity_mut(soldier)
+        .remove::<AreaDenialKit>();
+    harness.world_mut().entity_mut(sicario).insert(LightsOut);
+
+    // The soldier's own flashlight lights it up for the sicario, who stays
+    // in the dark, out of the soldier's shortened sight
+    let hurt = |world: &World, entity: Entity| {
+        let unit = world.get::<Unit>(entity).unwrap();
+        unit.health < unit.max_health
+    };
+    assert!(harness.run_until(10.0, |world| hurt(world, soldier)));
+    assert!(!hurt(harness.world(), sicario));
+}
+
+// Far from the battle, a sicario opens up on a soldier while a second soldier
+// stands out of sight but within earshot. Whether the shots bring it over.
+fn gunfire_draws_a_patrol(suppressed: bool) -> bool {
+    let mut harness = MissionHarness::new();
+    if suppressed {
+        harness
+            .world_mut()
+            .resource_mut::<UpgradeWorkshop>()
+            .purchased
+            .push(MissionUpgrade::Suppressors);
+    }
+    let sicario = harness.spawn_unit(UnitType::Sicario, Faction::Cartel, Vec3::ZERO);
+    let target = harness.spawn_unit(UnitType::Soldier, Faction::Military, Vec3::ZERO);
+    let listener = harness.spawn_unit(UnitType::Soldier, Faction::Military, Vec3::ZERO);
+    let post = Vec2::new(5000.0, 5000.0);
+    for (entity, offset) in [(sicario, 0.0), (target, 100.0), (listener, -290.0)] {
+        harness
+            .world_mut()
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation = (post + Vec2::new(offset, 0.0)).extend(0.0);
+        harness
+            .world_mut()
+            .entity_mut(entity)
+            .remove::<AreaDenialKit>();
+    }
+    for entity in [sicario, target] {
+        harness
+            .world_mut()
+            .get_mut::<Unit>(entity)
+            .unwrap()
+            .movement_speed = 0.0;
+    }
+
+    assert!(harness.run_until(10.0, |world| {
+        let unit = world.get::<Unit>(target).unwrap();
+        unit.health < unit.max_health
+    }));
+    harness.run_for(0.2);
+    let Some(investigating) = harness.world().get::<Investigating>(listener) else {
+        return false;
+    };
+    assert!(investigating.position.distance(post) < 1.0);
+    true
+}
+
+#[test]
+fn test_gunfire_sends_a_patrol_but_suppressed_fire_does_not() {
+    assert!(gunfire_draws_a_patrol(false));
+    assert!(!gunfire_draws_a_patrol(true));
+}
+
+// The withdrawal with the raid dead and one soldier left far off, so the
+// mission isn't won outright and the road is clear
+fn convoy_mission() -> MissionHarness {
+    let mut harness = MissionHarness::new();
+    harness.campaign_mut().progress.current_mission = MissionId::OrderedWithdrawal;
+    harness.game_state_mut().game_phase = GamePhase::MissionBriefing;
+    harness.tick();
+    harness.game_state_mut().game_phase = GamePhase::Preparation;
+    harness.tick();
+    only_objective(
+        &mut harness,
+        MissionObjective::new("EscortConvoy", "Withdrawal Routes"),
+    );
+    harness.kill_all(Faction::Military);
+    stand_still(&mut harness, Vec3::new(5000.0, 5000.0, 0.0));
+    harness
+}
+
+fn stand_still(harness: &mut MissionHarness, position: Vec3) -> Entity {
+    let soldier = harness.spawn_unit(UnitType::Soldier, Faction::Military, Vec3::ZERO);
+    harness
+        .world_mut()
+        .get_mut::<Transform>(soldier)
+        .unwrap()
+        .translation = position;
+    harness
+        .world_mut()
+        .get_mut::<Unit>(soldier)
+        .unwrap()
+        .movement_speed = 0.0;
+    soldier
+}
+
+fn convoy_status(harness: &MissionHarness) -> ConvoyStatus {
+    harness.game_state().convoy.clone().unwrap()
+}
+
+#[test]
+fn test_screened_convoy_gets_through_and_a_shot_up_one_fails_the_mission() {
+    let mut harness = convoy_mission();
+    let route = harness.world().resource::<Convoy>().route.clone();
+    let city_map = harness.world().resource::<CityMap>();
+    assert!(route.iter().all(|point| city_map
+        .tile_at(*point)
+        .is_some_and(|(col, row)| city_map.kind(col, row) == TileKind::Road)));
+
+    // Rolls along an open road
+    harness.run_for(2.0);
+    assert!(convoy_status(&harness).progress > 0.0);
+
+    // Stops dead with a soldier on the road beside it, one tough enough to
+    // outlast the convoy's guns for the check
+    let mut vehicles = harness
+        .world_mut()
+        .query_filtered::<&Transform, With<ConvoyVehicle>>();
+    let beside = vehicles.iter(harness.world()).next().unwrap().translation + Vec3::X * 40.0;
+    let blocker = stand_still(&mut harness, beside);
+    harness.world_mut().get_mut::<Unit>(blocker).unwrap().health = 100_000.0;
+    harness.tick();
+    let halted = convoy_status(&harness).progress;
+    harness.run_for(1.0);
+    assert_eq!(convoy_status(&harness).progress, halted);
+
+    // Screened and through, the objective is met
+    harness.world_mut().get_mut::<Unit>(blocker).unwrap().health = 0.0;
+    let mut columns = harness.world_mut().query::<&mut ConvoyVehicle>();
+    for mut vehicle in columns.iter_mut(harness.world_mut()) {
+        vehicle.next_waypoint = route.len();
+    }
+    harness.tick();
+    assert!(convoy_status(&harness).arrived);
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::Victory(VictoryType::AllObjectivesComplete)
+    ));
+    harness.assert_objective_completed("EscortConvoy");
+
+    // Shot up past saving, the mission is lost
+    let mut harness = convoy_mission();
+    harness.kill_where(|unit| unit.unit_type == UnitType::Vehicle);
+    harness.tick();
+    assert!(convoy_status(&harness).lost);
+    assert!(matches!(
+        harness.evaluate_objectives(),
+        MissionResult::Defeat(DefeatType::ObjectiveFailed)
+    ));
+}
+
+fn air_assault_mission() -> (MissionHarness, Entity, Vec2) {
+    let mut harness = MissionHarness::new();
+    harness.game_state_mut().game_phase = GamePhase::MissionBriefing;
+    harness.tick();
+    harness.game_state_mut().game_phase = GamePhase::Preparation;
+    harness.tick();
+
+    // The LZ furthest from the cartel, so nobody wanders into it by chance
+    let mut fighters = harness.world_mut().query::<(&Unit, &Transform)>();
+    let cartel: Vec<Vec2> = fighters
+        .iter(harness.world())
+        .filter(|(unit, _)| unit.faction == Faction::Cartel)
+        .map(|(_, transform)| transform.translation.truncate())
+        .collect();
+    let mut zones = harness
+        .world_mut()
+        .query_filtered::<(Entity, &Transform), With<LandingZone>>();
+    let distance_to_cartel = |position: Vec2| {
+        cartel
+            .iter()
+            .map(|fighter| fighter.distance(position))
+            .fold(f32::MAX, f32::min)
+    };
+    let (landing_zone, position) = zones
+        .iter(harness.world())
+        .map(|(entity, transform)| (entity, transform.translation.truncate()))
+        .max_by(|(_, a), (_, b)| distance_to_cartel(*a).total_cmp(&distance_to_cartel(*b)))
+        .expect("the map should have landing zones");
+
+    harness
+        .world_mut()
+        .resource_mut::<Events<AirAssaultEvent>>()
+        .send(AirAssaultEvent { landing_zone });
+    (harness, landing_zone, position)
+}
+
+fn assault_stage(world: &World) -> Option<AssaultStage> {
+    world
+        .iter_entities()
+        .find_map(|entity| entity.get::<AirAssault>().map(|assault| assault.stage))
+}
+
+fn special_forces(harness: &mut MissionHarness) -> usize {
+    let mut units = harness.world_mut().query::<&Unit>();
+    units
+        .iter(harness.world())
+        .filter(|unit| {
+            unit.unit_type == UnitType::SpecialForces && unit.faction == Faction::Military
+        })
+        .count()
+}
+
+#[test]
+fn test_air_assault_fast_ropes_a_squad_unless_the_lz_is_contested() {
+    let squad_size = AirAssaultBalance::default().squad_size;
+
+    // An open LZ: the helicopter circles, puts its squad down and flies off
+    let (mut harness, _, _) = air_assault_mission();
+    let before = special_forces(&mut harness);
+    harness.tick();
+    assert_eq!(assault_stage(harness.world()), Some(AssaultStage::Inbound));
+    let intel = harness.world().resource::<IntelSystem>();
+    assert!(intel
+        .global_intel_network
+        .active_intercepts
+        .iter()
+        .any(|intercept| matches!(intercept.message_type, RadioMessageType::AirSupport(_))));
+    assert!(harness.run_until(60.0, |world| {
+        assault_stage(world) == Some(AssaultStage::Departing)
+    }));
+    assert_eq!(special_forces(&mut harness), before + squad_size);
+    assert!(harness.run_until(60.0, |world| assault_stage(world).is_none()));
+
+    // A fighter on the LZ while it circles calls the landing off
+    let (mut harness, _, position) = air_assault_mission();
+    let before = special_forces(&mut harness);
+    assert!(harness.run_until(60.0, |world| {
+        assault_stage(world) == Some(AssaultStage::Circling)
+    }));
+    let fighter = harness.spawn_unit(UnitType::Sicario, Faction::Cartel, Vec3::ZERO);
+    harness
+        .world_mut()
+        .get_mut::<Transform>(fighter)
+        .unwrap()
+        .translation = position.extend(0.0);
+    let mut unit = harness.world_mut().get_mut::<Unit>(fighter).unwrap();
+    unit.health = 100_000.0;
+    unit.movement_speed = 0.0;
+    harness.tick();
+    assert_eq!(assault_stage(harness.world()), Some(AssaultStage::Aborted));
+    harness.run_for(10.0);
+    assert_eq!(special_forces(&mut harness), before);
+}
+
+fn place(harness: &mut MissionHarness, unit_type: UnitType, faction: Faction, at: Vec2) -> Entity {
+    let entity = harness.spawn_unit(unit_type, faction, Vec3::ZERO);
+    harness
+        .world_mut()
+        .get_mut::<Transform>(entity)
+        .unwrap()
+        .translation = at.extend(0.0);
+    let mut unit = harness.world_mut().get_mut::<Unit>(entity).unwrap();
+    unit.movement_speed = 0.0;
+    unit.health = 1000.0;
+    unit.max_health = 1000.0;
+    entity
+}
+
+#[test]
+fn test_mortar_rounds_are_marked_before_they_land_and_hit_whatever_is_under_them() {
+    let mut harness = MissionHarness::new();
+    let target = Vec2::new(3000.0, 3000.0);
+    let fighter = place(&mut harness, UnitType::Sicario, Faction::Cartel, target);
+    let bystander = place(
+        &mut harness,
+        UnitType::Sicario,
+        Faction::Cartel,
+        target + Vec2::new(400.0, 0.0),
+    );
+    harness
+        .world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            spawn_checkpoint(
+                &mut commands,
+                Faction::Cartel,
+                target + Vec2::new(30.0, 0.0),
+                &CheckpointBalance::default(),
+            );
+            spawn_trap(
+                &mut commands,
+                Faction::Military,
+                target - Vec2::new(30.0, 0.0),
+            );
+            // No scatter, so every round lands on the target
+            fire_salvo(
+                &mut commands,
+                Faction::Military,
+                target,
+                0.0,
+                &IndirectFireBalance::default(),
+            );
+        });
+
+    // Marked on the ground, with nobody hurt yet
+    harness.tick();
+    let mut rounds = harness.world_mut().query::<&IncomingRound>();
+    assert_eq!(
+        rounds.iter(harness.world()).count(),
+        IndirectFireBalance::default().rounds
+    );
+    let hurt = |harness: &MissionHarness, entity: Entity| {
+        let unit = harness.world().get::<Unit>(entity).unwrap();
+        unit.health < unit.max_health
+    };
+    assert!(!hurt(&harness, fighter));
+
+    // Down they come: the fighter under them is hit, the checkpoint falls
+    // and the trap goes up, while someone out of the blast is untouched
+    harness.run_for(7.0);
+    assert_eq!(rounds.iter(harness.world()).count(), 0);
+    assert!(hurt(&harness, fighter));
+    assert!(!hurt(&harness, bystander));
+    let mut checkpoints = harness.world_mut().query::<&Checkpoint>();
+    assert_eq!(checkpoints.iter(harness.world()).count(), 0);
+    let mut traps = harness.world_mut().query::<&Trap>();
+    assert_eq!(traps.iter(harness.world()).count(), 0);
+}
+
+fn wounded(harness: &MissionHarness, entity: Entity) -> bool {
+    harness.world().get::<Wounded>(entity).is_some()
+}
+
+#[test]
+fn test_elites_go_down_wounded_and_a_medic_gets_them_up_before_they_bleed_out() {
+    let mut harness = MissionHarness::new();
+    {
+        let mut balance = harness.world_mut().resource_mut::<BalanceConfig>();
+        balance.wounded.bleed_out_seconds = 3.0;
+        balance.wounded.stabilize_seconds = 1.0;
+    }
+    let spot = Vec2::new(3000.0, 3000.0);
+    let down = |harness: &mut MissionHarness, unit_type: UnitType, at: Vec2, amount: f32| {
+        let entity = place(harness, unit_type, Faction::Cartel, at);
+        harness.world_mut().get_mut::<Unit>(entity).unwrap().health = 1.0;
+        harness.damage(entity, amount, None);
+        entity
+    };
+    let saved = down(&mut harness, UnitType::Enforcer, spot, 10.0);
+    let left = down(
+        &mut harness,
+        UnitType::Sniper,
+        spot + Vec2::new(400.0, 0.0),
+        10.0,
+    );
+    let torn_apart = down(
+        &mut harness,
+        UnitType::HeavyGunner,
+        spot - Vec2::new(400.0, 0.0),
+        5000.0,
+    );
+    let sicario = down(
+        &mut harness,
+        UnitType::Sicario,
+        spot + Vec2::new(0.0, 400.0),
+        10.0,
+    );
+    harness.tick();
+
+    // Only the elites that weren't blown apart are down wounded, out of the
+    // fight but not dead yet
+    assert!(wounded(&harness, saved) && wounded(&harness, left));
+    assert!(harness.world().get::<Unit>(saved).is_none());
+    assert!(!wounded(&harness, torn_apart) && !wounded(&harness, sicario));
+    let political_state = harness.world().resource::<PoliticalState>();
+    assert_eq!(political_state.wounded_cartel, 2);
+    let dead_before = political_state.casualties_cartel;
+
+    // A medic gets to one in time; the other bleeds out
+    place(
+        &mut harness,
+        UnitType::Medic,
+        Faction::Cartel,
+        spot + Vec2::new(20.0, 0.0),
+    );
+    harness.run_for(4.0);
+    let back_up = harness.world().get::<Unit>(saved).unwrap();
+    assert!(back_up.health > 0.0);
+    assert!(!wounded(&harness, saved));
+    assert!(harness.world().get::<Unit>(left).unwrap().health <= 0.0);
+    assert!(harness.world().get::<Corpse>(left).is_some());
+    // Bleeding out is a death like any other; the battle at the safehouse
+    // may have added its own meanwhile
+    let political_state = harness.world().resource::<PoliticalState>();
+    assert!(political_state.casualties_cartel > dead_before);
+}
+
+#[test]
+fn test_soldiers_killed_outright_weigh_on_the_government() {
+    let mut harness = MissionHarness::new();
+    let soldier = place(
+        &mut harness,
+        UnitType::Soldier,
+        Faction::Military,
+        Vec2::new(5000.0, 5000.0),
+    );
+    let dead_before = harness
+        .world()
+        .resource::<PoliticalState>()
+        .casualties_military;
+
+    harness.damage(soldier, 5000.0, None);
+    harness.tick();
+
+    let political_state = harness.world().resource::<PoliticalState>();
+    assert_eq!(political_state.casualties_military, dead_before + 1);
+}
+
+fn cohesion(harness: &MissionHarness, entity: Entity) -> f32 {
+    harness
+        .world()
+        .get::<SquadCohesion>(entity)
+        .map_or(0.0, |cohesion| cohesion.level)
+}
+
+#[test]
+fn test_a_squad_that_fights_together_gains_cohesion_until_it_is_split_up() {
+    let mut harness = MissionHarness::new();
+    harness
+        .world_mut()
+        .resource_mut::<BalanceConfig>()
+        .cohesion
+        .full_cohesion_seconds = 10.0;
+    let spot = Vec2::new(3000.0, 3000.0);
+    let first = place(&mut harness, UnitType::Sicario, Faction::Cartel, spot);
+    let second = place(
+        &mut harness,
+        UnitType::Sicario,
+        Faction::Cartel,
+        spot + Vec2::new(0.0, 30.0),
+    );
+    place(
+        &mut harness,
+        UnitType::Soldier,
+        Faction::Military,
+        spot + Vec2::new(50.0, 0.0),
+    );
+    harness
+        .world_mut()
+        .resource_mut::<SquadRoster>()
+        .form(&[first, second]);
+
+    harness.run_for(3.0);
+    let built = cohesion(&harness, first);
+    assert!(built > 0.0);
+    assert_eq!(cohesion(&harness, second), built);
+
+    // Out of position, a member hurries back to the others
+    harness
+        .world_mut()
+        .get_mut::<Transform>(second)
+        .unwrap()
+        .translation = (spot + Vec2::new(0.0, 250.0)).extend(0.0);
+    harness.ticks(2);
+    let modifiers = harness.world().get::<StatModifiers>(second).unwrap();
+    assert!(modifiers.movement > 1.0);
+
+    // Pulled into a new squad, it starts over, and the one left behind is
+    // no squad at all
+    let third = place(
+        &mut harness,
+        UnitType::Sicario,
+        Faction::Cartel,
+        spot - Vec2::new(0.0, 30.0),
+    );
+    harness
+        .world_mut()
+        .resource_mut::<SquadRoster>()
+        .form(&[first, third]);
+    harness.tick();
+    assert!(cohesion(&harness, first) < built);
+    assert!(harness.world().get::<SquadCohesion>(second).is_none());
+}
+
+#[test]
+fn test_deployment_places_fighters_on_a_paused_map_and_ambushes_hold_fire_until_sprung() {
+    let mut harness = MissionHarness::new();
+    harness
+        .world_mut()
+        .resource_mut::<DeploymentPlan>()
+        .interactive = true;
+    harness.world_mut().run_system_once(deployment_start_system);
+    assert!(harness.world().resource::<DeploymentPlan>().planning);
+
+    // The clock stands still while the plan is made
+    let timer = harness.game_state().mission_timer;
+    harness.ticks(10);
+    assert_eq!(harness.game_state().mission_timer, timer);
+
+    // A move order near a safehouse puts the fighter there at once; one
+    // out in the open is refused
+    let fighter = harness
+        .find_unit(|unit| unit.unit_type == UnitType::Sicario && unit.faction == Faction::Cartel)
+        .unwrap();
+    let order = |harness: &mut MissionHarness, to: Vec2| {
+        harness
+            .world_mut()
+            .get_mut::<Movement>(fighter)
+            .unwrap()
+            .target_position = Some(to.extend(0.0));
+        harness.tick();
+    };
+    let position = |harness: &MissionHarness| {
+        harness
+            .world()
+            .get::<Transform>(fighter)
+            .unwrap()
+            .translation
+            .truncate()
+    };
+    let near_safehouse = Vec2::new(300.0, -150.0);
+    order(&mut harness, near_safehouse);
+    assert_eq!(position(&harness), near_safehouse);
+    order(&mut harness, Vec2::new(-1500.0, 0.0));
+    assert_eq!(position(&harness), near_safehouse);
+    assert!(harness
+        .world()
+        .get::<Movement>(fighter)
+        .unwrap()
+        .target_position
+        .is_none());
+
+    // Committing starts the clock
+    harness.world_mut().run_system_once(
+        |mut plan: ResMut<DeploymentPlan>, mut time: ResMut<Time<Virtual>>| {
+            plan.commit(&mut time);
+        },
+    );
+    harness.ticks(10);
+    assert!(harness.game_state().mission_timer > timer);
+
+    // Ambushers keep quiet with the army in range, until it gets too close
+    harness
+        .world_mut()
+        .resource_mut::<BalanceConfig>()
+        .deployment
+        .ambush_trigger_radius = 40.0;
+    let spot = Vec2::new(3000.0, 3000.0);
+    let ambushers = [
+        spot,
+        spot - Vec2::new(0.0, 150.0),
+        spot - Vec2::new(0.0, 1000.0),
+    ]
+    .map(|at| {
+        let entity = place(&mut harness, UnitType::Sicario, Faction::Cartel, at);
+        harness.world_mut().entity_mut(entity).insert(Ambush);
+        entity
+    });
+    let soldier = place(
+        &mut harness,
+        UnitType::Soldier,
+        Faction::Military,
+        spot + Vec2::new(100.0, 0.0),
+    );
+    harness.run_for(2.0);
+    let ambushing =
+        |harness: &MissionHarness, entity: Entity| harness.world().get::<Ambush>(entity).is_some();
+    assert!(ambushers.iter().all(|entity| ambushing(&harness, *entity)));
+    let unit = harness.world().get::<Unit>(soldier).unwrap();
+    assert_eq!(unit.health, unit.max_health);
+
+    // Sprung, the ambush opens up together; a fighter far off stays hidden
+    harness
+        .world_mut()
+        .get_mut::<Transform>(soldier)
+        .unwrap()
+        .translation = (spot + Vec2::new(30.0, 0.0)).extend(0.0);
+    harness.tick();
+    assert!(!ambushing(&harness, ambushers[0]));
+    assert!(!ambushing(&harness, ambushers[1]));
+    assert!(ambushing(&harness, ambushers[2]));
+    harness.run_for(2.0);
+    let unit = harness.world().get::<Unit>(soldier).unwrap();
+    assert!(unit.health < unit.max_health);
+}
+
+#[test]
+fn test_threat_analysis_spends_intel_and_identifies_the_target_after_a_delay() {
+    let mut harness = MissionHarness::new();
+    let cost = harness
+        .world()
+        .resource::<BalanceConfig>()
+        .threat_assessment
+        .intel_cost;
+    {
+        let mut intel = harness.world_mut().resource_mut::<IntelSystem>();
+        intel.reports_gathered = cost + 1;
+        intel.intel_spent = 0;
+        assert!(intel.spend_intel(cost));
+        assert_eq!(intel.intel_points(), 1);
+        // One report short of a second analysis
+        assert!(!intel.spend_intel(cost));
+        assert_eq!(intel.intel_points(), 1);
+    }
+
+    let seconds = harness
+        .world()
+        .resource::<BalanceConfig>()
+        .threat_assessment
+        .analysis_seconds;
+    let soldier = place(
+        &mut harness,
+        UnitType::Soldier,
+        Faction::Military,
+        Vec2::new(3000.0, 3000.0),
+    );
+    harness
+        .world_mut()
+        .entity_mut(soldier)
+        .insert(ThreatAnalysis { ready_in: seconds });
+    let ready = |harness: &MissionHarness| {
+        harness
+            .world()
+            .get::<ThreatAnalysis>(soldier)
+            .unwrap()
+            .ready_in
+            <= 0.0
+    };
+    harness.run_for(seconds * 0.5);
+    assert!(!ready(&harness));
+    harness.run_for(seconds * 0.5 + 0.5);
+    assert!(ready(&harness));
+}
+
+#[test]
+fn test_friendly_neighborhoods_send_informants_and_hostile_ones_tip_off_the_army() {
+    let mut harness = MissionHarness::new();
+    harness
+        .world_mut()
+        .resource_mut::<BalanceConfig>()
+        .sentiment
+        .tip_interval = 1.0;
+    let informants = |harness: &mut MissionHarness| {
+        harness
+            .world_mut()
+            .query::<&IntelOperator>()
+            .iter(harness.world())
+            .filter(|operator| operator.intel_type == IntelType::Informant)
+            .count()
+    };
+    let before = informants(&mut harness);
+
+    // Las Flores warms to the cartel, Tierra Blanca turns on it
+    {
+        let mut sentiment = harness.world_mut().resource_mut::<CivilianSentiment>();
+        sentiment.shift('F', 1.0);
+        sentiment.shift('T', -1.0);
+    }
+    let hideout = harness
+        .world()
+        .resource::<CityMap>()
+        .neighborhood_center('T')
+        .unwrap();
+    place(&mut harness, UnitType::Sicario, Faction::Cartel, hideout);
+    harness.tick();
+    let sentiment = harness.world().resource::<CivilianSentiment>();
+    assert_eq!(sentiment.standing('F'), Standing::Friendly);
+    assert_eq!(sentiment.standing('T'), Standing::Hostile);
+    assert_eq!(informants(&mut harness), before + 1);
+
+    // Only one informant per neighborhood
+    harness.ticks(10);
+    assert_eq!(informants(&mut harness), before + 1);
+
+    // The locals point the army to a fighter hiding among them
+    assert!(harness.run_until(2.0, |world| {
+        let city_map = world.resource::<CityMap>();
+        world
+            .iter_entities()
+            .filter_map(|entity| entity.get::<Investigating>())
+            .any(|investigating| {
+                city_map
+                    .neighborhood_at(investigating.position)
+                    .is_some_and(|neighborhood| neighborhood.key == 'T')
+            })
+    }));
+}
+
+#[test]
+fn test_recruited_operators_draw_upkeep_and_walk_off_when_the_money_runs_out() {
+    let mut harness = MissionHarness::new();
+    {
+        let mut balance = harness.world_mut().resource_mut::<BalanceConfig>();
+        balance.economy.neighborhood_income = 0.0;
+        balance.economy.objective_income = 0.0;
+    }
+    let network = IntelRecruitmentBalance::default();
+    let hire = |harness: &mut MissionHarness, intel_type: IntelType, at: Vec2| {
+        let upkeep = network.upkeep(&intel_type);
+        harness.world_mut().run_system_once(
+            move |mut commands: Commands,
+                  game_assets: Res<GameAssets>,
+                  balance: Res<BalanceConfig>| {
+                let operator = spawn_intel_operator(
+                    &mut commands,
+                    intel_type.clone(),
+                    at.extend(0.0),
+                    &game_assets,
+                    &balance,
+                );
+                commands
+                    .entity(operator)
+                    .insert(OperatorContract { upkeep });
+            },
+        );
+    };
+    let (lookout, radio_tech) = (Vec2::new(3000.0, 3000.0), Vec2::new(3000.0, 3400.0));
+    hire(&mut harness, IntelType::Reconnaissance, lookout);
+    hire(&mut harness, IntelType::RadioIntercept, radio_tech);
+    harness.world_mut().resource_mut::<CartelTreasury>().cash = 0.0;
+
+    // Their pay comes out of the income
+    harness.run_for(1.1);
+    let treasury = harness.world().resource::<CartelTreasury>();
+    assert!(
+        treasury.income_per_minute <= -(network.lookout_upkeep + network.radio_tech_upkeep),
+        "upkeep shows in the income, got {}",
+        treasury.income_per_minute
+    );
+    assert_eq!(treasury.cash, 0.0);
+
+    // Unpaid for long enough, the radio tech walks off and the lookout stays
+    harness.run_for(network.unpaid_grace);
+    let mut contracts = harness
+        .world_mut()
+        .query::<(&IntelOperator, &OperatorContract)>();
+    let left: Vec<IntelType> = contracts
+        .iter(harness.world())
+        .map(|(operator, _)| operator.intel_type.clone())
+        .collect();
+    assert_eq!(left, vec![IntelType::Reconnaissance]);
+}
+
+#[test]
+fn test_a_jammer_cuts_cartel_radios_and_three_lookouts_get_a_bearing_on_it() {
+    let mut harness = MissionHarness::new();
+    let balance = JammingBalance::default();
+    let site = Vec2::new(3000.0, 3000.0);
+    let jammer = harness
+        .world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            spawn_jammer(&mut commands, site, 0.0, &JammingBalance::default())
+        });
+
+    // One fighter just inside the field, one just outside within radio reach
+    let inside = place(
+        &mut harness,
+        UnitType::Sicario,
+        Faction::Cartel,
+        site + Vec2::X * (balance.radius - 30.0),
+    );
+    let outside = place(
+        &mut harness,
+        UnitType::Sicario,
+        Faction::Cartel,
+        site + Vec2::X * (balance.radius + 100.0),
+    );
+    for offset in [
+        Vec2::new(0.0, 400.0),
+        Vec2::new(-400.0, 0.0),
+        Vec2::new(300.0, -300.0),
+    ] {
+        harness.world_mut().run_system_once(
+            move |mut commands: Commands,
+                  game_assets: Res<GameAssets>,
+                  balance: Res<BalanceConfig>| {
+                spawn_intel_operator(
+                    &mut commands,
+                    IntelType::Reconnaissance,
+                    iso_to_world((site + offset).extend(0.0)),
+                    &game_assets,
+                    &balance,
+                );
+            },
+        );
+    }
+    // The squads hand out radios on the first tick
+    harness.tick();
+    let spotted = EnemyContact {
+        position: Vec3::new(-3000.0, -3000.0, 0.0),
+        enemy_type: UnitType::Tank,
+        confidence: 1.0,
+        last_seen: 0.0,
+    };
+    harness
+        .world_mut()
+        .get_mut::<Communication>(inside)
+        .unwrap()
+        .known_enemies
+        .push(spotted);
+    let heard = |harness: &MissionHarness| {
+        harness
+            .world()
+            .get::<Communication>(outside)
+            .unwrap()
+            .known_enemies
+            .iter()
+            .any(|contact| contact.enemy_type == UnitType::Tank)
+    };
+
+    // Nothing gets out through the static, but each lookout takes a bearing
+    harness.ticks(3);
+    assert!(!heard(&harness));
+    let bearings = &harness.world().get::<Jammer>(jammer).unwrap().bearings;
+    assert_eq!(bearings.len(), balance.bearings_needed);
+    for bearing in bearings {
+        let off = Vec2::from_angle(bearing.angle).angle_between(site - bearing.origin);
+        assert!(off.abs() <= balance.bearing_error.to_radians() + 1e-4);
+    }
+
+    // With the jammer gone the report goes out
+    harness.world_mut().entity_mut(jammer).despawn_recursive();
+    harness.tick();
+    assert!(heard(&harness));
+}
+
+#[test]
+fn test_a_double_agent_found_out_runs_until_fighters_catch_him_clear_of_soldiers() {
+    let mut harness = MissionHarness::new();
+    let balance = InformantBalance::default();
+    let post = Vec2::new(3000.0, -3000.0);
+    let informant = harness.world_mut().run_system_once(
+        move |mut commands: Commands, game_assets: Res<GameAssets>, balance: Res<BalanceConfig>| {
+            spawn_intel_operator(
+                &mut commands,
+                IntelType::Informant,
+                iso_to_world(post.extend(0.0)),
+                &game_assets,
+                &balance,
+            )
+        },
+    );
+    // Every informant gets a hidden reliability as they join
+    harness.tick();
+    assert!(harness.world().get::<InformantSource>(informant).is_some());
+
+    // Vetted, he turns out to be the army's, and runs
+    let escape = (post + Vec2::X * 2000.0).extend(0.0);
+    harness.world_mut().entity_mut(informant).insert((
+        InformantSource {
+            reliability: balance.min_reliability,
+            vetted: true,
+            double_agent: true,
+        },
+        DoubleAgent { escape },
+    ));
+    let fighter = place(
+        &mut harness,
+        UnitType::Sicario,
+        Faction::Cartel,
+        post + Vec2::Y * (balance.capture_radius - 10.0),
+    );
+    let soldier = place(
+        &mut harness,
+        UnitType::Soldier,
+        Faction::Military,
+        post - Vec2::Y * (balance.contest_radius - 20.0),
+    );
+    let points = harness.world().resource::<IntelSystem>().intel_points();
+
+    // A soldier close by keeps the fighters off him, and he gets closer to the army
+    harness.tick();
+    let fled = harness
+        .world()
+        .get::<Transform>(informant)
+        .unwrap()
+        .translation;
+    assert!(fled.distance(escape) < post.extend(0.0).distance(escape));
+
+    harness.world_mut().entity_mut(soldier).despawn_recursive();
+    harness.tick();
+    assert!(harness.world().get_entity(informant).is_none());
+    assert!(harness.world().get_entity(fighter).is_some());
+    assert!(
+        harness.world().resource::<IntelSystem>().intel_points() >= points + balance.capture_intel
+    );
+}
+
+#[test]
+fn test_recon_counts_add_up_to_an_order_of_battle_that_fades_without_new_reports() {
+    let mut harness = MissionHarness::new();
+    let balance = OrderOfBattleBalance::default();
+    // Nobody else reporting while the estimate is read
+    let operators: Vec<Entity> = harness
+        .world_mut()
+        .query_filtered::<Entity, With<IntelOperator>>()
+        .iter(harness.world())
+        .collect();
+    for operator in operators {
+        harness.world_mut().entity_mut(operator).despawn_recursive();
+    }
+    let network = &mut harness
+        .world_mut()
+        .resource_mut::<IntelSystem>()
+        .into_inner()
+        .global_intel_network;
+    network.active_intercepts.clear();
+    network.informant_reports.clear();
+    network.reconnaissance_data.clear();
+
+    let (center, name) = {
+        let city_map = harness.world().resource::<CityMap>();
+        let center = city_map.neighborhood_center('T').unwrap();
+        (
+            center,
+            city_map.neighborhood_at(center).unwrap().name.clone(),
+        )
+    };
+    let now = harness.world().resource::<Time>().elapsed_seconds() + 1.0;
+    let contact = |enemy_type| EnemyContact {
+        position: center.extend(0.0),
+        enemy_type,
+        confidence: 0.9,
+        last_seen: now,
+    };
+    harness
+        .world_mut()
+        .resource_mut::<IntelSystem>()
+        .global_intel_network
+        .reconnaissance_data
+        .push(ReconReport {
+            area_scanned: center.extend(0.0),
+            scan_radius: 300.0,
+            enemies_spotted: vec![
+                contact(UnitType::Soldier),
+                contact(UnitType::Soldier),
+                contact(UnitType::Soldier),
+                contact(UnitType::Sicario),
+            ],
+            terrain_info: TerrainIntel {
+                cover_points: Vec::new(),
+                choke_points: Vec::new(),
+                elevation_advantages: Vec::new(),
+                escape_routes: Vec::new(),
+            },
+            scan_time: now,
+        });
+    let soldiers = |harness: &MissionHarness| {
+        harness
+            .world()
+            .resource::<OrderOfBattle>()
+            .estimates
+            .iter()
+            .find(|estimate| estimate.area.as_deref() == Some(name.as_str()))
+            .cloned()
+    };
+
+    // Three soldiers counted there, and the lookout's own side left out
+    harness.run_for(balance.fusion_interval * 2.0);
+    let estimate = soldiers(&harness).unwrap();
+    assert_eq!(estimate.unit_type, UnitType::Soldier);
+    assert!((estimate.count - 3.0).abs() < 1e-4);
+    assert_eq!(
+        harness.world().resource::<OrderOfBattle>().estimates.len(),
+        1
+    );
+
+    // The same report isn't counted twice, and without a new one it fades
+    harness.run_for(balance.fusion_interval * 10.0);
+    let faded = soldiers(&harness).unwrap();
+    assert!((faded.count - 3.0).abs() < 1e-4);
+    assert!(faded.confidence < estimate.confidence);
+}
+
+#[test]
+fn test_missions_pin_weather_and_camera_area_only_while_played() {
+    let mut harness = MissionHarness::new();
+    harness.game_state_mut().game_phase = GamePhase::MissionBriefing;
+    harness.tick();
+    harness
+        .world_mut()
+        .resource_mut::<EnvironmentalState>()
+        .weather_type = WeatherType::HeavyRain;
+    let weather = |harness: &MissionHarness| {
+        harness
+            .world()
+            .resource::<EnvironmentalState>()
+            .weather_type
+    };
+
+    // The Initial Raid holds a clear sky, and the rain comes back after it
+    harness.campaign_mut().progress.current_mission = MissionId::InitialRaid;
+    harness.game_state_mut().game_phase = GamePhase::Preparation;
+    harness.tick();
+    assert_eq!(weather(&harness), WeatherType::Clear);
+    assert_eq!(
+        harness.world().resource::<MissionOverrides>().weather,
+        Some(WeatherType::Clear)
+    );
+    harness.game_state_mut().game_phase = GamePhase::Victory;
+    harness.tick();
+    assert_eq!(weather(&harness), WeatherType::HeavyRain);
+    assert_eq!(harness.world().resource::<MissionOverrides>().weather, None);
+
+    // The Las Quintas siege keeps the camera over Las Quintas
+    harness.campaign_mut().progress.current_mission = MissionId::LasQuintasSiege;
+    harness.game_state_mut().game_phase = GamePhase::Preparation;
+    harness.tick();
+    let overrides = harness.world().resource::<MissionOverrides>();
+    let bounds = overrides.camera_bounds.unwrap();
+    let city_map = harness.world().resource::<CityMap>();
+    let key = city_map.find_neighborhood("Las Quintas").unwrap().key;
+    let center = city_map.neighborhood_center(key).unwrap();
+    assert!(bounds.contains(center));
+    let far_east = Vec3::new(bounds.max.x + 1000.0, center.y, 5.0);
+    assert_eq!(
+        overrides.clamp_camera(far_east),
+        Vec3::new(bounds.max.x, center.y, 5.0)
+    );
+    assert_eq!(weather(&harness), WeatherType::HeavyRain);
+}